@@ -15,6 +15,7 @@ BuildRequires:  cairo-devel
 BuildRequires:  pango-devel
 BuildRequires:  gdk-pixbuf2-devel
 BuildRequires:  graphene-devel
+BuildRequires:  systemd-rpm-macros
 
 Requires:       gtk4 >= 4.14
 Requires:       libadwaita >= 1.5
@@ -42,6 +43,13 @@ cargo build --release
 
 %install
 install -Dm755 target/release/%{name} %{buildroot}%{_bindir}/%{name}
+install -Dm755 target/release/%{name}-monitor %{buildroot}%{_bindir}/%{name}-monitor
+%{_libexecdir}/%{name}-helper
+install -Dm755 target/release/%{name}-helper %{buildroot}%{_libexecdir}/%{name}-helper
+install -Dm644 data/%{name}-monitor.service %{buildroot}%{_userunitdir}/%{name}-monitor.service
+%{_datadir}/dbus-1/services/com.chrisdaggas.security-center.Monitor.service
+install -Dm644 data/com.chrisdaggas.security-center.Monitor.service %{buildroot}%{_datadir}/dbus-1/services/com.chrisdaggas.security-center.Monitor.service
+install -Dm644 data/com.chrisdaggas.security-center.policy %{buildroot}%{_datadir}/polkit-1/actions/com.chrisdaggas.security-center.policy
 install -Dm644 data/com.chrisdaggas.security-center.desktop %{buildroot}%{_datadir}/applications/com.chrisdaggas.security-center.desktop
 install -Dm644 data/com.chrisdaggas.security-center.metainfo.xml %{buildroot}%{_datadir}/metainfo/com.chrisdaggas.security-center.metainfo.xml
 install -Dm644 data/icons/hicolor/scalable/apps/com.chrisdaggas.security-center.svg %{buildroot}%{_datadir}/icons/hicolor/scalable/apps/com.chrisdaggas.security-center.svg
@@ -52,6 +60,9 @@ install -Dm644 README.md %{buildroot}%{_docdir}/%{name}/README.md
 %license LICENSE
 %doc README.md
 %{_bindir}/%{name}
+%{_bindir}/%{name}-monitor
+%{_userunitdir}/%{name}-monitor.service
+%{_datadir}/polkit-1/actions/com.chrisdaggas.security-center.policy
 %{_datadir}/applications/com.chrisdaggas.security-center.desktop
 %{_datadir}/metainfo/com.chrisdaggas.security-center.metainfo.xml
 %{_datadir}/icons/hicolor/scalable/apps/com.chrisdaggas.security-center.svg
@@ -2,10 +2,24 @@
 // Copyright (C) 2026 Christos Daggas
 // SPDX-License-Identifier: MIT
 
-//! Coordinated administrative actions using D-Bus.
+//! Typed administrative operations.
 //!
-//! This module provides a unified interface for common administrative
-//! operations, coordinating between systemd and firewalld.
+//! An [`Operation`] names what to do, an [`OperationRunner`] performs it
+//! through the firewalld and systemd clients and returns an
+//! [`OperationResult`] with the outcome, the steps taken (written as the
+//! equivalent `firewall-cmd`/`systemctl`/`gsettings` commands) and the
+//! duration. Settings without a D-Bus API go through the privileged helper. The
+//! Quick Actions page describes the operations it offers in
+//! [`QUICK_ACTIONS`]; other surfaces run the same operations and get the
+//! same results.
+//!
+//! Operations that can cut the machine off the network are risky (see
+//! [`Operation::is_risky`]). [`OperationRunner::run_revertible`] captures an
+//! [`Undo`] before running one, and the interface reverts it with
+//! [`OperationRunner::revert`] unless the user keeps the change in time.
+//! Removed ports and services are undone the same way, from the inverse
+//! calls their removal left in the operation journal
+//! ([`crate::firewall::journal`]).
 //!
 //! # Design Principles
 //!
@@ -14,22 +28,175 @@
 //! - Polkit authorization is handled by the D-Bus services
 //! - No automatic or background execution
 
-use anyhow::{anyhow, Context, Result};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
 use tracing::info;
-use zbus::blocking::Connection;
 
-use crate::systemd::SystemdClient;
+use super::remote_access::AccessMethod;
+use crate::firewall::{journal, FirewallClient};
+use crate::helper::{run_privileged, HelperOp};
+use crate::hooks::{self, HookEvent};
+use crate::systemd::{SystemdClient, WatchedUnit};
+
+/// An administrative operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    ReloadFirewall,
+    EnableFirewall,
+    DisableFirewall,
+    SetPanicMode {
+        enabled: bool,
+    },
+    RuntimeToPermanent,
+    FlushRuntime,
+    RestartNetworkManager,
+    RestartSsh,
+    EnableTimeSync,
+    ReloadSystemd,
+    SetDefaultZone {
+        zone: &'static str,
+    },
+    /// Remove a service from a zone, at runtime and permanently.
+    RemoveZoneService {
+        zone: &'static str,
+        service: &'static str,
+    },
+    StopAndDisableUnit {
+        unit: &'static str,
+    },
+    /// Turn a remote login method on (start its unit at once and at boot,
+    /// allow it in the default zone) or off (close it in every zone, then
+    /// stop and disable its units).
+    SetRemoteAccess {
+        method: AccessMethod,
+        enabled: bool,
+    },
+    EnableMacRandomization,
+    HardenSsh,
+    EnableScreenLock,
+}
+
+impl Operation {
+    /// Stable identifier, e.g. `firewall_reload`.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Operation::ReloadFirewall => "firewall_reload",
+            Operation::EnableFirewall => "firewall_enable",
+            Operation::DisableFirewall => "firewall_disable",
+            Operation::SetPanicMode { enabled: true } => "firewall_panic_on",
+            Operation::SetPanicMode { enabled: false } => "firewall_panic_off",
+            Operation::RuntimeToPermanent => "firewall_runtime_to_permanent",
+            Operation::FlushRuntime => "firewall_flush_runtime",
+            Operation::RestartNetworkManager => "restart_networkmanager",
+            Operation::RestartSsh => "restart_sshd",
+            Operation::EnableTimeSync => "enable_time_sync",
+            Operation::ReloadSystemd => "reload_systemd",
+            Operation::SetDefaultZone { .. } => "firewall_set_default_zone",
+            Operation::RemoveZoneService { .. } => "firewall_remove_service",
+            Operation::StopAndDisableUnit { .. } => "stop_disable_unit",
+            Operation::SetRemoteAccess { enabled: true, .. } => "remote_access_on",
+            Operation::SetRemoteAccess { enabled: false, .. } => "remote_access_off",
+            Operation::EnableMacRandomization => "enable_mac_randomization",
+            Operation::HardenSsh => "harden_sshd",
+            Operation::EnableScreenLock => "enable_screen_lock",
+        }
+    }
+
+    /// Whether the operation can cut the machine off the network, so it
+    /// reverts itself unless kept: panic mode, a lockdown default zone and
+    /// removing ssh from a zone.
+    pub fn is_risky(&self) -> bool {
+        match self {
+            Operation::SetPanicMode { enabled } => *enabled,
+            Operation::SetDefaultZone { zone } => is_risky_default_zone(zone),
+            Operation::RemoveZoneService { service, .. } => is_risky_service(service),
+            _ => false,
+        }
+    }
 
-/// D-Bus constants for firewalld
-const FIREWALLD_BUS: &str = "org.fedoraproject.FirewallD1";
-const FIREWALLD_PATH: &str = "/org/fedoraproject/FirewallD1";
-const FIREWALLD_INTERFACE: &str = "org.fedoraproject.FirewallD1";
+    /// Whether the operation changes the firewall, so the script hooks
+    /// hear about it.
+    pub fn changes_firewall(&self) -> bool {
+        matches!(self.depends_on(), Some((WatchedUnit::Firewalld, _)))
+    }
+
+    /// Quick action describing this operation.
+    pub fn action(&self) -> Option<&'static AdminAction> {
+        QUICK_ACTIONS.iter().find(|a| a.operation == *self)
+    }
+
+    /// The watched service this operation acts on, and whether that service
+    /// must already be running. Starting or restarting a service works while
+    /// it is stopped.
+    pub fn depends_on(&self) -> Option<(WatchedUnit, bool)> {
+        match self {
+            Operation::EnableFirewall => Some((WatchedUnit::Firewalld, false)),
+            Operation::ReloadFirewall
+            | Operation::DisableFirewall
+            | Operation::SetPanicMode { .. }
+            | Operation::RuntimeToPermanent
+            | Operation::FlushRuntime
+            | Operation::SetDefaultZone { .. }
+            | Operation::RemoveZoneService { .. } => Some((WatchedUnit::Firewalld, true)),
+            Operation::RestartNetworkManager => Some((WatchedUnit::NetworkManager, false)),
+            Operation::RestartSsh => Some((WatchedUnit::Ssh, false)),
+            _ => None,
+        }
+    }
+}
+
+/// Built-in zones that drop or reject all traffic not explicitly allowed.
+const LOCKDOWN_ZONES: &[&str] = &["drop", "block"];
+
+/// Seconds a risky change stays before it is reverted, unless kept.
+pub const REVERT_SECS: u32 = 60;
+
+/// Whether making `zone` the default zone can cut off remote access.
+pub fn is_risky_default_zone(zone: &str) -> bool {
+    LOCKDOWN_ZONES.contains(&zone)
+}
+
+/// Whether removing `service` from a zone can cut off remote access.
+pub fn is_risky_service(service: &str) -> bool {
+    service == "ssh"
+}
+
+/// What puts things back after a risky or destructive change, captured
+/// before it was made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Undo {
+    /// Make this zone the default again.
+    DefaultZone(String),
+    /// Leave panic mode.
+    PanicOff,
+    /// Add a service back to a zone, at runtime and permanently.
+    ZoneService { zone: String, service: String },
+    /// Make the inverse calls of an operation journal entry.
+    Journal(u64),
+}
+
+impl Undo {
+    /// The change, for the countdown shown until it is kept or reverted.
+    pub fn describe(&self) -> String {
+        match self {
+            Undo::DefaultZone(_) => "Default zone changed".to_string(),
+            Undo::PanicOff => "Panic mode enabled".to_string(),
+            Undo::ZoneService { zone, service } => {
+                format!("Service {} removed from zone {}", service, zone)
+            }
+            Undo::Journal(id) => {
+                journal::description(*id).unwrap_or_else(|| "Rules removed".to_string())
+            }
+        }
+    }
+}
 
 /// An administrative action that can be performed.
 #[derive(Debug, Clone)]
 pub struct AdminAction {
-    /// Unique identifier for this action
-    pub id: &'static str,
+    /// Operation this action runs
+    pub operation: Operation,
     /// Human-readable title
     pub title: &'static str,
     /// Detailed description of what this action does
@@ -50,34 +217,24 @@ pub enum ActionCategory {
     Services,
 }
 
-/// Result of an admin action execution.
-#[derive(Debug)]
-pub struct AdminActionResult {
+/// Outcome of running an operation.
+#[derive(Debug, Clone)]
+pub struct OperationResult {
+    pub operation: Operation,
     pub success: bool,
+    /// Summary on success, the error chain on failure.
     pub message: String,
-}
-
-impl AdminActionResult {
-    pub fn success(message: impl Into<String>) -> Self {
-        Self {
-            success: true,
-            message: message.into(),
-        }
-    }
-
-    pub fn failure(message: impl Into<String>) -> Self {
-        Self {
-            success: false,
-            message: message.into(),
-        }
-    }
+    /// Steps performed in order, as the equivalent commands, with failed
+    /// attempts marked.
+    pub details: Vec<String>,
+    pub duration: Duration,
 }
 
 /// All available quick admin actions.
 pub const QUICK_ACTIONS: &[AdminAction] = &[
     // Firewall actions
     AdminAction {
-        id: "firewall_reload",
+        operation: Operation::ReloadFirewall,
         title: "Reload Firewall",
         description: "Reload firewall rules from permanent configuration. Active connections are preserved.",
         icon: "view-refresh-symbolic",
@@ -85,7 +242,7 @@ pub const QUICK_ACTIONS: &[AdminAction] = &[
         category: ActionCategory::Firewall,
     },
     AdminAction {
-        id: "firewall_enable",
+        operation: Operation::EnableFirewall,
         title: "Enable Firewall",
         description: "Start the firewall service and enable it to start at boot.",
         icon: "security-high-symbolic",
@@ -93,7 +250,7 @@ pub const QUICK_ACTIONS: &[AdminAction] = &[
         category: ActionCategory::Firewall,
     },
     AdminAction {
-        id: "firewall_disable",
+        operation: Operation::DisableFirewall,
         title: "Disable Firewall",
         description: "Stop the firewall service and disable it at boot. WARNING: This will leave your system unprotected.",
         icon: "security-low-symbolic",
@@ -101,7 +258,7 @@ pub const QUICK_ACTIONS: &[AdminAction] = &[
         category: ActionCategory::Firewall,
     },
     AdminAction {
-        id: "firewall_panic_on",
+        operation: Operation::SetPanicMode { enabled: true },
         title: "Enable Panic Mode",
         description: "Block ALL network traffic immediately. Use for emergency lockdown.",
         icon: "dialog-error-symbolic",
@@ -109,7 +266,7 @@ pub const QUICK_ACTIONS: &[AdminAction] = &[
         category: ActionCategory::Firewall,
     },
     AdminAction {
-        id: "firewall_panic_off",
+        operation: Operation::SetPanicMode { enabled: false },
         title: "Disable Panic Mode",
         description: "Restore normal firewall operation after panic mode.",
         icon: "security-high-symbolic",
@@ -117,7 +274,7 @@ pub const QUICK_ACTIONS: &[AdminAction] = &[
         category: ActionCategory::Firewall,
     },
     AdminAction {
-        id: "firewall_runtime_to_permanent",
+        operation: Operation::RuntimeToPermanent,
         title: "Save Runtime to Permanent",
         description: "Copy all runtime firewall rules to permanent configuration.",
         icon: "document-save-symbolic",
@@ -125,7 +282,7 @@ pub const QUICK_ACTIONS: &[AdminAction] = &[
         category: ActionCategory::Firewall,
     },
     AdminAction {
-        id: "firewall_flush_runtime",
+        operation: Operation::FlushRuntime,
         title: "Flush Runtime Rules",
         description: "Remove all runtime firewall rules and reload permanent configuration.",
         icon: "edit-clear-all-symbolic",
@@ -135,7 +292,7 @@ pub const QUICK_ACTIONS: &[AdminAction] = &[
 
     // Network actions
     AdminAction {
-        id: "restart_networkmanager",
+        operation: Operation::RestartNetworkManager,
         title: "Restart NetworkManager",
         description: "Restart the NetworkManager service. This will briefly disconnect all network connections.",
         icon: "network-wired-symbolic",
@@ -145,7 +302,7 @@ pub const QUICK_ACTIONS: &[AdminAction] = &[
 
     // Service actions
     AdminAction {
-        id: "restart_sshd",
+        operation: Operation::RestartSsh,
         title: "Restart SSH Server",
         description: "Restart the SSH daemon. Existing connections will be preserved.",
         icon: "utilities-terminal-symbolic",
@@ -153,7 +310,15 @@ pub const QUICK_ACTIONS: &[AdminAction] = &[
         category: ActionCategory::Services,
     },
     AdminAction {
-        id: "reload_systemd",
+        operation: Operation::EnableTimeSync,
+        title: "Enable Time Synchronization",
+        description: "Start systemd-timesyncd and enable it at boot so the clock stays accurate for TLS and logs.",
+        icon: "preferences-system-time-symbolic",
+        destructive: false,
+        category: ActionCategory::Services,
+    },
+    AdminAction {
+        operation: Operation::ReloadSystemd,
         title: "Reload Systemd",
         description: "Reload systemd daemon configuration. Safe operation.",
         icon: "system-run-symbolic",
@@ -162,191 +327,477 @@ pub const QUICK_ACTIONS: &[AdminAction] = &[
     },
 ];
 
-/// Manager for executing quick admin actions.
-pub struct QuickActionsManager {
-    connection: Option<Connection>,
+/// Runs operations, calling firewalld through the firewall worker and
+/// connecting to systemd on first use.
+pub struct OperationRunner {
     systemd: Option<SystemdClient>,
+    details: Vec<String>,
 }
 
-impl Default for QuickActionsManager {
+impl Default for OperationRunner {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl QuickActionsManager {
+impl OperationRunner {
     pub fn new() -> Self {
         Self {
-            connection: None,
             systemd: None,
+            details: Vec::new(),
         }
     }
 
-    /// Ensure we're connected to D-Bus.
-    fn ensure_connected(&mut self) -> Result<&Connection> {
-        if self.connection.is_none() {
-            let conn = Connection::system().context("Failed to connect to system D-Bus")?;
-            self.connection = Some(conn);
+    /// Perform an operation and report how it went.
+    pub fn run(&mut self, operation: Operation) -> OperationResult {
+        let started = Instant::now();
+        self.details.clear();
+        let hooked = operation.changes_firewall();
+        if hooked {
+            hooks::dispatch(&HookEvent::PreRuleChange {
+                change: operation.id().to_string(),
+            });
         }
-        self.connection
-            .as_ref()
-            .ok_or_else(|| anyhow!("Not connected"))
-    }
-
-    /// Get a connected systemd client, connecting lazily on first use.
-    fn systemd_client(&mut self) -> Result<&SystemdClient> {
-        if self.systemd.is_none() {
-            let mut client = SystemdClient::new();
-            client.connect()?;
-            self.systemd = Some(client);
+        let result = self.perform(operation);
+        let duration = started.elapsed();
+        if hooked && result.is_ok() {
+            hooks::dispatch(&HookEvent::PostRuleChange {
+                change: operation.id().to_string(),
+            });
         }
-        self.systemd
-            .as_ref()
-            .ok_or_else(|| anyhow!("Not connected to systemd"))
-    }
 
-    /// Run a privileged systemd unit action (delegates to `SystemdClient`,
-    /// which validates parameters and uses polkit interactive authorization).
-    fn systemctl(&mut self, action: &str, service: &str) -> Result<()> {
-        self.systemd_client()?.run_unit_action(action, service)
+        let (success, message) = match result {
+            Ok(message) => {
+                info!("Operation {} completed in {:?}", operation.id(), duration);
+                (true, message)
+            }
+            Err(e) => {
+                info!("Operation {} failed: {}", operation.id(), e);
+                (false, format!("{:#}", e))
+            }
+        };
+        OperationResult {
+            operation,
+            success,
+            message,
+            details: std::mem::take(&mut self.details),
+            duration,
+        }
     }
 
-    /// Execute an admin action by ID.
-    pub fn execute(&mut self, action_id: &str) -> AdminActionResult {
-        let result = match action_id {
-            "firewall_reload" => self.firewall_reload(),
-            "firewall_enable" => self.firewall_enable(),
-            "firewall_disable" => self.firewall_disable(),
-            "firewall_panic_on" => self.firewall_panic_on(),
-            "firewall_panic_off" => self.firewall_panic_off(),
-            "firewall_runtime_to_permanent" => self.firewall_runtime_to_permanent(),
-            "firewall_flush_runtime" => self.firewall_flush_runtime(),
-            "restart_networkmanager" => self.restart_service("NetworkManager.service"),
-            "restart_sshd" => self.restart_ssh(),
-            "reload_systemd" => self.reload_systemd(),
-            _ => Err(anyhow!("Unknown action: {}", action_id)),
+    /// Perform an operation, and for a risky one that succeeded, also
+    /// return what reverts it.
+    pub fn run_revertible(&mut self, operation: Operation) -> (OperationResult, Option<Undo>) {
+        let undo = if operation.is_risky() {
+            match self.capture_undo(operation) {
+                Ok(undo) => undo,
+                Err(e) => {
+                    // Without a way back, a risky change must not run
+                    let result = OperationResult {
+                        operation,
+                        success: false,
+                        message: format!("Cannot prepare the revert: {:#}", e),
+                        details: Vec::new(),
+                        duration: Duration::ZERO,
+                    };
+                    return (result, None);
+                }
+            }
+        } else {
+            None
         };
+        let result = self.run(operation);
+        let undo = undo.filter(|_| result.success);
+        (result, undo)
+    }
 
-        match result {
-            Ok(msg) => {
-                info!("Action {} completed successfully", action_id);
-                AdminActionResult::success(msg)
+    /// Revert a risky change or undo a removal.
+    pub fn revert(&mut self, undo: &Undo) -> Result<String> {
+        self.details.clear();
+        match undo {
+            Undo::DefaultZone(zone) => {
+                let default = zone.clone();
+                self.firewall_step(
+                    &format!("firewall-cmd --set-default-zone={}", zone),
+                    move |c| c.set_default_zone(&default),
+                )?;
+                Ok(format!("Default zone set back to {}", zone))
             }
-            Err(e) => {
-                info!("Action {} failed: {}", action_id, e);
-                AdminActionResult::failure(format!("{:#}", e))
+            Undo::PanicOff => {
+                self.firewall_step(
+                    "firewall-cmd --panic-off",
+                    FirewallClient::disable_panic_mode,
+                )?;
+                Ok("Panic mode disabled".to_string())
+            }
+            Undo::ZoneService { zone, service } => {
+                let (restore_zone, restore_service) = (zone.clone(), service.clone());
+                self.firewall_step(
+                    &format!("firewall-cmd --zone={} --add-service={}", zone, service),
+                    move |c| {
+                        if c.enable_service(&restore_zone, &restore_service, true)?
+                            .failed()
+                        {
+                            bail!("Restored for this session only; the permanent change failed");
+                        }
+                        Ok(())
+                    },
+                )?;
+                Ok(format!("Service {} restored in zone {}", service, zone))
+            }
+            Undo::Journal(id) => {
+                let entry = journal::take(*id)
+                    .ok_or_else(|| anyhow!("The change is no longer in the undo journal"))?;
+                // Put back the rules in the reverse order of their removal
+                for call in entry.calls.into_iter().rev() {
+                    self.firewall_step(&call.command(), move |c| call.apply(c))?;
+                }
+                Ok(format!("Undone: {}", entry.description))
             }
         }
     }
 
-    // ═══════════════════════════════════════════════════════════════════════════
-    // FIREWALL ACTIONS
-    // ═══════════════════════════════════════════════════════════════════════════
-
-    fn firewall_reload(&mut self) -> Result<String> {
-        let conn = self.ensure_connected()?;
-
-        conn.call_method(
-            Some(FIREWALLD_BUS),
-            FIREWALLD_PATH,
-            Some(FIREWALLD_INTERFACE),
-            "reload",
-            &(),
-        )
-        .context("Failed to reload firewall. Is firewalld running?")?;
-
-        Ok("Firewall reloaded successfully".to_string())
+    /// What reverts `operation`, read before it runs.
+    fn capture_undo(&mut self, operation: Operation) -> Result<Option<Undo>> {
+        Ok(match operation {
+            Operation::SetPanicMode { enabled: true } => Some(Undo::PanicOff),
+            Operation::SetDefaultZone { .. } => {
+                Some(Undo::DefaultZone(with_firewall(|c| c.get_default_zone())?))
+            }
+            Operation::RemoveZoneService { zone, service } => Some(Undo::ZoneService {
+                zone: zone.to_string(),
+                service: service.to_string(),
+            }),
+            _ => None,
+        })
     }
 
-    fn firewall_enable(&mut self) -> Result<String> {
-        // Start the service
-        self.systemctl("start", "firewalld.service")?;
-        // Enable at boot
-        self.systemctl("enable", "firewalld.service")?;
-
-        Ok("Firewall enabled and started".to_string())
+    fn perform(&mut self, operation: Operation) -> Result<String> {
+        match operation {
+            Operation::ReloadFirewall => {
+                self.firewall_step("firewall-cmd --reload", FirewallClient::reload)?;
+                Ok("Firewall reloaded successfully".to_string())
+            }
+            Operation::EnableFirewall => {
+                self.unit_step("start", "firewalld.service")?;
+                self.unit_step("enable", "firewalld.service")?;
+                Ok("Firewall enabled and started".to_string())
+            }
+            Operation::DisableFirewall => {
+                self.unit_step("stop", "firewalld.service")?;
+                self.unit_step("disable", "firewalld.service")?;
+                Ok("Firewall stopped and disabled".to_string())
+            }
+            Operation::SetPanicMode { enabled: true } => {
+                self.firewall_step("firewall-cmd --panic-on", FirewallClient::enable_panic_mode)
+                    .context("Failed to enable panic mode")?;
+                Ok("Panic mode enabled - all traffic blocked".to_string())
+            }
+            Operation::SetPanicMode { enabled: false } => {
+                self.firewall_step(
+                    "firewall-cmd --panic-off",
+                    FirewallClient::disable_panic_mode,
+                )
+                .context("Failed to disable panic mode")?;
+                Ok("Panic mode disabled - normal operation restored".to_string())
+            }
+            Operation::RuntimeToPermanent => {
+                self.firewall_step(
+                    "firewall-cmd --runtime-to-permanent",
+                    FirewallClient::runtime_to_permanent,
+                )
+                .context("Failed to save runtime rules")?;
+                Ok("Runtime rules saved to permanent configuration".to_string())
+            }
+            Operation::FlushRuntime => {
+                // Reloading discards runtime changes
+                self.firewall_step("firewall-cmd --reload", FirewallClient::reload)?;
+                Ok("Runtime rules flushed, permanent rules restored".to_string())
+            }
+            Operation::RestartNetworkManager => {
+                self.unit_step("restart", "NetworkManager.service")?;
+                Ok("Service NetworkManager.service restarted".to_string())
+            }
+            Operation::RestartSsh => {
+                // sshd.service on RHEL/Fedora, ssh.service on Debian/Ubuntu
+                if self.unit_step("restart", "sshd.service").is_err() {
+                    self.unit_step("restart", "ssh.service")?;
+                }
+                Ok("SSH server restarted".to_string())
+            }
+            Operation::EnableTimeSync => {
+                self.unit_step("start", crate::admin::TIMESYNCD_UNIT)?;
+                self.unit_step("enable", crate::admin::TIMESYNCD_UNIT)?;
+                Ok("Time synchronization enabled".to_string())
+            }
+            Operation::ReloadSystemd => {
+                self.systemd_step("systemctl daemon-reload", SystemdClient::daemon_reload)?;
+                Ok("Systemd configuration reloaded".to_string())
+            }
+            Operation::SetDefaultZone { zone } => {
+                self.firewall_step(
+                    &format!("firewall-cmd --set-default-zone={}", zone),
+                    move |c| c.set_default_zone(zone),
+                )?;
+                Ok(format!("Default zone set to {}", zone))
+            }
+            Operation::RemoveZoneService { zone, service } => {
+                self.firewall_step(
+                    &format!(
+                        "firewall-cmd --permanent --zone={} --remove-service={}",
+                        zone, service
+                    ),
+                    move |c| {
+                        if c.disable_service(zone, service, true)?.failed() {
+                            bail!("Applied for this session only; the permanent change failed");
+                        }
+                        Ok(())
+                    },
+                )?;
+                Ok(format!("Service {} removed from zone {}", service, zone))
+            }
+            Operation::StopAndDisableUnit { unit } => {
+                self.unit_step("stop", unit)?;
+                self.unit_step("disable", unit)?;
+                Ok(format!("Service {} stopped and disabled", unit))
+            }
+            Operation::SetRemoteAccess {
+                method,
+                enabled: true,
+            } => self.enable_remote_access(method),
+            Operation::SetRemoteAccess {
+                method,
+                enabled: false,
+            } => self.disable_remote_access(method),
+            Operation::EnableMacRandomization => self.helper_step(
+                &format!("install {}", crate::admin::MAC_DROPIN),
+                HelperOp::EnableMacRandomization,
+            ),
+            Operation::HardenSsh => self.helper_step(
+                &format!("install {}", crate::admin::SSH_DROPIN),
+                HelperOp::HardenSsh,
+            ),
+            Operation::EnableScreenLock => {
+                let commands = crate::admin::enable_screen_lock()?;
+                self.details.extend(commands);
+                Ok("Screen lock enabled".to_string())
+            }
+        }
     }
 
-    fn firewall_disable(&mut self) -> Result<String> {
-        // Stop the service
-        self.systemctl("stop", "firewalld.service")?;
-        // Disable at boot
-        self.systemctl("disable", "firewalld.service")?;
+    /// Installed units of `method`, in its preferred order.
+    fn installed_units(&mut self, method: AccessMethod) -> Result<Vec<&'static str>> {
+        let systemd = self.systemd()?;
+        Ok(method
+            .units()
+            .iter()
+            .copied()
+            .filter(|unit| {
+                systemd
+                    .get_service_info(unit)
+                    .is_ok_and(|info| info.is_installed)
+            })
+            .collect())
+    }
 
-        Ok("Firewall stopped and disabled".to_string())
+    /// Start the first installed unit of `method` and enable it at boot,
+    /// then allow its firewalld service in the default zone.
+    fn enable_remote_access(&mut self, method: AccessMethod) -> Result<String> {
+        let Some(unit) = self.installed_units(method)?.first().copied() else {
+            bail!("{} is not installed", method.label());
+        };
+        self.unit_step("enable", unit)?;
+        self.unit_step("start", unit)?;
+
+        let service = method.firewall_service();
+        let zone = with_firewall(|c| c.get_default_zone())?;
+        let open_in = zone.clone();
+        self.firewall_step(
+            &format!(
+                "firewall-cmd --permanent --zone={} --add-service={}",
+                zone, service
+            ),
+            move |c| {
+                if c.enable_service(&open_in, service, true)?.failed() {
+                    bail!("Applied for this session only; the permanent change failed");
+                }
+                Ok(())
+            },
+        )?;
+        Ok(format!(
+            "{} turned on: {} started, {} allowed in zone {}",
+            method.label(),
+            unit,
+            service,
+            zone
+        ))
     }
 
-    fn firewall_panic_on(&mut self) -> Result<String> {
-        let conn = self.ensure_connected()?;
+    /// Close the firewalld service of `method` in every zone, then stop and
+    /// disable its installed units. Without firewalld running there is
+    /// nothing to close.
+    fn disable_remote_access(&mut self, method: AccessMethod) -> Result<String> {
+        let service = method.firewall_service();
+        let mut closed = Vec::new();
+        if let Ok(zones) = with_firewall(|c| Ok(c.get_zones())) {
+            for zone in method.open_in(&zones?) {
+                let close_in = zone.clone();
+                self.firewall_step(
+                    &format!(
+                        "firewall-cmd --permanent --zone={} --remove-service={}",
+                        zone, service
+                    ),
+                    move |c| {
+                        if c.disable_service(&close_in, service, true)?.failed() {
+                            bail!("Applied for this session only; the permanent change failed");
+                        }
+                        Ok(())
+                    },
+                )?;
+                closed.push(zone);
+            }
+        }
+        let units = self.installed_units(method)?;
+        for unit in &units {
+            self.unit_step("stop", unit)?;
+            self.unit_step("disable", unit)?;
+        }
+        Ok(format!(
+            "{} turned off: {} stopped, {} removed from {} zone(s)",
+            method.label(),
+            if units.is_empty() {
+                "nothing".to_string()
+            } else {
+                units.join(", ")
+            },
+            service,
+            closed.len()
+        ))
+    }
 
-        conn.call_method(
-            Some(FIREWALLD_BUS),
-            FIREWALLD_PATH,
-            Some(FIREWALLD_INTERFACE),
-            "enablePanicMode",
-            &(),
-        )
-        .context("Failed to enable panic mode")?;
+    /// Get a connected systemd client, connecting lazily on first use.
+    fn systemd(&mut self) -> Result<&SystemdClient> {
+        if self.systemd.is_none() {
+            let mut client = SystemdClient::new();
+            client.connect()?;
+            self.systemd = Some(client);
+        }
+        self.systemd
+            .as_ref()
+            .ok_or_else(|| anyhow!("Not connected to systemd"))
+    }
 
-        Ok("Panic mode enabled - all traffic blocked".to_string())
+    /// Run a firewalld call and record it as `command`.
+    fn firewall_step(
+        &mut self,
+        command: &str,
+        call: impl FnOnce(&FirewallClient) -> Result<()> + Send + 'static,
+    ) -> Result<()> {
+        let result = with_firewall(move |c| call(c));
+        self.record(command, &result);
+        result
     }
 
-    fn firewall_panic_off(&mut self) -> Result<String> {
-        let conn = self.ensure_connected()?;
+    /// Run a systemd call and record it as `command`.
+    fn systemd_step(
+        &mut self,
+        command: &str,
+        call: impl FnOnce(&SystemdClient) -> Result<()>,
+    ) -> Result<()> {
+        let result = self.systemd().and_then(call);
+        self.record(command, &result);
+        result
+    }
 
-        conn.call_method(
-            Some(FIREWALLD_BUS),
-            FIREWALLD_PATH,
-            Some(FIREWALLD_INTERFACE),
-            "disablePanicMode",
-            &(),
-        )
-        .context("Failed to disable panic mode")?;
+    /// Run a privileged unit action (validated by `SystemdClient`, with
+    /// polkit interactive authorization) and record it.
+    fn unit_step(&mut self, action: &str, unit: &str) -> Result<()> {
+        self.systemd_step(&format!("systemctl {} {}", action, unit), |client| {
+            client.run_unit_action(action, unit)
+        })
+    }
 
-        Ok("Panic mode disabled - normal operation restored".to_string())
+    /// Run a privileged helper operation and record it as `command`.
+    fn helper_step(&mut self, command: &str, op: HelperOp) -> Result<String> {
+        let result = run_privileged(&op);
+        self.record(command, &result);
+        result
     }
 
-    fn firewall_runtime_to_permanent(&mut self) -> Result<String> {
-        let conn = self.ensure_connected()?;
+    fn record<T>(&mut self, command: &str, result: &Result<T>) {
+        match result {
+            Ok(_) => self.details.push(command.to_string()),
+            Err(e) => self.details.push(format!("{}: failed: {:#}", command, e)),
+        }
+    }
+}
 
-        conn.call_method(
-            Some(FIREWALLD_BUS),
-            FIREWALLD_PATH,
-            Some(FIREWALLD_INTERFACE),
-            "runtimeToPermanent",
-            &(),
-        )
-        .context("Failed to save runtime rules")?;
+/// Run `call` on the firewall worker's client once it is connected.
+fn with_firewall<R: Send + 'static>(
+    call: impl FnOnce(&mut FirewallClient) -> Result<R> + Send + 'static,
+) -> Result<R> {
+    crate::firewall::call_blocking(move |client| {
+        client
+            .connect()
+            .context("Cannot reach firewalld. Is it running?")?;
+        call(client)
+    })?
+}
 
-        Ok("Runtime rules saved to permanent configuration".to_string())
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    fn firewall_flush_runtime(&mut self) -> Result<String> {
-        // Flush by reloading - this discards runtime changes
-        self.firewall_reload()?;
-        Ok("Runtime rules flushed, permanent rules restored".to_string())
+    #[test]
+    fn test_operation_ids_unique() {
+        let mut ids: Vec<&str> = QUICK_ACTIONS.iter().map(|a| a.operation.id()).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), QUICK_ACTIONS.len());
     }
 
-    // ═══════════════════════════════════════════════════════════════════════════
-    // SERVICE ACTIONS (via systemd D-Bus API with polkit interactive authorization)
-    // ═══════════════════════════════════════════════════════════════════════════
-
-    fn restart_service(&mut self, service: &str) -> Result<String> {
-        self.systemctl("restart", service)?;
-        Ok(format!("Service {} restarted", service))
+    #[test]
+    fn test_operation_depends_on() {
+        assert_eq!(
+            Operation::EnableFirewall.depends_on(),
+            Some((WatchedUnit::Firewalld, false))
+        );
+        assert_eq!(
+            Operation::SetPanicMode { enabled: true }.depends_on(),
+            Some((WatchedUnit::Firewalld, true))
+        );
+        assert_eq!(
+            Operation::RestartSsh.depends_on(),
+            Some((WatchedUnit::Ssh, false))
+        );
+        assert_eq!(Operation::EnableScreenLock.depends_on(), None);
     }
 
-    fn restart_ssh(&mut self) -> Result<String> {
-        // Try sshd.service first (RHEL/Fedora), then ssh.service (Debian/Ubuntu)
-        if self.systemctl("restart", "sshd.service").is_ok() {
-            return Ok("SSH server restarted".to_string());
+    #[test]
+    fn test_operation_is_risky() {
+        assert!(Operation::SetPanicMode { enabled: true }.is_risky());
+        assert!(!Operation::SetPanicMode { enabled: false }.is_risky());
+        assert!(Operation::SetDefaultZone { zone: "drop" }.is_risky());
+        assert!(!Operation::SetDefaultZone { zone: "public" }.is_risky());
+        assert!(Operation::RemoveZoneService {
+            zone: "public",
+            service: "ssh"
         }
-        self.systemctl("restart", "ssh.service")?;
-        Ok("SSH server restarted".to_string())
+        .is_risky());
+        assert!(!Operation::ReloadFirewall.is_risky());
     }
 
-    fn reload_systemd(&mut self) -> Result<String> {
-        self.systemd_client()?.daemon_reload()?;
-        Ok("Systemd configuration reloaded".to_string())
+    #[test]
+    fn test_operation_action_lookup() {
+        for action in QUICK_ACTIONS {
+            assert_eq!(
+                action.operation.action().map(|a| a.title),
+                Some(action.title)
+            );
+        }
+        assert_eq!(
+            Operation::SetPanicMode { enabled: true }.id(),
+            "firewall_panic_on"
+        );
     }
 }
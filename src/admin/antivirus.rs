@@ -0,0 +1,485 @@
+// Security Center - Antivirus
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! ClamAV detection, signature database age and on-demand scans.
+//!
+//! Everything is optional: without `clamscan` or `clamdscan` on the PATH
+//! the page only explains how to install ClamAV. Scans go through
+//! `clamdscan --fdpass` while clamd runs, as its signatures are already in
+//! memory, and otherwise through `clamscan`, which loads them first.
+//! [`AntivirusScan`] reads the scanner's report line by line on a thread;
+//! the page polls it for progress.
+
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::UNIX_EPOCH;
+
+use anyhow::{bail, Context, Result};
+
+use crate::systemd::{ServiceState, SystemdClient};
+
+const CLAMSCAN: &str = "clamscan";
+const CLAMDSCAN: &str = "clamdscan";
+const FRESHCLAM: &str = "freshclam";
+/// clamd's unit on Debian, Ubuntu and Arch, and on Fedora.
+const DAEMON_UNITS: [&str; 2] = ["clamav-daemon.service", "clamd@scan.service"];
+const FRESHCLAM_UNIT: &str = "clamav-freshclam.service";
+/// Where freshclam keeps the signatures unless configured otherwise.
+const DEFAULT_DATABASE_DIR: &str = "/var/lib/clamav";
+const FRESHCLAM_CONFIGS: [&str; 2] = ["/etc/clamav/freshclam.conf", "/etc/freshclam.conf"];
+/// Signatures older than this miss recent threats.
+pub const OUTDATED_AFTER_DAYS: i64 = 7;
+/// Read errors kept for display; a scan of a whole home can hit many.
+const MAX_ERRORS: usize = 100;
+
+/// Which ClamAV program scans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scanner {
+    /// `clamdscan`, handing files to the running clamd.
+    Daemon,
+    /// `clamscan`, loading the signatures itself.
+    Standalone,
+}
+
+/// The signature database.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Signatures {
+    /// Version of the daily database.
+    pub version: Option<u32>,
+    /// When it was built or downloaded, as a Unix timestamp.
+    pub updated: Option<i64>,
+}
+
+impl Signatures {
+    /// Whole days since the last update.
+    pub fn age_days(&self, now: i64) -> Option<i64> {
+        self.updated.map(|updated| (now - updated).max(0) / 86_400)
+    }
+
+    pub fn is_outdated(&self, now: i64) -> bool {
+        self.age_days(now)
+            .is_none_or(|days| days > OUTDATED_AFTER_DAYS)
+    }
+}
+
+/// What is installed and running of ClamAV.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AntivirusStatus {
+    /// The program scans would use; `None` when ClamAV is not installed.
+    pub scanner: Option<Scanner>,
+    /// Engine version, such as `1.0.5`.
+    pub engine: Option<String>,
+    pub signatures: Option<Signatures>,
+    pub daemon_running: bool,
+    pub freshclam_installed: bool,
+    /// Whether the freshclam service keeps the signatures up to date.
+    pub freshclam_running: bool,
+}
+
+/// Detect ClamAV and read its signature database.
+pub fn query_antivirus() -> AntivirusStatus {
+    let has_clamscan = program_installed(CLAMSCAN);
+    let has_clamdscan = program_installed(CLAMDSCAN);
+    if !has_clamscan && !has_clamdscan {
+        return AntivirusStatus::default();
+    }
+
+    let (daemon_running, freshclam_running) = service_states();
+    let scanner = if daemon_running && has_clamdscan {
+        Scanner::Daemon
+    } else if has_clamscan {
+        Scanner::Standalone
+    } else {
+        Scanner::Daemon
+    };
+
+    let version_line = Command::new(if has_clamscan { CLAMSCAN } else { CLAMDSCAN })
+        .arg("--version")
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+    let (engine, mut signatures) = version_line
+        .as_deref()
+        .map(parse_version)
+        .unwrap_or_default();
+    // An older engine may not print the date; the database files tell it
+    if signatures.as_ref().is_none_or(|s| s.updated.is_none()) {
+        if let Some(updated) = database_modified(&database_dir()) {
+            signatures.get_or_insert_with(Signatures::default).updated = Some(updated);
+        }
+    }
+
+    AntivirusStatus {
+        scanner: Some(scanner),
+        engine,
+        signatures,
+        daemon_running,
+        freshclam_installed: program_installed(FRESHCLAM),
+        freshclam_running,
+    }
+}
+
+/// Parse `ClamAV 1.0.5/27437/Wed Oct 16 08:25:01 2024` into the engine
+/// version and the daily signatures.
+fn parse_version(line: &str) -> (Option<String>, Option<Signatures>) {
+    let Some(rest) = line.strip_prefix("ClamAV ") else {
+        return (None, None);
+    };
+    let mut parts = rest.splitn(3, '/');
+    let engine = parts.next().map(|v| v.trim().to_string());
+    let Some(version) = parts.next().and_then(|v| v.trim().parse().ok()) else {
+        return (engine, None);
+    };
+    let updated = parts
+        .next()
+        .and_then(|date| {
+            chrono::NaiveDateTime::parse_from_str(date.trim(), "%a %b %e %H:%M:%S %Y").ok()
+        })
+        .map(|date| date.and_utc().timestamp());
+    (
+        engine,
+        Some(Signatures {
+            version: Some(version),
+            updated,
+        }),
+    )
+}
+
+fn program_installed(name: &str) -> bool {
+    std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).any(|dir| dir.join(name).is_file()))
+        .unwrap_or(false)
+}
+
+/// Whether clamd and the freshclam service are running.
+fn service_states() -> (bool, bool) {
+    let mut client = SystemdClient::new();
+    if client.connect().is_err() {
+        return (false, false);
+    }
+    let running = |unit: &str| {
+        client
+            .get_service_info(unit)
+            .is_ok_and(|info| info.state == ServiceState::Running)
+    };
+    (
+        DAEMON_UNITS.iter().any(|unit| running(unit)),
+        running(FRESHCLAM_UNIT),
+    )
+}
+
+/// `DatabaseDirectory` from freshclam's configuration, or the default.
+fn database_dir() -> PathBuf {
+    FRESHCLAM_CONFIGS
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .find_map(|config| {
+            config.lines().find_map(|line| {
+                let (key, value) = line.trim().split_once(char::is_whitespace)?;
+                (key == "DatabaseDirectory").then(|| PathBuf::from(value.trim()))
+            })
+        })
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_DATABASE_DIR))
+}
+
+/// When the daily database was last written.
+fn database_modified(dir: &Path) -> Option<i64> {
+    ["daily.cld", "daily.cvd"]
+        .iter()
+        .filter_map(|name| std::fs::metadata(dir.join(name)).ok()?.modified().ok())
+        .max()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|age| age.as_secs() as i64)
+}
+
+/// A file the scanner flagged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Detection {
+    pub path: String,
+    /// Signature name, such as `Eicar-Signature`.
+    pub signature: String,
+}
+
+/// What a running or finished scan has found so far.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanProgress {
+    pub files_scanned: u64,
+    /// The file or directory reported last.
+    pub current: Option<String>,
+    pub detections: Vec<Detection>,
+    /// Files that could not be scanned, up to a limit.
+    pub errors: Vec<String>,
+    /// Set once the scanner exited and its report is read.
+    pub finished: bool,
+    pub cancelled: bool,
+    /// The scanner failed rather than reporting files it could not read.
+    pub failure: Option<String>,
+}
+
+/// One line of the scanner's report.
+#[derive(Debug, PartialEq, Eq)]
+enum ReportLine {
+    Clean(String),
+    Infected(Detection),
+    Error(String),
+    /// `Scanned files: N` from the summary.
+    Scanned(u64),
+}
+
+fn parse_report_line(line: &str) -> Option<ReportLine> {
+    let line = line.trim_end();
+    if let Some(count) = line.strip_prefix("Scanned files: ") {
+        return count.trim().parse().ok().map(ReportLine::Scanned);
+    }
+    if line.ends_with(" ERROR") {
+        return Some(ReportLine::Error(
+            line.trim_end_matches(" ERROR").to_string(),
+        ));
+    }
+    // Paths can contain ": ", the verdict follows the last one
+    let (path, verdict) = line.rsplit_once(": ")?;
+    if path.is_empty() {
+        return None;
+    }
+    if verdict == "OK" {
+        return Some(ReportLine::Clean(path.to_string()));
+    }
+    verdict.strip_suffix(" FOUND").map(|signature| {
+        ReportLine::Infected(Detection {
+            path: path.to_string(),
+            signature: signature.to_string(),
+        })
+    })
+}
+
+/// A scan running in the background until finished or dropped.
+pub struct AntivirusScan {
+    child: Child,
+    progress: Arc<Mutex<ScanProgress>>,
+    /// Set when the report thread has read all output.
+    report_done: Arc<Mutex<bool>>,
+}
+
+impl AntivirusScan {
+    /// Scan `folders` recursively.
+    pub fn start(scanner: Scanner, folders: &[PathBuf]) -> Result<Self> {
+        if folders.is_empty() {
+            bail!("No folders to scan");
+        }
+        if let Some(missing) = folders.iter().find(|folder| !folder.is_dir()) {
+            bail!("{} is not a folder", missing.display());
+        }
+        let mut command = match scanner {
+            Scanner::Daemon => {
+                let mut command = Command::new(CLAMDSCAN);
+                // clamd runs as its own user; pass it open files instead
+                command.args(["--fdpass", "--multiscan"]);
+                command
+            }
+            Scanner::Standalone => {
+                let mut command = Command::new(CLAMSCAN);
+                command.arg("--recursive");
+                command
+            }
+        };
+        let mut child = command
+            .arg("--")
+            .args(folders)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to start the virus scanner")?;
+        let stdout = child.stdout.take().context("The scanner has no output")?;
+        let stderr = child.stderr.take().context("The scanner has no output")?;
+
+        let progress = Arc::new(Mutex::new(ScanProgress::default()));
+        let report_done = Arc::new(Mutex::new(false));
+
+        let thread_progress = progress.clone();
+        let thread_done = report_done.clone();
+        std::thread::Builder::new()
+            .name("antivirus-scan".to_string())
+            .spawn(move || {
+                for line in BufReader::new(stdout).lines() {
+                    let Ok(line) = line else { break };
+                    let Some(report) = parse_report_line(&line) else {
+                        continue;
+                    };
+                    let Ok(mut progress) = thread_progress.lock() else {
+                        break;
+                    };
+                    match report {
+                        ReportLine::Clean(path) => {
+                            progress.files_scanned += 1;
+                            progress.current = Some(path);
+                        }
+                        ReportLine::Infected(detection) => {
+                            progress.files_scanned += 1;
+                            progress.current = Some(detection.path.clone());
+                            progress.detections.push(detection);
+                        }
+                        ReportLine::Error(message) => {
+                            if progress.errors.len() < MAX_ERRORS {
+                                progress.errors.push(message);
+                            }
+                        }
+                        // clamdscan reports folders, not files
+                        ReportLine::Scanned(count) => {
+                            progress.files_scanned = progress.files_scanned.max(count)
+                        }
+                    }
+                }
+                if let Ok(mut done) = thread_done.lock() {
+                    *done = true;
+                }
+            })?;
+
+        // Warnings and errors about the scanner itself
+        let thread_progress = progress.clone();
+        std::thread::Builder::new()
+            .name("antivirus-errors".to_string())
+            .spawn(move || {
+                for line in BufReader::new(stderr).lines() {
+                    let Ok(line) = line else { break };
+                    let line = line.trim();
+                    if line.is_empty() {
+                        continue;
+                    }
+                    if let Ok(mut progress) = thread_progress.lock() {
+                        if progress.errors.len() < MAX_ERRORS {
+                            progress.errors.push(line.to_string());
+                        }
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            child,
+            progress,
+            report_done,
+        })
+    }
+
+    /// The progress so far, checking whether the scanner has exited.
+    pub fn poll(&mut self) -> ScanProgress {
+        let report_done = self.report_done.lock().map(|done| *done).unwrap_or(true);
+        let exit = if report_done {
+            self.child.try_wait().ok().flatten()
+        } else {
+            None
+        };
+        let Ok(mut progress) = self.progress.lock() else {
+            return ScanProgress {
+                finished: true,
+                failure: Some("The scan report is unavailable".to_string()),
+                ..ScanProgress::default()
+            };
+        };
+        if let Some(status) = exit {
+            if !progress.finished {
+                progress.finished = true;
+                // 0: nothing found, 1: found something, 2: some errors
+                match status.code() {
+                    Some(0) | Some(1) => {}
+                    Some(2) if progress.files_scanned > 0 => {}
+                    _ if progress.cancelled => {}
+                    _ => {
+                        progress.failure =
+                            Some(
+                                progress.errors.last().cloned().unwrap_or_else(|| {
+                                    format!("The scanner exited with {}", status)
+                                }),
+                            )
+                    }
+                }
+            }
+        }
+        progress.clone()
+    }
+
+    /// Stop the scan; [`AntivirusScan::poll`] then reports it finished.
+    pub fn cancel(&mut self) {
+        if let Ok(mut progress) = self.progress.lock() {
+            progress.cancelled = true;
+        }
+        let _ = self.child.kill();
+    }
+}
+
+impl Drop for AntivirusScan {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        let (engine, signatures) = parse_version("ClamAV 1.0.5/27437/Wed Oct 16 08:25:01 2024");
+        assert_eq!(engine.as_deref(), Some("1.0.5"));
+        let signatures = signatures.unwrap();
+        assert_eq!(signatures.version, Some(27437));
+        assert_eq!(signatures.updated, Some(1_729_067_101));
+
+        let (engine, signatures) = parse_version("ClamAV 1.3.1");
+        assert_eq!(engine.as_deref(), Some("1.3.1"));
+        assert_eq!(signatures, None);
+        assert_eq!(parse_version("clamscan: not found"), (None, None));
+    }
+
+    #[test]
+    fn test_signatures_outdated() {
+        let now = 1_729_067_101;
+        let fresh = Signatures {
+            version: Some(1),
+            updated: Some(now - 86_400),
+        };
+        assert_eq!(fresh.age_days(now), Some(1));
+        assert!(!fresh.is_outdated(now));
+        let old = Signatures {
+            version: Some(1),
+            updated: Some(now - 30 * 86_400),
+        };
+        assert!(old.is_outdated(now));
+        assert!(Signatures::default().is_outdated(now));
+    }
+
+    #[test]
+    fn test_parse_report_line() {
+        assert_eq!(
+            parse_report_line("/home/user/notes.txt: OK"),
+            Some(ReportLine::Clean("/home/user/notes.txt".to_string()))
+        );
+        assert_eq!(
+            parse_report_line("/home/user/a: b/eicar.com: Eicar-Signature FOUND"),
+            Some(ReportLine::Infected(Detection {
+                path: "/home/user/a: b/eicar.com".to_string(),
+                signature: "Eicar-Signature".to_string(),
+            }))
+        );
+        assert_eq!(
+            parse_report_line("/root/secret: Access denied. ERROR"),
+            Some(ReportLine::Error(
+                "/root/secret: Access denied.".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_report_line("Scanned files: 42"),
+            Some(ReportLine::Scanned(42))
+        );
+        assert_eq!(parse_report_line("/home/user/empty: Empty file"), None);
+        assert_eq!(
+            parse_report_line("----------- SCAN SUMMARY -----------"),
+            None
+        );
+    }
+}
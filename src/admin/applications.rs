@@ -0,0 +1,432 @@
+// Security Center - Applications
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Listening sockets and the firewall rules covering them, grouped by the
+//! executable that owns them.
+//!
+//! Zones, services and ports say what the firewall lets in; listening
+//! sockets say who is there to answer. [`query_applications`] joins the two
+//! per program, so the firewall reads as "nginx: ports 80, 443 allowed in
+//! public; listening on 0.0.0.0" rather than as a list of port numbers.
+//! Whether a port is allowed is decided the way the By Interface view does
+//! it (see `interface_exposure`), and ports or services opened for nothing
+//! that listens are listed apart.
+//!
+//! The executable comes from `/proc/<pid>/exe`, which is only readable for
+//! the user's own processes; sockets of other users' processes fall back to
+//! the command name, and sockets with no known process are grouped together.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::net::IpAddr;
+
+use anyhow::Result;
+
+use super::interface_exposure::ZonePolicy;
+use super::network::{parse_port_string, parse_rich_rule_port, ListeningEndpoint, Protocol};
+use super::NetworkExposure;
+use crate::validation::parse_port_spec;
+
+/// What makes a zone let a port in, or keep it out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleKind {
+    /// An open port or range, as firewalld writes it ("80/tcp").
+    Port(String),
+    /// An enabled service covering the port.
+    Service(String),
+    /// A rich rule with a port match, by its text.
+    RichRule(String),
+    /// The zone accepts all traffic.
+    ZoneTarget,
+}
+
+/// A firewall rule of one zone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppRule {
+    pub zone: String,
+    pub kind: RuleKind,
+    /// Whether the rule lets traffic in rather than blocking it.
+    pub allows: bool,
+}
+
+impl AppRule {
+    /// A short description such as "service http in public".
+    pub fn describe(&self) -> String {
+        match &self.kind {
+            RuleKind::Port(port) => format!("port {} in {}", port, self.zone),
+            RuleKind::Service(service) => format!("service {} in {}", service, self.zone),
+            RuleKind::RichRule(_) if self.allows => format!("rich rule in {}", self.zone),
+            RuleKind::RichRule(_) => format!("blocking rich rule in {}", self.zone),
+            RuleKind::ZoneTarget => format!("{} accepts all traffic", self.zone),
+        }
+    }
+}
+
+/// A port an application listens on.
+#[derive(Debug, Clone)]
+pub struct AppListener {
+    pub port: u16,
+    pub protocol: Protocol,
+    /// Addresses the port is bound to.
+    pub addresses: Vec<IpAddr>,
+    /// Zones that let connections to the port in.
+    pub allowed_in: Vec<String>,
+    /// Rules covering the port in any zone in use.
+    pub rules: Vec<AppRule>,
+}
+
+impl AppListener {
+    /// The port, with the protocol unless it is TCP.
+    pub fn port_label(&self) -> String {
+        match self.protocol {
+            Protocol::Tcp => self.port.to_string(),
+            Protocol::Udp => format!("{}/udp", self.port),
+        }
+    }
+}
+
+/// A program and the ports it listens on.
+#[derive(Debug, Clone)]
+pub struct Application {
+    /// File name of the executable, or the command name.
+    pub name: String,
+    /// Full path of the executable, when it could be read.
+    pub executable: Option<String>,
+    pub pids: Vec<u32>,
+    pub listeners: Vec<AppListener>,
+}
+
+impl Application {
+    /// Whether the process owning the sockets is unknown.
+    pub fn is_unknown(&self) -> bool {
+        self.pids.is_empty()
+    }
+
+    /// Whether some zone lets connections to one of its ports in.
+    pub fn is_allowed(&self) -> bool {
+        self.listeners.iter().any(|l| !l.allowed_in.is_empty())
+    }
+
+    /// One line about the application, such as "ports 80, 443 allowed in
+    /// public; listening on 0.0.0.0". `firewall` is false when the zones
+    /// could not be read.
+    pub fn summary(&self, firewall: bool) -> String {
+        let mut parts = Vec::new();
+        if firewall {
+            let mut by_zone: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+            for listener in &self.listeners {
+                for zone in &listener.allowed_in {
+                    let ports = by_zone.entry(zone).or_default();
+                    let label = listener.port_label();
+                    if !ports.contains(&label) {
+                        ports.push(label);
+                    }
+                }
+            }
+            if by_zone.is_empty() {
+                parts.push("not allowed in any zone".to_string());
+            }
+            for (zone, ports) in by_zone {
+                let noun = if ports.len() == 1 { "port" } else { "ports" };
+                parts.push(format!("{} {} allowed in {}", noun, ports.join(", "), zone));
+            }
+        } else {
+            parts.push("firewall not checked".to_string());
+        }
+
+        let addresses: BTreeSet<IpAddr> = self
+            .listeners
+            .iter()
+            .flat_map(|l| l.addresses.iter().copied())
+            .collect();
+        let addresses: Vec<String> = addresses.iter().map(IpAddr::to_string).collect();
+        parts.push(format!("listening on {}", addresses.join(", ")));
+        parts.join("; ")
+    }
+}
+
+/// Applications with listening sockets, and rules nothing listens behind.
+#[derive(Debug, Clone, Default)]
+pub struct ApplicationView {
+    /// Known programs by name, then the sockets with no known process.
+    pub applications: Vec<Application>,
+    /// Ports and services open in a zone with no socket listening on them.
+    pub unused: Vec<AppRule>,
+    /// Whether the firewall zones could be read.
+    pub firewall: bool,
+}
+
+/// Rules of every zone covering `port`/`protocol`.
+fn rules_for(policy: &ZonePolicy, port: u16, protocol: &str) -> Vec<AppRule> {
+    let covers = |(start, end): (u16, u16), p: &str| p == protocol && (start..=end).contains(&port);
+
+    let mut zones: Vec<_> = policy.zones.values().collect();
+    zones.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut rules = Vec::new();
+    for zone in zones {
+        let rule = |kind, allows| AppRule {
+            zone: zone.name.clone(),
+            kind,
+            allows,
+        };
+        for port_str in &zone.ports {
+            if parse_port_string(port_str).is_some_and(|(range, p)| covers(range, &p)) {
+                rules.push(rule(RuleKind::Port(port_str.clone()), true));
+            }
+        }
+        for name in &zone.services {
+            let covered = policy.services.get(name).is_some_and(|service| {
+                service.ports.iter().any(|(port, p)| {
+                    parse_port_spec(port).is_some_and(|range| covers(range, &p.to_lowercase()))
+                })
+            });
+            if covered {
+                rules.push(rule(RuleKind::Service(name.clone()), true));
+            }
+        }
+        for rich_rule in &zone.rich_rules {
+            if parse_rich_rule_port(rich_rule).is_some_and(|(range, p)| covers(range, &p)) {
+                let blocks = rich_rule.contains("reject") || rich_rule.contains("drop");
+                rules.push(rule(RuleKind::RichRule(rich_rule.clone()), !blocks));
+            }
+        }
+        if zone.target == "ACCEPT" {
+            rules.push(rule(RuleKind::ZoneTarget, true));
+        }
+    }
+    rules
+}
+
+/// Open ports and enabled services of the zones that no socket in
+/// `listening` (port and lowercase protocol) is behind.
+fn unused_rules(policy: &ZonePolicy, listening: &BTreeSet<(u16, String)>) -> Vec<AppRule> {
+    let in_use = |(start, end): (u16, u16), p: &str| {
+        listening
+            .iter()
+            .any(|(port, protocol)| protocol == p && (start..=end).contains(port))
+    };
+
+    let mut zones: Vec<_> = policy.zones.values().collect();
+    zones.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut unused = Vec::new();
+    for zone in zones {
+        for port_str in &zone.ports {
+            if parse_port_string(port_str).is_some_and(|(range, p)| !in_use(range, &p)) {
+                unused.push(AppRule {
+                    zone: zone.name.clone(),
+                    kind: RuleKind::Port(port_str.clone()),
+                    allows: true,
+                });
+            }
+        }
+        for name in &zone.services {
+            let Some(service) = policy.services.get(name) else {
+                continue;
+            };
+            let ports: Vec<((u16, u16), String)> = service
+                .ports
+                .iter()
+                .filter_map(|(port, p)| Some((parse_port_spec(port)?, p.to_lowercase())))
+                .collect();
+            // Services without ports (helpers, protocols) cannot be judged
+            if !ports.is_empty() && !ports.iter().any(|(range, p)| in_use(*range, p)) {
+                unused.push(AppRule {
+                    zone: zone.name.clone(),
+                    kind: RuleKind::Service(name.clone()),
+                    allows: true,
+                });
+            }
+        }
+    }
+    unused
+}
+
+/// Group listening sockets (as returned by
+/// [`NetworkExposure::scan_sockets`]) by the executable `exe_of` gives for
+/// their process, and attach the rules of `policy`.
+fn group_applications(
+    sockets: &[ListeningEndpoint],
+    exe_of: impl Fn(u32) -> Option<String>,
+    policy: Option<&ZonePolicy>,
+) -> ApplicationView {
+    // Key: executable path, else command name; `None` for unknown owners
+    let mut groups: BTreeMap<Option<String>, Application> = BTreeMap::new();
+    for socket in sockets {
+        let executable = socket.pid.and_then(&exe_of);
+        let key = executable.clone().or_else(|| socket.process_name.clone());
+        let app = groups.entry(key).or_insert_with(|| Application {
+            name: executable
+                .as_deref()
+                .and_then(|path| path.rsplit('/').next())
+                .map(str::to_string)
+                .or_else(|| socket.process_name.clone())
+                .unwrap_or_else(|| "Unknown process".to_string()),
+            executable,
+            pids: Vec::new(),
+            listeners: Vec::new(),
+        });
+        if let Some(pid) = socket.pid {
+            if !app.pids.contains(&pid) {
+                app.pids.push(pid);
+            }
+        }
+
+        match app
+            .listeners
+            .iter_mut()
+            .find(|l| l.port == socket.port && l.protocol == socket.protocol)
+        {
+            Some(listener) => {
+                if !listener.addresses.contains(&socket.local_addr) {
+                    listener.addresses.push(socket.local_addr);
+                }
+            }
+            None => {
+                let protocol = socket.protocol.as_str().to_lowercase();
+                let (allowed_in, rules) = match policy {
+                    Some(policy) => {
+                        let mut allowed_in: Vec<String> = policy
+                            .zones
+                            .keys()
+                            .filter(|zone| policy.allows(zone, socket.port, &protocol))
+                            .cloned()
+                            .collect();
+                        allowed_in.sort();
+                        (allowed_in, rules_for(policy, socket.port, &protocol))
+                    }
+                    None => (Vec::new(), Vec::new()),
+                };
+                app.listeners.push(AppListener {
+                    port: socket.port,
+                    protocol: socket.protocol,
+                    addresses: vec![socket.local_addr],
+                    allowed_in,
+                    rules,
+                });
+            }
+        }
+    }
+
+    let mut applications: Vec<Application> = groups.into_values().collect();
+    for app in &mut applications {
+        app.pids.sort_unstable();
+    }
+    applications.sort_by(|a, b| {
+        (a.is_unknown(), a.name.to_lowercase()).cmp(&(b.is_unknown(), b.name.to_lowercase()))
+    });
+
+    let listening: BTreeSet<(u16, String)> = sockets
+        .iter()
+        .map(|s| (s.port, s.protocol.as_str().to_lowercase()))
+        .collect();
+    ApplicationView {
+        applications,
+        unused: policy
+            .map(|policy| unused_rules(policy, &listening))
+            .unwrap_or_default(),
+        firewall: policy.is_some(),
+    }
+}
+
+/// The executable of a process, when the user may read it.
+fn process_executable(pid: u32) -> Option<String> {
+    let path = fs::read_link(format!("/proc/{}/exe", pid)).ok()?;
+    let path = path.to_string_lossy();
+    // Replaced on disk since the process started, as after an upgrade
+    Some(path.strip_suffix(" (deleted)").unwrap_or(&path).to_string())
+}
+
+/// Scan listening sockets and the firewall zones and group them by
+/// application. Without firewalld only the sockets are listed. Reads procfs
+/// and D-Bus, so call it off the main thread.
+pub fn query_applications() -> Result<ApplicationView> {
+    let sockets = NetworkExposure::new().scan_sockets()?;
+    let policy = ZonePolicy::query()
+        .map_err(|e| tracing::debug!("No zone policy for applications: {}", e))
+        .ok();
+    Ok(group_applications(
+        &sockets,
+        process_executable,
+        policy.as_ref(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Service, Zone};
+    use std::collections::HashMap;
+
+    fn socket(addr: &str, port: u16, pid: Option<u32>, process: Option<&str>) -> ListeningEndpoint {
+        ListeningEndpoint {
+            pid,
+            process_name: process.map(str::to_string),
+            ..ListeningEndpoint::for_test(port, Protocol::Tcp, addr)
+        }
+    }
+
+    fn policy() -> ZonePolicy {
+        let mut http = Service::new("http");
+        http.ports = vec![("80".to_string(), "tcp".to_string())];
+        let mut ssh = Service::new("ssh");
+        ssh.ports = vec![("22".to_string(), "tcp".to_string())];
+        let public = Zone {
+            name: "public".to_string(),
+            target: "default".to_string(),
+            services: vec!["http".to_string(), "ssh".to_string()],
+            ports: vec!["443/tcp".to_string(), "9000-9100/tcp".to_string()],
+            ..Default::default()
+        };
+        ZonePolicy {
+            default_zone: "public".to_string(),
+            bindings: HashMap::new(),
+            zones: HashMap::from([("public".to_string(), public)]),
+            services: HashMap::from([("http".to_string(), http), ("ssh".to_string(), ssh)]),
+        }
+    }
+
+    #[test]
+    fn test_group_applications() {
+        let sockets = [
+            socket("0.0.0.0", 80, Some(100), Some("nginx")),
+            socket("::", 80, Some(101), Some("nginx")),
+            socket("0.0.0.0", 443, Some(100), Some("nginx")),
+            socket("127.0.0.1", 5432, Some(200), Some("postgres")),
+            socket("0.0.0.0", 631, None, None),
+        ];
+        let exe_of = |pid: u32| (pid < 200).then(|| "/usr/sbin/nginx".to_string());
+        let view = group_applications(&sockets, exe_of, Some(&policy()));
+
+        assert_eq!(view.applications.len(), 3);
+        let nginx = &view.applications[0];
+        assert_eq!(nginx.name, "nginx");
+        assert_eq!(nginx.pids, vec![100, 101]);
+        assert_eq!(nginx.listeners.len(), 2);
+        assert_eq!(
+            nginx.summary(true),
+            "ports 80, 443 allowed in public; listening on 0.0.0.0, ::"
+        );
+        assert_eq!(
+            nginx.listeners[0].rules[0].kind,
+            RuleKind::Service("http".to_string())
+        );
+
+        let postgres = &view.applications[1];
+        assert!(!postgres.is_allowed());
+        assert_eq!(
+            postgres.summary(true),
+            "not allowed in any zone; listening on 127.0.0.1"
+        );
+        assert!(view.applications[2].is_unknown());
+
+        // Nothing listens on ssh or 9000-9100
+        assert_eq!(
+            view.unused
+                .iter()
+                .map(AppRule::describe)
+                .collect::<Vec<_>>(),
+            vec!["port 9000-9100/tcp in public", "service ssh in public"]
+        );
+    }
+}
@@ -0,0 +1,287 @@
+// Security Center - Security Assessment
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Host security assessment engine.
+//!
+//! Individual checks live next to the subsystem they inspect and return
+//! [`Finding`]s; this module collects them into one [`Assessment`] that the
+//! UI groups by [`FindingCategory`]. Every finding carries remediation
+//! guidance so the user knows what to do about it.
+//!
+//! The findings are summarised in a 0-100 hardening score: every finding
+//! deducts points according to its severity.
+//!
+//! [`ZONE_SERVICE_RULES`] is the baseline for firewall zones: services that
+//! should not be allowed in a zone of a given trust level, checked per zone
+//! by [`zone_suggestions`].
+
+use serde::{Deserialize, Serialize};
+
+use super::{bootloader, coredump, ct_helpers, firewall_conflicts, privileges};
+
+/// How serious a finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Severity {
+    Info,
+    Low,
+    Medium,
+    High,
+}
+
+impl Severity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Info => "Info",
+            Severity::Low => "Low",
+            Severity::Medium => "Medium",
+            Severity::High => "High",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Severity::Info => "dialog-information-symbolic",
+            Severity::Low => "security-medium-symbolic",
+            Severity::Medium => "dialog-warning-symbolic",
+            Severity::High => "dialog-error-symbolic",
+        }
+    }
+
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            Severity::Info => "dim-label",
+            Severity::Low | Severity::Medium => "warning",
+            Severity::High => "error",
+        }
+    }
+
+    /// Points a finding of this severity deducts from the score.
+    fn penalty(&self) -> u32 {
+        match self {
+            Severity::Info => 0,
+            Severity::Low => 3,
+            Severity::Medium => 8,
+            Severity::High => 15,
+        }
+    }
+}
+
+/// Area a finding belongs to, used for grouping in the UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FindingCategory {
+    Boot,
+    CoreDumps,
+    Firewall,
+    Services,
+}
+
+/// A single assessment result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// Stable identifier, e.g. `boot.cmdline.mitigations`.
+    pub id: String,
+    pub category: FindingCategory,
+    pub severity: Severity,
+    pub title: String,
+    /// What was found and why it matters.
+    pub detail: String,
+    /// How to fix it.
+    pub remediation: String,
+}
+
+/// Collected results of all checks.
+#[derive(Debug, Clone, Default)]
+pub struct Assessment {
+    pub findings: Vec<Finding>,
+}
+
+impl Assessment {
+    /// Run every check. Performs file reads only, so call it off the main thread.
+    pub fn run() -> Self {
+        let mut findings = bootloader::check_boot();
+        findings.extend(coredump::check_coredump());
+        findings.extend(ct_helpers::check_helpers());
+        findings.extend(firewall_conflicts::check_firewall_conflicts());
+        findings.extend(privileges::check_listener_privileges());
+        findings.sort_by(|a, b| b.severity.cmp(&a.severity).then(a.id.cmp(&b.id)));
+        Self { findings }
+    }
+
+    /// Findings of one category, most severe first.
+    pub fn in_category(&self, category: FindingCategory) -> Vec<&Finding> {
+        self.findings
+            .iter()
+            .filter(|f| f.category == category)
+            .collect()
+    }
+
+    /// Hardening score from 0 (many serious findings) to 100 (none).
+    pub fn score(&self) -> u32 {
+        let penalty: u32 = self.findings.iter().map(|f| f.severity.penalty()).sum();
+        100u32.saturating_sub(penalty)
+    }
+}
+
+/// Zones for networks that are not trusted at all.
+const UNTRUSTED_ZONES: &[&str] = &["public", "external", "dmz", "block", "drop"];
+/// Every zone except `trusted`, which accepts everything anyway.
+const ALL_BUT_TRUSTED: &[&str] = &[
+    "public", "external", "dmz", "block", "drop", "work", "home", "internal",
+];
+
+/// A service that should not be allowed in some zones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZoneServiceRule {
+    /// firewalld service name.
+    pub service: &'static str,
+    /// Zones the service should be removed from.
+    pub zones: &'static [&'static str],
+    pub severity: Severity,
+    /// Why the service is risky there.
+    pub reason: &'static str,
+}
+
+/// Baseline of services to keep out of zones, most severe first.
+pub const ZONE_SERVICE_RULES: &[ZoneServiceRule] = &[
+    ZoneServiceRule {
+        service: "telnet",
+        zones: ALL_BUT_TRUSTED,
+        severity: Severity::High,
+        reason: "Telnet sends passwords in clear text",
+    },
+    ZoneServiceRule {
+        service: "samba",
+        zones: UNTRUSTED_ZONES,
+        severity: Severity::High,
+        reason: "Shares files with everyone on the network",
+    },
+    ZoneServiceRule {
+        service: "nfs",
+        zones: UNTRUSTED_ZONES,
+        severity: Severity::High,
+        reason: "Exports file systems to everyone on the network",
+    },
+    ZoneServiceRule {
+        service: "nfs3",
+        zones: UNTRUSTED_ZONES,
+        severity: Severity::High,
+        reason: "Exports file systems to everyone on the network",
+    },
+    ZoneServiceRule {
+        service: "rdp",
+        zones: UNTRUSTED_ZONES,
+        severity: Severity::High,
+        reason: "Remote desktop is reachable from the network",
+    },
+    ZoneServiceRule {
+        service: "vnc-server",
+        zones: UNTRUSTED_ZONES,
+        severity: Severity::High,
+        reason: "Remote desktop is reachable from the network",
+    },
+    ZoneServiceRule {
+        service: "mountd",
+        zones: UNTRUSTED_ZONES,
+        severity: Severity::Medium,
+        reason: "Lets other hosts mount NFS exports",
+    },
+    ZoneServiceRule {
+        service: "rpc-bind",
+        zones: UNTRUSTED_ZONES,
+        severity: Severity::Medium,
+        reason: "Lists the RPC services this machine offers",
+    },
+    ZoneServiceRule {
+        service: "ftp",
+        zones: UNTRUSTED_ZONES,
+        severity: Severity::Medium,
+        reason: "FTP sends passwords in clear text",
+    },
+    ZoneServiceRule {
+        service: "tftp",
+        zones: ALL_BUT_TRUSTED,
+        severity: Severity::Medium,
+        reason: "TFTP serves files without authentication",
+    },
+    ZoneServiceRule {
+        service: "cockpit",
+        zones: UNTRUSTED_ZONES,
+        severity: Severity::Medium,
+        reason: "The web administration console is reachable from the network",
+    },
+    ZoneServiceRule {
+        service: "samba-client",
+        zones: UNTRUSTED_ZONES,
+        severity: Severity::Low,
+        reason: "Accepts Windows network browsing replies from any host",
+    },
+];
+
+/// Rules broken by the services allowed in `zone`, most severe first.
+pub fn zone_suggestions(zone: &str, services: &[String]) -> Vec<&'static ZoneServiceRule> {
+    ZONE_SERVICE_RULES
+        .iter()
+        .filter(|rule| rule.zones.contains(&zone))
+        .filter(|rule| services.iter().any(|s| s == rule.service))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(severity: Severity) -> Finding {
+        Finding {
+            id: "test".to_string(),
+            category: FindingCategory::Boot,
+            severity,
+            title: String::new(),
+            detail: String::new(),
+            remediation: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_score() {
+        assert_eq!(Assessment::default().score(), 100);
+        let assessment = Assessment {
+            findings: vec![
+                finding(Severity::High),
+                finding(Severity::Low),
+                finding(Severity::Info),
+            ],
+        };
+        assert_eq!(assessment.score(), 82);
+        let assessment = Assessment {
+            findings: vec![finding(Severity::High); 10],
+        };
+        assert_eq!(assessment.score(), 0);
+    }
+
+    #[test]
+    fn test_zone_suggestions() {
+        let services: Vec<String> = ["ssh", "samba-client", "samba", "telnet"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let public: Vec<&str> = zone_suggestions("public", &services)
+            .iter()
+            .map(|r| r.service)
+            .collect();
+        assert_eq!(public, vec!["telnet", "samba", "samba-client"]);
+        let home: Vec<&str> = zone_suggestions("home", &services)
+            .iter()
+            .map(|r| r.service)
+            .collect();
+        assert_eq!(home, vec!["telnet"]);
+        assert!(zone_suggestions("trusted", &services).is_empty());
+    }
+
+    #[test]
+    fn test_zone_rules_sorted_by_severity() {
+        assert!(ZONE_SERVICE_RULES
+            .windows(2)
+            .all(|w| w[0].severity >= w[1].severity));
+    }
+}
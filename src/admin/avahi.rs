@@ -0,0 +1,448 @@
+// Security Center - Avahi / mDNS Introspection
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Avahi (mDNS/DNS-SD) exposure introspection.
+//!
+//! Lists the services this machine advertises on the local network by
+//! browsing through the avahi-daemon D-Bus API and keeping only results
+//! flagged as registered locally. Static service definitions under
+//! `/etc/avahi/services` are read as well, since they are the usual place
+//! administrators (and packages) add advertisements.
+//!
+//! The result is correlated with the firewalld `mdns` service: advertising is
+//! only visible to other devices when UDP 5353 is allowed in an active zone.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use zbus::blocking::{Connection, MessageIterator};
+use zbus::zvariant::OwnedObjectPath;
+
+const AVAHI_BUS: &str = "org.freedesktop.Avahi";
+const AVAHI_SERVER_INTERFACE: &str = "org.freedesktop.Avahi.Server";
+const STATIC_SERVICES_DIR: &str = "/etc/avahi/services";
+
+/// `AVAHI_IF_UNSPEC` / `AVAHI_PROTO_UNSPEC`.
+const UNSPEC: i32 = -1;
+/// `AVAHI_LOOKUP_RESULT_LOCAL`: the entry was registered on this host.
+const LOOKUP_RESULT_LOCAL: u32 = 8;
+/// `AVAHI_SERVER_RUNNING`.
+const SERVER_RUNNING: i32 = 2;
+
+/// systemd units making up the Avahi daemon; the socket goes first so it
+/// cannot re-activate the service while it is being stopped.
+pub const AVAHI_UNITS: [&str; 2] = ["avahi-daemon.socket", "avahi-daemon.service"];
+
+/// A service this machine advertises over mDNS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdvertisedService {
+    /// Instance name as shown to browsing clients.
+    pub name: String,
+    /// DNS-SD type, e.g. `_ssh._tcp`.
+    pub service_type: String,
+    /// Advertised port, when known (static definitions only).
+    pub port: Option<u16>,
+    /// Static definition file this advertisement comes from, if any.
+    pub static_file: Option<PathBuf>,
+}
+
+impl AdvertisedService {
+    /// Human-readable label for the service type.
+    pub fn type_label(&self) -> &str {
+        service_type_label(&self.service_type).unwrap_or(&self.service_type)
+    }
+
+    /// systemd unit that publishes this advertisement, when well known.
+    pub fn publisher_unit(&self) -> Option<&'static str> {
+        publisher_unit(&self.service_type)
+    }
+}
+
+/// What the combination of Avahi and the firewall means for exposure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MdnsExposure {
+    /// Avahi advertises and mDNS is allowed: other devices can discover us.
+    Advertising { zones: Vec<String> },
+    /// Avahi advertises but the firewall drops mDNS in every active zone.
+    BlockedByFirewall,
+    /// Nothing advertises, yet mDNS is still allowed through the firewall.
+    OpenButIdle { zones: Vec<String> },
+    /// Avahi is not running and mDNS is blocked.
+    Quiet,
+}
+
+impl MdnsExposure {
+    /// Combine daemon state with the active zones that allow `mdns`.
+    pub fn assess(daemon_running: bool, mdns_zones: &[String]) -> Self {
+        match (daemon_running, mdns_zones.is_empty()) {
+            (true, false) => Self::Advertising {
+                zones: mdns_zones.to_vec(),
+            },
+            (true, true) => Self::BlockedByFirewall,
+            (false, false) => Self::OpenButIdle {
+                zones: mdns_zones.to_vec(),
+            },
+            (false, true) => Self::Quiet,
+        }
+    }
+}
+
+/// Snapshot of Avahi state.
+#[derive(Debug, Clone, Default)]
+pub struct AvahiStatus {
+    /// avahi-daemon answers on the bus and reports the running state.
+    pub daemon_running: bool,
+    /// Fully qualified mDNS host name (`host.local`).
+    pub host_name: Option<String>,
+    /// Services advertised by this host.
+    pub services: Vec<AdvertisedService>,
+}
+
+/// Query avahi-daemon for locally advertised services.
+///
+/// Browsing is asynchronous on the Avahi side, so results are collected for
+/// at most `timeout` (Avahi signals `AllForNow` well before that on a normal
+/// network).
+pub fn query_avahi(timeout: Duration) -> AvahiStatus {
+    let mut status = AvahiStatus {
+        services: read_static_services(Path::new(STATIC_SERVICES_DIR)),
+        ..Default::default()
+    };
+
+    // A dedicated connection: closing it at the end also ends the signal
+    // reader thread below.
+    let Ok(conn) = Connection::system() else {
+        return status;
+    };
+
+    let state: Result<i32> = server_call(&conn, "GetState", &());
+    status.daemon_running = matches!(state, Ok(SERVER_RUNNING));
+    if !status.daemon_running {
+        let _ = conn.close();
+        return status;
+    }
+    status.host_name = server_call(&conn, "GetHostNameFqdn", &()).ok();
+
+    match browse_local(&conn, timeout) {
+        Ok(found) => {
+            for (name, service_type) in found {
+                let known = status
+                    .services
+                    .iter()
+                    .any(|s| s.service_type == service_type && s.name == name);
+                if !known {
+                    status.services.push(AdvertisedService {
+                        name,
+                        service_type,
+                        port: None,
+                        static_file: None,
+                    });
+                }
+            }
+        }
+        Err(e) => tracing::warn!("Avahi browse failed: {}", e),
+    }
+    let _ = conn.close();
+
+    status.services.sort_by(|a, b| {
+        a.service_type
+            .cmp(&b.service_type)
+            .then(a.name.cmp(&b.name))
+    });
+    status
+}
+
+fn server_call<B, R>(conn: &Connection, method: &str, body: &B) -> Result<R>
+where
+    B: serde::ser::Serialize + zbus::zvariant::DynamicType,
+    R: for<'d> zbus::zvariant::DynamicDeserialize<'d>,
+{
+    Ok(conn
+        .call_method(
+            Some(AVAHI_BUS),
+            "/",
+            Some(AVAHI_SERVER_INTERFACE),
+            method,
+            body,
+        )?
+        .body()
+        .deserialize()?)
+}
+
+/// Browse every service type on `.local` and return the (name, type) pairs
+/// registered by this host.
+fn browse_local(conn: &Connection, timeout: Duration) -> Result<Vec<(String, String)>> {
+    // Subscribe before creating browsers so no ItemNew signal is missed.
+    let rule = zbus::MatchRule::builder()
+        .msg_type(zbus::message::Type::Signal)
+        .sender(AVAHI_BUS)?
+        .build();
+    let iter = MessageIterator::for_match_rule(rule, conn, None)
+        .context("Cannot subscribe to Avahi signals")?;
+
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for msg in iter.flatten() {
+            if tx.send(msg).is_err() {
+                break;
+            }
+        }
+    });
+
+    let type_browser: OwnedObjectPath = server_call(
+        conn,
+        "ServiceTypeBrowserNew",
+        &(UNSPEC, UNSPEC, "local", 0u32),
+    )?;
+
+    let mut browsers: Vec<OwnedObjectPath> = Vec::new();
+    let mut pending: HashSet<String> = HashSet::from([type_browser.to_string()]);
+    let mut seen_types: HashSet<String> = HashSet::new();
+    let mut found: Vec<(String, String)> = Vec::new();
+    let deadline = Instant::now() + timeout;
+
+    while !pending.is_empty() {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let Ok(msg) = rx.recv_timeout(remaining) else {
+            break;
+        };
+        let header = msg.header();
+        let (Some(path), Some(member)) = (header.path(), header.member()) else {
+            continue;
+        };
+        let path = path.to_string();
+        match member.as_str() {
+            "ItemNew" if path == type_browser.as_str() => {
+                let Ok((_, _, service_type, _, _)) =
+                    msg.body().deserialize::<(i32, i32, String, String, u32)>()
+                else {
+                    continue;
+                };
+                // The type browser reports each type once per interface.
+                if seen_types.insert(service_type.clone()) {
+                    let browser: Result<OwnedObjectPath> = server_call(
+                        conn,
+                        "ServiceBrowserNew",
+                        &(UNSPEC, UNSPEC, service_type.as_str(), "local", 0u32),
+                    );
+                    if let Ok(browser) = browser {
+                        pending.insert(browser.to_string());
+                        browsers.push(browser);
+                    }
+                }
+            }
+            "ItemNew" => {
+                let Ok((_, _, name, service_type, _, flags)) =
+                    msg.body()
+                        .deserialize::<(i32, i32, String, String, String, u32)>()
+                else {
+                    continue;
+                };
+                let entry = (name, service_type);
+                if flags & LOOKUP_RESULT_LOCAL != 0 && !found.contains(&entry) {
+                    found.push(entry);
+                }
+            }
+            "AllForNow" | "Failure" => {
+                pending.remove(&path);
+            }
+            _ => {}
+        }
+    }
+
+    for path in browsers.iter().chain(std::iter::once(&type_browser)) {
+        let iface = if *path == type_browser {
+            "org.freedesktop.Avahi.ServiceTypeBrowser"
+        } else {
+            "org.freedesktop.Avahi.ServiceBrowser"
+        };
+        let _ = conn.call_method(Some(AVAHI_BUS), path.as_str(), Some(iface), "Free", &());
+    }
+
+    Ok(found)
+}
+
+/// Read static service definitions (`*.service` XML files).
+fn read_static_services(dir: &Path) -> Vec<AdvertisedService> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut services = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("service") {
+            continue;
+        }
+        let Ok(xml) = fs::read_to_string(&path) else {
+            continue;
+        };
+        for (name, service_type, port) in parse_static_service(&xml) {
+            services.push(AdvertisedService {
+                name,
+                service_type,
+                port,
+                static_file: Some(path.clone()),
+            });
+        }
+    }
+    services
+}
+
+/// Parse an Avahi static service group into (name, type, port) triples.
+///
+/// The format is a tiny fixed XML schema; a tag scanner is sufficient and
+/// avoids pulling in an XML parser.
+fn parse_static_service(xml: &str) -> Vec<(String, String, Option<u16>)> {
+    let group_name = tag_text(xml, "name")
+        .map(|n| n.replace("%h", "<hostname>"))
+        .unwrap_or_default();
+    let mut results = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find("<service>").or_else(|| rest.find("<service ")) {
+        let after = &rest[start..];
+        let Some(end) = after.find("</service>") else {
+            break;
+        };
+        let block = &after[..end];
+        if let Some(service_type) = tag_text(block, "type") {
+            let port = tag_text(block, "port").and_then(|p| p.parse().ok());
+            results.push((group_name.clone(), service_type, port));
+        }
+        rest = &after[end + "</service>".len()..];
+    }
+    results
+}
+
+/// Text content of the first `<tag ...>text</tag>` occurrence.
+fn tag_text(xml: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}", tag);
+    let close = format!("</{}>", tag);
+    let mut search = xml;
+    loop {
+        let start = search.find(&open)?;
+        let after = &search[start + open.len()..];
+        // Make sure we matched the whole tag name (`<name>` not `<names>`).
+        if after.starts_with('>') || after.starts_with(' ') {
+            let body_start = after.find('>')? + 1;
+            let body = &after[body_start..];
+            let end = body.find(&close)?;
+            return Some(body[..end].trim().to_string());
+        }
+        search = after;
+    }
+}
+
+/// Friendly labels for common DNS-SD service types.
+fn service_type_label(service_type: &str) -> Option<&'static str> {
+    Some(match service_type {
+        "_ssh._tcp" => "SSH remote login",
+        "_sftp-ssh._tcp" => "SFTP file transfer",
+        "_workstation._tcp" => "Workstation presence",
+        "_device-info._tcp" => "Device information",
+        "_ipp._tcp" | "_ipps._tcp" | "_printer._tcp" => "Printer sharing",
+        "_smb._tcp" => "Windows file sharing (SMB)",
+        "_afpovertcp._tcp" => "Apple file sharing (AFP)",
+        "_nfs._tcp" => "NFS file sharing",
+        "_webdav._tcp" | "_webdavs._tcp" => "WebDAV file sharing",
+        "_http._tcp" | "_https._tcp" => "Web server",
+        "_rfb._tcp" => "Screen sharing (VNC)",
+        "_rdp._tcp" => "Remote desktop (RDP)",
+        "_daap._tcp" => "Music library sharing",
+        "_raop._tcp" | "_airplay._tcp" => "AirPlay receiver",
+        "_googlecast._tcp" => "Chromecast receiver",
+        "_spotify-connect._tcp" => "Spotify Connect",
+        _ => return None,
+    })
+}
+
+/// Well-known systemd units that publish a given service type.
+fn publisher_unit(service_type: &str) -> Option<&'static str> {
+    Some(match service_type {
+        "_ipp._tcp" | "_ipps._tcp" | "_printer._tcp" => "cups.service",
+        "_smb._tcp" => "smb.service",
+        "_nfs._tcp" => "nfs-server.service",
+        "_afpovertcp._tcp" => "netatalk.service",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SSH_SERVICE: &str = r#"<?xml version="1.0" standalone='no'?>
+<!DOCTYPE service-group SYSTEM "avahi-service.dtd">
+<service-group>
+  <name replace-wildcards="yes">%h</name>
+  <service>
+    <type>_ssh._tcp</type>
+    <port>22</port>
+  </service>
+  <service protocol="ipv6">
+    <type>_sftp-ssh._tcp</type>
+    <port>22</port>
+  </service>
+</service-group>
+"#;
+
+    #[test]
+    fn test_parse_static_service() {
+        let parsed = parse_static_service(SSH_SERVICE);
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].0, "<hostname>");
+        assert_eq!(parsed[0].1, "_ssh._tcp");
+        assert_eq!(parsed[0].2, Some(22));
+        assert_eq!(parsed[1].1, "_sftp-ssh._tcp");
+    }
+
+    #[test]
+    fn test_parse_static_service_garbage() {
+        assert!(parse_static_service("not xml at all").is_empty());
+        assert!(parse_static_service("<service><port>1</port></service>").is_empty());
+    }
+
+    #[test]
+    fn test_tag_text_exact_name() {
+        assert_eq!(
+            tag_text("<names>x</names><name>y</name>", "name").as_deref(),
+            Some("y")
+        );
+    }
+
+    #[test]
+    fn test_mdns_exposure_assess() {
+        let zones = vec!["home".to_string()];
+        assert_eq!(
+            MdnsExposure::assess(true, &zones),
+            MdnsExposure::Advertising {
+                zones: zones.clone()
+            }
+        );
+        assert_eq!(
+            MdnsExposure::assess(true, &[]),
+            MdnsExposure::BlockedByFirewall
+        );
+        assert_eq!(
+            MdnsExposure::assess(false, &zones),
+            MdnsExposure::OpenButIdle { zones }
+        );
+        assert_eq!(MdnsExposure::assess(false, &[]), MdnsExposure::Quiet);
+    }
+
+    #[test]
+    fn test_publisher_unit() {
+        let printer = AdvertisedService {
+            name: "Printer".into(),
+            service_type: "_ipp._tcp".into(),
+            port: None,
+            static_file: None,
+        };
+        assert_eq!(printer.publisher_unit(), Some("cups.service"));
+        assert_eq!(printer.type_label(), "Printer sharing");
+    }
+}
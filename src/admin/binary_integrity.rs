@@ -0,0 +1,348 @@
+// Security Center - Binary Integrity
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Package ownership and checksums of the programs listening on the network.
+//!
+//! A network service whose executable belongs to no package, or differs from
+//! the file its package installed, may have been tampered with: a backdoored
+//! `sshd` still listens on port 22. For each listening process,
+//! [`verify_listening_binaries`] finds the package owning the executable and
+//! has the package manager compare the file with its recorded digest
+//! (`rpm -V` or `dpkg --verify`).
+//!
+//! Most network services run as root, and neither the sockets nor
+//! `/proc/<pid>/exe` of another user's process can be read, so the scan and
+//! the check run in the privileged helper (see `crate::helper`). A binary
+//! replaced on disk since the process started is reported on its own: it is
+//! usually an upgrade awaiting a restart, but the running code can no longer
+//! be checked.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::network::{NetworkExposure, Protocol};
+use crate::helper::{run_privileged, HelperOp};
+
+const RPM: &str = "rpm";
+const DPKG: &str = "dpkg";
+/// rpm database locations, current and legacy.
+const RPM_DBS: &[&str] = &["/usr/lib/sysimage/rpm", "/var/lib/rpm"];
+const DPKG_STATUS: &str = "/var/lib/dpkg/status";
+/// Suffix the kernel adds to `/proc/<pid>/exe` once the file is gone.
+const DELETED_SUFFIX: &str = " (deleted)";
+
+/// How an executable compares with its package.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IntegrityStatus {
+    /// Owned by a package and unchanged.
+    Verified,
+    /// Owned by a package but its contents differ, with the package
+    /// manager's verification flags (such as `S.5....T.`).
+    Modified(String),
+    /// No installed package owns the file.
+    Unpackaged,
+    /// The file was deleted or replaced after the process started.
+    Replaced,
+    /// Could not be checked, with the reason.
+    Unknown(String),
+}
+
+impl IntegrityStatus {
+    /// Whether the status is a possible sign of compromise.
+    pub fn is_suspicious(&self) -> bool {
+        matches!(self, Self::Modified(_) | Self::Unpackaged)
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Verified => "Matches its package".to_string(),
+            Self::Modified(flags) => format!("Differs from its package ({})", flags),
+            Self::Unpackaged => "Not installed by any package".to_string(),
+            Self::Replaced => "Replaced on disk since it started".to_string(),
+            Self::Unknown(reason) => format!("Not checked: {}", reason),
+        }
+    }
+}
+
+/// The check of one listening process's executable.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BinaryIntegrity {
+    pub pid: u32,
+    pub process_name: Option<String>,
+    /// Ports the process listens on.
+    pub listeners: Vec<(u16, Protocol)>,
+    /// Executable path, empty when it could not be read.
+    pub executable: String,
+    pub package: Option<String>,
+    pub status: IntegrityStatus,
+}
+
+/// The package manager of the system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PackageManager {
+    Rpm,
+    Dpkg,
+}
+
+impl PackageManager {
+    fn detect() -> Option<Self> {
+        if RPM_DBS.iter().any(|db| Path::new(db).is_dir()) {
+            Some(Self::Rpm)
+        } else if Path::new(DPKG_STATUS).exists() {
+            Some(Self::Dpkg)
+        } else {
+            None
+        }
+    }
+
+    /// The package owning `path`, `None` when there is none.
+    fn owner(&self, path: &str) -> Result<Option<String>> {
+        match self {
+            Self::Rpm => {
+                let output = Command::new(RPM)
+                    .args(["-qf", "--queryformat", "%{NAME}\\n", path])
+                    .output()
+                    .context("Failed to run rpm")?;
+                // Exit status 1 with "is not owned by any package"
+                if !output.status.success() {
+                    return Ok(None);
+                }
+                Ok(String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .next()
+                    .map(str::to_string))
+            }
+            Self::Dpkg => {
+                for candidate in dpkg_paths(path) {
+                    let output = Command::new(DPKG)
+                        .args(["-S", &candidate])
+                        .output()
+                        .context("Failed to run dpkg")?;
+                    if output.status.success() {
+                        let stdout = String::from_utf8_lossy(&output.stdout);
+                        if let Some(package) = parse_dpkg_owner(&stdout, &candidate) {
+                            return Ok(Some(package));
+                        }
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    /// Verification flags of `path` in `package`, `None` when its contents
+    /// match.
+    fn verify(&self, package: &str, path: &str) -> Result<Option<String>> {
+        let output = match self {
+            Self::Rpm => Command::new(RPM)
+                .args(["-V", "--nomtime", package])
+                .output()
+                .context("Failed to run rpm")?,
+            Self::Dpkg => Command::new(DPKG)
+                .args(["--verify", package])
+                .output()
+                .context("Failed to run dpkg")?,
+        };
+        // Both exit non-zero when anything in the package differs
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(match self {
+            Self::Rpm => parse_rpm_verify(&stdout, path),
+            Self::Dpkg => dpkg_paths(path)
+                .iter()
+                .find_map(|candidate| parse_dpkg_verify(&stdout, candidate)),
+        })
+    }
+}
+
+/// The paths dpkg may know an executable by: on merged-/usr systems the
+/// kernel reports `/usr/sbin/x` while the package lists `/sbin/x`.
+fn dpkg_paths(path: &str) -> Vec<String> {
+    let mut paths = vec![path.to_string()];
+    if let Some(rest) = path.strip_prefix("/usr") {
+        if ["/bin/", "/sbin/", "/lib/", "/lib64/"]
+            .iter()
+            .any(|dir| rest.starts_with(dir))
+        {
+            paths.push(rest.to_string());
+        }
+    }
+    paths
+}
+
+/// The first package in `dpkg -S` output listing exactly `path`, as in
+/// "openssh-server: /usr/sbin/sshd" or "a, b: /path". Diversions are
+/// skipped.
+fn parse_dpkg_owner(output: &str, path: &str) -> Option<String> {
+    output
+        .lines()
+        .filter(|line| !line.starts_with("diversion by"))
+        .find_map(|line| {
+            let (packages, listed) = line.split_once(": ")?;
+            if listed != path {
+                return None;
+            }
+            let package = packages.split(", ").next()?.trim();
+            // Drop the architecture qualifier ("libc6:amd64")
+            Some(package.split(':').next().unwrap_or(package).to_string())
+        })
+}
+
+/// Flags of `path` in `rpm -V` output when its size or digest changed.
+/// Lines look like "S.5....T.  c /etc/file" or "missing     /usr/bin/x".
+fn parse_rpm_verify(output: &str, path: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let flags = line.split_whitespace().next()?;
+        if line.split_whitespace().last()? != path {
+            return None;
+        }
+        if flags == "missing" {
+            return Some(flags.to_string());
+        }
+        (flags.contains('S') || flags.contains('5')).then(|| flags.to_string())
+    })
+}
+
+/// Flags of `path` in `dpkg --verify` output when its digest changed. Lines
+/// look like "??5??????   /usr/sbin/sshd" or "??5?????? c /etc/file".
+fn parse_dpkg_verify(output: &str, path: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let flags = line.split_whitespace().next()?;
+        if line.split_whitespace().last()? != path {
+            return None;
+        }
+        (flags.len() == 9 && flags.as_bytes()[2] == b'5').then(|| flags.to_string())
+    })
+}
+
+/// Find the listening processes and check their executables. Runs as root
+/// inside the helper process; the result is JSON.
+pub fn verify_listeners() -> Result<String> {
+    let mut scanner = NetworkExposure::new();
+    let mut processes: BTreeMap<u32, BinaryIntegrity> = BTreeMap::new();
+    for endpoint in scanner.scan_sockets()? {
+        let Some(pid) = endpoint.pid else {
+            continue;
+        };
+        let binary = processes.entry(pid).or_insert_with(|| BinaryIntegrity {
+            pid,
+            process_name: endpoint.process_name.clone(),
+            listeners: Vec::new(),
+            executable: String::new(),
+            package: None,
+            status: IntegrityStatus::Unknown("the process has exited".to_string()),
+        });
+        let listener = (endpoint.port, endpoint.protocol);
+        if !binary.listeners.contains(&listener) {
+            binary.listeners.push(listener);
+        }
+    }
+
+    let manager = PackageManager::detect();
+    // Several processes often run the same executable
+    let mut checked: HashMap<String, (Option<String>, IntegrityStatus)> = HashMap::new();
+    for binary in processes.values_mut() {
+        let Ok(link) = fs::read_link(format!("/proc/{}/exe", binary.pid)) else {
+            continue;
+        };
+        let link = link.to_string_lossy().into_owned();
+        if let Some(path) = link.strip_suffix(DELETED_SUFFIX) {
+            binary.executable = path.to_string();
+            binary.status = IntegrityStatus::Replaced;
+            continue;
+        }
+        let (package, status) = checked
+            .entry(link.clone())
+            .or_insert_with(|| check_executable(manager, &link))
+            .clone();
+        binary.executable = link;
+        binary.package = package;
+        binary.status = status;
+    }
+    let results: Vec<BinaryIntegrity> = processes.into_values().collect();
+    Ok(serde_json::to_string(&results)?)
+}
+
+fn check_executable(
+    manager: Option<PackageManager>,
+    path: &str,
+) -> (Option<String>, IntegrityStatus) {
+    let Some(manager) = manager else {
+        return (
+            None,
+            IntegrityStatus::Unknown("no rpm or dpkg database".to_string()),
+        );
+    };
+    let package = match manager.owner(path) {
+        Ok(Some(package)) => package,
+        Ok(None) => return (None, IntegrityStatus::Unpackaged),
+        Err(e) => return (None, IntegrityStatus::Unknown(e.to_string())),
+    };
+    let status = match manager.verify(&package, path) {
+        Ok(None) => IntegrityStatus::Verified,
+        Ok(Some(flags)) => IntegrityStatus::Modified(flags),
+        Err(e) => IntegrityStatus::Unknown(e.to_string()),
+    };
+    (Some(package), status)
+}
+
+/// Check the executables of the listening processes in the privileged
+/// helper. Takes a few seconds per package.
+pub fn verify_listening_binaries() -> Result<Vec<BinaryIntegrity>> {
+    let json = run_privileged(&HelperOp::VerifyListeners)?;
+    serde_json::from_str(&json).context("Invalid binary verification output")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dpkg_owner() {
+        assert_eq!(
+            parse_dpkg_owner("openssh-server: /usr/sbin/sshd\n", "/usr/sbin/sshd"),
+            Some("openssh-server".to_string())
+        );
+        assert_eq!(
+            parse_dpkg_owner(
+                "diversion by dash from: /bin/sh\nlibc-bin:amd64, libc6: /sbin/ldconfig\n",
+                "/sbin/ldconfig"
+            ),
+            Some("libc-bin".to_string())
+        );
+        // `dpkg -S` matches patterns, so other paths can be listed
+        assert_eq!(
+            parse_dpkg_owner("nginx-core: /usr/sbin/nginx-debug\n", "/usr/sbin/nginx"),
+            None
+        );
+        assert_eq!(
+            dpkg_paths("/usr/sbin/sshd"),
+            vec!["/usr/sbin/sshd", "/sbin/sshd"]
+        );
+        assert_eq!(dpkg_paths("/usr/local/bin/x"), vec!["/usr/local/bin/x"]);
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        let rpm = "S.5....T.  c /etc/ssh/sshd_config\n.M.......    /usr/sbin/sshd\n";
+        assert_eq!(parse_rpm_verify(rpm, "/usr/sbin/sshd"), None);
+        let rpm = "S.5......    /usr/sbin/sshd\n";
+        assert_eq!(
+            parse_rpm_verify(rpm, "/usr/sbin/sshd"),
+            Some("S.5......".to_string())
+        );
+        assert_eq!(parse_rpm_verify(rpm, "/usr/sbin/ssh"), None);
+
+        let dpkg = "??5?????? c /etc/nginx/nginx.conf\n??5??????   /usr/sbin/nginx\n";
+        assert_eq!(
+            parse_dpkg_verify(dpkg, "/usr/sbin/nginx"),
+            Some("??5??????".to_string())
+        );
+        assert_eq!(parse_dpkg_verify(dpkg, "/etc/nginx/mime.types"), None);
+    }
+}
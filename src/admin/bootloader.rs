@@ -0,0 +1,338 @@
+// Security Center - Bootloader and Kernel Command Line Audit
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Boot chain checks for the assessment engine.
+//!
+//! - Whether GRUB requires a password before boot entries can be edited, or
+//!   whether the systemd-boot editor is enabled. Without this, anyone at the
+//!   console can append `init=/bin/sh` and get a root shell.
+//! - Whether the running kernel was booted with parameters that switch off
+//!   security features (`selinux=0`, `mitigations=off`, ...).
+//!
+//! Bootloader files are often readable by root only; in that case an
+//! informational finding says the check could not be completed.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use super::assessment::{Finding, FindingCategory, Severity};
+
+const KERNEL_CMDLINE: &str = "/proc/cmdline";
+
+const GRUB_CONFIGS: [&str; 2] = ["/boot/grub2/grub.cfg", "/boot/grub/grub.cfg"];
+const GRUB_USER_CONFIGS: [&str; 2] = ["/boot/grub2/user.cfg", "/boot/grub/user.cfg"];
+const LOADER_CONFIGS: [&str; 3] = [
+    "/boot/loader/loader.conf",
+    "/efi/loader/loader.conf",
+    "/boot/efi/loader/loader.conf",
+];
+
+/// Kernel parameters that weaken security: (parameter, severity, title, why).
+const RISKY_PARAMS: &[(&str, Severity, &str, &str)] = &[
+    (
+        "selinux=0",
+        Severity::High,
+        "SELinux disabled at boot",
+        "Mandatory access control is switched off for the whole system.",
+    ),
+    (
+        "enforcing=0",
+        Severity::Medium,
+        "SELinux forced into permissive mode",
+        "Policy violations are logged but not blocked.",
+    ),
+    (
+        "apparmor=0",
+        Severity::High,
+        "AppArmor disabled at boot",
+        "Application confinement profiles are not loaded.",
+    ),
+    (
+        "mitigations=off",
+        Severity::High,
+        "CPU vulnerability mitigations disabled",
+        "Spectre, Meltdown and related side-channel attacks are not mitigated.",
+    ),
+    (
+        "nospectre_v1",
+        Severity::Medium,
+        "Spectre v1 mitigation disabled",
+        "Bounds-check bypass attacks are not mitigated.",
+    ),
+    (
+        "nospectre_v2",
+        Severity::Medium,
+        "Spectre v2 mitigation disabled",
+        "Branch target injection attacks are not mitigated.",
+    ),
+    (
+        "spectre_v2=off",
+        Severity::Medium,
+        "Spectre v2 mitigation disabled",
+        "Branch target injection attacks are not mitigated.",
+    ),
+    (
+        "spec_store_bypass_disable=off",
+        Severity::Medium,
+        "Speculative Store Bypass mitigation disabled",
+        "Spectre v4 attacks are not mitigated.",
+    ),
+    (
+        "pti=off",
+        Severity::Medium,
+        "Kernel page table isolation disabled",
+        "Meltdown attacks are not mitigated on affected CPUs.",
+    ),
+    (
+        "nopti",
+        Severity::Medium,
+        "Kernel page table isolation disabled",
+        "Meltdown attacks are not mitigated on affected CPUs.",
+    ),
+    (
+        "mds=off",
+        Severity::Medium,
+        "MDS mitigation disabled",
+        "Microarchitectural data sampling attacks are not mitigated.",
+    ),
+    (
+        "l1tf=off",
+        Severity::Medium,
+        "L1TF mitigation disabled",
+        "L1 Terminal Fault attacks are not mitigated.",
+    ),
+    (
+        "nokaslr",
+        Severity::Medium,
+        "Kernel address space randomization disabled",
+        "Kernel memory addresses are predictable, which makes exploits easier.",
+    ),
+    (
+        "init=/bin/sh",
+        Severity::High,
+        "Boot goes straight to a root shell",
+        "The normal init system and login are bypassed.",
+    ),
+    (
+        "init=/bin/bash",
+        Severity::High,
+        "Boot goes straight to a root shell",
+        "The normal init system and login are bypassed.",
+    ),
+    (
+        "rd.break",
+        Severity::High,
+        "Boot stops in an initramfs root shell",
+        "The boot is interrupted with an unauthenticated root shell.",
+    ),
+    (
+        "audit=0",
+        Severity::Low,
+        "Kernel auditing disabled",
+        "Security-relevant events are not recorded by the audit subsystem.",
+    ),
+    (
+        "intel_iommu=off",
+        Severity::Low,
+        "IOMMU disabled",
+        "Devices can access arbitrary memory via DMA (e.g. over Thunderbolt).",
+    ),
+    (
+        "amd_iommu=off",
+        Severity::Low,
+        "IOMMU disabled",
+        "Devices can access arbitrary memory via DMA (e.g. over Thunderbolt).",
+    ),
+];
+
+/// Run all boot chain checks.
+pub(super) fn check_boot() -> Vec<Finding> {
+    let mut findings = Vec::new();
+    if let Some(finding) = check_bootloader_password() {
+        findings.push(finding);
+    }
+    if let Ok(cmdline) = fs::read_to_string(KERNEL_CMDLINE) {
+        findings.extend(risky_cmdline_findings(&cmdline));
+    }
+    findings
+}
+
+/// Result of reading the first existing file of a list.
+enum ConfigRead {
+    Missing,
+    Unreadable,
+    Text(String),
+}
+
+fn read_first(paths: &[&str]) -> ConfigRead {
+    for path in paths {
+        match fs::read_to_string(Path::new(path)) {
+            Ok(text) => return ConfigRead::Text(text),
+            Err(e) if e.kind() == ErrorKind::PermissionDenied => return ConfigRead::Unreadable,
+            Err(_) => continue,
+        }
+    }
+    ConfigRead::Missing
+}
+
+fn check_bootloader_password() -> Option<Finding> {
+    // systemd-boot
+    match read_first(&LOADER_CONFIGS) {
+        ConfigRead::Text(conf) if loader_editor_enabled(&conf) => {
+            return Some(Finding {
+                id: "boot.systemd-boot.editor".to_string(),
+                category: FindingCategory::Boot,
+                severity: Severity::Medium,
+                title: "systemd-boot allows editing kernel parameters".to_string(),
+                detail: "Anyone at the boot menu can change the kernel command line, for \
+                         example to start a root shell."
+                    .to_string(),
+                remediation: "Add the line \"editor no\" to loader/loader.conf on the EFI \
+                              system partition."
+                    .to_string(),
+            });
+        }
+        ConfigRead::Text(_) => return None,
+        ConfigRead::Unreadable => return Some(unverifiable("systemd-boot")),
+        ConfigRead::Missing => {}
+    }
+
+    // GRUB: Fedora/RHEL keep the password hash in user.cfg, which grub.cfg
+    // sources; elsewhere it is written into grub.cfg directly.
+    match read_first(&GRUB_CONFIGS) {
+        ConfigRead::Text(cfg) if cfg.contains("user.cfg") => match read_first(&GRUB_USER_CONFIGS) {
+            ConfigRead::Text(user) if user.contains("GRUB2_PASSWORD=") => None,
+            ConfigRead::Unreadable => Some(unverifiable("GRUB")),
+            _ => Some(grub_without_password()),
+        },
+        ConfigRead::Text(cfg) if grub_password_configured(&cfg) => None,
+        ConfigRead::Text(_) => Some(grub_without_password()),
+        ConfigRead::Unreadable => Some(unverifiable("GRUB")),
+        // No known bootloader configuration: nothing to judge
+        ConfigRead::Missing => None,
+    }
+}
+
+fn grub_without_password() -> Finding {
+    Finding {
+        id: "boot.grub.password".to_string(),
+        category: FindingCategory::Boot,
+        severity: Severity::Medium,
+        title: "GRUB has no password".to_string(),
+        detail: "Anyone at the boot menu can edit entries and change kernel \
+                 parameters, for example to start a root shell."
+            .to_string(),
+        remediation: "Run \"sudo grub2-setpassword\" (Fedora/RHEL). On Debian/Ubuntu, \
+                      create a hash with grub-mkpasswd-pbkdf2, add \"set superusers\" and \
+                      \"password_pbkdf2\" lines to /etc/grub.d/40_custom and run \
+                      \"sudo update-grub\"."
+            .to_string(),
+    }
+}
+
+fn unverifiable(bootloader: &str) -> Finding {
+    Finding {
+        id: "boot.password.unverified".to_string(),
+        category: FindingCategory::Boot,
+        severity: Severity::Info,
+        title: format!("{} password could not be checked", bootloader),
+        detail: "The bootloader configuration is readable by administrators only.".to_string(),
+        remediation: "Make sure a bootloader password is set, or check the configuration \
+                      as administrator."
+            .to_string(),
+    }
+}
+
+/// Whether a grub.cfg restricts editing to a password-protected superuser.
+fn grub_password_configured(cfg: &str) -> bool {
+    let active = |keyword: &str| {
+        cfg.lines()
+            .map(str::trim_start)
+            .any(|line| !line.starts_with('#') && line.starts_with(keyword))
+    };
+    active("set superusers") && (active("password_pbkdf2") || active("password "))
+}
+
+/// Whether the systemd-boot editor is enabled (it defaults to on).
+fn loader_editor_enabled(conf: &str) -> bool {
+    let value = conf
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .rev()
+        .find_map(|line| {
+            let (key, value) = line.split_once(char::is_whitespace)?;
+            (key == "editor").then(|| value.trim().to_lowercase())
+        });
+    !matches!(value.as_deref(), Some("no" | "0" | "false" | "off"))
+}
+
+/// Findings for risky parameters in a kernel command line.
+fn risky_cmdline_findings(cmdline: &str) -> Vec<Finding> {
+    let params: Vec<&str> = cmdline.split_whitespace().collect();
+    let mut findings: Vec<Finding> = Vec::new();
+    for (param, severity, title, why) in RISKY_PARAMS {
+        if !params.contains(param) {
+            continue;
+        }
+        findings.push(Finding {
+            id: format!("boot.cmdline.{}", param),
+            category: FindingCategory::Boot,
+            severity: *severity,
+            title: title.to_string(),
+            detail: format!("The kernel was booted with \"{}\". {}", param, why),
+            remediation: format!(
+                "Remove \"{p}\" from the kernel command line: run \"sudo grubby \
+                 --update-kernel=ALL --remove-args={p}\", or edit GRUB_CMDLINE_LINUX in \
+                 /etc/default/grub and regenerate the GRUB configuration, then reboot.",
+                p = param
+            ),
+        });
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grub_password_configured() {
+        let protected =
+            "set superusers=\"root\"\npassword_pbkdf2 root grub.pbkdf2.sha512.10000.AB\n";
+        assert!(grub_password_configured(protected));
+        let commented = "# set superusers=\"root\"\n# password_pbkdf2 root x\n";
+        assert!(!grub_password_configured(commented));
+        assert!(!grub_password_configured("menuentry 'Linux' {\n}\n"));
+    }
+
+    #[test]
+    fn test_loader_editor_enabled() {
+        assert!(loader_editor_enabled("timeout 3\n"));
+        assert!(loader_editor_enabled("editor yes\n"));
+        assert!(!loader_editor_enabled("timeout 3\neditor   no\n"));
+        assert!(!loader_editor_enabled("editor 0"));
+        assert!(loader_editor_enabled("#editor no\n"));
+    }
+
+    #[test]
+    fn test_risky_cmdline_findings() {
+        let cmdline = "BOOT_IMAGE=/vmlinuz-6.8 root=UUID=abc ro selinux=0 mitigations=off quiet\n";
+        let findings = risky_cmdline_findings(cmdline);
+        let ids: Vec<&str> = findings.iter().map(|f| f.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["boot.cmdline.selinux=0", "boot.cmdline.mitigations=off"]
+        );
+        assert!(findings.iter().all(|f| f.severity == Severity::High));
+        assert!(findings[0].remediation.contains("--remove-args=selinux=0"));
+    }
+
+    #[test]
+    fn test_risky_cmdline_exact_match() {
+        // "enforcing=1" must not match "enforcing=0", nor "audit=0" match "audit=1"
+        assert!(risky_cmdline_findings("ro enforcing=1 audit=1 quiet").is_empty());
+    }
+}
@@ -0,0 +1,302 @@
+// Security Center - Role Checklists
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Curated security checklists for what a machine is used for.
+//!
+//! Each [`ChecklistRole`] lists [`ChecklistItem`]s in the order they are
+//! worth doing. An item points at the page where it is checked and, when one
+//! operation covers it, at an [`Operation`] that does it in one click. Which
+//! items are done is the user's judgement and is kept in the settings by item
+//! id, so items can be reordered or reworded without losing progress.
+
+use super::actions::Operation;
+
+/// What a machine is used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecklistRole {
+    WebServer,
+    Workstation,
+    HomeNas,
+}
+
+/// One thing to check or change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChecklistItem {
+    /// Stable id, unique within the role.
+    pub id: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    /// Navigation id of the page where this is checked, e.g. `zones`.
+    pub page: Option<&'static str>,
+    /// Operation that takes care of the item.
+    pub operation: Option<Operation>,
+}
+
+const fn item(
+    id: &'static str,
+    title: &'static str,
+    description: &'static str,
+    page: Option<&'static str>,
+    operation: Option<Operation>,
+) -> ChecklistItem {
+    ChecklistItem {
+        id,
+        title,
+        description,
+        page,
+        operation,
+    }
+}
+
+const AUTO_UPDATES: ChecklistItem = item(
+    "auto-updates",
+    "Automatic security updates",
+    "Enable dnf-automatic.timer or unattended-upgrades so fixes install without waiting for you",
+    Some("system-services"),
+    None,
+);
+
+const BACKUPS: ChecklistItem = item(
+    "backups",
+    "Tested backups",
+    "Keep a recent copy of your data on another disk or machine, and try restoring a file from it",
+    None,
+    None,
+);
+
+const TIME_SYNC: ChecklistItem = item(
+    "time-sync",
+    "Synchronized clock",
+    "Certificates, logs and logins rely on the right time",
+    Some("platform"),
+    Some(Operation::EnableTimeSync),
+);
+
+const SSH_HARDENED: ChecklistItem = item(
+    "ssh-hardened",
+    "Hardened SSH server",
+    "No root or empty-password logins and few authentication attempts",
+    Some("system-services"),
+    Some(Operation::HardenSsh),
+);
+
+const FAIL2BAN: ChecklistItem = item(
+    "fail2ban",
+    "Brute-force protection",
+    "Let fail2ban ban addresses that keep failing to log in",
+    Some("fail2ban"),
+    None,
+);
+
+const WEB_SERVER: &[ChecklistItem] = &[
+    item(
+        "public-zone",
+        "Public default zone",
+        "Interfaces facing the Internet belong to a zone that trusts nobody",
+        Some("zones"),
+        Some(Operation::SetDefaultZone { zone: "public" }),
+    ),
+    item(
+        "web-ports-only",
+        "Only web services allowed",
+        "Allow http and https and remove services the site does not need",
+        Some("services"),
+        None,
+    ),
+    item(
+        "no-stray-listeners",
+        "No unexpected listeners",
+        "Databases and admin consoles should listen on localhost only",
+        Some("network-exposure"),
+        None,
+    ),
+    SSH_HARDENED,
+    FAIL2BAN,
+    item(
+        "service-sandboxing",
+        "Sandboxed web server",
+        "Add NoNewPrivileges and ProtectSystem to the web server's service",
+        Some("system-services"),
+        None,
+    ),
+    AUTO_UPDATES,
+    item(
+        "logging",
+        "Persistent logs",
+        "Keep the journal across reboots so incidents can be investigated",
+        Some("platform"),
+        None,
+    ),
+    TIME_SYNC,
+    BACKUPS,
+];
+
+const WORKSTATION: &[ChecklistItem] = &[
+    item(
+        "firewall-on",
+        "Firewall running",
+        "firewalld runs and starts at boot",
+        Some("overview"),
+        Some(Operation::EnableFirewall),
+    ),
+    item(
+        "no-listeners",
+        "Nothing listening on the network",
+        "A desktop rarely needs to accept connections; stop what you do not use",
+        Some("network-exposure"),
+        None,
+    ),
+    item(
+        "screen-lock",
+        "Screen lock",
+        "Lock the screen automatically when you step away",
+        None,
+        Some(Operation::EnableScreenLock),
+    ),
+    item(
+        "mac-randomization",
+        "Random MAC addresses",
+        "Networks cannot follow the machine from one place to the next",
+        None,
+        Some(Operation::EnableMacRandomization),
+    ),
+    item(
+        "antivirus",
+        "Up-to-date virus signatures",
+        "Scan downloads with ClamAV using current signatures",
+        Some("antivirus"),
+        None,
+    ),
+    item(
+        "secure-boot",
+        "Verified boot",
+        "Secure Boot and the TPM protect the boot chain from tampering",
+        Some("platform"),
+        None,
+    ),
+    AUTO_UPDATES,
+    BACKUPS,
+];
+
+const HOME_NAS: &[ChecklistItem] = &[
+    item(
+        "home-zone",
+        "Firewall zones",
+        "The LAN interface is in the home zone and nothing else reaches the shares",
+        Some("zones"),
+        None,
+    ),
+    item(
+        "samba-restricted",
+        "Samba restricted to the LAN",
+        "Allow the samba service in the home zone only, never in public",
+        Some("services"),
+        Some(Operation::RemoveZoneService {
+            zone: "public",
+            service: "samba",
+        }),
+    ),
+    item(
+        "no-stray-listeners",
+        "Only file sharing exposed",
+        "Check that web consoles and databases are not reachable from the network",
+        Some("network-exposure"),
+        None,
+    ),
+    SSH_HARDENED,
+    AUTO_UPDATES,
+    TIME_SYNC,
+    item(
+        "backups",
+        "Backups of the shares",
+        "RAID is not a backup; copy the shares to another disk or off site",
+        None,
+        None,
+    ),
+];
+
+impl ChecklistRole {
+    pub const ALL: [ChecklistRole; 3] = [
+        ChecklistRole::WebServer,
+        ChecklistRole::Workstation,
+        ChecklistRole::HomeNas,
+    ];
+
+    /// Stable id used in the settings.
+    pub fn id(&self) -> &'static str {
+        match self {
+            ChecklistRole::WebServer => "web-server",
+            ChecklistRole::Workstation => "workstation",
+            ChecklistRole::HomeNas => "home-nas",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            ChecklistRole::WebServer => "Web Server",
+            ChecklistRole::Workstation => "Workstation",
+            ChecklistRole::HomeNas => "Home NAS",
+        }
+    }
+
+    pub fn parse(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|role| role.id() == id)
+    }
+
+    pub fn items(&self) -> &'static [ChecklistItem] {
+        match self {
+            ChecklistRole::WebServer => WEB_SERVER,
+            ChecklistRole::Workstation => WORKSTATION,
+            ChecklistRole::HomeNas => HOME_NAS,
+        }
+    }
+
+    pub fn item(&self, id: &str) -> Option<&'static ChecklistItem> {
+        self.items().iter().find(|item| item.id == id)
+    }
+
+    /// Items of this role among `done`, and how many items it has.
+    pub fn progress(&self, done: &[String]) -> (usize, usize) {
+        let items = self.items();
+        let checked = items
+            .iter()
+            .filter(|item| done.iter().any(|id| id == item.id))
+            .count();
+        (checked, items.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checklists() {
+        for role in ChecklistRole::ALL {
+            assert_eq!(ChecklistRole::parse(role.id()), Some(role));
+            let items = role.items();
+            assert!(!items.is_empty());
+            for (i, item) in items.iter().enumerate() {
+                assert!(
+                    items[i + 1..].iter().all(|other| other.id != item.id),
+                    "{} repeats {}",
+                    role.id(),
+                    item.id
+                );
+            }
+        }
+        assert_eq!(ChecklistRole::parse("router"), None);
+
+        let nas = ChecklistRole::HomeNas;
+        assert!(nas.item("samba-restricted").is_some());
+        assert_eq!(nas.item("screen-lock"), None);
+        let done = vec![
+            "backups".to_string(),
+            "screen-lock".to_string(),
+            "auto-updates".to_string(),
+        ];
+        assert_eq!(nas.progress(&done), (2, nas.items().len()));
+        assert_eq!(nas.progress(&[]), (0, nas.items().len()));
+    }
+}
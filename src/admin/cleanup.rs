@@ -0,0 +1,707 @@
+// Security Center - Sensitive Leftovers Cleanup
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Discovery and secure removal of sensitive leftovers.
+//!
+//! Three categories are covered:
+//!
+//! - Shell and REPL history files in the user's home directory, noting how
+//!   much each grew since the sizes recorded at the previous scan
+//! - Thumbnails generated for files on removable media (USB sticks, SD cards),
+//!   which outlive the media itself and reveal what was on it
+//! - Core dumps captured by systemd-coredump, which can contain passwords,
+//!   keys and other secrets from the crashed process's memory
+//!
+//! Core dumps belong to root, so the privileged helper removes them
+//! ([`HelperOp::RemoveCoreDumps`]); unattended runs only do so when polkit
+//! grants it without a password.
+//!
+//! Secure deletion overwrites file contents before unlinking them. On SSDs and
+//! copy-on-write filesystems (btrfs) the overwrite may not reach the original
+//! blocks, so it reduces rather than eliminates recoverability.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Seek, SeekFrom, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::helper::{authorized_unattended, run_privileged, HelperOp};
+
+/// Directory where systemd-coredump stores captured core dumps.
+const COREDUMP_DIR: &str = "/var/lib/systemd/coredump";
+
+/// Most core dumps removed by one helper call.
+pub const MAX_CORE_DUMPS: usize = 256;
+
+/// Shell and interpreter history files, relative to the home directory.
+const HISTORY_FILES: &[&str] = &[
+    ".bash_history",
+    ".zsh_history",
+    ".histfile",
+    ".local/share/fish/fish_history",
+    ".python_history",
+    ".node_repl_history",
+    ".mysql_history",
+    ".psql_history",
+    ".sqlite_history",
+    ".lesshst",
+];
+
+/// Thumbnail cache sizes defined by the freedesktop thumbnail spec.
+const THUMBNAIL_DIRS: &[&str] = &["normal", "large", "x-large", "xx-large"];
+
+/// URI prefixes of mount points used for removable media.
+const REMOVABLE_PREFIXES: &[&str] = &["file:///media/", "file:///run/media/", "file:///mnt/"];
+
+/// Kind of sensitive leftover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CleanupCategory {
+    ShellHistory,
+    RemovableThumbnails,
+    CoreDumps,
+}
+
+impl CleanupCategory {
+    /// All categories, in display order.
+    pub const ALL: [CleanupCategory; 3] = [
+        CleanupCategory::ShellHistory,
+        CleanupCategory::RemovableThumbnails,
+        CleanupCategory::CoreDumps,
+    ];
+
+    /// Stable identifier used in settings.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::ShellHistory => "shell-history",
+            Self::RemovableThumbnails => "removable-thumbnails",
+            Self::CoreDumps => "core-dumps",
+        }
+    }
+
+    /// Look up a category by its settings identifier.
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|c| c.id() == id)
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::ShellHistory => "Shell History",
+            Self::RemovableThumbnails => "Removable Media Thumbnails",
+            Self::CoreDumps => "Core Dumps",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::ShellHistory => {
+                "Commands typed in shells and interpreters, including pasted secrets"
+            }
+            Self::RemovableThumbnails => {
+                "Previews of files from USB drives and SD cards that are no longer attached"
+            }
+            Self::CoreDumps => {
+                "Memory images of crashed programs that may contain passwords and keys"
+            }
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::ShellHistory => "utilities-terminal-symbolic",
+            Self::RemovableThumbnails => "media-removable-symbolic",
+            Self::CoreDumps => "dialog-warning-symbolic",
+        }
+    }
+
+    /// History files are emptied rather than removed so the shell keeps its
+    /// file with the original permissions.
+    fn truncate_only(&self) -> bool {
+        matches!(self, Self::ShellHistory)
+    }
+}
+
+/// A single file found by a cleanup scan.
+#[derive(Debug, Clone)]
+pub struct CleanupItem {
+    pub category: CleanupCategory,
+    pub path: PathBuf,
+    pub size: u64,
+    /// Short human-readable note (source URI, crashed program, ...).
+    pub detail: String,
+    /// Bytes a history file grew since the size recorded at the previous
+    /// scan.
+    pub growth: Option<u64>,
+}
+
+/// Outcome of a cleanup run.
+#[derive(Debug, Clone, Default)]
+pub struct CleanupReport {
+    /// Number of files removed or emptied.
+    pub cleaned: usize,
+    /// Total bytes reclaimed.
+    pub bytes: u64,
+    /// Files that could not be cleaned, with the reason.
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+impl CleanupReport {
+    /// One-line summary suitable for a toast.
+    pub fn summary(&self) -> String {
+        if self.failed.is_empty() {
+            format!("Cleaned {} files", self.cleaned)
+        } else {
+            format!(
+                "Cleaned {} files, {} could not be removed",
+                self.cleaned,
+                self.failed.len()
+            )
+        }
+    }
+}
+
+/// Scan for leftovers in the given categories. `history_sizes` are the
+/// sizes of the history files at the previous scan, from [`history_sizes`].
+pub fn scan_leftovers(
+    categories: &[CleanupCategory],
+    history_sizes: &BTreeMap<String, u64>,
+) -> Vec<CleanupItem> {
+    let mut items = Vec::new();
+    let home = dirs::home_dir();
+    for category in categories {
+        match category {
+            CleanupCategory::ShellHistory => {
+                if let Some(home) = &home {
+                    items.extend(scan_history(home, history_sizes));
+                }
+            }
+            CleanupCategory::RemovableThumbnails => {
+                if let Some(cache) = dirs::cache_dir() {
+                    items.extend(scan_thumbnails(&cache.join("thumbnails")));
+                }
+            }
+            CleanupCategory::CoreDumps => {
+                items.extend(scan_coredumps(Path::new(COREDUMP_DIR)));
+            }
+        }
+    }
+    items
+}
+
+/// The current size of each history file, by path, for the next scan to
+/// compare with.
+pub fn history_sizes() -> BTreeMap<String, u64> {
+    dirs::home_dir()
+        .map(|home| read_history_sizes(&home))
+        .unwrap_or_default()
+}
+
+/// Remove the given items, overwriting their contents first when `secure`.
+/// Core dumps are removed by the privileged helper, which may ask for a
+/// password when `interactive`; otherwise they are left in place unless
+/// polkit grants it without asking.
+pub fn clean_leftovers(items: &[CleanupItem], secure: bool, interactive: bool) -> CleanupReport {
+    let mut report = CleanupReport::default();
+    let (core_dumps, files): (Vec<&CleanupItem>, Vec<&CleanupItem>) = items
+        .iter()
+        .partition(|item| item.category == CleanupCategory::CoreDumps);
+    for item in files {
+        let result = if secure {
+            shred_file(&item.path, item.category.truncate_only())
+        } else if item.category.truncate_only() {
+            open_regular(&item.path).and_then(|f| f.set_len(0))
+        } else {
+            fs::remove_file(&item.path)
+        };
+        match result {
+            Ok(()) => {
+                report.cleaned += 1;
+                report.bytes += item.size;
+            }
+            Err(e) => {
+                let reason = if e.kind() == io::ErrorKind::PermissionDenied {
+                    "Administrator privileges required".to_string()
+                } else {
+                    e.to_string()
+                };
+                report.failed.push((item.path.clone(), reason));
+            }
+        }
+    }
+    for chunk in core_dumps.chunks(MAX_CORE_DUMPS) {
+        clean_core_dumps(chunk, secure, interactive, &mut report);
+    }
+    report
+}
+
+/// Have the helper remove `items`, all core dumps, adding the outcome to
+/// `report`.
+fn clean_core_dumps(
+    items: &[&CleanupItem],
+    secure: bool,
+    interactive: bool,
+    report: &mut CleanupReport,
+) {
+    let op = HelperOp::RemoveCoreDumps {
+        names: items
+            .iter()
+            .filter_map(|item| item.path.file_name()?.to_str())
+            .map(str::to_string)
+            .collect(),
+        secure,
+    };
+    let result = if interactive || authorized_unattended(&op) {
+        run_privileged(&op).map(|_| ())
+    } else {
+        Err(anyhow!(
+            "Administrator authorization required; clean core dumps from the application"
+        ))
+    };
+    // The helper goes on past a dump it cannot remove, so what is gone
+    // counts as cleaned whatever it reported
+    for item in items {
+        if fs::symlink_metadata(&item.path).is_err() {
+            report.cleaned += 1;
+            report.bytes += item.size;
+        } else {
+            let reason = match &result {
+                Err(e) => format!("{:#}", e),
+                Ok(()) => "Could not be removed".to_string(),
+            };
+            report.failed.push((item.path.clone(), reason));
+        }
+    }
+}
+
+/// Remove the core dumps `names` from the systemd-coredump directory,
+/// overwriting them first when `secure`. Runs as root inside the helper;
+/// the names were checked by [`validate_coredump_name`].
+pub fn remove_core_dumps(names: &[String], secure: bool) -> Result<String> {
+    let mut removed = 0;
+    let mut errors = Vec::new();
+    for name in names {
+        let path = Path::new(COREDUMP_DIR).join(name);
+        let result = if secure {
+            shred_file(&path, false)
+        } else {
+            fs::remove_file(&path)
+        };
+        match result {
+            Ok(()) => removed += 1,
+            Err(e) => errors.push(format!("{}: {}", name, e)),
+        }
+    }
+    if errors.is_empty() {
+        Ok(format!("Removed {} core dumps", removed))
+    } else {
+        bail!(
+            "Removed {} of {} core dumps; {}",
+            removed,
+            names.len(),
+            errors.join("; ")
+        )
+    }
+}
+
+/// The core dump file name `name`, if it is one systemd-coredump could
+/// have written: `core.` and a name without path separators.
+pub fn validate_coredump_name(name: &str) -> Option<&str> {
+    let valid = name.len() > "core.".len()
+        && name.len() <= 255
+        && name.starts_with("core.")
+        && !name.contains('/')
+        && !name.chars().any(char::is_control);
+    valid.then_some(name)
+}
+
+/// Whether a scheduled cleanup is due, given the last run (unix seconds),
+/// the interval in days (0 disables scheduling) and the current time.
+pub fn cleanup_due(last_run: Option<i64>, interval_days: u32, now: i64) -> bool {
+    if interval_days == 0 {
+        return false;
+    }
+    match last_run {
+        Some(last) => now - last >= i64::from(interval_days) * 86_400,
+        None => true,
+    }
+}
+
+fn scan_history(home: &Path, history_sizes: &BTreeMap<String, u64>) -> Vec<CleanupItem> {
+    HISTORY_FILES
+        .iter()
+        .filter_map(|rel| {
+            let path = home.join(rel);
+            let meta = fs::symlink_metadata(&path).ok()?;
+            if !meta.is_file() || meta.len() == 0 {
+                return None;
+            }
+            // A file that shrank was cleaned or rotated, not grown
+            let growth = history_sizes
+                .get(path.to_string_lossy().as_ref())
+                .and_then(|&previous| meta.len().checked_sub(previous))
+                .filter(|&growth| growth > 0);
+            Some(CleanupItem {
+                category: CleanupCategory::ShellHistory,
+                path,
+                size: meta.len(),
+                detail: String::new(),
+                growth,
+            })
+        })
+        .collect()
+}
+
+fn read_history_sizes(home: &Path) -> BTreeMap<String, u64> {
+    HISTORY_FILES
+        .iter()
+        .filter_map(|rel| {
+            let path = home.join(rel);
+            let meta = fs::symlink_metadata(&path).ok()?;
+            meta.is_file()
+                .then(|| (path.to_string_lossy().to_string(), meta.len()))
+        })
+        .collect()
+}
+
+fn scan_thumbnails(root: &Path) -> Vec<CleanupItem> {
+    let mut items = Vec::new();
+    for dir in THUMBNAIL_DIRS {
+        let Ok(entries) = fs::read_dir(root.join(dir)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("png") {
+                continue;
+            }
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            // Thumb::URI lives in a tEXt chunk right after IHDR; the first
+            // few kilobytes are enough to find it.
+            let Some(uri) = read_head(&path, 4096).and_then(|data| png_thumb_uri(&data)) else {
+                continue;
+            };
+            if is_removable_uri(&uri) {
+                items.push(CleanupItem {
+                    category: CleanupCategory::RemovableThumbnails,
+                    path,
+                    size: meta.len(),
+                    detail: uri,
+                    growth: None,
+                });
+            }
+        }
+    }
+    items
+}
+
+fn scan_coredumps(dir: &Path) -> Vec<CleanupItem> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.starts_with("core.") {
+                return None;
+            }
+            let meta = entry.metadata().ok()?;
+            Some(CleanupItem {
+                category: CleanupCategory::CoreDumps,
+                path: entry.path(),
+                size: meta.len(),
+                detail: coredump_program(&name).unwrap_or_default(),
+                growth: None,
+            })
+        })
+        .collect()
+}
+
+/// Extract the crashed program name from a systemd-coredump file name
+/// (`core.<comm>.<uid>.<boot-id>.<pid>.<timestamp>[.zst]`).
+fn coredump_program(name: &str) -> Option<String> {
+    let comm = name.strip_prefix("core.")?.split('.').next()?;
+    if comm.is_empty() {
+        None
+    } else {
+        Some(comm.replace("\\x2d", "-"))
+    }
+}
+
+fn read_head(path: &Path, len: usize) -> Option<Vec<u8>> {
+    use std::io::Read;
+    let mut buf = Vec::with_capacity(len);
+    fs::File::open(path)
+        .ok()?
+        .take(len as u64)
+        .read_to_end(&mut buf)
+        .ok()?;
+    Some(buf)
+}
+
+/// Find the `Thumb::URI` text chunk in PNG data.
+fn png_thumb_uri(data: &[u8]) -> Option<String> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    if !data.starts_with(SIGNATURE) {
+        return None;
+    }
+    let mut pos = SIGNATURE.len();
+    while pos + 8 <= data.len() {
+        let len = u32::from_be_bytes(data[pos..pos + 4].try_into().ok()?) as usize;
+        let kind = &data[pos + 4..pos + 8];
+        let body_start = pos + 8;
+        let body_end = body_start.checked_add(len)?;
+        if kind == b"IDAT" || kind == b"IEND" || body_end > data.len() {
+            return None;
+        }
+        if kind == b"tEXt" {
+            let body = &data[body_start..body_end];
+            if let Some(sep) = body.iter().position(|b| *b == 0) {
+                if &body[..sep] == b"Thumb::URI" {
+                    return Some(String::from_utf8_lossy(&body[sep + 1..]).to_string());
+                }
+            }
+        }
+        // Skip body and CRC.
+        pos = body_end + 4;
+    }
+    None
+}
+
+fn is_removable_uri(uri: &str) -> bool {
+    REMOVABLE_PREFIXES.iter().any(|p| uri.starts_with(p))
+}
+
+/// Open the regular file `path` for writing. A symbolic link is refused
+/// rather than followed, so a planted link cannot redirect the write, and
+/// the type is checked on the open descriptor, so the file cannot be
+/// swapped between the check and the write.
+fn open_regular(path: &Path) -> io::Result<fs::File> {
+    // O_NONBLOCK keeps a FIFO from blocking the open; regular files ignore it
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .custom_flags(libc::O_NOFOLLOW | libc::O_NONBLOCK)
+        .open(path)
+        .map_err(|e| {
+            if e.raw_os_error() == Some(libc::ELOOP) {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Refusing to overwrite a symbolic link",
+                )
+            } else {
+                e
+            }
+        })?;
+    if !file.metadata()?.is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Not a regular file",
+        ));
+    }
+    Ok(file)
+}
+
+/// Overwrite a file with pseudo-random data, flush it to disk, then either
+/// truncate it (`truncate_only`) or unlink it.
+fn shred_file(path: &Path, truncate_only: bool) -> io::Result<()> {
+    let mut file = open_regular(path)?;
+    let len = file.metadata()?.len();
+    let mut state = seed();
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut remaining = len;
+    file.seek(SeekFrom::Start(0))?;
+    while remaining > 0 {
+        for chunk in buf.chunks_mut(8) {
+            state = xorshift(state);
+            let bytes = state.to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+        let n = remaining.min(buf.len() as u64) as usize;
+        file.write_all(&buf[..n])?;
+        remaining -= n as u64;
+    }
+    file.sync_all()?;
+    file.set_len(0)?;
+    file.sync_all()?;
+    drop(file);
+
+    if truncate_only {
+        Ok(())
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+fn seed() -> u64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(std::process::id() as u64);
+    hasher.finish() | 1
+}
+
+fn xorshift(mut x: u64) -> u64 {
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png_with_text(key: &str, value: &str) -> Vec<u8> {
+        let mut data = b"\x89PNG\r\n\x1a\n".to_vec();
+        // Minimal IHDR (contents irrelevant to the parser).
+        data.extend_from_slice(&13u32.to_be_bytes());
+        data.extend_from_slice(b"IHDR");
+        data.extend_from_slice(&[0u8; 13]);
+        data.extend_from_slice(&[0u8; 4]);
+        let body = [key.as_bytes(), &[0], value.as_bytes()].concat();
+        data.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        data.extend_from_slice(b"tEXt");
+        data.extend_from_slice(&body);
+        data.extend_from_slice(&[0u8; 4]);
+        data
+    }
+
+    #[test]
+    fn test_png_thumb_uri() {
+        let data = png_with_text("Thumb::URI", "file:///run/media/user/USB/photo.jpg");
+        assert_eq!(
+            png_thumb_uri(&data).as_deref(),
+            Some("file:///run/media/user/USB/photo.jpg")
+        );
+        assert_eq!(png_thumb_uri(&png_with_text("Software", "x")), None);
+        assert_eq!(png_thumb_uri(b"not a png"), None);
+    }
+
+    #[test]
+    fn test_is_removable_uri() {
+        assert!(is_removable_uri("file:///media/usb/a.png"));
+        assert!(is_removable_uri("file:///run/media/user/SD/b.jpg"));
+        assert!(!is_removable_uri("file:///home/user/c.jpg"));
+    }
+
+    #[test]
+    fn test_coredump_program() {
+        assert_eq!(
+            coredump_program("core.firefox.1000.abc.1234.1700000000000000.zst").as_deref(),
+            Some("firefox")
+        );
+        assert_eq!(
+            coredump_program("core.gnome\\x2dshell.1000.abc.1.2.zst").as_deref(),
+            Some("gnome-shell")
+        );
+        assert_eq!(coredump_program("other"), None);
+    }
+
+    #[test]
+    fn test_cleanup_due() {
+        assert!(!cleanup_due(None, 0, 1_000_000));
+        assert!(cleanup_due(None, 7, 1_000_000));
+        assert!(!cleanup_due(Some(1_000_000), 1, 1_000_000 + 3_600));
+        assert!(cleanup_due(Some(1_000_000), 1, 1_000_000 + 86_400));
+    }
+
+    #[test]
+    fn test_shred_file_truncate_and_remove() {
+        let dir = std::env::temp_dir().join(format!("sc-cleanup-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let keep = dir.join("history");
+        fs::write(&keep, b"secret password").unwrap();
+        shred_file(&keep, true).unwrap();
+        assert_eq!(fs::metadata(&keep).unwrap().len(), 0);
+
+        let gone = dir.join("core");
+        fs::write(&gone, vec![7u8; 100_000]).unwrap();
+        shred_file(&gone, false).unwrap();
+        assert!(!gone.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_shred_file_refuses_links_and_fifos() {
+        let dir = std::env::temp_dir().join(format!("sc-cleanup-link-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let target = dir.join("target");
+        fs::write(&target, b"keep me").unwrap();
+        let link = dir.join("history");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+        assert!(shred_file(&link, true).is_err());
+        assert!(open_regular(&link).is_err());
+        assert_eq!(fs::read(&target).unwrap(), b"keep me");
+
+        let fifo = dir.join("fifo");
+        let name = std::ffi::CString::new(fifo.to_str().unwrap()).unwrap();
+        assert_eq!(unsafe { libc::mkfifo(name.as_ptr(), 0o600) }, 0);
+        assert!(shred_file(&fifo, false).is_err());
+        assert!(fifo.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_scan_history_growth() {
+        let home = std::env::temp_dir().join(format!("sc-cleanup-home-{}", std::process::id()));
+        fs::create_dir_all(&home).unwrap();
+        fs::write(home.join(".bash_history"), vec![b'x'; 300]).unwrap();
+        fs::write(home.join(".zsh_history"), vec![b'x'; 100]).unwrap();
+        fs::write(home.join(".python_history"), vec![b'x'; 50]).unwrap();
+
+        let mut sizes = read_history_sizes(&home);
+        assert_eq!(sizes.len(), 3);
+        let key = |rel: &str| home.join(rel).to_string_lossy().to_string();
+        assert_eq!(sizes[&key(".bash_history")], 300);
+        sizes.insert(key(".bash_history"), 100);
+        sizes.insert(key(".zsh_history"), 400);
+        sizes.remove(&key(".python_history"));
+
+        let items = scan_history(&home, &sizes);
+        let growth = |rel: &str| {
+            items
+                .iter()
+                .find(|item| item.path == home.join(rel))
+                .unwrap()
+                .growth
+        };
+        assert_eq!(growth(".bash_history"), Some(200));
+        // Shrunk since the previous scan, or never recorded
+        assert_eq!(growth(".zsh_history"), None);
+        assert_eq!(growth(".python_history"), None);
+
+        fs::remove_dir_all(&home).unwrap();
+    }
+
+    #[test]
+    fn test_validate_coredump_name() {
+        let name = "core.firefox.1000.abc.1234.1700000000000000.zst";
+        assert_eq!(validate_coredump_name(name), Some(name));
+        assert_eq!(validate_coredump_name("core."), None);
+        assert_eq!(validate_coredump_name("core.x/../../etc/shadow"), None);
+        assert_eq!(validate_coredump_name("../core.x"), None);
+        assert_eq!(validate_coredump_name("passwd"), None);
+        assert_eq!(validate_coredump_name("core.x\nname=y"), None);
+        assert_eq!(
+            validate_coredump_name(&format!("core.{}", "x".repeat(300))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_category_ids_round_trip() {
+        for category in CleanupCategory::ALL {
+            assert_eq!(CleanupCategory::from_id(category.id()), Some(category));
+        }
+        assert_eq!(CleanupCategory::from_id("bogus"), None);
+    }
+}
@@ -0,0 +1,675 @@
+// Security Center - Compliance Profiles
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Compliance scans with OpenSCAP.
+//!
+//! When `oscap` and the SCAP Security Guide content are installed, a profile
+//! from the distribution's data stream (CIS, ANSSI, STIG, ...) can be
+//! evaluated against the host. Most checks read root-only files, so the scan
+//! runs in the privileged helper (see `crate::helper`), which parses the
+//! XCCDF results there and hands back a compact [`ComplianceReport`].
+//!
+//! Rules that overlap with a setting the application manages point to the
+//! page where it can be fixed (see [`RuleResult::remediation`]).
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::helper::{run_privileged, HelperOp};
+
+/// Where scap-security-guide installs its data streams.
+pub const SCAP_CONTENT_DIR: &str = "/usr/share/xml/scap/ssg/content";
+
+const OSCAP: &str = "oscap";
+const RULE_PREFIX: &str = "xccdf_org.ssgproject.content_rule_";
+
+/// A profile of a data stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScapProfile {
+    /// XCCDF id, e.g. `xccdf_org.ssgproject.content_profile_cis_workstation_l1`.
+    pub id: String,
+    pub title: String,
+}
+
+/// OpenSCAP installation and the profiles it can evaluate.
+#[derive(Debug, Clone, Default)]
+pub struct ComplianceStatus {
+    pub oscap_installed: bool,
+    /// File name of the data stream used, inside [`SCAP_CONTENT_DIR`].
+    pub datastream: Option<String>,
+    pub profiles: Vec<ScapProfile>,
+}
+
+/// Result of a single rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RuleOutcome {
+    Pass,
+    Fail,
+    Error,
+    Unknown,
+    NotApplicable,
+    NotChecked,
+    Informational,
+    Fixed,
+}
+
+impl RuleOutcome {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pass" => Some(RuleOutcome::Pass),
+            "fail" => Some(RuleOutcome::Fail),
+            "error" => Some(RuleOutcome::Error),
+            "unknown" => Some(RuleOutcome::Unknown),
+            "notapplicable" => Some(RuleOutcome::NotApplicable),
+            "notchecked" => Some(RuleOutcome::NotChecked),
+            "informational" => Some(RuleOutcome::Informational),
+            "fixed" => Some(RuleOutcome::Fixed),
+            // notselected: not part of the profile
+            _ => None,
+        }
+    }
+}
+
+/// Page of the application that manages the setting a rule checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Remediation {
+    /// Navigation id of the page, e.g. `platform`.
+    pub page: &'static str,
+    /// What to change there.
+    pub label: &'static str,
+}
+
+/// Rule id suffixes (after `content_rule_`) and where to remediate them.
+/// Entries ending in `_` match as prefixes.
+const REMEDIATIONS: [(&str, Remediation); 20] = [
+    ("service_firewalld_enabled", FIREWALL),
+    ("package_firewalld_installed", FIREWALL),
+    ("set_firewalld_default_zone", ZONES),
+    ("firewalld_sshd_port_enabled", SERVICES),
+    ("coredump_disable_storage", COREDUMPS),
+    ("coredump_disable_backtraces", COREDUMPS),
+    ("sysctl_fs_suid_dumpable", COREDUMPS),
+    ("service_auditd_enabled", AUDITD),
+    ("package_audit_installed", AUDITD),
+    ("journald_storage", JOURNAL),
+    ("journald_compress", JOURNAL),
+    ("service_systemd-journald_enabled", JOURNAL),
+    ("service_chronyd_enabled", TIME_SYNC),
+    ("service_chronyd_or_ntpd_enabled", TIME_SYNC),
+    ("service_timesyncd_enabled", TIME_SYNC),
+    ("package_chrony_installed", TIME_SYNC),
+    ("service_avahi-daemon_disabled", MDNS),
+    ("package_avahi_removed", MDNS),
+    ("grub2_", BOOT),
+    ("zipl_", BOOT),
+];
+
+const FIREWALL: Remediation = Remediation {
+    page: "overview",
+    label: "Turn on the firewall",
+};
+const ZONES: Remediation = Remediation {
+    page: "zones",
+    label: "Choose the default zone",
+};
+const SERVICES: Remediation = Remediation {
+    page: "services",
+    label: "Allow the ssh service",
+};
+const COREDUMPS: Remediation = Remediation {
+    page: "platform",
+    label: "Restrict core dumps",
+};
+const AUDITD: Remediation = Remediation {
+    page: "platform",
+    label: "Enable audit logging",
+};
+const JOURNAL: Remediation = Remediation {
+    page: "platform",
+    label: "Review journal settings",
+};
+const TIME_SYNC: Remediation = Remediation {
+    page: "platform",
+    label: "Enable time synchronization",
+};
+const MDNS: Remediation = Remediation {
+    page: "network-exposure",
+    label: "Stop mDNS advertisements",
+};
+const BOOT: Remediation = Remediation {
+    page: "platform",
+    label: "Review boot chain findings",
+};
+
+/// Outcome of one rule of the evaluated profile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleResult {
+    /// XCCDF rule id.
+    pub id: String,
+    pub title: String,
+    /// `low`, `medium`, `high` or `unknown`.
+    pub severity: String,
+    pub outcome: RuleOutcome,
+}
+
+impl RuleResult {
+    /// Rule id without the SCAP Security Guide prefix.
+    pub fn short_id(&self) -> &str {
+        self.id.strip_prefix(RULE_PREFIX).unwrap_or(&self.id)
+    }
+
+    /// Where the application can fix this rule, if it manages the setting.
+    pub fn remediation(&self) -> Option<Remediation> {
+        let id = self.id.strip_prefix(RULE_PREFIX)?;
+        REMEDIATIONS
+            .iter()
+            .find(|(key, _)| {
+                if key.ends_with('_') {
+                    id.starts_with(key)
+                } else {
+                    id == *key
+                }
+            })
+            .map(|(_, remediation)| *remediation)
+    }
+}
+
+/// Results of evaluating a profile.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComplianceReport {
+    pub profile: String,
+    /// XCCDF default score (0-100), when reported.
+    pub score: Option<f64>,
+    pub results: Vec<RuleResult>,
+}
+
+impl ComplianceReport {
+    pub fn count(&self, outcome: RuleOutcome) -> usize {
+        self.results.iter().filter(|r| r.outcome == outcome).count()
+    }
+
+    /// Failed rules, most severe first.
+    pub fn failed(&self) -> Vec<&RuleResult> {
+        let mut failed: Vec<&RuleResult> = self
+            .results
+            .iter()
+            .filter(|r| matches!(r.outcome, RuleOutcome::Fail | RuleOutcome::Error))
+            .collect();
+        failed.sort_by_key(|r| (severity_rank(&r.severity), r.title.clone()));
+        failed
+    }
+}
+
+fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "high" => 0,
+        "medium" => 1,
+        "low" => 2,
+        _ => 3,
+    }
+}
+
+/// Detect OpenSCAP and list the profiles of the host's data stream.
+pub fn query_compliance() -> ComplianceStatus {
+    let mut status = ComplianceStatus {
+        oscap_installed: oscap_installed(),
+        ..Default::default()
+    };
+    if !status.oscap_installed {
+        return status;
+    }
+
+    let files: Vec<String> = fs::read_dir(SCAP_CONTENT_DIR)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| e.file_name().into_string().ok())
+                .filter(|name| validate_datastream(name).is_some())
+                .collect()
+        })
+        .unwrap_or_default();
+    let os_release = fs::read_to_string("/etc/os-release").unwrap_or_default();
+    let (os_id, version_id) = os_release_ids(&os_release);
+    status.datastream = pick_datastream(&files, &os_id, &version_id);
+
+    if let Some(datastream) = &status.datastream {
+        match list_profiles(&Path::new(SCAP_CONTENT_DIR).join(datastream)) {
+            Ok(profiles) => status.profiles = profiles,
+            Err(e) => tracing::warn!("Cannot list SCAP profiles: {}", e),
+        }
+    }
+    status
+}
+
+/// Evaluate `profile` of `datastream` in the privileged helper. Takes from
+/// a few seconds to several minutes depending on the profile.
+pub fn run_compliance_scan(datastream: &str, profile: &str) -> Result<ComplianceReport> {
+    let json = run_privileged(&HelperOp::RunComplianceScan {
+        datastream: datastream.to_string(),
+        profile: profile.to_string(),
+    })?;
+    serde_json::from_str(&json).context("Invalid compliance scan output")
+}
+
+/// Run `oscap xccdf eval` and parse its results. Runs as root inside the
+/// helper process; `results_dir` must be a fresh directory only root can
+/// write to.
+pub fn evaluate_profile(datastream: &str, profile: &str, results_dir: &Path) -> Result<String> {
+    let results = results_dir.join("results.xml");
+    let output = Command::new(OSCAP)
+        .args(["xccdf", "eval", "--profile", profile, "--results"])
+        .arg(&results)
+        .arg(Path::new(SCAP_CONTENT_DIR).join(datastream))
+        .output()
+        .context("Failed to run oscap")?;
+    // 2 means the evaluation finished with failed rules
+    if !matches!(output.status.code(), Some(0) | Some(2)) {
+        bail!(
+            "oscap failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let xml = fs::read_to_string(&results).context("oscap wrote no results")?;
+    let report = parse_results(&xml, profile)?;
+    Ok(serde_json::to_string(&report)?)
+}
+
+/// Accept only a data stream file name of scap-security-guide.
+pub fn validate_datastream(name: &str) -> Option<&str> {
+    let middle = name.strip_prefix("ssg-")?.strip_suffix("-ds.xml")?;
+    if !middle.is_empty()
+        && middle.len() <= 64
+        && middle
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+    {
+        Some(name)
+    } else {
+        None
+    }
+}
+
+/// Accept only an XCCDF profile id.
+pub fn validate_profile_id(id: &str) -> Option<&str> {
+    let rest = id.strip_prefix("xccdf_")?;
+    if !rest.is_empty()
+        && id.len() <= 256
+        && rest
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+    {
+        Some(id)
+    } else {
+        None
+    }
+}
+
+fn oscap_installed() -> bool {
+    std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).any(|dir| dir.join(OSCAP).is_file()))
+        .unwrap_or(false)
+}
+
+/// `ID` and `VERSION_ID` of os-release.
+fn os_release_ids(os_release: &str) -> (String, String) {
+    let mut id = String::new();
+    let mut version_id = String::new();
+    for line in os_release.lines() {
+        if let Some((key, value)) = line.split_once('=') {
+            let value = value.trim().trim_matches('"').to_string();
+            match key.trim() {
+                "ID" => id = value,
+                "VERSION_ID" => version_id = value,
+                _ => {}
+            }
+        }
+    }
+    (id, version_id)
+}
+
+/// The data stream for this OS: `ssg-ubuntu2204-ds.xml`, `ssg-rhel9-ds.xml`,
+/// `ssg-fedora-ds.xml`, ..., or the first one available.
+fn pick_datastream(files: &[String], os_id: &str, version_id: &str) -> Option<String> {
+    let major = version_id.split('.').next().unwrap_or("");
+    let candidates = [
+        format!("ssg-{}{}-ds.xml", os_id, version_id.replace('.', "")),
+        format!("ssg-{}{}-ds.xml", os_id, major),
+        format!("ssg-{}-ds.xml", os_id),
+    ];
+    candidates
+        .iter()
+        .find(|c| files.contains(c))
+        .cloned()
+        .or_else(|| {
+            let mut sorted = files.to_vec();
+            sorted.sort();
+            sorted.into_iter().next()
+        })
+}
+
+/// Parse `oscap info --profiles` output (`<id>:<title>` per line).
+fn list_profiles(datastream: &Path) -> Result<Vec<ScapProfile>> {
+    let output = Command::new(OSCAP)
+        .args(["info", "--profiles"])
+        .arg(datastream)
+        .output()
+        .context("Failed to run oscap")?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(parse_profiles(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_profiles(text: &str) -> Vec<ScapProfile> {
+    let mut profiles: Vec<ScapProfile> = text
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(id, _)| validate_profile_id(id.trim()).is_some())
+        .map(|(id, title)| ScapProfile {
+            id: id.trim().to_string(),
+            title: title.trim().to_string(),
+        })
+        .collect();
+    profiles.sort_by(|a, b| a.title.cmp(&b.title));
+    profiles.dedup_by(|a, b| a.id == b.id);
+    profiles
+}
+
+/// Parse an XCCDF results document: rule titles from the benchmark and
+/// outcomes from the test result.
+fn parse_results(xml: &str, profile: &str) -> Result<ComplianceReport> {
+    let mut titles: HashMap<String, String> = HashMap::new();
+    let mut report = ComplianceReport {
+        profile: profile.to_string(),
+        ..Default::default()
+    };
+    let mut saw_test_result = false;
+    // Rule whose title comes next, and rule-result being read
+    let mut current_rule: Option<String> = None;
+    let mut current_result: Option<(String, String)> = None;
+    let mut rest = xml;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        if let Some(after) = rest.strip_prefix("<!--") {
+            let end = after.find("-->").context("Unterminated comment")?;
+            rest = &after[end + 3..];
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix("<![CDATA[") {
+            let end = after.find("]]>").context("Unterminated CDATA section")?;
+            rest = &after[end + 3..];
+            continue;
+        }
+        let end = rest.find('>').context("Unterminated tag")?;
+        let raw = &rest[1..end];
+        rest = &rest[end + 1..];
+        if raw.starts_with('?') || raw.starts_with('!') {
+            continue;
+        }
+        if let Some(name) = raw.strip_prefix('/') {
+            match local_name(name.trim()) {
+                "Rule" => current_rule = None,
+                "rule-result" => current_result = None,
+                _ => {}
+            }
+            continue;
+        }
+
+        let self_closing = raw.ends_with('/');
+        let tag = raw.trim_end_matches('/').trim();
+        let (name, attrs) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+        match local_name(name) {
+            "Rule" if !self_closing => current_rule = attribute(attrs, "id"),
+            "title" if !self_closing => {
+                let text = element_text(&mut rest, name)?;
+                if let Some(rule) = current_rule.take() {
+                    titles.insert(rule, text);
+                }
+            }
+            "TestResult" => saw_test_result = true,
+            "rule-result" if !self_closing => {
+                let id = attribute(attrs, "idref").context("rule-result without idref")?;
+                let severity = attribute(attrs, "severity").unwrap_or_else(|| "unknown".into());
+                current_result = Some((id, severity));
+            }
+            "result" if !self_closing => {
+                let text = element_text(&mut rest, name)?;
+                if let Some((id, severity)) = current_result.take() {
+                    if let Some(outcome) = RuleOutcome::parse(&text) {
+                        report.results.push(RuleResult {
+                            title: String::new(),
+                            id,
+                            severity,
+                            outcome,
+                        });
+                    }
+                }
+            }
+            "score" if !self_closing && saw_test_result => {
+                let system = attribute(attrs, "system").unwrap_or_default();
+                let text = element_text(&mut rest, name)?;
+                if system.is_empty() || system == "urn:xccdf:scoring:default" {
+                    report.score = text.parse().ok().or(report.score);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !saw_test_result {
+        bail!("No test result in the oscap output");
+    }
+    for result in &mut report.results {
+        result.title = titles
+            .remove(&result.id)
+            .unwrap_or_else(|| result.short_id().replace('_', " "));
+    }
+    Ok(report)
+}
+
+/// Element name without its namespace prefix.
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Value of attribute `key` in a raw attribute list.
+fn attribute(attrs: &str, key: &str) -> Option<String> {
+    let mut rest = attrs;
+    loop {
+        rest = rest.trim_start();
+        let (name, after) = rest.split_once('=')?;
+        let after = after.trim_start();
+        let quote = after.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let value_end = after[1..].find(quote)?;
+        if name.trim() == key {
+            return Some(unescape(&after[1..1 + value_end]));
+        }
+        rest = &after[value_end + 2..];
+    }
+}
+
+/// Text of the element whose start tag was just read; nested markup is
+/// dropped and whitespace collapsed.
+fn element_text(rest: &mut &str, name: &str) -> Result<String> {
+    let close = format!("</{}>", name);
+    let end = rest
+        .find(&close)
+        .ok_or_else(|| anyhow!("Unterminated <{}>", name))?;
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in rest[..end].chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    *rest = &rest[end + close.len()..];
+    Ok(unescape(
+        &text.split_whitespace().collect::<Vec<_>>().join(" "),
+    ))
+}
+
+fn unescape(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESULTS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Benchmark xmlns="http://checklists.nist.gov/xccdf/1.2" id="xccdf_org.ssgproject.content_benchmark_FEDORA">
+  <title>Guide to the Secure Configuration of Fedora</title>
+  <Group id="xccdf_org.ssgproject.content_group_system">
+    <title>System Settings</title>
+    <Rule id="xccdf_org.ssgproject.content_rule_service_firewalld_enabled" selected="false" severity="medium">
+      <title xml:lang="en-US">Verify <html:code xmlns:html="http://www.w3.org/1999/xhtml">firewalld</html:code> Enabled</title>
+      <description><![CDATA[<title>not a title</title>]]></description>
+    </Rule>
+    <Rule id="xccdf_org.ssgproject.content_rule_sysctl_fs_suid_dumpable" selected="false" severity="medium">
+      <!-- <title>Commented</title> -->
+      <title>Disable Core Dumps for SUID &amp; SGID programs</title>
+    </Rule>
+    <Rule id="xccdf_org.ssgproject.content_rule_accounts_tmout" severity="low">
+      <title>Set Interactive Session Timeout</title>
+    </Rule>
+  </Group>
+  <TestResult id="xccdf_org.open-scap_testresult_cis">
+    <profile idref="xccdf_org.ssgproject.content_profile_cis"/>
+    <rule-result idref="xccdf_org.ssgproject.content_rule_service_firewalld_enabled" role="full" severity="medium" weight="1.000000">
+      <result>pass</result>
+    </rule-result>
+    <rule-result idref="xccdf_org.ssgproject.content_rule_sysctl_fs_suid_dumpable" severity="medium">
+      <result>fail</result>
+      <ident system="https://ncp.nist.gov/cce">CCE-80912-5</ident>
+    </rule-result>
+    <rule-result idref="xccdf_org.ssgproject.content_rule_accounts_tmout" severity="high">
+      <result>fail</result>
+    </rule-result>
+    <rule-result idref="xccdf_org.ssgproject.content_rule_package_telnet_removed" severity="low">
+      <result>notselected</result>
+    </rule-result>
+    <rule-result idref="xccdf_org.ssgproject.content_rule_audit_rules_mystery" severity="low">
+      <result>notapplicable</result>
+    </rule-result>
+    <score system="urn:xccdf:scoring:default" maximum="100.000000">66.666664</score>
+  </TestResult>
+</Benchmark>
+"#;
+
+    #[test]
+    fn test_parse_results() {
+        let report = parse_results(RESULTS, "cis").unwrap();
+        assert_eq!(report.results.len(), 4);
+        assert_eq!(report.count(RuleOutcome::Pass), 1);
+        assert_eq!(report.count(RuleOutcome::Fail), 2);
+        assert_eq!(report.count(RuleOutcome::NotApplicable), 1);
+        assert!((report.score.unwrap() - 66.67).abs() < 0.01);
+        assert_eq!(report.results[0].title, "Verify firewalld Enabled");
+        assert_eq!(
+            report.results[1].title,
+            "Disable Core Dumps for SUID & SGID programs"
+        );
+        // Rules missing from the benchmark fall back to their id
+        assert_eq!(report.results[3].title, "audit rules mystery");
+
+        let failed = report.failed();
+        assert_eq!(failed[0].short_id(), "accounts_tmout");
+        assert_eq!(failed[1].short_id(), "sysctl_fs_suid_dumpable");
+    }
+
+    #[test]
+    fn test_parse_results_requires_test_result() {
+        assert!(parse_results("<Benchmark><title>x</title></Benchmark>", "cis").is_err());
+        assert!(parse_results("<TestResult><result>pass", "cis").is_err());
+    }
+
+    #[test]
+    fn test_remediation() {
+        let rule = |id: &str| RuleResult {
+            id: format!("{}{}", RULE_PREFIX, id),
+            title: String::new(),
+            severity: "medium".to_string(),
+            outcome: RuleOutcome::Fail,
+        };
+        assert_eq!(
+            rule("coredump_disable_storage").remediation().unwrap().page,
+            "platform"
+        );
+        assert_eq!(rule("grub2_audit_argument").remediation(), Some(BOOT));
+        assert_eq!(rule("accounts_tmout").remediation(), None);
+        assert_eq!(rule("service_firewalld_enabled_extra").remediation(), None);
+    }
+
+    #[test]
+    fn test_parse_profiles() {
+        let text = "xccdf_org.ssgproject.content_profile_standard:Standard System Security Profile\n\
+                    xccdf_org.ssgproject.content_profile_cis_workstation_l1:CIS Workstation Level 1\n\
+                    Document type: Source Data Stream\n";
+        let profiles = parse_profiles(text);
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles[0].title, "CIS Workstation Level 1");
+    }
+
+    #[test]
+    fn test_pick_datastream() {
+        let files: Vec<String> = [
+            "ssg-fedora-ds.xml",
+            "ssg-rhel9-ds.xml",
+            "ssg-ubuntu2204-ds.xml",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        assert_eq!(
+            pick_datastream(&files, "ubuntu", "22.04").as_deref(),
+            Some("ssg-ubuntu2204-ds.xml")
+        );
+        assert_eq!(
+            pick_datastream(&files, "rhel", "9.3").as_deref(),
+            Some("ssg-rhel9-ds.xml")
+        );
+        assert_eq!(
+            pick_datastream(&files, "fedora", "40").as_deref(),
+            Some("ssg-fedora-ds.xml")
+        );
+        assert_eq!(
+            pick_datastream(&files, "arch", "").as_deref(),
+            Some("ssg-fedora-ds.xml")
+        );
+        assert_eq!(pick_datastream(&[], "fedora", "40"), None);
+    }
+
+    #[test]
+    fn test_validate_scan_arguments() {
+        assert!(validate_datastream("ssg-fedora-ds.xml").is_some());
+        assert!(validate_datastream("ssg-../../etc/shadow-ds.xml").is_none());
+        assert!(validate_datastream("fedora-ds.xml").is_none());
+        assert!(validate_profile_id("xccdf_org.ssgproject.content_profile_cis").is_some());
+        assert!(validate_profile_id("xccdf_org profile").is_none());
+        assert!(validate_profile_id("--remediate").is_none());
+    }
+
+    #[test]
+    fn test_os_release_ids() {
+        let (id, version) = os_release_ids("NAME=\"Ubuntu\"\nID=ubuntu\nVERSION_ID=\"22.04\"\n");
+        assert_eq!(id, "ubuntu");
+        assert_eq!(version, "22.04");
+    }
+}
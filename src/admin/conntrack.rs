@@ -0,0 +1,293 @@
+// Security Center - Connection Termination
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Terminating established connections from the Connections page.
+//!
+//! firewalld lets the packets of an established flow through because the
+//! kernel's connection tracking table remembers it. Terminating a
+//! connection deletes that entry with ctnetlink (`IPCTNL_MSG_CT_DELETE` on
+//! `NETLINK_NETFILTER`), so the firewall judges the next packet from the
+//! peer against the zone rules again, and closes the local socket with
+//! `ss -K` so the application sees the connection end instead of carrying
+//! on. Both need `CAP_NET_ADMIN` and run inside the privileged helper.
+//!
+//! An application is free to connect again; blocking the peer for good is
+//! a job for a rich rule.
+
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use netlink_packet_core::{NLM_F_ACK, NLM_F_REQUEST};
+use netlink_sys::{protocols::NETLINK_NETFILTER, Socket, SocketAddr as NetlinkAddr};
+
+use super::netlink::{self, put_attr, NLA_F_NESTED};
+use super::network::Protocol;
+use crate::helper::{run_privileged, HelperOp};
+
+// Netlink and ctnetlink constants (linux/netlink.h,
+// linux/netfilter/nfnetlink.h, linux/netfilter/nfnetlink_conntrack.h)
+const NFNETLINK_V0: u8 = 0;
+const NFNL_SUBSYS_CTNETLINK: u16 = 1;
+const IPCTNL_MSG_CT_DELETE: u16 = 2;
+const CTA_TUPLE_ORIG: u16 = 1;
+const CTA_TUPLE_IP: u16 = 1;
+const CTA_TUPLE_PROTO: u16 = 2;
+const CTA_IP_V4_SRC: u16 = 1;
+const CTA_IP_V4_DST: u16 = 2;
+const CTA_IP_V6_SRC: u16 = 3;
+const CTA_IP_V6_DST: u16 = 4;
+const CTA_PROTO_NUM: u16 = 1;
+const CTA_PROTO_SRC_PORT: u16 = 2;
+const CTA_PROTO_DST_PORT: u16 = 3;
+
+const AF_INET: u8 = 2;
+const AF_INET6: u8 = 10;
+const IPPROTO_TCP: u8 = 6;
+const IPPROTO_UDP: u8 = 17;
+const ENOENT: i32 = 2;
+
+/// pkexec resets PATH to the standard system directories.
+const SS: &str = "ss";
+
+/// One connection between a local and a remote socket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Flow {
+    pub protocol: Protocol,
+    pub local: SocketAddr,
+    pub remote: SocketAddr,
+}
+
+impl Flow {
+    /// Parse the helper argument form, `tcp,10.0.0.2:51000,203.0.113.7:443`.
+    /// Both ends must be of the same address family and have a port.
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.split(',');
+        let protocol = match parts.next()? {
+            "tcp" => Protocol::Tcp,
+            "udp" => Protocol::Udp,
+            _ => return None,
+        };
+        let local: SocketAddr = parts.next()?.parse().ok()?;
+        let remote: SocketAddr = parts.next()?.parse().ok()?;
+        if parts.next().is_some()
+            || local.is_ipv4() != remote.is_ipv4()
+            || local.port() == 0
+            || remote.port() == 0
+            || remote.ip().is_unspecified()
+        {
+            return None;
+        }
+        Some(Self {
+            protocol,
+            local,
+            remote,
+        })
+    }
+
+    fn protocol_number(&self) -> u8 {
+        match self.protocol {
+            Protocol::Tcp => IPPROTO_TCP,
+            Protocol::Udp => IPPROTO_UDP,
+        }
+    }
+}
+
+impl fmt::Display for Flow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let protocol = match self.protocol {
+            Protocol::Tcp => "tcp",
+            Protocol::Udp => "udp",
+        };
+        write!(f, "{},{},{}", protocol, self.local, self.remote)
+    }
+}
+
+/// Terminate `flows` through the privileged helper.
+pub fn terminate_connections(flows: &[Flow]) -> Result<String> {
+    if flows.is_empty() {
+        bail!("No connection to terminate");
+    }
+    run_privileged(&HelperOp::TerminateConnections {
+        flows: flows.to_vec(),
+    })
+}
+
+/// Delete the tracking entry of `flow` and close its local socket. Needs
+/// `CAP_NET_ADMIN`; runs inside the privileged helper. Returns whether a
+/// tracking entry was found.
+pub fn terminate_flow(flow: &Flow) -> Result<bool> {
+    let mut socket = Socket::new(NETLINK_NETFILTER)?;
+    socket.bind_auto()?;
+    socket.connect(&NetlinkAddr::new(0, 0))?;
+
+    // The entry is keyed by the direction that opened the connection, which
+    // is outbound for most flows and inbound for those to a local server
+    let mut found = false;
+    for (seq, (src, dst)) in [(flow.local, flow.remote), (flow.remote, flow.local)]
+        .into_iter()
+        .enumerate()
+    {
+        let request = delete_request(seq as u32 + 1, flow.protocol_number(), src, dst);
+        match netlink::request(&mut socket, seq as u32 + 1, &request) {
+            Ok(_) => found = true,
+            Err(e) if netlink::errno(&e) == Some(ENOENT) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    close_socket(flow)?;
+    Ok(found)
+}
+
+/// Close the local socket of `flow` with `ss -K`.
+fn close_socket(flow: &Flow) -> Result<()> {
+    let kind = match flow.protocol {
+        Protocol::Tcp => "-t",
+        Protocol::Udp => "-u",
+    };
+    let output = Command::new(SS)
+        .args(["-K", "-n", kind])
+        .arg("src")
+        .arg(ss_address(flow.local.ip()))
+        .arg("sport")
+        .arg("=")
+        .arg(format!(":{}", flow.local.port()))
+        .arg("dst")
+        .arg(ss_address(flow.remote.ip()))
+        .arg("dport")
+        .arg("=")
+        .arg(format!(":{}", flow.remote.port()))
+        .output()
+        .context("Failed to run ss")?;
+    if !output.status.success() {
+        bail!(
+            "Cannot close the socket: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// `ss` filters take IPv6 addresses in brackets, as they contain colons.
+fn ss_address(ip: IpAddr) -> String {
+    match ip {
+        IpAddr::V4(v4) => v4.to_string(),
+        IpAddr::V6(v6) => format!("[{}]", v6),
+    }
+}
+
+/// An `IPCTNL_MSG_CT_DELETE` request for the entry whose original direction
+/// runs from `src` to `dst`.
+fn delete_request(seq: u32, protocol: u8, src: SocketAddr, dst: SocketAddr) -> Vec<u8> {
+    let mut ip = Vec::new();
+    // Dual-stack sockets see IPv4 peers as mapped addresses; the kernel
+    // tracks those flows as IPv4
+    let family = match (canonical(src.ip()), canonical(dst.ip())) {
+        (IpAddr::V4(s), IpAddr::V4(d)) => {
+            put_attr(&mut ip, CTA_IP_V4_SRC, &s.octets());
+            put_attr(&mut ip, CTA_IP_V4_DST, &d.octets());
+            AF_INET
+        }
+        (s, d) => {
+            put_attr(&mut ip, CTA_IP_V6_SRC, &to_v6(s).octets());
+            put_attr(&mut ip, CTA_IP_V6_DST, &to_v6(d).octets());
+            AF_INET6
+        }
+    };
+    let mut proto = Vec::new();
+    put_attr(&mut proto, CTA_PROTO_NUM, &[protocol]);
+    // Ports are in network byte order
+    put_attr(&mut proto, CTA_PROTO_SRC_PORT, &src.port().to_be_bytes());
+    put_attr(&mut proto, CTA_PROTO_DST_PORT, &dst.port().to_be_bytes());
+
+    let mut tuple = Vec::new();
+    put_attr(&mut tuple, CTA_TUPLE_IP | NLA_F_NESTED, &ip);
+    put_attr(&mut tuple, CTA_TUPLE_PROTO | NLA_F_NESTED, &proto);
+    let mut attrs = Vec::new();
+    put_attr(&mut attrs, CTA_TUPLE_ORIG | NLA_F_NESTED, &tuple);
+
+    // nfgenmsg: family, version, resource id (big endian)
+    let mut body = vec![family, NFNETLINK_V0, 0, 0];
+    body.extend_from_slice(&attrs);
+    netlink::message(
+        NFNL_SUBSYS_CTNETLINK << 8 | IPCTNL_MSG_CT_DELETE,
+        NLM_F_REQUEST | NLM_F_ACK,
+        seq,
+        &body,
+    )
+}
+
+fn canonical(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+        IpAddr::V4(_) => ip,
+    }
+}
+
+fn to_v6(ip: IpAddr) -> std::net::Ipv6Addr {
+    match ip {
+        IpAddr::V4(v4) => v4.to_ipv6_mapped(),
+        IpAddr::V6(v6) => v6,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flow_round_trip() {
+        let flow = Flow::parse("tcp,192.168.1.5:51000,203.0.113.7:443").unwrap();
+        assert_eq!(flow.protocol, Protocol::Tcp);
+        assert_eq!(flow.remote.port(), 443);
+        assert_eq!(Flow::parse(&flow.to_string()), Some(flow));
+
+        let flow = Flow::parse("udp,[fe80::1]:5353,[2001:db8::7]:53").unwrap();
+        assert_eq!(Flow::parse(&flow.to_string()), Some(flow));
+    }
+
+    #[test]
+    fn test_flow_rejects_invalid() {
+        assert!(Flow::parse("icmp,10.0.0.1:1,10.0.0.2:2").is_none());
+        assert!(Flow::parse("tcp,10.0.0.1:1").is_none());
+        assert!(Flow::parse("tcp,10.0.0.1:1,10.0.0.2:2,extra").is_none());
+        assert!(Flow::parse("tcp,10.0.0.1:0,10.0.0.2:2").is_none());
+        assert!(Flow::parse("tcp,10.0.0.1:1,[::1]:2").is_none());
+        assert!(Flow::parse("tcp,10.0.0.1:1,0.0.0.0:2").is_none());
+        assert!(Flow::parse("tcp,10.0.0.1:1,10.0.0.2:2 --all").is_none());
+    }
+
+    #[test]
+    fn test_delete_request_layout() {
+        let src: SocketAddr = "10.0.0.1:51000".parse().unwrap();
+        let dst: SocketAddr = "10.0.0.2:443".parse().unwrap();
+        let msg = delete_request(7, IPPROTO_TCP, src, dst);
+
+        assert_eq!(
+            u32::from_ne_bytes(msg[0..4].try_into().unwrap()) as usize,
+            msg.len()
+        );
+        assert_eq!(u16::from_ne_bytes(msg[4..6].try_into().unwrap()), 0x0102);
+        assert_eq!(u32::from_ne_bytes(msg[8..12].try_into().unwrap()), 7);
+        assert_eq!(msg[16], AF_INET);
+        // CTA_TUPLE_ORIG > CTA_TUPLE_IP > source address
+        assert_eq!(
+            u16::from_ne_bytes(msg[22..24].try_into().unwrap()),
+            CTA_TUPLE_ORIG | NLA_F_NESTED
+        );
+        assert_eq!(&msg[32..36], &[10, 0, 0, 1]);
+        assert_eq!(&msg[40..44], &[10, 0, 0, 2]);
+        // The ports are big endian
+        let tail = &msg[msg.len() - 16..];
+        assert_eq!(&tail[4..6], &51000u16.to_be_bytes());
+        assert_eq!(&tail[12..14], &443u16.to_be_bytes());
+
+        // Mapped IPv4 peers of a dual-stack socket are looked up as IPv4
+        let src: SocketAddr = "[::ffff:10.0.0.1]:51000".parse().unwrap();
+        let dst: SocketAddr = "[::ffff:10.0.0.2]:443".parse().unwrap();
+        assert_eq!(delete_request(7, IPPROTO_TCP, src, dst), msg);
+    }
+}
@@ -0,0 +1,272 @@
+// Security Center - Core Dump Policy
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Core dump policy: whether the memory of crashing programs is kept and
+//! who can read it.
+//!
+//! A core dump contains everything the process had in memory, including
+//! passwords, keys and document contents. The relevant settings are:
+//!
+//! - `kernel.core_pattern`: a pipe to `systemd-coredump`, another handler or
+//!   a plain file name.
+//! - `Storage=` / `ProcessSizeMax=` in `coredump.conf`: whether
+//!   systemd-coredump writes dumps to `/var/lib/systemd/coredump`, to the
+//!   journal or nowhere.
+//! - `fs.suid_dumpable`: whether set-UID programs may dump core at all.
+//! - The core file size limit services inherit (`DefaultLimitCORE=`).
+//!
+//! Restrictions are written by the privileged helper (see `crate::helper`).
+
+use std::fs;
+use std::path::Path;
+
+use super::assessment::{Finding, FindingCategory, Severity};
+use super::logging::systemd_config_texts;
+use crate::systemd::SystemdClient;
+
+const CORE_PATTERN: &str = "/proc/sys/kernel/core_pattern";
+const SUID_DUMPABLE: &str = "/proc/sys/fs/suid_dumpable";
+const COREDUMP_DIR: &str = "/var/lib/systemd/coredump";
+
+/// Core dump settings in effect.
+#[derive(Debug, Clone, Default)]
+pub struct CoredumpStatus {
+    /// `kernel.core_pattern`.
+    pub core_pattern: String,
+    /// Effective systemd-coredump `Storage=` (`external` when unset).
+    pub storage: String,
+    /// Effective systemd-coredump `ProcessSizeMax=`, `None` for the default.
+    pub process_size_max: Option<String>,
+    /// `fs.suid_dumpable` (0 = off, 1 = unrestricted, 2 = root-readable only).
+    pub suid_dumpable: Option<u32>,
+    /// Soft core size limit services start with; `None` when unknown,
+    /// `u64::MAX` for unlimited.
+    pub service_core_limit: Option<u64>,
+    /// Dumps currently kept in `/var/lib/systemd/coredump`.
+    pub stored_dumps: usize,
+}
+
+impl CoredumpStatus {
+    /// Crashes are handled by systemd-coredump.
+    pub fn uses_systemd_coredump(&self) -> bool {
+        self.core_pattern.starts_with('|') && self.core_pattern.contains("systemd-coredump")
+    }
+
+    /// Crashes are piped to some other handler (apport, abrt, ...).
+    pub fn uses_other_handler(&self) -> bool {
+        self.core_pattern.starts_with('|') && !self.uses_systemd_coredump()
+    }
+
+    /// Dumps of crashing programs end up somewhere readable.
+    pub fn dumps_stored(&self) -> bool {
+        if self.uses_systemd_coredump() {
+            self.storage != "none" && self.process_size_max.as_deref() != Some("0")
+        } else {
+            // Plain file pattern or another handler: governed by the size limit
+            self.service_core_limit != Some(0)
+        }
+    }
+
+    /// Storage is off and set-UID programs cannot dump core.
+    pub fn restricted(&self) -> bool {
+        !self.dumps_stored() && self.suid_dumpable != Some(1)
+    }
+}
+
+/// Read the core dump policy.
+pub fn query_coredump() -> CoredumpStatus {
+    let mut storage: Option<String> = None;
+    let mut process_size_max: Option<String> = None;
+    for text in systemd_config_texts("coredump") {
+        let (s, m) = parse_coredump_conf(&text);
+        storage = s.or(storage);
+        process_size_max = m.or(process_size_max);
+    }
+
+    let service_core_limit = {
+        let mut client = SystemdClient::new();
+        client
+            .connect()
+            .ok()
+            .and_then(|()| client.default_core_limit().ok())
+    };
+
+    CoredumpStatus {
+        core_pattern: read_trimmed(CORE_PATTERN).unwrap_or_default(),
+        storage: storage.unwrap_or_else(|| "external".to_string()),
+        process_size_max,
+        suid_dumpable: read_trimmed(SUID_DUMPABLE).and_then(|v| v.parse().ok()),
+        service_core_limit,
+        stored_dumps: fs::read_dir(COREDUMP_DIR)
+            .map(|entries| entries.flatten().count())
+            .unwrap_or(0),
+    }
+}
+
+/// Assessment findings for the core dump policy.
+pub(super) fn check_coredump() -> Vec<Finding> {
+    coredump_findings(&query_coredump())
+}
+
+fn coredump_findings(status: &CoredumpStatus) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    if status.suid_dumpable == Some(1) {
+        findings.push(Finding {
+            id: "coredump.suid-dumpable".to_string(),
+            category: FindingCategory::CoreDumps,
+            severity: Severity::High,
+            title: "Set-UID programs can dump core".to_string(),
+            detail: "fs.suid_dumpable is 1: when a privileged program crashes, its memory \
+                     (which may include password hashes or keys) is dumped with the \
+                     permissions of the user who started it."
+                .to_string(),
+            remediation: "Use \"Restrict Core Dumps\" in the Core Dumps section, or set \
+                          \"fs.suid_dumpable = 0\" in a file under /etc/sysctl.d."
+                .to_string(),
+        });
+    }
+
+    if status.dumps_stored() {
+        let (where_, readers) = if !status.uses_systemd_coredump() {
+            if status.uses_other_handler() {
+                (
+                    "to a crash reporting handler",
+                    "Depending on the handler, they may be readable by administrators or sent \
+                     to a bug tracker.",
+                )
+            } else {
+                (
+                    "next to the crashing program",
+                    "They are readable by the user the program ran as.",
+                )
+            }
+        } else if status.storage == "journal" {
+            (
+                "in the system journal",
+                "Anyone allowed to read the system journal (wheel, adm or systemd-journal \
+                 group members) can read them.",
+            )
+        } else {
+            (
+                "under /var/lib/systemd/coredump",
+                "Each dump is readable by the user the program ran as and by root.",
+            )
+        };
+        findings.push(Finding {
+            id: "coredump.storage".to_string(),
+            category: FindingCategory::CoreDumps,
+            severity: Severity::Low,
+            title: "Crash dumps are stored".to_string(),
+            detail: format!(
+                "The memory of crashing programs is written {}. {} Dumps can contain \
+                 passwords, keys and document contents.",
+                where_, readers
+            ),
+            remediation: "Use \"Restrict Core Dumps\" in the Core Dumps section, or set \
+                          \"Storage=none\" and \"ProcessSizeMax=0\" in \
+                          /etc/systemd/coredump.conf. Keep dumps only while debugging a \
+                          crash."
+                .to_string(),
+        });
+    }
+
+    findings
+}
+
+/// Extract `Storage=` and `ProcessSizeMax=` from the `[Coredump]` section.
+fn parse_coredump_conf(text: &str) -> (Option<String>, Option<String>) {
+    let mut in_coredump = false;
+    let mut storage = None;
+    let mut size_max = None;
+    for line in text.lines().map(str::trim) {
+        if line.starts_with('#') || line.starts_with(';') || line.is_empty() {
+            continue;
+        }
+        if line.starts_with('[') {
+            in_coredump = line == "[Coredump]";
+            continue;
+        }
+        if !in_coredump {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        match key.trim() {
+            "Storage" if !value.is_empty() => storage = Some(value),
+            "ProcessSizeMax" if !value.is_empty() => size_max = Some(value),
+            _ => {}
+        }
+    }
+    (storage, size_max)
+}
+
+fn read_trimmed(path: &str) -> Option<String> {
+    fs::read_to_string(Path::new(path))
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn systemd_status() -> CoredumpStatus {
+        CoredumpStatus {
+            core_pattern: "|/usr/lib/systemd/systemd-coredump %P %u %g %s %t %c %h".to_string(),
+            storage: "external".to_string(),
+            suid_dumpable: Some(2),
+            service_core_limit: Some(u64::MAX),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_coredump_conf() {
+        let conf = "[Coredump]\n#Storage=external\nStorage=none\nProcessSizeMax=0\n";
+        assert_eq!(
+            parse_coredump_conf(conf),
+            (Some("none".to_string()), Some("0".to_string()))
+        );
+        assert_eq!(parse_coredump_conf("Storage=none\n"), (None, None));
+    }
+
+    #[test]
+    fn test_dumps_stored() {
+        let mut status = systemd_status();
+        assert!(status.dumps_stored());
+        status.storage = "none".to_string();
+        assert!(!status.dumps_stored());
+        assert!(status.restricted());
+
+        // A plain file pattern is governed by the core size limit
+        let mut status = CoredumpStatus {
+            core_pattern: "core".to_string(),
+            service_core_limit: Some(0),
+            ..Default::default()
+        };
+        assert!(!status.dumps_stored());
+        status.service_core_limit = Some(u64::MAX);
+        assert!(status.dumps_stored());
+    }
+
+    #[test]
+    fn test_coredump_findings() {
+        let mut status = systemd_status();
+        let ids: Vec<String> = coredump_findings(&status)
+            .into_iter()
+            .map(|f| f.id)
+            .collect();
+        assert_eq!(ids, vec!["coredump.storage"]);
+
+        status.suid_dumpable = Some(1);
+        status.process_size_max = Some("0".to_string());
+        let findings = coredump_findings(&status);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::High);
+    }
+}
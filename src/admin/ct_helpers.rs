@@ -0,0 +1,224 @@
+// Security Center - Connection Tracking Helpers
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Connection tracking helper policy.
+//!
+//! A conntrack helper reads the payload of a protocol such as FTP or SIP
+//! to find the extra connections it negotiates, and lets those in as
+//! "related". Helpers parse untrusted traffic in the kernel and have been
+//! abused to open ports an attacker chose (NAT slipstreaming), so they
+//! should only run for the services that need them.
+//!
+//! Older kernels attached every loaded helper to matching traffic on its
+//! own (`net.netfilter.nf_conntrack_helper`), and firewalld before 1.0
+//! could ask for that with `AutomaticHelpers`. Both are read from files
+//! here; firewalld services attach helpers explicitly, which is the
+//! recommended way.
+
+use std::fs;
+
+use super::assessment::{Finding, FindingCategory, Severity};
+
+/// firewalld's main configuration file.
+const FIREWALLD_CONF: &str = "/etc/firewalld/firewalld.conf";
+/// Kernel switch for automatic helper assignment, gone since Linux 6.0.
+const KERNEL_AUTO_HELPER: &str = "/proc/sys/net/netfilter/nf_conntrack_helper";
+const PROC_MODULES: &str = "/proc/modules";
+
+/// Conntrack helpers the kernel ships, by the name firewalld uses.
+const KNOWN_HELPERS: &[&str] = &[
+    "amanda",
+    "ftp",
+    "h323",
+    "irc",
+    "netbios_ns",
+    "pptp",
+    "sane",
+    "sip",
+    "snmp",
+    "tftp",
+];
+
+/// How helpers get attached to traffic on this system.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HelperPolicy {
+    /// firewalld's `AutomaticHelpers` (`yes`, `no` or `system`); `None` when
+    /// unset or when firewalld no longer has the setting.
+    pub firewalld_setting: Option<String>,
+    /// The kernel's automatic assignment, `None` on kernels without it.
+    pub kernel_auto: Option<bool>,
+    /// Helper modules currently loaded, e.g. `ftp`.
+    pub loaded: Vec<String>,
+}
+
+impl HelperPolicy {
+    /// Whether loaded helpers inspect matching traffic without a rule
+    /// asking for them.
+    pub fn automatic(&self) -> bool {
+        match self.firewalld_setting.as_deref() {
+            Some("yes") => true,
+            Some("no") => false,
+            _ => self.kernel_auto.unwrap_or(false),
+        }
+    }
+}
+
+/// Read the helper policy. Performs file reads only.
+pub fn query_helper_policy() -> HelperPolicy {
+    HelperPolicy {
+        firewalld_setting: fs::read_to_string(FIREWALLD_CONF)
+            .ok()
+            .and_then(|text| conf_value(&text, "AutomaticHelpers")),
+        kernel_auto: fs::read_to_string(KERNEL_AUTO_HELPER)
+            .ok()
+            .map(|value| value.trim() == "1"),
+        loaded: fs::read_to_string(PROC_MODULES)
+            .map(|text| loaded_helpers(&text))
+            .unwrap_or_default(),
+    }
+}
+
+/// Value of `key` in a `KEY=value` file such as firewalld.conf.
+pub(super) fn conf_value(text: &str, key: &str) -> Option<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .filter(|(k, _)| k.trim() == key)
+        .map(|(_, v)| v.trim().trim_matches('"').to_lowercase())
+        .next_back()
+        .filter(|v| !v.is_empty())
+}
+
+/// Helper names among the modules listed in `/proc/modules`.
+fn loaded_helpers(modules: &str) -> Vec<String> {
+    let mut helpers: Vec<String> = modules
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .filter_map(|module| module.strip_prefix("nf_conntrack_"))
+        .filter(|name| KNOWN_HELPERS.contains(name))
+        .map(String::from)
+        .collect();
+    helpers.sort();
+    helpers.dedup();
+    helpers
+}
+
+/// What a helper does and why it is a risk, for the UI.
+pub fn helper_description(name: &str) -> &'static str {
+    // A firewalld helper may carry a port suffix, e.g. `ftp` for `nf_conntrack_ftp`
+    let base = name
+        .trim_start_matches("nf_conntrack_")
+        .split(['-', '_'])
+        .next()
+        .unwrap_or(name);
+    match base {
+        "ftp" => {
+            "Opens the data ports of active and passive FTP. A client tricked into \
+             sending crafted FTP commands can open arbitrary ports back to it."
+        }
+        "tftp" => "Lets TFTP transfers reply from a new port.",
+        "sip" => {
+            "Opens the media ports SIP calls negotiate. Abused by NAT slipstreaming to \
+             reach any port on a machine behind the firewall."
+        }
+        "h323" => "Opens the ports of H.323 video calls; a large, rarely needed parser.",
+        "irc" => "Opens ports for IRC direct file transfers (DCC).",
+        "pptp" => "Tracks the GRE tunnels of PPTP VPNs, which are obsolete and insecure.",
+        "amanda" => "Opens the data ports of the Amanda backup client.",
+        "netbios" => "Lets NetBIOS name service broadcasts be answered.",
+        "snmp" => "Rewrites addresses inside SNMP packets behind NAT.",
+        "sane" => "Opens the data port of network scanners using SANE.",
+        _ => "Inspects traffic of this protocol to open the connections it negotiates.",
+    }
+}
+
+/// Assessment findings for the helper policy.
+pub(super) fn check_helpers() -> Vec<Finding> {
+    helper_findings(&query_helper_policy())
+}
+
+fn helper_findings(policy: &HelperPolicy) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    if policy.automatic() {
+        let source = if policy.firewalld_setting.as_deref() == Some("yes") {
+            "firewalld's AutomaticHelpers is \"yes\""
+        } else {
+            "net.netfilter.nf_conntrack_helper is 1"
+        };
+        findings.push(Finding {
+            id: "firewall.helpers.automatic".to_string(),
+            category: FindingCategory::Firewall,
+            severity: Severity::Medium,
+            title: "Conntrack helpers are assigned automatically".to_string(),
+            detail: format!(
+                "{}: every loaded helper inspects matching traffic on any interface, so a \
+                 crafted FTP or SIP packet can open ports no rule allows.",
+                source
+            ),
+            remediation: "Set Automatic Helpers to Off in the Connection Tracking Helpers \
+                          section of the Services page, or set \"AutomaticHelpers=no\" in \
+                          /etc/firewalld/firewalld.conf. Services that need a helper attach \
+                          it themselves."
+                .to_string(),
+        });
+    }
+    if !policy.loaded.is_empty() {
+        findings.push(Finding {
+            id: "firewall.helpers.loaded".to_string(),
+            category: FindingCategory::Firewall,
+            severity: Severity::Info,
+            title: "Conntrack helpers are loaded".to_string(),
+            detail: format!(
+                "Loaded helpers: {}. They parse untrusted traffic in the kernel for the \
+                 services that use them.",
+                policy.loaded.join(", ")
+            ),
+            remediation: "Disable services such as ftp or sip in zones that do not need \
+                          them; their helper then stops inspecting traffic."
+                .to_string(),
+        });
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_helper_policy() {
+        let conf = "# AutomaticHelpers=yes\nDefaultZone=public\nAutomaticHelpers=\"System\"\n";
+        assert_eq!(
+            conf_value(conf, "AutomaticHelpers").as_deref(),
+            Some("system")
+        );
+        assert_eq!(conf_value("AutomaticHelpers=\n", "AutomaticHelpers"), None);
+
+        let modules = "nf_nat_ftp 20480 0 - Live 0x0\n\
+                       nf_conntrack_ftp 28672 1 nf_nat_ftp, Live 0x0\n\
+                       nf_conntrack_netlink 57344 0 - Live 0x0\n\
+                       nf_conntrack 184320 4 nf_nat_ftp, Live 0x0\n";
+        assert_eq!(loaded_helpers(modules), vec!["ftp"]);
+
+        let mut policy = HelperPolicy {
+            firewalld_setting: Some("system".to_string()),
+            kernel_auto: Some(true),
+            loaded: vec!["ftp".to_string()],
+        };
+        assert!(policy.automatic());
+        let ids: Vec<String> = helper_findings(&policy).into_iter().map(|f| f.id).collect();
+        assert_eq!(
+            ids,
+            vec!["firewall.helpers.automatic", "firewall.helpers.loaded"]
+        );
+
+        policy.firewalld_setting = Some("no".to_string());
+        assert!(!policy.automatic());
+        policy.firewalld_setting = None;
+        policy.kernel_auto = None;
+        policy.loaded.clear();
+        assert!(helper_findings(&policy).is_empty());
+    }
+}
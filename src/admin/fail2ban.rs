@@ -0,0 +1,719 @@
+// Security Center - fail2ban
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! fail2ban jails, the addresses they ban and unbanning an address.
+//!
+//! fail2ban has no D-Bus API; `fail2ban-client` talks to the server over a
+//! Unix socket, sending each command as a pickled list of strings followed
+//! by an end marker, and reading back a pickled `(code, value)` tuple.
+//! [`Fail2banClient`] speaks the same protocol, with just enough of the
+//! pickle format for the replies of `status` and `set <jail> unbanip`.
+//!
+//! The socket is normally only open to root. When it cannot be opened, the
+//! jails are read and addresses unbanned through the privileged helper.
+
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::IpAddr;
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::helper::{run_privileged, HelperOp};
+
+/// The fail2ban server's command socket.
+pub const FAIL2BAN_SOCKET: &str = "/run/fail2ban/fail2ban.sock";
+/// Present when fail2ban is installed.
+const FAIL2BAN_PATHS: [&str; 3] = [
+    "/etc/fail2ban/jail.conf",
+    "/usr/bin/fail2ban-server",
+    "/usr/sbin/fail2ban-server",
+];
+/// Ends every command and reply on the socket.
+const END_COMMAND: &[u8] = b"<F2B_END_COMMAND>";
+/// Sent before closing the connection.
+const CLOSE_COMMAND: &[u8] = b"<F2B_CLOSE_COMMAND>";
+/// Largest reply read; a jail banning many addresses lists them all.
+const MAX_REPLY: usize = 16 * 1024 * 1024;
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(10);
+/// Longest jail name accepted for unbanning.
+const MAX_JAIL_NAME: usize = 64;
+
+/// A fail2ban jail and its counters.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Jail {
+    pub name: String,
+    /// Addresses with failures that have not been banned yet.
+    pub currently_failed: u64,
+    pub total_failed: u64,
+    pub currently_banned: u64,
+    /// Bans since the server started.
+    pub total_banned: u64,
+    /// Addresses banned now.
+    pub banned: Vec<String>,
+}
+
+/// What can be shown of fail2ban.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fail2banStatus {
+    NotInstalled,
+    /// Installed, but the server is not running.
+    NotRunning,
+    /// The server is running and its socket is only open to root.
+    NeedsAuthorization,
+    Running(Vec<Jail>),
+}
+
+/// Whether fail2ban is installed.
+pub fn is_installed() -> bool {
+    FAIL2BAN_PATHS.iter().any(|path| Path::new(path).exists())
+}
+
+/// The state of fail2ban and, when its socket can be opened, its jails.
+pub fn query_fail2ban() -> Result<Fail2banStatus> {
+    match open_socket() {
+        Ok(stream) => Ok(Fail2banStatus::Running(
+            Fail2banClient::from_stream(stream).jails()?,
+        )),
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+            Ok(Fail2banStatus::NeedsAuthorization)
+        }
+        Err(e)
+            if matches!(
+                e.kind(),
+                io::ErrorKind::NotFound | io::ErrorKind::ConnectionRefused
+            ) =>
+        {
+            if is_installed() {
+                Ok(Fail2banStatus::NotRunning)
+            } else {
+                Ok(Fail2banStatus::NotInstalled)
+            }
+        }
+        Err(e) => Err(e).context("Cannot connect to fail2ban"),
+    }
+}
+
+/// Read the jails through the privileged helper.
+pub fn read_jails_privileged() -> Result<Vec<Jail>> {
+    let json = run_privileged(&HelperOp::ReadFail2ban)?;
+    serde_json::from_str(&json).context("Invalid fail2ban output")
+}
+
+/// Print the jails as JSON. Runs as root inside the helper process.
+pub fn read_jails_json() -> Result<String> {
+    let jails = Fail2banClient::connect()?.jails()?;
+    Ok(serde_json::to_string(&jails)?)
+}
+
+/// Unban `ip` in `jail`, through the privileged helper when the socket is
+/// only open to root.
+pub fn unban_ip(jail: &str, ip: IpAddr) -> Result<()> {
+    let jail = validate_jail_name(jail).ok_or_else(|| anyhow!("Invalid jail: {}", jail))?;
+    match open_socket() {
+        Ok(stream) => Fail2banClient::from_stream(stream).unban(jail, ip),
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+            run_privileged(&HelperOp::Fail2banUnban {
+                jail: jail.to_string(),
+                ip,
+            })?;
+            Ok(())
+        }
+        Err(e) => Err(e).context("Cannot connect to fail2ban"),
+    }
+}
+
+/// Validate a jail name: letters, digits, `-`, `_` and `.`.
+pub fn validate_jail_name(name: &str) -> Option<&str> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_JAIL_NAME
+        && !name.starts_with(['-', '.'])
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    valid.then_some(name)
+}
+
+fn open_socket() -> io::Result<UnixStream> {
+    let stream = UnixStream::connect(FAIL2BAN_SOCKET)?;
+    stream.set_read_timeout(Some(SOCKET_TIMEOUT))?;
+    stream.set_write_timeout(Some(SOCKET_TIMEOUT))?;
+    Ok(stream)
+}
+
+/// A connection to the fail2ban server's command socket.
+pub struct Fail2banClient {
+    stream: UnixStream,
+}
+
+impl Fail2banClient {
+    /// Connect to [`FAIL2BAN_SOCKET`].
+    pub fn connect() -> Result<Self> {
+        let stream = open_socket().context("Cannot connect to fail2ban")?;
+        Ok(Self::from_stream(stream))
+    }
+
+    fn from_stream(stream: UnixStream) -> Self {
+        Self { stream }
+    }
+
+    /// Send one command and return the value of its reply, or the error the
+    /// server reported.
+    fn command(&mut self, args: &[&str]) -> Result<Pickle> {
+        let mut request = pickle_command(args);
+        request.extend_from_slice(END_COMMAND);
+        self.stream.write_all(&request)?;
+
+        let mut reply = Vec::new();
+        let mut buf = [0u8; 8192];
+        while !reply.ends_with(END_COMMAND) {
+            let n = self.stream.read(&mut buf)?;
+            if n == 0 {
+                bail!("fail2ban closed the connection");
+            }
+            reply.extend_from_slice(&buf[..n]);
+            if reply.len() > MAX_REPLY {
+                bail!("fail2ban reply is too large");
+            }
+        }
+        reply.truncate(reply.len() - END_COMMAND.len());
+
+        match unpickle(&reply)? {
+            Pickle::List(mut items) if items.len() == 2 => {
+                let value = items.pop().unwrap_or(Pickle::None);
+                match items[0].as_int() {
+                    Some(0) => Ok(value),
+                    _ => Err(anyhow!(
+                        "fail2ban: {}",
+                        value
+                            .as_text()
+                            .unwrap_or_else(|| "command failed".to_string())
+                    )),
+                }
+            }
+            _ => bail!("Unexpected reply from fail2ban"),
+        }
+    }
+
+    /// Names of the running jails.
+    pub fn jail_names(&mut self) -> Result<Vec<String>> {
+        let status = self.command(&["status"])?;
+        let list = status
+            .field("Jail list")
+            .and_then(Pickle::as_text)
+            .unwrap_or_default();
+        Ok(split_jail_list(&list))
+    }
+
+    /// Counters and banned addresses of one jail.
+    pub fn jail(&mut self, name: &str) -> Result<Jail> {
+        let status = self.command(&["status", name])?;
+        Ok(parse_jail_status(name, &status))
+    }
+
+    /// Every running jail.
+    pub fn jails(&mut self) -> Result<Vec<Jail>> {
+        let names = self.jail_names()?;
+        names.iter().map(|name| self.jail(name)).collect()
+    }
+
+    /// Lift the ban on `ip` in `jail`.
+    pub fn unban(&mut self, jail: &str, ip: IpAddr) -> Result<()> {
+        self.command(&["set", jail, "unbanip", &ip.to_string()])?;
+        Ok(())
+    }
+}
+
+impl Drop for Fail2banClient {
+    fn drop(&mut self) {
+        let _ = self.stream.write_all(CLOSE_COMMAND);
+    }
+}
+
+fn split_jail_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Read the reply of `status <jail>`:
+/// `[("Filter", [("Currently failed", n), ...]), ("Actions", [...])]`.
+fn parse_jail_status(name: &str, status: &Pickle) -> Jail {
+    let count = |section: &str, key: &str| {
+        status
+            .field(section)
+            .and_then(|s| s.field(key))
+            .and_then(Pickle::as_int)
+            .and_then(|n| u64::try_from(n).ok())
+            .unwrap_or(0)
+    };
+    let banned = status
+        .field("Actions")
+        .and_then(|actions| actions.field("Banned IP list"))
+        .map(|list| match list {
+            Pickle::List(items) => items.iter().filter_map(Pickle::as_text).collect(),
+            // Older servers join the addresses into one string
+            other => other
+                .as_text()
+                .map(|text| text.split_whitespace().map(str::to_string).collect())
+                .unwrap_or_default(),
+        })
+        .unwrap_or_default();
+    Jail {
+        name: name.to_string(),
+        currently_failed: count("Filter", "Currently failed"),
+        total_failed: count("Filter", "Total failed"),
+        currently_banned: count("Actions", "Currently banned"),
+        total_banned: count("Actions", "Total banned"),
+        banned,
+    }
+}
+
+/// A decoded pickle value, covering what fail2ban replies with. Tuples
+/// are read as lists.
+#[derive(Debug, Clone, PartialEq)]
+enum Pickle {
+    None,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    List(Vec<Pickle>),
+    Dict(Vec<(Pickle, Pickle)>),
+    /// A class or function, by its dotted name.
+    Global(String),
+    /// An instance, such as an exception or a fail2ban `IPAddr`.
+    Object {
+        class: String,
+        args: Vec<Pickle>,
+        state: Box<Pickle>,
+    },
+}
+
+impl Pickle {
+    fn as_int(&self) -> Option<i64> {
+        match self {
+            Pickle::Int(n) => Some(*n),
+            Pickle::Bool(b) => Some(i64::from(*b)),
+            _ => None,
+        }
+    }
+
+    /// The value as text. An object is shown by its first text argument,
+    /// or the `_raw` string fail2ban keeps in an `IPAddr`'s state.
+    fn as_text(&self) -> Option<String> {
+        match self {
+            Pickle::Text(text) => Some(text.clone()),
+            Pickle::Int(n) => Some(n.to_string()),
+            Pickle::Float(f) => Some(f.to_string()),
+            Pickle::Object { args, state, .. } => args
+                .iter()
+                .find_map(|arg| match arg {
+                    Pickle::Text(text) => Some(text.clone()),
+                    _ => None,
+                })
+                .or_else(|| state.find_key("_raw").and_then(Pickle::as_text)),
+            _ => None,
+        }
+    }
+
+    /// The value for `key` in a list of `(key, value)` pairs or a dict.
+    fn field(&self, key: &str) -> Option<&Pickle> {
+        match self {
+            Pickle::List(items) => items.iter().find_map(|item| match item {
+                Pickle::List(pair) if pair.len() == 2 && pair[0].is_text(key) => Some(&pair[1]),
+                _ => None,
+            }),
+            Pickle::Dict(entries) => entries.iter().find(|(k, _)| k.is_text(key)).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// `key` in this dict or, for slotted state `(dict, slots)`, in either.
+    fn find_key(&self, key: &str) -> Option<&Pickle> {
+        match self {
+            Pickle::Dict(_) => self.field(key),
+            Pickle::List(items) => items.iter().find_map(|item| item.find_key(key)),
+            _ => None,
+        }
+    }
+
+    fn is_text(&self, text: &str) -> bool {
+        matches!(self, Pickle::Text(t) if t == text)
+    }
+}
+
+/// Encode a command as fail2ban-client does: a list of strings, protocol 2.
+fn pickle_command(args: &[&str]) -> Vec<u8> {
+    let mut out = vec![0x80, 2, b']', b'('];
+    for arg in args {
+        out.push(b'X');
+        out.extend_from_slice(&(arg.len() as u32).to_le_bytes());
+        out.extend_from_slice(arg.as_bytes());
+    }
+    out.extend_from_slice(b"e.");
+    out
+}
+
+struct PickleReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> PickleReader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.data.len())
+            .ok_or_else(|| anyhow!("Truncated pickle"))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    fn text(&mut self, len: usize) -> Result<String> {
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).context("Invalid text in pickle")
+    }
+
+    fn line(&mut self) -> Result<String> {
+        let rest = &self.data[self.pos..];
+        let len = rest
+            .iter()
+            .position(|&b| b == b'\n')
+            .ok_or_else(|| anyhow!("Truncated pickle"))?;
+        let text = self.text(len)?;
+        self.pos += 1;
+        Ok(text)
+    }
+}
+
+/// Decode a pickle of protocol 2 to 5, without running anything it names.
+/// The memo holds copies, so a value changed after it was memoized is seen
+/// as it was then; fail2ban's replies do not share values that way.
+fn unpickle(data: &[u8]) -> Result<Pickle> {
+    let mut reader = PickleReader { data, pos: 0 };
+    let mut stack: Vec<Pickle> = Vec::new();
+    let mut marks: Vec<usize> = Vec::new();
+    let mut memo: HashMap<u32, Pickle> = HashMap::new();
+
+    fn pop(stack: &mut Vec<Pickle>) -> Result<Pickle> {
+        stack.pop().ok_or_else(|| anyhow!("Pickle stack underflow"))
+    }
+    fn pop_mark(stack: &mut Vec<Pickle>, marks: &mut Vec<usize>) -> Result<Vec<Pickle>> {
+        let mark = marks
+            .pop()
+            .filter(|&m| m <= stack.len())
+            .ok_or_else(|| anyhow!("Pickle mark missing"))?;
+        Ok(stack.split_off(mark))
+    }
+    fn object(class: Pickle, args: Pickle) -> Pickle {
+        let class = match class {
+            Pickle::Global(name) => name,
+            _ => String::new(),
+        };
+        let args = match args {
+            Pickle::List(items) => items,
+            other => vec![other],
+        };
+        Pickle::Object {
+            class,
+            args,
+            state: Box::new(Pickle::None),
+        }
+    }
+
+    loop {
+        let op = reader.byte()?;
+        match op {
+            // PROTO, FRAME
+            0x80 => {
+                reader.byte()?;
+            }
+            0x95 => {
+                reader.take(8)?;
+            }
+            // STOP
+            b'.' => return pop(&mut stack),
+            b'N' => stack.push(Pickle::None),
+            0x88 => stack.push(Pickle::Bool(true)),
+            0x89 => stack.push(Pickle::Bool(false)),
+            // BININT1, BININT2, BININT
+            b'K' => stack.push(Pickle::Int(i64::from(reader.byte()?))),
+            b'M' => {
+                let bytes = reader.take(2)?;
+                stack.push(Pickle::Int(i64::from(u16::from_le_bytes([
+                    bytes[0], bytes[1],
+                ]))));
+            }
+            b'J' => stack.push(Pickle::Int(i64::from(reader.u32()? as i32))),
+            // LONG1: little-endian two's complement
+            0x8a => {
+                let len = usize::from(reader.byte()?);
+                let bytes = reader.take(len)?;
+                if len > 8 {
+                    bail!("Integer in pickle is too large");
+                }
+                let negative = bytes.last().is_some_and(|&b| b & 0x80 != 0);
+                let mut buf = [if negative { 0xff } else { 0 }; 8];
+                buf[..len].copy_from_slice(bytes);
+                stack.push(Pickle::Int(i64::from_le_bytes(buf)));
+            }
+            // BINFLOAT, big-endian
+            b'G' => {
+                let mut buf = [0u8; 8];
+                buf.copy_from_slice(reader.take(8)?);
+                stack.push(Pickle::Float(f64::from_be_bytes(buf)));
+            }
+            // SHORT_BINUNICODE, BINUNICODE, BINUNICODE8
+            0x8c => {
+                let len = usize::from(reader.byte()?);
+                stack.push(Pickle::Text(reader.text(len)?));
+            }
+            b'X' => {
+                let len = reader.u32()? as usize;
+                stack.push(Pickle::Text(reader.text(len)?));
+            }
+            0x8d => {
+                let len = usize::try_from(reader.u64()?)?;
+                stack.push(Pickle::Text(reader.text(len)?));
+            }
+            // SHORT_BINBYTES, BINBYTES, BINBYTES8
+            b'C' | b'B' | 0x8e => {
+                let len = match op {
+                    b'C' => usize::from(reader.byte()?),
+                    b'B' => reader.u32()? as usize,
+                    _ => usize::try_from(reader.u64()?)?,
+                };
+                let bytes = reader.take(len)?;
+                stack.push(Pickle::Text(String::from_utf8_lossy(bytes).into_owned()));
+            }
+            b']' | b')' => stack.push(Pickle::List(Vec::new())),
+            b'}' => stack.push(Pickle::Dict(Vec::new())),
+            b'(' => marks.push(stack.len()),
+            // APPEND, APPENDS
+            b'a' => {
+                let value = pop(&mut stack)?;
+                match stack.last_mut() {
+                    Some(Pickle::List(items)) => items.push(value),
+                    _ => bail!("APPEND outside a list"),
+                }
+            }
+            b'e' => {
+                let values = pop_mark(&mut stack, &mut marks)?;
+                match stack.last_mut() {
+                    Some(Pickle::List(items)) => items.extend(values),
+                    _ => bail!("APPENDS outside a list"),
+                }
+            }
+            // TUPLE, TUPLE1, TUPLE2, TUPLE3
+            b't' => {
+                let items = pop_mark(&mut stack, &mut marks)?;
+                stack.push(Pickle::List(items));
+            }
+            0x85..=0x87 => {
+                let len = usize::from(op - 0x84);
+                if stack.len() < len {
+                    bail!("Pickle stack underflow");
+                }
+                let items = stack.split_off(stack.len() - len);
+                stack.push(Pickle::List(items));
+            }
+            // SETITEM, SETITEMS
+            b's' => {
+                let value = pop(&mut stack)?;
+                let key = pop(&mut stack)?;
+                match stack.last_mut() {
+                    Some(Pickle::Dict(entries)) => entries.push((key, value)),
+                    _ => bail!("SETITEM outside a dict"),
+                }
+            }
+            b'u' => {
+                let items = pop_mark(&mut stack, &mut marks)?;
+                if items.len() % 2 != 0 {
+                    bail!("SETITEMS with an odd number of items");
+                }
+                let mut items = items.into_iter();
+                match stack.last_mut() {
+                    Some(Pickle::Dict(entries)) => {
+                        while let (Some(key), Some(value)) = (items.next(), items.next()) {
+                            entries.push((key, value));
+                        }
+                    }
+                    _ => bail!("SETITEMS outside a dict"),
+                }
+            }
+            // MEMOIZE, BINPUT, LONG_BINPUT
+            0x94 | b'q' | b'r' => {
+                let index = match op {
+                    0x94 => memo.len() as u32,
+                    b'q' => u32::from(reader.byte()?),
+                    _ => reader.u32()?,
+                };
+                let top = stack
+                    .last()
+                    .cloned()
+                    .ok_or_else(|| anyhow!("Pickle stack underflow"))?;
+                memo.insert(index, top);
+            }
+            // BINGET, LONG_BINGET
+            b'h' | b'j' => {
+                let index = match op {
+                    b'h' => u32::from(reader.byte()?),
+                    _ => reader.u32()?,
+                };
+                let value = memo
+                    .get(&index)
+                    .cloned()
+                    .ok_or_else(|| anyhow!("Pickle memo entry missing"))?;
+                stack.push(value);
+            }
+            // GLOBAL, STACK_GLOBAL: recorded by name, never imported
+            b'c' => {
+                let module = reader.line()?;
+                let name = reader.line()?;
+                stack.push(Pickle::Global(format!("{}.{}", module, name)));
+            }
+            0x93 => {
+                let name = pop(&mut stack)?;
+                let module = pop(&mut stack)?;
+                let (Pickle::Text(module), Pickle::Text(name)) = (module, name) else {
+                    bail!("STACK_GLOBAL needs two strings");
+                };
+                stack.push(Pickle::Global(format!("{}.{}", module, name)));
+            }
+            // REDUCE, NEWOBJ
+            b'R' | 0x81 => {
+                let args = pop(&mut stack)?;
+                let class = pop(&mut stack)?;
+                stack.push(object(class, args));
+            }
+            // NEWOBJ_EX; keyword arguments are dropped
+            0x92 => {
+                pop(&mut stack)?;
+                let args = pop(&mut stack)?;
+                let class = pop(&mut stack)?;
+                stack.push(object(class, args));
+            }
+            // BUILD
+            b'b' => {
+                let new_state = pop(&mut stack)?;
+                match stack.last_mut() {
+                    Some(Pickle::Object { state, .. }) => **state = new_state,
+                    _ => bail!("BUILD outside an object"),
+                }
+            }
+            other => bail!("Unsupported pickle opcode {:#04x}", other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pickle_command_round_trip() {
+        let encoded = pickle_command(&["set", "sshd", "unbanip", "203.0.113.7"]);
+        assert!(encoded.starts_with(&[0x80, 2, b']', b'(', b'X', 3, 0, 0, 0]));
+        let decoded = unpickle(&encoded).unwrap();
+        assert_eq!(
+            decoded,
+            Pickle::List(vec![
+                Pickle::Text("set".into()),
+                Pickle::Text("sshd".into()),
+                Pickle::Text("unbanip".into()),
+                Pickle::Text("203.0.113.7".into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_jail_status() {
+        // The reply to `status sshd`, as the server pickles it (protocol 4)
+        let data = b"\x80\x04\x95\xc7\x00\x00\x00\x00\x00\x00\x00K\x00]\x94(\x8c\x06Filter\x94]\x94(\x8c\x10Currently failed\x94K\x02\x86\x94\x8c\x0cTotal failed\x94M,\x01\x86\x94\x8c\tFile list\x94]\x94\x8c\x0f/var/log/secure\x94a\x86\x94e\x86\x94\x8c\x07Actions\x94]\x94(\x8c\x10Currently banned\x94K\x01\x86\x94\x8c\x0cTotal banned\x94Jp\x11\x01\x00\x86\x94\x8c\x0eBanned IP list\x94]\x94\x8c\x0b203.0.113.7\x94a\x86\x94e\x86\x94e\x86\x94.";
+
+        let Pickle::List(reply) = unpickle(data).unwrap() else {
+            panic!("not a tuple");
+        };
+        assert_eq!(reply[0], Pickle::Int(0));
+        let jail = parse_jail_status("sshd", &reply[1]);
+        assert_eq!(
+            jail,
+            Jail {
+                name: "sshd".to_string(),
+                currently_failed: 2,
+                total_failed: 300,
+                currently_banned: 1,
+                total_banned: 70000,
+                banned: vec!["203.0.113.7".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_unpickle_objects() {
+        // An IPAddr built with __new__ and its state set by BUILD
+        let mut data = vec![0x80, 2, b'c'];
+        data.extend_from_slice(b"fail2ban.server.ipdns\nIPAddr\n");
+        data.extend_from_slice(&[b')', 0x81, b'N', b'}', b'X', 4, 0, 0, 0]);
+        data.extend_from_slice(b"_raw");
+        data.extend_from_slice(&[b'X', 9, 0, 0, 0]);
+        data.extend_from_slice(b"192.0.2.1");
+        data.extend_from_slice(&[b's', 0x86, b'b', b'.']);
+        assert_eq!(
+            unpickle(&data).unwrap().as_text().as_deref(),
+            Some("192.0.2.1")
+        );
+
+        let error = b"\x80\x02K\x01cexceptions\nKeyError\nq\x00X\x04\x00\x00\x00sshxq\x01\x85q\x02Rq\x03\x86q\x04.";
+        let Pickle::List(reply) = unpickle(error).unwrap() else {
+            panic!("not a tuple");
+        };
+        assert_eq!(reply[0], Pickle::Int(1));
+        assert_eq!(reply[1].as_text().as_deref(), Some("sshx"));
+
+        let negative = [0x80, 2, 0x8a, 2, 0x18, 0xfc, b'.'];
+        assert_eq!(unpickle(&negative).unwrap(), Pickle::Int(-1000));
+        assert!(unpickle(&[0x80, 2, b']', b'e', b'.']).is_err());
+        assert!(unpickle(&[0x80, 2, b'X', 9, 0, 0, 0, b'a']).is_err());
+    }
+
+    #[test]
+    fn test_jail_names() {
+        assert_eq!(
+            split_jail_list("sshd, recidive,nginx-http-auth"),
+            vec!["sshd", "recidive", "nginx-http-auth"]
+        );
+        assert!(split_jail_list("").is_empty());
+        assert_eq!(
+            validate_jail_name("nginx-http-auth"),
+            Some("nginx-http-auth")
+        );
+        assert_eq!(validate_jail_name("-h"), None);
+        assert_eq!(validate_jail_name("sshd unbanip"), None);
+        assert_eq!(validate_jail_name(""), None);
+    }
+}
@@ -0,0 +1,196 @@
+// Security Center - Conflicting Firewall Managers
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Other firewall managers running alongside firewalld.
+//!
+//! ufw, Shorewall, iptables-persistent and the iptables and nftables
+//! services each load a rule set of their own into the kernel. firewalld
+//! neither sees nor manages those rules, so a port opened in firewalld can
+//! still be dropped by them, and restarting either side silently undoes
+//! changes of the other. Most "my rule doesn't work" reports come from
+//! this. The units are looked up through systemd; disabling one goes
+//! through [`Operation::StopAndDisableUnit`](super::Operation).
+
+use std::fs;
+
+use super::assessment::{Finding, FindingCategory, Severity};
+use super::ct_helpers::conf_value;
+use crate::systemd::SystemdClient;
+
+/// ufw's own switch; its unit runs on every boot and only loads rules
+/// when this says so.
+const UFW_CONF: &str = "/etc/ufw/ufw.conf";
+
+/// Firewall managers that conflict with firewalld: name, systemd unit and
+/// what goes wrong.
+const MANAGERS: [(&str, &str, &str); 7] = [
+    (
+        "ufw",
+        "ufw.service",
+        "ufw loads its own iptables chains at boot. A port opened in firewalld can \
+         still be dropped by ufw, and which rule set wins depends on the order the \
+         kernel evaluates them in.",
+    ),
+    (
+        "Shorewall",
+        "shorewall.service",
+        "Shorewall replaces the whole IPv4 rule set when it starts, removing \
+         firewalld's rules; reloading firewalld in turn clears Shorewall's.",
+    ),
+    (
+        "Shorewall6",
+        "shorewall6.service",
+        "Shorewall6 replaces the whole IPv6 rule set when it starts, removing \
+         firewalld's rules; reloading firewalld in turn clears Shorewall6's.",
+    ),
+    (
+        "iptables-persistent",
+        "netfilter-persistent.service",
+        "netfilter-persistent restores saved iptables rules at boot next to \
+         firewalld's. Saved DROP or REJECT rules keep blocking traffic firewalld \
+         allows.",
+    ),
+    (
+        "iptables",
+        "iptables.service",
+        "The iptables service restores /etc/sysconfig/iptables at boot next to \
+         firewalld's rules, and its rules keep applying whatever firewalld allows.",
+    ),
+    (
+        "ip6tables",
+        "ip6tables.service",
+        "The ip6tables service restores /etc/sysconfig/ip6tables at boot next to \
+         firewalld's rules, and its rules keep applying whatever firewalld allows.",
+    ),
+    (
+        "nftables",
+        "nftables.service",
+        "The nftables service loads a rule set file that usually starts by flushing \
+         every table, including firewalld's, leaving the firewall without its rules \
+         until firewalld is reloaded.",
+    ),
+];
+
+/// A firewall manager active or enabled next to firewalld.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FirewallConflict {
+    /// Display name, e.g. `ufw`.
+    pub name: &'static str,
+    /// systemd unit, e.g. `ufw.service`.
+    pub unit: &'static str,
+    /// Why running it next to firewalld breaks rules.
+    pub explanation: &'static str,
+    /// Its rules are loaded now.
+    pub active: bool,
+    /// It loads its rules at boot.
+    pub enabled: bool,
+}
+
+impl FirewallConflict {
+    /// "active", "enabled at boot" or both, for the UI.
+    pub fn state(&self) -> &'static str {
+        match (self.active, self.enabled) {
+            (true, true) => "active and enabled at boot",
+            (true, false) => "active",
+            _ => "enabled at boot",
+        }
+    }
+}
+
+/// Firewall managers active or enabled alongside firewalld. Empty when
+/// systemd cannot be reached.
+pub fn query_firewall_conflicts() -> Vec<FirewallConflict> {
+    let mut client = SystemdClient::new();
+    if client.connect().is_err() {
+        return Vec::new();
+    }
+    let ufw_enabled = fs::read_to_string(UFW_CONF)
+        .map(|text| ufw_enabled(&text))
+        .unwrap_or(false);
+
+    MANAGERS
+        .iter()
+        .filter(|(_, unit, _)| *unit != "ufw.service" || ufw_enabled)
+        .filter_map(|&(name, unit, explanation)| {
+            let info = client.get_service_info(unit).ok()?;
+            if !info.is_installed {
+                return None;
+            }
+            let active = client.is_unit_active(unit).unwrap_or(false);
+            (active || info.is_enabled).then_some(FirewallConflict {
+                name,
+                unit,
+                explanation,
+                active,
+                enabled: info.is_enabled,
+            })
+        })
+        .collect()
+}
+
+/// Whether `ufw.conf` turns ufw on.
+fn ufw_enabled(conf: &str) -> bool {
+    conf_value(conf, "ENABLED").as_deref() == Some("yes")
+}
+
+/// Assessment findings for conflicting firewall managers.
+pub(super) fn check_firewall_conflicts() -> Vec<Finding> {
+    conflict_findings(&query_firewall_conflicts())
+}
+
+fn conflict_findings(conflicts: &[FirewallConflict]) -> Vec<Finding> {
+    conflicts
+        .iter()
+        .map(|conflict| Finding {
+            id: format!(
+                "firewall.conflict.{}",
+                conflict.unit.trim_end_matches(".service")
+            ),
+            category: FindingCategory::Firewall,
+            severity: Severity::High,
+            title: format!(
+                "{} is {} alongside firewalld",
+                conflict.name,
+                conflict.state()
+            ),
+            detail: conflict.explanation.to_string(),
+            remediation: format!(
+                "Stop and disable it from the banner on the Overview, or run \
+                 \"systemctl disable --now {}\". Reload firewalld afterwards; rules it \
+                 already loaded may stay until a reboot.",
+                conflict.unit
+            ),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conflict_findings() {
+        assert!(ufw_enabled("# ENABLED=no\nENABLED=yes\nLOGLEVEL=low\n"));
+        assert!(!ufw_enabled("ENABLED=no\n"));
+        assert!(!ufw_enabled(""));
+
+        let (name, unit, explanation) = MANAGERS[3];
+        let conflict = FirewallConflict {
+            name,
+            unit,
+            explanation,
+            active: false,
+            enabled: true,
+        };
+        let findings = conflict_findings(&[conflict]);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].id, "firewall.conflict.netfilter-persistent");
+        assert_eq!(findings[0].severity, Severity::High);
+        assert_eq!(
+            findings[0].title,
+            "iptables-persistent is enabled at boot alongside firewalld"
+        );
+        assert!(conflict_findings(&[]).is_empty());
+    }
+}
@@ -0,0 +1,281 @@
+// Security Center - Firewall Activity
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Firewall activity without root access.
+//!
+//! - Accepted traffic is approximated by the packets the IP stack delivered
+//!   to local sockets (`InDelivers` in `/proc/net/snmp` and `snmp6`): a
+//!   packet the firewall dropped never gets that far.
+//! - Denied packets come from the kernel log. firewalld writes one line per
+//!   rejected or dropped packet when its `LogDenied` setting is not `off`;
+//!   [`DeniedLog`] follows those lines through `journalctl`, which needs
+//!   membership in a group allowed to read the system journal.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+
+const SNMP: &str = "/proc/net/snmp";
+const SNMP6: &str = "/proc/net/snmp6";
+
+/// Denied packets kept for the log page.
+const MAX_RECENT: usize = 500;
+/// Kernel log entries read back when following starts.
+const BACKLOG_LINES: &str = "200";
+
+/// A packet firewalld rejected or dropped, from its kernel log line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeniedPacket {
+    /// Unix time in seconds.
+    pub time: u64,
+    /// Log prefix naming the chain and verdict, e.g. `filter_IN_public_REJECT`.
+    pub rule: String,
+    /// Incoming interface.
+    pub interface: String,
+    pub source: String,
+    pub destination: String,
+    pub protocol: String,
+    /// Destination port, for TCP and UDP.
+    pub port: Option<u16>,
+}
+
+impl DeniedPacket {
+    /// Zone named in the log prefix, if any.
+    pub fn zone(&self) -> Option<&str> {
+        let rest = self
+            .rule
+            .strip_prefix("filter_IN_")
+            .or_else(|| self.rule.strip_prefix("filter_FWD_"))?;
+        let (zone, _) = rest.rsplit_once('_')?;
+        Some(zone)
+    }
+}
+
+/// Packets delivered to local sockets over IPv4 and IPv6 since boot.
+pub fn read_delivered_packets() -> Option<u64> {
+    let v4 = fs::read_to_string(SNMP)
+        .ok()
+        .and_then(|c| snmp_counter(&c, "Ip:", "InDelivers"));
+    let v6 = fs::read_to_string(SNMP6)
+        .ok()
+        .and_then(|c| snmp6_counter(&c, "Ip6InDelivers"));
+    match (v4, v6) {
+        (None, None) => None,
+        (v4, v6) => Some(v4.unwrap_or(0) + v6.unwrap_or(0)),
+    }
+}
+
+/// Value of `field` in `/proc/net/snmp`, where each protocol has a header
+/// line with field names followed by a line with the values.
+fn snmp_counter(contents: &str, protocol: &str, field: &str) -> Option<u64> {
+    let mut lines = contents.lines().filter(|l| l.starts_with(protocol));
+    let names = lines.next()?;
+    let values = lines.next()?;
+    let index = names.split_whitespace().position(|n| n == field)?;
+    values.split_whitespace().nth(index)?.parse().ok()
+}
+
+/// Value of `field` in `/proc/net/snmp6`, one `name value` pair per line.
+fn snmp6_counter(contents: &str, field: &str) -> Option<u64> {
+    contents.lines().find_map(|line| {
+        let mut words = line.split_whitespace();
+        (words.next()? == field).then_some(())?;
+        words.next()?.parse().ok()
+    })
+}
+
+/// Parse a kernel log message written by a firewalld deny rule.
+pub fn parse_denied(message: &str) -> Option<DeniedPacket> {
+    let (rule, fields) = message.split_once(": ")?;
+    let rule = rule.trim();
+    if rule.contains(char::is_whitespace) || !(rule.ends_with("_REJECT") || rule.ends_with("_DROP"))
+    {
+        return None;
+    }
+
+    let mut packet = DeniedPacket {
+        rule: rule.to_string(),
+        ..Default::default()
+    };
+    for field in fields.split_whitespace() {
+        let Some((key, value)) = field.split_once('=') else {
+            continue;
+        };
+        match key {
+            "IN" => packet.interface = value.to_string(),
+            "SRC" => packet.source = value.to_string(),
+            "DST" => packet.destination = value.to_string(),
+            "PROTO" => packet.protocol = value.to_string(),
+            "DPT" => packet.port = value.parse().ok(),
+            _ => {}
+        }
+    }
+    (!packet.source.is_empty()).then_some(packet)
+}
+
+/// Parse one `journalctl -o json` line into a denied packet.
+fn parse_journal_entry(line: &str) -> Option<DeniedPacket> {
+    let entry: serde_json::Value = serde_json::from_str(line).ok()?;
+    let mut packet = parse_denied(entry.get("MESSAGE")?.as_str()?)?;
+    let micros: u64 = entry.get("__REALTIME_TIMESTAMP")?.as_str()?.parse().ok()?;
+    packet.time = micros / 1_000_000;
+    Some(packet)
+}
+
+/// Follows denied packets in the kernel log until dropped.
+pub struct DeniedLog {
+    child: Child,
+    recent: Arc<Mutex<VecDeque<DeniedPacket>>>,
+    /// Denied packets logged since following started.
+    count: Arc<AtomicU64>,
+    /// New packets not yet taken by [`DeniedLog::take_new`].
+    fresh: Arc<Mutex<Vec<DeniedPacket>>>,
+}
+
+impl DeniedLog {
+    /// Start following the kernel log, reading back recent entries first.
+    pub fn start() -> Result<Self> {
+        let mut child = Command::new("journalctl")
+            .args(["-k", "-f", "-o", "json", "-n", BACKLOG_LINES])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .context("Failed to run journalctl")?;
+        let stdout = child.stdout.take().context("journalctl has no output")?;
+
+        let recent = Arc::new(Mutex::new(VecDeque::new()));
+        let count = Arc::new(AtomicU64::new(0));
+        let fresh = Arc::new(Mutex::new(Vec::new()));
+        let started = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        let thread_recent = recent.clone();
+        let thread_count = count.clone();
+        let thread_fresh = fresh.clone();
+        std::thread::Builder::new()
+            .name("denied-log".to_string())
+            .spawn(move || {
+                for line in BufReader::new(stdout).lines() {
+                    let Ok(line) = line else { break };
+                    let Some(packet) = parse_journal_entry(&line) else {
+                        continue;
+                    };
+                    // The backlog fills the log page but is not new activity
+                    if packet.time >= started {
+                        thread_count.fetch_add(1, Ordering::Relaxed);
+                        if let Ok(mut fresh) = thread_fresh.lock() {
+                            if fresh.len() < MAX_RECENT {
+                                fresh.push(packet.clone());
+                            }
+                        }
+                    }
+                    if let Ok(mut recent) = thread_recent.lock() {
+                        if recent.len() == MAX_RECENT {
+                            recent.pop_front();
+                        }
+                        recent.push_back(packet);
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            child,
+            recent,
+            count,
+            fresh,
+        })
+    }
+
+    /// Denied packets logged since following started.
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Recently denied packets, newest first.
+    pub fn recent(&self) -> Vec<DeniedPacket> {
+        self.recent
+            .lock()
+            .map(|recent| recent.iter().rev().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Packets denied since the previous call, oldest first. The backlog
+    /// read back at start is not included.
+    pub fn take_new(&self) -> Vec<DeniedPacket> {
+        self.fresh
+            .lock()
+            .map(|mut fresh| std::mem::take(&mut *fresh))
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for DeniedLog {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snmp_counters() {
+        let snmp = "Ip: Forwarding DefaultTTL InReceives InDelivers OutRequests\n\
+                    Ip: 2 64 1000 950 800\n\
+                    Icmp: InMsgs InErrors\n\
+                    Icmp: 5 0\n";
+        assert_eq!(snmp_counter(snmp, "Ip:", "InDelivers"), Some(950));
+        assert_eq!(snmp_counter(snmp, "Ip:", "Missing"), None);
+        let snmp6 =
+            "Ip6InReceives                   \t120\nIp6InDelivers                   \t110\n";
+        assert_eq!(snmp6_counter(snmp6, "Ip6InDelivers"), Some(110));
+        assert_eq!(snmp6_counter(snmp6, "Ip6OutRequests"), None);
+    }
+
+    #[test]
+    fn test_parse_denied() {
+        let packet = parse_denied(
+            "filter_IN_public_REJECT: IN=wlp2s0 OUT= MAC=aa:bb SRC=192.168.1.5 \
+             DST=192.168.1.10 LEN=60 TOS=0x00 PROTO=TCP SPT=50000 DPT=22 SYN URGP=0",
+        )
+        .unwrap();
+        assert_eq!(packet.rule, "filter_IN_public_REJECT");
+        assert_eq!(packet.zone(), Some("public"));
+        assert_eq!(packet.interface, "wlp2s0");
+        assert_eq!(packet.source, "192.168.1.5");
+        assert_eq!(packet.destination, "192.168.1.10");
+        assert_eq!(packet.protocol, "TCP");
+        assert_eq!(packet.port, Some(22));
+
+        let packet = parse_denied(
+            "STATE_INVALID_DROP: IN=eth0 OUT= SRC=fe80::1 DST=ff02::1 PROTO=ICMPv6 TYPE=134",
+        )
+        .unwrap();
+        assert_eq!(packet.zone(), None);
+        assert_eq!(packet.port, None);
+
+        assert!(parse_denied("usb 1-1: new high-speed USB device").is_none());
+        assert!(parse_denied("audit: type=1400 SRC=1.2.3.4").is_none());
+        assert!(parse_denied("filter_IN_public_REJECT: IN=eth0 OUT=").is_none());
+    }
+
+    #[test]
+    fn test_parse_journal_entry() {
+        let line = r#"{"MESSAGE":"FINAL_REJECT: IN=eth0 SRC=10.0.0.2 DST=10.0.0.1 PROTO=UDP DPT=137","__REALTIME_TIMESTAMP":"1760000000123456"}"#;
+        let packet = parse_journal_entry(line).unwrap();
+        assert_eq!(packet.time, 1_760_000_000);
+        assert_eq!(packet.port, Some(137));
+        assert!(parse_journal_entry(r#"{"MESSAGE":[1,2,3]}"#).is_none());
+    }
+}
@@ -0,0 +1,166 @@
+// Security Center - Interface Hotplug
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! New network interfaces, as NetworkManager announces them.
+//!
+//! A USB tethering adapter, a docking station or a new container bridge
+//! lands in firewalld's default zone without anyone being asked.
+//! [`watch_interfaces`] follows NetworkManager's `DeviceAdded` signal and
+//! reports each new interface as a [`NewInterface`], so the window can ask
+//! which zone it belongs in. Loopback and the per-container ends of veth
+//! pairs are not reported: they come and go with every container start.
+
+use anyhow::{Context, Result};
+use tokio::sync::broadcast;
+use tracing::warn;
+use zbus::export::futures_util::StreamExt;
+use zbus::message::Type;
+use zbus::zvariant::{OwnedObjectPath, OwnedValue};
+use zbus::{Connection, MatchRule, MessageStream};
+
+const NM_BUS: &str = "org.freedesktop.NetworkManager";
+const NM_PATH: &str = "/org/freedesktop/NetworkManager";
+/// The manager object's interface shares the bus name.
+const NM_MANAGER: &str = "org.freedesktop.NetworkManager";
+const NM_DEVICE: &str = "org.freedesktop.NetworkManager.Device";
+const PROPERTIES_INTERFACE: &str = "org.freedesktop.DBus.Properties";
+
+/// `NMDeviceType` values of interest.
+const NM_DEVICE_TYPE_ETHERNET: u32 = 1;
+const NM_DEVICE_TYPE_WIFI: u32 = 2;
+const NM_DEVICE_TYPE_BT: u32 = 5;
+const NM_DEVICE_TYPE_MODEM: u32 = 8;
+const NM_DEVICE_TYPE_BRIDGE: u32 = 13;
+const NM_DEVICE_TYPE_TUN: u32 = 16;
+const NM_DEVICE_TYPE_VETH: u32 = 20;
+const NM_DEVICE_TYPE_WIREGUARD: u32 = 29;
+const NM_DEVICE_TYPE_LOOPBACK: u32 = 32;
+
+/// An interface that appeared while the app was running.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewInterface {
+    /// Kernel name, e.g. `usb0` or `br-3f2a`.
+    pub name: String,
+    /// `NMDeviceType` of the device.
+    pub device_type: u32,
+}
+
+impl NewInterface {
+    /// What kind of device this is, for the prompt.
+    pub fn kind(&self) -> &'static str {
+        match self.device_type {
+            NM_DEVICE_TYPE_ETHERNET => "Wired network adapter",
+            NM_DEVICE_TYPE_WIFI => "Wi-Fi adapter",
+            NM_DEVICE_TYPE_BT => "Bluetooth network",
+            NM_DEVICE_TYPE_MODEM => "Mobile broadband modem",
+            NM_DEVICE_TYPE_BRIDGE => "Network bridge",
+            NM_DEVICE_TYPE_TUN => "Tunnel or VPN interface",
+            NM_DEVICE_TYPE_WIREGUARD => "WireGuard VPN",
+            _ => "Network interface",
+        }
+    }
+}
+
+/// Whether an interface of `device_type` named `name` is worth asking about.
+pub fn is_prompt_worthy(name: &str, device_type: u32) -> bool {
+    !name.is_empty()
+        && name != "lo"
+        && !matches!(device_type, NM_DEVICE_TYPE_LOOPBACK | NM_DEVICE_TYPE_VETH)
+        // Container runtimes name the host ends of veth pairs like this
+        && !name.starts_with("veth")
+}
+
+/// A property of a NetworkManager device.
+async fn get_property(conn: &Connection, path: &OwnedObjectPath, name: &str) -> Option<OwnedValue> {
+    let reply = conn
+        .call_method(
+            Some(NM_BUS),
+            path.as_str(),
+            Some(PROPERTIES_INTERFACE),
+            "Get",
+            &(NM_DEVICE, name),
+        )
+        .await
+        .ok()?;
+    reply.body().deserialize().ok()
+}
+
+/// Send every interface NetworkManager adds until `sender` has no
+/// subscribers left.
+pub async fn watch(sender: broadcast::Sender<NewInterface>) -> Result<()> {
+    let conn = Connection::system()
+        .await
+        .context("Failed to connect to system D-Bus")?;
+
+    let rule = MatchRule::builder()
+        .msg_type(Type::Signal)
+        .sender(NM_BUS)?
+        .path(NM_PATH)?
+        .interface(NM_MANAGER)?
+        .member("DeviceAdded")?
+        .build();
+    let mut messages = MessageStream::for_match_rule(rule, &conn, None).await?;
+
+    while let Some(msg) = messages.next().await {
+        let Ok(msg) = msg else { continue };
+        let Ok(path) = msg.body().deserialize::<OwnedObjectPath>() else {
+            continue;
+        };
+        let Some(name) = get_property(&conn, &path, "Interface")
+            .await
+            .and_then(|value| String::try_from(value).ok())
+        else {
+            continue;
+        };
+        let device_type = get_property(&conn, &path, "DeviceType")
+            .await
+            .and_then(|value| u32::try_from(value).ok())
+            .unwrap_or(0);
+        if !is_prompt_worthy(&name, device_type) {
+            continue;
+        }
+        if sender.send(NewInterface { name, device_type }).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Follow new interfaces on a background thread.
+pub fn watch_interfaces() -> broadcast::Receiver<NewInterface> {
+    let (sender, receiver) = broadcast::channel(16);
+    std::thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                warn!("Cannot start the interface watcher: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = runtime.block_on(watch(sender)) {
+            warn!("Stopped following new interfaces: {:#}", e);
+        }
+    });
+    receiver
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_prompt_worthy() {
+        assert!(is_prompt_worthy("usb0", NM_DEVICE_TYPE_ETHERNET));
+        assert!(is_prompt_worthy("br-3f2a1c", NM_DEVICE_TYPE_BRIDGE));
+        assert!(is_prompt_worthy("wg0", NM_DEVICE_TYPE_WIREGUARD));
+        assert!(!is_prompt_worthy("lo", NM_DEVICE_TYPE_LOOPBACK));
+        assert!(!is_prompt_worthy("", NM_DEVICE_TYPE_ETHERNET));
+        assert!(!is_prompt_worthy("eth0@if12", NM_DEVICE_TYPE_VETH));
+        // Unmanaged veths may be reported with an unknown type
+        assert!(!is_prompt_worthy("veth1a2b3c", 0));
+    }
+}
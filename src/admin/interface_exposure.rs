@@ -0,0 +1,613 @@
+// Security Center - Per-Interface Exposure
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Network exposure broken down per interface.
+//!
+//! A socket listening on `0.0.0.0` or `::` accepts connections on every
+//! interface, but whether a connection gets that far depends on the
+//! firewalld zone of the interface it arrives on. On a machine with a LAN
+//! port, Wi-Fi, a VPN and container bridges, the same service can be open
+//! on one and filtered on the next. For each interface with an address,
+//! [`query_interface_exposure`] lists the listeners bound there and whether
+//! its zone lets them through.
+//!
+//! Addresses come from the kernel over rtnetlink (`RTM_GETADDR`, no
+//! privileges needed), interface types from sysfs. Interfaces bound to no
+//! zone are in the default zone. Rich rules limited to a source address
+//! and source bindings are left out: they decide per peer, not per
+//! interface.
+
+use std::collections::HashMap;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::path::Path;
+
+use anyhow::Result;
+use netlink_packet_core::{NLM_F_DUMP, NLM_F_REQUEST};
+use netlink_sys::{protocols::NETLINK_ROUTE, Socket, SocketAddr};
+
+use super::netlink::{self, parse_attrs};
+use super::network::{parse_port_string, parse_rich_rule_port, FirewallStatus, ListeningEndpoint};
+use crate::firewall;
+use crate::models::{Service, Zone};
+use crate::validation::parse_port_spec;
+
+// rtnetlink constants (linux/rtnetlink.h, linux/if_addr.h)
+const RTM_NEWADDR: u16 = 20;
+const RTM_GETADDR: u16 = 22;
+const IFA_ADDRESS: u16 = 1;
+const IFA_LOCAL: u16 = 2;
+const AF_INET: u8 = 2;
+const AF_INET6: u8 = 10;
+
+const SYS_CLASS_NET: &str = "/sys/class/net";
+
+/// Name prefixes of VPN and tunnel interfaces.
+const VPN_PREFIXES: &[&str] = &[
+    "tun",
+    "tap",
+    "wg",
+    "ppp",
+    "tailscale",
+    "zt",
+    "nordlynx",
+    "proton",
+    "ipsec",
+];
+
+/// Name prefixes of container and virtual machine networking.
+const VIRTUAL_PREFIXES: &[&str] = &[
+    "docker", "br-", "veth", "virbr", "vnet", "podman", "cni", "lxc", "lxd", "flannel", "cali",
+    "vmnet", "vboxnet",
+];
+
+/// What an interface connects to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterfaceKind {
+    /// A wired network card.
+    Wired,
+    Wireless,
+    /// A VPN or other tunnel.
+    Vpn,
+    /// Container or virtual machine networking (bridges, veth pairs).
+    Virtual,
+    Other,
+}
+
+impl InterfaceKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Wired => "LAN",
+            Self::Wireless => "Wi-Fi",
+            Self::Vpn => "VPN",
+            Self::Virtual => "Containers / VMs",
+            Self::Other => "Other",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Wired => "network-wired-symbolic",
+            Self::Wireless => "network-wireless-symbolic",
+            Self::Vpn => "network-vpn-symbolic",
+            Self::Virtual => "computer-symbolic",
+            Self::Other => "network-transmit-receive-symbolic",
+        }
+    }
+}
+
+/// An interface and the addresses assigned to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterfaceAddresses {
+    pub name: String,
+    pub addresses: Vec<IpAddr>,
+}
+
+/// What can be reached through one interface.
+#[derive(Debug, Clone)]
+pub struct InterfaceExposure {
+    pub name: String,
+    pub kind: InterfaceKind,
+    pub addresses: Vec<IpAddr>,
+    /// Zone the interface is in, `None` when firewalld is unreachable.
+    pub zone: Option<String>,
+    /// Endpoints listening on this interface, one per port and protocol.
+    /// The firewall status is that of the interface's zone.
+    pub listeners: Vec<ListeningEndpoint>,
+}
+
+impl InterfaceExposure {
+    /// Listeners the zone does not filter.
+    pub fn reachable(&self) -> impl Iterator<Item = &ListeningEndpoint> {
+        self.listeners
+            .iter()
+            .filter(|e| !matches!(e.firewall_status, FirewallStatus::Blocked))
+    }
+}
+
+/// The firewalld state deciding what each interface lets in.
+pub(super) struct ZonePolicy {
+    pub(super) default_zone: String,
+    /// Interface name -> zone, for interfaces bound to a zone.
+    pub(super) bindings: HashMap<String, String>,
+    /// Zones in use, with their target.
+    pub(super) zones: HashMap<String, Zone>,
+    /// Definitions of the services enabled in `zones`.
+    pub(super) services: HashMap<String, Service>,
+}
+
+impl ZonePolicy {
+    pub(super) fn query() -> Result<Self> {
+        firewall::read_blocking(|client| -> Result<Self> {
+            client.connect()?;
+
+            let mut zones = HashMap::new();
+            let mut bindings = HashMap::new();
+            let mut services = HashMap::new();
+            let mut default_zone = String::new();
+            for mut zone in client.get_zones()? {
+                if !zone.is_in_use() {
+                    continue;
+                }
+                if zone.is_default {
+                    default_zone = zone.name.clone();
+                }
+                for interface in &zone.interfaces {
+                    bindings.insert(interface.clone(), zone.name.clone());
+                }
+                zone.target = client
+                    .get_zone_target(&zone.name)
+                    .unwrap_or_else(|_| default_target(&zone.name).to_string());
+                for name in &zone.services {
+                    if !services.contains_key(name) {
+                        if let Ok(service) = client.get_service_settings(name) {
+                            services.insert(name.clone(), service);
+                        }
+                    }
+                }
+                zones.insert(zone.name.clone(), zone);
+            }
+
+            Ok(Self {
+                default_zone,
+                bindings,
+                zones,
+                services,
+            })
+        })?
+    }
+
+    /// Zone of an interface; unbound interfaces are in the default zone.
+    fn zone_of(&self, interface: &str) -> &str {
+        self.bindings
+            .get(interface)
+            .map(String::as_str)
+            .unwrap_or(&self.default_zone)
+    }
+
+    /// Whether `zone` lets connections to `port`/`protocol` in.
+    pub(super) fn allows(&self, zone: &str, port: u16, protocol: &str) -> bool {
+        let Some(zone) = self.zones.get(zone) else {
+            return false;
+        };
+        let covers =
+            |(start, end): (u16, u16), p: &str| p == protocol && (start..=end).contains(&port);
+
+        // Rich rules for everyone: denials win over everything else
+        let rules: Vec<(&String, (u16, u16), String)> = zone
+            .rich_rules
+            .iter()
+            .filter(|rule| !rule.contains("source "))
+            .filter_map(|rule| parse_rich_rule_port(rule).map(|(range, p)| (rule, range, p)))
+            .filter(|(_, range, p)| covers(*range, p))
+            .collect();
+        if rules
+            .iter()
+            .any(|(rule, _, _)| rule.contains("reject") || rule.contains("drop"))
+        {
+            return false;
+        }
+        if rules.iter().any(|(rule, _, _)| rule.contains("accept")) {
+            return true;
+        }
+
+        let port_open = zone
+            .ports
+            .iter()
+            .filter_map(|port| parse_port_string(port))
+            .any(|(range, p)| covers(range, &p));
+        let service_open = zone
+            .services
+            .iter()
+            .filter_map(|name| self.services.get(name))
+            .flat_map(|service| service.ports.iter())
+            .filter_map(|(port, p)| Some((parse_port_spec(port)?, p.to_lowercase())))
+            .any(|(range, p)| covers(range, &p));
+
+        port_open || service_open || zone.target == "ACCEPT"
+    }
+}
+
+/// Target of the built-in zones, for firewalld releases that cannot report
+/// it.
+fn default_target(zone: &str) -> &'static str {
+    match zone {
+        "trusted" => "ACCEPT",
+        "drop" => "DROP",
+        "block" => "%%REJECT%%",
+        _ => "default",
+    }
+}
+
+/// Classify an interface by its name and whether it is a wireless or
+/// physical device.
+fn classify_interface(name: &str, wireless: bool, physical: bool) -> InterfaceKind {
+    if wireless {
+        InterfaceKind::Wireless
+    } else if VPN_PREFIXES.iter().any(|p| name.starts_with(p)) {
+        InterfaceKind::Vpn
+    } else if VIRTUAL_PREFIXES.iter().any(|p| name.starts_with(p)) {
+        InterfaceKind::Virtual
+    } else if physical {
+        InterfaceKind::Wired
+    } else {
+        InterfaceKind::Other
+    }
+}
+
+fn interface_kind(name: &str) -> InterfaceKind {
+    let dir = Path::new(SYS_CLASS_NET).join(name);
+    classify_interface(
+        name,
+        dir.join("wireless").exists() || dir.join("phy80211").exists(),
+        dir.join("device").exists(),
+    )
+}
+
+/// The address an IPv4-mapped IPv6 address stands for.
+fn canonical(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+        _ => ip,
+    }
+}
+
+/// Whether a socket bound to `local_addr` accepts connections sent to one
+/// of `addresses`. Sockets on `::` are counted as dual-stack, the Linux
+/// default.
+fn listens_on(local_addr: IpAddr, addresses: &[IpAddr]) -> bool {
+    match canonical(local_addr) {
+        IpAddr::V4(addr) if addr.is_unspecified() => addresses.iter().any(IpAddr::is_ipv4),
+        IpAddr::V6(addr) if addr.is_unspecified() => !addresses.is_empty(),
+        addr => addresses.contains(&addr),
+    }
+}
+
+/// Combine interface addresses, listening sockets (sorted by port, as
+/// returned by [`NetworkExposure::scan_sockets`](super::NetworkExposure::scan_sockets))
+/// and the zone policy. Loopback interfaces are left out.
+fn interface_exposure(
+    interfaces: Vec<InterfaceAddresses>,
+    kind_of: impl Fn(&str) -> InterfaceKind,
+    sockets: &[ListeningEndpoint],
+    policy: Option<&ZonePolicy>,
+) -> Vec<InterfaceExposure> {
+    interfaces
+        .into_iter()
+        .filter(|iface| !iface.addresses.iter().all(IpAddr::is_loopback))
+        .map(|iface| {
+            let zone = policy.map(|policy| policy.zone_of(&iface.name).to_string());
+            let mut listeners: Vec<ListeningEndpoint> = sockets
+                .iter()
+                .filter(|socket| listens_on(socket.local_addr, &iface.addresses))
+                .cloned()
+                .collect();
+            listeners.dedup_by(|a, b| a.port == b.port && a.protocol == b.protocol);
+            for listener in &mut listeners {
+                listener.firewall_status = match (policy, &zone) {
+                    (Some(policy), Some(zone)) => {
+                        let protocol = listener.protocol.as_str().to_lowercase();
+                        if policy.allows(zone, listener.port, &protocol) {
+                            FirewallStatus::Allowed { zone: zone.clone() }
+                        } else {
+                            FirewallStatus::Blocked
+                        }
+                    }
+                    _ => FirewallStatus::Unknown,
+                };
+            }
+            InterfaceExposure {
+                kind: kind_of(&iface.name),
+                name: iface.name,
+                addresses: iface.addresses,
+                zone,
+                listeners,
+            }
+        })
+        .collect()
+}
+
+/// Break the given listening sockets down per interface. Without firewalld
+/// the interfaces have no zone and every status is
+/// [`FirewallStatus::Unknown`].
+pub fn query_interface_exposure(sockets: &[ListeningEndpoint]) -> Result<Vec<InterfaceExposure>> {
+    let interfaces = query_interface_addresses()?;
+    let policy = ZonePolicy::query()
+        .map_err(|e| tracing::debug!("No zone policy for interface exposure: {}", e))
+        .ok();
+    Ok(interface_exposure(
+        interfaces,
+        interface_kind,
+        sockets,
+        policy.as_ref(),
+    ))
+}
+
+/// Interface index -> name, from sysfs.
+fn interface_names() -> HashMap<u32, String> {
+    let Ok(entries) = fs::read_dir(SYS_CLASS_NET) else {
+        return HashMap::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let index = fs::read_to_string(entry.path().join("ifindex")).ok()?;
+            let name = entry.file_name().to_str()?.to_string();
+            Some((index.trim().parse().ok()?, name))
+        })
+        .collect()
+}
+
+/// Every address assigned to an interface, grouped by interface in index
+/// order.
+pub fn query_interface_addresses() -> Result<Vec<InterfaceAddresses>> {
+    let mut socket = Socket::new(NETLINK_ROUTE)?;
+    socket.bind_auto()?;
+    socket.connect(&SocketAddr::new(0, 0))?;
+
+    // struct ifaddrmsg, all zero: every family and interface
+    let msg = netlink::message(RTM_GETADDR, NLM_F_REQUEST | NLM_F_DUMP, 1, &[0u8; 8]);
+    let addresses: Vec<(u32, IpAddr)> = netlink::request(&mut socket, 1, &msg)?
+        .iter()
+        .filter(|reply| reply.msg_type == RTM_NEWADDR)
+        .filter_map(|reply| parse_address(&reply.payload))
+        .collect();
+    Ok(group_addresses(addresses, &interface_names()))
+}
+
+/// Group `(index, address)` pairs by interface. Addresses of interfaces
+/// without a name are dropped.
+fn group_addresses(
+    mut addresses: Vec<(u32, IpAddr)>,
+    names: &HashMap<u32, String>,
+) -> Vec<InterfaceAddresses> {
+    addresses.sort_by_key(|(index, _)| *index);
+    let mut grouped: Vec<(u32, InterfaceAddresses)> = Vec::new();
+    for (index, address) in addresses {
+        let Some(name) = names.get(&index) else {
+            continue;
+        };
+        match grouped.last_mut() {
+            Some((last, iface)) if *last == index => iface.addresses.push(address),
+            _ => grouped.push((
+                index,
+                InterfaceAddresses {
+                    name: name.clone(),
+                    addresses: vec![address],
+                },
+            )),
+        }
+    }
+    grouped.into_iter().map(|(_, iface)| iface).collect()
+}
+
+/// Interface index and address of an `RTM_NEWADDR` message.
+fn parse_address(payload: &[u8]) -> Option<(u32, IpAddr)> {
+    // struct ifaddrmsg: family, prefix length, flags, scope, index
+    let family = *payload.first()?;
+    let index = u32::from_ne_bytes(payload.get(4..8)?.try_into().ok()?);
+    let attrs = parse_attrs(payload.get(8..)?);
+    let attr = |kind: u16| attrs.iter().find(|(k, _)| *k == kind).map(|(_, v)| *v);
+    // On point-to-point links IFA_ADDRESS is the peer and IFA_LOCAL our end
+    let value = attr(IFA_LOCAL).or_else(|| attr(IFA_ADDRESS))?;
+    let address = match family {
+        AF_INET => IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(value.get(..4)?).ok()?)),
+        AF_INET6 => IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(value.get(..16)?).ok()?)),
+        _ => return None,
+    };
+    Some((index, address))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::admin::network::Protocol;
+
+    fn iface(name: &str, addresses: &[&str]) -> InterfaceAddresses {
+        InterfaceAddresses {
+            name: name.to_string(),
+            addresses: addresses.iter().map(|a| a.parse().unwrap()).collect(),
+        }
+    }
+
+    fn zone(name: &str, services: &[&str], ports: &[&str], target: &str) -> Zone {
+        Zone {
+            name: name.to_string(),
+            target: target.to_string(),
+            services: services.iter().map(|s| s.to_string()).collect(),
+            ports: ports.iter().map(|p| p.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn policy() -> ZonePolicy {
+        let mut ssh = Service::new("ssh");
+        ssh.ports = vec![("22".to_string(), "tcp".to_string())];
+        let mut home = zone("home", &["ssh"], &["8000-8100/tcp"], "default");
+        home.rich_rules =
+            vec![r#"rule family="ipv4" port port="8080" protocol="tcp" reject"#.to_string()];
+        ZonePolicy {
+            default_zone: "public".to_string(),
+            bindings: HashMap::from([
+                ("eth0".to_string(), "home".to_string()),
+                ("docker0".to_string(), "docker".to_string()),
+            ]),
+            zones: HashMap::from([
+                ("public".to_string(), zone("public", &[], &[], "default")),
+                ("home".to_string(), home),
+                ("docker".to_string(), zone("docker", &[], &[], "ACCEPT")),
+            ]),
+            services: HashMap::from([("ssh".to_string(), ssh)]),
+        }
+    }
+
+    #[test]
+    fn test_classify_interface() {
+        assert_eq!(
+            classify_interface("wlp2s0", true, true),
+            InterfaceKind::Wireless
+        );
+        assert_eq!(
+            classify_interface("enp3s0", false, true),
+            InterfaceKind::Wired
+        );
+        assert_eq!(classify_interface("wg0", false, false), InterfaceKind::Vpn);
+        assert_eq!(classify_interface("tun0", false, false), InterfaceKind::Vpn);
+        assert_eq!(
+            classify_interface("docker0", false, false),
+            InterfaceKind::Virtual
+        );
+        assert_eq!(
+            classify_interface("br-1a2b3c", false, false),
+            InterfaceKind::Virtual
+        );
+        assert_eq!(
+            classify_interface("bond0", false, false),
+            InterfaceKind::Other
+        );
+    }
+
+    #[test]
+    fn test_listens_on() {
+        let lan: Vec<IpAddr> = vec!["192.168.1.5".parse().unwrap(), "fe80::1".parse().unwrap()];
+        let v6_only: Vec<IpAddr> = vec!["fd00::5".parse().unwrap()];
+
+        assert!(listens_on("0.0.0.0".parse().unwrap(), &lan));
+        assert!(!listens_on("0.0.0.0".parse().unwrap(), &v6_only));
+        assert!(listens_on("::".parse().unwrap(), &v6_only));
+        assert!(listens_on("192.168.1.5".parse().unwrap(), &lan));
+        assert!(listens_on("::ffff:192.168.1.5".parse().unwrap(), &lan));
+        assert!(!listens_on("10.0.0.1".parse().unwrap(), &lan));
+        assert!(!listens_on("127.0.0.1".parse().unwrap(), &lan));
+    }
+
+    #[test]
+    fn test_zone_policy_allows() {
+        let policy = policy();
+        assert_eq!(policy.zone_of("eth0"), "home");
+        assert_eq!(policy.zone_of("wlan0"), "public");
+
+        // Through a service, a port range, and not past a reject rule
+        assert!(policy.allows("home", 22, "tcp"));
+        assert!(!policy.allows("home", 22, "udp"));
+        assert!(policy.allows("home", 8050, "tcp"));
+        assert!(!policy.allows("home", 8080, "tcp"));
+        assert!(!policy.allows("public", 22, "tcp"));
+        // An ACCEPT target lets everything in
+        assert!(policy.allows("docker", 5432, "tcp"));
+        assert!(!policy.allows("unknown", 22, "tcp"));
+    }
+
+    #[test]
+    fn test_interface_exposure() {
+        let sockets = vec![
+            ListeningEndpoint::for_test(22, Protocol::Tcp, "0.0.0.0"),
+            ListeningEndpoint::for_test(22, Protocol::Tcp, "::"),
+            ListeningEndpoint::for_test(631, Protocol::Tcp, "127.0.0.1"),
+            ListeningEndpoint::for_test(5432, Protocol::Tcp, "172.17.0.1"),
+            ListeningEndpoint::for_test(8080, Protocol::Tcp, "::"),
+        ];
+        let interfaces = vec![
+            iface("lo", &["127.0.0.1", "::1"]),
+            iface("eth0", &["192.168.1.5"]),
+            iface("wlan0", &["10.0.0.7", "fd00::7"]),
+            iface("docker0", &["172.17.0.1"]),
+        ];
+        let policy = policy();
+        let exposure = interface_exposure(
+            interfaces,
+            |_| InterfaceKind::Other,
+            &sockets,
+            Some(&policy),
+        );
+
+        let names: Vec<&str> = exposure.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["eth0", "wlan0", "docker0"]);
+
+        let eth0 = &exposure[0];
+        assert_eq!(eth0.zone.as_deref(), Some("home"));
+        let ports: Vec<u16> = eth0.listeners.iter().map(|l| l.port).collect();
+        assert_eq!(ports, [22, 8080]);
+        let reachable: Vec<u16> = eth0.reachable().map(|l| l.port).collect();
+        assert_eq!(reachable, [22]);
+
+        let wlan0 = &exposure[1];
+        assert_eq!(wlan0.zone.as_deref(), Some("public"));
+        assert_eq!(wlan0.listeners.len(), 2);
+        assert_eq!(wlan0.reachable().count(), 0);
+
+        let docker0 = &exposure[2];
+        let reachable: Vec<u16> = docker0.reachable().map(|l| l.port).collect();
+        assert_eq!(reachable, [22, 5432, 8080]);
+
+        // Without firewalld nothing is known about the zones
+        let exposure = interface_exposure(
+            vec![iface("eth0", &["192.168.1.5"])],
+            |_| InterfaceKind::Wired,
+            &sockets,
+            None,
+        );
+        assert_eq!(exposure[0].zone, None);
+        assert!(exposure[0]
+            .listeners
+            .iter()
+            .all(|l| l.firewall_status == FirewallStatus::Unknown));
+    }
+
+    #[test]
+    fn test_parse_address() {
+        let mut payload = vec![AF_INET, 24, 0, 0];
+        payload.extend_from_slice(&3u32.to_ne_bytes());
+        // IFA_ADDRESS is the peer on a point-to-point link
+        payload.extend_from_slice(&8u16.to_ne_bytes());
+        payload.extend_from_slice(&IFA_ADDRESS.to_ne_bytes());
+        payload.extend_from_slice(&[10, 8, 0, 1]);
+        payload.extend_from_slice(&8u16.to_ne_bytes());
+        payload.extend_from_slice(&IFA_LOCAL.to_ne_bytes());
+        payload.extend_from_slice(&[10, 8, 0, 2]);
+        assert_eq!(
+            parse_address(&payload),
+            Some((3, IpAddr::V4(Ipv4Addr::new(10, 8, 0, 2))))
+        );
+
+        let names = HashMap::from([(1, "lo".to_string()), (3, "tun0".to_string())]);
+        let grouped = group_addresses(
+            vec![
+                (3, "10.8.0.2".parse().unwrap()),
+                (1, "127.0.0.1".parse().unwrap()),
+                (3, "fd00::2".parse().unwrap()),
+                (9, "192.0.2.1".parse().unwrap()),
+            ],
+            &names,
+        );
+        assert_eq!(
+            grouped,
+            vec![
+                iface("lo", &["127.0.0.1"]),
+                iface("tun0", &["10.8.0.2", "fd00::2"]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+// Security Center - SSH Lockout Detection
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Remote SSH sessions a firewall change could cut off.
+//!
+//! [`query_ssh_sessions`] lists the established connections into the SSH
+//! server, found from the sockets `sshd` listens on, port 22 when the
+//! listener's process cannot be read, and `SSH_CONNECTION` when the app
+//! itself runs inside an SSH session. [`sessions_on`] picks those a rule
+//! closing or blocking a port range would stop. Zones are not taken into
+//! account: a session on any interface counts, to err on the safe side.
+
+use std::collections::BTreeSet;
+use std::net::IpAddr;
+
+use anyhow::Result;
+
+use super::network::{is_local_ip, ActiveConnection, ListeningEndpoint, NetworkExposure};
+
+/// The usual SSH port, assumed when the listener's process is unknown.
+const SSH_PORT: u16 = 22;
+
+/// An SSH session from a remote host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SshSession {
+    /// Local port the session came in on.
+    pub port: u16,
+    pub peer: IpAddr,
+}
+
+/// The client address and server port of an `SSH_CONNECTION` value,
+/// "client_ip client_port server_ip server_port".
+fn parse_ssh_connection(value: &str) -> Option<(IpAddr, u16)> {
+    let fields: Vec<&str> = value.split_whitespace().collect();
+    if fields.len() != 4 {
+        return None;
+    }
+    Some((fields[0].parse().ok()?, fields[3].parse().ok()?))
+}
+
+/// Ports the SSH server listens on.
+fn ssh_ports(listening: &[ListeningEndpoint], own_session: Option<u16>) -> BTreeSet<u16> {
+    let mut ports: BTreeSet<u16> = listening
+        .iter()
+        .filter(|endpoint| {
+            match endpoint.process_name.as_deref() {
+                Some(name) => name == "sshd" || name.starts_with("sshd-"),
+                // Root's sockets cannot be traced to a process without
+                // privileges, so trust the port number
+                None => endpoint.port == SSH_PORT,
+            }
+        })
+        .map(|endpoint| endpoint.port)
+        .collect();
+    ports.extend(own_session);
+    ports
+}
+
+/// Established connections from remote hosts into `ports`.
+fn sessions_into(ports: &BTreeSet<u16>, connections: &[ActiveConnection]) -> Vec<SshSession> {
+    let mut sessions: Vec<SshSession> = connections
+        .iter()
+        .filter(|c| ports.contains(&c.local_port) && !is_local_ip(c.remote_addr))
+        .map(|c| SshSession {
+            port: c.local_port,
+            peer: c.remote_addr,
+        })
+        .collect();
+    sessions.sort_by_key(|s| (s.port, s.peer));
+    sessions.dedup();
+    sessions
+}
+
+/// Remote SSH sessions into this machine. Reads procfs, so call it off the
+/// main thread.
+pub fn query_ssh_sessions() -> Result<Vec<SshSession>> {
+    let own = std::env::var("SSH_CONNECTION")
+        .ok()
+        .and_then(|value| parse_ssh_connection(&value));
+
+    let mut exposure = NetworkExposure::new();
+    let listening = exposure.scan_sockets()?;
+    let connections = exposure.scan_connections()?;
+
+    let mut sessions = sessions_into(
+        &ssh_ports(&listening, own.map(|(_, port)| port)),
+        &connections,
+    );
+    if let Some((peer, port)) = own.filter(|(peer, _)| !is_local_ip(*peer)) {
+        let session = SshSession { port, peer };
+        if !sessions.contains(&session) {
+            sessions.push(session);
+        }
+    }
+    Ok(sessions)
+}
+
+/// The sessions a rule stopping `protocol` traffic to ports `start..=end`
+/// would cut off. SSH runs over TCP only.
+pub fn sessions_on(
+    sessions: &[SshSession],
+    start: u16,
+    end: u16,
+    protocol: &str,
+) -> Vec<SshSession> {
+    if protocol != "tcp" {
+        return Vec::new();
+    }
+    sessions
+        .iter()
+        .filter(|s| (start..=end).contains(&s.port))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::admin::network::Protocol;
+
+    fn listener(port: u16, process: Option<&str>) -> ListeningEndpoint {
+        ListeningEndpoint {
+            process_name: process.map(str::to_string),
+            ..ListeningEndpoint::for_test(port, Protocol::Tcp, "0.0.0.0")
+        }
+    }
+
+    fn connection(local_port: u16, remote: &str) -> ActiveConnection {
+        ActiveConnection {
+            local_addr: "192.168.1.10".parse().unwrap(),
+            local_port,
+            remote_addr: remote.parse().unwrap(),
+            remote_port: 50000,
+            protocol: Protocol::Tcp,
+            inode: 0,
+            pid: None,
+            process_name: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_ssh_connection() {
+        assert_eq!(
+            parse_ssh_connection("203.0.113.5 51234 192.168.1.10 2222"),
+            Some(("203.0.113.5".parse().unwrap(), 2222))
+        );
+        assert_eq!(parse_ssh_connection(""), None);
+    }
+
+    #[test]
+    fn test_sessions() {
+        let listening = [
+            listener(2222, Some("sshd")),
+            listener(22, None),
+            listener(80, Some("nginx")),
+        ];
+        let ports = ssh_ports(&listening, None);
+        assert_eq!(ports.into_iter().collect::<Vec<_>>(), vec![22, 2222]);
+
+        let connections = [
+            connection(2222, "203.0.113.5"),
+            connection(80, "203.0.113.6"),
+            connection(22, "127.0.0.1"),
+        ];
+        let sessions = sessions_into(&ssh_ports(&listening, None), &connections);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].port, 2222);
+
+        assert_eq!(sessions_on(&sessions, 2000, 3000, "tcp").len(), 1);
+        assert!(sessions_on(&sessions, 2000, 3000, "udp").is_empty());
+        assert!(sessions_on(&sessions, 22, 22, "tcp").is_empty());
+    }
+}
@@ -0,0 +1,200 @@
+// Security Center - Journald and Auditd Status
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! System logging status: journald retention and auditd availability.
+//!
+//! Incident response depends on logs existing, so this reports whether the
+//! journal survives reboots, how much disk it may use and whether the audit
+//! daemon is installed and running. The effective journald settings are
+//! computed the way systemd does: the main `journald.conf` first, then
+//! drop-ins from all configuration directories in file-name order.
+//!
+//! Changes are written by the privileged helper (see `crate::helper`).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::systemd::{ServiceState, SystemdClient};
+
+/// Directories holding `<daemon>.conf.d` drop-ins, lowest priority first.
+const SYSTEMD_DROPIN_ROOTS: [&str; 3] = ["/usr/lib/systemd", "/run/systemd", "/etc/systemd"];
+const PERSISTENT_JOURNAL_DIR: &str = "/var/log/journal";
+const AUDITD_BINARIES: [&str; 2] = ["/usr/sbin/auditd", "/sbin/auditd"];
+
+/// systemd unit of the audit daemon.
+pub const AUDITD_UNIT: &str = "auditd.service";
+
+/// Effective journald and auditd state.
+#[derive(Debug, Clone, Default)]
+pub struct LoggingStatus {
+    /// Effective `Storage=` value (`auto` when unset).
+    pub storage: String,
+    /// Effective `SystemMaxUse=`, `None` for journald's default (10% of the
+    /// file system, capped at 4G).
+    pub system_max_use: Option<String>,
+    /// Logs are written to disk and survive reboots.
+    pub persistent: bool,
+    /// Bytes currently used by the persistent journal.
+    pub journal_usage: u64,
+    pub auditd_installed: bool,
+    pub auditd_running: bool,
+}
+
+/// Read the effective logging configuration.
+pub fn query_logging() -> LoggingStatus {
+    let mut storage: Option<String> = None;
+    let mut system_max_use: Option<String> = None;
+    for text in systemd_config_texts("journald") {
+        let (s, m) = parse_journald_conf(&text);
+        storage = s.or(storage);
+        if let Some(m) = m {
+            system_max_use = m;
+        }
+    }
+    let storage = storage.unwrap_or_else(|| "auto".to_string());
+
+    let journal_dir = Path::new(PERSISTENT_JOURNAL_DIR);
+    let persistent = match storage.as_str() {
+        "persistent" => true,
+        // "auto" stores on disk only when the directory exists
+        "auto" => journal_dir.is_dir(),
+        _ => false,
+    };
+    let journal_usage = if persistent { dir_size(journal_dir) } else { 0 };
+
+    let auditd_installed = AUDITD_BINARIES.iter().any(|p| Path::new(p).exists());
+    let auditd_running = auditd_installed && {
+        let mut client = SystemdClient::new();
+        client.connect().is_ok()
+            && client
+                .get_service_info(AUDITD_UNIT)
+                .is_ok_and(|info| info.state == ServiceState::Running)
+    };
+
+    LoggingStatus {
+        storage,
+        system_max_use,
+        persistent,
+        journal_usage,
+        auditd_installed,
+        auditd_running,
+    }
+}
+
+/// Texts of a systemd daemon configuration (`journald`, `coredump`, ...) in
+/// the order systemd applies them: the main file first, then drop-ins.
+pub(super) fn systemd_config_texts(name: &str) -> Vec<String> {
+    let mut texts: Vec<String> = ["/etc/systemd", "/usr/lib/systemd"]
+        .iter()
+        .find_map(|dir| fs::read_to_string(format!("{}/{}.conf", dir, name)).ok())
+        .into_iter()
+        .collect();
+
+    // Drop-ins are ordered by file name across all directories; a file in a
+    // later directory masks one with the same name in an earlier directory.
+    let mut dropins: Vec<(String, PathBuf)> = Vec::new();
+    for dir in SYSTEMD_DROPIN_ROOTS {
+        let Ok(entries) = fs::read_dir(format!("{}/{}.conf.d", dir, name)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.ends_with(".conf") {
+                continue;
+            }
+            dropins.retain(|(n, _)| *n != name);
+            dropins.push((name, entry.path()));
+        }
+    }
+    dropins.sort_by(|a, b| a.0.cmp(&b.0));
+    texts.extend(
+        dropins
+            .into_iter()
+            .filter_map(|(_, path)| fs::read_to_string(path).ok()),
+    );
+    texts
+}
+
+/// Extract `Storage=` and `SystemMaxUse=` from the `[Journal]` section.
+///
+/// `None` means the file does not set the option; an empty `SystemMaxUse=`
+/// resets it to the default and is returned as `Some(None)`.
+fn parse_journald_conf(text: &str) -> (Option<String>, Option<Option<String>>) {
+    let mut in_journal = false;
+    let mut storage = None;
+    let mut max_use = None;
+    for line in text.lines().map(str::trim) {
+        if line.starts_with('#') || line.starts_with(';') || line.is_empty() {
+            continue;
+        }
+        if line.starts_with('[') {
+            in_journal = line == "[Journal]";
+            continue;
+        }
+        if !in_journal {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        // An empty assignment resets the option to its default
+        let value = (!value.is_empty()).then(|| value.to_string());
+        match key.trim() {
+            "Storage" => storage = value.or(Some("auto".to_string())),
+            "SystemMaxUse" => max_use = Some(value),
+            _ => {}
+        }
+    }
+    (storage, max_use)
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(t) if t.is_dir() => dir_size(&entry.path()),
+            Ok(t) if t.is_file() => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            _ => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_journald_conf() {
+        let conf = "\
+[Journal]
+#Storage=auto
+Storage=persistent
+SystemMaxUse=1G
+[Other]
+Storage=volatile
+";
+        assert_eq!(
+            parse_journald_conf(conf),
+            (Some("persistent".to_string()), Some(Some("1G".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_parse_journald_conf_defaults() {
+        assert_eq!(
+            parse_journald_conf("[Journal]\n#SystemMaxUse=\n"),
+            (None, None)
+        );
+        assert_eq!(
+            parse_journald_conf("[Journal]\nSystemMaxUse=\n"),
+            (None, Some(None))
+        );
+        // Outside the [Journal] section nothing applies
+        assert_eq!(parse_journald_conf("Storage=volatile\n"), (None, None));
+    }
+}
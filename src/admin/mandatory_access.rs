@@ -0,0 +1,333 @@
+// Security Center - Mandatory Access Control
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Mandatory access control status: SELinux or AppArmor.
+//!
+//! SELinux is read from selinuxfs (`/sys/fs/selinux/enforce`) and
+//! `/etc/selinux/config`; AppArmor from the module parameter and its
+//! securityfs profile list, which only root can read. Recent denials come
+//! from the audit and kernel messages in the system journal, so like the
+//! firewall log they need membership in the wheel, adm or systemd-journal
+//! group.
+//!
+//! Switching SELinux between enforcing and permissive mode is done by the
+//! privileged helper (see `crate::helper`) and lasts until the next boot.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+use crate::helper::{run_privileged, HelperOp};
+
+/// `1` when SELinux enforces, `0` when permissive; absent when disabled.
+pub const SELINUX_ENFORCE: &str = "/sys/fs/selinux/enforce";
+const SELINUX_CONFIG: &str = "/etc/selinux/config";
+const APPARMOR_ENABLED: &str = "/sys/module/apparmor/parameters/enabled";
+const APPARMOR_PROFILES: &str = "/sys/kernel/security/apparmor/profiles";
+/// How far back denials are read.
+const JOURNAL_SINCE: &str = "-7d";
+/// Journal entries read back, newest first, before parsing.
+const JOURNAL_LINES: &str = "5000";
+/// Distinct denials kept.
+const MAX_DENIALS: usize = 50;
+
+/// SELinux state.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelinuxStatus {
+    /// Denials are enforced now, rather than only logged.
+    pub enforcing: bool,
+    /// `SELINUX=` in the config file: the mode at the next boot.
+    pub boot_mode: Option<String>,
+    /// `SELINUXTYPE=`, e.g. `targeted`.
+    pub policy: Option<String>,
+}
+
+/// AppArmor state. The counts are `None` when the profile list cannot be
+/// read without root.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AppArmorStatus {
+    pub enforce: Option<usize>,
+    pub complain: Option<usize>,
+}
+
+/// The mandatory access control system in use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MacSystem {
+    Selinux(SelinuxStatus),
+    AppArmor(AppArmorStatus),
+    None,
+}
+
+/// Denials of one program doing one thing to one target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacDenial {
+    /// Command name of the denied process.
+    pub process: String,
+    /// Denied permissions or operation, e.g. `read` or `open`.
+    pub permission: String,
+    /// File name or path acted on, when logged.
+    pub target: Option<String>,
+    /// SELinux source type or AppArmor profile.
+    pub context: String,
+    /// SELinux was permissive, so the access went through.
+    pub permissive: bool,
+    pub count: usize,
+    /// Unix time of the most recent denial.
+    pub last_seen: u64,
+}
+
+/// Mandatory access control state and recent denials.
+#[derive(Debug, Clone)]
+pub struct MandatoryAccess {
+    pub system: MacSystem,
+    /// Newest first.
+    pub denials: Vec<MacDenial>,
+}
+
+/// Read the MAC state and the denials of the last week. Blocks on
+/// `journalctl`, so call it off the main thread.
+pub fn query_mandatory_access() -> MandatoryAccess {
+    let system = detect_system();
+    let denials = match system {
+        MacSystem::None => Vec::new(),
+        _ => read_denials().unwrap_or_default(),
+    };
+    MandatoryAccess { system, denials }
+}
+
+/// Switch SELinux to enforcing or permissive mode until the next boot,
+/// through the privileged helper.
+pub fn set_selinux_enforcing(enforcing: bool) -> Result<String> {
+    run_privileged(&HelperOp::SetSelinuxMode { enforcing })
+}
+
+/// Write the SELinux mode. Runs as root inside the helper process.
+pub fn write_selinux_enforcing(enforcing: bool) -> Result<String> {
+    if !Path::new(SELINUX_ENFORCE).exists() {
+        bail!("SELinux is not enabled");
+    }
+    fs::write(SELINUX_ENFORCE, if enforcing { "1" } else { "0" })
+        .context("Failed to change the SELinux mode")?;
+    Ok(if enforcing {
+        "SELinux is enforcing".to_string()
+    } else {
+        "SELinux is permissive until the next boot".to_string()
+    })
+}
+
+fn detect_system() -> MacSystem {
+    if let Ok(enforce) = fs::read_to_string(SELINUX_ENFORCE) {
+        let config = fs::read_to_string(SELINUX_CONFIG).unwrap_or_default();
+        let settings = parse_selinux_config(&config);
+        return MacSystem::Selinux(SelinuxStatus {
+            enforcing: enforce.trim() == "1",
+            boot_mode: settings.get("SELINUX").cloned(),
+            policy: settings.get("SELINUXTYPE").cloned(),
+        });
+    }
+    let apparmor = fs::read_to_string(APPARMOR_ENABLED).is_ok_and(|v| v.trim() == "Y");
+    if apparmor {
+        let (enforce, complain) = match fs::read_to_string(APPARMOR_PROFILES) {
+            Ok(profiles) => {
+                let (enforce, complain) = count_apparmor_profiles(&profiles);
+                (Some(enforce), Some(complain))
+            }
+            Err(_) => (None, None),
+        };
+        return MacSystem::AppArmor(AppArmorStatus { enforce, complain });
+    }
+    MacSystem::None
+}
+
+/// `KEY=value` lines of `/etc/selinux/config`.
+fn parse_selinux_config(text: &str) -> HashMap<String, String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_lowercase()))
+        .collect()
+}
+
+/// Profiles in enforce and complain mode, from lines like
+/// `/usr/sbin/cupsd (enforce)`.
+fn count_apparmor_profiles(text: &str) -> (usize, usize) {
+    let mut enforce = 0;
+    let mut complain = 0;
+    for line in text.lines() {
+        if line.ends_with("(enforce)") {
+            enforce += 1;
+        } else if line.ends_with("(complain)") {
+            complain += 1;
+        }
+    }
+    (enforce, complain)
+}
+
+fn read_denials() -> Result<Vec<MacDenial>> {
+    let output = Command::new("journalctl")
+        .args(["-o", "json", "--no-pager", "-r", "-n", JOURNAL_LINES])
+        .args(["--since", JOURNAL_SINCE])
+        .args(["_TRANSPORT=audit", "_TRANSPORT=kernel"])
+        .output()
+        .context("Failed to run journalctl")?;
+
+    let mut denials: Vec<MacDenial> = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Some((denial, time)) = parse_journal_entry(line) else {
+            continue;
+        };
+        // Entries come newest first, so the first one sets last_seen
+        if let Some(existing) = denials.iter_mut().find(|d| d.same_as(&denial)) {
+            existing.count += 1;
+        } else if denials.len() < MAX_DENIALS {
+            denials.push(MacDenial {
+                last_seen: time,
+                ..denial
+            });
+        }
+    }
+    Ok(denials)
+}
+
+impl MacDenial {
+    fn same_as(&self, other: &MacDenial) -> bool {
+        self.process == other.process
+            && self.permission == other.permission
+            && self.target == other.target
+            && self.context == other.context
+    }
+}
+
+fn parse_journal_entry(line: &str) -> Option<(MacDenial, u64)> {
+    let entry: serde_json::Value = serde_json::from_str(line).ok()?;
+    let denial = parse_denial(entry.get("MESSAGE")?.as_str()?)?;
+    let micros: u64 = entry.get("__REALTIME_TIMESTAMP")?.as_str()?.parse().ok()?;
+    Some((denial, micros / 1_000_000))
+}
+
+/// Parse an SELinux AVC denial or an AppArmor `DENIED` audit message.
+fn parse_denial(message: &str) -> Option<MacDenial> {
+    if let Some(rest) = message.split_once("avc:").map(|(_, rest)| rest) {
+        let rest = rest.trim_start().strip_prefix("denied")?;
+        let (permissions, fields) = rest.split_once('}')?;
+        let permission = permissions
+            .trim_start()
+            .strip_prefix('{')?
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let fields = audit_fields(fields);
+        let context = fields
+            .get("scontext")
+            .and_then(|c| c.split(':').nth(2))
+            .unwrap_or("unknown")
+            .to_string();
+        return Some(MacDenial {
+            process: fields.get("comm")?.to_string(),
+            permission,
+            target: fields
+                .get("name")
+                .or_else(|| fields.get("path"))
+                .map(|s| s.to_string()),
+            context,
+            permissive: fields.get("permissive").is_some_and(|p| *p == "1"),
+            count: 1,
+            last_seen: 0,
+        });
+    }
+    if message.contains("apparmor=\"DENIED\"") {
+        let fields = audit_fields(message);
+        return Some(MacDenial {
+            process: fields.get("comm")?.to_string(),
+            permission: fields.get("operation")?.to_string(),
+            target: fields.get("name").map(|s| s.to_string()),
+            context: fields.get("profile")?.to_string(),
+            permissive: false,
+            count: 1,
+            last_seen: 0,
+        });
+    }
+    None
+}
+
+/// `key=value` pairs of an audit message; quoted values lose their quotes.
+fn audit_fields(text: &str) -> HashMap<&str, &str> {
+    let mut fields = HashMap::new();
+    let mut rest = text;
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].rsplit(' ').next().unwrap_or("");
+        let after = &rest[eq + 1..];
+        let (value, next) = match after.strip_prefix('"') {
+            Some(quoted) => match quoted.find('"') {
+                Some(end) => (&quoted[..end], &quoted[end + 1..]),
+                None => (quoted, ""),
+            },
+            None => match after.find(' ') {
+                Some(end) => (&after[..end], &after[end..]),
+                None => (after, ""),
+            },
+        };
+        if !key.is_empty() {
+            fields.insert(key, value);
+        }
+        rest = next;
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_selinux_denial() {
+        let message = "avc:  denied  { read write } for  pid=1234 comm=\"httpd\" \
+                       name=\"index.html\" dev=\"dm-0\" ino=4242 \
+                       scontext=system_u:system_r:httpd_t:s0 \
+                       tcontext=unconfined_u:object_r:user_home_t:s0 tclass=file permissive=1";
+        let denial = parse_denial(message).unwrap();
+        assert_eq!(denial.process, "httpd");
+        assert_eq!(denial.permission, "read write");
+        assert_eq!(denial.target.as_deref(), Some("index.html"));
+        assert_eq!(denial.context, "httpd_t");
+        assert!(denial.permissive);
+        assert!(parse_denial("avc:  granted  { setenforce } for pid=1 comm=\"x\"").is_none());
+    }
+
+    #[test]
+    fn test_parse_apparmor_denial() {
+        let message = "audit: type=1400 audit(1700000000.123:42): apparmor=\"DENIED\" \
+                       operation=\"open\" class=\"file\" profile=\"/usr/sbin/cupsd\" \
+                       name=\"/etc/shadow\" pid=812 comm=\"cupsd\" requested_mask=\"r\" \
+                       denied_mask=\"r\" fsuid=0 ouid=0";
+        let denial = parse_denial(message).unwrap();
+        assert_eq!(denial.process, "cupsd");
+        assert_eq!(denial.permission, "open");
+        assert_eq!(denial.target.as_deref(), Some("/etc/shadow"));
+        assert_eq!(denial.context, "/usr/sbin/cupsd");
+        assert!(parse_denial("apparmor=\"STATUS\" operation=\"profile_load\"").is_none());
+    }
+
+    #[test]
+    fn test_selinux_config_and_profiles() {
+        let config = "# comment\nSELINUX=Enforcing\nSELINUXTYPE=targeted\n";
+        let settings = parse_selinux_config(config);
+        assert_eq!(
+            settings.get("SELINUX").map(String::as_str),
+            Some("enforcing")
+        );
+        assert_eq!(
+            settings.get("SELINUXTYPE").map(String::as_str),
+            Some("targeted")
+        );
+
+        let profiles = "/usr/sbin/cupsd (enforce)\nnvidia_modprobe (complain)\n\
+                        /usr/bin/man (enforce)\nunconfined (unconfined)\n";
+        assert_eq!(count_apparmor_profiles(profiles), (2, 1));
+    }
+}
@@ -7,7 +7,44 @@
 //! This module contains pure Rust implementations for:
 //! - systemd service management via D-Bus
 //! - Network exposure introspection via procfs
-//! - Coordinated admin actions
+//! - Per-interface exposure: listeners each interface's zone lets through
+//! - Listening sockets and the rules covering them, grouped by application
+//! - Package ownership and checksums of listening executables
+//! - User and effective capabilities of listening processes
+//! - Quarantine of suspicious processes: snapshot, port blocks, service stop
+//! - mDNS advertisement introspection via avahi-daemon
+//! - Firewall activity: delivered packets and denied packets from the kernel log
+//! - Tripwire ports: alerts on probes of unused ports and blocking the prober
+//! - Per-address history (SSH logins, fail2ban bans) and block/trust actions
+//! - fail2ban jails, banned addresses and unbanning over fail2ban's socket
+//! - ClamAV detection, signature age and on-demand scans
+//! - Remote SSH sessions a firewall change could cut off
+//! - Other network namespaces (containers, VPNs) and exposure scans inside them
+//! - Wake-on-LAN (ethtool netlink) and remote management services
+//! - Terminating connections (conntrack netlink)
+//! - Connection tracking helper policy (automatic helpers, loaded modules)
+//! - Other firewall managers (ufw, Shorewall, iptables) running next to firewalld
+//! - Time synchronization (NTP/NTS) status
+//! - Tor and proxy detection, with proxies set system-wide where none belong
+//! - Journald retention and auditd status
+//! - Core dump storage and set-UID dump policy
+//! - Hit counters of firewalld port rules from nftables
+//! - Verification of port rule changes against the running firewall
+//! - Compliance profile scans with OpenSCAP
+//! - Desired-state policy files: drift detection and enforcement
+//! - TPM and measured boot indicators via sysfs
+//! - FIDO2/U2F security keys and locking the session when one is removed
+//! - SELinux/AppArmor status, recent denials and the SELinux mode
+//! - Typed admin operations with structured results
+//! - Travel hardening steps (zone, file sharing, MAC address, SSH, screen lock)
+//! - Role checklists (web server, workstation, home NAS) linking to pages and operations
+//! - Zone suggestions for the current network from NetworkManager metadata
+//! - New network interfaces announced by NetworkManager, for zone prompts
+//! - Security assessment checks (boot chain, core dumps, helpers, ...) with
+//!   remediation guidance and a hardening score
+//! - Security posture score (firewall, default zone, SSH, listeners, panic
+//!   mode, unsaved runtime rules) with remedies offered on the Overview
+//! - Discovery and secure removal of sensitive leftovers
 //!
 //! # Architecture
 //!
@@ -19,18 +56,137 @@
 //! ```
 
 mod actions;
+mod antivirus;
+mod applications;
+mod assessment;
+mod avahi;
+mod binary_integrity;
+mod bootloader;
+mod checklists;
+mod cleanup;
+mod compliance;
+mod conntrack;
+mod coredump;
+mod ct_helpers;
+mod fail2ban;
+mod firewall_conflicts;
+mod firewall_log;
 mod geoip;
+mod hotplug;
+mod interface_exposure;
 mod ipinfo;
+mod lockout;
+mod logging;
+mod mandatory_access;
+mod namespaces;
+mod netlink;
 mod network;
+mod policy;
+mod posture;
+mod privileges;
+mod proxy;
+mod quarantine;
+mod remote_access;
+mod rule_counters;
+mod rule_probe;
+mod security_keys;
 mod sock_diag;
+mod source_history;
+mod timesync;
+mod tpm;
+mod travel;
+mod tripwire;
+mod zone_advisor;
 
 pub use actions::{
-    ActionCategory, AdminAction, AdminActionResult, QuickActionsManager, QUICK_ACTIONS,
+    is_risky_default_zone, is_risky_service, ActionCategory, AdminAction, Operation,
+    OperationResult, OperationRunner, Undo, QUICK_ACTIONS, REVERT_SECS,
 };
+pub use antivirus::{
+    query_antivirus, AntivirusScan, AntivirusStatus, Detection, ScanProgress, Scanner, Signatures,
+    OUTDATED_AFTER_DAYS,
+};
+pub use applications::{
+    query_applications, AppListener, AppRule, Application, ApplicationView, RuleKind,
+};
+pub use assessment::{
+    zone_suggestions, Assessment, Finding, FindingCategory, Severity, ZoneServiceRule,
+};
+pub use avahi::{query_avahi, AdvertisedService, AvahiStatus, MdnsExposure, AVAHI_UNITS};
+pub use binary_integrity::{
+    verify_listeners, verify_listening_binaries, BinaryIntegrity, IntegrityStatus,
+};
+pub use checklists::{ChecklistItem, ChecklistRole};
+pub use cleanup::{
+    clean_leftovers, cleanup_due, history_sizes, remove_core_dumps, scan_leftovers,
+    validate_coredump_name, CleanupCategory, CleanupItem, CleanupReport, MAX_CORE_DUMPS,
+};
+pub use compliance::{
+    evaluate_profile, query_compliance, run_compliance_scan, validate_datastream,
+    validate_profile_id, ComplianceReport, ComplianceStatus, RuleOutcome, RuleResult,
+};
+pub use conntrack::{terminate_connections, terminate_flow, Flow};
+pub use coredump::{query_coredump, CoredumpStatus};
+pub use ct_helpers::{helper_description, query_helper_policy, HelperPolicy};
+pub use fail2ban::{
+    query_fail2ban, read_jails_json, read_jails_privileged, unban_ip, validate_jail_name,
+    Fail2banClient, Fail2banStatus, Jail, FAIL2BAN_SOCKET,
+};
+pub use firewall_conflicts::{query_firewall_conflicts, FirewallConflict};
+pub use firewall_log::{read_delivered_packets, DeniedLog, DeniedPacket};
 pub use geoip::GeoIp;
+pub use hotplug::{watch_interfaces, NewInterface};
+pub use interface_exposure::{
+    query_interface_addresses, query_interface_exposure, InterfaceAddresses, InterfaceExposure,
+    InterfaceKind,
+};
 pub use ipinfo::{lookup_ip_online, IpDetails};
+pub use lockout::{query_ssh_sessions, sessions_on, SshSession};
+pub use logging::{query_logging, LoggingStatus, AUDITD_UNIT};
+pub use mandatory_access::{
+    query_mandatory_access, set_selinux_enforcing, write_selinux_enforcing, AppArmorStatus,
+    MacDenial, MacSystem, MandatoryAccess, SelinuxStatus,
+};
+pub use namespaces::{
+    query_namespaces, scan_in_namespace, scan_namespace, NamespaceRef, NamespaceScan, NetNamespace,
+};
 pub use network::{
     get_service_name, is_local_ip, ActiveConnection, FirewallStatus, ListeningEndpoint,
-    NetworkExposure,
+    NetworkExposure, Protocol,
+};
+pub use policy::{
+    check_policy, create_policy_file, diff, enforce, enforce_unattended, enforcement_due, observe,
+    policy_from_system, policy_path, sysctl_path, DesiredState, Drift, EnforceReport,
+};
+pub use posture::{
+    evaluate_posture, query_posture, query_posture_input, Posture, PostureCheck, PostureInput,
+    Remedy,
+};
+pub use privileges::{query_listener_privileges, ProcessPrivileges};
+pub use proxy::{
+    query_proxy_status, ProxySetting, ProxySource, ProxyStatus, RoutedProgram, TorStatus,
 };
+pub use quarantine::{plan_quarantine, quarantine, QuarantinePlan};
+pub use remote_access::{
+    clear_wake_on_lan, disable_wake_on_lan, query_access_methods, query_wake_on_lan,
+    remote_access_endpoints, AccessMethod, AccessMethodStatus, WakeOnLan,
+};
+pub use rule_counters::{read_rule_counters, RuleCounters};
+pub use rule_probe::{probe_port, Expectation, RuleProbe};
+pub use security_keys::{lock_sessions, query_security_keys, KeyRemovalWatch, SecurityKey};
 pub use sock_diag::{collect_socket_bytes, collect_top_talkers, TalkerBytes};
+pub use source_history::{
+    block_source, query_source_history, remove_address_rule, trust_source, AuthEventKind,
+    DeniedSummary, SourceHistory,
+};
+pub use timesync::{query_time_sync, TimeDaemon, TimeSyncStatus, TIMESYNCD_UNIT};
+pub use tpm::{pcr_is_unused, pcr_purpose, query_tpm, read_pcr_banks, PcrBank, TpmStatus};
+pub use travel::{
+    enable_screen_lock, query_travel_plan, TravelPlan, TravelStep, MAC_DROPIN, MAC_DROPIN_CONTENTS,
+    SSH_DROPIN, SSH_DROPIN_CONTENTS, SSH_UNITS,
+};
+pub use tripwire::{parse_ports, protected_addresses, TripwireHit, Tripwires};
+pub use zone_advisor::{
+    apply_zone_suggestion, query_primary_network, query_zone_suggestion, suggest_zone,
+    ActiveNetwork, NetworkKind, NetworkSecurity, ZoneSuggestion,
+};
@@ -0,0 +1,346 @@
+// Security Center - Network Namespaces
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Network namespaces besides the host's, and exposure scans inside them.
+//!
+//! Containers, VPN setups and sandboxes often run in a network namespace of
+//! their own, with separate interfaces, sockets and (possibly) a separate
+//! firewall, so the host's procfs scan does not see them. [`query_namespaces`]
+//! lists the namespaces named by `ip netns` under `/run/netns` and those of
+//! the processes the user may inspect, and [`scan_namespace`] runs the
+//! exposure scan inside one of them.
+//!
+//! Entering a namespace with `setns(2)` needs root, so the scan runs in the
+//! privileged helper (see `crate::helper`). Once the helper has switched,
+//! `/proc/net` shows the sockets of the namespace while `/proc/<pid>` still
+//! covers every process, so [`NetworkExposure`] works unchanged.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::net::IpAddr;
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::io::AsRawFd;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::network::{NetworkExposure, Protocol};
+use crate::helper::{run_privileged, HelperOp};
+
+/// Where `ip netns add` bind-mounts named namespaces.
+const NAMED_NETNS_DIR: &str = "/run/netns";
+
+/// A network namespace, by the name `ip netns` gave it or by a process in it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NamespaceRef {
+    Named(String),
+    Process(u32),
+}
+
+impl NamespaceRef {
+    /// File that refers to the namespace.
+    fn path(&self) -> PathBuf {
+        match self {
+            NamespaceRef::Named(name) => PathBuf::from(NAMED_NETNS_DIR).join(name),
+            NamespaceRef::Process(pid) => PathBuf::from(format!("/proc/{}/ns/net", pid)),
+        }
+    }
+
+    /// Parse a helper argument written by [`fmt::Display`], validating it.
+    pub fn parse(arg: &str) -> Option<Self> {
+        if let Some(name) = arg.strip_prefix("name=") {
+            return validate_netns_name(name).map(|name| NamespaceRef::Named(name.to_string()));
+        }
+        let pid: u32 = arg.strip_prefix("pid=")?.parse().ok()?;
+        (pid > 0).then_some(NamespaceRef::Process(pid))
+    }
+}
+
+impl fmt::Display for NamespaceRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NamespaceRef::Named(name) => write!(f, "name={}", name),
+            NamespaceRef::Process(pid) => write!(f, "pid={}", pid),
+        }
+    }
+}
+
+/// Accept a namespace name as `ip netns` does: one path component.
+pub fn validate_netns_name(name: &str) -> Option<&str> {
+    if !name.is_empty()
+        && name.len() <= 64
+        && name != "."
+        && name != ".."
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+    {
+        Some(name)
+    } else {
+        None
+    }
+}
+
+/// A network namespace other than the application's own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetNamespace {
+    /// Inode of the namespace, which identifies it.
+    pub inode: u64,
+    /// Name from `ip netns`, if it has one.
+    pub name: Option<String>,
+    /// Processes in the namespace the user may inspect, lowest first.
+    pub pids: Vec<u32>,
+    /// Command name of the first process.
+    pub process: Option<String>,
+}
+
+impl NetNamespace {
+    /// How the helper enters the namespace.
+    pub fn reference(&self) -> Option<NamespaceRef> {
+        match (&self.name, self.pids.first()) {
+            (Some(name), _) => Some(NamespaceRef::Named(name.clone())),
+            (None, Some(pid)) => Some(NamespaceRef::Process(*pid)),
+            (None, None) => None,
+        }
+    }
+
+    /// The name, or the first process and its PID.
+    pub fn label(&self) -> String {
+        match (&self.name, &self.process, self.pids.first()) {
+            (Some(name), _, _) => name.clone(),
+            (None, Some(process), Some(pid)) => format!("{} (pid {})", process, pid),
+            (None, None, Some(pid)) => format!("pid {}", pid),
+            _ => format!("net:[{}]", self.inode),
+        }
+    }
+}
+
+/// The inode in a namespace link such as `net:[4026531840]`.
+fn parse_ns_link(link: &str) -> Option<u64> {
+    link.strip_prefix("net:[")?.strip_suffix(']')?.parse().ok()
+}
+
+fn process_namespace(pid: &str) -> Option<u64> {
+    let link = fs::read_link(format!("/proc/{}/ns/net", pid)).ok()?;
+    parse_ns_link(&link.to_string_lossy())
+}
+
+fn entry(namespaces: &mut BTreeMap<u64, NetNamespace>, inode: u64) -> &mut NetNamespace {
+    namespaces.entry(inode).or_insert_with(|| NetNamespace {
+        inode,
+        name: None,
+        pids: Vec::new(),
+        process: None,
+    })
+}
+
+/// Group named namespaces and processes by namespace inode, leaving out
+/// `own`.
+fn group_namespaces(
+    own: u64,
+    named: Vec<(String, u64)>,
+    processes: Vec<(u32, u64, Option<String>)>,
+) -> Vec<NetNamespace> {
+    let mut namespaces: BTreeMap<u64, NetNamespace> = BTreeMap::new();
+    for (name, inode) in named {
+        let namespace = entry(&mut namespaces, inode);
+        // Several names can refer to one namespace; keep the first in order
+        if namespace.name.as_ref().is_none_or(|n| name < *n) {
+            namespace.name = Some(name);
+        }
+    }
+    for (pid, inode, comm) in processes {
+        let namespace = entry(&mut namespaces, inode);
+        if namespace.pids.first().is_none_or(|first| pid < *first) {
+            namespace.process = comm;
+        }
+        namespace.pids.push(pid);
+    }
+
+    let mut namespaces: Vec<NetNamespace> = namespaces
+        .into_values()
+        .filter(|ns| ns.inode != own)
+        .map(|mut ns| {
+            ns.pids.sort_unstable();
+            ns
+        })
+        .collect();
+    namespaces.sort_by_key(|ns| (ns.name.is_none(), ns.label()));
+    namespaces
+}
+
+/// Network namespaces other than the application's own. Processes of other
+/// users only show up when running as root. Reads procfs, so call it off the
+/// main thread.
+pub fn query_namespaces() -> Result<Vec<NetNamespace>> {
+    let own = process_namespace("self").context("Cannot read the current network namespace")?;
+
+    let mut named = Vec::new();
+    if let Ok(entries) = fs::read_dir(NAMED_NETNS_DIR) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            // The mount point has the inode of the namespace once mounted
+            if let (Some(name), Ok(metadata)) = (validate_netns_name(&name), entry.metadata()) {
+                named.push((name.to_string(), metadata.ino()));
+            }
+        }
+    }
+
+    let mut processes = Vec::new();
+    for entry in fs::read_dir("/proc")
+        .context("Failed to read /proc")?
+        .flatten()
+    {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let Ok(pid) = name.parse::<u32>() else {
+            continue;
+        };
+        // Unreadable for processes the user may not trace
+        if let Some(inode) = process_namespace(&name) {
+            let comm = fs::read_to_string(format!("/proc/{}/comm", pid))
+                .ok()
+                .map(|s| s.trim().to_string());
+            processes.push((pid, inode, comm));
+        }
+    }
+
+    Ok(group_namespaces(own, named, processes))
+}
+
+/// A listening socket inside a namespace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamespaceListener {
+    pub local_addr: IpAddr,
+    pub port: u16,
+    pub protocol: Protocol,
+    pub process_name: Option<String>,
+}
+
+impl NamespaceListener {
+    /// Listening on every interface of the namespace.
+    pub fn is_exposed(&self) -> bool {
+        self.local_addr.is_unspecified()
+    }
+}
+
+/// An established connection inside a namespace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamespaceConnection {
+    pub local_port: u16,
+    pub remote_addr: IpAddr,
+    pub remote_port: u16,
+    pub process_name: Option<String>,
+}
+
+/// What a namespace listens on and is connected to.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamespaceScan {
+    pub listening: Vec<NamespaceListener>,
+    pub connections: Vec<NamespaceConnection>,
+}
+
+impl NamespaceScan {
+    pub fn exposed_count(&self) -> usize {
+        self.listening.iter().filter(|l| l.is_exposed()).count()
+    }
+}
+
+/// Scan the sockets of `namespace` in the privileged helper.
+pub fn scan_namespace(namespace: &NamespaceRef) -> Result<NamespaceScan> {
+    let json = run_privileged(&HelperOp::ScanNamespace {
+        namespace: namespace.clone(),
+    })?;
+    serde_json::from_str(&json).context("Invalid namespace scan output")
+}
+
+/// Switch into `namespace` and scan its sockets. Runs as root inside the
+/// helper process, which stays in the namespace until it exits, on its
+/// main thread so `/proc/self` follows the switch.
+pub fn scan_in_namespace(namespace: &NamespaceRef) -> Result<String> {
+    let file = fs::File::open(namespace.path())
+        .with_context(|| format!("Cannot open the {} namespace", namespace))?;
+    // SAFETY: setns only reads the descriptor, which `file` keeps open
+    if unsafe { libc::setns(file.as_raw_fd(), libc::CLONE_NEWNET) } != 0 {
+        return Err(anyhow!(
+            "Cannot enter the {} namespace: {}",
+            namespace,
+            std::io::Error::last_os_error()
+        ));
+    }
+
+    let mut exposure = NetworkExposure::new();
+    let mut listening: Vec<NamespaceListener> = exposure
+        .scan_sockets()?
+        .into_iter()
+        .map(|e| NamespaceListener {
+            local_addr: e.local_addr,
+            port: e.port,
+            protocol: e.protocol,
+            process_name: e.process_name,
+        })
+        .collect();
+    listening.dedup();
+    let connections = exposure
+        .scan_connections()?
+        .into_iter()
+        .map(|c| NamespaceConnection {
+            local_port: c.local_port,
+            remote_addr: c.remote_addr,
+            remote_port: c.remote_port,
+            process_name: c.process_name,
+        })
+        .collect();
+    Ok(serde_json::to_string(&NamespaceScan {
+        listening,
+        connections,
+    })?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namespace_ref_args() {
+        for namespace in [
+            NamespaceRef::Named("vpn".to_string()),
+            NamespaceRef::Process(4242),
+        ] {
+            assert_eq!(NamespaceRef::parse(&namespace.to_string()), Some(namespace));
+        }
+        assert_eq!(NamespaceRef::parse("name=../etc"), None);
+        assert_eq!(NamespaceRef::parse("name=.."), None);
+        assert_eq!(NamespaceRef::parse("pid=0"), None);
+        assert_eq!(NamespaceRef::parse("path=/proc/1/ns/net"), None);
+    }
+
+    #[test]
+    fn test_group_namespaces() {
+        assert_eq!(parse_ns_link("net:[4026531840]"), Some(4026531840));
+        assert_eq!(parse_ns_link("mnt:[4026531840]"), None);
+
+        let namespaces = group_namespaces(
+            1,
+            vec![("vpn".to_string(), 3)],
+            vec![
+                (10, 1, Some("bash".to_string())),
+                (30, 2, Some("sleep".to_string())),
+                (20, 2, Some("podman".to_string())),
+                (40, 3, None),
+            ],
+        );
+        assert_eq!(namespaces.len(), 2);
+        assert_eq!(namespaces[0].label(), "vpn");
+        assert_eq!(
+            namespaces[0].reference(),
+            Some(NamespaceRef::Named("vpn".to_string()))
+        );
+        assert_eq!(namespaces[1].pids, vec![20, 30]);
+        assert_eq!(namespaces[1].label(), "podman (pid 20)");
+        assert_eq!(namespaces[1].reference(), Some(NamespaceRef::Process(20)));
+    }
+}
@@ -0,0 +1,176 @@
+// Security Center - Raw Netlink Requests
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Requests to the netlink families the netlink crates here do not model:
+//! rtnetlink addresses, ctnetlink and generic netlink (ethtool).
+//!
+//! Callers build the family header and attributes with [`put_attr`],
+//! [`message`] frames
+//! them, and [`request`] sends them and collects the replies until the
+//! kernel ends the dump or acknowledges the request.
+
+use anyhow::{anyhow, bail, Result};
+use netlink_sys::Socket;
+
+/// `NLMSG_ERROR`, also the acknowledgement when its errno is zero.
+const NLMSG_ERROR: u16 = 2;
+const NLMSG_DONE: u16 = 3;
+const HEADER_LEN: usize = 16;
+/// Receive calls before a reply that never ends is given up on.
+const MAX_RECEIVES: usize = 1024;
+
+pub(super) const NLA_F_NESTED: u16 = 0x8000;
+const NLA_TYPE_MASK: u16 = 0x3fff;
+
+/// A reply to a [`request`]: its message type and the payload after the
+/// netlink header.
+pub(super) struct Reply {
+    pub msg_type: u16,
+    pub payload: Vec<u8>,
+}
+
+/// Frame `body` in a netlink header of `msg_type` with `flags` and `seq`.
+pub(super) fn message(msg_type: u16, flags: u16, seq: u32, body: &[u8]) -> Vec<u8> {
+    let len = HEADER_LEN + body.len();
+    let mut msg = Vec::with_capacity(len);
+    msg.extend_from_slice(&(len as u32).to_ne_bytes());
+    msg.extend_from_slice(&msg_type.to_ne_bytes());
+    msg.extend_from_slice(&flags.to_ne_bytes());
+    msg.extend_from_slice(&seq.to_ne_bytes());
+    // Port id: the kernel fills in the sender
+    msg.extend_from_slice(&0u32.to_ne_bytes());
+    msg.extend_from_slice(body);
+    msg
+}
+
+/// Send `msg`, framed by [`message`] with sequence number `seq`, and
+/// collect the replies to it until `NLMSG_DONE` or the acknowledgement.
+/// An error acknowledgement is returned as the [`std::io::Error`] of its
+/// errno.
+pub(super) fn request(socket: &mut Socket, seq: u32, msg: &[u8]) -> Result<Vec<Reply>> {
+    socket.send(msg, 0)?;
+
+    let mut replies = Vec::new();
+    let mut buf = vec![0u8; 32 * 1024];
+    // Safety cap: never loop forever if the kernel misbehaves.
+    for _ in 0..MAX_RECEIVES {
+        let size = socket.recv(&mut &mut buf[..], 0)?;
+        let mut data = &buf[..size];
+        while data.len() >= HEADER_LEN {
+            let msg_len = u32::from_ne_bytes(data[0..4].try_into()?) as usize;
+            let msg_type = u16::from_ne_bytes(data[4..6].try_into()?);
+            let msg_seq = u32::from_ne_bytes(data[8..12].try_into()?);
+            if msg_len < HEADER_LEN || msg_len > data.len() {
+                bail!("Malformed netlink message");
+            }
+            let payload = &data[HEADER_LEN..msg_len];
+            data = &data[align4(msg_len).min(data.len())..];
+            if msg_seq != seq {
+                continue;
+            }
+            match msg_type {
+                NLMSG_DONE => return Ok(replies),
+                NLMSG_ERROR => {
+                    let errno = payload
+                        .get(..4)
+                        .and_then(|b| b.try_into().ok())
+                        .map(i32::from_ne_bytes)
+                        .ok_or_else(|| anyhow!("Malformed netlink error"))?;
+                    if errno == 0 {
+                        return Ok(replies);
+                    }
+                    return Err(std::io::Error::from_raw_os_error(-errno).into());
+                }
+                _ => replies.push(Reply {
+                    msg_type,
+                    payload: payload.to_vec(),
+                }),
+            }
+        }
+    }
+    bail!("No netlink acknowledgement")
+}
+
+/// The errno of an error acknowledgement returned by [`request`].
+pub(super) fn errno(error: &anyhow::Error) -> Option<i32> {
+    error
+        .downcast_ref::<std::io::Error>()
+        .and_then(std::io::Error::raw_os_error)
+}
+
+/// Append a netlink attribute, padded to four bytes.
+pub(super) fn put_attr(buf: &mut Vec<u8>, kind: u16, payload: &[u8]) {
+    let len = 4 + payload.len();
+    buf.extend_from_slice(&(len as u16).to_ne_bytes());
+    buf.extend_from_slice(&kind.to_ne_bytes());
+    buf.extend_from_slice(payload);
+    buf.resize(buf.len() + (align4(len) - len), 0);
+}
+
+/// Split a run of netlink attributes into `(type, payload)`, without the
+/// nested and byte-order flags.
+pub(super) fn parse_attrs(mut data: &[u8]) -> Vec<(u16, &[u8])> {
+    let mut attrs = Vec::new();
+    while data.len() >= 4 {
+        let len = u16::from_ne_bytes([data[0], data[1]]) as usize;
+        let kind = u16::from_ne_bytes([data[2], data[3]]) & NLA_TYPE_MASK;
+        if len < 4 || len > data.len() {
+            break;
+        }
+        attrs.push((kind, &data[4..len]));
+        data = &data[align4(len).min(data.len())..];
+    }
+    attrs
+}
+
+fn align4(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_layout() {
+        let msg = message(22, 0x301, 7, &[1, 2, 3, 4]);
+        assert_eq!(msg.len(), 20);
+        assert_eq!(u32::from_ne_bytes(msg[0..4].try_into().unwrap()), 20);
+        assert_eq!(u16::from_ne_bytes(msg[4..6].try_into().unwrap()), 22);
+        assert_eq!(u16::from_ne_bytes(msg[6..8].try_into().unwrap()), 0x301);
+        assert_eq!(u32::from_ne_bytes(msg[8..12].try_into().unwrap()), 7);
+        assert_eq!(&msg[16..], &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_parse_attrs() {
+        // IFA_ADDRESS (1) with four bytes, then a nested attribute 3 padded
+        // from five bytes to eight
+        let attrs = [
+            8, 0, 1, 0, 192, 168, 1, 5, //
+            9, 0, 3, 0x80, b'e', b't', b'h', b'0', 0, 0, 0, 0,
+        ];
+        let parsed = parse_attrs(&attrs);
+        assert_eq!(
+            parsed,
+            vec![(1, &[192, 168, 1, 5][..]), (3, &b"eth0\0"[..])]
+        );
+
+        // A truncated attribute ends the run
+        assert_eq!(parse_attrs(&attrs[..attrs.len() - 6]).len(), 1);
+    }
+
+    #[test]
+    fn test_put_attr() {
+        let mut attrs = Vec::new();
+        put_attr(&mut attrs, 1, &[192, 168, 1, 5]);
+        put_attr(&mut attrs, 3 | NLA_F_NESTED, b"eth0\0");
+        // Padded to four bytes
+        assert_eq!(attrs.len(), 8 + 12);
+        assert_eq!(
+            parse_attrs(&attrs),
+            vec![(1, &[192, 168, 1, 5][..]), (3, &b"eth0\0"[..])]
+        );
+    }
+}
@@ -24,17 +24,19 @@
 //! ```
 
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::io::{BufRead, BufReader};
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::path::Path;
 
-use crate::firewall::FirewallClient;
+use crate::firewall;
+use crate::models::RichRule;
 use crate::validation::parse_port_spec;
 
 /// Protocol type for a listening endpoint.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Protocol {
     Tcp,
     Udp,
@@ -95,6 +97,8 @@ pub struct ListeningEndpoint {
     pub process_name: Option<String>,
     /// Process command line (if found)
     pub cmdline: Option<String>,
+    /// User that opened the socket, readable even when the process is not
+    pub uid: Option<u32>,
     /// Firewall status
     pub firewall_status: FirewallStatus,
 }
@@ -131,16 +135,29 @@ impl ListeningEndpoint {
             format!("Port {}", self.port)
         }
     }
+
+    /// An endpoint on `address` with no process or firewall status known,
+    /// for tests to fill in what they need.
+    #[cfg(test)]
+    pub fn for_test(port: u16, protocol: Protocol, address: &str) -> Self {
+        Self {
+            local_addr: address.parse().unwrap(),
+            port,
+            protocol,
+            inode: 0,
+            pid: None,
+            process_name: None,
+            cmdline: None,
+            uid: None,
+            firewall_status: FirewallStatus::Unknown,
+        }
+    }
 }
 
 /// An established network connection to or from a remote host.
 #[derive(Debug, Clone)]
 pub struct ActiveConnection {
-    // Parsed from the socket table; not shown in the grouped UI but kept as
-    // part of the connection model for detail views and tests.
-    #[allow(dead_code)]
     pub local_addr: IpAddr,
-    #[allow(dead_code)]
     pub local_port: u16,
     pub remote_addr: IpAddr,
     pub remote_port: u16,
@@ -193,6 +210,26 @@ impl NetworkExposure {
 
     /// Scan the system for listening endpoints.
     pub fn scan(&mut self) -> Result<Vec<ListeningEndpoint>> {
+        let sockets = self.scan_sockets()?;
+        Ok(self.merge_ports(sockets))
+    }
+
+    /// Merge sockets from [`Self::scan_sockets`] into one endpoint per
+    /// port and protocol, with its firewall status.
+    pub fn merge_ports(&self, mut endpoints: Vec<ListeningEndpoint>) -> Vec<ListeningEndpoint> {
+        // Remove duplicates (same port/protocol)
+        endpoints.dedup_by(|a, b| a.port == b.port && a.protocol == b.protocol);
+
+        // Check firewall status for each endpoint
+        self.update_firewall_status(&mut endpoints);
+
+        endpoints
+    }
+
+    /// Scan every listening socket, sorted by port. Unlike [`Self::scan`],
+    /// sockets on the same port but different addresses are kept apart and
+    /// the firewall status is left unchecked.
+    pub fn scan_sockets(&mut self) -> Result<Vec<ListeningEndpoint>> {
         // Build inode -> PID mapping first
         self.build_inode_map()?;
 
@@ -233,28 +270,19 @@ impl NetworkExposure {
         // Sort by port
         endpoints.sort_by_key(|e| (e.port, e.protocol as u8));
 
-        // Remove duplicates (same port/protocol)
-        endpoints.dedup_by(|a, b| a.port == b.port && a.protocol == b.protocol);
-
-        // Check firewall status for each endpoint
-        self.update_firewall_status(&mut endpoints);
-
         Ok(endpoints)
     }
 
     /// Update firewall status for all endpoints by checking firewalld rules.
     fn update_firewall_status(&self, endpoints: &mut [ListeningEndpoint]) {
-        // Try to connect to firewalld and get blocked ports
-        let mut client = FirewallClient::new();
-        if client.connect().is_err() {
-            // Firewalld not available, leave status as Unknown
+        // Get zones and their rich rules; with firewalld unavailable the
+        // status stays Unknown
+        let zones = firewall::read_blocking(|client| {
+            client.connect()?;
+            client.get_zones()
+        });
+        let Ok(Ok(zones)) = zones else {
             return;
-        }
-
-        // Get zones and their rich rules
-        let zones = match client.get_zones() {
-            Ok(zones) => zones,
-            Err(_) => return,
         };
 
         // Collect blocked and allowed port ranges (single ports are
@@ -358,6 +386,16 @@ impl NetworkExposure {
     /// sorted by remote address then port. Uses the same inode→PID map as the
     /// listening-socket scan — no privileges or new dependencies required.
     pub fn scan_connections(&mut self) -> Result<Vec<ActiveConnection>> {
+        self.scan_established(true)
+    }
+
+    /// Like [`scan_connections`](Self::scan_connections), but loopback peers
+    /// are kept, e.g. programs talking to a local proxy.
+    pub fn scan_all_connections(&mut self) -> Result<Vec<ActiveConnection>> {
+        self.scan_established(false)
+    }
+
+    fn scan_established(&mut self, remote_only: bool) -> Result<Vec<ActiveConnection>> {
         self.build_inode_map()?;
 
         let mut connections = Vec::new();
@@ -372,7 +410,7 @@ impl NetworkExposure {
                         Err(_) => continue,
                     };
                     if let Some(conn) = self.parse_connection_line(&line, ipv6) {
-                        if conn.is_remote() {
+                        if !remote_only || conn.is_remote() {
                             connections.push(conn);
                         }
                     }
@@ -510,6 +548,7 @@ impl NetworkExposure {
             return None;
         }
 
+        let uid = parts[7].parse::<u32>().ok();
         let inode = parts[9].parse::<u64>().ok()?;
 
         Some(ListeningEndpoint {
@@ -520,6 +559,7 @@ impl NetworkExposure {
             pid: None,
             process_name: None,
             cmdline: None,
+            uid,
             firewall_status: FirewallStatus::Unknown,
         })
     }
@@ -550,7 +590,7 @@ fn parse_addr_port(field: &str, ipv6: bool) -> Option<(IpAddr, u16)> {
 }
 
 /// Parse an IPv4 address from hex format (little-endian).
-fn parse_ipv4_hex(hex: &str) -> Option<Ipv4Addr> {
+pub(super) fn parse_ipv4_hex(hex: &str) -> Option<Ipv4Addr> {
     if hex.len() != 8 {
         return None;
     }
@@ -609,32 +649,15 @@ pub fn get_service_name(port: u16) -> Option<&'static str> {
 /// Example: `rule family="ipv4" port port="80" protocol="tcp" reject`
 /// Ranges like port="10-20" are also supported.
 /// Returns Some(((start, end), protocol)) if found; end == start for a single port.
-fn parse_rich_rule_port(rule: &str) -> Option<((u16, u16), String)> {
-    // Check if this is a port rule
-    if !rule.contains("port port=") {
-        return None;
-    }
-
-    // Extract port spec: port="80" or port="10-20"
-    let port_start = rule.find("port port=\"")?;
-    let port_value_start = port_start + 11; // length of 'port port="'
-    let remaining = &rule[port_value_start..];
-    let port_end = remaining.find('"')?;
-    let range = parse_port_spec(&remaining[..port_end])?;
-
-    // Extract protocol: protocol="tcp" or protocol="udp"
-    let proto_start = rule.find("protocol=\"")?;
-    let proto_value_start = proto_start + 10; // length of 'protocol="'
-    let remaining = &rule[proto_value_start..];
-    let proto_end = remaining.find('"')?;
-    let protocol = remaining[..proto_end].to_lowercase();
-
-    Some((range, protocol))
+pub(super) fn parse_rich_rule_port(rule: &str) -> Option<((u16, u16), String)> {
+    let rule = RichRule::parse(rule).ok()?;
+    let (range, protocol) = rule.port()?;
+    Some((range, protocol.to_lowercase()))
 }
 
 /// Parse a port string like "80/tcp" or "10-20/tcp" into ((start, end), protocol).
 /// end == start for a single port.
-fn parse_port_string(port_str: &str) -> Option<((u16, u16), String)> {
+pub(super) fn parse_port_string(port_str: &str) -> Option<((u16, u16), String)> {
     let (port_part, proto_part) = port_str.split_once('/')?;
     if proto_part.contains('/') {
         return None;
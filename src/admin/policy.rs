@@ -0,0 +1,749 @@
+// Security Center - Desired State Policy
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Security policy as code.
+//!
+//! A TOML file in the configuration directory declares how the system
+//! should look:
+//!
+//! ```toml
+//! default_zone = "public"
+//!
+//! [zones.public]
+//! services = ["ssh", "dhcpv6-client"]
+//! ports = ["8080/tcp", "60000-61000/udp"]
+//! # Report services and ports that are open but not listed
+//! exclusive = true
+//!
+//! [units]
+//! running = ["firewalld.service", "auditd.service"]
+//!
+//! [sysctl]
+//! "net.ipv4.ip_forward" = 0
+//! "kernel.kptr_restrict" = 2
+//! ```
+//!
+//! [`observe`] reads the matching parts of the live system, [`diff`] lists
+//! every difference as a [`Drift`] and [`enforce`] corrects them: firewall
+//! changes go through firewalld (runtime and permanent), units through
+//! systemd and sysctl values through the privileged helper.
+//! [`enforce_unattended`] corrects only what polkit allows without asking
+//! for a password, for the background monitor, and leaves the rest pending.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+
+use crate::firewall::{self, FirewallClient};
+use crate::helper::{check_authorization, run_privileged, HelperOp, HELPER_ACTION};
+use crate::systemd::{ServiceState, SystemdClient};
+use crate::validation::{
+    format_port_spec, parse_port_spec, validate_firewall_service, validate_protocol,
+    validate_service_name, validate_sysctl_key, validate_sysctl_value, validate_zone_name,
+};
+
+/// Largest policy file accepted.
+const MAX_POLICY_FILE_SIZE: u64 = 262_144;
+
+/// Polkit actions of the calls that correct drifts.
+const FIREWALLD_RUNTIME: &str = "org.fedoraproject.FirewallD1.all";
+const FIREWALLD_CONFIG: &str = "org.fedoraproject.FirewallD1.config";
+const SYSTEMD_MANAGE_UNITS: &str = "org.freedesktop.systemd1.manage-units";
+const SYSTEMD_MANAGE_UNIT_FILES: &str = "org.freedesktop.systemd1.manage-unit-files";
+const EDIT_SYSCTL_ACTION: &str = "com.chrisdaggas.security-center.edit-sysctl";
+
+/// Location of the policy file: `~/.config/security-center/policy.toml`.
+pub fn policy_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("security-center")
+        .join("policy.toml")
+}
+
+/// Allowed services and ports of a zone.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ZonePolicy {
+    pub services: Vec<String>,
+    /// Normalized `port/protocol` entries, as firewalld lists them.
+    pub ports: Vec<String>,
+    /// Whether services and ports not listed count as drift.
+    pub exclusive: bool,
+}
+
+/// The validated contents of a policy file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DesiredState {
+    pub default_zone: Option<String>,
+    pub zones: BTreeMap<String, ZonePolicy>,
+    /// Units that must be running (and are enabled when enforced).
+    pub running_units: Vec<String>,
+    /// sysctl keys in dotted form and their values.
+    pub sysctl: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawPolicy {
+    default_zone: Option<String>,
+    #[serde(default)]
+    zones: BTreeMap<String, RawZone>,
+    #[serde(default)]
+    units: RawUnits,
+    #[serde(default)]
+    sysctl: BTreeMap<String, RawSysctlValue>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawZone {
+    #[serde(default)]
+    services: Vec<String>,
+    #[serde(default)]
+    ports: Vec<String>,
+    #[serde(default)]
+    exclusive: bool,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawUnits {
+    #[serde(default)]
+    running: Vec<String>,
+}
+
+/// sysctl values may be written as numbers or strings.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawSysctlValue {
+    Number(i64),
+    Text(String),
+}
+
+impl DesiredState {
+    /// Read and validate the policy file; `Ok(None)` when there is none.
+    pub fn load() -> Result<Option<Self>> {
+        let path = policy_path();
+        let metadata = match fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        if metadata.len() > MAX_POLICY_FILE_SIZE {
+            bail!("Policy file is too large");
+        }
+        let text = fs::read_to_string(&path)?;
+        Self::parse(&text).map(Some)
+    }
+
+    /// Parse and validate policy TOML.
+    pub fn parse(text: &str) -> Result<Self> {
+        let raw: RawPolicy = toml::from_str(text).context("Invalid policy file")?;
+        let mut state = DesiredState::default();
+
+        if let Some(zone) = raw.default_zone {
+            validate_zone_name(&zone).ok_or_else(|| anyhow!("Invalid zone name: {}", zone))?;
+            state.default_zone = Some(zone);
+        }
+
+        for (name, zone) in raw.zones {
+            validate_zone_name(&name).ok_or_else(|| anyhow!("Invalid zone name: {}", name))?;
+            let mut policy = ZonePolicy {
+                exclusive: zone.exclusive,
+                ..Default::default()
+            };
+            for service in zone.services {
+                validate_firewall_service(&service)
+                    .ok_or_else(|| anyhow!("Invalid service name: {}", service))?;
+                if !policy.services.contains(&service) {
+                    policy.services.push(service);
+                }
+            }
+            for port in zone.ports {
+                let normalized =
+                    normalize_port(&port).ok_or_else(|| anyhow!("Invalid port: {}", port))?;
+                if !policy.ports.contains(&normalized) {
+                    policy.ports.push(normalized);
+                }
+            }
+            state.zones.insert(name, policy);
+        }
+
+        for unit in raw.units.running {
+            if unit.is_empty() {
+                bail!("Empty unit name");
+            }
+            validate_service_name(&unit)?;
+            if !state.running_units.contains(&unit) {
+                state.running_units.push(unit);
+            }
+        }
+
+        for (key, value) in raw.sysctl {
+            validate_sysctl_key(&key).ok_or_else(|| anyhow!("Invalid sysctl key: {}", key))?;
+            let value = match value {
+                RawSysctlValue::Number(n) => n.to_string(),
+                RawSysctlValue::Text(text) => text,
+            };
+            validate_sysctl_value(&value)
+                .ok_or_else(|| anyhow!("Invalid value for {}: {}", key, value))?;
+            state.sysctl.insert(key, normalize_sysctl(&value));
+        }
+
+        Ok(state)
+    }
+
+    /// Number of individual requirements in the policy.
+    pub fn rule_count(&self) -> usize {
+        usize::from(self.default_zone.is_some())
+            + self
+                .zones
+                .values()
+                .map(|z| z.services.len() + z.ports.len())
+                .sum::<usize>()
+            + self.running_units.len()
+            + self.sysctl.len()
+    }
+}
+
+/// `8080/tcp` or `60000-61000/udp`, normalized; `None` when invalid.
+fn normalize_port(port: &str) -> Option<String> {
+    let (spec, protocol) = port.split_once('/')?;
+    let (start, end) = parse_port_spec(spec)?;
+    let protocol = validate_protocol(protocol)?;
+    Some(format!("{}/{}", format_port_spec(start, end), protocol))
+}
+
+/// /proc/sys separates multi-part values with tabs.
+fn normalize_sysctl(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The parts of the live system a policy covers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ObservedState {
+    pub default_zone: String,
+    /// Zone name to (services, `port/protocol` entries).
+    pub zones: HashMap<String, (Vec<String>, Vec<String>)>,
+    /// Units of the policy that are running.
+    pub running_units: Vec<String>,
+    /// Current value of each sysctl of the policy; `None` when unreadable.
+    pub sysctl: HashMap<String, Option<String>>,
+}
+
+/// Read the live state of everything `state` declares.
+pub fn observe(state: &DesiredState) -> Result<ObservedState> {
+    let mut observed = ObservedState::default();
+
+    let (default_zone, zones) = firewall::read_blocking(|client| -> Result<_> {
+        client.connect()?;
+        Ok((client.get_default_zone()?, client.get_zones()?))
+    })??;
+    observed.default_zone = default_zone;
+    for zone in zones {
+        observed
+            .zones
+            .insert(zone.name.clone(), (zone.services, zone.ports));
+    }
+
+    if !state.running_units.is_empty() {
+        let mut systemd = SystemdClient::new();
+        systemd.connect()?;
+        for unit in &state.running_units {
+            let running = systemd
+                .get_service_info(unit)
+                .is_ok_and(|info| info.state == ServiceState::Running);
+            if running {
+                observed.running_units.push(unit.clone());
+            }
+        }
+    }
+
+    for key in state.sysctl.keys() {
+        let value = fs::read_to_string(sysctl_path(key))
+            .ok()
+            .map(|v| normalize_sysctl(&v));
+        observed.sysctl.insert(key.clone(), value);
+    }
+
+    Ok(observed)
+}
+
+/// `/proc/sys` path of a validated dotted sysctl key.
+pub fn sysctl_path(key: &str) -> PathBuf {
+    PathBuf::from("/proc/sys").join(key.replace('.', "/"))
+}
+
+/// A difference between the policy and the system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Drift {
+    DefaultZone {
+        expected: String,
+        actual: String,
+    },
+    /// A zone of the policy that firewalld does not know.
+    MissingZone {
+        zone: String,
+    },
+    MissingService {
+        zone: String,
+        service: String,
+    },
+    /// Only reported for exclusive zones.
+    ExtraService {
+        zone: String,
+        service: String,
+    },
+    MissingPort {
+        zone: String,
+        port: String,
+    },
+    /// Only reported for exclusive zones.
+    ExtraPort {
+        zone: String,
+        port: String,
+    },
+    UnitNotRunning {
+        unit: String,
+    },
+    Sysctl {
+        key: String,
+        expected: String,
+        actual: Option<String>,
+    },
+}
+
+impl Drift {
+    /// Whether [`enforce`] can correct this drift.
+    pub fn is_fixable(&self) -> bool {
+        !matches!(self, Drift::MissingZone { .. })
+    }
+
+    /// The polkit actions [`enforce`] needs to correct this drift.
+    pub fn polkit_actions(&self) -> &'static [&'static str] {
+        match self {
+            Drift::MissingZone { .. } => &[],
+            Drift::UnitNotRunning { .. } => &[SYSTEMD_MANAGE_UNITS, SYSTEMD_MANAGE_UNIT_FILES],
+            Drift::Sysctl { .. } => &[HELPER_ACTION, EDIT_SYSCTL_ACTION],
+            _ => &[FIREWALLD_RUNTIME, FIREWALLD_CONFIG],
+        }
+    }
+}
+
+/// Every difference between `state` and `observed`, in policy order.
+pub fn diff(state: &DesiredState, observed: &ObservedState) -> Vec<Drift> {
+    let mut drifts = Vec::new();
+
+    if let Some(expected) = &state.default_zone {
+        if *expected != observed.default_zone {
+            drifts.push(Drift::DefaultZone {
+                expected: expected.clone(),
+                actual: observed.default_zone.clone(),
+            });
+        }
+    }
+
+    for (zone, policy) in &state.zones {
+        let Some((services, ports)) = observed.zones.get(zone) else {
+            drifts.push(Drift::MissingZone { zone: zone.clone() });
+            continue;
+        };
+        for service in &policy.services {
+            if !services.contains(service) {
+                drifts.push(Drift::MissingService {
+                    zone: zone.clone(),
+                    service: service.clone(),
+                });
+            }
+        }
+        for port in &policy.ports {
+            if !ports.contains(port) {
+                drifts.push(Drift::MissingPort {
+                    zone: zone.clone(),
+                    port: port.clone(),
+                });
+            }
+        }
+        if policy.exclusive {
+            for service in services {
+                if !policy.services.contains(service) {
+                    drifts.push(Drift::ExtraService {
+                        zone: zone.clone(),
+                        service: service.clone(),
+                    });
+                }
+            }
+            for port in ports {
+                if !policy.ports.contains(port) {
+                    drifts.push(Drift::ExtraPort {
+                        zone: zone.clone(),
+                        port: port.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    for unit in &state.running_units {
+        if !observed.running_units.contains(unit) {
+            drifts.push(Drift::UnitNotRunning { unit: unit.clone() });
+        }
+    }
+
+    for (key, expected) in &state.sysctl {
+        let actual = observed.sysctl.get(key).cloned().flatten();
+        if actual.as_deref() != Some(expected.as_str()) {
+            drifts.push(Drift::Sysctl {
+                key: key.clone(),
+                expected: expected.clone(),
+                actual,
+            });
+        }
+    }
+
+    drifts
+}
+
+/// Load the policy and diff it against the live system: `None` when there
+/// is no policy file.
+pub fn check_policy() -> Result<Option<(DesiredState, Vec<Drift>)>> {
+    let Some(state) = DesiredState::load()? else {
+        return Ok(None);
+    };
+    let observed = observe(&state)?;
+    let drifts = diff(&state, &observed);
+    Ok(Some((state, drifts)))
+}
+
+/// Outcome of an enforcement run.
+#[derive(Debug, Clone, Default)]
+pub struct EnforceReport {
+    pub fixed: usize,
+    /// Drifts that could not be corrected, with the reason.
+    pub failed: Vec<(Drift, String)>,
+    /// Drifts left for an interactive run, since correcting them needs a
+    /// password.
+    pub pending: Vec<Drift>,
+}
+
+/// [`enforce`] without any authentication prompt: drifts whose polkit
+/// actions are not granted outright are left pending.
+pub fn enforce_unattended(state: &DesiredState, drifts: &[Drift]) -> EnforceReport {
+    let mut granted: HashMap<&str, bool> = HashMap::new();
+    let (allowed, pending): (Vec<Drift>, Vec<Drift>) = drifts.iter().cloned().partition(|drift| {
+        drift.polkit_actions().iter().all(|action| {
+            *granted
+                .entry(*action)
+                .or_insert_with(|| check_authorization(action, false).is_ok())
+        })
+    });
+    let mut report = enforce(state, &allowed);
+    report.pending = pending;
+    report
+}
+
+/// Correct `drifts`. Firewall changes are made at runtime and permanently;
+/// sysctl values are applied at once and persisted in a sysctl.d drop-in.
+pub fn enforce(state: &DesiredState, drifts: &[Drift]) -> EnforceReport {
+    let mut report = EnforceReport::default();
+
+    let mut systemd = SystemdClient::new();
+    let systemd_error = systemd.connect().err().map(|e| e.to_string());
+
+    let mut sysctl_drifts = Vec::new();
+    for drift in drifts {
+        let result = match drift {
+            Drift::MissingZone { .. } => Err(anyhow!("The zone does not exist")),
+            Drift::UnitNotRunning { unit } => match &systemd_error {
+                Some(e) => Err(anyhow!("{}", e)),
+                None => systemd
+                    .start_service(unit)
+                    .and_then(|()| systemd.enable_service(unit)),
+            },
+            Drift::Sysctl { .. } => {
+                sysctl_drifts.push(drift.clone());
+                continue;
+            }
+            _ => {
+                let drift = drift.clone();
+                firewall::call_blocking(move |client| {
+                    client.connect()?;
+                    enforce_firewall(client, &drift)
+                })
+                .unwrap_or_else(|e| Err(e.into()))
+            }
+        };
+        match result {
+            Ok(()) => report.fixed += 1,
+            Err(e) => report.failed.push((drift.clone(), e.to_string())),
+        }
+    }
+
+    if !sysctl_drifts.is_empty() {
+        // The drop-in holds every sysctl of the policy, not only the drifted ones
+        let settings: Vec<(String, String)> = state
+            .sysctl
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        match run_privileged(&HelperOp::ApplySysctl { settings }) {
+            Ok(_) => report.fixed += sysctl_drifts.len(),
+            Err(e) => report
+                .failed
+                .extend(sysctl_drifts.into_iter().map(|d| (d, e.to_string()))),
+        }
+    }
+
+    report
+}
+
+fn enforce_firewall(client: &FirewallClient, drift: &Drift) -> Result<()> {
+    let outcome = match drift {
+        Drift::DefaultZone { expected, .. } => return client.set_default_zone(expected),
+        Drift::MissingService { zone, service } => client.enable_service(zone, service, true)?,
+        Drift::ExtraService { zone, service } => client.disable_service(zone, service, true)?,
+        Drift::MissingPort { zone, port } | Drift::ExtraPort { zone, port } => {
+            let (spec, protocol) = port
+                .split_once('/')
+                .ok_or_else(|| anyhow!("Invalid port: {}", port))?;
+            if matches!(drift, Drift::MissingPort { .. }) {
+                client.add_port(zone, spec, protocol, true)?
+            } else {
+                client.remove_port(zone, spec, protocol, true)?
+            }
+        }
+        _ => bail!("Not a firewall drift"),
+    };
+    if outcome.failed() {
+        bail!("Applied for this session only; the permanent change failed");
+    }
+    Ok(())
+}
+
+/// Whether a scheduled enforcement is due, given the last run (unix
+/// seconds), the interval in hours (0 disables scheduling) and the current
+/// time.
+pub fn enforcement_due(last_run: Option<i64>, interval_hours: u32, now: i64) -> bool {
+    if interval_hours == 0 {
+        return false;
+    }
+    match last_run {
+        Some(last) => now - last >= i64::from(interval_hours) * 3_600,
+        None => true,
+    }
+}
+
+/// Policy TOML describing the current default zone and the services and
+/// ports of the active zones, as a starting point for a new policy.
+pub fn policy_from_system() -> Result<String> {
+    let (default_zone, zones) = firewall::read_blocking(|client| -> Result<_> {
+        client.connect()?;
+        Ok((client.get_default_zone()?, client.get_zones()?))
+    })??;
+
+    let mut text = String::from(
+        "# Security Center desired state\n\
+         # Set exclusive = true to report services and ports that are not listed.\n\n",
+    );
+    text.push_str(&format!("default_zone = {}\n", quote(&default_zone)));
+    for zone in zones {
+        if !zone.is_active && !zone.is_default {
+            continue;
+        }
+        text.push_str(&format!("\n[zones.{}]\n", zone.name));
+        text.push_str(&format!("services = {}\n", quote_list(&zone.services)));
+        text.push_str(&format!("ports = {}\n", quote_list(&zone.ports)));
+        text.push_str("exclusive = false\n");
+    }
+    text.push_str(
+        "\n[units]\nrunning = [\"firewalld.service\"]\n\n\
+         [sysctl]\n# \"net.ipv4.ip_forward\" = 0\n",
+    );
+    Ok(text)
+}
+
+/// Write `text` as the policy file, refusing to replace an existing one.
+pub fn create_policy_file(text: &str) -> Result<PathBuf> {
+    let path = policy_path();
+    if path.exists() {
+        bail!("A policy file already exists");
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, text)?;
+    Ok(path)
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn quote_list(values: &[String]) -> String {
+    let quoted: Vec<String> = values.iter().map(|v| quote(v)).collect();
+    format!("[{}]", quoted.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = r#"
+default_zone = "public"
+
+[zones.public]
+services = ["ssh", "mdns", "ssh"]
+ports = ["8080/tcp", "60000-61000/udp"]
+exclusive = true
+
+[zones.home]
+services = ["samba"]
+
+[units]
+running = ["auditd.service"]
+
+[sysctl]
+"net.ipv4.ip_forward" = 0
+"net.ipv4.tcp_rmem" = "4096  131072 6291456"
+"#;
+
+    fn observed() -> ObservedState {
+        let mut observed = ObservedState {
+            default_zone: "home".to_string(),
+            ..Default::default()
+        };
+        observed.zones.insert(
+            "public".to_string(),
+            (
+                vec!["ssh".to_string(), "cockpit".to_string()],
+                vec!["8080/tcp".to_string()],
+            ),
+        );
+        observed
+            .sysctl
+            .insert("net.ipv4.ip_forward".to_string(), Some("1".to_string()));
+        observed.sysctl.insert(
+            "net.ipv4.tcp_rmem".to_string(),
+            Some("4096 131072 6291456".to_string()),
+        );
+        observed
+    }
+
+    #[test]
+    fn test_parse_policy() {
+        let state = DesiredState::parse(POLICY).unwrap();
+        assert_eq!(state.default_zone.as_deref(), Some("public"));
+        let public = &state.zones["public"];
+        assert_eq!(public.services, vec!["ssh", "mdns"]);
+        assert_eq!(public.ports, vec!["8080/tcp", "60000-61000/udp"]);
+        assert!(public.exclusive);
+        assert!(!state.zones["home"].exclusive);
+        assert_eq!(state.sysctl["net.ipv4.ip_forward"], "0");
+        assert_eq!(state.sysctl["net.ipv4.tcp_rmem"], "4096 131072 6291456");
+        assert_eq!(state.rule_count(), 9);
+    }
+
+    #[test]
+    fn test_parse_policy_rejects_invalid() {
+        assert!(DesiredState::parse("default_zone = \"pub lic\"").is_err());
+        assert!(DesiredState::parse("[zones.public]\nports = [\"80\"]").is_err());
+        assert!(DesiredState::parse("[zones.public]\nports = [\"80/icmp\"]").is_err());
+        assert!(DesiredState::parse("[zones.public]\nservices = [\"ssh;\"]").is_err());
+        assert!(DesiredState::parse("[units]\nrunning = [\"sshd\"]").is_err());
+        assert!(DesiredState::parse("[sysctl]\n\"../../x\" = 1").is_err());
+        assert!(DesiredState::parse("[sysctl]\n\"kernel.x\" = \"1\\nkernel.y=2\"").is_err());
+        assert!(DesiredState::parse("unknown = 1").is_err());
+        assert!(DesiredState::parse("").unwrap().rule_count() == 0);
+    }
+
+    #[test]
+    fn test_diff() {
+        let state = DesiredState::parse(POLICY).unwrap();
+        let drifts = diff(&state, &observed());
+        assert_eq!(
+            drifts,
+            vec![
+                Drift::DefaultZone {
+                    expected: "public".to_string(),
+                    actual: "home".to_string()
+                },
+                Drift::MissingZone {
+                    zone: "home".to_string()
+                },
+                Drift::MissingService {
+                    zone: "public".to_string(),
+                    service: "mdns".to_string()
+                },
+                Drift::MissingPort {
+                    zone: "public".to_string(),
+                    port: "60000-61000/udp".to_string()
+                },
+                Drift::ExtraService {
+                    zone: "public".to_string(),
+                    service: "cockpit".to_string()
+                },
+                Drift::UnitNotRunning {
+                    unit: "auditd.service".to_string()
+                },
+                Drift::Sysctl {
+                    key: "net.ipv4.ip_forward".to_string(),
+                    expected: "0".to_string(),
+                    actual: Some("1".to_string())
+                },
+            ]
+        );
+        assert!(!drifts[1].is_fixable());
+        assert!(drifts[2].is_fixable());
+    }
+
+    #[test]
+    fn test_drift_polkit_actions() {
+        let sysctl = Drift::Sysctl {
+            key: "net.ipv4.ip_forward".to_string(),
+            expected: "0".to_string(),
+            actual: None,
+        };
+        assert!(sysctl.polkit_actions().contains(&EDIT_SYSCTL_ACTION));
+        assert_eq!(
+            HelperOp::ApplySysctl { settings: vec![] }.polkit_action(),
+            EDIT_SYSCTL_ACTION
+        );
+        // The helper's own actions are declared in the shipped policy
+        for action in sysctl.polkit_actions() {
+            let declared = format!("<action id=\"{}\">", action);
+            assert!(crate::helper::POLKIT_POLICY.contains(&declared));
+        }
+        let missing = Drift::MissingZone {
+            zone: "lab".to_string(),
+        };
+        assert!(missing.polkit_actions().is_empty());
+    }
+
+    #[test]
+    fn test_enforcement_due() {
+        assert!(!enforcement_due(None, 0, 1_000_000));
+        assert!(enforcement_due(None, 1, 1_000_000));
+        assert!(!enforcement_due(Some(1_000_000), 6, 1_000_000 + 3_600));
+        assert!(enforcement_due(Some(1_000_000), 6, 1_000_000 + 6 * 3_600));
+    }
+
+    #[test]
+    fn test_quote_list() {
+        assert_eq!(
+            quote_list(&["ssh".to_string(), "a\"b".to_string()]),
+            "[\"ssh\", \"a\\\"b\"]"
+        );
+        assert_eq!(quote_list(&[]), "[]");
+    }
+
+    #[test]
+    fn test_sysctl_path() {
+        assert_eq!(
+            sysctl_path("net.ipv4.ip_forward"),
+            PathBuf::from("/proc/sys/net/ipv4/ip_forward")
+        );
+    }
+}
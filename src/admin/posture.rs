@@ -0,0 +1,460 @@
+// Security Center - Security Posture
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Security posture score shown on the Overview.
+//!
+//! Where the [`Assessment`](super::Assessment) inspects the host (boot
+//! chain, core dumps, helpers), the posture looks at how the firewall
+//! protects it right now: whether firewalld runs, how strict the default
+//! zone is, whether SSH and other listeners are reachable from the network,
+//! panic mode and runtime rules missing from the permanent configuration.
+//!
+//! Every [`PostureCheck`] has a weight; the weights add up to 100 and the
+//! score is the weight of the checks that pass. A failed check names the
+//! [`Remedy`] the Overview offers for it.
+
+use super::actions::Operation;
+use super::assessment::zone_suggestions;
+use super::network::{FirewallStatus, ListeningEndpoint, NetworkExposure, Protocol};
+use crate::firewall;
+use crate::models::Zone;
+
+/// Built-in zones that let most traffic in; a default zone to avoid.
+const PERMISSIVE_ZONES: &[&str] = &["trusted", "home", "internal"];
+/// Built-in zones for networks that are not trusted.
+const UNTRUSTED_ZONES: &[&str] = &["public", "external", "dmz"];
+/// The zone a permissive default zone is replaced with.
+const HARDENED_DEFAULT_ZONE: &str = "public";
+/// Port sshd listens on.
+const SSH_PORT: u16 = 22;
+
+/// What fixes a failed check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Remedy {
+    /// Run an operation.
+    Run(Operation),
+    /// Review the runtime changes and keep or discard each.
+    ReviewDrift,
+    /// Open the page with this navigation id, e.g. `network-exposure`.
+    ShowPage(&'static str),
+}
+
+/// One check of the posture.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostureCheck {
+    /// Stable identifier, e.g. `firewall.running`.
+    pub id: &'static str,
+    pub title: &'static str,
+    /// Points the check is worth when it passes.
+    pub weight: u32,
+    pub passed: bool,
+    /// What was found.
+    pub detail: String,
+    /// How to fix it, when it failed and the Overview can help.
+    pub remedy: Option<Remedy>,
+}
+
+/// Results of all posture checks.
+#[derive(Debug, Clone, Default)]
+pub struct Posture {
+    pub checks: Vec<PostureCheck>,
+}
+
+impl Posture {
+    /// Score from 0 (no check passes) to 100 (all do).
+    pub fn score(&self) -> u32 {
+        let total: u32 = self.checks.iter().map(|c| c.weight).sum();
+        if total == 0 {
+            return 100;
+        }
+        let passed: u32 = self
+            .checks
+            .iter()
+            .filter(|c| c.passed)
+            .map(|c| c.weight)
+            .sum();
+        passed * 100 / total
+    }
+
+    /// Checks that failed, heaviest first.
+    pub fn failed(&self) -> Vec<&PostureCheck> {
+        let mut failed: Vec<&PostureCheck> = self.checks.iter().filter(|c| !c.passed).collect();
+        failed.sort_by_key(|c| std::cmp::Reverse(c.weight));
+        failed
+    }
+}
+
+/// What the checks look at.
+#[derive(Debug, Clone, Default)]
+pub struct PostureInput {
+    pub firewall_running: bool,
+    pub panic_mode: bool,
+    /// Zones with their permanent configuration.
+    pub zones: Vec<Zone>,
+    /// Listening sockets with their firewall status.
+    pub endpoints: Vec<ListeningEndpoint>,
+}
+
+/// Read the firewall and the listening sockets, then run the checks.
+/// Blocks on D-Bus and procfs, so call it off the main thread.
+pub fn query_posture() -> Posture {
+    evaluate_posture(&query_posture_input())
+}
+
+/// Read what the checks look at. Blocks like [`query_posture`].
+pub fn query_posture_input() -> PostureInput {
+    let mut input = PostureInput::default();
+    let state = firewall::read_blocking(|client| {
+        client.connect().ok()?;
+        let panic_mode = client.query_panic_mode().unwrap_or(false);
+        Some((panic_mode, client.get_zones().unwrap_or_default()))
+    });
+    if let Ok(Some((panic_mode, zones))) = state {
+        input.firewall_running = true;
+        input.panic_mode = panic_mode;
+        input.zones = zones;
+    }
+    match NetworkExposure::new().scan() {
+        Ok(endpoints) => input.endpoints = endpoints,
+        Err(e) => tracing::debug!("Posture: cannot scan listening sockets: {}", e),
+    }
+    input
+}
+
+/// Run the checks on what [`query_posture`] read.
+pub fn evaluate_posture(input: &PostureInput) -> Posture {
+    let mut checks = vec![PostureCheck {
+        id: "firewall.running",
+        title: "Firewall running",
+        weight: 30,
+        passed: input.firewall_running,
+        detail: if input.firewall_running {
+            "firewalld filters incoming traffic".to_string()
+        } else {
+            "firewalld is not running, so all incoming traffic is let through".to_string()
+        },
+        remedy: Some(Remedy::Run(Operation::EnableFirewall)),
+    }];
+
+    if input.firewall_running {
+        checks.push(check_default_zone(&input.zones));
+        checks.push(check_ssh(&input.zones, &input.endpoints));
+    } else {
+        for (id, title, weight) in [
+            ("zone.default", "Default zone hardened", 20),
+            ("ssh.exposed", "SSH not exposed", 15),
+        ] {
+            checks.push(unknown(id, title, weight));
+        }
+    }
+    checks.push(check_listeners(input.firewall_running, &input.endpoints));
+    if input.firewall_running {
+        checks.push(check_panic_mode(input.panic_mode));
+        checks.push(check_drift(&input.zones));
+    } else {
+        for (id, title, weight) in [
+            ("firewall.panic", "Panic mode off", 10),
+            ("firewall.drift", "Runtime rules saved", 10),
+        ] {
+            checks.push(unknown(id, title, weight));
+        }
+    }
+
+    for check in checks.iter_mut().filter(|c| c.passed) {
+        check.remedy = None;
+    }
+    Posture { checks }
+}
+
+/// A check that cannot be evaluated while firewalld is not running.
+fn unknown(id: &'static str, title: &'static str, weight: u32) -> PostureCheck {
+    PostureCheck {
+        id,
+        title,
+        weight,
+        passed: false,
+        detail: "Unknown while firewalld is not running".to_string(),
+        remedy: None,
+    }
+}
+
+/// The default zone should not accept everything, nor allow services
+/// the baseline keeps out of it.
+fn check_default_zone(zones: &[Zone]) -> PostureCheck {
+    let mut check = PostureCheck {
+        id: "zone.default",
+        title: "Default zone hardened",
+        weight: 20,
+        passed: false,
+        detail: String::new(),
+        remedy: None,
+    };
+    let Some(zone) = zones.iter().find(|z| z.is_default) else {
+        check.detail = "The default zone could not be read".to_string();
+        return check;
+    };
+
+    if PERMISSIVE_ZONES.contains(&zone.name.as_str()) || zone.target == "ACCEPT" {
+        check.detail = format!(
+            "The default zone {} lets most incoming traffic in; new interfaces join it",
+            zone.name
+        );
+        check.remedy = Some(Remedy::Run(Operation::SetDefaultZone {
+            zone: HARDENED_DEFAULT_ZONE,
+        }));
+        return check;
+    }
+
+    let rules = zone_suggestions(&zone.name, &zone.services);
+    if let Some(rule) = rules.first() {
+        let services: Vec<&str> = rules.iter().map(|r| r.service).collect();
+        check.detail = format!(
+            "The default zone {} allows {}. {}",
+            zone.name,
+            services.join(", "),
+            rule.reason
+        );
+        // The baseline only names built-in zones, so the name is static
+        check.remedy = rule.zones.iter().find(|z| **z == zone.name).map(|z| {
+            Remedy::Run(Operation::RemoveZoneService {
+                zone: *z,
+                service: rule.service,
+            })
+        });
+        return check;
+    }
+
+    check.passed = true;
+    check.detail = format!("The default zone {} only allows what it should", zone.name);
+    check
+}
+
+/// SSH should not be reachable through a zone for untrusted networks.
+fn check_ssh(zones: &[Zone], endpoints: &[ListeningEndpoint]) -> PostureCheck {
+    let mut check = PostureCheck {
+        id: "ssh.exposed",
+        title: "SSH not exposed",
+        weight: 15,
+        passed: true,
+        detail: String::new(),
+        remedy: None,
+    };
+    let listening = endpoints
+        .iter()
+        .any(|e| e.port == SSH_PORT && e.protocol == Protocol::Tcp && e.is_exposed());
+    if !listening {
+        check.detail = "No SSH server listens on the network".to_string();
+        return check;
+    }
+
+    let open_in = UNTRUSTED_ZONES.iter().find(|name| {
+        zones.iter().any(|z| {
+            z.name == **name
+                && z.is_in_use()
+                && (z.services.iter().any(|s| s == "ssh") || z.ports.iter().any(|p| p == "22/tcp"))
+        })
+    });
+    match open_in {
+        Some(zone) => {
+            check.passed = false;
+            check.detail = format!(
+                "The SSH server accepts connections through the {} zone, open to \
+                 password guessing from any host on the network",
+                zone
+            );
+            check.remedy = Some(Remedy::Run(Operation::RemoveZoneService {
+                zone: *zone,
+                service: "ssh",
+            }));
+        }
+        None => {
+            check.detail = "The SSH server is only reachable from trusted zones".to_string();
+        }
+    }
+    check
+}
+
+/// Services listening on all interfaces should not be let through the
+/// firewall. SSH has a check of its own.
+fn check_listeners(firewall_running: bool, endpoints: &[ListeningEndpoint]) -> PostureCheck {
+    let exposed: Vec<&ListeningEndpoint> = endpoints
+        .iter()
+        .filter(|e| e.is_exposed())
+        .filter(|e| !(e.port == SSH_PORT && e.protocol == Protocol::Tcp))
+        .filter(|e| {
+            !firewall_running || matches!(e.firewall_status, FirewallStatus::Allowed { .. })
+        })
+        .collect();
+    let names: Vec<String> = exposed.iter().map(|e| e.display_name()).collect();
+    PostureCheck {
+        id: "listeners.exposed",
+        title: "No services exposed on all interfaces",
+        weight: 15,
+        passed: exposed.is_empty(),
+        detail: if exposed.is_empty() {
+            "No service listening on all interfaces is let through the firewall".to_string()
+        } else {
+            format!(
+                "{} listening on all interfaces and reachable from the network: {}",
+                exposed.len(),
+                names.join(", ")
+            )
+        },
+        remedy: Some(Remedy::ShowPage("network-exposure")),
+    }
+}
+
+/// Panic mode drops all traffic; it is for emergencies, not to stay on.
+fn check_panic_mode(panic_mode: bool) -> PostureCheck {
+    PostureCheck {
+        id: "firewall.panic",
+        title: "Panic mode off",
+        weight: 10,
+        passed: !panic_mode,
+        detail: if panic_mode {
+            "Panic mode drops all network traffic until it is turned off".to_string()
+        } else {
+            "Network traffic flows through the firewall rules".to_string()
+        },
+        remedy: Some(Remedy::Run(Operation::SetPanicMode { enabled: false })),
+    }
+}
+
+/// Runtime rules should match the permanent configuration, or the next
+/// reload or reboot changes what is protected.
+fn check_drift(zones: &[Zone]) -> PostureCheck {
+    let changed: Vec<&str> = zones
+        .iter()
+        .filter(|z| z.unsaved_changes() > 0)
+        .map(|z| z.name.as_str())
+        .collect();
+    let total: usize = zones.iter().map(|z| z.unsaved_changes()).sum();
+    PostureCheck {
+        id: "firewall.drift",
+        title: "Runtime rules saved",
+        weight: 10,
+        passed: changed.is_empty(),
+        detail: if changed.is_empty() {
+            "The runtime rules match the permanent configuration".to_string()
+        } else {
+            format!(
+                "{} rules in {} differ from the permanent configuration and change at \
+                 the next reload or reboot",
+                total,
+                changed.join(", ")
+            )
+        },
+        remedy: Some(Remedy::ReviewDrift),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ZoneRules;
+
+    fn zone(name: &str, services: &[&str]) -> Zone {
+        let services: Vec<String> = services.iter().map(|s| s.to_string()).collect();
+        Zone {
+            name: name.to_string(),
+            target: "default".to_string(),
+            is_default: true,
+            permanent: Some(ZoneRules {
+                services: services.clone(),
+                ..Default::default()
+            }),
+            services,
+            ..Default::default()
+        }
+    }
+
+    fn endpoint(port: u16, zone: Option<&str>) -> ListeningEndpoint {
+        ListeningEndpoint {
+            firewall_status: match zone {
+                Some(zone) => FirewallStatus::Allowed {
+                    zone: zone.to_string(),
+                },
+                None => FirewallStatus::Blocked,
+            },
+            ..ListeningEndpoint::for_test(port, Protocol::Tcp, "0.0.0.0")
+        }
+    }
+
+    fn check<'a>(posture: &'a Posture, id: &str) -> &'a PostureCheck {
+        posture.checks.iter().find(|c| c.id == id).unwrap()
+    }
+
+    #[test]
+    fn test_weights_add_up() {
+        let total: u32 = evaluate_posture(&PostureInput::default())
+            .checks
+            .iter()
+            .map(|c| c.weight)
+            .sum();
+        assert_eq!(total, 100);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let hardened = PostureInput {
+            firewall_running: true,
+            panic_mode: false,
+            zones: vec![zone("public", &["dhcpv6-client"])],
+            endpoints: vec![endpoint(631, None)],
+        };
+        let posture = evaluate_posture(&hardened);
+        assert_eq!(posture.score(), 100);
+        assert!(posture.checks.iter().all(|c| c.remedy.is_none()));
+
+        let mut exposed = hardened.clone();
+        exposed.zones = vec![zone("public", &["ssh", "samba"])];
+        exposed.endpoints = vec![endpoint(22, Some("public")), endpoint(445, Some("public"))];
+        let posture = evaluate_posture(&exposed);
+        assert_eq!(posture.score(), 50);
+        assert_eq!(
+            check(&posture, "zone.default").remedy,
+            Some(Remedy::Run(Operation::RemoveZoneService {
+                zone: "public",
+                service: "samba"
+            }))
+        );
+        assert_eq!(
+            check(&posture, "ssh.exposed").remedy,
+            Some(Remedy::Run(Operation::RemoveZoneService {
+                zone: "public",
+                service: "ssh"
+            }))
+        );
+        assert!(!check(&posture, "listeners.exposed").passed);
+        assert_eq!(posture.failed()[0].id, "zone.default");
+
+        let mut trusted = hardened.clone();
+        trusted.zones = vec![zone("trusted", &[])];
+        trusted.zones[0].services.push("ssh".to_string());
+        trusted.panic_mode = true;
+        let posture = evaluate_posture(&trusted);
+        assert_eq!(
+            check(&posture, "zone.default").remedy,
+            Some(Remedy::Run(Operation::SetDefaultZone { zone: "public" }))
+        );
+        assert!(!check(&posture, "firewall.panic").passed);
+        assert!(!check(&posture, "firewall.drift").passed);
+        assert_eq!(posture.score(), 60);
+    }
+
+    #[test]
+    fn test_evaluate_stopped() {
+        let posture = evaluate_posture(&PostureInput::default());
+        assert_eq!(posture.score(), 15);
+        assert_eq!(
+            check(&posture, "firewall.running").remedy,
+            Some(Remedy::Run(Operation::EnableFirewall))
+        );
+        let posture = evaluate_posture(&PostureInput {
+            endpoints: vec![endpoint(80, None)],
+            ..Default::default()
+        });
+        assert_eq!(posture.score(), 0);
+    }
+}
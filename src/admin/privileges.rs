@@ -0,0 +1,344 @@
+// Security Center - Listener Privileges
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Privileges of listening processes: the user they run as and their
+//! effective capabilities, from `/proc/<pid>/status`.
+//!
+//! A network daemon that keeps root and its full capability set turns any
+//! remote code execution bug into a full compromise. Most daemons only need
+//! root to bind a port below 1024, which `CAP_NET_BIND_SERVICE` grants to an
+//! unprivileged user. Services hardened by systemd (`CapabilityBoundingSet=`,
+//! `User=`) show a reduced set even when running as root.
+//!
+//! The status file of any process is world-readable, but which process owns
+//! a socket is only known for the user's own processes unless the scan runs
+//! as root. For the others the socket's owner (from `/proc/net`) still tells
+//! whether it was opened by root.
+
+use std::collections::HashMap;
+use std::fs;
+
+use super::assessment::{Finding, FindingCategory, Severity};
+use super::network::{ListeningEndpoint, NetworkExposure};
+
+/// Capability names by bit number (see capabilities(7)).
+const CAPABILITIES: &[&str] = &[
+    "CAP_CHOWN",
+    "CAP_DAC_OVERRIDE",
+    "CAP_DAC_READ_SEARCH",
+    "CAP_FOWNER",
+    "CAP_FSETID",
+    "CAP_KILL",
+    "CAP_SETGID",
+    "CAP_SETUID",
+    "CAP_SETPCAP",
+    "CAP_LINUX_IMMUTABLE",
+    "CAP_NET_BIND_SERVICE",
+    "CAP_NET_BROADCAST",
+    "CAP_NET_ADMIN",
+    "CAP_NET_RAW",
+    "CAP_IPC_LOCK",
+    "CAP_IPC_OWNER",
+    "CAP_SYS_MODULE",
+    "CAP_SYS_RAWIO",
+    "CAP_SYS_CHROOT",
+    "CAP_SYS_PTRACE",
+    "CAP_SYS_PACCT",
+    "CAP_SYS_ADMIN",
+    "CAP_SYS_BOOT",
+    "CAP_SYS_NICE",
+    "CAP_SYS_RESOURCE",
+    "CAP_SYS_TIME",
+    "CAP_SYS_TTY_CONFIG",
+    "CAP_MKNOD",
+    "CAP_LEASE",
+    "CAP_AUDIT_WRITE",
+    "CAP_AUDIT_CONTROL",
+    "CAP_SETFCAP",
+    "CAP_MAC_OVERRIDE",
+    "CAP_MAC_ADMIN",
+    "CAP_SYSLOG",
+    "CAP_WAKE_ALARM",
+    "CAP_BLOCK_SUSPEND",
+    "CAP_AUDIT_READ",
+    "CAP_PERFMON",
+    "CAP_BPF",
+    "CAP_CHECKPOINT_RESTORE",
+];
+
+const CAP_SYS_ADMIN: u32 = 21;
+
+/// Daemons that need root for their job, such as switching to the user
+/// logging in, and are not flagged.
+const NEEDS_ROOT: &[&str] = &["systemd", "init", "sshd", "sshd-session", "smbd", "xinetd"];
+
+/// User and effective capabilities of a process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessPrivileges {
+    pub pid: u32,
+    /// Effective user ID.
+    pub uid: u32,
+    /// User name from `/etc/passwd`, if known.
+    pub user: Option<String>,
+    /// Effective capability mask (`CapEff`).
+    pub capabilities: u64,
+}
+
+impl ProcessPrivileges {
+    pub fn runs_as_root(&self) -> bool {
+        self.uid == 0
+    }
+
+    pub fn has_capability(&self, bit: u32) -> bool {
+        bit < 64 && self.capabilities & (1 << bit) != 0
+    }
+
+    /// True when every capability the kernel knows is effective.
+    pub fn has_all_capabilities(&self) -> bool {
+        (0..CAPABILITIES.len() as u32).all(|bit| self.has_capability(bit))
+    }
+
+    /// Names of the effective capabilities.
+    pub fn capability_names(&self) -> Vec<&'static str> {
+        CAPABILITIES
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| self.has_capability(*bit as u32))
+            .map(|(_, name)| *name)
+            .collect()
+    }
+
+    /// User name, or the numeric ID when the name is unknown.
+    pub fn user_label(&self) -> String {
+        self.user
+            .clone()
+            .unwrap_or_else(|| format!("uid {}", self.uid))
+    }
+
+    /// Effective capabilities for display, e.g. "all capabilities".
+    pub fn capabilities_label(&self) -> String {
+        if self.capabilities == 0 {
+            "no capabilities".to_string()
+        } else if self.has_all_capabilities() {
+            "all capabilities".to_string()
+        } else {
+            self.capability_names().join(", ")
+        }
+    }
+
+    /// Root with `CAP_SYS_ADMIN`, which nothing but the bounding set limits.
+    pub fn is_unrestricted(&self) -> bool {
+        self.runs_as_root() && self.has_capability(CAP_SYS_ADMIN)
+    }
+
+    /// An unrestricted daemon that does not need root for its job, so it
+    /// could run as its own user with at most `CAP_NET_BIND_SERVICE`.
+    pub fn could_run_unprivileged(&self, process_name: Option<&str>) -> bool {
+        self.is_unrestricted() && !process_name.is_some_and(|name| NEEDS_ROOT.contains(&name))
+    }
+}
+
+/// Parse `/proc/<pid>/status`.
+fn parse_status(pid: u32, status: &str) -> Option<ProcessPrivileges> {
+    let field = |key: &str| {
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix(key))
+            .map(str::trim)
+    };
+    // Real, effective, saved and filesystem IDs
+    let uid = field("Uid:")?.split_whitespace().nth(1)?.parse().ok()?;
+    let capabilities = u64::from_str_radix(field("CapEff:")?, 16).ok()?;
+    Some(ProcessPrivileges {
+        pid,
+        uid,
+        user: None,
+        capabilities,
+    })
+}
+
+/// Name of a user ID in `/etc/passwd` contents.
+fn passwd_name(passwd: &str, uid: u32) -> Option<String> {
+    passwd.lines().find_map(|line| {
+        let mut fields = line.split(':');
+        let name = fields.next()?;
+        (fields.nth(1)?.parse::<u32>().ok()? == uid).then(|| name.to_string())
+    })
+}
+
+/// Privileges of the processes owning the endpoints, by PID.
+pub fn query_listener_privileges(
+    endpoints: &[ListeningEndpoint],
+) -> HashMap<u32, ProcessPrivileges> {
+    let passwd = fs::read_to_string("/etc/passwd").unwrap_or_default();
+    let mut privileges = HashMap::new();
+    for pid in endpoints.iter().filter_map(|e| e.pid) {
+        if privileges.contains_key(&pid) {
+            continue;
+        }
+        let Ok(status) = fs::read_to_string(format!("/proc/{}/status", pid)) else {
+            continue;
+        };
+        if let Some(mut process) = parse_status(pid, &status) {
+            process.user = passwd_name(&passwd, process.uid);
+            privileges.insert(pid, process);
+        }
+    }
+    privileges
+}
+
+/// Assessment findings for listening daemons running with full root.
+pub(super) fn check_listener_privileges() -> Vec<Finding> {
+    let mut scanner = NetworkExposure::new();
+    let Ok(endpoints) = scanner.scan_sockets() else {
+        return Vec::new();
+    };
+    let privileges = query_listener_privileges(&endpoints);
+    privilege_findings(&endpoints, &privileges)
+}
+
+fn privilege_findings(
+    endpoints: &[ListeningEndpoint],
+    privileges: &HashMap<u32, ProcessPrivileges>,
+) -> Vec<Finding> {
+    // One finding per process, listing all its ports
+    let mut flagged: Vec<(&ProcessPrivileges, Vec<&ListeningEndpoint>)> = Vec::new();
+    for endpoint in endpoints {
+        let Some(process) = endpoint.pid.and_then(|pid| privileges.get(&pid)) else {
+            continue;
+        };
+        if !process.could_run_unprivileged(endpoint.process_name.as_deref()) {
+            continue;
+        }
+        match flagged.iter_mut().find(|(p, _)| p.pid == process.pid) {
+            Some((_, listeners)) => listeners.push(endpoint),
+            None => flagged.push((process, vec![endpoint])),
+        }
+    }
+
+    flagged
+        .into_iter()
+        .map(|(process, listeners)| {
+            let name = listeners[0]
+                .process_name
+                .clone()
+                .unwrap_or_else(|| format!("pid {}", process.pid));
+            let mut ports: Vec<String> = listeners
+                .iter()
+                .map(|e| format!("{}/{}", e.port, e.protocol.as_str().to_lowercase()))
+                .collect();
+            // IPv4 and IPv6 sockets of the same port
+            ports.dedup();
+            let exposed = listeners.iter().any(|e| e.is_exposed());
+            let low_port = listeners.iter().any(|e| e.port < 1024);
+            let mut remediation = format!(
+                "Run {} as a dedicated user: set \"User=\" or \"DynamicUser=yes\" in a \
+                 drop-in for its service",
+                name
+            );
+            if low_port {
+                remediation.push_str(
+                    ", with \"AmbientCapabilities=CAP_NET_BIND_SERVICE\" to bind ports \
+                     below 1024",
+                );
+            }
+            remediation.push_str(
+                ". If it must stay root, limit it with \"CapabilityBoundingSet=\" to the \
+                 capabilities it needs.",
+            );
+            Finding {
+                id: format!("services.privileges.{}", name),
+                category: FindingCategory::Services,
+                severity: if exposed {
+                    Severity::Medium
+                } else {
+                    Severity::Low
+                },
+                title: format!("{} listens as root with all privileges", name),
+                detail: format!(
+                    "{} (PID {}) listens on {} as root with {}. A flaw in it that can be \
+                     reached over the network gives full control of the system.",
+                    name,
+                    process.pid,
+                    ports.join(", "),
+                    process.capabilities_label()
+                ),
+                remediation,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::admin::Protocol;
+
+    const STATUS: &str = "Name:\tnginx\nUmask:\t0022\nState:\tS (sleeping)\n\
+                          Uid:\t1000\t0\t0\t0\nGid:\t0\t0\t0\t0\n\
+                          CapInh:\t0000000000000000\nCapPrm:\t000001ffffffffff\n\
+                          CapEff:\t000001ffffffffff\nCapBnd:\t000001ffffffffff\n";
+
+    fn listener(port: u16, pid: u32, name: &str) -> ListeningEndpoint {
+        ListeningEndpoint {
+            pid: Some(pid),
+            process_name: Some(name.to_string()),
+            uid: Some(0),
+            ..ListeningEndpoint::for_test(port, Protocol::Tcp, "0.0.0.0")
+        }
+    }
+
+    #[test]
+    fn test_parse_status() {
+        let process = parse_status(42, STATUS).unwrap();
+        assert_eq!(process.uid, 0);
+        assert!(process.runs_as_root());
+        assert!(process.has_all_capabilities());
+        assert_eq!(process.capabilities_label(), "all capabilities");
+        assert!(process.could_run_unprivileged(Some("nginx")));
+        assert!(!process.could_run_unprivileged(Some("sshd")));
+
+        let bind_only = ProcessPrivileges {
+            capabilities: 1 << 10,
+            ..process.clone()
+        };
+        assert_eq!(bind_only.capability_names(), ["CAP_NET_BIND_SERVICE"]);
+        assert!(!bind_only.is_unrestricted());
+        assert_eq!(parse_status(42, "Name:\tx\n"), None);
+
+        let passwd =
+            "root:x:0:0:root:/root:/bin/bash\nnginx:x:975:975::/var/lib/nginx:/sbin/nologin\n";
+        assert_eq!(passwd_name(passwd, 975).as_deref(), Some("nginx"));
+        assert_eq!(passwd_name(passwd, 1000), None);
+    }
+
+    #[test]
+    fn test_privilege_findings() {
+        let root = parse_status(7, STATUS).unwrap();
+        let user = ProcessPrivileges {
+            pid: 8,
+            uid: 975,
+            user: Some("nginx".to_string()),
+            capabilities: 1 << 10,
+        };
+        let endpoints = vec![
+            listener(80, 7, "httpd"),
+            listener(443, 7, "httpd"),
+            listener(8080, 8, "nginx"),
+            listener(22, 9, "sshd"),
+        ];
+        let privileges = HashMap::from([
+            (7, root.clone()),
+            (8, user),
+            (9, ProcessPrivileges { pid: 9, ..root }),
+        ]);
+
+        let findings = privilege_findings(&endpoints, &privileges);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].id, "services.privileges.httpd");
+        assert_eq!(findings[0].severity, Severity::Medium);
+        assert!(findings[0].detail.contains("80/tcp, 443/tcp"));
+        assert!(findings[0].remediation.contains("CAP_NET_BIND_SERVICE"));
+    }
+}
@@ -0,0 +1,253 @@
+// Security Center - Quarantine
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Quarantine of a suspicious listening process.
+//!
+//! Quarantining takes the steps a user would otherwise take one by one on
+//! the Network Exposure page, in the order that loses the least evidence:
+//!
+//! 1. Snapshot the executable's SHA-256 and the command line, which are
+//!    gone once the process stops.
+//! 2. Block each port it listens on with a reject rule in every active zone
+//!    and the default zone, runtime and permanent, so a restart does not
+//!    reopen it.
+//! 3. Stop the system service it runs in, found from its cgroup.
+//!
+//! The quarantine opens a high-severity [`Incident`] holding the snapshot
+//! as evidence, with every step on its timeline whether it succeeded or
+//! not (see `crate::incidents`).
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use super::network::Protocol;
+use super::{BinaryIntegrity, Severity};
+use crate::firewall;
+use crate::incidents::{add_incident, EvidenceKind, Incident};
+use crate::models::RichRule;
+use crate::systemd::SystemdClient;
+
+/// cgroup of system services.
+const SYSTEM_SLICE: &str = "/system.slice/";
+
+/// What quarantining a process will do, shown before it is done.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuarantinePlan {
+    pub pid: u32,
+    pub process_name: Option<String>,
+    pub executable: String,
+    pub listeners: Vec<(u16, Protocol)>,
+    /// The system service to stop, if the process runs in one.
+    pub unit: Option<String>,
+    pub reason: String,
+}
+
+impl QuarantinePlan {
+    /// The ports as "port/protocol".
+    pub fn ports(&self) -> Vec<String> {
+        self.listeners
+            .iter()
+            .map(|(port, protocol)| format!("{}/{}", port, protocol.as_str().to_lowercase()))
+            .collect()
+    }
+}
+
+/// The system service in a `/proc/<pid>/cgroup` file, such as
+/// "0::/system.slice/nginx.service". `None` for user sessions, containers
+/// and other scopes, which have no unit to stop.
+fn unit_from_cgroup(cgroup: &str) -> Option<String> {
+    cgroup.lines().find_map(|line| {
+        // "hierarchy-ID:controllers:path"; cgroup v2 has one "0::" line
+        let path = line.splitn(3, ':').nth(2)?;
+        let rest = path.strip_prefix(SYSTEM_SLICE)?;
+        rest.split('/')
+            .rfind(|part| part.ends_with(".service"))
+            .map(str::to_string)
+    })
+}
+
+/// Plan the quarantine of a process flagged by package verification.
+pub fn plan_quarantine(binary: &BinaryIntegrity) -> QuarantinePlan {
+    let unit = fs::read_to_string(format!("/proc/{}/cgroup", binary.pid))
+        .ok()
+        .and_then(|cgroup| unit_from_cgroup(&cgroup));
+    QuarantinePlan {
+        pid: binary.pid,
+        process_name: binary.process_name.clone(),
+        executable: binary.executable.clone(),
+        listeners: binary.listeners.clone(),
+        unit,
+        reason: binary.status.label(),
+    }
+}
+
+/// SHA-256 of a file with `sha256sum`, as lowercase hex.
+fn sha256_of(path: &str) -> Result<String> {
+    let output = Command::new("sha256sum")
+        .arg("--")
+        .arg(path)
+        .output()
+        .context("Failed to run sha256sum")?;
+    if !output.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .next()
+        .map(str::to_string)
+        .context("Empty sha256sum output")
+}
+
+fn cmdline_of(pid: u32) -> Option<String> {
+    let raw = fs::read(format!("/proc/{}/cmdline", pid)).ok()?;
+    let args: Vec<String> = raw
+        .split(|b| *b == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| String::from_utf8_lossy(arg).into_owned())
+        .collect();
+    (!args.is_empty()).then(|| args.join(" "))
+}
+
+/// Block the ports of `plan` in the active and default zones.
+fn block_ports(plan: &QuarantinePlan, incident: &mut Incident) -> Result<()> {
+    let listeners = plan.listeners.clone();
+    // Each step and whether it fully succeeded
+    let steps = firewall::call_blocking(move |client| -> Result<Vec<(String, bool)>> {
+        client.connect().context("Failed to connect to firewalld")?;
+        let mut zones: BTreeSet<String> = client
+            .get_zones()?
+            .into_iter()
+            .filter(|zone| zone.is_active)
+            .map(|zone| zone.name)
+            .collect();
+        zones.insert(client.get_default_zone()?);
+
+        let mut steps = Vec::new();
+        for (port, protocol) in &listeners {
+            let protocol = protocol.as_str().to_lowercase();
+            let rule = RichRule::port_block(&port.to_string(), &protocol).to_string();
+            for zone in &zones {
+                steps.push(match client.add_rich_rule(zone, &rule, true) {
+                    Ok(outcome) if outcome.failed() => (
+                        format!(
+                            "Blocked {}/{} in {} for this session only; saving permanently failed",
+                            port, protocol, zone
+                        ),
+                        false,
+                    ),
+                    Ok(_) => (format!("Blocked {}/{} in {}", port, protocol, zone), true),
+                    Err(e) => (
+                        format!("Failed to block {}/{} in {}: {}", port, protocol, zone, e),
+                        false,
+                    ),
+                });
+            }
+        }
+        Ok(steps)
+    })??;
+    for (step, ok) in steps {
+        incident.record(step, ok);
+    }
+    Ok(())
+}
+
+/// The process snapshot attached to the incident.
+fn process_evidence(plan: &QuarantinePlan, sha256: Option<&str>, cmdline: Option<&str>) -> String {
+    let mut lines = vec![format!("PID: {}", plan.pid)];
+    if let Some(name) = &plan.process_name {
+        lines.push(format!("Process: {}", name));
+    }
+    lines.push(format!("Executable: {}", plan.executable));
+    lines.push(format!("SHA-256: {}", sha256.unwrap_or("unknown")));
+    lines.push(format!("Command line: {}", cmdline.unwrap_or("unknown")));
+    lines.push(format!(
+        "Service: {}",
+        plan.unit.as_deref().unwrap_or("none")
+    ));
+    lines.push(format!("Ports: {}", plan.ports().join(", ")));
+    lines.push(format!("Reason: {}", plan.reason));
+    lines.join("\n")
+}
+
+/// Carry out `plan` and save the incident. Each step is attempted even when
+/// an earlier one failed; the timeline says which did. Talks to firewalld
+/// and systemd, so call it off the main thread.
+pub fn quarantine(plan: &QuarantinePlan) -> Result<Incident> {
+    let name = plan.process_name.as_deref().unwrap_or(&plan.executable);
+    let mut incident = Incident::new(&format!("Quarantined {}", name), Severity::High);
+    incident.record(format!("Quarantine started: {}", plan.reason), true);
+
+    let sha256 = match sha256_of(&plan.executable) {
+        Ok(hash) => {
+            incident.record(format!("Recorded SHA-256 of {}", plan.executable), true);
+            Some(hash)
+        }
+        Err(e) => {
+            incident.record(format!("Failed to hash {}: {}", plan.executable, e), false);
+            None
+        }
+    };
+    let cmdline = cmdline_of(plan.pid);
+    if cmdline.is_some() {
+        incident.record("Recorded the command line", true);
+    } else {
+        incident.record("The command line could not be read", false);
+    }
+    incident.attach(
+        EvidenceKind::Process,
+        &format!("Process {} ({})", name, plan.pid),
+        &process_evidence(plan, sha256.as_deref(), cmdline.as_deref()),
+    );
+
+    if let Err(e) = block_ports(plan, &mut incident) {
+        incident.record(format!("Failed to block the ports: {}", e), false);
+    }
+
+    match &plan.unit {
+        Some(unit) => {
+            let mut client = SystemdClient::new();
+            match client.connect().and_then(|_| client.stop_service(unit)) {
+                Ok(()) => incident.record(format!("Stopped {}", unit), true),
+                Err(e) => incident.record(format!("Failed to stop {}: {}", unit, e), false),
+            }
+        }
+        None => incident.record(
+            "Not running in a system service; the process was left running",
+            false,
+        ),
+    }
+
+    incident.id = add_incident(incident.clone())?;
+    Ok(incident)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_from_cgroup() {
+        assert_eq!(
+            unit_from_cgroup("0::/system.slice/nginx.service\n"),
+            Some("nginx.service".to_string())
+        );
+        assert_eq!(
+            unit_from_cgroup("0::/system.slice/system-sshd.slice/sshd@2-10.0.0.1:22.service\n"),
+            Some("sshd@2-10.0.0.1:22.service".to_string())
+        );
+        assert_eq!(
+            unit_from_cgroup(
+                "0::/user.slice/user-1000.slice/user@1000.service/app.slice/app.service\n"
+            ),
+            None
+        );
+        assert_eq!(
+            unit_from_cgroup("0::/system.slice/docker-4f2c.scope\n"),
+            None
+        );
+    }
+}
@@ -0,0 +1,512 @@
+// Security Center - Remote Access Exposure
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Remote access and remote power exposure.
+//!
+//! Two things let someone reach a machine from the network without a login
+//! prompt in front of them:
+//!
+//! - Wake-on-LAN: the network card powers the machine on when it sees a
+//!   matching packet. The state is read per interface with the ethtool
+//!   generic netlink family (`ETHTOOL_MSG_WOL_GET`, unprivileged); turning
+//!   it off needs `CAP_NET_ADMIN` and goes through the privileged helper.
+//! - Remote management services (VNC, RDP, IPMI, ...): recognized among the
+//!   listening endpoints by their well-known ports, see
+//!   [`remote_access_service`].
+//!
+//! The ways to log in remotely, SSH, the Cockpit web console, VNC, RDP and
+//! Telnet, are also known by the systemd units that provide them and the
+//! firewalld service that opens their ports; see [`AccessMethod`] and
+//! [`query_access_methods`]. Turning one on or off changes both, through
+//! [`Operation::SetRemoteAccess`](super::Operation::SetRemoteAccess).
+//!
+//! Wake-on-LAN changes last until the next boot; NetworkManager connection
+//! profiles or udev `.link` files may set it again when the link comes up.
+
+use std::fs;
+
+use anyhow::{anyhow, Context, Result};
+use netlink_packet_core::{NLM_F_ACK, NLM_F_REQUEST};
+use netlink_sys::{protocols::NETLINK_GENERIC, Socket, SocketAddr};
+
+use super::netlink::{self, parse_attrs, put_attr, NLA_F_NESTED};
+use super::network::{ListeningEndpoint, Protocol};
+use super::travel::SSH_UNITS;
+use crate::firewall;
+use crate::helper::{run_privileged, HelperOp};
+use crate::models::Zone;
+use crate::systemd::SystemdClient;
+use crate::validation::validate_interface_name;
+
+// Generic netlink constants (linux/genetlink.h)
+const GENL_ID_CTRL: u16 = 0x10;
+const CTRL_CMD_GETFAMILY: u8 = 3;
+const CTRL_ATTR_FAMILY_ID: u16 = 1;
+const CTRL_ATTR_FAMILY_NAME: u16 = 2;
+
+// ethtool netlink (linux/ethtool_netlink.h)
+const ETHTOOL_GENL_NAME: &str = "ethtool";
+const ETHTOOL_GENL_VERSION: u8 = 1;
+const ETHTOOL_MSG_WOL_GET: u8 = 9;
+const ETHTOOL_MSG_WOL_SET: u8 = 10;
+const ETHTOOL_A_HEADER_DEV_NAME: u16 = 2;
+const ETHTOOL_A_HEADER_FLAGS: u16 = 3;
+const ETHTOOL_FLAG_COMPACT_BITSETS: u32 = 1;
+const ETHTOOL_A_WOL_HEADER: u16 = 1;
+const ETHTOOL_A_WOL_MODES: u16 = 2;
+const ETHTOOL_A_BITSET_NOMASK: u16 = 1;
+const ETHTOOL_A_BITSET_SIZE: u16 = 2;
+const ETHTOOL_A_BITSET_VALUE: u16 = 4;
+const ETHTOOL_A_BITSET_MASK: u16 = 5;
+
+/// Wake-on-LAN modes (`WAKE_*` in linux/ethtool.h) and their labels.
+const WOL_MODES: [(u32, &str); 8] = [
+    (1 << 0, "PHY activity"),
+    (1 << 1, "unicast"),
+    (1 << 2, "multicast"),
+    (1 << 3, "broadcast"),
+    (1 << 4, "ARP"),
+    (1 << 5, "magic packet"),
+    (1 << 6, "SecureOn password"),
+    (1 << 7, "filter"),
+];
+
+/// Remote management services by port range and protocol (`None`: both).
+const REMOTE_SERVICES: [(u16, u16, Option<Protocol>, &str); 8] = [
+    (3389, 3389, None, "Remote Desktop (RDP)"),
+    (5900, 5909, Some(Protocol::Tcp), "VNC"),
+    (623, 623, Some(Protocol::Udp), "IPMI"),
+    (664, 664, Some(Protocol::Udp), "IPMI (secure RMCP)"),
+    (16992, 16995, Some(Protocol::Tcp), "Intel AMT"),
+    (5938, 5938, None, "TeamViewer"),
+    (7070, 7070, Some(Protocol::Tcp), "AnyDesk"),
+    (6000, 6009, Some(Protocol::Tcp), "X11"),
+];
+
+/// Wake-on-LAN state of one network interface.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WakeOnLan {
+    pub interface: String,
+    /// `WAKE_*` modes the hardware supports.
+    pub supported: u32,
+    /// `WAKE_*` modes currently armed.
+    pub enabled: u32,
+}
+
+impl WakeOnLan {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled != 0
+    }
+
+    /// Labels of the armed modes, e.g. `["magic packet"]`.
+    pub fn enabled_modes(&self) -> Vec<&'static str> {
+        WOL_MODES
+            .iter()
+            .filter(|(bit, _)| self.enabled & bit != 0)
+            .map(|(_, label)| *label)
+            .collect()
+    }
+}
+
+/// Name of the remote management service usually found on `port`.
+pub fn remote_access_service(port: u16, protocol: Protocol) -> Option<&'static str> {
+    REMOTE_SERVICES
+        .iter()
+        .find(|(start, end, proto, _)| {
+            (*start..=*end).contains(&port) && proto.is_none_or(|p| p == protocol)
+        })
+        .map(|(_, _, _, name)| *name)
+}
+
+/// Listening endpoints of remote management services that other hosts can
+/// reach (anything not bound to loopback), with the service name.
+pub fn remote_access_endpoints(
+    endpoints: &[ListeningEndpoint],
+) -> Vec<(&'static str, ListeningEndpoint)> {
+    endpoints
+        .iter()
+        .filter(|e| !e.local_addr.is_loopback())
+        .filter_map(|e| remote_access_service(e.port, e.protocol).map(|name| (name, e.clone())))
+        .collect()
+}
+
+/// A way to log in to or control the machine from the network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessMethod {
+    Ssh,
+    Cockpit,
+    Vnc,
+    Rdp,
+    Telnet,
+}
+
+impl AccessMethod {
+    pub const ALL: [AccessMethod; 5] = [
+        AccessMethod::Ssh,
+        AccessMethod::Cockpit,
+        AccessMethod::Vnc,
+        AccessMethod::Rdp,
+        AccessMethod::Telnet,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AccessMethod::Ssh => "SSH",
+            AccessMethod::Cockpit => "Cockpit Web Console",
+            AccessMethod::Vnc => "VNC",
+            AccessMethod::Rdp => "Remote Desktop (RDP)",
+            AccessMethod::Telnet => "Telnet",
+        }
+    }
+
+    /// Units that provide the method, of which a system usually has one.
+    /// Template instances such as `vncserver@:1.service` are not covered.
+    pub fn units(self) -> &'static [&'static str] {
+        match self {
+            AccessMethod::Ssh => SSH_UNITS,
+            AccessMethod::Cockpit => &["cockpit.socket"],
+            AccessMethod::Vnc => &["x11vnc.service", "wayvnc.service"],
+            AccessMethod::Rdp => &["xrdp.service", "gnome-remote-desktop.service"],
+            AccessMethod::Telnet => &["telnet.socket"],
+        }
+    }
+
+    /// The firewalld service opening the method's ports.
+    pub fn firewall_service(self) -> &'static str {
+        match self {
+            AccessMethod::Ssh => "ssh",
+            AccessMethod::Cockpit => "cockpit",
+            AccessMethod::Vnc => "vnc-server",
+            AccessMethod::Rdp => "rdp",
+            AccessMethod::Telnet => "telnet",
+        }
+    }
+
+    /// Whether the method sends passwords in the clear.
+    pub fn is_cleartext(self) -> bool {
+        self == AccessMethod::Telnet
+    }
+
+    /// Zones whose rules let the method's firewalld service through.
+    pub fn open_in(self, zones: &[Zone]) -> Vec<String> {
+        let service = self.firewall_service().to_string();
+        zones
+            .iter()
+            .filter(|zone| zone.services.contains(&service))
+            .map(|zone| zone.name.clone())
+            .collect()
+    }
+}
+
+/// What is installed, running and open of an [`AccessMethod`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessMethodStatus {
+    pub method: AccessMethod,
+    /// Units of the method that are installed.
+    pub installed: Vec<String>,
+    /// Installed units that run or listen.
+    pub active: Vec<String>,
+    /// Installed units started at boot.
+    pub enabled: Vec<String>,
+    /// Zones carrying traffic that let it through; empty also when
+    /// firewalld is not running.
+    pub open_in: Vec<String>,
+}
+
+impl AccessMethodStatus {
+    pub fn is_installed(&self) -> bool {
+        !self.installed.is_empty()
+    }
+
+    /// Whether the method runs or starts at boot.
+    pub fn is_on(&self) -> bool {
+        !self.active.is_empty() || !self.enabled.is_empty()
+    }
+
+    /// Whether other hosts can use it now: it runs and a zone in use lets
+    /// it through.
+    pub fn is_exposed(&self) -> bool {
+        !self.active.is_empty() && !self.open_in.is_empty()
+    }
+}
+
+/// State of every [`AccessMethod`], installed or not.
+pub fn query_access_methods() -> Result<Vec<AccessMethodStatus>> {
+    let mut systemd = SystemdClient::new();
+    systemd.connect()?;
+    let zones: Vec<Zone> = firewall::read_blocking(|client| match client.connect() {
+        Ok(()) => client
+            .get_zones()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(Zone::is_in_use)
+            .collect(),
+        Err(_) => Vec::new(),
+    })
+    .unwrap_or_default();
+
+    let mut result = Vec::new();
+    for method in AccessMethod::ALL {
+        let mut status = AccessMethodStatus {
+            method,
+            installed: Vec::new(),
+            active: Vec::new(),
+            enabled: Vec::new(),
+            open_in: method.open_in(&zones),
+        };
+        for unit in method.units() {
+            let Ok(info) = systemd.get_service_info(unit) else {
+                continue;
+            };
+            if !info.is_installed {
+                continue;
+            }
+            status.installed.push(unit.to_string());
+            // A listening socket is active without a running service
+            if systemd.is_unit_active(unit).unwrap_or(false) {
+                status.active.push(unit.to_string());
+            }
+            if info.is_enabled {
+                status.enabled.push(unit.to_string());
+            }
+        }
+        result.push(status);
+    }
+    Ok(result)
+}
+
+/// Wake-on-LAN state of every interface that supports it. Interfaces whose
+/// driver does not report Wake-on-LAN are left out.
+pub fn query_wake_on_lan() -> Result<Vec<WakeOnLan>> {
+    let mut names: Vec<String> = fs::read_dir("/sys/class/net")
+        .context("Failed to list network interfaces")?
+        .flatten()
+        .filter_map(|e| e.file_name().into_string().ok())
+        .filter(|name| name != "lo" && validate_interface_name(name).is_some())
+        .collect();
+    names.sort();
+
+    let mut socket = GenlSocket::open()?;
+    let family = socket.resolve_family(ETHTOOL_GENL_NAME)?;
+    let mut result = Vec::new();
+    for name in names {
+        let mut attrs = Vec::new();
+        put_attr(
+            &mut attrs,
+            ETHTOOL_A_WOL_HEADER | NLA_F_NESTED,
+            &request_header(&name),
+        );
+        // Virtual devices and drivers without get_wol answer EOPNOTSUPP
+        let Ok(replies) = socket.request(family, ETHTOOL_GENL_VERSION, ETHTOOL_MSG_WOL_GET, &attrs)
+        else {
+            continue;
+        };
+        if let Some(wol) = replies.iter().find_map(|r| parse_wol_reply(&name, r)) {
+            if wol.supported != 0 {
+                result.push(wol);
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Turn Wake-on-LAN off on `interface` through the privileged helper.
+pub fn disable_wake_on_lan(interface: &str) -> Result<String> {
+    let interface = validate_interface_name(interface)
+        .ok_or_else(|| anyhow!("Invalid interface name: {}", interface))?;
+    run_privileged(&HelperOp::DisableWakeOnLan {
+        interface: interface.to_string(),
+    })
+}
+
+/// Clear every Wake-on-LAN mode of `interface`. Needs `CAP_NET_ADMIN`; runs
+/// inside the privileged helper.
+pub fn clear_wake_on_lan(interface: &str) -> Result<()> {
+    let mut modes = Vec::new();
+    put_attr(&mut modes, ETHTOOL_A_BITSET_NOMASK, &[]);
+    put_attr(&mut modes, ETHTOOL_A_BITSET_SIZE, &32u32.to_ne_bytes());
+    put_attr(&mut modes, ETHTOOL_A_BITSET_VALUE, &0u32.to_ne_bytes());
+
+    let mut attrs = Vec::new();
+    put_attr(
+        &mut attrs,
+        ETHTOOL_A_WOL_HEADER | NLA_F_NESTED,
+        &request_header(interface),
+    );
+    put_attr(&mut attrs, ETHTOOL_A_WOL_MODES | NLA_F_NESTED, &modes);
+
+    let mut socket = GenlSocket::open()?;
+    let family = socket.resolve_family(ETHTOOL_GENL_NAME)?;
+    socket
+        .request(family, ETHTOOL_GENL_VERSION, ETHTOOL_MSG_WOL_SET, &attrs)
+        .with_context(|| format!("Cannot change Wake-on-LAN on {}", interface))?;
+    Ok(())
+}
+
+/// `ETHTOOL_A_*_HEADER` contents selecting a device by name, asking for
+/// compact bitsets.
+fn request_header(interface: &str) -> Vec<u8> {
+    let mut header = Vec::new();
+    let mut name = interface.as_bytes().to_vec();
+    name.push(0);
+    put_attr(&mut header, ETHTOOL_A_HEADER_DEV_NAME, &name);
+    put_attr(
+        &mut header,
+        ETHTOOL_A_HEADER_FLAGS,
+        &ETHTOOL_FLAG_COMPACT_BITSETS.to_ne_bytes(),
+    );
+    header
+}
+
+/// Decode the attributes of an `ETHTOOL_MSG_WOL_GET_REPLY`.
+fn parse_wol_reply(interface: &str, payload: &[u8]) -> Option<WakeOnLan> {
+    let modes = parse_attrs(payload)
+        .into_iter()
+        .find(|(kind, _)| *kind == ETHTOOL_A_WOL_MODES)?
+        .1;
+    let mut wol = WakeOnLan {
+        interface: interface.to_string(),
+        supported: 0,
+        enabled: 0,
+    };
+    for (kind, value) in parse_attrs(modes) {
+        match kind {
+            ETHTOOL_A_BITSET_VALUE => wol.enabled = read_u32(value)?,
+            ETHTOOL_A_BITSET_MASK => wol.supported = read_u32(value)?,
+            _ => {}
+        }
+    }
+    Some(wol)
+}
+
+fn read_u32(bytes: &[u8]) -> Option<u32> {
+    Some(u32::from_ne_bytes(bytes.get(..4)?.try_into().ok()?))
+}
+
+/// A generic netlink socket sending one request at a time.
+struct GenlSocket {
+    socket: Socket,
+    seq: u32,
+}
+
+impl GenlSocket {
+    fn open() -> Result<Self> {
+        let mut socket = Socket::new(NETLINK_GENERIC)?;
+        socket.bind_auto()?;
+        socket.connect(&SocketAddr::new(0, 0))?;
+        Ok(Self { socket, seq: 0 })
+    }
+
+    /// Numeric id of a generic netlink family.
+    fn resolve_family(&mut self, name: &str) -> Result<u16> {
+        let mut attrs = Vec::new();
+        let mut value = name.as_bytes().to_vec();
+        value.push(0);
+        put_attr(&mut attrs, CTRL_ATTR_FAMILY_NAME, &value);
+        let replies = self
+            .request(GENL_ID_CTRL, 1, CTRL_CMD_GETFAMILY, &attrs)
+            .with_context(|| format!("The kernel does not provide {} netlink", name))?;
+        replies
+            .iter()
+            .flat_map(|r| parse_attrs(r))
+            .find(|(kind, _)| *kind == CTRL_ATTR_FAMILY_ID)
+            .and_then(|(_, v)| Some(u16::from_ne_bytes(v.get(..2)?.try_into().ok()?)))
+            .ok_or_else(|| anyhow!("Malformed {} family reply", name))
+    }
+
+    /// Send a request and collect the attribute payloads of the replies
+    /// until the kernel acknowledges it.
+    fn request(&mut self, family: u16, version: u8, cmd: u8, attrs: &[u8]) -> Result<Vec<Vec<u8>>> {
+        self.seq = self.seq.wrapping_add(1);
+        let mut body = vec![cmd, version, 0, 0];
+        body.extend_from_slice(attrs);
+        let msg = netlink::message(family, NLM_F_REQUEST | NLM_F_ACK, self.seq, &body);
+        let replies = netlink::request(&mut self.socket, self.seq, &msg)?;
+        // Skip the generic netlink header (cmd, version, reserved)
+        Ok(replies
+            .into_iter()
+            .filter_map(|reply| reply.payload.get(4..).map(<[u8]>::to_vec))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_method_open_in() {
+        let mut public = Zone::new("public");
+        public.services = vec!["ssh".to_string(), "cockpit".to_string()];
+        let mut home = Zone::new("home");
+        home.services = vec!["ssh".to_string(), "vnc-server".to_string()];
+        let zones = [public, home];
+
+        assert_eq!(AccessMethod::Ssh.open_in(&zones), vec!["public", "home"]);
+        assert_eq!(AccessMethod::Vnc.open_in(&zones), vec!["home"]);
+        assert!(AccessMethod::Telnet.open_in(&zones).is_empty());
+        for method in AccessMethod::ALL {
+            assert!(!method.units().is_empty());
+            for unit in method.units() {
+                assert!(crate::validation::validate_service_name(unit).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_remote_access_service() {
+        assert_eq!(
+            remote_access_service(3389, Protocol::Udp),
+            Some("Remote Desktop (RDP)")
+        );
+        assert_eq!(remote_access_service(5901, Protocol::Tcp), Some("VNC"));
+        assert_eq!(remote_access_service(5901, Protocol::Udp), None);
+        assert_eq!(remote_access_service(623, Protocol::Udp), Some("IPMI"));
+        assert_eq!(remote_access_service(22, Protocol::Tcp), None);
+    }
+
+    #[test]
+    fn test_remote_access_endpoints_skip_loopback() {
+        let endpoints = vec![
+            ListeningEndpoint::for_test(5900, Protocol::Tcp, "0.0.0.0"),
+            ListeningEndpoint::for_test(3389, Protocol::Tcp, "127.0.0.1"),
+            ListeningEndpoint::for_test(3389, Protocol::Tcp, "192.168.1.5"),
+            ListeningEndpoint::for_test(443, Protocol::Tcp, "0.0.0.0"),
+        ];
+        let found: Vec<(&str, u16)> = remote_access_endpoints(&endpoints)
+            .iter()
+            .map(|(name, e)| (*name, e.port))
+            .collect();
+        assert_eq!(found, vec![("VNC", 5900), ("Remote Desktop (RDP)", 3389)]);
+    }
+
+    #[test]
+    fn test_parse_wol_reply() {
+        let mut modes = Vec::new();
+        put_attr(&mut modes, ETHTOOL_A_BITSET_SIZE, &8u32.to_ne_bytes());
+        put_attr(
+            &mut modes,
+            ETHTOOL_A_BITSET_VALUE,
+            &(1u32 << 5).to_ne_bytes(),
+        );
+        put_attr(
+            &mut modes,
+            ETHTOOL_A_BITSET_MASK,
+            &(1u32 << 0 | 1 << 5).to_ne_bytes(),
+        );
+        let mut reply = Vec::new();
+        put_attr(
+            &mut reply,
+            ETHTOOL_A_WOL_HEADER | NLA_F_NESTED,
+            &request_header("eth0"),
+        );
+        put_attr(&mut reply, ETHTOOL_A_WOL_MODES | NLA_F_NESTED, &modes);
+
+        let wol = parse_wol_reply("eth0", &reply).unwrap();
+        assert!(wol.is_enabled());
+        assert_eq!(wol.supported, 0b10_0001);
+        assert_eq!(wol.enabled_modes(), vec!["magic packet"]);
+        assert!(parse_wol_reply("eth0", &[]).is_none());
+    }
+}
@@ -0,0 +1,275 @@
+// Security Center - Rule Hit Counters
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Packet and byte counters for firewalld port rules.
+//!
+//! With the nftables backend, firewalld renders each zone into chains of the
+//! `inet firewalld` table: open ports land in `filter_IN_<zone>_allow`, port
+//! blocks from rich rules in `filter_IN_<zone>_deny`. Reading that table needs
+//! root, so the JSON listing comes from the privileged helper (see
+//! `crate::helper`) and is parsed here.
+//!
+//! Only rules that carry an nftables `counter` statement are counted. Rules
+//! firewalld generates without one have no hit count, and every counter is
+//! reset when firewalld reloads its rules.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::helper::{run_privileged, HelperOp};
+
+/// Packets and bytes matched by one or more rules.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuleHits {
+    pub packets: u64,
+    pub bytes: u64,
+}
+
+impl RuleHits {
+    fn add(&mut self, other: RuleHits) {
+        self.packets += other.packets;
+        self.bytes += other.bytes;
+    }
+}
+
+/// Identifies a port rule: zone, port spec ("22" or "60000-61000"),
+/// protocol and whether it blocks.
+type RuleKey = (String, String, String, bool);
+
+/// Hit counters for the port rules of every zone.
+#[derive(Debug, Clone, Default)]
+pub struct RuleCounters {
+    hits: HashMap<RuleKey, RuleHits>,
+}
+
+impl RuleCounters {
+    /// Parse `nft -j list table inet firewalld` output.
+    pub fn from_nft_json(json: &str) -> Result<Self> {
+        let root: Value = serde_json::from_str(json).context("Invalid nft JSON output")?;
+        let mut counters = RuleCounters::default();
+
+        let items = root
+            .get("nftables")
+            .and_then(Value::as_array)
+            .context("nft output has no ruleset")?;
+        for rule in items.iter().filter_map(|item| item.get("rule")) {
+            let Some((zone, blocked)) = rule
+                .get("chain")
+                .and_then(Value::as_str)
+                .and_then(zone_chain)
+            else {
+                continue;
+            };
+            let Some(expr) = rule.get("expr").and_then(Value::as_array) else {
+                continue;
+            };
+            let Some(hits) = expr.iter().find_map(counter_hits) else {
+                continue;
+            };
+            for (port_spec, protocol) in expr.iter().flat_map(port_matches) {
+                counters
+                    .hits
+                    .entry((zone.to_string(), port_spec, protocol, blocked))
+                    .or_default()
+                    .add(hits);
+            }
+        }
+
+        Ok(counters)
+    }
+
+    /// True when no port rule carries a counter.
+    pub fn is_empty(&self) -> bool {
+        self.hits.is_empty()
+    }
+
+    /// Number of port rules with a counter.
+    pub fn len(&self) -> usize {
+        self.hits.len()
+    }
+
+    /// Combined hits of a port rule across zones and protocols, or `None`
+    /// when none of them has a counter.
+    pub fn hits(
+        &self,
+        zones: &[String],
+        port_spec: &str,
+        protocols: &[String],
+        blocked: bool,
+    ) -> Option<RuleHits> {
+        let mut total: Option<RuleHits> = None;
+        for zone in zones {
+            for protocol in protocols {
+                let key = (
+                    zone.clone(),
+                    port_spec.to_string(),
+                    protocol.clone(),
+                    blocked,
+                );
+                if let Some(hits) = self.hits.get(&key) {
+                    total.get_or_insert_with(RuleHits::default).add(*hits);
+                }
+            }
+        }
+        total
+    }
+}
+
+/// Read the counters of firewalld's nftables rules. Prompts for
+/// authentication through the privileged helper.
+pub fn read_rule_counters() -> Result<RuleCounters> {
+    let json = run_privileged(&HelperOp::ReadRuleCounters)?;
+    RuleCounters::from_nft_json(&json)
+}
+
+/// Zone and verdict of a firewalld zone chain: `filter_IN_public_allow`
+/// gives `("public", false)`, `filter_IN_public_deny` `("public", true)`.
+fn zone_chain(chain: &str) -> Option<(&str, bool)> {
+    let rest = chain.strip_prefix("filter_IN_")?;
+    if let Some(zone) = rest.strip_suffix("_allow") {
+        Some((zone, false))
+    } else {
+        rest.strip_suffix("_deny").map(|zone| (zone, true))
+    }
+}
+
+fn counter_hits(expr: &Value) -> Option<RuleHits> {
+    let counter = expr.get("counter")?;
+    Some(RuleHits {
+        packets: counter.get("packets")?.as_u64()?,
+        bytes: counter.get("bytes")?.as_u64()?,
+    })
+}
+
+/// Port specs and protocol of a `<proto> dport <port|range|set>` match.
+fn port_matches(expr: &Value) -> Vec<(String, String)> {
+    let Some(m) = expr.get("match") else {
+        return Vec::new();
+    };
+    let Some(payload) = m.get("left").and_then(|l| l.get("payload")) else {
+        return Vec::new();
+    };
+    if payload.get("field").and_then(Value::as_str) != Some("dport") {
+        return Vec::new();
+    }
+    let Some(protocol) = payload.get("protocol").and_then(Value::as_str) else {
+        return Vec::new();
+    };
+
+    let right = m.get("right");
+    let values: Vec<&Value> = match right.and_then(|r| r.get("set")).and_then(Value::as_array) {
+        Some(set) => set.iter().collect(),
+        None => right.into_iter().collect(),
+    };
+    values
+        .into_iter()
+        .filter_map(port_spec)
+        .map(|spec| (spec, protocol.to_string()))
+        .collect()
+}
+
+/// `22` → "22", `{"range": [1000, 2000]}` → "1000-2000".
+fn port_spec(value: &Value) -> Option<String> {
+    if let Some(port) = value.as_u64() {
+        return Some(port.to_string());
+    }
+    let range = value.get("range")?.as_array()?;
+    match range.as_slice() {
+        [start, end] => Some(format!("{}-{}", start.as_u64()?, end.as_u64()?)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LISTING: &str = r#"{"nftables": [
+        {"metainfo": {"version": "1.0.9", "json_schema_version": 1}},
+        {"table": {"family": "inet", "name": "firewalld", "handle": 1}},
+        {"rule": {"family": "inet", "table": "firewalld", "chain": "filter_IN_public_allow",
+          "handle": 10, "expr": [
+            {"match": {"op": "==", "left": {"payload": {"protocol": "tcp", "field": "dport"}}, "right": 22}},
+            {"match": {"op": "in", "left": {"ct": {"key": "state"}}, "right": ["new", "untracked"]}},
+            {"counter": {"packets": 12, "bytes": 720}},
+            {"accept": null}]}},
+        {"rule": {"family": "inet", "table": "firewalld", "chain": "filter_IN_public_allow",
+          "handle": 11, "expr": [
+            {"match": {"op": "==", "left": {"payload": {"protocol": "udp", "field": "dport"}}, "right": {"range": [60000, 61000]}}},
+            {"counter": {"packets": 0, "bytes": 0}},
+            {"accept": null}]}},
+        {"rule": {"family": "inet", "table": "firewalld", "chain": "filter_IN_public_allow",
+          "handle": 12, "expr": [
+            {"match": {"op": "==", "left": {"payload": {"protocol": "tcp", "field": "dport"}}, "right": 8080}},
+            {"accept": null}]}},
+        {"rule": {"family": "inet", "table": "firewalld", "chain": "filter_IN_home_deny",
+          "handle": 13, "expr": [
+            {"match": {"op": "==", "left": {"payload": {"protocol": "tcp", "field": "dport"}}, "right": 23}},
+            {"counter": {"packets": 3, "bytes": 180}},
+            {"reject": null}]}}
+    ]}"#;
+
+    #[test]
+    fn test_from_nft_json() {
+        let counters = RuleCounters::from_nft_json(LISTING).unwrap();
+        assert_eq!(counters.len(), 3);
+
+        let zones = vec!["public".to_string()];
+        let tcp = vec!["tcp".to_string()];
+        assert_eq!(
+            counters.hits(&zones, "22", &tcp, false),
+            Some(RuleHits {
+                packets: 12,
+                bytes: 720
+            })
+        );
+        assert_eq!(
+            counters.hits(&zones, "60000-61000", &["udp".to_string()], false),
+            Some(RuleHits::default())
+        );
+        // No counter statement on the rule
+        assert_eq!(counters.hits(&zones, "8080", &tcp, false), None);
+        // Blocks are kept apart from open ports
+        assert_eq!(counters.hits(&zones, "22", &tcp, true), None);
+        assert!(counters
+            .hits(&["home".to_string()], "23", &tcp, true)
+            .is_some());
+    }
+
+    #[test]
+    fn test_hits_combine_zones_and_protocols() {
+        let counters = RuleCounters::from_nft_json(LISTING).unwrap();
+        let hits = counters
+            .hits(
+                &["public".to_string(), "home".to_string()],
+                "22",
+                &["tcp".to_string(), "udp".to_string()],
+                false,
+            )
+            .unwrap();
+        assert_eq!(hits.packets, 12);
+    }
+
+    #[test]
+    fn test_zone_chain() {
+        assert_eq!(
+            zone_chain("filter_IN_public_allow"),
+            Some(("public", false))
+        );
+        assert_eq!(
+            zone_chain("filter_IN_my_zone_deny"),
+            Some(("my_zone", true))
+        );
+        assert_eq!(zone_chain("filter_IN_public_pre"), None);
+        assert_eq!(zone_chain("filter_INPUT"), None);
+    }
+
+    #[test]
+    fn test_invalid_json() {
+        assert!(RuleCounters::from_nft_json("not json").is_err());
+        assert!(RuleCounters::from_nft_json("{}").is_err());
+    }
+}
@@ -0,0 +1,224 @@
+// Security Center - Rule Probe
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Quick check that a port rule took effect, run right after it is applied.
+//!
+//! firewalld accepts a change before it reaches the kernel, and a rule in a
+//! zone no interface uses protects nothing. [`probe_port`] reads the running
+//! configuration back and says whether the port is now open or closed in
+//! the zones the rule went into, on which interfaces that holds, and whether
+//! a local program listens on the port. Connecting to the machine's own
+//! addresses would go through loopback, which the firewall does not filter,
+//! so reachability is judged from the rules and the interfaces rather than
+//! from a connection attempt.
+
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream};
+use std::time::Duration;
+
+use anyhow::Result;
+
+use super::interface_exposure::query_interface_addresses;
+use crate::firewall::FirewallClient;
+use crate::models::{RichRule, RuleElement, Zone};
+use crate::validation::parse_port_spec;
+
+/// Longest wait for a local listener to accept.
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// What a rule change should have done to the port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expectation {
+    Open,
+    Closed,
+}
+
+/// Outcome of a probe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleProbe {
+    pub expected: Expectation,
+    /// The running firewall matches the expectation in every zone.
+    pub confirmed: bool,
+    /// Interfaces the zones apply to.
+    pub interfaces: Vec<String>,
+    /// Whether a program accepts TCP connections on the port locally;
+    /// `None` for UDP-only rules.
+    pub listening: Option<bool>,
+}
+
+impl RuleProbe {
+    /// Short result for a toast, e.g. "verified open on eth0".
+    pub fn summary(&self) -> String {
+        if !self.confirmed {
+            return "not yet in effect in the running firewall".to_string();
+        }
+        let state = match self.expected {
+            Expectation::Open => "open",
+            Expectation::Closed => "closed",
+        };
+        let mut summary = if self.interfaces.is_empty() {
+            format!("verified {}, but no interface uses the zone", state)
+        } else {
+            format!("verified {} on {}", state, self.interfaces.join(", "))
+        };
+        match (self.expected, self.listening) {
+            (Expectation::Open, Some(false)) => summary.push_str("; nothing listens on it yet"),
+            (Expectation::Closed, Some(true)) => {
+                summary.push_str("; a local program still listens on it")
+            }
+            _ => {}
+        }
+        summary
+    }
+}
+
+/// Whether `spec` ("8080" or "10-20") lies within the port entry `entry`.
+fn covers(entry: &str, spec: &str) -> bool {
+    match (parse_port_spec(entry), parse_port_spec(spec)) {
+        (Some((start, end)), Some((first, last))) => start <= first && last <= end,
+        _ => false,
+    }
+}
+
+/// Whether a rich rule of `zone` rejects or drops `spec`/`protocol`.
+fn zone_blocks(zone: &Zone, spec: &str, protocol: &str) -> bool {
+    zone.rich_rules.iter().any(|text| {
+        let Ok(rule) = RichRule::parse(text) else {
+            return false;
+        };
+        rule.source.is_none()
+            && rule.action.as_ref().is_some_and(|action| action.is_deny())
+            && matches!(&rule.element, Some(RuleElement::Port { port, protocol: p })
+                if p == protocol && covers(port, spec))
+    })
+}
+
+/// Whether `zone` lets `spec`/`protocol` through: its target accepts
+/// everything, or a port entry covers it and no rich rule blocks it.
+pub fn zone_allows(zone: &Zone, spec: &str, protocol: &str) -> bool {
+    if zone_blocks(zone, spec, protocol) {
+        return false;
+    }
+    zone.target == "ACCEPT"
+        || zone.ports.iter().any(|entry| {
+            entry
+                .split_once('/')
+                .is_some_and(|(port, p)| p == protocol && covers(port, spec))
+        })
+}
+
+/// Whether something accepts TCP connections on `port` on loopback.
+fn is_listening(port: u16) -> bool {
+    [
+        SocketAddr::from((Ipv4Addr::LOCALHOST, port)),
+        SocketAddr::from((Ipv6Addr::LOCALHOST, port)),
+    ]
+    .iter()
+    .any(|addr| TcpStream::connect_timeout(addr, CONNECT_TIMEOUT).is_ok())
+}
+
+/// Read the running configuration back after a change to `spec` in `zones`
+/// and check it against `expected`.
+pub fn probe_port(
+    client: &mut FirewallClient,
+    zones: &[String],
+    spec: &str,
+    protocols: &[String],
+    expected: Expectation,
+) -> Result<RuleProbe> {
+    let all = client.get_zones()?;
+    let mut confirmed = true;
+    let mut interfaces: Vec<String> = Vec::new();
+    for zone in all.iter().filter(|zone| zones.contains(&zone.name)) {
+        for protocol in protocols {
+            let open = zone_allows(zone, spec, protocol);
+            confirmed &= open == (expected == Expectation::Open);
+        }
+        interfaces.extend(zone.interfaces.iter().cloned());
+        // The default zone also covers the interfaces no zone claims
+        if zone.is_default {
+            let bound: Vec<&String> = all.iter().flat_map(|z| &z.interfaces).collect();
+            for link in query_interface_addresses().unwrap_or_default() {
+                if link.name != "lo" && !link.addresses.is_empty() && !bound.contains(&&link.name) {
+                    interfaces.push(link.name);
+                }
+            }
+        }
+    }
+    interfaces.sort();
+    interfaces.dedup();
+
+    let listening = protocols
+        .iter()
+        .any(|p| p == "tcp")
+        .then(|| parse_port_spec(spec).is_some_and(|(start, _)| is_listening(start)));
+    Ok(RuleProbe {
+        expected,
+        confirmed,
+        interfaces,
+        listening,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zone(target: &str, ports: &[&str], rich_rules: &[&str]) -> Zone {
+        Zone {
+            target: target.to_string(),
+            ports: ports.iter().map(|p| p.to_string()).collect(),
+            rich_rules: rich_rules.iter().map(|r| r.to_string()).collect(),
+            ..Zone::new("public")
+        }
+    }
+
+    #[test]
+    fn test_zone_allows() {
+        let public = zone("default", &["8080/tcp", "6000-6100/udp"], &[]);
+        assert!(zone_allows(&public, "8080", "tcp"));
+        assert!(!zone_allows(&public, "8080", "udp"));
+        assert!(zone_allows(&public, "6010-6020", "udp"));
+        assert!(!zone_allows(&public, "6050-6200", "udp"));
+        assert!(!zone_allows(&public, "22", "tcp"));
+
+        let blocked = zone(
+            "default",
+            &["8080/tcp"],
+            &[r#"rule port port="8080" protocol="tcp" reject"#],
+        );
+        assert!(!zone_allows(&blocked, "8080", "tcp"));
+        // A block for one address leaves the port open to the rest
+        let one_source = zone(
+            "default",
+            &["8080/tcp"],
+            &[
+                r#"rule family="ipv4" source address="203.0.113.7" port port="8080" protocol="tcp" drop"#,
+            ],
+        );
+        assert!(zone_allows(&one_source, "8080", "tcp"));
+        assert!(zone_allows(&zone("ACCEPT", &[], &[]), "443", "tcp"));
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut probe = RuleProbe {
+            expected: Expectation::Open,
+            confirmed: true,
+            interfaces: vec!["eth0".to_string(), "wlan0".to_string()],
+            listening: Some(false),
+        };
+        assert_eq!(
+            probe.summary(),
+            "verified open on eth0, wlan0; nothing listens on it yet"
+        );
+        probe.expected = Expectation::Closed;
+        probe.interfaces.clear();
+        assert_eq!(
+            probe.summary(),
+            "verified closed, but no interface uses the zone"
+        );
+        probe.confirmed = false;
+        assert_eq!(probe.summary(), "not yet in effect in the running firewall");
+    }
+}
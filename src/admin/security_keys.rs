@@ -0,0 +1,266 @@
+// Security Center - Hardware Security Keys
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! FIDO2/U2F security keys and locking the session when one is pulled out.
+//!
+//! Security keys are HID devices whose report descriptor declares the FIDO
+//! Alliance usage page (`0xF1D0`). Every HID device has a node under
+//! `/sys/class/hidraw`, readable without privileges, so
+//! [`query_security_keys`] lists the keys attached right now. A
+//! [`KeyRemovalWatch`] compares successive listings and reports keys that
+//! went away; [`lock_sessions`] then asks logind to lock the user's
+//! sessions, as a key taken along with its owner means they left the desk.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use zbus::blocking::Connection;
+use zbus::zvariant::OwnedObjectPath;
+
+const HIDRAW_CLASS_DIR: &str = "/sys/class/hidraw";
+const LOGIN1_BUS: &str = "org.freedesktop.login1";
+const LOGIN1_PATH: &str = "/org/freedesktop/login1";
+const LOGIN1_MANAGER: &str = "org.freedesktop.login1.Manager";
+const LOGIN1_SESSION: &str = "org.freedesktop.login1.Session";
+
+/// Usage page the FIDO Alliance registered for authenticators.
+const FIDO_USAGE_PAGE: u32 = 0xF1D0;
+
+/// An attached FIDO2/U2F security key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityKey {
+    /// hidraw node, e.g. `hidraw3`.
+    pub device: String,
+    /// Product name, e.g. `Yubico YubiKey OTP+FIDO+CCID`.
+    pub name: String,
+    /// Bus, vendor and product (`HID_ID`) with the serial number, when the
+    /// key reports one; the same across replugs.
+    pub id: String,
+}
+
+/// Whether a HID report descriptor declares the FIDO usage page.
+pub fn is_fido_descriptor(descriptor: &[u8]) -> bool {
+    let mut rest = descriptor;
+    while let Some(&prefix) = rest.first() {
+        // Long items: 0xFE, data size, long tag, data
+        if prefix == 0xFE {
+            let size = rest.get(1).copied().unwrap_or(0) as usize;
+            rest = rest.get(3 + size..).unwrap_or_default();
+            continue;
+        }
+        let size = match prefix & 0x03 {
+            3 => 4,
+            n => n as usize,
+        };
+        let Some(data) = rest.get(1..1 + size) else {
+            break;
+        };
+        // Usage Page, a global item
+        if prefix & 0xFC == 0x04 {
+            let value = data
+                .iter()
+                .rev()
+                .fold(0u32, |value, byte| (value << 8) | *byte as u32);
+            if value == FIDO_USAGE_PAGE {
+                return true;
+            }
+        }
+        rest = &rest[1 + size..];
+    }
+    false
+}
+
+/// Name and id of a HID device from its `uevent` file.
+pub fn parse_hid_uevent(uevent: &str) -> (String, String) {
+    let mut name = String::new();
+    let mut hid_id = String::new();
+    let mut serial = String::new();
+    for line in uevent.lines() {
+        match line.split_once('=') {
+            Some(("HID_NAME", value)) => name = value.trim().to_string(),
+            Some(("HID_ID", value)) => hid_id = value.trim().to_string(),
+            Some(("HID_UNIQ", value)) => serial = value.trim().to_string(),
+            _ => {}
+        }
+    }
+    let id = if serial.is_empty() {
+        hid_id
+    } else {
+        format!("{}:{}", hid_id, serial)
+    };
+    (name, id)
+}
+
+/// Security keys among the hidraw nodes in `class_dir`, by node name.
+pub fn list_security_keys_in(class_dir: &Path) -> Vec<SecurityKey> {
+    let Ok(entries) = fs::read_dir(class_dir) else {
+        return Vec::new();
+    };
+    let mut keys: Vec<SecurityKey> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let device_dir = entry.path().join("device");
+            let descriptor = fs::read(device_dir.join("report_descriptor")).ok()?;
+            if !is_fido_descriptor(&descriptor) {
+                return None;
+            }
+            let uevent = fs::read_to_string(device_dir.join("uevent")).unwrap_or_default();
+            let (name, id) = parse_hid_uevent(&uevent);
+            let device = entry.file_name().to_string_lossy().into_owned();
+            Some(SecurityKey {
+                name: if name.is_empty() {
+                    device.clone()
+                } else {
+                    name
+                },
+                id: if id.is_empty() { device.clone() } else { id },
+                device,
+            })
+        })
+        .collect();
+    keys.sort_by(|a, b| a.device.cmp(&b.device));
+    keys
+}
+
+/// The security keys attached now.
+pub fn query_security_keys() -> Vec<SecurityKey> {
+    list_security_keys_in(Path::new(HIDRAW_CLASS_DIR))
+}
+
+/// Keys seen in the previous listing, to tell which were removed.
+#[derive(Debug, Default)]
+pub struct KeyRemovalWatch {
+    present: BTreeMap<String, SecurityKey>,
+}
+
+impl KeyRemovalWatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take the keys attached now and return those attached last time that
+    /// are gone.
+    pub fn update(&mut self, keys: &[SecurityKey]) -> Vec<SecurityKey> {
+        let current: BTreeMap<String, SecurityKey> = keys
+            .iter()
+            .map(|key| (key.id.clone(), key.clone()))
+            .collect();
+        let removed = self
+            .present
+            .iter()
+            .filter(|(id, _)| !current.contains_key(*id))
+            .map(|(_, key)| key.clone())
+            .collect();
+        self.present = current;
+        removed
+    }
+
+    /// Forget the keys seen, so a removal while not watching goes unnoticed.
+    pub fn reset(&mut self) {
+        self.present.clear();
+    }
+}
+
+/// Lock every session of the current user through logind, returning how
+/// many were locked. Users may lock their own sessions without
+/// authentication.
+pub fn lock_sessions() -> Result<usize> {
+    let conn = Connection::system().context("Failed to connect to system D-Bus")?;
+    let sessions: Vec<(String, u32, String, String, OwnedObjectPath)> = conn
+        .call_method(
+            Some(LOGIN1_BUS),
+            LOGIN1_PATH,
+            Some(LOGIN1_MANAGER),
+            "ListSessions",
+            &(),
+        )
+        .context("Cannot list the login sessions")?
+        .body()
+        .deserialize()?;
+    // SAFETY: getuid cannot fail
+    let uid = unsafe { libc::getuid() };
+    let mut locked = 0;
+    for (id, _, _, _, path) in sessions.iter().filter(|session| session.1 == uid) {
+        match conn.call_method(
+            Some(LOGIN1_BUS),
+            path.as_str(),
+            Some(LOGIN1_SESSION),
+            "Lock",
+            &(),
+        ) {
+            Ok(_) => locked += 1,
+            Err(e) => tracing::warn!("Cannot lock session {}: {}", id, e),
+        }
+    }
+    Ok(locked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Start of a YubiKey's FIDO interface descriptor.
+    const FIDO_DESCRIPTOR: &[u8] = &[
+        0x06, 0xD0, 0xF1, 0x09, 0x01, 0xA1, 0x01, 0x09, 0x20, 0x15, 0x00, 0x26, 0xFF, 0x00,
+    ];
+    /// Start of a keyboard's descriptor.
+    const KEYBOARD_DESCRIPTOR: &[u8] = &[0x05, 0x01, 0x09, 0x06, 0xA1, 0x01, 0x05, 0x07];
+
+    #[test]
+    fn test_is_fido_descriptor() {
+        assert!(is_fido_descriptor(FIDO_DESCRIPTOR));
+        assert!(!is_fido_descriptor(KEYBOARD_DESCRIPTOR));
+        assert!(!is_fido_descriptor(&[]));
+        // Truncated item
+        assert!(!is_fido_descriptor(&[0x06, 0xD0]));
+        // 0xF1D0 as data of another item is not a usage page
+        assert!(!is_fido_descriptor(&[0x0A, 0xD0, 0xF1]));
+    }
+
+    #[test]
+    fn test_list_and_watch_keys() {
+        let dir =
+            std::env::temp_dir().join(format!("security-center-hidraw-{}", std::process::id()));
+        let add = |node: &str, descriptor: &[u8], uevent: &str| {
+            let device = dir.join(node).join("device");
+            fs::create_dir_all(&device).unwrap();
+            fs::write(device.join("report_descriptor"), descriptor).unwrap();
+            fs::write(device.join("uevent"), uevent).unwrap();
+        };
+        add(
+            "hidraw1",
+            FIDO_DESCRIPTOR,
+            "HID_ID=0003:00001050:00000407\nHID_NAME=Yubico YubiKey OTP+FIDO+CCID\nHID_UNIQ=\n",
+        );
+        add(
+            "hidraw0",
+            KEYBOARD_DESCRIPTOR,
+            "HID_ID=0003:0000046D:0000C31C\nHID_NAME=Logitech USB Keyboard\n",
+        );
+        let keys = list_security_keys_in(&dir);
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].device, "hidraw1");
+        assert_eq!(keys[0].name, "Yubico YubiKey OTP+FIDO+CCID");
+        assert_eq!(keys[0].id, "0003:00001050:00000407");
+
+        let mut watch = KeyRemovalWatch::new();
+        assert!(watch.update(&keys).is_empty());
+        assert!(watch.update(&keys).is_empty());
+        let removed = watch.update(&[]);
+        assert_eq!(removed, keys);
+        assert!(watch.update(&[]).is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_parse_hid_uevent() {
+        let (name, id) = parse_hid_uevent(
+            "DRIVER=hid-generic\nHID_ID=0003:000020A0:000042B1\nHID_NAME=Nitrokey 3\nHID_UNIQ=A1B2\n",
+        );
+        assert_eq!(name, "Nitrokey 3");
+        assert_eq!(id, "0003:000020A0:000042B1:A1B2");
+    }
+}
@@ -0,0 +1,449 @@
+// Security Center - Source Address History
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! What the system decided about one remote address: SSH logins it failed
+//! or passed and fail2ban bans, from the system journal, plus a summary of
+//! its packets in the firewall's denied log. Also the quick actions on an
+//! address: blocking it, trusting it and removing the rules about it.
+//!
+//! Like the denied packets, the journal can only be read with membership in
+//! a group allowed to read the system journal (wheel, adm or
+//! systemd-journal); without it the history is empty.
+
+use std::collections::BTreeSet;
+use std::net::IpAddr;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use tracing::{info, warn};
+
+use super::DeniedPacket;
+use crate::firewall::{self, RuleKind, RuleMatch};
+use crate::models::RichRule;
+
+/// Journal entries read back, newest first, before filtering by address.
+const JOURNAL_LINES: &str = "20000";
+/// How far back the journal is read.
+const JOURNAL_SINCE: &str = "-30d";
+/// Units whose messages record authentication decisions. `sshd@*` covers
+/// socket-activated SSH.
+const AUTH_UNITS: [&str; 4] = [
+    "sshd.service",
+    "ssh.service",
+    "sshd@*.service",
+    "fail2ban.service",
+];
+/// Events kept per address.
+const MAX_EVENTS: usize = 200;
+/// Zone that accepts all traffic from its sources.
+pub const TRUSTED_ZONE: &str = "trusted";
+
+/// An authentication decision about an address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthEventKind {
+    /// A rejected SSH login, as the named user.
+    FailedLogin {
+        user: String,
+    },
+    /// A successful SSH login, as the named user.
+    AcceptedLogin {
+        user: String,
+    },
+    /// fail2ban banned the address in the named jail.
+    Banned {
+        jail: String,
+    },
+    Unbanned {
+        jail: String,
+    },
+}
+
+/// An authentication decision and when it was logged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthEvent {
+    /// Unix time in seconds.
+    pub time: u64,
+    pub kind: AuthEventKind,
+}
+
+/// Authentication decisions about one address, newest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceHistory {
+    pub events: Vec<AuthEvent>,
+}
+
+impl SourceHistory {
+    pub fn failed_logins(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|e| matches!(e.kind, AuthEventKind::FailedLogin { .. }))
+            .count()
+    }
+
+    pub fn accepted_logins(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|e| matches!(e.kind, AuthEventKind::AcceptedLogin { .. }))
+            .count()
+    }
+
+    pub fn bans(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|e| matches!(e.kind, AuthEventKind::Banned { .. }))
+            .count()
+    }
+
+    /// Jails the address is still banned in: its latest event there is a ban.
+    pub fn banned_in(&self) -> Vec<String> {
+        let mut seen = BTreeSet::new();
+        let mut banned = Vec::new();
+        for event in &self.events {
+            let (jail, is_ban) = match &event.kind {
+                AuthEventKind::Banned { jail } => (jail, true),
+                AuthEventKind::Unbanned { jail } => (jail, false),
+                _ => continue,
+            };
+            if seen.insert(jail.clone()) && is_ban {
+                banned.push(jail.clone());
+            }
+        }
+        banned
+    }
+}
+
+/// Parse an sshd or fail2ban message into the address it is about and the
+/// decision taken.
+pub fn parse_auth_message(message: &str) -> Option<(IpAddr, AuthEventKind)> {
+    let words: Vec<&str> = message.split_whitespace().collect();
+
+    // sshd: "Failed password for [invalid user] NAME from ADDR port N ssh2"
+    if let Some(start) = words
+        .iter()
+        .position(|w| *w == "Failed" || *w == "Accepted")
+    {
+        let rest = &words[start..];
+        if rest.get(2) == Some(&"for") {
+            let from = rest.iter().position(|w| *w == "from")?;
+            let address: IpAddr = rest.get(from + 1)?.parse().ok()?;
+            let user_words = &rest[3..from];
+            let user_words = match user_words {
+                ["invalid", "user", name @ ..] => name,
+                other => other,
+            };
+            let user = user_words.join(" ");
+            let kind = if rest[0] == "Failed" {
+                AuthEventKind::FailedLogin { user }
+            } else {
+                AuthEventKind::AcceptedLogin { user }
+            };
+            return Some((address, kind));
+        }
+    }
+
+    // fail2ban: "... NOTICE  [sshd] Ban ADDR", also "Restore Ban" and "Unban"
+    let action = words.iter().position(|w| *w == "Ban" || *w == "Unban")?;
+    let address: IpAddr = words.get(action + 1)?.parse().ok()?;
+    let jail_index = match words.get(action.checked_sub(1)?) {
+        Some(&"Restore") => action.checked_sub(2)?,
+        _ => action - 1,
+    };
+    let jail = words
+        .get(jail_index)?
+        .strip_prefix('[')?
+        .strip_suffix(']')?
+        .to_string();
+    let kind = if words[action] == "Ban" {
+        AuthEventKind::Banned { jail }
+    } else {
+        AuthEventKind::Unbanned { jail }
+    };
+    Some((address, kind))
+}
+
+/// Parse one `journalctl -o json` line into an event about any address.
+fn parse_journal_entry(line: &str) -> Option<(IpAddr, AuthEvent)> {
+    let entry: serde_json::Value = serde_json::from_str(line).ok()?;
+    let (address, kind) = parse_auth_message(entry.get("MESSAGE")?.as_str()?)?;
+    let micros: u64 = entry.get("__REALTIME_TIMESTAMP")?.as_str()?.parse().ok()?;
+    Some((
+        address,
+        AuthEvent {
+            time: micros / 1_000_000,
+            kind,
+        },
+    ))
+}
+
+/// Read the authentication decisions about `address` from the last 30 days
+/// of the journal. Blocks on `journalctl`, so call it off the main thread.
+pub fn query_source_history(address: IpAddr) -> Result<SourceHistory> {
+    let mut command = Command::new("journalctl");
+    command.args(["-o", "json", "--no-pager", "-r", "-n", JOURNAL_LINES]);
+    command.args(["--since", JOURNAL_SINCE]);
+    for unit in AUTH_UNITS {
+        command.args(["-u", unit]);
+    }
+    let output = command.output().context("Failed to run journalctl")?;
+
+    let events = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_journal_entry)
+        .filter(|(a, _)| *a == address)
+        .map(|(_, event)| event)
+        .take(MAX_EVENTS)
+        .collect();
+    Ok(SourceHistory { events })
+}
+
+/// Rich rule dropping everything from `source`.
+pub fn block_rule(source: IpAddr) -> String {
+    RichRule::source_drop(source).to_string()
+}
+
+/// Drop all traffic from `source` in the default zone, at runtime and
+/// permanently. Returns the zone and whether saving the rule permanently
+/// failed, which leaves the block in place until the next reload.
+pub fn block_source(source: IpAddr) -> Result<(String, bool)> {
+    let (zone, outcome) = firewall::call_blocking(move |client| -> Result<_> {
+        client.connect().context("Failed to connect to firewalld")?;
+        let zone = client.get_default_zone()?;
+        let outcome = client.add_rich_rule(&zone, &block_rule(source), true)?;
+        Ok((zone, outcome))
+    })??;
+    if outcome.failed() {
+        warn!("Blocked {} in zone {} for this session only", source, zone);
+    } else {
+        info!("Blocked {} in zone {}", source, zone);
+    }
+    Ok((zone, outcome.failed()))
+}
+
+/// Accept all traffic from `source` by binding it to the trusted zone, at
+/// runtime and permanently. Returns whether saving permanently failed.
+pub fn trust_source(source: IpAddr) -> Result<bool> {
+    let outcome = firewall::call_blocking(move |client| {
+        client.connect().context("Failed to connect to firewalld")?;
+        client.add_source(TRUSTED_ZONE, &source.to_string(), true)
+    })??;
+    info!("Trusted {}", source);
+    Ok(outcome.failed())
+}
+
+/// Remove a zone source or rich rule found by
+/// [`rules_for_address`](crate::firewall::rules_for_address), at runtime and
+/// permanently. Returns whether removing it permanently failed.
+pub fn remove_address_rule(found: &RuleMatch) -> Result<bool> {
+    let found = found.clone();
+    let outcome = firewall::call_blocking(move |client| {
+        client.connect().context("Failed to connect to firewalld")?;
+        match found.kind {
+            RuleKind::Source => client.remove_source(&found.zone, &found.entry, true),
+            RuleKind::RichRule => client.remove_rich_rule(&found.zone, &found.entry, true),
+            kind => bail!("Cannot remove a {:?} entry for an address", kind),
+        }
+    })??;
+    Ok(outcome.failed())
+}
+
+/// The packets from one address in the denied log.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeniedSummary {
+    pub count: usize,
+    /// Unix time of the newest packet.
+    pub last_seen: Option<u64>,
+    /// Destination ports the address tried.
+    pub ports: BTreeSet<u16>,
+}
+
+impl DeniedSummary {
+    pub fn from_packets(packets: &[DeniedPacket], address: IpAddr) -> Self {
+        let mut summary = Self::default();
+        for packet in packets {
+            if packet.source.parse::<IpAddr>().ok() != Some(address) {
+                continue;
+            }
+            summary.count += 1;
+            summary.last_seen = summary.last_seen.max(Some(packet.time));
+            summary.ports.extend(packet.port);
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(text: &str) -> IpAddr {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_ssh_messages() {
+        assert_eq!(
+            parse_auth_message("Failed password for root from 203.0.113.7 port 4242 ssh2"),
+            Some((
+                addr("203.0.113.7"),
+                AuthEventKind::FailedLogin {
+                    user: "root".to_string()
+                }
+            ))
+        );
+        assert_eq!(
+            parse_auth_message(
+                "Failed password for invalid user admin from 2001:db8::1 port 4242 ssh2"
+            ),
+            Some((
+                addr("2001:db8::1"),
+                AuthEventKind::FailedLogin {
+                    user: "admin".to_string()
+                }
+            ))
+        );
+        assert_eq!(
+            parse_auth_message(
+                "Accepted publickey for chris from 192.168.1.5 port 50000 ssh2: ED25519 SHA256:abc"
+            ),
+            Some((
+                addr("192.168.1.5"),
+                AuthEventKind::AcceptedLogin {
+                    user: "chris".to_string()
+                }
+            ))
+        );
+        assert_eq!(
+            parse_auth_message("Failed password for root from somewhere port 22"),
+            None
+        );
+        assert_eq!(
+            parse_auth_message("Connection closed by 203.0.113.7 port 4242 [preauth]"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_fail2ban_messages() {
+        assert_eq!(
+            parse_auth_message("fail2ban.actions        [812]: NOTICE  [sshd] Ban 203.0.113.7"),
+            Some((
+                addr("203.0.113.7"),
+                AuthEventKind::Banned {
+                    jail: "sshd".to_string()
+                }
+            ))
+        );
+        assert_eq!(
+            parse_auth_message("NOTICE  [sshd] Restore Ban 203.0.113.7"),
+            Some((
+                addr("203.0.113.7"),
+                AuthEventKind::Banned {
+                    jail: "sshd".to_string()
+                }
+            ))
+        );
+        assert_eq!(
+            parse_auth_message("NOTICE  [recidive] Unban 203.0.113.7"),
+            Some((
+                addr("203.0.113.7"),
+                AuthEventKind::Unbanned {
+                    jail: "recidive".to_string()
+                }
+            ))
+        );
+        assert_eq!(parse_auth_message("Ban 203.0.113.7"), None);
+        assert_eq!(parse_auth_message("INFO [sshd] Found 203.0.113.7"), None);
+    }
+
+    #[test]
+    fn test_parse_journal_entry() {
+        let line = r#"{"MESSAGE":"NOTICE  [sshd] Ban 203.0.113.7","__REALTIME_TIMESTAMP":"1760000000123456"}"#;
+        let (address, event) = parse_journal_entry(line).unwrap();
+        assert_eq!(address, addr("203.0.113.7"));
+        assert_eq!(event.time, 1_760_000_000);
+        assert!(parse_journal_entry(r#"{"MESSAGE":"Started OpenSSH"}"#).is_none());
+    }
+
+    #[test]
+    fn test_history_summary() {
+        let event = |time, kind| AuthEvent { time, kind };
+        let history = SourceHistory {
+            events: vec![
+                event(
+                    40,
+                    AuthEventKind::Unbanned {
+                        jail: "recidive".to_string(),
+                    },
+                ),
+                event(
+                    30,
+                    AuthEventKind::Banned {
+                        jail: "sshd".to_string(),
+                    },
+                ),
+                event(
+                    20,
+                    AuthEventKind::Banned {
+                        jail: "recidive".to_string(),
+                    },
+                ),
+                event(
+                    10,
+                    AuthEventKind::FailedLogin {
+                        user: "root".to_string(),
+                    },
+                ),
+                event(
+                    5,
+                    AuthEventKind::AcceptedLogin {
+                        user: "chris".to_string(),
+                    },
+                ),
+            ],
+        };
+        assert_eq!(history.failed_logins(), 1);
+        assert_eq!(history.accepted_logins(), 1);
+        assert_eq!(history.bans(), 2);
+        assert_eq!(history.banned_in(), vec!["sshd".to_string()]);
+    }
+
+    #[test]
+    fn test_denied_summary() {
+        let packet = |source: &str, port, time| DeniedPacket {
+            time,
+            source: source.to_string(),
+            port,
+            ..Default::default()
+        };
+        let packets = [
+            packet("203.0.113.7", Some(23), 100),
+            packet("203.0.113.7", Some(3389), 300),
+            packet("203.0.113.7", None, 200),
+            packet("198.51.100.1", Some(22), 400),
+        ];
+        let summary = DeniedSummary::from_packets(&packets, addr("203.0.113.7"));
+        assert_eq!(summary.count, 3);
+        assert_eq!(summary.last_seen, Some(300));
+        assert_eq!(
+            summary.ports.into_iter().collect::<Vec<_>>(),
+            vec![23, 3389]
+        );
+        assert_eq!(
+            DeniedSummary::from_packets(&packets, addr("192.0.2.1")),
+            DeniedSummary::default()
+        );
+    }
+
+    #[test]
+    fn test_block_rule() {
+        assert_eq!(
+            block_rule(addr("203.0.113.7")),
+            "rule family=\"ipv4\" source address=\"203.0.113.7\" drop"
+        );
+        assert_eq!(
+            block_rule(addr("2001:db8::1")),
+            "rule family=\"ipv6\" source address=\"2001:db8::1\" drop"
+        );
+    }
+}
@@ -0,0 +1,319 @@
+// Security Center - Time Synchronization Status
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Time synchronization (NTP/NTS) status.
+//!
+//! Clock skew breaks TLS certificate validation and makes log timelines
+//! unreliable, so the app reports whether the clock is synchronized, which
+//! daemon keeps it in sync and which servers it uses.
+//!
+//! State comes from `org.freedesktop.timedate1` and, for systemd-timesyncd,
+//! `org.freedesktop.timesync1`. chrony has no D-Bus API, so its sources are
+//! read from its configuration files; it is also the only common daemon that
+//! supports Network Time Security (NTS).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use zbus::blocking::Connection;
+use zbus::zvariant::OwnedValue;
+
+use crate::systemd::{ServiceState, SystemdClient};
+
+const TIMEDATE_BUS: &str = "org.freedesktop.timedate1";
+const TIMEDATE_PATH: &str = "/org/freedesktop/timedate1";
+const TIMESYNC_BUS: &str = "org.freedesktop.timesync1";
+const TIMESYNC_PATH: &str = "/org/freedesktop/timesync1";
+const TIMESYNC_MANAGER: &str = "org.freedesktop.timesync1.Manager";
+
+const CHRONY_CONFIGS: [&str; 2] = ["/etc/chrony.conf", "/etc/chrony/chrony.conf"];
+
+/// systemd unit started by the "enable time synchronization" quick fix.
+pub const TIMESYNCD_UNIT: &str = "systemd-timesyncd.service";
+
+/// Daemon responsible for keeping the clock in sync.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeDaemon {
+    Timesyncd,
+    Chrony,
+    Ntpd,
+    None,
+}
+
+impl TimeDaemon {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimeDaemon::Timesyncd => "systemd-timesyncd",
+            TimeDaemon::Chrony => "chrony",
+            TimeDaemon::Ntpd => "ntpd",
+            TimeDaemon::None => "None",
+        }
+    }
+
+    /// Whether the daemon can authenticate servers with NTS.
+    pub fn supports_nts(&self) -> bool {
+        matches!(self, TimeDaemon::Chrony)
+    }
+}
+
+/// A configured time source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NtpServer {
+    pub host: String,
+    /// The source is authenticated with Network Time Security.
+    pub nts: bool,
+}
+
+/// Snapshot of the system's time synchronization state.
+#[derive(Debug, Clone)]
+pub struct TimeSyncStatus {
+    /// Network time synchronization is switched on (`timedatectl set-ntp`).
+    pub ntp_enabled: bool,
+    /// The kernel reports the clock as synchronized.
+    pub synchronized: bool,
+    /// The active time daemon.
+    pub daemon: TimeDaemon,
+    /// Configured servers (fallback servers only when nothing else is set).
+    pub servers: Vec<NtpServer>,
+    /// Server currently in use, when the daemon reports it.
+    pub current_server: Option<String>,
+}
+
+impl TimeSyncStatus {
+    /// Whether at least one configured source uses NTS.
+    pub fn nts_in_use(&self) -> bool {
+        self.servers.iter().any(|s| s.nts)
+    }
+
+    /// Whether offering the timesyncd quick fix makes sense.
+    pub fn needs_quick_fix(&self) -> bool {
+        self.daemon == TimeDaemon::None || !self.ntp_enabled
+    }
+}
+
+/// Query time synchronization state from timedated, timesyncd and chrony.
+pub fn query_time_sync() -> Result<TimeSyncStatus> {
+    let conn = Connection::system()?;
+
+    let ntp_enabled: bool =
+        get_property(&conn, TIMEDATE_BUS, TIMEDATE_PATH, TIMEDATE_BUS, "NTP").unwrap_or(false);
+    let synchronized: bool = get_property(
+        &conn,
+        TIMEDATE_BUS,
+        TIMEDATE_PATH,
+        TIMEDATE_BUS,
+        "NTPSynchronized",
+    )
+    .unwrap_or(false);
+
+    let daemon = active_daemon();
+    let (servers, current_server) = match daemon {
+        TimeDaemon::Timesyncd => timesyncd_servers(&conn),
+        TimeDaemon::Chrony => (chrony_servers(), None),
+        TimeDaemon::Ntpd | TimeDaemon::None => (Vec::new(), None),
+    };
+
+    Ok(TimeSyncStatus {
+        ntp_enabled,
+        synchronized,
+        daemon,
+        servers,
+        current_server,
+    })
+}
+
+fn get_property<T>(conn: &Connection, bus: &str, path: &str, iface: &str, name: &str) -> Result<T>
+where
+    T: TryFrom<OwnedValue>,
+    T::Error: Into<zbus::zvariant::Error>,
+{
+    let value: OwnedValue = conn
+        .call_method(
+            Some(bus),
+            path,
+            Some("org.freedesktop.DBus.Properties"),
+            "Get",
+            &(iface, name),
+        )?
+        .body()
+        .deserialize()?;
+    Ok(T::try_from(value).map_err(Into::into)?)
+}
+
+/// Find the running time daemon via systemd.
+fn active_daemon() -> TimeDaemon {
+    let mut client = SystemdClient::new();
+    if client.connect().is_err() {
+        return TimeDaemon::None;
+    }
+    let candidates = [
+        ("chronyd.service", TimeDaemon::Chrony),
+        ("chrony.service", TimeDaemon::Chrony),
+        (TIMESYNCD_UNIT, TimeDaemon::Timesyncd),
+        ("ntpd.service", TimeDaemon::Ntpd),
+        ("ntpsec.service", TimeDaemon::Ntpd),
+    ];
+    candidates
+        .into_iter()
+        .find(|(unit, _)| {
+            client
+                .get_service_info(unit)
+                .is_ok_and(|info| info.state == ServiceState::Running)
+        })
+        .map(|(_, daemon)| daemon)
+        .unwrap_or(TimeDaemon::None)
+}
+
+/// Servers from timesyncd, in its own order of preference.
+fn timesyncd_servers(conn: &Connection) -> (Vec<NtpServer>, Option<String>) {
+    let list = |name: &str| -> Vec<String> {
+        get_property(conn, TIMESYNC_BUS, TIMESYNC_PATH, TIMESYNC_MANAGER, name).unwrap_or_default()
+    };
+
+    let mut hosts: Vec<String> = Vec::new();
+    for name in ["RuntimeNTPServers", "LinkNTPServers", "SystemNTPServers"] {
+        for host in list(name) {
+            if !hosts.contains(&host) {
+                hosts.push(host);
+            }
+        }
+    }
+    if hosts.is_empty() {
+        hosts = list("FallbackNTPServers");
+    }
+
+    let current = get_property::<String>(
+        conn,
+        TIMESYNC_BUS,
+        TIMESYNC_PATH,
+        TIMESYNC_MANAGER,
+        "ServerName",
+    )
+    .ok()
+    .filter(|s| !s.is_empty());
+
+    let servers = hosts
+        .into_iter()
+        .map(|host| NtpServer { host, nts: false })
+        .collect();
+    (servers, current)
+}
+
+/// Servers from the chrony configuration, including `sourcedir`/`confdir`.
+fn chrony_servers() -> Vec<NtpServer> {
+    let Some(main) = CHRONY_CONFIGS.iter().map(Path::new).find(|p| p.exists()) else {
+        return Vec::new();
+    };
+    let Ok(conf) = fs::read_to_string(main) else {
+        return Vec::new();
+    };
+
+    let mut servers = parse_chrony_sources(&conf);
+    for (dir, ext) in chrony_include_dirs(&conf) {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut files: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().and_then(|e| e.to_str()) == Some(ext))
+            .collect();
+        files.sort();
+        for file in files {
+            if let Ok(text) = fs::read_to_string(&file) {
+                servers.extend(parse_chrony_sources(&text));
+            }
+        }
+    }
+    servers
+}
+
+/// Parse `server`/`pool`/`peer` directives from chrony configuration text.
+fn parse_chrony_sources(conf: &str) -> Vec<NtpServer> {
+    conf.lines()
+        .map(|line| line.split(['#', ';']).next().unwrap_or("").trim())
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            match words.next()? {
+                "server" | "pool" | "peer" => {}
+                _ => return None,
+            }
+            let host = words.next()?.to_string();
+            let nts = words.any(|w| w == "nts");
+            Some(NtpServer { host, nts })
+        })
+        .collect()
+}
+
+/// Directories chrony reads additional sources from, with the file extension
+/// it expects there.
+fn chrony_include_dirs(conf: &str) -> Vec<(PathBuf, &'static str)> {
+    conf.lines()
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            let ext = match words.next()? {
+                "sourcedir" => "sources",
+                "confdir" => "conf",
+                _ => return None,
+            };
+            Some(
+                words
+                    .map(move |d| (PathBuf::from(d), ext))
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chrony_sources() {
+        let conf = "\
+# Use public servers from the pool.ntp.org project.
+pool 2.fedora.pool.ntp.org iburst
+server time.cloudflare.com iburst nts
+  server ntp.example.org nts # comment
+#server disabled.example.org
+driftfile /var/lib/chrony/drift
+";
+        let servers = parse_chrony_sources(conf);
+        assert_eq!(servers.len(), 3);
+        assert_eq!(servers[0].host, "2.fedora.pool.ntp.org");
+        assert!(!servers[0].nts);
+        assert!(servers[1].nts);
+        assert!(servers[2].nts);
+    }
+
+    #[test]
+    fn test_chrony_include_dirs() {
+        let conf = "sourcedir /run/chrony-dhcp /etc/chrony/sources.d\nconfdir /etc/chrony/conf.d\n";
+        let dirs = chrony_include_dirs(conf);
+        assert_eq!(dirs.len(), 3);
+        assert_eq!(dirs[1], (PathBuf::from("/etc/chrony/sources.d"), "sources"));
+        assert_eq!(dirs[2].1, "conf");
+    }
+
+    #[test]
+    fn test_quick_fix_and_nts() {
+        let mut status = TimeSyncStatus {
+            ntp_enabled: true,
+            synchronized: true,
+            daemon: TimeDaemon::Chrony,
+            servers: vec![NtpServer {
+                host: "time.cloudflare.com".into(),
+                nts: true,
+            }],
+            current_server: None,
+        };
+        assert!(status.nts_in_use());
+        assert!(!status.needs_quick_fix());
+        status.daemon = TimeDaemon::None;
+        assert!(status.needs_quick_fix());
+    }
+}
@@ -0,0 +1,237 @@
+// Security Center - TPM and Measured Boot Status
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! TPM presence and measured boot indicators.
+//!
+//! Everything is read from sysfs/securityfs, which the kernel exposes to
+//! unprivileged users: the TPM character device class under `/sys/class/tpm`,
+//! its PCR banks (`pcr-<hash>/<index>`, kernel 5.12+), the firmware event log
+//! and the IMA interface under `/sys/kernel/security`.
+
+use std::fs;
+use std::path::Path;
+
+const TPM_CLASS_DIR: &str = "/sys/class/tpm";
+const SECURITYFS_DIR: &str = "/sys/kernel/security";
+const KERNEL_CMDLINE: &str = "/proc/cmdline";
+
+/// A TPM device found in sysfs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TpmDevice {
+    /// Device name, e.g. `tpm0`.
+    pub name: String,
+    /// Major specification version (1 or 2), when the kernel reports it.
+    pub version: Option<u32>,
+    /// Firmware description of the device, when available.
+    pub description: Option<String>,
+}
+
+impl TpmDevice {
+    pub fn is_tpm2(&self) -> bool {
+        self.version == Some(2)
+    }
+}
+
+/// One PCR bank (hash algorithm) with its register values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PcrBank {
+    /// Hash algorithm, e.g. `sha256`.
+    pub algorithm: String,
+    /// (index, hex digest) pairs in index order.
+    pub values: Vec<(u32, String)>,
+}
+
+/// TPM and measured boot state.
+#[derive(Debug, Clone, Default)]
+pub struct TpmStatus {
+    /// First TPM device, if any.
+    pub device: Option<TpmDevice>,
+    /// The firmware measured the boot chain into the TPM (event log present).
+    pub boot_event_log: bool,
+    /// The kernel's Integrity Measurement Architecture is active.
+    pub ima_active: bool,
+    /// IMA-related kernel command line options (`ima_policy=...` etc.).
+    pub ima_options: Vec<String>,
+}
+
+impl TpmStatus {
+    /// Measured boot is in effect when a TPM exists and the firmware logged
+    /// boot measurements into it.
+    pub fn measured_boot(&self) -> bool {
+        self.device.is_some() && self.boot_event_log
+    }
+}
+
+/// Read TPM presence and measured boot indicators.
+pub fn query_tpm() -> TpmStatus {
+    let device = first_tpm_device(Path::new(TPM_CLASS_DIR));
+    let securityfs = Path::new(SECURITYFS_DIR);
+    let boot_event_log = device.as_ref().is_some_and(|d| {
+        securityfs
+            .join(&d.name)
+            .join("binary_bios_measurements")
+            .exists()
+    });
+    let ima_active = securityfs.join("ima").is_dir();
+    let ima_options = fs::read_to_string(KERNEL_CMDLINE)
+        .map(|c| ima_cmdline_options(&c))
+        .unwrap_or_default();
+
+    TpmStatus {
+        device,
+        boot_event_log,
+        ima_active,
+        ima_options,
+    }
+}
+
+/// Read all PCR banks of the first TPM device.
+pub fn read_pcr_banks() -> Vec<PcrBank> {
+    let Some(device) = first_tpm_device(Path::new(TPM_CLASS_DIR)) else {
+        return Vec::new();
+    };
+    read_banks(&Path::new(TPM_CLASS_DIR).join(device.name))
+}
+
+fn first_tpm_device(class_dir: &Path) -> Option<TpmDevice> {
+    let mut names: Vec<String> = fs::read_dir(class_dir)
+        .ok()?
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        // tpmrm* are the resource-manager views of the same chips
+        .filter(|n| n.starts_with("tpm") && !n.starts_with("tpmrm"))
+        .collect();
+    names.sort();
+    let name = names.into_iter().next()?;
+    let dir = class_dir.join(&name);
+
+    let version = read_trimmed(&dir.join("tpm_version_major")).and_then(|v| v.parse().ok());
+    let description = read_trimmed(&dir.join("device/description"))
+        .or_else(|| read_trimmed(&dir.join("device/firmware_node/description")));
+
+    Some(TpmDevice {
+        name,
+        version,
+        description,
+    })
+}
+
+fn read_banks(device_dir: &Path) -> Vec<PcrBank> {
+    let Ok(entries) = fs::read_dir(device_dir) else {
+        return Vec::new();
+    };
+    let mut banks: Vec<PcrBank> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let algorithm = name.strip_prefix("pcr-")?.to_string();
+            let values = read_bank_values(&entry.path());
+            Some(PcrBank { algorithm, values })
+        })
+        .filter(|bank| !bank.values.is_empty())
+        .collect();
+    banks.sort_by(|a, b| a.algorithm.cmp(&b.algorithm));
+    banks
+}
+
+fn read_bank_values(bank_dir: &Path) -> Vec<(u32, String)> {
+    let Ok(entries) = fs::read_dir(bank_dir) else {
+        return Vec::new();
+    };
+    let mut values: Vec<(u32, String)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let index: u32 = entry.file_name().to_str()?.parse().ok()?;
+            let value = read_trimmed(&entry.path())?.to_lowercase();
+            Some((index, value))
+        })
+        .collect();
+    values.sort_by_key(|(index, _)| *index);
+    values
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// IMA/EVM options from the kernel command line.
+fn ima_cmdline_options(cmdline: &str) -> Vec<String> {
+    cmdline
+        .split_whitespace()
+        .filter(|opt| opt.starts_with("ima") || opt.starts_with("evm"))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether a PCR has never been extended (all zeros, or all ones for the
+/// locality-reset PCRs 17-22).
+pub fn pcr_is_unused(value: &str) -> bool {
+    !value.is_empty() && (value.chars().all(|c| c == '0') || value.chars().all(|c| c == 'f'))
+}
+
+/// What each PCR conventionally measures on PC platforms.
+pub fn pcr_purpose(index: u32) -> &'static str {
+    match index {
+        0 => "Firmware code",
+        1 => "Firmware configuration",
+        2 => "Option ROM code",
+        3 => "Option ROM configuration",
+        4 => "Boot loader code",
+        5 => "Boot loader configuration / GPT",
+        6 => "Platform-specific events",
+        7 => "Secure Boot state",
+        8 => "Kernel command line (GRUB)",
+        9 => "Kernel and initrd (GRUB)",
+        10 => "IMA measurements",
+        11 => "Unified kernel image (systemd)",
+        12 => "Kernel command line (systemd-stub)",
+        13 => "System extensions (systemd-stub)",
+        14 => "Shim / MOK certificates",
+        15 => "System identity (systemd)",
+        16 => "Debug",
+        23 => "Application support",
+        _ => "Dynamic / locality-reset",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ima_cmdline_options() {
+        let cmdline = "BOOT_IMAGE=/vmlinuz root=UUID=x ro ima_policy=tcb ima_appraise=fix quiet\n";
+        assert_eq!(
+            ima_cmdline_options(cmdline),
+            vec!["ima_policy=tcb".to_string(), "ima_appraise=fix".to_string()]
+        );
+        assert!(ima_cmdline_options("root=/dev/sda1 quiet").is_empty());
+    }
+
+    #[test]
+    fn test_pcr_is_unused() {
+        assert!(pcr_is_unused(&"0".repeat(64)));
+        assert!(pcr_is_unused(&"f".repeat(64)));
+        assert!(!pcr_is_unused("a3f1"));
+        assert!(!pcr_is_unused(""));
+    }
+
+    #[test]
+    fn test_measured_boot_requires_tpm() {
+        let mut status = TpmStatus {
+            boot_event_log: true,
+            ..Default::default()
+        };
+        assert!(!status.measured_boot());
+        status.device = Some(TpmDevice {
+            name: "tpm0".into(),
+            version: Some(2),
+            description: None,
+        });
+        assert!(status.measured_boot());
+    }
+}
@@ -0,0 +1,446 @@
+// Security Center - Travel Hardening
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! State and steps of the "Secure for Travel" wizard.
+//!
+//! [`query_travel_plan`] reads everything the wizard previews: the default
+//! zone, file-sharing daemons and the firewalld services letting them
+//! through, the NetworkManager MAC address policy, the SSH server
+//! configuration and the GNOME screen lock settings. For each
+//! [`TravelStep`] the plan lists the [`Operation`]s still needed, so a step
+//! that is already satisfied has nothing to run.
+//!
+//! MAC randomization and SSH hardening are drop-in files written by the
+//! privileged helper; the screen lock settings belong to the user and are
+//! written directly.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use gtk4::gio;
+use gtk4::prelude::*;
+
+use super::actions::Operation;
+use crate::firewall;
+use crate::systemd::{ServiceState, SystemdClient};
+
+/// Zone used on untrusted networks.
+pub const TRAVEL_ZONE: &str = "public";
+
+/// Daemons serving files to the local network.
+pub const FILE_SHARING_UNITS: &[&str] = &["smb.service", "nmb.service", "nfs-server.service"];
+
+/// firewalld services admitting file-sharing traffic.
+pub const FILE_SHARING_SERVICES: &[&str] =
+    &["samba", "samba-client", "nfs", "nfs3", "mountd", "rpc-bind"];
+
+const NM_CONF: &str = "/etc/NetworkManager/NetworkManager.conf";
+const NM_CONF_DIRS: &[&str] = &[
+    "/usr/lib/NetworkManager/conf.d",
+    "/etc/NetworkManager/conf.d",
+];
+/// Sorted after distribution snippets so its values take precedence.
+pub const MAC_DROPIN: &str = "/etc/NetworkManager/conf.d/90-security-center-mac.conf";
+/// Every network gets its own address, kept across reconnects so captive
+/// portals still recognise the machine.
+pub const MAC_DROPIN_CONTENTS: &str = "# Managed by Security Center\n\
+    [connection-security-center-mac]\n\
+    wifi.cloned-mac-address=stable\n\
+    ethernet.cloned-mac-address=stable\n";
+
+const SSHD_CONFIG: &str = "/etc/ssh/sshd_config";
+/// sshd keeps the first value it reads, so the drop-in sorts early.
+pub const SSH_DROPIN: &str = "/etc/ssh/sshd_config.d/10-security-center.conf";
+pub const SSH_DROPIN_CONTENTS: &str = "# Managed by Security Center\n\
+    PermitRootLogin no\n\
+    PermitEmptyPasswords no\n\
+    MaxAuthTries 3\n\
+    LoginGraceTime 30\n\
+    X11Forwarding no\n";
+/// sshd.service on RHEL/Fedora, ssh.service on Debian/Ubuntu.
+pub const SSH_UNITS: &[&str] = &["sshd.service", "ssh.service"];
+
+const SCREENSAVER_SCHEMA: &str = "org.gnome.desktop.screensaver";
+const SESSION_SCHEMA: &str = "org.gnome.desktop.session";
+/// Longest idle time, in seconds, before the screen blanks and locks.
+pub const MAX_IDLE_DELAY: u32 = 300;
+
+/// A step of the wizard, in the order it runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TravelStep {
+    PublicZone,
+    FileSharing,
+    MacRandomization,
+    Ssh,
+    ScreenLock,
+}
+
+impl TravelStep {
+    pub const ALL: [TravelStep; 5] = [
+        TravelStep::PublicZone,
+        TravelStep::FileSharing,
+        TravelStep::MacRandomization,
+        TravelStep::Ssh,
+        TravelStep::ScreenLock,
+    ];
+}
+
+/// GNOME screen lock settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScreenLock {
+    pub lock_enabled: bool,
+    /// Seconds between blanking and locking.
+    pub lock_delay: u32,
+    /// Seconds of inactivity before blanking; 0 never blanks.
+    pub idle_delay: u32,
+}
+
+impl ScreenLock {
+    /// The settings after the wizard: locking on, immediately, within
+    /// [`MAX_IDLE_DELAY`].
+    pub fn target(&self) -> ScreenLock {
+        ScreenLock {
+            lock_enabled: true,
+            lock_delay: 0,
+            idle_delay: match self.idle_delay {
+                0 => MAX_IDLE_DELAY,
+                delay => delay.min(MAX_IDLE_DELAY),
+            },
+        }
+    }
+}
+
+/// SSH server state relevant to hardening.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SshState {
+    /// An sshd configuration exists.
+    pub installed: bool,
+    /// `sshd_config` includes `sshd_config.d`, so a drop-in takes effect.
+    pub reads_drop_ins: bool,
+    /// The Security Center drop-in is in place.
+    pub hardened: bool,
+    /// The travel zone lets SSH in.
+    pub zone_allows_ssh: bool,
+}
+
+/// Current state behind every step of the wizard.
+#[derive(Debug, Clone, Default)]
+pub struct TravelPlan {
+    pub default_zone: String,
+    /// File-sharing units that are running or enabled.
+    pub sharing_units: Vec<&'static str>,
+    /// File-sharing services allowed in the travel zone.
+    pub sharing_services: Vec<&'static str>,
+    /// NetworkManager `wifi.cloned-mac-address` default, if set.
+    pub wifi_cloned_mac: Option<String>,
+    pub ssh: SshState,
+    /// `None` when the GNOME schemas are not installed.
+    pub screen_lock: Option<ScreenLock>,
+}
+
+impl TravelPlan {
+    /// Whether Wi-Fi connections already use a randomized address.
+    pub fn mac_randomized(&self) -> bool {
+        matches!(self.wifi_cloned_mac.as_deref(), Some("random" | "stable"))
+    }
+
+    /// Operations still needed for `step`; empty when it is already done
+    /// or cannot be applied on this system.
+    pub fn operations(&self, step: TravelStep) -> Vec<Operation> {
+        match step {
+            TravelStep::PublicZone if self.default_zone != TRAVEL_ZONE => {
+                vec![Operation::SetDefaultZone { zone: TRAVEL_ZONE }]
+            }
+            TravelStep::PublicZone => Vec::new(),
+            TravelStep::FileSharing => {
+                self.sharing_units
+                    .iter()
+                    .map(|&unit| Operation::StopAndDisableUnit { unit })
+                    .chain(self.sharing_services.iter().map(|&service| {
+                        Operation::RemoveZoneService {
+                            zone: TRAVEL_ZONE,
+                            service,
+                        }
+                    }))
+                    .collect()
+            }
+            TravelStep::MacRandomization if self.mac_randomized() => Vec::new(),
+            TravelStep::MacRandomization => vec![Operation::EnableMacRandomization],
+            TravelStep::Ssh => {
+                let mut operations = Vec::new();
+                if self.ssh.installed && self.ssh.reads_drop_ins && !self.ssh.hardened {
+                    operations.push(Operation::HardenSsh);
+                }
+                if self.ssh.zone_allows_ssh {
+                    operations.push(Operation::RemoveZoneService {
+                        zone: TRAVEL_ZONE,
+                        service: "ssh",
+                    });
+                }
+                operations
+            }
+            TravelStep::ScreenLock => match self.screen_lock {
+                Some(current) if current.target() != current => vec![Operation::EnableScreenLock],
+                _ => Vec::new(),
+            },
+        }
+    }
+}
+
+/// Read the state every step of the wizard starts from.
+pub fn query_travel_plan() -> Result<TravelPlan> {
+    let mut plan = TravelPlan::default();
+
+    let (default_zone, zone_services) = firewall::read_blocking(|client| -> Result<_> {
+        client
+            .connect()
+            .context("Cannot reach firewalld. Is it running?")?;
+        let zone_services = client
+            .get_zones()?
+            .into_iter()
+            .find(|zone| zone.name == TRAVEL_ZONE)
+            .map(|zone| zone.services)
+            .unwrap_or_default();
+        Ok((client.get_default_zone()?, zone_services))
+    })??;
+    plan.default_zone = default_zone;
+    plan.sharing_services = FILE_SHARING_SERVICES
+        .iter()
+        .copied()
+        .filter(|service| zone_services.iter().any(|s| s == service))
+        .collect();
+
+    let mut systemd = SystemdClient::new();
+    systemd.connect()?;
+    plan.sharing_units = FILE_SHARING_UNITS
+        .iter()
+        .copied()
+        .filter(|unit| {
+            systemd
+                .get_service_info(unit)
+                .is_ok_and(|info| info.state == ServiceState::Running || info.is_enabled)
+        })
+        .collect();
+
+    // Later files override earlier ones
+    plan.wifi_cloned_mac = nm_config_files().iter().rev().find_map(|path| {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| cloned_mac_address(&contents))
+    });
+
+    let sshd_config = fs::read_to_string(SSHD_CONFIG).ok();
+    plan.ssh = SshState {
+        installed: sshd_config.is_some(),
+        reads_drop_ins: sshd_config.as_deref().is_some_and(includes_drop_ins),
+        hardened: Path::new(SSH_DROPIN).exists(),
+        zone_allows_ssh: zone_services.iter().any(|s| s == "ssh"),
+    };
+
+    plan.screen_lock = query_screen_lock();
+    Ok(plan)
+}
+
+/// NetworkManager configuration files in the order they are read: the main
+/// file, then `conf.d` snippets sorted by name, `/etc` shadowing `/usr/lib`.
+fn nm_config_files() -> Vec<PathBuf> {
+    let mut snippets: Vec<(String, PathBuf)> = Vec::new();
+    for dir in NM_CONF_DIRS {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !name.ends_with(".conf") {
+                continue;
+            }
+            snippets.retain(|(n, _)| n != &name);
+            snippets.push((name, entry.path()));
+        }
+    }
+    snippets.sort();
+
+    let mut files = vec![PathBuf::from(NM_CONF)];
+    files.extend(snippets.into_iter().map(|(_, path)| path));
+    files
+}
+
+/// Last `wifi.cloned-mac-address` set in a `[connection*]` section of a
+/// NetworkManager configuration file.
+fn cloned_mac_address(contents: &str) -> Option<String> {
+    let mut in_connection = false;
+    let mut value = None;
+    for line in contents.lines() {
+        let line = line.trim();
+        if let Some(section) = line.strip_prefix('[') {
+            in_connection = section.starts_with("connection");
+            continue;
+        }
+        if !in_connection || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some((key, v)) = line.split_once('=') {
+            if key.trim() == "wifi.cloned-mac-address" {
+                value = Some(v.trim().to_string());
+            }
+        }
+    }
+    value
+}
+
+/// Whether an `sshd_config` reads the `sshd_config.d` drop-in directory.
+fn includes_drop_ins(config: &str) -> bool {
+    config.lines().any(|line| {
+        let mut words = line.split_whitespace();
+        words
+            .next()
+            .is_some_and(|keyword| keyword.eq_ignore_ascii_case("include"))
+            && words.any(|path| path.contains("sshd_config.d"))
+    })
+}
+
+/// The GSettings of `schema`, if it is installed.
+pub(super) fn settings(schema: &str) -> Option<gio::Settings> {
+    gio::SettingsSchemaSource::default()?.lookup(schema, true)?;
+    Some(gio::Settings::new(schema))
+}
+
+/// Read the GNOME screen lock settings.
+pub fn query_screen_lock() -> Option<ScreenLock> {
+    let screensaver = settings(SCREENSAVER_SCHEMA)?;
+    let session = settings(SESSION_SCHEMA)?;
+    Some(ScreenLock {
+        lock_enabled: screensaver.boolean("lock-enabled"),
+        lock_delay: screensaver.uint("lock-delay"),
+        idle_delay: session.uint("idle-delay"),
+    })
+}
+
+/// Apply [`ScreenLock::target`], returning the equivalent `gsettings`
+/// commands.
+pub fn enable_screen_lock() -> Result<Vec<String>> {
+    let missing = || anyhow!("GNOME screen lock settings are not available");
+    let screensaver = settings(SCREENSAVER_SCHEMA).ok_or_else(missing)?;
+    let session = settings(SESSION_SCHEMA).ok_or_else(missing)?;
+    let target = query_screen_lock().ok_or_else(missing)?.target();
+
+    screensaver.set_boolean("lock-enabled", target.lock_enabled)?;
+    screensaver.set_uint("lock-delay", target.lock_delay)?;
+    session.set_uint("idle-delay", target.idle_delay)?;
+    gio::Settings::sync();
+
+    Ok(vec![
+        format!("gsettings set {} lock-enabled true", SCREENSAVER_SCHEMA),
+        format!("gsettings set {} lock-delay 0", SCREENSAVER_SCHEMA),
+        format!(
+            "gsettings set {} idle-delay {}",
+            SESSION_SCHEMA, target.idle_delay
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cloned_mac_address() {
+        let conf = "[main]\nwifi.cloned-mac-address=preserve\n\
+                    [connection]\nwifi.cloned-mac-address = random\n\
+                    [connection-wifi]\n# wifi.cloned-mac-address=permanent\n";
+        assert_eq!(cloned_mac_address(conf), Some("random".to_string()));
+        assert_eq!(
+            cloned_mac_address("[device]\nwifi.scan-rand-mac-address=no\n"),
+            None
+        );
+        assert_eq!(
+            cloned_mac_address(MAC_DROPIN_CONTENTS),
+            Some("stable".to_string())
+        );
+    }
+
+    #[test]
+    fn test_includes_drop_ins() {
+        assert!(includes_drop_ins(
+            "# comment\nInclude /etc/ssh/sshd_config.d/*.conf\nPort 22\n"
+        ));
+        assert!(!includes_drop_ins(
+            "#Include /etc/ssh/sshd_config.d/*.conf\n"
+        ));
+        assert!(!includes_drop_ins("PermitRootLogin no\n"));
+    }
+
+    #[test]
+    fn test_screen_lock_target() {
+        let current = ScreenLock {
+            lock_enabled: false,
+            lock_delay: 30,
+            idle_delay: 0,
+        };
+        let target = current.target();
+        assert!(target.lock_enabled);
+        assert_eq!(target.lock_delay, 0);
+        assert_eq!(target.idle_delay, MAX_IDLE_DELAY);
+        assert_eq!(target.target(), target);
+        let short = ScreenLock {
+            idle_delay: 120,
+            ..target
+        };
+        assert_eq!(short.target(), short);
+    }
+
+    #[test]
+    fn test_plan_operations() {
+        let mut plan = TravelPlan {
+            default_zone: "home".to_string(),
+            sharing_units: vec!["smb.service"],
+            sharing_services: vec!["samba"],
+            wifi_cloned_mac: Some("preserve".to_string()),
+            ssh: SshState {
+                installed: true,
+                reads_drop_ins: false,
+                hardened: false,
+                zone_allows_ssh: true,
+            },
+            screen_lock: None,
+        };
+        assert_eq!(
+            plan.operations(TravelStep::PublicZone),
+            vec![Operation::SetDefaultZone { zone: "public" }]
+        );
+        assert_eq!(
+            plan.operations(TravelStep::FileSharing),
+            vec![
+                Operation::StopAndDisableUnit {
+                    unit: "smb.service"
+                },
+                Operation::RemoveZoneService {
+                    zone: "public",
+                    service: "samba"
+                },
+            ]
+        );
+        assert_eq!(
+            plan.operations(TravelStep::MacRandomization),
+            vec![Operation::EnableMacRandomization]
+        );
+        // Without the Include line only the firewall part applies
+        assert_eq!(
+            plan.operations(TravelStep::Ssh),
+            vec![Operation::RemoveZoneService {
+                zone: "public",
+                service: "ssh"
+            }]
+        );
+        assert!(plan.operations(TravelStep::ScreenLock).is_empty());
+
+        plan.default_zone = "public".to_string();
+        plan.wifi_cloned_mac = Some("stable".to_string());
+        plan.ssh.reads_drop_ins = true;
+        assert!(plan.operations(TravelStep::PublicZone).is_empty());
+        assert!(plan.operations(TravelStep::MacRandomization).is_empty());
+        assert_eq!(plan.operations(TravelStep::Ssh)[0], Operation::HardenSsh);
+    }
+}
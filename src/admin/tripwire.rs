@@ -0,0 +1,386 @@
+// Security Center - Tripwire Ports
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Tripwire ports: ports such as telnet (23) or RDP (3389) that nothing on
+//! this machine serves, so any connection attempt on them is a probe.
+//!
+//! Nothing listens on a tripwire port. Listening would need root below port
+//! 1024 and would answer the scanner; instead the firewall keeps rejecting
+//! the attempts and, with firewalld's `LogDenied` setting on, each one
+//! reaches the kernel log that [`DeniedLog`](super::DeniedLog) follows.
+//! [`Tripwires`] picks out the denied packets aimed at a tripwire port and
+//! reports each source once per port per [`ALERT_COOLDOWN`], so a scan
+//! does not become a flood of notifications.
+//! [`block_source`](super::block_source) drops all further traffic from a
+//! prober.
+//!
+//! Nothing is blocked without asking. A rejected packet never completes a
+//! handshake, so its source address is whatever the sender wrote there, a
+//! TCP SYN as much as a UDP datagram: blocking it unasked would let anyone
+//! block any address. A hit only offers the user to block the source, and
+//! [`Tripwires::offer_block`] keeps those offers to [`MAX_BLOCK_OFFERS`] an
+//! hour and never makes them for a reserved address, a default gateway or a
+//! DNS server, so a flood of forged probes cannot bury the user in prompts
+//! or talk them into cutting the machine off.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::net::{IpAddr, Ipv6Addr};
+
+use anyhow::{anyhow, Result};
+
+use super::network::parse_ipv4_hex;
+use super::DeniedPacket;
+use crate::blocklists;
+
+/// Seconds before the same source probing the same port is reported again.
+pub const ALERT_COOLDOWN: u64 = 600;
+/// Sources remembered for the cooldown before expired ones are pruned.
+const MAX_REMEMBERED: usize = 1024;
+/// Hits offering to block their source within [`BLOCK_OFFER_WINDOW`].
+pub const MAX_BLOCK_OFFERS: usize = 5;
+/// Seconds over which [`MAX_BLOCK_OFFERS`] is counted.
+const BLOCK_OFFER_WINDOW: u64 = 3600;
+/// Files naming the DNS servers: the system's, and the servers the
+/// systemd-resolved stub forwards to.
+const RESOLV_CONF_PATHS: [&str; 2] = ["/etc/resolv.conf", "/run/systemd/resolve/resolv.conf"];
+
+/// A connection attempt on a tripwire port.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TripwireHit {
+    pub source: IpAddr,
+    pub port: u16,
+    /// Protocol from the log line, e.g. `TCP`.
+    pub protocol: String,
+    /// Unix time in seconds.
+    pub time: u64,
+}
+
+/// Matches denied packets against the tripwire ports.
+#[derive(Debug, Default)]
+pub struct Tripwires {
+    ports: BTreeSet<u16>,
+    /// When each source was last reported for each port.
+    reported: HashMap<(IpAddr, u16), u64>,
+    /// When blocking was offered, within the last [`BLOCK_OFFER_WINDOW`].
+    block_offers: Vec<u64>,
+}
+
+impl Tripwires {
+    pub fn new(ports: &[u16]) -> Self {
+        Self {
+            ports: ports.iter().copied().collect(),
+            reported: HashMap::new(),
+            block_offers: Vec::new(),
+        }
+    }
+
+    /// Replace the watched ports, keeping the cooldowns of those still watched.
+    pub fn set_ports(&mut self, ports: &[u16]) {
+        self.ports = ports.iter().copied().collect();
+        let ports = &self.ports;
+        self.reported.retain(|(_, port), _| ports.contains(port));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ports.is_empty()
+    }
+
+    /// The hit `packet` represents, unless it missed the tripwires or its
+    /// source was reported for the port within [`ALERT_COOLDOWN`].
+    pub fn check(&mut self, packet: &DeniedPacket) -> Option<TripwireHit> {
+        let port = packet.port.filter(|p| self.ports.contains(p))?;
+        let source: IpAddr = packet.source.parse().ok()?;
+
+        if let Some(&last) = self.reported.get(&(source, port)) {
+            if packet.time < last.saturating_add(ALERT_COOLDOWN) {
+                return None;
+            }
+        }
+        if self.reported.len() >= MAX_REMEMBERED {
+            self.reported
+                .retain(|_, last| packet.time < last.saturating_add(ALERT_COOLDOWN));
+        }
+        self.reported.insert((source, port), packet.time);
+
+        Some(TripwireHit {
+            source,
+            port,
+            protocol: packet.protocol.clone(),
+            time: packet.time,
+        })
+    }
+
+    /// Whether the alert for `hit` should offer to block its source, which
+    /// counts towards [`MAX_BLOCK_OFFERS`]. See [`may_offer_block`].
+    pub fn offer_block(&mut self, hit: &TripwireHit, protected: &[IpAddr]) -> bool {
+        if !may_offer_block(hit, protected) {
+            return false;
+        }
+        self.block_offers
+            .retain(|&offered| hit.time < offered.saturating_add(BLOCK_OFFER_WINDOW));
+        if self.block_offers.len() >= MAX_BLOCK_OFFERS {
+            return false;
+        }
+        self.block_offers.push(hit.time);
+        true
+    }
+}
+
+/// Whether blocking the source of `hit` may be offered at all: the source
+/// is neither reserved nor one of `protected`, the addresses from
+/// [`protected_addresses`]. The source of a probe may be forged, so this
+/// only decides what the user is asked, never what is blocked unasked.
+pub fn may_offer_block(hit: &TripwireHit, protected: &[IpAddr]) -> bool {
+    let source = hit.source.to_canonical();
+    !blocklists::is_reserved(source) && !protected.iter().any(|ip| ip.to_canonical() == source)
+}
+
+/// The default gateways and DNS servers, which blocking is never offered
+/// for.
+pub fn protected_addresses() -> Vec<IpAddr> {
+    let read = |path: &str| fs::read_to_string(path).unwrap_or_default();
+    let mut addresses = parse_gateways(&read("/proc/net/route"));
+    addresses.extend(parse_gateways_v6(&read("/proc/net/ipv6_route")));
+    for path in RESOLV_CONF_PATHS {
+        addresses.extend(parse_nameservers(&read(path)));
+    }
+    addresses
+}
+
+/// The gateways of the default routes in `/proc/net/route`.
+fn parse_gateways(route: &str) -> Vec<IpAddr> {
+    route
+        .lines()
+        .filter_map(|line| {
+            // Iface Destination Gateway Flags ...
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                [_, "00000000", gateway, ..] if *gateway != "00000000" => {
+                    parse_ipv4_hex(gateway).map(IpAddr::V4)
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// The next hops of the default routes in `/proc/net/ipv6_route`.
+fn parse_gateways_v6(route: &str) -> Vec<IpAddr> {
+    route
+        .lines()
+        .filter_map(|line| {
+            // Destination, prefix, source, prefix, next hop, ...
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                [_, "00", _, _, next_hop, ..] if next_hop.len() == 32 => {
+                    u128::from_str_radix(next_hop, 16)
+                        .ok()
+                        .filter(|&hop| hop != 0)
+                        .map(|hop| IpAddr::V6(Ipv6Addr::from(hop)))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// The `nameserver` addresses of a resolv.conf, without IPv6 zone ids.
+fn parse_nameservers(resolv: &str) -> Vec<IpAddr> {
+    resolv
+        .lines()
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            if words.next() != Some("nameserver") {
+                return None;
+            }
+            words.next()?.split('%').next()?.parse().ok()
+        })
+        .collect()
+}
+
+/// Parse a list of ports separated by commas or spaces, e.g. `23, 3389`.
+/// The result is sorted and without duplicates.
+pub fn parse_ports(text: &str) -> Result<Vec<u16>> {
+    let ports: BTreeSet<u16> = text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            word.parse::<u16>()
+                .ok()
+                .filter(|&port| port > 0)
+                .ok_or_else(|| anyhow!("Invalid port: {}", word))
+        })
+        .collect::<Result<_>>()?;
+    Ok(ports.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn probe(source: &str, port: u16, time: u64) -> DeniedPacket {
+        DeniedPacket {
+            time,
+            rule: "filter_IN_public_REJECT".to_string(),
+            interface: "eth0".to_string(),
+            source: source.to_string(),
+            destination: "192.168.1.10".to_string(),
+            protocol: "TCP".to_string(),
+            port: Some(port),
+        }
+    }
+
+    #[test]
+    fn test_check_matches_tripwire_ports() {
+        let mut tripwires = Tripwires::new(&[23, 3389]);
+        let hit = tripwires.check(&probe("203.0.113.7", 23, 100)).unwrap();
+        assert_eq!(hit.source, "203.0.113.7".parse::<IpAddr>().unwrap());
+        assert_eq!(hit.port, 23);
+        assert_eq!(hit.protocol, "TCP");
+        assert!(tripwires.check(&probe("203.0.113.7", 22, 100)).is_none());
+
+        let mut no_port = probe("203.0.113.7", 23, 100);
+        no_port.port = None;
+        assert!(tripwires.check(&no_port).is_none());
+        assert!(tripwires.check(&probe("not-an-ip", 3389, 100)).is_none());
+    }
+
+    #[test]
+    fn test_check_cooldown() {
+        let mut tripwires = Tripwires::new(&[23, 3389]);
+        assert!(tripwires.check(&probe("203.0.113.7", 23, 100)).is_some());
+        assert!(tripwires.check(&probe("203.0.113.7", 23, 101)).is_none());
+        // Another port or another source is a new hit
+        assert!(tripwires.check(&probe("203.0.113.7", 3389, 101)).is_some());
+        assert!(tripwires.check(&probe("2001:db8::1", 23, 101)).is_some());
+        assert!(tripwires
+            .check(&probe("203.0.113.7", 23, 100 + ALERT_COOLDOWN))
+            .is_some());
+    }
+
+    #[test]
+    fn test_set_ports() {
+        let mut tripwires = Tripwires::new(&[23]);
+        assert!(!tripwires.is_empty());
+        assert!(tripwires.check(&probe("203.0.113.7", 23, 100)).is_some());
+        tripwires.set_ports(&[3389]);
+        assert!(tripwires.check(&probe("203.0.113.7", 23, 101)).is_none());
+        assert!(tripwires.check(&probe("203.0.113.7", 3389, 101)).is_some());
+        tripwires.set_ports(&[]);
+        assert!(tripwires.is_empty());
+    }
+
+    fn hit(source: &str, time: u64) -> TripwireHit {
+        TripwireHit {
+            source: source.parse().unwrap(),
+            port: 23,
+            protocol: "TCP".to_string(),
+            time,
+        }
+    }
+
+    #[test]
+    fn test_may_offer_block() {
+        let protected: Vec<IpAddr> = vec![
+            "198.51.100.1".parse().unwrap(),
+            "2001:db8::53".parse().unwrap(),
+        ];
+        assert!(may_offer_block(&hit("203.0.113.7", 100), &protected));
+        assert!(may_offer_block(&hit("2001:db8::7", 100), &protected));
+
+        for reserved in [
+            "127.0.0.1",
+            "192.168.1.20",
+            "10.0.0.5",
+            "169.254.3.4",
+            "fe80::1",
+            "fd00::1",
+            "::ffff:192.168.1.20",
+        ] {
+            assert!(
+                !may_offer_block(&hit(reserved, 100), &protected),
+                "{}",
+                reserved
+            );
+        }
+
+        // The gateway and the DNS server, also as IPv4-mapped addresses
+        assert!(!may_offer_block(&hit("198.51.100.1", 100), &protected));
+        assert!(!may_offer_block(
+            &hit("::ffff:198.51.100.1", 100),
+            &protected
+        ));
+        assert!(!may_offer_block(&hit("2001:db8::53", 100), &protected));
+    }
+
+    #[test]
+    fn test_offer_block_rate_limit() {
+        let mut tripwires = Tripwires::new(&[23]);
+        let protected: Vec<IpAddr> = vec!["198.51.100.1".parse().unwrap()];
+        for i in 0..MAX_BLOCK_OFFERS as u64 {
+            let source = format!("203.0.113.{}", i + 1);
+            assert!(tripwires.offer_block(&hit(&source, 100 + i), &protected));
+        }
+        // A flood of forged sources stops getting offers
+        assert!(!tripwires.offer_block(&hit("203.0.113.99", 200), &protected));
+        // Protected sources are refused without using up an offer
+        assert!(!tripwires.offer_block(&hit("198.51.100.1", 200 + BLOCK_OFFER_WINDOW), &protected));
+        assert!(tripwires.offer_block(&hit("203.0.113.99", 100 + BLOCK_OFFER_WINDOW), &protected));
+    }
+
+    #[test]
+    fn test_parse_gateways() {
+        let route = "\
+Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+eth0\t00000000\t0101A8C0\t0003\t0\t0\t100\t00000000\t0\t0\t0
+eth0\t0001A8C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0
+";
+        assert_eq!(
+            parse_gateways(route),
+            vec!["192.168.1.1".parse::<IpAddr>().unwrap()]
+        );
+
+        let route6 = "\
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 fe80000000000000021122fffe334455 00000400 00000001 00000000 00000003 eth0
+fe800000000000000000000000000000 40 00000000000000000000000000000000 00 00000000000000000000000000000000 00000100 00000001 00000000 00000001 eth0
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 00000000000000000000000000000000 ffffffff 00000001 00000000 00200200 lo
+";
+        assert_eq!(
+            parse_gateways_v6(route6),
+            vec!["fe80::211:22ff:fe33:4455".parse::<IpAddr>().unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_parse_nameservers() {
+        let resolv = "\
+# Generated by NetworkManager
+search lan
+nameserver 1.1.1.1
+nameserver fe80::1%eth0
+nameserver not-an-address
+options edns0
+";
+        assert_eq!(
+            parse_nameservers(resolv),
+            vec![
+                "1.1.1.1".parse::<IpAddr>().unwrap(),
+                "fe80::1".parse::<IpAddr>().unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_ports() {
+        assert_eq!(
+            parse_ports("3389, 23 23,,5900").unwrap(),
+            vec![23, 3389, 5900]
+        );
+        assert!(parse_ports("").unwrap().is_empty());
+        assert!(parse_ports("23, telnet").is_err());
+        assert!(parse_ports("0").is_err());
+        assert!(parse_ports("65536").is_err());
+    }
+}
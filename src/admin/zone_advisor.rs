@@ -0,0 +1,530 @@
+// Security Center - Zone Suggestions
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Zone suggestions for the network the machine is connected to.
+//!
+//! NetworkManager knows what kind of network the primary connection is:
+//! wired or Wi-Fi, the SSID and how the network authenticates. Open Wi-Fi
+//! and public hotspots call for the `public` zone, 802.1X networks are run
+//! by organisations and fit `work`, and a password-protected Wi-Fi is most
+//! likely someone's home. [`query_zone_suggestion`] compares that guess
+//! with the zone the interface is in and [`apply_zone_suggestion`] moves it.
+//!
+//! A zone chosen for the connection profile is respected unless the network
+//! looks less trustworthy than the zone allows; only zones assigned by
+//! default are also loosened.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context, Result};
+use zbus::blocking::{Connection, Proxy};
+use zbus::proxy::MethodFlags;
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+use crate::firewall;
+
+const NM_BUS: &str = "org.freedesktop.NetworkManager";
+const NM_PATH: &str = "/org/freedesktop/NetworkManager";
+const NM_SETTINGS_PATH: &str = "/org/freedesktop/NetworkManager/Settings";
+const NM_SETTINGS: &str = "org.freedesktop.NetworkManager.Settings";
+const NM_SETTINGS_CONNECTION: &str = "org.freedesktop.NetworkManager.Settings.Connection";
+const NM_ACTIVE_CONNECTION: &str = "org.freedesktop.NetworkManager.Connection.Active";
+const NM_DEVICE: &str = "org.freedesktop.NetworkManager.Device";
+/// `Update2` flag writing the profile to disk.
+const NM_UPDATE2_TO_DISK: u32 = 0x1;
+
+/// Settings of a connection profile, as returned by `GetSettings`.
+type ConnectionSettings = HashMap<String, HashMap<String, OwnedValue>>;
+
+/// Words in an SSID that mark a network open to the public.
+const PUBLIC_SSID_WORDS: &[&str] = &[
+    "guest",
+    "free",
+    "public",
+    "hotspot",
+    "airport",
+    "hotel",
+    "cafe",
+    "café",
+    "coffee",
+    "library",
+    "eduroam",
+    "wifi4eu",
+    "xfinitywifi",
+];
+
+/// Kind of network a connection attaches to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkKind {
+    Wired,
+    Wifi,
+    /// GSM or CDMA mobile broadband.
+    Mobile,
+    /// VPNs, bridges, tunnels and the like, which get no suggestion.
+    Other,
+}
+
+/// How joining the network is authenticated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkSecurity {
+    /// No authentication.
+    Open,
+    /// Opportunistic Wireless Encryption: encrypted, but anyone may join.
+    Owe,
+    /// WEP, broken since long ago.
+    Wep,
+    /// A shared password (WPA-PSK or SAE).
+    Personal,
+    /// 802.1X sign-in with individual credentials.
+    Enterprise,
+}
+
+/// The primary network connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveNetwork {
+    /// UUID of the connection profile.
+    pub uuid: String,
+    /// Name of the connection profile.
+    pub name: String,
+    pub interface: String,
+    pub kind: NetworkKind,
+    pub ssid: Option<String>,
+    pub security: NetworkSecurity,
+    /// Zone set in the profile; empty when firewalld's default zone applies.
+    pub profile_zone: String,
+}
+
+/// A better zone for the primary connection's interface.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZoneSuggestion {
+    pub network: ActiveNetwork,
+    /// Zone the interface is in now.
+    pub current_zone: String,
+    pub zone: &'static str,
+    /// Why the zone fits the network.
+    pub reason: &'static str,
+}
+
+impl ZoneSuggestion {
+    /// Identifies the suggestion for this network, so dismissing it does not
+    /// hide a different suggestion later.
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.network.uuid, self.zone)
+    }
+}
+
+/// The zone that fits `network`, and why; `None` when there is too little to
+/// go on, e.g. for a plain wired network.
+pub fn suggest_zone(network: &ActiveNetwork) -> Option<(&'static str, &'static str)> {
+    let public_ssid = network.ssid.as_deref().is_some_and(|ssid| {
+        let ssid = ssid.to_lowercase();
+        PUBLIC_SSID_WORDS.iter().any(|word| ssid.contains(word))
+    });
+
+    match (network.kind, network.security) {
+        (NetworkKind::Wifi, NetworkSecurity::Open | NetworkSecurity::Owe) => Some((
+            "public",
+            "Anyone nearby can join this Wi-Fi network without a password.",
+        )),
+        (NetworkKind::Wifi, NetworkSecurity::Wep) => Some((
+            "public",
+            "This Wi-Fi network uses WEP, which offers no real protection.",
+        )),
+        (NetworkKind::Wifi, _) if public_ssid => Some((
+            "public",
+            "The network name suggests a public or guest network.",
+        )),
+        (NetworkKind::Wifi, NetworkSecurity::Enterprise) => Some((
+            "work",
+            "This Wi-Fi network signs in with individual credentials, as organisations do.",
+        )),
+        (NetworkKind::Wifi, NetworkSecurity::Personal) => Some((
+            "home",
+            "This Wi-Fi network is protected by a shared password, as home networks are.",
+        )),
+        (NetworkKind::Wired, NetworkSecurity::Enterprise) => Some((
+            "work",
+            "This wired network signs in with 802.1X, as organisations do.",
+        )),
+        (NetworkKind::Mobile, _) => Some((
+            "public",
+            "Mobile broadband puts this computer on the carrier's shared network.",
+        )),
+        _ => None,
+    }
+}
+
+/// How much a zone trusts the network, from 0 for `drop` to 4 for `trusted`;
+/// `None` for custom zones, which cannot be judged.
+fn trust_rank(zone: &str) -> Option<u8> {
+    match zone {
+        "drop" | "block" => Some(0),
+        "public" | "external" | "dmz" => Some(1),
+        "work" => Some(2),
+        "home" | "internal" => Some(3),
+        "trusted" => Some(4),
+        _ => None,
+    }
+}
+
+/// Whether to suggest moving from `current` to `suggested`. A zone picked
+/// for the profile is only replaced by a stricter one.
+fn should_suggest(profile_zone: &str, current: &str, suggested: &str) -> bool {
+    if current == suggested {
+        return false;
+    }
+    match (trust_rank(current), trust_rank(suggested)) {
+        (Some(current), Some(suggested)) => profile_zone.is_empty() || suggested < current,
+        _ => false,
+    }
+}
+
+/// Read a string property of a settings section, if present.
+fn setting_str<'a>(settings: &'a ConnectionSettings, section: &str, key: &str) -> Option<&'a str> {
+    settings
+        .get(section)?
+        .get(key)
+        .and_then(|value| <&str>::try_from(value).ok())
+}
+
+/// Describe a connection profile attached to `interface`.
+fn network_from_settings(interface: &str, settings: &ConnectionSettings) -> ActiveNetwork {
+    let kind = match setting_str(settings, "connection", "type") {
+        Some("802-3-ethernet") => NetworkKind::Wired,
+        Some("802-11-wireless") => NetworkKind::Wifi,
+        Some("gsm" | "cdma") => NetworkKind::Mobile,
+        _ => NetworkKind::Other,
+    };
+
+    let ssid = settings
+        .get("802-11-wireless")
+        .and_then(|wifi| wifi.get("ssid"))
+        .and_then(|value| value.try_clone().ok())
+        .and_then(|value| Vec::<u8>::try_from(value).ok())
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned());
+
+    let security = match setting_str(settings, "802-11-wireless-security", "key-mgmt") {
+        Some("wpa-psk" | "sae") => NetworkSecurity::Personal,
+        Some("wpa-eap" | "wpa-eap-suite-b-192") => NetworkSecurity::Enterprise,
+        Some("owe") => NetworkSecurity::Owe,
+        // "none" is static WEP, "ieee8021x" dynamic WEP
+        Some(_) => NetworkSecurity::Wep,
+        None if settings.contains_key("802-1x") => NetworkSecurity::Enterprise,
+        None => NetworkSecurity::Open,
+    };
+
+    ActiveNetwork {
+        uuid: setting_str(settings, "connection", "uuid")
+            .unwrap_or_default()
+            .to_string(),
+        name: setting_str(settings, "connection", "id")
+            .unwrap_or_default()
+            .to_string(),
+        interface: interface.to_string(),
+        kind,
+        ssid,
+        security,
+        profile_zone: setting_str(settings, "connection", "zone")
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+fn get_property<T>(conn: &Connection, path: &str, iface: &str, name: &str) -> Result<T>
+where
+    T: TryFrom<OwnedValue>,
+    T::Error: Into<zbus::zvariant::Error>,
+{
+    let value: OwnedValue = conn
+        .call_method(
+            Some(NM_BUS),
+            path,
+            Some("org.freedesktop.DBus.Properties"),
+            "Get",
+            &(iface, name),
+        )?
+        .body()
+        .deserialize()?;
+    Ok(T::try_from(value).map_err(Into::into)?)
+}
+
+fn get_settings(conn: &Connection, profile: &str) -> Result<ConnectionSettings> {
+    let settings: ConnectionSettings = conn
+        .call_method(
+            Some(NM_BUS),
+            profile,
+            Some(NM_SETTINGS_CONNECTION),
+            "GetSettings",
+            &(),
+        )?
+        .body()
+        .deserialize()?;
+    Ok(settings)
+}
+
+/// The connection NetworkManager routes through, if any.
+pub fn query_primary_network() -> Result<Option<ActiveNetwork>> {
+    let conn = Connection::system().context("Failed to connect to system D-Bus")?;
+
+    let active: OwnedObjectPath = get_property(&conn, NM_PATH, NM_BUS, "PrimaryConnection")
+        .context("Cannot reach NetworkManager")?;
+    if active.as_str() == "/" {
+        return Ok(None);
+    }
+
+    let devices: Vec<OwnedObjectPath> =
+        get_property(&conn, active.as_str(), NM_ACTIVE_CONNECTION, "Devices")?;
+    let Some(device) = devices.first() else {
+        return Ok(None);
+    };
+    let interface: String = get_property(&conn, device.as_str(), NM_DEVICE, "Interface")?;
+    let profile: OwnedObjectPath =
+        get_property(&conn, active.as_str(), NM_ACTIVE_CONNECTION, "Connection")?;
+
+    let settings = get_settings(&conn, profile.as_str())?;
+    Ok(Some(network_from_settings(&interface, &settings)))
+}
+
+/// Suggest a zone for the primary connection, unless its interface is in a
+/// fitting zone already.
+pub fn query_zone_suggestion() -> Result<Option<ZoneSuggestion>> {
+    let Some(network) = query_primary_network()? else {
+        return Ok(None);
+    };
+    let Some((zone, reason)) = suggest_zone(&network) else {
+        return Ok(None);
+    };
+
+    let interface = network.interface.clone();
+    let current_zone = firewall::read_blocking(move |client| {
+        client.connect()?;
+        // Interfaces bound to no zone are in the default zone
+        match client
+            .get_interfaces()?
+            .into_iter()
+            .find(|iface| iface.name == interface)
+        {
+            Some(iface) => Ok(iface.zone),
+            None => client.get_default_zone(),
+        }
+    })??;
+
+    if !should_suggest(&network.profile_zone, &current_zone, zone) {
+        return Ok(None);
+    }
+    Ok(Some(ZoneSuggestion {
+        network,
+        current_zone,
+        zone,
+        reason,
+    }))
+}
+
+/// Move the interface to the suggested zone now, and set the zone of the
+/// connection profile so it is used whenever the network reconnects.
+///
+/// Returns whether the profile was updated too; without it the change lasts
+/// until the interface reconnects.
+pub fn apply_zone_suggestion(suggestion: &ZoneSuggestion) -> Result<bool> {
+    let (zone, interface) = (suggestion.zone, suggestion.network.interface.clone());
+    firewall::call_blocking(move |client| {
+        client
+            .connect()
+            .context("Cannot reach firewalld. Is it running?")?;
+        client.change_zone_of_interface(zone, &interface)
+    })??;
+
+    match set_profile_zone(&suggestion.network.uuid, suggestion.zone) {
+        Ok(()) => Ok(true),
+        Err(e) => {
+            tracing::warn!(
+                "Cannot set the zone of connection {}: {:#}",
+                suggestion.network.name,
+                e
+            );
+            Ok(false)
+        }
+    }
+}
+
+/// Set `connection.zone` of the profile with `uuid`. NetworkManager applies
+/// a zone change to an active connection right away.
+fn set_profile_zone(uuid: &str, zone: &str) -> Result<()> {
+    let conn = Connection::system().context("Failed to connect to system D-Bus")?;
+    let profile: OwnedObjectPath = conn
+        .call_method(
+            Some(NM_BUS),
+            NM_SETTINGS_PATH,
+            Some(NM_SETTINGS),
+            "GetConnectionByUuid",
+            &(uuid,),
+        )?
+        .body()
+        .deserialize()?;
+
+    // Secrets are left out of GetSettings; NetworkManager keeps the stored
+    // ones when an update carries none.
+    let mut settings = get_settings(&conn, profile.as_str())?;
+    settings
+        .get_mut("connection")
+        .ok_or_else(|| anyhow!("Connection profile has no connection settings"))?
+        .insert("zone".to_string(), OwnedValue::try_from(Value::from(zone))?);
+
+    let proxy = Proxy::new(&conn, NM_BUS, profile.as_str(), NM_SETTINGS_CONNECTION)?;
+    let args: HashMap<String, OwnedValue> = HashMap::new();
+    let _: Option<HashMap<String, OwnedValue>> = proxy.call_with_flags(
+        "Update2",
+        MethodFlags::AllowInteractiveAuth.into(),
+        &(settings, NM_UPDATE2_TO_DISK, args),
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(sections: &[(&str, &[(&str, Value<'static>)])]) -> ConnectionSettings {
+        sections
+            .iter()
+            .map(|(section, values)| {
+                let values = values
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), OwnedValue::try_from(value).unwrap()))
+                    .collect();
+                (section.to_string(), values)
+            })
+            .collect()
+    }
+
+    fn wifi(ssid: &str, security: NetworkSecurity) -> ActiveNetwork {
+        ActiveNetwork {
+            uuid: "6c3f7e0a-1f4b-4e8c-9d0b-1a2b3c4d5e6f".to_string(),
+            name: ssid.to_string(),
+            interface: "wlp2s0".to_string(),
+            kind: NetworkKind::Wifi,
+            ssid: Some(ssid.to_string()),
+            security,
+            profile_zone: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_network_from_settings() {
+        let wifi = settings(&[
+            (
+                "connection",
+                &[
+                    ("type", Value::from("802-11-wireless")),
+                    ("uuid", Value::from("6c3f7e0a")),
+                    ("id", Value::from("Cafe Corner")),
+                    ("zone", Value::from("public")),
+                ],
+            ),
+            (
+                "802-11-wireless",
+                &[("ssid", Value::from(b"Cafe Corner".to_vec()))],
+            ),
+            (
+                "802-11-wireless-security",
+                &[("key-mgmt", Value::from("sae"))],
+            ),
+        ]);
+        let network = network_from_settings("wlp2s0", &wifi);
+        assert_eq!(network.kind, NetworkKind::Wifi);
+        assert_eq!(network.ssid.as_deref(), Some("Cafe Corner"));
+        assert_eq!(network.security, NetworkSecurity::Personal);
+        assert_eq!(network.uuid, "6c3f7e0a");
+        assert_eq!(network.name, "Cafe Corner");
+        assert_eq!(network.profile_zone, "public");
+        assert_eq!(network.interface, "wlp2s0");
+
+        let wired = settings(&[
+            ("connection", &[("type", Value::from("802-3-ethernet"))]),
+            ("802-1x", &[("eap", Value::from(vec!["peap"]))]),
+        ]);
+        let network = network_from_settings("enp3s0", &wired);
+        assert_eq!(network.kind, NetworkKind::Wired);
+        assert_eq!(network.ssid, None);
+        assert_eq!(network.security, NetworkSecurity::Enterprise);
+        assert_eq!(network.profile_zone, "");
+
+        let open = settings(&[("connection", &[("type", Value::from("802-11-wireless"))])]);
+        assert_eq!(
+            network_from_settings("wlp2s0", &open).security,
+            NetworkSecurity::Open
+        );
+    }
+
+    #[test]
+    fn test_suggest_zone() {
+        let zone = |network: &ActiveNetwork| suggest_zone(network).map(|(zone, _)| zone);
+
+        assert_eq!(
+            zone(&wifi("Airport", NetworkSecurity::Open)),
+            Some("public")
+        );
+        assert_eq!(zone(&wifi("Library", NetworkSecurity::Owe)), Some("public"));
+        assert_eq!(
+            zone(&wifi("Old Router", NetworkSecurity::Wep)),
+            Some("public")
+        );
+        assert_eq!(
+            zone(&wifi("Hotel-Guest", NetworkSecurity::Personal)),
+            Some("public")
+        );
+        assert_eq!(
+            zone(&wifi("eduroam", NetworkSecurity::Enterprise)),
+            Some("public")
+        );
+        assert_eq!(
+            zone(&wifi("ACME Corp", NetworkSecurity::Enterprise)),
+            Some("work")
+        );
+        assert_eq!(
+            zone(&wifi("Smith Family", NetworkSecurity::Personal)),
+            Some("home")
+        );
+
+        let mut wired = wifi("", NetworkSecurity::Open);
+        wired.kind = NetworkKind::Wired;
+        wired.ssid = None;
+        assert_eq!(zone(&wired), None);
+        wired.security = NetworkSecurity::Enterprise;
+        assert_eq!(zone(&wired), Some("work"));
+
+        let mut mobile = wired.clone();
+        mobile.kind = NetworkKind::Mobile;
+        assert_eq!(zone(&mobile), Some("public"));
+        mobile.kind = NetworkKind::Other;
+        assert_eq!(zone(&mobile), None);
+    }
+
+    #[test]
+    fn test_should_suggest() {
+        // Default assignment: any better fit
+        assert!(should_suggest("", "public", "home"));
+        assert!(should_suggest("", "home", "public"));
+        assert!(!should_suggest("", "public", "public"));
+        // Chosen for the profile: only tighten
+        assert!(should_suggest("trusted", "trusted", "public"));
+        assert!(!should_suggest("public", "public", "home"));
+        // Custom zones are left alone
+        assert!(!should_suggest("", "office-lan", "public"));
+    }
+
+    #[test]
+    fn test_suggestion_key() {
+        let suggestion = ZoneSuggestion {
+            network: wifi("Smith Family", NetworkSecurity::Personal),
+            current_zone: "public".to_string(),
+            zone: "home",
+            reason: "",
+        };
+        assert_eq!(
+            suggestion.key(),
+            "6c3f7e0a-1f4b-4e8c-9d0b-1a2b3c4d5e6f:home"
+        );
+    }
+}
@@ -0,0 +1,578 @@
+// Security Center - Local JSON API
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Optional read-only HTTP API on the loopback interface.
+//!
+//! Scripts and local dashboards can query Security Center data without
+//! GObject bindings:
+//!
+//! ```text
+//! curl -H "Authorization: Bearer $(cat ~/.config/security-center/api-token)" \
+//!     http://127.0.0.1:8770/status
+//! ```
+//!
+//! Endpoints (all `GET`, all JSON):
+//! - `/status`: firewall state, default zone, panic mode and exposure counts
+//! - `/exposure`: listening endpoints and their firewall status
+//! - `/rules`: zones with their services, ports, sources and rich rules
+//!
+//! The server binds to 127.0.0.1 only, serves one request per connection and
+//! requires the token from [`token_path`] on every request. Browsers cannot
+//! send the `Authorization` header cross-origin without a CORS preflight,
+//! which the server never answers, and the `Host` header must name the
+//! loopback address, which defeats DNS rebinding.
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use tracing::{debug, info, warn};
+
+use crate::admin::{FirewallStatus, NetworkExposure};
+use crate::firewall;
+
+/// Port used unless the user picks another one.
+pub const DEFAULT_API_PORT: u16 = 8770;
+/// Lowest port the API may use; privileged ports are not available.
+pub const API_PORT_MIN: u16 = 1024;
+
+/// Largest request head (request line and headers) accepted.
+const MAX_REQUEST_SIZE: usize = 8192;
+/// Time a client has to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+/// How often the accept loop checks whether it should stop.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// Random bytes in a token (hex encoded, twice as many characters).
+const TOKEN_BYTES: usize = 32;
+
+/// Location of the access token: `~/.config/security-center/api-token`.
+pub fn token_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("security-center")
+        .join("api-token")
+}
+
+/// Clamp a configured port into the allowed range.
+pub fn clamp_api_port(port: u16) -> u16 {
+    port.max(API_PORT_MIN)
+}
+
+/// Read the access token, creating one on first use.
+pub fn load_or_create_token() -> Result<String> {
+    match fs::read_to_string(token_path()) {
+        Ok(text) if is_valid_token(text.trim()) => Ok(text.trim().to_string()),
+        Ok(_) => {
+            warn!("API token file is malformed, generating a new token");
+            regenerate_token()
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => regenerate_token(),
+        Err(e) => Err(e).context("Failed to read the API token"),
+    }
+}
+
+/// Replace the access token with a new random one. Clients using the old
+/// token are rejected from the next request on.
+pub fn regenerate_token() -> Result<String> {
+    #[cfg(unix)]
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let mut bytes = [0u8; TOKEN_BYTES];
+    fs::File::open("/dev/urandom")
+        .and_then(|mut f| f.read_exact(&mut bytes))
+        .context("Failed to read random bytes")?;
+    let token: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+
+    let path = token_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // Write a fresh file so the mode applies even when one existed.
+    let _ = fs::remove_file(&path);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options
+        .open(&path)
+        .context("Failed to create the API token file")?;
+    file.write_all(token.as_bytes())?;
+    Ok(token)
+}
+
+fn is_valid_token(token: &str) -> bool {
+    token.len() == TOKEN_BYTES * 2 && token.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// A running API server. Dropping it stops the server.
+#[derive(Debug)]
+pub struct ApiServer {
+    stop: Arc<AtomicBool>,
+}
+
+impl ApiServer {
+    /// Bind to `127.0.0.1:port` and serve requests on a background thread.
+    pub fn start(port: u16) -> Result<Self> {
+        if port < API_PORT_MIN {
+            bail!("Port {} is reserved for system services", port);
+        }
+        let token = load_or_create_token()?;
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+            .with_context(|| format!("Failed to listen on port {}", port))?;
+        listener.set_nonblocking(true)?;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let flag = stop.clone();
+        thread::Builder::new()
+            .name("local-api".to_string())
+            .spawn(move || serve(listener, port, &token, &flag))?;
+
+        info!("Local API listening on 127.0.0.1:{}", port);
+        Ok(Self { stop })
+    }
+}
+
+impl Drop for ApiServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+fn serve(listener: TcpListener, port: u16, token: &str, stop: &AtomicBool) {
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                if let Err(e) = handle_connection(stream, port, token) {
+                    debug!("Local API request failed: {}", e);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
+            Err(e) => {
+                warn!("Local API accept failed: {}", e);
+                thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+    info!("Local API on port {} stopped", port);
+}
+
+fn handle_connection(mut stream: TcpStream, port: u16, token: &str) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    stream.set_write_timeout(Some(READ_TIMEOUT))?;
+
+    let response = match read_request_head(&mut stream) {
+        Ok(head) => match parse_request(&head) {
+            Ok(request) => respond(&request, port, token),
+            Err(status) => error_response(status),
+        },
+        Err(e) if e.kind() == io::ErrorKind::InvalidData => error_response(Status::TooLarge),
+        Err(e) => return Err(e),
+    };
+    stream.write_all(&response.to_bytes())?;
+    stream.flush()
+}
+
+/// Read until the blank line ending the headers.
+fn read_request_head(stream: &mut impl Read) -> io::Result<String> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    loop {
+        let n = stream.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..n]);
+        if head.windows(4).any(|w| w == b"\r\n\r\n") {
+            break;
+        }
+        if head.len() > MAX_REQUEST_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request too large",
+            ));
+        }
+    }
+    Ok(String::from_utf8_lossy(&head).into_owned())
+}
+
+/// HTTP status codes the server uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Ok,
+    BadRequest,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    MethodNotAllowed,
+    TooLarge,
+    Unavailable,
+}
+
+impl Status {
+    fn code(self) -> u16 {
+        match self {
+            Status::Ok => 200,
+            Status::BadRequest => 400,
+            Status::Unauthorized => 401,
+            Status::Forbidden => 403,
+            Status::NotFound => 404,
+            Status::MethodNotAllowed => 405,
+            Status::TooLarge => 431,
+            Status::Unavailable => 503,
+        }
+    }
+
+    fn reason(self) -> &'static str {
+        match self {
+            Status::Ok => "OK",
+            Status::BadRequest => "Bad Request",
+            Status::Unauthorized => "Unauthorized",
+            Status::Forbidden => "Forbidden",
+            Status::NotFound => "Not Found",
+            Status::MethodNotAllowed => "Method Not Allowed",
+            Status::TooLarge => "Request Header Fields Too Large",
+            Status::Unavailable => "Service Unavailable",
+        }
+    }
+}
+
+/// The parts of a request the server looks at.
+#[derive(Debug, PartialEq, Eq)]
+struct Request {
+    method: String,
+    /// Path without the query string.
+    path: String,
+    host: Option<String>,
+    authorization: Option<String>,
+}
+
+fn parse_request(head: &str) -> Result<Request, Status> {
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split(' ');
+    let (Some(method), Some(target), Some(version), None) = (
+        request_line.next(),
+        request_line.next(),
+        request_line.next(),
+        request_line.next(),
+    ) else {
+        return Err(Status::BadRequest);
+    };
+    if !version.starts_with("HTTP/1.") || !target.starts_with('/') {
+        return Err(Status::BadRequest);
+    }
+
+    let mut request = Request {
+        method: method.to_string(),
+        path: target.split(['?', '#']).next().unwrap_or("/").to_string(),
+        host: None,
+        authorization: None,
+    };
+    for line in lines.take_while(|l| !l.is_empty()) {
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(Status::BadRequest);
+        };
+        let value = value.trim().to_string();
+        if name.eq_ignore_ascii_case("host") {
+            request.host = Some(value);
+        } else if name.eq_ignore_ascii_case("authorization") {
+            request.authorization = Some(value);
+        }
+    }
+    Ok(request)
+}
+
+/// Whether the Host header names this server on the loopback address.
+fn host_allowed(host: Option<&str>, port: u16) -> bool {
+    let Some(host) = host else {
+        return false;
+    };
+    let name = match host.split_once(':') {
+        Some((name, p)) if p.parse::<u16>() == Ok(port) => name,
+        Some(_) => return false,
+        None => host,
+    };
+    matches!(name, "127.0.0.1" | "localhost")
+}
+
+/// Whether the Authorization header carries the token.
+fn authorized(authorization: Option<&str>, token: &str) -> bool {
+    let Some(presented) = authorization
+        .and_then(|value| value.split_once(' '))
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+        .map(|(_, credentials)| credentials.trim())
+    else {
+        return false;
+    };
+    // Compare in constant time so response timing does not leak the token.
+    presented.len() == token.len()
+        && presented
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+struct Response {
+    status: Status,
+    body: Value,
+}
+
+impl Response {
+    fn to_bytes(&self) -> Vec<u8> {
+        let body = serde_json::to_string_pretty(&self.body).unwrap_or_else(|_| "{}".to_string());
+        let mut head = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+             Cache-Control: no-store\r\nConnection: close\r\n",
+            self.status.code(),
+            self.status.reason(),
+            body.len() + 1,
+        );
+        if self.status == Status::Unauthorized {
+            head.push_str("WWW-Authenticate: Bearer\r\n");
+        }
+        if self.status == Status::MethodNotAllowed {
+            head.push_str("Allow: GET\r\n");
+        }
+        head.push_str("\r\n");
+        head.push_str(&body);
+        head.push('\n');
+        head.into_bytes()
+    }
+}
+
+fn error_response(status: Status) -> Response {
+    Response {
+        status,
+        body: json!({ "error": status.reason() }),
+    }
+}
+
+fn respond(request: &Request, port: u16, token: &str) -> Response {
+    if !host_allowed(request.host.as_deref(), port) {
+        return error_response(Status::Forbidden);
+    }
+    if !authorized(request.authorization.as_deref(), token) {
+        return error_response(Status::Unauthorized);
+    }
+    let route: fn() -> Result<Value> = match request.path.as_str() {
+        "/status" => status_json,
+        "/exposure" => exposure_json,
+        "/rules" => rules_json,
+        _ => return error_response(Status::NotFound),
+    };
+    if request.method != "GET" {
+        return error_response(Status::MethodNotAllowed);
+    }
+    match route() {
+        Ok(body) => Response {
+            status: Status::Ok,
+            body,
+        },
+        Err(e) => Response {
+            status: Status::Unavailable,
+            body: json!({ "error": e.to_string() }),
+        },
+    }
+}
+
+fn status_json() -> Result<Value> {
+    // Default zone and panic mode, when firewalld is running
+    let state = firewall::read_blocking(|client| {
+        client.connect().ok()?;
+        Some((
+            client.get_default_zone().ok(),
+            client.query_panic_mode().ok(),
+        ))
+    })
+    .ok()
+    .flatten();
+    let endpoints = NetworkExposure::new().scan().unwrap_or_default();
+    Ok(json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "firewall": {
+            "running": state.is_some(),
+            "default_zone": state.as_ref().and_then(|(zone, _)| zone.clone()),
+            "panic_mode": state.as_ref().and_then(|(_, panic)| *panic),
+        },
+        "exposure": {
+            "listening": endpoints.len(),
+            "all_interfaces": endpoints.iter().filter(|e| e.is_exposed()).count(),
+            "warnings": endpoints.iter().filter(|e| e.warning().is_some()).count(),
+        },
+    }))
+}
+
+fn exposure_json() -> Result<Value> {
+    let endpoints = NetworkExposure::new().scan()?;
+    let endpoints: Vec<Value> = endpoints
+        .iter()
+        .map(|e| {
+            let (firewall, zone) = match &e.firewall_status {
+                FirewallStatus::Allowed { zone } => ("allowed", Some(zone.as_str())),
+                FirewallStatus::Blocked => ("blocked", None),
+                FirewallStatus::Unknown => ("unknown", None),
+            };
+            json!({
+                "address": e.local_addr.to_string(),
+                "port": e.port,
+                "protocol": e.protocol.as_str().to_lowercase(),
+                "pid": e.pid,
+                "process": e.process_name,
+                "all_interfaces": e.is_exposed(),
+                "firewall": firewall,
+                "zone": zone,
+                "warning": e.warning(),
+            })
+        })
+        .collect();
+    Ok(json!({ "endpoints": endpoints }))
+}
+
+fn rules_json() -> Result<Value> {
+    let (default_zone, zones) = firewall::read_blocking(|client| -> Result<_> {
+        client.connect()?;
+        Ok((client.get_default_zone()?, client.get_zones()?))
+    })??;
+    let zones: Vec<Value> = zones
+        .iter()
+        .map(|z| {
+            json!({
+                "name": z.name,
+                "active": z.is_active,
+                "default": z.name == default_zone,
+                "target": z.target,
+                "interfaces": z.interfaces,
+                "sources": z.sources,
+                "services": z.services,
+                "ports": z.ports,
+                "rich_rules": z.rich_rules,
+                "masquerade": z.masquerade,
+            })
+        })
+        .collect();
+    Ok(json!({ "default_zone": default_zone, "zones": zones }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff";
+
+    fn request(path: &str, host: &str, authorization: Option<&str>) -> Request {
+        Request {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            host: Some(host.to_string()),
+            authorization: authorization.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse_request() {
+        let head = "GET /status?pretty=1 HTTP/1.1\r\nHost: 127.0.0.1:8770\r\n\
+                    authorization: Bearer abc\r\nAccept: */*\r\n\r\n";
+        assert_eq!(
+            parse_request(head),
+            Ok(Request {
+                method: "GET".to_string(),
+                path: "/status".to_string(),
+                host: Some("127.0.0.1:8770".to_string()),
+                authorization: Some("Bearer abc".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_request("GET /status\r\n\r\n"),
+            Err(Status::BadRequest)
+        );
+        assert_eq!(
+            parse_request("GET http://evil/ HTTP/1.1\r\n\r\n"),
+            Err(Status::BadRequest)
+        );
+        assert_eq!(
+            parse_request("GET / HTTP/1.1\r\nbroken header\r\n\r\n"),
+            Err(Status::BadRequest)
+        );
+    }
+
+    #[test]
+    fn test_read_request_head_limit() {
+        let mut small: &[u8] = b"GET / HTTP/1.1\r\n\r\n";
+        assert!(read_request_head(&mut small).is_ok());
+        let big = vec![b'a'; MAX_REQUEST_SIZE * 2];
+        assert_eq!(
+            read_request_head(&mut big.as_slice()).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn test_host_allowed() {
+        assert!(host_allowed(Some("127.0.0.1:8770"), 8770));
+        assert!(host_allowed(Some("localhost:8770"), 8770));
+        assert!(host_allowed(Some("localhost"), 8770));
+        assert!(!host_allowed(Some("localhost:9000"), 8770));
+        assert!(!host_allowed(Some("attacker.example:8770"), 8770));
+        assert!(!host_allowed(None, 8770));
+    }
+
+    #[test]
+    fn test_authorized() {
+        let header = format!("Bearer {}", TOKEN);
+        assert!(authorized(Some(&header), TOKEN));
+        assert!(authorized(Some(&format!("bearer {}", TOKEN)), TOKEN));
+        assert!(!authorized(Some("Bearer wrong"), TOKEN));
+        assert!(!authorized(Some(&format!("Basic {}", TOKEN)), TOKEN));
+        assert!(!authorized(Some(TOKEN), TOKEN));
+        assert!(!authorized(None, TOKEN));
+    }
+
+    #[test]
+    fn test_respond_rejections() {
+        let auth = format!("Bearer {}", TOKEN);
+        let status = |r: Request| respond(&r, 8770, TOKEN).status;
+        assert_eq!(
+            status(request("/status", "evil.example", Some(&auth))),
+            Status::Forbidden
+        );
+        assert_eq!(
+            status(request("/status", "127.0.0.1:8770", None)),
+            Status::Unauthorized
+        );
+        assert_eq!(
+            status(request("/secrets", "127.0.0.1:8770", Some(&auth))),
+            Status::NotFound
+        );
+        let mut post = request("/rules", "127.0.0.1:8770", Some(&auth));
+        post.method = "POST".to_string();
+        assert_eq!(status(post), Status::MethodNotAllowed);
+    }
+
+    #[test]
+    fn test_response_bytes() {
+        let bytes = error_response(Status::Unauthorized).to_bytes();
+        let text = String::from_utf8(bytes).unwrap();
+        assert!(text.starts_with("HTTP/1.1 401 Unauthorized\r\n"));
+        assert!(text.contains("WWW-Authenticate: Bearer\r\n"));
+        let (head, body) = text.split_once("\r\n\r\n").unwrap();
+        assert!(head.contains(&format!("Content-Length: {}", body.len())));
+    }
+
+    #[test]
+    fn test_token_helpers() {
+        assert!(is_valid_token(TOKEN));
+        assert!(!is_valid_token("short"));
+        assert!(!is_valid_token(&"z".repeat(TOKEN_BYTES * 2)));
+        assert_eq!(clamp_api_port(80), API_PORT_MIN);
+        assert_eq!(clamp_api_port(DEFAULT_API_PORT), DEFAULT_API_PORT);
+    }
+}
@@ -4,18 +4,36 @@
 
 //! Main application struct and lifecycle management.
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::net::IpAddr;
+use std::time::Duration;
 
 use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
 use gtk4::{gio, glib};
 use libadwaita as adw;
 use libadwaita::prelude::*;
-use tracing::info;
+use tracing::{info, warn};
 
+use crate::admin::{self, Operation, OperationRunner, TripwireHit};
+use crate::api::{self, ApiServer};
 use crate::config::Settings;
-use crate::i18n::gettext;
-use crate::ui::MainWindow;
+use crate::data_usage::{self, UsageMeter};
+use crate::i18n::{format_bytes, format_datetime, gettext};
+use crate::monitor::{self, Notice};
+use crate::rule_expiry::{self, ExpiryStep, ExpiryWatch};
+use crate::safe_mode;
+use crate::security_scan;
+use crate::status_feed::{self, StatusFeed};
+use crate::tray::{Tray, TrayCommand};
+use crate::ui::density::{self, Density};
+use crate::ui::palette::{self, StatusPalette};
+use crate::ui::{Invalidation, MainWindow};
+
+/// Pages offered as the startup page, in the order of the preferences list.
+const STARTUP_PAGES: [&str; 3] = ["overview", "network-exposure", "ports"];
+/// How often clicks in the tray are picked up.
+const TRAY_POLL_INTERVAL: Duration = Duration::from_millis(200);
 
 glib::wrapper! {
     /// The main application object.
@@ -51,7 +69,47 @@ impl Application {
             })
             .build();
 
-        self.add_action_entries([quit_action, about_action, preferences_action]);
+        // Offered by tripwire notifications; the target is the address
+        let block_source_action = gio::ActionEntry::builder("block-source")
+            .parameter_type(Some(glib::VariantTy::STRING))
+            .activate(|app: &Self, _, parameter| {
+                let source = parameter
+                    .and_then(|p| p.get::<String>())
+                    .and_then(|s| s.parse::<IpAddr>().ok());
+                if let Some(source) = source {
+                    app.block_source(source);
+                }
+            })
+            .build();
+
+        // Offered by rule expiry notifications; the target is the rule's
+        // storage key
+        let extend_rule_action = gio::ActionEntry::builder("extend-rule")
+            .parameter_type(Some(glib::VariantTy::STRING))
+            .activate(|app: &Self, _, parameter| {
+                if let Some(key) = parameter.and_then(|p| p.get::<String>()) {
+                    app.extend_rule(key);
+                }
+            })
+            .build();
+
+        let remove_rule_action = gio::ActionEntry::builder("remove-rule")
+            .parameter_type(Some(glib::VariantTy::STRING))
+            .activate(|app: &Self, _, parameter| {
+                if let Some(key) = parameter.and_then(|p| p.get::<String>()) {
+                    app.remove_rule(key);
+                }
+            })
+            .build();
+
+        self.add_action_entries([
+            quit_action,
+            about_action,
+            preferences_action,
+            block_source_action,
+            extend_rule_action,
+            remove_rule_action,
+        ]);
     }
 
     fn show_preferences_dialog(&self) {
@@ -95,6 +153,60 @@ impl Application {
         });
 
         appearance_group.add(&theme_row);
+
+        // Status colours for colour vision deficiencies
+        let palette_labels: Vec<String> = StatusPalette::ALL
+            .iter()
+            .map(|p| gettext(p.label()))
+            .collect();
+        let palette_refs: Vec<&str> = palette_labels.iter().map(|s| s.as_str()).collect();
+        let palette_row = adw::ComboRow::builder()
+            .title(gettext("Status Colors"))
+            .subtitle(gettext(
+                "Colors used for good, warning and error states; icons always mark the state too",
+            ))
+            .model(&gtk4::StringList::new(&palette_refs))
+            .build();
+        let current_palette = palette::current();
+        let index = StatusPalette::ALL
+            .iter()
+            .position(|p| *p == current_palette)
+            .unwrap_or(0);
+        palette_row.set_selected(index as u32);
+
+        let app = self.clone();
+        palette_row.connect_selected_notify(move |row| {
+            if let Some(palette) = StatusPalette::ALL.get(row.selected() as usize) {
+                app.set_status_palette(*palette);
+            }
+        });
+        appearance_group.add(&palette_row);
+
+        // Tighter layout for small screens
+        let density_labels: Vec<String> = Density::ALL.iter().map(|d| gettext(d.label())).collect();
+        let density_refs: Vec<&str> = density_labels.iter().map(|s| s.as_str()).collect();
+        let density_row = adw::ComboRow::builder()
+            .title(gettext("Density"))
+            .subtitle(gettext(
+                "Compact reduces spacing and chart sizes for small screens",
+            ))
+            .model(&gtk4::StringList::new(&density_refs))
+            .build();
+        let current_density = density::current();
+        let index = Density::ALL
+            .iter()
+            .position(|d| *d == current_density)
+            .unwrap_or(0);
+        density_row.set_selected(index as u32);
+
+        let app = self.clone();
+        density_row.connect_selected_notify(move |row| {
+            if let Some(density) = Density::ALL.get(row.selected() as usize) {
+                app.set_density(*density);
+            }
+        });
+        appearance_group.add(&density_row);
+
         page.add(&appearance_group);
 
         let behavior_group = adw::PreferencesGroup::builder()
@@ -121,6 +233,34 @@ impl Application {
 
         behavior_group.add(&autostart_row);
 
+        let monitor_row = adw::SwitchRow::builder()
+            .title(gettext("Monitor in Background"))
+            .subtitle(gettext(
+                "Keep running scheduled cleanups, policy enforcement, security scans, data quotas and tripwire alerts while Security Center is closed",
+            ))
+            .active(monitor::is_service_enabled())
+            .build();
+
+        let dialog_weak = dialog.downgrade();
+        monitor_row.connect_active_notify(move |row| {
+            let enabled = row.is_active();
+            let dialog_weak = dialog_weak.clone();
+            glib::spawn_future_local(async move {
+                let result =
+                    gio::spawn_blocking(move || monitor::set_service_enabled(enabled)).await;
+                let message = match result {
+                    Ok(Ok(())) => return,
+                    Ok(Err(e)) => format!("{}: {}", gettext("Failed to change the monitor"), e),
+                    Err(_) => gettext("Failed to change the monitor"),
+                };
+                warn!("{}", message);
+                if let Some(dialog) = dialog_weak.upgrade() {
+                    dialog.add_toast(adw::Toast::new(&message));
+                }
+            });
+        });
+        behavior_group.add(&monitor_row);
+
         // Toggle the live firewall connections overview on the dashboard.
         let connections_enabled = self.imp().settings.borrow().show_connections_overview();
         let connections_row = adw::SwitchRow::builder()
@@ -193,11 +333,103 @@ impl Application {
         });
         behavior_group.add(&online_row);
 
-        // Note: no system-tray toggle here. The app has no tray backend, and an
-        // inert switch that silently does nothing erodes trust in every other
-        // control. Reintroduce it together with an actual StatusNotifierItem.
+        let tray_row = adw::SwitchRow::builder()
+            .title(gettext("Show in System Tray"))
+            .subtitle(gettext(
+                "Keep running in the background with a tray icon when the window is closed",
+            ))
+            .active(self.imp().tray.borrow().is_some())
+            .build();
+
+        let app = self.clone();
+        let dialog_weak = dialog.downgrade();
+        tray_row.connect_active_notify(move |row| {
+            let enabled = row.is_active();
+            match app.set_tray_enabled(enabled) {
+                Ok(()) => app.imp().settings.borrow_mut().set_show_tray_icon(enabled),
+                Err(e) => {
+                    warn!("Failed to show the tray icon: {:#}", e);
+                    row.set_active(false);
+                    if let Some(dialog) = dialog_weak.upgrade() {
+                        dialog.add_toast(adw::Toast::new(&format!(
+                            "{}: {}",
+                            gettext("Failed to show the tray icon"),
+                            e
+                        )));
+                    }
+                }
+            }
+        });
+        behavior_group.add(&tray_row);
+
+        let startup_row = adw::ComboRow::builder()
+            .title(gettext("Startup Page"))
+            .subtitle(gettext("Page shown when Security Center opens"))
+            .model(&gtk4::StringList::new(&[
+                gettext("Overview").as_str(),
+                gettext("Network Exposure").as_str(),
+                gettext("Ports").as_str(),
+            ]))
+            .build();
+        let current_startup = STARTUP_PAGES
+            .iter()
+            .position(|p| *p == self.imp().settings.borrow().startup_page())
+            .unwrap_or(0);
+        startup_row.set_selected(current_startup as u32);
+
+        let app = self.clone();
+        startup_row.connect_selected_notify(move |row| {
+            if let Some(page) = STARTUP_PAGES.get(row.selected() as usize) {
+                app.imp().settings.borrow_mut().set_startup_page(page);
+            }
+        });
+        behavior_group.add(&startup_row);
+
+        let resume_row = adw::SwitchRow::builder()
+            .title(gettext("Resume Last Scan"))
+            .subtitle(gettext(
+                "Open the page of the last Network Exposure, Platform Security or Cleanup scan and run it again",
+            ))
+            .active(self.imp().settings.borrow().resume_last_task())
+            .build();
+
+        let app = self.clone();
+        resume_row.connect_active_notify(move |row| {
+            app.imp()
+                .settings
+                .borrow_mut()
+                .set_resume_last_task(row.is_active());
+        });
+        behavior_group.add(&resume_row);
+
+        // Session only: started with --safe-mode, turned off here
+        let safe_mode_row = adw::SwitchRow::builder()
+            .title(gettext("Safe Mode"))
+            .subtitle(gettext(
+                "Pause statistics collection, auto-refresh, update checks and background scans until Security Center restarts",
+            ))
+            .active(safe_mode::is_enabled())
+            .build();
+
+        let app = self.clone();
+        safe_mode_row.connect_active_notify(move |row| {
+            let enabled = row.is_active();
+            safe_mode::set_enabled(enabled);
+            info!("Safe mode {}", if enabled { "on" } else { "off" });
+            if !enabled {
+                if let Some(window) = app.imp().window.get() {
+                    window.resume_background();
+                }
+            }
+        });
+        behavior_group.add(&safe_mode_row);
 
         page.add(&behavior_group);
+        page.add(&self.create_api_group(&dialog));
+        page.add(&self.create_status_feed_group(&dialog));
+        page.add(&self.create_data_quota_group());
+        page.add(&self.create_tripwire_group(&dialog));
+        page.add(&self.create_security_scan_group());
 
         dialog.add(&page);
 
@@ -206,11 +438,741 @@ impl Application {
         }
     }
 
+    /// Preferences for the read-only localhost JSON API.
+    fn create_api_group(&self, dialog: &adw::PreferencesDialog) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder()
+            .title(gettext("Local API"))
+            .description(gettext(
+                "Read-only JSON endpoints (/status, /exposure, /rules) on 127.0.0.1 for scripts and local dashboards",
+            ))
+            .build();
+
+        let enabled_row = adw::SwitchRow::builder()
+            .title(gettext("Enable Local API"))
+            .subtitle(gettext(
+                "Every request must send the access token as a Bearer authorization header",
+            ))
+            .active(self.imp().settings.borrow().api_enabled())
+            .build();
+
+        let app = self.clone();
+        let dialog_weak = dialog.downgrade();
+        enabled_row.connect_active_notify(move |row| {
+            app.imp()
+                .settings
+                .borrow_mut()
+                .set_api_enabled(row.is_active());
+            if let Err(e) = app.restart_api() {
+                if let Some(dialog) = dialog_weak.upgrade() {
+                    dialog.add_toast(adw::Toast::new(&e.to_string()));
+                }
+            }
+        });
+        group.add(&enabled_row);
+
+        let port_row = adw::SpinRow::builder()
+            .title(gettext("Port"))
+            .adjustment(&gtk4::Adjustment::new(
+                self.imp().settings.borrow().api_port() as f64,
+                api::API_PORT_MIN as f64,
+                u16::MAX as f64,
+                1.0,
+                10.0,
+                0.0,
+            ))
+            .build();
+
+        let app = self.clone();
+        let dialog_weak = dialog.downgrade();
+        port_row.connect_value_notify(move |row| {
+            let port = row.value().round() as u16;
+            app.imp().settings.borrow_mut().set_api_port(port);
+            if let Err(e) = app.restart_api() {
+                if let Some(dialog) = dialog_weak.upgrade() {
+                    dialog.add_toast(adw::Toast::new(&e.to_string()));
+                }
+            }
+        });
+        group.add(&port_row);
+
+        let token_row = adw::ActionRow::builder()
+            .title(gettext("Access Token"))
+            .subtitle(glib::markup_escape_text(
+                &api::token_path().display().to_string(),
+            ))
+            .build();
+
+        let copy_button = gtk4::Button::builder()
+            .icon_name("edit-copy-symbolic")
+            .tooltip_text(gettext("Copy Token"))
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat"])
+            .build();
+        let dialog_weak = dialog.downgrade();
+        copy_button.connect_clicked(move |button| {
+            let message = match api::load_or_create_token() {
+                Ok(token) => {
+                    button.clipboard().set_text(&token);
+                    gettext("Token copied to clipboard")
+                }
+                Err(e) => e.to_string(),
+            };
+            if let Some(dialog) = dialog_weak.upgrade() {
+                dialog.add_toast(adw::Toast::new(&message));
+            }
+        });
+        token_row.add_suffix(&copy_button);
+
+        let regenerate_button = gtk4::Button::builder()
+            .icon_name("view-refresh-symbolic")
+            .tooltip_text(gettext("Generate New Token"))
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat"])
+            .build();
+        let app = self.clone();
+        let dialog_weak = dialog.downgrade();
+        regenerate_button.connect_clicked(move |_| {
+            let message = match api::regenerate_token().and_then(|_| app.restart_api()) {
+                Ok(()) => gettext("New token generated; clients using the old one are rejected"),
+                Err(e) => e.to_string(),
+            };
+            if let Some(dialog) = dialog_weak.upgrade() {
+                dialog.add_toast(adw::Toast::new(&message));
+            }
+        });
+        token_row.add_suffix(&regenerate_button);
+        group.add(&token_row);
+
+        group
+    }
+
+    /// Preferences for the status file read by desktop widgets.
+    fn create_status_feed_group(&self, dialog: &adw::PreferencesDialog) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder()
+            .title(gettext("Desktop Widgets"))
+            .description(gettext(
+                "A status file with the hardening score and exposed port count, for shell extensions and panel widgets",
+            ))
+            .build();
+
+        let enabled_row = adw::SwitchRow::builder()
+            .title(gettext("Publish Status"))
+            .subtitle(glib::markup_escape_text(
+                &status_feed::feed_path().display().to_string(),
+            ))
+            .active(self.imp().settings.borrow().status_feed_enabled())
+            .build();
+
+        let app = self.clone();
+        let dialog_weak = dialog.downgrade();
+        enabled_row.connect_active_notify(move |row| {
+            app.imp()
+                .settings
+                .borrow_mut()
+                .set_status_feed_enabled(row.is_active());
+            if let Err(e) = app.restart_status_feed() {
+                if let Some(dialog) = dialog_weak.upgrade() {
+                    dialog.add_toast(adw::Toast::new(&e.to_string()));
+                }
+            }
+        });
+        group.add(&enabled_row);
+
+        group
+    }
+
+    /// Preferences for the monthly data quotas of metered interfaces.
+    fn create_data_quota_group(&self) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder()
+            .title(gettext("Data Quotas"))
+            .description(gettext(
+                "Monthly limits for metered connections, in GB; 0 means no limit. Traffic is counted while Security Center runs",
+            ))
+            .build();
+
+        let quotas = self.imp().settings.borrow().data_quotas().clone();
+        let mut interfaces: Vec<String> = data_usage::read_counters()
+            .into_iter()
+            .map(|(iface, _)| iface)
+            .collect();
+        // Keep quotas of interfaces that are down right now
+        interfaces.extend(quotas.keys().cloned());
+        interfaces.sort();
+        interfaces.dedup();
+
+        if interfaces.is_empty() {
+            group.add(
+                &adw::ActionRow::builder()
+                    .title(gettext("No network interfaces found"))
+                    .css_classes(vec!["dim-label".to_string()])
+                    .build(),
+            );
+            return group;
+        }
+
+        for iface in interfaces {
+            let used = self
+                .imp()
+                .data_usage
+                .borrow()
+                .as_ref()
+                .map_or(0, |meter| meter.used(&iface));
+            let quota_gb = quotas
+                .get(&iface)
+                .map_or(0, |q| q / data_usage::BYTES_PER_GB);
+            let row = adw::SpinRow::builder()
+                .title(glib::markup_escape_text(&iface).as_str())
+                .subtitle(gettext("%s used this month").replace("%s", &format_bytes(used)))
+                .adjustment(&gtk4::Adjustment::new(
+                    quota_gb as f64,
+                    0.0,
+                    100_000.0,
+                    1.0,
+                    10.0,
+                    0.0,
+                ))
+                .build();
+
+            let app = self.clone();
+            row.connect_value_notify(move |row| {
+                let bytes = row.value().round() as u64 * data_usage::BYTES_PER_GB;
+                app.imp()
+                    .settings
+                    .borrow_mut()
+                    .set_data_quota(&iface, bytes);
+                app.reload_monitor();
+                app.sample_data_usage();
+            });
+            group.add(&row);
+        }
+
+        group
+    }
+
+    /// Preferences for the tripwire ports.
+    fn create_tripwire_group(&self, dialog: &adw::PreferencesDialog) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder()
+            .title(gettext("Tripwire Ports"))
+            .description(gettext(
+                "Unused ports such as 23 or 3389 that nothing should connect to. The firewall must deny them, with Log Denied Packets turned on in the Firewall Log. A probe's source can be forged, so its alert offers to block the address instead of blocking it, at most five times an hour and never for local addresses, the gateway or DNS servers",
+            ))
+            .build();
+
+        let ports = self.imp().settings.borrow().tripwire_ports().to_vec();
+        let ports_row = adw::EntryRow::builder()
+            .title(gettext("Ports, separated by commas"))
+            .text(
+                ports
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+            .show_apply_button(true)
+            .build();
+
+        let app = self.clone();
+        let dialog_weak = dialog.downgrade();
+        ports_row.connect_apply(move |row| match admin::parse_ports(&row.text()) {
+            Ok(ports) => {
+                row.remove_css_class("error");
+                app.imp()
+                    .settings
+                    .borrow_mut()
+                    .set_tripwire_ports(ports.clone());
+                if let Some(window) = app.imp().window.get() {
+                    window.set_tripwire_ports(&ports);
+                }
+                app.reload_monitor();
+            }
+            Err(e) => {
+                row.add_css_class("error");
+                if let Some(dialog) = dialog_weak.upgrade() {
+                    dialog.add_toast(adw::Toast::new(&e.to_string()));
+                }
+            }
+        });
+        group.add(&ports_row);
+
+        group
+    }
+
+    /// Preferences for the scheduled security scan.
+    fn create_security_scan_group(&self) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder()
+            .title(gettext("Scheduled Scan"))
+            .description(gettext(
+                "Scan the listening ports and run the security checks in the background, with a notification when a port becomes reachable from the network or a check starts failing",
+            ))
+            .build();
+
+        let labels = [
+            gettext("Off"),
+            gettext("Every hour"),
+            gettext("Every 6 hours"),
+            gettext("Every day"),
+        ];
+        let label_refs: Vec<&str> = labels.iter().map(|s| s.as_str()).collect();
+        let settings = self.imp().settings.borrow();
+        let last_run = settings
+            .security_scan_last_run()
+            .map(|t| gettext("Last scan %s").replace("%s", &format_datetime(t)))
+            .unwrap_or_else(|| gettext("Not scanned yet"));
+        let interval_row = adw::ComboRow::builder()
+            .title(gettext("Scan Interval"))
+            .subtitle(last_run)
+            .model(&gtk4::StringList::new(&label_refs))
+            .build();
+        let current = security_scan::INTERVALS
+            .iter()
+            .position(|h| *h == settings.security_scan_interval_hours())
+            .unwrap_or(0);
+        drop(settings);
+        interval_row.set_selected(current as u32);
+
+        let app = self.clone();
+        interval_row.connect_selected_notify(move |row| {
+            let hours = security_scan::INTERVALS
+                .get(row.selected() as usize)
+                .copied()
+                .unwrap_or(0);
+            app.imp()
+                .settings
+                .borrow_mut()
+                .set_security_scan_interval_hours(hours);
+            app.reload_monitor();
+        });
+        group.add(&interval_row);
+
+        group
+    }
+
+    /// Alert about a probe of a tripwire port, offering to block the source
+    /// if `offer_block`.
+    pub fn report_tripwire_hit(&self, hit: &TripwireHit, offer_block: bool) {
+        warn!(
+            "Tripwire: {} probed port {}/{}",
+            hit.source,
+            hit.port,
+            hit.protocol.to_lowercase()
+        );
+
+        self.send_notice(&monitor::tripwire_notice(hit, offer_block));
+    }
+
+    /// Drop all traffic from `source` in the default zone and report the
+    /// outcome in the main window.
+    fn block_source(&self, source: IpAddr) {
+        let app = self.clone();
+        glib::spawn_future_local(async move {
+            let result = gio::spawn_blocking(move || admin::block_source(source)).await;
+            let message = match result {
+                Ok(result) => {
+                    if let Err(e) = &result {
+                        warn!("Failed to block {}: {:#}", source, e);
+                    }
+                    monitor::block_result_text(source, &result)
+                }
+                Err(_) => gettext("Failed to block address"),
+            };
+            if let Some(window) = app.imp().window.get() {
+                window.show_toast(&message);
+            }
+        });
+    }
+
+    /// Check temporary rules for expiry from now on, leaving it to the
+    /// background monitor while it runs.
+    fn start_rule_expiry(&self) {
+        self.imp().rule_expiry_started.set(true);
+
+        let app = self.downgrade();
+        glib::timeout_add_local(rule_expiry::CHECK_INTERVAL, move || {
+            let Some(app) = app.upgrade() else {
+                return glib::ControlFlow::Break;
+            };
+            if !app.monitor_running() {
+                app.check_rule_expiry();
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+
+    /// Warn about temporary rules about to expire and remove expired ones.
+    fn check_rule_expiry(&self) {
+        let now = chrono::Utc::now().timestamp();
+        let steps = rule_expiry::check_due(&mut self.imp().expiry_watch.borrow_mut());
+        for step in steps {
+            match step {
+                ExpiryStep::Warn(rule) => {
+                    info!("Temporary rule {} expires soon", rule.key);
+                    self.send_notice(&monitor::expiry_notice(&rule, now));
+                }
+                ExpiryStep::Expire(rule) => {
+                    info!("Temporary rule {} expired", rule.key);
+                    self.remove_rule(rule.key);
+                }
+            }
+        }
+    }
+
+    /// Give a temporary rule another hour and report the outcome.
+    fn extend_rule(&self, key: String) {
+        let result = rule_expiry::extend_rule(&key);
+        if let Err(e) = &result {
+            warn!("Failed to extend {}: {:#}", key, e);
+        }
+        self.finish_rule_action(&key, monitor::extend_result_text(&result));
+    }
+
+    /// Remove a temporary rule from firewalld and report the outcome.
+    fn remove_rule(&self, key: String) {
+        let app = self.clone();
+        glib::spawn_future_local(async move {
+            let task_key = key.clone();
+            let result = gio::spawn_blocking(move || rule_expiry::remove_rule(&task_key)).await;
+            let message = match result {
+                Ok(result) => {
+                    if let Err(e) = &result {
+                        warn!("Failed to remove {}: {:#}", key, e);
+                    }
+                    monitor::remove_result_text(&result)
+                }
+                Err(_) => gettext("Failed to remove temporary rule"),
+            };
+            app.finish_rule_action(&key, message);
+        });
+    }
+
+    /// Replace the rule's expiry notification with `message`, and show it
+    /// in the main window with the rules reloaded.
+    fn finish_rule_action(&self, key: &str, message: String) {
+        self.withdraw_notification(&format!("rule-expiry-{}", key));
+        match self.imp().window.get() {
+            Some(window) => {
+                window.show_toast(&message);
+                window.invalidate(Invalidation::PortsChanged);
+            }
+            None => self.send_notice(&monitor::expiry_result_notice(key, message)),
+        }
+    }
+
+    /// Run the scheduled security scan when due from now on, leaving it to
+    /// the background monitor while it runs.
+    fn start_security_scans(&self) {
+        self.imp().security_scan_started.set(true);
+
+        let app = self.downgrade();
+        glib::timeout_add_local(security_scan::CHECK_INTERVAL, move || {
+            let Some(app) = app.upgrade() else {
+                return glib::ControlFlow::Break;
+            };
+            if !app.monitor_running() && !safe_mode::is_enabled() {
+                app.scan_if_due();
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+
+    /// Scan in the background when the schedule is due and notify about
+    /// what is new since the previous scan.
+    fn scan_if_due(&self) {
+        let now = chrono::Utc::now().timestamp();
+        let due = {
+            let settings = self.imp().settings.borrow();
+            security_scan::scan_due(
+                settings.security_scan_last_run(),
+                settings.security_scan_interval_hours(),
+                now,
+            )
+        };
+        if !due || self.imp().security_scan_running.replace(true) {
+            return;
+        }
+
+        let app = self.clone();
+        glib::spawn_future_local(async move {
+            let alerts = gio::spawn_blocking(security_scan::run_scheduled_scan).await;
+            app.imp().security_scan_running.set(false);
+            app.imp()
+                .settings
+                .borrow_mut()
+                .set_security_scan_last_run(now);
+            let Ok(alerts) = alerts else {
+                return;
+            };
+            info!("Scheduled security scan: {} new findings", alerts.len());
+            for alert in &alerts {
+                app.send_notice(&monitor::scan_notice(alert));
+            }
+        });
+    }
+
+    /// Count traffic per interface for the data quotas from now on, or show
+    /// the counts of the background monitor while it runs.
+    fn start_data_usage_meter(&self) {
+        self.imp().data_usage_started.set(true);
+        if !safe_mode::is_enabled() {
+            self.sample_data_usage();
+        }
+
+        let app = self.downgrade();
+        glib::timeout_add_local(data_usage::SAMPLE_INTERVAL, move || {
+            let Some(app) = app.upgrade() else {
+                return glib::ControlFlow::Break;
+            };
+            if !safe_mode::is_enabled() {
+                app.sample_data_usage();
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+
+    /// Update the Overview usage card from the background monitor, or count
+    /// here when it is not running.
+    fn sample_data_usage(&self) {
+        let app = self.clone();
+        glib::spawn_future_local(async move {
+            let remote = gio::spawn_blocking(monitor::query_data_usage)
+                .await
+                .ok()
+                .flatten();
+            app.imp().monitor_running.set(remote.is_some());
+            match remote {
+                Some(usage) => {
+                    // The monitor keeps the counters while it runs
+                    if let Some(mut meter) = app.imp().data_usage.take() {
+                        meter.save();
+                    }
+                    if let Some(window) = app.imp().window.get() {
+                        window.set_data_usage(&usage);
+                    }
+                }
+                None => app.sample_data_usage_here(),
+            }
+        });
+    }
+
+    /// Advance the usage counters, notify about quota thresholds crossed
+    /// and update the Overview usage card.
+    fn sample_data_usage_here(&self) {
+        let quotas = self.imp().settings.borrow().data_quotas().clone();
+        let (alerts, usage) = {
+            let mut meter = self.imp().data_usage.borrow_mut();
+            let meter = meter.get_or_insert_with(UsageMeter::load);
+            let alerts = meter.sample(
+                &data_usage::read_counters(),
+                &data_usage::current_month(),
+                &quotas,
+            );
+            meter.save_if_due();
+            (alerts, meter.usage(&quotas))
+        };
+
+        for alert in &alerts {
+            info!(
+                "Data quota alert: {} at {}% ({} of {} bytes)",
+                alert.interface, alert.threshold, alert.used, alert.quota
+            );
+            self.send_notice(&monitor::quota_notice(alert));
+        }
+        if let Some(window) = self.imp().window.get() {
+            window.set_data_usage(&usage);
+        }
+    }
+
+    /// Send a desktop notification, replacing the previous one with the
+    /// same tag.
+    fn send_notice(&self, notice: &Notice) {
+        let notification = gio::Notification::new(&notice.title);
+        notification.set_body(Some(&notice.body));
+        notification.set_icon(&gio::ThemedIcon::new(notice.icon));
+        if notice.urgent {
+            notification.set_priority(gio::NotificationPriority::High);
+        }
+        if let Some(source) = notice.block_source {
+            notification.add_button_with_target_value(
+                &gettext("Block Address"),
+                "app.block-source",
+                Some(&source.to_string().to_variant()),
+            );
+        }
+        if let Some(key) = &notice.expiring_rule {
+            notification.add_button_with_target_value(
+                &gettext("Extend by 1 hour"),
+                "app.extend-rule",
+                Some(&key.to_variant()),
+            );
+            notification.add_button_with_target_value(
+                &gettext("Remove now"),
+                "app.remove-rule",
+                Some(&key.to_variant()),
+            );
+        }
+        self.send_notification(Some(&notice.tag), &notification);
+    }
+
+    /// Whether the background monitor answered the last data usage sample,
+    /// in which case it does the scheduled work and sends the alerts.
+    pub fn monitor_running(&self) -> bool {
+        self.imp().monitor_running.get()
+    }
+
+    /// Have the background monitor pick up changed settings now.
+    fn reload_monitor(&self) {
+        if self.monitor_running() {
+            gio::spawn_blocking(monitor::reload);
+        }
+    }
+
+    /// Show or remove the tray icon. While it shows, the application holds
+    /// itself and closing the window only hides it.
+    fn set_tray_enabled(&self, enabled: bool) -> anyhow::Result<()> {
+        let imp = self.imp();
+        if enabled == imp.tray.borrow().is_some() {
+            return Ok(());
+        }
+        if enabled {
+            let tray = Tray::start()?;
+            if let Some(window) = imp.window.get() {
+                tray.set_panic_mode(window.panic_mode());
+            }
+            imp.tray.replace(Some(tray));
+            imp.tray_hold.replace(Some(self.hold()));
+            self.start_tray_commands();
+        } else {
+            imp.tray.replace(None);
+            imp.tray_hold.replace(None);
+        }
+        if let Some(window) = imp.window.get() {
+            window.set_hide_on_close(enabled);
+        }
+        Ok(())
+    }
+
+    /// Act on clicks in the tray for as long as it shows.
+    fn start_tray_commands(&self) {
+        let app = self.downgrade();
+        glib::timeout_add_local(TRAY_POLL_INTERVAL, move || {
+            let Some(app) = app.upgrade() else {
+                return glib::ControlFlow::Break;
+            };
+            let commands = match app.imp().tray.borrow().as_ref() {
+                Some(tray) => tray.take_commands(),
+                None => return glib::ControlFlow::Break,
+            };
+            for command in commands {
+                app.handle_tray_command(command);
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+
+    fn handle_tray_command(&self, command: TrayCommand) {
+        let Some(window) = self.imp().window.get() else {
+            return;
+        };
+        match command {
+            TrayCommand::OpenWindow => window.present(),
+            TrayCommand::SetPanicMode(enabled) => window.request_panic_mode(enabled),
+            TrayCommand::ReloadFirewall => self.reload_firewall_from_tray(),
+            TrayCommand::Quit => self.quit(),
+        }
+    }
+
+    /// Reload firewalld and report the outcome in the window, or as a
+    /// notification while it is hidden.
+    fn reload_firewall_from_tray(&self) {
+        let app = self.clone();
+        glib::spawn_future_local(async move {
+            let result =
+                gio::spawn_blocking(|| OperationRunner::new().run(Operation::ReloadFirewall)).await;
+            let message = match result {
+                Ok(result) if result.success => gettext("Firewall reloaded"),
+                Ok(result) => format!(
+                    "{}: {}",
+                    gettext("Failed to reload the firewall"),
+                    result.message
+                ),
+                Err(_) => gettext("Failed to reload the firewall"),
+            };
+            let Some(window) = app.imp().window.get() else {
+                return;
+            };
+            window.refresh_data();
+            if window.is_visible() {
+                window.show_toast(&message);
+            } else {
+                app.send_notice(&Notice {
+                    tag: "tray-reload".to_string(),
+                    title: message,
+                    body: String::new(),
+                    icon: "view-refresh-symbolic",
+                    urgent: false,
+                    block_source: None,
+                    expiring_rule: None,
+                });
+            }
+        });
+    }
+
+    /// Show in the tray whether panic mode is on.
+    pub fn set_tray_panic_mode(&self, enabled: bool) {
+        if let Some(tray) = self.imp().tray.borrow().as_ref() {
+            tray.set_panic_mode(enabled);
+        }
+    }
+
+    /// Stop the status feed and start it again when enabled.
+    fn restart_status_feed(&self) -> anyhow::Result<()> {
+        self.imp().status_feed.replace(None);
+        if self.imp().settings.borrow().status_feed_enabled() {
+            self.imp().status_feed.replace(Some(StatusFeed::start()?));
+        }
+        Ok(())
+    }
+
+    /// Stop the local API and start it again when enabled, picking up the
+    /// current port and token.
+    fn restart_api(&self) -> anyhow::Result<()> {
+        self.imp().api_server.replace(None);
+        let (enabled, port) = {
+            let settings = self.imp().settings.borrow();
+            (settings.api_enabled(), settings.api_port())
+        };
+        if enabled {
+            self.imp().api_server.replace(Some(ApiServer::start(port)?));
+        }
+        Ok(())
+    }
+
     pub fn set_theme(&self, theme: &str) {
         self.imp().settings.borrow_mut().set_theme(theme);
         self.apply_theme(theme);
     }
 
+    pub fn set_status_palette(&self, palette: StatusPalette) {
+        self.imp()
+            .settings
+            .borrow_mut()
+            .set_status_palette(palette.id());
+        palette::apply(palette);
+    }
+
+    /// Remember the page whose scan just ran, for "Resume Last Scan".
+    /// Recorded through the application's settings so that later
+    /// preference changes, which save the whole file, keep it.
+    pub fn record_last_task(&self, page: &str) {
+        self.imp().settings.borrow_mut().set_last_task(page);
+    }
+
+    pub fn set_density(&self, density: Density) {
+        self.imp().settings.borrow_mut().set_density(density.id());
+        if let Some(window) = self.imp().window.get() {
+            density::apply(window, density);
+        }
+    }
+
     fn apply_theme(&self, theme: &str) {
         let style_manager = adw::StyleManager::default();
         match theme {
@@ -239,6 +1201,7 @@ impl Application {
     fn setup_shortcuts(&self) {
         self.set_accels_for_action("app.quit", &["<Control>q"]);
         self.set_accels_for_action("win.refresh", &["<Control>r", "F5"]);
+        self.set_accels_for_action("win.search-rules", &["<Control><Shift>f"]);
     }
 
     fn show_about_dialog(&self) {
@@ -471,6 +1434,17 @@ mod imp {
     pub struct Application {
         pub window: OnceCell<MainWindow>,
         pub settings: RefCell<Settings>,
+        pub api_server: RefCell<Option<ApiServer>>,
+        pub status_feed: RefCell<Option<StatusFeed>>,
+        pub data_usage: RefCell<Option<UsageMeter>>,
+        pub data_usage_started: Cell<bool>,
+        pub monitor_running: Cell<bool>,
+        pub expiry_watch: RefCell<ExpiryWatch>,
+        pub rule_expiry_started: Cell<bool>,
+        pub security_scan_started: Cell<bool>,
+        pub security_scan_running: Cell<bool>,
+        pub tray: RefCell<Option<Tray>>,
+        pub tray_hold: RefCell<Option<gio::ApplicationHoldGuard>>,
     }
 
     #[glib::object_subclass]
@@ -495,13 +1469,57 @@ mod imp {
 
             let theme = self.settings.borrow().theme().to_string();
             app.apply_theme(&theme);
+            let status_palette = self.settings.borrow().status_palette().to_string();
+            palette::apply(StatusPalette::from_id(&status_palette));
 
             app.setup_actions();
             app.setup_shortcuts();
 
-            let window = self.window.get_or_init(|| MainWindow::new(&*app));
+            let initial_page = self.settings.borrow().initial_page().to_string();
+            let window = self
+                .window
+                .get_or_init(|| MainWindow::new(&*app, &initial_page));
+            let saved_density = self.settings.borrow().density().to_string();
+            density::apply(window, Density::from_id(&saved_density));
+            window.set_tripwire_ports(self.settings.borrow().tripwire_ports());
 
             window.present();
+
+            if self.settings.borrow().show_tray_icon() && self.tray.borrow().is_none() {
+                if let Err(e) = app.set_tray_enabled(true) {
+                    warn!("Failed to show the tray icon: {:#}", e);
+                }
+            }
+
+            if self.settings.borrow().api_enabled() && self.api_server.borrow().is_none() {
+                if let Err(e) = app.restart_api() {
+                    warn!("Failed to start the local API: {}", e);
+                }
+            }
+            if self.settings.borrow().status_feed_enabled() && self.status_feed.borrow().is_none() {
+                if let Err(e) = app.restart_status_feed() {
+                    warn!("Failed to start the status feed: {}", e);
+                }
+            }
+            if !self.data_usage_started.get() {
+                app.start_data_usage_meter();
+            }
+            if !self.rule_expiry_started.get() {
+                app.start_rule_expiry();
+            }
+            if !self.security_scan_started.get() {
+                app.start_security_scans();
+            }
+        }
+
+        fn shutdown(&self) {
+            // Removes the status file so widgets stop showing it as current
+            self.status_feed.replace(None);
+            self.tray.replace(None);
+            if let Some(meter) = self.data_usage.borrow_mut().as_mut() {
+                meter.save();
+            }
+            self.parent_shutdown();
         }
 
         fn startup(&self) {
@@ -0,0 +1,24 @@
+// Security Center - Privileged Helper Entry Point
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! The program pkexec starts for the privileged helper operations. It runs
+//! nothing but [`helper::run_helper`]: no window, no settings and no other
+//! mode, so the polkit policy that lets pkexec start it grants no more than
+//! the validated operations. See the `helper` module of the library.
+
+use std::process::ExitCode;
+
+use security_center::helper;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) != Some(helper::HELPER_FLAG) {
+        eprintln!(
+            "This program is started by Security Center through pkexec; \
+             run security-center instead"
+        );
+        return ExitCode::FAILURE;
+    }
+    ExitCode::from(helper::run_helper(&args[2..]) as u8)
+}
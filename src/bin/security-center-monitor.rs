@@ -0,0 +1,35 @@
+// Security Center - Background Monitor Entry Point
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Runs the scheduled scans and notifications of Security Center without
+//! its window, as the `security-center-monitor` user systemd service. See
+//! the `monitor` module of the library.
+
+use std::process::ExitCode;
+
+use security_center::{helper, i18n, monitor, GETTEXT_DOMAIN};
+
+fn main() -> ExitCode {
+    // Only the separate helper program runs privileged
+    if helper::running_elevated() {
+        eprintln!("The Security Center monitor must not run as root or through pkexec");
+        return ExitCode::FAILURE;
+    }
+
+    i18n::init(GETTEXT_DOMAIN);
+
+    use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_target(false))
+        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
+
+    match monitor::run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            tracing::error!("{:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
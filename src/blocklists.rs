@@ -0,0 +1,712 @@
+// Security Center - IP Blocklists
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! IP blocklists imported from an https URL or a file.
+//!
+//! A blocklist is a text list of addresses and networks, one per line, with
+//! `#` or `;` comments: the format of FireHOL netsets, Spamhaus DROP and
+//! most published lists. Each imported list is loaded into two firewalld
+//! `hash:net` ipsets, one per address family, named `scbl4-<id>` and
+//! `scbl6-<id>`. Enabling a list binds them as sources of the [`BLOCK_ZONE`],
+//! so traffic from any listed address is dropped whatever zone its interface
+//! is in; disabling it unbinds them and keeps the entries.
+//!
+//! Entries covering loopback, private, shared, link-local or unique local
+//! addresses are skipped: some lists include them as bogons, and blocking
+//! them would cut the machine off its own network. Networks inside a larger
+//! listed network are dropped, since firewalld refuses overlapping entries.
+//!
+//! Plain http URLs are refused, on import and on every refresh of a list
+//! imported before: anyone on the path could rewrite the list and so
+//! choose what the firewall drops.
+//!
+//! Every change first asks polkit about the `manage-blocklist` action
+//! ([`MANAGE_BLOCKLIST_ACTION`]), which may ask for a password; scheduled
+//! refreshes check it without asking and record a refusal as the list's
+//! error until it is refreshed by hand. The check runs in the unprivileged
+//! application, so it is an advisory prompt, not a boundary: it lets polkit
+//! rules ask for confirmation before a change that can drop traffic from a
+//! large part of the internet, but any process of the same user could call
+//! firewalld directly. What firewalld accepts is decided by firewalld's own
+//! polkit actions, as for every other firewall change.
+//!
+//! Creating the ipsets takes a firewall reload, so importing refuses while
+//! runtime rules differ from the permanent configuration. Refreshing only
+//! replaces the entries. Lists with a refresh interval are read again by the
+//! background monitor, or the application while the monitor is not running.
+//! The lists are kept in `~/.config/security-center/blocklists.json`.
+
+use std::fs;
+use std::io::Read;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::firewall::{self, FirewallClient};
+use crate::helper::{check_authorization, MANAGE_BLOCKLIST_ACTION};
+use crate::models::Family;
+
+/// Zone the ipsets of enabled lists are bound to.
+pub const BLOCK_ZONE: &str = "drop";
+/// Most entries a list may have per address family; also the size of its
+/// ipsets.
+pub const MAX_ENTRIES: usize = 262_144;
+/// Refresh intervals offered, in hours; 0 never refreshes.
+pub const REFRESH_HOURS: [u32; 4] = [0, 6, 24, 168];
+
+const MAX_LIST_SIZE: u64 = 64 * 1_048_576; // 64 MB
+const MAX_BLOCKLISTS_FILE_SIZE: u64 = 1_048_576; // 1 MB
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Ranges never blocked, as (network, prefix).
+const RESERVED: [(&str, u8); 10] = [
+    ("0.0.0.0", 8),
+    ("10.0.0.0", 8),
+    ("100.64.0.0", 10),
+    ("127.0.0.0", 8),
+    ("169.254.0.0", 16),
+    ("172.16.0.0", 12),
+    ("192.168.0.0", 16),
+    ("::1", 128),
+    ("fc00::", 7),
+    ("fe80::", 10),
+];
+
+/// An imported blocklist.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Blocklist {
+    /// Unique; part of the ipset names.
+    pub id: u32,
+    pub name: String,
+    /// An https URL or an absolute file path.
+    pub source: String,
+    pub enabled: bool,
+    /// Hours between refreshes; 0 never refreshes.
+    #[serde(default)]
+    pub refresh_hours: u32,
+    /// Unix time of the last refresh attempt.
+    #[serde(default)]
+    pub checked_at: Option<i64>,
+    /// Unix time the entries were last loaded.
+    #[serde(default)]
+    pub updated_at: Option<i64>,
+    /// IPv4 and IPv6 entries loaded.
+    #[serde(default)]
+    pub entries: usize,
+    /// Lines skipped as invalid or reserved.
+    #[serde(default)]
+    pub skipped: usize,
+    /// Why the last refresh failed, if it did.
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+impl Blocklist {
+    /// Name of the list's ipset for `family`.
+    pub fn ipset_name(&self, family: Family) -> String {
+        match family {
+            Family::Ipv4 => format!("scbl4-{}", self.id),
+            Family::Ipv6 => format!("scbl6-{}", self.id),
+        }
+    }
+
+    /// Whether the list is downloaded rather than read from a file.
+    pub fn is_url(&self) -> bool {
+        is_url(&self.source)
+    }
+
+    /// Whether a scheduled refresh is due at `now`.
+    pub fn refresh_due(&self, now: i64) -> bool {
+        self.enabled
+            && self.refresh_hours > 0
+            && self
+                .checked_at
+                .is_none_or(|last| now - last >= i64::from(self.refresh_hours) * 3_600)
+    }
+}
+
+fn is_url(source: &str) -> bool {
+    source.starts_with("https://") || is_insecure_url(source)
+}
+
+/// Whether `source` is a plain http URL, which is never downloaded.
+pub fn is_insecure_url(source: &str) -> bool {
+    source.starts_with("http://")
+}
+
+/// Check that `source` is an https URL or an absolute file path.
+fn validate_source(source: &str) -> Result<()> {
+    if is_insecure_url(source) {
+        bail!("Plain http:// lists can be altered in transit; use an https:// URL");
+    }
+    if !is_url(source) && !Path::new(source).is_absolute() {
+        bail!("Enter an https:// URL or choose a file");
+    }
+    Ok(())
+}
+
+/// Progress of an import or refresh, shared with the thread showing it.
+#[derive(Debug, Default)]
+pub struct Progress {
+    state: Mutex<(&'static str, Option<f64>)>,
+}
+
+impl Progress {
+    fn set(&self, step: &'static str, fraction: Option<f64>) {
+        if let Ok(mut state) = self.state.lock() {
+            *state = (step, fraction);
+        }
+    }
+
+    /// The current step and, when known, how much of it is done (0 to 1).
+    pub fn get(&self) -> (&'static str, Option<f64>) {
+        self.state.lock().map(|state| *state).unwrap_or(("", None))
+    }
+}
+
+/// A network as its first address and prefix length; IPv4 in the low 32
+/// bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Net {
+    v6: bool,
+    start: u128,
+    prefix: u8,
+}
+
+impl Net {
+    /// Parse "192.0.2.0/24" or "2001:db8::1", clearing host bits. `None`
+    /// for invalid entries and for /0, which would block everything.
+    fn parse(text: &str) -> Option<Net> {
+        let (ip, prefix) = match text.split_once('/') {
+            Some((ip, prefix)) => (ip, Some(prefix.parse::<u8>().ok()?)),
+            None => (text, None),
+        };
+        let (v6, bits) = match ip.parse::<IpAddr>().ok()? {
+            IpAddr::V4(ip) => (false, u128::from(u32::from(ip))),
+            IpAddr::V6(ip) => (true, u128::from(ip)),
+        };
+        let max = if v6 { 128 } else { 32 };
+        let prefix = prefix.unwrap_or(max);
+        if prefix == 0 || prefix > max {
+            return None;
+        }
+        let net = Net {
+            v6,
+            start: 0,
+            prefix,
+        };
+        Some(Net {
+            start: bits & !net.host_mask(),
+            ..net
+        })
+    }
+
+    fn max(&self) -> u8 {
+        if self.v6 {
+            128
+        } else {
+            32
+        }
+    }
+
+    fn host_mask(&self) -> u128 {
+        // prefix >= 1, so the shift is at most 127
+        if self.prefix == self.max() {
+            0
+        } else {
+            (1u128 << (self.max() - self.prefix)) - 1
+        }
+    }
+
+    fn end(&self) -> u128 {
+        self.start | self.host_mask()
+    }
+
+    fn overlaps(&self, other: &Net) -> bool {
+        self.v6 == other.v6 && self.start <= other.end() && other.start <= self.end()
+    }
+
+    /// The [`RESERVED`] ranges.
+    fn reserved() -> Vec<Net> {
+        RESERVED
+            .iter()
+            .filter_map(|(ip, prefix)| Net::parse(&format!("{}/{}", ip, prefix)))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Net {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ip = if self.v6 {
+            IpAddr::V6(Ipv6Addr::from(self.start))
+        } else {
+            IpAddr::V4(Ipv4Addr::from(self.start as u32))
+        };
+        if self.prefix == self.max() {
+            write!(f, "{}", ip)
+        } else {
+            write!(f, "{}/{}", ip, self.prefix)
+        }
+    }
+}
+
+/// The entries of a blocklist, ready for ipsets.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ParsedList {
+    pub ipv4: Vec<String>,
+    pub ipv6: Vec<String>,
+    /// Lines that are not an address or network.
+    pub invalid: usize,
+    /// Entries covering reserved addresses.
+    pub reserved: usize,
+}
+
+impl ParsedList {
+    pub fn len(&self) -> usize {
+        self.ipv4.len() + self.ipv6.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Read a blocklist: one address or network per line, the first word of
+/// the line, after `#` and `;` comments are removed. Duplicates and
+/// networks inside another listed network are dropped.
+pub fn parse_blocklist(text: &str) -> ParsedList {
+    let reserved = Net::reserved();
+    let mut parsed = ParsedList::default();
+    let mut nets = Vec::new();
+    for line in text.lines() {
+        let line = line.split(['#', ';']).next().unwrap_or_default();
+        let Some(word) = line.split_whitespace().next() else {
+            continue;
+        };
+        match Net::parse(word) {
+            Some(net) if reserved.iter().any(|r| r.overlaps(&net)) => parsed.reserved += 1,
+            Some(net) => nets.push(net),
+            None => parsed.invalid += 1,
+        }
+    }
+
+    // Sorted by start, a covering network comes before what it covers;
+    // CIDR networks either nest or are apart, so one end to compare with
+    // is enough
+    nets.sort();
+    let mut covered: Option<Net> = None;
+    for net in nets {
+        if covered.is_some_and(|c| c.v6 == net.v6 && net.start <= c.end()) {
+            continue;
+        }
+        covered = Some(net);
+        if net.v6 {
+            parsed.ipv6.push(net.to_string());
+        } else {
+            parsed.ipv4.push(net.to_string());
+        }
+    }
+    parsed
+}
+
+/// Whether `ip` is in one of the ranges never blocked: loopback, private,
+/// shared, link-local or unique local. IPv4-mapped IPv6 addresses count as
+/// their IPv4 address.
+pub fn is_reserved(ip: IpAddr) -> bool {
+    let net = match ip.to_canonical() {
+        IpAddr::V4(ip) => Net {
+            v6: false,
+            start: u128::from(u32::from(ip)),
+            prefix: 32,
+        },
+        IpAddr::V6(ip) => Net {
+            v6: true,
+            start: u128::from(ip),
+            prefix: 128,
+        },
+    };
+    Net::reserved().iter().any(|r| r.overlaps(&net))
+}
+
+/// Read the text of a list from a URL or file.
+fn fetch(source: &str, progress: &Progress) -> Result<String> {
+    validate_source(source)?;
+    if !is_url(source) {
+        progress.set("Reading the file", None);
+        let size = fs::metadata(source)
+            .with_context(|| format!("Cannot read {}", source))?
+            .len();
+        if size > MAX_LIST_SIZE {
+            bail!("The file is larger than {} MB", MAX_LIST_SIZE / 1_048_576);
+        }
+        return fs::read_to_string(source).with_context(|| format!("Cannot read {}", source));
+    }
+
+    progress.set("Downloading", None);
+    // https_only also refuses a redirect to a plain http URL
+    let client = reqwest::blocking::Client::builder()
+        .https_only(true)
+        .timeout(DOWNLOAD_TIMEOUT)
+        .user_agent(format!("security-center/{}", env!("CARGO_PKG_VERSION")))
+        .build()
+        .context("Failed to build the HTTP client")?;
+    let mut response = client.get(source).send().context("Download failed")?;
+    if !response.status().is_success() {
+        bail!("The server answered {}", response.status());
+    }
+    let total = response.content_length().filter(|len| *len > 0);
+
+    let mut body = Vec::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = response.read(&mut buffer).context("Download failed")?;
+        if read == 0 {
+            break;
+        }
+        body.extend_from_slice(&buffer[..read]);
+        if body.len() as u64 > MAX_LIST_SIZE {
+            bail!("The list is larger than {} MB", MAX_LIST_SIZE / 1_048_576);
+        }
+        let fraction = total.map(|total| (body.len() as f64 / total as f64).min(1.0));
+        progress.set("Downloading", fraction);
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Fetch and parse a list, refusing lists with nothing to block or more
+/// than [`MAX_ENTRIES`] per family.
+fn read_list(source: &str, progress: &Progress) -> Result<ParsedList> {
+    let text = fetch(source, progress)?;
+    progress.set("Reading the entries", None);
+    let parsed = parse_blocklist(&text);
+    if parsed.is_empty() {
+        bail!("The list has no addresses or networks to block");
+    }
+    if parsed.ipv4.len().max(parsed.ipv6.len()) > MAX_ENTRIES {
+        bail!("The list has more than {} entries", MAX_ENTRIES);
+    }
+    Ok(parsed)
+}
+
+/// Run `call` on the firewall worker's client once it is connected.
+fn with_firewall<R: Send + 'static>(
+    call: impl FnOnce(&mut FirewallClient) -> Result<R> + Send + 'static,
+) -> Result<R> {
+    firewall::call_blocking(move |client| {
+        client
+            .connect()
+            .map_err(|_| anyhow!("Not connected to firewalld"))?;
+        call(client)
+    })?
+}
+
+/// Bind or unbind the ipsets of `list` to the [`BLOCK_ZONE`], at runtime
+/// and permanently.
+fn bind(client: &FirewallClient, list: &Blocklist, enabled: bool) -> Result<()> {
+    for family in [Family::Ipv4, Family::Ipv6] {
+        let source = format!("ipset:{}", list.ipset_name(family));
+        let outcome = if enabled {
+            client.add_source(BLOCK_ZONE, &source, true)?
+        } else {
+            client.remove_source(BLOCK_ZONE, &source, true)?
+        };
+        if outcome.failed() {
+            bail!(
+                "Saving {} in zone {} permanently failed",
+                source,
+                BLOCK_ZONE
+            );
+        }
+    }
+    Ok(())
+}
+
+fn blocklists_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("security-center")
+        .join("blocklists.json")
+}
+
+/// The imported blocklists. Empty when none are or the file is unreadable.
+pub fn load_blocklists() -> Vec<Blocklist> {
+    let path = blocklists_path();
+    if fs::metadata(&path).is_ok_and(|m| m.len() > MAX_BLOCKLISTS_FILE_SIZE) {
+        warn!("Blocklists file too large, ignoring");
+        return Vec::new();
+    }
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        warn!("Failed to parse the blocklists file: {}", e);
+        Vec::new()
+    })
+}
+
+fn save_blocklists(lists: &[Blocklist]) -> Result<()> {
+    let path = blocklists_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context("Failed to create the config directory")?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(lists)?)
+        .context("Failed to write the blocklists file")
+}
+
+/// Import a list from `source`, an https URL or an absolute file path,
+/// and enable it. Talks to firewalld and may download, so call it off the
+/// main thread.
+pub fn import_blocklist(
+    name: &str,
+    source: &str,
+    refresh_hours: u32,
+    progress: &Progress,
+) -> Result<Blocklist> {
+    let source = source.trim();
+    validate_source(source)?;
+    check_authorization(MANAGE_BLOCKLIST_ACTION, true)?;
+    let parsed = read_list(source, progress)?;
+
+    let unsaved = with_firewall(|client| {
+        Ok(client
+            .get_zones()?
+            .iter()
+            .any(|zone| zone.unsaved_changes() > 0))
+    })?;
+    if unsaved {
+        bail!(
+            "Loading a list reloads the firewall; save or discard the unsaved firewall \
+             changes first"
+        );
+    }
+
+    let mut lists = load_blocklists();
+    let now = chrono::Utc::now().timestamp();
+    let list = Blocklist {
+        id: lists.iter().map(|l| l.id).max().map_or(1, |id| id + 1),
+        name: if name.trim().is_empty() {
+            source.rsplit('/').next().unwrap_or(source).to_string()
+        } else {
+            name.trim().to_string()
+        },
+        source: source.to_string(),
+        enabled: true,
+        refresh_hours,
+        checked_at: Some(now),
+        updated_at: Some(now),
+        entries: parsed.len(),
+        skipped: parsed.invalid + parsed.reserved,
+        last_error: None,
+    };
+
+    progress.set("Creating the ipsets", None);
+    let (ipv4, ipv6) = (list.ipset_name(Family::Ipv4), list.ipset_name(Family::Ipv6));
+    with_firewall(move |client| {
+        client.create_ipset(&ipv4, Family::Ipv4, MAX_ENTRIES, &parsed.ipv4)?;
+        if let Err(e) = client.create_ipset(&ipv6, Family::Ipv6, MAX_ENTRIES, &parsed.ipv6) {
+            let _ = client.remove_ipset(&ipv4);
+            return Err(e);
+        }
+        Ok(())
+    })?;
+
+    progress.set("Reloading the firewall", None);
+    with_firewall(|client| client.reload())?;
+    progress.set("Enabling the list", None);
+    let enabled = list.clone();
+    with_firewall(move |client| bind(client, &enabled, true))?;
+
+    lists.push(list.clone());
+    save_blocklists(&lists)?;
+    info!(
+        "Imported blocklist {} with {} entries from {}",
+        list.name, list.entries, list.source
+    );
+    Ok(list)
+}
+
+/// Read a list again and replace the entries of its ipsets. The attempt is
+/// recorded whether it succeeds or not.
+pub fn refresh_blocklist(id: u32, progress: &Progress) -> Result<Blocklist> {
+    refresh(id, progress, true)
+}
+
+/// [`refresh_blocklist`], letting polkit ask for a password only when
+/// `interactive`.
+fn refresh(id: u32, progress: &Progress, interactive: bool) -> Result<Blocklist> {
+    let mut lists = load_blocklists();
+    let list = lists
+        .iter_mut()
+        .find(|l| l.id == id)
+        .ok_or_else(|| anyhow!("The blocklist no longer exists"))?;
+
+    let result = check_authorization(MANAGE_BLOCKLIST_ACTION, interactive)
+        .map_err(|e| {
+            if interactive {
+                e
+            } else {
+                anyhow!("{:#}; refresh the list by hand to authorize it", e)
+            }
+        })
+        .and_then(|()| read_list(&list.source, progress))
+        .and_then(|parsed| {
+            progress.set("Loading the entries", None);
+            let (ipv4, ipv6) = (list.ipset_name(Family::Ipv4), list.ipset_name(Family::Ipv6));
+            with_firewall(move |client| {
+                client.set_ipset_entries(&ipv4, &parsed.ipv4)?;
+                client.set_ipset_entries(&ipv6, &parsed.ipv6)?;
+                Ok(parsed)
+            })
+        });
+
+    let now = chrono::Utc::now().timestamp();
+    list.checked_at = Some(now);
+    match &result {
+        Ok(parsed) => {
+            list.updated_at = Some(now);
+            list.entries = parsed.len();
+            list.skipped = parsed.invalid + parsed.reserved;
+            list.last_error = None;
+        }
+        Err(e) => list.last_error = Some(e.to_string()),
+    }
+    let list = list.clone();
+    save_blocklists(&lists)?;
+    result.map(|_| list)
+}
+
+/// Enable or disable a list by binding or unbinding its ipsets.
+pub fn set_blocklist_enabled(id: u32, enabled: bool) -> Result<()> {
+    let mut lists = load_blocklists();
+    let list = lists
+        .iter_mut()
+        .find(|l| l.id == id)
+        .ok_or_else(|| anyhow!("The blocklist no longer exists"))?;
+    check_authorization(MANAGE_BLOCKLIST_ACTION, true)?;
+    let changed = list.clone();
+    with_firewall(move |client| bind(client, &changed, enabled))?;
+    list.enabled = enabled;
+    save_blocklists(&lists)
+}
+
+/// Disable a list and delete its ipsets.
+pub fn remove_blocklist(id: u32) -> Result<()> {
+    let mut lists = load_blocklists();
+    let list = lists
+        .iter()
+        .find(|l| l.id == id)
+        .ok_or_else(|| anyhow!("The blocklist no longer exists"))?;
+    check_authorization(MANAGE_BLOCKLIST_ACTION, true)?;
+    let removed = list.clone();
+    with_firewall(move |client| {
+        bind(client, &removed, false)?;
+        for family in [Family::Ipv4, Family::Ipv6] {
+            client.remove_ipset(&removed.ipset_name(family))?;
+        }
+        Ok(())
+    })?;
+    info!("Removed blocklist {}", list.name);
+    lists.retain(|l| l.id != id);
+    save_blocklists(&lists)
+}
+
+/// Refresh the lists whose interval has passed, returning each one's name
+/// and outcome.
+pub fn refresh_due_blocklists() -> Vec<(String, Result<Blocklist>)> {
+    let now = chrono::Utc::now().timestamp();
+    load_blocklists()
+        .into_iter()
+        .filter(|list| list.refresh_due(now))
+        .map(|list| (list.name, refresh(list.id, &Progress::default(), false)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_blocklist() {
+        let text = "\
+# FireHOL style header
+192.0.2.0/24
+192.0.2.7
+198.51.100.9/24 ; host bits are cleared
+203.0.113.5 SBL123
+2001:db8::/32
+2001:db8:1::/48
+10.0.0.0/8
+fe80::1
+0.0.0.0/0
+not-an-address
+";
+        let parsed = parse_blocklist(text);
+        assert_eq!(
+            parsed.ipv4,
+            vec!["192.0.2.0/24", "198.51.100.0/24", "203.0.113.5"]
+        );
+        assert_eq!(parsed.ipv6, vec!["2001:db8::/32"]);
+        assert_eq!(parsed.reserved, 2);
+        assert_eq!(parsed.invalid, 2);
+        assert_eq!(parsed.len(), 4);
+    }
+
+    #[test]
+    fn test_is_reserved() {
+        for ip in ["127.0.0.1", "10.1.2.3", "192.168.1.1", "169.254.0.5", "::1"] {
+            assert!(is_reserved(ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in ["fe80::1", "fd12::1", "::ffff:192.168.1.1"] {
+            assert!(is_reserved(ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in [
+            "203.0.113.7",
+            "8.8.8.8",
+            "2001:db8::1",
+            "::ffff:203.0.113.7",
+        ] {
+            assert!(!is_reserved(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    #[test]
+    fn test_validate_source() {
+        assert!(validate_source("https://example.org/firehol_level1.netset").is_ok());
+        assert!(validate_source("/home/user/drop.txt").is_ok());
+        assert!(validate_source("http://example.org/firehol_level1.netset").is_err());
+        assert!(validate_source("ftp://example.org/list").is_err());
+        assert!(validate_source("lists/drop.txt").is_err());
+        assert!(is_insecure_url("http://example.org/list"));
+        assert!(!is_insecure_url("https://example.org/list"));
+    }
+
+    #[test]
+    fn test_refresh_due() {
+        let mut list = Blocklist {
+            id: 1,
+            name: "level1".to_string(),
+            source: "https://example.org/firehol_level1.netset".to_string(),
+            enabled: true,
+            refresh_hours: 24,
+            checked_at: Some(1_000_000),
+            updated_at: Some(1_000_000),
+            entries: 10,
+            skipped: 0,
+            last_error: None,
+        };
+        assert_eq!(list.ipset_name(Family::Ipv6), "scbl6-1");
+        assert!(!list.refresh_due(1_000_000 + 3_600));
+        assert!(list.refresh_due(1_000_000 + 24 * 3_600));
+        list.enabled = false;
+        assert!(!list.refresh_due(1_000_000 + 24 * 3_600));
+        list.enabled = true;
+        list.refresh_hours = 0;
+        assert!(!list.refresh_due(i64::MAX));
+    }
+}
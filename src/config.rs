@@ -5,14 +5,27 @@
 //! Application settings management using a local JSON file.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 use tracing::warn;
 
-use crate::validation::{clamp_window_dimension, validate_theme};
+use crate::admin::ChecklistRole;
+use crate::migration::{self, Schema};
+use crate::validation::{
+    clamp_window_dimension, validate_density, validate_interface_name, validate_last_task,
+    validate_startup_page, validate_status_palette, validate_theme,
+};
 
 const MAX_CONFIG_FILE_SIZE: u64 = 1_048_576; // 1 MB
 
+/// Format history of `settings.json`; see [`migration`].
+const SCHEMA: Schema = Schema {
+    name: "settings",
+    // Version 1 added the version field
+    steps: &[|_| Ok(())],
+};
+
 /// Application settings.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -29,6 +42,12 @@ pub struct AppSettings {
     /// Theme preference: "system", "light", or "dark".
     #[serde(default = "default_theme")]
     pub theme: String,
+    /// Status colour palette: "default", "red-green" or "blue-yellow".
+    #[serde(default = "default_status_palette")]
+    pub status_palette: String,
+    /// Layout density: "comfortable" or "compact".
+    #[serde(default = "default_density")]
+    pub density: String,
     /// Start the application on login.
     #[serde(default)]
     pub autostart_on_login: bool,
@@ -45,6 +64,71 @@ pub struct AppSettings {
     /// details window. When false the app never contacts an online service.
     #[serde(default = "default_true")]
     pub enable_online_ip_lookup: bool,
+    /// Leftover categories included in cleanup runs (see `CleanupCategory::id`).
+    #[serde(default = "default_cleanup_categories")]
+    pub cleanup_categories: Vec<String>,
+    /// Overwrite leftovers before removing them.
+    #[serde(default = "default_true")]
+    pub cleanup_secure_delete: bool,
+    /// Days between scheduled cleanup runs; 0 disables scheduling.
+    #[serde(default)]
+    pub cleanup_interval_days: u32,
+    /// Unix timestamp of the last cleanup run.
+    #[serde(default)]
+    pub cleanup_last_run: Option<i64>,
+    /// Size of each shell history file at the last cleanup scan, by path.
+    #[serde(default)]
+    pub cleanup_history_sizes: BTreeMap<String, u64>,
+    /// Hours between scheduled enforcements of the desired-state policy;
+    /// 0 disables scheduling.
+    #[serde(default)]
+    pub policy_interval_hours: u32,
+    /// Unix timestamp of the last policy enforcement.
+    #[serde(default)]
+    pub policy_last_enforced: Option<i64>,
+    /// Hours between scheduled security scans; 0 disables scheduling.
+    #[serde(default)]
+    pub security_scan_interval_hours: u32,
+    /// Unix timestamp of the last scheduled security scan.
+    #[serde(default)]
+    pub security_scan_last_run: Option<i64>,
+    /// Serve the read-only JSON API on the loopback interface.
+    #[serde(default)]
+    pub api_enabled: bool,
+    /// TCP port of the JSON API.
+    #[serde(default = "default_api_port")]
+    pub api_port: u16,
+    /// Keep the status file for desktop widgets up to date.
+    #[serde(default)]
+    pub status_feed_enabled: bool,
+    /// Monthly data quota in bytes, by network interface.
+    #[serde(default)]
+    pub data_quotas: BTreeMap<String, u64>,
+    /// Ports nothing should connect to; denied attempts on them raise an alert.
+    #[serde(default)]
+    pub tripwire_ports: Vec<u16>,
+    /// Former setting to block tripwire probers without asking, which a
+    /// forged source address could abuse. Read so older files still load.
+    #[serde(default, skip_serializing)]
+    pub tripwire_auto_block: bool,
+    /// Lock the session when a FIDO2/U2F security key is removed.
+    #[serde(default)]
+    pub lock_on_key_removal: bool,
+    /// Page shown on launch: "overview", "network-exposure" or "ports".
+    #[serde(default = "default_startup_page")]
+    pub startup_page: String,
+    /// Open the page of the last scan on launch instead, which runs it again.
+    #[serde(default)]
+    pub resume_last_task: bool,
+    /// Page whose scan ran last: "network-exposure", "platform" or "cleanup".
+    #[serde(default)]
+    pub last_task: Option<String>,
+    /// Zone suggestions the user dismissed, as `<connection uuid>:<zone>`.
+    #[serde(default)]
+    pub dismissed_zone_suggestions: Vec<String>,
+    /// Role checklist items marked done, by role id (see `ChecklistRole::id`).
+    #[serde(default)]
+    pub checklist_progress: BTreeMap<String, Vec<String>>,
 }
 
 fn default_width() -> i32 {
@@ -56,16 +140,34 @@ fn default_height() -> i32 {
 fn default_theme() -> String {
     "system".to_string()
 }
+fn default_status_palette() -> String {
+    "default".to_string()
+}
+fn default_density() -> String {
+    "comfortable".to_string()
+}
+fn default_api_port() -> u16 {
+    crate::api::DEFAULT_API_PORT
+}
+fn default_startup_page() -> String {
+    "overview".to_string()
+}
 fn default_true() -> bool {
     true
 }
 fn default_dashboard_max_apps() -> usize {
     6
 }
+fn default_cleanup_categories() -> Vec<String> {
+    // Shell history is opt-in: wiping it is rarely what a user expects.
+    vec!["removable-thumbnails".to_string(), "core-dumps".to_string()]
+}
 
 /// Minimum and maximum number of dashboard connection cards the user may pick.
 pub const DASHBOARD_MAX_APPS_MIN: usize = 1;
 pub const DASHBOARD_MAX_APPS_MAX: usize = 24;
+/// Dismissed zone suggestions remembered.
+const MAX_DISMISSED_ZONE_SUGGESTIONS: usize = 64;
 
 /// Clamp the dashboard card count into the supported range.
 fn clamp_dashboard_max_apps(n: usize) -> usize {
@@ -79,15 +181,99 @@ impl Default for AppSettings {
             window_height: default_height(),
             is_maximized: false,
             theme: default_theme(),
+            status_palette: default_status_palette(),
+            density: default_density(),
             autostart_on_login: false,
             show_tray_icon: false,
             show_connections_overview: true,
             dashboard_max_apps: default_dashboard_max_apps(),
             enable_online_ip_lookup: true,
+            cleanup_categories: default_cleanup_categories(),
+            cleanup_secure_delete: true,
+            cleanup_interval_days: 0,
+            cleanup_last_run: None,
+            cleanup_history_sizes: BTreeMap::new(),
+            policy_interval_hours: 0,
+            policy_last_enforced: None,
+            security_scan_interval_hours: 0,
+            security_scan_last_run: None,
+            api_enabled: false,
+            api_port: default_api_port(),
+            status_feed_enabled: false,
+            data_quotas: BTreeMap::new(),
+            tripwire_ports: Vec::new(),
+            tripwire_auto_block: false,
+            lock_on_key_removal: false,
+            startup_page: default_startup_page(),
+            resume_last_task: false,
+            last_task: None,
+            dismissed_zone_suggestions: Vec::new(),
+            checklist_progress: BTreeMap::new(),
         }
     }
 }
 
+impl AppSettings {
+    /// Reset or clamp values a hand-edited or older file may hold.
+    fn sanitized(mut self) -> Self {
+        if validate_theme(&self.theme).is_none() {
+            warn!(
+                "Invalid theme '{}' in settings, resetting to system",
+                self.theme
+            );
+            self.theme = "system".to_string();
+        }
+        if validate_status_palette(&self.status_palette).is_none() {
+            warn!(
+                "Invalid status palette '{}' in settings, resetting to default",
+                self.status_palette
+            );
+            self.status_palette = default_status_palette();
+        }
+        if validate_density(&self.density).is_none() {
+            warn!(
+                "Invalid density '{}' in settings, resetting to comfortable",
+                self.density
+            );
+            self.density = default_density();
+        }
+        if validate_startup_page(&self.startup_page).is_none() {
+            warn!(
+                "Invalid startup page '{}' in settings, resetting to overview",
+                self.startup_page
+            );
+            self.startup_page = default_startup_page();
+        }
+        if self
+            .last_task
+            .as_deref()
+            .is_some_and(|t| validate_last_task(t).is_none())
+        {
+            warn!("Invalid last task in settings, discarding it");
+            self.last_task = None;
+        }
+        self.data_quotas
+            .retain(|iface, quota| validate_interface_name(iface).is_some() && *quota > 0);
+        self.tripwire_ports.retain(|port| *port > 0);
+        self.tripwire_ports.sort_unstable();
+        self.tripwire_ports.dedup();
+        self.checklist_progress.retain(|role, done| {
+            let Some(role) = ChecklistRole::parse(role) else {
+                return false;
+            };
+            done.retain(|item| role.item(item).is_some());
+            done.sort();
+            done.dedup();
+            !done.is_empty()
+        });
+        self.window_width = clamp_window_dimension(self.window_width);
+        self.window_height = clamp_window_dimension(self.window_height);
+        self.dashboard_max_apps = clamp_dashboard_max_apps(self.dashboard_max_apps);
+        self.api_port = crate::api::clamp_api_port(self.api_port);
+        self
+    }
+}
+
 /// Settings manager that persists to a JSON file.
 #[derive(Debug)]
 pub struct Settings {
@@ -116,85 +302,16 @@ impl Settings {
             }
         }
 
-        let settings = if path.exists() {
-            let metadata = fs::metadata(&path);
-            if let Ok(m) = metadata {
-                if m.len() > MAX_CONFIG_FILE_SIZE {
-                    warn!(
-                        "Settings file too large ({} bytes), using defaults",
-                        m.len()
-                    );
-                    AppSettings::default()
-                } else {
-                    match fs::read_to_string(&path) {
-                        Ok(content) => {
-                            match serde_json::from_str::<AppSettings>(&content) {
-                                Ok(mut s) => {
-                                    // Validate fields
-                                    if validate_theme(&s.theme).is_none() {
-                                        warn!(
-                                            "Invalid theme '{}' in settings, resetting to system",
-                                            s.theme
-                                        );
-                                        s.theme = "system".to_string();
-                                    }
-                                    s.window_width = clamp_window_dimension(s.window_width);
-                                    s.window_height = clamp_window_dimension(s.window_height);
-                                    s.dashboard_max_apps =
-                                        clamp_dashboard_max_apps(s.dashboard_max_apps);
-                                    s
-                                }
-                                Err(e) => {
-                                    warn!("Failed to parse settings: {}", e);
-                                    AppSettings::default()
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            warn!("Failed to read settings: {}", e);
-                            AppSettings::default()
-                        }
-                    }
-                }
-            } else {
-                AppSettings::default()
-            }
-        } else {
-            AppSettings::default()
-        };
+        let settings = migration::load::<AppSettings>(&SCHEMA, &path, MAX_CONFIG_FILE_SIZE)
+            .map(AppSettings::sanitized)
+            .unwrap_or_default();
 
         Self { settings, path }
     }
 
     pub fn save(&self) {
-        use std::io::Write;
-        #[cfg(unix)]
-        use std::os::unix::fs::PermissionsExt;
-
-        if let Some(parent) = self.path.parent() {
-            let _ = fs::create_dir_all(parent);
-        }
-
-        match serde_json::to_string_pretty(&self.settings) {
-            Ok(content) => match fs::File::create(&self.path) {
-                Ok(mut file) => {
-                    #[cfg(unix)]
-                    {
-                        if let Err(e) = file.set_permissions(fs::Permissions::from_mode(0o600)) {
-                            warn!("Failed to set file permissions: {}", e);
-                        }
-                    }
-                    if let Err(e) = file.write_all(content.as_bytes()) {
-                        warn!("Failed to save settings: {}", e);
-                    }
-                }
-                Err(e) => {
-                    warn!("Failed to create settings file: {}", e);
-                }
-            },
-            Err(e) => {
-                warn!("Failed to serialize settings: {}", e);
-            }
+        if let Err(e) = migration::save(&SCHEMA, &self.path, &self.settings) {
+            warn!("Failed to save settings: {:#}", e);
         }
     }
 
@@ -234,6 +351,24 @@ impl Settings {
         self.save();
     }
 
+    pub fn status_palette(&self) -> &str {
+        &self.settings.status_palette
+    }
+
+    pub fn set_status_palette(&mut self, palette: &str) {
+        self.settings.status_palette = palette.to_string();
+        self.save();
+    }
+
+    pub fn density(&self) -> &str {
+        &self.settings.density
+    }
+
+    pub fn set_density(&mut self, density: &str) {
+        self.settings.density = density.to_string();
+        self.save();
+    }
+
     pub fn autostart_on_login(&self) -> bool {
         self.settings.autostart_on_login
     }
@@ -278,6 +413,241 @@ impl Settings {
         self.settings.enable_online_ip_lookup = enabled;
         self.save();
     }
+
+    pub fn cleanup_category_enabled(&self, id: &str) -> bool {
+        self.settings.cleanup_categories.iter().any(|c| c == id)
+    }
+
+    pub fn set_cleanup_category_enabled(&mut self, id: &str, enabled: bool) {
+        self.settings.cleanup_categories.retain(|c| c != id);
+        if enabled {
+            self.settings.cleanup_categories.push(id.to_string());
+        }
+        self.save();
+    }
+
+    pub fn cleanup_secure_delete(&self) -> bool {
+        self.settings.cleanup_secure_delete
+    }
+
+    pub fn set_cleanup_secure_delete(&mut self, enabled: bool) {
+        self.settings.cleanup_secure_delete = enabled;
+        self.save();
+    }
+
+    pub fn cleanup_interval_days(&self) -> u32 {
+        self.settings.cleanup_interval_days
+    }
+
+    pub fn set_cleanup_interval_days(&mut self, days: u32) {
+        self.settings.cleanup_interval_days = days;
+        self.save();
+    }
+
+    pub fn cleanup_last_run(&self) -> Option<i64> {
+        self.settings.cleanup_last_run
+    }
+
+    pub fn set_cleanup_last_run(&mut self, timestamp: i64) {
+        self.settings.cleanup_last_run = Some(timestamp);
+        self.save();
+    }
+
+    pub fn cleanup_history_sizes(&self) -> &BTreeMap<String, u64> {
+        &self.settings.cleanup_history_sizes
+    }
+
+    pub fn set_cleanup_history_sizes(&mut self, sizes: BTreeMap<String, u64>) {
+        self.settings.cleanup_history_sizes = sizes;
+        self.save();
+    }
+
+    pub fn policy_interval_hours(&self) -> u32 {
+        self.settings.policy_interval_hours
+    }
+
+    pub fn set_policy_interval_hours(&mut self, hours: u32) {
+        self.settings.policy_interval_hours = hours;
+        self.save();
+    }
+
+    pub fn policy_last_enforced(&self) -> Option<i64> {
+        self.settings.policy_last_enforced
+    }
+
+    pub fn set_policy_last_enforced(&mut self, timestamp: i64) {
+        self.settings.policy_last_enforced = Some(timestamp);
+        self.save();
+    }
+
+    pub fn security_scan_interval_hours(&self) -> u32 {
+        self.settings.security_scan_interval_hours
+    }
+
+    pub fn set_security_scan_interval_hours(&mut self, hours: u32) {
+        self.settings.security_scan_interval_hours = hours;
+        self.save();
+    }
+
+    pub fn security_scan_last_run(&self) -> Option<i64> {
+        self.settings.security_scan_last_run
+    }
+
+    pub fn set_security_scan_last_run(&mut self, timestamp: i64) {
+        self.settings.security_scan_last_run = Some(timestamp);
+        self.save();
+    }
+
+    pub fn api_enabled(&self) -> bool {
+        self.settings.api_enabled
+    }
+
+    pub fn set_api_enabled(&mut self, enabled: bool) {
+        self.settings.api_enabled = enabled;
+        self.save();
+    }
+
+    pub fn api_port(&self) -> u16 {
+        self.settings.api_port
+    }
+
+    pub fn set_api_port(&mut self, port: u16) {
+        self.settings.api_port = crate::api::clamp_api_port(port);
+        self.save();
+    }
+
+    pub fn status_feed_enabled(&self) -> bool {
+        self.settings.status_feed_enabled
+    }
+
+    pub fn set_status_feed_enabled(&mut self, enabled: bool) {
+        self.settings.status_feed_enabled = enabled;
+        self.save();
+    }
+
+    pub fn data_quotas(&self) -> &BTreeMap<String, u64> {
+        &self.settings.data_quotas
+    }
+
+    /// Set the monthly quota of `iface` in bytes; 0 removes it.
+    pub fn set_data_quota(&mut self, iface: &str, bytes: u64) {
+        let Some(iface) = validate_interface_name(iface) else {
+            return;
+        };
+        if bytes == 0 {
+            self.settings.data_quotas.remove(iface);
+        } else {
+            self.settings.data_quotas.insert(iface.to_string(), bytes);
+        }
+        self.save();
+    }
+
+    pub fn tripwire_ports(&self) -> &[u16] {
+        &self.settings.tripwire_ports
+    }
+
+    pub fn set_tripwire_ports(&mut self, ports: Vec<u16>) {
+        self.settings.tripwire_ports = ports;
+        self.save();
+    }
+
+    pub fn lock_on_key_removal(&self) -> bool {
+        self.settings.lock_on_key_removal
+    }
+
+    pub fn set_lock_on_key_removal(&mut self, enabled: bool) {
+        self.settings.lock_on_key_removal = enabled;
+        self.save();
+    }
+
+    pub fn startup_page(&self) -> &str {
+        &self.settings.startup_page
+    }
+
+    pub fn set_startup_page(&mut self, page: &str) {
+        if let Some(page) = validate_startup_page(page) {
+            self.settings.startup_page = page.to_string();
+            self.save();
+        }
+    }
+
+    pub fn resume_last_task(&self) -> bool {
+        self.settings.resume_last_task
+    }
+
+    pub fn set_resume_last_task(&mut self, enabled: bool) {
+        self.settings.resume_last_task = enabled;
+        self.save();
+    }
+
+    pub fn last_task(&self) -> Option<&str> {
+        self.settings.last_task.as_deref()
+    }
+
+    /// Record the page whose scan just ran. Saves only on change, since
+    /// every visit to a scanning page counts as a run.
+    pub fn set_last_task(&mut self, task: &str) {
+        if let Some(task) = validate_last_task(task) {
+            if self.last_task() != Some(task) {
+                self.settings.last_task = Some(task.to_string());
+                self.save();
+            }
+        }
+    }
+
+    pub fn is_zone_suggestion_dismissed(&self, key: &str) -> bool {
+        self.settings
+            .dismissed_zone_suggestions
+            .iter()
+            .any(|k| k == key)
+    }
+
+    /// Stop suggesting `key`. Only the most recent dismissals are kept.
+    pub fn dismiss_zone_suggestion(&mut self, key: &str) {
+        if self.is_zone_suggestion_dismissed(key) {
+            return;
+        }
+        let dismissed = &mut self.settings.dismissed_zone_suggestions;
+        dismissed.push(key.to_string());
+        if dismissed.len() > MAX_DISMISSED_ZONE_SUGGESTIONS {
+            dismissed.remove(0);
+        }
+        self.save();
+    }
+
+    /// Items of the checklist of `role` marked done.
+    pub fn checklist_done(&self, role: ChecklistRole) -> &[String] {
+        self.settings
+            .checklist_progress
+            .get(role.id())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn set_checklist_item_done(&mut self, role: ChecklistRole, item: &str, done: bool) {
+        if role.item(item).is_none() {
+            return;
+        }
+        let progress = &mut self.settings.checklist_progress;
+        let items = progress.entry(role.id().to_string()).or_default();
+        items.retain(|id| id != item);
+        if done {
+            items.push(item.to_string());
+            items.sort();
+        } else if items.is_empty() {
+            progress.remove(role.id());
+        }
+        self.save();
+    }
+
+    /// Page to open on launch: the last scan when resuming is enabled and
+    /// one was recorded, the startup page otherwise.
+    pub fn initial_page(&self) -> &str {
+        match self.last_task() {
+            Some(task) if self.resume_last_task() => task,
+            _ => self.startup_page(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -299,6 +669,47 @@ mod tests {
         assert_eq!(validate_theme("hacked"), None);
     }
 
+    #[test]
+    fn test_validate_status_palette() {
+        assert_eq!(validate_status_palette("default"), Some("default"));
+        assert_eq!(validate_status_palette("red-green"), Some("red-green"));
+        assert_eq!(validate_status_palette("blue-yellow"), Some("blue-yellow"));
+        assert_eq!(validate_status_palette("rainbow"), None);
+    }
+
+    #[test]
+    fn test_validate_startup_page() {
+        assert_eq!(validate_startup_page("overview"), Some("overview"));
+        assert_eq!(validate_startup_page("ports"), Some("ports"));
+        assert_eq!(validate_startup_page("help"), None);
+        assert_eq!(validate_last_task("platform"), Some("platform"));
+        assert_eq!(validate_last_task("ports"), None);
+    }
+
+    #[test]
+    fn test_initial_page() {
+        let mut settings = Settings {
+            settings: AppSettings {
+                startup_page: "ports".to_string(),
+                last_task: Some("cleanup".to_string()),
+                ..AppSettings::default()
+            },
+            path: PathBuf::from("/nonexistent/settings.json"),
+        };
+        assert_eq!(settings.initial_page(), "ports");
+        settings.settings.resume_last_task = true;
+        assert_eq!(settings.initial_page(), "cleanup");
+        settings.settings.last_task = None;
+        assert_eq!(settings.initial_page(), "ports");
+    }
+
+    #[test]
+    fn test_validate_density() {
+        assert_eq!(validate_density("comfortable"), Some("comfortable"));
+        assert_eq!(validate_density("compact"), Some("compact"));
+        assert_eq!(validate_density("tiny"), None);
+    }
+
     #[test]
     fn test_clamp_dashboard_max_apps() {
         assert_eq!(clamp_dashboard_max_apps(0), DASHBOARD_MAX_APPS_MIN);
@@ -310,6 +721,102 @@ mod tests {
     fn test_defaults_include_new_fields() {
         let s = AppSettings::default();
         assert_eq!(s.dashboard_max_apps, 6);
+        assert_eq!(s.status_palette, "default");
+        assert_eq!(s.density, "comfortable");
         assert!(s.enable_online_ip_lookup);
+        assert!(!s.cleanup_categories.contains(&"shell-history".to_string()));
+        assert_eq!(s.cleanup_interval_days, 0);
+        assert!(!s.api_enabled);
+        assert_eq!(s.api_port, crate::api::DEFAULT_API_PORT);
+        assert!(!s.status_feed_enabled);
+        assert!(s.data_quotas.is_empty());
+        assert!(s.tripwire_ports.is_empty());
+        assert!(!s.tripwire_auto_block);
+        assert!(!s.lock_on_key_removal);
+        assert!(s.dismissed_zone_suggestions.is_empty());
+        assert!(s.checklist_progress.is_empty());
+    }
+
+    #[test]
+    fn test_checklist_progress() {
+        let path = std::env::temp_dir().join(format!(
+            "security-center-checklist-test-{}.json",
+            std::process::id()
+        ));
+        let mut settings = Settings {
+            settings: AppSettings::default(),
+            path: path.clone(),
+        };
+        let nas = ChecklistRole::HomeNas;
+        settings.set_checklist_item_done(nas, "backups", true);
+        settings.set_checklist_item_done(nas, "backups", true);
+        settings.set_checklist_item_done(nas, "no-such-item", true);
+        assert_eq!(settings.checklist_done(nas), ["backups".to_string()]);
+        assert!(settings.checklist_done(ChecklistRole::WebServer).is_empty());
+        settings.set_checklist_item_done(nas, "backups", false);
+        assert!(settings.settings.checklist_progress.is_empty());
+
+        let mut stale = AppSettings::default();
+        stale.checklist_progress.insert(
+            "home-nas".to_string(),
+            vec!["backups".to_string(), "gone".to_string()],
+        );
+        stale
+            .checklist_progress
+            .insert("router".to_string(), vec!["backups".to_string()]);
+        let sanitized = stale.sanitized();
+        assert_eq!(sanitized.checklist_progress.len(), 1);
+        assert_eq!(sanitized.checklist_progress["home-nas"], ["backups"]);
+        let _ = fs::remove_file(path.with_extension("json.bak"));
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_dismiss_zone_suggestion() {
+        let path = std::env::temp_dir().join(format!(
+            "security-center-settings-test-{}.json",
+            std::process::id()
+        ));
+        let mut settings = Settings {
+            settings: AppSettings::default(),
+            path: path.clone(),
+        };
+        settings.dismiss_zone_suggestion("uuid-a:home");
+        settings.dismiss_zone_suggestion("uuid-a:home");
+        assert!(settings.is_zone_suggestion_dismissed("uuid-a:home"));
+        assert!(!settings.is_zone_suggestion_dismissed("uuid-a:public"));
+        assert_eq!(settings.settings.dismissed_zone_suggestions.len(), 1);
+
+        for i in 0..MAX_DISMISSED_ZONE_SUGGESTIONS {
+            settings.dismiss_zone_suggestion(&format!("uuid-{}:public", i));
+        }
+        assert!(!settings.is_zone_suggestion_dismissed("uuid-a:home"));
+        assert_eq!(
+            settings.settings.dismissed_zone_suggestions.len(),
+            MAX_DISMISSED_ZONE_SUGGESTIONS
+        );
+        let _ = fs::remove_file(path.with_extension("json.bak"));
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_unversioned_settings_load() {
+        let dir = std::env::temp_dir().join(format!(
+            "security-center-settings-schema-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("settings.json");
+        fs::write(&path, r#"{"theme":"dark","api_port":1}"#).unwrap();
+
+        let loaded: AppSettings = migration::load(&SCHEMA, &path, MAX_CONFIG_FILE_SIZE).unwrap();
+        assert_eq!(loaded.theme, "dark");
+        assert_eq!(loaded.sanitized().api_port, crate::api::clamp_api_port(1));
+        assert!(dir.join("settings.json.v0.bak").exists());
+
+        // The version field written back does not trip deny_unknown_fields
+        let reloaded: AppSettings = migration::load(&SCHEMA, &path, MAX_CONFIG_FILE_SIZE).unwrap();
+        assert_eq!(reloaded.theme, "dark");
+        let _ = fs::remove_dir_all(&dir);
     }
 }
@@ -0,0 +1,363 @@
+// Security Center - Data Usage
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Monthly data usage per network interface, for quotas on metered
+//! connections such as mobile broadband or a tethered phone.
+//!
+//! While the application or the background monitor runs,
+//! [`UsageMeter::sample`] adds the bytes each interface received and sent
+//! since the previous sample to a counter for the calendar month. The
+//! counters are kept in `~/.local/share/security-center/data-usage.json`, so
+//! they survive restarts, and start again from zero when the month changes.
+//! Traffic while neither runs is not counted.
+//!
+//! When an interface with a quota crosses 50, 90 or 100 percent of it, the
+//! sample returns a [`QuotaAlert`]; each threshold is reported once a month.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// Bytes in one quota unit (GB) as shown in Preferences.
+pub const BYTES_PER_GB: u64 = 1024 * 1024 * 1024;
+/// Shares of the quota that raise an alert, in percent.
+pub const THRESHOLDS: [u8; 3] = [50, 90, 100];
+/// Time between samples.
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+/// How often the counters are written to disk while they change.
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+const MAX_LEDGER_FILE_SIZE: u64 = 1_048_576; // 1 MB
+
+/// Location of the persistent counters.
+pub fn ledger_path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("security-center")
+        .join("data-usage.json")
+}
+
+/// The current month as `YYYY-MM`, the period counters are kept for.
+pub fn current_month() -> String {
+    chrono::Local::now().format("%Y-%m").to_string()
+}
+
+/// Cumulative received plus sent bytes of every non-loopback interface.
+pub fn read_counters() -> Vec<(String, u64)> {
+    fs::read_to_string("/proc/net/dev")
+        .map(|content| parse_counters(&content))
+        .unwrap_or_default()
+}
+
+/// Parse `/proc/net/dev` into (interface, rx + tx bytes).
+fn parse_counters(content: &str) -> Vec<(String, u64)> {
+    content
+        .lines()
+        .skip(2)
+        .filter_map(|line| {
+            let (iface, stats) = line.split_once(':')?;
+            let iface = iface.trim();
+            if iface.is_empty() || iface == "lo" {
+                return None;
+            }
+            let fields: Vec<&str> = stats.split_whitespace().collect();
+            // Receive bytes is field 0, transmit bytes field 8
+            let rx = fields.first()?.parse::<u64>().ok()?;
+            let tx = fields.get(8)?.parse::<u64>().ok()?;
+            Some((iface.to_string(), rx.saturating_add(tx)))
+        })
+        .collect()
+}
+
+/// Usage of one interface that has a quota.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaUsage {
+    pub interface: String,
+    /// Bytes received and sent this month.
+    pub used: u64,
+    /// Monthly quota in bytes.
+    pub quota: u64,
+}
+
+impl QuotaUsage {
+    /// Share of the quota used; above 1.0 once it is exceeded.
+    pub fn fraction(&self) -> f64 {
+        if self.quota == 0 {
+            return 0.0;
+        }
+        self.used as f64 / self.quota as f64
+    }
+}
+
+/// An interface crossed one of the [`THRESHOLDS`] of its quota.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaAlert {
+    pub interface: String,
+    /// The highest threshold reached, in percent.
+    pub threshold: u8,
+    pub used: u64,
+    pub quota: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InterfaceUsage {
+    bytes: u64,
+    /// Highest threshold already reported this month.
+    #[serde(default)]
+    alerted: u8,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Ledger {
+    month: String,
+    #[serde(default)]
+    interfaces: BTreeMap<String, InterfaceUsage>,
+}
+
+/// The monthly counters and the interface readings they were last
+/// advanced from.
+#[derive(Debug)]
+pub struct UsageMeter {
+    ledger: Ledger,
+    /// Counter value of each interface at the previous sample.
+    baseline: HashMap<String, u64>,
+    path: PathBuf,
+    dirty: bool,
+    last_save: Instant,
+}
+
+impl UsageMeter {
+    /// Load the counters saved by a previous run.
+    pub fn load() -> Self {
+        let path = ledger_path();
+        let ledger = match fs::metadata(&path) {
+            Ok(m) if m.len() > MAX_LEDGER_FILE_SIZE => {
+                warn!("Data usage file too large ({} bytes), ignoring it", m.len());
+                Ledger::default()
+            }
+            Ok(_) => match fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|text| serde_json::from_str(&text).map_err(anyhow::Error::from))
+            {
+                Ok(ledger) => ledger,
+                Err(e) => {
+                    warn!("Failed to read data usage: {}", e);
+                    Ledger::default()
+                }
+            },
+            Err(_) => Ledger::default(),
+        };
+        Self::with_ledger(path, ledger)
+    }
+
+    fn with_ledger(path: PathBuf, ledger: Ledger) -> Self {
+        Self {
+            ledger,
+            baseline: HashMap::new(),
+            path,
+            dirty: false,
+            last_save: Instant::now(),
+        }
+    }
+
+    /// Add the traffic since the previous sample to `month`'s counters and
+    /// report the thresholds newly crossed. `quotas` maps interfaces to
+    /// monthly quotas in bytes.
+    pub fn sample(
+        &mut self,
+        counters: &[(String, u64)],
+        month: &str,
+        quotas: &BTreeMap<String, u64>,
+    ) -> Vec<QuotaAlert> {
+        if self.ledger.month != month {
+            self.ledger.month = month.to_string();
+            self.ledger.interfaces.clear();
+            self.dirty = true;
+        }
+
+        for (iface, total) in counters {
+            // The first reading of an interface only sets its baseline
+            let Some(previous) = self.baseline.insert(iface.clone(), *total) else {
+                continue;
+            };
+            // Counters restart when the interface is recreated
+            let delta = if *total >= previous {
+                total - previous
+            } else {
+                *total
+            };
+            if delta > 0 {
+                let usage = self.ledger.interfaces.entry(iface.clone()).or_default();
+                usage.bytes = usage.bytes.saturating_add(delta);
+                self.dirty = true;
+            }
+        }
+
+        let mut alerts = Vec::new();
+        for (iface, &quota) in quotas {
+            let usage = self.ledger.interfaces.entry(iface.clone()).or_default();
+            let reached = threshold_reached(usage.bytes, quota);
+            if reached > usage.alerted {
+                alerts.push(QuotaAlert {
+                    interface: iface.clone(),
+                    threshold: reached,
+                    used: usage.bytes,
+                    quota,
+                });
+            }
+            // Raising a quota re-arms the thresholds now below the usage
+            if usage.alerted != reached {
+                usage.alerted = reached;
+                self.dirty = true;
+            }
+        }
+        alerts
+    }
+
+    /// Bytes `iface` received and sent this month.
+    pub fn used(&self, iface: &str) -> u64 {
+        self.ledger.interfaces.get(iface).map_or(0, |u| u.bytes)
+    }
+
+    /// Usage of every interface with a quota, in interface order.
+    pub fn usage(&self, quotas: &BTreeMap<String, u64>) -> Vec<QuotaUsage> {
+        quotas
+            .iter()
+            .map(|(iface, &quota)| QuotaUsage {
+                interface: iface.clone(),
+                used: self.used(iface),
+                quota,
+            })
+            .collect()
+    }
+
+    /// Write the counters if they changed.
+    pub fn save(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        match serde_json::to_string_pretty(&self.ledger) {
+            Ok(text) => {
+                // Write beside the file and rename, so a crash mid-write
+                // cannot lose the month's counters
+                let partial = self.path.with_extension("json.tmp");
+                if let Err(e) =
+                    fs::write(&partial, text).and_then(|_| fs::rename(&partial, &self.path))
+                {
+                    warn!("Failed to save data usage: {}", e);
+                }
+            }
+            Err(e) => warn!("Failed to serialize data usage: {}", e),
+        }
+        self.dirty = false;
+        self.last_save = Instant::now();
+    }
+
+    /// Write the counters if they changed and were last written more than
+    /// a minute ago.
+    pub fn save_if_due(&mut self) {
+        if self.last_save.elapsed() >= SAVE_INTERVAL {
+            self.save();
+        }
+    }
+}
+
+/// Highest threshold `used` has reached of `quota`, or 0.
+fn threshold_reached(used: u64, quota: u64) -> u8 {
+    if quota == 0 {
+        return 0;
+    }
+    let percent = used as u128 * 100 / quota as u128;
+    THRESHOLDS
+        .iter()
+        .rev()
+        .find(|&&t| percent >= t as u128)
+        .copied()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meter() -> UsageMeter {
+        UsageMeter::with_ledger(PathBuf::new(), Ledger::default())
+    }
+
+    fn counters(total: u64) -> Vec<(String, u64)> {
+        vec![("wwan0".to_string(), total)]
+    }
+
+    #[test]
+    fn test_parse_counters() {
+        let content = "Inter-|   Receive                                                |  Transmit\n \
+            face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed\n    \
+            lo:  5000      50    0    0    0     0          0         0     5000      50    0    0    0     0       0          0\n  \
+            wwan0: 1200      10    0    0    0     0          0         0      300       3    0    0    0     0       0          0\n";
+        assert_eq!(parse_counters(content), vec![("wwan0".to_string(), 1500)]);
+    }
+
+    #[test]
+    fn test_sample_accumulates_deltas() {
+        let mut meter = meter();
+        let quotas = BTreeMap::new();
+        meter.sample(&counters(1_000), "2026-10", &quotas);
+        assert_eq!(meter.used("wwan0"), 0);
+        meter.sample(&counters(1_600), "2026-10", &quotas);
+        assert_eq!(meter.used("wwan0"), 600);
+        // Interface recreated: its counter starts over
+        meter.sample(&counters(100), "2026-10", &quotas);
+        assert_eq!(meter.used("wwan0"), 700);
+        // New month
+        meter.sample(&counters(150), "2026-11", &quotas);
+        assert_eq!(meter.used("wwan0"), 50);
+    }
+
+    #[test]
+    fn test_thresholds_alert_once() {
+        let mut meter = meter();
+        let quotas = BTreeMap::from([("wwan0".to_string(), 1_000)]);
+        meter.sample(&counters(0), "2026-10", &quotas);
+        assert!(meter.sample(&counters(400), "2026-10", &quotas).is_empty());
+
+        let alerts = meter.sample(&counters(500), "2026-10", &quotas);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].threshold, 50);
+        assert!(meter.sample(&counters(600), "2026-10", &quotas).is_empty());
+
+        // Jumping past two thresholds reports the highest
+        let alerts = meter.sample(&counters(1_200), "2026-10", &quotas);
+        assert_eq!(alerts[0].threshold, 100);
+        assert!(meter
+            .sample(&counters(1_300), "2026-10", &quotas)
+            .is_empty());
+
+        // Raising the quota re-arms the higher thresholds
+        let raised = BTreeMap::from([("wwan0".to_string(), 2_000)]);
+        assert!(meter
+            .sample(&counters(1_300), "2026-10", &raised)
+            .is_empty());
+        let alerts = meter.sample(&counters(1_900), "2026-10", &raised);
+        assert_eq!(alerts[0].threshold, 90);
+    }
+
+    #[test]
+    fn test_usage_fraction() {
+        let usage = QuotaUsage {
+            interface: "wwan0".to_string(),
+            used: 1_500,
+            quota: 1_000,
+        };
+        assert_eq!(usage.fraction(), 1.5);
+        assert_eq!(threshold_reached(10, 0), 0);
+    }
+}
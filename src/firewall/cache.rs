@@ -0,0 +1,158 @@
+// Security Center - Firewalld Metadata Cache
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Session cache of firewalld metadata that only changes when firewalld
+//! reloads or its permanent configuration is edited: the zone and service
+//! names and each service's definition. Every client shares it, so a
+//! refresh does not list and describe all services over D-Bus again.
+//!
+//! The cache carries a generation that [`invalidate`] bumps. A lookup that
+//! misses records the generation before calling firewalld and stores its
+//! answer only if no invalidation happened meanwhile, so a reply read
+//! before a reload never outlives it.
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+
+use tracing::debug;
+
+use super::interfaces;
+use crate::models::Service;
+
+static CACHE: Mutex<MetadataCache> = Mutex::new(MetadataCache::new());
+
+/// Cached firewalld metadata for one configuration generation.
+#[derive(Debug)]
+pub struct MetadataCache {
+    generation: u64,
+    zone_names: Option<Vec<String>>,
+    service_names: Option<Vec<String>>,
+    services: BTreeMap<String, Service>,
+}
+
+impl MetadataCache {
+    const fn new() -> Self {
+        Self {
+            generation: 0,
+            zone_names: None,
+            service_names: None,
+            services: BTreeMap::new(),
+        }
+    }
+
+    /// Current generation, to pass back when storing a reply.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Drop everything and start a new generation.
+    pub fn invalidate(&mut self) {
+        self.generation += 1;
+        self.zone_names = None;
+        self.service_names = None;
+        self.services.clear();
+    }
+
+    pub fn zone_names(&self) -> Option<Vec<String>> {
+        self.zone_names.clone()
+    }
+
+    pub fn store_zone_names(&mut self, generation: u64, names: &[String]) {
+        if generation == self.generation {
+            self.zone_names = Some(names.to_vec());
+        }
+    }
+
+    pub fn service_names(&self) -> Option<Vec<String>> {
+        self.service_names.clone()
+    }
+
+    pub fn store_service_names(&mut self, generation: u64, names: &[String]) {
+        if generation == self.generation {
+            self.service_names = Some(names.to_vec());
+        }
+    }
+
+    /// A service definition, without its per-zone enabled state.
+    pub fn service(&self, name: &str) -> Option<Service> {
+        self.services.get(name).cloned()
+    }
+
+    pub fn store_service(&mut self, generation: u64, service: &Service) {
+        if generation == self.generation {
+            self.services.insert(service.name.clone(), service.clone());
+        }
+    }
+}
+
+/// The shared cache.
+pub fn cache() -> MutexGuard<'static, MetadataCache> {
+    // Nothing in the cache can be left half-updated by a panic
+    CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Forget all cached metadata.
+pub fn invalidate() {
+    debug!("Invalidating firewalld metadata cache");
+    cache().invalidate();
+}
+
+/// Whether a firewalld signal means the cached metadata may be stale:
+/// a reload, or any change to the permanent configuration.
+pub fn invalidated_by(interface: &str, member: &str) -> bool {
+    (interface == interfaces::MAIN && member == "Reloaded")
+        || interface == interfaces::CONFIG
+        || interface.starts_with(&format!("{}.", interfaces::CONFIG))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_and_invalidate() {
+        let mut cache = MetadataCache::new();
+        assert!(cache.service_names().is_none());
+
+        let generation = cache.generation();
+        cache.store_service_names(generation, &["ssh".to_string()]);
+        cache.store_service(generation, &Service::new("ssh"));
+        assert_eq!(cache.service_names(), Some(vec!["ssh".to_string()]));
+        assert!(cache.service("ssh").is_some());
+
+        cache.invalidate();
+        assert!(cache.service_names().is_none());
+        assert!(cache.service("ssh").is_none());
+    }
+
+    #[test]
+    fn test_stale_reply_is_dropped() {
+        let mut cache = MetadataCache::new();
+        let generation = cache.generation();
+        // A reload lands while the reply is in flight
+        cache.invalidate();
+        cache.store_zone_names(generation, &["public".to_string()]);
+        cache.store_service(generation, &Service::new("ssh"));
+        assert!(cache.zone_names().is_none());
+        assert!(cache.service("ssh").is_none());
+    }
+
+    #[test]
+    fn test_invalidated_by() {
+        assert!(invalidated_by(interfaces::MAIN, "Reloaded"));
+        assert!(invalidated_by(interfaces::CONFIG, "ServiceAdded"));
+        assert!(invalidated_by(
+            "org.fedoraproject.FirewallD1.config.service",
+            "Updated"
+        ));
+        assert!(!invalidated_by(interfaces::ZONE, "ServiceAdded"));
+        assert!(!invalidated_by(interfaces::MAIN, "PanicModeEnabled"));
+        assert!(!invalidated_by(
+            "org.fedoraproject.FirewallD1.configx",
+            "Updated"
+        ));
+    }
+}
@@ -5,26 +5,55 @@
 //! Firewalld D-Bus client implementation.
 
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 
 use anyhow::{anyhow, Context, Result};
 use tokio::sync::broadcast;
 use tracing::{info, warn};
 use zbus::blocking::{Connection, Proxy};
 use zbus::proxy::MethodFlags;
-use zbus::zvariant::{ObjectPath, OwnedObjectPath};
-
-use super::{interfaces, paths, zone_description, BUS_NAME};
-use crate::models::{Interface, Service, Zone};
-use crate::validation::validate_zone_name;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Structure, Value};
+
+use super::info::{FirewallInfo, FirewallState};
+use super::{cache, interfaces, is_builtin_zone, paths, signals, zone_description, BUS_NAME};
+use crate::models::{
+    BlockedSource, DriftItem, DriftKind, DriftSide, Family, Interface, Service, Zone, ZoneRules,
+    ZoneSettings, ZONE_TARGETS,
+};
+use crate::validation::{
+    parse_port_spec, validate_firewall_service, validate_icmp_type, validate_interface_name,
+    validate_protocol, validate_zone_name,
+};
 
 /// Events emitted by the firewall client.
-#[derive(Debug, Clone)]
+///
+/// Besides the client's own changes, [`FirewallClient::watch_signals`]
+/// reports changes announced by firewalld, whoever made them.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FirewallEvent {
+    /// firewalld is on the bus, e.g. after starting.
     Connected,
+    /// firewalld left the bus.
     Disconnected,
+    /// Something else changed, such as a service or rich rule.
     StateChanged,
     Error(String),
+    Reloaded,
+    /// Panic mode was switched on (`true`) or off.
+    PanicModeChanged(bool),
+    DefaultZoneChanged(String),
+    /// An interface or source was bound to or removed from the zone.
+    ZoneChanged(String),
+    PortAdded {
+        zone: String,
+        port: String,
+        protocol: String,
+    },
+    PortRemoved {
+        zone: String,
+        port: String,
+        protocol: String,
+    },
 }
 
 /// Outcome of the permanent-config half of a firewall change.
@@ -49,6 +78,25 @@ impl PermanentOutcome {
     }
 }
 
+/// Reply of the legacy `getServiceSettings` call: version, short name,
+/// description, ports, modules, destinations, protocols, source ports.
+type LegacyServiceSettings = (
+    String,
+    String,
+    String,
+    Vec<(String, String)>,
+    Vec<String>,
+    HashMap<String, String>,
+    Vec<String>,
+    Vec<(String, String)>,
+);
+
+/// Accepted values of firewalld's LogDenied setting.
+pub const LOG_DENIED_VALUES: [&str; 5] = ["off", "all", "unicast", "broadcast", "multicast"];
+
+/// Accepted values of firewalld's AutomaticHelpers setting.
+pub const AUTOMATIC_HELPERS_VALUES: [&str; 3] = ["system", "yes", "no"];
+
 /// Client for interacting with firewalld via D-Bus.
 pub struct FirewallClient {
     connection: Option<Connection>,
@@ -74,23 +122,51 @@ impl FirewallClient {
         self.event_sender.subscribe()
     }
 
-    /// Connect to firewalld.
+    /// Follow firewalld's D-Bus signals on a background thread and send
+    /// each change to this client's subscribers, until none are left.
+    pub fn watch_signals(&self) {
+        let sender = self.event_sender.clone();
+        std::thread::spawn(move || {
+            let runtime = match tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    warn!("Cannot start the firewalld signal watcher: {}", e);
+                    return;
+                }
+            };
+            if let Err(e) = runtime.block_on(signals::watch(sender)) {
+                warn!("Stopped following firewalld signals: {:#}", e);
+            }
+        });
+    }
+
+    /// Connect to firewalld. Every client shares one system bus
+    /// connection, so connecting again only re-checks firewalld's state.
     pub fn connect(&mut self) -> Result<()> {
         info!("Connecting to firewalld...");
 
-        let conn = Connection::system().context("Failed to connect to system D-Bus")?;
+        let mut conn = shared_bus()?;
 
-        // Test connection by getting the default zone
-        let _: String = conn
-            .call_method(
-                Some(BUS_NAME),
-                paths::ROOT,
-                Some(interfaces::MAIN),
-                "getDefaultZone",
-                &(),
-            )?
-            .body()
-            .deserialize()?;
+        // Test the connection by reading firewalld's state
+        let state = match read_state(&conn) {
+            Err(e) if is_broken_bus(&e) => {
+                warn!("Reopening the system bus connection: {:#}", e);
+                forget_shared_bus();
+                conn = shared_bus()?;
+                read_state(&conn)?
+            }
+            result => result?,
+        };
+        match state {
+            FirewallState::Init => return Err(anyhow!("firewalld is still starting")),
+            FirewallState::Failed => {
+                warn!("firewalld failed to load its configuration and uses fallback rules")
+            }
+            _ => {}
+        }
 
         self.connection = Some(conn);
         let _ = self.event_sender.send(FirewallEvent::Connected);
@@ -104,6 +180,49 @@ impl FirewallClient {
         self.connection.is_some()
     }
 
+    /// Get firewalld's state: starting, running or failed.
+    pub fn get_state(&self) -> Result<FirewallState> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or_else(|| anyhow!("Not connected to firewalld"))?;
+        read_state(conn)
+    }
+
+    /// Get firewalld's version, state, backend and supported features.
+    pub fn get_info(&self) -> Result<FirewallInfo> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or_else(|| anyhow!("Not connected to firewalld"))?;
+
+        let props: HashMap<String, OwnedValue> = conn
+            .call_method(
+                Some(BUS_NAME),
+                paths::ROOT,
+                Some(interfaces::PROPERTIES),
+                "GetAll",
+                &(interfaces::MAIN,),
+            )?
+            .body()
+            .deserialize()?;
+
+        // FirewallBackend appeared in firewalld 0.6
+        let backend = conn
+            .call_method(
+                Some(BUS_NAME),
+                paths::CONFIG,
+                Some(interfaces::PROPERTIES),
+                "Get",
+                &(interfaces::CONFIG, "FirewallBackend"),
+            )
+            .ok()
+            .and_then(|reply| reply.body().deserialize::<OwnedValue>().ok())
+            .and_then(|value| String::try_from(value).ok());
+
+        Ok(FirewallInfo::from_properties(&props, backend))
+    }
+
     /// Call a firewalld method allowing polkit to prompt interactively.
     ///
     /// Without the ALLOW_INTERACTIVE_AUTHORIZATION flag, systems whose polkit
@@ -177,16 +296,25 @@ impl FirewallClient {
             .ok_or_else(|| anyhow!("Not connected to firewalld"))?;
 
         // Get zone names
-        let zone_names: Vec<String> = conn
-            .call_method(
-                Some(BUS_NAME),
-                paths::ROOT,
-                Some(interfaces::ZONE),
-                "getZones",
-                &(),
-            )?
-            .body()
-            .deserialize()?;
+        let cached = cache::cache().zone_names();
+        let zone_names: Vec<String> = match cached {
+            Some(names) => names,
+            None => {
+                let generation = cache::cache().generation();
+                let names: Vec<String> = conn
+                    .call_method(
+                        Some(BUS_NAME),
+                        paths::ROOT,
+                        Some(interfaces::ZONE),
+                        "getZones",
+                        &(),
+                    )?
+                    .body()
+                    .deserialize()?;
+                cache::cache().store_zone_names(generation, &names);
+                names
+            }
+        };
 
         // Get active zones
         let active_zones: HashMap<String, HashMap<String, Vec<String>>> = conn
@@ -208,6 +336,15 @@ impl FirewallClient {
         for name in zone_names {
             let mut zone = Zone::new(&name);
             zone.description = zone_description(&name).to_string();
+            if let Ok(mut settings) = self.get_runtime_zone_settings(&name) {
+                // Show the description given to a zone created by the user
+                let description: String = take(&mut settings, "description");
+                if !is_builtin_zone(&name) && !description.is_empty() {
+                    zone.description = description;
+                }
+                zone.icmp_blocks = take(&mut settings, "icmp_blocks");
+                zone.icmp_block_inversion = take(&mut settings, "icmp_block_inversion");
+            }
             zone.is_active = active_zones.contains_key(&name);
             zone.is_default = name == default_zone;
 
@@ -226,11 +363,17 @@ impl FirewallClient {
                 zone.rich_rules = rules;
             }
 
+            // Permanent rules, to flag those a reload would drop
+            zone.permanent = self.get_permanent_zone_rules(&name).ok();
+
             // Get zone interfaces from active zones
             if let Some(info) = active_zones.get(&name) {
                 if let Some(ifaces) = info.get("interfaces") {
                     zone.interfaces = ifaces.clone();
                 }
+                if let Some(sources) = info.get("sources") {
+                    zone.sources = sources.clone();
+                }
             }
 
             zones.push(zone);
@@ -319,24 +462,304 @@ impl FirewallClient {
         Ok(rules)
     }
 
-    /// Get all available services.
-    pub fn get_services(&mut self) -> Result<Vec<Service>> {
+    /// Get the runtime settings of a zone, leaving out those with their
+    /// default value. Needs firewalld 0.9 or newer.
+    fn get_runtime_zone_settings(&self, zone: &str) -> Result<HashMap<String, OwnedValue>> {
         let conn = self
             .connection
             .as_ref()
             .ok_or_else(|| anyhow!("Not connected to firewalld"))?;
 
-        // Get all service names (this is on the main interface)
-        let service_names: Vec<String> = conn
+        let settings: HashMap<String, OwnedValue> = conn
+            .call_method(
+                Some(BUS_NAME),
+                paths::ROOT,
+                Some(interfaces::ZONE),
+                "getZoneSettings2",
+                &(zone,),
+            )?
+            .body()
+            .deserialize()?;
+
+        Ok(settings)
+    }
+
+    /// Get the target of a zone: `default`, `ACCEPT`, `DROP` or
+    /// `%%REJECT%%`. Needs firewalld 0.9 or newer.
+    pub fn get_zone_target(&self, zone: &str) -> Result<String> {
+        let mut settings = self.get_runtime_zone_settings(zone)?;
+
+        match settings.remove("target") {
+            Some(target) => Ok(String::try_from(target)?),
+            None => Ok("default".to_string()),
+        }
+    }
+
+    /// Get the settings of a zone's permanent configuration, leaving out
+    /// those with their default value. Falls back to the older `getSettings`
+    /// before firewalld 0.9.
+    fn get_permanent_zone_settings(&self, zone: &str) -> Result<HashMap<String, OwnedValue>> {
+        validate_zone_name(zone).ok_or_else(|| anyhow!("Invalid zone name: {}", zone))?;
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or_else(|| anyhow!("Not connected to firewalld"))?;
+        let path = self.get_zone_config_path(zone)?;
+
+        let reply = match conn.call_method(
+            Some(BUS_NAME),
+            path.as_str(),
+            Some(interfaces::CONFIG_ZONE),
+            "getSettings2",
+            &(),
+        ) {
+            Ok(reply) => reply,
+            Err(e) if e.to_string().contains("UnknownMethod") => {
+                return self.get_legacy_zone_settings(&path);
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let settings: HashMap<String, OwnedValue> = reply.body().deserialize()?;
+
+        Ok(settings)
+    }
+
+    /// Get the settings of a zone's permanent configuration with
+    /// `getSettings`, keyed like `getSettings2`. The reply is a structure
+    /// whose fields are fixed by position; later firewalld versions
+    /// appended fields, so missing trailing ones are left out.
+    fn get_legacy_zone_settings(&self, path: &str) -> Result<HashMap<String, OwnedValue>> {
+        /// Names of the structure fields, in order; `None` for unused ones.
+        const FIELDS: [Option<&str>; 16] = [
+            None, // version
+            Some("short"),
+            Some("description"),
+            None, // unused
+            Some("target"),
+            Some("services"),
+            Some("ports"),
+            Some("icmp_blocks"),
+            Some("masquerade"),
+            Some("forward_ports"),
+            Some("interfaces"),
+            Some("sources"),
+            Some("rich_rules"),
+            Some("protocols"),
+            Some("source_ports"),
+            Some("icmp_block_inversion"),
+        ];
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or_else(|| anyhow!("Not connected to firewalld"))?;
+
+        let reply = conn.call_method(
+            Some(BUS_NAME),
+            path,
+            Some(interfaces::CONFIG_ZONE),
+            "getSettings",
+            &(),
+        )?;
+        let body = reply.body();
+        let structure: Structure = body.deserialize()?;
+
+        let mut settings = HashMap::new();
+        for (name, value) in FIELDS.iter().zip(structure.into_fields()) {
+            if let Some(name) = name {
+                settings.insert(name.to_string(), OwnedValue::try_from(value)?);
+            }
+        }
+        Ok(settings)
+    }
+
+    /// Get the services, ports and rich rules of a zone's permanent
+    /// configuration, to tell which runtime rules a reload would drop.
+    pub fn get_permanent_zone_rules(&self, zone: &str) -> Result<ZoneRules> {
+        let mut settings = self.get_permanent_zone_settings(zone)?;
+        let ports: Vec<(String, String)> = take(&mut settings, "ports");
+        Ok(ZoneRules {
+            services: take(&mut settings, "services"),
+            ports: ports
+                .into_iter()
+                .map(|(port, protocol)| format!("{}/{}", port, protocol))
+                .collect(),
+            rich_rules: take(&mut settings, "rich_rules"),
+        })
+    }
+
+    /// Get the editable settings of a zone's permanent configuration.
+    /// Needs firewalld 0.9 or newer.
+    pub fn get_zone_settings(&self, zone: &str) -> Result<ZoneSettings> {
+        let mut settings = self.get_permanent_zone_settings(zone)?;
+
+        let target: String = take(&mut settings, "target");
+        Ok(ZoneSettings {
+            short: take(&mut settings, "short"),
+            description: take(&mut settings, "description"),
+            target: if target.is_empty() {
+                "default".to_string()
+            } else {
+                target
+            },
+            icmp_blocks: take(&mut settings, "icmp_blocks"),
+            icmp_block_inversion: take(&mut settings, "icmp_block_inversion"),
+            masquerade: take(&mut settings, "masquerade"),
+            forward: take(&mut settings, "forward"),
+        })
+    }
+
+    /// Write the editable settings of a zone's permanent configuration.
+    /// The running firewall picks them up at the next reload.
+    pub fn update_zone_settings(&self, zone: &str, settings: &ZoneSettings) -> Result<()> {
+        validate_zone_name(zone).ok_or_else(|| anyhow!("Invalid zone name: {}", zone))?;
+        if !ZONE_TARGETS.contains(&settings.target.as_str()) {
+            return Err(anyhow!("Invalid zone target: {}", settings.target));
+        }
+        let path = self.get_zone_config_path(zone)?;
+
+        // update2 replaces only the keys given, leaving services, ports,
+        // rules and bindings alone
+        let mut dict: HashMap<&str, Value> = HashMap::new();
+        dict.insert("short", Value::from(settings.short.as_str()));
+        dict.insert("description", Value::from(settings.description.as_str()));
+        dict.insert("target", Value::from(settings.target.as_str()));
+        dict.insert("icmp_blocks", Value::from(settings.icmp_blocks.clone()));
+        dict.insert(
+            "icmp_block_inversion",
+            Value::from(settings.icmp_block_inversion),
+        );
+        dict.insert("masquerade", Value::from(settings.masquerade));
+        dict.insert("forward", Value::from(settings.forward));
+
+        let _: Option<()> = self.call_interactive(
+            ObjectPath::try_from(path.as_str())?,
+            interfaces::CONFIG_ZONE,
+            "update2",
+            &(dict,),
+        )?;
+
+        info!("Updated permanent settings of zone {}", zone);
+        let _ = self.event_sender.send(FirewallEvent::StateChanged);
+        Ok(())
+    }
+
+    /// Create a zone in the permanent configuration. firewalld loads it
+    /// into the running firewall at the next reload.
+    pub fn create_zone(&self, name: &str, target: &str, description: &str) -> Result<()> {
+        validate_zone_name(name).ok_or_else(|| anyhow!("Invalid zone name: {}", name))?;
+        if !ZONE_TARGETS.contains(&target) {
+            return Err(anyhow!("Invalid zone target: {}", target));
+        }
+
+        let mut dict: HashMap<&str, Value> = HashMap::new();
+        dict.insert("short", Value::from(name));
+        dict.insert("description", Value::from(description));
+        dict.insert("target", Value::from(target));
+
+        let _: Option<OwnedObjectPath> = self.call_interactive(
+            ObjectPath::try_from(paths::CONFIG)?,
+            interfaces::CONFIG,
+            "addZone2",
+            &(name, dict),
+        )?;
+
+        info!("Created zone {}", name);
+        cache::invalidate();
+        let _ = self.event_sender.send(FirewallEvent::StateChanged);
+        Ok(())
+    }
+
+    /// Delete a zone created by the user from the permanent configuration.
+    /// Zones shipped with firewalld and the default zone are refused. The
+    /// running firewall drops it at the next reload.
+    pub fn delete_zone(&self, name: &str) -> Result<()> {
+        validate_zone_name(name).ok_or_else(|| anyhow!("Invalid zone name: {}", name))?;
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or_else(|| anyhow!("Not connected to firewalld"))?;
+        if self.get_default_zone()? == name {
+            return Err(anyhow!("'{}' is the default zone", name));
+        }
+        let path = self.get_zone_config_path(name)?;
+
+        // Removing a built-in zone would only reset it to its defaults
+        let builtin: OwnedValue = conn
+            .call_method(
+                Some(BUS_NAME),
+                path.as_str(),
+                Some(interfaces::PROPERTIES),
+                "Get",
+                &(interfaces::CONFIG_ZONE, "builtin"),
+            )?
+            .body()
+            .deserialize()?;
+        if bool::try_from(builtin).unwrap_or(false) {
+            return Err(anyhow!("'{}' is built into firewalld", name));
+        }
+
+        let _: Option<()> = self.call_interactive(
+            ObjectPath::try_from(path.as_str())?,
+            interfaces::CONFIG_ZONE,
+            "removeZone",
+            &(),
+        )?;
+
+        info!("Deleted zone {}", name);
+        cache::invalidate();
+        let _ = self.event_sender.send(FirewallEvent::StateChanged);
+        Ok(())
+    }
+
+    /// Get the names of the ICMP types firewalld knows.
+    pub fn get_icmp_types(&self) -> Result<Vec<String>> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or_else(|| anyhow!("Not connected to firewalld"))?;
+
+        let mut types: Vec<String> = conn
             .call_method(
                 Some(BUS_NAME),
                 paths::ROOT,
                 Some(interfaces::MAIN),
-                "listServices",
+                "listIcmpTypes",
                 &(),
             )?
             .body()
             .deserialize()?;
+        types.sort();
+
+        Ok(types)
+    }
+
+    /// Get all available services.
+    pub fn get_services(&mut self) -> Result<Vec<Service>> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or_else(|| anyhow!("Not connected to firewalld"))?;
+
+        // Get all service names (this is on the main interface)
+        let cached = cache::cache().service_names();
+        let service_names: Vec<String> = match cached {
+            Some(names) => names,
+            None => {
+                let generation = cache::cache().generation();
+                let names: Vec<String> = conn
+                    .call_method(
+                        Some(BUS_NAME),
+                        paths::ROOT,
+                        Some(interfaces::MAIN),
+                        "listServices",
+                        &(),
+                    )?
+                    .body()
+                    .deserialize()?;
+                cache::cache().store_service_names(generation, &names);
+                names
+            }
+        };
 
         // Get default zone's enabled services
         let default_zone = self.get_default_zone()?;
@@ -358,6 +781,126 @@ impl FirewallClient {
         Ok(services)
     }
 
+    /// Get a service's full definition: description, ports, protocols,
+    /// source ports, helpers and destinations.
+    ///
+    /// Uses `getServiceSettings2` and falls back to the legacy tuple-based
+    /// `getServiceSettings` on firewalld releases older than 0.9. Answers
+    /// from the metadata cache when the definition was read since the last
+    /// reload.
+    pub fn get_service_settings(&self, name: &str) -> Result<Service> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or_else(|| anyhow!("Not connected to firewalld"))?;
+
+        if let Some(service) = cache::cache().service(name) {
+            return Ok(service);
+        }
+        let generation = cache::cache().generation();
+        let mut service = Service::new(name);
+
+        let settings: Result<HashMap<String, OwnedValue>> = conn
+            .call_method(
+                Some(BUS_NAME),
+                paths::ROOT,
+                Some(interfaces::MAIN),
+                "getServiceSettings2",
+                &(name,),
+            )
+            .map_err(anyhow::Error::from)
+            .and_then(|reply| Ok(reply.body().deserialize()?));
+
+        match settings {
+            Ok(mut settings) => {
+                if let Some(description) = settings.remove("description") {
+                    service.description = String::try_from(description).unwrap_or_default();
+                }
+                if let Some(ports) = settings.remove("ports") {
+                    service.ports = Vec::<(String, String)>::try_from(ports).unwrap_or_default();
+                }
+                if let Some(helpers) = settings.remove("helpers") {
+                    service.helpers = Vec::<String>::try_from(helpers).unwrap_or_default();
+                }
+                if let Some(protocols) = settings.remove("protocols") {
+                    service.protocols = Vec::<String>::try_from(protocols).unwrap_or_default();
+                }
+                if let Some(source_ports) = settings.remove("source_ports") {
+                    service.source_ports =
+                        Vec::<(String, String)>::try_from(source_ports).unwrap_or_default();
+                }
+                if let Some(destinations) = settings.remove("destination") {
+                    service.destinations = HashMap::<String, String>::try_from(destinations)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .collect();
+                }
+            }
+            Err(e) => {
+                warn!(
+                    "getServiceSettings2 failed for {}, trying legacy call: {}",
+                    name, e
+                );
+                let legacy: LegacyServiceSettings = conn
+                    .call_method(
+                        Some(BUS_NAME),
+                        paths::ROOT,
+                        Some(interfaces::MAIN),
+                        "getServiceSettings",
+                        &(name,),
+                    )?
+                    .body()
+                    .deserialize()?;
+                service.description = legacy.2;
+                service.ports = legacy.3;
+                service.helpers = legacy.4;
+                service.destinations = legacy.5.into_iter().collect();
+                service.protocols = legacy.6;
+                service.source_ports = legacy.7;
+            }
+        }
+        service.destinations.sort();
+
+        // Service XML descriptions are wrapped across several lines
+        service.description = service
+            .description
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        cache::cache().store_service(generation, &service);
+        Ok(service)
+    }
+
+    /// Create a service in the permanent configuration from its name,
+    /// description and ports. firewalld refuses a name already in use and
+    /// loads the service into the running firewall at the next reload.
+    pub fn create_service(&self, service: &Service) -> Result<()> {
+        validate_firewall_service(&service.name)
+            .ok_or_else(|| anyhow!("Invalid service name: {}", service.name))?;
+        for (port, protocol) in &service.ports {
+            if parse_port_spec(port).is_none() || validate_protocol(protocol).is_none() {
+                return Err(anyhow!("Invalid port: {}/{}", port, protocol));
+            }
+        }
+
+        let mut dict: HashMap<&str, Value> = HashMap::new();
+        dict.insert("short", Value::from(service.name.as_str()));
+        dict.insert("description", Value::from(service.description.as_str()));
+        dict.insert("ports", Value::from(service.ports.clone()));
+
+        let _: Option<OwnedObjectPath> = self.call_interactive(
+            ObjectPath::try_from(paths::CONFIG)?,
+            interfaces::CONFIG,
+            "addService2",
+            &(service.name.as_str(), dict),
+        )?;
+
+        info!("Created service {}", service.name);
+        cache::invalidate();
+        let _ = self.event_sender.send(FirewallEvent::StateChanged);
+        Ok(())
+    }
+
     /// Add a port to a zone. Runtime failure is an `Err`; the returned
     /// outcome reports whether the permanent half also succeeded.
     pub fn add_port(
@@ -444,7 +987,7 @@ impl FirewallClient {
 
     /// Apply a change to a zone's permanent configuration, reporting the
     /// outcome instead of silently swallowing failures.
-    fn apply_permanent<B>(&self, zone: &str, method: &str, body: &B) -> PermanentOutcome
+    pub(super) fn apply_permanent<B>(&self, zone: &str, method: &str, body: &B) -> PermanentOutcome
     where
         B: serde::ser::Serialize + zbus::zvariant::DynamicType,
     {
@@ -604,6 +1147,431 @@ impl FirewallClient {
         Ok(outcome)
     }
 
+    /// Resolve a difference between a zone's runtime and permanent
+    /// configuration. With `keep` the rule is added to the configuration
+    /// that lacks it; otherwise it is removed from the one that has it.
+    pub fn resolve_drift(&self, zone: &str, item: &DriftItem, keep: bool) -> Result<()> {
+        let port = || {
+            item.value
+                .split_once('/')
+                .ok_or_else(|| anyhow!("Invalid port: {}", item.value))
+        };
+        let value = item.value.as_str();
+
+        match (item.side, keep) {
+            // Change the runtime configuration
+            (DriftSide::RuntimeOnly, false) => {
+                match item.kind {
+                    DriftKind::Service => self.disable_service(zone, value, false)?,
+                    DriftKind::Port => {
+                        let (port, protocol) = port()?;
+                        self.remove_port(zone, port, protocol, false)?
+                    }
+                    DriftKind::RichRule => self.remove_rich_rule(zone, value, false)?,
+                };
+            }
+            (DriftSide::PermanentOnly, true) => {
+                match item.kind {
+                    DriftKind::Service => self.enable_service(zone, value, false)?,
+                    DriftKind::Port => {
+                        let (port, protocol) = port()?;
+                        self.add_port(zone, port, protocol, false)?
+                    }
+                    DriftKind::RichRule => self.add_rich_rule(zone, value, false)?,
+                };
+            }
+            // Change the permanent configuration
+            (DriftSide::RuntimeOnly, true) | (DriftSide::PermanentOnly, false) => {
+                validate_zone_name(zone).ok_or_else(|| anyhow!("Invalid zone name: {}", zone))?;
+                let verb = if keep { "add" } else { "remove" };
+                let outcome = match item.kind {
+                    DriftKind::Service => {
+                        self.apply_permanent(zone, &format!("{}Service", verb), &(value,))
+                    }
+                    DriftKind::Port => {
+                        let (port, protocol) = port()?;
+                        self.apply_permanent(zone, &format!("{}Port", verb), &(port, protocol))
+                    }
+                    DriftKind::RichRule => {
+                        self.apply_permanent(zone, &format!("{}RichRule", verb), &(value,))
+                    }
+                };
+                if let PermanentOutcome::Failed(e) = outcome {
+                    return Err(anyhow!(e));
+                }
+                info!(
+                    "{} {} {} in the permanent configuration of zone {}",
+                    if keep { "Saved" } else { "Removed" },
+                    item.kind.label(),
+                    value,
+                    zone
+                );
+                let _ = self.event_sender.send(FirewallEvent::StateChanged);
+            }
+        }
+        Ok(())
+    }
+
+    /// Block an ICMP type in a zone, or let it through when the zone's ICMP
+    /// block inversion is on.
+    pub fn add_icmp_block(
+        &self,
+        zone: &str,
+        icmp_type: &str,
+        permanent: bool,
+    ) -> Result<PermanentOutcome> {
+        validate_zone_name(zone).ok_or_else(|| anyhow!("Invalid zone name: {}", zone))?;
+        validate_icmp_type(icmp_type).ok_or_else(|| anyhow!("Invalid ICMP type: {}", icmp_type))?;
+        let result: Result<Option<String>> = self.call_interactive(
+            ObjectPath::try_from(paths::ROOT)?,
+            interfaces::ZONE,
+            "addIcmpBlock",
+            &(zone, icmp_type, 0i32),
+        );
+
+        match result {
+            Ok(_) => info!("Blocked ICMP type {} in zone {}", icmp_type, zone),
+            Err(e) if e.to_string().contains("ALREADY_ENABLED") => {}
+            Err(e) => return Err(e),
+        }
+
+        let outcome = if permanent {
+            self.apply_permanent(zone, "addIcmpBlock", &(icmp_type,))
+        } else {
+            PermanentOutcome::NotRequested
+        };
+
+        let _ = self.event_sender.send(FirewallEvent::StateChanged);
+        Ok(outcome)
+    }
+
+    /// Remove an ICMP type from a zone's ICMP blocks.
+    pub fn remove_icmp_block(
+        &self,
+        zone: &str,
+        icmp_type: &str,
+        permanent: bool,
+    ) -> Result<PermanentOutcome> {
+        validate_zone_name(zone).ok_or_else(|| anyhow!("Invalid zone name: {}", zone))?;
+        validate_icmp_type(icmp_type).ok_or_else(|| anyhow!("Invalid ICMP type: {}", icmp_type))?;
+        let result: Result<Option<String>> = self.call_interactive(
+            ObjectPath::try_from(paths::ROOT)?,
+            interfaces::ZONE,
+            "removeIcmpBlock",
+            &(zone, icmp_type),
+        );
+
+        match result {
+            Ok(_) => info!("Unblocked ICMP type {} in zone {}", icmp_type, zone),
+            Err(e) if e.to_string().contains("NOT_ENABLED") => {}
+            Err(e) => return Err(e),
+        }
+
+        let outcome = if permanent {
+            self.apply_permanent(zone, "removeIcmpBlock", &(icmp_type,))
+        } else {
+            PermanentOutcome::NotRequested
+        };
+
+        let _ = self.event_sender.send(FirewallEvent::StateChanged);
+        Ok(outcome)
+    }
+
+    /// Turn a zone's ICMP block inversion on or off. While it is on, the
+    /// listed ICMP types are the only ones let through.
+    pub fn set_icmp_block_inversion(
+        &self,
+        zone: &str,
+        enabled: bool,
+        permanent: bool,
+    ) -> Result<PermanentOutcome> {
+        validate_zone_name(zone).ok_or_else(|| anyhow!("Invalid zone name: {}", zone))?;
+        let method = if enabled {
+            "addIcmpBlockInversion"
+        } else {
+            "removeIcmpBlockInversion"
+        };
+        let result: Result<Option<String>> = self.call_interactive(
+            ObjectPath::try_from(paths::ROOT)?,
+            interfaces::ZONE,
+            method,
+            &(zone,),
+        );
+
+        match result {
+            Ok(_) => info!("Set ICMP block inversion of zone {} to {}", zone, enabled),
+            Err(e)
+                if e.to_string().contains("ALREADY_ENABLED")
+                    || e.to_string().contains("NOT_ENABLED") => {}
+            Err(e) => return Err(e),
+        }
+
+        let outcome = if permanent {
+            self.apply_permanent(zone, method, &())
+        } else {
+            PermanentOutcome::NotRequested
+        };
+
+        let _ = self.event_sender.send(FirewallEvent::StateChanged);
+        Ok(outcome)
+    }
+
+    /// Drop or reject all traffic from an address or network in a zone. The
+    /// rule gets the IPv4 or IPv6 family the address needs.
+    pub fn add_source_block(
+        &self,
+        zone: &str,
+        address: &str,
+        reject: bool,
+        permanent: bool,
+    ) -> Result<PermanentOutcome> {
+        let rule = BlockedSource::rule(address, reject)?;
+        self.add_rich_rule(zone, &rule.to_string(), permanent)
+    }
+
+    /// Remove every rule in a zone that blocks an address or network,
+    /// however it is written, at runtime and optionally permanently.
+    pub fn remove_source_block(
+        &self,
+        zone: &str,
+        address: &str,
+        permanent: bool,
+    ) -> Result<PermanentOutcome> {
+        let blocks = |rules: Vec<String>| -> Vec<String> {
+            rules
+                .into_iter()
+                .filter(|rule| {
+                    BlockedSource::from_rich_rule(rule, zone)
+                        .is_some_and(|blocked| blocked.matches(address))
+                })
+                .collect()
+        };
+
+        for rule in blocks(self.get_zone_rich_rules(zone)?) {
+            self.remove_rich_rule(zone, &rule, false)?;
+        }
+        if !permanent {
+            return Ok(PermanentOutcome::NotRequested);
+        }
+
+        let rules = match self.get_permanent_zone_rules(zone) {
+            Ok(rules) => rules.rich_rules,
+            Err(e) => return Ok(PermanentOutcome::Failed(e.to_string())),
+        };
+        let mut outcome = PermanentOutcome::Applied;
+        for rule in blocks(rules) {
+            let result = self.apply_permanent(zone, "removeRichRule", &(rule.as_str(),));
+            if result.failed() {
+                outcome = result;
+            }
+        }
+        let _ = self.event_sender.send(FirewallEvent::StateChanged);
+        Ok(outcome)
+    }
+
+    /// Bind a source address or network to a zone, so the zone's rules
+    /// apply to all traffic from it.
+    pub fn add_source(
+        &self,
+        zone: &str,
+        source: &str,
+        permanent: bool,
+    ) -> Result<PermanentOutcome> {
+        validate_zone_name(zone).ok_or_else(|| anyhow!("Invalid zone name: {}", zone))?;
+        let result: Result<Option<String>> = self.call_interactive(
+            ObjectPath::try_from(paths::ROOT)?,
+            interfaces::ZONE,
+            "addSource",
+            &(zone, source),
+        );
+
+        match result {
+            Ok(_) => info!("Added source {} to zone {}", source, zone),
+            Err(e) if e.to_string().contains("ALREADY_ENABLED") => {}
+            Err(e) => return Err(e),
+        }
+
+        let outcome = if permanent {
+            self.apply_permanent(zone, "addSource", &(source,))
+        } else {
+            PermanentOutcome::NotRequested
+        };
+
+        let _ = self.event_sender.send(FirewallEvent::StateChanged);
+        Ok(outcome)
+    }
+
+    /// Unbind a source address or network from a zone.
+    pub fn remove_source(
+        &self,
+        zone: &str,
+        source: &str,
+        permanent: bool,
+    ) -> Result<PermanentOutcome> {
+        validate_zone_name(zone).ok_or_else(|| anyhow!("Invalid zone name: {}", zone))?;
+        let result: Result<Option<String>> = self.call_interactive(
+            ObjectPath::try_from(paths::ROOT)?,
+            interfaces::ZONE,
+            "removeSource",
+            &(zone, source),
+        );
+
+        match result {
+            Ok(_) => info!("Removed source {} from zone {}", source, zone),
+            Err(e) if e.to_string().contains("NOT_ENABLED") => {}
+            Err(e) => return Err(e),
+        }
+
+        let outcome = if permanent {
+            self.apply_permanent(zone, "removeSource", &(source,))
+        } else {
+            PermanentOutcome::NotRequested
+        };
+
+        let _ = self.event_sender.send(FirewallEvent::StateChanged);
+        Ok(outcome)
+    }
+
+    /// Move a network interface to `zone` in the runtime configuration.
+    ///
+    /// NetworkManager assigns the zone again when the interface reconnects;
+    /// to make the binding last, set the zone of its connection profile.
+    pub fn change_zone_of_interface(&self, zone: &str, interface: &str) -> Result<()> {
+        validate_zone_name(zone).ok_or_else(|| anyhow!("Invalid zone name: {}", zone))?;
+        validate_interface_name(interface)
+            .ok_or_else(|| anyhow!("Invalid interface name: {}", interface))?;
+        let _: Option<String> = self.call_interactive(
+            ObjectPath::try_from(paths::ROOT)?,
+            interfaces::ZONE,
+            "changeZoneOfInterface",
+            &(zone, interface),
+        )?;
+
+        info!("Moved interface {} to zone {}", interface, zone);
+        let _ = self.event_sender.send(FirewallEvent::StateChanged);
+        Ok(())
+    }
+
+    /// Get the names of the ipsets the running firewall knows.
+    pub fn get_ipsets(&self) -> Result<Vec<String>> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or_else(|| anyhow!("Not connected to firewalld"))?;
+
+        let names: Vec<String> = conn
+            .call_method(
+                Some(BUS_NAME),
+                paths::ROOT,
+                Some(interfaces::IPSET),
+                "getIPSets",
+                &(),
+            )?
+            .body()
+            .deserialize()?;
+        Ok(names)
+    }
+
+    /// Create a `hash:net` ipset holding `entries` in the permanent
+    /// configuration. The running firewall knows it after the next reload.
+    pub fn create_ipset(
+        &self,
+        name: &str,
+        family: Family,
+        maxelem: usize,
+        entries: &[String],
+    ) -> Result<()> {
+        validate_ipset_name(name)?;
+        let mut options: HashMap<&str, String> = HashMap::new();
+        options.insert(
+            "family",
+            match family {
+                Family::Ipv4 => "inet",
+                Family::Ipv6 => "inet6",
+            }
+            .to_string(),
+        );
+        options.insert("maxelem", maxelem.to_string());
+        // (version, short, description, type, options, entries)
+        let settings = ("", name, "", "hash:net", options, entries.to_vec());
+
+        let _: Option<OwnedObjectPath> = self.call_interactive(
+            ObjectPath::try_from(paths::CONFIG)?,
+            interfaces::CONFIG,
+            "addIPSet",
+            &(name, settings),
+        )?;
+        info!("Created ipset {} with {} entries", name, entries.len());
+        Ok(())
+    }
+
+    /// Replace the entries of an ipset, permanently and in the running
+    /// firewall.
+    pub fn set_ipset_entries(&self, name: &str, entries: &[String]) -> Result<()> {
+        validate_ipset_name(name)?;
+        let path = self.get_ipset_config_path(name)?;
+        let _: Option<()> = self.call_interactive(
+            ObjectPath::try_from(path.as_str())?,
+            interfaces::CONFIG_IPSET,
+            "setEntries",
+            &(entries,),
+        )?;
+        let _: Option<()> = self.call_interactive(
+            ObjectPath::try_from(paths::ROOT)?,
+            interfaces::IPSET,
+            "setEntries",
+            &(name, entries),
+        )?;
+        info!("Loaded {} entries into ipset {}", entries.len(), name);
+        Ok(())
+    }
+
+    /// Delete an ipset from the permanent configuration. The running
+    /// firewall keeps it, emptied, until the next reload, so nothing else
+    /// has to be reloaded now.
+    pub fn remove_ipset(&self, name: &str) -> Result<()> {
+        validate_ipset_name(name)?;
+        if self.get_ipsets()?.iter().any(|n| n == name) {
+            let empty: Vec<String> = Vec::new();
+            let _: Option<()> = self.call_interactive(
+                ObjectPath::try_from(paths::ROOT)?,
+                interfaces::IPSET,
+                "setEntries",
+                &(name, empty),
+            )?;
+        }
+        let path = self.get_ipset_config_path(name)?;
+        let _: Option<()> = self.call_interactive(
+            ObjectPath::try_from(path.as_str())?,
+            interfaces::CONFIG_IPSET,
+            "remove",
+            &(),
+        )?;
+        info!("Removed ipset {}", name);
+        Ok(())
+    }
+
+    /// Get the D-Bus path for an ipset's permanent config.
+    fn get_ipset_config_path(&self, name: &str) -> Result<String> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or_else(|| anyhow!("Not connected to firewalld"))?;
+
+        let path: OwnedObjectPath = conn
+            .call_method(
+                Some(BUS_NAME),
+                paths::CONFIG,
+                Some(interfaces::CONFIG),
+                "getIPSetByName",
+                &(name,),
+            )?
+            .body()
+            .deserialize()?;
+
+        Ok(path.to_string())
+    }
+
     /// Get the D-Bus path for a zone's permanent config.
     fn get_zone_config_path(&self, zone_name: &str) -> Result<String> {
         let conn = self
@@ -670,6 +1638,21 @@ impl FirewallClient {
         )?;
 
         info!("Firewalld configuration reloaded");
+        cache::invalidate();
+        let _ = self.event_sender.send(FirewallEvent::StateChanged);
+        Ok(())
+    }
+
+    /// Copy the runtime configuration to the permanent configuration.
+    pub fn runtime_to_permanent(&self) -> Result<()> {
+        let _: Option<()> = self.call_interactive(
+            ObjectPath::try_from(paths::ROOT)?,
+            interfaces::MAIN,
+            "runtimeToPermanent",
+            &(),
+        )?;
+
+        info!("Runtime configuration saved as permanent");
         let _ = self.event_sender.send(FirewallEvent::StateChanged);
         Ok(())
     }
@@ -702,6 +1685,90 @@ impl FirewallClient {
         Ok(())
     }
 
+    /// Get the LogDenied setting: which rejected or dropped packets are
+    /// written to the kernel log.
+    pub fn get_log_denied(&self) -> Result<String> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or_else(|| anyhow!("Not connected to firewalld"))?;
+
+        let value: String = conn
+            .call_method(
+                Some(BUS_NAME),
+                paths::ROOT,
+                Some(interfaces::MAIN),
+                "getLogDenied",
+                &(),
+            )?
+            .body()
+            .deserialize()?;
+
+        Ok(value)
+    }
+
+    /// Set LogDenied; firewalld saves it and reloads its rules.
+    pub fn set_log_denied(&self, value: &str) -> Result<()> {
+        if !LOG_DENIED_VALUES.contains(&value) {
+            return Err(anyhow!("Invalid LogDenied value: {}", value));
+        }
+        let _: Option<()> = self.call_interactive(
+            ObjectPath::try_from(paths::ROOT)?,
+            interfaces::MAIN,
+            "setLogDenied",
+            &(value,),
+        )?;
+
+        info!("Set LogDenied to: {}", value);
+        let _ = self.event_sender.send(FirewallEvent::StateChanged);
+        Ok(())
+    }
+
+    /// Get the AutomaticHelpers setting, or `None` on firewalld 1.0 and
+    /// later, which dropped it and never assigns helpers automatically.
+    pub fn get_automatic_helpers(&self) -> Result<Option<String>> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or_else(|| anyhow!("Not connected to firewalld"))?;
+
+        let reply = conn.call_method(
+            Some(BUS_NAME),
+            paths::CONFIG,
+            Some(interfaces::PROPERTIES),
+            "Get",
+            &(interfaces::CONFIG, "AutomaticHelpers"),
+        );
+        let value: OwnedValue = match reply {
+            Ok(reply) => reply.body().deserialize()?,
+            Err(zbus::Error::MethodError(name, _, _))
+                if name.as_str().ends_with("InvalidArgs")
+                    || name.as_str().ends_with("UnknownProperty") =>
+            {
+                return Ok(None);
+            }
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Some(String::try_from(value)?.to_lowercase()))
+    }
+
+    /// Set AutomaticHelpers and reload so the running firewall uses it.
+    pub fn set_automatic_helpers(&self, value: &str) -> Result<()> {
+        if !AUTOMATIC_HELPERS_VALUES.contains(&value) {
+            return Err(anyhow!("Invalid AutomaticHelpers value: {}", value));
+        }
+        let _: Option<()> = self.call_interactive(
+            ObjectPath::try_from(paths::CONFIG)?,
+            interfaces::PROPERTIES,
+            "Set",
+            &(interfaces::CONFIG, "AutomaticHelpers", Value::from(value)),
+        )?;
+        self.reload()?;
+
+        info!("Set AutomaticHelpers to: {}", value);
+        Ok(())
+    }
+
     /// Query if panic mode is enabled.
     pub fn query_panic_mode(&self) -> Result<bool> {
         let conn = self
@@ -731,6 +1798,74 @@ impl Default for FirewallClient {
 }
 
 /// Map raw D-Bus/polkit errors to messages a user can act on.
+/// Remove `key` from a settings reply. Keys with their default value are
+/// left out of the reply, so a missing key gives the default.
+fn take<T: TryFrom<OwnedValue> + Default>(
+    settings: &mut HashMap<String, OwnedValue>,
+    key: &str,
+) -> T {
+    settings
+        .remove(key)
+        .and_then(|value| T::try_from(value).ok())
+        .unwrap_or_default()
+}
+
+/// Read firewalld's `state` property.
+/// The system bus connection shared by every client, opened on first use.
+static SHARED_BUS: Mutex<Option<Connection>> = Mutex::new(None);
+
+fn shared_bus() -> Result<Connection> {
+    let mut bus = SHARED_BUS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(conn) = bus.as_ref() {
+        return Ok(conn.clone());
+    }
+    let conn = Connection::system().context("Failed to connect to system D-Bus")?;
+    *bus = Some(conn.clone());
+    Ok(conn)
+}
+
+/// Drop the shared connection so the next client opens a new one.
+fn forget_shared_bus() {
+    SHARED_BUS.lock().unwrap_or_else(|e| e.into_inner()).take();
+}
+
+/// Whether `e` means the bus connection itself is gone, rather than
+/// firewalld refusing or failing a call.
+fn is_broken_bus(e: &anyhow::Error) -> bool {
+    matches!(
+        e.downcast_ref::<zbus::Error>(),
+        Some(zbus::Error::InputOutput(_))
+    )
+}
+
+fn read_state(conn: &Connection) -> Result<FirewallState> {
+    let value: OwnedValue = conn
+        .call_method(
+            Some(BUS_NAME),
+            paths::ROOT,
+            Some(interfaces::PROPERTIES),
+            "Get",
+            &(interfaces::MAIN, "state"),
+        )?
+        .body()
+        .deserialize()?;
+    Ok(FirewallState::parse(&String::try_from(value)?))
+}
+
+/// ipset names: letters, digits, `-` and `_`, at most 31 characters.
+fn validate_ipset_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= 31
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!("Invalid ipset name: {}", name))
+    }
+}
+
 fn friendly_dbus_error(e: &zbus::Error) -> String {
     let text = e.to_string();
     if text.contains("AccessDenied") || text.contains("NotAuthorized") {
@@ -0,0 +1,232 @@
+// Security Center - Firewall Diagnosis
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Why firewalld cannot be reached.
+//!
+//! A failed [`FirewallClient::connect`](super::FirewallClient::connect) can
+//! mean firewalld is not installed, is installed but stopped, refuses this
+//! user, or that the system bus itself is down. The D-Bus error tells the
+//! last two apart; when the bus reports that nobody owns firewalld's name,
+//! the `firewalld.service` unit says whether it is missing or just stopped.
+
+use std::fmt;
+
+use crate::systemd::{ServiceState, SystemdClient};
+
+/// The systemd unit that runs firewalld.
+pub const FIREWALLD_UNIT: &str = "firewalld.service";
+
+/// Why firewalld is unavailable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FirewallUnavailable {
+    /// There is no `firewalld.service` unit.
+    NotInstalled,
+    /// firewalld is installed but not running. `failed` is set when the
+    /// unit crashed or failed to start rather than being stopped.
+    Stopped { failed: bool },
+    /// firewalld or the bus policy refused this user.
+    AccessDenied,
+    /// The system D-Bus cannot be reached.
+    BusUnreachable,
+    /// Any other failure, with its message.
+    Other(String),
+}
+
+impl fmt::Display for FirewallUnavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FirewallUnavailable::NotInstalled => write!(f, "firewalld is not installed"),
+            FirewallUnavailable::Stopped { failed: true } => write!(f, "firewalld has failed"),
+            FirewallUnavailable::Stopped { failed: false } => write!(f, "firewalld is stopped"),
+            FirewallUnavailable::AccessDenied => write!(f, "access to firewalld was denied"),
+            FirewallUnavailable::BusUnreachable => write!(f, "the system D-Bus is unreachable"),
+            FirewallUnavailable::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// What a D-Bus error says about firewalld on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ErrorKind {
+    /// Nobody owns firewalld's bus name.
+    NoService,
+    AccessDenied,
+    BusUnreachable,
+    Other,
+}
+
+/// Classify a D-Bus error name, e.g. `org.freedesktop.DBus.Error.AccessDenied`.
+fn error_name_kind(name: &str) -> ErrorKind {
+    match name {
+        "org.freedesktop.DBus.Error.ServiceUnknown"
+        | "org.freedesktop.DBus.Error.NameHasNoOwner"
+        | "org.freedesktop.DBus.Error.Spawn.ServiceNotFound"
+        | "org.freedesktop.DBus.Error.Spawn.ChildExited"
+        | "org.freedesktop.systemd1.NoSuchUnit" => ErrorKind::NoService,
+        "org.freedesktop.DBus.Error.AccessDenied"
+        | "org.freedesktop.DBus.Error.InteractiveAuthorizationRequired"
+        | "org.freedesktop.PolicyKit1.Error.NotAuthorized"
+        | "org.fedoraproject.FirewallD1.NotAuthorizedException" => ErrorKind::AccessDenied,
+        "org.freedesktop.DBus.Error.NoServer"
+        | "org.freedesktop.DBus.Error.Disconnected"
+        | "org.freedesktop.DBus.Error.BadAddress" => ErrorKind::BusUnreachable,
+        _ => ErrorKind::Other,
+    }
+}
+
+/// Classify a zbus error.
+fn zbus_error_kind(err: &zbus::Error) -> ErrorKind {
+    match err {
+        zbus::Error::MethodError(name, _, _) => error_name_kind(name.as_str()),
+        zbus::Error::FDO(fdo) => match **fdo {
+            zbus::fdo::Error::ServiceUnknown(_) | zbus::fdo::Error::NameHasNoOwner(_) => {
+                ErrorKind::NoService
+            }
+            zbus::fdo::Error::AccessDenied(_)
+            | zbus::fdo::Error::InteractiveAuthorizationRequired(_) => ErrorKind::AccessDenied,
+            zbus::fdo::Error::NoServer(_)
+            | zbus::fdo::Error::Disconnected(_)
+            | zbus::fdo::Error::BadAddress(_)
+            | zbus::fdo::Error::IOError(_) => ErrorKind::BusUnreachable,
+            _ => ErrorKind::Other,
+        },
+        zbus::Error::InputOutput(_) | zbus::Error::Address(_) | zbus::Error::Handshake(_) => {
+            ErrorKind::BusUnreachable
+        }
+        _ => ErrorKind::Other,
+    }
+}
+
+/// Classify an error from anywhere in a connect attempt's error chain.
+fn error_kind(err: &anyhow::Error) -> ErrorKind {
+    err.chain()
+        .find_map(|cause| {
+            if let Some(err) = cause.downcast_ref::<zbus::Error>() {
+                Some(zbus_error_kind(err))
+            } else if cause.downcast_ref::<std::io::Error>().is_some() {
+                Some(ErrorKind::BusUnreachable)
+            } else {
+                None
+            }
+        })
+        .unwrap_or(ErrorKind::Other)
+}
+
+/// Map the state of `firewalld.service` to the reason firewalld is missing
+/// from the bus.
+fn unit_reason(installed: bool, state: &ServiceState) -> FirewallUnavailable {
+    if !installed {
+        FirewallUnavailable::NotInstalled
+    } else {
+        FirewallUnavailable::Stopped {
+            failed: *state == ServiceState::Failed,
+        }
+    }
+}
+
+/// Work out why connecting to firewalld failed with `err`.
+///
+/// Blocking: may query systemd over the system bus.
+pub fn diagnose(err: &anyhow::Error) -> FirewallUnavailable {
+    match error_kind(err) {
+        ErrorKind::AccessDenied => FirewallUnavailable::AccessDenied,
+        ErrorKind::BusUnreachable => FirewallUnavailable::BusUnreachable,
+        ErrorKind::NoService => {
+            let mut systemd = SystemdClient::new();
+            if let Err(e) = systemd.connect() {
+                tracing::warn!("Cannot ask systemd about {}: {:#}", FIREWALLD_UNIT, e);
+                // Without systemd the bus error is all there is to go on
+                return FirewallUnavailable::Stopped { failed: false };
+            }
+            match systemd.get_service_info(FIREWALLD_UNIT) {
+                Ok(info) => unit_reason(info.is_installed, &info.state),
+                // LoadUnit fails outright for unknown units on some systemd versions
+                Err(e) => match e.downcast_ref::<zbus::Error>().map(zbus_error_kind) {
+                    Some(ErrorKind::NoService) => FirewallUnavailable::NotInstalled,
+                    _ => FirewallUnavailable::Stopped { failed: false },
+                },
+            }
+        }
+        ErrorKind::Other => FirewallUnavailable::Other(format!("{:#}", err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use anyhow::Context;
+
+    #[test]
+    fn test_error_name_kind() {
+        assert_eq!(
+            error_name_kind("org.freedesktop.DBus.Error.ServiceUnknown"),
+            ErrorKind::NoService
+        );
+        assert_eq!(
+            error_name_kind("org.freedesktop.DBus.Error.NameHasNoOwner"),
+            ErrorKind::NoService
+        );
+        assert_eq!(
+            error_name_kind("org.freedesktop.DBus.Error.AccessDenied"),
+            ErrorKind::AccessDenied
+        );
+        assert_eq!(
+            error_name_kind("org.fedoraproject.FirewallD1.NotAuthorizedException"),
+            ErrorKind::AccessDenied
+        );
+        assert_eq!(
+            error_name_kind("org.freedesktop.DBus.Error.NoServer"),
+            ErrorKind::BusUnreachable
+        );
+        assert_eq!(
+            error_name_kind("org.freedesktop.DBus.Error.Failed"),
+            ErrorKind::Other
+        );
+    }
+
+    #[test]
+    fn test_error_kind_searches_chain() {
+        let err = anyhow::Error::new(zbus::Error::Address("unix:path=/nowhere".into()))
+            .context("Failed to connect to system D-Bus");
+        assert_eq!(error_kind(&err), ErrorKind::BusUnreachable);
+
+        let err = anyhow::Error::new(zbus::Error::FDO(Box::new(zbus::fdo::Error::AccessDenied(
+            "denied".into(),
+        ))));
+        assert_eq!(error_kind(&err), ErrorKind::AccessDenied);
+
+        let err: anyhow::Result<()> = Err(std::io::Error::from(std::io::ErrorKind::NotFound))
+            .context("Failed to connect to system D-Bus");
+        assert_eq!(error_kind(&err.unwrap_err()), ErrorKind::BusUnreachable);
+
+        assert_eq!(error_kind(&anyhow::anyhow!("timeout")), ErrorKind::Other);
+    }
+
+    #[test]
+    fn test_diagnose_without_systemd_lookup() {
+        let err = anyhow::Error::new(zbus::Error::Handshake("refused".into()));
+        assert_eq!(diagnose(&err), FirewallUnavailable::BusUnreachable);
+        assert_eq!(
+            diagnose(&anyhow::anyhow!("timeout")),
+            FirewallUnavailable::Other("timeout".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unit_reason() {
+        assert_eq!(
+            unit_reason(false, &ServiceState::Unknown),
+            FirewallUnavailable::NotInstalled
+        );
+        assert_eq!(
+            unit_reason(true, &ServiceState::Stopped),
+            FirewallUnavailable::Stopped { failed: false }
+        );
+        assert_eq!(
+            unit_reason(true, &ServiceState::Failed),
+            FirewallUnavailable::Stopped { failed: true }
+        );
+    }
+}
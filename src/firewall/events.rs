@@ -0,0 +1,160 @@
+// Security Center - Firewall Configuration Events
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Session log of firewall configuration changes announced by firewalld:
+//! reloads, panic mode and default zone switches, along with network
+//! interfaces appearing and the zone they were given. The Overview marks them
+//! on its activity chart so a spike in traffic can be set against the change
+//! that preceded it.
+//!
+//! Events are recorded from firewalld's D-Bus signals, so changes made with
+//! `firewall-cmd` or another tool show up as well as those made here. Only
+//! the most recent [`MAX_EVENTS`] are kept, and nothing is written to disk.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Local};
+
+use super::interfaces;
+
+/// Events kept before the oldest is dropped.
+pub const MAX_EVENTS: usize = 64;
+
+static LOG: Mutex<EventLog> = Mutex::new(EventLog::new());
+
+/// What changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigEventKind {
+    Reloaded,
+    PanicEnabled,
+    PanicDisabled,
+    /// The default zone was switched to the named zone.
+    DefaultZoneChanged(String),
+    /// A network interface appeared, e.g. a USB tethering adapter.
+    InterfaceAdded(String),
+    /// A new interface was assigned to a zone when asked.
+    InterfaceZoneAssigned {
+        interface: String,
+        zone: String,
+    },
+}
+
+impl ConfigEventKind {
+    /// The event a firewalld signal announces, if any. `arg` is the signal's
+    /// first string argument, which names the zone for `DefaultZoneChanged`.
+    pub fn from_signal(interface: &str, member: &str, arg: Option<&str>) -> Option<Self> {
+        if interface != interfaces::MAIN {
+            return None;
+        }
+        match member {
+            "Reloaded" => Some(Self::Reloaded),
+            "PanicModeEnabled" => Some(Self::PanicEnabled),
+            "PanicModeDisabled" => Some(Self::PanicDisabled),
+            "DefaultZoneChanged" => Some(Self::DefaultZoneChanged(arg?.to_string())),
+            _ => None,
+        }
+    }
+}
+
+/// A configuration change and when it was seen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigEvent {
+    pub time: DateTime<Local>,
+    pub kind: ConfigEventKind,
+}
+
+/// Bounded, oldest-first list of events.
+#[derive(Debug)]
+struct EventLog {
+    events: VecDeque<ConfigEvent>,
+}
+
+impl EventLog {
+    const fn new() -> Self {
+        Self {
+            events: VecDeque::new(),
+        }
+    }
+
+    fn push(&mut self, event: ConfigEvent) {
+        if self.events.len() == MAX_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+}
+
+/// Record that `kind` happened now.
+pub fn record(kind: ConfigEventKind) {
+    tracing::debug!("Firewall configuration event: {:?}", kind);
+    let event = ConfigEvent {
+        time: Local::now(),
+        kind,
+    };
+    // A panic cannot leave the log half-updated
+    LOG.lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .push(event);
+}
+
+/// Every recorded event, oldest first.
+pub fn recent() -> Vec<ConfigEvent> {
+    LOG.lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .events
+        .iter()
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_signal() {
+        assert_eq!(
+            ConfigEventKind::from_signal(interfaces::MAIN, "Reloaded", None),
+            Some(ConfigEventKind::Reloaded)
+        );
+        assert_eq!(
+            ConfigEventKind::from_signal(interfaces::MAIN, "PanicModeEnabled", None),
+            Some(ConfigEventKind::PanicEnabled)
+        );
+        assert_eq!(
+            ConfigEventKind::from_signal(interfaces::MAIN, "DefaultZoneChanged", Some("home")),
+            Some(ConfigEventKind::DefaultZoneChanged("home".to_string()))
+        );
+        // A zone change without its zone is malformed
+        assert_eq!(
+            ConfigEventKind::from_signal(interfaces::MAIN, "DefaultZoneChanged", None),
+            None
+        );
+        assert_eq!(
+            ConfigEventKind::from_signal(interfaces::ZONE, "ServiceAdded", Some("public")),
+            None
+        );
+        assert_eq!(
+            ConfigEventKind::from_signal(interfaces::CONFIG, "Reloaded", None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_log_is_bounded() {
+        let mut log = EventLog::new();
+        for i in 0..MAX_EVENTS + 3 {
+            log.push(ConfigEvent {
+                time: Local::now(),
+                kind: ConfigEventKind::DefaultZoneChanged(i.to_string()),
+            });
+        }
+        assert_eq!(log.events.len(), MAX_EVENTS);
+        assert_eq!(
+            log.events.front().map(|e| &e.kind),
+            Some(&ConfigEventKind::DefaultZoneChanged("3".to_string()))
+        );
+    }
+}
@@ -0,0 +1,139 @@
+// Security Center - Firewall Runtime Information
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! firewalld's own description of itself: version, state, packet filter
+//! backend and the address families it supports, read from the properties
+//! of its main D-Bus object.
+
+use std::collections::HashMap;
+
+use zbus::zvariant::OwnedValue;
+
+/// firewalld's `state` property.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FirewallState {
+    /// Starting up; rules are still being applied.
+    Init,
+    Running,
+    /// Loading the configuration failed and firewalld fell back to its
+    /// built-in defaults.
+    Failed,
+    Unknown(String),
+}
+
+impl FirewallState {
+    pub fn parse(state: &str) -> Self {
+        match state {
+            "INIT" => FirewallState::Init,
+            "RUNNING" => FirewallState::Running,
+            "FAILED" => FirewallState::Failed,
+            other => FirewallState::Unknown(other.to_string()),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            FirewallState::Init => "Starting".to_string(),
+            FirewallState::Running => "Running".to_string(),
+            FirewallState::Failed => "Failed (using fallback rules)".to_string(),
+            FirewallState::Unknown(state) => state.clone(),
+        }
+    }
+}
+
+/// Version, state and capabilities of the running firewalld.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FirewallInfo {
+    pub version: String,
+    /// Version of the D-Bus interface.
+    pub interface_version: String,
+    pub state: FirewallState,
+    /// `nftables` or `iptables`; `None` before firewalld 0.6, which only
+    /// had iptables.
+    pub backend: Option<String>,
+    pub ipv4: bool,
+    pub ipv6: bool,
+    /// Reverse path filtering of IPv6 traffic.
+    pub ipv6_rpfilter: bool,
+    /// Filtering of bridged traffic (ebtables).
+    pub bridge: bool,
+    pub ipset: bool,
+    pub ipset_types: Vec<String>,
+}
+
+impl FirewallInfo {
+    /// Build from the properties of `org.fedoraproject.FirewallD1` and the
+    /// `FirewallBackend` setting.
+    pub fn from_properties(props: &HashMap<String, OwnedValue>, backend: Option<String>) -> Self {
+        let text = |name: &str| {
+            props
+                .get(name)
+                .and_then(|v| <&str>::try_from(v).ok())
+                .unwrap_or_default()
+                .to_string()
+        };
+        let flag = |name: &str| {
+            props
+                .get(name)
+                .and_then(|v| bool::try_from(v).ok())
+                .unwrap_or(false)
+        };
+        let ipset_types = props
+            .get("IPSetTypes")
+            .and_then(|v| v.try_clone().ok())
+            .and_then(|v| Vec::<String>::try_from(v).ok())
+            .unwrap_or_default();
+
+        Self {
+            version: text("version"),
+            interface_version: text("interface_version"),
+            state: FirewallState::parse(&text("state")),
+            backend,
+            ipv4: flag("IPv4"),
+            ipv6: flag("IPv6"),
+            ipv6_rpfilter: flag("IPv6_rpfilter"),
+            bridge: flag("BRIDGE"),
+            ipset: flag("IPSet"),
+            ipset_types,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zbus::zvariant::Value;
+
+    fn owned(value: Value<'static>) -> OwnedValue {
+        OwnedValue::try_from(value).unwrap()
+    }
+
+    #[test]
+    fn test_firewall_info() {
+        let props = HashMap::from([
+            ("version".to_string(), owned(Value::from("2.1.2"))),
+            ("interface_version".to_string(), owned(Value::from("1.0"))),
+            ("state".to_string(), owned(Value::from("RUNNING"))),
+            ("IPv4".to_string(), owned(Value::from(true))),
+            ("IPv6".to_string(), owned(Value::from(true))),
+            ("BRIDGE".to_string(), owned(Value::from(false))),
+            (
+                "IPSetTypes".to_string(),
+                owned(Value::from(vec!["hash:ip", "hash:net"])),
+            ),
+        ]);
+        let info = FirewallInfo::from_properties(&props, Some("nftables".to_string()));
+        assert_eq!(info.version, "2.1.2");
+        assert_eq!(info.state, FirewallState::Running);
+        assert!(info.ipv4 && info.ipv6 && !info.bridge && !info.ipv6_rpfilter);
+        assert_eq!(info.ipset_types, ["hash:ip", "hash:net"]);
+        assert_eq!(info.backend.as_deref(), Some("nftables"));
+
+        assert_eq!(FirewallState::parse("FAILED"), FirewallState::Failed);
+        assert_eq!(
+            FirewallState::parse("STOPPING"),
+            FirewallState::Unknown("STOPPING".to_string())
+        );
+    }
+}
@@ -0,0 +1,350 @@
+// Security Center - Operation Journal
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Session journal of destructive rule changes and the firewalld calls that
+//! reverse them.
+//!
+//! Before a port or service is removed, [`inverse_of_removal`] reads which
+//! of the rules exist at runtime, permanently or both, and turns each into
+//! the [`InverseCall`] that adds it back to exactly those configurations.
+//! After the removal the calls are [`record`]ed as one entry, and the
+//! interface offers to undo it with the entry's id. Undoing [`take`]s the
+//! entry, so it runs at most once. Only the most recent [`MAX_ENTRIES`] are
+//! kept, and nothing is written to disk.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use anyhow::{bail, Result};
+
+use super::FirewallClient;
+use crate::models::{RichRule, Zone};
+
+/// Entries kept before the oldest is dropped.
+pub const MAX_ENTRIES: usize = 20;
+
+static JOURNAL: Mutex<Journal> = Mutex::new(Journal::new());
+
+/// A rule of a zone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleItem {
+    Port { port: String, protocol: String },
+    Service(String),
+    RichRule(String),
+}
+
+/// The configurations a rule is restored to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    Runtime,
+    Permanent,
+    Both,
+}
+
+/// A firewalld call that adds back a removed rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InverseCall {
+    pub zone: String,
+    pub item: RuleItem,
+    pub scope: Scope,
+}
+
+impl InverseCall {
+    /// The equivalent `firewall-cmd` command.
+    pub fn command(&self) -> String {
+        let option = match &self.item {
+            RuleItem::Port { port, protocol } => format!("--add-port={}/{}", port, protocol),
+            RuleItem::Service(service) => format!("--add-service={}", service),
+            RuleItem::RichRule(rule) => format!("--add-rich-rule='{}'", rule),
+        };
+        let permanent = if self.scope == Scope::Permanent {
+            "--permanent "
+        } else {
+            ""
+        };
+        format!("firewall-cmd {}--zone={} {}", permanent, self.zone, option)
+    }
+
+    /// Make the call.
+    pub fn apply(&self, client: &FirewallClient) -> Result<()> {
+        let zone = self.zone.as_str();
+        let outcome = match self.scope {
+            // Only the configuration side, without touching runtime
+            Scope::Permanent => match &self.item {
+                RuleItem::Port { port, protocol } => {
+                    client.apply_permanent(zone, "addPort", &(port, protocol))
+                }
+                RuleItem::Service(service) => {
+                    client.apply_permanent(zone, "addService", &(service,))
+                }
+                RuleItem::RichRule(rule) => client.apply_permanent(zone, "addRichRule", &(rule,)),
+            },
+            Scope::Runtime | Scope::Both => {
+                let permanent = self.scope == Scope::Both;
+                match &self.item {
+                    RuleItem::Port { port, protocol } => {
+                        client.add_port(zone, port, protocol, permanent)?
+                    }
+                    RuleItem::Service(service) => {
+                        client.enable_service(zone, service, permanent)?
+                    }
+                    RuleItem::RichRule(rule) => client.add_rich_rule(zone, rule, permanent)?,
+                }
+            }
+        };
+        if outcome.failed() {
+            bail!(
+                "The permanent configuration of zone {} was not restored",
+                zone
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Whether two rich rules are the same rule, however they are written.
+fn same_rule(a: &str, b: &str) -> bool {
+    a == b
+        || RichRule::parse(a)
+            .ok()
+            .is_some_and(|parsed| RichRule::parse(b).ok() == Some(parsed))
+}
+
+/// The calls that add back whichever of `items` `zone` holds, read before
+/// they are removed. Rich rules are restored as firewalld wrote them. When
+/// the permanent configuration is unknown, rules seen at runtime are
+/// restored to both.
+pub fn inverse_of_removal(zone: &Zone, items: &[RuleItem]) -> Vec<InverseCall> {
+    let mut calls: Vec<InverseCall> = Vec::new();
+    let mut push = |item: RuleItem, runtime: bool, permanent: bool| {
+        let scope = match (runtime, permanent) {
+            (true, true) => Scope::Both,
+            (true, false) => Scope::Runtime,
+            (false, true) => Scope::Permanent,
+            (false, false) => return,
+        };
+        let call = InverseCall {
+            zone: zone.name.clone(),
+            item,
+            scope,
+        };
+        if !calls.contains(&call) {
+            calls.push(call);
+        }
+    };
+
+    for item in items {
+        match item {
+            RuleItem::Port { port, protocol } => {
+                let entry = format!("{}/{}", port, protocol);
+                let runtime = zone.ports.contains(&entry);
+                let permanent = zone
+                    .permanent
+                    .as_ref()
+                    .map_or(runtime, |rules| rules.ports.contains(&entry));
+                push(item.clone(), runtime, permanent);
+            }
+            RuleItem::Service(service) => {
+                let runtime = zone.services.contains(service);
+                let permanent = zone
+                    .permanent
+                    .as_ref()
+                    .map_or(runtime, |rules| rules.services.contains(service));
+                push(item.clone(), runtime, permanent);
+            }
+            RuleItem::RichRule(rule) => {
+                let runtime = zone.rich_rules.iter().find(|r| same_rule(r, rule));
+                let permanent = match &zone.permanent {
+                    Some(rules) => rules.rich_rules.iter().find(|r| same_rule(r, rule)),
+                    None => runtime,
+                };
+                match (runtime, permanent) {
+                    (Some(a), Some(b)) if a != b => {
+                        push(RuleItem::RichRule(a.clone()), true, false);
+                        push(RuleItem::RichRule(b.clone()), false, true);
+                    }
+                    (a, b) => {
+                        if let Some(text) = a.or(b) {
+                            push(RuleItem::RichRule(text.clone()), a.is_some(), b.is_some());
+                        }
+                    }
+                }
+            }
+        }
+    }
+    calls
+}
+
+/// A change and the calls that reverse it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalEntry {
+    pub id: u64,
+    /// What was changed, e.g. "Port 8080 deleted".
+    pub description: String,
+    /// In the order the rules were found; undo them newest first.
+    pub calls: Vec<InverseCall>,
+}
+
+/// Bounded, oldest-first list of entries.
+#[derive(Debug)]
+struct Journal {
+    entries: VecDeque<JournalEntry>,
+    next_id: u64,
+}
+
+impl Journal {
+    const fn new() -> Self {
+        Self {
+            entries: VecDeque::new(),
+            next_id: 1,
+        }
+    }
+
+    fn push(&mut self, description: String, calls: Vec<InverseCall>) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(JournalEntry {
+            id,
+            description,
+            calls,
+        });
+        id
+    }
+
+    fn take(&mut self, id: u64) -> Option<JournalEntry> {
+        let index = self.entries.iter().position(|entry| entry.id == id)?;
+        self.entries.remove(index)
+    }
+}
+
+/// Record a change that `calls` reverse, returning the id to undo it with.
+/// A change that removed nothing has nothing to undo.
+pub fn record(description: String, calls: Vec<InverseCall>) -> Option<u64> {
+    if calls.is_empty() {
+        return None;
+    }
+    tracing::debug!("Journal: {} ({} inverse calls)", description, calls.len());
+    // A panic cannot leave the journal half-updated
+    Some(
+        JOURNAL
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(description, calls),
+    )
+}
+
+/// The description of entry `id`, if it is still in the journal.
+pub fn description(id: u64) -> Option<String> {
+    JOURNAL
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .entries
+        .iter()
+        .find(|entry| entry.id == id)
+        .map(|entry| entry.description.clone())
+}
+
+/// Remove entry `id` from the journal to undo it.
+pub fn take(id: u64) -> Option<JournalEntry> {
+    JOURNAL
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .take(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ZoneRules;
+
+    fn port(spec: &str, protocol: &str) -> RuleItem {
+        RuleItem::Port {
+            port: spec.to_string(),
+            protocol: protocol.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_inverse_of_removal() {
+        let block = r#"rule port port="3389" protocol="tcp" reject"#;
+        let zone = Zone {
+            ports: vec!["8080/tcp".to_string(), "53/udp".to_string()],
+            services: vec!["mdns".to_string()],
+            rich_rules: vec![block.to_string()],
+            permanent: Some(ZoneRules {
+                services: vec!["mdns".to_string(), "cups".to_string()],
+                ports: vec!["8080/tcp".to_string()],
+                // firewalld may write the same rule differently
+                rich_rules: vec![format!("{} ", block)],
+            }),
+            ..Zone::new("public")
+        };
+        let calls = inverse_of_removal(
+            &zone,
+            &[
+                port("8080", "tcp"),
+                port("53", "udp"),
+                port("22", "tcp"),
+                RuleItem::Service("cups".to_string()),
+                RuleItem::Service("mdns".to_string()),
+                // The same rule, written differently
+                RuleItem::RichRule(block.to_string()),
+                RuleItem::RichRule(r#"rule port port="3389" protocol="tcp"  reject"#.to_string()),
+            ],
+        );
+        let scopes: Vec<(&RuleItem, Scope)> = calls.iter().map(|c| (&c.item, c.scope)).collect();
+        assert_eq!(
+            scopes,
+            vec![
+                (&port("8080", "tcp"), Scope::Both),
+                (&port("53", "udp"), Scope::Runtime),
+                (&RuleItem::Service("cups".to_string()), Scope::Permanent),
+                (&RuleItem::Service("mdns".to_string()), Scope::Both),
+                (&RuleItem::RichRule(block.to_string()), Scope::Runtime),
+                (&RuleItem::RichRule(format!("{} ", block)), Scope::Permanent),
+            ]
+        );
+        assert_eq!(
+            calls[2].command(),
+            "firewall-cmd --permanent --zone=public --add-service=cups"
+        );
+
+        // Without the permanent configuration, runtime rules go to both
+        let runtime_only = Zone {
+            permanent: None,
+            ..zone
+        };
+        let calls = inverse_of_removal(&runtime_only, &[port("53", "udp")]);
+        assert_eq!(calls[0].scope, Scope::Both);
+    }
+
+    #[test]
+    fn test_journal() {
+        let call = InverseCall {
+            zone: "public".to_string(),
+            item: port("8080", "tcp"),
+            scope: Scope::Both,
+        };
+        let mut journal = Journal::new();
+        let first = journal.push("Port 8080 deleted".to_string(), vec![call.clone()]);
+        for _ in 0..MAX_ENTRIES {
+            journal.push("Another".to_string(), vec![call.clone()]);
+        }
+        // The oldest entry fell off the end
+        assert_eq!(journal.entries.len(), MAX_ENTRIES);
+        assert!(journal.take(first).is_none());
+
+        let last = journal.entries.back().map(|entry| entry.id).unwrap();
+        assert_eq!(
+            journal.take(last).map(|entry| entry.calls),
+            Some(vec![call])
+        );
+        // An entry is undone at most once
+        assert!(journal.take(last).is_none());
+        assert!(record("Nothing removed".to_string(), Vec::new()).is_none());
+    }
+}
@@ -4,19 +4,31 @@
 
 //! Firewalld D-Bus client and related utilities.
 
+pub mod cache;
 mod client;
+mod diagnose;
+pub mod events;
+mod info;
+pub mod journal;
+mod rule_search;
+mod signals;
+mod worker;
+mod zone_transfer;
+mod zone_xml;
 
-pub use client::FirewallClient;
+pub use client::{FirewallClient, FirewallEvent, AUTOMATIC_HELPERS_VALUES, LOG_DENIED_VALUES};
+pub use diagnose::{diagnose, FirewallUnavailable, FIREWALLD_UNIT};
+pub use info::{FirewallInfo, FirewallState};
+pub use rule_search::{rules_for_address, search_rules, RuleKind, RuleMatch, RuleQuery};
+pub use worker::{call, call_blocking, read, read_blocking};
+pub use zone_transfer::{apply_transfer, plan_transfer, TransferMode, ZoneChange, ZoneRule};
+pub use zone_xml::{parse_zone_xml, ZoneFile, MAX_ZONE_FILE_SIZE};
 
 // Part of the public client API; callers use the returned value's methods
 // without naming the type, so the re-export can read as unused.
 #[allow(unused_imports)]
 pub use client::PermanentOutcome;
 
-// Keep FirewallEvent for future use (event-based architecture)
-#[allow(unused_imports)]
-pub use client::FirewallEvent;
-
 /// D-Bus bus name for firewalld.
 pub const BUS_NAME: &str = "org.fedoraproject.FirewallD1";
 
@@ -35,6 +47,9 @@ pub mod interfaces {
     pub const ZONE: &str = "org.fedoraproject.FirewallD1.zone";
     pub const CONFIG: &str = "org.fedoraproject.FirewallD1.config";
     pub const CONFIG_ZONE: &str = "org.fedoraproject.FirewallD1.config.zone";
+    /// Runtime ipset interface (for entries of an ipset)
+    pub const IPSET: &str = "org.fedoraproject.FirewallD1.ipset";
+    pub const CONFIG_IPSET: &str = "org.fedoraproject.FirewallD1.config.ipset";
     pub const PROPERTIES: &str = "org.freedesktop.DBus.Properties";
 }
 
@@ -53,3 +68,27 @@ pub fn zone_description(name: &str) -> &'static str {
         _ => "Custom zone",
     }
 }
+
+/// Whether a zone ships with firewalld or a distribution, or is managed by
+/// other software, rather than created by the user. Such zones are not
+/// offered for deletion.
+pub fn is_builtin_zone(name: &str) -> bool {
+    matches!(
+        name,
+        "block"
+            | "dmz"
+            | "drop"
+            | "external"
+            | "home"
+            | "internal"
+            | "nm-shared"
+            | "public"
+            | "trusted"
+            | "work"
+            | "libvirt"
+            | "libvirt-routed"
+            | "docker"
+            | "FedoraServer"
+            | "FedoraWorkstation"
+    )
+}
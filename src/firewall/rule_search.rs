@@ -0,0 +1,364 @@
+// Security Center - Rule Search
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Searching every zone's ports, services, sources, interfaces and rich
+//! rules at once.
+//!
+//! A query is matched as a case-insensitive substring or as a regular
+//! expression. A bare port number also finds where that port is allowed
+//! without being spelled out: port ranges that cover it, and services whose
+//! definition opens it.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use anyhow::{Context, Result};
+use regex::{Regex, RegexBuilder};
+
+use crate::models::{Service, Zone};
+use crate::validation::parse_port_spec;
+
+/// Longest regular expression accepted, to keep compiled automata small.
+const MAX_PATTERN_LEN: usize = 256;
+
+/// What part of a zone a match was found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RuleKind {
+    Port,
+    Service,
+    Source,
+    Interface,
+    RichRule,
+}
+
+/// One zone entry matching a search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleMatch {
+    pub zone: String,
+    pub kind: RuleKind,
+    /// The entry as firewalld lists it, e.g. `8000-9000/tcp` or `https`.
+    pub entry: String,
+    /// Why the entry matches when the query is not in its text: the port
+    /// of a service definition that opens the searched port.
+    pub via: Option<String>,
+}
+
+/// A compiled search query.
+#[derive(Debug)]
+pub enum RuleQuery {
+    /// Case-insensitive substring, stored lowercased. A query that is a
+    /// port number also matches ranges and service ports covering it.
+    Text {
+        needle: String,
+        port: Option<u16>,
+    },
+    Regex(Regex),
+}
+
+impl RuleQuery {
+    /// Compile `query`. Fails only for an invalid regular expression; an
+    /// empty query matches nothing.
+    pub fn new(query: &str, regex: bool) -> Result<Self> {
+        let query = query.trim();
+        if !regex {
+            return Ok(Self::Text {
+                needle: query.to_lowercase(),
+                port: query.parse().ok(),
+            });
+        }
+        anyhow::ensure!(
+            query.len() <= MAX_PATTERN_LEN,
+            "Regular expression is longer than {} characters",
+            MAX_PATTERN_LEN
+        );
+        let regex = RegexBuilder::new(query)
+            .case_insensitive(true)
+            .size_limit(1 << 20)
+            .build()
+            .context("Invalid regular expression")?;
+        Ok(Self::Regex(regex))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Text { needle, .. } => needle.is_empty(),
+            Self::Regex(regex) => regex.as_str().is_empty(),
+        }
+    }
+
+    fn matches(&self, text: &str) -> bool {
+        match self {
+            Self::Text { needle, .. } => text.to_lowercase().contains(needle.as_str()),
+            Self::Regex(regex) => regex.is_match(text),
+        }
+    }
+
+    /// Whether the port spec `spec` ("8443" or "8000-9000") covers the
+    /// searched port number.
+    fn covers(&self, spec: &str) -> bool {
+        let Self::Text {
+            port: Some(port), ..
+        } = self
+        else {
+            return false;
+        };
+        parse_port_spec(spec).is_some_and(|(start, end)| (start..=end).contains(port))
+    }
+}
+
+/// Search every zone. `services` holds the definitions of the services
+/// enabled in the zones, by name; those missing are matched by name only.
+/// Results are ordered by zone, then by kind.
+pub fn search_rules(
+    zones: &[Zone],
+    services: &HashMap<String, Service>,
+    query: &RuleQuery,
+) -> Vec<RuleMatch> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches = Vec::new();
+    for zone in zones {
+        let mut push = |kind, entry: &str, via: Option<String>| {
+            matches.push(RuleMatch {
+                zone: zone.name.clone(),
+                kind,
+                entry: entry.to_string(),
+                via,
+            });
+        };
+
+        for port in &zone.ports {
+            let spec = port.split_once('/').map_or(port.as_str(), |(spec, _)| spec);
+            if query.matches(port) || query.covers(spec) {
+                push(RuleKind::Port, port, None);
+            }
+        }
+        for name in &zone.services {
+            if query.matches(name) {
+                push(RuleKind::Service, name, None);
+                continue;
+            }
+            let opened = services.get(name).and_then(|service| {
+                service
+                    .ports
+                    .iter()
+                    .map(|(spec, protocol)| (spec, format!("{}/{}", spec, protocol)))
+                    .find(|(spec, port)| query.matches(port) || query.covers(spec))
+                    .map(|(_, port)| port)
+            });
+            if let Some(port) = opened {
+                push(RuleKind::Service, name, Some(port));
+            }
+        }
+        for source in &zone.sources {
+            if query.matches(source) {
+                push(RuleKind::Source, source, None);
+            }
+        }
+        for interface in &zone.interfaces {
+            if query.matches(interface) {
+                push(RuleKind::Interface, interface, None);
+            }
+        }
+        for rule in &zone.rich_rules {
+            if query.matches(rule) || rich_rule_covers(rule, query) {
+                push(RuleKind::RichRule, rule, None);
+            }
+        }
+    }
+
+    matches.sort_by(|a, b| a.zone.cmp(&b.zone).then(a.kind.cmp(&b.kind)));
+    matches
+}
+
+/// Whether a rich rule's `port port="..."` covers the searched port.
+fn rich_rule_covers(rule: &str, query: &RuleQuery) -> bool {
+    rule.split("port=\"")
+        .skip(1)
+        .filter_map(|rest| rest.split_once('"').map(|(spec, _)| spec))
+        .any(|spec| query.covers(spec))
+}
+
+/// The zone sources and rich rules that apply to `address`: sources and
+/// rule `address="..."` attributes naming it or a network containing it.
+/// Results are ordered by zone, then by kind.
+pub fn rules_for_address(zones: &[Zone], address: IpAddr) -> Vec<RuleMatch> {
+    let mut matches = Vec::new();
+    for zone in zones {
+        let mut push = |kind, entry: &str| {
+            matches.push(RuleMatch {
+                zone: zone.name.clone(),
+                kind,
+                entry: entry.to_string(),
+                via: None,
+            });
+        };
+        for source in &zone.sources {
+            if network_contains(source, address) {
+                push(RuleKind::Source, source);
+            }
+        }
+        for rule in &zone.rich_rules {
+            let mentioned = rule
+                .split("address=\"")
+                .skip(1)
+                .filter_map(|rest| rest.split_once('"').map(|(spec, _)| spec))
+                .any(|spec| network_contains(spec, address));
+            if mentioned {
+                push(RuleKind::RichRule, rule);
+            }
+        }
+    }
+    matches.sort_by(|a, b| a.zone.cmp(&b.zone).then(a.kind.cmp(&b.kind)));
+    matches
+}
+
+/// Whether `spec`, an address or a network in CIDR notation, contains
+/// `address`. MAC addresses and ipsets contain nothing.
+fn network_contains(spec: &str, address: IpAddr) -> bool {
+    let (network, prefix) = match spec.split_once('/') {
+        Some((network, prefix)) => match prefix.parse::<u32>() {
+            Ok(prefix) => (network, Some(prefix)),
+            Err(_) => return false,
+        },
+        None => (spec, None),
+    };
+    match (network.parse::<IpAddr>(), address) {
+        (Ok(IpAddr::V4(network)), IpAddr::V4(address)) => {
+            let prefix = prefix.unwrap_or(32);
+            prefix <= 32 && mask(u32::from(network), prefix) == mask(u32::from(address), prefix)
+        }
+        (Ok(IpAddr::V6(network)), IpAddr::V6(address)) => {
+            let prefix = prefix.unwrap_or(128);
+            prefix <= 128 && mask(u128::from(network), prefix) == mask(u128::from(address), prefix)
+        }
+        _ => false,
+    }
+}
+
+/// `value` with all but its top `prefix` bits cleared.
+fn mask<T>(value: T, prefix: u32) -> T
+where
+    T: Copy + std::ops::Shr<u32, Output = T> + std::ops::Shl<u32, Output = T> + Default,
+{
+    let bits = std::mem::size_of::<T>() as u32 * 8;
+    if prefix == 0 {
+        T::default()
+    } else {
+        (value >> (bits - prefix)) << (bits - prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zones() -> Vec<Zone> {
+        let mut public = Zone::new("public");
+        public.ports = vec!["8000-9000/tcp".to_string(), "53/udp".to_string()];
+        public.services = vec!["ssh".to_string(), "https-alt".to_string()];
+        public.interfaces = vec!["wlp2s0".to_string()];
+        let mut home = Zone::new("home");
+        home.sources = vec!["192.168.1.0/24".to_string()];
+        home.rich_rules =
+            vec![r#"rule family="ipv4" port port="8440-8450" protocol="tcp" accept"#.to_string()];
+        vec![public, home]
+    }
+
+    fn services() -> HashMap<String, Service> {
+        let mut https_alt = Service::new("https-alt");
+        https_alt.ports = vec![("8443".to_string(), "tcp".to_string())];
+        HashMap::from([("https-alt".to_string(), https_alt)])
+    }
+
+    #[test]
+    fn test_port_number_search() {
+        let query = RuleQuery::new("8443", false).unwrap();
+        let found = search_rules(&zones(), &services(), &query);
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0].zone, "home");
+        assert_eq!(found[0].kind, RuleKind::RichRule);
+        assert_eq!(found[1].kind, RuleKind::Port);
+        assert_eq!(found[1].entry, "8000-9000/tcp");
+        assert_eq!(found[2].kind, RuleKind::Service);
+        assert_eq!(found[2].via.as_deref(), Some("8443/tcp"));
+    }
+
+    #[test]
+    fn test_substring_search() {
+        let query = RuleQuery::new("  WLP ", false).unwrap();
+        let found = search_rules(&zones(), &services(), &query);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].kind, RuleKind::Interface);
+
+        let query = RuleQuery::new("192.168.", false).unwrap();
+        let found = search_rules(&zones(), &services(), &query);
+        assert_eq!(found[0].kind, RuleKind::Source);
+
+        assert!(
+            search_rules(&zones(), &services(), &RuleQuery::new("", false).unwrap()).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_regex_search() {
+        let query = RuleQuery::new(r"^\d+/udp$", true).unwrap();
+        let found = search_rules(&zones(), &services(), &query);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].entry, "53/udp");
+
+        // Regex queries do not expand port ranges
+        let query = RuleQuery::new("^8443$", true).unwrap();
+        assert!(search_rules(&zones(), &services(), &query).is_empty());
+
+        assert!(RuleQuery::new("(unclosed", true).is_err());
+        assert!(RuleQuery::new(&"a".repeat(MAX_PATTERN_LEN + 1), true).is_err());
+    }
+
+    #[test]
+    fn test_rules_for_address() {
+        let mut zones = zones();
+        let mut drop = Zone::new("drop");
+        drop.sources = vec!["203.0.113.7".to_string(), "ipset:blocklist".to_string()];
+        drop.rich_rules = vec![
+            r#"rule family="ipv4" source address="203.0.113.0/24" drop"#.to_string(),
+            r#"rule family="ipv6" source address="2001:db8::/32" reject"#.to_string(),
+        ];
+        zones.push(drop);
+
+        let found = rules_for_address(&zones, "203.0.113.7".parse().unwrap());
+        assert_eq!(found.len(), 2);
+        assert_eq!(
+            (found[0].kind, found[0].entry.as_str()),
+            (RuleKind::Source, "203.0.113.7")
+        );
+        assert_eq!(found[1].kind, RuleKind::RichRule);
+
+        let found = rules_for_address(&zones, "192.168.1.20".parse().unwrap());
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].zone, "home");
+
+        let found = rules_for_address(&zones, "2001:db8::1".parse().unwrap());
+        assert_eq!(found.len(), 1);
+        assert!(rules_for_address(&zones, "198.51.100.1".parse().unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_network_contains() {
+        let v4 = "10.1.2.3".parse().unwrap();
+        assert!(network_contains("10.1.2.3", v4));
+        assert!(network_contains("10.0.0.0/8", v4));
+        assert!(network_contains("0.0.0.0/0", v4));
+        assert!(!network_contains(
+            "10.1.2.0/30",
+            "10.1.2.4".parse().unwrap()
+        ));
+        assert!(!network_contains("10.0.0.0/33", v4));
+        assert!(!network_contains("::/0", v4));
+        assert!(!network_contains("aa:bb:cc:dd:ee:ff", v4));
+        assert!(network_contains("fe80::/10", "fe80::1".parse().unwrap()));
+    }
+}
@@ -0,0 +1,230 @@
+// Security Center - Firewall Signals
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Live firewalld state changes from its D-Bus signals.
+//!
+//! [`watch`] subscribes to every signal firewalld emits and to the bus
+//! announcing firewalld starting or stopping, and turns each into a
+//! [`FirewallEvent`]. Changes made with `firewall-cmd` or another tool are
+//! reported as well as those made here. Along the way it drops cached
+//! metadata the signal makes stale and records configuration events for the
+//! Overview's activity chart.
+
+use anyhow::{Context, Result};
+use tokio::sync::broadcast;
+use zbus::export::futures_util::{stream, StreamExt};
+use zbus::message::{Body, Type};
+use zbus::{Connection, MatchRule, Message, MessageStream};
+
+use super::events::{self, ConfigEventKind};
+use super::{cache, interfaces, FirewallEvent, BUS_NAME};
+
+const DBUS_BUS: &str = "org.freedesktop.DBus";
+
+/// The event a firewalld signal announces. `args` are the signal's leading
+/// string arguments; signals without a more specific event, or missing
+/// arguments, are reported as [`FirewallEvent::StateChanged`].
+fn event_from_signal(interface: &str, member: &str, args: &[String]) -> FirewallEvent {
+    let arg = |index: usize| args.get(index).cloned();
+    let event = match (interface, member) {
+        (interfaces::MAIN, "Reloaded") => Some(FirewallEvent::Reloaded),
+        (interfaces::MAIN, "PanicModeEnabled") => Some(FirewallEvent::PanicModeChanged(true)),
+        (interfaces::MAIN, "PanicModeDisabled") => Some(FirewallEvent::PanicModeChanged(false)),
+        (interfaces::MAIN, "DefaultZoneChanged") => arg(0).map(FirewallEvent::DefaultZoneChanged),
+        (interfaces::ZONE, "PortAdded") => Some(FirewallEvent::PortAdded {
+            zone: arg(0).unwrap_or_default(),
+            port: arg(1).unwrap_or_default(),
+            protocol: arg(2).unwrap_or_default(),
+        })
+        .filter(|_| args.len() >= 3),
+        (interfaces::ZONE, "PortRemoved") => Some(FirewallEvent::PortRemoved {
+            zone: arg(0).unwrap_or_default(),
+            port: arg(1).unwrap_or_default(),
+            protocol: arg(2).unwrap_or_default(),
+        })
+        .filter(|_| args.len() >= 3),
+        (
+            interfaces::ZONE,
+            "ZoneChanged"
+            | "ZoneOfInterfaceChanged"
+            | "ZoneOfSourceChanged"
+            | "InterfaceAdded"
+            | "InterfaceRemoved"
+            | "SourceAdded"
+            | "SourceRemoved",
+        ) => arg(0).map(FirewallEvent::ZoneChanged),
+        _ => None,
+    };
+    event.unwrap_or(FirewallEvent::StateChanged)
+}
+
+/// The leading string arguments of a signal body. firewalld's signals carry
+/// up to three strings, optionally followed by a timeout.
+fn string_args(body: &Body) -> Vec<String> {
+    if let Ok((a, b, c, _)) = body.deserialize::<(String, String, String, i32)>() {
+        vec![a, b, c]
+    } else if let Ok((a, b, c)) = body.deserialize::<(String, String, String)>() {
+        vec![a, b, c]
+    } else if let Ok((a, b, _)) = body.deserialize::<(String, String, i32)>() {
+        vec![a, b]
+    } else if let Ok((a, b)) = body.deserialize::<(String, String)>() {
+        vec![a, b]
+    } else if let Ok(a) = body.deserialize::<String>() {
+        vec![a]
+    } else {
+        Vec::new()
+    }
+}
+
+/// The event a message from either subscription announces.
+fn event_from_message(msg: &Message) -> Option<FirewallEvent> {
+    let header = msg.header();
+    let interface = header.interface()?.as_str();
+    let member = header.member()?.as_str();
+
+    if interface == DBUS_BUS {
+        // NameOwnerChanged(name, old owner, new owner)
+        let (_, _, new_owner) = msg.body().deserialize::<(String, String, String)>().ok()?;
+        return Some(if new_owner.is_empty() {
+            FirewallEvent::Disconnected
+        } else {
+            FirewallEvent::Connected
+        });
+    }
+
+    // Reloads and permanent edits may change service definitions
+    if cache::invalidated_by(interface, member) {
+        cache::invalidate();
+    }
+    let args = string_args(&msg.body());
+    if let Some(kind) =
+        ConfigEventKind::from_signal(interface, member, args.first().map(String::as_str))
+    {
+        events::record(kind);
+    }
+    Some(event_from_signal(interface, member, &args))
+}
+
+/// Send an event for every firewalld signal until `sender` has no
+/// subscribers left.
+pub async fn watch(sender: broadcast::Sender<FirewallEvent>) -> Result<()> {
+    let conn = Connection::system()
+        .await
+        .context("Failed to connect to system D-Bus")?;
+
+    // Every signal emitted by firewalld, regardless of interface
+    let firewalld = MatchRule::builder()
+        .msg_type(Type::Signal)
+        .sender(BUS_NAME)?
+        .build();
+    // firewalld starting or stopping
+    let owner = MatchRule::builder()
+        .msg_type(Type::Signal)
+        .sender(DBUS_BUS)?
+        .interface(DBUS_BUS)?
+        .member("NameOwnerChanged")?
+        .arg(0, BUS_NAME)?
+        .build();
+
+    let firewalld = MessageStream::for_match_rule(firewalld, &conn, None).await?;
+    let owner = MessageStream::for_match_rule(owner, &conn, None).await?;
+    let mut messages = stream::select(firewalld, owner);
+
+    while let Some(msg) = messages.next().await {
+        let Ok(msg) = msg else { continue };
+        let Some(event) = event_from_message(&msg) else {
+            continue;
+        };
+        if sender.send(event).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_main_signals() {
+        assert_eq!(
+            event_from_signal(interfaces::MAIN, "Reloaded", &[]),
+            FirewallEvent::Reloaded
+        );
+        assert_eq!(
+            event_from_signal(interfaces::MAIN, "PanicModeEnabled", &[]),
+            FirewallEvent::PanicModeChanged(true)
+        );
+        assert_eq!(
+            event_from_signal(interfaces::MAIN, "PanicModeDisabled", &[]),
+            FirewallEvent::PanicModeChanged(false)
+        );
+        assert_eq!(
+            event_from_signal(interfaces::MAIN, "DefaultZoneChanged", &args(&["home"])),
+            FirewallEvent::DefaultZoneChanged("home".to_string())
+        );
+    }
+
+    #[test]
+    fn test_zone_signals() {
+        assert_eq!(
+            event_from_signal(
+                interfaces::ZONE,
+                "PortAdded",
+                &args(&["public", "8080", "tcp"])
+            ),
+            FirewallEvent::PortAdded {
+                zone: "public".to_string(),
+                port: "8080".to_string(),
+                protocol: "tcp".to_string(),
+            }
+        );
+        assert_eq!(
+            event_from_signal(
+                interfaces::ZONE,
+                "PortRemoved",
+                &args(&["public", "53", "udp"])
+            ),
+            FirewallEvent::PortRemoved {
+                zone: "public".to_string(),
+                port: "53".to_string(),
+                protocol: "udp".to_string(),
+            }
+        );
+        assert_eq!(
+            event_from_signal(
+                interfaces::ZONE,
+                "ZoneOfInterfaceChanged",
+                &args(&["work", "eth0"])
+            ),
+            FirewallEvent::ZoneChanged("work".to_string())
+        );
+    }
+
+    #[test]
+    fn test_other_signals_are_state_changes() {
+        // Missing arguments
+        assert_eq!(
+            event_from_signal(interfaces::MAIN, "DefaultZoneChanged", &[]),
+            FirewallEvent::StateChanged
+        );
+        assert_eq!(
+            event_from_signal(interfaces::ZONE, "PortAdded", &args(&["public"])),
+            FirewallEvent::StateChanged
+        );
+        assert_eq!(
+            event_from_signal(interfaces::ZONE, "ServiceAdded", &args(&["public", "ssh"])),
+            FirewallEvent::StateChanged
+        );
+        assert_eq!(
+            event_from_signal(interfaces::CONFIG_ZONE, "Updated", &args(&["public"])),
+            FirewallEvent::StateChanged
+        );
+    }
+}
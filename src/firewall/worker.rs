@@ -0,0 +1,145 @@
+// Security Center - Firewall Worker
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Background threads owning the app's [`FirewallClient`]s.
+//!
+//! Pages hand them closures through [`call`] or [`read`] and await the typed
+//! reply instead of building a client of their own on a blocking thread;
+//! code that already runs on a blocking thread uses [`call_blocking`] and
+//! [`read_blocking`]. Changes run one after another in the order they were
+//! made, so two pages changing the firewall at once cannot interleave their
+//! D-Bus calls. Reads have a thread and a client of their own, so a change
+//! waiting on a polkit password prompt does not hold up a refresh.
+//!
+//! A closure must not call into the worker itself: it would wait on its own
+//! queue forever.
+
+use std::sync::mpsc;
+use std::sync::{Mutex, OnceLock};
+
+use tokio::sync::oneshot;
+use tracing::warn;
+
+use super::FirewallClient;
+
+type Job = Box<dyn FnOnce(&mut FirewallClient) + Send>;
+type Queue = Mutex<mpsc::Sender<Job>>;
+
+/// Which thread a closure runs on.
+#[derive(Debug, Clone, Copy)]
+enum Lane {
+    Changes,
+    Reads,
+}
+
+/// The queue of `lane`, started on first use.
+fn queue(lane: Lane) -> &'static Queue {
+    static CHANGES: OnceLock<Queue> = OnceLock::new();
+    static READS: OnceLock<Queue> = OnceLock::new();
+    match lane {
+        Lane::Changes => CHANGES.get_or_init(|| start("firewall-worker")),
+        Lane::Reads => READS.get_or_init(|| start("firewall-reader")),
+    }
+}
+
+fn start(name: &str) -> Queue {
+    let (sender, jobs) = mpsc::channel::<Job>();
+    let spawned = std::thread::Builder::new()
+        .name(name.to_string())
+        .spawn(move || {
+            let mut client = FirewallClient::new();
+            for job in jobs {
+                job(&mut client);
+            }
+        });
+    if let Err(e) = spawned {
+        warn!("Cannot start the {}: {}", name, e);
+    }
+    Mutex::new(sender)
+}
+
+/// Queue `f` on `lane`, returning the receiver of its reply.
+fn submit<R, F>(lane: Lane, f: F) -> oneshot::Receiver<R>
+where
+    R: Send + 'static,
+    F: FnOnce(&mut FirewallClient) -> R + Send + 'static,
+{
+    let (reply, answer) = oneshot::channel();
+    let job: Job = Box::new(move |client| {
+        let _ = reply.send(f(client));
+    });
+    // If the worker is gone the job and its reply are dropped, which the
+    // caller sees as an error
+    if let Ok(queue) = queue(lane).lock() {
+        let _ = queue.send(job);
+    }
+    answer
+}
+
+/// Run `f`, which changes the firewall, on the worker's client and await
+/// what it returns. The client may not be connected yet, so `f` calls
+/// [`FirewallClient::connect`] first; with a connection already open that
+/// only re-checks firewalld's state. Fails only if the worker is gone.
+pub async fn call<R, F>(f: F) -> Result<R, oneshot::error::RecvError>
+where
+    R: Send + 'static,
+    F: FnOnce(&mut FirewallClient) -> R + Send + 'static,
+{
+    submit(Lane::Changes, f).await
+}
+
+/// [`call`] for `f` that only reads the firewall, on the reader's client.
+pub async fn read<R, F>(f: F) -> Result<R, oneshot::error::RecvError>
+where
+    R: Send + 'static,
+    F: FnOnce(&mut FirewallClient) -> R + Send + 'static,
+{
+    submit(Lane::Reads, f).await
+}
+
+/// [`call`], blocking the current thread until `f` returns. Not for the
+/// main thread or async code.
+pub fn call_blocking<R, F>(f: F) -> Result<R, oneshot::error::RecvError>
+where
+    R: Send + 'static,
+    F: FnOnce(&mut FirewallClient) -> R + Send + 'static,
+{
+    submit(Lane::Changes, f).blocking_recv()
+}
+
+/// [`read`], blocking the current thread until `f` returns. Not for the
+/// main thread or async code.
+pub fn read_blocking<R, F>(f: F) -> Result<R, oneshot::error::RecvError>
+where
+    R: Send + 'static,
+    F: FnOnce(&mut FirewallClient) -> R + Send + 'static,
+{
+    submit(Lane::Reads, f).blocking_recv()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let connected = runtime.block_on(call(|client| client.is_connected()));
+        assert_eq!(connected, Ok(false));
+        assert_eq!(runtime.block_on(read(|_| 42)), Ok(42));
+    }
+
+    #[test]
+    fn test_reads_do_not_wait_for_changes() {
+        // A change stuck, as behind a password prompt
+        let (release, stuck) = mpsc::channel::<()>();
+        let change = std::thread::spawn(|| call_blocking(move |_| stuck.recv().is_ok()));
+
+        assert_eq!(read_blocking(|client| client.is_connected()), Ok(false));
+        release.send(()).unwrap();
+        assert_eq!(change.join().unwrap(), Ok(true));
+    }
+}
@@ -0,0 +1,222 @@
+// Security Center - Zone Rule Transfer
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Moving and copying services and ports from one zone to another.
+//!
+//! A transfer is planned as a list of [`ZoneChange`]s: the rules are added
+//! to the destination first and, for a move, removed from the source after,
+//! so a rule is never missing from both zones. [`apply_transfer`] makes the
+//! changes at runtime and permanently; when one fails, the changes already
+//! made are reverted in reverse order and the zones are left as they were.
+
+use anyhow::{anyhow, Result};
+use tracing::warn;
+
+use super::client::{FirewallClient, PermanentOutcome};
+use crate::models::Zone;
+
+/// A service or port of a zone.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ZoneRule {
+    Service(String),
+    /// A port or range, e.g. `80` or `10-20`, and its protocol.
+    Port(String, String),
+}
+
+impl ZoneRule {
+    /// The services and ports of `zone`, services first.
+    pub fn of_zone(zone: &Zone) -> Vec<Self> {
+        let services = zone.services.iter().cloned().map(ZoneRule::Service);
+        let ports = zone.ports.iter().filter_map(|spec| {
+            let (port, protocol) = spec.split_once('/')?;
+            Some(ZoneRule::Port(port.to_string(), protocol.to_string()))
+        });
+        services.chain(ports).collect()
+    }
+
+    /// The rule as firewalld lists it, e.g. `ssh` or `8080/tcp`.
+    pub fn label(&self) -> String {
+        match self {
+            ZoneRule::Service(name) => name.clone(),
+            ZoneRule::Port(port, protocol) => format!("{}/{}", port, protocol),
+        }
+    }
+
+    /// Whether `zone` has the rule.
+    pub fn in_zone(&self, zone: &Zone) -> bool {
+        match self {
+            ZoneRule::Service(name) => zone.services.contains(name),
+            ZoneRule::Port(..) => zone.ports.contains(&self.label()),
+        }
+    }
+}
+
+/// Whether the rules stay in the source zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferMode {
+    Copy,
+    Move,
+}
+
+/// Adding a rule to or removing it from one zone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZoneChange {
+    pub zone: String,
+    pub rule: ZoneRule,
+    pub add: bool,
+}
+
+impl ZoneChange {
+    /// The change that undoes this one.
+    pub fn inverse(&self) -> Self {
+        Self {
+            add: !self.add,
+            ..self.clone()
+        }
+    }
+
+    /// Make the change to a copy of the zone, as a refresh would show it.
+    pub fn apply_to(&self, zone: &mut Zone) {
+        if zone.name != self.zone {
+            return;
+        }
+        let list = match self.rule {
+            ZoneRule::Service(_) => &mut zone.services,
+            ZoneRule::Port(..) => &mut zone.ports,
+        };
+        let label = self.rule.label();
+        if self.add {
+            if !list.contains(&label) {
+                list.push(label);
+            }
+        } else {
+            list.retain(|entry| *entry != label);
+        }
+    }
+}
+
+/// The changes that copy or move `rules` from `from` to `to`. Rules the
+/// destination already has are not added again; a move still removes them
+/// from the source.
+pub fn plan_transfer(
+    from: &Zone,
+    to: &Zone,
+    rules: &[ZoneRule],
+    mode: TransferMode,
+) -> Vec<ZoneChange> {
+    let adds = rules
+        .iter()
+        .filter(|rule| !rule.in_zone(to))
+        .map(|rule| ZoneChange {
+            zone: to.name.clone(),
+            rule: rule.clone(),
+            add: true,
+        });
+    let removes = rules
+        .iter()
+        .filter(|rule| mode == TransferMode::Move && rule.in_zone(from))
+        .map(|rule| ZoneChange {
+            zone: from.name.clone(),
+            rule: rule.clone(),
+            add: false,
+        });
+    adds.chain(removes).collect()
+}
+
+/// Make `changes` in order, at runtime and permanently. If one fails, the
+/// changes made so far are reverted and its error is returned.
+pub fn apply_transfer(client: &FirewallClient, changes: &[ZoneChange]) -> Result<()> {
+    for (index, change) in changes.iter().enumerate() {
+        if let Err(e) = apply_change(client, change) {
+            // The failed change may have gone through at runtime only, so
+            // it is reverted too; reverting what is not there is a no-op
+            for done in changes[..=index].iter().rev() {
+                if let Err(revert_error) = apply_change(client, &done.inverse()) {
+                    warn!(
+                        "Failed to revert {} in zone {}: {}",
+                        done.rule.label(),
+                        done.zone,
+                        revert_error
+                    );
+                }
+            }
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+fn apply_change(client: &FirewallClient, change: &ZoneChange) -> Result<()> {
+    let zone = change.zone.as_str();
+    let outcome = match (&change.rule, change.add) {
+        (ZoneRule::Service(name), true) => client.enable_service(zone, name, true)?,
+        (ZoneRule::Service(name), false) => client.disable_service(zone, name, true)?,
+        (ZoneRule::Port(port, protocol), true) => client.add_port(zone, port, protocol, true)?,
+        (ZoneRule::Port(port, protocol), false) => {
+            client.remove_port(zone, port, protocol, true)?
+        }
+    };
+    match outcome {
+        PermanentOutcome::Failed(e) => Err(anyhow!(
+            "{} in zone {} could not be saved permanently: {}",
+            change.rule.label(),
+            zone,
+            e
+        )),
+        PermanentOutcome::Applied | PermanentOutcome::NotRequested => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zone(name: &str, services: &[&str], ports: &[&str]) -> Zone {
+        let mut zone = Zone::new(name);
+        zone.services = services.iter().map(|s| s.to_string()).collect();
+        zone.ports = ports.iter().map(|p| p.to_string()).collect();
+        zone
+    }
+
+    #[test]
+    fn test_plan_transfer() {
+        let public = zone("public", &["ssh", "mdns"], &["8080/tcp"]);
+        let home = zone("home", &["mdns"], &[]);
+        let rules = ZoneRule::of_zone(&public);
+        assert_eq!(rules[2], ZoneRule::Port("8080".into(), "tcp".into()));
+
+        let copy = plan_transfer(&public, &home, &rules, TransferMode::Copy);
+        let labels: Vec<(String, bool)> = copy.iter().map(|c| (c.rule.label(), c.add)).collect();
+        assert_eq!(
+            labels,
+            vec![("ssh".to_string(), true), ("8080/tcp".to_string(), true)]
+        );
+        assert!(copy.iter().all(|c| c.zone == "home"));
+
+        // Adds come before removes, and mdns is removed though home has it
+        let moved = plan_transfer(&public, &home, &rules, TransferMode::Move);
+        assert_eq!(moved.len(), 5);
+        assert!(moved[..2].iter().all(|c| c.add && c.zone == "home"));
+        assert!(moved[2..].iter().all(|c| !c.add && c.zone == "public"));
+    }
+
+    #[test]
+    fn test_apply_to() {
+        let mut public = zone("public", &["ssh"], &["8080/tcp"]);
+        let change = ZoneChange {
+            zone: "public".to_string(),
+            rule: ZoneRule::Port("8080".into(), "tcp".into()),
+            add: false,
+        };
+        change.apply_to(&mut public);
+        assert!(public.ports.is_empty());
+        change.inverse().apply_to(&mut public);
+        change.inverse().apply_to(&mut public);
+        assert_eq!(public.ports, vec!["8080/tcp"]);
+
+        let mut home = zone("home", &[], &[]);
+        change.inverse().apply_to(&mut home);
+        assert!(home.ports.is_empty());
+    }
+}
@@ -0,0 +1,234 @@
+// Security Center - Zone XML Files
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Reading firewalld zone definition files (`/etc/firewalld/zones/*.xml`).
+//!
+//! Only the parts the application can apply through its own controls are
+//! extracted: services and ports. Everything else in the file (rich rules,
+//! sources, interfaces, forwarding, ...) is listed as skipped so the import
+//! preview can say what will not be carried over. The format is a flat list
+//! of elements, so a small tag scanner is enough.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::validation::{
+    format_port_spec, parse_port_spec, validate_firewall_service, validate_protocol,
+};
+
+/// Largest zone file accepted; real ones are a few kilobytes.
+pub const MAX_ZONE_FILE_SIZE: u64 = 262_144;
+
+/// Contents of a zone file relevant to an import.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ZoneFile {
+    /// Human-readable zone name (`<short>`).
+    pub short: String,
+    pub description: String,
+    /// `target` attribute of `<zone>`, if set.
+    pub target: Option<String>,
+    pub services: Vec<String>,
+    /// (port spec, protocol).
+    pub ports: Vec<(String, String)>,
+    /// Elements that are not imported, each listed once.
+    pub skipped: Vec<String>,
+}
+
+impl ZoneFile {
+    /// Number of services and ports that can be imported.
+    pub fn rule_count(&self) -> usize {
+        self.services.len() + self.ports.len()
+    }
+
+    fn skip(&mut self, what: &str) {
+        if !self.skipped.iter().any(|s| s == what) {
+            self.skipped.push(what.to_string());
+        }
+    }
+}
+
+/// Parse a firewalld zone XML file.
+pub fn parse_zone_xml(text: &str) -> Result<ZoneFile> {
+    let mut zone = ZoneFile::default();
+    let mut saw_zone = false;
+    let mut rest = text;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        if let Some(after) = rest.strip_prefix("<!--") {
+            let end = after.find("-->").context("Unterminated comment")?;
+            rest = &after[end + 3..];
+            continue;
+        }
+        let end = rest.find('>').context("Unterminated tag")?;
+        let raw = &rest[1..end];
+        rest = &rest[end + 1..];
+        if raw.starts_with('?') || raw.starts_with('!') || raw.starts_with('/') {
+            continue;
+        }
+
+        let self_closing = raw.ends_with('/');
+        let tag = raw.trim_end_matches('/').trim();
+        let (name, attrs) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+        let attrs = parse_attributes(attrs)?;
+
+        match name {
+            "zone" => {
+                saw_zone = true;
+                zone.target = attrs.get("target").cloned();
+            }
+            "short" | "description" if !self_closing => {
+                let close = format!("</{}>", name);
+                let end = rest
+                    .find(&close)
+                    .ok_or_else(|| anyhow!("Unterminated <{}>", name))?;
+                let text = unescape(rest[..end].split_whitespace().collect::<Vec<_>>().join(" "));
+                rest = &rest[end + close.len()..];
+                if name == "short" {
+                    zone.short = text;
+                } else {
+                    zone.description = text;
+                }
+            }
+            "service" => {
+                let service = attrs.get("name").map(String::as_str).unwrap_or("");
+                let service = validate_firewall_service(service)
+                    .ok_or_else(|| anyhow!("Invalid service name: {}", service))?;
+                if !zone.services.iter().any(|s| s == service) {
+                    zone.services.push(service.to_string());
+                }
+            }
+            "port" => {
+                let port = attrs.get("port").map(String::as_str).unwrap_or("");
+                let (start, end) =
+                    parse_port_spec(port).ok_or_else(|| anyhow!("Invalid port: {}", port))?;
+                let protocol = attrs.get("protocol").map(String::as_str).unwrap_or("");
+                match validate_protocol(protocol) {
+                    Some(protocol) => {
+                        let entry = (format_port_spec(start, end), protocol.to_string());
+                        if !zone.ports.contains(&entry) {
+                            zone.ports.push(entry);
+                        }
+                    }
+                    // sctp and dccp ports cannot be managed from the Ports page
+                    None => zone.skip(&format!("{} ports", protocol)),
+                }
+            }
+            "rule" => {
+                // Rich rules nest service and port elements of their own
+                zone.skip("rich rules");
+                if !self_closing {
+                    let end = rest.find("</rule>").context("Unterminated <rule>")?;
+                    rest = &rest[end + "</rule>".len()..];
+                }
+            }
+            other => zone.skip(other),
+        }
+    }
+
+    if !saw_zone {
+        bail!("Not a firewalld zone file");
+    }
+    Ok(zone)
+}
+
+/// Parse `key="value" key2='value'` attribute lists.
+fn parse_attributes(mut text: &str) -> Result<HashMap<String, String>> {
+    let mut attrs = HashMap::new();
+    loop {
+        text = text.trim_start();
+        if text.is_empty() {
+            return Ok(attrs);
+        }
+        let (key, after) = text
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Malformed attribute: {}", text))?;
+        let after = after.trim_start();
+        let quote = after
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+            .context("Unquoted attribute value")?;
+        let value_end = after[1..]
+            .find(quote)
+            .context("Unterminated attribute value")?;
+        attrs.insert(
+            key.trim().to_string(),
+            unescape(after[1..1 + value_end].to_string()),
+        );
+        text = &after[value_end + 2..];
+    }
+}
+
+fn unescape(text: String) -> String {
+    if !text.contains('&') {
+        return text;
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ZONE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<zone target="DROP">
+  <short>Home &amp; Lab</short>
+  <description>For use in home areas.
+    You mostly trust the other computers.</description>
+  <!-- <service name="telnet"/> -->
+  <service name="ssh"/>
+  <service name="mdns" />
+  <port port="8080" protocol="tcp"/>
+  <port protocol='udp' port='60000-61000'/>
+  <port port="9" protocol="sctp"/>
+  <interface name="eth0"/>
+  <rule family="ipv4">
+    <source address="10.0.0.0/8"/>
+    <service name="http"/>
+    <accept/>
+  </rule>
+  <forward/>
+</zone>
+"#;
+
+    #[test]
+    fn test_parse_zone_xml() {
+        let zone = parse_zone_xml(ZONE).unwrap();
+        assert_eq!(zone.short, "Home & Lab");
+        assert_eq!(
+            zone.description,
+            "For use in home areas. You mostly trust the other computers."
+        );
+        assert_eq!(zone.target.as_deref(), Some("DROP"));
+        // Commented-out and rich-rule services are not picked up
+        assert_eq!(zone.services, vec!["ssh", "mdns"]);
+        assert_eq!(
+            zone.ports,
+            vec![
+                ("8080".to_string(), "tcp".to_string()),
+                ("60000-61000".to_string(), "udp".to_string())
+            ]
+        );
+        assert_eq!(
+            zone.skipped,
+            vec!["sctp ports", "interface", "rich rules", "forward"]
+        );
+        assert_eq!(zone.rule_count(), 4);
+    }
+
+    #[test]
+    fn test_parse_zone_xml_rejects_invalid() {
+        assert!(parse_zone_xml("<service name=\"ssh\"/>").is_err());
+        assert!(parse_zone_xml("<zone><service name=\"ssh; rm\"/></zone>").is_err());
+        assert!(parse_zone_xml("<zone><port port=\"0\" protocol=\"tcp\"/></zone>").is_err());
+        assert!(parse_zone_xml("<zone><short>Open").is_err());
+        assert!(parse_zone_xml("<zone><service name=ssh/></zone>").is_err());
+    }
+}
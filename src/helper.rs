@@ -0,0 +1,1221 @@
+// Security Center - Privileged Helper
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Privileged helper for operations that have no D-Bus API.
+//!
+//! Most privileged work goes through firewalld and systemd over D-Bus with
+//! polkit authorization. A few tasks, such as writing journald or
+//! systemd-coredump drop-ins, removing core dumps, setting sysctl values,
+//! reading nftables counters, running an OpenSCAP compliance scan, scanning another network
+//! namespace, verifying the executables of listening processes, talking to
+//! fail2ban, switching the SELinux mode, turning off Wake-on-LAN, terminating connections or installing NetworkManager, sshd
+//! and service hardening drop-ins, need root access instead. For those the application runs the separate helper program,
+//! which does nothing else, through `pkexec`:
+//!
+//! ```text
+//! pkexec /usr/libexec/security-center-helper --privileged-helper <operation> [key=value ...]
+//! ```
+//!
+//! The operation and every argument are validated on both sides of the
+//! privilege boundary: [`HelperOp`] is the only way to build a command line,
+//! and the root side parses it back through the same allowlist before acting.
+//!
+//! # Authorization
+//!
+//! With the polkit policy ([`POLKIT_POLICY`]) installed, pkexec starts the
+//! helper after administrator authentication, and the helper then asks
+//! polkit whether the user may perform the operation's class, such as
+//! `edit-sshd` or `edit-sysctl` (see [`HelperOp::polkit_action`]). The
+//! classes default to `auth_admin_keep`, and polkit rules can allow or deny
+//! each one, so an organization can hand out only some capabilities. Without
+//! the policy the helper refuses every operation except installing it.
+//! Packages install the policy; `security-center --install-polkit-policy`
+//! installs it for a build from source.
+//!
+//! The application and the monitor refuse to run as root or under pkexec
+//! (see [`running_elevated`]), so the helper is the only code that does.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::net::IpAddr;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{anyhow, bail, Context, Result};
+use zbus::zvariant::Value;
+
+use crate::admin::{
+    clear_wake_on_lan, evaluate_profile, read_jails_json, remove_core_dumps, scan_in_namespace,
+    sysctl_path, terminate_flow, validate_coredump_name, validate_datastream, validate_jail_name,
+    validate_profile_id, verify_listeners, write_selinux_enforcing, Fail2banClient, Flow,
+    NamespaceRef, MAC_DROPIN, MAC_DROPIN_CONTENTS, MAX_CORE_DUMPS, SSH_DROPIN, SSH_DROPIN_CONTENTS,
+    SSH_UNITS,
+};
+use crate::systemd::{
+    hardening_dropin, hardening_dropin_path, validate_hardening, HardeningDirective, ServiceState,
+    SystemdClient,
+};
+use crate::validation::{
+    validate_interface_name, validate_journal_size, validate_journal_storage, validate_sysctl_key,
+    validate_sysctl_value,
+};
+
+/// Command-line flag the helper program requires.
+pub const HELPER_FLAG: &str = "--privileged-helper";
+
+const JOURNALD_DROPIN_DIR: &str = "/etc/systemd/journald.conf.d";
+const JOURNALD_DROPIN: &str = "/etc/systemd/journald.conf.d/60-security-center.conf";
+const JOURNALD_UNIT: &str = "systemd-journald.service";
+
+const COREDUMP_DROPIN_DIR: &str = "/etc/systemd/coredump.conf.d";
+const COREDUMP_DROPIN: &str = "/etc/systemd/coredump.conf.d/60-security-center.conf";
+const COREDUMP_SYSCTL: &str = "/etc/sysctl.d/60-security-center-coredump.conf";
+const SUID_DUMPABLE: &str = "/proc/sys/fs/suid_dumpable";
+
+const POLICY_SYSCTL: &str = "/etc/sysctl.d/60-security-center-policy.conf";
+
+/// pkexec resets PATH to the standard system directories.
+const NFT: &str = "nft";
+const FIREWALLD_TABLE: &str = "firewalld";
+
+/// Scratch directory for oscap results, only writable by root.
+const COMPLIANCE_RESULTS_DIR: &str = "/run/security-center-compliance";
+
+const NM_BUS: &str = "org.freedesktop.NetworkManager";
+const NM_PATH: &str = "/org/freedesktop/NetworkManager";
+/// `NM_MANAGER_RELOAD_FLAG_CONF`.
+const NM_RELOAD_CONF: u32 = 1;
+
+const SSHD: &str = "sshd";
+
+/// Most connections terminated by one helper call.
+const MAX_FLOWS: usize = 64;
+
+/// The polkit policy with the helper's actions.
+pub const POLKIT_POLICY: &str = include_str!("../data/com.chrisdaggas.security-center.policy");
+/// Flag that installs [`POLKIT_POLICY`].
+pub const INSTALL_POLICY_FLAG: &str = "--install-polkit-policy";
+const POLKIT_POLICY_PATH: &str =
+    "/usr/share/polkit-1/actions/com.chrisdaggas.security-center.policy";
+/// The helper program, where packages install it and the policy lets pkexec
+/// start it. The policy names this path whoever installs it.
+const HELPER_PATH: &str = "/usr/libexec/security-center-helper";
+/// File name of the helper program next to the application in a build tree.
+const HELPER_NAME: &str = "security-center-helper";
+
+const POLKIT_BUS: &str = "org.freedesktop.PolicyKit1";
+const POLKIT_PATH: &str = "/org/freedesktop/PolicyKit1/Authority";
+const POLKIT_AUTHORITY: &str = "org.freedesktop.PolicyKit1.Authority";
+/// `CheckAuthorization` flags that keep polkit from asking or let it ask
+/// for a password.
+const POLKIT_NO_INTERACTION: u32 = 0;
+const POLKIT_ALLOW_INTERACTION: u32 = 1;
+
+/// Polkit action pkexec checks before it starts the helper.
+pub const HELPER_ACTION: &str = "com.chrisdaggas.security-center.helper";
+/// Polkit action for importing, changing and refreshing IP blocklists,
+/// which the application checks itself before it calls firewalld. Only a
+/// prompt: firewalld's own polkit actions authorize the change.
+pub const MANAGE_BLOCKLIST_ACTION: &str = "com.chrisdaggas.security-center.manage-blocklist";
+
+/// pkexec exit codes for a dismissed dialog and a refused authorization.
+const PKEXEC_CANCELLED: i32 = 126;
+const PKEXEC_NOT_AUTHORIZED: i32 = 127;
+
+/// An operation the helper is allowed to perform as root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HelperOp {
+    /// Write (or remove, when both are `None`) the journald drop-in with
+    /// `Storage=` and `SystemMaxUse=`, then restart journald.
+    ConfigureJournald {
+        storage: Option<String>,
+        system_max_use: Option<String>,
+    },
+    /// Stop systemd-coredump from storing dumps and forbid set-UID programs
+    /// from dumping core (`restrict`), or remove those overrides again.
+    ConfigureCoredump { restrict: bool },
+    /// Remove core dumps from the systemd-coredump directory by file name,
+    /// overwriting them first when `secure`.
+    RemoveCoreDumps { names: Vec<String>, secure: bool },
+    /// Set sysctl values now and persist them in a sysctl.d drop-in, which
+    /// is replaced as a whole by every call.
+    ApplySysctl { settings: Vec<(String, String)> },
+    /// Print firewalld's nftables table as JSON, for rule hit counters.
+    /// Read-only.
+    ReadRuleCounters,
+    /// Evaluate an OpenSCAP profile of a scap-security-guide data stream and
+    /// print the results as JSON. Read-only; no remediation is applied.
+    RunComplianceScan { datastream: String, profile: String },
+    /// Enter a network namespace and print its listening sockets and
+    /// connections as JSON. Read-only.
+    ScanNamespace { namespace: NamespaceRef },
+    /// Check the executables of the listening processes against their
+    /// packages (`rpm -V`, `dpkg --verify`) and print the results as JSON.
+    /// Read-only.
+    VerifyListeners,
+    /// Print the fail2ban jails, their counters and banned addresses as
+    /// JSON. Read-only.
+    ReadFail2ban,
+    /// Lift fail2ban's ban on an address in one jail.
+    Fail2banUnban { jail: String, ip: IpAddr },
+    /// Switch SELinux between enforcing and permissive mode until the next
+    /// boot.
+    SetSelinuxMode { enforcing: bool },
+    /// Clear every Wake-on-LAN mode of a network interface until it is set
+    /// again (usually at the next boot).
+    DisableWakeOnLan { interface: String },
+    /// Install the NetworkManager drop-in giving each network its own
+    /// stable random MAC address, then reload NetworkManager's configuration.
+    EnableMacRandomization,
+    /// Install the sshd hardening drop-in if `sshd -t` accepts it, then
+    /// restart a running SSH server.
+    HardenSsh,
+    /// Write the hardening drop-in of a service (or remove it, when
+    /// `settings` is empty), reload systemd and restart the service if it
+    /// is running.
+    HardenService {
+        unit: String,
+        settings: Vec<(HardeningDirective, String)>,
+    },
+    /// Delete the connection tracking entries of established connections
+    /// and close their local sockets.
+    TerminateConnections { flows: Vec<Flow> },
+    /// Install [`POLKIT_POLICY`], letting pkexec start the helper at
+    /// [`HELPER_PATH`].
+    InstallPolkitPolicy,
+}
+
+impl HelperOp {
+    /// Command-line arguments passed after [`HELPER_FLAG`].
+    fn to_args(&self) -> Vec<String> {
+        match self {
+            HelperOp::ConfigureJournald {
+                storage,
+                system_max_use,
+            } => vec![
+                "configure-journald".to_string(),
+                format!("storage={}", storage.as_deref().unwrap_or("")),
+                format!("max-use={}", system_max_use.as_deref().unwrap_or("")),
+            ],
+            HelperOp::ConfigureCoredump { restrict } => vec![
+                "configure-coredump".to_string(),
+                format!("restrict={}", if *restrict { "yes" } else { "no" }),
+            ],
+            HelperOp::RemoveCoreDumps { names, secure } => {
+                let mut args = vec![
+                    "remove-core-dumps".to_string(),
+                    format!("secure={}", if *secure { "yes" } else { "no" }),
+                ];
+                args.extend(names.iter().map(|name| format!("name={}", name)));
+                args
+            }
+            HelperOp::ApplySysctl { settings } => {
+                let mut args = vec!["apply-sysctl".to_string()];
+                args.extend(settings.iter().map(|(k, v)| format!("{}={}", k, v)));
+                args
+            }
+            HelperOp::ReadRuleCounters => vec!["read-rule-counters".to_string()],
+            HelperOp::RunComplianceScan {
+                datastream,
+                profile,
+            } => vec![
+                "run-compliance-scan".to_string(),
+                format!("datastream={}", datastream),
+                format!("profile={}", profile),
+            ],
+            HelperOp::ScanNamespace { namespace } => {
+                vec!["scan-namespace".to_string(), namespace.to_string()]
+            }
+            HelperOp::VerifyListeners => vec!["verify-listeners".to_string()],
+            HelperOp::ReadFail2ban => vec!["read-fail2ban".to_string()],
+            HelperOp::Fail2banUnban { jail, ip } => vec![
+                "fail2ban-unban".to_string(),
+                format!("jail={}", jail),
+                format!("ip={}", ip),
+            ],
+            HelperOp::SetSelinuxMode { enforcing } => vec![
+                "set-selinux-mode".to_string(),
+                format!("enforcing={}", if *enforcing { "yes" } else { "no" }),
+            ],
+            HelperOp::DisableWakeOnLan { interface } => vec![
+                "disable-wake-on-lan".to_string(),
+                format!("interface={}", interface),
+            ],
+            HelperOp::EnableMacRandomization => vec!["enable-mac-randomization".to_string()],
+            HelperOp::HardenSsh => vec!["harden-ssh".to_string()],
+            HelperOp::HardenService { unit, settings } => {
+                let mut args = vec!["harden-service".to_string(), format!("unit={}", unit)];
+                args.extend(
+                    settings
+                        .iter()
+                        .map(|(directive, value)| format!("{}={}", directive.key(), value)),
+                );
+                args
+            }
+            HelperOp::TerminateConnections { flows } => {
+                let mut args = vec!["terminate-connections".to_string()];
+                args.extend(flows.iter().map(|flow| format!("flow={}", flow)));
+                args
+            }
+            HelperOp::InstallPolkitPolicy => vec!["install-polkit-policy".to_string()],
+        }
+    }
+
+    /// The polkit action that authorizes the operation.
+    pub fn polkit_action(&self) -> &'static str {
+        match self {
+            HelperOp::HardenSsh => "com.chrisdaggas.security-center.edit-sshd",
+            HelperOp::HardenService { .. } => "com.chrisdaggas.security-center.edit-services",
+            HelperOp::ApplySysctl { .. } | HelperOp::ConfigureCoredump { .. } => {
+                "com.chrisdaggas.security-center.edit-sysctl"
+            }
+            HelperOp::ConfigureJournald { .. } => "com.chrisdaggas.security-center.edit-logging",
+            HelperOp::RemoveCoreDumps { .. } => "com.chrisdaggas.security-center.clean-coredumps",
+            HelperOp::SetSelinuxMode { .. } => "com.chrisdaggas.security-center.edit-selinux",
+            HelperOp::DisableWakeOnLan { .. } | HelperOp::EnableMacRandomization => {
+                "com.chrisdaggas.security-center.edit-network"
+            }
+            HelperOp::TerminateConnections { .. } | HelperOp::Fail2banUnban { .. } => {
+                "com.chrisdaggas.security-center.manage-connections"
+            }
+            HelperOp::ReadRuleCounters
+            | HelperOp::RunComplianceScan { .. }
+            | HelperOp::ScanNamespace { .. }
+            | HelperOp::VerifyListeners
+            | HelperOp::ReadFail2ban => "com.chrisdaggas.security-center.read-system",
+            HelperOp::InstallPolkitPolicy => "com.chrisdaggas.security-center.install-policy",
+        }
+    }
+
+    /// Parse and validate helper arguments.
+    fn from_args(args: &[String]) -> Result<Self> {
+        let (op, params) = args
+            .split_first()
+            .ok_or_else(|| anyhow!("No helper operation given"))?;
+        match op.as_str() {
+            "configure-journald" => {
+                let mut storage = None;
+                let mut system_max_use = None;
+                for param in params {
+                    let (key, value) = param
+                        .split_once('=')
+                        .ok_or_else(|| anyhow!("Malformed argument: {}", param))?;
+                    match key {
+                        "storage" if value.is_empty() => storage = None,
+                        "storage" => {
+                            let v = validate_journal_storage(value)
+                                .ok_or_else(|| anyhow!("Invalid journal storage: {}", value))?;
+                            storage = Some(v.to_string());
+                        }
+                        "max-use" if value.is_empty() => system_max_use = None,
+                        "max-use" => {
+                            let v = validate_journal_size(value)
+                                .ok_or_else(|| anyhow!("Invalid journal size: {}", value))?;
+                            system_max_use = Some(v.to_string());
+                        }
+                        _ => bail!("Unknown argument: {}", key),
+                    }
+                }
+                Ok(HelperOp::ConfigureJournald {
+                    storage,
+                    system_max_use,
+                })
+            }
+            "configure-coredump" => match params {
+                [param] if param == "restrict=yes" => {
+                    Ok(HelperOp::ConfigureCoredump { restrict: true })
+                }
+                [param] if param == "restrict=no" => {
+                    Ok(HelperOp::ConfigureCoredump { restrict: false })
+                }
+                _ => bail!("Invalid core dump arguments"),
+            },
+            "remove-core-dumps" => {
+                let (secure, names) = params
+                    .split_first()
+                    .ok_or_else(|| anyhow!("remove-core-dumps needs secure=yes or secure=no"))?;
+                let secure = match secure.as_str() {
+                    "secure=yes" => true,
+                    "secure=no" => false,
+                    _ => bail!("remove-core-dumps needs secure=yes or secure=no"),
+                };
+                if names.is_empty() || names.len() > MAX_CORE_DUMPS {
+                    bail!(
+                        "remove-core-dumps needs between 1 and {} core dumps",
+                        MAX_CORE_DUMPS
+                    );
+                }
+                let names = names
+                    .iter()
+                    .map(|param| {
+                        param
+                            .strip_prefix("name=")
+                            .and_then(validate_coredump_name)
+                            .map(str::to_string)
+                            .ok_or_else(|| anyhow!("Invalid core dump: {}", param))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(HelperOp::RemoveCoreDumps { names, secure })
+            }
+            "apply-sysctl" => {
+                if params.is_empty() {
+                    bail!("apply-sysctl needs at least one setting");
+                }
+                let mut settings = Vec::new();
+                for param in params {
+                    let (key, value) = param
+                        .split_once('=')
+                        .ok_or_else(|| anyhow!("Malformed argument: {}", param))?;
+                    let key = validate_sysctl_key(key)
+                        .ok_or_else(|| anyhow!("Invalid sysctl key: {}", key))?;
+                    let value = validate_sysctl_value(value)
+                        .ok_or_else(|| anyhow!("Invalid sysctl value: {}", value))?;
+                    settings.push((key.to_string(), value.to_string()));
+                }
+                Ok(HelperOp::ApplySysctl { settings })
+            }
+            "read-rule-counters" if params.is_empty() => Ok(HelperOp::ReadRuleCounters),
+            "read-rule-counters" => bail!("read-rule-counters takes no arguments"),
+            "run-compliance-scan" => {
+                let mut datastream = None;
+                let mut profile = None;
+                for param in params {
+                    let (key, value) = param
+                        .split_once('=')
+                        .ok_or_else(|| anyhow!("Malformed argument: {}", param))?;
+                    match key {
+                        "datastream" => {
+                            let v = validate_datastream(value)
+                                .ok_or_else(|| anyhow!("Invalid data stream: {}", value))?;
+                            datastream = Some(v.to_string());
+                        }
+                        "profile" => {
+                            let v = validate_profile_id(value)
+                                .ok_or_else(|| anyhow!("Invalid profile: {}", value))?;
+                            profile = Some(v.to_string());
+                        }
+                        _ => bail!("Unknown argument: {}", key),
+                    }
+                }
+                match (datastream, profile) {
+                    (Some(datastream), Some(profile)) => Ok(HelperOp::RunComplianceScan {
+                        datastream,
+                        profile,
+                    }),
+                    _ => bail!("run-compliance-scan needs a data stream and a profile"),
+                }
+            }
+            "scan-namespace" => match params {
+                [param] => {
+                    let namespace = NamespaceRef::parse(param)
+                        .ok_or_else(|| anyhow!("Invalid namespace: {}", param))?;
+                    Ok(HelperOp::ScanNamespace { namespace })
+                }
+                _ => bail!("scan-namespace needs exactly one namespace"),
+            },
+            "set-selinux-mode" => match params {
+                [param] if param == "enforcing=yes" => {
+                    Ok(HelperOp::SetSelinuxMode { enforcing: true })
+                }
+                [param] if param == "enforcing=no" => {
+                    Ok(HelperOp::SetSelinuxMode { enforcing: false })
+                }
+                _ => bail!("Invalid SELinux mode arguments"),
+            },
+            "disable-wake-on-lan" => match params {
+                [param] => {
+                    let interface = param
+                        .strip_prefix("interface=")
+                        .and_then(validate_interface_name)
+                        .ok_or_else(|| anyhow!("Invalid interface: {}", param))?;
+                    Ok(HelperOp::DisableWakeOnLan {
+                        interface: interface.to_string(),
+                    })
+                }
+                _ => bail!("disable-wake-on-lan needs exactly one interface"),
+            },
+            "verify-listeners" if params.is_empty() => Ok(HelperOp::VerifyListeners),
+            "read-fail2ban" if params.is_empty() => Ok(HelperOp::ReadFail2ban),
+            "enable-mac-randomization" if params.is_empty() => Ok(HelperOp::EnableMacRandomization),
+            "harden-ssh" if params.is_empty() => Ok(HelperOp::HardenSsh),
+            "install-polkit-policy" if params.is_empty() => Ok(HelperOp::InstallPolkitPolicy),
+            "verify-listeners"
+            | "read-fail2ban"
+            | "enable-mac-randomization"
+            | "harden-ssh"
+            | "install-polkit-policy" => {
+                bail!("{} takes no arguments", op)
+            }
+            "fail2ban-unban" => {
+                let mut jail = None;
+                let mut ip = None;
+                for param in params {
+                    let (key, value) = param
+                        .split_once('=')
+                        .ok_or_else(|| anyhow!("Malformed argument: {}", param))?;
+                    match key {
+                        "jail" if jail.is_none() => {
+                            let v = validate_jail_name(value)
+                                .ok_or_else(|| anyhow!("Invalid jail: {}", value))?;
+                            jail = Some(v.to_string());
+                        }
+                        "ip" if ip.is_none() => {
+                            let v = value
+                                .parse::<IpAddr>()
+                                .map_err(|_| anyhow!("Invalid address: {}", value))?;
+                            ip = Some(v);
+                        }
+                        _ => bail!("Unknown argument: {}", key),
+                    }
+                }
+                match (jail, ip) {
+                    (Some(jail), Some(ip)) => Ok(HelperOp::Fail2banUnban { jail, ip }),
+                    _ => bail!("fail2ban-unban needs a jail and an address"),
+                }
+            }
+            "harden-service" => {
+                let (unit, params) = params
+                    .split_first()
+                    .ok_or_else(|| anyhow!("harden-service needs a unit"))?;
+                let unit = unit
+                    .strip_prefix("unit=")
+                    .ok_or_else(|| anyhow!("harden-service needs a unit"))?;
+                hardening_dropin_path(unit)?;
+                let mut settings: Vec<(HardeningDirective, String)> = Vec::new();
+                for param in params {
+                    let (key, value) = param
+                        .split_once('=')
+                        .ok_or_else(|| anyhow!("Malformed argument: {}", param))?;
+                    let setting = validate_hardening(key, value)
+                        .ok_or_else(|| anyhow!("Invalid hardening setting: {}", param))?;
+                    if settings
+                        .iter()
+                        .any(|(directive, _)| *directive == setting.0)
+                    {
+                        bail!("Duplicate setting: {}", key);
+                    }
+                    settings.push(setting);
+                }
+                Ok(HelperOp::HardenService {
+                    unit: unit.to_string(),
+                    settings,
+                })
+            }
+            "terminate-connections" => {
+                if params.is_empty() || params.len() > MAX_FLOWS {
+                    bail!(
+                        "terminate-connections needs between 1 and {} connections",
+                        MAX_FLOWS
+                    );
+                }
+                let flows = params
+                    .iter()
+                    .map(|param| {
+                        param
+                            .strip_prefix("flow=")
+                            .and_then(Flow::parse)
+                            .ok_or_else(|| anyhow!("Invalid connection: {}", param))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(HelperOp::TerminateConnections { flows })
+            }
+            other => bail!("Unknown helper operation: {}", other),
+        }
+    }
+
+    /// Perform the operation. Runs as root inside the helper process.
+    fn execute(&self) -> Result<String> {
+        match self {
+            HelperOp::ConfigureJournald {
+                storage,
+                system_max_use,
+            } => {
+                match journald_dropin(storage.as_deref(), system_max_use.as_deref()) {
+                    Some(contents) => {
+                        fs::create_dir_all(JOURNALD_DROPIN_DIR)?;
+                        write_atomic(Path::new(JOURNALD_DROPIN), &contents)?;
+                    }
+                    None => remove_if_exists(Path::new(JOURNALD_DROPIN))?,
+                }
+                let mut client = SystemdClient::new();
+                client.connect()?;
+                client.restart_service(JOURNALD_UNIT)?;
+                Ok("Journal settings applied".to_string())
+            }
+            HelperOp::ConfigureCoredump { restrict: true } => {
+                fs::create_dir_all(COREDUMP_DROPIN_DIR)?;
+                write_atomic(
+                    Path::new(COREDUMP_DROPIN),
+                    "# Managed by Security Center\n[Coredump]\nStorage=none\nProcessSizeMax=0\n",
+                )?;
+                write_atomic(
+                    Path::new(COREDUMP_SYSCTL),
+                    "# Managed by Security Center\nfs.suid_dumpable = 0\n",
+                )?;
+                // systemd-coredump reads its configuration on every crash;
+                // the sysctl is applied now instead of at the next boot
+                fs::write(SUID_DUMPABLE, "0")?;
+                Ok("Core dumps restricted".to_string())
+            }
+            HelperOp::ConfigureCoredump { restrict: false } => {
+                remove_if_exists(Path::new(COREDUMP_DROPIN))?;
+                remove_if_exists(Path::new(COREDUMP_SYSCTL))?;
+                Ok("Core dump restrictions removed".to_string())
+            }
+            HelperOp::RemoveCoreDumps { names, secure } => remove_core_dumps(names, *secure),
+            HelperOp::ApplySysctl { settings } => {
+                let mut contents = String::from("# Managed by Security Center\n");
+                for (key, value) in settings {
+                    contents.push_str(&format!("{} = {}\n", key, value));
+                }
+                write_atomic(Path::new(POLICY_SYSCTL), &contents)?;
+                // Unknown keys fail here rather than silently at the next boot
+                for (key, value) in settings {
+                    fs::write(sysctl_path(key), value)
+                        .with_context(|| format!("Cannot set {}", key))?;
+                }
+                Ok("sysctl values applied".to_string())
+            }
+            HelperOp::ReadRuleCounters => {
+                let output = Command::new(NFT)
+                    .args(["-j", "list", "table", "inet", FIREWALLD_TABLE])
+                    .output()
+                    .context("Failed to run nft")?;
+                if !output.status.success() {
+                    // Missing with the iptables backend or when firewalld is stopped
+                    bail!(
+                        "Cannot read the {} nftables table: {}",
+                        FIREWALLD_TABLE,
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    );
+                }
+                Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+            }
+            HelperOp::RunComplianceScan {
+                datastream,
+                profile,
+            } => {
+                let dir = Path::new(COMPLIANCE_RESULTS_DIR);
+                if dir.exists() {
+                    fs::remove_dir_all(dir)?;
+                }
+                fs::create_dir(dir)?;
+                fs::set_permissions(dir, fs::Permissions::from_mode(0o700))?;
+                let result = evaluate_profile(datastream, profile, dir);
+                fs::remove_dir_all(dir)?;
+                result
+            }
+            HelperOp::ScanNamespace { namespace } => scan_in_namespace(namespace),
+            HelperOp::VerifyListeners => verify_listeners(),
+            HelperOp::ReadFail2ban => read_jails_json(),
+            HelperOp::SetSelinuxMode { enforcing } => write_selinux_enforcing(*enforcing),
+            HelperOp::Fail2banUnban { jail, ip } => {
+                Fail2banClient::connect()?.unban(jail, *ip)?;
+                Ok(format!("Unbanned {} in {}", ip, jail))
+            }
+            HelperOp::DisableWakeOnLan { interface } => {
+                clear_wake_on_lan(interface)?;
+                Ok(format!("Wake-on-LAN disabled on {}", interface))
+            }
+            HelperOp::EnableMacRandomization => {
+                let path = Path::new(MAC_DROPIN);
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                write_atomic(path, MAC_DROPIN_CONTENTS)?;
+                // Takes effect the next time each connection comes up
+                match reload_network_manager_conf() {
+                    Ok(()) => Ok("MAC address randomization enabled".to_string()),
+                    Err(_) => Ok(
+                        "MAC address randomization enabled from the next NetworkManager start"
+                            .to_string(),
+                    ),
+                }
+            }
+            HelperOp::HardenSsh => {
+                let path = Path::new(SSH_DROPIN);
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                write_atomic(path, SSH_DROPIN_CONTENTS)?;
+                let check = Command::new(SSHD)
+                    .arg("-t")
+                    .output()
+                    .context("Failed to run sshd")?;
+                if !check.status.success() {
+                    remove_if_exists(path)?;
+                    bail!(
+                        "sshd rejected the configuration: {}",
+                        String::from_utf8_lossy(&check.stderr).trim()
+                    );
+                }
+                let mut client = SystemdClient::new();
+                client.connect()?;
+                for unit in SSH_UNITS {
+                    let running = client
+                        .get_service_info(unit)
+                        .is_ok_and(|info| info.state == ServiceState::Running);
+                    if running {
+                        client.restart_service(unit)?;
+                    }
+                }
+                Ok("SSH server hardened".to_string())
+            }
+            HelperOp::HardenService { unit, settings } => {
+                let path = hardening_dropin_path(unit)?;
+                let path = Path::new(&path);
+                match hardening_dropin(settings) {
+                    Some(contents) => {
+                        if let Some(dir) = path.parent() {
+                            fs::create_dir_all(dir)?;
+                        }
+                        write_atomic(path, &contents)?;
+                    }
+                    None => remove_if_exists(path)?,
+                }
+                let mut client = SystemdClient::new();
+                client.connect()?;
+                client.daemon_reload()?;
+                let running = client
+                    .get_service_info(unit)
+                    .is_ok_and(|info| info.state == ServiceState::Running);
+                if running {
+                    client.restart_service(unit)?;
+                    Ok(format!(
+                        "Hardening of {} applied and service restarted",
+                        unit
+                    ))
+                } else {
+                    Ok(format!("Hardening of {} applied from the next start", unit))
+                }
+            }
+            HelperOp::TerminateConnections { flows } => {
+                let mut tracked = 0;
+                for flow in flows {
+                    if terminate_flow(flow)? {
+                        tracked += 1;
+                    }
+                }
+                Ok(format!(
+                    "Terminated {} connections ({} tracked by the firewall)",
+                    flows.len(),
+                    tracked
+                ))
+            }
+            HelperOp::InstallPolkitPolicy => {
+                // A helper a user could replace must not be trusted to
+                // install a policy that lets pkexec start anything
+                let exe = std::env::current_exe()?;
+                let metadata = fs::metadata(&exe)?;
+                if !root_owned(metadata.uid(), metadata.mode()) {
+                    bail!(
+                        "{} must be owned by root and writable by nobody else; \
+                         install the package instead",
+                        exe.display()
+                    );
+                }
+                let path = Path::new(POLKIT_POLICY_PATH);
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                write_atomic(path, POLKIT_POLICY)?;
+                Ok(format!("Installed {}", POLKIT_POLICY_PATH))
+            }
+        }
+    }
+}
+
+/// Whether a file with owner `uid` and `mode` is owned by root and writable
+/// by no one else.
+fn root_owned(uid: u32, mode: u32) -> bool {
+    uid == 0 && mode & 0o022 == 0
+}
+
+/// Run `op` as root through pkexec, returning the helper's message.
+pub fn run_privileged(op: &HelperOp) -> Result<String> {
+    let exe = helper_binary()?;
+    let output = Command::new("pkexec")
+        .arg(exe)
+        .arg(HELPER_FLAG)
+        .args(op.to_args())
+        .output()
+        .context("Failed to run pkexec")?;
+
+    match output.status.code() {
+        Some(0) => Ok(String::from_utf8_lossy(&output.stdout).trim().to_string()),
+        Some(PKEXEC_CANCELLED) => Err(anyhow!("Authorization was cancelled")),
+        Some(PKEXEC_NOT_AUTHORIZED) => Err(anyhow!("Not authorized to change system settings")),
+        _ => {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            if stderr.is_empty() {
+                Err(anyhow!("Privileged helper failed"))
+            } else {
+                Err(anyhow!(stderr))
+            }
+        }
+    }
+}
+
+/// The helper program: the installed one, or the one built next to the
+/// running binary.
+fn helper_binary() -> Result<std::path::PathBuf> {
+    let installed = Path::new(HELPER_PATH);
+    if installed.exists() {
+        return Ok(installed.to_path_buf());
+    }
+    let exe = std::env::current_exe().context("Cannot locate the application binary")?;
+    let built = exe.with_file_name(HELPER_NAME);
+    if built.exists() {
+        Ok(built)
+    } else {
+        bail!("The privileged helper {} is not installed", HELPER_PATH)
+    }
+}
+
+/// Entry point of the helper program, with the arguments after
+/// [`HELPER_FLAG`]; returns the process exit code.
+pub fn run_helper(args: &[String]) -> i32 {
+    let result = running_as_root()
+        .then_some(())
+        .ok_or_else(|| anyhow!("The helper must be started through pkexec"))
+        .and_then(|()| HelperOp::from_args(args))
+        .and_then(|op| {
+            authorize(&op)?;
+            op.execute()
+        });
+    match result {
+        Ok(message) => {
+            println!("{}", message);
+            0
+        }
+        Err(e) => {
+            eprintln!("{:#}", e);
+            1
+        }
+    }
+}
+
+/// Ask polkit whether the user who ran pkexec may perform `op`, letting it
+/// prompt for a password.
+///
+/// Fails closed: a helper not started through pkexec, or one whose actions
+/// polkit does not know because the policy is missing, performs nothing but
+/// the installation of the policy, which pkexec authenticated an
+/// administrator for.
+fn authorize(op: &HelperOp) -> Result<()> {
+    let uid = std::env::var("PKEXEC_UID")
+        .map_err(|_| anyhow!("The helper must be started through pkexec"))?;
+    let uid: i32 = uid.parse().context("Invalid PKEXEC_UID")?;
+    if !Path::new(POLKIT_POLICY_PATH).exists() {
+        if *op == HelperOp::InstallPolkitPolicy {
+            return Ok(());
+        }
+        bail!(
+            "The polkit policy is not installed; run security-center {} first",
+            INSTALL_POLICY_FLAG
+        );
+    }
+
+    // pkexec replaced itself with the helper, so the parent is the process
+    // that ran pkexec
+    check_process(
+        std::os::unix::process::parent_id(),
+        uid,
+        op.polkit_action(),
+        POLKIT_ALLOW_INTERACTION,
+    )
+}
+
+/// Whether polkit lets this process run `op` through the helper without
+/// asking for a password, for work done unattended.
+pub fn authorized_unattended(op: &HelperOp) -> bool {
+    [HELPER_ACTION, op.polkit_action()]
+        .iter()
+        .all(|action| check_authorization(action, false).is_ok())
+}
+
+/// Ask polkit whether this process may perform `action`. With
+/// `interactive` polkit may ask for a password; without, an action that
+/// needs one is refused with an error saying so.
+pub fn check_authorization(action: &str, interactive: bool) -> Result<()> {
+    let uid = unsafe { libc::getuid() } as i32;
+    let flags = if interactive {
+        POLKIT_ALLOW_INTERACTION
+    } else {
+        POLKIT_NO_INTERACTION
+    };
+    check_process(std::process::id(), uid, action, flags)
+}
+
+/// Ask polkit whether process `pid` of user `uid` may perform `action`.
+fn check_process(pid: u32, uid: i32, action: &str, flags: u32) -> Result<()> {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid))
+        .context("Cannot read the calling process")?;
+    let start_time =
+        process_start_time(&stat).ok_or_else(|| anyhow!("Cannot read the calling process"))?;
+
+    let subject = (
+        "unix-process",
+        HashMap::from([
+            ("pid", Value::U32(pid)),
+            ("start-time", Value::U64(start_time)),
+            ("uid", Value::I32(uid)),
+        ]),
+    );
+    let details: HashMap<&str, &str> = HashMap::new();
+    let connection = zbus::blocking::Connection::system()?;
+    let reply = connection.call_method(
+        Some(POLKIT_BUS),
+        POLKIT_PATH,
+        Some(POLKIT_AUTHORITY),
+        "CheckAuthorization",
+        &(subject, action, details, flags, ""),
+    )?;
+    let (authorized, challenge, result): (bool, bool, HashMap<String, String>) =
+        reply.body().deserialize()?;
+    if authorized {
+        Ok(())
+    } else if result.get("polkit.dismissed").map(String::as_str) == Some("true") {
+        bail!("Authorization was cancelled")
+    } else if challenge {
+        bail!("Waiting for authorization")
+    } else {
+        bail!("Not authorized for {}", action)
+    }
+}
+
+/// Start time of a process in clock ticks since boot, field 22 of
+/// `/proc/<pid>/stat`. Fields are counted after the command name, which is
+/// in parentheses and may itself contain spaces.
+fn process_start_time(stat: &str) -> Option<u64> {
+    let (_, fields) = stat.rsplit_once(')')?;
+    fields.split_whitespace().nth(19)?.parse().ok()
+}
+
+/// `/proc/self` is owned by the effective user of the process.
+fn running_as_root() -> bool {
+    fs::metadata("/proc/self").is_ok_and(|m| m.uid() == 0)
+}
+
+/// Whether the process runs as root or was started through pkexec. Only the
+/// helper program may; the application and the monitor refuse to.
+pub fn running_elevated() -> bool {
+    running_as_root() || std::env::var_os("PKEXEC_UID").is_some()
+}
+
+/// Contents of the journald drop-in, or `None` when nothing is overridden.
+fn journald_dropin(storage: Option<&str>, system_max_use: Option<&str>) -> Option<String> {
+    if storage.is_none() && system_max_use.is_none() {
+        return None;
+    }
+    let mut contents = String::from("# Managed by Security Center\n[Journal]\n");
+    if let Some(storage) = storage {
+        contents.push_str(&format!("Storage={}\n", storage));
+    }
+    if let Some(size) = system_max_use {
+        contents.push_str(&format!("SystemMaxUse={}\n", size));
+    }
+    Some(contents)
+}
+
+/// Ask NetworkManager to re-read its configuration files.
+fn reload_network_manager_conf() -> Result<()> {
+    let connection = zbus::blocking::Connection::system()?;
+    connection.call_method(
+        Some(NM_BUS),
+        NM_PATH,
+        Some(NM_BUS),
+        "Reload",
+        &(NM_RELOAD_CONF,),
+    )?;
+    Ok(())
+}
+
+/// Write a world-readable configuration file via a temporary file and rename.
+fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let tmp = path.with_extension("tmp");
+    {
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+    }
+    fs::set_permissions(&tmp, fs::Permissions::from_mode(0o644))?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_journald_args_round_trip() {
+        let op = HelperOp::ConfigureJournald {
+            storage: Some("persistent".to_string()),
+            system_max_use: None,
+        };
+        assert_eq!(HelperOp::from_args(&op.to_args()).unwrap(), op);
+        let op = HelperOp::ConfigureCoredump { restrict: true };
+        assert_eq!(HelperOp::from_args(&op.to_args()).unwrap(), op);
+        let op = HelperOp::RemoveCoreDumps {
+            names: vec![
+                "core.firefox.1000.abc.1234.1700000000000000.zst".to_string(),
+                "core.bash.1000.abc.99.1700000000000001".to_string(),
+            ],
+            secure: true,
+        };
+        assert_eq!(HelperOp::from_args(&op.to_args()).unwrap(), op);
+        let op = HelperOp::ReadRuleCounters;
+        assert_eq!(HelperOp::from_args(&op.to_args()).unwrap(), op);
+        let op = HelperOp::ApplySysctl {
+            settings: vec![
+                ("net.ipv4.ip_forward".to_string(), "0".to_string()),
+                (
+                    "net.ipv4.tcp_rmem".to_string(),
+                    "4096 131072 6291456".to_string(),
+                ),
+            ],
+        };
+        assert_eq!(HelperOp::from_args(&op.to_args()).unwrap(), op);
+        let op = HelperOp::RunComplianceScan {
+            datastream: "ssg-fedora-ds.xml".to_string(),
+            profile: "xccdf_org.ssgproject.content_profile_cis".to_string(),
+        };
+        assert_eq!(HelperOp::from_args(&op.to_args()).unwrap(), op);
+        let op = HelperOp::ScanNamespace {
+            namespace: NamespaceRef::Named("vpn".to_string()),
+        };
+        assert_eq!(HelperOp::from_args(&op.to_args()).unwrap(), op);
+        let op = HelperOp::VerifyListeners;
+        assert_eq!(HelperOp::from_args(&op.to_args()).unwrap(), op);
+        let op = HelperOp::ReadFail2ban;
+        assert_eq!(HelperOp::from_args(&op.to_args()).unwrap(), op);
+        let op = HelperOp::SetSelinuxMode { enforcing: false };
+        assert_eq!(HelperOp::from_args(&op.to_args()).unwrap(), op);
+        let op = HelperOp::Fail2banUnban {
+            jail: "sshd".to_string(),
+            ip: "2001:db8::7".parse().unwrap(),
+        };
+        assert_eq!(HelperOp::from_args(&op.to_args()).unwrap(), op);
+        let op = HelperOp::DisableWakeOnLan {
+            interface: "enp3s0".to_string(),
+        };
+        assert_eq!(HelperOp::from_args(&op.to_args()).unwrap(), op);
+        let op = HelperOp::EnableMacRandomization;
+        assert_eq!(HelperOp::from_args(&op.to_args()).unwrap(), op);
+        let op = HelperOp::HardenSsh;
+        assert_eq!(HelperOp::from_args(&op.to_args()).unwrap(), op);
+        let op = HelperOp::HardenService {
+            unit: "nginx.service".to_string(),
+            settings: vec![
+                (HardeningDirective::NoNewPrivileges, "yes".to_string()),
+                (HardeningDirective::ProtectSystem, "strict".to_string()),
+            ],
+        };
+        assert_eq!(HelperOp::from_args(&op.to_args()).unwrap(), op);
+        let op = HelperOp::HardenService {
+            unit: "nginx.service".to_string(),
+            settings: Vec::new(),
+        };
+        assert_eq!(HelperOp::from_args(&op.to_args()).unwrap(), op);
+        let op = HelperOp::InstallPolkitPolicy;
+        assert_eq!(HelperOp::from_args(&op.to_args()).unwrap(), op);
+        let op = HelperOp::TerminateConnections {
+            flows: vec![
+                Flow::parse("tcp,192.168.1.5:51000,203.0.113.7:443").unwrap(),
+                Flow::parse("udp,[2001:db8::2]:5353,[2001:db8::7]:53").unwrap(),
+            ],
+        };
+        assert_eq!(HelperOp::from_args(&op.to_args()).unwrap(), op);
+    }
+
+    #[test]
+    fn test_from_args_rejects_invalid() {
+        assert!(HelperOp::from_args(&args(&[])).is_err());
+        assert!(HelperOp::from_args(&args(&["rm-rf"])).is_err());
+        assert!(HelperOp::from_args(&args(&["configure-journald", "storage=none"])).is_err());
+        assert!(
+            HelperOp::from_args(&args(&["configure-journald", "max-use=1G\nStorage=none"]))
+                .is_err()
+        );
+        assert!(HelperOp::from_args(&args(&["configure-journald", "user=root"])).is_err());
+        assert!(HelperOp::from_args(&args(&["configure-journald", "storage"])).is_err());
+        assert!(HelperOp::from_args(&args(&["configure-coredump"])).is_err());
+        assert!(HelperOp::from_args(&args(&["configure-coredump", "restrict=1"])).is_err());
+        assert!(HelperOp::from_args(&args(&["remove-core-dumps", "secure=yes"])).is_err());
+        assert!(HelperOp::from_args(&args(&["remove-core-dumps", "name=core.x"])).is_err());
+        assert!(HelperOp::from_args(&args(&[
+            "remove-core-dumps",
+            "secure=no",
+            "name=core.x/../../../etc/shadow"
+        ]))
+        .is_err());
+        assert!(
+            HelperOp::from_args(&args(&["remove-core-dumps", "secure=no", "name=passwd"])).is_err()
+        );
+        assert!(HelperOp::from_args(&args(&["remove-core-dumps", "secure=no", "core.x"])).is_err());
+        assert!(HelperOp::from_args(&args(&["read-rule-counters", "table=filter"])).is_err());
+        assert!(HelperOp::from_args(&args(&["apply-sysctl"])).is_err());
+        assert!(HelperOp::from_args(&args(&["apply-sysctl", "../shadow=1"])).is_err());
+        assert!(HelperOp::from_args(&args(&["apply-sysctl", "kernel.x=1\nkernel.y=2"])).is_err());
+        assert!(HelperOp::from_args(&args(&[
+            "run-compliance-scan",
+            "datastream=ssg-fedora-ds.xml"
+        ]))
+        .is_err());
+        assert!(HelperOp::from_args(&args(&[
+            "run-compliance-scan",
+            "datastream=/etc/shadow",
+            "profile=xccdf_org.ssgproject.content_profile_cis"
+        ]))
+        .is_err());
+        assert!(HelperOp::from_args(&args(&[
+            "run-compliance-scan",
+            "datastream=ssg-fedora-ds.xml",
+            "profile=xccdf_cis --remediate"
+        ]))
+        .is_err());
+        assert!(HelperOp::from_args(&args(&["scan-namespace"])).is_err());
+        assert!(HelperOp::from_args(&args(&["scan-namespace", "name=../../proc/1"])).is_err());
+        assert!(HelperOp::from_args(&args(&["verify-listeners", "pid=1"])).is_err());
+        assert!(HelperOp::from_args(&args(&["fail2ban-unban", "jail=sshd"])).is_err());
+        assert!(HelperOp::from_args(&args(&["set-selinux-mode"])).is_err());
+        assert!(HelperOp::from_args(&args(&["set-selinux-mode", "enforcing=0"])).is_err());
+        assert!(
+            HelperOp::from_args(&args(&["fail2ban-unban", "jail=--all", "ip=203.0.113.7"]))
+                .is_err()
+        );
+        assert!(HelperOp::from_args(&args(&[
+            "fail2ban-unban",
+            "jail=sshd",
+            "ip=203.0.113.7 --all"
+        ]))
+        .is_err());
+        assert!(HelperOp::from_args(&args(&["disable-wake-on-lan"])).is_err());
+        assert!(HelperOp::from_args(&args(&["disable-wake-on-lan", "interface=../eth0"])).is_err());
+        assert!(HelperOp::from_args(&args(&[
+            "disable-wake-on-lan",
+            "interface=eth0",
+            "interface=eth1"
+        ]))
+        .is_err());
+        assert!(HelperOp::from_args(&args(&["harden-ssh", "PermitRootLogin=yes"])).is_err());
+        assert!(HelperOp::from_args(&args(&["harden-service"])).is_err());
+        assert!(HelperOp::from_args(&args(&[
+            "harden-service",
+            "unit=../../etc/passwd.service",
+            "NoNewPrivileges=yes"
+        ]))
+        .is_err());
+        assert!(HelperOp::from_args(&args(&[
+            "harden-service",
+            "unit=nginx.service",
+            "ExecStartPre=/bin/sh"
+        ]))
+        .is_err());
+        assert!(HelperOp::from_args(&args(&[
+            "harden-service",
+            "unit=nginx.service",
+            "PrivateDevices=yes",
+            "PrivateDevices=yes"
+        ]))
+        .is_err());
+        assert!(HelperOp::from_args(&args(&["install-polkit-policy", "path=/tmp"])).is_err());
+        assert!(HelperOp::from_args(&args(&["terminate-connections"])).is_err());
+        assert!(HelperOp::from_args(&args(&[
+            "terminate-connections",
+            "flow=tcp,10.0.0.1:1,10.0.0.2:2 -D"
+        ]))
+        .is_err());
+        assert!(HelperOp::from_args(&args(&[
+            "terminate-connections",
+            "tcp,10.0.0.1:1,10.0.0.2:2"
+        ]))
+        .is_err());
+    }
+
+    #[test]
+    fn test_journald_dropin() {
+        assert_eq!(journald_dropin(None, None), None);
+        let contents = journald_dropin(Some("persistent"), Some("1G")).unwrap();
+        assert!(contents.contains("[Journal]\nStorage=persistent\nSystemMaxUse=1G\n"));
+    }
+
+    #[test]
+    fn test_polkit_actions_are_declared() {
+        let ops = [
+            HelperOp::ConfigureJournald {
+                storage: None,
+                system_max_use: None,
+            },
+            HelperOp::ConfigureCoredump { restrict: true },
+            HelperOp::RemoveCoreDumps {
+                names: vec![],
+                secure: false,
+            },
+            HelperOp::ApplySysctl { settings: vec![] },
+            HelperOp::ReadRuleCounters,
+            HelperOp::DisableWakeOnLan {
+                interface: "eth0".to_string(),
+            },
+            HelperOp::EnableMacRandomization,
+            HelperOp::HardenSsh,
+            HelperOp::HardenService {
+                unit: "nginx.service".to_string(),
+                settings: vec![],
+            },
+            HelperOp::TerminateConnections { flows: vec![] },
+            HelperOp::ReadFail2ban,
+            HelperOp::SetSelinuxMode { enforcing: true },
+            HelperOp::InstallPolkitPolicy,
+        ];
+        for op in &ops {
+            let declared = format!("<action id=\"{}\">", op.polkit_action());
+            assert!(POLKIT_POLICY.contains(&declared), "{:?}", op);
+        }
+        assert_eq!(
+            HelperOp::HardenSsh.polkit_action(),
+            "com.chrisdaggas.security-center.edit-sshd"
+        );
+    }
+
+    #[test]
+    fn test_polkit_policy_helper() {
+        // pkexec may only start the installed helper, never without a password
+        assert!(POLKIT_POLICY.contains(&format!(
+            "<annotate key=\"org.freedesktop.policykit.exec.path\">{}</annotate>",
+            HELPER_PATH
+        )));
+        assert_eq!(POLKIT_POLICY.matches("policykit.exec.path").count(), 1);
+        assert!(!POLKIT_POLICY.contains("<allow_active>yes</allow_active>"));
+        assert!(POLKIT_POLICY.contains(&format!("<action id=\"{}\">", MANAGE_BLOCKLIST_ACTION)));
+
+        assert!(root_owned(0, 0o100755));
+        assert!(!root_owned(1000, 0o100755));
+        assert!(!root_owned(0, 0o100775));
+        assert!(!root_owned(0, 0o100757));
+    }
+
+    #[test]
+    fn test_process_start_time() {
+        let stat = "4242 (my (odd) app) S 1 4242 4242 0 -1 4194560 1200 0 0 0 \
+                    10 5 0 0 20 0 3 0 987654 123456789 2500";
+        assert_eq!(process_start_time(stat), Some(987654));
+        assert_eq!(process_start_time("4242 (app) S 1"), None);
+    }
+}
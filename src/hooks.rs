@@ -0,0 +1,381 @@
+// Security Center - Script Hooks
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! External script hooks, an extension point for power users.
+//!
+//! Every executable in `~/.config/security-center/hooks/` runs, in name
+//! order, when a [`HookEvent`] happens: before and after a firewall change
+//! and when a scheduled security scan finishes. The event arrives as one
+//! JSON object on standard input, for example
+//! `{"event":"post-rule-change","change":"ports"}`. A hook may print a JSON
+//! object with warnings to standard output:
+//!
+//! ```json
+//! {"warnings": [{"title": "Port 8080 open", "severity": "medium", "detail": "..."}]}
+//! ```
+//!
+//! The Incidents page is where this application tracks problems until
+//! they are resolved, so each warning opens an incident there, unless the
+//! same hook already reported an unresolved one with the same title.
+//! Hooks must be owned by the user and writable by nobody else; others are
+//! skipped. A hook that runs longer than [`HOOK_TIMEOUT`] is killed, and
+//! hooks do not run in safe mode.
+
+use std::fs;
+use std::io::{Read, Write};
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::admin::Severity;
+use crate::incidents::{self, EvidenceKind, Incident, IncidentStatus};
+
+/// Longest a hook may run.
+pub const HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+/// Output past this is ignored.
+const MAX_HOOK_OUTPUT: u64 = 64 * 1024;
+/// Warnings taken from one run of a hook.
+const MAX_WARNINGS: usize = 16;
+const MAX_TITLE_LEN: usize = 200;
+/// Timeline entry naming the hook behind an incident, before its name.
+const REPORTED_BY: &str = "Reported by hook ";
+/// Attempts at starting a hook whose file is still open for writing.
+const SPAWN_ATTEMPTS: u32 = 5;
+
+/// Directory holding the hooks.
+pub fn hooks_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("security-center")
+        .join("hooks")
+}
+
+/// What happened, as sent to the hooks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum HookEvent {
+    /// A firewall change is about to be made. `change` is the id of the
+    /// admin operation, e.g. `firewall_set_default_zone`.
+    PreRuleChange { change: String },
+    /// A firewall change was made: `ports`, `services` or `zones` for edits
+    /// on the pages, or the id of an admin operation.
+    PostRuleChange { change: String },
+    /// A scheduled security scan finished. Ports are `port/protocol`,
+    /// findings are keys such as `port:8080/tcp` or `check:ssh.exposed`.
+    ScanFinished {
+        exposed_ports: Vec<String>,
+        failed_checks: Vec<String>,
+        new_findings: Vec<String>,
+    },
+}
+
+/// A warning printed by a hook.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct HookWarning {
+    pub title: String,
+    /// `info`, `low`, `medium` or `high`; anything else counts as medium.
+    #[serde(default)]
+    pub severity: Option<String>,
+    #[serde(default)]
+    pub detail: String,
+}
+
+impl HookWarning {
+    pub fn severity(&self) -> Severity {
+        match self.severity.as_deref() {
+            Some("info") => Severity::Info,
+            Some("low") => Severity::Low,
+            Some("high") => Severity::High,
+            _ => Severity::Medium,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct HookOutput {
+    #[serde(default)]
+    warnings: Vec<HookWarning>,
+}
+
+/// The warnings in a hook's output. Empty output has none.
+pub fn parse_hook_output(output: &str) -> Result<Vec<HookWarning>> {
+    if output.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let output: HookOutput = serde_json::from_str(output).context("Invalid hook output")?;
+    Ok(output
+        .warnings
+        .into_iter()
+        .filter(|w| !w.title.trim().is_empty())
+        .take(MAX_WARNINGS)
+        .map(|mut w| {
+            w.title = w.title.trim().chars().take(MAX_TITLE_LEN).collect();
+            w
+        })
+        .collect())
+}
+
+/// Whether `path` may run as a hook: an executable regular file owned by
+/// `uid` that nobody else can write to.
+fn is_runnable_hook(path: &Path, uid: u32) -> bool {
+    let Ok(metadata) = fs::metadata(path) else {
+        return false;
+    };
+    let mode = metadata.permissions().mode();
+    metadata.is_file() && mode & 0o111 != 0 && mode & 0o022 == 0 && metadata.uid() == uid
+}
+
+/// The hooks in `dir`, in name order. Hidden files are skipped.
+pub fn list_hooks(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    // SAFETY: getuid cannot fail
+    let uid = unsafe { libc::getuid() };
+    let mut hooks: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            !path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+        })
+        .filter(|path| {
+            let runnable = is_runnable_hook(path, uid);
+            if !runnable && path.is_file() {
+                warn!(
+                    "Skipping hook {}: not executable, or writable by others",
+                    path.display()
+                );
+            }
+            runnable
+        })
+        .collect();
+    hooks.sort();
+    hooks
+}
+
+/// Run one hook with `input` on standard input and return what it printed.
+fn run_hook(path: &Path, input: &str) -> Result<String> {
+    let mut command = Command::new(path);
+    command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+    // A hook just written may still be open in a process forked meanwhile,
+    // which makes exec fail with ETXTBSY until that process execs in turn
+    let mut attempt = 1;
+    let mut child = loop {
+        match command.spawn() {
+            Err(e) if e.raw_os_error() == Some(libc::ETXTBSY) && attempt < SPAWN_ATTEMPTS => {
+                attempt += 1;
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            result => break result.with_context(|| format!("Cannot start {}", path.display()))?,
+        }
+    };
+
+    // Events fit in the pipe's buffer, so this does not wait for the hook;
+    // one that exits without reading them is not an error
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(input.as_bytes());
+    }
+    let stdout = child.stdout.take();
+    let reader = std::thread::spawn(move || {
+        let mut output = Vec::new();
+        if let Some(stdout) = stdout {
+            let _ = stdout.take(MAX_HOOK_OUTPUT).read_to_end(&mut output);
+        }
+        output
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() > HOOK_TIMEOUT {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(anyhow!(
+                "{} did not finish within {} seconds",
+                path.display(),
+                HOOK_TIMEOUT.as_secs()
+            ));
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+    let output = reader.join().unwrap_or_default();
+    if !status.success() {
+        return Err(anyhow!("{} failed: {}", path.display(), status));
+    }
+    Ok(String::from_utf8_lossy(&output).into_owned())
+}
+
+/// Run every hook in `dir` for `event`, returning the warnings printed,
+/// with the name of the hook that printed them. Failing hooks are logged.
+pub fn run_hooks_in(dir: &Path, event: &HookEvent) -> Vec<(String, HookWarning)> {
+    let hooks = list_hooks(dir);
+    if hooks.is_empty() {
+        return Vec::new();
+    }
+    let input = match serde_json::to_string(event) {
+        Ok(input) => input,
+        Err(e) => {
+            warn!("Cannot encode hook event: {}", e);
+            return Vec::new();
+        }
+    };
+    let mut warnings = Vec::new();
+    for hook in hooks {
+        let name = hook
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        match run_hook(&hook, &input).and_then(|output| parse_hook_output(&output)) {
+            Ok(found) => warnings.extend(found.into_iter().map(|w| (name.clone(), w))),
+            Err(e) => warn!("Hook {}: {:#}", name, e),
+        }
+    }
+    warnings
+}
+
+/// `(hook, title)` of each unresolved incident a hook opened.
+fn open_hook_warnings(incidents: &[Incident]) -> Vec<(String, String)> {
+    incidents
+        .iter()
+        .filter(|i| i.status != IncidentStatus::Resolved)
+        .filter_map(|i| {
+            let hook = i
+                .timeline
+                .iter()
+                .find_map(|entry| entry.message.strip_prefix(REPORTED_BY))?;
+            Some((hook.to_string(), i.title.clone()))
+        })
+        .collect()
+}
+
+/// Run the hooks for `event` and open an incident for each new warning.
+/// Blocks until every hook finished, so call it off the main thread.
+pub fn dispatch(event: &HookEvent) {
+    if crate::safe_mode::is_enabled() {
+        return;
+    }
+    let warnings = run_hooks_in(&hooks_dir(), event);
+    if warnings.is_empty() {
+        return;
+    }
+    let mut open = open_hook_warnings(&incidents::load_incidents());
+    for (hook, warning) in warnings {
+        let key = (hook.clone(), warning.title.clone());
+        if open.contains(&key) {
+            continue;
+        }
+        let mut incident = Incident::new(&warning.title, warning.severity());
+        incident.record(format!("{}{}", REPORTED_BY, hook), true);
+        if !warning.detail.trim().is_empty() {
+            incident.attach(EvidenceKind::Other, &hook, &warning.detail);
+        }
+        match incidents::add_incident(incident) {
+            Ok(_) => info!("Hook {} reported: {}", hook, warning.title),
+            Err(e) => warn!("Cannot record the warning of hook {}: {:#}", hook, e),
+        }
+        open.push(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hook_event_json() {
+        let event = HookEvent::PostRuleChange {
+            change: "ports".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"event":"post-rule-change","change":"ports"}"#
+        );
+    }
+
+    #[test]
+    fn test_parse_hook_output() {
+        assert!(parse_hook_output("  \n").unwrap().is_empty());
+        assert!(parse_hook_output("not json").is_err());
+
+        let warnings = parse_hook_output(
+            r#"{"warnings":[{"title":" Port 8080 open ","severity":"high"},{"title":""},
+                {"title":"Backup overdue","detail":"Last run 9 days ago"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].title, "Port 8080 open");
+        assert_eq!(warnings[0].severity(), Severity::High);
+        assert_eq!(warnings[1].severity(), Severity::Medium);
+        assert_eq!(warnings[1].detail, "Last run 9 days ago");
+    }
+
+    #[test]
+    fn test_open_hook_warnings() {
+        let mut reported = Incident::new("Changed", Severity::Medium);
+        reported.record(format!("{}10-warn", REPORTED_BY), true);
+        let mut resolved = reported.clone();
+        resolved.title = "Old".to_string();
+        resolved.set_status(IncidentStatus::Resolved);
+        // Opened by hand with the same title as a hook warning
+        let manual = Incident::new("Changed", Severity::High);
+
+        assert_eq!(
+            open_hook_warnings(&[reported, resolved, manual]),
+            vec![("10-warn".to_string(), "Changed".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_run_hooks() {
+        let dir =
+            std::env::temp_dir().join(format!("security-center-hooks-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let write_hook = |name: &str, script: &str, mode: u32| {
+            let path = dir.join(name);
+            fs::write(&path, script).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(mode)).unwrap();
+        };
+        write_hook(
+            "10-warn",
+            "#!/bin/sh\ngrep -q post-rule-change && echo '{\"warnings\":[{\"title\":\"Changed\"}]}'\n",
+            0o700,
+        );
+        write_hook("20-fail", "#!/bin/sh\nexit 1\n", 0o700);
+        write_hook(
+            "30-shared",
+            "#!/bin/sh\necho '{\"warnings\":[{\"title\":\"No\"}]}'\n",
+            0o777,
+        );
+        write_hook("40-plain", "not a program\n", 0o600);
+
+        let hooks = list_hooks(&dir);
+        assert_eq!(hooks, vec![dir.join("10-warn"), dir.join("20-fail")]);
+
+        let event = HookEvent::PostRuleChange {
+            change: "zones".to_string(),
+        };
+        let warnings = run_hooks_in(&dir, &event);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].0, "10-warn");
+        assert_eq!(warnings[0].1.title, "Changed");
+
+        let event = HookEvent::PreRuleChange {
+            change: "firewall_reload".to_string(),
+        };
+        assert!(run_hooks_in(&dir, &event).is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -2,13 +2,36 @@
 // Copyright (C) 2026 Christos Daggas
 // SPDX-License-Identifier: MIT
 
-//! Thin gettext wrapper so UI code can call `gettext("...")` uniformly.
+//! Thin gettext wrapper so UI code can call `gettext("...")` uniformly,
+//! and locale-aware formatting of numbers, byte sizes and dates.
 //!
 //! Translation catalogs are installed as `<prefix>/share/locale/<lang>/
 //! LC_MESSAGES/security-center.mo`. When running uninstalled, set
 //! `TEXTDOMAINDIR` to a directory with that layout to test translations.
+//!
+//! Rust's formatting ignores the locale, so numbers shown to the user go
+//! through [`format_number`], [`format_decimal`], [`format_percent`] and
+//! [`format_bytes`], which use the separators of `LC_NUMERIC`, and dates
+//! through [`format_datetime`] and [`format_time`], which use the formats
+//! of `LC_TIME`. Values written to files other programs read keep fixed
+//! formats.
 
+use std::ffi::{c_char, CStr};
 use std::path::Path;
+use std::sync::OnceLock;
+
+/// Binary byte units, each 1024 times the previous one.
+const BYTE_UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+/// Separators of the user's locale for numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct NumberFormat {
+    decimal: String,
+    /// Empty when the locale does not group digits, as in the C locale.
+    thousands: String,
+}
+
+static NUMBER_FORMAT: OnceLock<NumberFormat> = OnceLock::new();
 
 /// Initialize the gettext text domain and bind the locale directory.
 pub fn init(domain: &str) {
@@ -29,6 +52,8 @@ pub fn init(domain: &str) {
     if let Err(e) = gettextrs::textdomain(domain) {
         tracing::warn!("textdomain failed: {}", e);
     }
+    // Read the separators now, on the main thread, right after setlocale
+    number_format();
 }
 
 /// Best-effort default catalog directory next to the running binary's prefix.
@@ -54,3 +79,160 @@ fn default_locale_dir() -> String {
 pub fn gettext(msgid: &str) -> String {
     gettextrs::gettext(msgid)
 }
+
+impl NumberFormat {
+    /// Read the separators of the current `LC_NUMERIC` locale; the C
+    /// locale's until [`init`] has run.
+    fn from_locale() -> Self {
+        let read = |ptr: *const c_char| {
+            // SAFETY: localeconv's strings are NUL-terminated and stay valid
+            // until the next setlocale call; they are copied right away.
+            (!ptr.is_null()).then(|| {
+                unsafe { CStr::from_ptr(ptr) }
+                    .to_string_lossy()
+                    .into_owned()
+            })
+        };
+        // SAFETY: localeconv returns a pointer to static data or null.
+        let conv = unsafe { libc::localeconv() };
+        if conv.is_null() {
+            return Self {
+                decimal: ".".to_string(),
+                thousands: String::new(),
+            };
+        }
+        // SAFETY: checked for null above.
+        let (decimal, thousands) = unsafe { ((*conv).decimal_point, (*conv).thousands_sep) };
+        Self {
+            decimal: read(decimal)
+                .filter(|s| !s.is_empty())
+                .unwrap_or_else(|| ".".to_string()),
+            thousands: read(thousands).unwrap_or_default(),
+        }
+    }
+
+    /// ASCII `digits` with the thousands separator between groups of three.
+    fn group(&self, digits: &str) -> String {
+        let mut grouped = String::with_capacity(digits.len() + digits.len() / 3 * 3);
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                grouped.push_str(&self.thousands);
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+
+    fn integer(&self, n: u64) -> String {
+        self.group(&n.to_string())
+    }
+
+    fn decimal(&self, value: f64, places: usize) -> String {
+        let text = format!("{:.*}", places, value.abs());
+        let (integer, fraction) = text.split_once('.').unwrap_or((&text, ""));
+        let mut formatted = String::new();
+        // No sign on values that round to zero
+        if value < 0.0 && text.bytes().any(|b| (b'1'..=b'9').contains(&b)) {
+            formatted.push('-');
+        }
+        formatted.push_str(&self.group(integer));
+        if !fraction.is_empty() {
+            formatted.push_str(&self.decimal);
+            formatted.push_str(fraction);
+        }
+        formatted
+    }
+}
+
+fn number_format() -> &'static NumberFormat {
+    NUMBER_FORMAT.get_or_init(NumberFormat::from_locale)
+}
+
+/// `n` with the locale's thousands separator, e.g. `12,345` or `12.345`.
+pub fn format_number(n: u64) -> String {
+    number_format().integer(n)
+}
+
+/// `value` rounded to `places` decimals, with the locale's separators.
+pub fn format_decimal(value: f64, places: usize) -> String {
+    number_format().decimal(value, places)
+}
+
+/// A percentage given in percent, e.g. `42.5%` or `42,5%`.
+pub fn format_percent(percent: f64, places: usize) -> String {
+    format!("{}%", format_decimal(percent, places))
+}
+
+/// A byte count in binary units, e.g. `512 B` or `1.5 MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < BYTE_UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", format_number(bytes), BYTE_UNITS[0])
+    } else {
+        format!("{} {}", format_decimal(value, 1), BYTE_UNITS[unit])
+    }
+}
+
+/// A transfer rate in bytes per second, e.g. `1.2 MiB/s`.
+pub fn format_rate(bytes_per_sec: f64) -> String {
+    format!("{}/s", format_bytes(bytes_per_sec.max(0.0) as u64))
+}
+
+/// Local date and time of a Unix timestamp in the locale's format.
+pub fn format_datetime(timestamp: i64) -> String {
+    strftime(timestamp, c"%x %X")
+}
+
+/// Local time of day of a Unix timestamp in the locale's format.
+pub fn format_time(timestamp: i64) -> String {
+    strftime(timestamp, c"%X")
+}
+
+/// Format a Unix timestamp in local time with `strftime`, which follows
+/// `LC_TIME` unlike chrono.
+fn strftime(timestamp: i64, format: &CStr) -> String {
+    let time = timestamp as libc::time_t;
+    let mut buf = [0u8; 128];
+    // SAFETY: tm is plain data; localtime_r fills it and reports failure
+    // with null. strftime writes at most buf.len() bytes, NUL included,
+    // and returns the length without the NUL (0 when it does not fit).
+    let len = unsafe {
+        let mut tm: libc::tm = std::mem::zeroed();
+        if libc::localtime_r(&time, &mut tm).is_null() {
+            return String::new();
+        }
+        libc::strftime(buf.as_mut_ptr().cast(), buf.len(), format.as_ptr(), &tm)
+    };
+    String::from_utf8_lossy(&buf[..len]).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_format() {
+        let german = NumberFormat {
+            decimal: ",".to_string(),
+            thousands: ".".to_string(),
+        };
+        assert_eq!(german.integer(0), "0");
+        assert_eq!(german.integer(999), "999");
+        assert_eq!(german.integer(1_234_567), "1.234.567");
+        assert_eq!(german.decimal(1234.56, 1), "1.234,6");
+        assert_eq!(german.decimal(-1234.0, 0), "-1.234");
+        assert_eq!(german.decimal(-0.04, 1), "0,0");
+
+        // Tests run in the C locale: no grouping, a dot for decimals
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 * 1024), "5.0 GiB");
+        assert_eq!(format_rate(2048.0), "2.0 KiB/s");
+        assert_eq!(format_percent(42.46, 1), "42.5%");
+    }
+}
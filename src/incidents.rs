@@ -0,0 +1,410 @@
+// Security Center - Incidents
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Lightweight incident records.
+//!
+//! An [`Incident`] is something the user is looking into: a title, a
+//! severity, a status that moves from open to resolved, free-form notes and
+//! the evidence gathered along the way (scan results, log excerpts, the
+//! snapshot of a quarantined process). Every change is added to its
+//! timeline, so the record shows what was known and done when.
+//!
+//! Quarantining a process opens an incident by itself (see
+//! `crate::admin::quarantine`); others are opened from the Incidents page.
+//! The records live in `~/.config/security-center/incidents.json`, newest
+//! first, and can be exported one at a time as Markdown or JSON.
+
+use std::fs;
+use std::io::Write;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::admin::{DeniedPacket, NetworkExposure, Severity};
+use crate::i18n::format_datetime;
+
+const MAX_INCIDENTS_FILE_SIZE: u64 = 16 * 1_048_576; // 16 MB
+/// Longest evidence text kept; longer excerpts are cut.
+pub const MAX_EVIDENCE_LEN: usize = 64 * 1024;
+/// Denied packets attached as a log excerpt.
+const DENIED_EXCERPT_LINES: usize = 100;
+
+/// Where an incident stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IncidentStatus {
+    Open,
+    Investigating,
+    Resolved,
+}
+
+impl IncidentStatus {
+    pub const ALL: [IncidentStatus; 3] = [
+        IncidentStatus::Open,
+        IncidentStatus::Investigating,
+        IncidentStatus::Resolved,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            IncidentStatus::Open => "Open",
+            IncidentStatus::Investigating => "Investigating",
+            IncidentStatus::Resolved => "Resolved",
+        }
+    }
+}
+
+/// What a piece of evidence is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EvidenceKind {
+    ScanResult,
+    LogExcerpt,
+    /// Snapshot of a quarantined process.
+    Process,
+    Other,
+}
+
+impl EvidenceKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EvidenceKind::ScanResult => "Scan Result",
+            EvidenceKind::LogExcerpt => "Log Excerpt",
+            EvidenceKind::Process => "Process",
+            EvidenceKind::Other => "Other",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            EvidenceKind::ScanResult => "network-server-symbolic",
+            EvidenceKind::LogExcerpt => "text-x-generic-symbolic",
+            EvidenceKind::Process => "application-x-executable-symbolic",
+            EvidenceKind::Other => "mail-attachment-symbolic",
+        }
+    }
+}
+
+/// Something attached to an incident.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Evidence {
+    /// Unix timestamp.
+    pub added_at: i64,
+    pub kind: EvidenceKind,
+    pub title: String,
+    pub content: String,
+}
+
+/// One step of an incident.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    /// Unix timestamp.
+    pub at: i64,
+    pub message: String,
+    /// Whether the step succeeded.
+    pub ok: bool,
+}
+
+/// An incident and what is known about it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Incident {
+    /// Unique within the log.
+    pub id: u64,
+    pub title: String,
+    pub severity: Severity,
+    pub status: IncidentStatus,
+    /// Unix timestamp.
+    pub opened_at: i64,
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub evidence: Vec<Evidence>,
+    #[serde(default)]
+    pub timeline: Vec<TimelineEntry>,
+}
+
+fn now() -> i64 {
+    chrono::Local::now().timestamp()
+}
+
+impl Incident {
+    /// Open an incident. The id is the opening time in milliseconds, made
+    /// unique when the incident is added to the log.
+    pub fn new(title: &str, severity: Severity) -> Self {
+        let mut incident = Self {
+            id: chrono::Local::now().timestamp_millis().max(0) as u64,
+            title: title.trim().to_string(),
+            severity,
+            status: IncidentStatus::Open,
+            opened_at: now(),
+            notes: String::new(),
+            evidence: Vec::new(),
+            timeline: Vec::new(),
+        };
+        incident.record(format!("Opened with {} severity", severity.label()), true);
+        incident
+    }
+
+    /// Append a step to the timeline.
+    pub fn record(&mut self, message: impl Into<String>, ok: bool) {
+        self.timeline.push(TimelineEntry {
+            at: now(),
+            message: message.into(),
+            ok,
+        });
+    }
+
+    /// Whether any step on the timeline failed.
+    pub fn has_failures(&self) -> bool {
+        self.timeline.iter().any(|entry| !entry.ok)
+    }
+
+    pub fn set_status(&mut self, status: IncidentStatus) {
+        if status != self.status {
+            self.status = status;
+            self.record(format!("Status changed to {}", status.label()), true);
+        }
+    }
+
+    pub fn set_severity(&mut self, severity: Severity) {
+        if severity != self.severity {
+            self.severity = severity;
+            self.record(format!("Severity changed to {}", severity.label()), true);
+        }
+    }
+
+    pub fn set_notes(&mut self, notes: &str) {
+        if notes != self.notes {
+            self.notes = notes.to_string();
+            self.record("Notes edited", true);
+        }
+    }
+
+    /// Attach evidence, cutting content over [`MAX_EVIDENCE_LEN`].
+    pub fn attach(&mut self, kind: EvidenceKind, title: &str, content: &str) {
+        let mut content = content.to_string();
+        if content.len() > MAX_EVIDENCE_LEN {
+            let mut end = MAX_EVIDENCE_LEN;
+            while !content.is_char_boundary(end) {
+                end -= 1;
+            }
+            content.truncate(end);
+            content.push_str("\n[truncated]");
+        }
+        self.record(format!("Attached {}: {}", kind.label(), title), true);
+        self.evidence.push(Evidence {
+            added_at: now(),
+            kind,
+            title: title.to_string(),
+            content,
+        });
+    }
+
+    /// The record as a Markdown document.
+    pub fn to_markdown(&self) -> String {
+        let mut doc = format!(
+            "# {}\n\n- Severity: {}\n- Status: {}\n- Opened: {}\n",
+            self.title,
+            self.severity.label(),
+            self.status.label(),
+            format_datetime(self.opened_at)
+        );
+        if !self.notes.is_empty() {
+            doc.push_str(&format!("\n## Notes\n\n{}\n", self.notes));
+        }
+        if !self.evidence.is_empty() {
+            doc.push_str("\n## Evidence\n");
+            for evidence in &self.evidence {
+                doc.push_str(&format!(
+                    "\n### {} ({}, {})\n\n```\n{}\n```\n",
+                    evidence.title,
+                    evidence.kind.label(),
+                    format_datetime(evidence.added_at),
+                    evidence.content.trim_end()
+                ));
+            }
+        }
+        doc.push_str("\n## Timeline\n\n");
+        for entry in &self.timeline {
+            doc.push_str(&format!(
+                "- {} {}{}\n",
+                format_datetime(entry.at),
+                entry.message,
+                if entry.ok { "" } else { " (failed)" }
+            ));
+        }
+        doc
+    }
+
+    /// Write the record to `path`: JSON for a `.json` file, Markdown
+    /// otherwise.
+    pub fn export(&self, path: &Path) -> Result<()> {
+        let content = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::to_string_pretty(self)?
+        } else {
+            self.to_markdown()
+        };
+        fs::write(path, content).with_context(|| format!("Cannot write {}", path.display()))
+    }
+}
+
+fn log_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("security-center")
+        .join("incidents.json")
+}
+
+/// Recorded incidents, newest first. Empty when the log is missing or
+/// unreadable.
+pub fn load_incidents() -> Vec<Incident> {
+    let path = log_path();
+    if fs::metadata(&path).is_ok_and(|m| m.len() > MAX_INCIDENTS_FILE_SIZE) {
+        warn!("Incident log too large, ignoring");
+        return Vec::new();
+    }
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        warn!("Failed to parse the incident log: {}", e);
+        Vec::new()
+    })
+}
+
+fn save_incidents(incidents: &[Incident]) -> Result<()> {
+    let path = log_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context("Failed to create the config directory")?;
+    }
+    let content = serde_json::to_string_pretty(incidents)?;
+    let mut file = fs::File::create(&path).context("Failed to write the incident log")?;
+    // Evidence can hold command lines and addresses
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+/// Put `incident` first, with an id no other incident has.
+fn prepend(incidents: &mut Vec<Incident>, mut incident: Incident) -> u64 {
+    if let Some(max) = incidents.iter().map(|i| i.id).max() {
+        incident.id = incident.id.max(max + 1);
+    }
+    let id = incident.id;
+    incidents.insert(0, incident);
+    id
+}
+
+/// Add an incident to the log, returning its id.
+pub fn add_incident(incident: Incident) -> Result<u64> {
+    let mut incidents = load_incidents();
+    let id = prepend(&mut incidents, incident);
+    save_incidents(&incidents)?;
+    Ok(id)
+}
+
+/// Replace the incident with the same id.
+pub fn update_incident(incident: &Incident) -> Result<()> {
+    let mut incidents = load_incidents();
+    let slot = incidents
+        .iter_mut()
+        .find(|i| i.id == incident.id)
+        .ok_or_else(|| anyhow!("The incident no longer exists"))?;
+    *slot = incident.clone();
+    save_incidents(&incidents)
+}
+
+/// Remove an incident from the log.
+pub fn delete_incident(id: u64) -> Result<()> {
+    let mut incidents = load_incidents();
+    incidents.retain(|i| i.id != id);
+    save_incidents(&incidents)
+}
+
+/// The listening sockets, one per line, as scan evidence. Reads procfs, so
+/// call it off the main thread.
+pub fn listening_ports_excerpt() -> Result<String> {
+    let mut scanner = NetworkExposure::new();
+    let endpoints = scanner.scan_sockets()?;
+    Ok(endpoints
+        .iter()
+        .map(|e| {
+            let process = match (&e.process_name, e.pid) {
+                (Some(name), Some(pid)) => format!("{} (pid {})", name, pid),
+                (Some(name), None) => name.clone(),
+                _ => "unknown process".to_string(),
+            };
+            let addr = match e.local_addr {
+                std::net::IpAddr::V6(addr) => format!("[{}]", addr),
+                addr => addr.to_string(),
+            };
+            format!("{}:{} {} {}", addr, e.port, e.protocol.as_str(), process)
+        })
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// The most recent denied packets, one per line, as a log excerpt.
+pub fn denied_packets_excerpt(packets: &[DeniedPacket]) -> String {
+    packets
+        .iter()
+        .take(DENIED_EXCERPT_LINES)
+        .map(|p| {
+            let port = p.port.map(|port| format!(":{}", port)).unwrap_or_default();
+            format!(
+                "{} {} {} -> {}{} {} on {}",
+                format_datetime(p.time as i64),
+                p.rule,
+                p.source,
+                p.destination,
+                port,
+                p.protocol,
+                p.interface
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_incident_record() {
+        let mut incident = Incident::new("  Port scan from 203.0.113.7 ", Severity::Medium);
+        assert_eq!(incident.title, "Port scan from 203.0.113.7");
+        incident.set_status(IncidentStatus::Investigating);
+        incident.set_status(IncidentStatus::Investigating);
+        incident.set_notes("Seen by the tripwire on 2222");
+        incident.attach(EvidenceKind::LogExcerpt, "Denied packets", "a\nb");
+        incident.attach(EvidenceKind::Other, "Large", &"é".repeat(MAX_EVIDENCE_LEN));
+        assert_eq!(incident.timeline.len(), 5);
+        assert!(!incident.has_failures());
+        assert!(incident.evidence[1].content.ends_with("[truncated]"));
+
+        let markdown = incident.to_markdown();
+        assert!(markdown.starts_with("# Port scan from 203.0.113.7\n"));
+        assert!(markdown.contains("- Status: Investigating"));
+        assert!(markdown.contains("### Denied packets (Log Excerpt, "));
+
+        incident.record("Failed to stop x.service", false);
+        assert!(incident.has_failures());
+    }
+
+    #[test]
+    fn test_prepend_unique_ids() {
+        let mut incidents = Vec::new();
+        let first = Incident::new("First", Severity::Low);
+        let second = Incident {
+            id: first.id,
+            ..Incident::new("Second", Severity::High)
+        };
+        let first_id = prepend(&mut incidents, first);
+        let second_id = prepend(&mut incidents, second);
+        assert_ne!(first_id, second_id);
+        assert_eq!(incidents[0].title, "Second");
+    }
+}
@@ -0,0 +1,46 @@
+// Security Center - Library
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Security Center - A GTK4/Libadwaita security management application.
+//!
+//! The crate builds two binaries on this library:
+//!
+//! - `security-center`, the application (and, started through pkexec, the
+//!   privileged helper), optionally kept in the background with a tray icon;
+//!   see [`tray`]
+//! - `security-center-monitor`, the optional background monitor run as a
+//!   user systemd service; see [`monitor`]
+
+pub mod admin;
+pub mod api;
+pub mod application;
+pub mod autostart;
+pub mod blocklists;
+pub mod config;
+pub mod data_usage;
+pub mod firewall;
+pub mod helper;
+pub mod hooks;
+pub mod i18n;
+pub mod incidents;
+pub mod migration;
+pub mod models;
+pub mod monitor;
+pub mod rule_expiry;
+pub mod safe_mode;
+pub mod security_scan;
+pub mod stats;
+pub mod status_feed;
+pub mod storage;
+pub mod systemd;
+pub mod tray;
+pub mod ui;
+pub mod validation;
+pub mod version_check;
+
+/// Application ID, also the well-known D-Bus name of the running application.
+pub const APP_ID: &str = "com.chrisdaggas.security-center";
+
+/// Translation domain — must match the installed `<domain>.mo` files.
+pub const GETTEXT_DOMAIN: &str = "security-center";
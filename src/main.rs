@@ -3,32 +3,34 @@
 // SPDX-License-Identifier: MIT
 
 //! Security Center - A GTK4/Libadwaita security management application.
+//! The modules live in the library (`lib.rs`).
 
 use gtk4::prelude::*;
 use gtk4::{gio, glib};
 
-mod admin;
-mod application;
-mod autostart;
-mod config;
-mod firewall;
-mod i18n;
-mod models;
-mod stats;
-mod storage;
-mod systemd;
-mod ui;
-mod validation;
-mod version_check;
-
-use application::Application;
-
-const APP_ID: &str = "com.chrisdaggas.security-center";
-
-/// Translation domain — must match the installed `<domain>.mo` files.
-const GETTEXT_DOMAIN: &str = "security-center";
+use security_center::application::Application;
+use security_center::{helper, i18n, safe_mode, APP_ID, GETTEXT_DOMAIN};
 
 fn main() -> glib::ExitCode {
+    // Only the separate helper program runs privileged
+    if helper::running_elevated() {
+        eprintln!("Security Center must not run as root or through pkexec");
+        return glib::ExitCode::FAILURE;
+    }
+    let mut args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some(helper::INSTALL_POLICY_FLAG) {
+        return match helper::run_privileged(&helper::HelperOp::InstallPolkitPolicy) {
+            Ok(message) => {
+                println!("{}", message);
+                glib::ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("{:#}", e);
+                glib::ExitCode::FAILURE
+            }
+        };
+    }
+
     glib::set_prgname(Some("security-center"));
 
     // Wire up gettext so translated .mo catalogs load for the user's locale.
@@ -51,6 +53,11 @@ fn main() -> glib::ExitCode {
         gio::resources_register(&resource);
     }
 
+    if safe_mode::take_flag(&mut args) {
+        safe_mode::set_enabled(true);
+        tracing::info!("Safe mode: background collectors are paused");
+    }
+
     let app = Application::new(APP_ID);
-    app.run()
+    app.run_with_args(&args)
 }
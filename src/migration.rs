@@ -0,0 +1,403 @@
+// Security Center - Config Migrations
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Versioned JSON files with explicit migration steps.
+//!
+//! Settings and port metadata carry their format version in
+//! [`VERSION_KEY`]. Loading a file of an older version first copies it to
+//! `<file>.v<version>.bak`, then runs the [`Schema`]'s steps in order and
+//! writes the result back, so an upgrade never silently drops what the
+//! older format held. A file written by a newer version is left alone.
+//!
+//! Every save keeps the previous file as `<file>.bak`. A file that no longer
+//! parses is moved aside to `<file>.corrupt` and the newest backup that
+//! loads is restored in its place.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use tracing::{info, warn};
+
+/// Field holding the format version of a file; missing means version 0.
+pub const VERSION_KEY: &str = "schema_version";
+
+/// One migration step, turning a file of version `n` into version `n + 1`.
+pub type Migration = fn(&mut Value) -> Result<()>;
+
+/// The format history of one file.
+pub struct Schema {
+    /// What the file holds, for log messages.
+    pub name: &'static str,
+    /// `steps[n]` migrates version `n` to `n + 1`; the current version is
+    /// the number of steps.
+    pub steps: &'static [Migration],
+}
+
+impl Schema {
+    /// The version written by this build.
+    pub fn version(&self) -> u32 {
+        self.steps.len() as u32
+    }
+}
+
+/// A file written by a newer version of Security Center.
+#[derive(Debug)]
+pub struct NewerVersion {
+    pub found: u32,
+    pub supported: u32,
+}
+
+impl std::fmt::Display for NewerVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "written by a newer version (format {}, this build reads up to {})",
+            self.found, self.supported
+        )
+    }
+}
+
+impl std::error::Error for NewerVersion {}
+
+/// Format version of a parsed file.
+pub fn file_version(value: &Value) -> u32 {
+    value
+        .get(VERSION_KEY)
+        .and_then(Value::as_u64)
+        .map_or(0, |v| u32::try_from(v).unwrap_or(u32::MAX))
+}
+
+/// Run the steps from the version of `value` to the current one and stamp
+/// it with the current version; returns the version it had.
+pub fn migrate(schema: &Schema, value: &mut Value) -> Result<u32> {
+    let from = file_version(value);
+    if from > schema.version() {
+        return Err(NewerVersion {
+            found: from,
+            supported: schema.version(),
+        }
+        .into());
+    }
+    if !value.is_object() {
+        bail!("not a JSON object");
+    }
+    for (version, step) in schema.steps.iter().enumerate().skip(from as usize) {
+        step(value).with_context(|| format!("migrating from format {}", version))?;
+    }
+    match value.as_object_mut() {
+        Some(object) => {
+            object.insert(VERSION_KEY.to_string(), schema.version().into());
+        }
+        None => bail!("migration left no JSON object"),
+    }
+    Ok(from)
+}
+
+/// Load a versioned file: migrate it when it is older, recover it from the
+/// newest backup when it is corrupt. `None` when there is nothing usable.
+pub fn load<T: DeserializeOwned>(schema: &Schema, path: &Path, max_size: u64) -> Option<T> {
+    if !path.exists() {
+        return None;
+    }
+    match read_file(schema, path, max_size) {
+        Ok((data, value, from)) => {
+            if from < schema.version() {
+                if let Err(e) = store_migrated(path, &value, from) {
+                    warn!("Failed to store the migrated {}: {:#}", schema.name, e);
+                } else {
+                    info!(
+                        "Migrated {} from format {} to {}",
+                        schema.name,
+                        from,
+                        schema.version()
+                    );
+                }
+            }
+            return Some(data);
+        }
+        Err(e) if e.is::<NewerVersion>() => {
+            warn!("Not loading {}: {}", schema.name, e);
+            return None;
+        }
+        Err(e) => warn!("The {} file is corrupt: {:#}", schema.name, e),
+    }
+
+    let corrupt = sibling(path, "corrupt");
+    if let Err(e) = fs::rename(path, &corrupt) {
+        warn!("Failed to move the corrupt {} aside: {}", schema.name, e);
+    }
+    for backup in backups(path) {
+        match read_file::<T>(schema, &backup, max_size) {
+            Ok((data, value, _)) => {
+                if let Err(e) = write_private(path, &value) {
+                    warn!("Failed to restore {}: {:#}", schema.name, e);
+                }
+                info!("Restored {} from {}", schema.name, backup.display());
+                return Some(data);
+            }
+            Err(e) => warn!("Skipping backup {}: {:#}", backup.display(), e),
+        }
+    }
+    None
+}
+
+/// Save `data` as the current version, keeping the previous file as
+/// `<file>.bak`. Refuses to overwrite a file of a newer version.
+pub fn save<T: Serialize>(schema: &Schema, path: &Path, data: &T) -> Result<()> {
+    let mut value = serde_json::to_value(data).context("serializing")?;
+    match value.as_object_mut() {
+        Some(object) => {
+            object.insert(VERSION_KEY.to_string(), schema.version().into());
+        }
+        None => bail!("{} is not a JSON object", schema.name),
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+    }
+    // A corrupt previous file is not worth keeping over the last good backup
+    if let Ok(previous) = fs::read(path) {
+        if let Ok(existing) = serde_json::from_slice::<Value>(&previous) {
+            let found = file_version(&existing);
+            if found > schema.version() {
+                return Err(NewerVersion {
+                    found,
+                    supported: schema.version(),
+                }
+                .into());
+            }
+            write_bytes(&sibling(path, "bak"), &previous)?;
+        }
+    }
+    write_private(path, &value)
+}
+
+/// Parse, migrate and deserialize a file; returns the data, the migrated
+/// JSON and the version the file had.
+fn read_file<T: DeserializeOwned>(
+    schema: &Schema,
+    path: &Path,
+    max_size: u64,
+) -> Result<(T, Value, u32)> {
+    let size = fs::metadata(path)
+        .with_context(|| format!("reading {}", path.display()))?
+        .len();
+    if size > max_size {
+        bail!("too large ({} bytes)", size);
+    }
+    let content = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+    let mut value: Value = serde_json::from_slice(&content).context("invalid JSON")?;
+    let from = migrate(schema, &mut value)?;
+
+    let mut fields = value.clone();
+    if let Some(object) = fields.as_object_mut() {
+        object.remove(VERSION_KEY);
+    }
+    let data = serde_json::from_value(fields).context("unexpected content")?;
+    Ok((data, value, from))
+}
+
+/// Keep the file of version `from` as `<file>.v<from>.bak` and replace it
+/// with the migrated JSON.
+fn store_migrated(path: &Path, value: &Value, from: u32) -> Result<()> {
+    let backup = sibling(path, &format!("v{}.bak", from));
+    fs::copy(path, &backup).with_context(|| format!("backing up to {}", backup.display()))?;
+    write_private(path, value)
+}
+
+/// Backups of `path`, newest first.
+fn backups(path: &Path) -> Vec<PathBuf> {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+        return Vec::new();
+    };
+    let prefix = format!("{}.", name.to_string_lossy());
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut found: Vec<(SystemTime, PathBuf)> = entries
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.starts_with(&prefix) && name.ends_with(".bak")
+        })
+        .map(|entry| {
+            let modified = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            (modified, entry.path())
+        })
+        .collect();
+    found.sort_by(|a, b| b.cmp(a));
+    found.into_iter().map(|(_, path)| path).collect()
+}
+
+/// `<file>.<suffix>` next to `path`.
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Write JSON readable by the user only, replacing the file in one step.
+fn write_private(path: &Path, value: &Value) -> Result<()> {
+    let content = serde_json::to_string_pretty(value).context("serializing")?;
+    let tmp = sibling(path, "tmp");
+    write_bytes(&tmp, content.as_bytes())?;
+    fs::rename(&tmp, path).with_context(|| format!("replacing {}", path.display()))
+}
+
+fn write_bytes(path: &Path, content: &[u8]) -> Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("creating {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        // An existing file keeps its mode on open
+        file.set_permissions(fs::Permissions::from_mode(0o600))
+            .with_context(|| format!("setting permissions of {}", path.display()))?;
+    }
+    file.write_all(content)
+        .with_context(|| format!("writing {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Notes {
+        notes: BTreeMap<String, String>,
+    }
+
+    fn wrap_notes(value: &mut Value) -> Result<()> {
+        *value = json!({ "notes": value.take() });
+        Ok(())
+    }
+
+    fn rename_notes(value: &mut Value) -> Result<()> {
+        let object = value.as_object_mut().context("not an object")?;
+        if let Some(Value::Object(notes)) = object.get_mut("notes") {
+            if let Some(text) = notes.remove("old") {
+                notes.insert("new".to_string(), text);
+            }
+        }
+        Ok(())
+    }
+
+    const NOTES: Schema = Schema {
+        name: "test notes",
+        steps: &[wrap_notes, rename_notes],
+    };
+
+    fn temp_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "security-center-migration-{}-{}",
+            test,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_migrate_runs_steps_in_order() {
+        let mut value = json!({ "old": "kept" });
+        assert_eq!(migrate(&NOTES, &mut value).unwrap(), 0);
+        assert_eq!(
+            value,
+            json!({ "notes": { "new": "kept" }, "schema_version": 2 })
+        );
+
+        // Only the missing steps run
+        let mut value = json!({ "notes": { "old": "x" }, "schema_version": 2 });
+        assert_eq!(migrate(&NOTES, &mut value).unwrap(), 2);
+        assert_eq!(value["notes"], json!({ "old": "x" }));
+
+        let mut value = json!({ "notes": {}, "schema_version": 3 });
+        let err = migrate(&NOTES, &mut value).unwrap_err();
+        assert!(err.is::<NewerVersion>());
+    }
+
+    #[test]
+    fn test_load_backs_up_before_migrating() {
+        let dir = temp_dir("backup");
+        let path = dir.join("notes.json");
+        fs::write(&path, r#"{"old":"kept","other":"too"}"#).unwrap();
+
+        let notes: Notes = load(&NOTES, &path, 1024).unwrap();
+        assert_eq!(notes.notes["new"], "kept");
+        assert_eq!(notes.notes["other"], "too");
+
+        let backup = fs::read_to_string(dir.join("notes.json.v0.bak")).unwrap();
+        assert_eq!(backup, r#"{"old":"kept","other":"too"}"#);
+        let stored: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(file_version(&stored), 2);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_corrupt_file_recovers_from_latest_backup() {
+        let dir = temp_dir("corrupt");
+        let path = dir.join("notes.json");
+        let first = Notes {
+            notes: BTreeMap::from([("a".to_string(), "1".to_string())]),
+        };
+        let second = Notes {
+            notes: BTreeMap::from([("b".to_string(), "2".to_string())]),
+        };
+        save(&NOTES, &path, &first).unwrap();
+        save(&NOTES, &path, &second).unwrap();
+        assert!(dir.join("notes.json.bak").exists());
+
+        fs::write(&path, "{\"notes\": {\"b\": ").unwrap();
+        let restored: Notes = load(&NOTES, &path, 1024).unwrap();
+        assert_eq!(restored, first);
+        assert!(dir.join("notes.json.corrupt").exists());
+        // The restored copy is in place again
+        let reloaded: Notes = load(&NOTES, &path, 1024).unwrap();
+        assert_eq!(reloaded, first);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_save_keeps_newer_files() {
+        let dir = temp_dir("newer");
+        let path = dir.join("notes.json");
+        fs::write(&path, r#"{"notes":{},"schema_version":9}"#).unwrap();
+
+        assert!(load::<Notes>(&NOTES, &path, 1024).is_none());
+        let notes = Notes {
+            notes: BTreeMap::new(),
+        };
+        assert!(save(&NOTES, &path, &notes).is_err());
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("\"schema_version\":9"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
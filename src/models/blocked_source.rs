@@ -0,0 +1,179 @@
+// Security Center - Blocked Source Model
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Addresses and networks a zone drops or rejects all traffic from.
+//!
+//! A block is a rich rule with nothing but a family, a source address and
+//! a `drop` or `reject` action, such as
+//! `rule family="ipv6" source address="2001:db8::/32" drop`. firewalld needs
+//! the family of an address match, so it is always taken from the address.
+
+use std::net::IpAddr;
+
+use anyhow::{anyhow, Result};
+
+use super::{AddressKind, Family, RichRule, RuleAction, RuleAddress};
+
+/// A source address or network whose traffic a zone stops.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockedSource {
+    /// Address or network in CIDR notation, as [`parse_address`] writes it.
+    pub address: String,
+    pub family: Family,
+    pub zone: String,
+    /// Reject with an error instead of dropping silently.
+    pub reject: bool,
+    /// Whether the rule is in the permanent configuration too.
+    pub is_permanent: bool,
+    /// The exact rich-rule string, to remove the block by its real text.
+    pub raw_rule: String,
+}
+
+impl BlockedSource {
+    /// Read a block from one of `zone`'s rich rules, if the rule is one.
+    pub fn from_rich_rule(rule: &str, zone: &str) -> Option<Self> {
+        let parsed = RichRule::parse(rule).ok()?;
+        let source = parsed.source.as_ref()?;
+        if source.kind != AddressKind::Address
+            || source.invert
+            || parsed.element.is_some()
+            || parsed.destination.is_some()
+            || parsed.log.is_some()
+            || parsed.audit.is_some()
+            || parsed.action_limit.is_some()
+        {
+            return None;
+        }
+        let reject = match parsed.action? {
+            RuleAction::Drop => false,
+            RuleAction::Reject(_) => true,
+            _ => return None,
+        };
+        let (address, family) = parse_address(&source.value).ok()?;
+        Some(Self {
+            address,
+            family,
+            zone: zone.to_string(),
+            reject,
+            is_permanent: true,
+            raw_rule: rule.to_string(),
+        })
+    }
+
+    /// The rule blocking `address`, an address or network typed by the
+    /// user, with the family it needs.
+    pub fn rule(address: &str, reject: bool) -> Result<RichRule> {
+        let (address, family) = parse_address(address)?;
+        Ok(RichRule {
+            family: Some(family),
+            source: Some(RuleAddress {
+                kind: AddressKind::Address,
+                value: address,
+                invert: false,
+            }),
+            action: Some(if reject {
+                RuleAction::Reject(None)
+            } else {
+                RuleAction::Drop
+            }),
+            ..Default::default()
+        })
+    }
+
+    /// Whether this block is for `address`, compared as parsed so
+    /// `2001:DB8::1` matches `2001:db8:0::1`.
+    pub fn matches(&self, address: &str) -> bool {
+        parse_address(address).is_ok_and(|(address, _)| address == self.address)
+    }
+}
+
+/// Validate an address or CIDR network and write it the way firewalld
+/// does: a single host loses its `/32` or `/128` prefix and IPv6 is
+/// written in its short lowercase form.
+pub fn parse_address(text: &str) -> Result<(String, Family)> {
+    let text = text.trim();
+    let invalid = || anyhow!("Not an IP address or network: {}", text);
+    let (network, prefix) = match text.split_once('/') {
+        Some((network, prefix)) => (network, Some(prefix)),
+        None => (text, None),
+    };
+    let ip: IpAddr = network.parse().map_err(|_| invalid())?;
+    let (family, max) = match ip {
+        IpAddr::V4(_) => (Family::Ipv4, 32),
+        IpAddr::V6(_) => (Family::Ipv6, 128),
+    };
+    let prefix = match prefix {
+        Some(prefix) => prefix
+            .parse::<u8>()
+            .ok()
+            .filter(|p| *p <= max)
+            .ok_or_else(invalid)?,
+        None => max,
+    };
+    if prefix == 0 {
+        return Err(anyhow!("Blocking {} would block every address", text));
+    }
+    let address = if prefix == max {
+        ip.to_string()
+    } else {
+        format!("{}/{}", ip, prefix)
+    };
+    Ok((address, family))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_address() {
+        assert_eq!(
+            parse_address(" 203.0.113.7/32 ").unwrap(),
+            ("203.0.113.7".to_string(), Family::Ipv4)
+        );
+        assert_eq!(
+            parse_address("2001:DB8:0::/32").unwrap(),
+            ("2001:db8::/32".to_string(), Family::Ipv6)
+        );
+        assert!(parse_address("10.0.0.0/33").is_err());
+        assert!(parse_address("0.0.0.0/0").is_err());
+        assert!(parse_address("example.com").is_err());
+    }
+
+    #[test]
+    fn test_rule_round_trip() {
+        let rule = BlockedSource::rule("2001:db8::1", false).unwrap();
+        assert_eq!(
+            rule.to_string(),
+            r#"rule family="ipv6" source address="2001:db8::1" drop"#
+        );
+        let blocked = BlockedSource::from_rich_rule(&rule.to_string(), "public").unwrap();
+        assert_eq!(blocked.family, Family::Ipv6);
+        assert!(!blocked.reject);
+        assert!(blocked.matches("2001:DB8:0:0::1/128"));
+
+        let rule = BlockedSource::rule("198.51.100.0/24", true).unwrap();
+        assert_eq!(
+            rule.to_string(),
+            r#"rule family="ipv4" source address="198.51.100.0/24" reject"#
+        );
+    }
+
+    #[test]
+    fn test_other_rules_are_not_blocks() {
+        for rule in [
+            r#"rule family="ipv4" source address="10.0.0.1" accept"#,
+            r#"rule family="ipv4" source not address="10.0.0.1" drop"#,
+            r#"rule family="ipv4" source address="10.0.0.1" port port="22" protocol="tcp" drop"#,
+            r#"rule family="ipv4" source ipset="bad" drop"#,
+        ] {
+            assert_eq!(
+                BlockedSource::from_rich_rule(rule, "public"),
+                None,
+                "{}",
+                rule
+            );
+        }
+    }
+}
@@ -18,14 +18,23 @@ pub struct ConsolidatedPort {
     pub end_number: Option<u16>,
     /// User-given name (if any).
     pub name: Option<String>,
+    /// Description of the first rule that has one.
+    pub description: Option<String>,
+    /// Unix time the earliest temporary rule among these is removed, from
+    /// the stored metadata.
+    pub expires_at: Option<i64>,
     /// List of protocols (e.g., ["tcp"], ["udp"], or ["tcp", "udp"]).
     pub protocols: Vec<String>,
     /// List of zones this port rule applies to.
     pub zones: Vec<String>,
     /// Action: "accept" (open) or "deny"/"reject"/"drop" (blocked).
     pub action: String,
-    /// Whether this is a permanent rule.
+    /// Whether this is a permanent rule, i.e. none of the rules it was
+    /// built from exists at runtime only.
     pub is_permanent: bool,
+    /// The rules this entry was built from that exist at runtime only and
+    /// are lost when firewalld reloads.
+    pub runtime_only: Vec<Port>,
     /// Exact rich-rule strings this consolidated entry was built from.
     /// Used to remove blocked rules by their real text rather than a guess.
     pub raw_rules: Vec<String>,
@@ -38,10 +47,17 @@ impl ConsolidatedPort {
             number: port.number,
             end_number: port.end_number,
             name: port.name.clone(),
+            description: port.description.clone(),
+            expires_at: None,
             protocols: vec![port.protocol.clone()],
             zones: port.zone.clone().map(|z| vec![z]).unwrap_or_default(),
             action: port.action.clone(),
             is_permanent: port.is_permanent,
+            runtime_only: if port.is_permanent {
+                Vec::new()
+            } else {
+                vec![port.clone()]
+            },
             raw_rules: port.raw_rule.clone().into_iter().collect(),
         }
     }
@@ -88,6 +104,13 @@ impl ConsolidatedPort {
                 if existing.name.is_none() && port.name.is_some() {
                     existing.name = port.name.clone();
                 }
+                if existing.description.is_none() && port.description.is_some() {
+                    existing.description = port.description.clone();
+                }
+                if !port.is_permanent {
+                    existing.is_permanent = false;
+                    existing.runtime_only.push(port.clone());
+                }
                 // Collect the exact rule text for later removal
                 if let Some(rule) = &port.raw_rule {
                     if !existing.raw_rules.contains(rule) {
@@ -171,10 +194,107 @@ impl ConsolidatedPort {
     }
 }
 
+/// Which verdict the Ports page shows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VerdictFilter {
+    #[default]
+    Any,
+    Open,
+    Blocked,
+}
+
+/// What the Ports page lists, besides the zone.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PortFilter {
+    pub verdict: VerdictFilter,
+    /// `tcp` or `udp`; empty for both.
+    pub protocol: String,
+    /// Lowercase text: a port number, which matches the ranges covering
+    /// it, or part of the port, name, zone, protocol or "open"/"blocked".
+    pub query: String,
+}
+
+impl PortFilter {
+    pub fn matches(&self, port: &ConsolidatedPort) -> bool {
+        let verdict = match self.verdict {
+            VerdictFilter::Any => true,
+            VerdictFilter::Open => !port.is_blocked(),
+            VerdictFilter::Blocked => port.is_blocked(),
+        };
+        let protocol = self.protocol.is_empty() || port.protocols.contains(&self.protocol);
+        verdict && protocol && self.matches_query(port)
+    }
+
+    fn matches_query(&self, port: &ConsolidatedPort) -> bool {
+        let query = self.query.trim();
+        if query.is_empty() {
+            return true;
+        }
+        if let Ok(number) = query.parse::<u16>() {
+            let end = port.end_number.unwrap_or(port.number);
+            if (port.number..=end).contains(&number) {
+                return true;
+            }
+        }
+        let verdict = if port.is_blocked() {
+            "blocked"
+        } else {
+            "open allowed"
+        };
+        port.port_spec().contains(query)
+            || verdict.contains(query)
+            || port
+                .name
+                .as_ref()
+                .is_some_and(|name| name.to_lowercase().contains(query))
+            || port
+                .well_known_service()
+                .is_some_and(|service| service.to_lowercase().contains(query))
+            || port
+                .zones
+                .iter()
+                .any(|zone| zone.to_lowercase().contains(query))
+            || port.protocols.iter().any(|protocol| protocol == query)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_port_filter() {
+        let mut web = ConsolidatedPort::from_port(&Port::with_zone(8443, "tcp", "public"));
+        web.name = Some("Admin Panel".to_string());
+        let mut range = ConsolidatedPort::from_port(&Port::with_zone(6000, "udp", "home"));
+        range.end_number = Some(6100);
+        range.action = "reject".to_string();
+
+        let mut filter = PortFilter::default();
+        assert!(filter.matches(&web) && filter.matches(&range));
+
+        filter.query = "6050".to_string();
+        assert!(!filter.matches(&web));
+        assert!(filter.matches(&range));
+        filter.query = "admin".to_string();
+        assert!(filter.matches(&web) && !filter.matches(&range));
+        filter.query = "home".to_string();
+        assert!(!filter.matches(&web) && filter.matches(&range));
+        filter.query = "udp".to_string();
+        assert!(!filter.matches(&web) && filter.matches(&range));
+        filter.query = "blocked".to_string();
+        assert!(!filter.matches(&web) && filter.matches(&range));
+        filter.query = "84".to_string();
+        assert!(filter.matches(&web) && !filter.matches(&range));
+
+        filter.query.clear();
+        filter.verdict = VerdictFilter::Open;
+        assert!(filter.matches(&web) && !filter.matches(&range));
+        filter.verdict = VerdictFilter::Any;
+        filter.protocol = "udp".to_string();
+        assert!(!filter.matches(&web) && filter.matches(&range));
+    }
+
     #[test]
     fn test_consolidate_same_port_multiple_zones() {
         let ports = vec![
@@ -221,6 +341,12 @@ mod tests {
         assert_eq!(range.port_spec(), "10-20");
         assert_eq!(range.zones.len(), 2);
         assert_eq!(range.display_title(), "Ports 10-20");
+        assert_eq!(ports[0].port_count(), 11);
+        assert_eq!(ports[2].port_count(), 1);
+        assert_eq!(
+            Port::range_with_zone(1, 65535, "udp", "public").port_count(),
+            65535
+        );
         // A well-known port number at the start of a range must not
         // be labeled as that service
         let ssh_range =
@@ -242,4 +368,25 @@ mod tests {
         // Should create 2 separate entries (one allowed, one denied)
         assert_eq!(consolidated.len(), 2);
     }
+
+    #[test]
+    fn test_consolidate_runtime_only() {
+        let mut public = Port::with_zone(8080, "tcp", "public");
+        public.is_permanent = true;
+        let home = Port::with_zone(8080, "tcp", "home");
+
+        let consolidated = ConsolidatedPort::consolidate(&[public.clone(), home]);
+        assert_eq!(consolidated.len(), 1);
+        // One runtime-only zone makes the whole entry runtime-only
+        assert!(!consolidated[0].is_permanent);
+        assert_eq!(consolidated[0].runtime_only.len(), 1);
+        assert_eq!(
+            consolidated[0].runtime_only[0].zone.as_deref(),
+            Some("home")
+        );
+
+        let consolidated = ConsolidatedPort::consolidate(&[public]);
+        assert!(consolidated[0].is_permanent);
+        assert!(consolidated[0].runtime_only.is_empty());
+    }
 }
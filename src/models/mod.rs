@@ -4,15 +4,29 @@
 
 //! Data models for firewall entities.
 
+mod blocked_source;
 mod interface;
+mod objects;
 mod port;
+mod rich_rule;
 mod service;
 mod zone;
 
-pub use consolidated_port::ConsolidatedPort;
+pub use blocked_source::{parse_address, BlockedSource};
+pub use consolidated_port::{ConsolidatedPort, PortFilter, VerdictFilter};
 pub use interface::Interface;
+pub use objects::{
+    rebind_store, store_models, sync_store, ConsolidatedPortObject, ModelObject, PortObject,
+    ServiceObject, UnitObject, ZoneObject,
+};
 pub use port::Port;
+pub use rich_rule::{
+    AddressKind, Family, RichRule, RuleAction, RuleAddress, RuleElement, RuleLog, RULE_LOG_LEVELS,
+    RULE_PORT_PROTOCOLS,
+};
 pub use service::Service;
-pub use zone::Zone;
+pub use zone::{
+    target_label, DriftItem, DriftKind, DriftSide, Zone, ZoneRules, ZoneSettings, ZONE_TARGETS,
+};
 
 mod consolidated_port;
@@ -0,0 +1,496 @@
+// Security Center - Model Objects
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! GObject wrappers around the plain models and systemd's service
+//! listings, for property bindings, expression watches and
+//! `gio::ListStore` containers.
+//!
+//! Each wrapper holds a copy of its model and exposes the fields as
+//! properties. [`sync_store`] updates a store in place from a fresh list of
+//! models: objects that are still present keep their identity and only
+//! notify the properties that actually changed, so widgets bound to them
+//! follow live updates without being rebuilt, and only the stretch of the
+//! store that changed is replaced, so list views keep their other rows.
+
+use std::collections::HashMap;
+
+use gtk4::gio;
+use gtk4::glib;
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::*;
+
+use super::{ConsolidatedPort, Port, Service, Zone};
+use crate::systemd::UnitEntry;
+
+/// A GObject wrapper around one of the plain models.
+pub trait ModelObject: IsA<glib::Object> {
+    type Model: Clone;
+
+    /// Wrap a copy of `model`.
+    fn from_model(model: &Self::Model) -> Self;
+
+    /// Identity of a model across refreshes.
+    fn key(model: &Self::Model) -> String;
+
+    /// Copy of the wrapped model.
+    fn model(&self) -> Self::Model;
+
+    /// Replace the wrapped model, notifying the properties that changed.
+    fn update(&self, model: &Self::Model);
+}
+
+/// Make `store` hold one object per model, in order, reusing the objects
+/// of models that are still present.
+pub fn sync_store<O: ModelObject>(store: &gio::ListStore, models: &[O::Model]) {
+    let current: Vec<O> = store.iter::<O>().filter_map(Result::ok).collect();
+    let mut by_key: HashMap<String, O> = current
+        .iter()
+        .map(|object| (O::key(&object.model()), object.clone()))
+        .collect();
+
+    let objects: Vec<O> = models
+        .iter()
+        .map(|model| match by_key.remove(&O::key(model)) {
+            Some(object) => {
+                object.update(model);
+                object
+            }
+            None => O::from_model(model),
+        })
+        .collect();
+
+    // Property updates are enough for the objects that stay in place; only
+    // the changed stretch is replaced, so list views keep the other rows
+    let (position, removed, added) = changed_span(&current, &objects);
+    if removed > 0 || !added.is_empty() {
+        store.splice(position as u32, removed as u32, added);
+    }
+}
+
+/// The stretch of `old` that differs from `new`, as the position, the
+/// number of old items to remove there and the new items to insert.
+fn changed_span<'a, T: PartialEq>(old: &[T], new: &'a [T]) -> (usize, usize, &'a [T]) {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    (
+        prefix,
+        old.len() - prefix - suffix,
+        &new[prefix..new.len() - suffix],
+    )
+}
+
+/// Put the objects of `store` back in place, so list views bind their rows
+/// again after state outside the objects changed.
+pub fn rebind_store(store: &gio::ListStore) {
+    let objects: Vec<glib::Object> = store.iter().filter_map(Result::ok).collect();
+    store.splice(0, store.n_items(), &objects);
+}
+
+/// Copies of the models held by `store`.
+pub fn store_models<O: ModelObject>(store: &gio::ListStore) -> Vec<O::Model> {
+    store
+        .iter::<O>()
+        .filter_map(Result::ok)
+        .map(|object| object.model())
+        .collect()
+}
+
+/// Set `property` to `value` if it differs, so unchanged fields stay quiet.
+fn set_if_changed<V>(object: &impl IsA<glib::Object>, property: &str, current: &V, value: &V)
+where
+    V: PartialEq + ToValue,
+{
+    if current != value {
+        object.set_property(property, value.to_value());
+    }
+}
+
+glib::wrapper! {
+    /// A firewall zone as a GObject.
+    pub struct ZoneObject(ObjectSubclass<zone_imp::ZoneObject>);
+}
+
+impl ModelObject for ZoneObject {
+    type Model = Zone;
+
+    fn from_model(model: &Zone) -> Self {
+        let object: Self = glib::Object::new();
+        object.imp().data.replace(model.clone());
+        object
+    }
+
+    fn key(model: &Zone) -> String {
+        model.name.clone()
+    }
+
+    fn model(&self) -> Zone {
+        self.imp().data.borrow().clone()
+    }
+
+    fn update(&self, model: &Zone) {
+        let old = self.model();
+        // Fields without a property first, then the notifying setters
+        {
+            let mut data = self.imp().data.borrow_mut();
+            data.target = model.target.clone();
+            data.rich_rules = model.rich_rules.clone();
+            data.icmp_blocks = model.icmp_blocks.clone();
+            data.icmp_block_inversion = model.icmp_block_inversion;
+            data.permanent = model.permanent.clone();
+        }
+        set_if_changed(self, "name", &old.name, &model.name);
+        set_if_changed(self, "description", &old.description, &model.description);
+        set_if_changed(self, "is-active", &old.is_active, &model.is_active);
+        set_if_changed(self, "is-default", &old.is_default, &model.is_default);
+        set_if_changed(self, "services", &old.services, &model.services);
+        set_if_changed(self, "ports", &old.ports, &model.ports);
+        set_if_changed(self, "interfaces", &old.interfaces, &model.interfaces);
+        set_if_changed(self, "sources", &old.sources, &model.sources);
+        set_if_changed(self, "masquerade", &old.masquerade, &model.masquerade);
+        set_if_changed(self, "forward", &old.forward, &model.forward);
+        if old.is_in_use() != model.is_in_use() {
+            self.notify("in-use");
+        }
+    }
+}
+
+mod zone_imp {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[derive(Default, glib::Properties)]
+    #[properties(wrapper_type = super::ZoneObject)]
+    pub struct ZoneObject {
+        #[property(name = "name", get, set, type = String, member = name)]
+        #[property(name = "description", get, set, type = String, member = description)]
+        #[property(name = "is-active", get, set, type = bool, member = is_active)]
+        #[property(name = "is-default", get, set, type = bool, member = is_default)]
+        #[property(name = "services", get, set, type = Vec<String>, member = services)]
+        #[property(name = "ports", get, set, type = Vec<String>, member = ports)]
+        #[property(name = "interfaces", get, set, type = Vec<String>, member = interfaces)]
+        #[property(name = "sources", get, set, type = Vec<String>, member = sources)]
+        #[property(name = "masquerade", get, set, type = bool, member = masquerade)]
+        #[property(name = "forward", get, set, type = bool, member = forward)]
+        #[property(name = "in-use", get = |o: &Self| o.data.borrow().is_in_use(), type = bool)]
+        pub data: RefCell<Zone>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ZoneObject {
+        const NAME: &'static str = "SecurityCenterZoneObject";
+        type Type = super::ZoneObject;
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for ZoneObject {}
+}
+
+glib::wrapper! {
+    /// A firewall service definition as a GObject.
+    pub struct ServiceObject(ObjectSubclass<service_imp::ServiceObject>);
+}
+
+impl ModelObject for ServiceObject {
+    type Model = Service;
+
+    fn from_model(model: &Service) -> Self {
+        let object: Self = glib::Object::new();
+        object.imp().data.replace(model.clone());
+        object
+    }
+
+    fn key(model: &Service) -> String {
+        model.name.clone()
+    }
+
+    fn model(&self) -> Service {
+        self.imp().data.borrow().clone()
+    }
+
+    fn update(&self, model: &Service) {
+        let old = self.model();
+        set_if_changed(self, "name", &old.name, &model.name);
+        set_if_changed(self, "description", &old.description, &model.description);
+        set_if_changed(self, "is-enabled", &old.is_enabled, &model.is_enabled);
+        if old.ports != model.ports {
+            self.imp().data.borrow_mut().ports = model.ports.clone();
+            self.notify("ports-summary");
+        }
+        if old.helpers != model.helpers {
+            self.imp().data.borrow_mut().helpers = model.helpers.clone();
+        }
+    }
+}
+
+mod service_imp {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[derive(Default, glib::Properties)]
+    #[properties(wrapper_type = super::ServiceObject)]
+    pub struct ServiceObject {
+        #[property(name = "name", get, set, type = String, member = name)]
+        #[property(name = "description", get, set, type = String, member = description)]
+        #[property(name = "is-enabled", get, set, type = bool, member = is_enabled)]
+        #[property(
+            name = "ports-summary",
+            get = |o: &Self| o.data.borrow().ports_summary(),
+            type = String
+        )]
+        pub data: RefCell<Service>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ServiceObject {
+        const NAME: &'static str = "SecurityCenterServiceObject";
+        type Type = super::ServiceObject;
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for ServiceObject {}
+}
+
+glib::wrapper! {
+    /// A firewall port rule as a GObject. The rule itself (port, protocol,
+    /// zone and verdict) is its identity and read-only; a changed rule is a
+    /// different object.
+    pub struct PortObject(ObjectSubclass<port_imp::PortObject>);
+}
+
+impl ModelObject for PortObject {
+    type Model = Port;
+
+    fn from_model(model: &Port) -> Self {
+        let object: Self = glib::Object::new();
+        object.imp().data.replace(model.clone());
+        object
+    }
+
+    fn key(model: &Port) -> String {
+        format!(
+            "{}/{}@{}:{}",
+            model.port_spec(),
+            model.protocol,
+            model.zone.as_deref().unwrap_or_default(),
+            model.action
+        )
+    }
+
+    fn model(&self) -> Port {
+        self.imp().data.borrow().clone()
+    }
+
+    fn update(&self, model: &Port) {
+        let old = self.model();
+        self.imp().data.borrow_mut().raw_rule = model.raw_rule.clone();
+        set_if_changed(self, "name", &old.name, &model.name);
+        set_if_changed(self, "description", &old.description, &model.description);
+        set_if_changed(self, "is-permanent", &old.is_permanent, &model.is_permanent);
+        if old.name != model.name {
+            self.notify("display-string");
+        }
+    }
+}
+
+mod port_imp {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[derive(Default, glib::Properties)]
+    #[properties(wrapper_type = super::PortObject)]
+    pub struct PortObject {
+        #[property(name = "number", get = |o: &Self| o.data.borrow().number as u32, type = u32)]
+        #[property(
+            name = "end-number",
+            get = |o: &Self| {
+                let data = o.data.borrow();
+                data.end_number.unwrap_or(data.number) as u32
+            },
+            type = u32
+        )]
+        #[property(name = "protocol", get = |o: &Self| o.data.borrow().protocol.clone(), type = String)]
+        #[property(name = "zone", get = |o: &Self| o.data.borrow().zone.clone(), type = Option<String>)]
+        #[property(name = "action", get = |o: &Self| o.data.borrow().action.clone(), type = String)]
+        #[property(name = "name", get, set, type = Option<String>, member = name)]
+        #[property(name = "description", get, set, type = Option<String>, member = description)]
+        #[property(name = "is-permanent", get, set, type = bool, member = is_permanent)]
+        #[property(
+            name = "display-string",
+            get = |o: &Self| o.data.borrow().display_string(),
+            type = String
+        )]
+        pub data: RefCell<Port>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for PortObject {
+        const NAME: &'static str = "SecurityCenterPortObject";
+        type Type = super::PortObject;
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for PortObject {}
+}
+
+glib::wrapper! {
+    /// A row of the Ports page: one port or range with every zone and
+    /// protocol it has rules in. Like [`PortObject`], its content is its
+    /// identity, so a changed row is a different object.
+    pub struct ConsolidatedPortObject(ObjectSubclass<consolidated_port_imp::ConsolidatedPortObject>);
+}
+
+impl ModelObject for ConsolidatedPortObject {
+    type Model = ConsolidatedPort;
+
+    fn from_model(model: &ConsolidatedPort) -> Self {
+        let object: Self = glib::Object::new();
+        object.imp().data.replace(model.clone());
+        object
+    }
+
+    fn key(model: &ConsolidatedPort) -> String {
+        format!(
+            "{}:{}@{}/{}:{}:{}:{}:{}:{}",
+            model.port_spec(),
+            model.action,
+            model.zones.join(","),
+            model.protocols.join(","),
+            model.name.as_deref().unwrap_or_default(),
+            model.description.as_deref().unwrap_or_default(),
+            model.expires_at.unwrap_or_default(),
+            model.runtime_only.len(),
+            model.raw_rules.join("|")
+        )
+    }
+
+    fn model(&self) -> ConsolidatedPort {
+        self.imp().data.borrow().clone()
+    }
+
+    fn update(&self, _model: &ConsolidatedPort) {
+        // Same key, same content
+    }
+}
+
+mod consolidated_port_imp {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[derive(Default, glib::Properties)]
+    #[properties(wrapper_type = super::ConsolidatedPortObject)]
+    pub struct ConsolidatedPortObject {
+        #[property(name = "number", get = |o: &Self| o.data.borrow().number as u32, type = u32)]
+        #[property(name = "is-blocked", get = |o: &Self| o.data.borrow().is_blocked(), type = bool)]
+        #[property(
+            name = "title",
+            get = |o: &Self| o.data.borrow().display_title(),
+            type = String
+        )]
+        pub data: RefCell<ConsolidatedPort>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ConsolidatedPortObject {
+        const NAME: &'static str = "SecurityCenterConsolidatedPortObject";
+        type Type = super::ConsolidatedPortObject;
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for ConsolidatedPortObject {}
+}
+
+glib::wrapper! {
+    /// A systemd service of the All Services list as a GObject.
+    pub struct UnitObject(ObjectSubclass<unit_imp::UnitObject>);
+}
+
+impl ModelObject for UnitObject {
+    type Model = UnitEntry;
+
+    fn from_model(model: &UnitEntry) -> Self {
+        let object: Self = glib::Object::new();
+        object.imp().data.replace(model.clone());
+        object
+    }
+
+    fn key(model: &UnitEntry) -> String {
+        model.name.clone()
+    }
+
+    fn model(&self) -> UnitEntry {
+        self.imp().data.borrow().clone()
+    }
+
+    fn update(&self, model: &UnitEntry) {
+        let old = self.model();
+        set_if_changed(self, "description", &old.description, &model.description);
+        set_if_changed(self, "sub-state", &old.sub_state, &model.sub_state);
+        set_if_changed(self, "file-state", &old.file_state, &model.file_state);
+    }
+}
+
+mod unit_imp {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[derive(Default, glib::Properties)]
+    #[properties(wrapper_type = super::UnitObject)]
+    pub struct UnitObject {
+        #[property(name = "name", get = |o: &Self| o.data.borrow().name.clone(), type = String)]
+        #[property(name = "description", get, set, type = String, member = description)]
+        #[property(name = "sub-state", get, set, type = String, member = sub_state)]
+        #[property(name = "file-state", get, set, type = String, member = file_state)]
+        pub data: RefCell<UnitEntry>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for UnitObject {
+        const NAME: &'static str = "SecurityCenterUnitObject";
+        type Type = super::UnitObject;
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for UnitObject {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_span() {
+        assert_eq!(changed_span(&[1, 2, 3], &[1, 2, 3]), (3, 0, &[][..]));
+        assert_eq!(changed_span(&[1, 2, 3], &[1, 4, 3]), (1, 1, &[4][..]));
+        assert_eq!(changed_span(&[1, 2, 3], &[1, 2, 5, 3]), (2, 0, &[5][..]));
+        assert_eq!(changed_span(&[1, 2, 3], &[3]), (0, 2, &[][..]));
+        assert_eq!(changed_span(&[], &[7, 8]), (0, 0, &[7, 8][..]));
+        // Repeated items are not counted twice
+        assert_eq!(changed_span(&[1, 1], &[1]), (1, 1, &[][..]));
+    }
+
+    #[test]
+    fn test_consolidated_port_key() {
+        let port = ConsolidatedPort::from_port(&Port::with_zone(8080, "tcp", "public"));
+        let key = ConsolidatedPortObject::key(&port);
+
+        let mut described = port.clone();
+        described.description = Some("Staging server".to_string());
+        assert_ne!(ConsolidatedPortObject::key(&described), key);
+
+        let mut temporary = port.clone();
+        temporary.expires_at = Some(1_700_000_000);
+        assert_ne!(ConsolidatedPortObject::key(&temporary), key);
+        assert_eq!(ConsolidatedPortObject::key(&port.clone()), key);
+    }
+}
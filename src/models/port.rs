@@ -4,6 +4,7 @@
 
 //! Firewall port model.
 
+use super::{RichRule, RuleAction};
 use crate::validation::{format_port_spec, parse_port_spec};
 
 /// A firewall port rule covering a single port or an inclusive range.
@@ -65,6 +66,17 @@ impl Port {
         self.end_number.is_some_and(|end| end > self.number)
     }
 
+    /// Whether the rule rejects or drops the traffic.
+    pub fn is_blocked(&self) -> bool {
+        matches!(self.action.as_str(), "deny" | "reject" | "drop")
+    }
+
+    /// How many ports the rule covers: 1, or the size of the range.
+    pub fn port_count(&self) -> u32 {
+        let end = self.end_number.unwrap_or(self.number).max(self.number);
+        u32::from(end - self.number) + 1
+    }
+
     /// The firewalld port string: "8080" or "10-20".
     pub fn port_spec(&self) -> String {
         format_port_spec(self.number, self.end_number.unwrap_or(self.number))
@@ -124,32 +136,14 @@ impl Port {
     /// Port ranges like port="10-20" are also supported.
     /// Returns Some(Port) if this is a port reject/drop rule, None otherwise.
     pub fn parse_from_rich_rule(rule: &str, zone: &str) -> Option<Self> {
-        // Check if this is a port reject or drop rule
-        if !rule.contains("port port=") || (!rule.contains("reject") && !rule.contains("drop")) {
-            return None;
-        }
-
-        // Extract port spec: port="80" or port="10-20"
-        let port_start = rule.find("port port=\"")?;
-        let port_value_start = port_start + 11; // length of 'port port="'
-        let remaining = &rule[port_value_start..];
-        let port_end = remaining.find('"')?;
-        let port_str = &remaining[..port_end];
-        let (range_start, range_end) = parse_port_spec(port_str)?;
-
-        // Extract protocol: protocol="tcp" or protocol="udp"
-        let proto_start = rule.find("protocol=\"")?;
-        let proto_value_start = proto_start + 10; // length of 'protocol="'
-        let remaining = &rule[proto_value_start..];
-        let proto_end = remaining.find('"')?;
-        let protocol = &remaining[..proto_end];
-
-        // Determine the action (reject or drop)
-        let action = if rule.contains("reject") {
-            "reject"
-        } else {
-            "drop"
+        let parsed = RichRule::parse(rule).ok()?;
+        // Only port reject or drop rules
+        let action = match parsed.action {
+            Some(RuleAction::Reject(_)) => "reject",
+            Some(RuleAction::Drop) => "drop",
+            _ => return None,
         };
+        let ((range_start, range_end), protocol) = parsed.port()?;
 
         Some(Self {
             number: range_start,
@@ -0,0 +1,868 @@
+// Security Center - Rich Rule Model
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! firewalld rich rules.
+//!
+//! [`RichRule`] is the structured form of the `rule ...` strings firewalld
+//! keeps per zone (firewalld.richlanguage(5)). [`RichRule::parse`] reads
+//! that syntax and `Display` writes it back in the order firewalld prints
+//! it, so a rule read from firewalld comes out as the same string.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::net::IpAddr;
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::validation::parse_port_spec;
+
+/// Protocols a rich rule port, source port or forward port can use.
+pub const RULE_PORT_PROTOCOLS: [&str; 4] = ["tcp", "udp", "sctp", "dccp"];
+
+/// Log levels of the `log` element.
+pub const RULE_LOG_LEVELS: [&str; 8] = [
+    "emerg", "alert", "crit", "error", "warning", "notice", "info", "debug",
+];
+
+/// Address family a rule is limited to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Family {
+    Ipv4,
+    Ipv6,
+}
+
+impl Family {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ipv4 => "ipv4",
+            Self::Ipv6 => "ipv6",
+        }
+    }
+
+    fn parse(s: &str) -> Result<Self> {
+        match s {
+            "ipv4" => Ok(Self::Ipv4),
+            "ipv6" => Ok(Self::Ipv6),
+            _ => Err(anyhow!("Unknown family: {}", s)),
+        }
+    }
+}
+
+/// How a source or destination is given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressKind {
+    /// An address or network in CIDR notation.
+    Address,
+    Mac,
+    Ipset,
+}
+
+impl AddressKind {
+    fn attribute(&self) -> &'static str {
+        match self {
+            Self::Address => "address",
+            Self::Mac => "mac",
+            Self::Ipset => "ipset",
+        }
+    }
+}
+
+/// The source or destination of a rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleAddress {
+    pub kind: AddressKind,
+    pub value: String,
+    /// Match everything except this address (`not`).
+    pub invert: bool,
+}
+
+impl RuleAddress {
+    /// Read an address typed by the user: an address or network, a MAC
+    /// address, or `ipset:NAME`.
+    pub fn from_input(text: &str) -> Result<Self> {
+        let text = text.trim();
+        let (kind, value) = match text.strip_prefix("ipset:") {
+            Some(name) => (AddressKind::Ipset, name),
+            None if is_mac(text) => (AddressKind::Mac, text),
+            None => (AddressKind::Address, text),
+        };
+        let address = Self {
+            kind,
+            value: value.to_string(),
+            invert: false,
+        };
+        address.validate()?;
+        Ok(address)
+    }
+
+    /// The address as [`from_input`](Self::from_input) reads it.
+    pub fn input_text(&self) -> String {
+        match self.kind {
+            AddressKind::Ipset => format!("ipset:{}", self.value),
+            _ => self.value.clone(),
+        }
+    }
+
+    /// The IP family of an address or network, if it is one.
+    fn family(&self) -> Option<Family> {
+        if self.kind != AddressKind::Address {
+            return None;
+        }
+        let network = self.value.split('/').next()?;
+        match network.parse::<IpAddr>().ok()? {
+            IpAddr::V4(_) => Some(Family::Ipv4),
+            IpAddr::V6(_) => Some(Family::Ipv6),
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        let valid = match self.kind {
+            AddressKind::Address => {
+                let (network, prefix) = match self.value.split_once('/') {
+                    Some((network, prefix)) => (network, Some(prefix)),
+                    None => (self.value.as_str(), None),
+                };
+                match network.parse::<IpAddr>() {
+                    Ok(ip) => {
+                        let max = if ip.is_ipv4() { 32 } else { 128 };
+                        match prefix {
+                            Some(prefix) => prefix.parse::<u8>().is_ok_and(|p| p <= max),
+                            None => true,
+                        }
+                    }
+                    Err(_) => false,
+                }
+            }
+            AddressKind::Mac => is_mac(&self.value),
+            AddressKind::Ipset => is_name(&self.value),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid {}: {}", self.kind.attribute(), self.value))
+        }
+    }
+}
+
+impl fmt::Display for RuleAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.invert {
+            write!(f, "not ")?;
+        }
+        write!(f, "{}=\"{}\"", self.kind.attribute(), self.value)
+    }
+}
+
+/// What a rule matches besides its addresses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleElement {
+    Service(String),
+    Port {
+        port: String,
+        protocol: String,
+    },
+    /// A protocol name from /etc/protocols, or its number.
+    Protocol(String),
+    IcmpBlock(String),
+    IcmpType(String),
+    Masquerade,
+    ForwardPort {
+        port: String,
+        protocol: String,
+        to_port: Option<String>,
+        to_addr: Option<String>,
+    },
+    SourcePort {
+        port: String,
+        protocol: String,
+    },
+}
+
+impl RuleElement {
+    /// Elements that carry their own verdict and take no action.
+    fn is_self_acting(&self) -> bool {
+        matches!(
+            self,
+            Self::Masquerade | Self::IcmpBlock(_) | Self::ForwardPort { .. }
+        )
+    }
+
+    fn validate(&self) -> Result<()> {
+        let check_port = |port: &str, protocol: &str| -> Result<()> {
+            parse_port_spec(port).ok_or_else(|| anyhow!("Invalid port: {}", port))?;
+            if !RULE_PORT_PROTOCOLS.contains(&protocol) {
+                bail!("Invalid protocol: {}", protocol);
+            }
+            Ok(())
+        };
+        match self {
+            Self::Service(name) | Self::IcmpBlock(name) | Self::IcmpType(name) => {
+                if !is_name(name) {
+                    bail!("Invalid name: {}", name);
+                }
+            }
+            Self::Protocol(protocol) => {
+                if !is_name(protocol) {
+                    bail!("Invalid protocol: {}", protocol);
+                }
+            }
+            Self::Masquerade => {}
+            Self::Port { port, protocol } | Self::SourcePort { port, protocol } => {
+                check_port(port, protocol)?;
+            }
+            Self::ForwardPort {
+                port,
+                protocol,
+                to_port,
+                to_addr,
+            } => {
+                check_port(port, protocol)?;
+                if to_port.is_none() && to_addr.is_none() {
+                    bail!("A forward port needs a target port or address");
+                }
+                if let Some(to_port) = to_port {
+                    parse_port_spec(to_port).ok_or_else(|| anyhow!("Invalid port: {}", to_port))?;
+                }
+                if let Some(to_addr) = to_addr {
+                    to_addr
+                        .parse::<IpAddr>()
+                        .map_err(|_| anyhow!("Invalid address: {}", to_addr))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for RuleElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Service(name) => write!(f, "service name=\"{}\"", name),
+            Self::Port { port, protocol } => {
+                write!(f, "port port=\"{}\" protocol=\"{}\"", port, protocol)
+            }
+            Self::Protocol(protocol) => write!(f, "protocol value=\"{}\"", protocol),
+            Self::IcmpBlock(name) => write!(f, "icmp-block name=\"{}\"", name),
+            Self::IcmpType(name) => write!(f, "icmp-type name=\"{}\"", name),
+            Self::Masquerade => write!(f, "masquerade"),
+            Self::ForwardPort {
+                port,
+                protocol,
+                to_port,
+                to_addr,
+            } => {
+                write!(
+                    f,
+                    "forward-port port=\"{}\" protocol=\"{}\"",
+                    port, protocol
+                )?;
+                if let Some(to_port) = to_port {
+                    write!(f, " to-port=\"{}\"", to_port)?;
+                }
+                if let Some(to_addr) = to_addr {
+                    write!(f, " to-addr=\"{}\"", to_addr)?;
+                }
+                Ok(())
+            }
+            Self::SourcePort { port, protocol } => {
+                write!(f, "source-port port=\"{}\" protocol=\"{}\"", port, protocol)
+            }
+        }
+    }
+}
+
+/// Logging of the packets a rule matches.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleLog {
+    pub prefix: Option<String>,
+    /// One of [`RULE_LOG_LEVELS`].
+    pub level: Option<String>,
+    /// Rate limit such as `3/m`.
+    pub limit: Option<String>,
+}
+
+/// Verdict of a rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleAction {
+    Accept,
+    /// Reject, optionally with this ICMP reject type.
+    Reject(Option<String>),
+    Drop,
+    /// Mark the packets with this mark (`mark[/mask]`).
+    Mark(String),
+}
+
+impl RuleAction {
+    /// Whether the rule stops traffic.
+    pub fn is_deny(&self) -> bool {
+        matches!(self, Self::Reject(_) | Self::Drop)
+    }
+}
+
+impl fmt::Display for RuleAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Accept => write!(f, "accept"),
+            Self::Reject(None) => write!(f, "reject"),
+            Self::Reject(Some(kind)) => write!(f, "reject type=\"{}\"", kind),
+            Self::Drop => write!(f, "drop"),
+            Self::Mark(set) => write!(f, "mark set=\"{}\"", set),
+        }
+    }
+}
+
+/// A firewalld rich rule.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RichRule {
+    /// Order among rich rules; lower runs first, 0 is the default.
+    pub priority: i32,
+    pub family: Option<Family>,
+    pub source: Option<RuleAddress>,
+    pub destination: Option<RuleAddress>,
+    pub element: Option<RuleElement>,
+    pub log: Option<RuleLog>,
+    /// Audit the matched packets, with an optional rate limit.
+    pub audit: Option<Option<String>>,
+    pub action: Option<RuleAction>,
+    /// Rate limit of the action, such as `3/m`.
+    pub action_limit: Option<String>,
+}
+
+impl RichRule {
+    /// Rule rejecting a port or range over IPv4 and IPv6.
+    pub fn port_block(port_spec: &str, protocol: &str) -> Self {
+        Self {
+            element: Some(RuleElement::Port {
+                port: port_spec.to_string(),
+                protocol: protocol.to_string(),
+            }),
+            action: Some(RuleAction::Reject(None)),
+            ..Default::default()
+        }
+    }
+
+    /// Rule dropping everything from `source`.
+    pub fn source_drop(source: IpAddr) -> Self {
+        let family = match source {
+            IpAddr::V4(_) => Family::Ipv4,
+            IpAddr::V6(_) => Family::Ipv6,
+        };
+        Self {
+            family: Some(family),
+            source: Some(RuleAddress {
+                kind: AddressKind::Address,
+                value: source.to_string(),
+                invert: false,
+            }),
+            action: Some(RuleAction::Drop),
+            ..Default::default()
+        }
+    }
+
+    /// The port range and protocol of a `port` element, with end equal to
+    /// start for a single port.
+    pub fn port(&self) -> Option<((u16, u16), &str)> {
+        match &self.element {
+            Some(RuleElement::Port { port, protocol }) => {
+                Some((parse_port_spec(port)?, protocol.as_str()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse a rule in firewalld's rich language.
+    pub fn parse(text: &str) -> Result<Self> {
+        let tokens = tokenize(text)?;
+        let mut tokens = Tokens { tokens, pos: 0 };
+        if tokens.word().as_deref() != Some("rule") {
+            bail!("A rich rule starts with 'rule'");
+        }
+
+        let mut rule = Self::default();
+        while let Some(token) = tokens.next() {
+            match token {
+                Token::Attr(key, value) if key == "family" => {
+                    rule.family = Some(Family::parse(&value)?);
+                }
+                Token::Attr(key, value) if key == "priority" => {
+                    rule.priority = value
+                        .parse()
+                        .map_err(|_| anyhow!("Invalid priority: {}", value))?;
+                }
+                Token::Word(word) => match word.as_str() {
+                    "source" => rule.source = Some(tokens.address()?),
+                    "destination" => rule.destination = Some(tokens.address()?),
+                    "log" => {
+                        let mut attrs = tokens.attrs(&["prefix", "level"]);
+                        rule.log = Some(RuleLog {
+                            prefix: attrs.remove("prefix"),
+                            level: attrs.remove("level"),
+                            limit: tokens.limit()?,
+                        });
+                    }
+                    "audit" => rule.audit = Some(tokens.limit()?),
+                    "accept" | "reject" | "drop" | "mark" => {
+                        if rule.action.is_some() {
+                            bail!("A rule has at most one action");
+                        }
+                        rule.action = Some(match word.as_str() {
+                            "accept" => RuleAction::Accept,
+                            "reject" => RuleAction::Reject(tokens.attrs(&["type"]).remove("type")),
+                            "drop" => RuleAction::Drop,
+                            _ => {
+                                RuleAction::Mark(tokens.required(&["set"])?.remove("set").unwrap())
+                            }
+                        });
+                        rule.action_limit = tokens.limit()?;
+                    }
+                    _ => {
+                        if rule.element.is_some() {
+                            bail!("A rule has at most one element");
+                        }
+                        rule.element = Some(tokens.element(&word)?);
+                    }
+                },
+                Token::Attr(key, _) => bail!("Unexpected attribute: {}", key),
+            }
+        }
+        Ok(rule)
+    }
+
+    /// Check the rule the way firewalld would before accepting it.
+    pub fn validate(&self) -> Result<()> {
+        if !(-32768..=32767).contains(&self.priority) {
+            bail!("Priority must be between -32768 and 32767");
+        }
+        for address in [&self.source, &self.destination].into_iter().flatten() {
+            address.validate()?;
+            if let (Some(family), Some(needed)) = (self.family, address.family()) {
+                if family != needed {
+                    bail!("{} is not an {} address", address.value, family.as_str());
+                }
+            }
+        }
+        if let (Some(source), Some(destination)) = (&self.source, &self.destination) {
+            if let (Some(a), Some(b)) = (source.family(), destination.family()) {
+                if a != b {
+                    bail!("Source and destination are of different families");
+                }
+            }
+        }
+        if let Some(element) = &self.element {
+            element.validate()?;
+            if element.is_self_acting() && self.action.is_some() {
+                bail!("Masquerade, ICMP block and forward port rules take no action");
+            }
+            if !element.is_self_acting()
+                && self.action.is_none()
+                && self.log.is_none()
+                && self.audit.is_none()
+            {
+                bail!("Choose an action, logging or auditing");
+            }
+        } else if self.action.is_none() && self.log.is_none() && self.audit.is_none() {
+            bail!("Choose an action, logging or auditing");
+        }
+        if self.element.is_none()
+            && self.source.is_none()
+            && self.destination.is_none()
+            && self.action.is_some()
+        {
+            bail!("A rule without a source, destination or element would match everything");
+        }
+        if let Some(log) = &self.log {
+            if let Some(prefix) = &log.prefix {
+                if prefix.contains(['"', '\n']) || prefix.len() > 127 {
+                    bail!("Invalid log prefix");
+                }
+            }
+            if let Some(level) = &log.level {
+                if !RULE_LOG_LEVELS.contains(&level.as_str()) {
+                    bail!("Invalid log level: {}", level);
+                }
+            }
+        }
+        let limits = [
+            self.log.as_ref().and_then(|log| log.limit.as_ref()),
+            self.audit.as_ref().and_then(|limit| limit.as_ref()),
+            self.action_limit.as_ref(),
+        ];
+        for limit in limits.into_iter().flatten() {
+            if !is_limit(limit) {
+                bail!("Invalid limit: {} (use e.g. 3/m)", limit);
+            }
+        }
+        match &self.action {
+            Some(RuleAction::Reject(Some(kind))) if !is_name(kind) => {
+                bail!("Invalid reject type: {}", kind);
+            }
+            Some(RuleAction::Mark(set)) if !is_mark(set) => {
+                bail!("Invalid mark: {}", set);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for RichRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rule")?;
+        if self.priority != 0 {
+            write!(f, " priority=\"{}\"", self.priority)?;
+        }
+        if let Some(family) = self.family {
+            write!(f, " family=\"{}\"", family.as_str())?;
+        }
+        if let Some(source) = &self.source {
+            write!(f, " source {}", source)?;
+        }
+        if let Some(destination) = &self.destination {
+            write!(f, " destination {}", destination)?;
+        }
+        if let Some(element) = &self.element {
+            write!(f, " {}", element)?;
+        }
+        if let Some(log) = &self.log {
+            write!(f, " log")?;
+            if let Some(prefix) = &log.prefix {
+                write!(f, " prefix=\"{}\"", prefix)?;
+            }
+            if let Some(level) = &log.level {
+                write!(f, " level=\"{}\"", level)?;
+            }
+            if let Some(limit) = &log.limit {
+                write!(f, " limit value=\"{}\"", limit)?;
+            }
+        }
+        if let Some(limit) = &self.audit {
+            write!(f, " audit")?;
+            if let Some(limit) = limit {
+                write!(f, " limit value=\"{}\"", limit)?;
+            }
+        }
+        if let Some(action) = &self.action {
+            write!(f, " {}", action)?;
+            if let Some(limit) = &self.action_limit {
+                write!(f, " limit value=\"{}\"", limit)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    /// `key="value"`, `key='value'` or `key=value`.
+    Attr(String, String),
+}
+
+/// Split a rule into words and attributes; quoted values may hold spaces.
+fn tokenize(text: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.trim().chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut word = String::new();
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() || c == '=' {
+                break;
+            }
+            word.push(c);
+            chars.next();
+        }
+        if chars.peek() != Some(&'=') {
+            tokens.push(Token::Word(word));
+            continue;
+        }
+        chars.next();
+        let mut value = String::new();
+        match chars.peek() {
+            Some(&quote) if quote == '"' || quote == '\'' => {
+                chars.next();
+                loop {
+                    match chars.next() {
+                        Some(c) if c == quote => break,
+                        Some(c) => value.push(c),
+                        None => bail!("Unterminated quote after {}=", word),
+                    }
+                }
+            }
+            _ => {
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() {
+                        break;
+                    }
+                    value.push(c);
+                    chars.next();
+                }
+            }
+        }
+        if word.is_empty() {
+            bail!("Attribute without a name");
+        }
+        tokens.push(Token::Attr(word, value));
+    }
+    Ok(tokens)
+}
+
+/// Cursor over the tokens of a rule.
+struct Tokens {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Tokens {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn word(&mut self) -> Option<String> {
+        match self.next() {
+            Some(Token::Word(word)) => Some(word),
+            _ => None,
+        }
+    }
+
+    /// The attributes that follow, all of them among `allowed`.
+    fn attrs(&mut self, allowed: &[&str]) -> HashMap<String, String> {
+        let mut attrs = HashMap::new();
+        while let Some(Token::Attr(key, value)) = self.peek() {
+            if !allowed.contains(&key.as_str()) {
+                break;
+            }
+            attrs.insert(key.clone(), value.clone());
+            self.pos += 1;
+        }
+        attrs
+    }
+
+    /// Like [`attrs`](Self::attrs), with every one of `keys` present.
+    fn required(&mut self, keys: &[&str]) -> Result<HashMap<String, String>> {
+        let attrs = self.attrs(keys);
+        if let Some(missing) = keys.iter().find(|key| !attrs.contains_key(**key)) {
+            bail!("Missing {}=", missing);
+        }
+        Ok(attrs)
+    }
+
+    /// An optional `limit value="..."`.
+    fn limit(&mut self) -> Result<Option<String>> {
+        if self.peek() != Some(&Token::Word("limit".to_string())) {
+            return Ok(None);
+        }
+        self.pos += 1;
+        Ok(self.required(&["value"])?.remove("value"))
+    }
+
+    /// `[not] address|mac|ipset="..."` after `source` or `destination`.
+    fn address(&mut self) -> Result<RuleAddress> {
+        let invert = self.peek() == Some(&Token::Word("not".to_string()));
+        if invert {
+            self.pos += 1;
+        }
+        let mut attrs = self.attrs(&["address", "mac", "ipset"]);
+        if attrs.len() != 1 {
+            bail!("A source or destination needs one address, mac or ipset");
+        }
+        let (kind, value) = if let Some(value) = attrs.remove("address") {
+            (AddressKind::Address, value)
+        } else if let Some(value) = attrs.remove("mac") {
+            (AddressKind::Mac, value)
+        } else {
+            (
+                AddressKind::Ipset,
+                attrs.remove("ipset").unwrap_or_default(),
+            )
+        };
+        Ok(RuleAddress {
+            kind,
+            value,
+            invert,
+        })
+    }
+
+    /// The element named `word` and its attributes.
+    fn element(&mut self, word: &str) -> Result<RuleElement> {
+        Ok(match word {
+            "service" => RuleElement::Service(self.required(&["name"])?.remove("name").unwrap()),
+            "port" => {
+                let mut attrs = self.required(&["port", "protocol"])?;
+                RuleElement::Port {
+                    port: attrs.remove("port").unwrap(),
+                    protocol: attrs.remove("protocol").unwrap(),
+                }
+            }
+            "protocol" => {
+                RuleElement::Protocol(self.required(&["value"])?.remove("value").unwrap())
+            }
+            "icmp-block" => {
+                RuleElement::IcmpBlock(self.required(&["name"])?.remove("name").unwrap())
+            }
+            "icmp-type" => RuleElement::IcmpType(self.required(&["name"])?.remove("name").unwrap()),
+            "masquerade" => RuleElement::Masquerade,
+            "forward-port" => {
+                let mut attrs = self.attrs(&["port", "protocol", "to-port", "to-addr"]);
+                RuleElement::ForwardPort {
+                    port: attrs
+                        .remove("port")
+                        .ok_or_else(|| anyhow!("Missing port="))?,
+                    protocol: attrs
+                        .remove("protocol")
+                        .ok_or_else(|| anyhow!("Missing protocol="))?,
+                    to_port: attrs.remove("to-port"),
+                    to_addr: attrs.remove("to-addr"),
+                }
+            }
+            "source-port" => {
+                let mut attrs = self.required(&["port", "protocol"])?;
+                RuleElement::SourcePort {
+                    port: attrs.remove("port").unwrap(),
+                    protocol: attrs.remove("protocol").unwrap(),
+                }
+            }
+            _ => bail!("Unknown rule element: {}", word),
+        })
+    }
+}
+
+/// A name made of letters, digits and `-_.+`, as used for services, ICMP
+/// types, ipsets and protocols.
+fn is_name(s: &str) -> bool {
+    !s.is_empty()
+        && s.len() <= 64
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '+'))
+}
+
+fn is_mac(s: &str) -> bool {
+    let parts: Vec<&str> = s.split(':').collect();
+    parts.len() == 6
+        && parts
+            .iter()
+            .all(|p| p.len() == 2 && p.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// A rate limit: a number, `/` and one of s, m, h or d.
+fn is_limit(s: &str) -> bool {
+    match s.split_once('/') {
+        Some((count, unit)) => {
+            count.parse::<u32>().is_ok_and(|n| n > 0) && matches!(unit, "s" | "m" | "h" | "d")
+        }
+        None => false,
+    }
+}
+
+/// A mark, optionally with a mask: `0x10` or `0x10/0xff`.
+fn is_mark(s: &str) -> bool {
+    let number = |n: &str| match n.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16).is_ok(),
+        None => n.parse::<u32>().is_ok(),
+    };
+    match s.split_once('/') {
+        Some((mark, mask)) => number(mark) && number(mask),
+        None => number(s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for text in [
+            r#"rule port port="80" protocol="tcp" reject"#,
+            r#"rule family="ipv4" port port="8000-9000" protocol="udp" drop"#,
+            r#"rule family="ipv4" source address="203.0.113.0/24" drop"#,
+            r#"rule priority="-10" family="ipv6" source not address="2001:db8::/32" service name="ssh" log prefix="ssh in " level="info" limit value="3/m" accept"#,
+            r#"rule source mac="00:11:22:33:44:55" destination ipset="blocklist" protocol value="icmp" audit limit value="1/s" reject type="icmp-host-prohibited" limit value="5/m""#,
+            r#"rule family="ipv4" forward-port port="80" protocol="tcp" to-port="8080" to-addr="192.0.2.1""#,
+            r#"rule family="ipv4" source address="192.0.2.0/24" masquerade"#,
+            r#"rule icmp-block name="echo-request""#,
+        ] {
+            let rule = RichRule::parse(text).unwrap();
+            assert_eq!(rule.to_string(), text);
+            rule.validate().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_parse_forms() {
+        // Unquoted and single-quoted values, attribute order and spacing
+        let rule =
+            RichRule::parse("rule  family=ipv4 priority='5' port protocol=tcp port=22  accept")
+                .unwrap();
+        assert_eq!(rule.priority, 5);
+        assert_eq!(rule.family, Some(Family::Ipv4));
+        assert_eq!(rule.port(), Some(((22, 22), "tcp")));
+        assert_eq!(rule.action, Some(RuleAction::Accept));
+
+        assert!(RichRule::parse("port port=\"80\" protocol=\"tcp\" accept").is_err());
+        assert!(RichRule::parse("rule port port=\"80\" accept").is_err());
+        assert!(RichRule::parse("rule frobnicate accept").is_err());
+        assert!(RichRule::parse("rule source address=\"10.0.0.1 accept").is_err());
+        assert!(RichRule::parse("rule service name=\"ssh\" accept drop").is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let valid = RichRule::port_block("8080", "tcp");
+        assert_eq!(
+            valid.to_string(),
+            r#"rule port port="8080" protocol="tcp" reject"#
+        );
+        valid.validate().unwrap();
+        assert_eq!(
+            RichRule::source_drop("2001:db8::1".parse().unwrap()).to_string(),
+            r#"rule family="ipv6" source address="2001:db8::1" drop"#
+        );
+
+        let invalid = [
+            // Matches everything
+            "rule accept",
+            // Nothing to do
+            r#"rule service name="ssh""#,
+            // Family mismatch
+            r#"rule family="ipv6" source address="10.0.0.0/8" accept"#,
+            r#"rule source address="10.0.0.0/33" accept"#,
+            r#"rule port port="0" protocol="tcp" accept"#,
+            r#"rule port port="80" protocol="icmp" accept"#,
+            r#"rule masquerade accept"#,
+            r#"rule service name="ssh" log level="loud" accept"#,
+            r#"rule service name="ssh" accept limit value="often""#,
+        ];
+        for text in invalid {
+            let rule = RichRule::parse(text).unwrap();
+            assert!(rule.validate().is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_address_input() {
+        let address = RuleAddress::from_input(" 192.0.2.0/24 ").unwrap();
+        assert_eq!(address.kind, AddressKind::Address);
+        assert_eq!(address.input_text(), "192.0.2.0/24");
+
+        let address = RuleAddress::from_input("AA:bb:cc:dd:ee:ff").unwrap();
+        assert_eq!(address.kind, AddressKind::Mac);
+
+        let address = RuleAddress::from_input("ipset:blocklist").unwrap();
+        assert_eq!(address.kind, AddressKind::Ipset);
+        assert_eq!(address.input_text(), "ipset:blocklist");
+
+        assert!(RuleAddress::from_input("example.com").is_err());
+        assert!(RuleAddress::from_input("ipset:bad name").is_err());
+    }
+}
@@ -4,12 +4,23 @@
 
 //! Firewall service model.
 
+use crate::validation::parse_port_spec;
+
 /// A firewall service definition.
 #[derive(Debug, Clone, Default)]
 pub struct Service {
     pub name: String,
     pub description: String,
     pub ports: Vec<(String, String)>, // (port, protocol)
+    /// Connection tracking helpers the service attaches, e.g. `ftp`.
+    pub helpers: Vec<String>,
+    /// IP protocols let through as a whole, e.g. `gre`.
+    pub protocols: Vec<String>,
+    /// Source ports of the traffic, as (port, protocol).
+    pub source_ports: Vec<(String, String)>,
+    /// Destination address per family (`ipv4`, `ipv6`) the service is
+    /// limited to, sorted by family.
+    pub destinations: Vec<(String, String)>,
     pub is_enabled: bool,
 }
 
@@ -54,6 +65,24 @@ impl Service {
         }
     }
 
+    /// Whether the lowercase `query` matches the name, the description or,
+    /// as a number, a port the service opens.
+    pub fn matches_query(&self, query: &str) -> bool {
+        let query = query.trim();
+        if query.is_empty()
+            || self.name.to_lowercase().contains(query)
+            || self.human_description().to_lowercase().contains(query)
+        {
+            return true;
+        }
+        let Ok(number) = query.parse::<u16>() else {
+            return false;
+        };
+        self.ports.iter().any(|(port, _)| {
+            parse_port_spec(port).is_some_and(|(start, end)| (start..=end).contains(&number))
+        })
+    }
+
     /// Get a summary of the ports used by this service.
     pub fn ports_summary(&self) -> String {
         if self.ports.is_empty() {
@@ -74,3 +103,25 @@ impl Service {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_query() {
+        let mut service = Service::new("sip");
+        service.description = "Session Initiation Protocol".to_string();
+        service.ports = vec![
+            ("5060".to_string(), "tcp".to_string()),
+            ("10000-20000".to_string(), "udp".to_string()),
+        ];
+        assert!(service.matches_query(""));
+        assert!(service.matches_query("si"));
+        assert!(service.matches_query("initiation"));
+        assert!(service.matches_query("5060"));
+        assert!(service.matches_query("15000"));
+        assert!(!service.matches_query("5061"));
+        assert!(!service.matches_query("http"));
+    }
+}
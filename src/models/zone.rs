@@ -4,6 +4,8 @@
 
 //! Firewall zone model.
 
+use super::RichRule;
+
 /// A firewall zone.
 #[derive(Debug, Clone, Default)]
 pub struct Zone {
@@ -19,6 +21,22 @@ pub struct Zone {
     pub rich_rules: Vec<String>,
     pub masquerade: bool,
     pub forward: bool,
+    /// ICMP types blocked, or the only ones let through when
+    /// `icmp_block_inversion` is on.
+    pub icmp_blocks: Vec<String>,
+    pub icmp_block_inversion: bool,
+    /// The zone's rules in the permanent configuration, when they could be
+    /// read. Rules above that are missing here are lost on reload.
+    pub permanent: Option<ZoneRules>,
+}
+
+/// The services, ports and rich rules of a zone's configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ZoneRules {
+    pub services: Vec<String>,
+    /// Ports as "80/tcp" or "10-20/udp".
+    pub ports: Vec<String>,
+    pub rich_rules: Vec<String>,
 }
 
 impl Zone {
@@ -30,6 +48,115 @@ impl Zone {
         }
     }
 
+    /// Whether the zone carries traffic: it is the default zone or has
+    /// interfaces or sources bound to it.
+    pub fn is_in_use(&self) -> bool {
+        self.is_default || !self.interfaces.is_empty() || !self.sources.is_empty()
+    }
+
+    /// Whether `service` is enabled at runtime only. False when the
+    /// permanent configuration is unknown.
+    pub fn is_runtime_only_service(&self, service: &str) -> bool {
+        self.permanent
+            .as_ref()
+            .is_some_and(|permanent| !permanent.services.iter().any(|s| s == service))
+    }
+
+    /// Whether `port` ("80/tcp") is open at runtime only.
+    pub fn is_runtime_only_port(&self, port: &str) -> bool {
+        self.permanent
+            .as_ref()
+            .is_some_and(|permanent| !permanent.ports.iter().any(|p| p == port))
+    }
+
+    /// Whether `rule` exists at runtime only. Rules are compared as parsed,
+    /// since firewalld may write the same rule with different spacing or
+    /// quoting.
+    pub fn is_runtime_only_rich_rule(&self, rule: &str) -> bool {
+        let parsed = RichRule::parse(rule).ok();
+        self.permanent.as_ref().is_some_and(|permanent| {
+            !permanent.rich_rules.iter().any(|other| {
+                other == rule || parsed.is_some() && RichRule::parse(other).ok() == parsed
+            })
+        })
+    }
+
+    /// The services, ports and rich rules that differ between runtime and
+    /// the permanent configuration, in either direction: those a reload or
+    /// reboot would drop, and those it would bring back. Empty when the
+    /// permanent configuration is unknown.
+    pub fn drift(&self) -> Vec<DriftItem> {
+        let Some(permanent) = &self.permanent else {
+            return Vec::new();
+        };
+        let item = |kind, side, value: &String| DriftItem {
+            kind,
+            side,
+            value: value.clone(),
+        };
+        let mut items: Vec<DriftItem> = self
+            .services
+            .iter()
+            .filter(|s| self.is_runtime_only_service(s))
+            .map(|s| item(DriftKind::Service, DriftSide::RuntimeOnly, s))
+            .chain(
+                self.ports
+                    .iter()
+                    .filter(|p| self.is_runtime_only_port(p))
+                    .map(|p| item(DriftKind::Port, DriftSide::RuntimeOnly, p)),
+            )
+            .chain(
+                self.rich_rules
+                    .iter()
+                    .filter(|r| self.is_runtime_only_rich_rule(r))
+                    .map(|r| item(DriftKind::RichRule, DriftSide::RuntimeOnly, r)),
+            )
+            .collect();
+
+        // The other direction: the runtime rules as if they were permanent
+        let runtime = Zone {
+            permanent: Some(ZoneRules {
+                services: self.services.clone(),
+                ports: self.ports.clone(),
+                rich_rules: self.rich_rules.clone(),
+            }),
+            ..Zone::default()
+        };
+        items.extend(
+            permanent
+                .services
+                .iter()
+                .filter(|s| runtime.is_runtime_only_service(s))
+                .map(|s| item(DriftKind::Service, DriftSide::PermanentOnly, s))
+                .chain(
+                    permanent
+                        .ports
+                        .iter()
+                        .filter(|p| runtime.is_runtime_only_port(p))
+                        .map(|p| item(DriftKind::Port, DriftSide::PermanentOnly, p)),
+                )
+                .chain(
+                    permanent
+                        .rich_rules
+                        .iter()
+                        .filter(|r| runtime.is_runtime_only_rich_rule(r))
+                        .map(|r| item(DriftKind::RichRule, DriftSide::PermanentOnly, r)),
+                ),
+        );
+        items
+    }
+
+    /// How many services, ports and rich rules differ between runtime and
+    /// the permanent configuration; see [`Zone::drift`].
+    pub fn unsaved_changes(&self) -> usize {
+        self.drift().len()
+    }
+
+    /// How many of the [`Zone::unsaved_changes`] only `side` has.
+    pub fn drift_count(&self, side: DriftSide) -> usize {
+        self.drift().iter().filter(|item| item.side == side).count()
+    }
+
     /// Get the trust level of the zone (for sorting/display).
     pub fn trust_level(&self) -> i32 {
         match self.name.as_str() {
@@ -62,3 +189,140 @@ impl Zone {
         }
     }
 }
+
+/// What kind of rule differs between runtime and permanent configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftKind {
+    Service,
+    /// A port as "80/tcp".
+    Port,
+    RichRule,
+}
+
+impl DriftKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DriftKind::Service => "Service",
+            DriftKind::Port => "Port",
+            DriftKind::RichRule => "Rich Rule",
+        }
+    }
+}
+
+/// Which configuration has a rule the other lacks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftSide {
+    /// Added at runtime, or removed permanently; gone after a reload.
+    RuntimeOnly,
+    /// Removed at runtime, or added permanently; back after a reload.
+    PermanentOnly,
+}
+
+/// A service, port or rich rule present in only one of a zone's runtime and
+/// permanent configurations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DriftItem {
+    pub kind: DriftKind,
+    pub side: DriftSide,
+    /// The service name, "port/protocol" or rich rule, as the side that has
+    /// it writes it.
+    pub value: String,
+}
+
+/// Targets a zone can have: what happens to packets no rule of the zone
+/// matched. firewalld spells REJECT as `%%REJECT%%`.
+pub const ZONE_TARGETS: [&str; 4] = ["default", "ACCEPT", "%%REJECT%%", "DROP"];
+
+/// The editable part of a zone's permanent configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ZoneSettings {
+    pub short: String,
+    pub description: String,
+    /// One of [`ZONE_TARGETS`].
+    pub target: String,
+    /// ICMP types blocked, or the only ones allowed when inverted.
+    pub icmp_blocks: Vec<String>,
+    pub icmp_block_inversion: bool,
+    pub masquerade: bool,
+    /// Forward packets between interfaces and sources of the zone.
+    pub forward: bool,
+}
+
+/// Display name of a zone target.
+pub fn target_label(target: &str) -> &str {
+    match target {
+        "default" => "Default",
+        "%%REJECT%%" => "REJECT",
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runtime_only() {
+        let mut zone = Zone::new("public");
+        zone.services = vec!["ssh".to_string(), "http".to_string()];
+        // Without the permanent configuration nothing is flagged
+        assert!(!zone.is_runtime_only_service("http"));
+
+        zone.permanent = Some(ZoneRules {
+            services: vec!["ssh".to_string()],
+            ports: vec!["8080/tcp".to_string()],
+            rich_rules: vec![
+                r#"rule family="ipv4" port port="25" protocol="tcp" reject"#.to_string()
+            ],
+        });
+        assert!(!zone.is_runtime_only_service("ssh"));
+        assert!(zone.is_runtime_only_service("http"));
+        assert!(!zone.is_runtime_only_port("8080/tcp"));
+        assert!(zone.is_runtime_only_port("8080/udp"));
+        // The same rule written differently is still permanent
+        assert!(
+            !zone.is_runtime_only_rich_rule(r#"rule family=ipv4 port port=25 protocol=tcp reject"#)
+        );
+        assert!(zone.is_runtime_only_rich_rule(
+            r#"rule family="ipv4" port port="26" protocol="tcp" reject"#
+        ));
+    }
+
+    #[test]
+    fn test_unsaved_changes() {
+        let mut zone = Zone::new("public");
+        zone.services = vec!["ssh".to_string(), "http".to_string()];
+        zone.rich_rules = vec![r#"rule family=ipv4 port port=25 protocol=tcp reject"#.to_string()];
+        assert_eq!(zone.unsaved_changes(), 0);
+
+        zone.permanent = Some(ZoneRules {
+            services: vec!["ssh".to_string()],
+            ports: vec!["8080/tcp".to_string()],
+            rich_rules: vec![
+                r#"rule family="ipv4" port port="25" protocol="tcp" reject"#.to_string()
+            ],
+        });
+        // http added at runtime, 8080/tcp removed at runtime
+        assert_eq!(zone.unsaved_changes(), 2);
+        assert_eq!(zone.drift_count(DriftSide::RuntimeOnly), 1);
+        assert_eq!(zone.drift_count(DriftSide::PermanentOnly), 1);
+        assert_eq!(
+            zone.drift(),
+            vec![
+                DriftItem {
+                    kind: DriftKind::Service,
+                    side: DriftSide::RuntimeOnly,
+                    value: "http".to_string(),
+                },
+                DriftItem {
+                    kind: DriftKind::Port,
+                    side: DriftSide::PermanentOnly,
+                    value: "8080/tcp".to_string(),
+                },
+            ]
+        );
+        zone.services.pop();
+        zone.ports.push("8080/tcp".to_string());
+        assert_eq!(zone.unsaved_changes(), 0);
+    }
+}
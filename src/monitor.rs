@@ -0,0 +1,1089 @@
+// Security Center - Background Monitor
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Background monitor: the scheduled work and notifications of Security
+//! Center, run by the `security-center-monitor` binary as a user systemd
+//! service ([`UNIT_NAME`]) so they carry on while the application is closed.
+//!
+//! The monitor runs the scheduled leftovers cleanup, policy enforcement,
+//! blocklist refreshes ([`blocklists`]) and security scans
+//! ([`security_scan`]), counts data usage for the quotas,
+//! watches the tripwire ports, removes temporary port rules when they
+//! expire ([`rule_expiry`]) and locks the session when a security key is
+//! removed, if asked to, sending desktop notifications through
+//! `org.freedesktop.Notifications`. It owns [`BUS_NAME`] on the session bus
+//! and serves [`INTERFACE`] there:
+//!
+//! - `DataUsage() -> a(stt)`: interface, bytes used this month and monthly
+//!   quota of every interface with a quota
+//! - `Reload()`: read the settings again before the next check
+//!
+//! The application asks for the data usage every
+//! [`SAMPLE_INTERVAL`](data_usage::SAMPLE_INTERVAL). While the monitor
+//! answers, the application shows its counts and leaves the scheduled work,
+//! the quota alerts, the tripwire alerts and the scan alerts to it. The other way round,
+//! clicking a monitor notification reaches the application through its
+//! `org.freedesktop.Application` interface when it is running; otherwise the
+//! monitor starts it, or blocks the probing address or extends or removes
+//! the expiring rule itself.
+//!
+//! Settings are shared through the settings file, read again every sample
+//! interval.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use tracing::{debug, info, warn};
+use zbus::blocking::Connection;
+use zbus::zvariant::Value;
+
+use crate::admin::{
+    self, check_policy, clean_leftovers, cleanup_due, enforce_unattended, enforcement_due,
+    history_sizes, scan_leftovers, CleanupCategory, DeniedLog, Drift, KeyRemovalWatch, TripwireHit,
+    Tripwires,
+};
+use crate::blocklists;
+use crate::config::Settings;
+use crate::data_usage::{self, QuotaAlert, QuotaUsage, UsageMeter};
+use crate::i18n::{format_bytes, format_percent, format_time, gettext};
+use crate::rule_expiry::{self, ExpiringRule, ExpiryStep, ExpiryWatch};
+use crate::security_scan::{self, scan_due, ScanAlert};
+use crate::APP_ID;
+
+/// Well-known name of the monitor on the session bus.
+pub const BUS_NAME: &str = "com.chrisdaggas.security-center.Monitor";
+/// Object path of the monitor interface.
+pub const OBJECT_PATH: &str = "/com/chrisdaggas/security_center/Monitor";
+/// D-Bus interface of the monitor.
+pub const INTERFACE: &str = "com.chrisdaggas.SecurityCenter.Monitor";
+/// The user unit that runs the monitor.
+pub const UNIT_NAME: &str = "security-center-monitor.service";
+
+/// Object path GApplication derives from [`APP_ID`].
+const APP_OBJECT_PATH: &str = "/com/chrisdaggas/security_center";
+const NOTIFICATIONS: &str = "org.freedesktop.Notifications";
+const NOTIFICATIONS_PATH: &str = "/org/freedesktop/Notifications";
+
+/// Delay before the schedules are first checked, as in the application.
+const SCHEDULE_DELAY: Duration = Duration::from_secs(10);
+/// Time between checks of the cleanup and policy schedules.
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+/// Time between checks for tripwire probes.
+const TICK: Duration = Duration::from_secs(1);
+
+/// Unit file shipped with the packages; written to the user's unit directory
+/// with the `ExecStart` path adjusted when no package installed it.
+const UNIT_FILE: &str = include_str!("../data/security-center-monitor.service");
+/// D-Bus activation file that lets the session bus start the unit for
+/// [`BUS_NAME`]; installed alongside it the same way.
+const DBUS_SERVICE_FILE: &str =
+    include_str!("../data/com.chrisdaggas.security-center.Monitor.service");
+const PACKAGED_EXEC: &str = "/usr/bin/security-center-monitor";
+
+/// A desktop notification, sent by the application through GApplication or
+/// by the monitor over D-Bus. A notification replaces the previous one with
+/// the same tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notice {
+    pub tag: String,
+    pub title: String,
+    pub body: String,
+    pub icon: &'static str,
+    pub urgent: bool,
+    /// Offer to block this address.
+    pub block_source: Option<IpAddr>,
+    /// Offer to extend or remove the temporary rule with this storage key.
+    pub expiring_rule: Option<String>,
+}
+
+/// The notice for a quota threshold. Each interface has one, replaced as
+/// the usage grows.
+pub fn quota_notice(alert: &QuotaAlert) -> Notice {
+    let title = if alert.threshold >= 100 {
+        gettext("Data quota used up on %s").replace("%s", &alert.interface)
+    } else {
+        gettext("%s of the data quota used on %s")
+            .replacen("%s", &format_percent(alert.threshold as f64, 0), 1)
+            .replacen("%s", &alert.interface, 1)
+    };
+    let body = gettext("%s of %s used this month")
+        .replacen("%s", &format_bytes(alert.used), 1)
+        .replacen("%s", &format_bytes(alert.quota), 1);
+    Notice {
+        tag: format!("data-quota-{}", alert.interface),
+        title,
+        body,
+        icon: "network-cellular-symbolic",
+        urgent: alert.threshold >= 100,
+        block_source: None,
+        expiring_rule: None,
+    }
+}
+
+/// The notice for a probe of a tripwire port, offering to block the source
+/// if `offer_block`.
+pub fn tripwire_notice(hit: &TripwireHit, offer_block: bool) -> Notice {
+    let source = hit.source.to_string();
+    let title = gettext("%s probed tripwire port %s")
+        .replacen("%s", &source, 1)
+        .replacen("%s", &hit.port.to_string(), 1);
+    Notice {
+        tag: format!("tripwire-{}", source),
+        title,
+        body: gettext("Nothing serves this port, so the attempt was most likely a scan."),
+        icon: "security-high-symbolic",
+        urgent: true,
+        block_source: offer_block.then_some(hit.source),
+        expiring_rule: None,
+    }
+}
+
+/// The notice that a temporary rule expires soon, offering to extend or
+/// remove it.
+pub fn expiry_notice(rule: &ExpiringRule, now: i64) -> Notice {
+    let minutes = ((rule.metadata.expires_at - now).max(0) + 59) / 60;
+    Notice {
+        tag: format!("rule-expiry-{}", rule.key),
+        title: gettext("Temporary rule expires in %d min").replacen("%d", &minutes.to_string(), 1),
+        body: gettext("%s will be removed from the firewall.").replacen("%s", &rule.label(), 1),
+        icon: "alarm-symbolic",
+        urgent: false,
+        block_source: None,
+        expiring_rule: Some(rule.key.clone()),
+    }
+}
+
+/// The notice replacing [`expiry_notice`] once the rule was removed,
+/// extended, or could not be removed.
+pub fn expiry_result_notice(key: &str, text: String) -> Notice {
+    Notice {
+        tag: format!("rule-expiry-{}", key),
+        title: text,
+        body: String::new(),
+        icon: "alarm-symbolic",
+        urgent: false,
+        block_source: None,
+        expiring_rule: None,
+    }
+}
+
+/// The notice for something a scheduled scan found that the previous scan
+/// did not. Each finding has one.
+pub fn scan_notice(alert: &ScanAlert) -> Notice {
+    let (title, body, urgent) = match alert {
+        ScanAlert::ExposedPort(endpoint) => {
+            let zone = match &endpoint.firewall_status {
+                admin::FirewallStatus::Allowed { zone } => zone.as_str(),
+                _ => "",
+            };
+            let title = gettext("Port %s/%s is now reachable from the network")
+                .replacen("%s", &endpoint.port.to_string(), 1)
+                .replacen("%s", &endpoint.protocol.as_str().to_lowercase(), 1);
+            let body = gettext("%s listens on all interfaces and zone '%s' lets it through.")
+                .replacen("%s", endpoint.process_name.as_deref().unwrap_or("?"), 1)
+                .replacen("%s", zone, 1);
+            (title, body, true)
+        }
+        ScanAlert::FailedCheck(check) => (
+            gettext("Security check failed: %s").replacen("%s", &gettext(check.title), 1),
+            check.detail.clone(),
+            check.weight >= 20,
+        ),
+    };
+    Notice {
+        tag: format!("scan-{}", alert.key()),
+        title,
+        body,
+        icon: "security-low-symbolic",
+        urgent,
+        block_source: None,
+        expiring_rule: None,
+    }
+}
+
+/// Outcome of [`rule_expiry::remove_rule`] as a one-line message.
+pub fn remove_result_text(result: &Result<ExpiringRule>) -> String {
+    match result {
+        Ok(rule) => gettext("Removed temporary rule %s").replacen("%s", &rule.label(), 1),
+        Err(e) => format!("{}: {}", gettext("Failed to remove temporary rule"), e),
+    }
+}
+
+/// Outcome of [`rule_expiry::extend_rule`] as a one-line message.
+pub fn extend_result_text(result: &Result<ExpiringRule>) -> String {
+    match result {
+        Ok(rule) => gettext("%s extended until %s")
+            .replacen("%s", &rule.label(), 1)
+            .replacen(
+                "%s",
+                &rule
+                    .metadata
+                    .expires()
+                    .map(|t| format_time(t.timestamp()))
+                    .unwrap_or_default(),
+                1,
+            ),
+        Err(e) => format!("{}: {}", gettext("Failed to extend temporary rule"), e),
+    }
+}
+
+/// Outcome of [`admin::block_source`] as a one-line message.
+pub fn block_result_text(source: IpAddr, result: &Result<(String, bool)>) -> String {
+    match result {
+        Ok((zone, false)) => gettext("Blocked %s in zone '%s'")
+            .replacen("%s", &source.to_string(), 1)
+            .replacen("%s", zone, 1),
+        Ok((zone, true)) => {
+            gettext("Blocked %s in zone '%s' for this session only — saving permanently failed")
+                .replacen("%s", &source.to_string(), 1)
+                .replacen("%s", zone, 1)
+        }
+        Err(e) => format!("{}: {}", gettext("Failed to block address"), e),
+    }
+}
+
+/// Usage counted by the running monitor, or `None` when it is not running.
+pub fn query_data_usage() -> Option<Vec<QuotaUsage>> {
+    let connection = Connection::session().ok()?;
+    let reply = connection
+        .call_method(
+            Some(BUS_NAME),
+            OBJECT_PATH,
+            Some(INTERFACE),
+            "DataUsage",
+            &(),
+        )
+        .ok()?;
+    let usage: Vec<(String, u64, u64)> = reply.body().deserialize().ok()?;
+    Some(
+        usage
+            .into_iter()
+            .map(|(interface, used, quota)| QuotaUsage {
+                interface,
+                used,
+                quota,
+            })
+            .collect(),
+    )
+}
+
+/// Ask the running monitor to read the settings again.
+pub fn reload() {
+    let result = Connection::session().and_then(|connection| {
+        connection.call_method(Some(BUS_NAME), OBJECT_PATH, Some(INTERFACE), "Reload", &())
+    });
+    if let Err(e) = result {
+        debug!("Monitor not reloaded: {}", e);
+    }
+}
+
+/// Whether the monitor's user unit is enabled.
+pub fn is_service_enabled() -> bool {
+    Command::new("systemctl")
+        .args(["--user", "is-enabled", "--quiet", UNIT_NAME])
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+/// Enable and start the monitor's user unit, or stop and disable it. A
+/// unit is written to the user's unit directory first when no package
+/// installed one.
+pub fn set_service_enabled(enabled: bool) -> Result<()> {
+    if enabled {
+        let installed = Command::new("systemctl")
+            .args(["--user", "cat", UNIT_NAME])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success());
+        if !installed {
+            install_user_unit()?;
+            systemctl(&["daemon-reload"])?;
+        }
+        systemctl(&["enable", "--now", UNIT_NAME])
+    } else {
+        systemctl(&["disable", "--now", UNIT_NAME])
+    }
+}
+
+fn systemctl(args: &[&str]) -> Result<()> {
+    let output = Command::new("systemctl")
+        .arg("--user")
+        .args(args)
+        .output()
+        .context("Failed to run systemctl")?;
+    if !output.status.success() {
+        bail!(
+            "systemctl {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Write the unit for the monitor binary next to the running executable.
+fn install_user_unit() -> Result<()> {
+    let exe = std::env::current_exe().context("Cannot locate the running executable")?;
+    let monitor = exe.with_file_name("security-center-monitor");
+    if !monitor.exists() {
+        bail!("{} is not installed", monitor.display());
+    }
+    let dir = dirs::config_dir()
+        .context("Could not determine the configuration directory")?
+        .join("systemd")
+        .join("user");
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(UNIT_NAME);
+    std::fs::write(&path, unit_file(&monitor))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    info!("Installed {}", path.display());
+
+    let dir = dirs::data_dir()
+        .context("Could not determine the data directory")?
+        .join("dbus-1")
+        .join("services");
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(format!("{}.service", BUS_NAME));
+    std::fs::write(&path, dbus_service_file(&monitor))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    info!("Installed {}", path.display());
+    Ok(())
+}
+
+/// The unit file with `ExecStart` pointing at `exec`.
+fn unit_file(exec: &Path) -> String {
+    UNIT_FILE.replace(PACKAGED_EXEC, &exec.to_string_lossy())
+}
+
+/// The D-Bus activation file with `Exec` pointing at `exec`.
+fn dbus_service_file(exec: &Path) -> String {
+    DBUS_SERVICE_FILE.replace(PACKAGED_EXEC, &exec.to_string_lossy())
+}
+
+/// What a click on a monitor notification asks for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum NotificationAction {
+    /// The notification itself was clicked.
+    Open,
+    BlockSource(IpAddr),
+    /// Extend the temporary rule with this storage key.
+    ExtendRule(String),
+    /// Remove the temporary rule with this storage key now.
+    RemoveRule(String),
+}
+
+impl NotificationAction {
+    const BLOCK_PREFIX: &'static str = "block-source:";
+    const EXTEND_PREFIX: &'static str = "extend-rule:";
+    const REMOVE_PREFIX: &'static str = "remove-rule:";
+
+    /// The action key sent with the notification.
+    fn key(&self) -> String {
+        match self {
+            Self::Open => "default".to_string(),
+            Self::BlockSource(source) => format!("{}{}", Self::BLOCK_PREFIX, source),
+            Self::ExtendRule(key) => format!("{}{}", Self::EXTEND_PREFIX, key),
+            Self::RemoveRule(key) => format!("{}{}", Self::REMOVE_PREFIX, key),
+        }
+    }
+
+    fn parse(key: &str) -> Option<Self> {
+        if key == "default" {
+            return Some(Self::Open);
+        }
+        if let Some(rule) = key.strip_prefix(Self::EXTEND_PREFIX) {
+            return Some(Self::ExtendRule(rule.to_string()));
+        }
+        if let Some(rule) = key.strip_prefix(Self::REMOVE_PREFIX) {
+            return Some(Self::RemoveRule(rule.to_string()));
+        }
+        key.strip_prefix(Self::BLOCK_PREFIX)?
+            .parse()
+            .ok()
+            .map(Self::BlockSource)
+    }
+}
+
+/// State the D-Bus interface shares with the monitor loop.
+#[derive(Debug, Default)]
+struct Shared {
+    usage: Mutex<Vec<QuotaUsage>>,
+    reload: AtomicBool,
+}
+
+struct MonitorInterface(Arc<Shared>);
+
+#[zbus::interface(name = "com.chrisdaggas.SecurityCenter.Monitor")]
+impl MonitorInterface {
+    /// Interface, bytes used this month and monthly quota in bytes of every
+    /// interface with a quota.
+    fn data_usage(&self) -> Vec<(String, u64, u64)> {
+        self.0
+            .usage
+            .lock()
+            .map(|usage| {
+                usage
+                    .iter()
+                    .map(|u| (u.interface.clone(), u.used, u.quota))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Read the settings again before the next check.
+    fn reload(&self) {
+        self.0.reload.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Sends notifications and handles their actions.
+struct Notifier {
+    connection: Connection,
+    /// ID of the notification shown for each tag.
+    ids: Mutex<HashMap<String, u32>>,
+}
+
+impl Notifier {
+    fn new(connection: Connection) -> Self {
+        Self {
+            connection,
+            ids: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn send(&self, notice: &Notice) {
+        if let Err(e) = self.try_send(notice) {
+            warn!("Failed to send notification: {}", e);
+        }
+    }
+
+    fn try_send(&self, notice: &Notice) -> Result<()> {
+        let replaces = self
+            .ids
+            .lock()
+            .ok()
+            .and_then(|ids| ids.get(&notice.tag).copied())
+            .unwrap_or(0);
+
+        let mut actions = vec![NotificationAction::Open.key(), gettext("Open")];
+        if let Some(source) = notice.block_source {
+            actions.push(NotificationAction::BlockSource(source).key());
+            actions.push(gettext("Block Address"));
+        }
+        if let Some(key) = &notice.expiring_rule {
+            actions.push(NotificationAction::ExtendRule(key.clone()).key());
+            actions.push(gettext("Extend by 1 hour"));
+            actions.push(NotificationAction::RemoveRule(key.clone()).key());
+            actions.push(gettext("Remove now"));
+        }
+        let mut hints: HashMap<&str, Value> = HashMap::new();
+        hints.insert("desktop-entry", Value::from(APP_ID));
+        hints.insert("urgency", Value::U8(if notice.urgent { 2 } else { 1 }));
+
+        let reply = self.connection.call_method(
+            Some(NOTIFICATIONS),
+            NOTIFICATIONS_PATH,
+            Some(NOTIFICATIONS),
+            "Notify",
+            &(
+                gettext("Security Center"),
+                replaces,
+                notice.icon,
+                &notice.title,
+                &notice.body,
+                actions,
+                hints,
+                -1i32,
+            ),
+        )?;
+        let id: u32 = reply.body().deserialize()?;
+        if let Ok(mut ids) = self.ids.lock() {
+            ids.insert(notice.tag.clone(), id);
+        }
+        Ok(())
+    }
+
+    /// Act on clicks of the notifications sent, until the bus goes away.
+    fn handle_actions(&self) -> Result<()> {
+        let rule = zbus::MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .interface(NOTIFICATIONS)?
+            .member("ActionInvoked")?
+            .build();
+        let messages =
+            zbus::blocking::MessageIterator::for_match_rule(rule, &self.connection, None)?;
+        for message in messages {
+            let Ok((id, key)) = message?.body().deserialize::<(u32, String)>() else {
+                continue;
+            };
+            let ours = self
+                .ids
+                .lock()
+                .is_ok_and(|ids| ids.values().any(|&sent| sent == id));
+            if !ours {
+                continue;
+            }
+            match NotificationAction::parse(&key) {
+                Some(NotificationAction::Open) => self.open_application(),
+                Some(NotificationAction::BlockSource(source)) => {
+                    if self.application_running() {
+                        self.activate_action("block-source", &source.to_string());
+                    } else {
+                        self.block_source(source);
+                    }
+                }
+                Some(NotificationAction::ExtendRule(key)) => {
+                    if self.application_running() {
+                        self.activate_action("extend-rule", &key);
+                    } else {
+                        self.extend_rule(&key);
+                    }
+                }
+                Some(NotificationAction::RemoveRule(key)) => {
+                    if self.application_running() {
+                        self.activate_action("remove-rule", &key);
+                    } else {
+                        self.remove_rule(&key);
+                    }
+                }
+                None => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Block `source` without the application and report the outcome.
+    fn block_source(&self, source: IpAddr) {
+        let result = admin::block_source(source);
+        if let Err(e) = &result {
+            warn!("Failed to block {}: {:#}", source, e);
+        }
+        self.send(&Notice {
+            tag: format!("tripwire-{}", source),
+            title: block_result_text(source, &result),
+            body: String::new(),
+            icon: "security-high-symbolic",
+            urgent: false,
+            block_source: None,
+            expiring_rule: None,
+        });
+    }
+
+    /// Extend a temporary rule without the application and report the
+    /// outcome.
+    fn extend_rule(&self, key: &str) {
+        let result = rule_expiry::extend_rule(key);
+        if let Err(e) = &result {
+            warn!("Failed to extend {}: {:#}", key, e);
+        }
+        self.send(&expiry_result_notice(key, extend_result_text(&result)));
+    }
+
+    /// Remove a temporary rule without the application and report the
+    /// outcome.
+    fn remove_rule(&self, key: &str) {
+        let result = rule_expiry::remove_rule(key);
+        if let Err(e) = &result {
+            warn!("Failed to remove {}: {:#}", key, e);
+        }
+        self.send(&expiry_result_notice(key, remove_result_text(&result)));
+    }
+
+    fn application_running(&self) -> bool {
+        self.connection
+            .call_method(
+                Some("org.freedesktop.DBus"),
+                "/org/freedesktop/DBus",
+                Some("org.freedesktop.DBus"),
+                "NameHasOwner",
+                &(APP_ID),
+            )
+            .ok()
+            .and_then(|reply| reply.body().deserialize::<bool>().ok())
+            .unwrap_or(false)
+    }
+
+    /// Bring the application to the front, starting it when it is not
+    /// running.
+    fn open_application(&self) {
+        if self.application_running() {
+            let platform_data: HashMap<&str, Value> = HashMap::new();
+            if let Err(e) = self.connection.call_method(
+                Some(APP_ID),
+                APP_OBJECT_PATH,
+                Some("org.freedesktop.Application"),
+                "Activate",
+                &(platform_data),
+            ) {
+                warn!("Failed to activate the application: {}", e);
+            }
+            return;
+        }
+        if let Err(e) = Command::new(application_binary()).spawn() {
+            warn!("Failed to start the application: {}", e);
+        }
+    }
+
+    /// Activate an application action with a string parameter.
+    fn activate_action(&self, action: &str, parameter: &str) {
+        let platform_data: HashMap<&str, Value> = HashMap::new();
+        if let Err(e) = self.connection.call_method(
+            Some(APP_ID),
+            APP_OBJECT_PATH,
+            Some("org.freedesktop.Application"),
+            "ActivateAction",
+            &(action, vec![Value::from(parameter)], platform_data),
+        ) {
+            warn!("Failed to activate {} in the application: {}", action, e);
+        }
+    }
+}
+
+/// The application binary installed next to the monitor, or the one on
+/// `PATH`.
+fn application_binary() -> PathBuf {
+    std::env::current_exe()
+        .map(|exe| exe.with_file_name("security-center"))
+        .ok()
+        .filter(|path| path.exists())
+        .unwrap_or_else(|| PathBuf::from("security-center"))
+}
+
+/// Categories currently enabled in settings.
+fn enabled_categories(settings: &Settings) -> Vec<CleanupCategory> {
+    CleanupCategory::ALL
+        .into_iter()
+        .filter(|c| settings.cleanup_category_enabled(c.id()))
+        .collect()
+}
+
+/// The monitor loop and the state it keeps between checks.
+struct Monitor {
+    shared: Arc<Shared>,
+    notifier: Arc<Notifier>,
+    settings: Settings,
+    meter: UsageMeter,
+    denied_log: Option<DeniedLog>,
+    tripwires: Tripwires,
+    expiry: ExpiryWatch,
+    keys: KeyRemovalWatch,
+    /// Set while a scheduled cleanup or enforcement runs.
+    scheduled_running: Arc<AtomicBool>,
+}
+
+impl Monitor {
+    fn new(shared: Arc<Shared>, notifier: Arc<Notifier>) -> Self {
+        let settings = Settings::new();
+        let denied_log = DeniedLog::start()
+            .map_err(|e| warn!("Cannot follow denied packets: {:#}", e))
+            .ok();
+        let tripwires = Tripwires::new(settings.tripwire_ports());
+        Self {
+            shared,
+            notifier,
+            settings,
+            meter: UsageMeter::load(),
+            denied_log,
+            tripwires,
+            expiry: ExpiryWatch::new(),
+            keys: KeyRemovalWatch::new(),
+            scheduled_running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    fn run(mut self) -> Result<()> {
+        let mut next_sample = Instant::now();
+        let mut next_schedule = Instant::now() + SCHEDULE_DELAY;
+        let mut next_expiry = Instant::now();
+        loop {
+            let now = Instant::now();
+            if self.shared.reload.swap(false, Ordering::Relaxed) || now >= next_sample {
+                self.reload_settings();
+                self.sample_data_usage();
+                next_sample = now + data_usage::SAMPLE_INTERVAL;
+            }
+            self.check_tripwires();
+            self.check_security_keys();
+            if now >= next_expiry {
+                self.check_rule_expiry();
+                next_expiry = now + rule_expiry::CHECK_INTERVAL;
+            }
+            if now >= next_schedule {
+                self.run_scheduled();
+                next_schedule = now + SCHEDULE_CHECK_INTERVAL;
+            }
+            std::thread::sleep(TICK);
+        }
+    }
+
+    fn reload_settings(&mut self) {
+        self.settings = Settings::new();
+        self.tripwires.set_ports(self.settings.tripwire_ports());
+    }
+
+    fn sample_data_usage(&mut self) {
+        let quotas = self.settings.data_quotas();
+        let alerts = self.meter.sample(
+            &data_usage::read_counters(),
+            &data_usage::current_month(),
+            quotas,
+        );
+        self.meter.save_if_due();
+        if let Ok(mut usage) = self.shared.usage.lock() {
+            *usage = self.meter.usage(quotas);
+        }
+        for alert in &alerts {
+            info!(
+                "Data quota alert: {} at {}% ({} of {} bytes)",
+                alert.interface, alert.threshold, alert.used, alert.quota
+            );
+            self.notifier.send(&quota_notice(alert));
+        }
+    }
+
+    fn check_tripwires(&mut self) {
+        let Some(log) = &self.denied_log else {
+            return;
+        };
+        let packets = log.take_new();
+        if self.tripwires.is_empty() {
+            return;
+        }
+        let hits: Vec<TripwireHit> = packets
+            .iter()
+            .filter_map(|p| self.tripwires.check(p))
+            .collect();
+        if hits.is_empty() {
+            return;
+        }
+        // Read only when needed: the routes and DNS servers may have changed
+        let protected = admin::protected_addresses();
+        for hit in hits {
+            warn!(
+                "Tripwire: {} probed port {}/{}",
+                hit.source,
+                hit.port,
+                hit.protocol.to_lowercase()
+            );
+            let offer_block = self.tripwires.offer_block(&hit, &protected);
+            self.notifier.send(&tripwire_notice(&hit, offer_block));
+        }
+    }
+
+    /// Lock the user's sessions when a security key is pulled out, if asked
+    /// to.
+    fn check_security_keys(&mut self) {
+        if !self.settings.lock_on_key_removal() {
+            self.keys.reset();
+            return;
+        }
+        let removed = self.keys.update(&admin::query_security_keys());
+        if let Some(key) = removed.first() {
+            info!("Security key {} removed, locking the session", key.name);
+            if let Err(e) = admin::lock_sessions() {
+                warn!("Cannot lock the session: {:#}", e);
+            }
+        }
+    }
+
+    /// Warn about temporary rules about to expire and remove expired ones,
+    /// the removals in the background.
+    fn check_rule_expiry(&mut self) {
+        let now = chrono::Utc::now().timestamp();
+        for step in rule_expiry::check_due(&mut self.expiry) {
+            match step {
+                ExpiryStep::Warn(rule) => {
+                    info!("Temporary rule {} expires soon", rule.key);
+                    self.notifier.send(&expiry_notice(&rule, now));
+                }
+                ExpiryStep::Expire(rule) => {
+                    info!("Temporary rule {} expired", rule.key);
+                    let notifier = self.notifier.clone();
+                    std::thread::spawn(move || notifier.remove_rule(&rule.key));
+                }
+            }
+        }
+    }
+
+    /// Run the cleanup, policy enforcement, blocklist refreshes and security
+    /// scan that are due, in the background so tripwire checks carry on.
+    fn run_scheduled(&self) {
+        if self.scheduled_running.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        let notifier = self.notifier.clone();
+        let running = self.scheduled_running.clone();
+        std::thread::spawn(move || {
+            let settings = Settings::new();
+            run_cleanup_if_due(&settings, &notifier);
+            enforce_policy_if_due(&settings, &notifier);
+            refresh_blocklists_if_due(&notifier);
+            scan_if_due(&settings, &notifier);
+            running.store(false, Ordering::Relaxed);
+        });
+    }
+}
+
+fn run_cleanup_if_due(settings: &Settings, notifier: &Notifier) {
+    let now = chrono::Utc::now().timestamp();
+    if !cleanup_due(
+        settings.cleanup_last_run(),
+        settings.cleanup_interval_days(),
+        now,
+    ) {
+        return;
+    }
+    let items = scan_leftovers(
+        &enabled_categories(settings),
+        settings.cleanup_history_sizes(),
+    );
+    // Unattended: core dumps are left for the window if removing them
+    // needs a password
+    let report = clean_leftovers(&items, settings.cleanup_secure_delete(), false);
+    let mut saved = Settings::new();
+    saved.set_cleanup_last_run(now);
+    saved.set_cleanup_history_sizes(history_sizes());
+    for (path, reason) in &report.failed {
+        warn!("Cleanup: could not remove {}: {}", path.display(), reason);
+    }
+    info!("Scheduled cleanup: {}", report.summary());
+    if report.cleaned > 0 || !report.failed.is_empty() {
+        notifier.send(&Notice {
+            tag: "cleanup".to_string(),
+            title: gettext("Scheduled cleanup finished"),
+            body: report.summary(),
+            icon: "user-trash-symbolic",
+            urgent: false,
+            block_source: None,
+            expiring_rule: None,
+        });
+    }
+}
+
+fn enforce_policy_if_due(settings: &Settings, notifier: &Notifier) {
+    let now = chrono::Utc::now().timestamp();
+    if !enforcement_due(
+        settings.policy_last_enforced(),
+        settings.policy_interval_hours(),
+        now,
+    ) {
+        return;
+    }
+    let report = match check_policy() {
+        Ok(Some((state, drifts))) => {
+            let fixable: Vec<Drift> = drifts.into_iter().filter(Drift::is_fixable).collect();
+            Some(enforce_unattended(&state, &fixable))
+        }
+        // No policy: nothing to enforce, try again next interval
+        Ok(None) => None,
+        Err(e) => {
+            warn!("Policy check failed: {:#}", e);
+            return;
+        }
+    };
+    Settings::new().set_policy_last_enforced(now);
+    let Some(report) = report else {
+        return;
+    };
+    for (drift, reason) in &report.failed {
+        warn!("Policy enforcement: {:?}: {}", drift, reason);
+    }
+    if !report.pending.is_empty() {
+        info!(
+            "Policy enforcement: {} differences need authorization",
+            report.pending.len()
+        );
+        notifier.send(&Notice {
+            tag: "policy-pending".to_string(),
+            title: gettext("Policy differences need authorization"),
+            body: gettext("Enforce the policy in Security Center to correct %d differences")
+                .replace("%d", &report.pending.len().to_string()),
+            icon: "security-medium-symbolic",
+            urgent: false,
+            block_source: None,
+            expiring_rule: None,
+        });
+    }
+    let total = report.fixed + report.failed.len();
+    if total > 0 {
+        notifier.send(&Notice {
+            tag: "policy".to_string(),
+            title: gettext("Policy enforced"),
+            body: gettext("Corrected %d of %d differences")
+                .replacen("%d", &report.fixed.to_string(), 1)
+                .replacen("%d", &total.to_string(), 1),
+            icon: "security-high-symbolic",
+            urgent: !report.failed.is_empty(),
+            block_source: None,
+            expiring_rule: None,
+        });
+    }
+}
+
+fn refresh_blocklists_if_due(notifier: &Notifier) {
+    for (name, result) in blocklists::refresh_due_blocklists() {
+        match result {
+            Ok(list) => info!("Refreshed blocklist {}: {} entries", name, list.entries),
+            Err(e) => {
+                warn!("Blocklist {} refresh failed: {:#}", name, e);
+                notifier.send(&Notice {
+                    tag: "blocklist".to_string(),
+                    title: gettext("Blocklist refresh failed"),
+                    body: format!("{}: {}", name, e),
+                    icon: "dialog-warning-symbolic",
+                    urgent: false,
+                    block_source: None,
+                    expiring_rule: None,
+                });
+            }
+        }
+    }
+}
+
+fn scan_if_due(settings: &Settings, notifier: &Notifier) {
+    let now = chrono::Utc::now().timestamp();
+    if !scan_due(
+        settings.security_scan_last_run(),
+        settings.security_scan_interval_hours(),
+        now,
+    ) {
+        return;
+    }
+    let alerts = security_scan::run_scheduled_scan();
+    Settings::new().set_security_scan_last_run(now);
+    info!("Scheduled security scan: {} new findings", alerts.len());
+    for alert in &alerts {
+        notifier.send(&scan_notice(alert));
+    }
+}
+
+/// Run the monitor until the process is stopped. Fails when another
+/// monitor already owns [`BUS_NAME`].
+///
+/// The usage counters are saved at most a minute apart, so stopping the
+/// service loses no more than the last minute of traffic.
+pub fn run() -> Result<()> {
+    // The object server answers calls on tasks of this runtime, while the
+    // monitor loop runs on the calling thread
+    let runtime = tokio::runtime::Runtime::new().context("Failed to start the runtime")?;
+    let shared = Arc::new(Shared::default());
+    let connection = runtime
+        .block_on(async {
+            zbus::connection::Builder::session()?
+                .name(BUS_NAME)?
+                .serve_at(OBJECT_PATH, MonitorInterface(shared.clone()))?
+                .build()
+                .await
+        })
+        .with_context(|| format!("Failed to own {} on the session bus", BUS_NAME))?;
+    info!("Background monitor running as {}", BUS_NAME);
+
+    let notifier = Arc::new(Notifier::new(Connection::from(connection)));
+    let listener = notifier.clone();
+    std::thread::Builder::new()
+        .name("notification-actions".to_string())
+        .spawn(move || {
+            if let Err(e) = listener.handle_actions() {
+                warn!("Stopped handling notification actions: {}", e);
+            }
+        })?;
+
+    Monitor::new(shared, notifier).run()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_action_keys() {
+        let source: IpAddr = "203.0.113.7".parse().unwrap();
+        for action in [
+            NotificationAction::Open,
+            NotificationAction::BlockSource(source),
+            NotificationAction::BlockSource("2001:db8::1".parse().unwrap()),
+            NotificationAction::ExtendRule("8080/tcp/public".to_string()),
+            NotificationAction::RemoveRule("10-20/udp/home".to_string()),
+        ] {
+            assert_eq!(NotificationAction::parse(&action.key()), Some(action));
+        }
+        assert_eq!(NotificationAction::parse("block-source:nowhere"), None);
+        assert_eq!(NotificationAction::parse("open"), None);
+    }
+
+    #[test]
+    fn test_tripwire_notice() {
+        let hit = TripwireHit {
+            source: "203.0.113.7".parse().unwrap(),
+            port: 23,
+            protocol: "TCP".to_string(),
+            time: 100,
+        };
+        let notice = tripwire_notice(&hit, true);
+        assert_eq!(notice.tag, "tripwire-203.0.113.7");
+        assert_eq!(notice.title, "203.0.113.7 probed tripwire port 23");
+        assert_eq!(notice.block_source, Some(hit.source));
+        // Past the offer limit the alert only reports the probe
+        assert_eq!(tripwire_notice(&hit, false).block_source, None);
+    }
+
+    #[test]
+    fn test_quota_notice() {
+        let alert = QuotaAlert {
+            interface: "wwan0".to_string(),
+            threshold: 90,
+            used: 9 * data_usage::BYTES_PER_GB,
+            quota: 10 * data_usage::BYTES_PER_GB,
+        };
+        let notice = quota_notice(&alert);
+        assert_eq!(notice.tag, "data-quota-wwan0");
+        assert_eq!(notice.title, "90% of the data quota used on wwan0");
+        assert!(!notice.urgent);
+        let used_up = QuotaAlert {
+            threshold: 100,
+            ..alert
+        };
+        assert!(quota_notice(&used_up).urgent);
+    }
+
+    #[test]
+    fn test_scan_notice() {
+        let check = admin::PostureCheck {
+            id: "firewall.panic",
+            title: "Panic mode off",
+            weight: 10,
+            passed: false,
+            detail: "Panic mode drops all traffic".to_string(),
+            remedy: None,
+        };
+        let notice = scan_notice(&ScanAlert::FailedCheck(check));
+        assert_eq!(notice.tag, "scan-check:firewall.panic");
+        assert_eq!(notice.title, "Security check failed: Panic mode off");
+        assert!(!notice.urgent);
+    }
+
+    #[test]
+    fn test_unit_file() {
+        let unit = unit_file(Path::new("/home/user/.cargo/bin/security-center-monitor"));
+        assert!(unit.contains("ExecStart=/home/user/.cargo/bin/security-center-monitor\n"));
+        assert!(unit.contains(&format!("BusName={}\n", BUS_NAME)));
+    }
+
+    #[test]
+    fn test_dbus_service_file() {
+        let service = dbus_service_file(Path::new("/home/user/.cargo/bin/security-center-monitor"));
+        assert!(service.starts_with("[D-BUS Service]\n"));
+        assert!(service.contains(&format!("Name={}\n", BUS_NAME)));
+        assert!(service.contains("Exec=/home/user/.cargo/bin/security-center-monitor\n"));
+        assert!(service.contains(&format!("SystemdService={}\n", UNIT_NAME)));
+    }
+}
@@ -0,0 +1,252 @@
+// Security Center - Rule Expiry
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Temporary port rules.
+//!
+//! A port rule added with an expiry records when it ends in its metadata
+//! ([`PortMetadata::expires_at`]). Every [`CHECK_INTERVAL`] the background
+//! monitor, or the application while the monitor is not running, asks an
+//! [`ExpiryWatch`] what is due: [`WARNING_SECS`] before a rule expires a
+//! notification offers to extend it by [`EXTENSION_SECS`] or remove it right
+//! away, and once it has expired the rule is removed from firewalld, runtime
+//! and permanent, and its metadata forgotten.
+//!
+//! Expiry is only checked while one of them runs; a rule whose time passed
+//! in between is removed at the first check.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::firewall;
+use crate::models::RichRule;
+use crate::storage::{PortMetadata, PortStorage};
+use crate::validation::{format_port_spec, validate_protocol};
+
+/// Time between expiry checks.
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// How long before a rule expires its notification is sent.
+pub const WARNING_SECS: i64 = 10 * 60;
+/// How much "Extend" adds to a rule's lifetime.
+pub const EXTENSION_SECS: i64 = 60 * 60;
+
+/// A temporary rule and the storage key of its metadata.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpiringRule {
+    pub key: String,
+    pub metadata: PortMetadata,
+}
+
+impl ExpiringRule {
+    /// The port spec ("8080" or "10-20").
+    pub fn port_spec(&self) -> String {
+        format_port_spec(self.metadata.port, self.metadata.end_port)
+    }
+
+    /// Short description for notifications, e.g. "Dev server (8080/tcp in
+    /// public)".
+    pub fn label(&self) -> String {
+        let rule = format!(
+            "{}/{} in {}",
+            self.port_spec(),
+            self.metadata.protocol,
+            self.metadata.zone
+        );
+        if self.metadata.name.is_empty() {
+            rule
+        } else {
+            format!("{} ({})", self.metadata.name, rule)
+        }
+    }
+
+    fn is_block(&self) -> bool {
+        self.metadata.incoming_action == "block"
+    }
+}
+
+/// What an expiry check found due.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpiryStep {
+    /// The rule expires within [`WARNING_SECS`].
+    Warn(ExpiringRule),
+    /// The rule has expired and is to be removed.
+    Expire(ExpiringRule),
+}
+
+/// Remembers which rules were warned about, so each expiry is announced
+/// once. Extending a rule moves its expiry and warns again later.
+#[derive(Debug, Default)]
+pub struct ExpiryWatch {
+    warned: HashSet<(String, i64)>,
+}
+
+impl ExpiryWatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The warnings and removals due at `now` among the stored rules.
+    pub fn check(&mut self, entries: Vec<(String, PortMetadata)>, now: i64) -> Vec<ExpiryStep> {
+        let mut steps = Vec::new();
+        let mut current = HashSet::new();
+        for (key, metadata) in entries {
+            let expires_at = metadata.expires_at;
+            if expires_at <= 0 || metadata.port == 0 {
+                continue;
+            }
+            let rule = ExpiringRule { key, metadata };
+            if now >= expires_at {
+                steps.push(ExpiryStep::Expire(rule));
+                continue;
+            }
+            let id = (rule.key.clone(), expires_at);
+            if now >= expires_at - WARNING_SECS && !self.warned.contains(&id) {
+                steps.push(ExpiryStep::Warn(rule));
+            }
+            current.insert(id);
+        }
+        // Forget rules that were removed or extended meanwhile
+        self.warned.retain(|id| current.contains(id));
+        for step in &steps {
+            if let ExpiryStep::Warn(rule) = step {
+                self.warned
+                    .insert((rule.key.clone(), rule.metadata.expires_at));
+            }
+        }
+        steps
+    }
+}
+
+/// Check the stored rules with `watch`.
+pub fn check_due(watch: &mut ExpiryWatch) -> Vec<ExpiryStep> {
+    let entries = PortStorage::new().entries();
+    watch.check(entries, chrono::Utc::now().timestamp())
+}
+
+/// Give a temporary rule [`EXTENSION_SECS`] more, counted from its expiry
+/// or from now if that has passed. Returns the rule with its new expiry.
+pub fn extend_rule(key: &str) -> Result<ExpiringRule> {
+    let mut storage = PortStorage::new();
+    let mut metadata = storage
+        .get(key)
+        .filter(|m| m.expires_at > 0)
+        .ok_or_else(|| anyhow!("No temporary rule {}", key))?;
+    let now = chrono::Utc::now().timestamp();
+    metadata.expires_at = metadata.expires_at.max(now) + EXTENSION_SECS;
+    storage.set(key.to_string(), metadata.clone());
+    Ok(ExpiringRule {
+        key: key.to_string(),
+        metadata,
+    })
+}
+
+/// Remove a temporary rule from firewalld, runtime and permanent, and
+/// forget its metadata. Returns the rule removed.
+pub fn remove_rule(key: &str) -> Result<ExpiringRule> {
+    let mut storage = PortStorage::new();
+    let metadata = storage
+        .get(key)
+        .ok_or_else(|| anyhow!("No temporary rule {}", key))?;
+    let rule = ExpiringRule {
+        key: key.to_string(),
+        metadata,
+    };
+    let protocol = validate_protocol(&rule.metadata.protocol)
+        .ok_or_else(|| anyhow!("Invalid protocol: {}", rule.metadata.protocol))?;
+    let protocol = protocol.to_string();
+    let zone = rule.metadata.zone.clone();
+    let port_spec = rule.port_spec();
+    let is_block = rule.is_block();
+
+    firewall::call_blocking(move |client| -> Result<()> {
+        client
+            .connect()
+            .context("Cannot reach firewalld. Is it running?")?;
+        for permanent in [false, true] {
+            if is_block {
+                let reject = RichRule::port_block(&port_spec, &protocol).to_string();
+                client.remove_rich_rule(&zone, &reject, permanent)?;
+            } else {
+                client.remove_port(&zone, &port_spec, &protocol, permanent)?;
+            }
+        }
+        Ok(())
+    })??;
+    storage.remove(key);
+    Ok(rule)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, expires_at: i64) -> (String, PortMetadata) {
+        let mut metadata = PortMetadata::new("Dev server");
+        metadata.port = 8080;
+        metadata.protocol = "tcp".to_string();
+        metadata.zone = "public".to_string();
+        metadata.incoming_action = "allow".to_string();
+        metadata.expires_at = expires_at;
+        (key.to_string(), metadata)
+    }
+
+    fn keys(steps: &[ExpiryStep]) -> Vec<(&'static str, &str)> {
+        steps
+            .iter()
+            .map(|step| match step {
+                ExpiryStep::Warn(rule) => ("warn", rule.key.as_str()),
+                ExpiryStep::Expire(rule) => ("expire", rule.key.as_str()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_expiry_watch() {
+        let mut watch = ExpiryWatch::new();
+        let now = 1_000_000;
+        let entries = || {
+            vec![
+                entry("permanent", 0),
+                entry("later", now + 3600),
+                entry("soon", now + 300),
+                entry("gone", now - 1),
+            ]
+        };
+
+        let mut steps = watch.check(entries(), now);
+        steps.sort_by_key(|s| format!("{:?}", s));
+        assert_eq!(keys(&steps), [("expire", "gone"), ("warn", "soon")]);
+
+        // Each expiry is announced once; expired rules are due until removed
+        assert_eq!(
+            keys(&watch.check(entries(), now + 60)),
+            [("expire", "gone")]
+        );
+
+        // An extended rule is warned about again before its new expiry
+        let extended = vec![entry("soon", now + 300 + EXTENSION_SECS)];
+        assert!(watch.check(extended.clone(), now + 120).is_empty());
+        assert_eq!(
+            keys(&watch.check(extended, now + 300 + EXTENSION_SECS - 60)),
+            [("warn", "soon")]
+        );
+    }
+
+    #[test]
+    fn test_label() {
+        let (key, mut metadata) = entry("8080/tcp/public", 1);
+        let rule = ExpiringRule {
+            key: key.clone(),
+            metadata: metadata.clone(),
+        };
+        assert_eq!(rule.label(), "Dev server (8080/tcp in public)");
+        assert!(!rule.is_block());
+
+        metadata.name = String::new();
+        metadata.end_port = 8090;
+        let rule = ExpiringRule { key, metadata };
+        assert_eq!(rule.label(), "8080-8090/tcp in public");
+    }
+}
@@ -0,0 +1,54 @@
+// Security Center - Safe Mode
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Safe mode, for debugging on constrained systems or when a collector is
+//! suspected of causing hangs.
+//!
+//! Started with [`SAFE_MODE_FLAG`], the application skips its background
+//! work: statistics collection, auto-refresh, the update check, the
+//! scheduled scans and the script hooks. The timers keep running and skip their work while safe
+//! mode is on, so turning it off in Preferences resumes them.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Command line flag that starts the application in safe mode.
+pub const SAFE_MODE_FLAG: &str = "--safe-mode";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Whether background collectors are paused.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Pause or resume the background collectors.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Remove [`SAFE_MODE_FLAG`] from the command line, which GApplication
+/// would reject, and return whether it was given.
+pub fn take_flag(args: &mut Vec<String>) -> bool {
+    let before = args.len();
+    args.retain(|arg| arg != SAFE_MODE_FLAG);
+    args.len() != before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_flag() {
+        let mut args = vec![
+            "security-center".to_string(),
+            SAFE_MODE_FLAG.to_string(),
+            "--verbose".to_string(),
+        ];
+        assert!(take_flag(&mut args));
+        assert_eq!(args, ["security-center", "--verbose"]);
+        assert!(!take_flag(&mut args));
+        assert_eq!(args.len(), 2);
+    }
+}
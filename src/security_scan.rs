@@ -0,0 +1,273 @@
+// Security Center - Scheduled Security Scan
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Scheduled security scans.
+//!
+//! Every few hours, as set in Preferences, the background monitor, or the
+//! application while the monitor is not running, scans the listening
+//! sockets as on the Network Exposure page and runs the posture checks of
+//! the Overview. A [`ScanBaseline`] remembers what the previous scan found
+//! in `~/.local/share/security-center/scan-baseline.json`, so only what is
+//! new since then raises a [`ScanAlert`]: a port that became reachable from
+//! the network, or a check that started failing. The first scan only
+//! records the baseline, and a finding that goes away and comes back is
+//! reported again. Each scheduled scan also runs the script hooks (see
+//! [`crate::hooks`]).
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::admin::{
+    evaluate_posture, query_posture_input, FirewallStatus, ListeningEndpoint, PostureCheck,
+};
+use crate::hooks::{self, HookEvent};
+
+/// Time between checks whether a scan is due, in the application.
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+/// Intervals offered in Preferences, in hours; 0 turns scans off.
+pub const INTERVALS: [u32; 4] = [0, 1, 6, 24];
+const MAX_BASELINE_FILE_SIZE: u64 = 1_048_576; // 1 MB
+
+/// Location of the persistent baseline.
+pub fn baseline_path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("security-center")
+        .join("scan-baseline.json")
+}
+
+/// Whether a scheduled scan is due, given the last run (unix seconds), the
+/// interval in hours (0 disables scheduling) and the current time.
+pub fn scan_due(last_run: Option<i64>, interval_hours: u32, now: i64) -> bool {
+    if interval_hours == 0 {
+        return false;
+    }
+    match last_run {
+        Some(last) => now - last >= i64::from(interval_hours) * 3_600,
+        None => true,
+    }
+}
+
+/// What one scan found.
+#[derive(Debug, Clone, Default)]
+pub struct ScanResult {
+    /// Sockets on all interfaces that a zone lets through, one per port
+    /// and protocol.
+    pub exposed: Vec<ListeningEndpoint>,
+    /// Posture checks that failed.
+    pub failed_checks: Vec<PostureCheck>,
+}
+
+/// Something a scan found that the previous one did not.
+#[derive(Debug, Clone)]
+pub enum ScanAlert {
+    ExposedPort(ListeningEndpoint),
+    FailedCheck(PostureCheck),
+}
+
+impl ScanAlert {
+    /// Stable key of the finding, e.g. `port:8080/tcp` or
+    /// `check:ssh.exposed`.
+    pub fn key(&self) -> String {
+        match self {
+            Self::ExposedPort(endpoint) => port_key(endpoint),
+            Self::FailedCheck(check) => check_key(check),
+        }
+    }
+}
+
+/// Scan the listening sockets and run the posture checks. Blocks on D-Bus
+/// and procfs, so call it off the main thread.
+pub fn run_scan() -> ScanResult {
+    let input = query_posture_input();
+    let posture = evaluate_posture(&input);
+    let mut exposed: Vec<ListeningEndpoint> = input
+        .endpoints
+        .into_iter()
+        .filter(|e| e.is_exposed() && matches!(e.firewall_status, FirewallStatus::Allowed { .. }))
+        .collect();
+    exposed.sort_by_key(|e| (e.port, e.protocol.as_str()));
+    exposed.dedup_by_key(|e| port_key(e));
+    ScanResult {
+        exposed,
+        failed_checks: posture.failed().into_iter().cloned().collect(),
+    }
+}
+
+/// Run a scan, compare it with the saved baseline and save it as the new
+/// one. Blocks like [`run_scan`].
+pub fn run_scheduled_scan() -> Vec<ScanAlert> {
+    let result = run_scan();
+    let exposed_ports = result
+        .exposed
+        .iter()
+        .map(|e| format!("{}/{}", e.port, e.protocol.as_str().to_lowercase()))
+        .collect();
+    let failed_checks = result
+        .failed_checks
+        .iter()
+        .map(|c| c.id.to_string())
+        .collect();
+    let mut baseline = ScanBaseline::load();
+    let alerts = baseline.update(result);
+    baseline.save();
+    hooks::dispatch(&HookEvent::ScanFinished {
+        exposed_ports,
+        failed_checks,
+        new_findings: alerts.iter().map(ScanAlert::key).collect(),
+    });
+    alerts
+}
+
+fn port_key(endpoint: &ListeningEndpoint) -> String {
+    format!(
+        "port:{}/{}",
+        endpoint.port,
+        endpoint.protocol.as_str().to_lowercase()
+    )
+}
+
+fn check_key(check: &PostureCheck) -> String {
+    format!("check:{}", check.id)
+}
+
+/// Findings of the previous scan.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanBaseline {
+    /// Whether any scan was recorded yet.
+    #[serde(default)]
+    pub recorded: bool,
+    /// Keys of the findings, see [`ScanAlert::key`].
+    #[serde(default)]
+    pub findings: BTreeSet<String>,
+}
+
+impl ScanBaseline {
+    /// Load the baseline saved by a previous scan.
+    pub fn load() -> Self {
+        let path = baseline_path();
+        match fs::metadata(&path) {
+            Ok(m) if m.len() > MAX_BASELINE_FILE_SIZE => {
+                warn!("Scan baseline too large ({} bytes), ignoring it", m.len());
+                Self::default()
+            }
+            Ok(_) => match fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|text| serde_json::from_str(&text).map_err(anyhow::Error::from))
+            {
+                Ok(baseline) => baseline,
+                Err(e) => {
+                    warn!("Failed to read the scan baseline: {}", e);
+                    Self::default()
+                }
+            },
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self) {
+        let path = baseline_path();
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        match serde_json::to_string_pretty(self) {
+            Ok(text) => {
+                if let Err(e) = fs::write(&path, text) {
+                    warn!("Failed to save the scan baseline: {}", e);
+                }
+            }
+            Err(e) => warn!("Failed to serialize the scan baseline: {}", e),
+        }
+    }
+
+    /// Replace the baseline with `result` and return what it found that
+    /// the baseline did not; nothing for the first scan.
+    pub fn update(&mut self, result: ScanResult) -> Vec<ScanAlert> {
+        let alerts: Vec<ScanAlert> = result
+            .exposed
+            .into_iter()
+            .map(ScanAlert::ExposedPort)
+            .chain(result.failed_checks.into_iter().map(ScanAlert::FailedCheck))
+            .collect();
+        let findings: BTreeSet<String> = alerts.iter().map(ScanAlert::key).collect();
+        let first = !self.recorded;
+        let previous = std::mem::replace(&mut self.findings, findings);
+        self.recorded = true;
+        if first {
+            return Vec::new();
+        }
+        alerts
+            .into_iter()
+            .filter(|alert| !previous.contains(&alert.key()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::admin::Protocol;
+
+    fn endpoint(port: u16, zone: &str) -> ListeningEndpoint {
+        ListeningEndpoint {
+            process_name: Some("httpd".to_string()),
+            firewall_status: FirewallStatus::Allowed {
+                zone: zone.to_string(),
+            },
+            ..ListeningEndpoint::for_test(port, Protocol::Tcp, "0.0.0.0")
+        }
+    }
+
+    fn check(id: &'static str) -> PostureCheck {
+        PostureCheck {
+            id,
+            title: "Check",
+            weight: 10,
+            passed: false,
+            detail: String::new(),
+            remedy: None,
+        }
+    }
+
+    #[test]
+    fn test_scan_due() {
+        assert!(!scan_due(None, 0, 10_000));
+        assert!(scan_due(None, 6, 10_000));
+        assert!(!scan_due(Some(10_000), 6, 10_000 + 6 * 3_600 - 1));
+        assert!(scan_due(Some(10_000), 6, 10_000 + 6 * 3_600));
+    }
+
+    #[test]
+    fn test_baseline_update() {
+        let mut baseline = ScanBaseline::default();
+        let first = ScanResult {
+            exposed: vec![endpoint(22, "public")],
+            failed_checks: vec![check("ssh.exposed")],
+        };
+        // The first scan only records what is there
+        assert!(baseline.update(first.clone()).is_empty());
+        assert!(baseline.findings.contains("port:22/tcp"));
+        assert!(baseline.update(first).is_empty());
+
+        let alerts = baseline.update(ScanResult {
+            exposed: vec![endpoint(22, "public"), endpoint(8080, "public")],
+            failed_checks: vec![check("firewall.panic")],
+        });
+        let keys: Vec<String> = alerts.iter().map(ScanAlert::key).collect();
+        assert_eq!(keys, vec!["port:8080/tcp", "check:firewall.panic"]);
+
+        // A check that passed in between is reported again
+        let alerts = baseline.update(ScanResult {
+            exposed: Vec::new(),
+            failed_checks: vec![check("ssh.exposed"), check("firewall.panic")],
+        });
+        let keys: Vec<String> = alerts.iter().map(ScanAlert::key).collect();
+        assert_eq!(keys, vec!["check:ssh.exposed"]);
+    }
+}
@@ -11,6 +11,12 @@ use std::time::{Duration, SystemTime};
 use serde::{Deserialize, Serialize};
 use tracing::warn;
 
+use crate::migration;
+
+/// Format of the cache file. A cache of another version is discarded
+/// rather than migrated; it is rebuilt within a few samples.
+const CACHE_VERSION: u32 = 1;
+
 /// Cached statistics data.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(deny_unknown_fields)]
@@ -81,7 +87,12 @@ impl StatsCache {
             return None;
         }
         let content = fs::read_to_string(&self.path).ok()?;
-        let cached: CachedStats = serde_json::from_str(&content).ok()?;
+        let mut value: serde_json::Value = serde_json::from_str(&content).ok()?;
+        if migration::file_version(&value) != CACHE_VERSION {
+            return None;
+        }
+        value.as_object_mut()?.remove(migration::VERSION_KEY);
+        let cached: CachedStats = serde_json::from_value(value).ok()?;
 
         // Check if cache is fresh
         let now = SystemTime::now()
@@ -103,7 +114,13 @@ impl StatsCache {
             let _ = fs::create_dir_all(parent);
         }
 
-        match serde_json::to_string_pretty(stats) {
+        let versioned = serde_json::to_value(stats).map(|mut value| {
+            if let Some(object) = value.as_object_mut() {
+                object.insert(migration::VERSION_KEY.to_string(), CACHE_VERSION.into());
+            }
+            value
+        });
+        match versioned.and_then(|value| serde_json::to_string_pretty(&value)) {
             Ok(content) => {
                 if let Err(e) = fs::write(&self.path, content) {
                     warn!("Failed to save stats cache: {}", e);
@@ -4,11 +4,15 @@
 
 //! Data collectors for firewall statistics.
 
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::fs;
 use std::time::Instant;
 
-use super::models::{ConnectionStats, TrafficCounters, TrafficRatioSnapshot};
+use super::models::{
+    ConnectionStats, InterfaceCounters, InterfaceRates, ProcessConnections, TrafficCounters,
+    TrafficRatioSnapshot,
+};
+use crate::admin::ActiveConnection;
 
 /// Internal time series for connection history.
 #[derive(Debug)]
@@ -209,3 +213,288 @@ impl ConnectionCollector {
             .unwrap_or(0)
     }
 }
+
+/// Collector for connection counts per process, attributed through the
+/// socket inodes [`NetworkExposure`](crate::admin::NetworkExposure) maps
+/// to PIDs.
+#[derive(Debug)]
+pub struct ProcessCollector {
+    /// All connections per sample, oldest first.
+    totals: VecDeque<u32>,
+    /// Per-process counts, aligned with `totals`.
+    history: HashMap<String, VecDeque<u32>>,
+    max_samples: usize,
+}
+
+impl Default for ProcessCollector {
+    fn default() -> Self {
+        Self {
+            totals: VecDeque::with_capacity(60),
+            history: HashMap::new(),
+            max_samples: 60,
+        }
+    }
+}
+
+impl ProcessCollector {
+    /// Create a new process collector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a sample from the connections of one scan.
+    pub fn push_connections(&mut self, connections: &[ActiveConnection]) {
+        let mut counts: HashMap<String, u32> = HashMap::new();
+        for conn in connections {
+            *counts.entry(conn.process_label()).or_insert(0) += 1;
+        }
+
+        if self.totals.len() >= self.max_samples {
+            self.totals.pop_front();
+            for values in self.history.values_mut() {
+                values.pop_front();
+            }
+        }
+        self.totals.push_back(connections.len() as u32);
+        let samples = self.totals.len();
+
+        for (process, values) in self.history.iter_mut() {
+            values.push_back(counts.remove(process).unwrap_or(0));
+        }
+        // Processes seen for the first time had no connections before
+        for (process, count) in counts {
+            let mut values: VecDeque<u32> = std::iter::repeat_n(0, samples - 1).collect();
+            values.push_back(count);
+            self.history.insert(process, values);
+        }
+        // Forget processes without a connection in the whole window
+        self.history
+            .retain(|_, values| values.iter().any(|v| *v > 0));
+    }
+
+    /// All connections per sample, oldest first.
+    pub fn totals(&self) -> &VecDeque<u32> {
+        &self.totals
+    }
+
+    /// The `n` processes with the most connections now, ties broken by the
+    /// busiest in the window, then by name.
+    pub fn top(&self, n: usize) -> Vec<ProcessConnections> {
+        let mut top: Vec<ProcessConnections> = self
+            .history
+            .iter()
+            .map(|(process, values)| ProcessConnections {
+                process: process.clone(),
+                current: values.back().copied().unwrap_or(0),
+                history: values.iter().copied().collect(),
+            })
+            .collect();
+        top.sort_by(|a, b| {
+            b.current
+                .cmp(&a.current)
+                .then_with(|| {
+                    let peak = |p: &ProcessConnections| p.history.iter().max().copied();
+                    peak(b).cmp(&peak(a))
+                })
+                .then_with(|| a.process.cmp(&b.process))
+        });
+        top.truncate(n);
+        top
+    }
+}
+
+/// Collector for bandwidth, packet and drop rates per network interface,
+/// from the counters in `/proc/net/dev`.
+#[derive(Debug)]
+pub struct InterfaceCollector {
+    /// The previous reading, for delta computation.
+    last: Option<(Instant, HashMap<String, InterfaceCounters>)>,
+    /// Rates per interface, oldest first.
+    history: HashMap<String, VecDeque<InterfaceRates>>,
+    max_samples: usize,
+}
+
+impl Default for InterfaceCollector {
+    fn default() -> Self {
+        Self {
+            last: None,
+            history: HashMap::new(),
+            max_samples: 60,
+        }
+    }
+}
+
+impl InterfaceCollector {
+    /// Create a new interface collector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read `/proc/net/dev` and add a sample.
+    pub fn collect(&mut self) {
+        if let Ok(content) = fs::read_to_string("/proc/net/dev") {
+            self.push_counters(parse_net_dev(&content), Instant::now());
+        }
+    }
+
+    /// Add a sample from counters read at `at`. The first reading only sets
+    /// the baseline.
+    pub fn push_counters(&mut self, counters: HashMap<String, InterfaceCounters>, at: Instant) {
+        let Some((last_at, last)) = self.last.replace((at, counters.clone())) else {
+            return;
+        };
+        let secs = at.duration_since(last_at).as_secs_f64();
+        if secs <= 0.0 {
+            return;
+        }
+
+        // Interfaces that went away take their history with them
+        self.history.retain(|name, _| counters.contains_key(name));
+        let samples = self.history.values().map(VecDeque::len).max().unwrap_or(0);
+        for (name, now) in &counters {
+            let rates = last
+                .get(name)
+                .map(|before| InterfaceRates::between(before, now, secs))
+                .unwrap_or_default();
+            // Interfaces seen for the first time had no traffic before
+            let values = self.history.entry(name.clone()).or_insert_with(|| {
+                std::iter::repeat_n(InterfaceRates::default(), samples).collect()
+            });
+            if values.len() >= self.max_samples {
+                values.pop_front();
+            }
+            values.push_back(rates);
+        }
+    }
+
+    /// Interfaces with history, sorted by name.
+    pub fn interfaces(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.history.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Rates of `interface` per sample, oldest first.
+    pub fn history(&self, interface: &str) -> Option<&VecDeque<InterfaceRates>> {
+        self.history.get(interface)
+    }
+}
+
+/// Counters per interface from the contents of `/proc/net/dev`, without
+/// the loopback interface.
+fn parse_net_dev(content: &str) -> HashMap<String, InterfaceCounters> {
+    content
+        .lines()
+        .skip(2)
+        .filter_map(|line| {
+            let (name, fields) = line.split_once(':')?;
+            let name = name.trim();
+            if name == "lo" || name.is_empty() {
+                return None;
+            }
+            let fields: Vec<u64> = fields
+                .split_whitespace()
+                .map(|field| field.parse().ok())
+                .collect::<Option<_>>()?;
+            if fields.len() < 12 {
+                return None;
+            }
+            // Receive: bytes packets errs drop ...; transmit starts at column 8
+            let counters = InterfaceCounters {
+                rx_bytes: fields[0],
+                rx_packets: fields[1],
+                rx_drops: fields[3],
+                tx_bytes: fields[8],
+                tx_packets: fields[9],
+                tx_drops: fields[11],
+            };
+            Some((name.to_string(), counters))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::admin::Protocol;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    fn connection(process: &str) -> ActiveConnection {
+        ActiveConnection {
+            local_addr: IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)),
+            local_port: 40000,
+            remote_addr: IpAddr::V4(Ipv4Addr::new(203, 0, 113, 7)),
+            remote_port: 443,
+            protocol: Protocol::Tcp,
+            inode: 1,
+            pid: Some(1),
+            process_name: Some(process.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_process_collector() {
+        let mut collector = ProcessCollector {
+            max_samples: 3,
+            ..Default::default()
+        };
+        collector.push_connections(&[connection("firefox"), connection("firefox")]);
+        collector.push_connections(&[connection("firefox"), connection("ssh")]);
+        collector.push_connections(&[connection("ssh")]);
+
+        let top = collector.top(5);
+        assert_eq!(top[0].process, "ssh");
+        assert_eq!(top[0].history, vec![0, 1, 1]);
+        assert_eq!(top[1].process, "firefox");
+        assert_eq!(top[1].history, vec![2, 1, 0]);
+        assert_eq!(
+            collector.totals().iter().copied().collect::<Vec<_>>(),
+            vec![2, 2, 1]
+        );
+
+        // firefox leaves the window once its last connection scrolls out
+        collector.push_connections(&[]);
+        collector.push_connections(&[]);
+        assert_eq!(collector.top(5).len(), 1);
+        assert_eq!(collector.top(5)[0].history, vec![1, 0, 0]);
+    }
+
+    #[test]
+    fn test_interface_collector() {
+        let header = "Inter-|   Receive                                                |  Transmit\n \
+                      face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed\n";
+        let first = format!(
+            "{}    lo:  500 5 0 0 0 0 0 0  500 5 0 0 0 0 0 0\n  eth0: 1000 10 0 1 0 0 0 0 4000 20 0 0 0 0 0 0\n",
+            header
+        );
+        let second = format!(
+            "{}  eth0: 3000 30 0 3 0 0 0 0 5000 30 0 0 0 0 0 0\n wlan0: 100 1 0 0 0 0 0 0 100 1 0 0 0 0 0 0\n",
+            header
+        );
+        let counters = parse_net_dev(&first);
+        assert_eq!(counters.len(), 1);
+        assert_eq!(counters["eth0"].rx_drops, 1);
+        assert_eq!(counters["eth0"].tx_bytes, 4000);
+
+        let mut collector = InterfaceCollector::new();
+        let start = Instant::now();
+        collector.push_counters(counters, start);
+        assert!(collector.interfaces().is_empty());
+        collector.push_counters(
+            parse_net_dev(&second),
+            start + std::time::Duration::from_secs(2),
+        );
+        assert_eq!(collector.interfaces(), vec!["eth0", "wlan0"]);
+        let eth0 = collector.history("eth0").unwrap();
+        assert_eq!(eth0.len(), 1);
+        assert_eq!(eth0[0].rx_bytes, 1000.0);
+        assert_eq!(eth0[0].rx_packets, 10.0);
+        assert_eq!(eth0[0].rx_drops, 1.0);
+        assert_eq!(eth0[0].tx_bytes, 500.0);
+        // A new interface has no rate until its second reading
+        assert_eq!(
+            collector.history("wlan0").unwrap()[0],
+            InterfaceRates::default()
+        );
+    }
+}
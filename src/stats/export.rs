@@ -0,0 +1,235 @@
+// Security Center - Chart Data Export
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Export of the time series behind the Overview charts.
+//!
+//! A [`ChartData`] holds what a chart plots: one column per series and one
+//! row per sample, oldest first, taken every `interval_secs`. Samples carry
+//! no time of their own, so [`ChartData::timestamps`] counts back from when
+//! the data was taken. Times are written in RFC 3339 and numbers with a dot,
+//! whatever the locale, so spreadsheets and scripts read them alike.
+
+use chrono::{DateTime, Local};
+use serde_json::{json, Map, Value};
+
+/// File format of an export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// The samples of a chart's series, aligned by time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartData {
+    /// Chart title, e.g. `Connections`.
+    pub title: String,
+    /// Unit of the values, e.g. `KiB/s`.
+    pub unit: String,
+    /// Seconds between samples.
+    pub interval_secs: u64,
+    /// Unix time of the newest sample.
+    pub taken_at: i64,
+    pub columns: Vec<String>,
+    /// One value per column; `None` where a series has no sample yet.
+    pub rows: Vec<Vec<Option<f64>>>,
+}
+
+impl ChartData {
+    /// Align `series`, each a name and its values oldest first, on their
+    /// newest sample, taken now. Shorter series are padded at the start.
+    pub fn from_series(
+        title: &str,
+        unit: &str,
+        interval_secs: u64,
+        series: Vec<(String, Vec<f64>)>,
+    ) -> Self {
+        let len = series
+            .iter()
+            .map(|(_, values)| values.len())
+            .max()
+            .unwrap_or(0);
+        let rows = (0..len)
+            .map(|row| {
+                series
+                    .iter()
+                    .map(|(_, values)| {
+                        (row + values.len())
+                            .checked_sub(len)
+                            .map(|index| values[index])
+                    })
+                    .collect()
+            })
+            .collect();
+        Self {
+            title: title.to_string(),
+            unit: unit.to_string(),
+            interval_secs,
+            taken_at: Local::now().timestamp(),
+            columns: series.into_iter().map(|(name, _)| name).collect(),
+            rows,
+        }
+    }
+
+    /// Unix time of every row.
+    pub fn timestamps(&self) -> Vec<i64> {
+        let count = self.rows.len() as i64;
+        (0..count)
+            .map(|row| self.taken_at - (count - 1 - row) * self.interval_secs as i64)
+            .collect()
+    }
+
+    /// The data as CSV: a `time` column, then one column per series.
+    pub fn to_csv(&self) -> String {
+        let mut csv = std::iter::once("time".to_string())
+            .chain(self.columns.iter().map(|c| csv_field(c)))
+            .collect::<Vec<_>>()
+            .join(",");
+        csv.push('\n');
+        for (time, row) in self.timestamps().into_iter().zip(&self.rows) {
+            let fields: Vec<String> = std::iter::once(rfc3339(time))
+                .chain(
+                    row.iter()
+                        .map(|value| value.map(|v| v.to_string()).unwrap_or_default()),
+                )
+                .collect();
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// The data as a JSON document with one object per sample.
+    pub fn to_json(&self) -> String {
+        let samples: Vec<Value> = self
+            .timestamps()
+            .into_iter()
+            .zip(&self.rows)
+            .map(|(time, row)| {
+                let mut sample = Map::new();
+                sample.insert("time".to_string(), json!(rfc3339(time)));
+                for (column, value) in self.columns.iter().zip(row) {
+                    sample.insert(column.clone(), json!(value));
+                }
+                Value::Object(sample)
+            })
+            .collect();
+        let document = json!({
+            "chart": self.title,
+            "unit": self.unit,
+            "interval": self.interval_secs,
+            "columns": self.columns,
+            "samples": samples,
+        });
+        serde_json::to_string_pretty(&document).unwrap_or_default()
+    }
+
+    pub fn render(&self, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Csv => self.to_csv(),
+            ExportFormat::Json => self.to_json(),
+        }
+    }
+
+    /// Suggested file name, e.g. `connections-2026-10-17-1530.csv`.
+    pub fn file_name(&self, format: ExportFormat) -> String {
+        let slug: String = self
+            .title
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '-' })
+            .collect();
+        let stamp = DateTime::from_timestamp(self.taken_at, 0)
+            .map(|dt| dt.with_timezone(&Local).format("%Y-%m-%d-%H%M").to_string())
+            .unwrap_or_default();
+        format!("{}-{}.{}", slug, stamp, format.extension())
+    }
+}
+
+fn rfc3339(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|dt| dt.with_timezone(&Local).to_rfc3339())
+        .unwrap_or_default()
+}
+
+/// Quote a CSV field when it holds a separator, quote or line break.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data() -> ChartData {
+        let mut data = ChartData::from_series(
+            "Traffic Ratio",
+            "packets",
+            5,
+            vec![
+                ("Accepted".to_string(), vec![1.0, 2.0, 3.5]),
+                ("Denied, logged".to_string(), vec![4.0]),
+            ],
+        );
+        data.taken_at = 1_000;
+        data
+    }
+
+    #[test]
+    fn test_from_series() {
+        let data = data();
+        assert_eq!(
+            data.rows,
+            vec![
+                vec![Some(1.0), None],
+                vec![Some(2.0), None],
+                vec![Some(3.5), Some(4.0)],
+            ]
+        );
+        assert_eq!(data.timestamps(), vec![990, 995, 1_000]);
+        let empty = ChartData::from_series("Empty", "", 1, Vec::new());
+        assert!(empty.rows.is_empty());
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let csv = data().to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "time,Accepted,\"Denied, logged\"");
+        assert!(lines[1].ends_with(",1,"));
+        assert!(lines[3].ends_with(",3.5,4"));
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json: Value = serde_json::from_str(&data().to_json()).unwrap();
+        assert_eq!(json["chart"], "Traffic Ratio");
+        assert_eq!(json["interval"], 5);
+        assert_eq!(json["samples"].as_array().unwrap().len(), 3);
+        assert_eq!(json["samples"][0]["Accepted"], 1.0);
+        assert!(json["samples"][0]["Denied, logged"].is_null());
+        assert_eq!(json["samples"][2]["Denied, logged"], 4.0);
+    }
+
+    #[test]
+    fn test_file_name() {
+        let name = data().file_name(ExportFormat::Csv);
+        assert!(name.starts_with("traffic-ratio-"));
+        assert!(name.ends_with(".csv"));
+    }
+}
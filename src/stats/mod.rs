@@ -4,14 +4,22 @@
 
 //! Traffic statistics collection and aggregation.
 //!
-//! Retained for reuse: the overview dashboard now drives its live view from
-//! netlink/procfs directly rather than these cached collectors, so nothing
-//! constructs them at the moment.
+//! The overview dashboard drives most of its live view from netlink/procfs
+//! directly; of these collectors it only uses [`ProcessCollector`] for the
+//! connection history per process and [`InterfaceCollector`] for bandwidth
+//! per interface. The others are retained for reuse. [`ChartData`] carries
+//! the series a chart plots out to CSV or JSON. [`RuleStats`] counts the
+//! port rules for the Ports summary and the Overview, and [`RuleHistory`]
+//! keeps those counts over time.
 #![allow(dead_code, unused_imports)]
 
 mod cache;
 mod collectors;
+mod export;
 pub mod models;
+mod rules;
 
 pub use cache::{CachedConnectionStats, CachedStats, CachedTrafficRatio, StatsCache};
-pub use collectors::{ConnectionCollector, TrafficCollector};
+pub use collectors::{ConnectionCollector, InterfaceCollector, ProcessCollector, TrafficCollector};
+pub use export::{ChartData, ExportFormat};
+pub use rules::{RuleCounts, RuleHistory, RuleSample, RuleStats};
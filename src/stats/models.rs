@@ -58,6 +58,55 @@ impl TrafficRatioSnapshot {
     }
 }
 
+/// Connections of one process: the latest count and its history.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProcessConnections {
+    /// Process name, or `pid N` when the name cannot be read.
+    pub process: String,
+    /// Connections in the latest sample.
+    pub current: u32,
+    /// One count per sample, oldest first.
+    pub history: Vec<u32>,
+}
+
+/// Cumulative counters of one network interface, as in `/proc/net/dev`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InterfaceCounters {
+    pub rx_bytes: u64,
+    pub rx_packets: u64,
+    pub rx_drops: u64,
+    pub tx_bytes: u64,
+    pub tx_packets: u64,
+    pub tx_drops: u64,
+}
+
+/// Per-second rates of one network interface over one sample.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InterfaceRates {
+    pub rx_bytes: f64,
+    pub rx_packets: f64,
+    pub rx_drops: f64,
+    pub tx_bytes: f64,
+    pub tx_packets: f64,
+    pub tx_drops: f64,
+}
+
+impl InterfaceRates {
+    /// Rates between two readings of the counters `secs` apart. Counters
+    /// that went backwards, as when an interface is recreated, count as 0.
+    pub fn between(old: &InterfaceCounters, new: &InterfaceCounters, secs: f64) -> Self {
+        let rate = |old: u64, new: u64| new.saturating_sub(old) as f64 / secs;
+        Self {
+            rx_bytes: rate(old.rx_bytes, new.rx_bytes),
+            rx_packets: rate(old.rx_packets, new.rx_packets),
+            rx_drops: rate(old.rx_drops, new.rx_drops),
+            tx_bytes: rate(old.tx_bytes, new.tx_bytes),
+            tx_packets: rate(old.tx_packets, new.tx_packets),
+            tx_drops: rate(old.tx_drops, new.tx_drops),
+        }
+    }
+}
+
 /// Connection statistics by protocol.
 #[derive(Debug, Clone, Default)]
 pub struct ConnectionStats {
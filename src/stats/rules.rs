@@ -0,0 +1,251 @@
+// Security Center - Rule Statistics
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Counts of the port rules, for the summaries and charts that show them.
+//!
+//! [`RuleStats::from_ports`] counts open and blocked rules and the ports
+//! they cover, in total, per zone and per protocol, so a blocked range of
+//! 100 ports weighs 100 rather than 1. A port covered in two zones or for
+//! both protocols counts once for each.
+//!
+//! [`RuleHistory`] keeps the totals over time in
+//! `~/.local/share/security-center/rule-history.json`: a sample when they
+//! change, and at least one a day, for 90 days.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::models::Port;
+
+/// Time a sample is kept, in seconds.
+const HISTORY_RETENTION: i64 = 90 * 24 * 3600;
+/// Time after which an unchanged sample is repeated, in seconds.
+const SAMPLE_INTERVAL: i64 = 24 * 3600;
+/// Most samples kept, whatever their age.
+const MAX_SAMPLES: usize = 2000;
+const MAX_HISTORY_FILE_SIZE: u64 = 1_048_576; // 1 MB
+
+/// Open and blocked rules, and the ports they cover.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleCounts {
+    pub open_rules: u32,
+    pub blocked_rules: u32,
+    pub open_ports: u32,
+    pub blocked_ports: u32,
+}
+
+impl RuleCounts {
+    fn add(&mut self, port: &Port) {
+        if port.is_blocked() {
+            self.blocked_rules += 1;
+            self.blocked_ports += port.port_count();
+        } else {
+            self.open_rules += 1;
+            self.open_ports += port.port_count();
+        }
+    }
+}
+
+/// Counts of a set of port rules.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleStats {
+    pub total: RuleCounts,
+    /// By zone name; rules without a zone are left out.
+    pub zones: BTreeMap<String, RuleCounts>,
+    /// By protocol (`tcp`, `udp`).
+    pub protocols: BTreeMap<String, RuleCounts>,
+    /// Rules covering a range rather than a single port.
+    pub ranges: u32,
+    /// Ports those ranges cover.
+    pub range_ports: u32,
+}
+
+impl RuleStats {
+    /// Count open and blocked `ports`.
+    pub fn from_ports(ports: &[Port]) -> Self {
+        let mut stats = Self::default();
+        for port in ports {
+            stats.total.add(port);
+            if let Some(zone) = &port.zone {
+                stats.zones.entry(zone.clone()).or_default().add(port);
+            }
+            stats
+                .protocols
+                .entry(port.protocol.clone())
+                .or_default()
+                .add(port);
+            if port.is_range() {
+                stats.ranges += 1;
+                stats.range_ports += port.port_count();
+            }
+        }
+        stats
+    }
+
+    /// Counts for `protocol`, zero when it has no rules.
+    pub fn protocol(&self, protocol: &str) -> RuleCounts {
+        self.protocols.get(protocol).copied().unwrap_or_default()
+    }
+}
+
+/// The totals at one time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleSample {
+    /// Unix seconds.
+    pub timestamp: i64,
+    pub counts: RuleCounts,
+}
+
+/// Totals over time, oldest first.
+#[derive(Debug, Default)]
+pub struct RuleHistory {
+    samples: Vec<RuleSample>,
+    path: PathBuf,
+    dirty: bool,
+}
+
+impl RuleHistory {
+    /// Location of the saved history.
+    pub fn path() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("security-center")
+            .join("rule-history.json")
+    }
+
+    /// Load the history saved by a previous run.
+    pub fn load() -> Self {
+        let path = Self::path();
+        let samples = match fs::metadata(&path) {
+            Ok(m) if m.len() > MAX_HISTORY_FILE_SIZE => {
+                warn!("Rule history too large ({} bytes), ignoring it", m.len());
+                Vec::new()
+            }
+            Ok(_) => match fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|text| serde_json::from_str(&text).map_err(anyhow::Error::from))
+            {
+                Ok(samples) => samples,
+                Err(e) => {
+                    warn!("Failed to read rule history: {}", e);
+                    Vec::new()
+                }
+            },
+            Err(_) => Vec::new(),
+        };
+        Self {
+            samples,
+            path,
+            dirty: false,
+        }
+    }
+
+    /// The samples, oldest first.
+    pub fn samples(&self) -> &[RuleSample] {
+        &self.samples
+    }
+
+    /// Add the totals of `stats` at `now` (Unix seconds) when they changed
+    /// or the last sample is a day old, dropping samples past retention.
+    pub fn record(&mut self, stats: &RuleStats, now: i64) {
+        let due = self.samples.last().is_none_or(|last| {
+            last.counts != stats.total || now - last.timestamp >= SAMPLE_INTERVAL
+        });
+        if !due {
+            return;
+        }
+        self.samples.push(RuleSample {
+            timestamp: now,
+            counts: stats.total,
+        });
+        self.samples
+            .retain(|sample| now - sample.timestamp < HISTORY_RETENTION);
+        let excess = self.samples.len().saturating_sub(MAX_SAMPLES);
+        self.samples.drain(..excess);
+        self.dirty = true;
+    }
+
+    /// Write the history if it changed.
+    pub fn save(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        match serde_json::to_string(&self.samples) {
+            Ok(text) => {
+                let partial = self.path.with_extension("json.tmp");
+                if let Err(e) =
+                    fs::write(&partial, text).and_then(|_| fs::rename(&partial, &self.path))
+                {
+                    warn!("Failed to save rule history: {}", e);
+                }
+            }
+            Err(e) => warn!("Failed to serialize rule history: {}", e),
+        }
+        self.dirty = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocked(number: u16, end: u16, protocol: &str, zone: &str) -> Port {
+        let mut port = Port::range_with_zone(number, end, protocol, zone);
+        port.action = "reject".to_string();
+        port
+    }
+
+    #[test]
+    fn test_rule_stats() {
+        let ports = vec![
+            Port::with_zone(22, "tcp", "public"),
+            Port::with_zone(53, "udp", "home"),
+            blocked(6000, 6099, "udp", "public"),
+            blocked(3389, 3389, "tcp", "public"),
+        ];
+        let stats = RuleStats::from_ports(&ports);
+        assert_eq!(
+            stats.total,
+            RuleCounts {
+                open_rules: 2,
+                blocked_rules: 2,
+                open_ports: 2,
+                blocked_ports: 101,
+            }
+        );
+        assert_eq!(stats.zones["public"].blocked_ports, 101);
+        assert_eq!(stats.zones["home"].open_rules, 1);
+        assert_eq!(stats.protocol("udp").blocked_ports, 100);
+        assert_eq!(stats.protocol("tcp").open_ports, 1);
+        assert_eq!(stats.protocol("sctp"), RuleCounts::default());
+        assert_eq!((stats.ranges, stats.range_ports), (1, 100));
+    }
+
+    #[test]
+    fn test_history_records_changes() {
+        let mut history = RuleHistory::default();
+        let mut stats = RuleStats::from_ports(&[Port::with_zone(22, "tcp", "public")]);
+        history.record(&stats, 1_000);
+        history.record(&stats, 2_000);
+        assert_eq!(history.samples().len(), 1);
+
+        stats.total.blocked_ports = 5;
+        history.record(&stats, 3_000);
+        assert_eq!(history.samples().len(), 2);
+        // Unchanged, but a day later
+        history.record(&stats, 3_000 + SAMPLE_INTERVAL);
+        assert_eq!(history.samples().len(), 3);
+
+        // Samples past retention are dropped
+        history.record(&stats, 1_000 + HISTORY_RETENTION + SAMPLE_INTERVAL);
+        assert!(history.samples().iter().all(|s| s.timestamp > 1_000));
+    }
+}
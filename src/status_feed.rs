@@ -0,0 +1,252 @@
+// Security Center - Status Feed
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Machine-readable status file for desktop widgets such as a GNOME Shell
+//! extension showing the hardening score and exposed port count in the top
+//! bar. Polling a file costs the widget nothing while the app is closed and
+//! needs no D-Bus name or token.
+//!
+//! While enabled, a background thread rewrites [`feed_path`]
+//! (`$XDG_RUNTIME_DIR/security-center/status.json`) every
+//! [`UPDATE_INTERVAL`]. The file is replaced atomically, so readers never
+//! see a partial write, and removed when the feed stops or the application
+//! quits. The contract is documented in `docs/status-feed.md`:
+//!
+//! ```json
+//! {
+//!   "schema": 1,
+//!   "updated": 1760000000,
+//!   "interval": 30,
+//!   "firewall": { "running": true, "default_zone": "public", "panic_mode": false },
+//!   "score": 82,
+//!   "findings": { "high": 0, "medium": 2, "low": 1 },
+//!   "exposure": { "listening": 12, "exposed_ports": 3, "warnings": 1 }
+//! }
+//! ```
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use tracing::{debug, info, warn};
+
+use crate::admin::{Assessment, NetworkExposure, Severity};
+use crate::firewall;
+
+/// Version of the file layout. Bumped only when a field is removed or
+/// changes meaning; new fields may appear at any time.
+pub const SCHEMA_VERSION: u32 = 1;
+/// Time between updates.
+pub const UPDATE_INTERVAL: Duration = Duration::from_secs(30);
+/// How often the update thread checks whether it should stop.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Location of the status file: `$XDG_RUNTIME_DIR/security-center/status.json`,
+/// or the user cache directory where there is no runtime directory.
+pub fn feed_path() -> PathBuf {
+    dirs::runtime_dir()
+        .or_else(dirs::cache_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("security-center")
+        .join("status.json")
+}
+
+/// One reading of everything the feed reports.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatusSnapshot {
+    /// Unix time in seconds.
+    pub updated: u64,
+    pub firewall_running: bool,
+    pub default_zone: Option<String>,
+    pub panic_mode: Option<bool>,
+    /// Hardening score, 0-100.
+    pub score: Option<u32>,
+    /// Findings by severity: high, medium, low.
+    pub findings: [usize; 3],
+    /// Listening sockets.
+    pub listening: usize,
+    /// Listening sockets bound to all interfaces.
+    pub exposed_ports: usize,
+    /// Listening sockets reachable from a public or external zone.
+    pub warnings: usize,
+}
+
+impl StatusSnapshot {
+    /// Read the firewall, the assessment and the listening sockets. Blocks
+    /// on D-Bus and procfs, so call it off the main thread.
+    pub fn collect() -> Self {
+        let mut snapshot = Self {
+            updated: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            ..Default::default()
+        };
+
+        let state = firewall::read_blocking(|client| {
+            client.connect().ok()?;
+            Some((
+                client.get_default_zone().ok(),
+                client.query_panic_mode().ok(),
+            ))
+        });
+        if let Ok(Some((default_zone, panic_mode))) = state {
+            snapshot.firewall_running = true;
+            snapshot.default_zone = default_zone;
+            snapshot.panic_mode = panic_mode;
+        }
+
+        let assessment = Assessment::run();
+        snapshot.score = Some(assessment.score());
+        for finding in &assessment.findings {
+            match finding.severity {
+                Severity::High => snapshot.findings[0] += 1,
+                Severity::Medium => snapshot.findings[1] += 1,
+                Severity::Low => snapshot.findings[2] += 1,
+                Severity::Info => {}
+            }
+        }
+
+        match NetworkExposure::new().scan() {
+            Ok(endpoints) => {
+                snapshot.listening = endpoints.len();
+                snapshot.exposed_ports = endpoints.iter().filter(|e| e.is_exposed()).count();
+                snapshot.warnings = endpoints.iter().filter(|e| e.warning().is_some()).count();
+            }
+            Err(e) => debug!("Status feed: cannot scan listening sockets: {}", e),
+        }
+        snapshot
+    }
+
+    /// The document written to the status file.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "schema": SCHEMA_VERSION,
+            "updated": self.updated,
+            "interval": UPDATE_INTERVAL.as_secs(),
+            "firewall": {
+                "running": self.firewall_running,
+                "default_zone": self.default_zone,
+                "panic_mode": self.panic_mode,
+            },
+            "score": self.score,
+            "findings": {
+                "high": self.findings[0],
+                "medium": self.findings[1],
+                "low": self.findings[2],
+            },
+            "exposure": {
+                "listening": self.listening,
+                "exposed_ports": self.exposed_ports,
+                "warnings": self.warnings,
+            },
+        })
+    }
+}
+
+/// Replace the status file with `snapshot`.
+fn write_feed(snapshot: &StatusSnapshot) -> Result<()> {
+    let path = feed_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create the status directory")?;
+    }
+    let text = serde_json::to_string_pretty(&snapshot.to_json())? + "\n";
+    // Write beside the file and rename over it so readers see whole files
+    let partial = path.with_extension("json.tmp");
+    fs::write(&partial, text).context("Failed to write the status file")?;
+    fs::rename(&partial, &path).context("Failed to replace the status file")?;
+    Ok(())
+}
+
+/// A running status feed. Dropping it stops the updates and removes the
+/// status file.
+#[derive(Debug)]
+pub struct StatusFeed {
+    stop: Arc<AtomicBool>,
+}
+
+impl StatusFeed {
+    /// Write the status file now and then every [`UPDATE_INTERVAL`] on a
+    /// background thread.
+    pub fn start() -> Result<Self> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let flag = stop.clone();
+        thread::Builder::new()
+            .name("status-feed".to_string())
+            .spawn(move || run(&flag))?;
+        info!("Status feed writing to {}", feed_path().display());
+        Ok(Self { stop })
+    }
+}
+
+impl Drop for StatusFeed {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // A file left behind would show the last reading as current
+        let _ = fs::remove_file(feed_path());
+        info!("Status feed stopped");
+    }
+}
+
+fn run(stop: &AtomicBool) {
+    while !stop.load(Ordering::Relaxed) {
+        let snapshot = StatusSnapshot::collect();
+        // Stopped while collecting: the file is already gone
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+        if let Err(e) = write_feed(&snapshot) {
+            warn!("Status feed update failed: {:#}", e);
+        }
+        let mut waited = Duration::ZERO;
+        while waited < UPDATE_INTERVAL && !stop.load(Ordering::Relaxed) {
+            thread::sleep(POLL_INTERVAL);
+            waited += POLL_INTERVAL;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_json_contract() {
+        let snapshot = StatusSnapshot {
+            updated: 1_760_000_000,
+            firewall_running: true,
+            default_zone: Some("public".to_string()),
+            panic_mode: Some(false),
+            score: Some(82),
+            findings: [0, 2, 1],
+            listening: 12,
+            exposed_ports: 3,
+            warnings: 1,
+        };
+        let doc = snapshot.to_json();
+        assert_eq!(doc["schema"], 1);
+        assert_eq!(doc["updated"], 1_760_000_000u64);
+        assert_eq!(doc["interval"], 30);
+        assert_eq!(doc["firewall"]["running"], true);
+        assert_eq!(doc["firewall"]["default_zone"], "public");
+        assert_eq!(doc["firewall"]["panic_mode"], false);
+        assert_eq!(doc["score"], 82);
+        assert_eq!(doc["findings"]["medium"], 2);
+        assert_eq!(doc["exposure"]["exposed_ports"], 3);
+    }
+
+    #[test]
+    fn test_unknown_values_are_null() {
+        let doc = StatusSnapshot::default().to_json();
+        assert_eq!(doc["firewall"]["running"], false);
+        assert!(doc["firewall"]["default_zone"].is_null());
+        assert!(doc["firewall"]["panic_mode"].is_null());
+        assert!(doc["score"].is_null());
+    }
+}
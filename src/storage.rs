@@ -3,20 +3,62 @@
 // SPDX-License-Identifier: MIT
 
 //! Persistent storage for port metadata.
-
-use std::collections::HashMap;
+//!
+//! Entries are keyed by port spec, protocol and zone
+//! ([`PortStorage::make_key`]). [`PortStorage::lookup`] finds the entry
+//! documenting a port seen elsewhere, such as a listening socket on the
+//! Network Exposure page.
+//!
+//! The names and descriptions can be shared without the rules: a
+//! [`MetadataProfile`] exported on one machine is imported on another with
+//! [`PortStorage::plan_import`], which sets apart the entries that would
+//! overwrite a different local name, and [`PortStorage::apply_import`],
+//! which replaces only the conflicting entries the user picked.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
+use anyhow::{bail, Context};
 use serde::{Deserialize, Serialize};
 use tracing::warn;
 
-use crate::validation::{validate_port_name, validate_protocol};
+use crate::migration::{self, Schema};
+use crate::validation::{
+    parse_port_spec, validate_port_name, validate_protocol, validate_zone_name,
+};
 
 const MAX_STORAGE_FILE_SIZE: u64 = 1_048_576; // 1 MB
 
+/// Marks a file as a metadata profile rather than another JSON file.
+const PROFILE_FORMAT: &str = "security-center-port-names";
+const PROFILE_VERSION: u32 = 1;
+/// Longest description kept from an imported profile, in characters.
+const MAX_DESCRIPTION_CHARS: usize = 1024;
+
+/// Format history of `port_metadata.json`; see [`migration`].
+const SCHEMA: Schema = Schema {
+    name: "port metadata",
+    steps: &[wrap_entries],
+};
+
+/// Version 1 moved the entries from the top level into `ports`, making room
+/// for the version field.
+fn wrap_entries(value: &mut serde_json::Value) -> anyhow::Result<()> {
+    *value = serde_json::json!({ "ports": value.take() });
+    Ok(())
+}
+
+/// Contents of `port_metadata.json`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PortFile {
+    ports: HashMap<String, PortMetadata>,
+}
+
 /// Metadata about a port rule.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(deny_unknown_fields)]
 pub struct PortMetadata {
     pub name: String,
@@ -37,10 +79,14 @@ pub struct PortMetadata {
     /// releases, whose `deny_unknown_fields` rejects unknown keys.
     #[serde(default, skip_serializing_if = "is_zero")]
     pub end_port: u16,
+    /// When a temporary rule is removed, as a Unix timestamp; 0 for a rule
+    /// that does not expire. Skipped when 0 like `end_port`.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub expires_at: i64,
 }
 
-fn is_zero(value: &u16) -> bool {
-    *value == 0
+fn is_zero<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
 }
 
 impl PortMetadata {
@@ -55,6 +101,7 @@ impl PortMetadata {
             protocol: String::new(),
             port: 0,
             end_port: 0,
+            expires_at: 0,
         }
     }
 
@@ -69,17 +116,42 @@ impl PortMetadata {
             protocol: String::new(),
             port: 0,
             end_port: 0,
+            expires_at: 0,
+        }
+    }
+
+    /// When the rule was created from this application, if recorded.
+    pub fn created(&self) -> Option<chrono::NaiveDateTime> {
+        chrono::NaiveDateTime::parse_from_str(&self.created_at, "%Y-%m-%d %H:%M:%S").ok()
+    }
+
+    /// When a temporary rule is removed, in local time.
+    pub fn expires(&self) -> Option<chrono::DateTime<chrono::Local>> {
+        if self.expires_at <= 0 {
+            return None;
         }
+        chrono::DateTime::from_timestamp(self.expires_at, 0)
+            .map(|t| t.with_timezone(&chrono::Local))
     }
 }
 
 /// Storage for port metadata.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct PortStorage {
     data: HashMap<String, PortMetadata>,
     path: PathBuf,
     loaded: bool,
     dirty: bool,
+    /// Modification time of the file when it was last read or written.
+    /// The background monitor edits the file too, e.g. when a temporary
+    /// rule expires; a newer file is read again.
+    modified: Option<SystemTime>,
+}
+
+impl Default for PortStorage {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PortStorage {
@@ -94,36 +166,28 @@ impl PortStorage {
             path,
             loaded: false,
             dirty: false,
+            modified: None,
         }
     }
 
+    fn file_modified(&self) -> Option<SystemTime> {
+        fs::metadata(&self.path).and_then(|m| m.modified()).ok()
+    }
+
     fn ensure_loaded(&mut self) {
-        if !self.loaded {
+        if !self.loaded || (!self.dirty && self.file_modified() != self.modified) {
             self.load();
             self.loaded = true;
         }
     }
 
     fn load(&mut self) {
-        let metadata = fs::metadata(&self.path);
-        if let Ok(m) = metadata {
-            if m.len() > MAX_STORAGE_FILE_SIZE {
-                warn!("Port metadata file too large ({} bytes), ignoring", m.len());
-                self.dirty = false;
-                return;
-            }
-        }
-
-        if let Ok(content) = fs::read_to_string(&self.path) {
-            match serde_json::from_str::<HashMap<String, PortMetadata>>(&content) {
-                Ok(data) => {
-                    self.data = Self::sanitize_data(data);
-                }
-                Err(e) => {
-                    warn!("Failed to parse port metadata: {}", e);
-                }
-            }
+        if let Some(file) = migration::load::<PortFile>(&SCHEMA, &self.path, MAX_STORAGE_FILE_SIZE)
+        {
+            self.data = Self::sanitize_data(file.ports);
         }
+        // Migrating or restoring rewrites the file
+        self.modified = self.file_modified();
         self.dirty = false;
     }
 
@@ -147,15 +211,10 @@ impl PortStorage {
             if meta.end_port != 0 && meta.end_port <= meta.port {
                 meta.end_port = 0;
             }
-            // Sanitize the name through the same charset rules as user input.
-            // The metadata file is untrusted (SECURITY.md), and names are shown
-            // in row titles; an unsanitized name could inject Pango markup.
-            // Truncate over-long names first so only the charset can reject.
-            // Cut on a char boundary so multibyte names cannot panic.
-            if meta.name.chars().count() > 64 {
-                meta.name = meta.name.chars().take(64).collect();
+            if meta.expires_at < 0 {
+                meta.expires_at = 0;
             }
-            match validate_port_name(&meta.name) {
+            match clean_name(&meta.name) {
                 Some(clean) => meta.name = clean,
                 None => {
                     warn!("Discarding port metadata entry with unsafe name");
@@ -168,40 +227,17 @@ impl PortStorage {
     }
 
     pub fn save(&mut self) {
-        use std::io::Write;
-        #[cfg(unix)]
-        use std::os::unix::fs::PermissionsExt;
-
         if !self.dirty {
             return;
         }
 
-        if let Some(parent) = self.path.parent() {
-            let _ = fs::create_dir_all(parent);
-        }
-
-        match serde_json::to_string_pretty(&self.data) {
-            Ok(content) => match fs::File::create(&self.path) {
-                Ok(mut file) => {
-                    #[cfg(unix)]
-                    {
-                        if let Err(e) = file.set_permissions(fs::Permissions::from_mode(0o600)) {
-                            warn!("Failed to set file permissions: {}", e);
-                        }
-                    }
-                    if let Err(e) = file.write_all(content.as_bytes()) {
-                        warn!("Failed to save port metadata: {}", e);
-                    } else {
-                        self.dirty = false;
-                    }
-                }
-                Err(e) => {
-                    warn!("Failed to create port metadata file: {}", e);
-                }
-            },
-            Err(e) => {
-                warn!("Failed to serialize port metadata: {}", e);
+        let file = serde_json::json!({ "ports": &self.data });
+        match migration::save(&SCHEMA, &self.path, &file) {
+            Ok(()) => {
+                self.dirty = false;
+                self.modified = self.file_modified();
             }
+            Err(e) => warn!("Failed to save port metadata: {:#}", e),
         }
     }
 
@@ -240,11 +276,271 @@ impl PortStorage {
         self.data.values().cloned().collect()
     }
 
+    /// Every entry with its key.
+    pub fn entries(&mut self) -> Vec<(String, PortMetadata)> {
+        self.ensure_loaded();
+        self.data
+            .iter()
+            .map(|(key, metadata)| (key.clone(), metadata.clone()))
+            .collect()
+    }
+
+    /// The entry documenting `port`/`protocol`, its own or a range covering
+    /// it. An entry for `zone` wins over one for another zone, and a single
+    /// port over a range.
+    pub fn lookup(
+        &mut self,
+        port: u16,
+        protocol: &str,
+        zone: Option<&str>,
+    ) -> Option<PortMetadata> {
+        self.ensure_loaded();
+        find_entry(&self.data, port, protocol, zone).cloned()
+    }
+
     /// Build a storage key from a port spec ("80" or "10-20"), protocol and zone.
     /// Single-port keys keep the historical "80/tcp/public" format.
     pub fn make_key(port_spec: &str, protocol: &str, zone: &str) -> String {
         format!("{}/{}/{}", port_spec, protocol, zone)
     }
+
+    /// The names and descriptions of every named entry.
+    pub fn export_profile(&mut self) -> MetadataProfile {
+        self.ensure_loaded();
+        MetadataProfile {
+            ports: self
+                .data
+                .iter()
+                .map(|(key, metadata)| (key.clone(), SharedMetadata::of(metadata)))
+                .filter(|(_, shared)| !shared.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Compare `profile` with the local entries. Only the rules in `live`
+    /// (storage keys) are considered, since metadata of rules that do not
+    /// exist is dropped on the next refresh.
+    pub fn plan_import(&mut self, profile: &MetadataProfile, live: &HashSet<String>) -> ImportPlan {
+        self.ensure_loaded();
+        let mut plan = ImportPlan::default();
+        for (key, imported) in &profile.ports {
+            if !live.contains(key) {
+                plan.skipped += 1;
+                continue;
+            }
+            let local = self
+                .data
+                .get(key)
+                .map(SharedMetadata::of)
+                .unwrap_or_default();
+            if local == *imported {
+                plan.unchanged += 1;
+            } else if local.is_empty() {
+                plan.added.push((key.clone(), imported.clone()));
+            } else {
+                plan.conflicts.push(MetadataConflict {
+                    key: key.clone(),
+                    local,
+                    imported: imported.clone(),
+                });
+            }
+        }
+        plan
+    }
+
+    /// Apply `plan`: add its new entries and replace the conflicting ones
+    /// whose key is in `replace`, keeping the rest of each local entry.
+    /// Returns how many entries were written.
+    pub fn apply_import(&mut self, plan: &ImportPlan, replace: &HashSet<String>) -> usize {
+        self.ensure_loaded();
+        let chosen = plan
+            .conflicts
+            .iter()
+            .filter(|conflict| replace.contains(&conflict.key))
+            .map(|conflict| (&conflict.key, &conflict.imported));
+        let mut changed = 0;
+        for (key, shared) in plan.added.iter().map(|(k, s)| (k, s)).chain(chosen) {
+            let Some((start, end, protocol, zone)) = parse_key(key) else {
+                continue;
+            };
+            // Not created here, so without a creation time
+            let entry = self
+                .data
+                .entry(key.clone())
+                .or_insert_with(|| PortMetadata {
+                    zone: zone.to_string(),
+                    protocol: protocol.to_string(),
+                    port: start,
+                    end_port: if end > start { end } else { 0 },
+                    ..PortMetadata::default()
+                });
+            entry.name = shared.name.clone();
+            entry.description = shared.description.clone();
+            changed += 1;
+        }
+        if changed > 0 {
+            self.dirty = true;
+            self.save();
+        }
+        changed
+    }
+}
+
+/// The part of an entry a user writes, shared between machines.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SharedMetadata {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+impl SharedMetadata {
+    fn of(metadata: &PortMetadata) -> Self {
+        Self {
+            name: metadata.name.clone(),
+            description: metadata.description.clone(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.name.is_empty() && self.description.is_empty()
+    }
+}
+
+/// Contents of an exported profile file.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProfileFile {
+    format: String,
+    version: u32,
+    ports: BTreeMap<String, SharedMetadata>,
+}
+
+/// Names and descriptions of port rules, keyed like [`PortStorage`], without
+/// the rules themselves.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetadataProfile {
+    pub ports: BTreeMap<String, SharedMetadata>,
+}
+
+impl MetadataProfile {
+    /// Read a profile exported on this or another machine. Entries with an
+    /// invalid key or an unsafe name are left out.
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let size = fs::metadata(path)
+            .with_context(|| format!("Cannot read {}", path.display()))?
+            .len();
+        if size > MAX_STORAGE_FILE_SIZE {
+            bail!("The file is too large ({} bytes)", size);
+        }
+        let text = fs::read_to_string(path)?;
+        Self::parse(&text)
+    }
+
+    fn parse(text: &str) -> anyhow::Result<Self> {
+        let file: ProfileFile = serde_json::from_str(text).context("Not a port names profile")?;
+        if file.format != PROFILE_FORMAT {
+            bail!("Not a port names profile");
+        }
+        if file.version > PROFILE_VERSION {
+            bail!(
+                "The profile comes from a newer release (version {})",
+                file.version
+            );
+        }
+        let mut ports = BTreeMap::new();
+        for (key, shared) in file.ports {
+            let Some(name) = parse_key(&key).and(clean_name(&shared.name)) else {
+                warn!(
+                    "Skipping profile entry {:?}: invalid key or unsafe name",
+                    key
+                );
+                continue;
+            };
+            let description = shared
+                .description
+                .chars()
+                .take(MAX_DESCRIPTION_CHARS)
+                .collect();
+            ports.insert(key, SharedMetadata { name, description });
+        }
+        Ok(Self { ports })
+    }
+
+    /// Write the profile to `path`.
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        let file = ProfileFile {
+            format: PROFILE_FORMAT.to_string(),
+            version: PROFILE_VERSION,
+            ports: self.ports.clone(),
+        };
+        fs::write(path, serde_json::to_string_pretty(&file)?)
+            .with_context(|| format!("Cannot write {}", path.display()))
+    }
+}
+
+/// An imported entry that would replace a different local one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataConflict {
+    pub key: String,
+    pub local: SharedMetadata,
+    pub imported: SharedMetadata,
+}
+
+/// What importing a profile would change.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportPlan {
+    /// Entries for rules that have no name here yet.
+    pub added: Vec<(String, SharedMetadata)>,
+    pub conflicts: Vec<MetadataConflict>,
+    /// Entries matching the local ones already.
+    pub unchanged: usize,
+    /// Entries for rules this machine does not have.
+    pub skipped: usize,
+}
+
+/// The port, range end, protocol and zone of a storage key.
+fn parse_key(key: &str) -> Option<(u16, u16, &str, &str)> {
+    let mut parts = key.splitn(3, '/');
+    let (start, end) = parse_port_spec(parts.next()?)?;
+    let protocol = validate_protocol(parts.next()?)?;
+    let zone = validate_zone_name(parts.next()?)?;
+    Some((start, end, protocol, zone))
+}
+
+/// Sanitize a name through the same charset rules as user input. Metadata
+/// files are untrusted (SECURITY.md), and names are shown in row titles; an
+/// unsanitized name could inject Pango markup. Over-long names are truncated
+/// first so only the charset can reject, on a char boundary so multibyte
+/// names cannot panic.
+fn clean_name(name: &str) -> Option<String> {
+    let name: String = name.chars().take(64).collect();
+    validate_port_name(&name)
+}
+
+/// See [`PortStorage::lookup`].
+fn find_entry<'a>(
+    data: &'a HashMap<String, PortMetadata>,
+    port: u16,
+    protocol: &str,
+    zone: Option<&str>,
+) -> Option<&'a PortMetadata> {
+    data.iter()
+        .filter_map(|(key, metadata)| {
+            let mut parts = key.splitn(3, '/');
+            let (start, end) = parse_port_spec(parts.next()?)?;
+            let key_protocol = parts.next()?;
+            let key_zone = parts.next().unwrap_or_default();
+            if !(start..=end).contains(&port) || !key_protocol.eq_ignore_ascii_case(protocol) {
+                return None;
+            }
+            let other_zone = zone.is_some_and(|z| z != key_zone);
+            // Sort key: same zone first, then the narrowest range, then the key
+            Some(((other_zone, end - start, key.as_str()), metadata))
+        })
+        .min_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, metadata)| metadata)
 }
 
 #[cfg(test)]
@@ -253,6 +549,19 @@ mod tests {
     #[cfg(unix)]
     use std::os::unix::fs::PermissionsExt;
 
+    #[test]
+    fn test_created() {
+        let mut metadata = PortMetadata::new("SSH");
+        assert!(metadata.created().is_some());
+        metadata.created_at = "2026-01-31 08:15:00".to_string();
+        assert_eq!(
+            metadata.created().unwrap().to_string(),
+            "2026-01-31 08:15:00"
+        );
+        metadata.created_at = String::new();
+        assert!(metadata.created().is_none());
+    }
+
     #[test]
     fn test_sanitize_data_discards_invalid_protocol() {
         let mut data = HashMap::new();
@@ -268,6 +577,7 @@ mod tests {
                 protocol: "tcp\" reject".to_string(),
                 port: 80,
                 end_port: 0,
+                expires_at: 0,
             },
         );
         let sanitized = PortStorage::sanitize_data(data);
@@ -289,6 +599,7 @@ mod tests {
                 protocol: "tcp".to_string(),
                 port: 80,
                 end_port: 0,
+                expires_at: 0,
             },
         );
         let sanitized = PortStorage::sanitize_data(data);
@@ -296,6 +607,29 @@ mod tests {
         assert_eq!(sanitized["80/tcp/public"].protocol, "tcp");
     }
 
+    #[test]
+    fn test_expires_at_optional() {
+        let mut metadata = PortMetadata::new("HTTP");
+        let json = serde_json::to_string(&metadata).unwrap();
+        assert!(!json.contains("expires_at"));
+        assert_eq!(metadata.expires(), None);
+
+        metadata.expires_at = 1_700_000_000;
+        let json = serde_json::to_string(&metadata).unwrap();
+        let parsed: PortMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.expires_at, 1_700_000_000);
+        assert!(parsed.expires().is_some());
+
+        let mut data = HashMap::new();
+        metadata.expires_at = -5;
+        metadata.protocol = "tcp".to_string();
+        metadata.zone = "public".to_string();
+        metadata.port = 80;
+        data.insert("80/tcp/public".to_string(), metadata);
+        let sanitized = PortStorage::sanitize_data(data);
+        assert_eq!(sanitized["80/tcp/public"].expires_at, 0);
+    }
+
     #[test]
     fn test_sanitize_data_discards_port_zero_with_protocol() {
         let mut data = HashMap::new();
@@ -311,6 +645,7 @@ mod tests {
                 protocol: "tcp".to_string(),
                 port: 0,
                 end_port: 0,
+                expires_at: 0,
             },
         );
         let sanitized = PortStorage::sanitize_data(data);
@@ -333,12 +668,114 @@ mod tests {
                 protocol: "tcp".to_string(),
                 port: 80,
                 end_port: 0,
+                expires_at: 0,
             },
         );
         let sanitized = PortStorage::sanitize_data(data);
         assert_eq!(sanitized["80/tcp/public"].name.len(), 64);
     }
 
+    #[test]
+    fn test_find_entry() {
+        let mut data = HashMap::new();
+        data.insert(
+            "8000-8100/tcp/public".to_string(),
+            PortMetadata::new("Dev servers"),
+        );
+        data.insert("8080/tcp/home".to_string(), PortMetadata::new("Proxy"));
+        data.insert("8080/udp/public".to_string(), PortMetadata::new("QUIC"));
+
+        let name =
+            |port, protocol, zone| find_entry(&data, port, protocol, zone).map(|m| m.name.as_str());
+        assert_eq!(name(8080, "tcp", Some("home")), Some("Proxy"));
+        assert_eq!(name(8080, "tcp", Some("public")), Some("Dev servers"));
+        // Without a zone the single port wins over the range
+        assert_eq!(name(8080, "tcp", None), Some("Proxy"));
+        assert_eq!(name(8080, "UDP", None), Some("QUIC"));
+        assert_eq!(name(8050, "tcp", Some("work")), Some("Dev servers"));
+        assert_eq!(name(22, "tcp", None), None);
+    }
+
+    #[test]
+    fn test_profile_parse() {
+        let profile = MetadataProfile::parse(
+            r#"{"format":"security-center-port-names","version":1,"ports":{
+                "8080/tcp/public":{"name":"Web","description":"Dev server"},
+                "6000-6100/udp/home":{"name":"X11"},
+                "80/tcp/public\" reject":{"name":"Bad key"},
+                "443/tcp/public":{"name":"<b>Markup</b>"}}}"#,
+        )
+        .unwrap();
+        let keys: Vec<&String> = profile.ports.keys().collect();
+        assert_eq!(keys, vec!["6000-6100/udp/home", "8080/tcp/public"]);
+        assert_eq!(profile.ports["8080/tcp/public"].description, "Dev server");
+
+        assert!(MetadataProfile::parse(r#"{"ports":{}}"#).is_err());
+        assert!(MetadataProfile::parse(
+            r#"{"format":"security-center-port-names","version":9,"ports":{}}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_import_merge() {
+        let mut storage = PortStorage {
+            data: HashMap::new(),
+            path: PathBuf::from("/nonexistent/port_metadata.json"),
+            loaded: true,
+            dirty: false,
+            modified: None,
+        };
+        storage
+            .data
+            .insert("22/tcp/public".to_string(), PortMetadata::new("SSH"));
+        let mut web = PortMetadata::with_description("Web", "Dev server");
+        web.expires_at = 1_700_000_000;
+        storage.data.insert("8080/tcp/public".to_string(), web);
+
+        let shared = |name: &str, description: &str| SharedMetadata {
+            name: name.to_string(),
+            description: description.to_string(),
+        };
+        let profile = MetadataProfile {
+            ports: BTreeMap::from([
+                ("22/tcp/public".to_string(), shared("SSH", "")),
+                (
+                    "8080/tcp/public".to_string(),
+                    shared("Proxy", "Shared cache"),
+                ),
+                ("6000-6100/udp/public".to_string(), shared("X11", "")),
+                ("9090/tcp/public".to_string(), shared("Cockpit", "")),
+            ]),
+        };
+        let live: HashSet<String> = ["22/tcp/public", "8080/tcp/public", "6000-6100/udp/public"]
+            .iter()
+            .map(|key| key.to_string())
+            .collect();
+        let plan = storage.plan_import(&profile, &live);
+        assert_eq!(plan.unchanged, 1);
+        assert_eq!(plan.skipped, 1);
+        assert_eq!(plan.added.len(), 1);
+        assert_eq!(plan.conflicts.len(), 1);
+        assert_eq!(plan.conflicts[0].local, shared("Web", "Dev server"));
+
+        // Keeping the local name only adds the new entry
+        assert_eq!(storage.apply_import(&plan, &HashSet::new()), 1);
+        assert_eq!(storage.data["8080/tcp/public"].name, "Web");
+        let x11 = &storage.data["6000-6100/udp/public"];
+        assert_eq!(
+            (x11.port, x11.end_port, x11.protocol.as_str()),
+            (6000, 6100, "udp")
+        );
+
+        let replace = HashSet::from(["8080/tcp/public".to_string()]);
+        assert_eq!(storage.apply_import(&plan, &replace), 2);
+        let proxy = &storage.data["8080/tcp/public"];
+        assert_eq!(proxy.name, "Proxy");
+        // The rest of the local entry stays
+        assert_eq!(proxy.expires_at, 1_700_000_000);
+    }
+
     #[test]
     #[cfg(unix)]
     fn test_save_sets_permissions() {
@@ -349,6 +786,7 @@ mod tests {
             path: tmp.clone(),
             loaded: true,
             dirty: true,
+            modified: None,
         };
         storage.save();
 
@@ -359,4 +797,35 @@ mod tests {
         // Cleanup
         let _ = std::fs::remove_file(&tmp);
     }
+
+    #[test]
+    fn test_unversioned_file_migrates() {
+        let dir = std::env::temp_dir().join(format!(
+            "security-center-storage-schema-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("port_metadata.json");
+        fs::write(
+            &path,
+            r#"{"8080/tcp/public":{"name":"Web","description":"","created_at":"","port":8080,"protocol":"tcp"}}"#,
+        )
+        .unwrap();
+
+        let mut storage = PortStorage {
+            data: HashMap::new(),
+            path: path.clone(),
+            loaded: false,
+            dirty: false,
+            modified: None,
+        };
+        assert_eq!(storage.get("8080/tcp/public").unwrap().name, "Web");
+        assert!(dir.join("port_metadata.json.v0.bak").exists());
+        let stored: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(migration::file_version(&stored), 1);
+        assert_eq!(stored["ports"]["8080/tcp/public"]["port"], 8080);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
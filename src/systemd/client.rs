@@ -4,18 +4,39 @@
 
 //! Systemd D-Bus client implementation for service management.
 
+use std::collections::HashMap;
+
 use anyhow::{anyhow, Context, Result};
 use tracing::info;
 use zbus::blocking::{Connection, Proxy};
 use zbus::proxy::MethodFlags;
-use zbus::zvariant::OwnedObjectPath;
+use zbus::zvariant::{OwnedObjectPath, OwnedValue};
 
+use super::sandbox::{SandboxReport, SandboxSettings};
+use super::units::{merge_units, UnitEntry, UnitScope};
 use crate::validation::{validate_service_name, validate_systemctl_action};
 
-const SYSTEMD_BUS: &str = "org.freedesktop.systemd1";
-const SYSTEMD_PATH: &str = "/org/freedesktop/systemd1";
-const MANAGER_INTERFACE: &str = "org.freedesktop.systemd1.Manager";
-const UNIT_INTERFACE: &str = "org.freedesktop.systemd1.Unit";
+pub(super) const SYSTEMD_BUS: &str = "org.freedesktop.systemd1";
+pub(super) const SYSTEMD_PATH: &str = "/org/freedesktop/systemd1";
+pub(super) const MANAGER_INTERFACE: &str = "org.freedesktop.systemd1.Manager";
+pub(super) const UNIT_INTERFACE: &str = "org.freedesktop.systemd1.Unit";
+const SERVICE_INTERFACE: &str = "org.freedesktop.systemd1.Service";
+
+/// A row of `ListUnitsByPatterns`: name, description, load state, active
+/// state, sub state, followed unit, object path, job id, job type and job
+/// path.
+type ListedUnit = (
+    String,
+    String,
+    String,
+    String,
+    String,
+    String,
+    OwnedObjectPath,
+    u32,
+    String,
+    OwnedObjectPath,
+);
 
 /// Service state enumeration.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -27,6 +48,8 @@ pub enum ServiceState {
 }
 
 impl ServiceState {
+    /// State for a systemd `SubState`; unlike `FromStr` this cannot fail.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         match s {
             "running" => ServiceState::Running,
@@ -54,9 +77,16 @@ pub struct ServiceInfo {
     pub description: String,
     pub state: ServiceState,
     pub is_enabled: bool,
+    /// False when systemd has no unit file by this name (`LoadState` is
+    /// `not-found`).
+    pub is_installed: bool,
+    /// Masked: linked to /dev/null, so it cannot be started at all.
+    pub is_masked: bool,
     pub unit_path: String,
     pub main_pid: u32,
     pub memory_current: Option<u64>,
+    /// Sandboxing settings and exposure, for installed services.
+    pub sandbox: Option<SandboxReport>,
 }
 
 impl ServiceInfo {
@@ -68,25 +98,18 @@ impl ServiceInfo {
             description: String::new(),
             state: ServiceState::Unknown,
             is_enabled: false,
+            is_installed: true,
+            is_masked: false,
             unit_path: String::new(),
             main_pid: 0,
             memory_current: None,
+            sandbox: None,
         }
     }
 
     /// Format memory as human-readable string.
     pub fn memory_display(&self) -> Option<String> {
-        self.memory_current.map(|bytes| {
-            if bytes >= 1024 * 1024 * 1024 {
-                format!("{:.1} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
-            } else if bytes >= 1024 * 1024 {
-                format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
-            } else if bytes >= 1024 {
-                format!("{:.1} KB", bytes as f64 / 1024.0)
-            } else {
-                format!("{} B", bytes)
-            }
-        })
+        self.memory_current.map(crate::i18n::format_bytes)
     }
 }
 
@@ -103,9 +126,22 @@ impl SystemdClient {
 
     /// Connect to systemd.
     pub fn connect(&mut self) -> Result<()> {
+        self.connect_scope(UnitScope::System)
+    }
+
+    /// Connect to the system manager, or to the user's own manager on the
+    /// session bus.
+    pub fn connect_scope(&mut self, scope: UnitScope) -> Result<()> {
         info!("Connecting to systemd...");
 
-        let conn = Connection::system().context("Failed to connect to system D-Bus")?;
+        let conn = match scope {
+            UnitScope::System => {
+                Connection::system().context("Failed to connect to system D-Bus")?
+            }
+            UnitScope::User => {
+                Connection::session().context("Failed to connect to session D-Bus")?
+            }
+        };
 
         // Test connection by getting systemd version
         let value: zbus::zvariant::OwnedValue = conn
@@ -127,6 +163,45 @@ impl SystemdClient {
         Ok(())
     }
 
+    /// Every service of the connected manager, loaded or only installed,
+    /// sorted by name.
+    pub fn list_all_services(&self) -> Result<Vec<UnitEntry>> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or_else(|| anyhow!("Not connected to systemd"))?;
+        let patterns: &[&str] = &["*.service"];
+
+        let units: Vec<ListedUnit> = conn
+            .call_method(
+                Some(SYSTEMD_BUS),
+                SYSTEMD_PATH,
+                Some(MANAGER_INTERFACE),
+                "ListUnitsByPatterns",
+                &(&[] as &[&str], patterns),
+            )?
+            .body()
+            .deserialize()?;
+        let files: Vec<(String, String)> = conn
+            .call_method(
+                Some(SYSTEMD_BUS),
+                SYSTEMD_PATH,
+                Some(MANAGER_INTERFACE),
+                "ListUnitFilesByPatterns",
+                &(&[] as &[&str], patterns),
+            )?
+            .body()
+            .deserialize()?;
+
+        // Units other units refer to but that do not exist are skipped
+        let loaded = units
+            .into_iter()
+            .filter(|unit| unit.2 != "not-found")
+            .map(|(name, description, _, _, sub_state, ..)| (name, description, sub_state))
+            .collect();
+        Ok(merge_units(loaded, files))
+    }
+
     /// List important security-related services.
     pub fn list_security_services(&self) -> Result<Vec<ServiceInfo>> {
         // Pre-defined list of security-related services to show
@@ -223,6 +298,10 @@ impl SystemdClient {
         let mut info = ServiceInfo::new(name);
         info.unit_path = unit_path.to_string();
 
+        if let Ok(load_state) = self.get_unit_property(&unit_path, "LoadState") {
+            info.is_installed = load_state != "not-found";
+        }
+
         // Get description
         if let Ok(desc) = self.get_unit_property(&unit_path, "Description") {
             info.description = desc;
@@ -236,6 +315,14 @@ impl SystemdClient {
         // Get enabled state
         if let Ok(unit_file_state) = self.get_unit_property(&unit_path, "UnitFileState") {
             info.is_enabled = unit_file_state == "enabled" || unit_file_state == "static";
+            info.is_masked = unit_file_state.starts_with("masked");
+        }
+
+        if info.is_installed {
+            info.sandbox = self
+                .get_sandbox_settings(&unit_path)
+                .ok()
+                .map(|settings| SandboxReport::evaluate(&settings));
         }
 
         // Get MainPID (only for running services)
@@ -256,6 +343,63 @@ impl SystemdClient {
         Ok(info)
     }
 
+    /// Whether a unit is active, including oneshot services that exited
+    /// after doing their work, such as loading firewall rules. Units
+    /// systemd has not loaded are inactive.
+    pub fn is_unit_active(&self, name: &str) -> Result<bool> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or_else(|| anyhow!("Not connected to systemd"))?;
+
+        let Ok(reply) = conn.call_method(
+            Some(SYSTEMD_BUS),
+            SYSTEMD_PATH,
+            Some(MANAGER_INTERFACE),
+            "GetUnit",
+            &(name,),
+        ) else {
+            return Ok(false);
+        };
+        let unit_path: OwnedObjectPath = reply.body().deserialize()?;
+        Ok(self.get_unit_property(&unit_path, "ActiveState")? == "active")
+    }
+
+    /// Paths of a unit's main file and its drop-ins, in the order systemd
+    /// applies them.
+    pub fn get_unit_file_paths(&self, name: &str) -> Result<Vec<String>> {
+        validate_service_name(name)?;
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or_else(|| anyhow!("Not connected to systemd"))?;
+
+        let unit_path: OwnedObjectPath = conn
+            .call_method(
+                Some(SYSTEMD_BUS),
+                SYSTEMD_PATH,
+                Some(MANAGER_INTERFACE),
+                "LoadUnit",
+                &(name,),
+            )?
+            .body()
+            .deserialize()?;
+
+        let mut paths = vec![self.get_unit_property(&unit_path, "FragmentPath")?];
+        let drop_ins: OwnedValue = conn
+            .call_method(
+                Some(SYSTEMD_BUS),
+                unit_path.as_ref(),
+                Some("org.freedesktop.DBus.Properties"),
+                "Get",
+                &(UNIT_INTERFACE, "DropInPaths"),
+            )?
+            .body()
+            .deserialize()?;
+        paths.extend(Vec::<String>::try_from(drop_ins).unwrap_or_default());
+        Ok(paths)
+    }
+
     /// Get a property from a unit.
     fn get_unit_property(&self, unit_path: &OwnedObjectPath, property: &str) -> Result<String> {
         let conn = self
@@ -329,6 +473,102 @@ impl SystemdClient {
         Ok(v)
     }
 
+    /// Read the sandboxing settings of a service in one call.
+    fn get_sandbox_settings(&self, unit_path: &OwnedObjectPath) -> Result<SandboxSettings> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or_else(|| anyhow!("Not connected to systemd"))?;
+
+        let mut props: HashMap<String, OwnedValue> = conn
+            .call_method(
+                Some(SYSTEMD_BUS),
+                unit_path.as_ref(),
+                Some("org.freedesktop.DBus.Properties"),
+                "GetAll",
+                &(SERVICE_INTERFACE,),
+            )?
+            .body()
+            .deserialize()?;
+
+        // Properties older systemd versions lack count as not set
+        let flag = |props: &HashMap<String, OwnedValue>, name: &str| {
+            props
+                .get(name)
+                .and_then(|v| bool::try_from(v).ok())
+                .unwrap_or(false)
+        };
+        let text = |props: &HashMap<String, OwnedValue>, name: &str| {
+            props
+                .get(name)
+                .and_then(|v| <&str>::try_from(v).ok())
+                .unwrap_or_default()
+                .to_string()
+        };
+        let mut list = |name: &str| {
+            props
+                .remove(name)
+                .and_then(|v| <(bool, Vec<String>)>::try_from(v).ok())
+                .unwrap_or_default()
+        };
+        let address_families = list("RestrictAddressFamilies");
+        let system_call_filter = list("SystemCallFilter");
+
+        Ok(SandboxSettings {
+            user: text(&props, "User"),
+            dynamic_user: flag(&props, "DynamicUser"),
+            no_new_privileges: flag(&props, "NoNewPrivileges"),
+            private_tmp: flag(&props, "PrivateTmp"),
+            private_devices: flag(&props, "PrivateDevices"),
+            private_network: flag(&props, "PrivateNetwork"),
+            private_users: flag(&props, "PrivateUsers"),
+            protect_home: text(&props, "ProtectHome"),
+            protect_system: text(&props, "ProtectSystem"),
+            protect_kernel_tunables: flag(&props, "ProtectKernelTunables"),
+            protect_kernel_modules: flag(&props, "ProtectKernelModules"),
+            protect_kernel_logs: flag(&props, "ProtectKernelLogs"),
+            protect_control_groups: flag(&props, "ProtectControlGroups"),
+            protect_clock: flag(&props, "ProtectClock"),
+            protect_hostname: flag(&props, "ProtectHostname"),
+            protect_proc: text(&props, "ProtectProc"),
+            restrict_suid_sgid: flag(&props, "RestrictSUIDSGID"),
+            restrict_realtime: flag(&props, "RestrictRealtime"),
+            lock_personality: flag(&props, "LockPersonality"),
+            memory_deny_write_execute: flag(&props, "MemoryDenyWriteExecute"),
+            capability_bounding_set: props
+                .get("CapabilityBoundingSet")
+                .and_then(|v| u64::try_from(v).ok())
+                .unwrap_or(u64::MAX),
+            address_families,
+            system_call_filter,
+        })
+    }
+
+    /// Default soft core file size limit for services (`DefaultLimitCORESoft`);
+    /// `u64::MAX` means unlimited.
+    pub fn default_core_limit(&self) -> Result<u64> {
+        let conn = self
+            .connection
+            .as_ref()
+            .ok_or_else(|| anyhow!("Not connected to systemd"))?;
+
+        let value: zbus::zvariant::OwnedValue = conn
+            .call_method(
+                Some(SYSTEMD_BUS),
+                SYSTEMD_PATH,
+                Some("org.freedesktop.DBus.Properties"),
+                "Get",
+                &(MANAGER_INTERFACE, "DefaultLimitCORESoft"),
+            )?
+            .body()
+            .deserialize()?;
+
+        let v: u64 = value
+            .try_into()
+            .map_err(|_| anyhow!("Property is not a u64"))?;
+        Ok(v)
+    }
+
     /// Start a service (polkit prompts for authorization via D-Bus).
     pub fn start_service(&self, name: &str) -> Result<()> {
         self.run_unit_action("start", name)?;
@@ -364,6 +604,35 @@ impl SystemdClient {
         Ok(())
     }
 
+    /// Ask a service to reload its configuration without restarting.
+    pub fn reload_service(&self, name: &str) -> Result<()> {
+        self.run_unit_action("reload", name)?;
+        info!("Reloaded service: {}", name);
+        Ok(())
+    }
+
+    /// Reload a service if it supports reloading, otherwise restart it.
+    pub fn reload_or_restart_service(&self, name: &str) -> Result<()> {
+        self.run_unit_action("reload-or-restart", name)?;
+        info!("Reloaded or restarted service: {}", name);
+        Ok(())
+    }
+
+    /// Mask a service so it cannot be started, by hand or as a dependency.
+    /// A running service keeps running until stopped.
+    pub fn mask_service(&self, name: &str) -> Result<()> {
+        self.run_unit_action("mask", name)?;
+        info!("Masked service: {}", name);
+        Ok(())
+    }
+
+    /// Unmask a service so it can be started again.
+    pub fn unmask_service(&self, name: &str) -> Result<()> {
+        self.run_unit_action("unmask", name)?;
+        info!("Unmasked service: {}", name);
+        Ok(())
+    }
+
     /// Reload the systemd daemon configuration (equivalent of `systemctl daemon-reload`).
     pub fn daemon_reload(&self) -> Result<()> {
         self.run_unit_action("daemon-reload", "")?;
@@ -379,7 +648,8 @@ impl SystemdClient {
     /// the user for credentials when needed.
     ///
     /// Supported actions: `start`, `stop`, `restart`, `enable`, `disable`,
-    /// and `daemon-reload` (which ignores `unit`).
+    /// `reload`, `reload-or-restart`, `mask`, `unmask` and `daemon-reload`
+    /// (which ignores `unit`).
     pub fn run_unit_action(&self, action: &str, unit: &str) -> Result<()> {
         // Validate parameters before invoking privileged operations
         validate_systemctl_action(action)?;
@@ -419,6 +689,28 @@ impl SystemdClient {
                 // Make systemd pick up the changed unit files
                 let _: () = self.call_manager_interactive("Reload", &())?;
             }
+            "reload" => {
+                let _job: OwnedObjectPath =
+                    self.call_manager_interactive("ReloadUnit", &(unit, "replace"))?;
+            }
+            "reload-or-restart" => {
+                let _job: OwnedObjectPath =
+                    self.call_manager_interactive("ReloadOrRestartUnit", &(unit, "replace"))?;
+            }
+            "mask" => {
+                // MaskUnitFiles(files, runtime, force) -> changes
+                let _changes: Vec<(String, String, String)> = self.call_manager_interactive(
+                    "MaskUnitFiles",
+                    &(&[unit] as &[&str], false, true),
+                )?;
+                let _: () = self.call_manager_interactive("Reload", &())?;
+            }
+            "unmask" => {
+                // UnmaskUnitFiles(files, runtime) -> changes
+                let _changes: Vec<(String, String, String)> =
+                    self.call_manager_interactive("UnmaskUnitFiles", &(&[unit] as &[&str], false))?;
+                let _: () = self.call_manager_interactive("Reload", &())?;
+            }
             "daemon-reload" => {
                 let _: () = self.call_manager_interactive("Reload", &())?;
             }
@@ -0,0 +1,181 @@
+// Security Center - Service Journal
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Journal lines of one unit, read back or followed live through
+//! `journalctl -u <unit> -o json`.
+//!
+//! Reading another service's journal needs the user to be in the
+//! `systemd-journal` or `adm` group; otherwise `journalctl` only returns the
+//! user's own entries, which for a system service is usually nothing.
+
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::validation::validate_service_name;
+
+/// Most lines a followed log keeps before the oldest are dropped.
+const MAX_FOLLOWED: usize = 5000;
+
+/// One journal entry of a unit.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JournalLine {
+    /// Unix time in seconds.
+    pub time: i64,
+    /// syslog priority: 0 (emergency) to 7 (debug).
+    pub priority: u8,
+    /// Program that logged the line, e.g. `sshd`.
+    pub identifier: String,
+    pub pid: Option<u32>,
+    pub message: String,
+}
+
+impl JournalLine {
+    /// Error, critical, alert or emergency.
+    pub fn is_error(&self) -> bool {
+        self.priority <= 3
+    }
+
+    pub fn is_warning(&self) -> bool {
+        self.priority == 4
+    }
+}
+
+/// Parse one `journalctl -o json` line.
+fn parse_journal_entry(line: &str) -> Option<JournalLine> {
+    let entry: serde_json::Value = serde_json::from_str(line).ok()?;
+    let text = |key: &str| entry.get(key).and_then(|v| v.as_str());
+    // Messages that are not valid UTF-8 come as an array of bytes
+    let message = match entry.get("MESSAGE")? {
+        serde_json::Value::String(message) => message.clone(),
+        serde_json::Value::Array(bytes) => {
+            let bytes: Vec<u8> = bytes
+                .iter()
+                .filter_map(|b| b.as_u64().and_then(|b| u8::try_from(b).ok()))
+                .collect();
+            String::from_utf8_lossy(&bytes).into_owned()
+        }
+        _ => return None,
+    };
+    let micros: i64 = text("__REALTIME_TIMESTAMP")?.parse().ok()?;
+    Some(JournalLine {
+        time: micros / 1_000_000,
+        priority: text("PRIORITY").and_then(|p| p.parse().ok()).unwrap_or(6),
+        identifier: text("SYSLOG_IDENTIFIER")
+            .or_else(|| text("_COMM"))
+            .unwrap_or_default()
+            .to_string(),
+        pid: text("_PID").and_then(|p| p.parse().ok()),
+        message,
+    })
+}
+
+fn journalctl(unit: &str, lines: usize, follow: bool) -> Result<Command> {
+    validate_service_name(unit)?;
+    if unit.is_empty() {
+        return Err(anyhow!("A unit name is required"));
+    }
+    let mut command = Command::new("journalctl");
+    command.args(["-u", unit, "-o", "json", "--no-pager", "-n"]);
+    command.arg(lines.to_string());
+    if follow {
+        command.arg("-f");
+    }
+    Ok(command)
+}
+
+/// The last `lines` journal lines of a unit, oldest first. Blocks on
+/// `journalctl`, so call it off the main thread.
+pub fn read_unit_log(unit: &str, lines: usize) -> Result<Vec<JournalLine>> {
+    let output = journalctl(unit, lines, false)?
+        .stderr(Stdio::null())
+        .output()
+        .context("Failed to run journalctl")?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_journal_entry)
+        .collect())
+}
+
+/// Follows new journal lines of a unit until dropped.
+pub struct UnitLog {
+    child: Child,
+    fresh: Arc<Mutex<Vec<JournalLine>>>,
+}
+
+impl UnitLog {
+    /// Start following a unit's journal from now on.
+    pub fn follow(unit: &str) -> Result<Self> {
+        let mut child = journalctl(unit, 0, true)?
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .context("Failed to run journalctl")?;
+        let stdout = child.stdout.take().context("journalctl has no output")?;
+
+        let fresh = Arc::new(Mutex::new(Vec::new()));
+        let thread_fresh = fresh.clone();
+        std::thread::Builder::new()
+            .name("unit-log".to_string())
+            .spawn(move || {
+                for line in BufReader::new(stdout).lines() {
+                    let Ok(line) = line else { break };
+                    let Some(entry) = parse_journal_entry(&line) else {
+                        continue;
+                    };
+                    if let Ok(mut fresh) = thread_fresh.lock() {
+                        if fresh.len() < MAX_FOLLOWED {
+                            fresh.push(entry);
+                        }
+                    }
+                }
+            })?;
+
+        Ok(Self { child, fresh })
+    }
+
+    /// Lines logged since the previous call, oldest first.
+    pub fn take_new(&self) -> Vec<JournalLine> {
+        self.fresh
+            .lock()
+            .map(|mut fresh| std::mem::take(&mut *fresh))
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for UnitLog {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_journal_entry() {
+        let line = r#"{"__REALTIME_TIMESTAMP":"1760690521123456","PRIORITY":"3","SYSLOG_IDENTIFIER":"sshd","_PID":"812","MESSAGE":"error: Bind to port 22 on 0.0.0.0 failed: Address already in use."}"#;
+        let entry = parse_journal_entry(line).unwrap();
+        assert_eq!(entry.time, 1760690521);
+        assert_eq!(entry.identifier, "sshd");
+        assert_eq!(entry.pid, Some(812));
+        assert!(entry.is_error());
+        assert!(entry.message.starts_with("error: Bind"));
+
+        let binary =
+            r#"{"__REALTIME_TIMESTAMP":"1000000","_COMM":"nginx","MESSAGE":[104,105,255]}"#;
+        let entry = parse_journal_entry(binary).unwrap();
+        assert_eq!(entry.message, "hi\u{fffd}");
+        assert_eq!(entry.identifier, "nginx");
+        assert_eq!(entry.priority, 6);
+        assert_eq!(parse_journal_entry("{\"MESSAGE\":\"no time\"}"), None);
+
+        assert!(journalctl("sshd.service; reboot", 10, false).is_err());
+        assert!(journalctl("", 10, false).is_err());
+    }
+}
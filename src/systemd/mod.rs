@@ -2,10 +2,25 @@
 // Copyright (C) 2026 Christos Daggas
 // SPDX-License-Identifier: MIT
 
-//! Systemd D-Bus client for service management.
+//! Systemd D-Bus client for service management, listings of every system
+//! or user service, sandboxing analysis, unit files and journal lines of
+//! services, and a watch on the state of the services the app relies on.
 
 mod client;
+mod journal;
+mod sandbox;
+mod unit_file;
+mod units;
+mod watch;
 
 pub use client::ServiceInfo;
 pub use client::ServiceState;
 pub use client::SystemdClient;
+pub use journal::{read_unit_log, JournalLine, UnitLog};
+pub use sandbox::{ExposureLevel, SandboxCheck, SandboxReport, SandboxSettings};
+pub use unit_file::{
+    hardening_dropin, hardening_dropin_path, parse_hardening_dropin, read_unit_files,
+    validate_hardening, HardeningDirective, UnitFile,
+};
+pub use units::{EnablementFilter, StateFilter, UnitEntry, UnitFilter, UnitScope};
+pub use watch::{watch_units, UnitActivity, UnitStatus, WatchedUnit};
@@ -0,0 +1,422 @@
+// Security Center - Service Sandboxing
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Sandboxing analysis of services, in the spirit of
+//! `systemd-analyze security`.
+//!
+//! The sandboxing settings of a service (`ProtectHome=`, `PrivateTmp=`,
+//! `CapabilityBoundingSet=`, ...) are read from its D-Bus properties and
+//! each one missing adds to an exposure score from 0.0 (fully sandboxed) to
+//! 10.0 (nothing restricted). The weights follow `systemd-analyze`: running
+//! as root or sharing the host network count far more than a shared
+//! hostname. The score is an estimate of how much a compromised service
+//! could reach, not a verdict; some services need the access they have.
+
+/// Sandboxing settings of a service, as systemd reports them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SandboxSettings {
+    /// `User=`, empty for root.
+    pub user: String,
+    pub dynamic_user: bool,
+    pub no_new_privileges: bool,
+    pub private_tmp: bool,
+    pub private_devices: bool,
+    pub private_network: bool,
+    pub private_users: bool,
+    /// `no`, `yes`, `read-only` or `tmpfs`.
+    pub protect_home: String,
+    /// `no`, `yes`, `full` or `strict`.
+    pub protect_system: String,
+    pub protect_kernel_tunables: bool,
+    pub protect_kernel_modules: bool,
+    pub protect_kernel_logs: bool,
+    pub protect_control_groups: bool,
+    pub protect_clock: bool,
+    pub protect_hostname: bool,
+    /// `default`, `invisible`, `ptraceable` or `noaccess`.
+    pub protect_proc: String,
+    pub restrict_suid_sgid: bool,
+    pub restrict_realtime: bool,
+    pub lock_personality: bool,
+    pub memory_deny_write_execute: bool,
+    /// `CapabilityBoundingSet=` as a capability mask.
+    pub capability_bounding_set: u64,
+    /// `RestrictAddressFamilies=`: whether the list is an allow list, and the families.
+    pub address_families: (bool, Vec<String>),
+    /// `SystemCallFilter=`: whether the list is an allow list, and the calls.
+    pub system_call_filter: (bool, Vec<String>),
+}
+
+/// One sandboxing setting and how much its current value exposes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SandboxCheck {
+    /// Unit file setting, e.g. `PrivateTmp=`.
+    pub setting: &'static str,
+    /// What the setting protects.
+    pub description: &'static str,
+    /// Current value for display.
+    pub value: String,
+    pub weight: u32,
+    /// 0.0 when the setting is fully applied, 1.0 when not at all.
+    pub badness: f64,
+}
+
+impl SandboxCheck {
+    pub fn passed(&self) -> bool {
+        self.badness == 0.0
+    }
+}
+
+/// Exposure levels, named as `systemd-analyze security` names them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ExposureLevel {
+    Perfect,
+    Safe,
+    Ok,
+    Medium,
+    Exposed,
+    Unsafe,
+}
+
+impl ExposureLevel {
+    fn from_exposure(exposure: f64) -> Self {
+        match exposure {
+            e if e >= 9.0 => ExposureLevel::Unsafe,
+            e if e >= 7.5 => ExposureLevel::Exposed,
+            e if e >= 5.0 => ExposureLevel::Medium,
+            e if e >= 1.0 => ExposureLevel::Ok,
+            e if e > 0.0 => ExposureLevel::Safe,
+            _ => ExposureLevel::Perfect,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExposureLevel::Perfect => "Perfect",
+            ExposureLevel::Safe => "Safe",
+            ExposureLevel::Ok => "OK",
+            ExposureLevel::Medium => "Medium",
+            ExposureLevel::Exposed => "Exposed",
+            ExposureLevel::Unsafe => "Unsafe",
+        }
+    }
+
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            ExposureLevel::Perfect | ExposureLevel::Safe | ExposureLevel::Ok => "success",
+            ExposureLevel::Medium | ExposureLevel::Exposed => "warning",
+            ExposureLevel::Unsafe => "error",
+        }
+    }
+}
+
+/// Sandboxing checks of one service with its exposure score.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SandboxReport {
+    pub checks: Vec<SandboxCheck>,
+}
+
+const CAP_SYS_ADMIN: u64 = 1 << 21;
+
+fn flag(value: bool) -> String {
+    if value { "yes" } else { "no" }.to_string()
+}
+
+fn list_value((allow, entries): &(bool, Vec<String>)) -> String {
+    match (allow, entries.is_empty()) {
+        (false, true) => "not set".to_string(),
+        (true, true) => "none".to_string(),
+        (true, false) => entries.join(" "),
+        (false, false) => format!("~{}", entries.join(" ")),
+    }
+}
+
+impl SandboxReport {
+    /// Rate the settings of a service.
+    pub fn evaluate(settings: &SandboxSettings) -> Self {
+        let mut checks = Vec::new();
+        let mut check = |setting, description, value: String, weight, badness: f64| {
+            checks.push(SandboxCheck {
+                setting,
+                description,
+                value,
+                weight,
+                badness,
+            });
+        };
+        let bool_check = |value: bool| if value { 0.0 } else { 1.0 };
+
+        let runs_as_root =
+            !settings.dynamic_user && matches!(settings.user.as_str(), "" | "root" | "0");
+        check(
+            "User=/DynamicUser=",
+            "Runs as its own user instead of root",
+            if settings.dynamic_user {
+                "dynamic".to_string()
+            } else if runs_as_root {
+                "root".to_string()
+            } else {
+                settings.user.clone()
+            },
+            2000,
+            bool_check(!runs_as_root),
+        );
+        check(
+            "PrivateNetwork=",
+            "Has no access to the host network",
+            flag(settings.private_network),
+            2500,
+            bool_check(settings.private_network),
+        );
+        check(
+            "PrivateUsers=",
+            "Cannot see or act as the other users of the system",
+            flag(settings.private_users),
+            1500,
+            bool_check(settings.private_users),
+        );
+        check(
+            "CapabilityBoundingSet=",
+            "Cannot gain CAP_SYS_ADMIN, the capability closest to full root",
+            if settings.capability_bounding_set & CAP_SYS_ADMIN != 0 {
+                "includes CAP_SYS_ADMIN".to_string()
+            } else {
+                "excludes CAP_SYS_ADMIN".to_string()
+            },
+            1500,
+            bool_check(settings.capability_bounding_set & CAP_SYS_ADMIN == 0),
+        );
+        check(
+            "RestrictAddressFamilies=",
+            "Can only open the kinds of sockets it needs",
+            list_value(&settings.address_families),
+            1500,
+            bool_check(settings.address_families.0),
+        );
+        check(
+            "NoNewPrivileges=",
+            "Cannot gain privileges through set-UID programs",
+            flag(settings.no_new_privileges),
+            1000,
+            bool_check(settings.no_new_privileges),
+        );
+        check(
+            "PrivateDevices=",
+            "Has no access to physical devices",
+            flag(settings.private_devices),
+            1000,
+            bool_check(settings.private_devices),
+        );
+        check(
+            "PrivateTmp=",
+            "Has its own /tmp, invisible to other programs",
+            flag(settings.private_tmp),
+            1000,
+            bool_check(settings.private_tmp),
+        );
+        check(
+            "ProtectHome=",
+            "Cannot read or change users' home directories",
+            settings.protect_home.clone(),
+            1000,
+            match settings.protect_home.as_str() {
+                "yes" | "tmpfs" => 0.0,
+                "read-only" => 0.5,
+                _ => 1.0,
+            },
+        );
+        check(
+            "ProtectSystem=",
+            "Cannot change the operating system files",
+            settings.protect_system.clone(),
+            1000,
+            match settings.protect_system.as_str() {
+                "strict" => 0.0,
+                "full" => 0.25,
+                "yes" => 0.5,
+                _ => 1.0,
+            },
+        );
+        check(
+            "ProtectKernelTunables=",
+            "Cannot change kernel settings in /proc/sys and /sys",
+            flag(settings.protect_kernel_tunables),
+            1000,
+            bool_check(settings.protect_kernel_tunables),
+        );
+        check(
+            "ProtectKernelModules=",
+            "Cannot load kernel modules",
+            flag(settings.protect_kernel_modules),
+            1000,
+            bool_check(settings.protect_kernel_modules),
+        );
+        check(
+            "ProtectKernelLogs=",
+            "Cannot read the kernel log",
+            flag(settings.protect_kernel_logs),
+            1000,
+            bool_check(settings.protect_kernel_logs),
+        );
+        check(
+            "ProtectControlGroups=",
+            "Cannot change control groups",
+            flag(settings.protect_control_groups),
+            1000,
+            bool_check(settings.protect_control_groups),
+        );
+        check(
+            "ProtectClock=",
+            "Cannot change the system clock",
+            flag(settings.protect_clock),
+            1000,
+            bool_check(settings.protect_clock),
+        );
+        check(
+            "ProtectProc=",
+            "Cannot see the processes of other users",
+            settings.protect_proc.clone(),
+            1000,
+            bool_check(matches!(
+                settings.protect_proc.as_str(),
+                "invisible" | "ptraceable" | "noaccess"
+            )),
+        );
+        check(
+            "RestrictSUIDSGID=",
+            "Cannot create set-UID or set-GID files",
+            flag(settings.restrict_suid_sgid),
+            1000,
+            bool_check(settings.restrict_suid_sgid),
+        );
+        check(
+            "SystemCallFilter=",
+            "Can only make the system calls it needs",
+            list_value(&settings.system_call_filter),
+            1000,
+            match &settings.system_call_filter {
+                (true, _) => 0.0,
+                (false, calls) if !calls.is_empty() => 0.5,
+                _ => 1.0,
+            },
+        );
+        check(
+            "RestrictRealtime=",
+            "Cannot take over a CPU with realtime scheduling",
+            flag(settings.restrict_realtime),
+            500,
+            bool_check(settings.restrict_realtime),
+        );
+        check(
+            "LockPersonality=",
+            "Cannot switch to another execution domain",
+            flag(settings.lock_personality),
+            100,
+            bool_check(settings.lock_personality),
+        );
+        check(
+            "MemoryDenyWriteExecute=",
+            "Cannot create memory that is both writable and executable",
+            flag(settings.memory_deny_write_execute),
+            100,
+            bool_check(settings.memory_deny_write_execute),
+        );
+        check(
+            "ProtectHostname=",
+            "Cannot change the hostname",
+            flag(settings.protect_hostname),
+            50,
+            bool_check(settings.protect_hostname),
+        );
+
+        Self { checks }
+    }
+
+    /// Exposure from 0.0 (fully sandboxed) to 10.0, rounded to one decimal.
+    pub fn exposure(&self) -> f64 {
+        let total: u32 = self.checks.iter().map(|c| c.weight).sum();
+        if total == 0 {
+            return 0.0;
+        }
+        let bad: f64 = self
+            .checks
+            .iter()
+            .map(|c| c.weight as f64 * c.badness)
+            .sum();
+        (bad / total as f64 * 100.0).round() / 10.0
+    }
+
+    pub fn level(&self) -> ExposureLevel {
+        ExposureLevel::from_exposure(self.exposure())
+    }
+
+    /// Checks whose setting is not fully applied, most important first.
+    pub fn failed(&self) -> Vec<&SandboxCheck> {
+        let mut failed: Vec<&SandboxCheck> = self.checks.iter().filter(|c| !c.passed()).collect();
+        failed.sort_by(|a, b| {
+            (b.weight as f64 * b.badness).total_cmp(&(a.weight as f64 * a.badness))
+        });
+        failed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsandboxed_service() {
+        let report = SandboxReport::evaluate(&SandboxSettings {
+            protect_home: "no".to_string(),
+            protect_system: "no".to_string(),
+            protect_proc: "default".to_string(),
+            capability_bounding_set: u64::MAX,
+            ..Default::default()
+        });
+        assert_eq!(report.exposure(), 10.0);
+        assert_eq!(report.level(), ExposureLevel::Unsafe);
+        assert_eq!(report.failed()[0].setting, "PrivateNetwork=");
+    }
+
+    #[test]
+    fn test_sandboxed_service() {
+        let settings = SandboxSettings {
+            user: "chrony".to_string(),
+            no_new_privileges: true,
+            private_tmp: true,
+            private_devices: true,
+            private_users: true,
+            protect_home: "yes".to_string(),
+            protect_system: "strict".to_string(),
+            protect_kernel_tunables: true,
+            protect_kernel_modules: true,
+            protect_kernel_logs: true,
+            protect_control_groups: true,
+            protect_clock: false,
+            protect_hostname: true,
+            protect_proc: "invisible".to_string(),
+            restrict_suid_sgid: true,
+            restrict_realtime: true,
+            lock_personality: true,
+            memory_deny_write_execute: true,
+            capability_bounding_set: 1 << 25,
+            address_families: (true, vec!["AF_INET".to_string(), "AF_INET6".to_string()]),
+            system_call_filter: (true, vec!["@system-service".to_string()]),
+            ..Default::default()
+        };
+        let report = SandboxReport::evaluate(&settings);
+        let failed: Vec<&str> = report.failed().iter().map(|c| c.setting).collect();
+        assert_eq!(failed, ["PrivateNetwork=", "ProtectClock="]);
+        assert_eq!(report.exposure(), 1.5);
+        assert_eq!(report.level(), ExposureLevel::Ok);
+
+        let partial = SandboxReport::evaluate(&SandboxSettings {
+            protect_system: "full".to_string(),
+            protect_home: "read-only".to_string(),
+            ..settings
+        });
+        assert_eq!(partial.failed().len(), 4);
+        assert!(partial.exposure() > report.exposure());
+    }
+}
@@ -0,0 +1,191 @@
+// Security Center - Service Unit Files
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! The files that define a service, and the hardening drop-in this app
+//! writes next to them.
+//!
+//! Hardening settings such as `NoNewPrivileges=` cannot be changed on an
+//! installed service over D-Bus (`SetUnitProperties` only covers resource
+//! control), so the privileged helper writes them to
+//! `/etc/systemd/system/<unit>.d/60-security-center-hardening.conf` and then
+//! reloads systemd and restarts the service over D-Bus.
+
+use std::fs;
+
+use anyhow::{anyhow, Result};
+
+use crate::validation::validate_service_name;
+
+/// File name of the hardening drop-in.
+pub const HARDENING_DROPIN: &str = "60-security-center-hardening.conf";
+
+const HARDENING_HEADER: &str = "# Managed by Security Center\n[Service]\n";
+
+/// Unit files larger than this are shown truncated.
+const MAX_UNIT_FILE_SIZE: usize = 256 * 1024;
+
+/// A hardening setting the editor can add to a service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HardeningDirective {
+    NoNewPrivileges,
+    ProtectSystem,
+    PrivateDevices,
+}
+
+impl HardeningDirective {
+    pub const ALL: [HardeningDirective; 3] = [
+        HardeningDirective::NoNewPrivileges,
+        HardeningDirective::ProtectSystem,
+        HardeningDirective::PrivateDevices,
+    ];
+
+    /// Name of the setting in a unit file.
+    pub fn key(&self) -> &'static str {
+        match self {
+            HardeningDirective::NoNewPrivileges => "NoNewPrivileges",
+            HardeningDirective::ProtectSystem => "ProtectSystem",
+            HardeningDirective::PrivateDevices => "PrivateDevices",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            HardeningDirective::NoNewPrivileges => {
+                "The service and its children cannot gain privileges through set-UID programs"
+            }
+            HardeningDirective::ProtectSystem => {
+                "Mount /usr and /boot read-only; full adds /etc, strict the whole file system"
+            }
+            HardeningDirective::PrivateDevices => {
+                "Hide physical devices; only pseudo devices such as /dev/null stay reachable"
+            }
+        }
+    }
+
+    /// Values the setting accepts, mildest first.
+    pub fn values(&self) -> &'static [&'static str] {
+        match self {
+            HardeningDirective::ProtectSystem => &["yes", "full", "strict"],
+            _ => &["yes"],
+        }
+    }
+
+    pub fn parse(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.key() == key)
+    }
+}
+
+/// A hardening setting and its value, checked against
+/// [`HardeningDirective::values`].
+pub fn validate_hardening(key: &str, value: &str) -> Option<(HardeningDirective, String)> {
+    let directive = HardeningDirective::parse(key)?;
+    directive
+        .values()
+        .contains(&value)
+        .then(|| (directive, value.to_string()))
+}
+
+/// Path of the hardening drop-in of `unit`, which must be a `.service`.
+pub fn hardening_dropin_path(unit: &str) -> Result<String> {
+    validate_service_name(unit)?;
+    if !unit.ends_with(".service") || unit.starts_with('.') {
+        return Err(anyhow!("Not a service: {}", unit));
+    }
+    Ok(format!(
+        "/etc/systemd/system/{}.d/{}",
+        unit, HARDENING_DROPIN
+    ))
+}
+
+/// Contents of the hardening drop-in, or `None` when nothing is set.
+pub fn hardening_dropin(settings: &[(HardeningDirective, String)]) -> Option<String> {
+    if settings.is_empty() {
+        return None;
+    }
+    let mut contents = String::from(HARDENING_HEADER);
+    for (directive, value) in settings {
+        contents.push_str(&format!("{}={}\n", directive.key(), value));
+    }
+    Some(contents)
+}
+
+/// The settings of a hardening drop-in written by [`hardening_dropin`];
+/// other lines are ignored.
+pub fn parse_hardening_dropin(contents: &str) -> Vec<(HardeningDirective, String)> {
+    contents
+        .lines()
+        .filter_map(|line| line.trim().split_once('='))
+        .filter_map(|(key, value)| validate_hardening(key.trim(), value.trim()))
+        .collect()
+}
+
+/// One file of a unit: its main unit file or a drop-in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitFile {
+    pub path: String,
+    /// Contents, or why they could not be read.
+    pub contents: Result<String, String>,
+}
+
+impl UnitFile {
+    /// Whether this is the drop-in [`hardening_dropin`] writes.
+    pub fn is_hardening_dropin(&self) -> bool {
+        self.path.ends_with(&format!("/{}", HARDENING_DROPIN))
+    }
+}
+
+/// Read the files at `paths`. Unit files are world-readable.
+pub fn read_unit_files(paths: &[String]) -> Vec<UnitFile> {
+    paths
+        .iter()
+        .filter(|path| !path.is_empty())
+        .map(|path| {
+            let contents = fs::read(path)
+                .map(|bytes| {
+                    let end = bytes.len().min(MAX_UNIT_FILE_SIZE);
+                    String::from_utf8_lossy(&bytes[..end]).into_owned()
+                })
+                .map_err(|e| e.to_string());
+            UnitFile {
+                path: path.clone(),
+                contents,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hardening_dropin() {
+        let settings = vec![
+            (HardeningDirective::NoNewPrivileges, "yes".to_string()),
+            (HardeningDirective::ProtectSystem, "full".to_string()),
+        ];
+        let contents = hardening_dropin(&settings).unwrap();
+        assert_eq!(
+            contents,
+            "# Managed by Security Center\n[Service]\nNoNewPrivileges=yes\nProtectSystem=full\n"
+        );
+        assert_eq!(parse_hardening_dropin(&contents), settings);
+        assert_eq!(hardening_dropin(&[]), None);
+
+        assert_eq!(
+            validate_hardening("ProtectSystem", "strict\nUser=root"),
+            None
+        );
+        assert_eq!(validate_hardening("ExecStart", "/bin/sh"), None);
+        assert_eq!(validate_hardening("PrivateDevices", "full"), None);
+
+        assert_eq!(
+            hardening_dropin_path("nginx.service").unwrap(),
+            "/etc/systemd/system/nginx.service.d/60-security-center-hardening.conf"
+        );
+        assert!(hardening_dropin_path("sshd.socket").is_err());
+        assert!(hardening_dropin_path("../sshd.service").is_err());
+        assert!(hardening_dropin_path("").is_err());
+    }
+}
@@ -0,0 +1,222 @@
+// Security Center - Unit Listing
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Every service of the system or user manager, for the All Services view.
+//!
+//! A listing combines two manager calls: `ListUnitsByPatterns` for the
+//! services systemd has loaded, with their state, and
+//! `ListUnitFilesByPatterns` for the installed unit files, with their
+//! enablement. Services that are installed but not loaded appear as
+//! stopped. [`UnitFilter`] narrows the list down by state, enablement and
+//! text.
+
+use std::collections::BTreeMap;
+
+use super::client::ServiceState;
+
+/// Which service manager to list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnitScope {
+    /// The system manager, on the system bus.
+    #[default]
+    System,
+    /// The user's own manager, on the session bus.
+    User,
+}
+
+/// A service as listed by its manager.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnitEntry {
+    pub name: String,
+    pub description: String,
+    /// `SubState`, e.g. `running`, `exited`, `dead` or `failed`.
+    pub sub_state: String,
+    /// `UnitFileState`, e.g. `enabled`, `disabled`, `static` or `masked`;
+    /// empty for units without a unit file.
+    pub file_state: String,
+}
+
+impl UnitEntry {
+    pub fn state(&self) -> ServiceState {
+        ServiceState::from_str(&self.sub_state)
+    }
+
+    /// Started at boot or by another unit, as on the Security view.
+    pub fn is_enabled(&self) -> bool {
+        matches!(
+            self.file_state.as_str(),
+            "enabled" | "enabled-runtime" | "static"
+        )
+    }
+
+    pub fn is_masked(&self) -> bool {
+        self.file_state.starts_with("masked")
+    }
+}
+
+/// Combine the loaded units (name, description, sub state) with the
+/// installed unit files (path, state), sorted by name. Templates such as
+/// `getty@.service` cannot run by themselves and are left out.
+pub fn merge_units(
+    loaded: Vec<(String, String, String)>,
+    files: Vec<(String, String)>,
+) -> Vec<UnitEntry> {
+    let mut units: BTreeMap<String, UnitEntry> = BTreeMap::new();
+    for (path, file_state) in files {
+        let Some(name) = path.rsplit('/').next() else {
+            continue;
+        };
+        if name.ends_with("@.service") {
+            continue;
+        }
+        units.insert(
+            name.to_string(),
+            UnitEntry {
+                name: name.to_string(),
+                description: String::new(),
+                sub_state: "dead".to_string(),
+                file_state,
+            },
+        );
+    }
+    for (name, description, sub_state) in loaded {
+        let entry = units.entry(name.clone()).or_insert_with(|| UnitEntry {
+            name,
+            description: String::new(),
+            sub_state: String::new(),
+            file_state: String::new(),
+        });
+        entry.description = description;
+        entry.sub_state = sub_state;
+    }
+    units.into_values().collect()
+}
+
+/// State shown by the state filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StateFilter {
+    #[default]
+    Any,
+    Running,
+    Stopped,
+    Failed,
+}
+
+/// Enablement shown by the enablement filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EnablementFilter {
+    #[default]
+    Any,
+    Enabled,
+    Disabled,
+    Masked,
+}
+
+/// What the All Services view shows.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnitFilter {
+    pub state: StateFilter,
+    pub enablement: EnablementFilter,
+    /// Lowercase text matched against the name and description.
+    pub query: String,
+}
+
+impl UnitFilter {
+    pub fn matches(&self, unit: &UnitEntry) -> bool {
+        let state = match self.state {
+            StateFilter::Any => true,
+            StateFilter::Running => unit.state() == ServiceState::Running,
+            StateFilter::Failed => unit.state() == ServiceState::Failed,
+            StateFilter::Stopped => unit.state() == ServiceState::Stopped,
+        };
+        let enablement = match self.enablement {
+            EnablementFilter::Any => true,
+            EnablementFilter::Enabled => unit.is_enabled(),
+            EnablementFilter::Masked => unit.is_masked(),
+            EnablementFilter::Disabled => !unit.is_enabled() && !unit.is_masked(),
+        };
+        state
+            && enablement
+            && (self.query.is_empty()
+                || unit.name.to_lowercase().contains(&self.query)
+                || unit.description.to_lowercase().contains(&self.query))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_and_filter_units() {
+        let loaded = vec![
+            (
+                "sshd.service".to_string(),
+                "OpenSSH server daemon".to_string(),
+                "running".to_string(),
+            ),
+            (
+                "session-2.service".to_string(),
+                "Transient".to_string(),
+                "failed".to_string(),
+            ),
+        ];
+        let files = vec![
+            (
+                "/usr/lib/systemd/system/sshd.service".to_string(),
+                "enabled".to_string(),
+            ),
+            (
+                "/usr/lib/systemd/system/cups.service".to_string(),
+                "disabled".to_string(),
+            ),
+            (
+                "/usr/lib/systemd/system/getty@.service".to_string(),
+                "enabled".to_string(),
+            ),
+            (
+                "/etc/systemd/system/telnet.service".to_string(),
+                "masked".to_string(),
+            ),
+        ];
+        let units = merge_units(loaded, files);
+        let names: Vec<&str> = units.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "cups.service",
+                "session-2.service",
+                "sshd.service",
+                "telnet.service"
+            ]
+        );
+        assert_eq!(units[0].state(), ServiceState::Stopped);
+        assert_eq!(units[2].description, "OpenSSH server daemon");
+        assert!(units[2].is_enabled());
+
+        let shown = |filter: &UnitFilter| -> Vec<&str> {
+            units
+                .iter()
+                .filter(|u| filter.matches(u))
+                .map(|u| u.name.as_str())
+                .collect()
+        };
+        let mut filter = UnitFilter::default();
+        assert_eq!(shown(&filter).len(), 4);
+        filter.state = StateFilter::Stopped;
+        assert_eq!(shown(&filter), ["cups.service", "telnet.service"]);
+        filter.enablement = EnablementFilter::Disabled;
+        assert_eq!(shown(&filter), ["cups.service"]);
+        filter = UnitFilter {
+            enablement: EnablementFilter::Masked,
+            ..UnitFilter::default()
+        };
+        assert_eq!(shown(&filter), ["telnet.service"]);
+        filter = UnitFilter {
+            query: "openssh".to_string(),
+            ..UnitFilter::default()
+        };
+        assert_eq!(shown(&filter), ["sshd.service"]);
+    }
+}
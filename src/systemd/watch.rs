@@ -0,0 +1,325 @@
+// Security Center - Unit State Watch
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Live state of the services the app relies on.
+//!
+//! [`watch_units`] follows systemd's `PropertiesChanged` signals for
+//! firewalld, NetworkManager and the SSH server and reports each change of
+//! their `ActiveState` as a [`UnitStatus`], starting with the state each is
+//! in. The pages use it to pause actions while a service is starting,
+//! stopping or restarting, and to say why.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use tokio::sync::broadcast;
+use tracing::warn;
+use zbus::export::futures_util::{stream, StreamExt};
+use zbus::message::Type;
+use zbus::zvariant::{OwnedObjectPath, OwnedValue};
+use zbus::{Connection, MatchRule, MessageStream};
+
+use super::client::{MANAGER_INTERFACE, SYSTEMD_BUS, SYSTEMD_PATH, UNIT_INTERFACE};
+
+const PROPERTIES_INTERFACE: &str = "org.freedesktop.DBus.Properties";
+
+/// A service whose state is followed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WatchedUnit {
+    Firewalld,
+    NetworkManager,
+    Ssh,
+}
+
+impl WatchedUnit {
+    pub const ALL: [WatchedUnit; 3] = [Self::Firewalld, Self::NetworkManager, Self::Ssh];
+
+    /// Unit names to try, in order. The SSH server is `sshd.service` on
+    /// Fedora and RHEL and `ssh.service` on Debian and Ubuntu.
+    pub fn unit_names(&self) -> &'static [&'static str] {
+        match self {
+            Self::Firewalld => &["firewalld.service"],
+            Self::NetworkManager => &["NetworkManager.service"],
+            Self::Ssh => &["sshd.service", "ssh.service"],
+        }
+    }
+
+    /// Name shown to the user.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Firewalld => "firewalld",
+            Self::NetworkManager => "NetworkManager",
+            Self::Ssh => "SSH server",
+        }
+    }
+}
+
+/// What a unit is doing, from its systemd `ActiveState`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitActivity {
+    Active,
+    Reloading,
+    Activating,
+    Deactivating,
+    Inactive,
+    Failed,
+    /// No unit by any of its names is installed.
+    Missing,
+}
+
+impl UnitActivity {
+    /// Activity for an `ActiveState` value.
+    pub fn from_active_state(state: &str) -> Self {
+        match state {
+            "active" => Self::Active,
+            "reloading" => Self::Reloading,
+            "activating" => Self::Activating,
+            "deactivating" => Self::Deactivating,
+            "failed" => Self::Failed,
+            _ => Self::Inactive,
+        }
+    }
+
+    /// Whether the unit is between states, so actions on it should wait.
+    pub fn is_transitioning(&self) -> bool {
+        matches!(
+            self,
+            Self::Reloading | Self::Activating | Self::Deactivating
+        )
+    }
+
+    /// Short state for status pills, e.g. "Running".
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Active => "Running",
+            Self::Reloading => "Reloading",
+            Self::Activating => "Starting",
+            Self::Deactivating => "Stopping",
+            Self::Inactive => "Stopped",
+            Self::Failed => "Failed",
+            Self::Missing => "Not installed",
+        }
+    }
+
+    /// Why an action on the unit cannot run now, with `%s` standing for the
+    /// unit's label, or `None` if it can. `needs_running` is false for
+    /// actions that start or restart the unit themselves.
+    pub fn blocked_reason(&self, needs_running: bool) -> Option<&'static str> {
+        match self {
+            Self::Active => None,
+            Self::Reloading => Some("%s is reloading — try again in a moment"),
+            Self::Activating => Some("%s is starting — try again in a moment"),
+            Self::Deactivating => Some("%s is stopping — try again in a moment"),
+            Self::Inactive | Self::Failed if needs_running => Some("%s is not running"),
+            Self::Inactive | Self::Failed => None,
+            Self::Missing => Some("%s is not installed"),
+        }
+    }
+}
+
+/// A watched unit's activity, sent when it changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnitStatus {
+    pub unit: WatchedUnit,
+    pub activity: UnitActivity,
+}
+
+/// The activity a `PropertiesChanged` signal of a unit announces, if it
+/// changed `ActiveState`.
+fn activity_from_changes(mut changed: HashMap<String, OwnedValue>) -> Option<UnitActivity> {
+    let state = String::try_from(changed.remove("ActiveState")?).ok()?;
+    Some(UnitActivity::from_active_state(&state))
+}
+
+/// Object path of the first installed unit among `unit`'s names.
+async fn load_unit(conn: &Connection, unit: WatchedUnit) -> Option<OwnedObjectPath> {
+    for name in unit.unit_names() {
+        let Ok(reply) = conn
+            .call_method(
+                Some(SYSTEMD_BUS),
+                SYSTEMD_PATH,
+                Some(MANAGER_INTERFACE),
+                "LoadUnit",
+                &(name,),
+            )
+            .await
+        else {
+            continue;
+        };
+        let Ok(path) = reply.body().deserialize::<OwnedObjectPath>() else {
+            continue;
+        };
+        if get_property(conn, &path, "LoadState").await.as_deref() != Some("not-found") {
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// A string property of a unit.
+async fn get_property(conn: &Connection, path: &OwnedObjectPath, name: &str) -> Option<String> {
+    let reply = conn
+        .call_method(
+            Some(SYSTEMD_BUS),
+            path.as_str(),
+            Some(PROPERTIES_INTERFACE),
+            "Get",
+            &(UNIT_INTERFACE, name),
+        )
+        .await
+        .ok()?;
+    let value: OwnedValue = reply.body().deserialize().ok()?;
+    String::try_from(value).ok()
+}
+
+/// Send the state of every watched unit, then every change of it, until
+/// `sender` has no subscribers left.
+pub async fn watch(sender: broadcast::Sender<UnitStatus>) -> Result<()> {
+    let conn = Connection::system()
+        .await
+        .context("Failed to connect to system D-Bus")?;
+
+    // systemd only emits unit signals while some client is subscribed
+    conn.call_method(
+        Some(SYSTEMD_BUS),
+        SYSTEMD_PATH,
+        Some(MANAGER_INTERFACE),
+        "Subscribe",
+        &(),
+    )
+    .await
+    .context("Failed to subscribe to systemd signals")?;
+
+    let mut streams = Vec::new();
+    for unit in WatchedUnit::ALL {
+        let Some(path) = load_unit(&conn, unit).await else {
+            let _ = sender.send(UnitStatus {
+                unit,
+                activity: UnitActivity::Missing,
+            });
+            continue;
+        };
+
+        // Subscribe before reading the state, so no change falls in between
+        let rule = MatchRule::builder()
+            .msg_type(Type::Signal)
+            .sender(SYSTEMD_BUS)?
+            .path(path.clone())?
+            .interface(PROPERTIES_INTERFACE)?
+            .member("PropertiesChanged")?
+            .arg(0, UNIT_INTERFACE)?
+            .build();
+        let messages = MessageStream::for_match_rule(rule, &conn, None).await?;
+        streams.push(messages.map(move |msg| (unit, msg)));
+
+        if let Some(state) = get_property(&conn, &path, "ActiveState").await {
+            let activity = UnitActivity::from_active_state(&state);
+            if sender.send(UnitStatus { unit, activity }).is_err() {
+                return Ok(());
+            }
+        }
+    }
+
+    let mut messages = stream::select_all(streams);
+    while let Some((unit, msg)) = messages.next().await {
+        let Ok(msg) = msg else { continue };
+        let Ok((_, changed, _)) = msg
+            .body()
+            .deserialize::<(String, HashMap<String, OwnedValue>, Vec<String>)>()
+        else {
+            continue;
+        };
+        let Some(activity) = activity_from_changes(changed) else {
+            continue;
+        };
+        if sender.send(UnitStatus { unit, activity }).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Follow the watched units on a background thread. The receiver gets each
+/// unit's current state first, then every change.
+pub fn watch_units() -> broadcast::Receiver<UnitStatus> {
+    let (sender, receiver) = broadcast::channel(16);
+    std::thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                warn!("Cannot start the unit state watcher: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = runtime.block_on(watch(sender)) {
+            warn!("Stopped following unit states: {:#}", e);
+        }
+    });
+    receiver
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zbus::zvariant::Str;
+
+    #[test]
+    fn test_from_active_state() {
+        assert_eq!(
+            UnitActivity::from_active_state("active"),
+            UnitActivity::Active
+        );
+        assert_eq!(
+            UnitActivity::from_active_state("deactivating"),
+            UnitActivity::Deactivating
+        );
+        assert_eq!(
+            UnitActivity::from_active_state("failed"),
+            UnitActivity::Failed
+        );
+        // Anything else, such as "maintenance", counts as not running
+        assert_eq!(
+            UnitActivity::from_active_state("maintenance"),
+            UnitActivity::Inactive
+        );
+        assert!(UnitActivity::Activating.is_transitioning());
+        assert!(!UnitActivity::Failed.is_transitioning());
+    }
+
+    #[test]
+    fn test_blocked_reason() {
+        assert_eq!(UnitActivity::Active.blocked_reason(true), None);
+        // Starting a stopped unit is fine, using it is not
+        assert_eq!(UnitActivity::Inactive.blocked_reason(false), None);
+        assert!(UnitActivity::Failed.blocked_reason(true).is_some());
+        // Nothing runs while the unit is between states
+        assert!(UnitActivity::Activating.blocked_reason(false).is_some());
+        assert!(UnitActivity::Reloading.blocked_reason(true).is_some());
+        assert!(UnitActivity::Missing.blocked_reason(false).is_some());
+    }
+
+    #[test]
+    fn test_activity_from_changes() {
+        let changes = |entries: &[(&str, &'static str)]| {
+            entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), OwnedValue::from(Str::from_static(value))))
+                .collect::<HashMap<_, _>>()
+        };
+        assert_eq!(
+            activity_from_changes(changes(&[("SubState", "running")])),
+            None
+        );
+        assert_eq!(
+            activity_from_changes(changes(&[
+                ("SubState", "reload"),
+                ("ActiveState", "reloading")
+            ])),
+            Some(UnitActivity::Reloading)
+        );
+    }
+}
@@ -0,0 +1,468 @@
+// Security Center - System Tray
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! System tray icon for the background mode.
+//!
+//! The icon is a StatusNotifierItem (the KDE/AppIndicator tray protocol)
+//! served on the session bus with zbus: `org.kde.StatusNotifierItem` at
+//! [`ITEM_PATH`] and its menu as `com.canonical.dbusmenu` at [`MENU_PATH`],
+//! registered with `org.kde.StatusNotifierWatcher`. Desktops without a
+//! watcher, such as GNOME without the AppIndicator extension, have no tray;
+//! [`Tray::start`] fails there.
+//!
+//! Clicks arrive on the tray's runtime and are queued as [`TrayCommand`]s
+//! for the application to take on its main loop. The icon asks for
+//! attention while panic mode is on, and the menu's Block All Traffic item
+//! is checked.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tracing::{debug, info};
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Structure, Type, Value};
+use zbus::SignalContext;
+
+use crate::i18n::gettext;
+use crate::APP_ID;
+
+/// Object path of the StatusNotifierItem.
+pub const ITEM_PATH: &str = "/StatusNotifierItem";
+/// Object path of the item's menu.
+pub const MENU_PATH: &str = "/MenuBar";
+const WATCHER: &str = "org.kde.StatusNotifierWatcher";
+const WATCHER_PATH: &str = "/StatusNotifierWatcher";
+const ICON_NAME: &str = "com.chrisdaggas.security-center-symbolic";
+const ATTENTION_ICON_NAME: &str = "dialog-warning-symbolic";
+
+/// What a click in the tray asks the application for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayCommand {
+    /// Show the main window.
+    OpenWindow,
+    /// Turn panic mode on or off.
+    SetPanicMode(bool),
+    ReloadFirewall,
+    Quit,
+}
+
+/// Menu entries, by dbusmenu item id; 0 is the root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MenuItem {
+    Open = 1,
+    PanicMode = 2,
+    Reload = 3,
+    Quit = 4,
+    FirstSeparator = 10,
+    SecondSeparator = 11,
+}
+
+impl MenuItem {
+    /// Menu order, top to bottom.
+    const ALL: [MenuItem; 6] = [
+        MenuItem::Open,
+        MenuItem::FirstSeparator,
+        MenuItem::PanicMode,
+        MenuItem::Reload,
+        MenuItem::SecondSeparator,
+        MenuItem::Quit,
+    ];
+
+    fn from_id(id: i32) -> Option<Self> {
+        Self::ALL.into_iter().find(|item| *item as i32 == id)
+    }
+
+    /// The dbusmenu properties of the item.
+    fn properties(self, panic_mode: bool) -> HashMap<String, Value<'static>> {
+        let mut properties = HashMap::new();
+        let label = match self {
+            MenuItem::Open => gettext("Open Security Center"),
+            MenuItem::PanicMode => gettext("Block All Traffic"),
+            MenuItem::Reload => gettext("Reload Firewall"),
+            MenuItem::Quit => gettext("Quit"),
+            MenuItem::FirstSeparator | MenuItem::SecondSeparator => {
+                properties.insert("type".to_string(), Value::from("separator"));
+                return properties;
+            }
+        };
+        properties.insert("label".to_string(), Value::from(label));
+        if self == MenuItem::PanicMode {
+            properties.insert("toggle-type".to_string(), Value::from("checkmark"));
+            properties.insert(
+                "toggle-state".to_string(),
+                Value::from(i32::from(panic_mode)),
+            );
+        }
+        properties
+    }
+
+    /// What a click on the item asks for.
+    fn command(self, panic_mode: bool) -> Option<TrayCommand> {
+        match self {
+            MenuItem::Open => Some(TrayCommand::OpenWindow),
+            MenuItem::PanicMode => Some(TrayCommand::SetPanicMode(!panic_mode)),
+            MenuItem::Reload => Some(TrayCommand::ReloadFirewall),
+            MenuItem::Quit => Some(TrayCommand::Quit),
+            MenuItem::FirstSeparator | MenuItem::SecondSeparator => None,
+        }
+    }
+}
+
+/// State the D-Bus interfaces share with the application.
+#[derive(Debug)]
+struct Shared {
+    commands: Sender<TrayCommand>,
+    panic_mode: AtomicBool,
+    /// Menu layout revision, bumped when an item changes.
+    revision: AtomicU32,
+}
+
+impl Shared {
+    fn send(&self, command: TrayCommand) {
+        // The receiver only goes away with the tray itself
+        let _ = self.commands.send(command);
+    }
+
+    fn panic_mode(&self) -> bool {
+        self.panic_mode.load(Ordering::Relaxed)
+    }
+}
+
+struct StatusNotifierItem(Arc<Shared>);
+
+#[zbus::interface(name = "org.kde.StatusNotifierItem")]
+impl StatusNotifierItem {
+    /// Primary click on the icon.
+    fn activate(&self, _x: i32, _y: i32) {
+        self.0.send(TrayCommand::OpenWindow);
+    }
+
+    fn secondary_activate(&self, _x: i32, _y: i32) {
+        self.0.send(TrayCommand::OpenWindow);
+    }
+
+    /// Hosts show [`Self::menu`] themselves.
+    fn context_menu(&self, _x: i32, _y: i32) {}
+
+    fn scroll(&self, _delta: i32, _orientation: String) {}
+
+    #[zbus(property)]
+    fn category(&self) -> String {
+        "SystemServices".to_string()
+    }
+
+    #[zbus(property)]
+    fn id(&self) -> String {
+        APP_ID.to_string()
+    }
+
+    #[zbus(property)]
+    fn title(&self) -> String {
+        gettext("Security Center")
+    }
+
+    #[zbus(property)]
+    fn status(&self) -> String {
+        item_status(self.0.panic_mode()).to_string()
+    }
+
+    #[zbus(property)]
+    fn icon_name(&self) -> String {
+        ICON_NAME.to_string()
+    }
+
+    #[zbus(property)]
+    fn attention_icon_name(&self) -> String {
+        ATTENTION_ICON_NAME.to_string()
+    }
+
+    /// Icon name, icon pixmaps, title and description.
+    #[zbus(property)]
+    fn tool_tip(&self) -> (String, Vec<(i32, i32, Vec<u8>)>, String, String) {
+        let text = if self.0.panic_mode() {
+            gettext("Panic mode is on — all traffic is blocked")
+        } else {
+            gettext("Firewall monitoring in the background")
+        };
+        (String::new(), Vec::new(), gettext("Security Center"), text)
+    }
+
+    #[zbus(property)]
+    fn item_is_menu(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn menu(&self) -> OwnedObjectPath {
+        ObjectPath::from_static_str_unchecked(MENU_PATH).into()
+    }
+
+    #[zbus(signal)]
+    async fn new_status(ctxt: &SignalContext<'_>, status: &str) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn new_tool_tip(ctxt: &SignalContext<'_>) -> zbus::Result<()>;
+}
+
+fn item_status(panic_mode: bool) -> &'static str {
+    if panic_mode {
+        "NeedsAttention"
+    } else {
+        "Active"
+    }
+}
+
+/// A dbusmenu layout node: id, properties and child nodes as variants.
+#[derive(Debug, Serialize, Type)]
+struct MenuLayout {
+    id: i32,
+    properties: HashMap<String, Value<'static>>,
+    children: Vec<Value<'static>>,
+}
+
+struct DbusMenu(Arc<Shared>);
+
+impl DbusMenu {
+    /// Properties of item `id`; the root has none.
+    fn properties_of(&self, id: i32) -> Option<HashMap<String, Value<'static>>> {
+        if id == 0 {
+            return Some(HashMap::new());
+        }
+        MenuItem::from_id(id).map(|item| item.properties(self.0.panic_mode()))
+    }
+
+    fn clicked(&self, id: i32, event: &str) {
+        if event != "clicked" {
+            return;
+        }
+        if let Some(command) =
+            MenuItem::from_id(id).and_then(|item| item.command(self.0.panic_mode()))
+        {
+            self.0.send(command);
+        }
+    }
+}
+
+#[zbus::interface(name = "com.canonical.dbusmenu")]
+impl DbusMenu {
+    /// The whole menu; it is flat, so the parent and depth do not matter.
+    fn get_layout(
+        &self,
+        _parent_id: i32,
+        _recursion_depth: i32,
+        _property_names: Vec<String>,
+    ) -> (u32, MenuLayout) {
+        let panic_mode = self.0.panic_mode();
+        let children = MenuItem::ALL
+            .into_iter()
+            .map(|item| {
+                Value::from(Structure::from((
+                    item as i32,
+                    item.properties(panic_mode),
+                    Vec::<Value<'static>>::new(),
+                )))
+            })
+            .collect();
+        let layout = MenuLayout {
+            id: 0,
+            properties: HashMap::new(),
+            children,
+        };
+        (self.0.revision.load(Ordering::Relaxed), layout)
+    }
+
+    fn get_group_properties(
+        &self,
+        ids: Vec<i32>,
+        _property_names: Vec<String>,
+    ) -> Vec<(i32, HashMap<String, Value<'static>>)> {
+        ids.into_iter()
+            .filter_map(|id| Some((id, self.properties_of(id)?)))
+            .collect()
+    }
+
+    fn get_property(&self, id: i32, name: String) -> zbus::fdo::Result<OwnedValue> {
+        self.properties_of(id)
+            .and_then(|mut properties| properties.remove(&name))
+            .and_then(|value| OwnedValue::try_from(value).ok())
+            .ok_or_else(|| zbus::fdo::Error::InvalidArgs(format!("No property {} on {}", name, id)))
+    }
+
+    fn event(&self, id: i32, event_id: String, _data: OwnedValue, _timestamp: u32) {
+        self.clicked(id, &event_id);
+    }
+
+    /// Ids of the events whose item does not exist.
+    fn event_group(&self, events: Vec<(i32, String, OwnedValue, u32)>) -> Vec<i32> {
+        let mut unknown = Vec::new();
+        for (id, event_id, _, _) in events {
+            if MenuItem::from_id(id).is_some() {
+                self.clicked(id, &event_id);
+            } else {
+                unknown.push(id);
+            }
+        }
+        unknown
+    }
+
+    /// The menu never changes on opening.
+    fn about_to_show(&self, _id: i32) -> bool {
+        false
+    }
+
+    fn about_to_show_group(&self, _ids: Vec<i32>) -> (Vec<i32>, Vec<i32>) {
+        (Vec::new(), Vec::new())
+    }
+
+    #[zbus(property)]
+    fn version(&self) -> u32 {
+        3
+    }
+
+    #[zbus(property)]
+    fn text_direction(&self) -> String {
+        "ltr".to_string()
+    }
+
+    #[zbus(property)]
+    fn status(&self) -> String {
+        "normal".to_string()
+    }
+
+    #[zbus(property)]
+    fn icon_theme_path(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    #[zbus(signal)]
+    async fn layout_updated(
+        ctxt: &SignalContext<'_>,
+        revision: u32,
+        parent: i32,
+    ) -> zbus::Result<()>;
+}
+
+/// The tray icon, shown until dropped.
+pub struct Tray {
+    runtime: tokio::runtime::Runtime,
+    /// `None` once dropped.
+    connection: Option<zbus::Connection>,
+    shared: Arc<Shared>,
+    commands: Receiver<TrayCommand>,
+}
+
+impl Tray {
+    /// Show the icon. Fails when the session bus cannot be reached or no
+    /// tray host runs.
+    pub fn start() -> Result<Self> {
+        let runtime = tokio::runtime::Runtime::new().context("Failed to start the runtime")?;
+        let (sender, commands) = mpsc::channel();
+        let shared = Arc::new(Shared {
+            commands: sender,
+            panic_mode: AtomicBool::new(false),
+            revision: AtomicU32::new(1),
+        });
+        let bus_name = format!("org.kde.StatusNotifierItem-{}-1", std::process::id());
+
+        let connection = runtime.block_on(async {
+            let connection = zbus::connection::Builder::session()?
+                .name(bus_name.as_str())?
+                .serve_at(ITEM_PATH, StatusNotifierItem(shared.clone()))?
+                .serve_at(MENU_PATH, DbusMenu(shared.clone()))?
+                .build()
+                .await
+                .context("Failed to serve the tray icon on the session bus")?;
+            connection
+                .call_method(
+                    Some(WATCHER),
+                    WATCHER_PATH,
+                    Some(WATCHER),
+                    "RegisterStatusNotifierItem",
+                    &(bus_name.as_str()),
+                )
+                .await
+                .context("No system tray is available on this desktop")?;
+            anyhow::Ok(connection)
+        })?;
+        info!("Tray icon registered as {}", bus_name);
+
+        Ok(Self {
+            runtime,
+            connection: Some(connection),
+            shared,
+            commands,
+        })
+    }
+
+    /// Clicks since the last call, oldest first.
+    pub fn take_commands(&self) -> Vec<TrayCommand> {
+        self.commands.try_iter().collect()
+    }
+
+    /// Show whether panic mode is on, in the icon and the menu.
+    pub fn set_panic_mode(&self, enabled: bool) {
+        if self.shared.panic_mode.swap(enabled, Ordering::Relaxed) == enabled {
+            return;
+        }
+        let revision = self.shared.revision.fetch_add(1, Ordering::Relaxed) + 1;
+        let Some(connection) = self.connection.clone() else {
+            return;
+        };
+        self.runtime.spawn(async move {
+            let server = connection.object_server();
+            let result = async {
+                let item = server.interface::<_, StatusNotifierItem>(ITEM_PATH).await?;
+                StatusNotifierItem::new_status(item.signal_context(), item_status(enabled)).await?;
+                StatusNotifierItem::new_tool_tip(item.signal_context()).await?;
+                let menu = server.interface::<_, DbusMenu>(MENU_PATH).await?;
+                DbusMenu::layout_updated(menu.signal_context(), revision, 0).await
+            }
+            .await;
+            if let Err(e) = result {
+                debug!("Tray update not sent: {}", e);
+            }
+        });
+    }
+}
+
+impl Drop for Tray {
+    fn drop(&mut self) {
+        // Close the connection on its runtime, which takes the icon away
+        if let Some(connection) = self.connection.take() {
+            self.runtime.block_on(async move { drop(connection) });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_menu_items() {
+        for item in MenuItem::ALL {
+            assert_eq!(MenuItem::from_id(item as i32), Some(item));
+        }
+        assert_eq!(MenuItem::from_id(0), None);
+        assert_eq!(
+            MenuItem::PanicMode.command(false),
+            Some(TrayCommand::SetPanicMode(true))
+        );
+        assert_eq!(
+            MenuItem::PanicMode.command(true),
+            Some(TrayCommand::SetPanicMode(false))
+        );
+        assert_eq!(MenuItem::FirstSeparator.command(false), None);
+
+        let properties = MenuItem::PanicMode.properties(true);
+        assert_eq!(properties["toggle-state"], Value::from(1i32));
+        assert_eq!(
+            MenuItem::SecondSeparator.properties(false)["type"],
+            Value::from("separator")
+        );
+    }
+}
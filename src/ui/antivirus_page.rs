@@ -0,0 +1,704 @@
+// Security Center - Antivirus Page
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Antivirus page: ClamAV's engine, the age of its signatures and whether
+//! clamd and freshclam run, with on-demand scans of chosen folders.
+
+use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::*;
+use gtk4::{gio, glib};
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use super::density::{self, Role};
+use crate::admin::{
+    query_antivirus, AntivirusScan, AntivirusStatus, ScanProgress, Scanner, OUTDATED_AFTER_DAYS,
+};
+use crate::i18n::gettext;
+
+/// How often a running scan's progress is shown.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(300);
+/// Read errors listed after a scan; the rest are only counted.
+const MAX_LISTED_ERRORS: usize = 5;
+
+glib::wrapper! {
+    /// ClamAV status and scans page.
+    pub struct AntivirusPage(ObjectSubclass<imp::AntivirusPage>)
+        @extends gtk4::Box, gtk4::Widget,
+        @implements gtk4::Orientable;
+}
+
+impl AntivirusPage {
+    /// Create a new antivirus page.
+    pub fn new() -> Self {
+        let page: Self = glib::Object::new();
+        page.setup_ui();
+        page
+    }
+
+    /// Setup the UI.
+    fn setup_ui(&self) {
+        let imp = self.imp();
+
+        self.set_orientation(gtk4::Orientation::Vertical);
+        self.set_spacing(0);
+
+        // Header with refresh button
+        let header_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(12)
+            .build();
+        density::track(&header_box, Role::PageHeader);
+
+        let title_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(4)
+            .hexpand(true)
+            .build();
+
+        let title = gtk4::Label::builder()
+            .label(gettext("Antivirus"))
+            .css_classes(vec!["title-1".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        let subtitle = gtk4::Label::builder()
+            .label(gettext("ClamAV signatures and on-demand scans"))
+            .css_classes(vec!["dim-label".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        title_box.append(&title);
+        title_box.append(&subtitle);
+
+        let refresh_button = gtk4::Button::builder()
+            .icon_name("view-refresh-symbolic")
+            .css_classes(vec!["flat".to_string()])
+            .tooltip_text(gettext("Refresh"))
+            .valign(gtk4::Align::Center)
+            .build();
+        let page = self.clone();
+        refresh_button.connect_clicked(move |_| {
+            page.refresh();
+        });
+
+        header_box.append(&title_box);
+        header_box.append(&refresh_button);
+        self.append(&header_box);
+
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .vscrollbar_policy(gtk4::PolicyType::Automatic)
+            .vexpand(true)
+            .hexpand(true)
+            .build();
+
+        let content = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .hexpand(true)
+            .build();
+        density::track(
+            &content,
+            Role::Content {
+                margin: 24,
+                spacing: 24,
+            },
+        );
+
+        // ClamAV status
+        content.append(&Self::create_section_header(
+            "security-high-symbolic",
+            &gettext("ClamAV"),
+        ));
+        let status_group = adw::PreferencesGroup::new();
+        imp.status_group.replace(Some(status_group.clone()));
+        content.append(&status_group);
+
+        // Folders to scan and the scan controls
+        content.append(&Self::create_section_header(
+            "folder-symbolic",
+            &gettext("Scan"),
+        ));
+        let scan_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(12)
+            .build();
+        let folders_group = adw::PreferencesGroup::builder()
+            .description(gettext("Folders are scanned with everything inside them"))
+            .build();
+        let add_button = gtk4::Button::builder()
+            .icon_name("list-add-symbolic")
+            .css_classes(vec!["flat".to_string()])
+            .tooltip_text(gettext("Add Folder"))
+            .valign(gtk4::Align::Center)
+            .build();
+        let page = self.clone();
+        add_button.connect_clicked(move |_| {
+            page.choose_folder();
+        });
+        folders_group.set_header_suffix(Some(&add_button));
+        imp.folders_group.replace(Some(folders_group.clone()));
+        scan_box.append(&folders_group);
+
+        let progress_bar = gtk4::ProgressBar::builder()
+            .show_text(true)
+            .visible(false)
+            .build();
+        imp.progress_bar.replace(Some(progress_bar.clone()));
+        scan_box.append(&progress_bar);
+
+        let buttons = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(12)
+            .halign(gtk4::Align::End)
+            .build();
+        let cancel_button = gtk4::Button::builder()
+            .label(gettext("Cancel"))
+            .visible(false)
+            .build();
+        let page = self.clone();
+        cancel_button.connect_clicked(move |_| {
+            page.cancel_scan();
+        });
+        let scan_button = gtk4::Button::builder()
+            .label(gettext("Scan Now"))
+            .css_classes(vec!["suggested-action".to_string()])
+            .sensitive(false)
+            .build();
+        let page = self.clone();
+        scan_button.connect_clicked(move |_| {
+            page.start_scan();
+        });
+        buttons.append(&cancel_button);
+        buttons.append(&scan_button);
+        imp.cancel_button.replace(Some(cancel_button));
+        imp.scan_button.replace(Some(scan_button));
+        scan_box.append(&buttons);
+        imp.scan_box.replace(Some(scan_box.clone()));
+        content.append(&scan_box);
+
+        // Results of the last scan
+        content.append(&Self::create_section_header(
+            "dialog-warning-symbolic",
+            &gettext("Results"),
+        ));
+        let results_group = adw::PreferencesGroup::new();
+        imp.results_group.replace(Some(results_group.clone()));
+        content.append(&results_group);
+        self.show_results_notice(
+            "dialog-information-symbolic",
+            &gettext("No scan yet"),
+            &gettext("Choose folders and scan them for malware"),
+        );
+
+        // Downloads is where unwanted files usually arrive
+        let default_folder = dirs::download_dir().or_else(dirs::home_dir);
+        imp.folders.replace(default_folder.into_iter().collect());
+        self.display_folders();
+
+        scrolled.set_child(Some(&content));
+        self.append(&scrolled);
+    }
+
+    /// Detect ClamAV again.
+    pub fn refresh(&self) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            match gio::spawn_blocking(query_antivirus).await {
+                Ok(status) => page.display_status(&status),
+                Err(_) => page.show_toast(&gettext("Failed to detect ClamAV")),
+            }
+        });
+    }
+
+    fn display_status(&self, status: &AntivirusStatus) {
+        let imp = self.imp();
+        let Some(group) = imp.status_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.status_rows.take() {
+            group.remove(&row);
+        }
+        imp.scanner.set(status.scanner);
+        self.update_scan_controls();
+
+        let mut rows: Vec<gtk4::Widget> = Vec::new();
+        let Some(scanner) = status.scanner else {
+            let row = Self::create_notice_row(
+                "dialog-information-symbolic",
+                &gettext("ClamAV is not installed"),
+                &gettext(
+                    "Install the clamav package, and clamav-freshclam to keep its signatures \
+                     up to date, to scan files for malware.",
+                ),
+            );
+            group.add(&row);
+            rows.push(row.upcast());
+            imp.status_rows.replace(rows);
+            if let Some(scan_box) = imp.scan_box.borrow().as_ref() {
+                scan_box.set_sensitive(false);
+            }
+            return;
+        };
+        if let Some(scan_box) = imp.scan_box.borrow().as_ref() {
+            scan_box.set_sensitive(true);
+        }
+
+        let engine = adw::ActionRow::builder()
+            .title(gettext("Engine"))
+            .subtitle(
+                status
+                    .engine
+                    .as_deref()
+                    .map(|version| format!("ClamAV {}", version))
+                    .unwrap_or_else(|| gettext("Unknown version")),
+            )
+            .build();
+        engine.add_css_class("property");
+        rows.push(engine.upcast());
+
+        rows.push(Self::create_signatures_row(status).upcast());
+
+        let (daemon_icon, daemon_text) = if status.daemon_running {
+            ("emblem-ok-symbolic", gettext("Running; scans use it"))
+        } else {
+            (
+                "dialog-information-symbolic",
+                gettext("Not running; each scan loads the signatures first, which takes a while"),
+            )
+        };
+        let daemon = Self::create_notice_row(daemon_icon, &gettext("clamd"), &daemon_text);
+        rows.push(daemon.upcast());
+
+        let (updater_icon, updater_text) = if status.freshclam_running {
+            (
+                "emblem-ok-symbolic",
+                gettext("Running; signatures are updated automatically"),
+            )
+        } else if status.freshclam_installed {
+            (
+                "dialog-warning-symbolic",
+                gettext("Not running; start clamav-freshclam.service on the System Services page"),
+            )
+        } else {
+            (
+                "dialog-warning-symbolic",
+                gettext("Not installed; signatures are not updated"),
+            )
+        };
+        let updater = Self::create_notice_row(updater_icon, &gettext("freshclam"), &updater_text);
+        rows.push(updater.upcast());
+
+        let scanner_text = match scanner {
+            Scanner::Daemon => gettext("clamdscan, through clamd"),
+            Scanner::Standalone => gettext("clamscan"),
+        };
+        let scanner_row = adw::ActionRow::builder()
+            .title(gettext("Scanner"))
+            .subtitle(scanner_text)
+            .build();
+        scanner_row.add_css_class("property");
+        rows.push(scanner_row.upcast());
+
+        for row in &rows {
+            group.add(row);
+        }
+        imp.status_rows.replace(rows);
+    }
+
+    fn create_signatures_row(status: &AntivirusStatus) -> adw::ActionRow {
+        let now = chrono::Utc::now().timestamp();
+        let Some(signatures) = status.signatures.as_ref() else {
+            return Self::create_notice_row(
+                "dialog-error-symbolic",
+                &gettext("Signatures"),
+                &gettext("No signature database; run freshclam to download it"),
+            );
+        };
+        let age = match signatures.age_days(now) {
+            Some(0) => gettext("Updated today"),
+            Some(1) => gettext("Updated yesterday"),
+            Some(days) => gettext("Updated %d days ago").replace("%d", &days.to_string()),
+            None => gettext("Update time unknown"),
+        };
+        let subtitle = match signatures.version {
+            Some(version) => format!(
+                "{} · {}",
+                gettext("Version %d").replace("%d", &version.to_string()),
+                age
+            ),
+            None => age,
+        };
+        let icon = if signatures.is_outdated(now) {
+            "dialog-warning-symbolic"
+        } else {
+            "emblem-ok-symbolic"
+        };
+        let row = Self::create_notice_row(icon, &gettext("Signatures"), &subtitle);
+        if signatures.is_outdated(now) {
+            row.set_tooltip_text(Some(
+                &gettext("Signatures older than %d days miss recent threats")
+                    .replace("%d", &OUTDATED_AFTER_DAYS.to_string()),
+            ));
+        }
+        row
+    }
+
+    /// Show the folders to scan, each with a button to remove it.
+    fn display_folders(&self) {
+        let imp = self.imp();
+        let Some(group) = imp.folders_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.folder_rows.take() {
+            group.remove(&row);
+        }
+
+        let folders = imp.folders.borrow().clone();
+        let mut rows: Vec<gtk4::Widget> = Vec::new();
+        if folders.is_empty() {
+            let row = adw::ActionRow::builder()
+                .title(gettext("No folders chosen"))
+                .css_classes(vec!["dim-label".to_string()])
+                .build();
+            rows.push(row.upcast());
+        }
+        for folder in folders {
+            let row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&folder.display().to_string()).as_str())
+                .build();
+            row.add_prefix(&gtk4::Image::from_icon_name("folder-symbolic"));
+            let remove_button = gtk4::Button::builder()
+                .icon_name("list-remove-symbolic")
+                .css_classes(vec!["flat".to_string()])
+                .tooltip_text(gettext("Remove"))
+                .valign(gtk4::Align::Center)
+                .build();
+            let page = self.clone();
+            remove_button.connect_clicked(move |_| {
+                page.imp().folders.borrow_mut().retain(|f| *f != folder);
+                page.display_folders();
+            });
+            row.add_suffix(&remove_button);
+            rows.push(row.upcast());
+        }
+        for row in &rows {
+            group.add(row);
+        }
+        imp.folder_rows.replace(rows);
+        self.update_scan_controls();
+    }
+
+    fn choose_folder(&self) {
+        let Some(window) = self.root().and_downcast::<gtk4::Window>() else {
+            return;
+        };
+        let dialog = gtk4::FileDialog::builder()
+            .title(gettext("Choose a Folder to Scan"))
+            .modal(true)
+            .build();
+        let page = self.clone();
+        dialog.select_folder(Some(&window), gio::Cancellable::NONE, move |result| {
+            // Cancelling the dialog is not an error
+            let Ok(folder) = result else {
+                return;
+            };
+            let Some(path) = folder.path() else {
+                page.show_toast(&gettext("Choose a local folder"));
+                return;
+            };
+            let mut folders = page.imp().folders.borrow_mut();
+            if !folders.contains(&path) {
+                folders.push(path);
+            }
+            drop(folders);
+            page.display_folders();
+        });
+    }
+
+    /// Enable scanning when ClamAV is there, folders are chosen and no
+    /// scan runs.
+    fn update_scan_controls(&self) {
+        let imp = self.imp();
+        let running = imp.scanning.get();
+        let ready = imp.scanner.get().is_some() && !imp.folders.borrow().is_empty();
+        if let Some(button) = imp.scan_button.borrow().as_ref() {
+            button.set_sensitive(ready && !running);
+        }
+        if let Some(button) = imp.cancel_button.borrow().as_ref() {
+            button.set_visible(running);
+        }
+        if let Some(group) = imp.folders_group.borrow().as_ref() {
+            group.set_sensitive(!running);
+        }
+    }
+
+    fn start_scan(&self) {
+        let imp = self.imp();
+        let Some(scanner) = imp.scanner.get() else {
+            return;
+        };
+        let folders = imp.folders.borrow().clone();
+        let scan = match AntivirusScan::start(scanner, &folders) {
+            Ok(scan) => scan,
+            Err(e) => {
+                self.show_toast(&format!("{}: {}", gettext("Failed to start the scan"), e));
+                return;
+            }
+        };
+        imp.scan.replace(Some(scan));
+        imp.scanning.set(true);
+        self.update_scan_controls();
+        self.show_results_notice(
+            "content-loading-symbolic",
+            &gettext("Scanning…"),
+            &gettext("Threats found are listed here as the scan goes"),
+        );
+        if let Some(bar) = imp.progress_bar.borrow().as_ref() {
+            bar.set_fraction(0.0);
+            bar.set_visible(true);
+        }
+
+        let page = self.downgrade();
+        glib::timeout_add_local(PROGRESS_INTERVAL, move || {
+            let Some(page) = page.upgrade() else {
+                return glib::ControlFlow::Break;
+            };
+            let Some(progress) = page.imp().scan.borrow_mut().as_mut().map(|s| s.poll()) else {
+                return glib::ControlFlow::Break;
+            };
+            page.display_progress(&progress);
+            if progress.finished {
+                page.finish_scan(&progress);
+                return glib::ControlFlow::Break;
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+
+    fn cancel_scan(&self) {
+        if let Some(scan) = self.imp().scan.borrow_mut().as_mut() {
+            scan.cancel();
+        }
+    }
+
+    fn display_progress(&self, progress: &ScanProgress) {
+        let imp = self.imp();
+        if let Some(bar) = imp.progress_bar.borrow().as_ref() {
+            // The scanners do not say how many files there are
+            bar.pulse();
+            let scanned =
+                gettext("%d files scanned").replace("%d", &progress.files_scanned.to_string());
+            let text = match progress.current.as_deref() {
+                Some(current) => format!("{} · {}", scanned, current),
+                None => scanned,
+            };
+            bar.set_text(Some(&text));
+        }
+        if !progress.detections.is_empty()
+            && imp.listed_detections.get() != progress.detections.len()
+        {
+            self.display_results(progress);
+        }
+    }
+
+    fn finish_scan(&self, progress: &ScanProgress) {
+        let imp = self.imp();
+        imp.scan.take();
+        imp.scanning.set(false);
+        self.update_scan_controls();
+        if let Some(bar) = imp.progress_bar.borrow().as_ref() {
+            bar.set_visible(false);
+        }
+
+        if let Some(failure) = progress.failure.as_deref() {
+            self.show_results_notice(
+                "dialog-error-symbolic",
+                &gettext("The scan failed"),
+                failure,
+            );
+            return;
+        }
+        self.display_results(progress);
+        let summary = if progress.cancelled {
+            gettext("Scan cancelled")
+        } else if progress.detections.is_empty() {
+            gettext("Scan finished, no threats found")
+        } else {
+            gettext("Scan finished, %d threats found")
+                .replace("%d", &progress.detections.len().to_string())
+        };
+        self.show_toast(&summary);
+    }
+
+    /// List what the scan found and could not read.
+    fn display_results(&self, progress: &ScanProgress) {
+        let imp = self.imp();
+        let Some(group) = imp.results_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.result_rows.take() {
+            group.remove(&row);
+        }
+        imp.listed_detections.set(progress.detections.len());
+
+        let mut rows: Vec<gtk4::Widget> = Vec::new();
+        if progress.finished && progress.detections.is_empty() {
+            let (title, subtitle) = if progress.cancelled {
+                (
+                    gettext("Scan cancelled"),
+                    gettext("Nothing was found before it stopped"),
+                )
+            } else {
+                (
+                    gettext("No threats found"),
+                    gettext("%d files scanned").replace("%d", &progress.files_scanned.to_string()),
+                )
+            };
+            let icon = if progress.cancelled {
+                "dialog-information-symbolic"
+            } else {
+                "emblem-ok-symbolic"
+            };
+            rows.push(Self::create_notice_row(icon, &title, &subtitle).upcast());
+        }
+        for detection in &progress.detections {
+            let row = Self::create_notice_row(
+                "dialog-warning-symbolic",
+                &detection.path,
+                &detection.signature,
+            );
+            row.set_title_lines(2);
+            rows.push(row.upcast());
+        }
+        if progress.finished && !progress.errors.is_empty() {
+            let row = adw::ExpanderRow::builder()
+                .title(
+                    gettext("%d files could not be scanned")
+                        .replace("%d", &progress.errors.len().to_string()),
+                )
+                .build();
+            row.add_prefix(&gtk4::Image::from_icon_name("dialog-information-symbolic"));
+            for error in progress.errors.iter().take(MAX_LISTED_ERRORS) {
+                let error_row = adw::ActionRow::builder()
+                    .title(glib::markup_escape_text(error).as_str())
+                    .title_lines(2)
+                    .build();
+                error_row.add_css_class("property");
+                row.add_row(&error_row);
+            }
+            rows.push(row.upcast());
+        }
+        for row in &rows {
+            group.add(row);
+        }
+        imp.result_rows.replace(rows);
+    }
+
+    fn show_results_notice(&self, icon_name: &str, title: &str, subtitle: &str) {
+        let imp = self.imp();
+        let Some(group) = imp.results_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.result_rows.take() {
+            group.remove(&row);
+        }
+        imp.listed_detections.set(0);
+        let row = Self::create_notice_row(icon_name, title, subtitle);
+        group.add(&row);
+        imp.result_rows.replace(vec![row.upcast()]);
+    }
+
+    fn create_notice_row(icon_name: &str, title: &str, subtitle: &str) -> adw::ActionRow {
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(title).as_str())
+            .subtitle(glib::markup_escape_text(subtitle).as_str())
+            .build();
+        row.add_prefix(&gtk4::Image::from_icon_name(icon_name));
+        row
+    }
+
+    /// Show a toast message.
+    fn show_toast(&self, message: &str) {
+        if let Some(root) = self.root() {
+            if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                if let Some(main_window) = window.downcast_ref::<super::MainWindow>() {
+                    main_window.show_toast(message);
+                }
+            }
+        }
+    }
+
+    /// Create a section header with icon on the left.
+    fn create_section_header(icon_name: &str, title: &str) -> gtk4::Box {
+        let header = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(8)
+            .margin_bottom(6)
+            .build();
+
+        let icon = gtk4::Image::builder()
+            .icon_name(icon_name)
+            .css_classes(vec!["heading".to_string()])
+            .build();
+
+        let label = gtk4::Label::builder()
+            .label(title)
+            .css_classes(vec!["heading".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        header.append(&icon);
+        header.append(&label);
+        header
+    }
+}
+
+impl Default for AntivirusPage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct AntivirusPage {
+        pub status_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub status_rows: RefCell<Vec<gtk4::Widget>>,
+        /// The program scans use; `None` until ClamAV is detected.
+        pub scanner: Cell<Option<Scanner>>,
+        pub scan_box: RefCell<Option<gtk4::Box>>,
+        pub folders_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub folder_rows: RefCell<Vec<gtk4::Widget>>,
+        /// Folders the next scan covers.
+        pub folders: RefCell<Vec<PathBuf>>,
+        pub progress_bar: RefCell<Option<gtk4::ProgressBar>>,
+        pub scan_button: RefCell<Option<gtk4::Button>>,
+        pub cancel_button: RefCell<Option<gtk4::Button>>,
+        pub results_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub result_rows: RefCell<Vec<gtk4::Widget>>,
+        /// Detections listed so far, to redraw only when there are more.
+        pub listed_detections: Cell<usize>,
+        /// The running scan, stopped when the page goes away.
+        pub scan: RefCell<Option<AntivirusScan>>,
+        pub scanning: Cell<bool>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for AntivirusPage {
+        const NAME: &'static str = "SecurityCenterAntivirusPage";
+        type Type = super::AntivirusPage;
+        type ParentType = gtk4::Box;
+    }
+
+    impl ObjectImpl for AntivirusPage {}
+    impl WidgetImpl for AntivirusPage {}
+    impl BoxImpl for AntivirusPage {}
+}
@@ -0,0 +1,172 @@
+// Security Center - Application State
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Shared application state that pages report their changes to.
+//!
+//! A page that changed the firewall invalidates only what it touched
+//! through a signal on [`AppState`], instead of asking the window to fetch
+//! everything again. The window merges the invalidations that arrive
+//! together into one [`RefreshScope`] and updates only the affected pages,
+//! saving D-Bus round trips and needless re-rendering.
+
+use std::sync::OnceLock;
+
+use gtk4::glib;
+use gtk4::glib::subclass::Signal;
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::*;
+
+/// What part of the firewall configuration changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Invalidation {
+    /// Ports opened, closed or blocked in a zone; blocks are rich rules.
+    PortsChanged,
+    /// Services enabled in a zone, or service definitions.
+    ServicesChanged,
+    /// Anything else about zones: interfaces, sources, rich rules, settings.
+    ZonesChanged,
+}
+
+impl Invalidation {
+    const ALL: [Invalidation; 3] = [
+        Invalidation::PortsChanged,
+        Invalidation::ServicesChanged,
+        Invalidation::ZonesChanged,
+    ];
+
+    /// What changed, as told to the script hooks.
+    pub fn hook_name(self) -> &'static str {
+        match self {
+            Invalidation::PortsChanged => "ports",
+            Invalidation::ServicesChanged => "services",
+            Invalidation::ZonesChanged => "zones",
+        }
+    }
+
+    fn signal_name(self) -> &'static str {
+        match self {
+            Invalidation::PortsChanged => "ports-changed",
+            Invalidation::ServicesChanged => "services-changed",
+            Invalidation::ZonesChanged => "zones-changed",
+        }
+    }
+}
+
+/// What a refresh fetches and which pages it updates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RefreshScope {
+    pub ports: bool,
+    pub services: bool,
+    pub zones: bool,
+    /// Panic mode, LogDenied and the connection status.
+    pub status: bool,
+}
+
+impl RefreshScope {
+    /// Everything, as after a reload or when firewalld reconnects.
+    pub const ALL: RefreshScope = RefreshScope {
+        ports: true,
+        services: true,
+        zones: true,
+        status: true,
+    };
+
+    /// Both scopes together.
+    pub fn merge(self, other: RefreshScope) -> RefreshScope {
+        RefreshScope {
+            ports: self.ports || other.ports,
+            services: self.services || other.services,
+            zones: self.zones || other.zones,
+            status: self.status || other.status,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == RefreshScope::default()
+    }
+
+    /// Whether the Services page shows something in scope.
+    pub fn services_page(&self) -> bool {
+        self.services || self.zones
+    }
+
+    /// Whether the Ports page shows something in scope.
+    pub fn ports_page(&self) -> bool {
+        self.ports || self.zones
+    }
+
+    /// Whether the Rich Rules page shows something in scope.
+    pub fn rich_rules_page(&self) -> bool {
+        self.ports || self.zones
+    }
+}
+
+impl From<Invalidation> for RefreshScope {
+    fn from(change: Invalidation) -> Self {
+        RefreshScope {
+            ports: change == Invalidation::PortsChanged,
+            services: change == Invalidation::ServicesChanged,
+            zones: change == Invalidation::ZonesChanged,
+            status: false,
+        }
+    }
+}
+
+glib::wrapper! {
+    /// Application state shared by the main window and its pages.
+    pub struct AppState(ObjectSubclass<imp::AppState>);
+}
+
+impl AppState {
+    pub fn new() -> Self {
+        glib::Object::new()
+    }
+
+    /// Report that `change` happened, so whatever shows it refreshes.
+    pub fn invalidate(&self, change: Invalidation) {
+        self.emit_by_name::<()>(change.signal_name(), &[]);
+    }
+
+    /// Call `f` with every change reported.
+    pub fn connect_invalidated<F: Fn(Invalidation) + Clone + 'static>(&self, f: F) {
+        for change in Invalidation::ALL {
+            let f = f.clone();
+            self.connect_local(change.signal_name(), false, move |_| {
+                f(change);
+                None
+            });
+        }
+    }
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct AppState;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for AppState {
+        const NAME: &'static str = "SecurityCenterAppState";
+        type Type = super::AppState;
+    }
+
+    impl ObjectImpl for AppState {
+        fn signals() -> &'static [Signal] {
+            static SIGNALS: OnceLock<Vec<Signal>> = OnceLock::new();
+            SIGNALS.get_or_init(|| {
+                Invalidation::ALL
+                    .iter()
+                    .map(|change| Signal::builder(change.signal_name()).build())
+                    .collect()
+            })
+        }
+    }
+}
@@ -0,0 +1,374 @@
+// Security Center - Applications Page
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Applications page: each program listening on the network, the ports it
+//! listens on and the firewall rules that let them in, followed by the
+//! ports and services the zones open with nothing listening behind them.
+
+use std::cell::RefCell;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use super::app_icons;
+use super::density::{self, Role};
+use crate::admin::{get_service_name, AppListener, Application, ApplicationView};
+use crate::i18n::gettext;
+
+glib::wrapper! {
+    /// Firewall rules and listening sockets grouped by application.
+    pub struct ApplicationsPage(ObjectSubclass<imp::ApplicationsPage>)
+        @extends gtk4::Box, gtk4::Widget,
+        @implements gtk4::Orientable;
+}
+
+impl ApplicationsPage {
+    /// Create a new applications page.
+    pub fn new() -> Self {
+        let page: Self = glib::Object::new();
+        page.setup_ui();
+        page
+    }
+
+    /// Setup the UI.
+    fn setup_ui(&self) {
+        let imp = self.imp();
+
+        self.set_orientation(gtk4::Orientation::Vertical);
+        self.set_spacing(0);
+
+        // Header with refresh button
+        let header_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(12)
+            .build();
+        density::track(&header_box, Role::PageHeader);
+
+        let title_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(4)
+            .hexpand(true)
+            .build();
+
+        let title = gtk4::Label::builder()
+            .label(gettext("Applications"))
+            .css_classes(vec!["title-1".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        let subtitle = gtk4::Label::builder()
+            .label(gettext(
+                "Programs listening on the network and the firewall rules that let them in",
+            ))
+            .css_classes(vec!["dim-label".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        title_box.append(&title);
+        title_box.append(&subtitle);
+
+        let refresh_button = gtk4::Button::builder()
+            .icon_name("view-refresh-symbolic")
+            .css_classes(vec!["flat".to_string()])
+            .tooltip_text(gettext("Refresh"))
+            .valign(gtk4::Align::Center)
+            .build();
+        let page = self.clone();
+        refresh_button.connect_clicked(move |_| {
+            page.refresh();
+        });
+
+        header_box.append(&title_box);
+        header_box.append(&refresh_button);
+        self.append(&header_box);
+
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .vscrollbar_policy(gtk4::PolicyType::Automatic)
+            .vexpand(true)
+            .hexpand(true)
+            .build();
+
+        let content = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .hexpand(true)
+            .build();
+        density::track(
+            &content,
+            Role::Content {
+                margin: 24,
+                spacing: 24,
+            },
+        );
+
+        // Listening applications
+        content.append(&Self::create_section_header(
+            "application-x-executable-symbolic",
+            &gettext("Listening Applications"),
+        ));
+        let apps_group = adw::PreferencesGroup::new();
+        imp.apps_group.replace(Some(apps_group.clone()));
+        content.append(&apps_group);
+
+        // Rules with nothing behind them
+        let unused_header = Self::create_section_header(
+            "dialog-information-symbolic",
+            &gettext("Open Without a Listener"),
+        );
+        unused_header.set_visible(false);
+        imp.unused_header.replace(Some(unused_header.clone()));
+        content.append(&unused_header);
+        let unused_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "Ports and services the firewall lets in although no program listens on them. \
+                 Closing them keeps a program started later from being reachable unnoticed.",
+            ))
+            .visible(false)
+            .build();
+        imp.unused_group.replace(Some(unused_group.clone()));
+        content.append(&unused_group);
+
+        scrolled.set_child(Some(&content));
+        self.append(&scrolled);
+    }
+
+    /// Scan listening sockets and the zones, grouped by application.
+    pub fn refresh(&self) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(crate::admin::query_applications).await;
+            match result {
+                Ok(Ok(view)) => page.display(&view),
+                Ok(Err(e)) => {
+                    tracing::error!("Failed to scan applications: {}", e);
+                    page.show_toast(&format!("{}: {}", gettext("Failed to scan"), e));
+                }
+                Err(e) => tracing::error!("Application scan task failed: {:?}", e),
+            }
+        });
+    }
+
+    fn display(&self, view: &ApplicationView) {
+        let imp = self.imp();
+
+        if let Some(group) = imp.apps_group.borrow().clone() {
+            for row in imp.app_rows.take() {
+                group.remove(&row);
+            }
+            let mut rows: Vec<gtk4::Widget> = view
+                .applications
+                .iter()
+                .map(|app| Self::create_app_row(app, view.firewall).upcast())
+                .collect();
+            if rows.is_empty() {
+                let row = adw::ActionRow::builder()
+                    .title(gettext("No application is listening"))
+                    .subtitle(gettext("Nothing on this computer accepts connections"))
+                    .build();
+                row.add_prefix(&gtk4::Image::from_icon_name("emblem-ok-symbolic"));
+                rows.push(row.upcast());
+            }
+            let description = (!view.firewall)
+                .then(|| gettext("firewalld is not running, so the rules are not shown"));
+            group.set_description(description.as_deref());
+            for row in &rows {
+                group.add(row);
+            }
+            imp.app_rows.replace(rows);
+        }
+
+        if let Some(group) = imp.unused_group.borrow().clone() {
+            for row in imp.unused_rows.take() {
+                group.remove(&row);
+            }
+            let rows: Vec<gtk4::Widget> = view
+                .unused
+                .iter()
+                .map(|rule| {
+                    let row = adw::ActionRow::builder()
+                        .title(glib::markup_escape_text(&rule.describe()).as_str())
+                        .subtitle(gettext("No program listens on it"))
+                        .build();
+                    row.add_prefix(&gtk4::Image::from_icon_name("dialog-information-symbolic"));
+                    row.upcast()
+                })
+                .collect();
+            for row in &rows {
+                group.add(row);
+            }
+            let has_any = !rows.is_empty();
+            imp.unused_rows.replace(rows);
+            group.set_visible(has_any);
+            if let Some(header) = imp.unused_header.borrow().as_ref() {
+                header.set_visible(has_any);
+            }
+        }
+    }
+
+    /// Create a row for an application, with one child row per port.
+    fn create_app_row(app: &Application, firewall: bool) -> adw::ExpanderRow {
+        let title = if app.is_unknown() {
+            gettext("Unknown Process")
+        } else {
+            app_icons::display_process_name(&app.name)
+        };
+        let row = adw::ExpanderRow::builder()
+            .title(glib::markup_escape_text(&title).as_str())
+            .subtitle(glib::markup_escape_text(&app.summary(firewall)).as_str())
+            .build();
+
+        let first_port = app.listeners.first().map_or(0, |l| l.port);
+        let icon = if app.is_unknown() {
+            "dialog-question-symbolic".to_string()
+        } else {
+            app_icons::icon_for_process(&app.name, first_port)
+        };
+        let image = gtk4::Image::from_icon_name(&icon);
+        image.set_pixel_size(32);
+        row.add_prefix(&image);
+
+        if firewall {
+            let (status_icon, tooltip) = if app.is_allowed() {
+                (
+                    "security-low-symbolic",
+                    gettext("Reachable through the firewall"),
+                )
+            } else {
+                ("security-high-symbolic", gettext("Blocked by the firewall"))
+            };
+            let status = gtk4::Image::from_icon_name(status_icon);
+            status.set_tooltip_text(Some(&tooltip));
+            row.add_suffix(&status);
+        }
+
+        if let Some(executable) = &app.executable {
+            let pids: Vec<String> = app.pids.iter().map(u32::to_string).collect();
+            let exe_row = adw::ActionRow::builder()
+                .title(gettext("Executable"))
+                .subtitle(
+                    glib::markup_escape_text(&format!("{} • pid {}", executable, pids.join(", ")))
+                        .as_str(),
+                )
+                .subtitle_selectable(true)
+                .build();
+            exe_row.add_prefix(&gtk4::Image::from_icon_name(
+                "application-x-executable-symbolic",
+            ));
+            row.add_row(&exe_row);
+        }
+
+        for listener in &app.listeners {
+            row.add_row(&Self::create_listener_row(listener, firewall));
+        }
+        row
+    }
+
+    /// Create a row for one port of an application and the rules for it.
+    fn create_listener_row(listener: &AppListener, firewall: bool) -> adw::ActionRow {
+        let mut title = format!("{}/{}", listener.port, listener.protocol.as_str());
+        if let Some(service) = get_service_name(listener.port) {
+            title.push_str(&format!(" ({})", service));
+        }
+
+        let addresses: Vec<String> = listener.addresses.iter().map(|a| a.to_string()).collect();
+        let rules: Vec<String> = listener.rules.iter().map(|r| r.describe()).collect();
+        let rules = if !firewall {
+            gettext("Firewall not checked")
+        } else if rules.is_empty() {
+            gettext("No rule lets it in")
+        } else {
+            rules.join(", ")
+        };
+        let row = adw::ActionRow::builder()
+            .title(title)
+            .subtitle(
+                glib::markup_escape_text(&format!(
+                    "{} {} • {}",
+                    gettext("On"),
+                    addresses.join(", "),
+                    rules
+                ))
+                .as_str(),
+            )
+            .build();
+
+        let icon = if !firewall {
+            "security-medium-symbolic"
+        } else if listener.allowed_in.is_empty() {
+            "security-high-symbolic"
+        } else {
+            "security-low-symbolic"
+        };
+        row.add_prefix(&gtk4::Image::from_icon_name(icon));
+        row
+    }
+
+    /// Show a toast notification.
+    fn show_toast(&self, message: &str) {
+        if let Some(window) = self
+            .root()
+            .and_then(|root| root.downcast::<gtk4::Window>().ok())
+            .and_then(|window| window.downcast::<super::MainWindow>().ok())
+        {
+            window.show_toast(message);
+        }
+    }
+
+    /// Create a section header with icon on the left.
+    fn create_section_header(icon_name: &str, title: &str) -> gtk4::Box {
+        let header = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(8)
+            .margin_bottom(6)
+            .build();
+
+        let icon = gtk4::Image::builder()
+            .icon_name(icon_name)
+            .css_classes(vec!["heading".to_string()])
+            .build();
+
+        let label = gtk4::Label::builder()
+            .label(title)
+            .css_classes(vec!["heading".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        header.append(&icon);
+        header.append(&label);
+        header
+    }
+}
+
+impl Default for ApplicationsPage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct ApplicationsPage {
+        pub apps_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub app_rows: RefCell<Vec<gtk4::Widget>>,
+        pub unused_header: RefCell<Option<gtk4::Box>>,
+        pub unused_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub unused_rows: RefCell<Vec<gtk4::Widget>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ApplicationsPage {
+        const NAME: &'static str = "SecurityCenterApplicationsPage";
+        type Type = super::ApplicationsPage;
+        type ParentType = gtk4::Box;
+    }
+
+    impl ObjectImpl for ApplicationsPage {}
+    impl WidgetImpl for ApplicationsPage {}
+    impl BoxImpl for ApplicationsPage {}
+}
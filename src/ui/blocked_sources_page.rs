@@ -0,0 +1,927 @@
+// Security Center - Blocked Sources Page
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Addresses and networks whose traffic is dropped or rejected, per zone,
+//! with a form to block more, and the imported IP blocklists.
+
+use std::cell::{Cell, RefCell};
+use std::sync::Arc;
+use std::time::Duration;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use super::app_state::Invalidation;
+use super::density::{self, Role};
+use crate::blocklists::{self, Blocklist, Progress, BLOCK_ZONE, REFRESH_HOURS};
+use crate::i18n::{format_datetime, format_number, gettext};
+use crate::models::{parse_address, BlockedSource, Family, Zone};
+
+/// Labels of [`REFRESH_HOURS`], in the same order.
+const REFRESH_LABELS: [&str; 4] = ["Never", "Every 6 Hours", "Daily", "Weekly"];
+
+glib::wrapper! {
+    /// Blocked sources page listing the blocked addresses of each zone.
+    pub struct BlockedSourcesPage(ObjectSubclass<imp::BlockedSourcesPage>)
+        @extends gtk4::Box, gtk4::Widget,
+        @implements gtk4::Orientable;
+}
+
+impl BlockedSourcesPage {
+    /// Create a new blocked sources page.
+    pub fn new() -> Self {
+        let page: Self = glib::Object::new();
+        page.setup_ui();
+        page
+    }
+
+    /// Setup the UI.
+    fn setup_ui(&self) {
+        let imp = self.imp();
+
+        self.set_orientation(gtk4::Orientation::Vertical);
+        self.set_spacing(0);
+
+        // Header
+        let header_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(12)
+            .build();
+        density::track(&header_box, Role::PageHeader);
+
+        let title_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(4)
+            .hexpand(true)
+            .build();
+
+        let title = gtk4::Label::builder()
+            .label(gettext("Blocked Sources"))
+            .css_classes(vec!["title-1".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        let subtitle = gtk4::Label::builder()
+            .label(gettext(
+                "Addresses and networks whose traffic the firewall stops",
+            ))
+            .css_classes(vec!["dim-label".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        title_box.append(&title);
+        title_box.append(&subtitle);
+        header_box.append(&title_box);
+
+        let add_button = gtk4::Button::builder()
+            .label(gettext("Block Address"))
+            .css_classes(vec!["suggested-action".to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+
+        let page = self.clone();
+        add_button.connect_clicked(move |_| {
+            page.show_block_dialog();
+        });
+        header_box.append(&add_button);
+        self.append(&header_box);
+
+        // Scrolled container
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .vexpand(true)
+            .hexpand(true)
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .build();
+        self.append(&scrolled);
+
+        let content = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .hexpand(true)
+            .build();
+        density::track(
+            &content,
+            Role::Content {
+                margin: 24,
+                spacing: 24,
+            },
+        );
+        scrolled.set_child(Some(&content));
+
+        // Imported lists, above the per-zone blocks
+        let import_button = gtk4::Button::builder()
+            .label(gettext("Import"))
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat"])
+            .tooltip_text(gettext("Import a blocklist from a URL or file"))
+            .build();
+        let page = self.clone();
+        import_button.connect_clicked(move |_| {
+            page.show_import_dialog();
+        });
+        let blocklists_group = adw::PreferencesGroup::builder()
+            .title(gettext("Blocklists"))
+            .description(
+                gettext(
+                    "Published lists of addresses to block, such as FireHOL or Spamhaus \
+                     DROP. Traffic from them is dropped in zone '%s' whatever zone its \
+                     interface is in.",
+                )
+                .replace("%s", BLOCK_ZONE),
+            )
+            .header_suffix(&import_button)
+            .build();
+
+        let progress_bar = gtk4::ProgressBar::builder()
+            .valign(gtk4::Align::Center)
+            .width_request(160)
+            .build();
+        let progress_row = adw::ActionRow::builder().visible(false).build();
+        progress_row.add_suffix(&progress_bar);
+        blocklists_group.add(&progress_row);
+        content.append(&blocklists_group);
+
+        imp.blocklists_group.replace(Some(blocklists_group));
+        imp.progress_row.replace(Some(progress_row));
+        imp.progress_bar.replace(Some(progress_bar));
+
+        let empty = adw::StatusPage::builder()
+            .icon_name("action-unavailable-symbolic")
+            .title(gettext("No Blocked Sources"))
+            .description(gettext(
+                "Block an IPv4 or IPv6 address, or a whole network such as 203.0.113.0/24",
+            ))
+            .vexpand(true)
+            .build();
+        content.append(&empty);
+
+        imp.content.replace(Some(content));
+        imp.empty.replace(Some(empty));
+    }
+
+    /// Show a toast message.
+    fn show_toast(&self, message: &str) {
+        if let Some(window) = self.main_window() {
+            window.show_toast(message);
+        }
+    }
+
+    /// Have the main window refresh what shows zone rules.
+    fn request_refresh(&self) {
+        if let Some(window) = self.main_window() {
+            window.invalidate(Invalidation::ZonesChanged);
+        }
+    }
+
+    fn main_window(&self) -> Option<super::MainWindow> {
+        self.root()
+            .and_then(|root| root.downcast::<gtk4::Window>().ok())
+            .and_then(|window| window.downcast::<super::MainWindow>().ok())
+    }
+
+    /// Update the page with the blocks among the rich rules of `zones`.
+    pub fn set_zones(&self, zones: &[Zone]) {
+        self.refresh_blocklists();
+        let imp = self.imp();
+        imp.zone_names
+            .replace(zones.iter().map(|z| z.name.clone()).collect());
+        if let Some(zone) = zones.iter().find(|z| z.is_default) {
+            imp.default_zone.replace(zone.name.clone());
+        }
+
+        let Some(content) = imp.content.borrow().clone() else {
+            return;
+        };
+        for group in imp.groups.borrow_mut().drain(..) {
+            content.remove(&group);
+        }
+
+        let mut groups = Vec::new();
+        for zone in zones {
+            let sources: Vec<BlockedSource> = zone
+                .rich_rules
+                .iter()
+                .filter_map(|rule| {
+                    let mut source = BlockedSource::from_rich_rule(rule, &zone.name)?;
+                    source.is_permanent = !zone.is_runtime_only_rich_rule(rule);
+                    Some(source)
+                })
+                .collect();
+            if sources.is_empty() {
+                continue;
+            }
+
+            let group = adw::PreferencesGroup::builder()
+                .title(glib::markup_escape_text(&zone.name).as_str())
+                .build();
+            for source in &sources {
+                group.add(&self.create_source_row(source));
+            }
+            content.append(&group);
+            groups.push(group);
+        }
+
+        if let Some(empty) = imp.empty.borrow().as_ref() {
+            empty.set_visible(groups.is_empty());
+        }
+        imp.groups.replace(groups);
+    }
+
+    /// Row for one blocked address or network.
+    fn create_source_row(&self, source: &BlockedSource) -> adw::ActionRow {
+        let kind = match (source.family, source.address.contains('/')) {
+            (Family::Ipv4, false) => gettext("IPv4 address"),
+            (Family::Ipv4, true) => gettext("IPv4 network"),
+            (Family::Ipv6, false) => gettext("IPv6 address"),
+            (Family::Ipv6, true) => gettext("IPv6 network"),
+        };
+        let action = if source.reject {
+            gettext("Rejected")
+        } else {
+            gettext("Dropped silently")
+        };
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(&source.address).as_str())
+            .subtitle(glib::markup_escape_text(&format!("{} • {}", kind, action)).as_str())
+            .title_selectable(true)
+            .build();
+        row.add_prefix(&gtk4::Image::from_icon_name("action-unavailable-symbolic"));
+
+        if !source.is_permanent {
+            let runtime_label = gtk4::Label::builder()
+                .label(gettext("Runtime only"))
+                .css_classes(vec![
+                    "caption".to_string(),
+                    "card".to_string(),
+                    "warning".to_string(),
+                ])
+                .tooltip_text(gettext(
+                    "Lost when firewalld reloads or the system restarts",
+                ))
+                .valign(gtk4::Align::Center)
+                .build();
+            row.add_suffix(&runtime_label);
+        }
+
+        let page = self.clone();
+        let source = source.clone();
+        let delete_button = gtk4::Button::builder()
+            .icon_name("user-trash-symbolic")
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat", "error"])
+            .tooltip_text(gettext("Unblock"))
+            .build();
+        delete_button.connect_clicked(move |button| {
+            page.confirm_unblock(&source, button);
+        });
+        row.add_suffix(&delete_button);
+
+        row
+    }
+
+    /// Present the form to block an address or network.
+    fn show_block_dialog(&self) {
+        let imp = self.imp();
+        let zones = imp.zone_names.borrow().clone();
+        if zones.is_empty() {
+            self.show_toast(&gettext("Not connected to firewalld"));
+            return;
+        }
+        let default_zone = imp.default_zone.borrow().clone();
+
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("Block Address"))
+            .body(gettext(
+                "All traffic from this address or network is stopped in the chosen zone",
+            ))
+            .build();
+
+        let group = adw::PreferencesGroup::new();
+
+        let address_entry = adw::EntryRow::builder()
+            .title(gettext(
+                "Address or network (e.g. 203.0.113.7 or 2001:db8::/32)",
+            ))
+            .build();
+        group.add(&address_entry);
+
+        let zone_row = adw::ComboRow::builder()
+            .title(gettext("Zone"))
+            .model(&gtk4::StringList::new(
+                &zones.iter().map(|z| z.as_str()).collect::<Vec<_>>(),
+            ))
+            .selected(zones.iter().position(|z| *z == default_zone).unwrap_or(0) as u32)
+            .build();
+        group.add(&zone_row);
+
+        let action_row = adw::ComboRow::builder()
+            .title(gettext("Action"))
+            .subtitle(gettext("Rejecting tells the sender it was refused"))
+            .model(&gtk4::StringList::new(&[
+                gettext("Drop silently").as_str(),
+                gettext("Reject").as_str(),
+            ]))
+            .build();
+        group.add(&action_row);
+
+        let permanent_row = adw::SwitchRow::builder()
+            .title(gettext("Make Permanent"))
+            .subtitle(gettext("Rule persists after reboot"))
+            .active(true)
+            .build();
+        group.add(&permanent_row);
+
+        dialog.set_extra_child(Some(&group));
+        dialog.add_response("cancel", "_Cancel");
+        dialog.add_response("block", "_Block");
+        dialog.set_response_appearance("block", adw::ResponseAppearance::Destructive);
+        dialog.set_default_response(Some("cancel"));
+
+        // Gate Block on a valid address so a typo keeps the dialog open
+        let revalidate = {
+            let dialog = dialog.clone();
+            let address_entry = address_entry.clone();
+            move || {
+                let valid = parse_address(&address_entry.text()).is_ok();
+                if valid || address_entry.text().is_empty() {
+                    address_entry.remove_css_class("error");
+                } else {
+                    address_entry.add_css_class("error");
+                }
+                dialog.set_response_enabled("block", valid);
+            }
+        };
+        revalidate();
+        address_entry.connect_changed(move |_| revalidate());
+
+        let page = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "block" {
+                return;
+            }
+            let Some(zone) = zones.get(zone_row.selected() as usize) else {
+                return;
+            };
+            page.block(
+                zone,
+                address_entry.text().as_str(),
+                action_row.selected() == 1,
+                permanent_row.is_active(),
+            );
+        });
+
+        if let Some(window) = self.main_window() {
+            dialog.present(Some(&window));
+        }
+    }
+
+    /// Block `address` in `zone`.
+    fn block(&self, zone: &str, address: &str, reject: bool, permanent: bool) {
+        let zone = zone.to_string();
+        let address = address.trim().to_string();
+        let page = self.clone();
+
+        glib::spawn_future_local(async move {
+            let zone_clone = zone.clone();
+            let address_clone = address.clone();
+            let result = crate::firewall::call(move |client| {
+                if client.connect().is_err() {
+                    return Err(anyhow::anyhow!("Not connected to firewalld"));
+                }
+                client.add_source_block(&zone_clone, &address_clone, reject, permanent)
+            })
+            .await;
+
+            match result {
+                Ok(Ok(outcome)) => {
+                    let message = if outcome.failed() {
+                        gettext("Blocked %s for this session only — saving permanently failed")
+                    } else {
+                        gettext("Blocked %s")
+                    };
+                    page.show_toast(&message.replace("%s", &address));
+                    page.request_refresh();
+                }
+                Ok(Err(e)) => {
+                    page.show_toast(&format!("{}: {}", gettext("Failed to block address"), e));
+                }
+                Err(_) => {
+                    page.show_toast(&gettext("Failed to block address"));
+                }
+            }
+        });
+    }
+
+    /// Ask before unblocking, then remove the block at runtime and
+    /// permanently.
+    fn confirm_unblock(&self, source: &BlockedSource, button: &gtk4::Button) {
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("Unblock %s?").replace("%s", &source.address))
+            .body(
+                gettext("Traffic from it is handled by the rules of zone '%s' again.")
+                    .replace("%s", &source.zone),
+            )
+            .build();
+        dialog.add_response("cancel", "_Cancel");
+        dialog.add_response("unblock", "_Unblock");
+        dialog.set_response_appearance("unblock", adw::ResponseAppearance::Destructive);
+        dialog.set_default_response(Some("cancel"));
+
+        let page = self.clone();
+        let source = source.clone();
+        let button = button.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response == "unblock" {
+                button.set_sensitive(false);
+                page.unblock(&source, &button);
+            }
+        });
+
+        if let Some(window) = self.main_window() {
+            dialog.present(Some(&window));
+        }
+    }
+
+    /// Remove every rule blocking `source` in its zone.
+    fn unblock(&self, source: &BlockedSource, button: &gtk4::Button) {
+        let zone = source.zone.clone();
+        let address = source.address.clone();
+        let page = self.clone();
+        let button = button.clone();
+
+        glib::spawn_future_local(async move {
+            let address_clone = address.clone();
+            let result = crate::firewall::call(move |client| {
+                if client.connect().is_err() {
+                    return Err(anyhow::anyhow!("Not connected to firewalld"));
+                }
+                client.remove_source_block(&zone, &address_clone, true)
+            })
+            .await;
+
+            match result {
+                Ok(Ok(outcome)) => {
+                    let message = if outcome.failed() {
+                        gettext("Unblocked %s for this session only — saving permanently failed")
+                    } else {
+                        gettext("Unblocked %s")
+                    };
+                    page.show_toast(&message.replace("%s", &address));
+                    page.request_refresh();
+                }
+                Ok(Err(e)) => {
+                    button.set_sensitive(true);
+                    page.show_toast(&format!("{}: {}", gettext("Failed to unblock address"), e));
+                }
+                Err(_) => {
+                    button.set_sensitive(true);
+                    page.show_toast(&gettext("Failed to unblock address"));
+                }
+            }
+        });
+    }
+}
+
+impl BlockedSourcesPage {
+    /// Rebuild the Blocklists rows from the saved lists.
+    fn refresh_blocklists(&self) {
+        let imp = self.imp();
+        let Some(group) = imp.blocklists_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.blocklist_rows.take() {
+            group.remove(&row);
+        }
+        let lists = blocklists::load_blocklists();
+        let rows: Vec<gtk4::Widget> = if lists.is_empty() {
+            let row = adw::ActionRow::builder()
+                .title(gettext("No blocklists imported"))
+                .css_classes(["dim-label"])
+                .build();
+            vec![row.upcast()]
+        } else {
+            lists
+                .iter()
+                .map(|list| self.create_blocklist_row(list).upcast())
+                .collect()
+        };
+        for row in &rows {
+            group.add(row);
+        }
+        imp.blocklist_rows.replace(rows);
+    }
+
+    /// Row for an imported list: its state, a refresh button, an on/off
+    /// switch and a delete button.
+    fn create_blocklist_row(&self, list: &Blocklist) -> adw::ActionRow {
+        let mut details =
+            vec![gettext("%d entries").replace("%d", &format_number(list.entries as u64))];
+        if let Some(updated) = list.updated_at {
+            details.push(gettext("updated %s").replace("%s", &format_datetime(updated)));
+        }
+        if let Some(label) = REFRESH_HOURS
+            .iter()
+            .position(|h| *h == list.refresh_hours)
+            .filter(|i| *i > 0)
+            .map(|i| REFRESH_LABELS[i])
+        {
+            details.push(gettext(label).to_lowercase());
+        }
+        let insecure = blocklists::is_insecure_url(&list.source);
+        let mut subtitle = format!("{}\n{}", list.source, details.join(" • "));
+        if insecure {
+            subtitle.push_str(&format!(
+                "\n{}",
+                gettext(
+                    "Plain http:// is not downloaded; import the list again from an https:// URL"
+                )
+            ));
+        } else if let Some(error) = &list.last_error {
+            subtitle.push_str(&format!("\n{}: {}", gettext("Last refresh failed"), error));
+        }
+
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(&list.name).as_str())
+            .subtitle(glib::markup_escape_text(&subtitle).as_str())
+            .subtitle_lines(3)
+            .build();
+        let icon = if insecure || list.last_error.is_some() {
+            let icon = gtk4::Image::from_icon_name("dialog-warning-symbolic");
+            icon.add_css_class("warning");
+            icon
+        } else {
+            gtk4::Image::from_icon_name("view-list-symbolic")
+        };
+        row.add_prefix(&icon);
+
+        let refresh_button = gtk4::Button::builder()
+            .icon_name("view-refresh-symbolic")
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat"])
+            .tooltip_text(gettext("Refresh Now"))
+            .build();
+        let page = self.clone();
+        let id = list.id;
+        refresh_button.connect_clicked(move |_| {
+            page.refresh_blocklist(id);
+        });
+        row.add_suffix(&refresh_button);
+
+        let switch = gtk4::Switch::builder()
+            .active(list.enabled)
+            .valign(gtk4::Align::Center)
+            .tooltip_text(gettext("Block the addresses of this list"))
+            .build();
+        let page = self.clone();
+        switch.connect_state_set(move |switch, enabled| {
+            switch.set_sensitive(false);
+            page.set_blocklist_enabled(id, enabled, switch);
+            glib::Propagation::Stop
+        });
+        row.add_suffix(&switch);
+
+        let delete_button = gtk4::Button::builder()
+            .icon_name("user-trash-symbolic")
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat", "error"])
+            .tooltip_text(gettext("Remove Blocklist"))
+            .build();
+        let page = self.clone();
+        let name = list.name.clone();
+        delete_button.connect_clicked(move |_| {
+            page.confirm_remove_blocklist(id, &name);
+        });
+        row.add_suffix(&delete_button);
+
+        row
+    }
+
+    /// Run `task` in the background, showing its progress in the Blocklists
+    /// group, then reload the lists and report the outcome with `done`.
+    fn run_blocklist_task<T, F, D>(&self, task: F, done: D)
+    where
+        T: Send + 'static,
+        F: FnOnce(&Progress) -> anyhow::Result<T> + Send + 'static,
+        D: FnOnce(&Self, anyhow::Result<T>) + 'static,
+    {
+        let imp = self.imp();
+        if imp.task_running.replace(true) {
+            self.show_toast(&gettext("A blocklist is already being loaded"));
+            return;
+        }
+        let progress = Arc::new(Progress::default());
+        if let Some(row) = imp.progress_row.borrow().as_ref() {
+            row.set_title(&gettext("Starting"));
+            row.set_visible(true);
+        }
+
+        // Poll the shared progress until the task clears the flag
+        let page = self.downgrade();
+        let shown = progress.clone();
+        glib::timeout_add_local(Duration::from_millis(100), move || {
+            let Some(page) = page.upgrade() else {
+                return glib::ControlFlow::Break;
+            };
+            let imp = page.imp();
+            if !imp.task_running.get() {
+                return glib::ControlFlow::Break;
+            }
+            let (step, fraction) = shown.get();
+            if let Some(row) = imp.progress_row.borrow().as_ref() {
+                if !step.is_empty() {
+                    row.set_title(&gettext(step));
+                }
+            }
+            if let Some(bar) = imp.progress_bar.borrow().as_ref() {
+                match fraction {
+                    Some(fraction) => bar.set_fraction(fraction),
+                    None => bar.pulse(),
+                }
+            }
+            glib::ControlFlow::Continue
+        });
+
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(move || task(&progress))
+                .await
+                .unwrap_or_else(|_| Err(anyhow::anyhow!("The task failed")));
+            let imp = page.imp();
+            imp.task_running.set(false);
+            if let Some(row) = imp.progress_row.borrow().as_ref() {
+                row.set_visible(false);
+            }
+            page.refresh_blocklists();
+            done(&page, result);
+        });
+    }
+
+    /// Refresh the lists whose interval has passed, in the background.
+    pub fn refresh_blocklists_if_due(&self) {
+        let now = chrono::Utc::now().timestamp();
+        if !blocklists::load_blocklists()
+            .iter()
+            .any(|list| list.refresh_due(now))
+        {
+            return;
+        }
+        self.run_blocklist_task(
+            |_| Ok(blocklists::refresh_due_blocklists()),
+            |page, result| {
+                let Ok(results) = result else {
+                    return;
+                };
+                for (name, result) in results {
+                    if let Err(e) = result {
+                        page.show_toast(&format!(
+                            "{} {}: {}",
+                            gettext("Failed to refresh blocklist"),
+                            name,
+                            e
+                        ));
+                    }
+                }
+            },
+        );
+    }
+
+    /// Download or read a list again now.
+    fn refresh_blocklist(&self, id: u32) {
+        self.run_blocklist_task(
+            move |progress| blocklists::refresh_blocklist(id, progress),
+            |page, result| match result {
+                Ok(list) => page.show_toast(
+                    &gettext("Loaded %d entries into %s")
+                        .replace("%d", &list.entries.to_string())
+                        .replace("%s", &list.name),
+                ),
+                Err(e) => page.show_toast(&format!(
+                    "{}: {}",
+                    gettext("Failed to refresh the blocklist"),
+                    e
+                )),
+            },
+        );
+    }
+
+    /// Bind or unbind a list's ipsets, putting the switch back on failure.
+    fn set_blocklist_enabled(&self, id: u32, enabled: bool, switch: &gtk4::Switch) {
+        let page = self.clone();
+        let switch = switch.clone();
+        glib::spawn_future_local(async move {
+            let result =
+                gtk4::gio::spawn_blocking(move || blocklists::set_blocklist_enabled(id, enabled))
+                    .await;
+            switch.set_sensitive(true);
+            match result {
+                Ok(Ok(())) => switch.set_state(enabled),
+                Ok(Err(e)) => {
+                    switch.set_active(!enabled);
+                    page.show_toast(&format!(
+                        "{}: {}",
+                        gettext("Failed to change the blocklist"),
+                        e
+                    ));
+                }
+                Err(_) => {
+                    switch.set_active(!enabled);
+                    page.show_toast(&gettext("Failed to change the blocklist"));
+                }
+            }
+        });
+    }
+
+    /// Ask before removing a list and its ipsets.
+    fn confirm_remove_blocklist(&self, id: u32, name: &str) {
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("Remove %s?").replace("%s", name))
+            .body(gettext(
+                "Its addresses are no longer blocked and its ipsets are deleted.",
+            ))
+            .build();
+        dialog.add_response("cancel", "_Cancel");
+        dialog.add_response("remove", "_Remove");
+        dialog.set_response_appearance("remove", adw::ResponseAppearance::Destructive);
+        dialog.set_default_response(Some("cancel"));
+
+        let page = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "remove" {
+                return;
+            }
+            page.run_blocklist_task(
+                move |_| blocklists::remove_blocklist(id),
+                |page, result| {
+                    if let Err(e) = result {
+                        page.show_toast(&format!(
+                            "{}: {}",
+                            gettext("Failed to remove the blocklist"),
+                            e
+                        ));
+                    }
+                    page.request_refresh();
+                },
+            );
+        });
+
+        if let Some(window) = self.main_window() {
+            dialog.present(Some(&window));
+        }
+    }
+
+    /// Present the form to import a list from a URL or file.
+    fn show_import_dialog(&self) {
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("Import Blocklist"))
+            .body(gettext(
+                "A text list with one address or network per line. Private and local \
+                 addresses in it are skipped.",
+            ))
+            .build();
+
+        let group = adw::PreferencesGroup::new();
+        let name_entry = adw::EntryRow::builder()
+            .title(gettext("Name (optional)"))
+            .build();
+        group.add(&name_entry);
+
+        let source_entry = adw::EntryRow::builder()
+            .title(gettext("URL or file"))
+            .build();
+        let choose_button = gtk4::Button::builder()
+            .icon_name("document-open-symbolic")
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat"])
+            .tooltip_text(gettext("Choose File"))
+            .build();
+        let page = self.clone();
+        let entry = source_entry.clone();
+        choose_button.connect_clicked(move |_| {
+            let Some(window) = page.main_window() else {
+                return;
+            };
+            let file_dialog = gtk4::FileDialog::builder()
+                .title(gettext("Choose Blocklist"))
+                .modal(true)
+                .build();
+            let entry = entry.clone();
+            file_dialog.open(Some(&window), gtk4::gio::Cancellable::NONE, move |result| {
+                if let Some(path) = result.ok().and_then(|file| file.path()) {
+                    entry.set_text(&path.display().to_string());
+                }
+            });
+        });
+        source_entry.add_suffix(&choose_button);
+        group.add(&source_entry);
+
+        let labels: Vec<String> = REFRESH_LABELS.iter().map(|l| gettext(l)).collect();
+        let refresh_row = adw::ComboRow::builder()
+            .title(gettext("Refresh"))
+            .subtitle(gettext("Download or read the list again"))
+            .model(&gtk4::StringList::new(
+                &labels.iter().map(String::as_str).collect::<Vec<_>>(),
+            ))
+            .selected(2)
+            .build();
+        group.add(&refresh_row);
+
+        dialog.set_extra_child(Some(&group));
+        dialog.add_response("cancel", "_Cancel");
+        dialog.add_response("import", &gettext("_Import"));
+        dialog.set_response_appearance("import", adw::ResponseAppearance::Suggested);
+        dialog.set_response_enabled("import", false);
+        let dialog_clone = dialog.clone();
+        source_entry.connect_changed(move |entry| {
+            let text = entry.text();
+            let insecure = blocklists::is_insecure_url(text.trim());
+            if insecure {
+                entry.add_css_class("error");
+                entry.set_tooltip_text(Some(&gettext(
+                    "Plain http:// lists can be altered in transit; use an https:// URL",
+                )));
+            } else {
+                entry.remove_css_class("error");
+                entry.set_tooltip_text(None);
+            }
+            dialog_clone.set_response_enabled("import", !text.trim().is_empty() && !insecure);
+        });
+
+        let page = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "import" {
+                return;
+            }
+            let name = name_entry.text().to_string();
+            let source = source_entry.text().to_string();
+            let refresh_hours = REFRESH_HOURS
+                .get(refresh_row.selected() as usize)
+                .copied()
+                .unwrap_or(0);
+            page.run_blocklist_task(
+                move |progress| {
+                    blocklists::import_blocklist(&name, &source, refresh_hours, progress)
+                },
+                |page, result| match result {
+                    Ok(list) => {
+                        page.show_toast(
+                            &gettext("Imported %s with %d entries")
+                                .replace("%s", &list.name)
+                                .replace("%d", &list.entries.to_string()),
+                        );
+                        page.request_refresh();
+                    }
+                    Err(e) => page.show_toast(&format!(
+                        "{}: {}",
+                        gettext("Failed to import the blocklist"),
+                        e
+                    )),
+                },
+            );
+        });
+
+        if let Some(window) = self.main_window() {
+            dialog.present(Some(&window));
+        }
+    }
+}
+
+impl Default for BlockedSourcesPage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct BlockedSourcesPage {
+        pub content: RefCell<Option<gtk4::Box>>,
+        pub empty: RefCell<Option<adw::StatusPage>>,
+        /// One group per zone that blocks sources, replaced on every refresh.
+        pub groups: RefCell<Vec<adw::PreferencesGroup>>,
+        pub zone_names: RefCell<Vec<String>>,
+        pub default_zone: RefCell<String>,
+        pub blocklists_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub blocklist_rows: RefCell<Vec<gtk4::Widget>>,
+        /// Shows the step of a running import or refresh.
+        pub progress_row: RefCell<Option<adw::ActionRow>>,
+        pub progress_bar: RefCell<Option<gtk4::ProgressBar>>,
+        /// One import, refresh or removal runs at a time.
+        pub task_running: Cell<bool>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for BlockedSourcesPage {
+        const NAME: &'static str = "SecurityCenterBlockedSourcesPage";
+        type Type = super::BlockedSourcesPage;
+        type ParentType = gtk4::Box;
+    }
+
+    impl ObjectImpl for BlockedSourcesPage {}
+    impl WidgetImpl for BlockedSourcesPage {}
+    impl BoxImpl for BlockedSourcesPage {}
+}
@@ -0,0 +1,483 @@
+// Security Center - Cleanup Page
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Cleanup page for finding and securely removing sensitive leftovers
+//! (shell history, removable-media thumbnails, core dumps).
+
+use std::cell::RefCell;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use super::density::{self, Role};
+use crate::admin::{
+    clean_leftovers, cleanup_due, history_sizes, scan_leftovers, CleanupCategory, CleanupItem,
+    CleanupReport,
+};
+use crate::config::Settings;
+use crate::i18n::{format_bytes, format_datetime, gettext};
+
+/// Rows shown per category before the list is summarised.
+const MAX_ROWS_PER_CATEGORY: usize = 25;
+
+/// Schedule choices offered in the UI: (label, interval in days).
+const SCHEDULES: [(&str, u32); 4] = [("Never", 0), ("Daily", 1), ("Weekly", 7), ("Monthly", 30)];
+
+glib::wrapper! {
+    /// Sensitive leftovers cleanup page.
+    pub struct CleanupPage(ObjectSubclass<imp::CleanupPage>)
+        @extends gtk4::Box, gtk4::Widget,
+        @implements gtk4::Orientable;
+}
+
+impl CleanupPage {
+    /// Create a new cleanup page.
+    pub fn new() -> Self {
+        let page: Self = glib::Object::new();
+        page.setup_ui();
+        page
+    }
+
+    /// Setup the UI.
+    fn setup_ui(&self) {
+        let imp = self.imp();
+        let settings = Settings::new();
+
+        self.set_orientation(gtk4::Orientation::Vertical);
+        self.set_spacing(0);
+
+        // Header with scan and clean buttons
+        let header_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(12)
+            .build();
+        density::track(&header_box, Role::PageHeader);
+
+        let title_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(4)
+            .hexpand(true)
+            .build();
+
+        let title = gtk4::Label::builder()
+            .label(gettext("Cleanup"))
+            .css_classes(vec!["title-1".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        let subtitle = gtk4::Label::builder()
+            .label(gettext("Find and remove sensitive leftovers"))
+            .css_classes(vec!["dim-label".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        title_box.append(&title);
+        title_box.append(&subtitle);
+
+        let scan_button = gtk4::Button::builder()
+            .icon_name("view-refresh-symbolic")
+            .css_classes(vec!["flat".to_string()])
+            .tooltip_text(gettext("Scan for leftovers"))
+            .valign(gtk4::Align::Center)
+            .build();
+        let page = self.clone();
+        scan_button.connect_clicked(move |_| {
+            page.refresh();
+        });
+
+        let clean_button = gtk4::Button::builder()
+            .label(gettext("Clean Up"))
+            .css_classes(vec!["destructive-action".to_string()])
+            .valign(gtk4::Align::Center)
+            .sensitive(false)
+            .build();
+        let page = self.clone();
+        clean_button.connect_clicked(move |_| {
+            page.confirm_clean();
+        });
+        imp.clean_button.replace(Some(clean_button.clone()));
+
+        header_box.append(&title_box);
+        header_box.append(&scan_button);
+        header_box.append(&clean_button);
+        self.append(&header_box);
+
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .vscrollbar_policy(gtk4::PolicyType::Automatic)
+            .vexpand(true)
+            .hexpand(true)
+            .build();
+
+        let content = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .hexpand(true)
+            .build();
+        density::track(
+            &content,
+            Role::Content {
+                margin: 24,
+                spacing: 24,
+            },
+        );
+
+        // Category toggles
+        content.append(&Self::create_section_header(
+            "edit-clear-all-symbolic",
+            &gettext("Categories"),
+        ));
+        let categories_group = adw::PreferencesGroup::builder()
+            .description(gettext("Choose which kinds of leftovers to look for"))
+            .build();
+        for category in CleanupCategory::ALL {
+            let row = adw::SwitchRow::builder()
+                .title(gettext(category.title()))
+                .subtitle(gettext(category.description()))
+                .active(settings.cleanup_category_enabled(category.id()))
+                .build();
+            row.add_prefix(&gtk4::Image::from_icon_name(category.icon()));
+            let page = self.clone();
+            row.connect_active_notify(move |row| {
+                let mut settings = Settings::new();
+                settings.set_cleanup_category_enabled(category.id(), row.is_active());
+                page.refresh();
+            });
+            categories_group.add(&row);
+        }
+        content.append(&categories_group);
+
+        // Options
+        content.append(&Self::create_section_header(
+            "preferences-system-symbolic",
+            &gettext("Options"),
+        ));
+        let options_group = adw::PreferencesGroup::new();
+
+        let secure_row = adw::SwitchRow::builder()
+            .title(gettext("Secure Delete"))
+            .subtitle(gettext(
+                "Overwrite files before removing them (less effective on SSDs)",
+            ))
+            .active(settings.cleanup_secure_delete())
+            .build();
+        secure_row.connect_active_notify(|row| {
+            let mut settings = Settings::new();
+            settings.set_cleanup_secure_delete(row.is_active());
+        });
+        options_group.add(&secure_row);
+
+        let schedule_labels: Vec<String> = SCHEDULES.iter().map(|(l, _)| gettext(l)).collect();
+        let schedule_refs: Vec<&str> = schedule_labels.iter().map(|s| s.as_str()).collect();
+        let schedule_row = adw::ComboRow::builder()
+            .title(gettext("Automatic Cleanup"))
+            .model(&gtk4::StringList::new(&schedule_refs))
+            .build();
+        let current = settings.cleanup_interval_days();
+        let selected = SCHEDULES
+            .iter()
+            .position(|(_, days)| *days == current)
+            .unwrap_or(0);
+        schedule_row.set_selected(selected as u32);
+        schedule_row.set_subtitle(&last_run_text(settings.cleanup_last_run()));
+        schedule_row.connect_selected_notify(|row| {
+            let days = SCHEDULES
+                .get(row.selected() as usize)
+                .map(|(_, d)| *d)
+                .unwrap_or(0);
+            let mut settings = Settings::new();
+            settings.set_cleanup_interval_days(days);
+        });
+        imp.schedule_row.replace(Some(schedule_row.clone()));
+        options_group.add(&schedule_row);
+        content.append(&options_group);
+
+        // Findings
+        content.append(&Self::create_section_header(
+            "system-search-symbolic",
+            &gettext("Findings"),
+        ));
+        let findings_group = adw::PreferencesGroup::builder()
+            .description(gettext("Scanning…"))
+            .build();
+        imp.findings_group.replace(Some(findings_group.clone()));
+        content.append(&findings_group);
+
+        scrolled.set_child(Some(&content));
+        self.append(&scrolled);
+    }
+
+    /// Scan the enabled categories and show the findings.
+    pub fn refresh(&self) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let items = gtk4::gio::spawn_blocking(|| {
+                let mut settings = Settings::new();
+                let items = scan_leftovers(&enabled_categories(), settings.cleanup_history_sizes());
+                settings.set_cleanup_history_sizes(history_sizes());
+                items
+            })
+            .await;
+            if let Ok(items) = items {
+                page.display_items(items);
+            }
+        });
+    }
+
+    /// Run a cleanup in the background when the configured schedule is due.
+    pub fn run_scheduled_if_due(&self) {
+        let settings = Settings::new();
+        let now = chrono::Utc::now().timestamp();
+        if !cleanup_due(
+            settings.cleanup_last_run(),
+            settings.cleanup_interval_days(),
+            now,
+        ) {
+            return;
+        }
+
+        let secure = settings.cleanup_secure_delete();
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let report = gtk4::gio::spawn_blocking(move || {
+                let items = scan_leftovers(&enabled_categories(), &Default::default());
+                // Scheduled: core dumps wait for a manual run if removing
+                // them needs a password
+                clean_leftovers(&items, secure, false)
+            })
+            .await;
+            if let Ok(report) = report {
+                page.finish_clean(&report);
+            }
+        });
+    }
+
+    /// Replace the findings list with freshly scanned items.
+    fn display_items(&self, items: Vec<CleanupItem>) {
+        let imp = self.imp();
+
+        let old_rows = imp.current_rows.take();
+        if let Some(group) = imp.findings_group.borrow().as_ref() {
+            for row in old_rows {
+                group.remove(&row);
+            }
+
+            let total: u64 = items.iter().map(|i| i.size).sum();
+            if items.is_empty() {
+                group.set_description(Some(&gettext("No leftovers found")));
+            } else {
+                group.set_description(Some(
+                    &gettext("%d files, %s in total")
+                        .replace("%d", &items.len().to_string())
+                        .replace("%s", &format_bytes(total)),
+                ));
+            }
+
+            let mut new_rows = Vec::new();
+            for category in CleanupCategory::ALL {
+                let matching: Vec<&CleanupItem> =
+                    items.iter().filter(|i| i.category == category).collect();
+                for item in matching.iter().take(MAX_ROWS_PER_CATEGORY) {
+                    let row = Self::create_item_row(item);
+                    group.add(&row);
+                    new_rows.push(row);
+                }
+                if matching.len() > MAX_ROWS_PER_CATEGORY {
+                    let more =
+                        adw::ActionRow::builder()
+                            .title(gettext("and %d more").replace(
+                                "%d",
+                                &(matching.len() - MAX_ROWS_PER_CATEGORY).to_string(),
+                            ))
+                            .css_classes(vec!["dim-label".to_string()])
+                            .build();
+                    group.add(&more);
+                    new_rows.push(more);
+                }
+            }
+            imp.current_rows.replace(new_rows);
+        }
+
+        if let Some(button) = imp.clean_button.borrow().as_ref() {
+            button.set_sensitive(!items.is_empty());
+        }
+        imp.items.replace(items);
+    }
+
+    /// Create a row for a single leftover.
+    fn create_item_row(item: &CleanupItem) -> adw::ActionRow {
+        let title = item
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| item.path.display().to_string());
+        let mut subtitle = vec![format_bytes(item.size)];
+        if let Some(growth) = item.growth {
+            subtitle.push(
+                gettext("grew by %s since the last scan").replace("%s", &format_bytes(growth)),
+            );
+        }
+        if !item.detail.is_empty() {
+            subtitle.push(item.detail.clone());
+        }
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(&title).as_str())
+            .subtitle(glib::markup_escape_text(&subtitle.join(" • ")).as_str())
+            .tooltip_text(item.path.display().to_string())
+            .build();
+        row.add_prefix(&gtk4::Image::from_icon_name(item.category.icon()));
+        row
+    }
+
+    /// Ask for confirmation, then remove everything currently listed.
+    fn confirm_clean(&self) {
+        let count = self.imp().items.borrow().len();
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("Remove leftovers?"))
+            .body(
+                gettext("%d files will be permanently removed. This cannot be undone.")
+                    .replace("%d", &count.to_string()),
+            )
+            .build();
+        dialog.add_responses(&[
+            ("cancel", gettext("Cancel").as_str()),
+            ("clean", gettext("Clean Up").as_str()),
+        ]);
+        dialog.set_response_appearance("clean", adw::ResponseAppearance::Destructive);
+        dialog.set_default_response(Some("cancel"));
+        dialog.set_close_response("cancel");
+
+        let page = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response == "clean" {
+                page.clean();
+            }
+        });
+
+        if let Some(root) = self.root() {
+            if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                dialog.present(Some(window));
+            }
+        }
+    }
+
+    /// Remove the listed items in the background.
+    fn clean(&self) {
+        let imp = self.imp();
+        let items = imp.items.borrow().clone();
+        let secure = Settings::new().cleanup_secure_delete();
+        if let Some(button) = imp.clean_button.borrow().as_ref() {
+            button.set_sensitive(false);
+        }
+
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let report =
+                gtk4::gio::spawn_blocking(move || clean_leftovers(&items, secure, true)).await;
+            match report {
+                Ok(report) => page.finish_clean(&report),
+                Err(_) => page.show_toast(&gettext("Cleanup failed")),
+            }
+        });
+    }
+
+    /// Record the run, report the outcome and rescan.
+    fn finish_clean(&self, report: &CleanupReport) {
+        let now = chrono::Utc::now().timestamp();
+        let mut settings = Settings::new();
+        settings.set_cleanup_last_run(now);
+        if let Some(row) = self.imp().schedule_row.borrow().as_ref() {
+            row.set_subtitle(&last_run_text(Some(now)));
+        }
+        for (path, reason) in &report.failed {
+            tracing::warn!("Cleanup: could not remove {}: {}", path.display(), reason);
+        }
+        self.show_toast(&report.summary());
+        self.refresh();
+    }
+
+    /// Show a toast on the main window.
+    fn show_toast(&self, message: &str) {
+        if let Some(root) = self.root() {
+            if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                if let Some(main_window) = window.downcast_ref::<super::MainWindow>() {
+                    main_window.show_toast(message);
+                }
+            }
+        }
+    }
+
+    /// Create a section header with icon on the left.
+    fn create_section_header(icon_name: &str, title: &str) -> gtk4::Box {
+        let header = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(8)
+            .margin_bottom(6)
+            .build();
+
+        let icon = gtk4::Image::builder()
+            .icon_name(icon_name)
+            .css_classes(vec!["heading".to_string()])
+            .build();
+
+        let label = gtk4::Label::builder()
+            .label(title)
+            .css_classes(vec!["heading".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        header.append(&icon);
+        header.append(&label);
+        header
+    }
+}
+
+impl Default for CleanupPage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Categories currently enabled in settings.
+fn enabled_categories() -> Vec<CleanupCategory> {
+    let settings = Settings::new();
+    CleanupCategory::ALL
+        .into_iter()
+        .filter(|c| settings.cleanup_category_enabled(c.id()))
+        .collect()
+}
+
+/// Subtitle describing when the last cleanup ran.
+fn last_run_text(last_run: Option<i64>) -> String {
+    match last_run {
+        Some(ts) => gettext("Last run %s").replace("%s", &format_datetime(ts)),
+        None => gettext("Never run"),
+    }
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct CleanupPage {
+        pub clean_button: RefCell<Option<gtk4::Button>>,
+        pub schedule_row: RefCell<Option<adw::ComboRow>>,
+        pub findings_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub current_rows: RefCell<Vec<adw::ActionRow>>,
+        pub items: RefCell<Vec<CleanupItem>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for CleanupPage {
+        const NAME: &'static str = "SecurityCenterCleanupPage";
+        type Type = super::CleanupPage;
+        type ParentType = gtk4::Box;
+    }
+
+    impl ObjectImpl for CleanupPage {}
+    impl WidgetImpl for CleanupPage {}
+    impl BoxImpl for CleanupPage {}
+}
@@ -0,0 +1,610 @@
+// Security Center - Compliance Page
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Compliance page: compares the system with the desired-state policy file
+//! (see `crate::admin::DesiredState`), lists every drift and corrects them
+//! on demand or on a schedule.
+
+use std::cell::{Cell, RefCell};
+
+use gtk4::gio;
+use gtk4::glib;
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use super::density::{self, Role};
+use crate::admin::{
+    check_policy, create_policy_file, enforce, enforcement_due, policy_from_system, policy_path,
+    DesiredState, Drift, EnforceReport,
+};
+use crate::config::Settings;
+use crate::i18n::{format_datetime, gettext};
+
+/// Schedule choices offered in the UI: (label, interval in hours).
+const SCHEDULES: [(&str, u32); 4] = [
+    ("Never", 0),
+    ("Every Hour", 1),
+    ("Every 6 Hours", 6),
+    ("Daily", 24),
+];
+
+/// Result of checking the policy: `None` when there is no policy file.
+type PolicyCheck = anyhow::Result<Option<(DesiredState, Vec<Drift>)>>;
+
+glib::wrapper! {
+    /// Desired-state compliance page.
+    pub struct CompliancePage(ObjectSubclass<imp::CompliancePage>)
+        @extends gtk4::Box, gtk4::Widget,
+        @implements gtk4::Orientable;
+}
+
+impl CompliancePage {
+    /// Create a new compliance page.
+    pub fn new() -> Self {
+        let page: Self = glib::Object::new();
+        page.setup_ui();
+        page
+    }
+
+    /// Setup the UI.
+    fn setup_ui(&self) {
+        let imp = self.imp();
+        let settings = Settings::new();
+
+        self.set_orientation(gtk4::Orientation::Vertical);
+        self.set_spacing(0);
+
+        // Header with check and enforce buttons
+        let header_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(12)
+            .build();
+        density::track(&header_box, Role::PageHeader);
+
+        let title_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(4)
+            .hexpand(true)
+            .build();
+
+        let title = gtk4::Label::builder()
+            .label(gettext("Compliance"))
+            .css_classes(vec!["title-1".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        let subtitle = gtk4::Label::builder()
+            .label(gettext("Compare the system with your desired-state policy"))
+            .css_classes(vec!["dim-label".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        title_box.append(&title);
+        title_box.append(&subtitle);
+
+        let check_button = gtk4::Button::builder()
+            .icon_name("view-refresh-symbolic")
+            .css_classes(vec!["flat".to_string()])
+            .tooltip_text(gettext("Check for drift"))
+            .valign(gtk4::Align::Center)
+            .build();
+        let page = self.clone();
+        check_button.connect_clicked(move |_| {
+            page.refresh();
+        });
+
+        let enforce_button = gtk4::Button::builder()
+            .label(gettext("Enforce"))
+            .css_classes(vec!["suggested-action".to_string()])
+            .valign(gtk4::Align::Center)
+            .sensitive(false)
+            .build();
+        let page = self.clone();
+        enforce_button.connect_clicked(move |_| {
+            page.confirm_enforce();
+        });
+        imp.enforce_button.replace(Some(enforce_button.clone()));
+
+        header_box.append(&title_box);
+        header_box.append(&check_button);
+        header_box.append(&enforce_button);
+        self.append(&header_box);
+
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .vscrollbar_policy(gtk4::PolicyType::Automatic)
+            .vexpand(true)
+            .hexpand(true)
+            .build();
+
+        let content = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .hexpand(true)
+            .build();
+        density::track(
+            &content,
+            Role::Content {
+                margin: 24,
+                spacing: 24,
+            },
+        );
+
+        // Policy file and schedule
+        content.append(&Self::create_section_header(
+            "text-x-generic-symbolic",
+            &gettext("Policy"),
+        ));
+        let policy_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "Default zone, allowed services and ports per zone, required running services and sysctl values, declared in a TOML file",
+            ))
+            .build();
+
+        let policy_row = adw::ActionRow::builder()
+            .title(gettext("Policy File"))
+            .subtitle(glib::markup_escape_text(&policy_path().display().to_string()).as_str())
+            .build();
+        let create_button = gtk4::Button::builder()
+            .label(gettext("Create"))
+            .tooltip_text(gettext(
+                "Write a policy describing the current firewall configuration",
+            ))
+            .valign(gtk4::Align::Center)
+            .visible(false)
+            .build();
+        let page = self.clone();
+        create_button.connect_clicked(move |btn| {
+            btn.set_sensitive(false);
+            page.create_policy(btn.clone());
+        });
+        let open_button = gtk4::Button::builder()
+            .icon_name("document-edit-symbolic")
+            .tooltip_text(gettext("Open in the default editor"))
+            .css_classes(vec!["flat".to_string()])
+            .valign(gtk4::Align::Center)
+            .visible(false)
+            .build();
+        let page = self.clone();
+        open_button.connect_clicked(move |_| {
+            page.open_policy();
+        });
+        policy_row.add_suffix(&create_button);
+        policy_row.add_suffix(&open_button);
+        policy_group.add(&policy_row);
+        imp.create_button.replace(Some(create_button));
+        imp.open_button.replace(Some(open_button));
+
+        let schedule_labels: Vec<String> = SCHEDULES.iter().map(|(l, _)| gettext(l)).collect();
+        let schedule_refs: Vec<&str> = schedule_labels.iter().map(|s| s.as_str()).collect();
+        let schedule_row = adw::ComboRow::builder()
+            .title(gettext("Automatic Enforcement"))
+            .model(&gtk4::StringList::new(&schedule_refs))
+            .build();
+        let current = settings.policy_interval_hours();
+        let selected = SCHEDULES
+            .iter()
+            .position(|(_, hours)| *hours == current)
+            .unwrap_or(0);
+        schedule_row.set_selected(selected as u32);
+        schedule_row.set_subtitle(&last_enforced_text(settings.policy_last_enforced()));
+        schedule_row.connect_selected_notify(|row| {
+            let hours = SCHEDULES
+                .get(row.selected() as usize)
+                .map(|(_, h)| *h)
+                .unwrap_or(0);
+            let mut settings = Settings::new();
+            settings.set_policy_interval_hours(hours);
+        });
+        policy_group.add(&schedule_row);
+        imp.schedule_row.replace(Some(schedule_row));
+        content.append(&policy_group);
+
+        // Drift
+        content.append(&Self::create_section_header(
+            "dialog-warning-symbolic",
+            &gettext("Drift"),
+        ));
+        let drift_group = adw::PreferencesGroup::builder()
+            .description(gettext("Checking…"))
+            .build();
+        imp.drift_group.replace(Some(drift_group.clone()));
+        content.append(&drift_group);
+
+        scrolled.set_child(Some(&content));
+        self.append(&scrolled);
+    }
+
+    /// Read the policy and compare it with the system.
+    pub fn refresh(&self) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            if let Ok(check) = gtk4::gio::spawn_blocking(check_policy).await {
+                page.display_check(check);
+            }
+        });
+    }
+
+    /// Enforce the policy in the background when the configured schedule is
+    /// due.
+    pub fn enforce_scheduled_if_due(&self) {
+        let settings = Settings::new();
+        let now = chrono::Utc::now().timestamp();
+        if self.imp().enforcing.get()
+            || !enforcement_due(
+                settings.policy_last_enforced(),
+                settings.policy_interval_hours(),
+                now,
+            )
+        {
+            return;
+        }
+
+        self.imp().enforcing.set(true);
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let report = gtk4::gio::spawn_blocking(|| {
+                let (state, drifts) = check_policy().ok().flatten()?;
+                let fixable: Vec<Drift> = drifts.into_iter().filter(Drift::is_fixable).collect();
+                Some(enforce(&state, &fixable))
+            })
+            .await;
+            page.imp().enforcing.set(false);
+            match report {
+                Ok(Some(report)) => page.finish_enforce(&report, false),
+                // No policy: nothing to enforce, try again next interval
+                Ok(None) => page.record_enforced(),
+                Err(_) => {}
+            }
+        });
+    }
+
+    /// Show the drift list, or why it cannot be computed.
+    fn display_check(&self, check: PolicyCheck) {
+        let imp = self.imp();
+        let has_policy = !matches!(check, Ok(None));
+        if let Some(button) = imp.create_button.borrow().as_ref() {
+            button.set_visible(!has_policy);
+            button.set_sensitive(true);
+        }
+        if let Some(button) = imp.open_button.borrow().as_ref() {
+            button.set_visible(has_policy);
+        }
+
+        let old_rows = imp.drift_rows.take();
+        let Some(group) = imp.drift_group.borrow().clone() else {
+            return;
+        };
+        for row in old_rows {
+            group.remove(&row);
+        }
+
+        let drifts = match check {
+            Ok(Some((state, drifts))) => {
+                if drifts.is_empty() {
+                    group.set_description(Some(
+                        &gettext("The system matches all %d requirements of the policy")
+                            .replace("%d", &state.rule_count().to_string()),
+                    ));
+                } else {
+                    group.set_description(Some(
+                        &gettext("%d differences from the policy")
+                            .replace("%d", &drifts.len().to_string()),
+                    ));
+                }
+                drifts
+            }
+            Ok(None) => {
+                group.set_description(Some(&gettext(
+                    "No policy file yet: create one from the current configuration, then edit it",
+                )));
+                Vec::new()
+            }
+            Err(e) => {
+                group.set_description(Some(&format!(
+                    "{}: {:#}",
+                    gettext("Cannot check the policy"),
+                    e
+                )));
+                Vec::new()
+            }
+        };
+
+        let mut new_rows = Vec::new();
+        for drift in &drifts {
+            let row = Self::create_drift_row(drift);
+            group.add(&row);
+            new_rows.push(row);
+        }
+        imp.drift_rows.replace(new_rows);
+
+        if let Some(button) = imp.enforce_button.borrow().as_ref() {
+            button.set_sensitive(!imp.enforcing.get() && drifts.iter().any(Drift::is_fixable));
+        }
+        imp.drifts.replace(drifts);
+    }
+
+    /// Create a row describing a single drift.
+    fn create_drift_row(drift: &Drift) -> adw::ActionRow {
+        let (icon, title, subtitle) = match drift {
+            Drift::DefaultZone { expected, actual } => (
+                "network-workgroup-symbolic",
+                gettext("Default zone is '%s'").replace("%s", actual),
+                gettext("The policy requires '%s'").replace("%s", expected),
+            ),
+            Drift::MissingZone { zone } => (
+                "dialog-error-symbolic",
+                gettext("Zone '%s' does not exist").replace("%s", zone),
+                gettext("Create it with firewall-cmd; it cannot be enforced from here"),
+            ),
+            Drift::MissingService { zone, service } => (
+                "application-x-addon-symbolic",
+                gettext("Service %s is not allowed").replace("%s", service),
+                gettext("Zone %s • required by the policy").replace("%s", zone),
+            ),
+            Drift::ExtraService { zone, service } => (
+                "application-x-addon-symbolic",
+                gettext("Service %s is allowed").replace("%s", service),
+                gettext("Zone %s • not in the policy").replace("%s", zone),
+            ),
+            Drift::MissingPort { zone, port } => (
+                "network-transmit-receive-symbolic",
+                gettext("Port %s is closed").replace("%s", port),
+                gettext("Zone %s • required by the policy").replace("%s", zone),
+            ),
+            Drift::ExtraPort { zone, port } => (
+                "network-transmit-receive-symbolic",
+                gettext("Port %s is open").replace("%s", port),
+                gettext("Zone %s • not in the policy").replace("%s", zone),
+            ),
+            Drift::UnitNotRunning { unit } => (
+                "system-run-symbolic",
+                gettext("%s is not running").replace("%s", unit),
+                gettext("Required by the policy"),
+            ),
+            Drift::Sysctl {
+                key,
+                expected,
+                actual,
+            } => (
+                "preferences-system-symbolic",
+                gettext("%s is %s").replacen("%s", key, 1).replacen(
+                    "%s",
+                    actual.as_deref().unwrap_or(&gettext("unreadable")),
+                    1,
+                ),
+                gettext("The policy requires %s").replace("%s", expected),
+            ),
+        };
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(&title).as_str())
+            .subtitle(glib::markup_escape_text(&subtitle).as_str())
+            .build();
+        let image = gtk4::Image::from_icon_name(icon);
+        if !drift.is_fixable() {
+            image.add_css_class("error");
+        }
+        row.add_prefix(&image);
+        row
+    }
+
+    /// Ask for confirmation, then correct every fixable drift.
+    fn confirm_enforce(&self) {
+        let count = self
+            .imp()
+            .drifts
+            .borrow()
+            .iter()
+            .filter(|d| d.is_fixable())
+            .count();
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("Enforce policy?"))
+            .body(
+                gettext(
+                    "%d differences will be corrected. Firewall changes are also made permanent.",
+                )
+                .replace("%d", &count.to_string()),
+            )
+            .build();
+        dialog.add_responses(&[
+            ("cancel", gettext("Cancel").as_str()),
+            ("enforce", gettext("Enforce").as_str()),
+        ]);
+        dialog.set_response_appearance("enforce", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("cancel"));
+        dialog.set_close_response("cancel");
+
+        let page = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response == "enforce" {
+                page.enforce_now();
+            }
+        });
+
+        if let Some(root) = self.root() {
+            if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                dialog.present(Some(window));
+            }
+        }
+    }
+
+    /// Re-read the policy and correct the drift in the background.
+    fn enforce_now(&self) {
+        let imp = self.imp();
+        if imp.enforcing.get() {
+            return;
+        }
+        imp.enforcing.set(true);
+        if let Some(button) = imp.enforce_button.borrow().as_ref() {
+            button.set_sensitive(false);
+        }
+
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(|| {
+                let (state, drifts) = check_policy()?
+                    .ok_or_else(|| anyhow::anyhow!("The policy file was removed"))?;
+                let fixable: Vec<Drift> = drifts.into_iter().filter(Drift::is_fixable).collect();
+                Ok::<_, anyhow::Error>(enforce(&state, &fixable))
+            })
+            .await;
+            page.imp().enforcing.set(false);
+            match result {
+                Ok(Ok(report)) => page.finish_enforce(&report, true),
+                Ok(Err(e)) => {
+                    page.show_toast(&format!("{}: {}", gettext("Enforcement failed"), e));
+                    page.refresh();
+                }
+                Err(_) => {
+                    page.show_toast(&gettext("Enforcement failed"));
+                    page.refresh();
+                }
+            }
+        });
+    }
+
+    /// Record the run, report the outcome and check again.
+    fn finish_enforce(&self, report: &EnforceReport, manual: bool) {
+        self.record_enforced();
+        for (drift, reason) in &report.failed {
+            tracing::warn!("Policy enforcement: {:?}: {}", drift, reason);
+        }
+        let total = report.fixed + report.failed.len();
+        if manual || total > 0 {
+            self.show_toast(
+                &gettext("Corrected %d of %d differences")
+                    .replacen("%d", &report.fixed.to_string(), 1)
+                    .replacen("%d", &total.to_string(), 1),
+            );
+        }
+        self.refresh();
+    }
+
+    fn record_enforced(&self) {
+        let now = chrono::Utc::now().timestamp();
+        let mut settings = Settings::new();
+        settings.set_policy_last_enforced(now);
+        if let Some(row) = self.imp().schedule_row.borrow().as_ref() {
+            row.set_subtitle(&last_enforced_text(Some(now)));
+        }
+    }
+
+    /// Write a starting policy from the current firewall configuration.
+    fn create_policy(&self, btn: gtk4::Button) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result =
+                gtk4::gio::spawn_blocking(|| create_policy_file(&policy_from_system()?)).await;
+            match result {
+                Ok(Ok(_)) => {
+                    page.show_toast(&gettext("Policy file created"));
+                    page.refresh();
+                }
+                Ok(Err(e)) => {
+                    page.show_toast(&format!(
+                        "{}: {}",
+                        gettext("Failed to create the policy file"),
+                        e
+                    ));
+                    btn.set_sensitive(true);
+                }
+                Err(_) => {
+                    page.show_toast(&gettext("Failed to create the policy file"));
+                    btn.set_sensitive(true);
+                }
+            }
+        });
+    }
+
+    /// Open the policy file with the default application.
+    fn open_policy(&self) {
+        let file = gio::File::for_path(policy_path());
+        let launcher = gtk4::FileLauncher::new(Some(&file));
+        let window = self.root().and_downcast::<gtk4::Window>();
+        let page = self.clone();
+        launcher.launch(window.as_ref(), gio::Cancellable::NONE, move |result| {
+            if result.is_err() {
+                page.show_toast(&gettext("No application can open the policy file"));
+            }
+        });
+    }
+
+    /// Show a toast on the main window.
+    fn show_toast(&self, message: &str) {
+        if let Some(root) = self.root() {
+            if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                if let Some(main_window) = window.downcast_ref::<super::MainWindow>() {
+                    main_window.show_toast(message);
+                }
+            }
+        }
+    }
+
+    /// Create a section header with icon on the left.
+    fn create_section_header(icon_name: &str, title: &str) -> gtk4::Box {
+        let header = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(8)
+            .margin_bottom(6)
+            .build();
+
+        let icon = gtk4::Image::builder()
+            .icon_name(icon_name)
+            .css_classes(vec!["heading".to_string()])
+            .build();
+
+        let label = gtk4::Label::builder()
+            .label(title)
+            .css_classes(vec!["heading".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        header.append(&icon);
+        header.append(&label);
+        header
+    }
+}
+
+impl Default for CompliancePage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Subtitle describing when the policy was last enforced.
+fn last_enforced_text(last_run: Option<i64>) -> String {
+    match last_run {
+        Some(ts) => gettext("Last enforced %s").replace("%s", &format_datetime(ts)),
+        None => gettext("Never enforced"),
+    }
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct CompliancePage {
+        pub enforce_button: RefCell<Option<gtk4::Button>>,
+        pub create_button: RefCell<Option<gtk4::Button>>,
+        pub open_button: RefCell<Option<gtk4::Button>>,
+        pub schedule_row: RefCell<Option<adw::ComboRow>>,
+        pub drift_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub drift_rows: RefCell<Vec<adw::ActionRow>>,
+        pub drifts: RefCell<Vec<Drift>>,
+        /// Guards against overlapping manual and scheduled runs.
+        pub enforcing: Cell<bool>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for CompliancePage {
+        const NAME: &'static str = "SecurityCenterCompliancePage";
+        type Type = super::CompliancePage;
+        type ParentType = gtk4::Box;
+    }
+
+    impl ObjectImpl for CompliancePage {}
+    impl WidgetImpl for CompliancePage {}
+    impl BoxImpl for CompliancePage {}
+}
@@ -7,11 +7,11 @@
 //! Where the dashboard shows only the top few talkers, this page lists *all*
 //! established outgoing sessions grouped per destination, with per-group byte
 //! totals, offline country, live search and sorting. Clicking any row opens the
-//! IP details window.
+//! IP details window; its stop button terminates the group's connections.
 
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 
 use gtk4::glib;
 use gtk4::prelude::*;
@@ -20,8 +20,10 @@ use libadwaita as adw;
 use libadwaita::prelude::*;
 
 use super::app_icons::icon_for_process;
-use super::ip_details::{present_ip_details, IpDetailsContext};
-use crate::i18n::gettext;
+use super::density::{self, Role};
+use super::ip_details::{present_ip_details, ConnectionFacts, IpDetailsContext};
+use crate::admin::{terminate_connections, Flow};
+use crate::i18n::{format_bytes, gettext};
 
 /// How often the list refreshes while the page is on screen.
 const REFRESH_SECS: u32 = 5;
@@ -38,6 +40,8 @@ pub(crate) struct ConnGroup {
     bytes_in: u64,
     bytes_out: u64,
     country: Option<String>,
+    /// The sockets behind the group, for terminating them.
+    flows: Vec<Flow>,
 }
 
 impl ConnGroup {
@@ -92,11 +96,8 @@ impl ConnectionsPage {
         let header = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Horizontal)
             .spacing(12)
-            .margin_start(24)
-            .margin_end(24)
-            .margin_top(24)
-            .margin_bottom(12)
             .build();
+        density::track(&header, Role::PageHeader);
         let title_box = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Vertical)
             .spacing(4)
@@ -256,12 +257,18 @@ impl ConnectionsPage {
                 .copied()
                 .unwrap_or((0, 0));
             let proc = conn.process_label();
+            let flow = Flow {
+                protocol: conn.protocol,
+                local: SocketAddr::new(conn.local_addr, conn.local_port),
+                remote: SocketAddr::new(conn.remote_addr, conn.remote_port),
+            };
             if let Some(g) = groups.iter_mut().find(|g| {
                 g.process == proc && g.addr == conn.remote_addr && g.port == conn.remote_port
             }) {
                 g.count += 1;
                 g.bytes_in = g.bytes_in.saturating_add(bin);
                 g.bytes_out = g.bytes_out.saturating_add(bout);
+                g.flows.push(flow);
                 if g.pid.is_none() {
                     g.pid = conn.pid;
                 }
@@ -276,6 +283,7 @@ impl ConnectionsPage {
                     bytes_in: bin,
                     bytes_out: bout,
                     country: geo_labels.get(&conn.remote_addr).cloned(),
+                    flows: vec![flow],
                 });
             }
         }
@@ -415,6 +423,17 @@ impl ConnectionsPage {
             .build();
         row.add_suffix(&info);
 
+        let terminate = gtk4::Button::builder()
+            .icon_name("process-stop-symbolic")
+            .css_classes(vec!["flat".to_string()])
+            .valign(gtk4::Align::Center)
+            .tooltip_text(gettext("Terminate connection"))
+            .build();
+        let page = self.clone();
+        let group = g.clone();
+        terminate.connect_clicked(move |_| page.confirm_terminate(&group));
+        row.add_suffix(&terminate);
+
         // Both the info button and row activation open the details window.
         let ctx_src = g.clone();
         let page = self.clone();
@@ -423,12 +442,14 @@ impl ConnectionsPage {
                 &page,
                 IpDetailsContext {
                     ip: ctx_src.addr,
-                    port: ctx_src.port,
-                    protocol: ctx_src.protocol.clone(),
-                    process: Some(ctx_src.process.clone()),
-                    pid: ctx_src.pid,
-                    bytes_in: ctx_src.bytes_in,
-                    bytes_out: ctx_src.bytes_out,
+                    connection: Some(ConnectionFacts {
+                        port: ctx_src.port,
+                        protocol: ctx_src.protocol.clone(),
+                        process: Some(ctx_src.process.clone()),
+                        pid: ctx_src.pid,
+                        bytes_in: ctx_src.bytes_in,
+                        bytes_out: ctx_src.bytes_out,
+                    }),
                     country_label: ctx_src.country.clone(),
                 },
             );
@@ -438,6 +459,67 @@ impl ConnectionsPage {
         row.connect_activated(move |_| open());
         row
     }
+
+    /// Ask for confirmation, then terminate every connection of a group.
+    fn confirm_terminate(&self, g: &ConnGroup) {
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("Terminate connection?"))
+            .body(
+                gettext(
+                    "%s will lose its connection to %s. It may connect again unless a firewall rule blocks the address.",
+                )
+                .replacen("%s", &g.process, 1)
+                .replacen("%s", &format!("{}:{}", g.addr, g.port), 1),
+            )
+            .build();
+        dialog.add_responses(&[
+            ("cancel", gettext("Cancel").as_str()),
+            ("terminate", gettext("Terminate").as_str()),
+        ]);
+        dialog.set_response_appearance("terminate", adw::ResponseAppearance::Destructive);
+        dialog.set_default_response(Some("cancel"));
+        dialog.set_close_response("cancel");
+
+        let page = self.clone();
+        let flows = g.flows.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response == "terminate" {
+                page.terminate(flows.clone());
+            }
+        });
+
+        if let Some(root) = self.root() {
+            if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                dialog.present(Some(window));
+            }
+        }
+    }
+
+    /// Terminate connections through the privileged helper, then rescan.
+    fn terminate(&self, flows: Vec<Flow>) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(move || terminate_connections(&flows)).await;
+            let message = match result {
+                Ok(Ok(_)) => gettext("Connection terminated"),
+                Ok(Err(e)) => e.to_string(),
+                Err(_) => gettext("Could not terminate the connection"),
+            };
+            page.show_toast(&message);
+            page.refresh();
+        });
+    }
+
+    /// Show a toast notification.
+    fn show_toast(&self, message: &str) {
+        if let Some(root) = self.root() {
+            if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                if let Some(main_window) = window.downcast_ref::<super::MainWindow>() {
+                    main_window.show_toast(message);
+                }
+            }
+        }
+    }
 }
 
 /// A country sort key that pushes unknown/empty countries to the end.
@@ -497,22 +579,6 @@ fn set_chip(cell: &RefCell<Option<gtk4::Label>>, text: &str) {
     }
 }
 
-/// Format a byte count as a compact human-readable string (B/KB/MB/GB).
-fn format_bytes(bytes: u64) -> String {
-    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
-    let mut value = bytes as f64;
-    let mut unit = 0;
-    while value >= 1024.0 && unit < UNITS.len() - 1 {
-        value /= 1024.0;
-        unit += 1;
-    }
-    if unit == 0 {
-        format!("{} {}", bytes, UNITS[unit])
-    } else {
-        format!("{:.1} {}", value, UNITS[unit])
-    }
-}
-
 mod imp {
     use super::*;
 
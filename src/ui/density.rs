@@ -0,0 +1,145 @@
+// Security Center - Layout Density
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Comfortable / compact layout density.
+//!
+//! Compact mode targets small laptop screens (1366×768). It works on two
+//! levels:
+//!
+//! - The main window carries the `compact` style class, and `style.css`
+//!   tightens rows, cards and headings under `window.compact`.
+//! - Page constructors lay out their header and content boxes, and charts
+//!   their size, through [`track`], which applies the current density and
+//!   re-applies it when the preference changes.
+
+use std::cell::{Cell, RefCell};
+
+use gtk4::glib;
+use gtk4::prelude::*;
+
+/// Layout density preference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Density {
+    Comfortable,
+    Compact,
+}
+
+impl Density {
+    pub const ALL: [Density; 2] = [Density::Comfortable, Density::Compact];
+
+    /// Identifier stored in the settings file.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Density::Comfortable => "comfortable",
+            Density::Compact => "compact",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Self {
+        match id {
+            "compact" => Density::Compact,
+            _ => Density::Comfortable,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Density::Comfortable => "Comfortable",
+            Density::Compact => "Compact",
+        }
+    }
+
+    /// Scale a comfortable margin or spacing.
+    fn space(&self, comfortable: i32) -> i32 {
+        match self {
+            Density::Comfortable => comfortable,
+            Density::Compact => comfortable / 2,
+        }
+    }
+
+    /// Scale a comfortable widget size (`-1` stays unset).
+    fn scale(&self, size: i32) -> i32 {
+        match (self, size) {
+            (_, -1) | (Density::Comfortable, _) => size,
+            (Density::Compact, _) => size * 3 / 4,
+        }
+    }
+}
+
+/// How a tracked widget is laid out.
+#[derive(Debug, Clone, Copy)]
+pub enum Role {
+    /// Page title row above the scrolled content.
+    PageHeader,
+    /// Main vertical content box of a page with its comfortable margin and
+    /// spacing between sections.
+    Content { margin: i32, spacing: i32 },
+    /// Widget with a comfortable size request of (width, height).
+    Size(i32, i32),
+}
+
+thread_local! {
+    static CURRENT: Cell<Density> = const { Cell::new(Density::Comfortable) };
+    static TRACKED: RefCell<Vec<(glib::WeakRef<gtk4::Widget>, Role)>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// The density in use.
+pub fn current() -> Density {
+    CURRENT.with(|c| c.get())
+}
+
+/// Lay out `widget` for `role` now and whenever the density changes.
+pub fn track(widget: &impl IsA<gtk4::Widget>, role: Role) {
+    let widget = widget.upcast_ref::<gtk4::Widget>();
+    layout(widget, role, current());
+    TRACKED.with(|tracked| tracked.borrow_mut().push((widget.downgrade(), role)));
+}
+
+/// Switch density: toggle the style class on `window` and re-layout every
+/// tracked widget that is still alive.
+pub fn apply(window: &impl IsA<gtk4::Widget>, density: Density) {
+    CURRENT.with(|c| c.set(density));
+    if density == Density::Compact {
+        window.add_css_class("compact");
+    } else {
+        window.remove_css_class("compact");
+    }
+    TRACKED.with(|tracked| {
+        tracked
+            .borrow_mut()
+            .retain(|(weak, role)| match weak.upgrade() {
+                Some(widget) => {
+                    layout(&widget, *role, density);
+                    true
+                }
+                None => false,
+            });
+    });
+}
+
+fn layout(widget: &gtk4::Widget, role: Role, density: Density) {
+    match role {
+        Role::PageHeader => {
+            let margin = density.space(24);
+            widget.set_margin_start(margin);
+            widget.set_margin_end(margin);
+            widget.set_margin_top(margin);
+            widget.set_margin_bottom(margin / 2);
+        }
+        Role::Content { margin, spacing } => {
+            let margin = density.space(margin);
+            widget.set_margin_start(margin);
+            widget.set_margin_end(margin);
+            widget.set_margin_top(margin);
+            widget.set_margin_bottom(margin);
+            if let Some(content) = widget.downcast_ref::<gtk4::Box>() {
+                content.set_spacing(density.space(spacing));
+            }
+        }
+        Role::Size(width, height) => {
+            widget.set_size_request(density.scale(width), density.scale(height));
+        }
+    }
+}
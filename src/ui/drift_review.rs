@@ -0,0 +1,211 @@
+// Security Center - Configuration drift review
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Review of the differences between the runtime firewall and its permanent
+//! configuration, opened from the Overview banner. Each zone lists the
+//! services, ports and rich rules only one side has; each can be kept,
+//! which adds it to the other side, or discarded, which removes it from the
+//! side that has it. A reload then changes nothing.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use super::app_state::Invalidation;
+use super::service_ports::attach_service_tooltip;
+use super::MainWindow;
+use crate::i18n::gettext;
+use crate::models::{DriftItem, DriftKind, DriftSide, Zone};
+
+/// Build and present the differences of `zones` anchored to `window`.
+pub fn present_drift_review(window: &MainWindow, zones: &[Zone]) {
+    let dialog = adw::Dialog::builder()
+        .title(gettext("Unsaved Firewall Changes"))
+        .content_width(600)
+        .content_height(620)
+        .build();
+
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&adw::HeaderBar::new());
+
+    let page = adw::PreferencesPage::builder()
+        .description(gettext(
+            "Runtime only rules are lost at the next reload or reboot; permanent only \
+             rules come back then. Keep a rule to add it to the side that lacks it, or \
+             discard it to remove it from the side that has it.",
+        ))
+        .build();
+
+    let empty = adw::StatusPage::builder()
+        .icon_name("emblem-ok-symbolic")
+        .title(gettext("No Differences"))
+        .description(gettext(
+            "The runtime firewall matches the permanent configuration",
+        ))
+        .vexpand(true)
+        .build();
+
+    let stack = gtk4::Stack::new();
+    stack.add_named(&page, Some("items"));
+    stack.add_named(&empty, Some("empty"));
+
+    // Rows left unresolved, to show the empty page after the last one
+    let total = Rc::new(Cell::new(0));
+    for zone in zones {
+        let items = zone.drift();
+        if items.is_empty() {
+            continue;
+        }
+        let group = adw::PreferencesGroup::builder()
+            .title(glib::markup_escape_text(&zone.name).as_str())
+            .build();
+        let left = Rc::new(Cell::new(items.len()));
+        total.set(total.get() + items.len());
+        let remaining = Remaining {
+            group: group.clone(),
+            left,
+            total: total.clone(),
+            stack: stack.clone(),
+        };
+        for item in &items {
+            group.add(&create_item_row(window, &zone.name, item, &remaining));
+        }
+        page.add(&group);
+    }
+    stack.set_visible_child_name(if total.get() > 0 { "items" } else { "empty" });
+
+    toolbar.set_content(Some(&stack));
+    dialog.set_child(Some(&toolbar));
+    dialog.present(Some(window));
+}
+
+/// Unresolved rows of a zone's group and of the whole dialog.
+#[derive(Clone)]
+struct Remaining {
+    group: adw::PreferencesGroup,
+    left: Rc<Cell<usize>>,
+    total: Rc<Cell<usize>>,
+    stack: gtk4::Stack,
+}
+
+impl Remaining {
+    /// Remove a resolved row, hiding its group or the whole list once empty.
+    fn resolve(&self, row: &adw::ActionRow) {
+        self.group.remove(row);
+        self.left.set(self.left.get().saturating_sub(1));
+        self.total.set(self.total.get().saturating_sub(1));
+        if self.left.get() == 0 {
+            self.group.set_visible(false);
+        }
+        if self.total.get() == 0 {
+            self.stack.set_visible_child_name("empty");
+        }
+    }
+}
+
+/// Row for one difference, with its keep and discard buttons.
+fn create_item_row(
+    window: &MainWindow,
+    zone: &str,
+    item: &DriftItem,
+    remaining: &Remaining,
+) -> adw::ActionRow {
+    let (side, icon_name, keep_label, keep_tooltip, discard_tooltip) = match item.side {
+        DriftSide::RuntimeOnly => (
+            gettext("Runtime only"),
+            "dialog-warning-symbolic",
+            gettext("Save"),
+            gettext("Add it to the permanent configuration"),
+            gettext("Remove it from the running firewall now"),
+        ),
+        DriftSide::PermanentOnly => (
+            gettext("Permanent only"),
+            "view-refresh-symbolic",
+            gettext("Apply"),
+            gettext("Add it to the running firewall now"),
+            gettext("Remove it from the permanent configuration"),
+        ),
+    };
+    let row = adw::ActionRow::builder()
+        .title(glib::markup_escape_text(&item.value).as_str())
+        .subtitle(format!("{} • {}", gettext(item.kind.label()), side))
+        .title_selectable(true)
+        .build();
+    row.add_prefix(&gtk4::Image::from_icon_name(icon_name));
+    if item.kind == DriftKind::Service {
+        attach_service_tooltip(&row, &item.value);
+    }
+
+    let keep = gtk4::Button::builder()
+        .label(keep_label)
+        .tooltip_text(keep_tooltip)
+        .valign(gtk4::Align::Center)
+        .css_classes(["suggested-action"])
+        .build();
+    let discard = gtk4::Button::builder()
+        .label(gettext("Discard"))
+        .tooltip_text(discard_tooltip)
+        .valign(gtk4::Align::Center)
+        .css_classes(["flat", "error"])
+        .build();
+    row.add_suffix(&keep);
+    row.add_suffix(&discard);
+
+    for (button, keep_rule) in [(&keep, true), (&discard, false)] {
+        let window = window.clone();
+        let zone = zone.to_string();
+        let item = item.clone();
+        let row = row.clone();
+        let remaining = remaining.clone();
+        let buttons = [keep.clone(), discard.clone()];
+        button.connect_clicked(move |_| {
+            for button in &buttons {
+                button.set_sensitive(false);
+            }
+            let window = window.clone();
+            let zone = zone.clone();
+            let item = item.clone();
+            let row = row.clone();
+            let remaining = remaining.clone();
+            let buttons = buttons.clone();
+            glib::spawn_future_local(async move {
+                let result = crate::firewall::call(move |client| {
+                    if client.connect().is_err() {
+                        return Err(anyhow::anyhow!("Not connected to firewalld"));
+                    }
+                    client.resolve_drift(&zone, &item, keep_rule)
+                })
+                .await;
+
+                match result {
+                    Ok(Ok(())) => {
+                        remaining.resolve(&row);
+                        window.invalidate(Invalidation::ZonesChanged);
+                    }
+                    Ok(Err(e)) => {
+                        for button in &buttons {
+                            button.set_sensitive(true);
+                        }
+                        window.show_toast(&format!(
+                            "{}: {}",
+                            gettext("Failed to resolve the difference"),
+                            e
+                        ));
+                    }
+                    Err(_) => {
+                        for button in &buttons {
+                            button.set_sensitive(true);
+                        }
+                        window.show_toast(&gettext("Failed to resolve the difference"));
+                    }
+                }
+            });
+        });
+    }
+    row
+}
@@ -0,0 +1,438 @@
+// Security Center - fail2ban Page
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! fail2ban page: the jails fail2ban runs next to firewalld, the addresses
+//! each one bans and its ban statistics, with unbanning an address.
+
+use std::cell::{Cell, RefCell};
+use std::net::IpAddr;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use super::density::{self, Role};
+use super::ip_details::{present_ip_details, IpDetailsContext};
+use crate::admin::{query_fail2ban, read_jails_privileged, unban_ip, Fail2banStatus, Jail};
+use crate::i18n::gettext;
+
+glib::wrapper! {
+    /// fail2ban jails and bans page.
+    pub struct Fail2banPage(ObjectSubclass<imp::Fail2banPage>)
+        @extends gtk4::Box, gtk4::Widget,
+        @implements gtk4::Orientable;
+}
+
+impl Fail2banPage {
+    /// Create a new fail2ban page.
+    pub fn new() -> Self {
+        let page: Self = glib::Object::new();
+        page.setup_ui();
+        page
+    }
+
+    /// Setup the UI.
+    fn setup_ui(&self) {
+        let imp = self.imp();
+
+        self.set_orientation(gtk4::Orientation::Vertical);
+        self.set_spacing(0);
+
+        // Header with refresh button
+        let header_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(12)
+            .build();
+        density::track(&header_box, Role::PageHeader);
+
+        let title_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(4)
+            .hexpand(true)
+            .build();
+
+        let title = gtk4::Label::builder()
+            .label(gettext("fail2ban"))
+            .css_classes(vec!["title-1".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        let subtitle = gtk4::Label::builder()
+            .label(gettext("Addresses banned after repeated failed logins"))
+            .css_classes(vec!["dim-label".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        title_box.append(&title);
+        title_box.append(&subtitle);
+
+        let refresh_button = gtk4::Button::builder()
+            .icon_name("view-refresh-symbolic")
+            .css_classes(vec!["flat".to_string()])
+            .tooltip_text(gettext("Refresh"))
+            .valign(gtk4::Align::Center)
+            .build();
+        let page = self.clone();
+        refresh_button.connect_clicked(move |_| {
+            page.refresh();
+        });
+
+        header_box.append(&title_box);
+        header_box.append(&refresh_button);
+        self.append(&header_box);
+
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .vscrollbar_policy(gtk4::PolicyType::Automatic)
+            .vexpand(true)
+            .hexpand(true)
+            .build();
+
+        let content = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .hexpand(true)
+            .build();
+        density::track(
+            &content,
+            Role::Content {
+                margin: 24,
+                spacing: 24,
+            },
+        );
+
+        // Ban statistics
+        content.append(&Self::create_section_header(
+            "view-list-bullet-symbolic",
+            &gettext("Statistics"),
+        ));
+        let stats_group = adw::PreferencesGroup::new();
+        imp.stats_group.replace(Some(stats_group.clone()));
+        content.append(&stats_group);
+
+        // Jails
+        content.append(&Self::create_section_header(
+            "action-unavailable-symbolic",
+            &gettext("Jails"),
+        ));
+        let jails_group = adw::PreferencesGroup::new();
+        imp.jails_group.replace(Some(jails_group.clone()));
+        content.append(&jails_group);
+
+        scrolled.set_child(Some(&content));
+        self.append(&scrolled);
+    }
+
+    /// Read the jails from fail2ban, through the privileged helper once the
+    /// user has asked for that.
+    pub fn refresh(&self) {
+        let page = self.clone();
+        let privileged = self.imp().privileged.get();
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(move || match query_fail2ban()? {
+                Fail2banStatus::NeedsAuthorization if privileged => {
+                    read_jails_privileged().map(Fail2banStatus::Running)
+                }
+                status => Ok(status),
+            })
+            .await;
+            match result {
+                Ok(Ok(status)) => page.display(&status),
+                Ok(Err(e)) => {
+                    // Ask again rather than prompting on every refresh
+                    page.imp().privileged.set(false);
+                    page.display_error(&e.to_string());
+                }
+                Err(_) => page.display_error(&gettext("Failed to read fail2ban")),
+            }
+        });
+    }
+
+    fn clear(&self) -> Option<(adw::PreferencesGroup, adw::PreferencesGroup)> {
+        let imp = self.imp();
+        let stats_group = imp.stats_group.borrow().clone()?;
+        let jails_group = imp.jails_group.borrow().clone()?;
+        for row in imp.stat_rows.take() {
+            stats_group.remove(&row);
+        }
+        for row in imp.jail_rows.take() {
+            jails_group.remove(&row);
+        }
+        Some((stats_group, jails_group))
+    }
+
+    fn display(&self, status: &Fail2banStatus) {
+        let Some((stats_group, jails_group)) = self.clear() else {
+            return;
+        };
+        let imp = self.imp();
+
+        let jails = match status {
+            Fail2banStatus::Running(jails) => jails,
+            Fail2banStatus::NotInstalled => {
+                let row = Self::create_notice_row(
+                    "dialog-information-symbolic",
+                    &gettext("fail2ban is not installed"),
+                    &gettext(
+                        "fail2ban bans addresses that fail to log in too often. Install the \
+                         fail2ban package to use it with firewalld.",
+                    ),
+                );
+                jails_group.add(&row);
+                imp.jail_rows.replace(vec![row.upcast()]);
+                return;
+            }
+            Fail2banStatus::NotRunning => {
+                let row = Self::create_notice_row(
+                    "dialog-warning-symbolic",
+                    &gettext("fail2ban is not running"),
+                    &gettext("Start fail2ban.service on the System Services page"),
+                );
+                jails_group.add(&row);
+                imp.jail_rows.replace(vec![row.upcast()]);
+                return;
+            }
+            Fail2banStatus::NeedsAuthorization => {
+                let row = Self::create_notice_row(
+                    "system-lock-screen-symbolic",
+                    &gettext("Reading fail2ban requires administrator access"),
+                    &gettext("fail2ban only answers to root"),
+                );
+                let button = gtk4::Button::builder()
+                    .label(gettext("Read as Administrator"))
+                    .valign(gtk4::Align::Center)
+                    .css_classes(vec!["suggested-action".to_string()])
+                    .build();
+                let page = self.clone();
+                button.connect_clicked(move |button| {
+                    button.set_sensitive(false);
+                    page.imp().privileged.set(true);
+                    page.refresh();
+                });
+                row.add_suffix(&button);
+                jails_group.add(&row);
+                imp.jail_rows.replace(vec![row.upcast()]);
+                return;
+            }
+        };
+
+        // Statistics
+        let banned_now: u64 = jails.iter().map(|jail| jail.currently_banned).sum();
+        let total_banned: u64 = jails.iter().map(|jail| jail.total_banned).sum();
+        let failing: u64 = jails.iter().map(|jail| jail.currently_failed).sum();
+        let total_failed: u64 = jails.iter().map(|jail| jail.total_failed).sum();
+        let stats = [
+            (gettext("Jails"), jails.len() as u64),
+            (gettext("Banned Now"), banned_now),
+            (gettext("Bans Since fail2ban Started"), total_banned),
+            (gettext("Addresses Failing Now"), failing),
+            (gettext("Failures Since fail2ban Started"), total_failed),
+        ];
+        let mut stat_rows: Vec<gtk4::Widget> = Vec::new();
+        for (title, value) in stats {
+            let row = adw::ActionRow::builder().title(title).build();
+            row.add_suffix(
+                &gtk4::Label::builder()
+                    .label(value.to_string())
+                    .css_classes(vec!["dim-label".to_string()])
+                    .build(),
+            );
+            stats_group.add(&row);
+            stat_rows.push(row.upcast());
+        }
+        imp.stat_rows.replace(stat_rows);
+
+        // Jails
+        let mut jail_rows: Vec<gtk4::Widget> = Vec::new();
+        if jails.is_empty() {
+            let row = Self::create_notice_row(
+                "dialog-information-symbolic",
+                &gettext("No jails are enabled"),
+                &gettext("Enable jails in /etc/fail2ban/jail.local"),
+            );
+            jail_rows.push(row.upcast());
+        }
+        for jail in jails {
+            jail_rows.push(self.create_jail_row(jail).upcast());
+        }
+        for row in &jail_rows {
+            jails_group.add(row);
+        }
+        imp.jail_rows.replace(jail_rows);
+    }
+
+    fn display_error(&self, message: &str) {
+        let Some((_, jails_group)) = self.clear() else {
+            return;
+        };
+        let row = Self::create_notice_row(
+            "dialog-error-symbolic",
+            &gettext("Failed to read fail2ban"),
+            message,
+        );
+        jails_group.add(&row);
+        self.imp().jail_rows.replace(vec![row.upcast()]);
+    }
+
+    fn create_notice_row(icon_name: &str, title: &str, subtitle: &str) -> adw::ActionRow {
+        let row = adw::ActionRow::builder()
+            .title(title)
+            .subtitle(glib::markup_escape_text(subtitle).as_str())
+            .build();
+        row.add_prefix(&gtk4::Image::from_icon_name(icon_name));
+        row
+    }
+
+    fn create_jail_row(&self, jail: &Jail) -> adw::ExpanderRow {
+        let subtitle = format!(
+            "{} · {} · {}",
+            gettext("%d banned").replace("%d", &jail.currently_banned.to_string()),
+            gettext("%d bans in total").replace("%d", &jail.total_banned.to_string()),
+            gettext("%d failing").replace("%d", &jail.currently_failed.to_string()),
+        );
+        let row = adw::ExpanderRow::builder()
+            .title(glib::markup_escape_text(&jail.name).as_str())
+            .subtitle(subtitle)
+            .build();
+        let icon = if jail.banned.is_empty() {
+            "emblem-ok-symbolic"
+        } else {
+            "action-unavailable-symbolic"
+        };
+        row.add_prefix(&gtk4::Image::from_icon_name(icon));
+
+        if jail.banned.is_empty() {
+            let empty = adw::ActionRow::builder()
+                .title(gettext("No addresses are banned"))
+                .css_classes(vec!["dim-label".to_string()])
+                .build();
+            row.add_row(&empty);
+        }
+        for address in &jail.banned {
+            row.add_row(&self.create_banned_row(&jail.name, address));
+        }
+        row
+    }
+
+    fn create_banned_row(&self, jail: &str, address: &str) -> adw::ActionRow {
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(address).as_str())
+            .build();
+        row.add_css_class("property");
+
+        // Addresses are usually single hosts, but a jail can ban a network
+        let Ok(ip) = address.parse::<IpAddr>() else {
+            return row;
+        };
+
+        let unban_button = gtk4::Button::builder()
+            .label(gettext("Unban"))
+            .valign(gtk4::Align::Center)
+            .tooltip_text(gettext("Lift the ban on this address"))
+            .build();
+        let page = self.clone();
+        let jail = jail.to_string();
+        unban_button.connect_clicked(move |button| {
+            button.set_sensitive(false);
+            page.unban(&jail, ip);
+        });
+        row.add_suffix(&unban_button);
+
+        row.set_activatable(true);
+        row.add_suffix(&gtk4::Image::from_icon_name("go-next-symbolic"));
+        row.connect_activated(move |row| {
+            present_ip_details(row, IpDetailsContext::for_address(ip));
+        });
+        row
+    }
+
+    /// Lift the ban on `ip` in `jail`, then read the jails again.
+    fn unban(&self, jail: &str, ip: IpAddr) {
+        let page = self.clone();
+        let jail = jail.to_string();
+        glib::spawn_future_local(async move {
+            let jail_for_unban = jail.clone();
+            let result = gtk4::gio::spawn_blocking(move || unban_ip(&jail_for_unban, ip)).await;
+            match result {
+                Ok(Ok(())) => page.show_toast(
+                    &gettext("Unbanned %s in %j")
+                        .replace("%s", &ip.to_string())
+                        .replace("%j", &jail),
+                ),
+                Ok(Err(e)) => {
+                    page.show_toast(&format!("{}: {}", gettext("Failed to unban address"), e))
+                }
+                Err(_) => page.show_toast(&gettext("Failed to unban address")),
+            }
+            page.refresh();
+        });
+    }
+
+    /// Show a toast message.
+    fn show_toast(&self, message: &str) {
+        if let Some(root) = self.root() {
+            if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                if let Some(main_window) = window.downcast_ref::<super::MainWindow>() {
+                    main_window.show_toast(message);
+                }
+            }
+        }
+    }
+
+    /// Create a section header with icon on the left.
+    fn create_section_header(icon_name: &str, title: &str) -> gtk4::Box {
+        let header = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(8)
+            .margin_bottom(6)
+            .build();
+
+        let icon = gtk4::Image::builder()
+            .icon_name(icon_name)
+            .css_classes(vec!["heading".to_string()])
+            .build();
+
+        let label = gtk4::Label::builder()
+            .label(title)
+            .css_classes(vec!["heading".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        header.append(&icon);
+        header.append(&label);
+        header
+    }
+}
+
+impl Default for Fail2banPage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct Fail2banPage {
+        pub stats_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub jails_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub stat_rows: RefCell<Vec<gtk4::Widget>>,
+        pub jail_rows: RefCell<Vec<gtk4::Widget>>,
+        /// Set once the user chose to read fail2ban as administrator.
+        pub privileged: Cell<bool>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Fail2banPage {
+        const NAME: &'static str = "SecurityCenterFail2banPage";
+        type Type = super::Fail2banPage;
+        type ParentType = gtk4::Box;
+    }
+
+    impl ObjectImpl for Fail2banPage {}
+    impl WidgetImpl for Fail2banPage {}
+    impl BoxImpl for Fail2banPage {}
+}
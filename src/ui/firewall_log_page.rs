@@ -0,0 +1,376 @@
+// Security Center - Firewall Log Page
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Firewall log page: packets the firewall rejected or dropped, read from
+//! the kernel log, and firewalld's LogDenied setting that controls which of
+//! them are logged.
+
+use std::cell::{Cell, RefCell};
+use std::net::IpAddr;
+use std::rc::{Rc, Weak};
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use super::density::{self, Role};
+use super::ip_details::{present_ip_details, IpDetailsContext};
+use crate::admin::{DeniedLog, DeniedPacket};
+use crate::firewall::LOG_DENIED_VALUES;
+use crate::i18n::{format_datetime, gettext};
+
+/// Rows shown before the list is summarised.
+const MAX_ROWS: usize = 100;
+
+/// Labels for [`LOG_DENIED_VALUES`], in the same order.
+const LOG_DENIED_LABELS: [&str; 5] = ["Off", "All", "Unicast", "Broadcast", "Multicast"];
+
+glib::wrapper! {
+    /// Denied packets log page.
+    pub struct FirewallLogPage(ObjectSubclass<imp::FirewallLogPage>)
+        @extends gtk4::Box, gtk4::Widget,
+        @implements gtk4::Orientable;
+}
+
+impl FirewallLogPage {
+    /// Create a new firewall log page.
+    pub fn new() -> Self {
+        let page: Self = glib::Object::new();
+        page.setup_ui();
+        page
+    }
+
+    /// Set the kernel log follower the page lists packets from. The main
+    /// window owns it.
+    pub fn set_denied_log(&self, log: &Rc<DeniedLog>) {
+        self.imp().denied_log.replace(Rc::downgrade(log));
+    }
+
+    /// Setup the UI.
+    fn setup_ui(&self) {
+        let imp = self.imp();
+
+        self.set_orientation(gtk4::Orientation::Vertical);
+        self.set_spacing(0);
+
+        // Header with refresh button
+        let header_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(12)
+            .build();
+        density::track(&header_box, Role::PageHeader);
+
+        let title_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(4)
+            .hexpand(true)
+            .build();
+
+        let title = gtk4::Label::builder()
+            .label(gettext("Firewall Log"))
+            .css_classes(vec!["title-1".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        let subtitle = gtk4::Label::builder()
+            .label(gettext("Packets the firewall rejected or dropped"))
+            .css_classes(vec!["dim-label".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        title_box.append(&title);
+        title_box.append(&subtitle);
+
+        let refresh_button = gtk4::Button::builder()
+            .icon_name("view-refresh-symbolic")
+            .css_classes(vec!["flat".to_string()])
+            .tooltip_text(gettext("Refresh"))
+            .valign(gtk4::Align::Center)
+            .build();
+        let page = self.clone();
+        refresh_button.connect_clicked(move |_| {
+            page.refresh();
+        });
+
+        header_box.append(&title_box);
+        header_box.append(&refresh_button);
+        self.append(&header_box);
+
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .vscrollbar_policy(gtk4::PolicyType::Automatic)
+            .vexpand(true)
+            .hexpand(true)
+            .build();
+
+        let content = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .hexpand(true)
+            .build();
+        density::track(
+            &content,
+            Role::Content {
+                margin: 24,
+                spacing: 24,
+            },
+        );
+
+        // Logging setting
+        content.append(&Self::create_section_header(
+            "preferences-system-symbolic",
+            &gettext("Logging"),
+        ));
+        let settings_group = adw::PreferencesGroup::new();
+        let labels: Vec<String> = LOG_DENIED_LABELS.iter().map(|l| gettext(l)).collect();
+        let label_refs: Vec<&str> = labels.iter().map(|s| s.as_str()).collect();
+        let log_denied_row = adw::ComboRow::builder()
+            .title(gettext("Log Denied Packets"))
+            .subtitle(gettext(
+                "Which rejected or dropped packets firewalld writes to the kernel log",
+            ))
+            .model(&gtk4::StringList::new(&label_refs))
+            .sensitive(false)
+            .build();
+        let page = self.clone();
+        log_denied_row.connect_selected_notify(move |row| {
+            if page.imp().updating.get() {
+                return;
+            }
+            if let Some(value) = LOG_DENIED_VALUES.get(row.selected() as usize) {
+                page.set_log_denied(value);
+            }
+        });
+        settings_group.add(&log_denied_row);
+        imp.log_denied_row.replace(Some(log_denied_row));
+        content.append(&settings_group);
+
+        // Denied packets
+        content.append(&Self::create_section_header(
+            "action-unavailable-symbolic",
+            &gettext("Denied Packets"),
+        ));
+        let packets_group = adw::PreferencesGroup::new();
+        imp.packets_group.replace(Some(packets_group.clone()));
+        content.append(&packets_group);
+
+        scrolled.set_child(Some(&content));
+        self.append(&scrolled);
+    }
+
+    /// Read the LogDenied setting and list the recent denied packets.
+    pub fn refresh(&self) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let log_denied = crate::firewall::read(move |client| {
+                client.connect()?;
+                client.get_log_denied()
+            })
+            .await
+            .ok()
+            .and_then(|r| r.ok());
+            page.display(log_denied.as_deref());
+        });
+    }
+
+    fn display(&self, log_denied: Option<&str>) {
+        let imp = self.imp();
+
+        if let Some(row) = imp.log_denied_row.borrow().as_ref() {
+            imp.updating.set(true);
+            match log_denied.and_then(|v| LOG_DENIED_VALUES.iter().position(|x| *x == v)) {
+                Some(index) => {
+                    row.set_selected(index as u32);
+                    row.set_sensitive(true);
+                }
+                None => row.set_sensitive(false),
+            }
+            imp.updating.set(false);
+        }
+
+        let Some(group) = imp.packets_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.packet_rows.take() {
+            group.remove(&row);
+        }
+
+        let denied_log = imp.denied_log.borrow().upgrade();
+        let packets = denied_log
+            .as_ref()
+            .map(|log| log.recent())
+            .unwrap_or_default();
+
+        let mut rows: Vec<gtk4::Widget> = Vec::new();
+        if packets.is_empty() {
+            let hint = if denied_log.is_none() {
+                gettext("The kernel log cannot be read: journalctl is not available")
+            } else if log_denied == Some("off") {
+                gettext("Denied packets are not logged. Choose which ones to log above.")
+            } else {
+                gettext(
+                    "Nothing logged yet. Reading the kernel log requires membership in the \
+                     wheel, adm or systemd-journal group.",
+                )
+            };
+            let row = adw::ActionRow::builder()
+                .title(gettext("No denied packets"))
+                .subtitle(hint)
+                .build();
+            row.add_prefix(&gtk4::Image::from_icon_name("emblem-ok-symbolic"));
+            rows.push(row.upcast());
+            group.set_description(None);
+        } else {
+            group.set_description(Some(
+                &gettext("%d packets, newest first").replace("%d", &packets.len().to_string()),
+            ));
+            for packet in packets.iter().take(MAX_ROWS) {
+                rows.push(Self::create_packet_row(packet).upcast());
+            }
+            if packets.len() > MAX_ROWS {
+                let more = adw::ActionRow::builder()
+                    .title(
+                        gettext("%d older packets not shown")
+                            .replace("%d", &(packets.len() - MAX_ROWS).to_string()),
+                    )
+                    .css_classes(vec!["dim-label".to_string()])
+                    .build();
+                rows.push(more.upcast());
+            }
+        }
+
+        for row in &rows {
+            group.add(row);
+        }
+        imp.packet_rows.replace(rows);
+    }
+
+    fn create_packet_row(packet: &DeniedPacket) -> adw::ActionRow {
+        let destination = match packet.port {
+            Some(port) if packet.destination.contains(':') => {
+                format!("[{}]:{}", packet.destination, port)
+            }
+            Some(port) => format!("{}:{}", packet.destination, port),
+            None => packet.destination.clone(),
+        };
+        let title = format!("{} → {}", packet.source, destination);
+
+        let mut details = vec![packet.protocol.clone()];
+        if !packet.interface.is_empty() {
+            details.push(packet.interface.clone());
+        }
+        details.push(
+            packet
+                .zone()
+                .map(|zone| gettext("zone %s").replace("%s", zone))
+                .unwrap_or_else(|| packet.rule.clone()),
+        );
+        details.push(format_datetime(packet.time as i64));
+
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(&title).as_str())
+            .subtitle(glib::markup_escape_text(&details.join(" · ")).as_str())
+            .build();
+        row.add_css_class("property");
+        row.set_tooltip_text(Some(&packet.rule));
+        row.add_prefix(&gtk4::Image::from_icon_name("action-unavailable-symbolic"));
+        if let Ok(source) = packet.source.parse::<IpAddr>() {
+            row.set_activatable(true);
+            row.add_suffix(&gtk4::Image::from_icon_name("go-next-symbolic"));
+            row.connect_activated(move |row| {
+                present_ip_details(row, IpDetailsContext::for_address(source));
+            });
+        }
+        row
+    }
+
+    /// Change LogDenied; firewalld reloads its rules to apply it.
+    fn set_log_denied(&self, value: &'static str) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result = crate::firewall::call(move |client| {
+                client.connect()?;
+                client.set_log_denied(value)
+            })
+            .await;
+            match result {
+                Ok(Ok(())) => page.show_toast(&gettext("Denied packet logging updated")),
+                Ok(Err(e)) => page.show_toast(&format!(
+                    "{}: {}",
+                    gettext("Failed to change denied packet logging"),
+                    e
+                )),
+                Err(_) => page.show_toast(&gettext("Failed to change denied packet logging")),
+            }
+            page.refresh();
+        });
+    }
+
+    /// Show a toast message.
+    fn show_toast(&self, message: &str) {
+        if let Some(root) = self.root() {
+            if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                if let Some(main_window) = window.downcast_ref::<super::MainWindow>() {
+                    main_window.show_toast(message);
+                }
+            }
+        }
+    }
+
+    /// Create a section header with icon on the left.
+    fn create_section_header(icon_name: &str, title: &str) -> gtk4::Box {
+        let header = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(8)
+            .margin_bottom(6)
+            .build();
+
+        let icon = gtk4::Image::builder()
+            .icon_name(icon_name)
+            .css_classes(vec!["heading".to_string()])
+            .build();
+
+        let label = gtk4::Label::builder()
+            .label(title)
+            .css_classes(vec!["heading".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        header.append(&icon);
+        header.append(&label);
+        header
+    }
+}
+
+impl Default for FirewallLogPage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct FirewallLogPage {
+        pub denied_log: RefCell<Weak<DeniedLog>>,
+        pub log_denied_row: RefCell<Option<adw::ComboRow>>,
+        pub packets_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub packet_rows: RefCell<Vec<gtk4::Widget>>,
+        /// Set while the combo row is updated from firewalld.
+        pub updating: Cell<bool>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for FirewallLogPage {
+        const NAME: &'static str = "SecurityCenterFirewallLogPage";
+        type Type = super::FirewallLogPage;
+        type ParentType = gtk4::Box;
+    }
+
+    impl ObjectImpl for FirewallLogPage {}
+    impl WidgetImpl for FirewallLogPage {}
+    impl BoxImpl for FirewallLogPage {}
+}
@@ -9,6 +9,7 @@ use gtk4::glib;
 use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
 
+use super::density::{self, Role};
 use crate::i18n::gettext;
 
 mod imp {
@@ -51,10 +52,7 @@ impl HelpPage {
     fn setup_ui(&self) {
         // Page header
         let header_box = gtk::Box::new(gtk::Orientation::Vertical, 4);
-        header_box.set_margin_start(24);
-        header_box.set_margin_end(24);
-        header_box.set_margin_top(24);
-        header_box.set_margin_bottom(12);
+        density::track(&header_box, Role::PageHeader);
 
         let title = gtk::Label::new(Some(gettext("Help").as_str()));
         title.add_css_class("title-1");
@@ -95,7 +93,47 @@ impl HelpPage {
                 "The Overview page provides a summary of your system's security status. \
              It displays the current firewall state, active zone, number of open ports, \
              and running services. Use this page to get a quick assessment of your \
-             system's security configuration and identify potential issues.",
+             system's security configuration and identify potential issues. \
+             A banner suggests a zone for the network you are connected to, \
+             based on its type, name and security: public for open Wi-Fi and hotspots, work for \
+             802.1X networks, home for password-protected Wi-Fi. Apply moves the interface and \
+             sets the zone of its NetworkManager connection; dismissed suggestions are not shown again. \
+             When a new network interface appears, such as a USB tethering adapter or a container \
+             bridge, you are asked which zone it belongs in; otherwise it stays in the default zone. \
+             A red banner warns when another firewall manager (ufw, Shorewall, iptables-persistent, \
+             or the iptables or nftables services) is active or enabled next to firewalld: its rules \
+             are invisible to firewalld and can block what firewalld allows, or wipe firewalld's rules. \
+             Disable stops and disables it and reloads firewalld; the conflict is also reported by \
+             the security assessment. \
+             Another banner warns when services, ports or rich rules at runtime differ from the \
+             permanent configuration, so they would change at the next reload or reboot; Save \
+             Permanently copies the runtime rules over, like the Save Runtime to Permanent action. \
+             Review lists each difference per zone: a runtime only rule can be saved permanently \
+             or removed now, and a permanent only rule applied now or removed from the permanent \
+             configuration. \
+             The Security Score card rates how well the firewall protects the system from 0 to \
+             100: firewalld running, a default zone that does not let everything in, SSH not \
+             reachable through the public, external or dmz zone, no other services listening on \
+             all interfaces let through, panic mode off and runtime rules saved. Each check is \
+             worth a share of the points; a failed one has a button that fixes it or opens the \
+             page to fix it on, and removing ssh reverts itself unless you keep the change. \
+             The Bandwidth chart follows the download and upload rates of the interface chosen in \
+             its menu over the last five minutes, with its packets per second and the packets it \
+             dropped below. \
+             The Connections chart follows the number of remote connections over the last five \
+             minutes; By Process splits it into the five processes holding the most, which are \
+             listed below it. \
+             The Traffic Ratio chart compares the packets the firewall accepted and denied over \
+             the last five minutes. Denied packets are only counted when firewalld logs them; if \
+             its LogDenied setting is off, Log Denied Packets turns it on for all packets. \
+             The save button on each chart exports the samples it plots to a CSV or JSON file, \
+             one row per sample with its time, for use in a spreadsheet or script. \
+             Save Status Snapshot in the main menu saves the whole Overview as a PNG image stamped \
+             with the host name and time, ready to attach to a ticket. \
+             About Firewall in the main menu shows firewalld's version, whether it is running, \
+             still starting or failed to load its configuration (it then falls back to default \
+             rules), the packet filter it uses (nftables or iptables) and whether IPv4, IPv6, \
+             bridge filtering and IP sets are supported.",
             ),
         ));
 
@@ -105,7 +143,22 @@ impl HelpPage {
             &gettext("Firewall zones define trust levels for network connections. \
              The Zones page lets you view and manage firewalld zones such as public, home, work, and trusted. \
              Assign network interfaces to zones, configure default zones, and create custom zones \
-             for specific security requirements. Each zone has its own set of allowed services and ports.")
+             for specific security requirements. Each zone has its own set of allowed services and ports; \
+             its row counts its services, ports and rich rules and names the interfaces and sources \
+             bound to it, and expanding it lists them. \
+             Zones in use are checked against a baseline: services that should not be allowed at their \
+             trust level, such as samba or vnc-server in public, appear as chips that remove them in one click. \
+             The edit button of a zone changes its target, blocked ICMP types, masquerading, forwarding \
+             and description in the permanent configuration, reloading firewalld to apply them. \
+             New Zone creates a zone from a name, target and description; zones you created can be \
+             deleted while no interface or source uses them and they are not the default zone. \
+             Move Rules shows two zones side by side: select services and ports on one side, choose \
+             Move or Copy and press the arrow toward the other zone. The changes are made at runtime \
+             and permanently; if one fails, the others are undone and both zones stay as they were. \
+             The Topology map at the top links each interface and source to its zone and each zone to \
+             the services and ports it lets through. Expanding a zone shows ICMP Filtering: toggle \
+             each ICMP type, such as echo-request, timestamp-request or redirect, to block it at once \
+             and permanently, or turn on Invert to let only the selected types through.")
         ));
 
         // Services section
@@ -115,8 +168,27 @@ impl HelpPage {
                 "The Services page manages firewall service definitions. \
              Services are predefined combinations of ports and protocols (like HTTP, SSH, or DNS). \
              Enable or disable services for specific zones, view service details, \
-             and add custom service definitions. Using services is easier and more maintainable \
-             than managing individual port rules.",
+             and add custom service definitions. Select a service to see everything it \
+             defines: ports, whole protocols, source ports, helpers and destination addresses. \
+             The Create Custom Service button next to + defines a new service from a name, \
+             a description and ports such as 8080/tcp, 6000-6100/udp; it is saved permanently \
+             and firewalld reloads to offer it. The search matches names, descriptions and, \
+             as a number, the ports a service opens. The + button opens a picker that searches \
+             service names and descriptions as you type. A Runtime only badge marks services enabled \
+             only until firewalld reloads; the save button next to it makes them permanent. \
+             Disabling a service that remote SSH sessions come in on warns first and offers to \
+             try the change for 60 seconds, undoing it unless you keep it; disabling ssh itself \
+             is always undone after 60 seconds unless you keep it. Other services can be \
+             put back with the Undo button on the message that confirms they were disabled. \
+             Connection Tracking Helpers lists the enabled services of the zone that attach a \
+             helper, such as ftp or sip, and what each helper opens. Helpers parse untrusted \
+             traffic in the kernel and have been used to open ports behind firewalls, so disable \
+             services that attach one when the zone does not need them. Automatic Helpers shows \
+             whether loaded helpers inspect all matching traffic on their own; where firewalld \
+             still has the setting it can be changed here, and Off is recommended. \
+             Hovering a service name here, in the zone list and topology map, in zone imports and \
+             in drift reviews shows the ports and protocols it opens. Using \
+             services is easier and more maintainable than managing individual port rules.",
             ),
         ));
 
@@ -126,9 +198,208 @@ impl HelpPage {
             &gettext(
                 "The Ports page allows direct management of open ports. \
              Add or remove port rules for specific zones, specify TCP or UDP protocols, \
-             and set port ranges. View all currently open ports and their associated zones. \
+             and set port ranges such as 6000-6100, also when editing a rule. View all currently open ports and \
+             their associated zones. \
+             The Zone selector at the top narrows the lists to one zone's rules and preselects that \
+             zone when you add a port; All Zones shows everything. \
+             Below it, search by port number (a range matches the ports it covers), name, zone \
+             or protocol, and narrow the lists to TCP or UDP or to open or blocked rules. \
+             The Summary counts allowed and blocked ports per protocol, a range counting every \
+             port it covers, and charts how many ports were blocked over the last 90 days. \
              Use this page when you need to open ports for applications that don't have \
-             predefined service definitions.",
+             predefined service definitions. Under Rule Usage, read nftables hit counters to \
+             spot rules no traffic has matched and remove old unused ones. Rules missing from the \
+             permanent configuration carry a Runtime only badge, since a reload or restart drops \
+             them; the save button next to it makes them permanent. Closing or blocking a port \
+             that remote SSH sessions come in on warns first and offers to try the change for 60 \
+             seconds: unless you keep it in time, it is undone, so a lockout fixes itself. \
+             After each change the running firewall is read back: the message that confirms \
+             it says whether the port is verified open or closed and on which interfaces, or \
+             that the change is not yet in effect. Deleting a port, or removing unused rules, \
+             shows an Undo button for 10 seconds that adds the rules back to the runtime and \
+             permanent configurations they were removed from. Under Port Names, export the \
+             names and descriptions you gave rules to a file and import it on another machine \
+             so a team shares one naming scheme; only names travel, not the rules. An import \
+             names the matching rules that have no name yet and lists those named differently \
+             so you choose which names to replace; entries for rules this machine lacks are \
+             skipped.",
+            ),
+        ));
+
+        // Rich Rules section
+        content_box.append(&self.create_section(
+            &gettext("Rich Rules"),
+            &gettext(
+                "Rich rules match traffic more precisely than services and ports: by source or \
+             destination address, service, port, protocol or ICMP type, and can log, audit or \
+             rate-limit what they match. The Rich Rules page lists each zone's rules in plain words \
+             with the rule text below. Add Rule opens a builder with a field for every part of the \
+             rule and a live preview; Save stays disabled until the rule is valid. Editing a rule \
+             replaces it, and the original is restored if firewalld refuses the new one.",
+            ),
+        ));
+
+        // Blocked Sources section
+        content_box.append(&self.create_section(
+            &gettext("Blocked Sources"),
+            &gettext(
+                "The Blocked Sources page stops all traffic from an IPv4 or IPv6 address or a whole \
+             network in CIDR notation, such as 203.0.113.0/24 or 2001:db8::/32. Choose the zone, \
+             whether packets are dropped silently or rejected, and whether the block survives a \
+             reload. Blocks made elsewhere, such as from a connection's details, are listed too.",
+            ),
+        ));
+
+        // Blocklists section
+        content_box.append(&self.create_section(
+            &gettext("Blocklists"),
+            &gettext(
+                "Import published lists of bad addresses, such as the FireHOL or Spamhaus DROP \
+             lists, from a URL or a file with Import on the Blocked Sources page. Each list is \
+             loaded into a pair of firewalld ipsets whose traffic is dropped, so lists with \
+             hundreds of thousands of networks stay fast. Private and local ranges are skipped \
+             so a list cannot lock you out of your own network. Lists can be switched off \
+             without losing them, refreshed by hand, or refreshed every 6 hours, daily or \
+             weekly. Importing needs a firewall reload, so save or discard unsaved runtime \
+             changes first.",
+            ),
+        ));
+
+        // Rule Search section
+        content_box.append(&self.create_section(
+            &gettext("Rule Search"),
+            &gettext(
+                "Press Ctrl+Shift+F or the search button in the header to search every zone's ports, \
+             services, sources, interfaces and rich rules at once. Searching for a port number also \
+             finds the port ranges and service definitions that open it, so you can tell where a \
+             port such as 8443 is actually allowed. Switch on .* to search with a regular expression. \
+             Select a match to open the page that manages it.",
+            ),
+        ));
+
+        // Firewall Log section
+        content_box.append(&self.create_section(
+            &gettext("Firewall Log"),
+            &gettext(
+                "The small chart in the header bar shows packets accepted (blue) and denied \
+             (red) per second over the last minute; click it to open the Firewall Log page. \
+             The page lists the packets firewalld rejected or dropped, read from the kernel \
+             log, and sets which denied packets are logged at all, since logging is off by \
+             default. Reading the kernel log requires membership in the wheel, adm or \
+             systemd-journal group.",
+            ),
+        ));
+
+        // fail2ban section
+        content_box.append(&self.create_section(
+            &gettext("fail2ban"),
+            &gettext(
+                "If fail2ban runs next to firewalld, the fail2ban page lists its jails with the \
+             addresses each one bans now, its failure and ban counters, and totals across all \
+             jails. Unban lifts the ban on one address; click an address for its details. \
+             fail2ban usually only answers to root, so reading it asks for administrator \
+             authentication first.",
+            ),
+        ));
+
+        // IP Details section
+        content_box.append(&self.create_section(
+            &gettext("IP Details"),
+            &gettext(
+                "Click a remote address on the Overview, Connections, Network Exposure or \
+             Firewall Log page to see everything known about it: the zone sources and rich \
+             rules that apply to it, its packets in the firewall log, SSH logins and fail2ban \
+             bans from the last 30 days of the system journal, and its location. Block drops \
+             all of its traffic in the default zone; Trust adds it to the trusted zone.",
+            ),
+        ));
+
+        // Data Quotas section
+        content_box.append(&self.create_section(
+            &gettext("Data Quotas"),
+            &gettext(
+                "For metered connections such as mobile broadband, set a monthly quota per \
+             network interface under Preferences → Data Quotas. Security Center counts the data \
+             each interface sends and receives while it or the background monitor runs, keeps \
+             the count across restarts and starts again each calendar month. A notification \
+             appears when an interface reaches 50%, 90% and 100% of its quota, and the Overview \
+             shows a usage bar for each.",
+            ),
+        ));
+
+        // Tripwire Ports section
+        content_box.append(&self.create_section(
+            &gettext("Tripwire Ports"),
+            &gettext(
+                "Ports such as 23 (telnet) or 3389 (remote desktop) that nothing on this machine \
+             serves can act as tripwires: anyone connecting to them is probing. List them under \
+             Preferences → Tripwire Ports. Nothing listens on them; the firewall keeps denying \
+             the attempts, so Log Denied Packets must be on in the Firewall Log. Each probing \
+             address raises a notification with a button to block it in the default zone, or is \
+             blocked straight away when Block Probing Addresses is on.",
+            ),
+        ));
+
+        // Temporary Rules section
+        content_box.append(&self.create_section(
+            &gettext("Temporary Rules"),
+            &gettext(
+                "When adding a port rule, choose Remove After to open or block the port only for \
+             an hour, eight hours or a day. The Ports page shows until when each temporary rule \
+             stays. Ten minutes before it is removed a notification offers to extend it by an \
+             hour or remove it right away; once its time is up it is removed from the runtime \
+             and permanent configuration. Expiry is checked while Security Center or the \
+             background monitor runs.",
+            ),
+        ));
+
+        // Scheduled Scan section
+        content_box.append(&self.create_section(
+            &gettext("Scheduled Scan"),
+            &gettext(
+                "Choose a Scan Interval under Preferences → Scheduled Scan to scan the listening \
+             ports and run the Security Score checks every hour, six hours or day. A \
+             notification reports each port that became reachable from the network and each \
+             check that started failing since the previous scan; the first scan only records \
+             what is there. Scans run while Security Center or the background monitor runs.",
+            ),
+        ));
+
+        // System Tray section
+        content_box.append(&self.create_section(
+            &gettext("System Tray"),
+            &gettext(
+                "Turn on Show in System Tray under Preferences → Behavior to add a Security \
+             Center icon to the tray. Its menu opens the window, turns panic mode on or off and \
+             reloads the firewall; turning panic mode on asks for confirmation in the window \
+             first. While the icon shows, closing the window only hides it and Security Center \
+             keeps running until you choose Quit. The desktop needs a StatusNotifierItem tray; \
+             on GNOME that is the AppIndicator extension.",
+            ),
+        ));
+
+        // Safe Mode section
+        content_box.append(&self.create_section(
+            &gettext("Safe Mode"),
+            &gettext(
+                "Start Security Center with security-center --safe-mode to skip statistics \
+             collection, auto-refresh, the update check and background scans, for example on \
+             a constrained system or when a page seems to hang. Pages still load when opened. \
+             Turn Safe Mode off under Preferences → Behavior to resume the background work \
+             without restarting.",
+            ),
+        ));
+
+        // Background Monitor section
+        content_box.append(&self.create_section(
+            &gettext("Background Monitor"),
+            &gettext(
+                "Turn on Monitor in Background under Preferences to keep the scheduled cleanup, \
+             scheduled policy enforcement, scheduled security scan, data quota and tripwire \
+             alerts and temporary rule \
+             expiry running while Security Center is closed. They then run in security-center-monitor, a systemd \
+             user service started on login, and Security Center leaves them to it while it is \
+             open. Clicking one of its notifications opens Security Center.",
             ),
         ));
 
@@ -140,7 +411,24 @@ impl HelpPage {
              Monitor the status of services like firewalld, NetworkManager, and other \
              security-related daemons. Start, stop, enable, or disable services directly \
              from this interface. Ensure critical security services are running and \
-             configured to start at boot.",
+             configured to start at boot. The menu at the end of each row reloads a \
+             service's configuration and masks a service that should never run, which stops \
+             it and keeps anything from starting it until it is unmasked. \
+             The score next to each service rates its sandboxing from 0 (fully sandboxed) \
+             to 10 (unrestricted), like systemd-analyze security: running as its own user, \
+             a private network and /tmp, read-only system files and similar settings lower \
+             it. Click the score to see which settings the service uses and which it lacks. \
+             The log button shows the last lines the service wrote to the journal, errors and \
+             warnings highlighted, for example to find out why it failed; the play button \
+             follows new lines as they are logged. Reading the logs of system services needs \
+             membership in the systemd-journal or adm group. \
+             The edit button shows the service's unit file and drop-ins, and can add \
+             NoNewPrivileges, ProtectSystem and PrivateDevices in a drop-in of its own; \
+             applying reloads systemd and restarts the service, and switching a setting off \
+             removes it again. \
+             Switch to All Services to list every service of the system, or your own user \
+             services, and narrow the list down by state, by whether they start at boot and by \
+             name or description.",
             ),
         ));
 
@@ -152,7 +440,128 @@ impl HelpPage {
              View listening ports and their associated processes, identify potentially \
              unnecessary exposed services, and get recommendations for reducing your \
              network footprint. This helps you understand what services are accessible \
-             from the network and minimize security risks.",
+             from the network and minimize security risks. A port you named on the Ports page \
+             shows that name next to the program listening on it, and its description when \
+             expanded, so what you meant to open can be compared with what actually listens. \
+             The By Interface section shows, \
+             for each network interface, which listening ports its firewall zone lets \
+             through, so a service open on the LAN but filtered on Wi-Fi is told apart. \
+             The Remote Access section lists the installed ways to log in remotely (SSH, the \
+             Cockpit web console, VNC, RDP and Telnet) with whether each runs, starts at boot \
+             and is open in a zone in use. Its switch turns the method on, starting its service \
+             and allowing it in the default zone, or off, removing it from every zone and \
+             stopping and disabling the service. The section also lists network cards with Wake-on-LAN enabled and \
+             remote management services such as VNC, RDP or IPMI that other hosts can reach. Disabling Wake-on-LAN lasts until \
+             the next boot, when the network configuration may turn it on again. \
+             The Network Namespaces section lists containers, VPNs and sandboxes that \
+             have a network stack of their own, named by `ip netns` or by a process \
+             inside them. Scanning one lists its open ports and connections; this needs \
+             administrator rights, and the firewall zones of this computer do not apply \
+             inside it. The shield button in the header checks each listening program \
+             against the package that installed it (rpm -V or dpkg --verify) and lists \
+             the results under Binary Integrity. A program no package owns, or whose \
+             contents differ from its package, is flagged on its port as a possible sign \
+             of compromise; a program replaced on disk since it started usually waits for \
+             a restart after an update. Quarantine on a flagged program records its \
+             SHA-256 and command line, blocks its ports in every active zone and stops its \
+             system service; each step is kept with its time in an incident on the \
+             Incidents page. \
+             Expanding a port shows the user its program runs as and its effective \
+             capabilities. A program running as root with all privileges is flagged when it \
+             could run as its own user instead, and is also reported by the security \
+             assessment. Which program opened a socket is only known for your own programs \
+             unless the application runs as root.",
+            ),
+        ));
+
+        // Applications section
+        content_box.append(&self.create_section(
+            &gettext("Applications"),
+            &gettext(
+                "The Applications page groups listening ports by the program that owns them \
+             and shows, for each port, which zones let it in and why: an open port, an \
+             enabled service, a rich rule or a zone that accepts everything. A program \
+             marked as blocked listens, but no zone lets connections to it through. \
+             Programs of other users show their command name instead of the executable \
+             path. Open Without a Listener lists ports and services the zones open \
+             although nothing listens on them; closing them on the Ports and Services \
+             pages keeps a program started later from becoming reachable unnoticed.",
+            ),
+        ));
+
+        // Incidents section
+        content_box.append(&self.create_section(
+            &gettext("Incidents"),
+            &gettext(
+                "The Incidents page keeps a record of anything worth looking into: a title, \
+             a severity, notes and a status that moves from Open through Investigating to \
+             Resolved. Add Evidence attaches the current listening ports, the most recent \
+             denied packets or pasted text, such as a log excerpt, each with the time it \
+             was added. Every change is kept on the incident's timeline. Quarantining a \
+             process opens an incident with its snapshot and the steps taken, and warnings \
+             printed by script hooks in ~/.config/security-center/hooks open one each. \
+             Export saves \
+             a record as Markdown, or as JSON when the file name ends in .json. Records \
+             are stored in ~/.config/security-center/incidents.json.",
+            ),
+        ));
+
+        // Platform Security section
+        content_box.append(&self.create_section(
+            &gettext("Platform Security"),
+            &gettext(
+                "The Platform Security page checks host settings that other protections \
+             depend on. It shows whether the clock is synchronized, which time servers \
+             are used and whether they are authenticated with NTS, since a wrong clock \
+             breaks certificate validation and makes logs unreliable. It also reports \
+             whether a TPM is present and whether the boot chain is measured into it, \
+             lists attached FIDO2 and U2F security keys and can lock the session as soon as one \
+             is pulled out (the background monitor keeps doing so while the app is closed), \
+             and audits the bootloader password and kernel parameters such as \
+             selinux=0 or mitigations=off, with guidance on how to fix each finding. \
+             The Mandatory Access Control section shows whether SELinux enforces its policy \
+             or AppArmor is active with how many profiles, lists the denials of the last 7 \
+             days, and switches SELinux to permissive mode until the next boot after \
+             authentication. The Tor and Proxies section shows whether a Tor daemon runs, \
+             every proxy set in the desktop settings, the environment, shell startup files and \
+             package managers, and which programs are connected to one right now. A proxy set \
+             for every user in a shell startup file or for all system services is flagged: \
+             desktops and package managers keep their own proxy settings, and malware sets \
+             one there to intercept traffic. The Core Dumps section shows whether the memory of crashing programs is \
+             stored and lets you restrict it, and a hardening score summarises all \
+             findings. The Logging section shows whether the journal survives reboots, lets you \
+             change its storage and size limit, and checks that auditd is running. When \
+             OpenSCAP is installed, the Security Benchmarks section runs a benchmark profile such as \
+             CIS and lists the failed rules, with a shortcut to the page that fixes those \
+             this application manages.",
+            ),
+        ));
+
+        // Antivirus section
+        content_box.append(&self.create_section(
+            &gettext("Antivirus"),
+            &gettext(
+                "The Antivirus page works with ClamAV when it is installed. It shows the engine \
+             version, how old the signature database is, flagging signatures older than a \
+             week, and whether clamd and the freshclam updater are running. Choose folders, \
+             Downloads by default, and Scan Now checks every file inside them; progress and \
+             detections appear while the scan runs. Scans use clamd when it runs, and \
+             otherwise clamscan, which loads the signatures first and takes longer. Nothing \
+             found is deleted or moved.",
+            ),
+        ));
+
+        // Compliance section
+        content_box.append(&self.create_section(
+            &gettext("Compliance"),
+            &gettext(
+                "The Compliance page compares the system with a desired-state policy kept in \
+             ~/.config/security-center/policy.toml: the default zone, the services and ports \
+             allowed in each zone, services that must be running and sysctl values. Create \
+             the file from the current firewall configuration, edit it, and the page lists \
+             every difference. Enforce corrects them, making firewall changes permanent; \
+             enforcement can also run automatically on a schedule while the app is open. \
+             Zones marked exclusive also report services and ports that are not listed.",
             ),
         ));
 
@@ -163,7 +572,31 @@ impl HelpPage {
                 "Quick Actions provides one-click security operations for common tasks. \
              Enable panic mode to immediately block all network traffic, toggle the firewall, \
              apply preset security profiles, or reset to default settings. \
-             Use these actions for emergency situations or quick configuration changes.",
+             Use these actions for emergency situations or quick configuration changes. \
+             Secure for Travel previews and applies several changes at once: the public zone \
+             as default, file sharing stopped, a random MAC address per network, a stricter \
+             SSH server and a prompt screen lock. Switch off any step you want to skip. \
+             Security Checklists lists what to check on a web server, a workstation or a home \
+             NAS. Tick items off as you go; your progress is saved. Each item opens the page \
+             where it is checked, and Fix makes the change when one action covers it. \
+             Actions on firewalld, NetworkManager or the SSH server pause while that service is \
+             starting, stopping or reloading, and the reason is shown next to them; the Overview \
+             shows the live state of all three. Changes that can cut this machine off the network \
+             (panic mode, drop or block as the default zone, and removing the ssh service) apply at \
+             once, but a banner counts down 60 seconds and undoes them unless you click Keep \
+             Changes.",
+            ),
+        ));
+
+        // Local API section
+        content_box.append(&self.create_section(
+            &gettext("Local API"),
+            &gettext(
+                "Scripts and local dashboards can read Security Center data over HTTP once the \
+             Local API is enabled in Preferences. It listens on 127.0.0.1 only and answers GET \
+             requests to /status, /exposure and /rules with JSON. Every request must carry the \
+             token stored in ~/.config/security-center/api-token, for example: \
+             curl -H \"Authorization: Bearer $TOKEN\" http://127.0.0.1:8770/status",
             ),
         ));
 
@@ -202,3 +635,9 @@ impl HelpPage {
         section
     }
 }
+
+impl Default for HelpPage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,795 @@
+// Security Center - Incidents Page
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Incidents page: the incident records with their notes, evidence and
+//! timelines, a form to open one, and export of a record to a file.
+
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+
+use gtk4::gio;
+use gtk4::glib;
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+use tracing::error;
+
+use super::density::{self, Role};
+use crate::admin::{DeniedLog, Severity};
+use crate::i18n::{format_datetime, gettext};
+use crate::incidents::{self, Evidence, EvidenceKind, Incident, IncidentStatus};
+
+/// Severities offered in the forms, mildest first.
+const SEVERITIES: [Severity; 4] = [
+    Severity::Info,
+    Severity::Low,
+    Severity::Medium,
+    Severity::High,
+];
+
+/// Sources of evidence in the Add Evidence form, in its order.
+const EVIDENCE_SOURCES: [&str; 3] = ["Listening Ports", "Recent Denied Packets", "Text"];
+
+glib::wrapper! {
+    /// Incidents page listing the incident records.
+    pub struct IncidentsPage(ObjectSubclass<imp::IncidentsPage>)
+        @extends gtk4::Box, gtk4::Widget,
+        @implements gtk4::Orientable;
+}
+
+impl IncidentsPage {
+    /// Create a new incidents page.
+    pub fn new() -> Self {
+        let page: Self = glib::Object::new();
+        page.setup_ui();
+        page
+    }
+
+    /// Set the kernel log follower denied packets are attached from. The
+    /// main window owns it.
+    pub fn set_denied_log(&self, log: &Rc<DeniedLog>) {
+        self.imp().denied_log.replace(Rc::downgrade(log));
+    }
+
+    /// Setup the UI.
+    fn setup_ui(&self) {
+        let imp = self.imp();
+
+        self.set_orientation(gtk4::Orientation::Vertical);
+        self.set_spacing(0);
+
+        // Header
+        let header_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(12)
+            .build();
+        density::track(&header_box, Role::PageHeader);
+
+        let title_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(4)
+            .hexpand(true)
+            .build();
+
+        let title = gtk4::Label::builder()
+            .label(gettext("Incidents"))
+            .css_classes(vec!["title-1".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        let subtitle = gtk4::Label::builder()
+            .label(gettext(
+                "What happened, what was found and what was done about it",
+            ))
+            .css_classes(vec!["dim-label".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        title_box.append(&title);
+        title_box.append(&subtitle);
+        header_box.append(&title_box);
+
+        let new_button = gtk4::Button::builder()
+            .label(gettext("New Incident"))
+            .css_classes(vec!["suggested-action".to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+
+        let page = self.clone();
+        new_button.connect_clicked(move |_| {
+            page.show_new_dialog();
+        });
+        header_box.append(&new_button);
+        self.append(&header_box);
+
+        // Scrolled container
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .vexpand(true)
+            .hexpand(true)
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .build();
+        self.append(&scrolled);
+
+        let content = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .hexpand(true)
+            .build();
+        density::track(
+            &content,
+            Role::Content {
+                margin: 24,
+                spacing: 24,
+            },
+        );
+        scrolled.set_child(Some(&content));
+
+        let empty = adw::StatusPage::builder()
+            .icon_name("folder-documents-symbolic")
+            .title(gettext("No Incidents"))
+            .description(gettext(
+                "Open an incident to keep notes and evidence about something suspicious. \
+                 Quarantining a process opens one automatically.",
+            ))
+            .vexpand(true)
+            .build();
+        content.append(&empty);
+
+        imp.content.replace(Some(content));
+        imp.empty.replace(Some(empty));
+    }
+
+    /// Show a toast message.
+    fn show_toast(&self, message: &str) {
+        if let Some(window) = self.main_window() {
+            window.show_toast(message);
+        }
+    }
+
+    fn main_window(&self) -> Option<super::MainWindow> {
+        self.root()
+            .and_then(|root| root.downcast::<gtk4::Window>().ok())
+            .and_then(|window| window.downcast::<super::MainWindow>().ok())
+    }
+
+    /// Reload the incident log.
+    pub fn refresh(&self) {
+        let imp = self.imp();
+        let Some(content) = imp.content.borrow().clone() else {
+            return;
+        };
+        for group in imp.groups.borrow_mut().drain(..) {
+            content.remove(&group);
+        }
+
+        let incidents = incidents::load_incidents();
+        let (resolved, active): (Vec<&Incident>, Vec<&Incident>) = incidents
+            .iter()
+            .partition(|incident| incident.status == IncidentStatus::Resolved);
+
+        let mut groups = Vec::new();
+        for (title, list) in [(gettext("Active"), active), (gettext("Resolved"), resolved)] {
+            if list.is_empty() {
+                continue;
+            }
+            let group = adw::PreferencesGroup::builder().title(title).build();
+            for incident in list {
+                group.add(&self.create_incident_row(incident));
+            }
+            content.append(&group);
+            groups.push(group);
+        }
+
+        if let Some(empty) = imp.empty.borrow().as_ref() {
+            empty.set_visible(groups.is_empty());
+        }
+        imp.groups.replace(groups);
+    }
+
+    /// Save a changed incident and reload the list.
+    fn save(&self, incident: &Incident) {
+        self.imp().expanded.set(Some(incident.id));
+        if let Err(e) = incidents::update_incident(incident) {
+            error!("Failed to save incident {}: {}", incident.id, e);
+            self.show_toast(&format!(
+                "{}: {}",
+                gettext("Failed to save the incident"),
+                e
+            ));
+        }
+        // The change may come from a row about to be replaced
+        let page = self.clone();
+        glib::idle_add_local_once(move || page.refresh());
+    }
+
+    /// Row for one incident: its settings, notes, evidence, timeline and
+    /// actions.
+    fn create_incident_row(&self, incident: &Incident) -> adw::ExpanderRow {
+        let subtitle = format!(
+            "{} • {} • {}",
+            format_datetime(incident.opened_at),
+            gettext(incident.status.label()),
+            gettext("%d evidence items").replace("%d", &incident.evidence.len().to_string())
+        );
+        let row = adw::ExpanderRow::builder()
+            .title(glib::markup_escape_text(&incident.title).as_str())
+            .subtitle(subtitle)
+            .expanded(self.imp().expanded.get() == Some(incident.id))
+            .build();
+        let icon = gtk4::Image::from_icon_name(incident.severity.icon());
+        icon.set_tooltip_text(Some(gettext(incident.severity.label()).as_str()));
+        row.add_prefix(&icon);
+
+        let status_row = adw::ComboRow::builder()
+            .title(gettext("Status"))
+            .model(&gtk4::StringList::new(
+                &IncidentStatus::ALL
+                    .iter()
+                    .map(|s| gettext(s.label()))
+                    .collect::<Vec<_>>()
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>(),
+            ))
+            .selected(
+                IncidentStatus::ALL
+                    .iter()
+                    .position(|s| *s == incident.status)
+                    .unwrap_or(0) as u32,
+            )
+            .build();
+        let page = self.clone();
+        let current = incident.clone();
+        status_row.connect_selected_notify(move |combo| {
+            if let Some(status) = IncidentStatus::ALL.get(combo.selected() as usize) {
+                let mut incident = current.clone();
+                incident.set_status(*status);
+                page.save(&incident);
+            }
+        });
+        row.add_row(&status_row);
+
+        let severity_row = adw::ComboRow::builder()
+            .title(gettext("Severity"))
+            .model(&Self::severity_model())
+            .selected(
+                SEVERITIES
+                    .iter()
+                    .position(|s| *s == incident.severity)
+                    .unwrap_or(0) as u32,
+            )
+            .build();
+        let page = self.clone();
+        let current = incident.clone();
+        severity_row.connect_selected_notify(move |combo| {
+            if let Some(severity) = SEVERITIES.get(combo.selected() as usize) {
+                let mut incident = current.clone();
+                incident.set_severity(*severity);
+                page.save(&incident);
+            }
+        });
+        row.add_row(&severity_row);
+
+        let notes = if incident.notes.is_empty() {
+            gettext("No notes")
+        } else {
+            incident.notes.clone()
+        };
+        let notes_row = adw::ActionRow::builder()
+            .title(gettext("Notes"))
+            .subtitle(glib::markup_escape_text(&notes).as_str())
+            .subtitle_selectable(true)
+            .build();
+        let edit_button = gtk4::Button::builder()
+            .icon_name("document-edit-symbolic")
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat"])
+            .tooltip_text(gettext("Edit Notes"))
+            .build();
+        let page = self.clone();
+        let current = incident.clone();
+        edit_button.connect_clicked(move |_| {
+            page.show_notes_dialog(&current);
+        });
+        notes_row.add_suffix(&edit_button);
+        row.add_row(&notes_row);
+
+        for evidence in &incident.evidence {
+            row.add_row(&self.create_evidence_row(evidence));
+        }
+
+        for entry in &incident.timeline {
+            let step = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&entry.message).as_str())
+                .subtitle(format_datetime(entry.at))
+                .build();
+            let (icon_name, class) = if entry.ok {
+                ("emblem-ok-symbolic", "success")
+            } else {
+                ("dialog-error-symbolic", "error")
+            };
+            let icon = gtk4::Image::from_icon_name(icon_name);
+            icon.add_css_class(class);
+            step.add_prefix(&icon);
+            row.add_row(&step);
+        }
+
+        row.add_row(&self.create_actions_row(incident));
+        row
+    }
+
+    /// Row for one piece of evidence, with a button showing its content.
+    fn create_evidence_row(&self, evidence: &Evidence) -> adw::ActionRow {
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(&evidence.title).as_str())
+            .subtitle(format!(
+                "{} • {}",
+                gettext(evidence.kind.label()),
+                format_datetime(evidence.added_at)
+            ))
+            .build();
+        row.add_prefix(&gtk4::Image::from_icon_name(evidence.kind.icon()));
+
+        let view_button = gtk4::Button::builder()
+            .icon_name("view-reveal-symbolic")
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat"])
+            .tooltip_text(gettext("Show Evidence"))
+            .build();
+        let page = self.clone();
+        let evidence = evidence.clone();
+        view_button.connect_clicked(move |_| {
+            page.show_evidence_dialog(&evidence);
+        });
+        row.add_suffix(&view_button);
+        row
+    }
+
+    /// Row with the Add Evidence, Export and Delete buttons.
+    fn create_actions_row(&self, incident: &Incident) -> adw::ActionRow {
+        let row = adw::ActionRow::new();
+        let button_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(8)
+            .halign(gtk4::Align::End)
+            .hexpand(true)
+            .margin_top(6)
+            .margin_bottom(6)
+            .build();
+
+        let add_button = gtk4::Button::with_label(&gettext("Add Evidence"));
+        let page = self.clone();
+        let current = incident.clone();
+        add_button.connect_clicked(move |_| {
+            page.show_evidence_form(&current);
+        });
+        button_box.append(&add_button);
+
+        let export_button = gtk4::Button::with_label(&gettext("Export"));
+        let page = self.clone();
+        let current = incident.clone();
+        export_button.connect_clicked(move |_| {
+            page.export(&current);
+        });
+        button_box.append(&export_button);
+
+        let delete_button = gtk4::Button::builder()
+            .label(gettext("Delete"))
+            .css_classes(["destructive-action"])
+            .build();
+        let page = self.clone();
+        let current = incident.clone();
+        delete_button.connect_clicked(move |_| {
+            page.confirm_delete(&current);
+        });
+        button_box.append(&delete_button);
+
+        row.set_child(Some(&button_box));
+        row
+    }
+
+    fn severity_model() -> gtk4::StringList {
+        let labels: Vec<String> = SEVERITIES.iter().map(|s| gettext(s.label())).collect();
+        gtk4::StringList::new(&labels.iter().map(String::as_str).collect::<Vec<_>>())
+    }
+
+    /// A multi-line text editor in a frame, for notes and pasted evidence.
+    fn create_text_editor(text: &str, monospace: bool) -> (gtk4::Frame, gtk4::TextView) {
+        let view = gtk4::TextView::builder()
+            .wrap_mode(gtk4::WrapMode::WordChar)
+            .monospace(monospace)
+            .top_margin(8)
+            .bottom_margin(8)
+            .left_margin(8)
+            .right_margin(8)
+            .build();
+        view.buffer().set_text(text);
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .min_content_height(160)
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .child(&view)
+            .build();
+        let frame = gtk4::Frame::builder().child(&scrolled).build();
+        (frame, view)
+    }
+
+    fn text_of(view: &gtk4::TextView) -> String {
+        let buffer = view.buffer();
+        buffer
+            .text(&buffer.start_iter(), &buffer.end_iter(), false)
+            .to_string()
+    }
+
+    /// Present the form to open an incident.
+    fn show_new_dialog(&self) {
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("New Incident"))
+            .body(gettext("Evidence can be attached once it is open"))
+            .build();
+
+        let form = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(12)
+            .build();
+        let group = adw::PreferencesGroup::new();
+        let title_entry = adw::EntryRow::builder()
+            .title(gettext("Title (e.g. SSH probes from 203.0.113.7)"))
+            .build();
+        group.add(&title_entry);
+        let severity_row = adw::ComboRow::builder()
+            .title(gettext("Severity"))
+            .model(&Self::severity_model())
+            .selected(2)
+            .build();
+        group.add(&severity_row);
+        form.append(&group);
+
+        let notes_label = gtk4::Label::builder()
+            .label(gettext("Notes"))
+            .css_classes(vec!["heading".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+        form.append(&notes_label);
+        let (notes_frame, notes_view) = Self::create_text_editor("", false);
+        form.append(&notes_frame);
+
+        dialog.set_extra_child(Some(&form));
+        dialog.add_response("cancel", "_Cancel");
+        dialog.add_response("open", &gettext("_Open Incident"));
+        dialog.set_response_appearance("open", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("open"));
+
+        // An incident needs a title
+        dialog.set_response_enabled("open", false);
+        let dialog_clone = dialog.clone();
+        title_entry.connect_changed(move |entry| {
+            dialog_clone.set_response_enabled("open", !entry.text().trim().is_empty());
+        });
+
+        let page = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "open" {
+                return;
+            }
+            let severity = SEVERITIES
+                .get(severity_row.selected() as usize)
+                .copied()
+                .unwrap_or(Severity::Medium);
+            let mut incident = Incident::new(title_entry.text().as_str(), severity);
+            incident.notes = Self::text_of(&notes_view).trim().to_string();
+            match incidents::add_incident(incident) {
+                Ok(id) => {
+                    page.imp().expanded.set(Some(id));
+                    page.refresh();
+                }
+                Err(e) => {
+                    error!("Failed to open an incident: {}", e);
+                    page.show_toast(&format!(
+                        "{}: {}",
+                        gettext("Failed to open the incident"),
+                        e
+                    ));
+                }
+            }
+        });
+
+        if let Some(window) = self.main_window() {
+            dialog.present(Some(&window));
+        }
+    }
+
+    /// Present the notes of `incident` for editing.
+    fn show_notes_dialog(&self, incident: &Incident) {
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("Edit Notes"))
+            .body(glib::markup_escape_text(&incident.title).as_str())
+            .build();
+        let (frame, view) = Self::create_text_editor(&incident.notes, false);
+        dialog.set_extra_child(Some(&frame));
+        dialog.add_response("cancel", "_Cancel");
+        dialog.add_response("save", &gettext("_Save"));
+        dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+
+        let page = self.clone();
+        let incident = incident.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response == "save" {
+                let mut incident = incident.clone();
+                incident.set_notes(Self::text_of(&view).trim());
+                page.save(&incident);
+            }
+        });
+
+        if let Some(window) = self.main_window() {
+            dialog.present(Some(&window));
+        }
+    }
+
+    /// Show the content of a piece of evidence.
+    fn show_evidence_dialog(&self, evidence: &Evidence) {
+        let dialog = adw::Dialog::builder()
+            .title(&evidence.title)
+            .content_width(640)
+            .content_height(480)
+            .build();
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&adw::HeaderBar::new());
+
+        let view = gtk4::TextView::builder()
+            .editable(false)
+            .monospace(true)
+            .wrap_mode(gtk4::WrapMode::WordChar)
+            .top_margin(12)
+            .bottom_margin(12)
+            .left_margin(12)
+            .right_margin(12)
+            .build();
+        view.buffer().set_text(&evidence.content);
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .vexpand(true)
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .child(&view)
+            .build();
+        toolbar.set_content(Some(&scrolled));
+        dialog.set_child(Some(&toolbar));
+
+        if let Some(window) = self.main_window() {
+            dialog.present(Some(&window));
+        }
+    }
+
+    /// Present the form to attach evidence to `incident`.
+    fn show_evidence_form(&self, incident: &Incident) {
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("Add Evidence"))
+            .body(glib::markup_escape_text(&incident.title).as_str())
+            .build();
+
+        let form = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(12)
+            .build();
+        let group = adw::PreferencesGroup::new();
+        let sources: Vec<String> = EVIDENCE_SOURCES.iter().map(|s| gettext(s)).collect();
+        let source_row = adw::ComboRow::builder()
+            .title(gettext("Source"))
+            .model(&gtk4::StringList::new(
+                &sources.iter().map(String::as_str).collect::<Vec<_>>(),
+            ))
+            .build();
+        group.add(&source_row);
+        let title_entry = adw::EntryRow::builder()
+            .title(gettext("Title (optional)"))
+            .build();
+        group.add(&title_entry);
+        form.append(&group);
+
+        // Only pasted text needs the editor
+        let (text_frame, text_view) = Self::create_text_editor("", true);
+        text_frame.set_visible(false);
+        form.append(&text_frame);
+        let frame = text_frame.clone();
+        source_row.connect_selected_notify(move |combo| {
+            frame.set_visible(combo.selected() == 2);
+        });
+
+        dialog.set_extra_child(Some(&form));
+        dialog.add_response("cancel", "_Cancel");
+        dialog.add_response("add", &gettext("_Add"));
+        dialog.set_response_appearance("add", adw::ResponseAppearance::Suggested);
+
+        let page = self.clone();
+        let incident = incident.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "add" {
+                return;
+            }
+            let title = title_entry.text().trim().to_string();
+            match source_row.selected() {
+                0 => page.attach_listening_ports(&incident, title),
+                1 => page.attach_denied_packets(&incident, title),
+                _ => {
+                    let text = Self::text_of(&text_view);
+                    if text.trim().is_empty() {
+                        page.show_toast(&gettext("Nothing to attach"));
+                        return;
+                    }
+                    let title = if title.is_empty() {
+                        gettext("Note")
+                    } else {
+                        title
+                    };
+                    let mut incident = incident.clone();
+                    incident.attach(EvidenceKind::Other, &title, &text);
+                    page.save(&incident);
+                }
+            }
+        });
+
+        if let Some(window) = self.main_window() {
+            dialog.present(Some(&window));
+        }
+    }
+
+    /// Scan the listening sockets and attach them to `incident`.
+    fn attach_listening_ports(&self, incident: &Incident, title: String) {
+        let page = self.clone();
+        let mut incident = incident.clone();
+        glib::spawn_future_local(async move {
+            let result = gio::spawn_blocking(incidents::listening_ports_excerpt).await;
+            match result {
+                Ok(Ok(excerpt)) => {
+                    let title = if title.is_empty() {
+                        gettext("Listening ports")
+                    } else {
+                        title
+                    };
+                    incident.attach(EvidenceKind::ScanResult, &title, &excerpt);
+                    page.save(&incident);
+                }
+                Ok(Err(e)) => {
+                    page.show_toast(&format!("{}: {}", gettext("Failed to scan the ports"), e));
+                }
+                Err(_) => page.show_toast(&gettext("Failed to scan the ports")),
+            }
+        });
+    }
+
+    /// Attach the most recent denied packets to `incident`.
+    fn attach_denied_packets(&self, incident: &Incident, title: String) {
+        let packets = self
+            .imp()
+            .denied_log
+            .borrow()
+            .upgrade()
+            .map(|log| log.recent())
+            .unwrap_or_default();
+        if packets.is_empty() {
+            self.show_toast(&gettext("No denied packets have been logged"));
+            return;
+        }
+        let title = if title.is_empty() {
+            gettext("Denied packets")
+        } else {
+            title
+        };
+        let mut incident = incident.clone();
+        incident.attach(
+            EvidenceKind::LogExcerpt,
+            &title,
+            &incidents::denied_packets_excerpt(&packets),
+        );
+        self.save(&incident);
+    }
+
+    /// Ask for a file and export `incident` to it.
+    fn export(&self, incident: &Incident) {
+        let Some(window) = self.main_window() else {
+            return;
+        };
+        let name = format!(
+            "incident-{}.md",
+            chrono::DateTime::from_timestamp(incident.opened_at, 0)
+                .map(|dt| dt
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d-%H%M")
+                    .to_string())
+                .unwrap_or_else(|| incident.id.to_string())
+        );
+        let dialog = gtk4::FileDialog::builder()
+            .title(gettext("Export Incident"))
+            .initial_name(name)
+            .modal(true)
+            .build();
+        let page = self.clone();
+        let incident = incident.clone();
+        dialog.save(Some(&window), gio::Cancellable::NONE, move |result| {
+            // Cancelling the dialog is not an error
+            let Ok(file) = result else {
+                return;
+            };
+            let Some(path) = file.path() else {
+                page.show_toast(&gettext("Choose a local file"));
+                return;
+            };
+            match incident.export(&path) {
+                Ok(()) => page.show_toast(
+                    &gettext("Incident exported to %s").replace("%s", &path.display().to_string()),
+                ),
+                Err(e) => page.show_toast(&format!(
+                    "{}: {}",
+                    gettext("Failed to export the incident"),
+                    e
+                )),
+            }
+        });
+    }
+
+    /// Ask before deleting `incident`.
+    fn confirm_delete(&self, incident: &Incident) {
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("Delete Incident?"))
+            .body(
+                gettext("“%s” and its evidence are removed. Export it first to keep a copy.")
+                    .replace("%s", &incident.title),
+            )
+            .build();
+        dialog.add_response("cancel", "_Cancel");
+        dialog.add_response("delete", &gettext("_Delete"));
+        dialog.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
+        dialog.set_default_response(Some("cancel"));
+
+        let page = self.clone();
+        let id = incident.id;
+        dialog.connect_response(None, move |_, response| {
+            if response != "delete" {
+                return;
+            }
+            if let Err(e) = incidents::delete_incident(id) {
+                page.show_toast(&format!(
+                    "{}: {}",
+                    gettext("Failed to delete the incident"),
+                    e
+                ));
+            }
+            page.refresh();
+        });
+
+        if let Some(window) = self.main_window() {
+            dialog.present(Some(&window));
+        }
+    }
+}
+
+impl Default for IncidentsPage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct IncidentsPage {
+        pub content: RefCell<Option<gtk4::Box>>,
+        pub empty: RefCell<Option<adw::StatusPage>>,
+        /// Active and resolved incidents, replaced on every refresh.
+        pub groups: RefCell<Vec<adw::PreferencesGroup>>,
+        /// Incident kept expanded across refreshes, the last one changed.
+        pub expanded: Cell<Option<u64>>,
+        pub denied_log: RefCell<Weak<DeniedLog>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for IncidentsPage {
+        const NAME: &'static str = "SecurityCenterIncidentsPage";
+        type Type = super::IncidentsPage;
+        type ParentType = gtk4::Box;
+    }
+
+    impl ObjectImpl for IncidentsPage {}
+    impl WidgetImpl for IncidentsPage {}
+    impl BoxImpl for IncidentsPage {}
+}
@@ -3,31 +3,58 @@
 // SPDX-License-Identifier: MIT
 
 //! A reusable window that shows everything known about a single remote IP:
-//! the local connection facts and offline country instantly, plus an optional
-//! on-demand online lookup (ISP, city, region, ASN, reverse DNS) and quick
-//! links to open the address on external reputation/geolocation services.
+//! the local connection facts and offline country instantly, the firewall's
+//! view of it (rules naming it, denied packets, SSH logins and fail2ban bans)
+//! with quick block and trust actions, plus an optional on-demand online
+//! lookup (ISP, city, region, ASN, reverse DNS) and quick links to open the
+//! address on external reputation/geolocation services.
 
+use std::cell::RefCell;
 use std::net::IpAddr;
+use std::rc::Rc;
 
 use gtk4::glib;
 use gtk4::prelude::*;
 use libadwaita as adw;
 use libadwaita::prelude::*;
 
-use crate::admin::IpDetails;
-use crate::i18n::gettext;
+use super::rule_search::{kind_icon, kind_label};
+use crate::admin::{AuthEventKind, DeniedPacket, DeniedSummary, IpDetails, SourceHistory};
+use crate::firewall::{rules_for_address, RuleMatch};
+use crate::i18n::{format_bytes, format_datetime, gettext};
 
-/// Everything the caller already knows about the connection to `ip`.
+/// History entries listed before the rest are left out.
+const MAX_HISTORY_ROWS: usize = 25;
+
+/// Everything the caller already knows about `ip`.
 pub struct IpDetailsContext {
     pub ip: IpAddr,
+    /// The connection the address was opened from, if any.
+    pub connection: Option<ConnectionFacts>,
+    /// Offline "flag + country" label, when the local GeoIP database resolves.
+    /// Looked up in the background when `None`.
+    pub country_label: Option<String>,
+}
+
+impl IpDetailsContext {
+    /// Context for an address seen outside a connection, e.g. in a log.
+    pub fn for_address(ip: IpAddr) -> Self {
+        Self {
+            ip,
+            connection: None,
+            country_label: None,
+        }
+    }
+}
+
+/// A local connection to the address.
+pub struct ConnectionFacts {
     pub port: u16,
     pub protocol: String,
     pub process: Option<String>,
     pub pid: Option<u32>,
     pub bytes_in: u64,
     pub bytes_out: u64,
-    /// Offline "flag + country" label, when the local GeoIP database resolves.
-    pub country_label: Option<String>,
 }
 
 /// Rows whose values are filled in once the online lookup returns.
@@ -90,28 +117,40 @@ pub fn present_ip_details(parent: &impl IsA<gtk4::Widget>, ctx: IpDetailsContext
     page.add(&hero_group);
 
     // --- Connection facts (all known locally) ---
-    let conn_group = adw::PreferencesGroup::builder()
-        .title(gettext("Connection"))
-        .build();
+    if let Some(conn) = &ctx.connection {
+        let conn_group = adw::PreferencesGroup::builder()
+            .title(gettext("Connection"))
+            .build();
 
-    let process_text = match (&ctx.process, ctx.pid) {
-        (Some(name), Some(pid)) => format!("{name} (PID {pid})"),
-        (Some(name), None) => name.clone(),
-        (None, Some(pid)) => format!("PID {pid}"),
-        (None, None) => gettext("Unknown"),
-    };
-    conn_group.add(&static_row(&gettext("Application"), &process_text));
-    conn_group.add(&static_row(
-        &gettext("Remote endpoint"),
-        &format!("{}:{}", ctx.ip, ctx.port),
-    ));
-    conn_group.add(&static_row(&gettext("Protocol"), &ctx.protocol));
-    conn_group.add(&static_row(
-        &gettext("Data received"),
-        &format_bytes(ctx.bytes_in),
-    ));
-    conn_group.add(&static_row(&gettext("Data sent"), &format_bytes(ctx.bytes_out)));
-    page.add(&conn_group);
+        let process_text = match (&conn.process, conn.pid) {
+            (Some(name), Some(pid)) => format!("{name} (PID {pid})"),
+            (Some(name), None) => name.clone(),
+            (None, Some(pid)) => format!("PID {pid}"),
+            (None, None) => gettext("Unknown"),
+        };
+        conn_group.add(&static_row(&gettext("Application"), &process_text));
+        conn_group.add(&static_row(
+            &gettext("Remote endpoint"),
+            &format!("{}:{}", ctx.ip, conn.port),
+        ));
+        conn_group.add(&static_row(&gettext("Protocol"), &conn.protocol));
+        conn_group.add(&static_row(
+            &gettext("Data received"),
+            &format_bytes(conn.bytes_in),
+        ));
+        conn_group.add(&static_row(
+            &gettext("Data sent"),
+            &format_bytes(conn.bytes_out),
+        ));
+        page.add(&conn_group);
+    }
+
+    // --- Firewall view and history (loaded in the background) ---
+    let toasts = adw::ToastOverlay::new();
+    let firewall = FirewallSection::new(parent.as_ref(), ctx.ip, &toasts);
+    page.add(&firewall.summary_group);
+    page.add(&firewall.rules_group);
+    page.add(&firewall.history_group);
 
     // --- Location (offline country now; rest filled online) ---
     let loc_group = adw::PreferencesGroup::builder()
@@ -270,9 +309,362 @@ pub fn present_ip_details(parent: &impl IsA<gtk4::Widget>, ctx: IpDetailsContext
     }
     page.add(&ext_group);
 
-    toolbar.set_content(Some(&page));
+    toasts.set_child(Some(&page));
+    toolbar.set_content(Some(&toasts));
     dialog.set_child(Some(&toolbar));
     dialog.present(Some(parent));
+
+    firewall.load(
+        ctx.country_label
+            .is_none()
+            .then(|| (hero_country.clone(), country_row.clone())),
+    );
+}
+
+/// What the firewall and the journal know about an address.
+struct AddressFacts {
+    rules: anyhow::Result<Vec<RuleMatch>>,
+    history: anyhow::Result<SourceHistory>,
+    country_label: Option<String>,
+}
+
+/// Read the zone rules and the journal for `ip`, and its offline country
+/// when `lookup_country`. Blocks on D-Bus and `journalctl`.
+fn load_address_facts(ip: IpAddr, lookup_country: bool) -> AddressFacts {
+    let rules = crate::firewall::read_blocking(move |client| -> anyhow::Result<_> {
+        client.connect()?;
+        let zones = client.get_zones()?;
+        Ok(rules_for_address(&zones, ip))
+    })
+    .unwrap_or_else(|e| Err(e.into()));
+    AddressFacts {
+        rules,
+        history: crate::admin::query_source_history(ip),
+        country_label: if lookup_country {
+            crate::admin::GeoIp::load().country_label(ip)
+        } else {
+            None
+        },
+    }
+}
+
+/// The firewall groups of the dialog: a summary of decisions about the
+/// address, the rules naming it and a history of its denied packets,
+/// logins and bans. Reloaded after each action.
+#[derive(Clone)]
+struct FirewallSection {
+    /// Widget in the main window, to read its denied packet log.
+    anchor: gtk4::Widget,
+    ip: IpAddr,
+    toasts: adw::ToastOverlay,
+    summary_group: adw::PreferencesGroup,
+    rules_group: adw::PreferencesGroup,
+    history_group: adw::PreferencesGroup,
+    /// Rows added by the last load, removed before the next.
+    rows: Rc<RefCell<Vec<(adw::PreferencesGroup, gtk4::Widget)>>>,
+}
+
+impl FirewallSection {
+    fn new(anchor: &gtk4::Widget, ip: IpAddr, toasts: &adw::ToastOverlay) -> Self {
+        let summary_group = adw::PreferencesGroup::builder()
+            .title(gettext("Firewall"))
+            .build();
+        let rules_group = adw::PreferencesGroup::builder()
+            .title(gettext("Rules"))
+            .description(gettext(
+                "Zone sources and rich rules that apply to this address",
+            ))
+            .build();
+        let history_group = adw::PreferencesGroup::builder()
+            .title(gettext("History"))
+            .description(gettext("Newest first"))
+            .visible(false)
+            .build();
+
+        let section = Self {
+            anchor: anchor.clone(),
+            ip,
+            toasts: toasts.clone(),
+            summary_group,
+            rules_group,
+            history_group,
+            rows: Rc::new(RefCell::new(Vec::new())),
+        };
+
+        let actions = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(6)
+            .valign(gtk4::Align::Center)
+            .build();
+        let block_button = gtk4::Button::builder()
+            .label(gettext("Block"))
+            .tooltip_text(gettext(
+                "Drop all traffic from this address in the default zone",
+            ))
+            .css_classes(vec!["destructive-action".to_string()])
+            .build();
+        let trust_button = gtk4::Button::builder()
+            .label(gettext("Trust"))
+            .tooltip_text(gettext("Accept all traffic from this address"))
+            .build();
+        actions.append(&block_button);
+        actions.append(&trust_button);
+        section.summary_group.set_header_suffix(Some(&actions));
+
+        let this = section.clone();
+        block_button.connect_clicked(move |_| this.block());
+        let this = section.clone();
+        trust_button.connect_clicked(move |_| this.confirm_trust());
+
+        section
+    }
+
+    /// Load the facts in the background and show them. `country` holds the
+    /// hero label and Country row to fill when the caller had no country.
+    fn load(&self, country: Option<(gtk4::Label, adw::ActionRow)>) {
+        let packets = self
+            .anchor
+            .root()
+            .and_then(|root| root.downcast::<gtk4::Window>().ok())
+            .and_downcast::<super::MainWindow>()
+            .map(|window| window.denied_packets())
+            .unwrap_or_default();
+        let section = self.clone();
+        let ip = self.ip;
+        let lookup_country = country.is_some();
+        glib::spawn_future_local(async move {
+            let Ok(facts) =
+                gtk4::gio::spawn_blocking(move || load_address_facts(ip, lookup_country)).await
+            else {
+                return;
+            };
+            if let (Some((hero, row)), Some(label)) = (country, &facts.country_label) {
+                hero.set_text(label);
+                row.set_subtitle(glib::markup_escape_text(label).as_str());
+            }
+            section.show(&facts, &packets);
+        });
+    }
+
+    /// Replace the rows with `facts` and the denied `packets`, newest first.
+    fn show(&self, facts: &AddressFacts, packets: &[DeniedPacket]) {
+        for (group, row) in self.rows.take() {
+            group.remove(&row);
+        }
+        let mut rows: Vec<(adw::PreferencesGroup, gtk4::Widget)> = Vec::new();
+
+        // Summary
+        let denied = DeniedSummary::from_packets(packets, self.ip);
+        let denied_text = match denied.last_seen {
+            Some(last) if denied.count > 0 => {
+                let mut text = gettext("%d in the recent firewall log, last at %s")
+                    .replacen("%d", &denied.count.to_string(), 1)
+                    .replacen("%s", &format_datetime(last as i64), 1);
+                if !denied.ports.is_empty() {
+                    let ports: Vec<String> = denied.ports.iter().map(|p| p.to_string()).collect();
+                    text.push_str(" · ");
+                    text.push_str(&gettext("ports %s").replace("%s", &ports.join(", ")));
+                }
+                text
+            }
+            _ => gettext("None in the recent firewall log"),
+        };
+        rows.push((
+            self.summary_group.clone(),
+            static_row(&gettext("Denied packets"), &denied_text).upcast(),
+        ));
+
+        let (logins_text, bans_text) = match &facts.history {
+            Ok(history) => {
+                let logins = gettext("%d failed, %d accepted in the last 30 days")
+                    .replacen("%d", &history.failed_logins().to_string(), 1)
+                    .replacen("%d", &history.accepted_logins().to_string(), 1);
+                let banned_in = history.banned_in();
+                let bans = if !banned_in.is_empty() {
+                    gettext("Banned in %s").replace("%s", &banned_in.join(", "))
+                } else if history.bans() > 0 {
+                    gettext("%d bans, none active").replace("%d", &history.bans().to_string())
+                } else {
+                    gettext("Never banned")
+                };
+                (logins, bans)
+            }
+            Err(e) => {
+                let text = format!("{}: {}", gettext("Cannot read the journal"), e);
+                (text.clone(), text)
+            }
+        };
+        rows.push((
+            self.summary_group.clone(),
+            static_row(&gettext("SSH logins"), &logins_text).upcast(),
+        ));
+        rows.push((
+            self.summary_group.clone(),
+            static_row(&gettext("fail2ban"), &bans_text).upcast(),
+        ));
+
+        // Rules
+        match &facts.rules {
+            Ok(found) if found.is_empty() => rows.push((
+                self.rules_group.clone(),
+                static_row(
+                    &gettext("No rules"),
+                    &gettext("The zone of the receiving interface decides"),
+                )
+                .upcast(),
+            )),
+            Ok(found) => {
+                for found in found {
+                    rows.push((self.rules_group.clone(), self.rule_row(found).upcast()));
+                }
+            }
+            Err(e) => rows.push((
+                self.rules_group.clone(),
+                static_row(&gettext("Cannot read the firewall rules"), &e.to_string()).upcast(),
+            )),
+        }
+
+        // History: denied packets, logins and bans merged by time
+        let mut entries: Vec<(u64, &str, String)> = packets
+            .iter()
+            .filter(|p| p.source.parse::<IpAddr>().ok() == Some(self.ip))
+            .map(|p| {
+                let title = match p.port {
+                    Some(port) => gettext("Denied %s to port %s")
+                        .replacen("%s", &p.protocol, 1)
+                        .replacen("%s", &port.to_string(), 1),
+                    None => gettext("Denied %s").replace("%s", &p.protocol),
+                };
+                (p.time, "action-unavailable-symbolic", title)
+            })
+            .collect();
+        if let Ok(history) = &facts.history {
+            entries.extend(history.events.iter().map(|event| match &event.kind {
+                AuthEventKind::FailedLogin { user } => (
+                    event.time,
+                    "dialog-warning-symbolic",
+                    gettext("Failed SSH login as %s").replace("%s", user),
+                ),
+                AuthEventKind::AcceptedLogin { user } => (
+                    event.time,
+                    "emblem-ok-symbolic",
+                    gettext("SSH login as %s").replace("%s", user),
+                ),
+                AuthEventKind::Banned { jail } => (
+                    event.time,
+                    "security-high-symbolic",
+                    gettext("Banned by fail2ban (%s)").replace("%s", jail),
+                ),
+                AuthEventKind::Unbanned { jail } => (
+                    event.time,
+                    "edit-undo-symbolic",
+                    gettext("Unbanned by fail2ban (%s)").replace("%s", jail),
+                ),
+            }));
+        }
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.0));
+        for (time, icon, title) in entries.iter().take(MAX_HISTORY_ROWS) {
+            let row = static_row(title, &format_datetime(*time as i64));
+            row.add_prefix(&gtk4::Image::from_icon_name(icon));
+            rows.push((self.history_group.clone(), row.upcast()));
+        }
+        self.history_group.set_visible(!entries.is_empty());
+
+        for (group, row) in &rows {
+            group.add(row);
+        }
+        self.rows.replace(rows);
+    }
+
+    /// A rule naming the address, with a button to remove it.
+    fn rule_row(&self, found: &RuleMatch) -> adw::ActionRow {
+        let row = static_row(
+            &found.entry,
+            &format!(
+                "{} · {}",
+                gettext("Zone %s").replace("%s", &found.zone),
+                kind_label(found.kind)
+            ),
+        );
+        row.add_prefix(&gtk4::Image::from_icon_name(kind_icon(found.kind)));
+
+        let remove_button = gtk4::Button::builder()
+            .icon_name("user-trash-symbolic")
+            .tooltip_text(gettext("Remove"))
+            .valign(gtk4::Align::Center)
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        let section = self.clone();
+        let found = found.clone();
+        remove_button.connect_clicked(move |_| {
+            let found = found.clone();
+            section.run(
+                move || crate::admin::remove_address_rule(&found),
+                gettext("Rule removed"),
+            );
+        });
+        row.add_suffix(&remove_button);
+        row
+    }
+
+    fn block(&self) {
+        let ip = self.ip;
+        self.run(
+            move || crate::admin::block_source(ip).map(|(_, failed)| failed),
+            gettext("%s blocked in the default zone").replace("%s", &ip.to_string()),
+        );
+    }
+
+    fn confirm_trust(&self) {
+        let dialog = adw::AlertDialog::new(
+            Some(&gettext("Trust %s?").replace("%s", &self.ip.to_string())),
+            Some(&gettext(
+                "The address is added to the trusted zone, which accepts all of its traffic \
+                 whatever the rules of other zones.",
+            )),
+        );
+        dialog.add_response("cancel", &gettext("Cancel"));
+        dialog.add_response("trust", &gettext("Trust"));
+        dialog.set_response_appearance("trust", adw::ResponseAppearance::Destructive);
+        dialog.set_default_response(Some("cancel"));
+        dialog.set_close_response("cancel");
+
+        let section = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "trust" {
+                return;
+            }
+            let ip = section.ip;
+            section.run(
+                move || crate::admin::trust_source(ip),
+                gettext("%s added to the trusted zone").replace("%s", &ip.to_string()),
+            );
+        });
+        dialog.present(Some(&self.toasts));
+    }
+
+    /// Run a firewall change in the background, report it and reload. The
+    /// change returns whether saving it permanently failed.
+    fn run<F>(&self, change: F, done: String)
+    where
+        F: FnOnce() -> anyhow::Result<bool> + Send + 'static,
+    {
+        let section = self.clone();
+        glib::spawn_future_local(async move {
+            let message = match gtk4::gio::spawn_blocking(change).await {
+                Ok(Ok(false)) => done,
+                Ok(Ok(true)) => format!(
+                    "{} — {}",
+                    done,
+                    gettext("for this session only, saving permanently failed")
+                ),
+                Ok(Err(e)) => format!("{}: {}", gettext("Firewall change failed"), e),
+                Err(_) => gettext("Firewall change failed"),
+            };
+            section.toasts.add_toast(adw::Toast::new(&message));
+            section.load(None);
+        });
+    }
 }
 
 /// Populate the online-only rows from a lookup result.
@@ -387,19 +779,3 @@ fn link_row(name: &str, url: &str) -> adw::ActionRow {
     });
     row
 }
-
-/// Format a byte count as a compact human-readable string (B/KB/MB/GB).
-fn format_bytes(bytes: u64) -> String {
-    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
-    let mut value = bytes as f64;
-    let mut unit = 0;
-    while value >= 1024.0 && unit < UNITS.len() - 1 {
-        value /= 1024.0;
-        unit += 1;
-    }
-    if unit == 0 {
-        format!("{} {}", bytes, UNITS[unit])
-    } else {
-        format!("{:.1} {}", value, UNITS[unit])
-    }
-}
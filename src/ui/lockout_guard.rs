@@ -0,0 +1,180 @@
+// Security Center - SSH Lockout Guard
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Warning before a change that would cut off remote SSH sessions, with a
+//! trial mode: the change is applied at runtime only and the window's
+//! revert banner undoes it unless the user keeps it in time, so whoever
+//! just locked themselves out gets their access back without doing anything.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gtk4::glib;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use crate::admin::{query_ssh_sessions, sessions_on, SshSession};
+use crate::firewall::FirewallClient;
+use crate::i18n::gettext;
+
+/// One half of a trial, run with a connected client off the main thread.
+pub type TrialStep = Box<dyn FnOnce(&FirewallClient) -> anyhow::Result<()> + Send>;
+
+/// A change split into runtime-only steps for a trial.
+pub struct TrialChange {
+    /// Make the change at runtime only.
+    pub apply: TrialStep,
+    /// Undo what `apply` did.
+    pub revert: TrialStep,
+}
+
+/// Call `then` with the SSH sessions that stopping traffic to `ranges`,
+/// each a first port, last port and protocol, would cut off. Sessions are
+/// read off the main thread; when they cannot be read, `then` gets none.
+pub fn with_sessions_on(
+    ranges: Vec<(u16, u16, String)>,
+    then: impl FnOnce(Vec<SshSession>) + 'static,
+) {
+    glib::spawn_future_local(async move {
+        let sessions = gtk4::gio::spawn_blocking(query_ssh_sessions)
+            .await
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("task error")));
+        let sessions = match sessions {
+            Ok(sessions) => sessions,
+            Err(e) => {
+                tracing::warn!("Could not read SSH sessions: {}", e);
+                Vec::new()
+            }
+        };
+        let mut affected: Vec<SshSession> = Vec::new();
+        for (start, end, protocol) in &ranges {
+            for session in sessions_on(&sessions, *start, *end, protocol) {
+                if !affected.contains(&session) {
+                    affected.push(session);
+                }
+            }
+        }
+        then(affected);
+    });
+}
+
+/// Warn that `what` would cut off `sessions` before going ahead.
+///
+/// "Apply Now" calls `commit`, which makes the change the usual way. "Try
+/// for 60 Seconds" runs the trial and calls `commit` once the user keeps
+/// the change. `cancel` is called when nothing changes in the end, so the
+/// caller can put its controls back.
+pub fn present_lockout_guard(
+    window: &super::MainWindow,
+    what: &str,
+    sessions: &[SshSession],
+    trial: TrialChange,
+    commit: impl Fn() + 'static,
+    cancel: impl Fn() + 'static,
+) {
+    let peers: Vec<String> = sessions
+        .iter()
+        .map(|s| format!("{} (port {})", s.peer, s.port))
+        .collect();
+    let dialog = adw::AlertDialog::builder()
+        .heading(gettext("This may cut off SSH sessions"))
+        .body(format!(
+            "{} {}\n\n{}",
+            gettext("%s stops traffic to the SSH server, which these hosts are connected to:")
+                .replacen("%s", what, 1),
+            peers.join(", "),
+            gettext(
+                "If you manage this machine remotely, you may lose access to it. \
+                 Try the change for 60 seconds: it is undone by itself unless you keep it."
+            )
+        ))
+        .build();
+    dialog.add_response("cancel", "_Cancel");
+    dialog.add_response("apply", "_Apply Now");
+    dialog.add_response("trial", "_Try for 60 Seconds");
+    dialog.set_response_appearance("apply", adw::ResponseAppearance::Destructive);
+    dialog.set_response_appearance("trial", adw::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("trial"));
+    dialog.set_close_response("cancel");
+
+    let commit: Rc<dyn Fn()> = Rc::new(commit);
+    let cancel: Rc<dyn Fn()> = Rc::new(cancel);
+    let trial = Cell::new(Some(trial));
+    let what = what.to_string();
+    let window_clone = window.clone();
+    dialog.connect_response(None, move |_, response| match response {
+        "apply" => commit(),
+        "trial" => {
+            if let Some(trial) = trial.take() {
+                start_trial(&window_clone, &what, trial, commit.clone(), cancel.clone());
+            }
+        }
+        _ => cancel(),
+    });
+
+    dialog.present(Some(window));
+}
+
+/// Run `step` on the firewall worker, off the main thread.
+async fn run_step(step: TrialStep) -> anyhow::Result<()> {
+    crate::firewall::call(move |client| {
+        client
+            .connect()
+            .map_err(|e| anyhow::anyhow!("Not connected to firewalld: {}", e))?;
+        step(client)
+    })
+    .await
+    .unwrap_or_else(|_| Err(anyhow::anyhow!("task error")))
+}
+
+/// Apply the trial, then count down to reverting it.
+fn start_trial(
+    window: &super::MainWindow,
+    what: &str,
+    trial: TrialChange,
+    commit: Rc<dyn Fn()>,
+    cancel: Rc<dyn Fn()>,
+) {
+    let TrialChange { apply, revert } = trial;
+    let window = window.clone();
+    let what = what.to_string();
+    glib::spawn_future_local(async move {
+        if let Err(e) = run_step(apply).await {
+            window.show_toast(&format!("{}: {}", gettext("Failed to apply the change"), e));
+            cancel();
+            return;
+        }
+        present_countdown(&window, &what, revert, commit, cancel);
+    });
+}
+
+/// Count down to reverting the trial change on the window's revert banner,
+/// and commit it for good when the user keeps it.
+fn present_countdown(
+    window: &super::MainWindow,
+    what: &str,
+    revert: TrialStep,
+    commit: Rc<dyn Fn()>,
+    cancel: Rc<dyn Fn()>,
+) {
+    let window_clone = window.clone();
+    window.start_revert_countdown(
+        what,
+        move || {
+            glib::spawn_future_local(async move {
+                match run_step(revert).await {
+                    Ok(()) => window_clone.show_toast(&gettext("Change reverted")),
+                    Err(e) => window_clone.show_toast(&format!(
+                        "{}: {}",
+                        gettext("Failed to revert the change"),
+                        e
+                    )),
+                }
+                cancel();
+                window_clone.refresh_data();
+            });
+        },
+        move || commit(),
+    );
+}
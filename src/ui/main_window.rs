@@ -7,17 +7,42 @@
 use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
+use super::app_state::{AppState, Invalidation, RefreshScope};
+use super::widgets::ActivitySparkline;
 use super::{
-    ConnectionsPage, HelpPage, NetworkExposurePage, OverviewPage, PortsPage, QuickActionsPage,
-    ServicesPage, SystemServicesPage, ZonesPage,
+    AntivirusPage, ApplicationsPage, BlockedSourcesPage, CleanupPage, CompliancePage,
+    ConnectionsPage, Fail2banPage, FirewallLogPage, HelpPage, IncidentsPage, NetworkExposurePage,
+    OverviewPage, PlatformPage, PortsPage, QuickActionsPage, RichRulesPage, ServicesPage,
+    SystemServicesPage, ZonesPage,
 };
-use crate::firewall::FirewallClient;
+use crate::admin::{
+    lock_sessions, protected_addresses, query_security_keys, read_delivered_packets, DeniedLog,
+    DeniedPacket, KeyRemovalWatch, OperationRunner, TripwireHit, Tripwires, Undo, REVERT_SECS,
+};
+use crate::data_usage::QuotaUsage;
+use crate::firewall::events::ConfigEventKind;
+use crate::firewall::{FirewallClient, FirewallEvent, FirewallInfo, FirewallState};
 use crate::i18n::gettext;
+use crate::safe_mode;
+use crate::stats::{RuleHistory, RuleStats};
 use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
 use gtk4::{gio, glib};
 use libadwaita as adw;
 use libadwaita::prelude::*;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+
+/// Seconds between checks whether a scheduled policy enforcement is due.
+const POLICY_CHECK_SECS: u32 = 15 * 60;
+/// Seconds an Undo toast stays up.
+const UNDO_TOAST_SECS: u32 = 10;
+
+/// A change waiting on the revert banner to be kept or reverted.
+pub struct PendingChange {
+    title: String,
+    revert: Box<dyn FnOnce()>,
+    keep: Box<dyn FnOnce()>,
+}
 
 glib::wrapper! {
     /// The main application window.
@@ -27,8 +52,9 @@ glib::wrapper! {
 }
 
 impl MainWindow {
-    /// Create a new main window.
-    pub fn new(app: &impl IsA<gtk4::Application>) -> Self {
+    /// Create a new main window showing `initial_page` (a sidebar page name;
+    /// unknown names fall back to the overview).
+    pub fn new(app: &impl IsA<gtk4::Application>, initial_page: &str) -> Self {
         let settings = crate::config::Settings::new();
         let window: Self = glib::Object::builder()
             .property("application", app)
@@ -41,9 +67,14 @@ impl MainWindow {
         if settings.is_maximized() {
             window.maximize();
         }
+        window
+            .imp()
+            .lock_on_key_removal
+            .set(settings.lock_on_key_removal());
 
-        window.setup_ui();
+        window.setup_ui(initial_page);
         window.setup_actions();
+        window.setup_drop_target();
 
         // Show window immediately, connect to firewalld after main loop starts
         window.set_visible(true);
@@ -54,9 +85,231 @@ impl MainWindow {
             win.connect_to_firewalld();
         });
 
+        // Run the scheduled leftovers cleanup and blocklist refreshes once the
+        // UI has settled, unless the background monitor takes care of them or
+        // safe mode pauses them
+        let win = window.clone();
+        glib::timeout_add_seconds_local_once(10, move || {
+            if win.monitor_running() || safe_mode::is_enabled() {
+                return;
+            }
+            if let Some(page) = win.imp().cleanup_page.borrow().as_ref() {
+                page.run_scheduled_if_due();
+            }
+            if let Some(page) = win.imp().compliance_page.borrow().as_ref() {
+                page.enforce_scheduled_if_due();
+            }
+            if let Some(page) = win.imp().blocked_sources_page.borrow().as_ref() {
+                page.refresh_blocklists_if_due();
+            }
+        });
+
+        // Policy enforcement and blocklist refreshes run hourly at most; check
+        // while the app is open
+        let win = window.downgrade();
+        glib::timeout_add_seconds_local(POLICY_CHECK_SECS, move || {
+            let Some(win) = win.upgrade() else {
+                return glib::ControlFlow::Break;
+            };
+            if win.monitor_running() || safe_mode::is_enabled() {
+                return glib::ControlFlow::Continue;
+            }
+            if let Some(page) = win.imp().compliance_page.borrow().as_ref() {
+                page.enforce_scheduled_if_due();
+            }
+            if let Some(page) = win.imp().blocked_sources_page.borrow().as_ref() {
+                page.refresh_blocklists_if_due();
+            }
+            glib::ControlFlow::Continue
+        });
+
+        window.start_activity_monitor();
+
         window
     }
 
+    /// Follow denied packets in the kernel log and sample firewall activity
+    /// every second for the header sparkline and the tripwire ports, unless
+    /// safe mode pauses it.
+    fn start_activity_monitor(&self) {
+        if !safe_mode::is_enabled() {
+            self.start_denied_log();
+        }
+
+        let win = self.downgrade();
+        glib::timeout_add_seconds_local(1, move || {
+            let Some(win) = win.upgrade() else {
+                return glib::ControlFlow::Break;
+            };
+            if safe_mode::is_enabled() {
+                return glib::ControlFlow::Continue;
+            }
+            win.sample_activity();
+            win.check_tripwires();
+            win.check_security_keys();
+            glib::ControlFlow::Continue
+        });
+    }
+
+    /// Resume the work skipped while safe mode was on: follow denied
+    /// packets and check for updates, if not done yet.
+    pub fn resume_background(&self) {
+        let imp = self.imp();
+        // The totals from before the pause would show as one burst
+        imp.last_activity.set(None);
+        if imp.denied_log.borrow().is_none() {
+            self.start_denied_log();
+        }
+        if !imp.updates_checked.get() {
+            self.check_for_updates();
+        }
+    }
+
+    /// Follow denied packets in the kernel log for the Firewall Log and
+    /// Incidents pages.
+    fn start_denied_log(&self) {
+        let imp = self.imp();
+        match DeniedLog::start() {
+            Ok(log) => {
+                let log = Rc::new(log);
+                if let Some(page) = imp.firewall_log_page.borrow().as_ref() {
+                    page.set_denied_log(&log);
+                }
+                if let Some(page) = imp.incidents_page.borrow().as_ref() {
+                    page.set_denied_log(&log);
+                }
+                imp.denied_log.replace(Some(log));
+            }
+            Err(e) => tracing::warn!("Cannot follow denied packets: {:#}", e),
+        }
+    }
+
+    /// Push the packets accepted and denied during the last second to the
+    /// header sparkline and the Overview traffic ratio.
+    fn sample_activity(&self) {
+        let imp = self.imp();
+        let denied_total = imp.denied_log.borrow().as_ref().map(|log| log.count());
+        let current = (
+            read_delivered_packets().unwrap_or(0),
+            denied_total.unwrap_or(0),
+        );
+        let Some(previous) = imp.last_activity.replace(Some(current)) else {
+            return;
+        };
+        let accepted = current.0.saturating_sub(previous.0);
+        let denied = current.1.saturating_sub(previous.1);
+
+        if let Some(sparkline) = imp.activity_sparkline.borrow().as_ref() {
+            sparkline.push(accepted as f64, denied as f64);
+        }
+        if let Some(page) = imp.overview_page.borrow().as_ref() {
+            page.push_packet_counts(accepted, denied_total.map(|_| denied));
+        }
+        if let Some(button) = imp.activity_button.borrow().as_ref() {
+            let denied_text = match denied_total {
+                Some(_) => gettext("Denied: %s packets/s").replace("%s", &denied.to_string()),
+                None => gettext("Denied: unavailable"),
+            };
+            button.set_tooltip_text(Some(&format!(
+                "{}\n{}\n{}",
+                gettext("Accepted: %s packets/s").replace("%s", &accepted.to_string()),
+                denied_text,
+                gettext("Click to open the firewall log")
+            )));
+        }
+    }
+
+    /// Recently denied packets, newest first; empty when the kernel log
+    /// cannot be followed.
+    pub fn denied_packets(&self) -> Vec<DeniedPacket> {
+        self.imp()
+            .denied_log
+            .borrow()
+            .as_ref()
+            .map(|log| log.recent())
+            .unwrap_or_default()
+    }
+
+    /// Watch for denied packets on these ports and report each probe.
+    pub fn set_tripwire_ports(&self, ports: &[u16]) {
+        self.imp().tripwires.borrow_mut().set_ports(ports);
+    }
+
+    /// Count the open and blocked `ports` and add the totals to the rule
+    /// history.
+    fn record_rule_stats(&self, ports: &[crate::models::Port]) -> RuleStats {
+        let stats = RuleStats::from_ports(ports);
+        let mut history = self.imp().rule_history.borrow_mut();
+        let history = history.get_or_insert_with(RuleHistory::load);
+        history.record(&stats, chrono::Utc::now().timestamp());
+        history.save();
+        stats
+    }
+
+    /// Lock the session when a security key is removed, or stop doing so.
+    pub fn set_lock_on_key_removal(&self, enabled: bool) {
+        self.imp().lock_on_key_removal.set(enabled);
+    }
+
+    /// Lock the user's sessions when a security key was pulled out since the
+    /// last check, unless the background monitor does.
+    fn check_security_keys(&self) {
+        let imp = self.imp();
+        if !imp.lock_on_key_removal.get() || self.monitor_running() {
+            imp.key_watch.borrow_mut().reset();
+            return;
+        }
+        let removed = imp.key_watch.borrow_mut().update(&query_security_keys());
+        if let Some(key) = removed.first() {
+            tracing::info!("Security key {} removed, locking the session", key.name);
+            gio::spawn_blocking(|| {
+                if let Err(e) = lock_sessions() {
+                    tracing::warn!("Cannot lock the session: {:#}", e);
+                }
+            });
+        }
+    }
+
+    /// Hand probes of a tripwire port among the packets denied since the
+    /// last check to the application.
+    fn check_tripwires(&self) {
+        let imp = self.imp();
+        let Some(packets) = imp.denied_log.borrow().as_ref().map(|log| log.take_new()) else {
+            return;
+        };
+        let hits: Vec<TripwireHit> = {
+            let mut tripwires = imp.tripwires.borrow_mut();
+            if tripwires.is_empty() {
+                return;
+            }
+            packets.iter().filter_map(|p| tripwires.check(p)).collect()
+        };
+        if hits.is_empty() {
+            return;
+        }
+        if let Some(app) = self
+            .application()
+            .and_downcast::<crate::application::Application>()
+        {
+            // The background monitor alerts about the same probes
+            if app.monitor_running() {
+                return;
+            }
+            let protected = protected_addresses();
+            let mut tripwires = imp.tripwires.borrow_mut();
+            for hit in &hits {
+                app.report_tripwire_hit(hit, tripwires.offer_block(hit, &protected));
+            }
+        }
+    }
+
+    /// Whether the background monitor is running the scheduled work.
+    fn monitor_running(&self) -> bool {
+        self.application()
+            .and_downcast::<crate::application::Application>()
+            .is_some_and(|app| app.monitor_running())
+    }
+
     /// Show a toast notification.
     pub fn show_toast(&self, message: &str) {
         let imp = self.imp();
@@ -66,6 +319,136 @@ impl MainWindow {
         }
     }
 
+    /// Show a countdown banner for a change that `revert` undoes after
+    /// `REVERT_SECS` unless the user keeps it, which calls `keep`. Changes
+    /// made while the banner counts down join it and restart the count;
+    /// they are kept or reverted together, the newest reverted first.
+    pub fn start_revert_countdown(
+        &self,
+        title: &str,
+        revert: impl FnOnce() + 'static,
+        keep: impl FnOnce() + 'static,
+    ) {
+        let imp = self.imp();
+        let first = imp.pending_changes.borrow().is_empty();
+        imp.pending_changes.borrow_mut().push(PendingChange {
+            title: title.to_string(),
+            revert: Box::new(revert),
+            keep: Box::new(keep),
+        });
+        imp.revert_remaining.set(REVERT_SECS);
+        self.update_revert_banner();
+        if !first {
+            return;
+        }
+
+        let generation = imp.revert_generation.get() + 1;
+        imp.revert_generation.set(generation);
+        let window = self.downgrade();
+        glib::timeout_add_seconds_local(1, move || {
+            let Some(window) = window.upgrade() else {
+                return glib::ControlFlow::Break;
+            };
+            let imp = window.imp();
+            // Kept meanwhile, maybe with a new countdown since
+            if imp.revert_generation.get() != generation {
+                return glib::ControlFlow::Break;
+            }
+            let remaining = imp.revert_remaining.get().saturating_sub(1);
+            imp.revert_remaining.set(remaining);
+            if remaining > 0 {
+                window.update_revert_banner();
+                return glib::ControlFlow::Continue;
+            }
+            window.settle_pending_changes(false);
+            glib::ControlFlow::Break
+        });
+    }
+
+    /// Count down to reverting a risky operation, then run `undo` through
+    /// the operation runner unless the user keeps the change.
+    pub fn revert_unless_kept(&self, undo: Undo) {
+        let window = self.clone();
+        let title = undo.describe();
+        self.start_revert_countdown(&title, move || window.apply_undo(undo), || {});
+    }
+
+    /// Show `message` with an Undo button that makes the inverse calls of
+    /// operation journal entry `id`.
+    pub fn show_undo_toast(&self, message: &str, id: u64) {
+        let imp = self.imp();
+        if let Some(toast_overlay) = imp.toast_overlay.borrow().as_ref() {
+            let toast = adw::Toast::builder()
+                .title(message)
+                .button_label(gettext("Undo"))
+                .timeout(UNDO_TOAST_SECS)
+                .build();
+            let window = self.clone();
+            toast.connect_button_clicked(move |_| window.apply_undo(Undo::Journal(id)));
+            toast_overlay.add_toast(toast);
+        }
+    }
+
+    /// Run `undo` through the operation runner and refresh.
+    fn apply_undo(&self, undo: Undo) {
+        let window = self.clone();
+        glib::spawn_future_local(async move {
+            let result =
+                gtk4::gio::spawn_blocking(move || OperationRunner::new().revert(&undo)).await;
+            match result {
+                Ok(Ok(message)) => window.show_toast(&message),
+                Ok(Err(e)) => window.show_toast(&format!(
+                    "{}: {:#}",
+                    gettext("Failed to revert the change"),
+                    e
+                )),
+                Err(_) => window.show_toast(&gettext("Failed to revert the change")),
+            }
+            window.refresh_data();
+        });
+    }
+
+    /// Show the pending changes and the time left on the revert banner.
+    fn update_revert_banner(&self) {
+        let imp = self.imp();
+        let Some(banner) = imp.revert_banner.borrow().clone() else {
+            return;
+        };
+        let pending = imp.pending_changes.borrow();
+        let what = match pending.as_slice() {
+            [] => {
+                banner.set_revealed(false);
+                return;
+            }
+            [change] => change.title.clone(),
+            changes => gettext("%d changes").replacen("%d", &changes.len().to_string(), 1),
+        };
+        banner.set_title(
+            &gettext("%s — reverting in %d s unless kept")
+                .replacen("%s", &glib::markup_escape_text(&what), 1)
+                .replacen("%d", &imp.revert_remaining.get().to_string(), 1),
+        );
+        banner.set_revealed(true);
+    }
+
+    /// Keep or revert every change on the revert banner and hide it.
+    fn settle_pending_changes(&self, keep: bool) {
+        let imp = self.imp();
+        imp.revert_generation.set(imp.revert_generation.get() + 1);
+        let changes = imp.pending_changes.take();
+        self.update_revert_banner();
+        if keep {
+            for change in changes {
+                (change.keep)();
+            }
+            self.show_toast(&gettext("Changes kept"));
+        } else {
+            for change in changes.into_iter().rev() {
+                (change.revert)();
+            }
+        }
+    }
+
     /// Show or hide the overview's firewall connections section.
     pub fn set_connections_overview_visible(&self, visible: bool) {
         if let Some(page) = self.imp().overview_page.borrow().as_ref() {
@@ -80,8 +463,15 @@ impl MainWindow {
         }
     }
 
+    /// Show the monthly usage of interfaces with a data quota.
+    pub fn set_data_usage(&self, usage: &[QuotaUsage]) {
+        if let Some(page) = self.imp().overview_page.borrow().as_ref() {
+            page.set_data_usage(usage);
+        }
+    }
+
     /// Setup the main UI.
-    fn setup_ui(&self) {
+    fn setup_ui(&self, initial_page: &str) {
         let imp = self.imp();
 
         // Create toast overlay for notifications
@@ -101,10 +491,20 @@ impl MainWindow {
         let zones_page = ZonesPage::new();
         let services_page = ServicesPage::new();
         let ports_page = PortsPage::new();
+        let rich_rules_page = RichRulesPage::new();
+        let blocked_sources_page = BlockedSourcesPage::new();
         let system_services_page = SystemServicesPage::new();
         let network_exposure_page = NetworkExposurePage::new();
+        let applications_page = ApplicationsPage::new();
+        let incidents_page = IncidentsPage::new();
+        let platform_page = PlatformPage::new();
+        let antivirus_page = AntivirusPage::new();
+        let compliance_page = CompliancePage::new();
+        let cleanup_page = CleanupPage::new();
         let quick_actions_page = QuickActionsPage::new();
         let help_page = HelpPage::new();
+        let firewall_log_page = FirewallLogPage::new();
+        let fail2ban_page = Fail2banPage::new();
 
         // Wire up clients to pages
         zones_page.set_client(imp.client.clone());
@@ -116,8 +516,18 @@ impl MainWindow {
         stack.add_named(&zones_page, Some("zones"));
         stack.add_named(&services_page, Some("services"));
         stack.add_named(&ports_page, Some("ports"));
+        stack.add_named(&rich_rules_page, Some("rich-rules"));
+        stack.add_named(&blocked_sources_page, Some("blocked-sources"));
+        stack.add_named(&firewall_log_page, Some("firewall-log"));
+        stack.add_named(&fail2ban_page, Some("fail2ban"));
         stack.add_named(&system_services_page, Some("system-services"));
         stack.add_named(&network_exposure_page, Some("network-exposure"));
+        stack.add_named(&applications_page, Some("applications"));
+        stack.add_named(&incidents_page, Some("incidents"));
+        stack.add_named(&platform_page, Some("platform"));
+        stack.add_named(&antivirus_page, Some("antivirus"));
+        stack.add_named(&compliance_page, Some("compliance"));
+        stack.add_named(&cleanup_page, Some("cleanup"));
         stack.add_named(&quick_actions_page, Some("quick-actions"));
         stack.add_named(&help_page, Some("help"));
 
@@ -158,10 +568,20 @@ impl MainWindow {
         imp.zones_page.replace(Some(zones_page));
         imp.services_page.replace(Some(services_page));
         imp.ports_page.replace(Some(ports_page));
+        imp.rich_rules_page.replace(Some(rich_rules_page));
+        imp.blocked_sources_page.replace(Some(blocked_sources_page));
         imp.system_services_page.replace(Some(system_services_page));
         imp.network_exposure_page
             .replace(Some(network_exposure_page));
+        imp.applications_page.replace(Some(applications_page));
+        imp.incidents_page.replace(Some(incidents_page));
+        imp.platform_page.replace(Some(platform_page));
+        imp.antivirus_page.replace(Some(antivirus_page));
+        imp.compliance_page.replace(Some(compliance_page));
+        imp.cleanup_page.replace(Some(cleanup_page));
         imp.quick_actions_page.replace(Some(quick_actions_page));
+        imp.firewall_log_page.replace(Some(firewall_log_page));
+        imp.fail2ban_page.replace(Some(fail2ban_page));
         imp.stack.replace(Some(stack.clone()));
 
         // === MAIN HORIZONTAL LAYOUT ===
@@ -209,12 +629,34 @@ impl MainWindow {
             ("zones", "Zones", "network-server-symbolic"),
             ("services", "Services", "application-x-addon-symbolic"),
             ("ports", "Ports", "network-transmit-receive-symbolic"),
+            ("rich-rules", "Rich Rules", "format-justify-left-symbolic"),
+            (
+                "blocked-sources",
+                "Blocked Sources",
+                "action-unavailable-symbolic",
+            ),
+            (
+                "firewall-log",
+                "Firewall Log",
+                "document-open-recent-symbolic",
+            ),
+            ("fail2ban", "fail2ban", "system-lock-screen-symbolic"),
             ("system-services", "System Services", "system-run-symbolic"),
             (
                 "network-exposure",
                 "Network Exposure",
                 "network-wired-symbolic",
             ),
+            (
+                "applications",
+                "Applications",
+                "application-x-executable-symbolic",
+            ),
+            ("incidents", "Incidents", "folder-documents-symbolic"),
+            ("platform", "Platform Security", "computer-symbolic"),
+            ("antivirus", "Antivirus", "security-high-symbolic"),
+            ("compliance", "Compliance", "emblem-documents-symbolic"),
+            ("cleanup", "Cleanup", "edit-clear-all-symbolic"),
             ("quick-actions", "Quick Actions", "system-shutdown-symbolic"),
             ("help", "Help", "help-about-symbolic"),
         ];
@@ -266,8 +708,18 @@ impl MainWindow {
                     "zones" => "Zones",
                     "services" => "Services",
                     "ports" => "Ports",
+                    "rich-rules" => "Rich Rules",
+                    "blocked-sources" => "Blocked Sources",
+                    "firewall-log" => "Firewall Log",
+                    "fail2ban" => "fail2ban",
                     "system-services" => "System Services",
                     "network-exposure" => "Network Exposure",
+                    "applications" => "Applications",
+                    "incidents" => "Incidents",
+                    "platform" => "Platform Security",
+                    "antivirus" => "Antivirus",
+                    "compliance" => "Compliance",
+                    "cleanup" => "Cleanup",
                     "quick-actions" => "Quick Actions",
                     "help" => "Help",
                     _ => "Overview",
@@ -296,12 +748,74 @@ impl MainWindow {
                             page.refresh();
                         }
                     }
+                    "applications" => {
+                        if let Some(page) = window_clone.imp().applications_page.borrow().as_ref() {
+                            page.refresh();
+                        }
+                    }
+                    "incidents" => {
+                        if let Some(page) = window_clone.imp().incidents_page.borrow().as_ref() {
+                            page.refresh();
+                        }
+                    }
+                    "platform" => {
+                        if let Some(page) = window_clone.imp().platform_page.borrow().as_ref() {
+                            page.refresh();
+                        }
+                    }
+                    "antivirus" => {
+                        if let Some(page) = window_clone.imp().antivirus_page.borrow().as_ref() {
+                            page.refresh();
+                        }
+                    }
+                    "compliance" => {
+                        if let Some(page) = window_clone.imp().compliance_page.borrow().as_ref() {
+                            page.refresh();
+                        }
+                    }
+                    "cleanup" => {
+                        if let Some(page) = window_clone.imp().cleanup_page.borrow().as_ref() {
+                            page.refresh();
+                        }
+                    }
+                    "firewall-log" => {
+                        if let Some(page) = window_clone.imp().firewall_log_page.borrow().as_ref() {
+                            page.refresh();
+                        }
+                    }
+                    "fail2ban" => {
+                        if let Some(page) = window_clone.imp().fail2ban_page.borrow().as_ref() {
+                            page.refresh();
+                        }
+                    }
                     _ => {}
                 }
+
+                // Opening a scanning page runs its scan; remember it so the
+                // next launch can resume there
+                if matches!(name.as_str(), "network-exposure" | "platform" | "cleanup") {
+                    if let Some(app) = window_clone
+                        .application()
+                        .and_downcast::<crate::application::Application>()
+                    {
+                        app.record_last_task(&name);
+                    }
+                }
             }
         });
 
-        if let Some(row) = nav_list.row_at_index(0) {
+        imp.nav_list.replace(Some(nav_list.clone()));
+
+        let mut initial_row = nav_list.row_at_index(0);
+        let mut index = 0;
+        while let Some(row) = nav_list.row_at_index(index) {
+            if row.widget_name() == initial_page {
+                initial_row = Some(row);
+                break;
+            }
+            index += 1;
+        }
+        if let Some(row) = initial_row {
             nav_list.select_row(Some(&row));
         }
 
@@ -356,7 +870,9 @@ impl MainWindow {
         *imp.nav_boxes.borrow_mut() = nav_boxes;
         imp.sidebar_collapsed.set(false);
 
-        self.check_for_updates();
+        if !safe_mode::is_enabled() {
+            self.check_for_updates();
+        }
 
         // Separator between sidebar and content
         let separator = gtk4::Separator::new(gtk4::Orientation::Vertical);
@@ -386,8 +902,41 @@ impl MainWindow {
             .build();
         header.pack_end(&refresh_button);
 
+        let search_button = gtk4::Button::builder()
+            .icon_name("system-search-symbolic")
+            .action_name("win.search-rules")
+            .tooltip_text(gettext("Search Rules (Ctrl+Shift+F)"))
+            .build();
+        header.pack_end(&search_button);
+
+        // Firewall activity at a glance; opens the firewall log
+        let activity_sparkline = ActivitySparkline::new();
+        let activity_button = gtk4::Button::builder()
+            .child(&activity_sparkline)
+            .css_classes(["flat"])
+            .tooltip_text(gettext("Click to open the firewall log"))
+            .build();
+        let window_clone = self.clone();
+        activity_button.connect_clicked(move |_| {
+            window_clone.show_page("firewall-log");
+        });
+        header.pack_start(&activity_button);
+        imp.activity_sparkline.replace(Some(activity_sparkline));
+        imp.activity_button.replace(Some(activity_button));
+
         content_box.append(&header);
 
+        // Countdown of risky changes that revert themselves unless kept
+        let revert_banner = adw::Banner::builder()
+            .button_label(gettext("Keep Changes"))
+            .build();
+        let window_clone = self.clone();
+        revert_banner.connect_button_clicked(move |_| {
+            window_clone.settle_pending_changes(true);
+        });
+        content_box.append(&revert_banner);
+        imp.revert_banner.replace(Some(revert_banner));
+
         let scrolled = gtk4::ScrolledWindow::builder()
             .hscrollbar_policy(gtk4::PolicyType::Never)
             .vscrollbar_policy(gtk4::PolicyType::Automatic)
@@ -556,6 +1105,25 @@ impl MainWindow {
         menu_list.set_margin_start(6);
         menu_list.set_margin_end(6);
 
+        // Status snapshot button
+        let snapshot_btn = gtk4::Button::new();
+        let snapshot_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 12);
+        snapshot_box.set_margin_start(6);
+        snapshot_box.set_margin_end(6);
+        snapshot_box.set_margin_top(8);
+        snapshot_box.set_margin_bottom(8);
+        let snapshot_icon = gtk4::Image::from_icon_name("camera-photo-symbolic");
+        let snapshot_label = gtk4::Label::new(Some(gettext("Save Status Snapshot").as_str()));
+        snapshot_label.set_halign(gtk4::Align::Start);
+        snapshot_label.set_hexpand(true);
+        snapshot_box.append(&snapshot_icon);
+        snapshot_box.append(&snapshot_label);
+        snapshot_btn.set_child(Some(&snapshot_box));
+        snapshot_btn.add_css_class("flat");
+        snapshot_btn.add_css_class("menu-item");
+        snapshot_btn.set_action_name(Some("win.save-snapshot"));
+        menu_list.append(&snapshot_btn);
+
         // Preferences button
         let prefs_btn = gtk4::Button::new();
         let prefs_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 12);
@@ -575,6 +1143,25 @@ impl MainWindow {
         prefs_btn.set_action_name(Some("app.preferences"));
         menu_list.append(&prefs_btn);
 
+        // About Firewall button
+        let firewall_btn = gtk4::Button::new();
+        let firewall_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 12);
+        firewall_box.set_margin_start(6);
+        firewall_box.set_margin_end(6);
+        firewall_box.set_margin_top(8);
+        firewall_box.set_margin_bottom(8);
+        let firewall_icon = gtk4::Image::from_icon_name("security-high-symbolic");
+        let firewall_label = gtk4::Label::new(Some(gettext("About Firewall").as_str()));
+        firewall_label.set_halign(gtk4::Align::Start);
+        firewall_label.set_hexpand(true);
+        firewall_box.append(&firewall_icon);
+        firewall_box.append(&firewall_label);
+        firewall_btn.set_child(Some(&firewall_box));
+        firewall_btn.add_css_class("flat");
+        firewall_btn.add_css_class("menu-item");
+        firewall_btn.set_action_name(Some("win.firewall-info"));
+        menu_list.append(&firewall_btn);
+
         // About button
         let about_btn = gtk4::Button::new();
         let about_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 12);
@@ -636,7 +1223,208 @@ impl MainWindow {
             })
             .build();
 
-        self.add_action_entries([refresh, action_toggle_sidebar]);
+        // Search rules across all zones
+        let search_rules = gio::ActionEntry::builder("search-rules")
+            .activate(|window: &Self, _, _| {
+                super::rule_search::present_rule_search(window);
+            })
+            .build();
+
+        // Save the Overview as an image
+        let save_snapshot = gio::ActionEntry::builder("save-snapshot")
+            .activate(|window: &Self, _, _| {
+                window.save_status_snapshot();
+            })
+            .build();
+
+        // firewalld's version, state and backend
+        let firewall_info = gio::ActionEntry::builder("firewall-info")
+            .activate(|window: &Self, _, _| {
+                window.show_firewall_info();
+            })
+            .build();
+
+        self.add_action_entries([
+            refresh,
+            action_toggle_sidebar,
+            search_rules,
+            save_snapshot,
+            firewall_info,
+        ]);
+    }
+
+    /// Read firewalld's runtime properties and show them in a dialog.
+    fn show_firewall_info(&self) {
+        let window = self.clone();
+        glib::spawn_future_local(async move {
+            let result = crate::firewall::read(move |client| {
+                client.connect()?;
+                client.get_info()
+            })
+            .await;
+            match result {
+                Ok(Ok(info)) => window.present_firewall_info(&info),
+                Ok(Err(e)) => window.show_toast(&format!(
+                    "{}: {}",
+                    gettext("Cannot read firewalld's information"),
+                    crate::firewall::diagnose(&e)
+                )),
+                Err(_) => {}
+            }
+        });
+    }
+
+    /// Present a dialog with firewalld's version, state and features.
+    fn present_firewall_info(&self, info: &FirewallInfo) {
+        let dialog = adw::Dialog::builder()
+            .title(gettext("About Firewall"))
+            .content_width(480)
+            .build();
+
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&adw::HeaderBar::new());
+
+        let prefs = adw::PreferencesPage::new();
+        let yes_no = |value: bool| {
+            if value {
+                gettext("Supported")
+            } else {
+                gettext("Not supported")
+            }
+        };
+
+        let daemon_group = adw::PreferencesGroup::builder()
+            .title(gettext("firewalld"))
+            .build();
+        let state_class = match info.state {
+            FirewallState::Running => "success",
+            FirewallState::Failed => "error",
+            _ => "warning",
+        };
+        for (title, value, class) in [
+            (gettext("Version"), info.version.clone(), None),
+            (
+                gettext("State"),
+                gettext(&info.state.label()),
+                Some(state_class),
+            ),
+            (
+                gettext("Backend"),
+                info.backend
+                    .clone()
+                    .unwrap_or_else(|| "iptables".to_string()),
+                None,
+            ),
+            (
+                gettext("D-Bus Interface Version"),
+                info.interface_version.clone(),
+                None,
+            ),
+        ] {
+            let row = adw::ActionRow::builder().title(title).build();
+            let label = gtk4::Label::builder()
+                .label(value)
+                .selectable(true)
+                .valign(gtk4::Align::Center)
+                .build();
+            if let Some(class) = class {
+                label.add_css_class(class);
+            }
+            row.add_suffix(&label);
+            daemon_group.add(&row);
+        }
+        prefs.add(&daemon_group);
+
+        let features_group = adw::PreferencesGroup::builder()
+            .title(gettext("Features"))
+            .build();
+        let ipset_types = info.ipset_types.join(", ");
+        for (title, subtitle, supported) in [
+            (gettext("IPv4"), String::new(), info.ipv4),
+            (gettext("IPv6"), String::new(), info.ipv6),
+            (
+                gettext("IPv6 Reverse Path Filter"),
+                gettext("Drops IPv6 packets whose source address would not be routed back"),
+                info.ipv6_rpfilter,
+            ),
+            (
+                gettext("Bridge Filtering"),
+                gettext("Rules for traffic forwarded between bridged interfaces"),
+                info.bridge,
+            ),
+            (gettext("IP Sets"), ipset_types, info.ipset),
+        ] {
+            let row = adw::ActionRow::builder()
+                .title(title)
+                .subtitle(subtitle)
+                .build();
+            let icon = gtk4::Image::from_icon_name(if supported {
+                "emblem-ok-symbolic"
+            } else {
+                "window-close-symbolic"
+            });
+            icon.set_tooltip_text(Some(&yes_no(supported)));
+            row.add_suffix(&icon);
+            features_group.add(&row);
+        }
+        prefs.add(&features_group);
+
+        toolbar.set_content(Some(&prefs));
+        dialog.set_child(Some(&toolbar));
+        dialog.present(Some(self));
+    }
+
+    /// Render the Overview to a PNG, stamped with the host and time, and
+    /// save it where the user picks.
+    fn save_status_snapshot(&self) {
+        self.show_page("overview");
+
+        // The page may have just been switched to; let it lay out first
+        let window = self.clone();
+        glib::timeout_add_local_once(std::time::Duration::from_millis(150), move || {
+            let texture = window
+                .imp()
+                .overview_page
+                .borrow()
+                .as_ref()
+                .and_then(|page| page.render_snapshot());
+            let Some(texture) = texture else {
+                window.show_toast(&gettext("Could not render the Overview"));
+                return;
+            };
+
+            let name = format!(
+                "security-status-{}.png",
+                chrono::Local::now().format("%Y-%m-%d-%H%M")
+            );
+            let dialog = gtk4::FileDialog::builder()
+                .title(gettext("Save Status Snapshot"))
+                .initial_name(name)
+                .modal(true)
+                .build();
+            let parent = window.clone();
+            dialog.save(Some(&parent), gio::Cancellable::NONE, move |result| {
+                // Cancelling the dialog is not an error
+                let Ok(file) = result else {
+                    return;
+                };
+                let Some(path) = file.path() else {
+                    window.show_toast(&gettext("Choose a local file"));
+                    return;
+                };
+                match texture.save_to_png(&path) {
+                    Ok(()) => window.show_toast(
+                        &gettext("Status snapshot saved to %s")
+                            .replace("%s", &path.display().to_string()),
+                    ),
+                    Err(e) => window.show_toast(&format!(
+                        "{}: {}",
+                        gettext("Failed to save the status snapshot"),
+                        e
+                    )),
+                }
+            });
+        });
     }
 
     /// Toggle sidebar between collapsed (icons only) and expanded.
@@ -702,92 +1490,313 @@ impl MainWindow {
 
     /// Connect to firewalld (non-blocking).
     fn connect_to_firewalld(&self) {
+        // Refresh what the pages report changed
+        let window = self.downgrade();
+        self.imp().app_state.connect_invalidated(move |change| {
+            if let Some(window) = window.upgrade() {
+                window.queue_refresh(change.into());
+            }
+        });
         // Trigger a refresh - the refresh_data method handles connection
         self.refresh_data();
         // Also react to changes made outside the app (firewall-cmd, other tools)
         self.start_firewalld_signal_listener();
+        self.start_unit_listener();
+        self.start_interface_listener();
     }
 
-    /// Listen for firewalld D-Bus signals and refresh the UI when the firewall
-    /// state changes outside the app, so it never shows stale data.
-    fn start_firewalld_signal_listener(&self) {
-        let (tx, rx) = async_channel::unbounded::<()>();
+    /// Follow the systemd state of firewalld, NetworkManager and the SSH
+    /// server, so the pages can pause actions while one is restarting.
+    fn start_unit_listener(&self) {
+        let mut statuses = crate::systemd::watch_units();
 
-        // Blocking signal loop on a dedicated thread: any firewalld signal
-        // pushes a tick into the channel.
-        std::thread::spawn(move || {
-            let conn = match zbus::blocking::Connection::system() {
-                Ok(conn) => conn,
-                Err(e) => {
-                    tracing::warn!("Signal listener: cannot connect to system bus: {}", e);
-                    return;
+        let window = self.clone();
+        glib::spawn_future_local(async move {
+            loop {
+                let status = match statuses.recv().await {
+                    Ok(status) => status,
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                };
+                let imp = window.imp();
+                if let Some(page) = imp.overview_page.borrow().as_ref() {
+                    page.set_unit_activity(status);
                 }
-            };
-
-            // Match every signal emitted by firewalld, regardless of interface
-            let rule = match zbus::MatchRule::builder()
-                .msg_type(zbus::message::Type::Signal)
-                .sender(crate::firewall::BUS_NAME)
-                .map(|b| b.build())
-            {
-                Ok(rule) => rule,
-                Err(e) => {
-                    tracing::warn!("Signal listener: bad match rule: {}", e);
-                    return;
+                if let Some(page) = imp.quick_actions_page.borrow().as_ref() {
+                    page.set_unit_activity(status);
                 }
-            };
+            }
+        });
+    }
 
-            let iter = match zbus::blocking::MessageIterator::for_match_rule(rule, &conn, None) {
-                Ok(iter) => iter,
-                Err(e) => {
-                    tracing::warn!("Signal listener: cannot subscribe: {}", e);
-                    return;
+    /// Ask which zone a network interface that appears should be in, instead
+    /// of letting it land in the default zone unnoticed.
+    fn start_interface_listener(&self) {
+        let mut interfaces = crate::admin::watch_interfaces();
+
+        let window = self.clone();
+        glib::spawn_future_local(async move {
+            loop {
+                let interface = match interfaces.recv().await {
+                    Ok(interface) => interface,
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                };
+                crate::firewall::events::record(ConfigEventKind::InterfaceAdded(
+                    interface.name.clone(),
+                ));
+                if let Some(page) = window.imp().overview_page.borrow().as_ref() {
+                    page.set_config_events(&crate::firewall::events::recent());
                 }
+                // NetworkManager puts the interface in its profile's zone
+                // once the device activates; give it a moment
+                glib::timeout_future(std::time::Duration::from_secs(3)).await;
+                window.prompt_interface_zone(interface).await;
+            }
+        });
+    }
+
+    /// Offer to move a new interface out of the default zone, unless
+    /// something already put it in a zone of its own.
+    async fn prompt_interface_zone(&self, interface: crate::admin::NewInterface) {
+        let name = interface.name.clone();
+        let zones = crate::firewall::read(move |client| {
+            client.connect().ok()?;
+            client.get_zones().ok()
+        })
+        .await
+        .ok()
+        .flatten();
+        let Some(zones) = zones else {
+            return;
+        };
+        let default_zone = self.imp().default_zone.borrow().clone();
+        let bound = zones
+            .iter()
+            .any(|zone| zone.name != default_zone && zone.interfaces.contains(&name));
+        if bound {
+            return;
+        }
+        let zone_names: Vec<String> = zones.into_iter().map(|zone| zone.name).collect();
+
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("New Network Interface"))
+            .body(
+                gettext(
+                    "%s (%s) appeared and is in the default zone. \
+                     Choose the zone whose rules should apply to it.",
+                )
+                .replacen("%s", &interface.name, 1)
+                .replacen("%s", &gettext(interface.kind()), 1),
+            )
+            .build();
+
+        let group = adw::PreferencesGroup::new();
+        let zone_row = adw::ComboRow::builder()
+            .title(gettext("Zone"))
+            .model(&gtk4::StringList::new(
+                &zone_names.iter().map(|z| z.as_str()).collect::<Vec<_>>(),
+            ))
+            .selected(
+                zone_names
+                    .iter()
+                    .position(|z| *z == default_zone)
+                    .unwrap_or(0) as u32,
+            )
+            .build();
+        group.add(&zone_row);
+        dialog.set_extra_child(Some(&group));
+
+        dialog.add_response("keep", "_Keep Default Zone");
+        dialog.add_response("assign", "_Assign Zone");
+        dialog.set_response_appearance("assign", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("assign"));
+        dialog.set_close_response("keep");
+
+        let window = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "assign" {
+                return;
+            }
+            let Some(zone) = zone_names.get(zone_row.selected() as usize).cloned() else {
+                return;
             };
+            window.assign_interface_zone(interface.name.clone(), zone);
+        });
+        dialog.present(Some(self));
+    }
 
-            for msg in iter {
-                if msg.is_ok() {
-                    // A closed channel means the window is gone — stop listening
-                    if tx.send_blocking(()).is_err() {
-                        break;
+    /// Move `interface` to `zone` and note it on the activity timeline.
+    fn assign_interface_zone(&self, interface: String, zone: String) {
+        let window = self.clone();
+        glib::spawn_future_local(async move {
+            let result = {
+                let (interface, zone) = (interface.clone(), zone.clone());
+                crate::firewall::call(move |client| {
+                    client.connect()?;
+                    client.change_zone_of_interface(&zone, &interface)
+                })
+                .await
+            };
+            match result {
+                Ok(Ok(())) => {
+                    window.show_toast(
+                        &gettext("Moved %s to zone %s")
+                            .replacen("%s", &interface, 1)
+                            .replacen("%s", &zone, 1),
+                    );
+                    crate::firewall::events::record(ConfigEventKind::InterfaceZoneAssigned {
+                        interface,
+                        zone,
+                    });
+                    if let Some(page) = window.imp().overview_page.borrow().as_ref() {
+                        page.set_config_events(&crate::firewall::events::recent());
                     }
+                    window.invalidate(Invalidation::ZonesChanged);
                 }
+                Ok(Err(e)) => window.show_toast(&format!(
+                    "{}: {}",
+                    gettext("Cannot change the zone of the interface"),
+                    e
+                )),
+                Err(_) => window.show_toast(&gettext("Failed to execute action")),
             }
         });
+    }
 
-        // On the main loop, coalesce bursts of signals into a single refresh
+    /// Follow firewalld's D-Bus signals and refresh the UI when the firewall
+    /// state changes outside the app, so it never shows stale data.
+    fn start_firewalld_signal_listener(&self) {
         let window = self.clone();
         glib::spawn_future_local(async move {
-            while rx.recv().await.is_ok() {
-                // Debounce: wait a beat, then drain any signals that piled up
+            let subscribed = crate::firewall::read(|client| {
+                let events = client.subscribe();
+                client.watch_signals();
+                events
+            })
+            .await;
+            let Ok(mut events) = subscribed else {
+                return;
+            };
+            loop {
+                let mut scope = match events.recv().await {
+                    Ok(event) => window.show_firewall_event(&event),
+                    // Missed events could have changed anything
+                    Err(RecvError::Lagged(_)) => RefreshScope::ALL,
+                    Err(RecvError::Closed) => break,
+                };
+                // Debounce: wait a beat, then take any events that piled up,
+                // so a burst of signals costs a single refresh
                 glib::timeout_future(std::time::Duration::from_millis(500)).await;
-                while rx.try_recv().is_ok() {}
-                window.refresh_data();
+                loop {
+                    match events.try_recv() {
+                        Ok(event) => scope = scope.merge(window.show_firewall_event(&event)),
+                        Err(TryRecvError::Lagged(_)) => scope = RefreshScope::ALL,
+                        Err(_) => break,
+                    }
+                }
+                if let Some(page) = window.imp().overview_page.borrow().as_ref() {
+                    page.set_config_events(&crate::firewall::events::recent());
+                }
+                window.queue_refresh(scope);
+            }
+        });
+    }
+
+    /// Show what a firewall event tells right away, and return what the
+    /// refresh that follows needs to update.
+    fn show_firewall_event(&self, event: &FirewallEvent) -> RefreshScope {
+        match event {
+            FirewallEvent::PanicModeChanged(enabled) => {
+                self.update_status(true, *enabled);
+                RefreshScope::ALL
+            }
+            FirewallEvent::DefaultZoneChanged(zone) => {
+                self.imp().default_zone.replace(zone.clone());
+                Invalidation::ZonesChanged.into()
+            }
+            FirewallEvent::PortAdded { .. } | FirewallEvent::PortRemoved { .. } => {
+                Invalidation::PortsChanged.into()
+            }
+            // Services and rich rules are not told apart by firewalld's signals
+            FirewallEvent::ZoneChanged(_) | FirewallEvent::StateChanged => {
+                Invalidation::ZonesChanged.into()
+            }
+            FirewallEvent::Connected
+            | FirewallEvent::Disconnected
+            | FirewallEvent::Reloaded
+            | FirewallEvent::Error(_) => RefreshScope::ALL,
+        }
+    }
+
+    /// Report that `change` happened; the pages showing it refresh once the
+    /// main loop is idle, and the script hooks hear about it.
+    pub fn invalidate(&self, change: Invalidation) {
+        self.imp().app_state.invalidate(change);
+        let event = crate::hooks::HookEvent::PostRuleChange {
+            change: change.hook_name().to_string(),
+        };
+        gio::spawn_blocking(move || crate::hooks::dispatch(&event));
+    }
+
+    /// Refresh `scope` once the main loop is idle, together with anything
+    /// else invalidated meanwhile.
+    fn queue_refresh(&self, scope: RefreshScope) {
+        if scope.is_empty() {
+            return;
+        }
+        let imp = self.imp();
+        let pending = imp.pending_refresh.take();
+        imp.pending_refresh
+            .set(Some(pending.unwrap_or_default().merge(scope)));
+        if pending.is_some() {
+            return;
+        }
+        let window = self.downgrade();
+        glib::idle_add_local_once(move || {
+            let Some(window) = window.upgrade() else {
+                return;
+            };
+            if let Some(scope) = window.imp().pending_refresh.take() {
+                window.refresh_scope(scope);
             }
         });
     }
 
     /// Refresh all data from firewalld without blocking the UI.
     pub fn refresh_data(&self) {
+        self.refresh_scope(RefreshScope::ALL);
+    }
+
+    /// Fetch what `scope` covers from firewalld without blocking the UI, and
+    /// update the pages that show it.
+    fn refresh_scope(&self, scope: RefreshScope) {
         let window = self.clone();
 
-        // Run D-Bus calls in a background thread to avoid freezing the UI
+        // Run D-Bus calls on the firewall worker to avoid freezing the UI
         glib::spawn_future_local(async move {
-            let data = gio::spawn_blocking(move || {
-                // This runs in a background thread
-                let client = crate::firewall::FirewallClient::new();
-                let mut client = client;
-
-                if client.connect().is_err() {
-                    return None;
+            let data = crate::firewall::read(move |client| {
+                if let Err(e) = client.connect() {
+                    return Err(crate::firewall::diagnose(&e));
                 }
 
                 let zones = client.get_zones().ok();
-                let services = client.get_services().ok();
+                let services = if scope.services_page() {
+                    client.get_services().ok()
+                } else {
+                    None
+                };
                 let default_zone = client.get_default_zone().ok();
                 // Panic mode blocks all traffic; without this the dashboard
                 // would report "protected" while everything is being dropped.
-                let panic_mode = client.query_panic_mode().unwrap_or(false);
+                let (panic_mode, log_denied) = if scope.status {
+                    (
+                        client.query_panic_mode().unwrap_or(false),
+                        client.get_log_denied().ok(),
+                    )
+                } else {
+                    (false, None)
+                };
 
                 let ports: Vec<crate::models::Port> = zones
                     .as_ref()
@@ -796,7 +1805,10 @@ impl MainWindow {
                             .iter()
                             .flat_map(|zone| {
                                 zone.ports.iter().filter_map(|port_str| {
-                                    crate::models::Port::parse_with_zone(port_str, &zone.name)
+                                    let mut port =
+                                        crate::models::Port::parse_with_zone(port_str, &zone.name)?;
+                                    port.is_permanent = !zone.is_runtime_only_port(port_str);
+                                    Some(port)
                                 })
                             })
                             .collect()
@@ -811,34 +1823,67 @@ impl MainWindow {
                             .iter()
                             .flat_map(|zone| {
                                 zone.rich_rules.iter().filter_map(|rule| {
-                                    crate::models::Port::parse_from_rich_rule(rule, &zone.name)
+                                    let mut port = crate::models::Port::parse_from_rich_rule(
+                                        rule, &zone.name,
+                                    )?;
+                                    port.is_permanent = !zone.is_runtime_only_rich_rule(rule);
+                                    Some(port)
                                 })
                             })
                             .collect()
                     })
                     .unwrap_or_default();
 
-                Some((
+                Ok((
                     zones,
                     services,
                     default_zone,
                     ports,
                     blocked_ports,
                     panic_mode,
+                    log_denied,
                 ))
             })
             .await;
 
             // Back on the main thread - update UI
             match data {
-                Ok(Some((zones, services, default_zone, ports, blocked_ports, panic_mode))) => {
+                Ok(Ok((
+                    zones,
+                    services,
+                    default_zone,
+                    ports,
+                    blocked_ports,
+                    panic_mode,
+                    log_denied,
+                ))) => {
                     let imp = window.imp();
 
+                    // Remember zones for imports dropped onto the window
+                    if let Some(ref zones) = zones {
+                        imp.zone_names
+                            .replace(zones.iter().map(|z| z.name.clone()).collect());
+                    }
+                    if let Some(ref zone) = default_zone {
+                        imp.default_zone.replace(zone.clone());
+                    }
+
                     // Update zones page
                     if let Some(ref zones) = zones {
+                        // Zone rows list ports and services, so any change shows there
                         if let Some(page) = imp.zones_page.borrow().as_ref() {
                             page.set_zones(zones);
                         }
+                        if scope.rich_rules_page() {
+                            if let Some(page) = imp.rich_rules_page.borrow().as_ref() {
+                                page.set_zones(zones);
+                            }
+                        }
+                        if scope.zones {
+                            if let Some(page) = imp.blocked_sources_page.borrow().as_ref() {
+                                page.set_zones(zones);
+                            }
+                        }
                     }
 
                     // Update services page
@@ -859,48 +1904,149 @@ impl MainWindow {
                                     .map(|z| (z.name.clone(), z.services.clone()))
                                     .collect();
                                 page.set_zone_services(map);
+                                // Services a reload would drop, per zone
+                                let runtime_only: std::collections::HashMap<
+                                    String,
+                                    std::collections::HashSet<String>,
+                                > = zones
+                                    .iter()
+                                    .map(|z| {
+                                        let services = z
+                                            .services
+                                            .iter()
+                                            .filter(|s| z.is_runtime_only_service(s))
+                                            .cloned()
+                                            .collect();
+                                        (z.name.clone(), services)
+                                    })
+                                    .collect();
+                                page.set_runtime_only_services(runtime_only);
                             }
                             page.set_services(services);
                         }
                     }
 
+                    // Merge open and blocked ports into a single list, counted
+                    // once for the Ports summary and the Overview
+                    let mut all_ports = ports.clone();
+                    all_ports.extend(blocked_ports.iter().cloned());
+                    let rule_stats = zones
+                        .is_some()
+                        .then(|| window.record_rule_stats(&all_ports));
+
                     // Update ports page with both open and blocked ports
-                    if let Some(page) = imp.ports_page.borrow().as_ref() {
-                        // Pass available zone names for the dropdown
-                        if let Some(ref zones) = zones {
-                            let zone_names: Vec<String> =
-                                zones.iter().map(|z| z.name.clone()).collect();
-                            page.set_available_zones(&zone_names);
-                        }
-                        // Preselect the real default zone in the Add dialog, so
-                        // opened ports land in a zone bound to an interface
-                        if let Some(ref zone) = default_zone {
-                            page.set_zone(zone);
+                    if scope.ports_page() {
+                        if let Some(page) = imp.ports_page.borrow().as_ref() {
+                            // Pass available zone names for the dropdown
+                            if let Some(ref zones) = zones {
+                                let zone_names: Vec<String> =
+                                    zones.iter().map(|z| z.name.clone()).collect();
+                                page.set_available_zones(&zone_names);
+                            }
+                            // Preselect the real default zone in the Add dialog, so
+                            // opened ports land in a zone bound to an interface
+                            if let Some(ref zone) = default_zone {
+                                page.set_zone(zone);
+                            }
+                            page.set_ports(&all_ports);
+                            if let Some(ref stats) = rule_stats {
+                                let history = imp.rule_history.borrow();
+                                let samples = history.as_ref().map_or(&[][..], |h| h.samples());
+                                page.set_rule_stats(stats, samples);
+                            }
                         }
-                        // Merge open and blocked ports into a single list
-                        let mut all_ports = ports.clone();
-                        all_ports.extend(blocked_ports.iter().cloned());
-                        page.set_ports(&all_ports);
                     }
 
                     // Update overview page quick stats and blocked ports
-                    if let Some(ref zones) = zones {
+                    if let (Some(zones), Some(stats)) = (&zones, &rule_stats) {
                         if let Some(page) = imp.overview_page.borrow().as_ref() {
                             page.set_zones(zones);
-                            page.set_blocked_ports(&blocked_ports);
+                            page.set_rule_stats(stats);
                         }
                     }
-
-                    window.update_status(true, panic_mode);
+                    if scope.status {
+                        if let Some(page) = imp.overview_page.borrow().as_ref() {
+                            page.set_log_denied(log_denied.as_deref());
+                        }
+                        window.update_status(true, panic_mode);
+                    }
                 }
-                _ => {
-                    // Connection to firewalld failed — the service is likely stopped
+                Ok(Err(reason)) => {
+                    tracing::info!("firewalld unavailable: {}", reason);
+                    window.update_unavailable(&reason);
+                }
+                Err(_) => {
                     window.update_status(false, false);
                 }
             }
         });
     }
 
+    /// Accept firewalld zone XML files dropped anywhere on the window and
+    /// open the import preview for them.
+    fn setup_drop_target(&self) {
+        let drop_target =
+            gtk4::DropTarget::new(gio::File::static_type(), gtk4::gdk::DragAction::COPY);
+        let window = self.clone();
+        drop_target.connect_drop(move |_, value, _, _| match value.get::<gio::File>() {
+            Ok(file) => {
+                window.import_dropped_file(&file);
+                true
+            }
+            Err(_) => false,
+        });
+        self.add_controller(drop_target);
+    }
+
+    /// Read and parse a dropped file, then show the import preview.
+    fn import_dropped_file(&self, file: &gio::File) {
+        let Some(path) = file.path() else {
+            self.show_toast(&gettext("Only local files can be imported"));
+            return;
+        };
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if !file_name.ends_with(".xml") {
+            self.show_toast(&gettext("Only firewalld zone files (.xml) can be imported"));
+            return;
+        }
+
+        let window = self.clone();
+        glib::spawn_future_local(async move {
+            let result = gio::spawn_blocking(move || {
+                let size = std::fs::metadata(&path)?.len();
+                if size > crate::firewall::MAX_ZONE_FILE_SIZE {
+                    anyhow::bail!("File is too large for a zone definition");
+                }
+                let text = std::fs::read_to_string(&path)?;
+                crate::firewall::parse_zone_xml(&text)
+            })
+            .await;
+
+            match result {
+                Ok(Ok(zone_file)) => {
+                    let zones = window.imp().zone_names.borrow().clone();
+                    let default_zone = window.imp().default_zone.borrow().clone();
+                    super::zone_import::present_zone_import(
+                        &window,
+                        &file_name,
+                        zone_file,
+                        &zones,
+                        &default_zone,
+                    );
+                }
+                Ok(Err(e)) => {
+                    window.show_toast(&format!("{}: {}", gettext("Cannot import this file"), e));
+                }
+                Err(_) => {
+                    window.show_toast(&gettext("Cannot import this file"));
+                }
+            }
+        });
+    }
+
     /// Confirm before blocking all traffic (panic mode), then apply or revert.
     fn confirm_block_all_traffic(&self, switch: &gtk4::Switch) {
         let window = self.clone();
@@ -943,9 +2089,7 @@ impl MainWindow {
         let window = self.clone();
 
         glib::spawn_future_local(async move {
-            let result = gtk4::gio::spawn_blocking(move || {
-                // Create a new client in the background thread
-                let mut client = FirewallClient::new();
+            let result = crate::firewall::call(move |client| {
                 if let Err(e) = client.connect() {
                     return Err(format!("Failed to connect: {}", e));
                 }
@@ -992,12 +2136,51 @@ impl MainWindow {
         });
     }
 
+    /// Whether panic mode is on, as of the last refresh.
+    pub fn panic_mode(&self) -> bool {
+        self.imp().panic_mode.get()
+    }
+
+    /// Turn panic mode on or off from outside the Overview, e.g. the tray.
+    /// Turning it on asks first, as the traffic switch does.
+    pub fn request_panic_mode(&self, enabled: bool) {
+        if !self.imp().firewall_connected.get() {
+            self.present();
+            self.show_toast(&gettext("Firewall service is not running"));
+            return;
+        }
+        if enabled == self.panic_mode() {
+            return;
+        }
+        if enabled {
+            self.present();
+            let switch = self
+                .imp()
+                .overview_page
+                .borrow()
+                .as_ref()
+                .and_then(|page| page.traffic_switch());
+            if let Some(switch) = switch {
+                self.confirm_block_all_traffic(&switch);
+            }
+        } else {
+            self.toggle_firewall(true);
+        }
+    }
+
     /// Update the firewall status display.
     fn update_status(&self, connected: bool, panic_mode: bool) {
         let imp = self.imp();
 
         // Track whether firewalld is running
         imp.firewall_connected.set(connected);
+        imp.panic_mode.set(connected && panic_mode);
+        if let Some(app) = self
+            .application()
+            .and_downcast::<crate::application::Application>()
+        {
+            app.set_tray_panic_mode(connected && panic_mode);
+        }
 
         // Guard: prevent the switch signal from triggering toggle_firewall
         imp.updating_switch.set(true);
@@ -1016,6 +2199,19 @@ impl MainWindow {
         imp.updating_switch.set(false);
     }
 
+    /// Show why firewalld cannot be reached.
+    fn update_unavailable(&self, reason: &crate::firewall::FirewallUnavailable) {
+        let imp = self.imp();
+        imp.firewall_connected.set(false);
+        imp.panic_mode.set(false);
+
+        imp.updating_switch.set(true);
+        if let Some(page) = imp.overview_page.borrow().as_ref() {
+            page.set_firewall_unavailable(reason);
+        }
+        imp.updating_switch.set(false);
+    }
+
     /// Show an error message.
     #[allow(dead_code)]
     fn show_error(&self, message: &str) {
@@ -1028,6 +2224,21 @@ impl MainWindow {
         dialog.present(Some(self));
     }
 
+    /// Switch to the page with navigation id `name`, e.g. `platform`.
+    pub fn show_page(&self, name: &str) {
+        let Some(nav_list) = self.imp().nav_list.borrow().clone() else {
+            return;
+        };
+        let mut index = 0;
+        while let Some(row) = nav_list.row_at_index(index) {
+            if row.widget_name() == name {
+                nav_list.select_row(Some(&row));
+                return;
+            }
+            index += 1;
+        }
+    }
+
     /// Get the firewall client.
     pub fn client(&self) -> Rc<RefCell<FirewallClient>> {
         self.imp().client.clone()
@@ -1037,6 +2248,7 @@ impl MainWindow {
     fn check_for_updates(&self) {
         use crate::version_check;
 
+        self.imp().updates_checked.set(true);
         let obj = self.clone();
         const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -1099,10 +2311,43 @@ mod imp {
         pub zones_page: RefCell<Option<ZonesPage>>,
         pub services_page: RefCell<Option<ServicesPage>>,
         pub ports_page: RefCell<Option<PortsPage>>,
+        pub rich_rules_page: RefCell<Option<RichRulesPage>>,
+        pub blocked_sources_page: RefCell<Option<BlockedSourcesPage>>,
         pub system_services_page: RefCell<Option<SystemServicesPage>>,
         pub network_exposure_page: RefCell<Option<NetworkExposurePage>>,
+        pub applications_page: RefCell<Option<ApplicationsPage>>,
+        pub incidents_page: RefCell<Option<IncidentsPage>>,
+        pub platform_page: RefCell<Option<PlatformPage>>,
+        pub antivirus_page: RefCell<Option<AntivirusPage>>,
+        pub compliance_page: RefCell<Option<CompliancePage>>,
+        pub cleanup_page: RefCell<Option<CleanupPage>>,
         pub quick_actions_page: RefCell<Option<QuickActionsPage>>,
+        pub firewall_log_page: RefCell<Option<FirewallLogPage>>,
+        pub fail2ban_page: RefCell<Option<Fail2banPage>>,
+        /// Header sparkline of accepted and denied packets per second.
+        pub activity_button: RefCell<Option<gtk4::Button>>,
+        pub activity_sparkline: RefCell<Option<ActivitySparkline>>,
+        /// Kernel log follower for denied packets, stopped on close.
+        pub denied_log: RefCell<Option<Rc<DeniedLog>>>,
+        /// Delivered and denied packet totals at the previous sample.
+        pub last_activity: Cell<Option<(u64, u64)>>,
+        /// Ports whose denied packets are reported as probes.
+        pub tripwires: RefCell<Tripwires>,
+        /// Counts of the port rules over time, loaded at the first refresh.
+        pub rule_history: RefCell<Option<RuleHistory>>,
+        /// Security keys attached at the previous check, and whether to lock
+        /// the session when one goes away.
+        pub key_watch: RefCell<KeyRemovalWatch>,
+        pub lock_on_key_removal: Cell<bool>,
         pub update_banner: RefCell<Option<gtk4::Box>>,
+        /// Whether the release check ran; safe mode skips it at startup.
+        pub updates_checked: Cell<bool>,
+        /// Risky changes that revert themselves unless kept in time.
+        pub revert_banner: RefCell<Option<adw::Banner>>,
+        pub pending_changes: RefCell<Vec<PendingChange>>,
+        pub revert_remaining: Cell<u32>,
+        /// Bumped when the pending changes are settled, to stop the timer.
+        pub revert_generation: Cell<u64>,
         // Collapsible sidebar fields
         pub sidebar_collapsed: Cell<bool>,
         pub sidebar_box: RefCell<Option<gtk4::Box>>,
@@ -1111,10 +2356,21 @@ mod imp {
         pub info_box: RefCell<Option<gtk4::Box>>,
         pub nav_labels: RefCell<Vec<gtk4::Label>>,
         pub nav_boxes: RefCell<Vec<gtk4::Box>>,
+        pub nav_list: RefCell<Option<gtk4::ListBox>>,
         /// Guard flag to prevent traffic switch signal feedback loops.
         pub updating_switch: Cell<bool>,
         /// Whether firewalld is currently connected/running.
         pub firewall_connected: Cell<bool>,
+        /// Whether panic mode was on at the last status update.
+        pub panic_mode: Cell<bool>,
+        /// Zone names and default zone from the last refresh, offered as
+        /// destinations when a zone file is dropped onto the window.
+        pub zone_names: RefCell<Vec<String>>,
+        pub default_zone: RefCell<String>,
+        /// Where pages report what they changed.
+        pub app_state: AppState,
+        /// Invalidations waiting for the next idle refresh.
+        pub pending_refresh: Cell<Option<RefreshScope>>,
     }
 
     #[glib::object_subclass]
@@ -1126,7 +2382,16 @@ mod imp {
 
     impl ObjectImpl for MainWindow {}
     impl WidgetImpl for MainWindow {}
-    impl WindowImpl for MainWindow {}
+    impl WindowImpl for MainWindow {
+        fn close_request(&self) -> glib::Propagation {
+            // Stop journalctl with the window rather than at process exit;
+            // a window hidden to the tray keeps following it
+            if !self.obj().hides_on_close() {
+                self.denied_log.take();
+            }
+            self.parent_close_request()
+        }
+    }
     impl ApplicationWindowImpl for MainWindow {}
     impl AdwApplicationWindowImpl for MainWindow {}
 }
@@ -4,28 +4,62 @@
 
 //! User interface components.
 
+mod antivirus_page;
 mod app_icons;
+mod app_state;
+mod applications_page;
+mod blocked_sources_page;
+mod cleanup_page;
+mod compliance_page;
 mod connections_page;
+mod drift_review;
+mod fail2ban_page;
+mod firewall_log_page;
 mod help_page;
+mod incidents_page;
 mod ip_details;
+mod lockout_guard;
 mod main_window;
 mod network_exposure_page;
 mod overview_page;
+mod platform_page;
 mod ports_page;
 mod quick_actions_page;
+mod rich_rule_builder;
+mod rich_rules_page;
+mod role_checklists;
+mod rule_search;
+mod service_ports;
 mod services_page;
 mod system_services_page;
+mod travel_wizard;
+mod zone_editor;
+mod zone_import;
+mod zone_transfer;
 mod zones_page;
 
+pub mod density;
+pub mod palette;
 pub mod widgets;
 
+pub use antivirus_page::AntivirusPage;
+pub use app_state::Invalidation;
+pub use applications_page::ApplicationsPage;
+pub use blocked_sources_page::BlockedSourcesPage;
+pub use cleanup_page::CleanupPage;
+pub use compliance_page::CompliancePage;
 pub use connections_page::ConnectionsPage;
+pub use fail2ban_page::Fail2banPage;
+pub use firewall_log_page::FirewallLogPage;
 pub use help_page::HelpPage;
+pub use incidents_page::IncidentsPage;
 pub use main_window::MainWindow;
 pub use network_exposure_page::NetworkExposurePage;
 pub use overview_page::OverviewPage;
+pub use platform_page::PlatformPage;
 pub use ports_page::PortsPage;
 pub use quick_actions_page::QuickActionsPage;
+pub use rich_rules_page::RichRulesPage;
 pub use services_page::ServicesPage;
 pub use system_services_page::SystemServicesPage;
 pub use zones_page::ZonesPage;
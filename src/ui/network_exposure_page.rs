@@ -10,7 +10,17 @@
 //! - Show process names and PIDs
 //! - Correlate with firewall rules
 //! - Highlight risky configurations
+//! - Names and descriptions given to ports on the Ports page, next to what
+//!   actually listens on them
+//! - Per-interface breakdown: what each network can reach given its zone
 //! - Quick actions to close ports or stop services
+//! - mDNS advertisements from avahi-daemon, tied to the firewalld `mdns` service
+//! - Remote access: login methods (SSH, Cockpit, VNC, RDP, Telnet) with a
+//!   switch each that changes both the unit and the firewall, Wake-on-LAN
+//!   and reachable remote management services
+//! - Other network namespaces (containers, VPNs), scanned one at a time
+//! - Package verification of listening executables, flagging unpackaged or
+//!   modified binaries, and quarantine of a flagged process
 //!
 //! # Architecture
 //!
@@ -18,16 +28,27 @@
 //! Firewall status is obtained via the existing FirewallClient.
 
 use std::cell::RefCell;
+use std::collections::HashMap;
 
 use gtk4::glib;
 use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
 use libadwaita as adw;
 use libadwaita::prelude::*;
-use tracing::error;
-
-use crate::admin::{get_service_name, FirewallStatus, ListeningEndpoint, NetworkExposure};
-use crate::i18n::gettext;
+use tracing::{error, warn};
+
+use super::app_state::Invalidation;
+use super::density::{self, Role};
+use super::ip_details::{present_ip_details, IpDetailsContext};
+use crate::admin::{
+    get_service_name, remote_access_endpoints, AccessMethod, AccessMethodStatus, AdvertisedService,
+    AvahiStatus, BinaryIntegrity, FirewallStatus, IntegrityStatus, InterfaceExposure,
+    ListeningEndpoint, MdnsExposure, NamespaceScan, NetNamespace, NetworkExposure, Operation,
+    OperationRunner, ProcessPrivileges, WakeOnLan, AVAHI_UNITS,
+};
+use crate::i18n::{format_bytes, gettext};
+use crate::models::RichRule;
+use crate::storage::PortStorage;
 use crate::ui::widgets::BarChart;
 use crate::validation::validate_protocol;
 
@@ -61,11 +82,8 @@ impl NetworkExposurePage {
         let header = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Horizontal)
             .spacing(12)
-            .margin_start(24)
-            .margin_end(24)
-            .margin_top(24)
-            .margin_bottom(12)
             .build();
+        density::track(&header, Role::PageHeader);
 
         let title_box = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Vertical)
@@ -100,7 +118,23 @@ impl NetworkExposurePage {
             page.refresh();
         });
 
+        let verify_button = gtk4::Button::builder()
+            .icon_name("security-high-symbolic")
+            .tooltip_text(gettext(
+                "Verify that listening programs match their packages (requires administrator rights)",
+            ))
+            .css_classes(vec!["flat".to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+
+        let page = self.clone();
+        verify_button.connect_clicked(move |btn| {
+            btn.set_sensitive(false);
+            page.verify_binaries(btn.clone());
+        });
+
         header.append(&title_box);
+        header.append(&verify_button);
         header.append(&refresh_button);
         self.append(&header);
 
@@ -115,13 +149,15 @@ impl NetworkExposurePage {
 
         let content = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Vertical)
-            .spacing(24)
-            .margin_top(24)
-            .margin_bottom(24)
-            .margin_start(24)
-            .margin_end(24)
             .hexpand(true)
             .build();
+        density::track(
+            &content,
+            Role::Content {
+                margin: 24,
+                spacing: 24,
+            },
+        );
 
         // Summary cards
         let summary_box = gtk4::Box::builder()
@@ -172,6 +208,90 @@ impl NetworkExposurePage {
         imp.local_group.replace(Some(local_group.clone()));
         content.append(&local_group);
 
+        // Package verification of the listening executables
+        let integrity_header =
+            Self::create_section_header("security-high-symbolic", &gettext("Binary Integrity"));
+        integrity_header.set_visible(false);
+        imp.integrity_header.replace(Some(integrity_header.clone()));
+        content.append(&integrity_header);
+        let integrity_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "Listening programs compared with the packages that installed them. \
+                 A program no package owns, or one that differs from its package, \
+                 may have been tampered with.",
+            ))
+            .visible(false)
+            .build();
+        imp.integrity_group.replace(Some(integrity_group.clone()));
+        content.append(&integrity_group);
+
+        // Per-interface breakdown of what each network can reach
+        let interfaces_header =
+            Self::create_section_header("network-wired-symbolic", &gettext("By Interface"));
+        interfaces_header.set_visible(false);
+        imp.interfaces_header
+            .replace(Some(interfaces_header.clone()));
+        content.append(&interfaces_header);
+        let interfaces_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "Ports reachable on each network, given the firewall zone of its interface",
+            ))
+            .visible(false)
+            .build();
+        imp.interfaces_group.replace(Some(interfaces_group.clone()));
+        content.append(&interfaces_group);
+
+        // Remote access (login methods, Wake-on-LAN, remote management services)
+        let remote_header = Self::create_section_header(
+            "preferences-desktop-remote-desktop-symbolic",
+            &gettext("Remote Access"),
+        );
+        remote_header.set_visible(false);
+        imp.remote_header.replace(Some(remote_header.clone()));
+        content.append(&remote_header);
+        let remote_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "Ways to log in to, power on or control this computer from the network",
+            ))
+            .visible(false)
+            .build();
+        imp.remote_group.replace(Some(remote_group.clone()));
+        content.append(&remote_group);
+
+        // Network namespaces of containers, VPNs and sandboxes
+        let namespaces_header =
+            Self::create_section_header("network-server-symbolic", &gettext("Network Namespaces"));
+        namespaces_header.set_visible(false);
+        imp.namespaces_header
+            .replace(Some(namespaces_header.clone()));
+        content.append(&namespaces_header);
+        let namespaces_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "Containers, VPNs and sandboxes with a network stack of their own. \
+                 The firewall zones above do not apply inside them.",
+            ))
+            .visible(false)
+            .build();
+        imp.namespaces_group.replace(Some(namespaces_group.clone()));
+        content.append(&namespaces_group);
+
+        // Local network discovery (Avahi / mDNS advertisements)
+        let mdns_header = Self::create_section_header(
+            "network-workgroup-symbolic",
+            &gettext("Local Network Discovery (mDNS)"),
+        );
+        mdns_header.set_visible(false);
+        imp.mdns_header.replace(Some(mdns_header.clone()));
+        content.append(&mdns_header);
+        let mdns_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "Services this computer announces to other devices on the local network",
+            ))
+            .visible(false)
+            .build();
+        imp.mdns_group.replace(Some(mdns_group.clone()));
+        content.append(&mdns_group);
+
         // Active connections (established sessions to remote hosts)
         let conn_header = Self::create_section_header(
             "network-transmit-receive-symbolic",
@@ -184,7 +304,7 @@ impl NetworkExposurePage {
         // Top talkers — remote hosts ranked by number of open connections
         let talkers_card = self.create_chart_card(&gettext("Top Remote Hosts (by connections)"));
         let talkers_chart = BarChart::new();
-        talkers_chart.set_size_request(-1, 140);
+        density::track(&talkers_chart, Role::Size(-1, 140));
         talkers_chart.set_hexpand(true);
         talkers_chart.set_margin_top(8);
         talkers_chart.set_margin_bottom(8);
@@ -290,14 +410,31 @@ impl NetworkExposurePage {
 
     /// Refresh the network exposure data.
     pub fn refresh(&self) {
+        self.refresh_mdns();
+        self.refresh_namespaces();
         let page = self.clone();
 
         glib::spawn_future_local(async move {
             let result = gtk4::gio::spawn_blocking(move || {
                 let mut scanner = NetworkExposure::new();
-                let endpoints = scanner.scan()?;
+                let sockets = scanner.scan_sockets()?;
+                // Best-effort: empty when interface addresses cannot be read
+                let interfaces =
+                    crate::admin::query_interface_exposure(&sockets).unwrap_or_else(|e| {
+                        warn!("Failed to read interface addresses: {}", e);
+                        Vec::new()
+                    });
+                // User and capabilities of each listening process
+                let privileges = crate::admin::query_listener_privileges(&sockets);
+                let endpoints = scanner.merge_ports(sockets);
                 // Established connections share the same scanner/inode map
                 let connections = scanner.scan_connections().unwrap_or_default();
+                // Empty on kernels without ethtool netlink
+                let wake_on_lan = crate::admin::query_wake_on_lan().unwrap_or_default();
+                let access_methods = crate::admin::query_access_methods().unwrap_or_else(|e| {
+                    warn!("Failed to read remote login methods: {}", e);
+                    Vec::new()
+                });
                 // Real per-host byte totals via netlink sock_diag (best-effort)
                 let talkers = crate::admin::collect_top_talkers().ok();
                 // Resolve remote-host countries offline; empty when connections have no remotes
@@ -306,12 +443,33 @@ impl NetworkExposurePage {
                     .iter()
                     .filter_map(|c| geo.country_label(c.remote_addr).map(|l| (c.remote_addr, l)))
                     .collect();
-                Ok::<_, anyhow::Error>((endpoints, connections, talkers, geo_labels))
+                Ok::<_, anyhow::Error>((
+                    endpoints,
+                    interfaces,
+                    connections,
+                    access_methods,
+                    wake_on_lan,
+                    talkers,
+                    geo_labels,
+                    privileges,
+                ))
             })
             .await;
 
             match result {
-                Ok(Ok((endpoints, connections, talkers, geo_labels))) => {
+                Ok(Ok((
+                    endpoints,
+                    interfaces,
+                    connections,
+                    access_methods,
+                    wake_on_lan,
+                    talkers,
+                    geo_labels,
+                    privileges,
+                ))) => {
+                    page.imp().privileges.replace(privileges);
+                    page.update_interfaces(interfaces);
+                    page.update_remote_access(&endpoints, access_methods, wake_on_lan);
                     page.update_endpoints(endpoints);
                     page.update_connections(connections, talkers, geo_labels);
                 }
@@ -326,6 +484,902 @@ impl NetworkExposurePage {
         });
     }
 
+    /// Rebuild the By Interface section.
+    fn update_interfaces(&self, interfaces: Vec<InterfaceExposure>) {
+        let imp = self.imp();
+        let Some(group) = imp.interfaces_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.interface_rows.take() {
+            group.remove(&row);
+        }
+
+        let rows: Vec<gtk4::Widget> = interfaces
+            .iter()
+            .map(|iface| self.create_interface_row(iface).upcast())
+            .collect();
+        for row in &rows {
+            group.add(row);
+        }
+        imp.interface_rows.replace(rows);
+
+        let visible = !interfaces.is_empty();
+        group.set_visible(visible);
+        if let Some(header) = imp.interfaces_header.borrow().as_ref() {
+            header.set_visible(visible);
+        }
+    }
+
+    /// Create a row for one interface, expanding to its listeners.
+    fn create_interface_row(&self, iface: &InterfaceExposure) -> adw::ExpanderRow {
+        let addresses: Vec<String> = iface.addresses.iter().map(|a| a.to_string()).collect();
+        let zone = match &iface.zone {
+            Some(zone) => gettext("Zone %s").replacen("%s", zone, 1),
+            None => gettext("Zone unknown"),
+        };
+        let subtitle = format!(
+            "{} • {} • {}",
+            iface.kind.label(),
+            zone,
+            addresses.join(", ")
+        );
+
+        let row = adw::ExpanderRow::builder()
+            .title(glib::markup_escape_text(&iface.name).as_str())
+            .subtitle(glib::markup_escape_text(&subtitle).as_str())
+            .build();
+        row.add_prefix(&gtk4::Image::from_icon_name(iface.kind.icon()));
+
+        let reachable = iface.reachable().count();
+        let (summary, icon, class) = if reachable == 0 {
+            (
+                gettext("Nothing reachable"),
+                "security-high-symbolic",
+                "success",
+            )
+        } else {
+            (
+                gettext("%s reachable").replacen("%s", &reachable.to_string(), 1),
+                "dialog-warning-symbolic",
+                "warning",
+            )
+        };
+        let summary_icon = gtk4::Image::from_icon_name(icon);
+        summary_icon.add_css_class(class);
+        let summary_label = gtk4::Label::builder()
+            .label(summary)
+            .css_classes(vec!["caption".to_string(), class.to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+        row.add_suffix(&summary_icon);
+        row.add_suffix(&summary_label);
+
+        if iface.listeners.is_empty() {
+            let empty_row = adw::ActionRow::builder()
+                .title(gettext("No services listen on this interface"))
+                .build();
+            row.add_row(&empty_row);
+        }
+
+        for listener in &iface.listeners {
+            let title = match get_service_name(listener.port) {
+                Some(service) => format!("{} ({})", listener.port, service),
+                None => listener.port.to_string(),
+            };
+            let process = listener
+                .process_name
+                .clone()
+                .unwrap_or_else(|| gettext("Unknown Process"));
+            let subtitle = format!(
+                "{} • {} • {}",
+                process,
+                listener.protocol.as_str(),
+                listener.firewall_status.label()
+            );
+            let listener_row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&title).as_str())
+                .subtitle(glib::markup_escape_text(&subtitle).as_str())
+                .build();
+            let icon = match listener.firewall_status {
+                FirewallStatus::Blocked => "security-high-symbolic",
+                FirewallStatus::Allowed { .. } => "dialog-warning-symbolic",
+                FirewallStatus::Unknown => "security-medium-symbolic",
+            };
+            listener_row.add_prefix(&gtk4::Image::from_icon_name(icon));
+            row.add_row(&listener_row);
+        }
+
+        row
+    }
+
+    /// Rebuild the Remote Access section.
+    fn update_remote_access(
+        &self,
+        endpoints: &[ListeningEndpoint],
+        access_methods: Vec<AccessMethodStatus>,
+        wake_on_lan: Vec<WakeOnLan>,
+    ) {
+        let imp = self.imp();
+        let Some(group) = imp.remote_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.remote_rows.take() {
+            group.remove(&row);
+        }
+
+        let mut rows: Vec<gtk4::Widget> = Vec::new();
+
+        for status in access_methods.iter().filter(|s| s.is_installed()) {
+            rows.push(self.create_access_method_row(status).upcast());
+        }
+
+        for wol in &wake_on_lan {
+            rows.push(self.create_wake_on_lan_row(wol).upcast());
+        }
+
+        for (service, endpoint) in remote_access_endpoints(endpoints) {
+            let process = endpoint
+                .process_name
+                .clone()
+                .unwrap_or_else(|| gettext("Unknown Process"));
+            let subtitle = format!(
+                "{}/{} • {} • {}",
+                endpoint.port,
+                endpoint.protocol.as_str(),
+                process,
+                endpoint.firewall_status.label()
+            );
+            let row = adw::ActionRow::builder()
+                .title(service)
+                .subtitle(glib::markup_escape_text(&subtitle).as_str())
+                .build();
+            let icon = match endpoint.firewall_status {
+                FirewallStatus::Blocked => "security-high-symbolic",
+                _ => "dialog-warning-symbolic",
+            };
+            row.add_prefix(&gtk4::Image::from_icon_name(icon));
+
+            let button_box = gtk4::Box::builder()
+                .orientation(gtk4::Orientation::Horizontal)
+                .spacing(6)
+                .valign(gtk4::Align::Center)
+                .build();
+
+            if let Some(process_name) = &endpoint.process_name {
+                let stop_btn = gtk4::Button::builder()
+                    .label(gettext("Stop Service"))
+                    .tooltip_text(gettext("Stop the systemd service using this port"))
+                    .build();
+                let unit = format!("{}.service", process_name);
+                let display = service.to_string();
+                let page = self.clone();
+                stop_btn.connect_clicked(move |btn| {
+                    btn.set_sensitive(false);
+                    page.confirm_stop_service(&unit, &display, btn.clone());
+                });
+                button_box.append(&stop_btn);
+            }
+
+            if !matches!(endpoint.firewall_status, FirewallStatus::Blocked) {
+                let block_btn = gtk4::Button::builder()
+                    .label(gettext("Block Port"))
+                    .css_classes(vec!["destructive-action".to_string()])
+                    .tooltip_text(gettext("Add a firewall rule to block this port"))
+                    .build();
+                let port = endpoint.port;
+                let protocol = endpoint.protocol.as_str().to_lowercase();
+                let page = self.clone();
+                block_btn.connect_clicked(move |btn| {
+                    btn.set_sensitive(false);
+                    page.block_port(port, &protocol);
+                });
+                button_box.append(&block_btn);
+            }
+
+            row.add_suffix(&button_box);
+            rows.push(row.upcast());
+        }
+
+        if rows.is_empty() {
+            let row = adw::ActionRow::builder()
+                .title(gettext("No remote access found"))
+                .subtitle(gettext(
+                    "No remote login service is installed, no interface has Wake-on-LAN and no remote management service listens on the network",
+                ))
+                .build();
+            row.add_prefix(&gtk4::Image::from_icon_name("security-high-symbolic"));
+            rows.push(row.upcast());
+        }
+
+        for row in &rows {
+            group.add(row);
+        }
+        imp.remote_rows.replace(rows);
+
+        group.set_visible(true);
+        if let Some(header) = imp.remote_header.borrow().as_ref() {
+            header.set_visible(true);
+        }
+    }
+
+    /// Create a row for a remote login method, with a switch turning its
+    /// unit and firewall service on or off together.
+    fn create_access_method_row(&self, status: &AccessMethodStatus) -> adw::ActionRow {
+        let method = status.method;
+        let running = if status.active.is_empty() {
+            gettext("Stopped")
+        } else {
+            gettext("Running")
+        };
+        let boot = if status.enabled.is_empty() {
+            gettext("not started at boot")
+        } else {
+            gettext("starts at boot")
+        };
+        let firewall = if status.open_in.is_empty() {
+            gettext("closed in the firewall")
+        } else {
+            gettext("open in %s").replace("%s", &status.open_in.join(", "))
+        };
+        let mut subtitle = format!("{} • {} • {}", running, boot, firewall);
+        if method.is_cleartext() {
+            subtitle = format!("{} • {}", subtitle, gettext("sends passwords unencrypted"));
+        }
+
+        let row = adw::ActionRow::builder()
+            .title(gettext(method.label()))
+            .subtitle(glib::markup_escape_text(&subtitle).as_str())
+            .build();
+        row.set_tooltip_text(Some(&status.installed.join(", ")));
+        let icon = if status.is_exposed() {
+            "dialog-warning-symbolic"
+        } else {
+            "security-high-symbolic"
+        };
+        row.add_prefix(&gtk4::Image::from_icon_name(icon));
+
+        let switch = gtk4::Switch::builder()
+            .active(status.is_on())
+            .valign(gtk4::Align::Center)
+            .tooltip_text(gettext(
+                "Start or stop the service and allow or close it in the firewall",
+            ))
+            .build();
+        let page = self.clone();
+        switch.connect_state_set(move |switch, enabled| {
+            // Set back after a failure or a cancel, while still insensitive
+            if !switch.is_sensitive() {
+                return glib::Propagation::Proceed;
+            }
+            switch.set_sensitive(false);
+            if enabled {
+                page.set_access_method(method, true, switch.clone());
+            } else {
+                page.confirm_disable_access_method(method, switch.clone());
+            }
+            glib::Propagation::Stop
+        });
+        row.add_suffix(&switch);
+        row.set_activatable_widget(Some(&switch));
+        row
+    }
+
+    /// Ask before turning a login method off; sessions using it end.
+    fn confirm_disable_access_method(&self, method: AccessMethod, switch: gtk4::Switch) {
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("Turn off %s?").replace("%s", &gettext(method.label())))
+            .body(gettext(
+                "The service is stopped and kept from starting at boot, and it is removed \
+                 from every firewall zone. Remote sessions using it end, including yours if \
+                 you are connected through it.",
+            ))
+            .build();
+        dialog.add_response("cancel", "_Cancel");
+        dialog.add_response("disable", "_Turn Off");
+        dialog.set_response_appearance("disable", adw::ResponseAppearance::Destructive);
+        dialog.set_default_response(Some("cancel"));
+
+        let page = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response == "disable" {
+                page.set_access_method(method, false, switch.clone());
+            } else {
+                switch.set_active(true);
+                switch.set_sensitive(true);
+            }
+        });
+
+        if let Some(root) = self.root() {
+            if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                dialog.present(Some(window));
+            }
+        }
+    }
+
+    /// Turn a login method on or off, then refresh the section.
+    fn set_access_method(&self, method: AccessMethod, enabled: bool, switch: gtk4::Switch) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(move || {
+                OperationRunner::new().run(Operation::SetRemoteAccess { method, enabled })
+            })
+            .await;
+
+            match result {
+                Ok(result) if result.success => {
+                    page.show_toast(&result.message);
+                    switch.set_state(enabled);
+                }
+                Ok(result) => {
+                    page.show_toast(&format!(
+                        "{}: {}",
+                        gettext("Failed to change %s").replace("%s", &gettext(method.label())),
+                        result.message
+                    ));
+                    switch.set_active(!enabled);
+                }
+                Err(_) => {
+                    page.show_toast(
+                        &gettext("Failed to change %s").replace("%s", &gettext(method.label())),
+                    );
+                    switch.set_active(!enabled);
+                }
+            }
+            switch.set_sensitive(true);
+            page.request_refresh(Invalidation::ServicesChanged);
+            page.refresh();
+        });
+    }
+
+    /// Create a row for the Wake-on-LAN state of one interface.
+    fn create_wake_on_lan_row(&self, wol: &WakeOnLan) -> adw::ActionRow {
+        let row = adw::ActionRow::builder()
+            .title(
+                gettext("Wake-on-LAN on %s")
+                    .replace("%s", &glib::markup_escape_text(&wol.interface)),
+            )
+            .build();
+
+        if !wol.is_enabled() {
+            row.set_subtitle(&gettext("Disabled"));
+            row.add_prefix(&gtk4::Image::from_icon_name("security-high-symbolic"));
+            return row;
+        }
+
+        row.set_subtitle(
+            &gettext("Wakes on %s; the computer can be powered on over the network")
+                .replace("%s", &wol.enabled_modes().join(", ")),
+        );
+        row.add_prefix(&gtk4::Image::from_icon_name("dialog-warning-symbolic"));
+
+        let disable_btn = gtk4::Button::builder()
+            .label(gettext("Disable"))
+            .css_classes(vec!["destructive-action".to_string()])
+            .valign(gtk4::Align::Center)
+            .tooltip_text(gettext(
+                "Turn off Wake-on-LAN until the next boot (requires administrator rights)",
+            ))
+            .build();
+        let page = self.clone();
+        let interface = wol.interface.clone();
+        disable_btn.connect_clicked(move |btn| {
+            btn.set_sensitive(false);
+            page.disable_wake_on_lan(interface.clone(), btn.clone());
+        });
+        row.add_suffix(&disable_btn);
+        row
+    }
+
+    /// Turn off Wake-on-LAN on an interface through the privileged helper.
+    fn disable_wake_on_lan(&self, interface: String, btn: gtk4::Button) {
+        let page = self.clone();
+
+        glib::spawn_future_local(async move {
+            let result =
+                gtk4::gio::spawn_blocking(move || crate::admin::disable_wake_on_lan(&interface))
+                    .await;
+
+            match result {
+                Ok(Ok(message)) => {
+                    page.show_toast(&message);
+                    page.refresh();
+                }
+                Ok(Err(e)) => {
+                    error!("Failed to disable Wake-on-LAN: {}", e);
+                    page.show_toast(&format!(
+                        "{}: {}",
+                        gettext("Failed to disable Wake-on-LAN"),
+                        e
+                    ));
+                    btn.set_sensitive(true);
+                }
+                Err(_) => {
+                    page.show_toast(&gettext("Failed to disable Wake-on-LAN"));
+                    btn.set_sensitive(true);
+                }
+            }
+        });
+    }
+
+    /// List the network namespaces besides this one.
+    fn refresh_namespaces(&self) {
+        let page = self.clone();
+
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(crate::admin::query_namespaces).await;
+
+            match result {
+                Ok(Ok(namespaces)) => page.update_namespaces(namespaces, None),
+                Ok(Err(e)) => warn!("Failed to list network namespaces: {}", e),
+                Err(e) => error!("Namespace query task failed: {:?}", e),
+            }
+        });
+    }
+
+    /// Rebuild the Network Namespaces section, expanding the namespace with
+    /// inode `expand`.
+    fn update_namespaces(&self, namespaces: Vec<NetNamespace>, expand: Option<u64>) {
+        let imp = self.imp();
+        let Some(group) = imp.namespaces_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.namespace_rows.take() {
+            group.remove(&row);
+        }
+        // Scans of namespaces that are gone
+        imp.namespace_scans
+            .borrow_mut()
+            .retain(|inode, _| namespaces.iter().any(|ns| ns.inode == *inode));
+
+        let rows: Vec<gtk4::Widget> = namespaces
+            .iter()
+            .map(|ns| {
+                let row = self.create_namespace_row(ns);
+                row.set_expanded(expand == Some(ns.inode));
+                row.upcast()
+            })
+            .collect();
+        for row in &rows {
+            group.add(row);
+        }
+        imp.namespace_rows.replace(rows);
+
+        let has_any = !namespaces.is_empty();
+        imp.namespaces.replace(namespaces);
+        group.set_visible(has_any);
+        if let Some(header) = imp.namespaces_header.borrow().as_ref() {
+            header.set_visible(has_any);
+        }
+    }
+
+    /// Create a row for a namespace, with the results of its last scan.
+    fn create_namespace_row(&self, namespace: &NetNamespace) -> adw::ExpanderRow {
+        let scans = self.imp().namespace_scans.borrow();
+        let scan = scans.get(&namespace.inode);
+
+        let subtitle = match scan {
+            Some(scan) => gettext("%d listening (%e on all interfaces) • %c connections")
+                .replace("%d", &scan.listening.len().to_string())
+                .replace("%e", &scan.exposed_count().to_string())
+                .replace("%c", &scan.connections.len().to_string()),
+            None if namespace.name.is_some() => gettext("Named namespace • not scanned"),
+            None => gettext("%d processes • not scanned")
+                .replace("%d", &namespace.pids.len().to_string()),
+        };
+        let row = adw::ExpanderRow::builder()
+            .title(glib::markup_escape_text(&namespace.label()).as_str())
+            .subtitle(subtitle)
+            .enable_expansion(scan.is_some())
+            .build();
+        let icon = match scan {
+            Some(scan) if scan.exposed_count() > 0 => "dialog-warning-symbolic",
+            _ => "network-server-symbolic",
+        };
+        row.add_prefix(&gtk4::Image::from_icon_name(icon));
+
+        if let Some(reference) = namespace.reference() {
+            let scan_btn = gtk4::Button::builder()
+                .label(if scan.is_some() {
+                    gettext("Scan Again")
+                } else {
+                    gettext("Scan")
+                })
+                .valign(gtk4::Align::Center)
+                .tooltip_text(gettext(
+                    "List the ports open inside this namespace (requires administrator rights)",
+                ))
+                .build();
+            let page = self.clone();
+            let inode = namespace.inode;
+            scan_btn.connect_clicked(move |btn| {
+                btn.set_sensitive(false);
+                page.scan_namespace(inode, reference.clone(), btn.clone());
+            });
+            row.add_suffix(&scan_btn);
+        }
+
+        let Some(scan) = scan else {
+            return row;
+        };
+        for listener in &scan.listening {
+            let title = match get_service_name(listener.port) {
+                Some(service) => format!(
+                    "{}/{} ({})",
+                    listener.port,
+                    listener.protocol.as_str(),
+                    service
+                ),
+                None => format!("{}/{}", listener.port, listener.protocol.as_str()),
+            };
+            let process = listener
+                .process_name
+                .clone()
+                .unwrap_or_else(|| gettext("Unknown Process"));
+            let listener_row = adw::ActionRow::builder()
+                .title(title)
+                .subtitle(
+                    glib::markup_escape_text(&format!("{} • {}", listener.local_addr, process))
+                        .as_str(),
+                )
+                .build();
+            let icon = if listener.is_exposed() {
+                "dialog-warning-symbolic"
+            } else {
+                "computer-symbolic"
+            };
+            listener_row.add_prefix(&gtk4::Image::from_icon_name(icon));
+            row.add_row(&listener_row);
+        }
+        for conn in &scan.connections {
+            let process = conn
+                .process_name
+                .clone()
+                .unwrap_or_else(|| gettext("Unknown Process"));
+            let conn_row = adw::ActionRow::builder()
+                .title(format!("{}:{}", conn.remote_addr, conn.remote_port))
+                .subtitle(
+                    glib::markup_escape_text(&format!(
+                        "{} {} • {}",
+                        gettext("Local port"),
+                        conn.local_port,
+                        process
+                    ))
+                    .as_str(),
+                )
+                .build();
+            conn_row.add_prefix(&gtk4::Image::from_icon_name(
+                "network-transmit-receive-symbolic",
+            ));
+            row.add_row(&conn_row);
+        }
+        if scan.listening.is_empty() && scan.connections.is_empty() {
+            row.add_row(
+                &adw::ActionRow::builder()
+                    .title(gettext("No listening ports or connections"))
+                    .build(),
+            );
+        }
+        row
+    }
+
+    /// Scan a namespace through the privileged helper and show the results
+    /// under its row.
+    fn scan_namespace(&self, inode: u64, reference: crate::admin::NamespaceRef, btn: gtk4::Button) {
+        let page = self.clone();
+
+        glib::spawn_future_local(async move {
+            let result =
+                gtk4::gio::spawn_blocking(move || crate::admin::scan_namespace(&reference)).await;
+
+            match result {
+                Ok(Ok(scan)) => {
+                    let imp = page.imp();
+                    imp.namespace_scans.borrow_mut().insert(inode, scan);
+                    let namespaces = imp.namespaces.borrow().clone();
+                    page.update_namespaces(namespaces, Some(inode));
+                }
+                Ok(Err(e)) => {
+                    error!("Failed to scan network namespace: {}", e);
+                    page.show_toast(&format!(
+                        "{}: {}",
+                        gettext("Failed to scan the namespace"),
+                        e
+                    ));
+                    btn.set_sensitive(true);
+                }
+                Err(_) => {
+                    page.show_toast(&gettext("Failed to scan the namespace"));
+                    btn.set_sensitive(true);
+                }
+            }
+        });
+    }
+
+    /// Query Avahi and the firewalld `mdns` service state.
+    fn refresh_mdns(&self) {
+        let page = self.clone();
+
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(move || {
+                let status = crate::admin::query_avahi(std::time::Duration::from_secs(3));
+                // Active zones that let mDNS through; empty when firewalld is unreachable
+                let mdns_zones: Vec<String> =
+                    crate::firewall::read_blocking(|client| match client.connect() {
+                        Ok(()) => client
+                            .get_zones()
+                            .unwrap_or_default()
+                            .into_iter()
+                            .filter(|z| z.is_active && z.services.iter().any(|s| s == "mdns"))
+                            .map(|z| z.name)
+                            .collect(),
+                        Err(_) => Vec::new(),
+                    })
+                    .unwrap_or_default();
+                (status, mdns_zones)
+            })
+            .await;
+
+            match result {
+                Ok((status, mdns_zones)) => page.update_mdns(status, mdns_zones),
+                Err(e) => error!("mDNS query task failed: {:?}", e),
+            }
+        });
+    }
+
+    /// Rebuild the mDNS section.
+    fn update_mdns(&self, status: AvahiStatus, mdns_zones: Vec<String>) {
+        let imp = self.imp();
+        let Some(group) = imp.mdns_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.mdns_rows.take() {
+            group.remove(&row);
+        }
+
+        let exposure = MdnsExposure::assess(status.daemon_running, &mdns_zones);
+        let mut rows: Vec<gtk4::Widget> = Vec::new();
+
+        let (title, subtitle, icon) = match &exposure {
+            MdnsExposure::Advertising { zones } => (
+                gettext("Advertising on the local network"),
+                gettext("Avahi is running and mDNS is allowed in zone(s): %s")
+                    .replace("%s", &zones.join(", ")),
+                "dialog-warning-symbolic",
+            ),
+            MdnsExposure::BlockedByFirewall => (
+                gettext("Announcements blocked by firewall"),
+                gettext(
+                    "Avahi is running, but mDNS is not allowed in any active zone, \
+                     so other devices cannot discover these services",
+                ),
+                "security-high-symbolic",
+            ),
+            MdnsExposure::OpenButIdle { zones } => (
+                gettext("mDNS allowed but unused"),
+                gettext("Avahi is not running, yet mDNS is still allowed in zone(s): %s")
+                    .replace("%s", &zones.join(", ")),
+                "dialog-information-symbolic",
+            ),
+            MdnsExposure::Quiet => (
+                gettext("Not advertising"),
+                gettext("Avahi is not running and mDNS is blocked"),
+                "security-high-symbolic",
+            ),
+        };
+
+        let status_row = adw::ActionRow::builder()
+            .title(title)
+            .subtitle(match &status.host_name {
+                Some(host) => format!("{} • {}", subtitle, host),
+                None => subtitle,
+            })
+            .build();
+        status_row.add_prefix(&gtk4::Image::from_icon_name(icon));
+
+        let button_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(6)
+            .valign(gtk4::Align::Center)
+            .build();
+
+        if !mdns_zones.is_empty() {
+            let block_btn = gtk4::Button::builder()
+                .label(gettext("Block mDNS"))
+                .tooltip_text(gettext("Remove the mdns service from these zones"))
+                .build();
+            let page = self.clone();
+            let zones = mdns_zones.clone();
+            block_btn.connect_clicked(move |btn| {
+                btn.set_sensitive(false);
+                page.block_mdns(zones.clone());
+            });
+            button_box.append(&block_btn);
+        }
+
+        if status.daemon_running {
+            let disable_btn = gtk4::Button::builder()
+                .label(gettext("Disable Avahi"))
+                .css_classes(vec!["destructive-action".to_string()])
+                .tooltip_text(gettext(
+                    "Stop avahi-daemon and keep it from starting at boot",
+                ))
+                .build();
+            let page = self.clone();
+            disable_btn.connect_clicked(move |btn| {
+                btn.set_sensitive(false);
+                page.confirm_disable_avahi(btn.clone());
+            });
+            button_box.append(&disable_btn);
+        }
+
+        status_row.add_suffix(&button_box);
+        rows.push(status_row.upcast());
+
+        for service in &status.services {
+            rows.push(self.create_mdns_row(service).upcast());
+        }
+
+        for row in &rows {
+            group.add(row);
+        }
+        imp.mdns_rows.replace(rows);
+
+        // Hide the whole section on systems that never had Avahi set up
+        let relevant =
+            status.daemon_running || !status.services.is_empty() || !mdns_zones.is_empty();
+        group.set_visible(relevant);
+        if let Some(header) = imp.mdns_header.borrow().as_ref() {
+            header.set_visible(relevant);
+        }
+    }
+
+    /// Create a row for one advertised service.
+    fn create_mdns_row(&self, service: &AdvertisedService) -> adw::ActionRow {
+        let mut subtitle = format!("{} • {}", service.type_label(), service.service_type);
+        if let Some(port) = service.port {
+            subtitle.push_str(&format!(" • {}", port));
+        }
+        if let Some(path) = &service.static_file {
+            subtitle.push_str(&format!(" • {}", path.display()));
+        }
+
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(&service.name).as_str())
+            .subtitle(glib::markup_escape_text(&subtitle).as_str())
+            .build();
+
+        if let Some(unit) = service.publisher_unit() {
+            let stop_btn = gtk4::Button::builder()
+                .label(gettext("Stop Service"))
+                .valign(gtk4::Align::Center)
+                .tooltip_text(unit)
+                .build();
+            let page = self.clone();
+            let display = service.type_label().to_string();
+            stop_btn.connect_clicked(move |btn| {
+                btn.set_sensitive(false);
+                page.confirm_stop_service(unit, &display, btn.clone());
+            });
+            row.add_suffix(&stop_btn);
+        } else if service.static_file.is_some() {
+            // Static definitions are root-owned files; point at them instead
+            // of deleting configuration on the user's behalf.
+            row.set_tooltip_text(Some(&gettext(
+                "Defined in a static Avahi service file; remove the file as administrator to stop advertising it",
+            )));
+        }
+
+        row
+    }
+
+    /// Remove the firewalld `mdns` service from the given zones.
+    fn block_mdns(&self, zones: Vec<String>) {
+        let page = self.clone();
+
+        glib::spawn_future_local(async move {
+            let result = crate::firewall::call(move |client| {
+                if let Err(e) = client.connect() {
+                    return Err(anyhow::anyhow!("Failed to connect to firewalld: {}", e));
+                }
+                let mut session_only = Vec::new();
+                for zone in &zones {
+                    if client.disable_service(zone, "mdns", true)?.failed() {
+                        session_only.push(zone.clone());
+                    }
+                }
+                Ok(session_only)
+            })
+            .await;
+
+            match result {
+                Ok(Ok(session_only)) => {
+                    if session_only.is_empty() {
+                        page.show_toast(&gettext("mDNS blocked"));
+                    } else {
+                        page.show_toast(&format!(
+                            "mDNS blocked in '{}' for this session only — saving permanently failed",
+                            session_only.join(", ")
+                        ));
+                    }
+                    page.refresh();
+                    page.request_refresh(Invalidation::ServicesChanged);
+                }
+                Ok(Err(e)) => {
+                    error!("Failed to block mDNS: {}", e);
+                    page.show_toast(&format!("{}: {}", gettext("Failed to block mDNS"), e));
+                    page.refresh_mdns();
+                }
+                Err(_) => {
+                    error!("Task failed");
+                    page.show_toast(&gettext("Failed to block mDNS"));
+                    page.refresh_mdns();
+                }
+            }
+        });
+    }
+
+    /// Confirm, then stop and disable avahi-daemon.
+    fn confirm_disable_avahi(&self, btn: gtk4::Button) {
+        let page = self.clone();
+
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("Disable Avahi?"))
+            .body(gettext(
+                "This stops avahi-daemon and disables it at boot. This computer will no \
+                 longer announce itself, and network printers or shares may no longer be \
+                 discovered automatically.",
+            ))
+            .build();
+        dialog.add_response("cancel", "_Cancel");
+        dialog.add_response("disable", "_Disable");
+        dialog.set_response_appearance("disable", adw::ResponseAppearance::Destructive);
+        dialog.set_default_response(Some("cancel"));
+
+        dialog.connect_response(None, move |_, response| {
+            if response != "disable" {
+                btn.set_sensitive(true);
+                return;
+            }
+
+            let page = page.clone();
+            let btn = btn.clone();
+            glib::spawn_future_local(async move {
+                let result = gtk4::gio::spawn_blocking(move || {
+                    let mut client = crate::systemd::SystemdClient::new();
+                    client.connect()?;
+                    // Socket first so activation cannot restart the daemon
+                    for unit in AVAHI_UNITS {
+                        client.stop_service(unit)?;
+                        client.disable_service(unit)?;
+                    }
+                    Ok::<_, anyhow::Error>(())
+                })
+                .await;
+
+                match result {
+                    Ok(Ok(())) => {
+                        page.show_toast(&gettext("Avahi disabled"));
+                        page.refresh_mdns();
+                    }
+                    Ok(Err(e)) => {
+                        page.show_toast(&format!("{}: {}", gettext("Failed to disable Avahi"), e));
+                        btn.set_sensitive(true);
+                    }
+                    Err(_) => {
+                        page.show_toast(&gettext("Failed to disable Avahi"));
+                        btn.set_sensitive(true);
+                    }
+                }
+            });
+        });
+
+        if let Some(root) = self.root() {
+            if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                dialog.present(Some(window));
+            }
+        }
+    }
+
     /// A titled card container matching the overview cards.
     fn create_chart_card(&self, title: &str) -> gtk4::Frame {
         let frame = gtk4::Frame::builder().build();
@@ -494,6 +1548,19 @@ impl NetworkExposurePage {
                         .build();
                     row.add_suffix(&badge);
                 }
+                row.add_suffix(&gtk4::Image::from_icon_name("go-next-symbolic"));
+                row.set_activatable(true);
+                let ip = g.addr;
+                let country_label = geo_labels.get(&g.addr).cloned();
+                row.connect_activated(move |row| {
+                    present_ip_details(
+                        row,
+                        IpDetailsContext {
+                            country_label: country_label.clone(),
+                            ..IpDetailsContext::for_address(ip)
+                        },
+                    );
+                });
                 group.add(&row);
             }
             group.set_visible(has_any);
@@ -503,6 +1570,262 @@ impl NetworkExposurePage {
         }
     }
 
+    /// Check the listening executables against their packages through the
+    /// privileged helper, then flag the endpoints they serve.
+    fn verify_binaries(&self, btn: gtk4::Button) {
+        let page = self.clone();
+
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(crate::admin::verify_listening_binaries).await;
+            btn.set_sensitive(true);
+
+            match result {
+                Ok(Ok(results)) => {
+                    let suspicious = results.iter().filter(|b| b.status.is_suspicious()).count();
+                    if suspicious == 0 {
+                        page.show_toast(&gettext("No unpackaged or modified listening programs"));
+                    } else {
+                        page.show_toast(&format!(
+                            "{} {}",
+                            suspicious,
+                            gettext("listening programs are unpackaged or modified")
+                        ));
+                    }
+                    page.update_integrity(results);
+                    let endpoints = page.imp().endpoints.borrow().clone();
+                    page.update_endpoints(endpoints);
+                }
+                Ok(Err(e)) => {
+                    error!("Failed to verify listening programs: {}", e);
+                    page.show_toast(&format!(
+                        "{}: {}",
+                        gettext("Failed to verify listening programs"),
+                        e
+                    ));
+                }
+                Err(_) => page.show_toast(&gettext("Failed to verify listening programs")),
+            }
+        });
+    }
+
+    /// Rebuild the Binary Integrity section, flagged programs first.
+    fn update_integrity(&self, mut results: Vec<BinaryIntegrity>) {
+        let imp = self.imp();
+        results.sort_by_key(|b| {
+            (
+                !b.status.is_suspicious(),
+                b.status == IntegrityStatus::Verified,
+            )
+        });
+
+        if let Some(group) = imp.integrity_group.borrow().as_ref() {
+            for row in imp.integrity_rows.take() {
+                group.remove(&row);
+            }
+            let mut rows: Vec<gtk4::Widget> = results
+                .iter()
+                .map(|binary| self.create_integrity_row(binary).upcast())
+                .collect();
+            if rows.is_empty() {
+                let row = adw::ActionRow::builder()
+                    .title(gettext("No listening process found"))
+                    .build();
+                rows.push(row.upcast());
+            }
+            for row in &rows {
+                group.add(row);
+            }
+            imp.integrity_rows.replace(rows);
+            group.set_visible(true);
+        }
+        if let Some(header) = imp.integrity_header.borrow().as_ref() {
+            header.set_visible(true);
+        }
+        imp.integrity.replace(results);
+    }
+
+    /// Create a row for the check of one listening process.
+    fn create_integrity_row(&self, binary: &BinaryIntegrity) -> adw::ActionRow {
+        let ports: Vec<String> = binary
+            .listeners
+            .iter()
+            .map(|(port, protocol)| format!("{}/{}", port, protocol.as_str()))
+            .collect();
+        let title = format!(
+            "{} (pid {})",
+            binary.process_name.as_deref().unwrap_or("?"),
+            binary.pid
+        );
+        let mut subtitle = binary.executable.clone();
+        if let Some(package) = &binary.package {
+            subtitle = format!("{} • {}", subtitle, package);
+        }
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(&title).as_str())
+            .subtitle(
+                glib::markup_escape_text(&format!("{} • {}", subtitle, ports.join(", "))).as_str(),
+            )
+            .subtitle_selectable(true)
+            .build();
+
+        let (icon_name, class) = Self::integrity_icon(&binary.status);
+        let icon = gtk4::Image::from_icon_name(icon_name);
+        if let Some(class) = class {
+            icon.add_css_class(class);
+        }
+        row.add_prefix(&icon);
+
+        let label = gtk4::Label::builder()
+            .label(binary.status.label())
+            .css_classes(vec!["caption".to_string()])
+            .valign(gtk4::Align::Center)
+            .wrap(true)
+            .build();
+        if let Some(class) = class {
+            label.add_css_class(class);
+        }
+        row.add_suffix(&label);
+
+        if binary.status.is_suspicious() {
+            row.add_suffix(&self.create_quarantine_button(binary));
+        }
+        row
+    }
+
+    /// Create a button that quarantines the process of `binary`.
+    fn create_quarantine_button(&self, binary: &BinaryIntegrity) -> gtk4::Button {
+        let button = gtk4::Button::builder()
+            .label(gettext("Quarantine"))
+            .css_classes(vec!["destructive-action".to_string()])
+            .tooltip_text(gettext(
+                "Record the program, block its ports and stop its service",
+            ))
+            .valign(gtk4::Align::Center)
+            .build();
+        let page = self.clone();
+        let binary = binary.clone();
+        button.connect_clicked(move |btn| {
+            btn.set_sensitive(false);
+            page.confirm_quarantine(&binary, btn.clone());
+        });
+        button
+    }
+
+    /// Show the steps of quarantining a process, then take them.
+    fn confirm_quarantine(&self, binary: &BinaryIntegrity, btn: gtk4::Button) {
+        let plan = crate::admin::plan_quarantine(binary);
+        let name = plan
+            .process_name
+            .clone()
+            .unwrap_or_else(|| plan.executable.clone());
+
+        let mut steps = vec![
+            format!(
+                "• {} {}",
+                gettext("Record the SHA-256 and command line of"),
+                plan.executable
+            ),
+            format!(
+                "• {} {}",
+                gettext("Block in every active zone:"),
+                plan.ports().join(", ")
+            ),
+        ];
+        match &plan.unit {
+            Some(unit) => steps.push(format!("• {} {}", gettext("Stop the service"), unit)),
+            None => steps.push(format!(
+                "• {}",
+                gettext("The process runs in no system service and is left running")
+            )),
+        }
+
+        let dialog = adw::AlertDialog::builder()
+            .heading(format!("{} {}?", gettext("Quarantine"), name))
+            .body(format!(
+                "{}\n\n{}\n\n{}",
+                plan.reason,
+                steps.join("\n"),
+                gettext("The steps are kept as an incident on the Incidents page.")
+            ))
+            .build();
+        dialog.add_response("cancel", &gettext("_Cancel"));
+        dialog.add_response("quarantine", &gettext("_Quarantine"));
+        dialog.set_response_appearance("quarantine", adw::ResponseAppearance::Destructive);
+        dialog.set_default_response(Some("cancel"));
+
+        let page = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "quarantine" {
+                btn.set_sensitive(true);
+                return;
+            }
+            let page = page.clone();
+            let plan = plan.clone();
+            let name = name.clone();
+            let btn = btn.clone();
+            glib::spawn_future_local(async move {
+                let result =
+                    gtk4::gio::spawn_blocking(move || crate::admin::quarantine(&plan)).await;
+
+                match result {
+                    Ok(Ok(incident)) => {
+                        if !incident.has_failures() {
+                            page.show_toast(&format!("{} {}", gettext("Quarantined"), name));
+                        } else {
+                            page.show_toast(&format!(
+                                "{} {}",
+                                gettext("Some quarantine steps failed for"),
+                                name
+                            ));
+                        }
+                        page.refresh();
+                        page.request_refresh(Invalidation::PortsChanged);
+                    }
+                    Ok(Err(e)) => {
+                        error!("Failed to quarantine {}: {}", name, e);
+                        page.show_toast(&format!("{}: {}", gettext("Failed to quarantine"), e));
+                        btn.set_sensitive(true);
+                    }
+                    Err(_) => {
+                        page.show_toast(&gettext("Failed to quarantine"));
+                        btn.set_sensitive(true);
+                    }
+                }
+            });
+        });
+
+        if let Some(root) = self.root() {
+            if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                dialog.present(Some(window));
+            }
+        }
+    }
+
+    /// Icon and style class of an integrity status.
+    fn integrity_icon(status: &IntegrityStatus) -> (&'static str, Option<&'static str>) {
+        match status {
+            IntegrityStatus::Verified => ("emblem-ok-symbolic", Some("success")),
+            IntegrityStatus::Modified(_) | IntegrityStatus::Unpackaged => {
+                ("dialog-error-symbolic", Some("error"))
+            }
+            IntegrityStatus::Replaced => ("dialog-warning-symbolic", Some("warning")),
+            IntegrityStatus::Unknown(_) => ("dialog-question-symbolic", None),
+        }
+    }
+
+    /// The last check of the process behind an endpoint, if any.
+    fn integrity_of(&self, endpoint: &ListeningEndpoint) -> Option<BinaryIntegrity> {
+        self.imp()
+            .integrity
+            .borrow()
+            .iter()
+            .find(|b| {
+                b.listeners.contains(&(endpoint.port, endpoint.protocol))
+                    && endpoint.pid.is_none_or(|pid| pid == b.pid)
+            })
+            .cloned()
+    }
+
     /// Update the UI with scanned endpoints.
     fn update_endpoints(&self, endpoints: Vec<ListeningEndpoint>) {
         let imp = self.imp();
@@ -581,6 +1904,44 @@ impl NetworkExposurePage {
         }
     }
 
+    /// Row with the user and capabilities of the endpoint's process, flagged
+    /// when a root daemon keeps privileges it does not need.
+    fn create_privileges_row(&self, endpoint: &ListeningEndpoint) -> Option<adw::ActionRow> {
+        let privileges: Option<ProcessPrivileges> = endpoint
+            .pid
+            .and_then(|pid| self.imp().privileges.borrow().get(&pid).cloned());
+        let (subtitle, flagged) = match &privileges {
+            Some(process) => (
+                format!(
+                    "{} • {}",
+                    process.user_label(),
+                    process.capabilities_label()
+                ),
+                process.could_run_unprivileged(endpoint.process_name.as_deref()),
+            ),
+            // The process of another user's socket is only visible to root
+            None if endpoint.uid == Some(0) => (
+                gettext("root • capabilities of other users' processes are not readable"),
+                false,
+            ),
+            None => return None,
+        };
+        let row = adw::ActionRow::builder()
+            .title(gettext("Privileges"))
+            .subtitle(glib::markup_escape_text(&subtitle).as_str())
+            .subtitle_selectable(true)
+            .build();
+        if flagged {
+            let icon = gtk4::Image::from_icon_name("dialog-warning-symbolic");
+            icon.add_css_class("warning");
+            row.add_prefix(&icon);
+            row.set_tooltip_text(Some(&gettext(
+                "Runs as root with all privileges; it could run as its own user",
+            )));
+        }
+        Some(row)
+    }
+
     /// Create a row for an endpoint.
     fn create_endpoint_row(&self, endpoint: &ListeningEndpoint) -> adw::ExpanderRow {
         let port_label = if let Some(service) = get_service_name(endpoint.port) {
@@ -594,9 +1955,24 @@ impl NetworkExposurePage {
             .clone()
             .unwrap_or_else(|| gettext("Unknown Process"));
 
+        // What the user documented for this port on the Ports page
+        let zone = match &endpoint.firewall_status {
+            FirewallStatus::Allowed { zone } => Some(zone.as_str()),
+            _ => None,
+        };
+        let note =
+            self.imp()
+                .storage
+                .borrow_mut()
+                .lookup(endpoint.port, endpoint.protocol.as_str(), zone);
+
+        let mut subtitle = format!("{} • {}", process_name, endpoint.protocol.as_str());
+        if let Some(note) = &note {
+            subtitle = format!("{} • {}", note.name, subtitle);
+        }
         let row = adw::ExpanderRow::builder()
             .title(&port_label)
-            .subtitle(format!("{} • {}", process_name, endpoint.protocol.as_str()))
+            .subtitle(glib::markup_escape_text(&subtitle).as_str())
             .build();
 
         // Status icon based on exposure
@@ -616,18 +1992,32 @@ impl NetworkExposurePage {
             .valign(gtk4::Align::Center)
             .build();
 
-        match &endpoint.firewall_status {
-            FirewallStatus::Allowed { .. } => {
-                fw_label.add_css_class("warning");
-            }
-            FirewallStatus::Blocked => {
-                fw_label.add_css_class("success");
-            }
-            _ => {}
+        // Icon as well as colour, so the state reads without colour vision
+        let fw_icon = match &endpoint.firewall_status {
+            FirewallStatus::Allowed { .. } => Some(("dialog-warning-symbolic", "warning")),
+            FirewallStatus::Blocked => Some(("security-high-symbolic", "success")),
+            _ => None,
+        };
+        if let Some((icon_name, class)) = fw_icon {
+            fw_label.add_css_class(class);
+            let icon = gtk4::Image::from_icon_name(icon_name);
+            icon.add_css_class(class);
+            row.add_suffix(&icon);
         }
 
         row.add_suffix(&fw_label);
 
+        // Package verification, once run
+        let integrity = self.integrity_of(endpoint);
+        if let Some(binary) = &integrity {
+            if binary.status.is_suspicious() {
+                let icon = gtk4::Image::from_icon_name("dialog-error-symbolic");
+                icon.add_css_class("error");
+                icon.set_tooltip_text(Some(&binary.status.label()));
+                row.add_prefix(&icon);
+            }
+        }
+
         // Warning if risky
         if let Some(warning) = endpoint.warning() {
             let warning_row = adw::ActionRow::builder()
@@ -637,6 +2027,21 @@ impl NetworkExposurePage {
             row.add_row(&warning_row);
         }
 
+        if let Some(note) = &note {
+            let description = if note.description.is_empty() {
+                gettext("No description")
+            } else {
+                note.description.clone()
+            };
+            let note_row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&note.name).as_str())
+                .subtitle(glib::markup_escape_text(&description).as_str())
+                .tooltip_text(gettext("Your note from the Ports page"))
+                .build();
+            note_row.add_prefix(&gtk4::Image::from_icon_name("document-edit-symbolic"));
+            row.add_row(&note_row);
+        }
+
         // Details row
         let details_row = adw::ActionRow::builder()
             .title(gettext("Listening Address"))
@@ -659,6 +2064,28 @@ impl NetworkExposurePage {
             row.add_row(&process_row);
         }
 
+        if let Some(privileges_row) = self.create_privileges_row(endpoint) {
+            row.add_row(&privileges_row);
+        }
+
+        if let Some(binary) = &integrity {
+            let mut subtitle = binary.status.label();
+            if let Some(package) = &binary.package {
+                subtitle = format!("{} • {}", package, subtitle);
+            }
+            let package_row = adw::ActionRow::builder()
+                .title(gettext("Package"))
+                .subtitle(glib::markup_escape_text(&subtitle).as_str())
+                .build();
+            let (icon_name, class) = Self::integrity_icon(&binary.status);
+            let icon = gtk4::Image::from_icon_name(icon_name);
+            if let Some(class) = class {
+                icon.add_css_class(class);
+            }
+            package_row.add_prefix(&icon);
+            row.add_row(&package_row);
+        }
+
         // Actions row
         let actions_row = adw::ActionRow::builder().title(gettext("Actions")).build();
 
@@ -686,6 +2113,11 @@ impl NetworkExposurePage {
             button_box.append(&stop_btn);
         }
 
+        // Quarantine, when package verification flagged the program
+        if let Some(binary) = integrity.as_ref().filter(|b| b.status.is_suspicious()) {
+            button_box.append(&self.create_quarantine_button(binary));
+        }
+
         // Block port button (red with white text)
         let block_btn = gtk4::Button::builder()
             .label(gettext("Block Port"))
@@ -783,8 +2215,7 @@ impl NetworkExposurePage {
             let port_clone = port_str.clone();
             let protocol_clone = protocol.clone();
 
-            let result = gtk4::gio::spawn_blocking(move || {
-                let mut client = crate::firewall::FirewallClient::new();
+            let result = crate::firewall::call(move |client| {
                 if let Err(e) = client.connect() {
                     return Err(anyhow::anyhow!("Failed to connect to firewalld: {}", e));
                 }
@@ -800,10 +2231,7 @@ impl NetworkExposurePage {
 
                 // Add rich rule to reject connections on this port.
                 // No family attribute so the block covers IPv4 and IPv6.
-                let rule = format!(
-                    "rule port port=\"{}\" protocol=\"{}\" reject",
-                    port_clone, valid_proto
-                );
+                let rule = RichRule::port_block(&port_clone, valid_proto).to_string();
 
                 // Add once with permanent=true: the client writes both runtime
                 // and permanent config in a single call and reports the outcome
@@ -829,7 +2257,7 @@ impl NetworkExposurePage {
                     // Refresh to show updated status
                     page.refresh();
                     // Also refresh main window data so Ports page updates
-                    page.request_refresh();
+                    page.request_refresh(Invalidation::PortsChanged);
                 }
                 Ok(Err(e)) => {
                     error!("Failed to block port: {}", e);
@@ -854,12 +2282,12 @@ impl NetworkExposurePage {
         }
     }
 
-    /// Request a global refresh from the main window.
-    fn request_refresh(&self) {
+    /// Have the main window refresh what shows `change`.
+    fn request_refresh(&self, change: Invalidation) {
         if let Some(root) = self.root() {
             if let Some(window) = root.downcast_ref::<gtk4::Window>() {
                 if let Some(main_window) = window.downcast_ref::<super::MainWindow>() {
-                    main_window.refresh_data();
+                    main_window.invalidate(change);
                 }
             }
         }
@@ -894,22 +2322,6 @@ impl NetworkExposurePage {
     }
 }
 
-/// Format a byte count as a compact human-readable string (B/KB/MB/GB).
-fn format_bytes(bytes: u64) -> String {
-    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
-    let mut value = bytes as f64;
-    let mut unit = 0;
-    while value >= 1024.0 && unit < UNITS.len() - 1 {
-        value /= 1024.0;
-        unit += 1;
-    }
-    if unit == 0 {
-        format!("{} {}", bytes, UNITS[unit])
-    } else {
-        format!("{:.1} {}", value, UNITS[unit])
-    }
-}
-
 mod imp {
     use super::*;
 
@@ -923,12 +2335,35 @@ mod imp {
         pub exposed_group: RefCell<Option<adw::PreferencesGroup>>,
         pub local_header: RefCell<Option<gtk4::Box>>,
         pub local_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub mdns_header: RefCell<Option<gtk4::Box>>,
+        pub mdns_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub mdns_rows: RefCell<Vec<gtk4::Widget>>,
+        pub interfaces_header: RefCell<Option<gtk4::Box>>,
+        pub interfaces_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub interface_rows: RefCell<Vec<gtk4::Widget>>,
+        pub remote_header: RefCell<Option<gtk4::Box>>,
+        pub remote_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub remote_rows: RefCell<Vec<gtk4::Widget>>,
+        pub namespaces_header: RefCell<Option<gtk4::Box>>,
+        pub namespaces_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub namespace_rows: RefCell<Vec<gtk4::Widget>>,
+        pub namespaces: RefCell<Vec<NetNamespace>>,
+        /// Last scan of each namespace, by inode, kept across refreshes.
+        pub namespace_scans: RefCell<HashMap<u64, NamespaceScan>>,
         pub connections_header: RefCell<Option<gtk4::Box>>,
         pub connections_group: RefCell<Option<adw::PreferencesGroup>>,
         pub talkers_card: RefCell<Option<gtk4::Frame>>,
         pub talkers_chart: RefCell<Option<BarChart>>,
         pub status_label: RefCell<Option<gtk4::Label>>,
         pub endpoints: RefCell<Vec<ListeningEndpoint>>,
+        pub storage: RefCell<PortStorage>,
+        pub integrity_header: RefCell<Option<gtk4::Box>>,
+        pub integrity_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub integrity_rows: RefCell<Vec<gtk4::Widget>>,
+        /// Last package verification, kept across refreshes.
+        pub integrity: RefCell<Vec<BinaryIntegrity>>,
+        /// User and capabilities of listening processes, by PID.
+        pub privileges: RefCell<HashMap<u32, ProcessPrivileges>>,
     }
 
     #[glib::object_subclass]
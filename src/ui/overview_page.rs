@@ -2,27 +2,67 @@
 // Copyright (C) 2026 Christos Daggas
 // SPDX-License-Identifier: MIT
 
-//! Overview dashboard page: firewall status, live per-application connections,
-//! and a real-time analytics row (connection breakdown, network activity,
-//! protocols, and remote countries).
+//! Overview dashboard page: firewall status, the security score, live
+//! per-application connections, and a real-time analytics row (connection breakdown, network activity,
+//! bandwidth per interface, connections per process, protocols, remote
+//! countries, and accepted vs denied packets).
 
 use std::cell::{Cell, RefCell};
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::net::{IpAddr, Ipv4Addr};
 
-use gtk4::glib;
 use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
+use gtk4::{gdk, glib, graphene};
+use libadwaita as adw;
 
 use super::app_icons::{display_process_name, icon_for_process, protocol_of};
-use super::widgets::{list_interfaces, DonutChart, MeterBar, NetworkActivityChart, Sparkline};
+use super::app_state::Invalidation;
+use super::density::{self, Role};
+use super::ip_details::{present_ip_details, IpDetailsContext};
+use super::palette;
+use super::widgets::{
+    list_interfaces, ChartAnnotation, DataSeries, DonutChart, LineChart, MeterBar,
+    NetworkActivityChart, SegmentMark, Sparkline,
+};
 use crate::admin::is_local_ip;
-use crate::i18n::gettext;
-use crate::models::Zone;
+use crate::admin::{
+    apply_zone_suggestion, query_firewall_conflicts, query_posture, query_zone_suggestion,
+    FirewallConflict, NetworkKind, Operation, OperationRunner, Posture, PostureCheck, Remedy,
+    ZoneSuggestion,
+};
+use crate::data_usage::QuotaUsage;
+use crate::firewall::events::{ConfigEvent, ConfigEventKind};
+use crate::firewall::FirewallUnavailable;
+use crate::i18n::{
+    format_bytes, format_datetime, format_decimal, format_number, format_percent, format_rate,
+    gettext,
+};
+use crate::models::{DriftSide, Zone};
+use crate::safe_mode;
+use crate::stats::{ChartData, ExportFormat, InterfaceCollector, ProcessCollector, RuleStats};
+use crate::systemd::{UnitActivity, UnitStatus, WatchedUnit};
 
 /// How often the live connection dashboard refreshes.
 const REFRESH_SECS: u32 = 5;
 const INTERVAL_SECS: f64 = REFRESH_SECS as f64;
+/// Seconds of packet counts the traffic ratio donut sums.
+const RATIO_WINDOW_SECS: usize = 300;
+/// Processes the connections chart breaks down.
+const TOP_PROCESSES: usize = 5;
+/// How often the current network is checked for a better zone, systemd
+/// for other firewall managers and the security score recomputed.
+const ZONE_SUGGESTION_SECS: u32 = 60;
+
+/// Overview charts whose data can be exported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportChart {
+    NetworkActivity,
+    Bandwidth,
+    Connections,
+    Breakdown,
+    TrafficRatio,
+}
 
 /// Represents the overall firewall state.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -69,6 +109,11 @@ impl OverviewPage {
     pub fn set_firewall_state(&self, state: FirewallState) {
         let imp = self.imp();
 
+        imp.unavailable.replace(None);
+        if let Some(button) = imp.status_action.borrow().as_ref() {
+            button.set_visible(false);
+        }
+
         match state {
             FirewallState::Active => {
                 if let Some(switch) = imp.traffic_switch.borrow().as_ref() {
@@ -106,7 +151,7 @@ impl OverviewPage {
                     label.add_css_class("error");
                 }
                 if let Some(icon) = imp.status_icon.borrow().as_ref() {
-                    icon.set_icon_name(Some("security-low-symbolic"));
+                    icon.set_icon_name(Some("changes-prevent-symbolic"));
                     icon.remove_css_class("success");
                     icon.remove_css_class("warning");
                     icon.add_css_class("error");
@@ -144,11 +189,27 @@ impl OverviewPage {
             }
         }
 
-        // Keep the System Status stat pill in sync with the firewall state.
-        let (pill_text, pill_class) = match state {
-            FirewallState::Active => (gettext("Protected"), "pill-ok"),
-            FirewallState::PanicMode => (gettext("Locked"), "pill-err"),
-            FirewallState::Stopped => (gettext("Unprotected"), "pill-warn"),
+        // Keep the System Status stat pill in sync with the firewall state. The
+        // tile icon differs per state so it does not rely on colour alone.
+        let (pill_text, pill_class, tile_icon, tile_class) = match state {
+            FirewallState::Active => (
+                gettext("Protected"),
+                "pill-ok",
+                "security-high-symbolic",
+                "success",
+            ),
+            FirewallState::PanicMode => (
+                gettext("Locked"),
+                "pill-err",
+                "changes-prevent-symbolic",
+                "error",
+            ),
+            FirewallState::Stopped => (
+                gettext("Unprotected"),
+                "pill-warn",
+                "security-low-symbolic",
+                "warning",
+            ),
         };
         if let Some(label) = imp.stat_status.borrow().as_ref() {
             label.set_label(&pill_text);
@@ -157,6 +218,477 @@ impl OverviewPage {
             }
             label.add_css_class(pill_class);
         }
+        if let Some(tile) = imp.stat_status_tile.borrow().as_ref() {
+            tile.set_icon_name(Some(tile_icon));
+            for c in ["success", "warning", "error"] {
+                tile.remove_css_class(c);
+            }
+            tile.add_css_class(tile_class);
+        }
+        self.apply_firewalld_activity();
+    }
+
+    /// Show why firewalld cannot be reached, with guidance for the case and
+    /// the action that fits it: starting a stopped firewall, or checking
+    /// again once the cause is dealt with.
+    pub fn set_firewall_unavailable(&self, reason: &FirewallUnavailable) {
+        self.set_firewall_state(FirewallState::Stopped);
+        let imp = self.imp();
+
+        let (title, subtitle, action) = match reason {
+            FirewallUnavailable::NotInstalled => (
+                gettext("Firewall Not Installed"),
+                gettext(
+                    "firewalld is not installed — install the firewalld package with your \
+                     distribution's package manager",
+                ),
+                gettext("Check Again"),
+            ),
+            FirewallUnavailable::Stopped { failed: false } => (
+                gettext("Firewall Inactive"),
+                gettext("Firewall is stopped — traffic is unfiltered"),
+                gettext("Start Firewall"),
+            ),
+            FirewallUnavailable::Stopped { failed: true } => (
+                gettext("Firewall Failed"),
+                gettext(
+                    "firewalld failed to start — traffic is unfiltered. Run \
+                     journalctl -u firewalld for details",
+                ),
+                gettext("Start Firewall"),
+            ),
+            FirewallUnavailable::AccessDenied => (
+                gettext("Firewall Status Unavailable"),
+                gettext(
+                    "Your account may not query firewalld — sign in to a local session or \
+                     ask an administrator",
+                ),
+                gettext("Check Again"),
+            ),
+            FirewallUnavailable::BusUnreachable => (
+                gettext("System Bus Unreachable"),
+                gettext("The system D-Bus cannot be reached, so firewalld cannot be queried"),
+                gettext("Check Again"),
+            ),
+            FirewallUnavailable::Other(message) => (
+                gettext("Firewall Status Unavailable"),
+                message.clone(),
+                gettext("Check Again"),
+            ),
+        };
+        if let Some(label) = imp.status_title.borrow().as_ref() {
+            label.set_label(&title);
+        }
+        if let Some(label) = imp.status_subtitle.borrow().as_ref() {
+            label.set_label(&subtitle);
+        }
+        if let Some(button) = imp.status_action.borrow().as_ref() {
+            button.set_label(&action);
+            button.set_visible(true);
+        }
+
+        // Stopped or missing means unfiltered traffic; otherwise firewalld may
+        // well be running and the state is simply unknown.
+        let known = matches!(
+            reason,
+            FirewallUnavailable::NotInstalled | FirewallUnavailable::Stopped { .. }
+        );
+        if !known {
+            if let Some(label) = imp.traffic_label.borrow().as_ref() {
+                label.set_label(&gettext("Status Unknown"));
+            }
+            if let Some(icon) = imp.status_icon.borrow().as_ref() {
+                icon.set_icon_name(Some("dialog-question-symbolic"));
+            }
+            if let Some(label) = imp.stat_status.borrow().as_ref() {
+                label.set_label(&gettext("Unknown"));
+            }
+            if let Some(tile) = imp.stat_status_tile.borrow().as_ref() {
+                tile.set_icon_name(Some("dialog-question-symbolic"));
+            }
+        }
+
+        imp.unavailable.replace(Some(reason.clone()));
+        self.apply_firewalld_activity();
+    }
+
+    /// Show the live state of a watched service, and pause the firewall
+    /// controls while firewalld is starting, stopping or reloading.
+    pub fn set_unit_activity(&self, status: UnitStatus) {
+        let imp = self.imp();
+        if let Some(pill) = imp.unit_pills.borrow().get(&status.unit) {
+            pill.set_label(&format!(
+                "{} · {}",
+                gettext(status.unit.label()),
+                gettext(status.activity.label())
+            ));
+            let class = match status.activity {
+                UnitActivity::Active => "pill-ok",
+                UnitActivity::Failed => "pill-err",
+                _ => "pill-warn",
+            };
+            for c in ["pill-ok", "pill-warn", "pill-err"] {
+                pill.remove_css_class(c);
+            }
+            pill.add_css_class(class);
+            pill.set_visible(true);
+        }
+        if status.unit == WatchedUnit::Firewalld {
+            imp.firewalld_activity.set(Some(status.activity));
+            self.apply_firewalld_activity();
+        }
+    }
+
+    /// While firewalld is between states, disable the controls that need it
+    /// and say why. The next firewall state update restores them.
+    fn apply_firewalld_activity(&self) {
+        let imp = self.imp();
+        let Some(activity) = imp.firewalld_activity.get() else {
+            return;
+        };
+        let busy = activity.is_transitioning();
+        if let Some(button) = imp.restart_button.borrow().as_ref() {
+            button.set_sensitive(!busy);
+        }
+        if !busy {
+            return;
+        }
+        if let Some(switch) = imp.traffic_switch.borrow().as_ref() {
+            switch.set_sensitive(false);
+        }
+        if let Some(button) = imp.status_action.borrow().as_ref() {
+            button.set_visible(false);
+        }
+        if let Some(reason) = activity.blocked_reason(true) {
+            if let Some(label) = imp.status_subtitle.borrow().as_ref() {
+                label.set_label(
+                    &gettext(reason).replace("%s", &gettext(WatchedUnit::Firewalld.label())),
+                );
+            }
+        }
+    }
+
+    /// Run the status card action for the current failure.
+    fn run_status_action(&self, button: &gtk4::Button) {
+        let Some(main_window) = self.main_window() else {
+            return;
+        };
+        let stopped = matches!(
+            *self.imp().unavailable.borrow(),
+            Some(FirewallUnavailable::Stopped { .. })
+        );
+        if !stopped {
+            main_window.refresh_data();
+            return;
+        }
+
+        button.set_sensitive(false);
+        let button = button.clone();
+        glib::spawn_future_local(async move {
+            let result =
+                gtk4::gio::spawn_blocking(|| OperationRunner::new().run(Operation::EnableFirewall))
+                    .await;
+            button.set_sensitive(true);
+            match result {
+                Ok(result) if result.success => {
+                    main_window.show_toast(&gettext("Firewall started"));
+                }
+                Ok(result) => {
+                    main_window.show_toast(&format!(
+                        "{}: {}",
+                        gettext("Failed to start firewall"),
+                        result.message
+                    ));
+                }
+                Err(_) => {
+                    main_window.show_toast(&gettext("Failed to start firewall"));
+                }
+            }
+            main_window.refresh_data();
+        });
+    }
+
+    /// The main window this page is shown in.
+    fn main_window(&self) -> Option<super::MainWindow> {
+        self.root()
+            .and_then(|root| root.downcast::<gtk4::Window>().ok())
+            .and_then(|window| window.downcast::<super::MainWindow>().ok())
+    }
+
+    /// Look for a better zone for the current network and show or hide the
+    /// suggestion banner.
+    pub fn refresh_zone_suggestion(&self) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(query_zone_suggestion).await;
+            let suggestion = match result {
+                Ok(Ok(suggestion)) => suggestion.filter(|suggestion| {
+                    !crate::config::Settings::new().is_zone_suggestion_dismissed(&suggestion.key())
+                }),
+                Ok(Err(e)) => {
+                    tracing::debug!("No zone suggestion: {:#}", e);
+                    None
+                }
+                Err(_) => None,
+            };
+            page.show_zone_suggestion(suggestion);
+        });
+    }
+
+    fn show_zone_suggestion(&self, suggestion: Option<ZoneSuggestion>) {
+        let imp = self.imp();
+        if let Some(ref suggestion) = suggestion {
+            let network = &suggestion.network;
+            let name = network.ssid.as_deref().unwrap_or(&network.name);
+            if let Some(icon) = imp.zone_suggestion_icon.borrow().as_ref() {
+                icon.set_icon_name(Some(match network.kind {
+                    NetworkKind::Wifi => "network-wireless-symbolic",
+                    NetworkKind::Mobile => "network-cellular-symbolic",
+                    _ => "network-wired-symbolic",
+                }));
+            }
+            if let Some(label) = imp.zone_suggestion_title.borrow().as_ref() {
+                label.set_label(
+                    &gettext("Use the “%s” zone on %s?")
+                        .replacen("%s", suggestion.zone, 1)
+                        .replacen("%s", name, 1),
+                );
+            }
+            if let Some(label) = imp.zone_suggestion_reason.borrow().as_ref() {
+                label.set_label(&format!(
+                    "{} {}",
+                    gettext(suggestion.reason),
+                    gettext("%s is in the “%s” zone now.")
+                        .replacen("%s", &network.interface, 1)
+                        .replacen("%s", &suggestion.current_zone, 1)
+                ));
+            }
+        }
+        if let Some(revealer) = imp.zone_suggestion_revealer.borrow().as_ref() {
+            revealer.set_reveal_child(suggestion.is_some());
+        }
+        imp.zone_suggestion.replace(suggestion);
+    }
+
+    /// Move the interface to the suggested zone.
+    fn apply_zone_suggestion(&self, button: &gtk4::Button) {
+        let Some(suggestion) = self.imp().zone_suggestion.borrow().clone() else {
+            return;
+        };
+        button.set_sensitive(false);
+        let button = button.clone();
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let applied = suggestion.clone();
+            let result = gtk4::gio::spawn_blocking(move || apply_zone_suggestion(&applied)).await;
+            button.set_sensitive(true);
+
+            let interface = &suggestion.network.interface;
+            let message = match result {
+                Ok(Ok(true)) => {
+                    page.show_zone_suggestion(None);
+                    gettext("%s moved to the “%s” zone")
+                        .replacen("%s", interface, 1)
+                        .replacen("%s", suggestion.zone, 1)
+                }
+                Ok(Ok(false)) => {
+                    page.show_zone_suggestion(None);
+                    gettext("%s moved to the “%s” zone until it reconnects")
+                        .replacen("%s", interface, 1)
+                        .replacen("%s", suggestion.zone, 1)
+                }
+                Ok(Err(e)) => format!("{}: {}", gettext("Failed to change zone"), e),
+                Err(_) => gettext("Failed to change zone"),
+            };
+            if let Some(main_window) = page.main_window() {
+                main_window.show_toast(&message);
+                main_window.invalidate(Invalidation::ZonesChanged);
+            }
+        });
+    }
+
+    /// Hide the suggestion and do not make it again for this network.
+    fn dismiss_zone_suggestion(&self) {
+        if let Some(suggestion) = self.imp().zone_suggestion.borrow().as_ref() {
+            crate::config::Settings::new().dismiss_zone_suggestion(&suggestion.key());
+        }
+        self.show_zone_suggestion(None);
+    }
+
+    /// Look for other firewall managers running next to firewalld and show
+    /// or hide the warning banner.
+    pub fn refresh_firewall_conflicts(&self) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let conflicts = gtk4::gio::spawn_blocking(query_firewall_conflicts)
+                .await
+                .unwrap_or_default();
+            page.show_firewall_conflicts(conflicts);
+        });
+    }
+
+    fn show_firewall_conflicts(&self, conflicts: Vec<FirewallConflict>) {
+        let imp = self.imp();
+        if let [conflict] = conflicts.as_slice() {
+            set_label(
+                &imp.conflict_title,
+                &gettext("%s is also managing the firewall").replace("%s", conflict.name),
+            );
+            if let Some(button) = imp.conflict_button.borrow().as_ref() {
+                button.set_label(&gettext("Disable %s").replace("%s", conflict.name));
+            }
+        } else {
+            set_label(
+                &imp.conflict_title,
+                &gettext("Other firewall managers are running"),
+            );
+            if let Some(button) = imp.conflict_button.borrow().as_ref() {
+                button.set_label(&gettext("Disable Them"));
+            }
+        }
+        let mut lines: Vec<String> = conflicts
+            .iter()
+            .map(|conflict| {
+                format!(
+                    "{} ({}): {}",
+                    conflict.name,
+                    gettext(conflict.state()),
+                    gettext(conflict.explanation)
+                )
+            })
+            .collect();
+        lines.push(gettext(
+            "Keep firewalld as the only firewall: stop and disable the others, then reload firewalld.",
+        ));
+        set_label(&imp.conflict_detail, &lines.join("\n"));
+
+        if let Some(revealer) = imp.conflict_revealer.borrow().as_ref() {
+            revealer.set_reveal_child(!conflicts.is_empty());
+        }
+        imp.conflicts.replace(conflicts);
+    }
+
+    /// Stop and disable the conflicting managers, then reload firewalld so
+    /// its rules are applied again.
+    fn disable_conflicts(&self, button: &gtk4::Button) {
+        let units: Vec<&'static str> = self
+            .imp()
+            .conflicts
+            .borrow()
+            .iter()
+            .map(|conflict| conflict.unit)
+            .collect();
+        if units.is_empty() {
+            return;
+        }
+        button.set_sensitive(false);
+        let button = button.clone();
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(move || {
+                let mut runner = OperationRunner::new();
+                let failures: Vec<String> = units
+                    .into_iter()
+                    .map(|unit| runner.run(Operation::StopAndDisableUnit { unit }))
+                    .filter(|result| !result.success)
+                    .map(|result| result.message)
+                    .collect();
+                runner.run(Operation::ReloadFirewall);
+                failures
+            })
+            .await;
+            button.set_sensitive(true);
+
+            let message = match result {
+                Ok(failures) if failures.is_empty() => {
+                    gettext("Other firewall managers disabled; firewalld reloaded")
+                }
+                Ok(failures) => format!(
+                    "{}: {}",
+                    gettext("Failed to disable a firewall manager"),
+                    failures.join("; ")
+                ),
+                Err(_) => gettext("Failed to disable a firewall manager"),
+            };
+            page.refresh_firewall_conflicts();
+            if let Some(main_window) = page.main_window() {
+                main_window.show_toast(&message);
+                main_window.refresh_data();
+            }
+        });
+    }
+
+    /// Build the warning shown while another firewall manager runs next to
+    /// firewalld; hidden otherwise.
+    fn build_conflict_banner(&self) -> gtk4::Revealer {
+        let imp = self.imp();
+
+        let frame = gtk4::Frame::new(None);
+        frame.add_css_class("card");
+
+        let row = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(12)
+            .margin_top(12)
+            .margin_bottom(12)
+            .margin_start(18)
+            .margin_end(12)
+            .build();
+
+        let icon = gtk4::Image::builder()
+            .icon_name("dialog-error-symbolic")
+            .pixel_size(24)
+            .css_classes(vec!["error".to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+        row.append(&icon);
+
+        let text = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(2)
+            .valign(gtk4::Align::Center)
+            .hexpand(true)
+            .build();
+        let title = gtk4::Label::builder()
+            .css_classes(vec!["heading".to_string()])
+            .halign(gtk4::Align::Start)
+            .xalign(0.0)
+            .wrap(true)
+            .build();
+        let detail = gtk4::Label::builder()
+            .css_classes(vec!["dim-label".to_string(), "caption".to_string()])
+            .halign(gtk4::Align::Start)
+            .xalign(0.0)
+            .wrap(true)
+            .build();
+        text.append(&title);
+        text.append(&detail);
+        row.append(&text);
+
+        let disable = gtk4::Button::builder()
+            .tooltip_text(gettext("Stop and disable it, then reload firewalld"))
+            .css_classes(vec!["pill".to_string(), "destructive-action".to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+        let page = self.downgrade();
+        disable.connect_clicked(move |button| {
+            if let Some(page) = page.upgrade() {
+                page.disable_conflicts(button);
+            }
+        });
+        row.append(&disable);
+
+        frame.set_child(Some(&row));
+        let revealer = gtk4::Revealer::builder()
+            .transition_type(gtk4::RevealerTransitionType::SlideDown)
+            .reveal_child(false)
+            .child(&frame)
+            .build();
+        imp.conflict_revealer.replace(Some(revealer.clone()));
+        imp.conflict_title.replace(Some(title));
+        imp.conflict_detail.replace(Some(detail));
+        imp.conflict_button.replace(Some(disable));
+        revealer
     }
 
     /// Setup the UI.
@@ -181,19 +713,26 @@ impl OverviewPage {
 
         let content = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Vertical)
-            .spacing(18)
-            .margin_top(20)
-            .margin_bottom(20)
-            .margin_start(20)
-            .margin_end(20)
             .hexpand(true)
             .build();
+        density::track(
+            &content,
+            Role::Content {
+                margin: 20,
+                spacing: 18,
+            },
+        );
 
         // Top controls and cards come first; the live connections overview
         // belongs at the bottom of the dashboard.
+        content.append(&self.build_conflict_banner());
+        content.append(&self.build_zone_suggestion());
+        content.append(&self.build_unsaved_banner());
         content.append(&self.build_status_card());
+        content.append(&self.build_posture_card());
         content.append(&self.build_stat_cards());
         content.append(&self.build_analytics());
+        content.append(&self.build_data_usage_card());
         content.append(&self.build_connections_hub());
 
         // Honour the saved "show connections overview" preference.
@@ -201,17 +740,554 @@ impl OverviewPage {
 
         scrolled.set_child(Some(&content));
         self.append(&scrolled);
+        self.imp().content.replace(Some(content));
 
-        // Kick off the live connection dashboard.
+        // Kick off the live connection dashboard; safe mode pauses the
+        // periodic refreshes.
         let page = self.clone();
         glib::timeout_add_seconds_local_once(2, move || {
-            page.refresh_connected_hosts();
+            if !safe_mode::is_enabled() {
+                page.refresh_connected_hosts();
+            }
         });
         let page = self.clone();
+        // The first reading is the baseline of the bandwidth rates
+        self.imp().interface_stats.borrow_mut().collect();
         glib::timeout_add_seconds_local(REFRESH_SECS, move || {
+            if safe_mode::is_enabled() {
+                return glib::ControlFlow::Continue;
+            }
             page.refresh_connected_hosts();
+            page.sample_bandwidth();
             glib::ControlFlow::Continue
         });
+
+        let page = self.clone();
+        glib::timeout_add_seconds_local_once(3, move || {
+            if safe_mode::is_enabled() {
+                return;
+            }
+            page.refresh_zone_suggestion();
+            page.refresh_firewall_conflicts();
+            page.refresh_posture();
+        });
+        let page = self.clone();
+        glib::timeout_add_seconds_local(ZONE_SUGGESTION_SECS, move || {
+            if safe_mode::is_enabled() {
+                return glib::ControlFlow::Continue;
+            }
+            page.refresh_zone_suggestion();
+            page.refresh_firewall_conflicts();
+            page.refresh_posture();
+            glib::ControlFlow::Continue
+        });
+    }
+
+    /// Build the banner suggesting a zone for the current network; hidden
+    /// until there is a suggestion.
+    fn build_zone_suggestion(&self) -> gtk4::Revealer {
+        let imp = self.imp();
+
+        let frame = gtk4::Frame::new(None);
+        frame.add_css_class("card");
+
+        let row = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(12)
+            .margin_top(12)
+            .margin_bottom(12)
+            .margin_start(18)
+            .margin_end(12)
+            .build();
+
+        let icon = gtk4::Image::builder()
+            .icon_name("network-wireless-symbolic")
+            .pixel_size(24)
+            .css_classes(vec!["accent".to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+        imp.zone_suggestion_icon.replace(Some(icon.clone()));
+        row.append(&icon);
+
+        let text = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(2)
+            .valign(gtk4::Align::Center)
+            .hexpand(true)
+            .build();
+        let title = gtk4::Label::builder()
+            .css_classes(vec!["heading".to_string()])
+            .halign(gtk4::Align::Start)
+            .xalign(0.0)
+            .wrap(true)
+            .build();
+        imp.zone_suggestion_title.replace(Some(title.clone()));
+        let reason = gtk4::Label::builder()
+            .css_classes(vec!["dim-label".to_string(), "caption".to_string()])
+            .halign(gtk4::Align::Start)
+            .xalign(0.0)
+            .wrap(true)
+            .build();
+        imp.zone_suggestion_reason.replace(Some(reason.clone()));
+        text.append(&title);
+        text.append(&reason);
+        row.append(&text);
+
+        let apply = gtk4::Button::builder()
+            .label(gettext("Apply"))
+            .tooltip_text(gettext("Move the interface to the suggested zone"))
+            .css_classes(vec!["pill".to_string(), "suggested-action".to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+        let page = self.downgrade();
+        apply.connect_clicked(move |button| {
+            if let Some(page) = page.upgrade() {
+                page.apply_zone_suggestion(button);
+            }
+        });
+        row.append(&apply);
+
+        let dismiss = gtk4::Button::builder()
+            .icon_name("window-close-symbolic")
+            .tooltip_text(gettext("Dismiss"))
+            .css_classes(vec!["flat".to_string(), "circular".to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+        let page = self.downgrade();
+        dismiss.connect_clicked(move |_| {
+            if let Some(page) = page.upgrade() {
+                page.dismiss_zone_suggestion();
+            }
+        });
+        row.append(&dismiss);
+
+        frame.set_child(Some(&row));
+        let revealer = gtk4::Revealer::builder()
+            .transition_type(gtk4::RevealerTransitionType::SlideDown)
+            .reveal_child(false)
+            .child(&frame)
+            .build();
+        imp.zone_suggestion_revealer.replace(Some(revealer.clone()));
+        revealer
+    }
+
+    /// Build the banner warning that runtime firewall rules differ from the
+    /// permanent configuration; hidden while they match.
+    fn build_unsaved_banner(&self) -> gtk4::Revealer {
+        let imp = self.imp();
+
+        let frame = gtk4::Frame::new(None);
+        frame.add_css_class("card");
+
+        let row = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(12)
+            .margin_top(12)
+            .margin_bottom(12)
+            .margin_start(18)
+            .margin_end(12)
+            .build();
+
+        let icon = gtk4::Image::builder()
+            .icon_name("dialog-warning-symbolic")
+            .pixel_size(24)
+            .css_classes(vec!["warning".to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+        row.append(&icon);
+
+        let text = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(2)
+            .valign(gtk4::Align::Center)
+            .hexpand(true)
+            .build();
+        let title = gtk4::Label::builder()
+            .label(gettext("Firewall changes are not saved"))
+            .css_classes(vec!["heading".to_string()])
+            .halign(gtk4::Align::Start)
+            .xalign(0.0)
+            .wrap(true)
+            .build();
+        let detail = gtk4::Label::builder()
+            .css_classes(vec!["dim-label".to_string(), "caption".to_string()])
+            .halign(gtk4::Align::Start)
+            .xalign(0.0)
+            .wrap(true)
+            .build();
+        imp.unsaved_detail.replace(Some(detail.clone()));
+        text.append(&title);
+        text.append(&detail);
+        row.append(&text);
+
+        let review = gtk4::Button::builder()
+            .label(gettext("Review"))
+            .tooltip_text(gettext("List the differences and keep or discard each one"))
+            .css_classes(vec!["pill".to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+        let page = self.downgrade();
+        review.connect_clicked(move |_| {
+            let Some(page) = page.upgrade() else {
+                return;
+            };
+            if let Some(window) = page.main_window() {
+                let zones = page.imp().unsaved_zones.borrow().clone();
+                super::drift_review::present_drift_review(&window, &zones);
+            }
+        });
+        row.append(&review);
+
+        let save = gtk4::Button::builder()
+            .label(gettext("Save Permanently"))
+            .tooltip_text(gettext(
+                "Make the permanent configuration match the runtime rules: rules only at \
+                 runtime are kept, rules only in the permanent configuration are deleted",
+            ))
+            .css_classes(vec!["pill".to_string(), "suggested-action".to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+        let page = self.downgrade();
+        save.connect_clicked(move |button| {
+            if let Some(page) = page.upgrade() {
+                page.save_runtime_to_permanent(button);
+            }
+        });
+        row.append(&save);
+
+        frame.set_child(Some(&row));
+        let revealer = gtk4::Revealer::builder()
+            .transition_type(gtk4::RevealerTransitionType::SlideDown)
+            .reveal_child(false)
+            .child(&frame)
+            .build();
+        imp.unsaved_revealer.replace(Some(revealer.clone()));
+        revealer
+    }
+
+    /// Show or hide the unsaved-changes banner for the zones' state, with
+    /// one line for each direction the rules differ in.
+    fn show_unsaved_changes(&self, zones: &[Zone]) {
+        let imp = self.imp();
+        imp.unsaved_zones.replace(zones.to_vec());
+
+        // A reload drops the runtime-only rules and restores the
+        // permanent-only ones
+        let directions = [
+            (
+                DriftSide::RuntimeOnly,
+                gettext(
+                    "%d rules in %s exist only at runtime and are lost at the next reload \
+                     or reboot",
+                ),
+            ),
+            (
+                DriftSide::PermanentOnly,
+                gettext(
+                    "%d rules in %s were removed at runtime and come back at the next \
+                     reload or reboot",
+                ),
+            ),
+        ];
+        let lines: Vec<String> = directions
+            .iter()
+            .filter_map(|(side, text)| {
+                let changed: Vec<(&str, usize)> = zones
+                    .iter()
+                    .map(|zone| (zone.name.as_str(), zone.drift_count(*side)))
+                    .filter(|(_, count)| *count > 0)
+                    .collect();
+                if changed.is_empty() {
+                    return None;
+                }
+                let total: usize = changed.iter().map(|(_, count)| count).sum();
+                let names: Vec<&str> = changed.iter().map(|(name, _)| *name).collect();
+                Some(
+                    text.replace("%d", &total.to_string())
+                        .replace("%s", &names.join(", ")),
+                )
+            })
+            .collect();
+
+        if let Some(label) = imp.unsaved_detail.borrow().as_ref() {
+            label.set_label(&lines.join("\n"));
+        }
+        if let Some(revealer) = imp.unsaved_revealer.borrow().as_ref() {
+            revealer.set_reveal_child(!lines.is_empty());
+        }
+    }
+
+    /// Save the runtime rules permanently, then refresh so the banner hides.
+    fn save_runtime_to_permanent(&self, button: &gtk4::Button) {
+        let Some(main_window) = self.main_window() else {
+            return;
+        };
+        button.set_sensitive(false);
+        let button = button.clone();
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(|| {
+                OperationRunner::new().run(Operation::RuntimeToPermanent)
+            })
+            .await;
+            button.set_sensitive(true);
+            match result {
+                Ok(result) if result.success => {
+                    main_window.show_toast(&gettext("Firewall rules saved permanently"));
+                }
+                Ok(result) => {
+                    main_window.show_toast(&format!(
+                        "{}: {}",
+                        gettext("Failed to save firewall rules"),
+                        result.message
+                    ));
+                }
+                Err(_) => {
+                    main_window.show_toast(&gettext("Failed to save firewall rules"));
+                }
+            }
+            main_window.refresh_data();
+        });
+    }
+
+    /// Build the card with the security score and its checks, each failed
+    /// one with a button that fixes it.
+    fn build_posture_card(&self) -> gtk4::Frame {
+        let imp = self.imp();
+        let (frame, content) = panel_card(
+            &gettext("Security Score"),
+            &gettext("Firewall posture · checked every minute"),
+        );
+
+        let header = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(16)
+            .margin_bottom(12)
+            .build();
+        let score = gtk4::Label::builder()
+            .label("–")
+            .css_classes(vec!["title-1".to_string(), "numeric".to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+        header.append(&score);
+        let meter = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(6)
+            .valign(gtk4::Align::Center)
+            .hexpand(true)
+            .build();
+        let summary = gtk4::Label::builder()
+            .label(gettext("Checking…"))
+            .css_classes(vec!["caption".to_string(), "dim-label".to_string()])
+            .halign(gtk4::Align::Start)
+            .xalign(0.0)
+            .wrap(true)
+            .build();
+        meter.append(&summary);
+        let bar = MeterBar::new();
+        meter.append(&bar);
+        header.append(&meter);
+        content.append(&header);
+
+        let list = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(10)
+            .build();
+        content.append(&list);
+
+        imp.posture_score.replace(Some(score));
+        imp.posture_summary.replace(Some(summary));
+        imp.posture_bar.replace(Some(bar));
+        imp.posture_list.replace(Some(list));
+        frame
+    }
+
+    /// Recompute the security score in the background. A request while one
+    /// is running is folded into it.
+    pub fn refresh_posture(&self) {
+        let imp = self.imp();
+        if imp.posture_running.get() {
+            imp.posture_again.set(true);
+            return;
+        }
+        imp.posture_running.set(true);
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(query_posture).await;
+            let imp = page.imp();
+            imp.posture_running.set(false);
+            if let Ok(posture) = result {
+                page.show_posture(&posture);
+            }
+            if imp.posture_again.replace(false) {
+                page.refresh_posture();
+            }
+        });
+    }
+
+    fn show_posture(&self, posture: &Posture) {
+        let imp = self.imp();
+        let score = posture.score();
+        set_label(&imp.posture_score, &format_number(score as u64));
+        let failed = posture.failed();
+        set_label(
+            &imp.posture_summary,
+            &if failed.is_empty() {
+                gettext("All checks pass")
+            } else {
+                gettext("%d of %d checks need attention")
+                    .replacen("%d", &failed.len().to_string(), 1)
+                    .replacen("%d", &posture.checks.len().to_string(), 1)
+            },
+        );
+        if let Some(bar) = imp.posture_bar.borrow().as_ref() {
+            let palette = palette::current();
+            let (r, g, b) = if score >= 80 {
+                palette.success()
+            } else if score >= 50 {
+                palette.warning()
+            } else {
+                palette.error()
+            };
+            bar.set_color(r, g, b);
+            bar.set_fraction(score as f64 / 100.0);
+        }
+
+        let list_ref = imp.posture_list.borrow();
+        let Some(list) = list_ref.as_ref() else {
+            return;
+        };
+        while let Some(child) = list.first_child() {
+            list.remove(&child);
+        }
+        // Failed checks first, heaviest first; passed ones below
+        for check in failed
+            .into_iter()
+            .chain(posture.checks.iter().filter(|c| c.passed))
+        {
+            list.append(&self.create_posture_row(check));
+        }
+    }
+
+    /// Row for one posture check: state, title, what was found, the points
+    /// it is worth and the remedy of a failed check.
+    fn create_posture_row(&self, check: &PostureCheck) -> gtk4::Box {
+        let row = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(12)
+            .build();
+        let (icon_name, class) = if check.passed {
+            ("emblem-ok-symbolic", "success")
+        } else {
+            ("dialog-warning-symbolic", "warning")
+        };
+        row.append(
+            &gtk4::Image::builder()
+                .icon_name(icon_name)
+                .css_classes(vec![class.to_string()])
+                .valign(gtk4::Align::Center)
+                .build(),
+        );
+
+        let text = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(2)
+            .valign(gtk4::Align::Center)
+            .hexpand(true)
+            .build();
+        text.append(
+            &gtk4::Label::builder()
+                .label(gettext(check.title))
+                .halign(gtk4::Align::Start)
+                .xalign(0.0)
+                .wrap(true)
+                .build(),
+        );
+        text.append(
+            &gtk4::Label::builder()
+                .label(check.detail.as_str())
+                .css_classes(vec!["caption".to_string(), "dim-label".to_string()])
+                .halign(gtk4::Align::Start)
+                .xalign(0.0)
+                .wrap(true)
+                .build(),
+        );
+        row.append(&text);
+
+        row.append(
+            &gtk4::Label::builder()
+                .label(if check.passed {
+                    format!("+{}", check.weight)
+                } else {
+                    format!("0 / {}", check.weight)
+                })
+                .css_classes(vec![
+                    "caption".to_string(),
+                    "conn-meta".to_string(),
+                    "numeric".to_string(),
+                ])
+                .valign(gtk4::Align::Center)
+                .build(),
+        );
+
+        if let Some(remedy) = check.remedy {
+            let button = gtk4::Button::builder()
+                .label(remedy_label(remedy))
+                .css_classes(vec!["pill".to_string()])
+                .valign(gtk4::Align::Center)
+                .build();
+            let page = self.downgrade();
+            button.connect_clicked(move |button| {
+                if let Some(page) = page.upgrade() {
+                    page.apply_remedy(remedy, button);
+                }
+            });
+            row.append(&button);
+        }
+        row
+    }
+
+    /// Fix a failed check. Risky operations revert themselves unless kept.
+    fn apply_remedy(&self, remedy: Remedy, button: &gtk4::Button) {
+        let Some(main_window) = self.main_window() else {
+            return;
+        };
+        let operation = match remedy {
+            Remedy::Run(operation) => operation,
+            Remedy::ReviewDrift => {
+                let zones = self.imp().unsaved_zones.borrow().clone();
+                super::drift_review::present_drift_review(&main_window, &zones);
+                return;
+            }
+            Remedy::ShowPage(name) => {
+                main_window.show_page(name);
+                return;
+            }
+        };
+
+        button.set_sensitive(false);
+        let button = button.clone();
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result =
+                gtk4::gio::spawn_blocking(move || OperationRunner::new().run_revertible(operation))
+                    .await;
+            button.set_sensitive(true);
+            match result {
+                Ok((result, undo)) => {
+                    main_window.show_toast(&if result.success {
+                        result.message
+                    } else {
+                        format!("{}: {}", gettext("Failed to apply the fix"), result.message)
+                    });
+                    if let Some(undo) = undo {
+                        main_window.revert_unless_kept(undo);
+                    }
+                }
+                Err(_) => main_window.show_toast(&gettext("Failed to apply the fix")),
+            }
+            main_window.refresh_data();
+            page.refresh_posture();
+        });
     }
 
     /// Build the firewall status card (icon + title + zone/restart/traffic toggle).
@@ -255,11 +1331,46 @@ impl OverviewPage {
             .label(gettext("Your system is protected"))
             .css_classes(vec!["dim-label".to_string()])
             .halign(gtk4::Align::Start)
+            .xalign(0.0)
+            .wrap(true)
             .build();
         imp.status_subtitle.replace(Some(status_subtitle.clone()));
 
+        // Shown only while firewalld is unavailable.
+        let status_action = gtk4::Button::builder()
+            .css_classes(vec!["pill".to_string(), "suggested-action".to_string()])
+            .halign(gtk4::Align::Start)
+            .margin_top(6)
+            .visible(false)
+            .build();
+        let page = self.downgrade();
+        status_action.connect_clicked(move |button| {
+            if let Some(page) = page.upgrade() {
+                page.run_status_action(button);
+            }
+        });
+        imp.status_action.replace(Some(status_action.clone()));
+
+        // Live state of the services the app relies on, shown once known.
+        let unit_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(6)
+            .margin_top(6)
+            .halign(gtk4::Align::Start)
+            .build();
+        for unit in WatchedUnit::ALL {
+            let pill = gtk4::Label::builder()
+                .css_classes(vec!["caption".to_string()])
+                .visible(false)
+                .build();
+            unit_box.append(&pill);
+            imp.unit_pills.borrow_mut().insert(unit, pill);
+        }
+
         status_text.append(&status_title);
         status_text.append(&status_subtitle);
+        status_text.append(&status_action);
+        status_text.append(&unit_box);
         status_box.append(&status_icon);
         status_box.append(&status_text);
 
@@ -319,8 +1430,7 @@ impl OverviewPage {
             button.set_sensitive(false);
             let btn = button.clone();
             glib::spawn_future_local(async move {
-                let result = gtk4::gio::spawn_blocking(move || {
-                    let mut client = crate::firewall::FirewallClient::new();
+                let result = crate::firewall::call(move |client| {
                     if let Err(e) = client.connect() {
                         return Err(format!("Not connected to firewalld: {}", e));
                     }
@@ -355,6 +1465,7 @@ impl OverviewPage {
                 }
             });
         });
+        imp.restart_button.replace(Some(restart_button.clone()));
         restart_box.append(&restart_button);
         restart_box.append(&restart_label);
         toggle_box.append(&restart_box);
@@ -398,7 +1509,7 @@ impl OverviewPage {
             .homogeneous(true)
             .build();
 
-        let (active_card, active_val) = stat_card(
+        let (active_card, _, active_val) = stat_card(
             "network-transmit-receive-symbolic",
             "accent",
             &gettext("Active Connections"),
@@ -406,7 +1517,7 @@ impl OverviewPage {
         imp.metric_active.replace(Some(active_val));
         row.append(&active_card);
 
-        let (blocked_card, blocked_val) = stat_card(
+        let (blocked_card, _, blocked_val) = stat_card(
             "action-unavailable-symbolic",
             "error",
             &gettext("Blocked Ports"),
@@ -414,13 +1525,13 @@ impl OverviewPage {
         imp.metric_blocked.replace(Some(blocked_val));
         row.append(&blocked_card);
 
-        let (apps_card, apps_val) =
+        let (apps_card, _, apps_val) =
             stat_card("view-app-grid-symbolic", "purple", &gettext("Applications"));
         imp.metric_apps.replace(Some(apps_val));
         row.append(&apps_card);
 
         // System Status card carries a coloured pill instead of a number.
-        let (status_card, status_val) = stat_card(
+        let (status_card, status_tile, status_val) = stat_card(
             "security-high-symbolic",
             "success",
             &gettext("System Status"),
@@ -430,6 +1541,7 @@ impl OverviewPage {
         status_val.set_label(&gettext("Protected"));
         status_val.set_halign(gtk4::Align::Start);
         imp.stat_status.replace(Some(status_val));
+        imp.stat_status_tile.replace(Some(status_tile));
         row.append(&status_card);
 
         row
@@ -545,20 +1657,274 @@ impl OverviewPage {
             .selection_mode(gtk4::SelectionMode::None)
             .build();
 
-        flow.append(&self.create_network_activity_card());
-        flow.append(&self.build_protocols_panel());
-        flow.append(&self.build_countries_panel());
-        flow.append(&self.build_donut_panel());
+        flow.append(&self.create_network_activity_card());
+        flow.append(&self.build_bandwidth_panel());
+        flow.append(&self.build_process_connections_panel());
+        flow.append(&self.build_protocols_panel());
+        flow.append(&self.build_countries_panel());
+        flow.append(&self.build_donut_panel());
+        flow.append(&self.build_traffic_ratio_panel());
+
+        flow
+    }
+
+    /// Menu button offering the data behind `chart` as CSV or JSON.
+    fn export_menu(&self, chart: ExportChart) -> gtk4::MenuButton {
+        let items = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(2)
+            .build();
+        let popover = gtk4::Popover::builder().child(&items).build();
+        for (label, format) in [
+            (gettext("Export as CSV…"), ExportFormat::Csv),
+            (gettext("Export as JSON…"), ExportFormat::Json),
+        ] {
+            let button = gtk4::Button::builder()
+                .label(label)
+                .css_classes(vec!["flat".to_string()])
+                .build();
+            let page = self.downgrade();
+            let popover_ref = popover.downgrade();
+            button.connect_clicked(move |_| {
+                if let Some(popover) = popover_ref.upgrade() {
+                    popover.popdown();
+                }
+                if let Some(page) = page.upgrade() {
+                    page.export_chart(chart, format);
+                }
+            });
+            items.append(&button);
+        }
+        gtk4::MenuButton::builder()
+            .icon_name("document-save-symbolic")
+            .tooltip_text(gettext("Export Data"))
+            .css_classes(vec!["flat".to_string()])
+            .valign(gtk4::Align::Center)
+            .popover(&popover)
+            .build()
+    }
+
+    /// The series `chart` plots, `None` before it has any.
+    fn chart_data(&self, chart: ExportChart) -> Option<ChartData> {
+        let imp = self.imp();
+        let line_series = |chart: &RefCell<Option<LineChart>>| -> Vec<(String, Vec<f64>)> {
+            chart
+                .borrow()
+                .as_ref()
+                .map(|chart| {
+                    chart
+                        .series()
+                        .into_iter()
+                        .map(|series| (series.label, series.values))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let data = match chart {
+            ExportChart::NetworkActivity => {
+                let (inbound, outbound) = imp.network_chart.borrow().as_ref()?.data();
+                ChartData::from_series(
+                    &gettext("Network Activity"),
+                    "KiB/s",
+                    1,
+                    vec![
+                        (gettext("Download"), inbound),
+                        (gettext("Upload"), outbound),
+                    ],
+                )
+            }
+            ExportChart::Bandwidth => ChartData::from_series(
+                &format!(
+                    "{} {}",
+                    gettext("Bandwidth"),
+                    self.chosen_interface().unwrap_or_default()
+                ),
+                "KiB/s",
+                REFRESH_SECS as u64,
+                line_series(&imp.bandwidth_chart),
+            ),
+            ExportChart::Connections => ChartData::from_series(
+                &gettext("Connections"),
+                "connections",
+                REFRESH_SECS as u64,
+                line_series(&imp.process_chart),
+            ),
+            ExportChart::Breakdown => {
+                let segments = imp.donut.borrow().as_ref()?.segments();
+                let labels = [gettext("Active"), gettext("Blocked"), gettext("Idle")];
+                ChartData::from_series(
+                    &gettext("Connection Overview"),
+                    "count",
+                    REFRESH_SECS as u64,
+                    labels
+                        .into_iter()
+                        .zip(segments)
+                        .map(|(label, (value, _, _))| (label, vec![value]))
+                        .collect(),
+                )
+            }
+            ExportChart::TrafficRatio => {
+                let samples = imp.packet_samples.borrow();
+                let mut series = vec![(
+                    gettext("Accepted"),
+                    samples
+                        .iter()
+                        .map(|(accepted, _)| *accepted as f64)
+                        .collect(),
+                )];
+                // Without LogDenied the denied column would be all zeros
+                if imp.denied_available.get() {
+                    series.push((
+                        gettext("Denied"),
+                        samples.iter().map(|(_, denied)| *denied as f64).collect(),
+                    ));
+                }
+                ChartData::from_series(&gettext("Traffic Ratio"), "packets/s", 1, series)
+            }
+        };
+        (!data.rows.is_empty()).then_some(data)
+    }
+
+    /// Save the data behind `chart` to a file the user picks.
+    fn export_chart(&self, chart: ExportChart, format: ExportFormat) {
+        let Some(main_window) = self.main_window() else {
+            return;
+        };
+        let Some(data) = self.chart_data(chart) else {
+            main_window.show_toast(&gettext("No data collected yet"));
+            return;
+        };
+        let dialog = gtk4::FileDialog::builder()
+            .title(gettext("Export Chart Data"))
+            .initial_name(data.file_name(format))
+            .modal(true)
+            .build();
+        let parent = main_window.clone();
+        dialog.save(Some(&parent), gtk4::gio::Cancellable::NONE, move |result| {
+            // Cancelling the dialog is not an error
+            let Ok(file) = result else {
+                return;
+            };
+            let Some(path) = file.path() else {
+                main_window.show_toast(&gettext("Choose a local file"));
+                return;
+            };
+            match std::fs::write(&path, data.render(format)) {
+                Ok(()) => main_window.show_toast(
+                    &gettext("Chart data saved to %s").replace("%s", &path.display().to_string()),
+                ),
+                Err(e) => main_window.show_toast(&format!(
+                    "{}: {}",
+                    gettext("Failed to save the chart data"),
+                    e
+                )),
+            }
+        });
+    }
+
+    /// Panel: packets accepted vs denied by the firewall over the last few
+    /// minutes, with a way to turn on logging of denied packets.
+    fn build_traffic_ratio_panel(&self) -> gtk4::Frame {
+        let imp = self.imp();
+        let (frame, content) = panel_card_with_suffix(
+            &gettext("Traffic Ratio"),
+            &gettext("Packets in the last 5 minutes"),
+            Some(self.export_menu(ExportChart::TrafficRatio).upcast_ref()),
+        );
+
+        let donut = DonutChart::new();
+        donut.set_halign(gtk4::Align::Center);
+        imp.ratio_donut.replace(Some(donut.clone()));
+
+        let overlay = gtk4::Overlay::new();
+        overlay.set_halign(gtk4::Align::Center);
+        overlay.set_child(Some(&donut));
+
+        let center = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(0)
+            .halign(gtk4::Align::Center)
+            .valign(gtk4::Align::Center)
+            .can_target(false)
+            .build();
+        let share = gtk4::Label::builder()
+            .label("–")
+            .css_classes(vec!["title-1".to_string()])
+            .build();
+        imp.ratio_share.replace(Some(share.clone()));
+        center.append(&share);
+        center.append(
+            &gtk4::Label::builder()
+                .label(gettext("Denied"))
+                .css_classes(vec!["caption".to_string(), "dim-label".to_string()])
+                .build(),
+        );
+        overlay.add_overlay(&center);
+        content.append(&overlay);
+
+        let legend = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(8)
+            .margin_top(14)
+            .build();
+        let accepted_val = legend_row(
+            &legend,
+            "object-select-symbolic",
+            "success",
+            &gettext("Accepted"),
+        );
+        let denied_val = legend_row(
+            &legend,
+            "window-close-symbolic",
+            "error",
+            &gettext("Denied"),
+        );
+        imp.ratio_accepted_val.replace(Some(accepted_val));
+        imp.ratio_denied_val.replace(Some(denied_val));
+        content.append(&legend);
+
+        // Why denied packets may be missing, and the fix when it is ours
+        let hint = gtk4::Label::builder()
+            .css_classes(vec!["caption".to_string(), "dim-label".to_string()])
+            .halign(gtk4::Align::Start)
+            .wrap(true)
+            .xalign(0.0)
+            .margin_top(10)
+            .visible(false)
+            .build();
+        imp.ratio_hint.replace(Some(hint.clone()));
+        content.append(&hint);
+
+        let enable_button = gtk4::Button::builder()
+            .label(gettext("Log Denied Packets"))
+            .tooltip_text(gettext(
+                "Have firewalld log every rejected or dropped packet so they can be counted",
+            ))
+            .halign(gtk4::Align::Start)
+            .margin_top(8)
+            .css_classes(vec!["pill".to_string()])
+            .visible(false)
+            .build();
+        let page = self.downgrade();
+        enable_button.connect_clicked(move |button| {
+            if let Some(page) = page.upgrade() {
+                button.set_sensitive(false);
+                page.enable_log_denied(button);
+            }
+        });
+        imp.ratio_enable_button.replace(Some(enable_button.clone()));
+        content.append(&enable_button);
 
-        flow
+        frame
     }
 
     /// Panel: connection-state donut with a legend.
     fn build_donut_panel(&self) -> gtk4::Frame {
         let imp = self.imp();
-        let (frame, content) = panel_card(
+        let (frame, content) = panel_card_with_suffix(
             &gettext("Connection Overview"),
             &gettext("By current state"),
+            Some(self.export_menu(ExportChart::Breakdown).upcast_ref()),
         );
 
         let donut = DonutChart::new();
@@ -597,9 +1963,25 @@ impl OverviewPage {
             .spacing(8)
             .margin_top(14)
             .build();
-        let active_val = legend_row(&legend, "sw-success", &gettext("Active"));
-        let blocked_val = legend_row(&legend, "sw-error", &gettext("Blocked"));
-        let idle_val = legend_row(&legend, "sw-idle", &gettext("Idle"));
+        // Icons repeat the marks drawn on the ring.
+        let active_val = legend_row(
+            &legend,
+            "object-select-symbolic",
+            "success",
+            &gettext("Active"),
+        );
+        let blocked_val = legend_row(
+            &legend,
+            "window-close-symbolic",
+            "error",
+            &gettext("Blocked"),
+        );
+        let idle_val = legend_row(
+            &legend,
+            "list-remove-symbolic",
+            "dim-label",
+            &gettext("Idle"),
+        );
         imp.donut_active_val.replace(Some(active_val));
         imp.donut_blocked_val.replace(Some(blocked_val));
         imp.donut_idle_val.replace(Some(idle_val));
@@ -646,11 +2028,35 @@ impl OverviewPage {
         frame
     }
 
+    /// Card: this month's usage of interfaces with a data quota. Hidden
+    /// until a quota is set in Preferences.
+    fn build_data_usage_card(&self) -> gtk4::Frame {
+        let imp = self.imp();
+        let (frame, content) = panel_card(
+            &gettext("Data Usage"),
+            &gettext("Metered interfaces · this month"),
+        );
+        frame.set_visible(false);
+
+        let list = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(12)
+            .build();
+        content.append(&list);
+
+        imp.usage_card.replace(Some(frame.clone()));
+        imp.usage_box.replace(Some(list));
+        frame
+    }
+
     /// Create the live network-activity card (real /proc/net/dev bandwidth).
     fn create_network_activity_card(&self) -> gtk4::Frame {
         let imp = self.imp();
-        let (frame, content) =
-            panel_card(&gettext("Network Activity"), &gettext("Throughput · live"));
+        let (frame, content) = panel_card_with_suffix(
+            &gettext("Network Activity"),
+            &gettext("Throughput · live"),
+            Some(self.export_menu(ExportChart::NetworkActivity).upcast_ref()),
+        );
 
         // Interface selector.
         let header = gtk4::Box::builder()
@@ -670,12 +2076,12 @@ impl OverviewPage {
 
         let chart = NetworkActivityChart::new();
         chart.set_hexpand(true);
-        chart.set_size_request(-1, 120);
+        density::track(&chart, Role::Size(-1, 120));
         chart.set_margin_top(4);
         content.append(&chart);
 
         let rate_label = gtk4::Label::builder()
-            .label("↓ 0.0 KB/s   ↑ 0.0 KB/s")
+            .label(format!("↓ {}   ↑ {}", format_rate(0.0), format_rate(0.0)))
             .css_classes(vec!["dim-label".to_string(), "caption".to_string()])
             .halign(gtk4::Align::Start)
             .margin_top(6)
@@ -696,7 +2102,12 @@ impl OverviewPage {
 
         let rate_label_cb = rate_label.clone();
         chart.connect_rate_updated(move |inb, outb| {
-            rate_label_cb.set_label(&format!("↓ {:.1} KB/s   ↑ {:.1} KB/s", inb, outb));
+            // The chart reports KB/s
+            rate_label_cb.set_label(&format!(
+                "↓ {}   ↑ {}",
+                format_rate(inb * 1024.0),
+                format_rate(outb * 1024.0)
+            ));
         });
         chart.start_live_collection();
 
@@ -705,6 +2116,275 @@ impl OverviewPage {
         frame
     }
 
+    /// Panel: download and upload rates of one interface over the last few
+    /// minutes, with its packet and drop rates below.
+    fn build_bandwidth_panel(&self) -> gtk4::Frame {
+        let imp = self.imp();
+        let (frame, content) = panel_card_with_suffix(
+            &gettext("Bandwidth"),
+            &gettext("Per interface · last 5 minutes"),
+            Some(self.export_menu(ExportChart::Bandwidth).upcast_ref()),
+        );
+
+        let header = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(8)
+            .build();
+        header.append(&gtk4::Label::builder().hexpand(true).build());
+        let dropdown = gtk4::DropDown::from_strings(&[]);
+        dropdown.set_tooltip_text(Some(&gettext("Choose which interface to graph")));
+        dropdown.add_css_class("flat");
+        let page = self.clone();
+        dropdown.connect_selected_notify(move |_| {
+            page.render_bandwidth();
+        });
+        header.append(&dropdown);
+        content.append(&header);
+
+        let chart = LineChart::new();
+        chart.set_hexpand(true);
+        chart.set_max_points(60);
+        density::track(&chart, Role::Size(-1, 120));
+        chart.set_margin_top(4);
+        content.append(&chart);
+
+        let detail = gtk4::Label::builder()
+            .label(gettext("Collecting…"))
+            .css_classes(vec!["dim-label".to_string(), "caption".to_string()])
+            .halign(gtk4::Align::Start)
+            .wrap(true)
+            .xalign(0.0)
+            .margin_top(6)
+            .build();
+        content.append(&detail);
+
+        imp.bandwidth_dropdown.replace(Some(dropdown));
+        imp.bandwidth_chart.replace(Some(chart));
+        imp.bandwidth_detail.replace(Some(detail));
+        frame
+    }
+
+    /// Read the interface counters and redraw the bandwidth panel, keeping
+    /// the chosen interface when others come and go.
+    fn sample_bandwidth(&self) {
+        let imp = self.imp();
+        imp.interface_stats.borrow_mut().collect();
+        let interfaces = imp.interface_stats.borrow().interfaces();
+
+        if *imp.bandwidth_interfaces.borrow() != interfaces {
+            let chosen = self.chosen_interface();
+            imp.bandwidth_interfaces.replace(interfaces.clone());
+            if let Some(dropdown) = imp.bandwidth_dropdown.borrow().as_ref() {
+                let names: Vec<&str> = interfaces.iter().map(String::as_str).collect();
+                // Replacing the model re-renders through the selection
+                dropdown.set_model(Some(&gtk4::StringList::new(&names)));
+                let index = chosen
+                    .and_then(|name| interfaces.iter().position(|i| *i == name))
+                    .unwrap_or(0);
+                dropdown.set_selected(index as u32);
+            }
+        }
+        self.render_bandwidth();
+    }
+
+    /// The interface picked in the bandwidth panel.
+    fn chosen_interface(&self) -> Option<String> {
+        let imp = self.imp();
+        let selected = imp.bandwidth_dropdown.borrow().as_ref()?.selected() as usize;
+        imp.bandwidth_interfaces.borrow().get(selected).cloned()
+    }
+
+    /// Redraw the bandwidth chart and totals for the chosen interface.
+    fn render_bandwidth(&self) {
+        let imp = self.imp();
+        let Some(interface) = self.chosen_interface() else {
+            if let Some(chart) = imp.bandwidth_chart.borrow().as_ref() {
+                chart.set_series(Vec::new());
+            }
+            set_label(&imp.bandwidth_detail, &gettext("No network interfaces"));
+            return;
+        };
+        let stats = imp.interface_stats.borrow();
+        let Some(history) = stats.history(&interface) else {
+            return;
+        };
+
+        if let Some(chart) = imp.bandwidth_chart.borrow().as_ref() {
+            let (r, g, b) = color_accent();
+            let mut download = DataSeries::new(
+                &gettext("Download"),
+                gdk::RGBA::new(r as f32, g as f32, b as f32, 1.0),
+            );
+            download.set_values(history.iter().map(|r| r.rx_bytes / 1024.0).collect());
+            let mut upload = DataSeries::new(&gettext("Upload"), process_rgba(1));
+            upload.set_values(history.iter().map(|r| r.tx_bytes / 1024.0).collect());
+            chart.set_series(vec![download, upload]);
+        }
+
+        let latest = history.back().copied().unwrap_or_default();
+        let direction = |arrow: &str, bytes: f64, packets: f64, drops: f64| {
+            format!(
+                "{} {} · {} · {}",
+                arrow,
+                format_rate(bytes),
+                gettext("%s packets/s").replace("%s", &format_decimal(packets, 0)),
+                gettext("%s dropped/s").replace("%s", &format_decimal(drops, 1)),
+            )
+        };
+        set_label(
+            &imp.bandwidth_detail,
+            &format!(
+                "{}\n{}",
+                direction("↓", latest.rx_bytes, latest.rx_packets, latest.rx_drops),
+                direction("↑", latest.tx_bytes, latest.tx_packets, latest.tx_drops),
+            ),
+        );
+    }
+
+    /// Panel: remote connections over time, overall or broken down by the
+    /// processes holding them, with the busiest processes listed below.
+    fn build_process_connections_panel(&self) -> gtk4::Frame {
+        let imp = self.imp();
+        let (frame, content) = panel_card_with_suffix(
+            &gettext("Connections"),
+            &gettext("Remote connections · last 5 minutes"),
+            Some(self.export_menu(ExportChart::Connections).upcast_ref()),
+        );
+
+        let header = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(8)
+            .build();
+        header.append(&gtk4::Label::builder().hexpand(true).build());
+        let by_process = gtk4::ToggleButton::builder()
+            .label(gettext("By Process"))
+            .tooltip_text(gettext("Graph the busiest processes separately"))
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        let page = self.clone();
+        by_process.connect_toggled(move |_| {
+            page.render_process_connections();
+        });
+        header.append(&by_process);
+        content.append(&header);
+
+        let chart = LineChart::new();
+        chart.set_hexpand(true);
+        chart.set_show_legend(false);
+        chart.set_max_points(60);
+        density::track(&chart, Role::Size(-1, 120));
+        chart.set_margin_top(4);
+        content.append(&chart);
+
+        let list = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(6)
+            .margin_top(10)
+            .build();
+        content.append(&list);
+
+        imp.process_chart.replace(Some(chart));
+        imp.process_toggle.replace(Some(by_process));
+        imp.process_box.replace(Some(list));
+        frame
+    }
+
+    /// Redraw the connections chart and the top process list from the
+    /// collected history.
+    fn render_process_connections(&self) {
+        let imp = self.imp();
+        let collector = imp.process_stats.borrow();
+        let top = collector.top(TOP_PROCESSES);
+        let by_process = imp
+            .process_toggle
+            .borrow()
+            .as_ref()
+            .is_some_and(|toggle| toggle.is_active());
+
+        if let Some(chart) = imp.process_chart.borrow().as_ref() {
+            let series = if by_process {
+                top.iter()
+                    .enumerate()
+                    .map(|(i, process)| {
+                        let mut series = DataSeries::new(&process.process, process_rgba(i));
+                        series.set_values(process.history.iter().map(|v| *v as f64).collect());
+                        series
+                    })
+                    .collect()
+            } else {
+                let (r, g, b) = color_accent();
+                let mut series = DataSeries::new(
+                    &gettext("All"),
+                    gdk::RGBA::new(r as f32, g as f32, b as f32, 1.0),
+                );
+                series.set_values(collector.totals().iter().map(|v| *v as f64).collect());
+                vec![series]
+            };
+            chart.set_series(series);
+        }
+
+        let list_ref = imp.process_box.borrow();
+        let Some(list) = list_ref.as_ref() else {
+            return;
+        };
+        if top.is_empty() {
+            placeholder(list, &gettext("No remote connections"));
+            return;
+        }
+        while let Some(child) = list.first_child() {
+            list.remove(&child);
+        }
+        for (i, process) in top.iter().enumerate() {
+            let row = gtk4::Box::builder()
+                .orientation(gtk4::Orientation::Horizontal)
+                .spacing(9)
+                .build();
+            // The swatch is the legend of the broken-down chart
+            let swatch = gtk4::DrawingArea::builder()
+                .content_width(10)
+                .content_height(10)
+                .valign(gtk4::Align::Center)
+                .opacity(if by_process { 1.0 } else { 0.35 })
+                .build();
+            let color = process_rgba(i);
+            swatch.set_draw_func(move |_, cr, width, height| {
+                let radius = width.min(height) as f64 / 2.0;
+                cr.set_source_rgba(
+                    color.red() as f64,
+                    color.green() as f64,
+                    color.blue() as f64,
+                    1.0,
+                );
+                cr.arc(
+                    width as f64 / 2.0,
+                    height as f64 / 2.0,
+                    radius,
+                    0.0,
+                    std::f64::consts::TAU,
+                );
+                let _ = cr.fill();
+            });
+            row.append(&swatch);
+            row.append(
+                &gtk4::Label::builder()
+                    .label(display_process_name(&process.process))
+                    .css_classes(vec!["caption".to_string()])
+                    .halign(gtk4::Align::Start)
+                    .hexpand(true)
+                    .ellipsize(gtk4::pango::EllipsizeMode::End)
+                    .build(),
+            );
+            row.append(
+                &gtk4::Label::builder()
+                    .label(format_number(process.current as u64))
+                    .css_classes(vec!["caption".to_string(), "numeric".to_string()])
+                    .halign(gtk4::Align::End)
+                    .build(),
+            );
+            list.append(&row);
+        }
+    }
+
     /// Scan connections + per-socket bytes + country, then render the dashboard.
     fn refresh_connected_hosts(&self) {
         let page = self.clone();
@@ -807,9 +2487,15 @@ impl OverviewPage {
         *prev_sock = cur_sock;
         drop(prev_sock);
 
+        // --- Connections per process ---
+        let remote: Vec<crate::admin::ActiveConnection> =
+            connections.into_iter().filter(|c| c.is_remote()).collect();
+        imp.process_stats.borrow_mut().push_connections(&remote);
+        self.render_process_connections();
+
         // --- Stat cards ---
-        set_label(&imp.metric_active, &remote_count.to_string());
-        set_label(&imp.metric_apps, &apps.len().to_string());
+        set_label(&imp.metric_active, &format_number(remote_count as u64));
+        set_label(&imp.metric_apps, &format_number(apps.len() as u64));
 
         // --- Connections hub chip ---
         if let Some(chip) = imp.conn_chip.borrow().as_ref() {
@@ -824,18 +2510,18 @@ impl OverviewPage {
         let blocked = imp.blocked_count.get();
         if let Some(donut) = imp.donut.borrow().as_ref() {
             donut.set_segments(&[
-                (remote_count as f64, color_success()),
-                (blocked as f64, color_error()),
-                (listening as f64, color_idle()),
+                (remote_count as f64, color_success(), SegmentMark::Check),
+                (blocked as f64, color_error(), SegmentMark::Cross),
+                (listening as f64, color_idle(), SegmentMark::Dash),
             ]);
         }
         set_label(
             &imp.donut_total,
-            &(remote_count + blocked + listening).to_string(),
+            &format_number((remote_count + blocked + listening) as u64),
         );
-        set_label(&imp.donut_active_val, &remote_count.to_string());
-        set_label(&imp.donut_blocked_val, &blocked.to_string());
-        set_label(&imp.donut_idle_val, &listening.to_string());
+        set_label(&imp.donut_active_val, &format_number(remote_count as u64));
+        set_label(&imp.donut_blocked_val, &format_number(blocked as u64));
+        set_label(&imp.donut_idle_val, &format_number(listening as u64));
 
         // --- Protocols + countries panels ---
         self.render_protocols(&proto_counts, remote_count);
@@ -967,7 +2653,7 @@ impl OverviewPage {
             );
             top.append(
                 &gtk4::Label::builder()
-                    .label(format!("{:.0}%", frac * 100.0))
+                    .label(format_percent(frac * 100.0, 0))
                     .css_classes(vec!["caption".to_string(), "numeric".to_string()])
                     .halign(gtk4::Align::End)
                     .build(),
@@ -1022,7 +2708,7 @@ impl OverviewPage {
             );
             top.append(
                 &gtk4::Label::builder()
-                    .label(count.to_string())
+                    .label(format_number(count as u64))
                     .css_classes(vec![
                         "caption".to_string(),
                         "conn-meta".to_string(),
@@ -1049,13 +2735,228 @@ impl OverviewPage {
                 label.set_label(&default_zone.name);
             }
         }
+        self.show_unsaved_changes(zones);
+        self.refresh_posture();
+    }
+
+    /// Add one second of packet counts to the traffic ratio donut; `denied`
+    /// is `None` when denied packets cannot be read from the kernel log.
+    pub fn push_packet_counts(&self, accepted: u64, denied: Option<u64>) {
+        let imp = self.imp();
+        imp.denied_available.set(denied.is_some());
+        let (accepted, denied) = {
+            let mut samples = imp.packet_samples.borrow_mut();
+            samples.push_back((accepted, denied.unwrap_or(0)));
+            while samples.len() > RATIO_WINDOW_SECS {
+                samples.pop_front();
+            }
+            samples
+                .iter()
+                .fold((0u64, 0u64), |(a, d), (sa, sd)| (a + sa, d + sd))
+        };
+
+        if let Some(donut) = imp.ratio_donut.borrow().as_ref() {
+            donut.set_segments(&[
+                (accepted as f64, color_success(), SegmentMark::Check),
+                (denied as f64, color_error(), SegmentMark::Cross),
+            ]);
+        }
+        set_label(&imp.ratio_accepted_val, &format_number(accepted));
+        let total = accepted + denied;
+        if imp.denied_available.get() {
+            set_label(&imp.ratio_denied_val, &format_number(denied));
+            let share = if total == 0 {
+                format_percent(0.0, 0)
+            } else {
+                format_percent(denied as f64 * 100.0 / total as f64, 1)
+            };
+            set_label(&imp.ratio_share, &share);
+        } else {
+            set_label(&imp.ratio_denied_val, "–");
+            set_label(&imp.ratio_share, "–");
+        }
+        self.update_ratio_hint();
+    }
+
+    /// Record firewalld's LogDenied setting, `None` when it cannot be read.
+    pub fn set_log_denied(&self, value: Option<&str>) {
+        self.imp().log_denied.replace(value.map(String::from));
+        self.update_ratio_hint();
+    }
+
+    /// Explain a traffic ratio without denied packets, offering to turn on
+    /// LogDenied when that is the reason.
+    fn update_ratio_hint(&self) {
+        let imp = self.imp();
+        let logging_off = imp.log_denied.borrow().as_deref() == Some("off");
+        let text = if logging_off {
+            Some(gettext(
+                "Denied packets are not logged, so none are counted.",
+            ))
+        } else if !imp.denied_available.get() {
+            Some(gettext(
+                "Denied packets cannot be read from the system journal.",
+            ))
+        } else {
+            None
+        };
+        if let Some(hint) = imp.ratio_hint.borrow().as_ref() {
+            hint.set_visible(text.is_some());
+            hint.set_label(text.as_deref().unwrap_or_default());
+        }
+        if let Some(button) = imp.ratio_enable_button.borrow().as_ref() {
+            button.set_visible(logging_off);
+        }
+    }
+
+    /// Set LogDenied to `all`; firewalld saves it and reloads its rules.
+    fn enable_log_denied(&self, button: &gtk4::Button) {
+        let page = self.clone();
+        let button = button.clone();
+        glib::spawn_future_local(async move {
+            let result = crate::firewall::call(move |client| {
+                client.connect()?;
+                client.set_log_denied("all")
+            })
+            .await;
+            button.set_sensitive(true);
+            let Some(main_window) = page.main_window() else {
+                return;
+            };
+            match result {
+                Ok(Ok(())) => {
+                    page.set_log_denied(Some("all"));
+                    main_window.show_toast(&gettext("Denied packets are now logged"));
+                    main_window.refresh_data();
+                }
+                Ok(Err(e)) => main_window.show_toast(&format!(
+                    "{}: {}",
+                    gettext("Failed to change denied packet logging"),
+                    e
+                )),
+                Err(_) => {
+                    main_window.show_toast(&gettext("Failed to change denied packet logging"))
+                }
+            }
+        });
+    }
+
+    /// Update the blocked-ports count (stat card + donut on next refresh)
+    /// from the rule counts; a blocked range counts every port it covers.
+    pub fn set_rule_stats(&self, stats: &RuleStats) {
+        let imp = self.imp();
+        let blocked = stats.total.blocked_ports;
+        imp.blocked_count.set(blocked as usize);
+        set_label(&imp.metric_blocked, &format_number(u64::from(blocked)));
     }
 
-    /// Update the blocked-ports count (stat card + donut on next refresh).
-    pub fn set_blocked_ports(&self, blocked_ports: &[crate::models::Port]) {
+    /// Show the monthly usage of interfaces with a data quota; the card is
+    /// hidden when there are none.
+    pub fn set_data_usage(&self, usage: &[QuotaUsage]) {
         let imp = self.imp();
-        imp.blocked_count.set(blocked_ports.len());
-        set_label(&imp.metric_blocked, &blocked_ports.len().to_string());
+        if let Some(card) = imp.usage_card.borrow().as_ref() {
+            card.set_visible(!usage.is_empty());
+        }
+        let list_ref = imp.usage_box.borrow();
+        let Some(list) = list_ref.as_ref() else {
+            return;
+        };
+        while let Some(child) = list.first_child() {
+            list.remove(&child);
+        }
+
+        let palette = palette::current();
+        for entry in usage {
+            let row = gtk4::Box::builder()
+                .orientation(gtk4::Orientation::Vertical)
+                .spacing(5)
+                .build();
+            let top = gtk4::Box::builder()
+                .orientation(gtk4::Orientation::Horizontal)
+                .build();
+            top.append(
+                &gtk4::Label::builder()
+                    .label(&entry.interface)
+                    .css_classes(vec!["caption".to_string(), "conn-meta".to_string()])
+                    .halign(gtk4::Align::Start)
+                    .hexpand(true)
+                    .ellipsize(gtk4::pango::EllipsizeMode::End)
+                    .build(),
+            );
+            top.append(
+                &gtk4::Label::builder()
+                    .label(
+                        gettext("%s of %s")
+                            .replacen("%s", &format_bytes(entry.used), 1)
+                            .replacen("%s", &format_bytes(entry.quota), 1),
+                    )
+                    .css_classes(vec![
+                        "caption".to_string(),
+                        "conn-meta".to_string(),
+                        "numeric".to_string(),
+                    ])
+                    .halign(gtk4::Align::End)
+                    .build(),
+            );
+            row.append(&top);
+
+            let fraction = entry.fraction();
+            let (r, g, b) = if fraction >= 1.0 {
+                palette.error()
+            } else if fraction >= 0.9 {
+                palette.warning()
+            } else {
+                color_accent()
+            };
+            let bar = MeterBar::new();
+            bar.set_color(r, g, b);
+            bar.set_fraction(fraction);
+            row.append(&bar);
+            list.append(&row);
+        }
+    }
+
+    /// Mark firewall configuration changes on the network activity chart, so
+    /// traffic spikes can be matched to the change that preceded them.
+    pub fn set_config_events(&self, events: &[ConfigEvent]) {
+        let Some(chart) = self.imp().network_chart.borrow().clone() else {
+            return;
+        };
+        let palette = palette::current();
+        let annotations = events
+            .iter()
+            .map(|event| {
+                let (label, color) = match &event.kind {
+                    ConfigEventKind::Reloaded => (gettext("Firewall reloaded"), (0.6, 0.6, 0.6)),
+                    ConfigEventKind::PanicEnabled => {
+                        (gettext("Panic mode turned on"), palette.error())
+                    }
+                    ConfigEventKind::PanicDisabled => {
+                        (gettext("Panic mode turned off"), palette.success())
+                    }
+                    ConfigEventKind::DefaultZoneChanged(zone) => (
+                        gettext("Default zone switched to %s").replace("%s", zone),
+                        palette.warning(),
+                    ),
+                    ConfigEventKind::InterfaceAdded(interface) => (
+                        gettext("Interface %s appeared").replace("%s", interface),
+                        palette.warning(),
+                    ),
+                    ConfigEventKind::InterfaceZoneAssigned { interface, zone } => (
+                        gettext("Interface %s moved to zone %s")
+                            .replacen("%s", interface, 1)
+                            .replacen("%s", zone, 1),
+                        (0.6, 0.6, 0.6),
+                    ),
+                };
+                ChartAnnotation {
+                    time: event.time,
+                    label,
+                    color,
+                }
+            })
+            .collect();
+        chart.set_annotations(annotations);
     }
 
     /// Show or hide the firewall connections overview section.
@@ -1072,6 +2973,51 @@ impl OverviewPage {
         self.imp().max_apps.set(count.max(1));
         self.render_cards();
     }
+
+    /// Render the whole dashboard, scrolled-off parts included, to a
+    /// texture stamped with the host name and the time. `None` while the
+    /// page is not on screen.
+    pub fn render_snapshot(&self) -> Option<gdk::Texture> {
+        let content = self.imp().content.borrow().clone()?;
+        let (width, height) = (content.width() as f32, content.height() as f32);
+        if !content.is_mapped() || width <= 0.0 || height <= 0.0 {
+            return None;
+        }
+        let bounds = graphene::Rect::new(0.0, 0.0, width, height);
+
+        let snapshot = gtk4::Snapshot::new();
+        let background = if adw::StyleManager::default().is_dark() {
+            gdk::RGBA::new(0.14, 0.14, 0.14, 1.0)
+        } else {
+            gdk::RGBA::new(0.98, 0.98, 0.98, 1.0)
+        };
+        snapshot.append_color(&background, &bounds);
+        gtk4::WidgetPaintable::new(Some(&content)).snapshot(&snapshot, width as f64, height as f64);
+
+        // Watermark in the bottom right corner
+        let now = chrono::Local::now();
+        let stamp = format!(
+            "Security Center • {} • {} {}",
+            glib::host_name(),
+            format_datetime(now.timestamp()),
+            now.format("%Z")
+        );
+        let layout = content.create_pango_layout(Some(&stamp));
+        let (text_width, text_height) = layout.pixel_size();
+        let mut ink = content.color();
+        ink.set_alpha(0.55);
+        snapshot.save();
+        snapshot.translate(&graphene::Point::new(
+            width - text_width as f32 - 16.0,
+            height - text_height as f32 - 10.0,
+        ));
+        snapshot.append_layout(&layout, &ink);
+        snapshot.restore();
+
+        let node = snapshot.to_node()?;
+        let renderer = self.native()?.renderer()?;
+        Some(renderer.render_texture(&node, Some(&bounds)))
+    }
 }
 
 impl Default for OverviewPage {
@@ -1176,6 +3122,28 @@ fn build_app_card(d: AppCardData) -> gtk4::Box {
             .build(),
     );
 
+    // Clicking the tile opens everything known about its address
+    if !d.primary_addr.is_unspecified() {
+        tile.set_tooltip_text(Some(
+            &gettext("Show details for %s").replace("%s", &d.primary_addr.to_string()),
+        ));
+        let click = gtk4::GestureClick::new();
+        let ip = d.primary_addr;
+        let country_label = d.country.clone();
+        click.connect_released(move |gesture, _, _, _| {
+            if let Some(tile) = gesture.widget() {
+                present_ip_details(
+                    &tile,
+                    IpDetailsContext {
+                        country_label: country_label.clone(),
+                        ..IpDetailsContext::for_address(ip)
+                    },
+                );
+            }
+        });
+        tile.add_controller(click);
+    }
+
     let spark = Sparkline::new();
     spark.set_values(&d.history);
     spark.set_hexpand(true);
@@ -1193,7 +3161,7 @@ fn build_app_card(d: AppCardData) -> gtk4::Box {
         .build();
     rate_col.append(
         &gtk4::Label::builder()
-            .label(format!("{:.0} KB/s", d.down_kbs + d.up_kbs))
+            .label(format_rate((d.down_kbs + d.up_kbs) * 1024.0))
             .css_classes(vec!["heading".to_string(), "numeric".to_string()])
             .halign(gtk4::Align::End)
             .build(),
@@ -1215,7 +3183,11 @@ fn build_app_card(d: AppCardData) -> gtk4::Box {
 }
 
 /// Create a summary stat card, returning the card and its value label.
-fn stat_card(icon: &str, tile_class: &str, caption: &str) -> (gtk4::Frame, gtk4::Label) {
+fn stat_card(
+    icon: &str,
+    tile_class: &str,
+    caption: &str,
+) -> (gtk4::Frame, gtk4::Image, gtk4::Label) {
     let frame = gtk4::Frame::new(None);
     frame.add_css_class("card");
 
@@ -1254,12 +3226,38 @@ fn stat_card(icon: &str, tile_class: &str, caption: &str) -> (gtk4::Frame, gtk4:
     );
 
     frame.set_child(Some(&content));
-    (frame, value)
+    (frame, icon_tile, value)
+}
+
+/// Button label of a remedy.
+fn remedy_label(remedy: Remedy) -> String {
+    match remedy {
+        Remedy::Run(Operation::EnableFirewall) => gettext("Start Firewall"),
+        Remedy::Run(Operation::SetPanicMode { .. }) => gettext("Turn Off"),
+        Remedy::Run(Operation::SetDefaultZone { zone }) => {
+            gettext("Switch to %s").replace("%s", zone)
+        }
+        Remedy::Run(Operation::RemoveZoneService { service, .. }) => {
+            gettext("Remove %s").replace("%s", service)
+        }
+        Remedy::Run(_) => gettext("Fix"),
+        Remedy::ReviewDrift => gettext("Review"),
+        Remedy::ShowPage(_) => gettext("Show"),
+    }
 }
 
 /// Create an analytics panel card with a title + hint, returning the card and
 /// the inner content box to append into.
 fn panel_card(title: &str, hint: &str) -> (gtk4::Frame, gtk4::Box) {
+    panel_card_with_suffix(title, hint, None)
+}
+
+/// [`panel_card`] with `suffix`, e.g. a menu button, at the end of the title.
+fn panel_card_with_suffix(
+    title: &str,
+    hint: &str,
+    suffix: Option<&gtk4::Widget>,
+) -> (gtk4::Frame, gtk4::Box) {
     let frame = gtk4::Frame::new(None);
     frame.add_css_class("card");
     frame.add_css_class("dashboard-card");
@@ -1273,13 +3271,22 @@ fn panel_card(title: &str, hint: &str) -> (gtk4::Frame, gtk4::Box) {
         .margin_end(16)
         .build();
 
-    content.append(
+    let title_row = gtk4::Box::builder()
+        .orientation(gtk4::Orientation::Horizontal)
+        .spacing(8)
+        .build();
+    title_row.append(
         &gtk4::Label::builder()
             .label(title)
             .css_classes(vec!["heading".to_string()])
             .halign(gtk4::Align::Start)
+            .hexpand(true)
             .build(),
     );
+    if let Some(suffix) = suffix {
+        title_row.append(suffix);
+    }
+    content.append(&title_row);
     content.append(
         &gtk4::Label::builder()
             .label(hint)
@@ -1293,15 +3300,17 @@ fn panel_card(title: &str, hint: &str) -> (gtk4::Frame, gtk4::Box) {
     (frame, content)
 }
 
-/// Append a donut legend row (swatch + label + value), returning the value label.
-fn legend_row(parent: &gtk4::Box, swatch_class: &str, label: &str) -> gtk4::Label {
+/// Append a donut legend row (state icon + label + value), returning the value label.
+fn legend_row(parent: &gtk4::Box, icon: &str, color_class: &str, label: &str) -> gtk4::Label {
     let row = gtk4::Box::builder()
         .orientation(gtk4::Orientation::Horizontal)
         .spacing(9)
         .build();
     row.append(
-        &gtk4::Box::builder()
-            .css_classes(vec!["swatch".to_string(), swatch_class.to_string()])
+        &gtk4::Image::builder()
+            .icon_name(icon)
+            .pixel_size(12)
+            .css_classes(vec!["swatch".to_string(), color_class.to_string()])
             .valign(gtk4::Align::Center)
             .build(),
     );
@@ -1371,15 +3380,28 @@ fn color_accent() -> (f64, f64, f64) {
     (0.21, 0.52, 0.89)
 }
 fn color_success() -> (f64, f64, f64) {
-    (0.18, 0.76, 0.49)
+    palette::current().success()
 }
 fn color_error() -> (f64, f64, f64) {
-    (0.88, 0.11, 0.14)
+    palette::current().error()
 }
 fn color_idle() -> (f64, f64, f64) {
     (0.55, 0.55, 0.58)
 }
 
+/// Series color of the `index`th busiest process.
+fn process_rgba(index: usize) -> gdk::RGBA {
+    const COLORS: [(f32, f32, f32); TOP_PROCESSES] = [
+        (0.21, 0.52, 0.89),
+        (0.90, 0.50, 0.20),
+        (0.40, 0.75, 0.40),
+        (0.65, 0.40, 0.80),
+        (0.85, 0.35, 0.50),
+    ];
+    let (r, g, b) = COLORS[index % COLORS.len()];
+    gdk::RGBA::new(r, g, b, 1.0)
+}
+
 mod imp {
     use super::*;
 
@@ -1389,7 +3411,42 @@ mod imp {
         pub status_icon: RefCell<Option<gtk4::Image>>,
         pub status_title: RefCell<Option<gtk4::Label>>,
         pub status_subtitle: RefCell<Option<gtk4::Label>>,
+        pub status_action: RefCell<Option<gtk4::Button>>,
+        /// Why firewalld is unavailable, while it is.
+        pub unavailable: RefCell<Option<FirewallUnavailable>>,
+        // Conflicting firewall manager banner
+        pub conflict_revealer: RefCell<Option<gtk4::Revealer>>,
+        pub conflict_title: RefCell<Option<gtk4::Label>>,
+        pub conflict_detail: RefCell<Option<gtk4::Label>>,
+        pub conflict_button: RefCell<Option<gtk4::Button>>,
+        pub conflicts: RefCell<Vec<FirewallConflict>>,
+        // Security score
+        pub posture_score: RefCell<Option<gtk4::Label>>,
+        pub posture_summary: RefCell<Option<gtk4::Label>>,
+        pub posture_bar: RefCell<Option<MeterBar>>,
+        pub posture_list: RefCell<Option<gtk4::Box>>,
+        pub posture_running: Cell<bool>,
+        /// Another refresh was asked for while one was running.
+        pub posture_again: Cell<bool>,
+        // Zone suggestion banner
+        pub zone_suggestion_revealer: RefCell<Option<gtk4::Revealer>>,
+        pub zone_suggestion_icon: RefCell<Option<gtk4::Image>>,
+        pub zone_suggestion_title: RefCell<Option<gtk4::Label>>,
+        pub zone_suggestion_reason: RefCell<Option<gtk4::Label>>,
+        pub zone_suggestion: RefCell<Option<ZoneSuggestion>>,
+        // Runtime/permanent difference banner
+        pub unsaved_revealer: RefCell<Option<gtk4::Revealer>>,
+        pub unsaved_detail: RefCell<Option<gtk4::Label>>,
+        /// Zones of the last refresh, for the Review dialog.
+        pub unsaved_zones: RefCell<Vec<Zone>>,
         pub traffic_switch: RefCell<Option<gtk4::Switch>>,
+        /// Everything below the scrollbars, for status snapshots.
+        pub content: RefCell<Option<gtk4::Box>>,
+        pub restart_button: RefCell<Option<gtk4::Button>>,
+        /// State pill per watched service.
+        pub unit_pills: RefCell<HashMap<WatchedUnit, gtk4::Label>>,
+        /// What firewalld is doing, once the unit watch has reported it.
+        pub firewalld_activity: Cell<Option<UnitActivity>>,
         pub traffic_label: RefCell<Option<gtk4::Label>>,
         pub default_zone_label: RefCell<Option<gtk4::Label>>,
         // Stat cards
@@ -1397,6 +3454,7 @@ mod imp {
         pub metric_blocked: RefCell<Option<gtk4::Label>>,
         pub metric_apps: RefCell<Option<gtk4::Label>>,
         pub stat_status: RefCell<Option<gtk4::Label>>,
+        pub stat_status_tile: RefCell<Option<gtk4::Image>>,
         // Connections hub
         pub connections_hub: RefCell<Option<gtk4::Frame>>,
         pub app_flow: RefCell<Option<gtk4::FlowBox>>,
@@ -1407,12 +3465,37 @@ mod imp {
         pub donut_active_val: RefCell<Option<gtk4::Label>>,
         pub donut_blocked_val: RefCell<Option<gtk4::Label>>,
         pub donut_idle_val: RefCell<Option<gtk4::Label>>,
+        // Analytics: traffic ratio donut
+        pub ratio_donut: RefCell<Option<DonutChart>>,
+        pub ratio_share: RefCell<Option<gtk4::Label>>,
+        pub ratio_accepted_val: RefCell<Option<gtk4::Label>>,
+        pub ratio_denied_val: RefCell<Option<gtk4::Label>>,
+        pub ratio_hint: RefCell<Option<gtk4::Label>>,
+        pub ratio_enable_button: RefCell<Option<gtk4::Button>>,
+        /// Packets (accepted, denied) per second, oldest first.
+        pub packet_samples: RefCell<VecDeque<(u64, u64)>>,
+        pub denied_available: Cell<bool>,
+        pub log_denied: RefCell<Option<String>>,
         // Analytics: protocols + countries
         pub proto_box: RefCell<Option<gtk4::Box>>,
+        pub usage_card: RefCell<Option<gtk4::Frame>>,
+        pub usage_box: RefCell<Option<gtk4::Box>>,
         pub country_box: RefCell<Option<gtk4::Box>>,
         // Analytics: network activity chart
         pub network_chart: RefCell<Option<NetworkActivityChart>>,
         pub rate_label: RefCell<Option<gtk4::Label>>,
+        // Analytics: bandwidth per interface
+        pub interface_stats: RefCell<InterfaceCollector>,
+        pub bandwidth_dropdown: RefCell<Option<gtk4::DropDown>>,
+        /// Interfaces listed in the dropdown, in its order.
+        pub bandwidth_interfaces: RefCell<Vec<String>>,
+        pub bandwidth_chart: RefCell<Option<LineChart>>,
+        pub bandwidth_detail: RefCell<Option<gtk4::Label>>,
+        // Analytics: connections per process
+        pub process_stats: RefCell<ProcessCollector>,
+        pub process_chart: RefCell<Option<LineChart>>,
+        pub process_toggle: RefCell<Option<gtk4::ToggleButton>>,
+        pub process_box: RefCell<Option<gtk4::Box>>,
         // Live state
         pub blocked_count: Cell<usize>,
         // How many application cards to render (user-configurable).
@@ -0,0 +1,155 @@
+// Security Center - Status Palettes
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Status colour palettes for colour vision deficiencies.
+//!
+//! The selected palette overrides libadwaita's named `success`, `warning` and
+//! `error` colours for the whole application, so every `.success`/`.error`
+//! style class follows it. Widgets drawn with Cairo read the same colours from
+//! [`current`]. Colour is never the only signal: states also carry distinct
+//! icons and chart marks.
+
+use std::cell::{Cell, RefCell};
+
+/// A set of status colours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusPalette {
+    /// libadwaita's green / yellow / red.
+    Default,
+    /// Blue / orange / vermillion (Okabe-Ito), for protanopia and deuteranopia.
+    RedGreen,
+    /// Teal / purple / red, for tritanopia.
+    BlueYellow,
+}
+
+impl StatusPalette {
+    pub const ALL: [StatusPalette; 3] = [
+        StatusPalette::Default,
+        StatusPalette::RedGreen,
+        StatusPalette::BlueYellow,
+    ];
+
+    /// Identifier stored in the settings file.
+    pub fn id(&self) -> &'static str {
+        match self {
+            StatusPalette::Default => "default",
+            StatusPalette::RedGreen => "red-green",
+            StatusPalette::BlueYellow => "blue-yellow",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|p| p.id() == id)
+            .unwrap_or(StatusPalette::Default)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StatusPalette::Default => "Standard",
+            StatusPalette::RedGreen => "Red-Green Safe",
+            StatusPalette::BlueYellow => "Blue-Yellow Safe",
+        }
+    }
+
+    /// Good state, as linear RGB in `0.0..=1.0`.
+    pub fn success(&self) -> (f64, f64, f64) {
+        match self {
+            StatusPalette::Default => (0.18, 0.76, 0.49),
+            StatusPalette::RedGreen => (0.0, 0.45, 0.70),
+            StatusPalette::BlueYellow => (0.0, 0.56, 0.53),
+        }
+    }
+
+    /// Attention state.
+    pub fn warning(&self) -> (f64, f64, f64) {
+        match self {
+            StatusPalette::Default => (0.90, 0.65, 0.04),
+            StatusPalette::RedGreen => (0.90, 0.62, 0.0),
+            StatusPalette::BlueYellow => (0.56, 0.27, 0.68),
+        }
+    }
+
+    /// Bad state.
+    pub fn error(&self) -> (f64, f64, f64) {
+        match self {
+            StatusPalette::Default => (0.88, 0.11, 0.14),
+            StatusPalette::RedGreen => (0.84, 0.37, 0.0),
+            StatusPalette::BlueYellow => (0.82, 0.16, 0.16),
+        }
+    }
+
+    /// CSS overriding libadwaita's status colours; empty for the default.
+    fn css(&self) -> String {
+        if *self == StatusPalette::Default {
+            return String::new();
+        }
+        let mut css = String::new();
+        for (name, rgb) in [
+            ("success", self.success()),
+            ("warning", self.warning()),
+            ("error", self.error()),
+        ] {
+            let hex = to_hex(rgb);
+            css.push_str(&format!(
+                "@define-color {name}_color {hex};\n@define-color {name}_bg_color {hex};\n"
+            ));
+        }
+        // libadwaita 1.6+ styles widgets through CSS variables
+        if gtk4::check_version(4, 16, 0).is_none() {
+            css.push_str(":root {\n");
+            for (name, rgb) in [
+                ("success", self.success()),
+                ("warning", self.warning()),
+                ("error", self.error()),
+            ] {
+                let hex = to_hex(rgb);
+                css.push_str(&format!(
+                    "  --{name}-color: {hex};\n  --{name}-bg-color: {hex};\n"
+                ));
+            }
+            css.push_str("}\n");
+        }
+        css
+    }
+}
+
+thread_local! {
+    static CURRENT: Cell<StatusPalette> = const { Cell::new(StatusPalette::Default) };
+    static PROVIDER: RefCell<Option<gtk4::CssProvider>> = const { RefCell::new(None) };
+}
+
+/// The palette in use.
+pub fn current() -> StatusPalette {
+    CURRENT.with(|c| c.get())
+}
+
+/// Switch the whole application to `palette`. Cairo-drawn widgets pick it up
+/// on their next redraw.
+pub fn apply(palette: StatusPalette) {
+    CURRENT.with(|c| c.set(palette));
+    let Some(display) = gtk4::gdk::Display::default() else {
+        return;
+    };
+    PROVIDER.with(|cell| {
+        let mut provider = cell.borrow_mut();
+        let provider = provider.get_or_insert_with(|| {
+            let provider = gtk4::CssProvider::new();
+            // Above the application stylesheet so the overrides win
+            gtk4::style_context_add_provider_for_display(
+                &display,
+                &provider,
+                gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION + 1,
+            );
+            provider
+        });
+        provider.load_from_string(&palette.css());
+    });
+}
+
+fn to_hex((r, g, b): (f64, f64, f64)) -> String {
+    let channel = |v: f64| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", channel(r), channel(g), channel(b))
+}
@@ -0,0 +1,1875 @@
+// Security Center - Platform Security Page
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Platform security page: host-level settings outside the firewall that
+//! other protections depend on: time synchronization, the TPM / measured
+//! boot chain, FIDO2/U2F security keys, bootloader / kernel command line
+//! findings, SELinux or AppArmor, Tor and proxies, core dump policy, logging
+//! and OpenSCAP compliance profiles. A hardening score at the top summarises
+//! the assessment.
+
+use std::cell::{Cell, RefCell};
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use super::density::{self, Role};
+use crate::admin::{
+    pcr_is_unused, pcr_purpose, query_compliance, query_coredump, query_logging,
+    query_mandatory_access, query_proxy_status, query_security_keys, query_time_sync, query_tpm,
+    read_pcr_banks, run_compliance_scan, set_selinux_enforcing, Assessment, ComplianceReport,
+    ComplianceStatus, CoredumpStatus, Finding, FindingCategory, LoggingStatus, MacDenial,
+    MacSystem, MandatoryAccess, Operation, OperationRunner, PcrBank, ProxyStatus, RuleOutcome,
+    RuleResult, SecurityKey, SelinuxStatus, TimeDaemon, TimeSyncStatus, TpmStatus, AUDITD_UNIT,
+};
+use crate::helper::{run_privileged, HelperOp};
+use crate::i18n::{format_bytes, format_datetime, format_percent, gettext};
+
+/// journald `Storage=` choices: (value, label).
+const JOURNAL_STORAGE: [(&str, &str); 3] = [
+    ("auto", "Automatic"),
+    ("persistent", "Persistent"),
+    ("volatile", "Memory Only"),
+];
+
+/// `SystemMaxUse=` presets: (value, label); empty means journald's default.
+const JOURNAL_SIZES: [(&str, &str); 6] = [
+    ("", "System Default"),
+    ("256M", "256 MB"),
+    ("512M", "512 MB"),
+    ("1G", "1 GB"),
+    ("2G", "2 GB"),
+    ("4G", "4 GB"),
+];
+
+glib::wrapper! {
+    /// Platform security page.
+    pub struct PlatformPage(ObjectSubclass<imp::PlatformPage>)
+        @extends gtk4::Box, gtk4::Widget,
+        @implements gtk4::Orientable;
+}
+
+impl PlatformPage {
+    /// Create a new platform security page.
+    pub fn new() -> Self {
+        let page: Self = glib::Object::new();
+        page.setup_ui();
+        page
+    }
+
+    /// Setup the UI.
+    fn setup_ui(&self) {
+        let imp = self.imp();
+
+        self.set_orientation(gtk4::Orientation::Vertical);
+        self.set_spacing(0);
+
+        // Header with refresh button
+        let header_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(12)
+            .build();
+        density::track(&header_box, Role::PageHeader);
+
+        let title_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(4)
+            .hexpand(true)
+            .build();
+
+        let title = gtk4::Label::builder()
+            .label(gettext("Platform Security"))
+            .css_classes(vec!["title-1".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        let subtitle = gtk4::Label::builder()
+            .label(gettext("System settings that other protections rely on"))
+            .css_classes(vec!["dim-label".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        title_box.append(&title);
+        title_box.append(&subtitle);
+
+        let refresh_button = gtk4::Button::builder()
+            .icon_name("view-refresh-symbolic")
+            .css_classes(vec!["flat".to_string()])
+            .tooltip_text(gettext("Refresh"))
+            .valign(gtk4::Align::Center)
+            .build();
+        let page = self.clone();
+        refresh_button.connect_clicked(move |_| {
+            page.refresh();
+        });
+
+        header_box.append(&title_box);
+        header_box.append(&refresh_button);
+        self.append(&header_box);
+
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .vscrollbar_policy(gtk4::PolicyType::Automatic)
+            .vexpand(true)
+            .hexpand(true)
+            .build();
+
+        let content = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .hexpand(true)
+            .build();
+        density::track(
+            &content,
+            Role::Content {
+                margin: 24,
+                spacing: 24,
+            },
+        );
+
+        // Hardening score
+        let score_group = adw::PreferencesGroup::new();
+        let score_row = adw::ActionRow::builder()
+            .title(gettext("Hardening Score"))
+            .subtitle(gettext("Checking…"))
+            .build();
+        let score_bar = gtk4::LevelBar::builder()
+            .min_value(0.0)
+            .max_value(100.0)
+            .width_request(120)
+            .valign(gtk4::Align::Center)
+            .build();
+        let score_label = gtk4::Label::builder()
+            .css_classes(vec!["title-3".to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+        score_row.add_suffix(&score_bar);
+        score_row.add_suffix(&score_label);
+        score_group.add(&score_row);
+        content.append(&score_group);
+        imp.score_row.replace(Some(score_row));
+        imp.score_bar.replace(Some(score_bar));
+        imp.score_label.replace(Some(score_label));
+
+        // Time synchronization
+        content.append(&Self::create_section_header(
+            "preferences-system-time-symbolic",
+            &gettext("Time Synchronization"),
+        ));
+        let time_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "An accurate clock is needed to validate TLS certificates and to keep logs trustworthy",
+            ))
+            .build();
+        imp.time_group.replace(Some(time_group.clone()));
+        content.append(&time_group);
+
+        // TPM and measured boot
+        content.append(&Self::create_section_header(
+            "channel-secure-symbolic",
+            &gettext("TPM and Measured Boot"),
+        ));
+        let tpm_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "A TPM records what was loaded during boot, so tampering with firmware, boot loader or kernel can be detected",
+            ))
+            .build();
+        imp.tpm_group.replace(Some(tpm_group.clone()));
+        content.append(&tpm_group);
+
+        // FIDO2/U2F security keys
+        content.append(&Self::create_section_header(
+            "auth-fingerprint-symbolic",
+            &gettext("Security Keys"),
+        ));
+        let keys_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "FIDO2 and U2F keys sign you in with a touch; taking the key along can lock the computer behind you",
+            ))
+            .build();
+        let lock_row = adw::SwitchRow::builder()
+            .title(gettext("Lock When Key Is Removed"))
+            .subtitle(gettext(
+                "Lock the session as soon as an attached security key is pulled out; the background monitor keeps doing so while the app is closed",
+            ))
+            .active(crate::config::Settings::new().lock_on_key_removal())
+            .build();
+        let page = self.clone();
+        lock_row.connect_active_notify(move |row| {
+            crate::config::Settings::new().set_lock_on_key_removal(row.is_active());
+            if let Some(window) = page.root().and_downcast::<super::MainWindow>() {
+                window.set_lock_on_key_removal(row.is_active());
+            }
+            gtk4::gio::spawn_blocking(crate::monitor::reload);
+        });
+        keys_group.add(&lock_row);
+        imp.keys_group.replace(Some(keys_group.clone()));
+        content.append(&keys_group);
+
+        // Bootloader and kernel command line findings
+        content.append(&Self::create_section_header(
+            "system-reboot-symbolic",
+            &gettext("Boot Security"),
+        ));
+        let boot_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "Bootloader protection and kernel parameters that weaken security",
+            ))
+            .build();
+        imp.boot_group.replace(Some(boot_group.clone()));
+        content.append(&boot_group);
+
+        // SELinux or AppArmor
+        content.append(&Self::create_section_header(
+            "security-high-symbolic",
+            &gettext("Mandatory Access Control"),
+        ));
+        let mac_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "SELinux or AppArmor confine programs to what their policy allows, even when they run as root",
+            ))
+            .build();
+        imp.mac_group.replace(Some(mac_group.clone()));
+        content.append(&mac_group);
+
+        // Tor and proxies
+        content.append(&Self::create_section_header(
+            "network-vpn-symbolic",
+            &gettext("Tor and Proxies"),
+        ));
+        let proxy_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "Proxies see the traffic sent through them; one set where none belongs can intercept everything",
+            ))
+            .build();
+        imp.proxy_group.replace(Some(proxy_group.clone()));
+        content.append(&proxy_group);
+
+        // Core dumps
+        content.append(&Self::create_section_header(
+            "dialog-warning-symbolic",
+            &gettext("Core Dumps"),
+        ));
+        let coredump_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "Memory of crashing programs can contain passwords, keys and documents",
+            ))
+            .build();
+        let restrict_row = adw::SwitchRow::builder()
+            .title(gettext("Restrict Core Dumps"))
+            .subtitle(gettext(
+                "Do not store crash dumps and stop set-UID programs from dumping core",
+            ))
+            .sensitive(false)
+            .build();
+        let page = self.clone();
+        restrict_row.connect_active_notify(move |row| {
+            if page.imp().updating_coredump.get() {
+                return;
+            }
+            page.set_coredump_restricted(row.is_active());
+        });
+        coredump_group.add(&restrict_row);
+        imp.restrict_row.replace(Some(restrict_row));
+        imp.coredump_group.replace(Some(coredump_group.clone()));
+        content.append(&coredump_group);
+
+        // Logging
+        content.append(&Self::create_section_header(
+            "document-open-recent-symbolic",
+            &gettext("Logging"),
+        ));
+        let logging_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "Incident response depends on logs that survive reboots and are kept long enough",
+            ))
+            .build();
+
+        let apply_button = gtk4::Button::builder()
+            .label(gettext("Apply"))
+            .css_classes(vec!["suggested-action".to_string()])
+            .valign(gtk4::Align::Center)
+            .sensitive(false)
+            .build();
+        let page = self.clone();
+        apply_button.connect_clicked(move |btn| {
+            btn.set_sensitive(false);
+            page.apply_logging();
+        });
+        logging_group.set_header_suffix(Some(&apply_button));
+        imp.logging_apply.replace(Some(apply_button));
+
+        let journal_row = adw::ActionRow::builder().title(gettext("Journal")).build();
+        logging_group.add(&journal_row);
+        imp.journal_row.replace(Some(journal_row));
+
+        let storage_labels: Vec<String> = JOURNAL_STORAGE.iter().map(|(_, l)| gettext(l)).collect();
+        let storage_refs: Vec<&str> = storage_labels.iter().map(|s| s.as_str()).collect();
+        let storage_row = adw::ComboRow::builder()
+            .title(gettext("Storage"))
+            .subtitle(gettext("Persistent keeps logs on disk across reboots"))
+            .model(&gtk4::StringList::new(&storage_refs))
+            .build();
+        let page = self.clone();
+        storage_row.connect_selected_notify(move |_| {
+            page.update_logging_apply();
+        });
+        logging_group.add(&storage_row);
+        imp.storage_row.replace(Some(storage_row));
+
+        let size_row = adw::ComboRow::builder()
+            .title(gettext("Maximum Disk Usage"))
+            .subtitle(gettext(
+                "Older entries are removed when the journal reaches this size",
+            ))
+            .build();
+        let page = self.clone();
+        size_row.connect_selected_notify(move |_| {
+            page.update_logging_apply();
+        });
+        logging_group.add(&size_row);
+        imp.size_row.replace(Some(size_row));
+
+        let auditd_row = adw::ActionRow::builder()
+            .title(gettext("Audit Daemon (auditd)"))
+            .build();
+        let auditd_button = gtk4::Button::builder()
+            .label(gettext("Enable"))
+            .tooltip_text(gettext("Start auditd and enable it at boot"))
+            .valign(gtk4::Align::Center)
+            .visible(false)
+            .build();
+        let page = self.clone();
+        auditd_button.connect_clicked(move |btn| {
+            btn.set_sensitive(false);
+            page.enable_auditd(btn.clone());
+        });
+        auditd_row.add_suffix(&auditd_button);
+        logging_group.add(&auditd_row);
+        imp.auditd_row.replace(Some(auditd_row));
+        imp.auditd_button.replace(Some(auditd_button));
+
+        content.append(&logging_group);
+
+        // Compliance profiles
+        content.append(&Self::create_section_header(
+            "emblem-documents-symbolic",
+            &gettext("Security Benchmarks"),
+        ));
+        let compliance_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "Check this system against a security benchmark such as CIS with OpenSCAP",
+            ))
+            .build();
+
+        let scan_button = gtk4::Button::builder()
+            .label(gettext("Run Scan"))
+            .css_classes(vec!["suggested-action".to_string()])
+            .valign(gtk4::Align::Center)
+            .sensitive(false)
+            .build();
+        let page = self.clone();
+        scan_button.connect_clicked(move |_| {
+            page.run_compliance_scan();
+        });
+        compliance_group.set_header_suffix(Some(&scan_button));
+        imp.scan_button.replace(Some(scan_button));
+
+        let profile_row = adw::ComboRow::builder()
+            .title(gettext("Profile"))
+            .visible(false)
+            .build();
+        compliance_group.add(&profile_row);
+        imp.profile_row.replace(Some(profile_row));
+
+        let compliance_row = adw::ActionRow::builder()
+            .title(gettext("OpenSCAP"))
+            .subtitle(gettext("Checking…"))
+            .build();
+        let report_button = gtk4::Button::builder()
+            .label(gettext("Results"))
+            .valign(gtk4::Align::Center)
+            .visible(false)
+            .build();
+        let page = self.clone();
+        report_button.connect_clicked(move |_| {
+            let report = page.imp().compliance_report.borrow().clone();
+            if let Some(report) = report {
+                page.present_compliance_report(&report);
+            }
+        });
+        compliance_row.add_suffix(&report_button);
+        compliance_group.add(&compliance_row);
+        imp.compliance_row.replace(Some(compliance_row));
+        imp.report_button.replace(Some(report_button));
+
+        content.append(&compliance_group);
+
+        scrolled.set_child(Some(&content));
+        self.append(&scrolled);
+    }
+
+    /// Reload every section.
+    pub fn refresh(&self) {
+        self.refresh_time_sync();
+        self.refresh_tpm();
+        self.refresh_security_keys();
+        self.refresh_mandatory_access();
+        self.refresh_assessment();
+        self.refresh_proxies();
+        self.refresh_coredump();
+        self.refresh_logging();
+        self.refresh_compliance();
+    }
+
+    /// Query time synchronization state in the background.
+    fn refresh_time_sync(&self) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(query_time_sync).await;
+            match result {
+                Ok(Ok(status)) => page.display_time_sync(&status),
+                Ok(Err(e)) => {
+                    tracing::warn!("Failed to query time synchronization: {}", e);
+                    page.display_time_sync_error();
+                }
+                Err(_) => page.display_time_sync_error(),
+            }
+        });
+    }
+
+    /// Rebuild the time synchronization rows.
+    fn display_time_sync(&self, status: &TimeSyncStatus) {
+        let imp = self.imp();
+        let Some(group) = imp.time_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.time_rows.take() {
+            group.remove(&row);
+        }
+        let mut rows: Vec<gtk4::Widget> = Vec::new();
+
+        // Overall state
+        let (title, icon, css) = if status.synchronized {
+            (
+                gettext("Clock synchronized"),
+                "emblem-ok-symbolic",
+                "success",
+            )
+        } else {
+            (
+                gettext("Clock not synchronized"),
+                "dialog-warning-symbolic",
+                "warning",
+            )
+        };
+        let subtitle = match status.daemon {
+            TimeDaemon::None => gettext("No time synchronization service is running"),
+            daemon if !status.ntp_enabled => {
+                gettext("%s is running but network time is switched off")
+                    .replace("%s", daemon.label())
+            }
+            daemon => gettext("Using %s").replace("%s", daemon.label()),
+        };
+        let state_row = adw::ActionRow::builder()
+            .title(title)
+            .subtitle(subtitle)
+            .build();
+        let state_icon = gtk4::Image::from_icon_name(icon);
+        state_icon.add_css_class(css);
+        state_row.add_prefix(&state_icon);
+
+        if status.needs_quick_fix() {
+            let fix_button = gtk4::Button::builder()
+                .label(gettext("Enable"))
+                .tooltip_text(gettext("Start systemd-timesyncd and enable it at boot"))
+                .css_classes(vec!["suggested-action".to_string()])
+                .valign(gtk4::Align::Center)
+                .build();
+            let page = self.clone();
+            fix_button.connect_clicked(move |btn| {
+                btn.set_sensitive(false);
+                page.enable_time_sync(btn.clone());
+            });
+            state_row.add_suffix(&fix_button);
+        }
+        rows.push(state_row.upcast());
+
+        // Network Time Security
+        let (nts_subtitle, nts_icon) = if status.nts_in_use() {
+            (
+                gettext("Time sources are authenticated with NTS"),
+                "security-high-symbolic",
+            )
+        } else if status.daemon.supports_nts() {
+            (
+                gettext("Not in use: add the \"nts\" option to server lines in the chrony configuration"),
+                "security-medium-symbolic",
+            )
+        } else {
+            (
+                gettext("Not supported by the current time service; chrony supports NTS"),
+                "security-medium-symbolic",
+            )
+        };
+        let nts_row = adw::ActionRow::builder()
+            .title(gettext("Network Time Security (NTS)"))
+            .subtitle(nts_subtitle)
+            .build();
+        nts_row.add_prefix(&gtk4::Image::from_icon_name(nts_icon));
+        rows.push(nts_row.upcast());
+
+        // Configured servers
+        if !status.servers.is_empty() {
+            let servers_row = adw::ExpanderRow::builder()
+                .title(gettext("Time Servers"))
+                .subtitle(gettext("%d configured").replace("%d", &status.servers.len().to_string()))
+                .build();
+            servers_row.add_prefix(&gtk4::Image::from_icon_name("network-server-symbolic"));
+            for server in &status.servers {
+                let mut tags = Vec::new();
+                if status.current_server.as_deref() == Some(server.host.as_str()) {
+                    tags.push(gettext("In use"));
+                }
+                if server.nts {
+                    tags.push("NTS".to_string());
+                }
+                let row = adw::ActionRow::builder()
+                    .title(glib::markup_escape_text(&server.host).as_str())
+                    .subtitle(tags.join(" • "))
+                    .build();
+                servers_row.add_row(&row);
+            }
+            rows.push(servers_row.upcast());
+        }
+
+        for row in &rows {
+            group.add(row);
+        }
+        imp.time_rows.replace(rows);
+    }
+
+    /// Show a single row explaining that the state is unknown.
+    fn display_time_sync_error(&self) {
+        let imp = self.imp();
+        let Some(group) = imp.time_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.time_rows.take() {
+            group.remove(&row);
+        }
+        let row = adw::ActionRow::builder()
+            .title(gettext("Time synchronization status unavailable"))
+            .subtitle(gettext("Could not reach timedated on the system bus"))
+            .build();
+        row.add_prefix(&gtk4::Image::from_icon_name("dialog-question-symbolic"));
+        group.add(&row);
+        imp.time_rows.replace(vec![row.upcast()]);
+    }
+
+    /// Run the timesyncd quick fix.
+    fn enable_time_sync(&self, btn: gtk4::Button) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result =
+                gtk4::gio::spawn_blocking(|| OperationRunner::new().run(Operation::EnableTimeSync))
+                    .await;
+            match result {
+                Ok(result) => {
+                    page.show_toast(&result.message);
+                    if result.success {
+                        page.refresh_time_sync();
+                    } else {
+                        btn.set_sensitive(true);
+                    }
+                }
+                Err(_) => {
+                    page.show_toast(&gettext("Failed to enable time synchronization"));
+                    btn.set_sensitive(true);
+                }
+            }
+        });
+    }
+
+    /// Read TPM state in the background.
+    fn refresh_tpm(&self) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            if let Ok(status) = gtk4::gio::spawn_blocking(query_tpm).await {
+                page.display_tpm(&status);
+            }
+        });
+    }
+
+    /// Rebuild the TPM rows.
+    fn display_tpm(&self, status: &TpmStatus) {
+        let imp = self.imp();
+        let Some(group) = imp.tpm_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.tpm_rows.take() {
+            group.remove(&row);
+        }
+        let mut rows: Vec<gtk4::Widget> = Vec::new();
+
+        // TPM presence
+        let tpm_row = adw::ActionRow::new();
+        match &status.device {
+            Some(device) => {
+                tpm_row.set_title(&match device.version {
+                    Some(version) => gettext("TPM %d present").replace("%d", &version.to_string()),
+                    None => gettext("TPM present"),
+                });
+                let mut subtitle = if device.is_tpm2() {
+                    gettext("Can seal disk encryption keys and attest to the boot state")
+                } else {
+                    gettext("TPM 1.2 is outdated; many tools require TPM 2.0")
+                };
+                if let Some(description) = &device.description {
+                    subtitle = format!("{} • {}", description, subtitle);
+                }
+                tpm_row.set_subtitle(&glib::markup_escape_text(&subtitle));
+                let icon = gtk4::Image::from_icon_name(if device.is_tpm2() {
+                    "emblem-ok-symbolic"
+                } else {
+                    "dialog-warning-symbolic"
+                });
+                icon.add_css_class(if device.is_tpm2() {
+                    "success"
+                } else {
+                    "warning"
+                });
+                tpm_row.add_prefix(&icon);
+
+                let pcr_button = gtk4::Button::builder()
+                    .label(gettext("View PCR Banks"))
+                    .valign(gtk4::Align::Center)
+                    .build();
+                let page = self.clone();
+                pcr_button.connect_clicked(move |_| {
+                    page.show_pcr_banks();
+                });
+                tpm_row.add_suffix(&pcr_button);
+            }
+            None => {
+                tpm_row.set_title(&gettext("No TPM detected"));
+                tpm_row.set_subtitle(&gettext(
+                    "The TPM may be missing or disabled in the firmware settings",
+                ));
+                tpm_row.add_prefix(&gtk4::Image::from_icon_name("dialog-information-symbolic"));
+            }
+        }
+        rows.push(tpm_row.upcast());
+
+        // Measured boot
+        let (title, subtitle, icon) = if status.measured_boot() {
+            (
+                gettext("Measured boot active"),
+                gettext("The firmware recorded the boot chain in the TPM event log"),
+                "security-high-symbolic",
+            )
+        } else {
+            (
+                gettext("No boot measurements"),
+                gettext("No TPM event log was found, so the boot chain cannot be verified"),
+                "security-medium-symbolic",
+            )
+        };
+        let boot_row = adw::ActionRow::builder()
+            .title(title)
+            .subtitle(subtitle)
+            .build();
+        boot_row.add_prefix(&gtk4::Image::from_icon_name(icon));
+        rows.push(boot_row.upcast());
+
+        // Integrity Measurement Architecture
+        let ima_subtitle = match (status.ima_active, status.ima_options.is_empty()) {
+            (true, false) => gettext("Active with %s").replace("%s", &status.ima_options.join(" ")),
+            (true, true) => {
+                gettext("Available in the kernel but no policy is set on the kernel command line")
+            }
+            (false, _) => gettext("Not active: files run after boot are not measured"),
+        };
+        let ima_row = adw::ActionRow::builder()
+            .title(gettext("Integrity Measurement Architecture (IMA)"))
+            .subtitle(glib::markup_escape_text(&ima_subtitle).as_str())
+            .build();
+        ima_row.add_prefix(&gtk4::Image::from_icon_name(
+            if status.ima_active && !status.ima_options.is_empty() {
+                "security-high-symbolic"
+            } else {
+                "dialog-information-symbolic"
+            },
+        ));
+        rows.push(ima_row.upcast());
+
+        for row in &rows {
+            group.add(row);
+        }
+        imp.tpm_rows.replace(rows);
+    }
+
+    /// List the attached security keys in the background.
+    fn refresh_security_keys(&self) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            if let Ok(keys) = gtk4::gio::spawn_blocking(query_security_keys).await {
+                page.display_security_keys(&keys);
+            }
+        });
+    }
+
+    /// Rebuild the rows of the attached keys, above the lock switch.
+    fn display_security_keys(&self, keys: &[SecurityKey]) {
+        let imp = self.imp();
+        let Some(group) = imp.keys_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.key_rows.take() {
+            group.remove(&row);
+        }
+        let mut rows: Vec<gtk4::Widget> = Vec::new();
+        for key in keys {
+            let row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&key.name).as_str())
+                .subtitle(key.device.as_str())
+                .build();
+            let icon = gtk4::Image::from_icon_name("emblem-ok-symbolic");
+            icon.add_css_class("success");
+            row.add_prefix(&icon);
+            rows.push(row.upcast());
+        }
+        if keys.is_empty() {
+            let row = adw::ActionRow::builder()
+                .title(gettext("No security key attached"))
+                .subtitle(gettext("Plug in a FIDO2 or U2F key to see it here"))
+                .build();
+            row.add_prefix(&gtk4::Image::from_icon_name("dialog-information-symbolic"));
+            rows.push(row.upcast());
+        }
+        for row in &rows {
+            group.add(row);
+        }
+        imp.key_rows.replace(rows);
+    }
+
+    /// Read the PCR banks and show them in a dialog.
+    fn show_pcr_banks(&self) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            if let Ok(banks) = gtk4::gio::spawn_blocking(read_pcr_banks).await {
+                page.present_pcr_dialog(&banks);
+            }
+        });
+    }
+
+    /// Present a dialog listing every PCR of every bank.
+    fn present_pcr_dialog(&self, banks: &[PcrBank]) {
+        let dialog = adw::Dialog::builder()
+            .title(gettext("PCR Banks"))
+            .content_width(640)
+            .content_height(560)
+            .build();
+
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&adw::HeaderBar::new());
+
+        let prefs = adw::PreferencesPage::new();
+        if banks.is_empty() {
+            let group = adw::PreferencesGroup::builder()
+                .description(gettext(
+                    "This kernel does not expose PCR values in sysfs (Linux 5.12 or newer is required)",
+                ))
+                .build();
+            prefs.add(&group);
+        }
+        for bank in banks {
+            let group = adw::PreferencesGroup::builder()
+                .title(bank.algorithm.to_uppercase())
+                .build();
+            for (index, value) in &bank.values {
+                let mut subtitle = gettext(pcr_purpose(*index));
+                if pcr_is_unused(value) {
+                    subtitle = format!("{} • {}", subtitle, gettext("Not extended"));
+                }
+                let row = adw::ActionRow::builder()
+                    .title(format!("PCR {}", index))
+                    .subtitle(subtitle)
+                    .build();
+                let value_label = gtk4::Label::builder()
+                    .label(value)
+                    .css_classes(vec!["monospace".to_string(), "caption".to_string()])
+                    .selectable(true)
+                    .wrap(true)
+                    .wrap_mode(gtk4::pango::WrapMode::Char)
+                    .max_width_chars(32)
+                    .xalign(1.0)
+                    .build();
+                row.add_suffix(&value_label);
+                group.add(&row);
+            }
+            prefs.add(&group);
+        }
+
+        toolbar.set_content(Some(&prefs));
+        dialog.set_child(Some(&toolbar));
+        dialog.present(Some(self));
+    }
+
+    /// Run the assessment checks in the background.
+    fn refresh_assessment(&self) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            if let Ok(assessment) = gtk4::gio::spawn_blocking(Assessment::run).await {
+                page.display_findings(&assessment);
+            }
+        });
+    }
+
+    /// Rebuild the assessment findings rows and the score.
+    fn display_findings(&self, assessment: &Assessment) {
+        let imp = self.imp();
+        self.display_score(assessment);
+        let Some(group) = imp.boot_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.boot_rows.take() {
+            group.remove(&row);
+        }
+
+        let findings = assessment.in_category(FindingCategory::Boot);
+        let mut rows: Vec<gtk4::Widget> = Vec::new();
+        if findings.is_empty() {
+            let row = adw::ActionRow::builder()
+                .title(gettext("No issues found"))
+                .subtitle(gettext(
+                    "The bootloader is protected and no risky kernel parameters are set",
+                ))
+                .build();
+            let icon = gtk4::Image::from_icon_name("emblem-ok-symbolic");
+            icon.add_css_class("success");
+            row.add_prefix(&icon);
+            rows.push(row.upcast());
+        }
+        for finding in findings {
+            rows.push(Self::create_finding_row(finding).upcast());
+        }
+
+        for row in &rows {
+            group.add(row);
+        }
+        imp.boot_rows.replace(rows);
+    }
+
+    /// Show the hardening score summarising all findings.
+    fn display_score(&self, assessment: &Assessment) {
+        let imp = self.imp();
+        let score = assessment.score();
+        if let Some(label) = imp.score_label.borrow().as_ref() {
+            label.set_label(&format!("{} / 100", score));
+        }
+        if let Some(bar) = imp.score_bar.borrow().as_ref() {
+            bar.set_value(score as f64);
+        }
+        if let Some(row) = imp.score_row.borrow().as_ref() {
+            let count = assessment.findings.len();
+            row.set_subtitle(&if count == 0 {
+                gettext("No issues found")
+            } else {
+                gettext("%d findings, see the sections below").replace("%d", &count.to_string())
+            });
+        }
+    }
+
+    /// Create an expandable row showing a finding and how to fix it.
+    fn create_finding_row(finding: &Finding) -> adw::ExpanderRow {
+        let row = adw::ExpanderRow::builder()
+            .title(glib::markup_escape_text(&finding.title).as_str())
+            .subtitle(gettext(finding.severity.label()))
+            .build();
+        let icon = gtk4::Image::from_icon_name(finding.severity.icon());
+        icon.add_css_class(finding.severity.css_class());
+        row.add_prefix(&icon);
+
+        for (label, text) in [
+            (gettext("Details"), &finding.detail),
+            (gettext("Remediation"), &finding.remediation),
+        ] {
+            let text_row = adw::ActionRow::builder()
+                .title(label)
+                .subtitle(glib::markup_escape_text(text).as_str())
+                .subtitle_selectable(true)
+                .build();
+            row.add_row(&text_row);
+        }
+        row
+    }
+
+    /// Read the SELinux or AppArmor state and recent denials in the
+    /// background.
+    fn refresh_mandatory_access(&self) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            if let Ok(status) = gtk4::gio::spawn_blocking(query_mandatory_access).await {
+                page.display_mandatory_access(&status);
+            }
+        });
+    }
+
+    /// Rebuild the mandatory access control rows.
+    fn display_mandatory_access(&self, status: &MandatoryAccess) {
+        let imp = self.imp();
+        let Some(group) = imp.mac_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.mac_rows.take() {
+            group.remove(&row);
+        }
+        let mut rows: Vec<gtk4::Widget> = Vec::new();
+
+        match &status.system {
+            MacSystem::Selinux(selinux) => {
+                rows.push(self.create_selinux_row(selinux).upcast());
+            }
+            MacSystem::AppArmor(apparmor) => {
+                let subtitle = match (apparmor.enforce, apparmor.complain) {
+                    (Some(enforce), Some(complain)) => {
+                        gettext("%e profiles enforced, %c in complain mode")
+                            .replace("%e", &enforce.to_string())
+                            .replace("%c", &complain.to_string())
+                    }
+                    _ => gettext("Profile counts are only visible to root"),
+                };
+                let row = adw::ActionRow::builder()
+                    .title(gettext("AppArmor is active"))
+                    .subtitle(subtitle)
+                    .build();
+                row.add_prefix(&Self::state_icon(true, "warning"));
+                rows.push(row.upcast());
+            }
+            MacSystem::None => {
+                let row = adw::ActionRow::builder()
+                    .title(gettext("No mandatory access control"))
+                    .subtitle(gettext(
+                        "Neither SELinux nor AppArmor is enabled; programs are limited only by \
+                         file permissions",
+                    ))
+                    .build();
+                row.add_prefix(&Self::state_icon(false, "warning"));
+                rows.push(row.upcast());
+            }
+        }
+
+        if status.system != MacSystem::None {
+            rows.push(Self::create_denials_row(&status.denials).upcast());
+        }
+
+        for row in &rows {
+            group.add(row);
+        }
+        imp.mac_rows.replace(rows);
+    }
+
+    fn create_selinux_row(&self, selinux: &SelinuxStatus) -> adw::SwitchRow {
+        let mut details = Vec::new();
+        if let Some(policy) = &selinux.policy {
+            details.push(gettext("%s policy").replace("%s", policy));
+        }
+        match selinux.boot_mode.as_deref() {
+            Some("enforcing") if !selinux.enforcing => {
+                details.push(gettext("enforcing again after a reboot"))
+            }
+            Some("permissive") => details.push(gettext("permissive at boot")),
+            _ => {}
+        }
+        let subtitle = if selinux.enforcing {
+            gettext("Enforcing: accesses the policy does not allow are denied")
+        } else {
+            gettext("Permissive: accesses the policy does not allow are only logged")
+        };
+        let subtitle = if details.is_empty() {
+            subtitle
+        } else {
+            format!("{} · {}", subtitle, details.join(" · "))
+        };
+
+        let row = adw::SwitchRow::builder()
+            .title(gettext("SELinux Enforcing"))
+            .subtitle(subtitle)
+            .active(selinux.enforcing)
+            .build();
+        row.add_prefix(&Self::state_icon(selinux.enforcing, "warning"));
+        let page = self.clone();
+        row.connect_active_notify(move |row| {
+            if page.imp().updating_selinux.get() {
+                return;
+            }
+            if row.is_active() {
+                page.set_selinux_enforcing(true);
+            } else {
+                page.confirm_selinux_permissive(row);
+            }
+        });
+        row
+    }
+
+    fn create_denials_row(denials: &[MacDenial]) -> adw::ExpanderRow {
+        let total: usize = denials.iter().map(|denial| denial.count).sum();
+        let row = adw::ExpanderRow::builder()
+            .title(gettext("Recent Denials"))
+            .subtitle(if denials.is_empty() {
+                gettext(
+                    "None in the last 7 days. Reading them requires membership in the wheel, \
+                     adm or systemd-journal group.",
+                )
+            } else {
+                gettext("%d in the last 7 days").replace("%d", &total.to_string())
+            })
+            .enable_expansion(!denials.is_empty())
+            .build();
+        row.add_prefix(&Self::state_icon(denials.is_empty(), "warning"));
+
+        for denial in denials {
+            let title = match &denial.target {
+                Some(target) => format!("{}: {} {}", denial.process, denial.permission, target),
+                None => format!("{}: {}", denial.process, denial.permission),
+            };
+            let mut details = vec![denial.context.clone()];
+            if denial.permissive {
+                details.push(gettext("allowed (permissive)"));
+            }
+            if denial.count > 1 {
+                details.push(gettext("%d times").replace("%d", &denial.count.to_string()));
+            }
+            details.push(format_datetime(denial.last_seen as i64));
+            let denial_row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&title).as_str())
+                .subtitle(glib::markup_escape_text(&details.join(" · ")).as_str())
+                .build();
+            denial_row.add_css_class("property");
+            row.add_row(&denial_row);
+        }
+        row
+    }
+
+    /// Permissive mode turns SELinux protection off; ask first.
+    fn confirm_selinux_permissive(&self, row: &adw::SwitchRow) {
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("Switch SELinux to permissive mode?"))
+            .body(gettext(
+                "SELinux stops denying accesses its policy does not allow and only logs them, \
+                 until you switch it back or the system reboots. Use this to find out whether \
+                 SELinux is what blocks a program.",
+            ))
+            .build();
+        dialog.add_response("cancel", "_Cancel");
+        dialog.add_response("permissive", "_Switch to Permissive");
+        dialog.set_response_appearance("permissive", adw::ResponseAppearance::Destructive);
+        dialog.set_default_response(Some("cancel"));
+
+        let page = self.clone();
+        let row = row.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response == "permissive" {
+                page.set_selinux_enforcing(false);
+            } else {
+                page.imp().updating_selinux.set(true);
+                row.set_active(true);
+                page.imp().updating_selinux.set(false);
+            }
+        });
+
+        if let Some(root) = self.root() {
+            if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                dialog.present(Some(window));
+            }
+        }
+    }
+
+    /// Change the SELinux mode through the privileged helper.
+    fn set_selinux_enforcing(&self, enforcing: bool) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(move || set_selinux_enforcing(enforcing)).await;
+            match result {
+                Ok(Ok(message)) => page.show_toast(&message),
+                Ok(Err(e)) => page.show_toast(&format!(
+                    "{}: {}",
+                    gettext("Failed to change the SELinux mode"),
+                    e
+                )),
+                Err(_) => page.show_toast(&gettext("Failed to change the SELinux mode")),
+            }
+            // Reload either way so the switch reflects the real state
+            page.refresh_mandatory_access();
+        });
+    }
+
+    /// Detect Tor and the configured proxies in the background.
+    fn refresh_proxies(&self) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            if let Ok(status) = gtk4::gio::spawn_blocking(query_proxy_status).await {
+                page.display_proxies(&status);
+            }
+        });
+    }
+
+    /// Rebuild the Tor and proxy rows.
+    fn display_proxies(&self, status: &ProxyStatus) {
+        let imp = self.imp();
+        let Some(group) = imp.proxy_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.proxy_rows.take() {
+            group.remove(&row);
+        }
+        let mut rows: Vec<gtk4::Widget> = Vec::new();
+
+        // Proxies set where none belong come first
+        for setting in status.unexpected() {
+            let row = adw::ActionRow::builder()
+                .title(gettext("Unexpected system-wide proxy"))
+                .subtitle(
+                    glib::markup_escape_text(
+                        &gettext("%s sets %s for every user. Desktops and package managers keep their own proxy settings; malware sets one here to intercept traffic. Remove it unless you know who added it.")
+                            .replacen("%s", &setting.origin, 1)
+                            .replacen("%s", &setting.value, 1),
+                    )
+                    .as_str(),
+                )
+                .build();
+            row.add_css_class("error");
+            row.add_prefix(&Self::state_icon(false, "error"));
+            rows.push(row.upcast());
+        }
+
+        let tor_row = adw::ActionRow::builder().title(gettext("Tor")).build();
+        if status.tor.is_running() {
+            let socks: Vec<String> = status
+                .tor
+                .socks
+                .iter()
+                .map(|(addr, port)| std::net::SocketAddr::new(*addr, *port).to_string())
+                .collect();
+            tor_row.set_subtitle(&if socks.is_empty() {
+                gettext("Running")
+            } else {
+                gettext("Running, SOCKS proxy on %s").replace("%s", &socks.join(", "))
+            });
+            tor_row.add_prefix(&gtk4::Image::from_icon_name("emblem-ok-symbolic"));
+        } else {
+            tor_row.set_subtitle(&gettext("Not running"));
+            tor_row.add_prefix(&gtk4::Image::from_icon_name("network-offline-symbolic"));
+        }
+        rows.push(tor_row.upcast());
+
+        if status.settings.is_empty() {
+            let row = adw::ActionRow::builder()
+                .title(gettext("No proxy configured"))
+                .subtitle(gettext("Programs connect directly"))
+                .build();
+            rows.push(row.upcast());
+        } else {
+            let proxies_row = adw::ExpanderRow::builder()
+                .title(gettext("Configured Proxies"))
+                .subtitle(gettext("%d settings").replace("%d", &status.settings.len().to_string()))
+                .build();
+            for setting in &status.settings {
+                let scope = if setting.system_wide {
+                    gettext("every user")
+                } else {
+                    gettext("this user")
+                };
+                let row = adw::ActionRow::builder()
+                    .title(glib::markup_escape_text(&setting.value).as_str())
+                    .subtitle(
+                        glib::markup_escape_text(&format!(
+                            "{} • {} • {} ({})",
+                            gettext(setting.source.label()),
+                            setting.key,
+                            setting.origin,
+                            scope
+                        ))
+                        .as_str(),
+                    )
+                    .build();
+                row.add_prefix(&Self::state_icon(!setting.is_unexpected(), "error"));
+                proxies_row.add_row(&row);
+            }
+            rows.push(proxies_row.upcast());
+        }
+
+        let routed_row = adw::ExpanderRow::builder()
+            .title(gettext("Programs Using a Proxy"))
+            .build();
+        if status.routed.is_empty() {
+            routed_row.set_subtitle(&gettext("No program is connected to Tor or a proxy"));
+            routed_row.set_enable_expansion(false);
+        } else {
+            routed_row.set_subtitle(
+                &gettext("%d programs connected right now")
+                    .replace("%d", &status.routed.len().to_string()),
+            );
+            for program in &status.routed {
+                let row = adw::ActionRow::builder()
+                    .title(glib::markup_escape_text(&program.process).as_str())
+                    .subtitle(
+                        glib::markup_escape_text(
+                            &gettext("Through %s").replace("%s", &program.via),
+                        )
+                        .as_str(),
+                    )
+                    .build();
+                routed_row.add_row(&row);
+            }
+        }
+        rows.push(routed_row.upcast());
+
+        for row in &rows {
+            group.add(row);
+        }
+        imp.proxy_rows.replace(rows);
+    }
+
+    /// Read the core dump policy in the background.
+    fn refresh_coredump(&self) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            if let Ok(status) = gtk4::gio::spawn_blocking(query_coredump).await {
+                page.display_coredump(&status);
+            }
+        });
+    }
+
+    /// Rebuild the core dump rows.
+    fn display_coredump(&self, status: &CoredumpStatus) {
+        let imp = self.imp();
+        let Some(group) = imp.coredump_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.coredump_rows.take() {
+            group.remove(&row);
+        }
+
+        if let Some(row) = imp.restrict_row.borrow().as_ref() {
+            imp.updating_coredump.set(true);
+            row.set_active(status.restricted());
+            imp.updating_coredump.set(false);
+            row.set_sensitive(true);
+        }
+
+        let mut rows: Vec<gtk4::Widget> = Vec::new();
+
+        // Where dumps go
+        let (title, subtitle) = if !status.dumps_stored() {
+            (
+                gettext("Crash dumps are not stored"),
+                gettext("Crashing programs leave no memory image behind"),
+            )
+        } else if status.uses_systemd_coredump() && status.storage == "journal" {
+            (
+                gettext("Crash dumps are stored in the journal"),
+                gettext("Readable by anyone who can read the system journal"),
+            )
+        } else if status.uses_systemd_coredump() {
+            (
+                gettext("Crash dumps are stored on disk"),
+                gettext("%d dumps in /var/lib/systemd/coredump, readable by their owner and root")
+                    .replace("%d", &status.stored_dumps.to_string()),
+            )
+        } else if status.uses_other_handler() {
+            (
+                gettext("Crash dumps go to a crash reporter"),
+                status.core_pattern.trim_start_matches('|').to_string(),
+            )
+        } else {
+            (
+                gettext("Crash dumps are written as files"),
+                gettext("Saved as \"%s\", readable by the user the program ran as")
+                    .replace("%s", &status.core_pattern),
+            )
+        };
+        let storage_row = adw::ActionRow::builder()
+            .title(title)
+            .subtitle(glib::markup_escape_text(&subtitle).as_str())
+            .build();
+        storage_row.add_prefix(&Self::state_icon(!status.dumps_stored(), "warning"));
+        rows.push(storage_row.upcast());
+
+        // Set-UID programs
+        let (subtitle, ok) = match status.suid_dumpable {
+            Some(0) => (gettext("Never dump core"), true),
+            Some(2) => (gettext("Dump core readable by root only"), true),
+            Some(1) => (
+                gettext("Dump core readable by the calling user (insecure)"),
+                false,
+            ),
+            _ => (gettext("Unknown"), true),
+        };
+        let suid_row = adw::ActionRow::builder()
+            .title(gettext("Set-UID Programs"))
+            .subtitle(subtitle)
+            .build();
+        suid_row.add_prefix(&Self::state_icon(ok, "error"));
+        rows.push(suid_row.upcast());
+
+        // Size limit inherited by services
+        if let Some(limit) = status.service_core_limit {
+            let subtitle = match limit {
+                0 => gettext("Services cannot dump core"),
+                u64::MAX => gettext("Unlimited"),
+                n => format_bytes(n),
+            };
+            let limit_row = adw::ActionRow::builder()
+                .title(gettext("Service Core Size Limit"))
+                .subtitle(subtitle)
+                .build();
+            rows.push(limit_row.upcast());
+        }
+
+        for row in &rows {
+            group.add(row);
+        }
+        imp.coredump_rows.replace(rows);
+    }
+
+    /// A check mark for a good state, a warning icon otherwise.
+    fn state_icon(ok: bool, bad_class: &str) -> gtk4::Image {
+        let (icon_name, class) = if ok {
+            ("emblem-ok-symbolic", "success")
+        } else {
+            ("dialog-warning-symbolic", bad_class)
+        };
+        let icon = gtk4::Image::from_icon_name(icon_name);
+        icon.add_css_class(class);
+        icon
+    }
+
+    /// Apply or remove the core dump restrictions through the privileged helper.
+    fn set_coredump_restricted(&self, restrict: bool) {
+        if let Some(row) = self.imp().restrict_row.borrow().as_ref() {
+            row.set_sensitive(false);
+        }
+        let op = HelperOp::ConfigureCoredump { restrict };
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(move || run_privileged(&op)).await;
+            match result {
+                Ok(Ok(message)) => page.show_toast(&message),
+                Ok(Err(e)) => page.show_toast(&format!(
+                    "{}: {}",
+                    gettext("Failed to change core dump policy"),
+                    e
+                )),
+                Err(_) => page.show_toast(&gettext("Failed to change core dump policy")),
+            }
+            // Reload either way so the switch reflects the real state
+            page.refresh_coredump();
+            page.refresh_assessment();
+        });
+    }
+
+    /// Read the logging configuration in the background.
+    fn refresh_logging(&self) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            if let Ok(status) = gtk4::gio::spawn_blocking(query_logging).await {
+                page.display_logging(status);
+            }
+        });
+    }
+
+    /// Show the logging state and load it into the editable rows.
+    fn display_logging(&self, status: LoggingStatus) {
+        let imp = self.imp();
+
+        if let Some(row) = imp.journal_row.borrow().as_ref() {
+            if status.persistent {
+                row.set_title(&gettext("Journal kept on disk"));
+                row.set_subtitle(
+                    &gettext("Using %s").replace("%s", &format_bytes(status.journal_usage)),
+                );
+            } else {
+                row.set_title(&gettext("Journal kept in memory only"));
+                row.set_subtitle(&gettext("Logs are lost at every reboot"));
+            }
+        }
+
+        if let Some(row) = imp.storage_row.borrow().as_ref() {
+            let index = JOURNAL_STORAGE
+                .iter()
+                .position(|(v, _)| *v == status.storage)
+                .unwrap_or(0);
+            row.set_selected(index as u32);
+        }
+
+        // Presets, plus the current value when it is not one of them
+        let mut sizes: Vec<(String, String)> = JOURNAL_SIZES
+            .iter()
+            .map(|(v, l)| (v.to_string(), gettext(l)))
+            .collect();
+        let current = status.system_max_use.clone().unwrap_or_default();
+        if !sizes.iter().any(|(v, _)| *v == current) {
+            sizes.push((current.clone(), current.clone()));
+        }
+        if let Some(row) = imp.size_row.borrow().as_ref() {
+            let labels: Vec<&str> = sizes.iter().map(|(_, l)| l.as_str()).collect();
+            row.set_model(Some(&gtk4::StringList::new(&labels)));
+            let index = sizes.iter().position(|(v, _)| *v == current).unwrap_or(0);
+            row.set_selected(index as u32);
+        }
+        imp.journal_sizes
+            .replace(sizes.into_iter().map(|(v, _)| v).collect());
+
+        if let Some(row) = imp.auditd_row.borrow().as_ref() {
+            row.set_subtitle(&if status.auditd_running {
+                gettext("Running: security-relevant kernel events are recorded")
+            } else if status.auditd_installed {
+                gettext("Installed but not running")
+            } else {
+                gettext("Not installed: install the audit package to record kernel security events")
+            });
+        }
+        if let Some(button) = imp.auditd_button.borrow().as_ref() {
+            button.set_visible(status.auditd_installed && !status.auditd_running);
+            button.set_sensitive(true);
+        }
+
+        imp.logging_status.replace(Some(status));
+        self.update_logging_apply();
+    }
+
+    /// The storage and size currently selected in the rows.
+    fn selected_logging(&self) -> (String, Option<String>) {
+        let imp = self.imp();
+        let storage = imp
+            .storage_row
+            .borrow()
+            .as_ref()
+            .and_then(|row| JOURNAL_STORAGE.get(row.selected() as usize))
+            .map(|(v, _)| v.to_string())
+            .unwrap_or_else(|| "auto".to_string());
+        let size = imp
+            .size_row
+            .borrow()
+            .as_ref()
+            .and_then(|row| {
+                imp.journal_sizes
+                    .borrow()
+                    .get(row.selected() as usize)
+                    .cloned()
+            })
+            .filter(|v| !v.is_empty());
+        (storage, size)
+    }
+
+    /// Enable Apply only when the selection differs from the system state.
+    fn update_logging_apply(&self) {
+        let imp = self.imp();
+        let changed = imp.logging_status.borrow().as_ref().is_some_and(|status| {
+            let (storage, size) = self.selected_logging();
+            storage != status.storage || size != status.system_max_use
+        });
+        if let Some(button) = imp.logging_apply.borrow().as_ref() {
+            button.set_sensitive(changed);
+        }
+    }
+
+    /// Write the journald settings through the privileged helper.
+    fn apply_logging(&self) {
+        let (storage, system_max_use) = self.selected_logging();
+        let op = HelperOp::ConfigureJournald {
+            storage: Some(storage),
+            system_max_use,
+        };
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(move || run_privileged(&op)).await;
+            match result {
+                Ok(Ok(message)) => page.show_toast(&message),
+                Ok(Err(e)) => page.show_toast(&format!(
+                    "{}: {}",
+                    gettext("Failed to apply journal settings"),
+                    e
+                )),
+                Err(_) => page.show_toast(&gettext("Failed to apply journal settings")),
+            }
+            page.refresh_logging();
+        });
+    }
+
+    /// Start and enable auditd.
+    fn enable_auditd(&self, btn: gtk4::Button) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(|| {
+                let mut client = crate::systemd::SystemdClient::new();
+                client.connect()?;
+                client.start_service(AUDITD_UNIT)?;
+                client.enable_service(AUDITD_UNIT)
+            })
+            .await;
+            match result {
+                Ok(Ok(())) => {
+                    page.show_toast(&gettext("Audit daemon enabled"));
+                    page.refresh_logging();
+                }
+                Ok(Err(e)) => {
+                    page.show_toast(&format!("{}: {}", gettext("Failed to enable auditd"), e));
+                    btn.set_sensitive(true);
+                }
+                Err(_) => {
+                    page.show_toast(&gettext("Failed to enable auditd"));
+                    btn.set_sensitive(true);
+                }
+            }
+        });
+    }
+
+    /// Detect OpenSCAP and its profiles once; they only change when
+    /// packages are installed.
+    fn refresh_compliance(&self) {
+        if self.imp().compliance_status.borrow().is_some() {
+            return;
+        }
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            if let Ok(status) = gtk4::gio::spawn_blocking(query_compliance).await {
+                page.display_compliance(status);
+            }
+        });
+    }
+
+    fn display_compliance(&self, status: ComplianceStatus) {
+        let imp = self.imp();
+        let ready = status.datastream.is_some() && !status.profiles.is_empty();
+
+        if let Some(row) = imp.compliance_row.borrow().as_ref() {
+            if !status.oscap_installed {
+                row.set_subtitle(&gettext(
+                    "Not installed: install openscap-scanner and scap-security-guide to run compliance profiles",
+                ));
+            } else if !ready {
+                row.set_subtitle(&gettext(
+                    "No benchmark content found: install scap-security-guide",
+                ));
+            } else if imp.compliance_report.borrow().is_none() {
+                row.set_subtitle(&gettext("Not scanned yet"));
+            }
+        }
+        if let Some(row) = imp.profile_row.borrow().as_ref() {
+            let titles: Vec<&str> = status.profiles.iter().map(|p| p.title.as_str()).collect();
+            row.set_model(Some(&gtk4::StringList::new(&titles)));
+            row.set_subtitle(
+                &status
+                    .datastream
+                    .as_deref()
+                    .map(glib::markup_escape_text)
+                    .unwrap_or_default(),
+            );
+            row.set_visible(ready);
+        }
+        if let Some(button) = imp.scan_button.borrow().as_ref() {
+            button.set_sensitive(ready);
+        }
+        imp.compliance_status.replace(Some(status));
+    }
+
+    /// Evaluate the selected profile through the privileged helper.
+    fn run_compliance_scan(&self) {
+        let imp = self.imp();
+        let selected = imp.compliance_status.borrow().as_ref().and_then(|status| {
+            let index = imp.profile_row.borrow().as_ref()?.selected() as usize;
+            Some((
+                status.datastream.clone()?,
+                status.profiles.get(index)?.clone(),
+            ))
+        });
+        let Some((datastream, profile)) = selected else {
+            return;
+        };
+
+        if let Some(button) = imp.scan_button.borrow().as_ref() {
+            button.set_sensitive(false);
+        }
+        if let Some(row) = imp.compliance_row.borrow().as_ref() {
+            row.set_subtitle(&gettext("Scanning… this can take several minutes"));
+        }
+
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let profile_id = profile.id.clone();
+            let result =
+                gtk4::gio::spawn_blocking(move || run_compliance_scan(&datastream, &profile_id))
+                    .await;
+            let imp = page.imp();
+            if let Some(button) = imp.scan_button.borrow().as_ref() {
+                button.set_sensitive(true);
+            }
+            match result {
+                Ok(Ok(mut report)) => {
+                    report.profile = profile.title;
+                    page.display_compliance_summary(&report);
+                    page.present_compliance_report(&report);
+                    imp.compliance_report.replace(Some(report));
+                }
+                Ok(Err(e)) => {
+                    page.display_compliance_summary_error();
+                    page.show_toast(&format!("{}: {}", gettext("Compliance scan failed"), e));
+                }
+                Err(_) => {
+                    page.display_compliance_summary_error();
+                    page.show_toast(&gettext("Compliance scan failed"));
+                }
+            }
+        });
+    }
+
+    fn display_compliance_summary(&self, report: &ComplianceReport) {
+        let imp = self.imp();
+        if let Some(row) = imp.compliance_row.borrow().as_ref() {
+            let mut summary = gettext("%d passed, %d failed")
+                .replacen("%d", &report.count(RuleOutcome::Pass).to_string(), 1)
+                .replacen("%d", &report.failed().len().to_string(), 1);
+            if let Some(score) = report.score {
+                summary = format!(
+                    "{} • {} {}",
+                    summary,
+                    gettext("Score"),
+                    format_percent(score, 0)
+                );
+            }
+            row.set_title(&glib::markup_escape_text(&report.profile));
+            row.set_subtitle(&summary);
+        }
+        if let Some(button) = imp.report_button.borrow().as_ref() {
+            button.set_visible(true);
+        }
+    }
+
+    fn display_compliance_summary_error(&self) {
+        let imp = self.imp();
+        let report = imp.compliance_report.borrow().clone();
+        match report {
+            Some(report) => self.display_compliance_summary(&report),
+            None => {
+                if let Some(row) = imp.compliance_row.borrow().as_ref() {
+                    row.set_subtitle(&gettext("Not scanned yet"));
+                }
+            }
+        }
+    }
+
+    /// Show the failed rules of a scan, with a shortcut to the page that can
+    /// fix the ones this application manages.
+    fn present_compliance_report(&self, report: &ComplianceReport) {
+        let dialog = adw::Dialog::builder()
+            .title(gettext("Compliance Results"))
+            .content_width(560)
+            .content_height(640)
+            .build();
+
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&adw::HeaderBar::new());
+        let prefs_page = adw::PreferencesPage::new();
+
+        let summary_group = adw::PreferencesGroup::builder()
+            .title(glib::markup_escape_text(&report.profile).as_str())
+            .build();
+        if let Some(score) = report.score {
+            let score_row = adw::ActionRow::builder()
+                .title(gettext("Score"))
+                .subtitle(format_percent(score, 1))
+                .build();
+            summary_group.add(&score_row);
+        }
+        for (label, outcome) in [
+            ("Passed", RuleOutcome::Pass),
+            ("Failed", RuleOutcome::Fail),
+            ("Errors", RuleOutcome::Error),
+            ("Not Applicable", RuleOutcome::NotApplicable),
+            ("Not Checked", RuleOutcome::NotChecked),
+        ] {
+            let count = report.count(outcome);
+            if count == 0 && outcome != RuleOutcome::Fail {
+                continue;
+            }
+            let row = adw::ActionRow::builder().title(gettext(label)).build();
+            row.add_suffix(
+                &gtk4::Label::builder()
+                    .label(count.to_string())
+                    .css_classes(vec!["dim-label".to_string()])
+                    .build(),
+            );
+            summary_group.add(&row);
+        }
+        prefs_page.add(&summary_group);
+
+        let failed = report.failed();
+        let failed_group = adw::PreferencesGroup::builder()
+            .title(gettext("Failed Rules"))
+            .build();
+        if failed.is_empty() {
+            let row = adw::ActionRow::builder()
+                .title(gettext("Every checked rule passed"))
+                .build();
+            row.add_prefix(&gtk4::Image::from_icon_name("emblem-ok-symbolic"));
+            failed_group.add(&row);
+        }
+        for result in failed {
+            failed_group.add(&self.create_rule_row(result, &dialog));
+        }
+        prefs_page.add(&failed_group);
+
+        toolbar.set_content(Some(&prefs_page));
+        dialog.set_child(Some(&toolbar));
+        if let Some(root) = self.root() {
+            dialog.present(Some(&root));
+        }
+    }
+
+    fn create_rule_row(&self, result: &RuleResult, dialog: &adw::Dialog) -> adw::ActionRow {
+        let severity = match result.severity.as_str() {
+            "high" => gettext("High"),
+            "medium" => gettext("Medium"),
+            "low" => gettext("Low"),
+            _ => gettext("Unknown"),
+        };
+        let mut subtitle = format!("{} • {}", severity, result.short_id());
+        if result.outcome == RuleOutcome::Error {
+            subtitle = format!("{} • {}", subtitle, gettext("Could not be checked"));
+        }
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(&result.title).as_str())
+            .subtitle(glib::markup_escape_text(&subtitle).as_str())
+            .build();
+        let (icon, class) = match result.severity.as_str() {
+            "high" => ("dialog-error-symbolic", "error"),
+            "medium" => ("dialog-warning-symbolic", "warning"),
+            _ => ("dialog-information-symbolic", "dim-label"),
+        };
+        let image = gtk4::Image::from_icon_name(icon);
+        image.add_css_class(class);
+        row.add_prefix(&image);
+
+        if let Some(remediation) = result.remediation() {
+            let button = gtk4::Button::builder()
+                .label(gettext(remediation.label))
+                .valign(gtk4::Align::Center)
+                .build();
+            let page = self.clone();
+            let dialog = dialog.clone();
+            button.connect_clicked(move |_| {
+                dialog.close();
+                if let Some(window) = page
+                    .root()
+                    .and_downcast_ref::<gtk4::Window>()
+                    .and_then(|w| w.downcast_ref::<super::MainWindow>().cloned())
+                {
+                    window.show_page(remediation.page);
+                }
+            });
+            row.add_suffix(&button);
+        }
+        row
+    }
+
+    /// Show a toast on the main window.
+    fn show_toast(&self, message: &str) {
+        if let Some(root) = self.root() {
+            if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                if let Some(main_window) = window.downcast_ref::<super::MainWindow>() {
+                    main_window.show_toast(message);
+                }
+            }
+        }
+    }
+
+    /// Create a section header with icon on the left.
+    fn create_section_header(icon_name: &str, title: &str) -> gtk4::Box {
+        let header = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(8)
+            .margin_bottom(6)
+            .build();
+
+        let icon = gtk4::Image::builder()
+            .icon_name(icon_name)
+            .css_classes(vec!["heading".to_string()])
+            .build();
+
+        let label = gtk4::Label::builder()
+            .label(title)
+            .css_classes(vec!["heading".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        header.append(&icon);
+        header.append(&label);
+        header
+    }
+}
+
+impl Default for PlatformPage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct PlatformPage {
+        pub time_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub time_rows: RefCell<Vec<gtk4::Widget>>,
+        pub tpm_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub tpm_rows: RefCell<Vec<gtk4::Widget>>,
+        pub keys_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub key_rows: RefCell<Vec<gtk4::Widget>>,
+        pub boot_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub boot_rows: RefCell<Vec<gtk4::Widget>>,
+        pub mac_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub mac_rows: RefCell<Vec<gtk4::Widget>>,
+        /// Set while the SELinux switch is put back after a cancelled change.
+        pub updating_selinux: Cell<bool>,
+        pub score_row: RefCell<Option<adw::ActionRow>>,
+        pub score_bar: RefCell<Option<gtk4::LevelBar>>,
+        pub score_label: RefCell<Option<gtk4::Label>>,
+        pub proxy_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub proxy_rows: RefCell<Vec<gtk4::Widget>>,
+        pub coredump_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub coredump_rows: RefCell<Vec<gtk4::Widget>>,
+        pub restrict_row: RefCell<Option<adw::SwitchRow>>,
+        pub updating_coredump: Cell<bool>,
+        pub logging_apply: RefCell<Option<gtk4::Button>>,
+        pub journal_row: RefCell<Option<adw::ActionRow>>,
+        pub storage_row: RefCell<Option<adw::ComboRow>>,
+        pub size_row: RefCell<Option<adw::ComboRow>>,
+        pub journal_sizes: RefCell<Vec<String>>,
+        pub auditd_row: RefCell<Option<adw::ActionRow>>,
+        pub auditd_button: RefCell<Option<gtk4::Button>>,
+        pub logging_status: RefCell<Option<LoggingStatus>>,
+        pub scan_button: RefCell<Option<gtk4::Button>>,
+        pub profile_row: RefCell<Option<adw::ComboRow>>,
+        pub compliance_row: RefCell<Option<adw::ActionRow>>,
+        pub report_button: RefCell<Option<gtk4::Button>>,
+        pub compliance_status: RefCell<Option<ComplianceStatus>>,
+        pub compliance_report: RefCell<Option<ComplianceReport>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for PlatformPage {
+        const NAME: &'static str = "SecurityCenterPlatformPage";
+        type Type = super::PlatformPage;
+        type ParentType = gtk4::Box;
+    }
+
+    impl ObjectImpl for PlatformPage {}
+    impl WidgetImpl for PlatformPage {}
+    impl BoxImpl for PlatformPage {}
+}
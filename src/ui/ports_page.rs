@@ -4,19 +4,30 @@
 
 //! Ports management page.
 
-use std::cell::RefCell;
+use std::cell::{OnceCell, RefCell};
+use std::collections::HashSet;
 use std::rc::Rc;
 
-use gtk4::glib;
 use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
+use gtk4::{gio, glib};
 use libadwaita as adw;
 use libadwaita::prelude::*;
 
+use super::app_state::Invalidation;
+use super::density::{self, Role};
+use super::lockout_guard::{self, TrialChange};
+use super::widgets::Sparkline;
+use crate::admin::{probe_port, read_rule_counters, Expectation, RuleCounters, RuleProbe};
+use crate::firewall::journal::{self, InverseCall, RuleItem};
 use crate::firewall::FirewallClient;
-use crate::i18n::gettext;
-use crate::models::{ConsolidatedPort, Port};
-use crate::storage::{PortMetadata, PortStorage};
+use crate::i18n::{format_bytes, format_datetime, format_time, gettext};
+use crate::models::{
+    rebind_store, store_models, sync_store, ConsolidatedPort, ConsolidatedPortObject, Family,
+    ModelObject, Port, PortFilter, PortObject, RichRule, RuleAction, VerdictFilter,
+};
+use crate::stats::{RuleSample, RuleStats};
+use crate::storage::{ImportPlan, MetadataProfile, PortMetadata, PortStorage};
 use crate::validation::{format_port_spec, parse_port_spec, validate_port_name, validate_protocol};
 
 glib::wrapper! {
@@ -26,6 +37,9 @@ glib::wrapper! {
         @implements gtk4::Orientable;
 }
 
+/// Lifetimes offered for a new rule, in seconds; 0 keeps it until removed.
+const EXPIRY_CHOICES: [i64; 4] = [0, 60 * 60, 8 * 60 * 60, 24 * 60 * 60];
+
 /// Every block rich-rule string that could exist for a port/protocol, so we
 /// can remove a block regardless of how it was created: the current
 /// dual-stack (family-less) form, the legacy `family="ipv4"` form, the
@@ -40,20 +54,117 @@ fn block_rule_variants(port_spec: &str, proto: &str) -> Vec<String> {
 
     let mut rules = Vec::new();
     for spec in &specs {
-        for verb in ["reject", "drop"] {
-            rules.push(format!(
-                "rule port port=\"{}\" protocol=\"{}\" {}",
-                spec, proto, verb
-            ));
-            rules.push(format!(
-                "rule family=\"ipv4\" port port=\"{}\" protocol=\"{}\" {}",
-                spec, proto, verb
-            ));
+        for action in [RuleAction::Reject(None), RuleAction::Drop] {
+            for family in [None, Some(Family::Ipv4)] {
+                let rule = RichRule {
+                    family,
+                    action: Some(action.clone()),
+                    ..RichRule::port_block(spec, proto)
+                };
+                rules.push(rule.to_string());
+            }
         }
     }
     rules
 }
 
+/// Remove every rule a consolidated entry stands for, runtime and permanent,
+/// in all of its zones. Individual failures are ignored: a rule may exist in
+/// only one of the configurations.
+fn remove_port_rules(client: &FirewallClient, port: &ConsolidatedPort) {
+    let port_str = port.port_spec();
+
+    for zone in &port.zones {
+        // Remove the exact rich rules this entry was parsed from,
+        // so externally-created rules (any family or verb) match.
+        for rule in &port.raw_rules {
+            let _ = client.remove_rich_rule(zone, rule, false);
+            let _ = client.remove_rich_rule(zone, rule, true);
+        }
+
+        for protocol in &port.protocols {
+            // Remove from both runtime and permanent
+            let _ = client.remove_port(zone, &port_str, protocol, false);
+            let _ = client.remove_port(zone, &port_str, protocol, true);
+
+            // Fall back to reconstructed block rules for rules we did
+            // not capture (dual-stack family-less + legacy ipv4, both verbs)
+            if let Some(valid_proto) = validate_protocol(protocol) {
+                for rule in block_rule_variants(&port_str, valid_proto) {
+                    let _ = client.remove_rich_rule(zone, &rule, false);
+                    let _ = client.remove_rich_rule(zone, &rule, true);
+                }
+            }
+        }
+    }
+}
+
+/// The calls that put back what [`remove_port_rules`] removes for `port`,
+/// read from its zones beforehand.
+fn capture_port_rules(
+    client: &mut FirewallClient,
+    port: &ConsolidatedPort,
+) -> anyhow::Result<Vec<InverseCall>> {
+    let port_str = port.port_spec();
+    let mut items: Vec<RuleItem> = port
+        .raw_rules
+        .iter()
+        .map(|rule| RuleItem::RichRule(rule.clone()))
+        .collect();
+    for protocol in &port.protocols {
+        items.push(RuleItem::Port {
+            port: port_str.clone(),
+            protocol: protocol.clone(),
+        });
+        if let Some(valid_proto) = validate_protocol(protocol) {
+            items.extend(
+                block_rule_variants(&port_str, valid_proto)
+                    .into_iter()
+                    .map(RuleItem::RichRule),
+            );
+        }
+    }
+
+    Ok(client
+        .get_zones()?
+        .iter()
+        .filter(|zone| port.zones.contains(&zone.name))
+        .flat_map(|zone| journal::inverse_of_removal(zone, &items))
+        .collect())
+}
+
+/// Write rules that exist at runtime only to the permanent configuration.
+/// Open ports are added as ports and blocked ports as their exact rich rule;
+/// firewalld keeps the runtime rule and only writes the permanent one.
+fn make_rules_permanent(client: &FirewallClient, ports: &[Port]) -> anyhow::Result<()> {
+    for port in ports {
+        let zone = port
+            .zone
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Rule for {} has no zone", port.port_spec()))?;
+        let outcome = match &port.raw_rule {
+            Some(rule) => client.add_rich_rule(zone, rule, true)?,
+            None => client.add_port(zone, &port.port_spec(), &port.protocol, true)?,
+        };
+        if outcome.failed() {
+            return Err(anyhow::anyhow!(
+                "Saving {}/{} in zone {} failed",
+                port.port_spec(),
+                port.protocol,
+                zone
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The probe result to append to a toast, e.g. " — verified open on eth0".
+fn probe_suffix(probe: Option<&RuleProbe>) -> String {
+    probe
+        .map(|probe| format!(" — {}", probe.summary()))
+        .unwrap_or_default()
+}
+
 impl PortsPage {
     /// Create a new ports page.
     pub fn new() -> Self {
@@ -78,11 +189,8 @@ impl PortsPage {
         let header_box = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Horizontal)
             .spacing(12)
-            .margin_start(24)
-            .margin_end(24)
-            .margin_top(24)
-            .margin_bottom(12)
             .build();
+        density::track(&header_box, Role::PageHeader);
 
         let title_box = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Vertical)
@@ -130,15 +238,94 @@ impl PortsPage {
         // Main content - no clamp for full width
         let content = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Vertical)
-            .spacing(24)
-            .margin_top(24)
-            .margin_bottom(24)
-            .margin_start(24)
-            .margin_end(24)
             .hexpand(true)
             .build();
+        density::track(
+            &content,
+            Role::Content {
+                margin: 24,
+                spacing: 24,
+            },
+        );
         scrolled.set_child(Some(&content));
 
+        // Zone selector — shows one zone's rules and is where new ones go
+        let zone_group = adw::PreferencesGroup::builder().build();
+        let zone_dropdown = adw::ComboRow::builder()
+            .title(gettext("Zone"))
+            .subtitle(gettext(
+                "Rules shown here; new rules are added to this zone",
+            ))
+            .model(&gtk4::StringList::new(&[gettext("All Zones").as_str()]))
+            .build();
+        zone_dropdown.add_prefix(&gtk4::Image::from_icon_name("network-server-symbolic"));
+        let page_for_zone = self.clone();
+        zone_dropdown.connect_selected_notify(move |row| {
+            // The first entry stands for every zone
+            let zone = match row.selected() {
+                0 | gtk4::INVALID_LIST_POSITION => String::new(),
+                i => page_for_zone
+                    .imp()
+                    .cached_zones
+                    .borrow()
+                    .get(i as usize - 1)
+                    .cloned()
+                    .unwrap_or_default(),
+            };
+            page_for_zone.imp().selected_zone.replace(zone);
+            page_for_zone.refilter();
+        });
+        zone_group.add(&zone_dropdown);
+        content.append(&zone_group);
+        imp.zone_dropdown.replace(Some(zone_dropdown));
+
+        // Filter bar: port number, name, zone or protocol, and the verdict
+        let filter_bar = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(6)
+            .build();
+        let search_entry = gtk4::SearchEntry::builder()
+            .placeholder_text(gettext("Search by port, name, zone or protocol"))
+            .hexpand(true)
+            .build();
+        let protocol_dropdown =
+            gtk4::DropDown::from_strings(&[gettext("Any Protocol").as_str(), "TCP", "UDP"]);
+        protocol_dropdown.set_tooltip_text(Some(&gettext("Protocol")));
+        let verdict_dropdown = gtk4::DropDown::from_strings(&[
+            &gettext("Open and Blocked"),
+            &gettext("Open"),
+            &gettext("Blocked"),
+        ]);
+        verdict_dropdown.set_tooltip_text(Some(&gettext("Verdict")));
+        filter_bar.append(&search_entry);
+        filter_bar.append(&protocol_dropdown);
+        filter_bar.append(&verdict_dropdown);
+        content.append(&filter_bar);
+
+        let page = self.clone();
+        search_entry.connect_search_changed(move |entry| {
+            page.imp().port_filter.borrow_mut().query = entry.text().to_lowercase();
+            page.refilter();
+        });
+        let page = self.clone();
+        protocol_dropdown.connect_selected_notify(move |dropdown| {
+            page.imp().port_filter.borrow_mut().protocol = match dropdown.selected() {
+                1 => "tcp".to_string(),
+                2 => "udp".to_string(),
+                _ => String::new(),
+            };
+            page.refilter();
+        });
+        let page = self.clone();
+        verdict_dropdown.connect_selected_notify(move |dropdown| {
+            page.imp().port_filter.borrow_mut().verdict = match dropdown.selected() {
+                1 => VerdictFilter::Open,
+                2 => VerdictFilter::Blocked,
+                _ => VerdictFilter::Any,
+            };
+            page.refilter();
+        });
+
         // Ports group
         content.append(&Self::create_section_header(
             "network-transmit-symbolic",
@@ -147,8 +334,12 @@ impl PortsPage {
         let ports_group = adw::PreferencesGroup::builder()
             .description(gettext("Custom ports opened in the firewall"))
             .build();
+        let ports_placeholder = adw::ActionRow::builder().sensitive(false).build();
+        ports_group.add(&ports_placeholder);
         content.append(&ports_group);
+        content.append(&self.create_port_list(false));
         imp.ports_group.replace(Some(ports_group));
+        imp.ports_placeholder.replace(Some(ports_placeholder));
 
         // Blocked ports group
         content.append(&Self::create_section_header(
@@ -158,8 +349,104 @@ impl PortsPage {
         let blocked_ports_group = adw::PreferencesGroup::builder()
             .description(gettext("Ports explicitly blocked via rich rules"))
             .build();
+        let blocked_placeholder = adw::ActionRow::builder()
+            .title(gettext("No blocked ports"))
+            .subtitle(gettext("No ports are explicitly blocked via rules"))
+            .sensitive(false)
+            .build();
+        blocked_ports_group.add(&blocked_placeholder);
         content.append(&blocked_ports_group);
+        content.append(&self.create_port_list(true));
         imp.blocked_ports_group.replace(Some(blocked_ports_group));
+        imp.blocked_placeholder.replace(Some(blocked_placeholder));
+        self.update_placeholders();
+
+        // Rule usage: nftables hit counters and the unused-rule cleanup
+        content.append(&Self::create_section_header(
+            "utilities-system-monitor-symbolic",
+            &gettext("Rule Usage"),
+        ));
+        let usage_group = adw::PreferencesGroup::builder()
+            .description(gettext("Find rules that no traffic has matched"))
+            .build();
+
+        let counters_row = adw::ActionRow::builder()
+            .title(gettext("Hit Counters"))
+            .subtitle(gettext(
+                "Read per-rule packet counters from nftables (requires authentication)",
+            ))
+            .build();
+        let load_button = gtk4::Button::builder()
+            .label(gettext("Read"))
+            .valign(gtk4::Align::Center)
+            .build();
+        let page_for_counters = self.clone();
+        load_button.connect_clicked(move |button| page_for_counters.load_counters(button));
+        counters_row.add_suffix(&load_button);
+        usage_group.add(&counters_row);
+
+        let cleanup_row = adw::ActionRow::builder()
+            .title(gettext("Clean Up Unused Rules"))
+            .subtitle(gettext(
+                "Remove open ports with no hits that are older than a chosen age",
+            ))
+            .build();
+        let cleanup_button = gtk4::Button::builder()
+            .label(gettext("Review"))
+            .valign(gtk4::Align::Center)
+            .sensitive(false)
+            .build();
+        let page_for_cleanup = self.clone();
+        cleanup_button.connect_clicked(move |_| page_for_cleanup.show_cleanup_dialog());
+        cleanup_row.add_suffix(&cleanup_button);
+        usage_group.add(&cleanup_row);
+
+        content.append(&usage_group);
+        imp.counters_row.replace(Some(counters_row));
+        imp.cleanup_button.replace(Some(cleanup_button));
+
+        // Port names: the metadata alone, shared between machines
+        content.append(&Self::create_section_header(
+            "document-edit-symbolic",
+            &gettext("Port Names"),
+        ));
+        let names_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "Share the names and descriptions of rules with other machines, without the rules",
+            ))
+            .build();
+
+        let export_row = adw::ActionRow::builder()
+            .title(gettext("Export Names"))
+            .subtitle(gettext(
+                "Save the names and descriptions of every named rule",
+            ))
+            .build();
+        let export_button = gtk4::Button::builder()
+            .label(gettext("Export"))
+            .valign(gtk4::Align::Center)
+            .build();
+        let page_for_export = self.clone();
+        export_button.connect_clicked(move |_| page_for_export.export_names());
+        export_row.add_suffix(&export_button);
+        names_group.add(&export_row);
+
+        let import_row = adw::ActionRow::builder()
+            .title(gettext("Import Names"))
+            .subtitle(gettext(
+                "Name the matching rules here, choosing which names to replace",
+            ))
+            .build();
+        let import_button = gtk4::Button::builder()
+            .label(gettext("Import"))
+            .valign(gtk4::Align::Center)
+            .build();
+        let page_for_import = self.clone();
+        import_button.connect_clicked(move |_| page_for_import.import_names());
+        import_row.add_suffix(&import_button);
+        names_group.add(&import_row);
+
+        content.append(&names_group);
 
         // Summary group
         content.append(&Self::create_section_header(
@@ -178,25 +465,192 @@ impl PortsPage {
 
     /// Set available zones for the dropdown.
     pub fn set_available_zones(&self, zones: &[String]) {
-        self.imp().cached_zones.replace(zones.to_vec());
+        let imp = self.imp();
+        if *imp.cached_zones.borrow() == zones {
+            return;
+        }
+        // Replacing the model resets the selection, so keep the target
+        let target = imp.selected_zone.borrow().clone();
+        imp.cached_zones.replace(zones.to_vec());
+        if let Some(dropdown) = imp.zone_dropdown.borrow().as_ref() {
+            let all = gettext("All Zones");
+            let names: Vec<&str> = std::iter::once(all.as_str())
+                .chain(zones.iter().map(|z| z.as_str()))
+                .collect();
+            dropdown.set_model(Some(&gtk4::StringList::new(&names)));
+            let position = zones.iter().position(|z| *z == target).map_or(0, |i| i + 1);
+            dropdown.set_selected(position as u32);
+        }
     }
 
-    /// Populate with ports data.
-    pub fn set_ports(&self, ports: &[Port]) {
+    /// Hides rules outside the selected zone or not matching the filter
+    /// bar; shared by both lists.
+    fn rule_filter(&self) -> gtk4::CustomFilter {
+        self.imp()
+            .rule_filter
+            .get_or_init(|| {
+                let page = self.downgrade();
+                gtk4::CustomFilter::new(move |object| {
+                    let (Some(page), Some(port)) = (
+                        page.upgrade(),
+                        object.downcast_ref::<ConsolidatedPortObject>(),
+                    ) else {
+                        return true;
+                    };
+                    let imp = page.imp();
+                    let port = port.model();
+                    let zone = imp.selected_zone.borrow();
+                    (zone.is_empty() || port.zones.contains(&*zone))
+                        && imp.port_filter.borrow().matches(&port)
+                })
+            })
+            .clone()
+    }
+
+    /// Apply a change of the filter bar to both lists.
+    fn refilter(&self) {
+        self.rule_filter().changed(gtk4::FilterChange::Different);
+    }
+
+    /// The ports from firewalld, as a list of [`PortObject`]s that stays the
+    /// same across refreshes.
+    pub fn port_store(&self) -> gio::ListStore {
+        self.imp()
+            .port_store
+            .get_or_init(gio::ListStore::new::<PortObject>)
+            .clone()
+    }
+
+    /// The rows of both lists, one [`ConsolidatedPortObject`] per port or
+    /// range and verdict.
+    fn row_store(&self) -> gio::ListStore {
+        self.imp()
+            .row_store
+            .get_or_init(gio::ListStore::new::<ConsolidatedPortObject>)
+            .clone()
+    }
+
+    /// The list of open or blocked rules. Rows are built for the rules in
+    /// sight and again only when their rule changes.
+    fn create_port_list(&self, blocked: bool) -> gtk4::ScrolledWindow {
+        let sorter = gtk4::CustomSorter::new(|a, b| {
+            let key = |object: &glib::Object| {
+                object.downcast_ref::<ConsolidatedPortObject>().map(|port| {
+                    let port = port.model();
+                    (port.number, port.end_number)
+                })
+            };
+            key(a).cmp(&key(b)).into()
+        });
+        let sorted = gtk4::SortListModel::new(Some(self.row_store()), Some(sorter));
+        let verdict =
+            gtk4::BoolFilter::new(Some(ConsolidatedPortObject::this_expression("is-blocked")));
+        verdict.set_invert(!blocked);
+        let filter = gtk4::EveryFilter::new();
+        filter.append(verdict);
+        filter.append(self.rule_filter());
+        let model = gtk4::FilterListModel::new(Some(sorted), Some(filter));
+        let page = self.clone();
+        model.connect_items_changed(move |_, _, _, _| page.update_placeholders());
+
+        let factory = gtk4::SignalListItemFactory::new();
+        let page = self.downgrade();
+        factory.connect_bind(move |_, item| {
+            let Some(item) = item.downcast_ref::<gtk4::ListItem>() else {
+                return;
+            };
+            let port = item
+                .item()
+                .and_downcast::<ConsolidatedPortObject>()
+                .map(|port| port.model());
+            if let (Some(page), Some(port)) = (page.upgrade(), port) {
+                item.set_child(Some(&page.create_port_row(&port)));
+            }
+        });
+        factory.connect_unbind(|_, item| {
+            if let Some(item) = item.downcast_ref::<gtk4::ListItem>() {
+                item.set_child(gtk4::Widget::NONE);
+            }
+        });
+
+        let list_view = gtk4::ListView::builder()
+            .model(&gtk4::NoSelection::new(Some(model.clone())))
+            .factory(&factory)
+            .show_separators(true)
+            .css_classes(vec!["card".to_string()])
+            .build();
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .propagate_natural_height(true)
+            .max_content_height(480)
+            .child(&list_view)
+            .build();
         let imp = self.imp();
+        if blocked {
+            imp.blocked_model.replace(Some(model));
+            imp.blocked_list.replace(Some(scrolled.clone()));
+        } else {
+            imp.open_model.replace(Some(model));
+            imp.open_list.replace(Some(scrolled.clone()));
+        }
+        scrolled
+    }
 
-        // Clear existing rows from all groups using tracked rows
-        Self::clear_preferences_group_rows(imp.ports_group.borrow().as_ref(), &imp.ports_rows);
-        Self::clear_preferences_group_rows(
-            imp.blocked_ports_group.borrow().as_ref(),
-            &imp.blocked_rows,
-        );
-        Self::clear_preferences_group_rows(imp.summary_group.borrow().as_ref(), &imp.summary_rows);
+    /// Show a placeholder instead of a list without rules.
+    fn update_placeholders(&self) {
+        let imp = self.imp();
+        let count = |model: &RefCell<Option<gtk4::FilterListModel>>| {
+            model
+                .borrow()
+                .as_ref()
+                .map(|model| model.n_items())
+                .unwrap_or_default()
+        };
+        let (open, blocked) = (count(&imp.open_model), count(&imp.blocked_model));
+        if let Some(placeholder) = imp.ports_placeholder.borrow().as_ref() {
+            placeholder.set_visible(open == 0);
+            if blocked == 0 {
+                placeholder.set_title(&gettext("No port rules configured"));
+                placeholder.set_subtitle(&gettext("Click 'Add Port' to create a rule"));
+            } else {
+                placeholder.set_title(&gettext("No open ports"));
+                placeholder.set_subtitle(&gettext("Click 'Add Port' to allow traffic on a port"));
+            }
+        }
+        if let Some(list) = imp.open_list.borrow().as_ref() {
+            list.set_visible(open > 0);
+        }
+        // Say so when the rules are only out of view
+        let zone = imp.selected_zone.borrow().clone();
+        let filtered = *imp.port_filter.borrow() != PortFilter::default();
+        if let Some(placeholder) = imp.ports_placeholder.borrow().as_ref() {
+            if filtered && open == 0 {
+                placeholder.set_title(&gettext("No matching open ports"));
+                placeholder.set_subtitle(&gettext("Change the search or filters to see more"));
+            } else if !zone.is_empty() && open == 0 {
+                placeholder.set_title(&gettext("No open ports in '%s'").replace("%s", &zone));
+            }
+        }
+        if let Some(placeholder) = imp.blocked_placeholder.borrow().as_ref() {
+            placeholder.set_visible(blocked == 0);
+            if filtered {
+                placeholder.set_title(&gettext("No matching blocked ports"));
+            } else if zone.is_empty() {
+                placeholder.set_title(&gettext("No blocked ports"));
+            } else {
+                placeholder.set_title(&gettext("No blocked ports in '%s'").replace("%s", &zone));
+            }
+        }
+        if let Some(list) = imp.blocked_list.borrow().as_ref() {
+            list.set_visible(blocked > 0);
+        }
+    }
 
-        let mut tcp_count = 0;
-        let mut udp_count = 0;
-        let mut tcp_deny_count = 0;
-        let mut udp_deny_count = 0;
+    /// Populate with ports data.
+    pub fn set_ports(&self, ports: &[Port]) {
+        let imp = self.imp();
+        sync_store::<PortObject>(&self.port_store(), ports);
+        imp.consolidated.borrow_mut().clear();
 
         // Collect ports from firewalld (allowed + blocked already merged by caller)
         let mut all_ports: Vec<Port> = ports.to_vec();
@@ -226,6 +680,9 @@ impl PortsPage {
                         if !metadata.name.is_empty() {
                             port.name = Some(metadata.name.clone());
                         }
+                        if !metadata.description.is_empty() {
+                            port.description = Some(metadata.description.clone());
+                        }
                     } else if port.is_range()
                         && !single_keys.contains(&(
                             port.number,
@@ -281,283 +738,396 @@ impl PortsPage {
             }
         }
 
-        if all_ports.is_empty() {
-            if let Some(group) = imp.ports_group.borrow().as_ref() {
-                let placeholder = adw::ActionRow::builder()
-                    .title(gettext("No port rules configured"))
-                    .subtitle(gettext("Click 'Add Port' to create a rule"))
-                    .sensitive(false)
-                    .build();
-                group.add(&placeholder);
-                imp.ports_rows.borrow_mut().push(placeholder);
-            }
-        } else {
-            // Count stats before consolidation
-            for port in &all_ports {
-                let is_blocked =
-                    port.action == "deny" || port.action == "reject" || port.action == "drop";
-                if port.protocol == "tcp" {
-                    if is_blocked {
-                        tcp_deny_count += 1;
-                    } else {
-                        tcp_count += 1;
-                    }
-                } else {
-                    if is_blocked {
-                        udp_deny_count += 1;
-                    } else {
-                        udp_count += 1;
-                    }
-                }
-            }
-
-            // Consolidate ports — groups same port number across zones/protocols
-            let consolidated_ports = ConsolidatedPort::consolidate(&all_ports);
-
-            let mut has_open = false;
-            let mut has_blocked = false;
-
-            for port in &consolidated_ports {
-                if port.is_blocked() {
-                    has_blocked = true;
-                    self.add_consolidated_port_row(port, true);
-                } else {
-                    has_open = true;
-                    self.add_consolidated_port_row(port, false);
-                }
+        // Consolidate ports — groups same port number across zones/protocols.
+        // Rows of rules that did not change stay as they are.
+        let mut consolidated_ports = ConsolidatedPort::consolidate(&all_ports);
+        {
+            // Temporary rules show when they are removed; part of the row's
+            // identity, so a changed expiry rebinds it
+            let mut storage = imp.storage.borrow_mut();
+            for port in &mut consolidated_ports {
+                port.expires_at = port
+                    .zones
+                    .iter()
+                    .flat_map(|zone| port.protocols.iter().map(move |p| (zone, p)))
+                    .filter_map(|(zone, protocol)| {
+                        storage.get(&PortStorage::make_key(&port.port_spec(), protocol, zone))
+                    })
+                    .filter_map(|metadata| metadata.expires())
+                    .map(|expires| expires.timestamp())
+                    .min();
             }
+        }
+        sync_store::<ConsolidatedPortObject>(&self.row_store(), &consolidated_ports);
+        imp.consolidated.replace(consolidated_ports);
+    }
 
-            // Placeholder if no open ports
-            if !has_open {
-                if let Some(group) = imp.ports_group.borrow().as_ref() {
-                    let placeholder = adw::ActionRow::builder()
-                        .title(gettext("No open ports"))
-                        .subtitle(gettext("Click 'Add Port' to allow traffic on a port"))
-                        .sensitive(false)
-                        .build();
-                    group.add(&placeholder);
-                    imp.ports_rows.borrow_mut().push(placeholder);
-                }
-            }
+    /// Show the rule counts in the summary: rules and the ports they cover
+    /// per protocol, ranges, and blocked ports over time from `history`.
+    pub fn set_rule_stats(&self, stats: &RuleStats, history: &[RuleSample]) {
+        let imp = self.imp();
+        Self::clear_preferences_group_rows(imp.summary_group.borrow().as_ref(), &imp.summary_rows);
+        let Some(group) = imp.summary_group.borrow().clone() else {
+            return;
+        };
+        let add_row = |title: String, subtitle: String, icon: &str| {
+            let row = adw::ActionRow::builder()
+                .title(title)
+                .subtitle(subtitle)
+                .build();
+            row.add_prefix(&gtk4::Image::from_icon_name(icon));
+            group.add(&row);
+            imp.summary_rows.borrow_mut().push(row.clone());
+            row
+        };
 
-            // Placeholder if no blocked ports
-            if !has_blocked {
-                if let Some(group) = imp.blocked_ports_group.borrow().as_ref() {
-                    let placeholder = adw::ActionRow::builder()
-                        .title(gettext("No blocked ports"))
-                        .subtitle(gettext("No ports are explicitly blocked via rules"))
-                        .sensitive(false)
-                        .build();
-                    group.add(&placeholder);
-                    imp.blocked_rows.borrow_mut().push(placeholder);
-                }
-            }
+        for (protocol, title, icon) in [
+            ("tcp", gettext("TCP Ports"), "network-transmit-symbolic"),
+            ("udp", gettext("UDP Ports"), "network-receive-symbolic"),
+        ] {
+            let counts = stats.protocol(protocol);
+            add_row(
+                title,
+                gettext("%d allowed, %d blocked")
+                    .replacen("%d", &counts.open_ports.to_string(), 1)
+                    .replacen("%d", &counts.blocked_ports.to_string(), 1),
+                icon,
+            );
         }
 
-        // Update summary
-        if let Some(group) = imp.summary_group.borrow().as_ref() {
-            let tcp_row = adw::ActionRow::builder()
-                .title(gettext("TCP Ports"))
-                .subtitle(
-                    gettext("%d allowed, %d blocked")
-                        .replacen("%d", &tcp_count.to_string(), 1)
-                        .replacen("%d", &tcp_deny_count.to_string(), 1),
-                )
-                .build();
-            tcp_row.add_prefix(&gtk4::Image::from_icon_name("network-transmit-symbolic"));
-            group.add(&tcp_row);
-            imp.summary_rows.borrow_mut().push(tcp_row);
+        if stats.ranges > 0 {
+            add_row(
+                gettext("Port Ranges"),
+                gettext("%d rules covering %d ports")
+                    .replacen("%d", &stats.ranges.to_string(), 1)
+                    .replacen("%d", &stats.range_ports.to_string(), 1),
+                "view-continuous-symbolic",
+            );
+        }
 
-            let udp_row = adw::ActionRow::builder()
-                .title(gettext("UDP Ports"))
-                .subtitle(
-                    gettext("%d allowed, %d blocked")
-                        .replacen("%d", &udp_count.to_string(), 1)
-                        .replacen("%d", &udp_deny_count.to_string(), 1),
-                )
-                .build();
-            udp_row.add_prefix(&gtk4::Image::from_icon_name("network-receive-symbolic"));
-            group.add(&udp_row);
-            imp.summary_rows.borrow_mut().push(udp_row);
+        // Blocked ports over time, once there is more than one sample
+        if let [first, .., _] = history {
+            let row = add_row(
+                gettext("Blocked Ports Over Time"),
+                gettext("%d now, %d on %s")
+                    .replacen("%d", &stats.total.blocked_ports.to_string(), 1)
+                    .replacen("%d", &first.counts.blocked_ports.to_string(), 1)
+                    .replacen("%s", &format_datetime(first.timestamp), 1),
+                "utilities-system-monitor-symbolic",
+            );
+            let sparkline = Sparkline::new();
+            sparkline.set_size_request(120, 28);
+            sparkline.set_valign(gtk4::Align::Center);
+            sparkline.set_values(
+                &history
+                    .iter()
+                    .map(|sample| f64::from(sample.counts.blocked_ports))
+                    .collect::<Vec<_>>(),
+            );
+            row.add_suffix(&sparkline);
         }
     }
 
-    /// Add a consolidated port row to the appropriate group (open or blocked).
-    fn add_consolidated_port_row(&self, port: &ConsolidatedPort, is_blocked: bool) {
+    /// Build the row of a consolidated port, open or blocked.
+    fn create_port_row(&self, port: &ConsolidatedPort) -> adw::ActionRow {
         let imp = self.imp();
+        let is_blocked = port.is_blocked();
+        // Maximum zone badges shown inline; the rest collapse into a "+N" badge
+        const MAX_ZONE_BADGES: usize = 3;
+
+        let title = port.display_title();
+
+        // Build a compact subtitle showing zones and protocol.
+        // Long zone lists are summarized to keep the row height stable.
+        let zone_text = if port.zones.is_empty() {
+            String::new()
+        } else if port.zones.len() == 1 {
+            gettext("Zone: %s").replace("%s", &port.zones[0])
+        } else if port.zones.len() <= MAX_ZONE_BADGES {
+            format!("Zones: {}", port.zones.join(", "))
+        } else {
+            format!("{} zones", port.zones.len())
+        };
 
-        // Select the target group and row tracker based on whether this is a blocked port
-        let group_ref = if is_blocked {
-            imp.blocked_ports_group.borrow()
+        let proto_text = port.protocol_display();
+        let subtitle = if zone_text.is_empty() {
+            proto_text
         } else {
-            imp.ports_group.borrow()
+            format!("{} • {}", zone_text, proto_text)
         };
 
-        if let Some(group) = group_ref.as_ref() {
-            // Maximum zone badges shown inline; the rest collapse into a "+N" badge
-            const MAX_ZONE_BADGES: usize = 3;
+        // AdwActionRow renders title/subtitle as Pango markup. Names are
+        // already charset-restricted at load, but escape here too so no
+        // value (name or zone) can ever be interpreted as markup.
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(&title).as_str())
+            .subtitle(glib::markup_escape_text(&subtitle).as_str())
+            .build();
 
-            let title = port.display_title();
+        // Full zone list stays available on hover
+        if port.zones.len() > MAX_ZONE_BADGES {
+            row.set_tooltip_text(Some(&format!("Zones: {}", port.zones.join(", "))));
+        }
 
-            // Build a compact subtitle showing zones and protocol.
-            // Long zone lists are summarized to keep the row height stable.
-            let zone_text = if port.zones.is_empty() {
-                String::new()
-            } else if port.zones.len() == 1 {
-                gettext("Zone: %s").replace("%s", &port.zones[0])
-            } else if port.zones.len() <= MAX_ZONE_BADGES {
-                format!("Zones: {}", port.zones.join(", "))
-            } else {
-                format!("{} zones", port.zones.len())
-            };
+        // Status icon
+        let action_icon = if is_blocked {
+            let icon = gtk4::Image::from_icon_name("dialog-error-symbolic");
+            icon.add_css_class("error");
+            icon
+        } else {
+            let icon = gtk4::Image::from_icon_name("object-select-symbolic");
+            icon.add_css_class("success");
+            icon
+        };
+        row.add_prefix(&action_icon);
 
-            let proto_text = port.protocol_display();
-            let subtitle = if zone_text.is_empty() {
-                proto_text
-            } else {
-                format!("{} • {}", zone_text, proto_text)
-            };
+        // Zone badges in the suffix area
+        let suffix_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(8)
+            .valign(gtk4::Align::Center)
+            .build();
 
-            // AdwActionRow renders title/subtitle as Pango markup. Names are
-            // already charset-restricted at load, but escape here too so no
-            // value (name or zone) can ever be interpreted as markup.
-            let row = adw::ActionRow::builder()
-                .title(glib::markup_escape_text(&title).as_str())
-                .subtitle(glib::markup_escape_text(&subtitle).as_str())
+        for zone in port.zones.iter().take(MAX_ZONE_BADGES) {
+            let label = gtk4::Label::builder()
+                .label(zone)
+                .css_classes(vec!["caption".to_string(), "card".to_string()])
                 .build();
+            label.add_css_class("dim-label");
+            suffix_box.append(&label);
+        }
 
-            // Full zone list stays available on hover
-            if port.zones.len() > MAX_ZONE_BADGES {
-                row.set_tooltip_text(Some(&format!("Zones: {}", port.zones.join(", "))));
-            }
-
-            // Status icon
-            let action_icon = if is_blocked {
-                let icon = gtk4::Image::from_icon_name("dialog-error-symbolic");
-                icon.add_css_class("error");
-                icon
-            } else {
-                let icon = gtk4::Image::from_icon_name("object-select-symbolic");
-                icon.add_css_class("success");
-                icon
-            };
-            row.add_prefix(&action_icon);
-
-            // Zone badges in the suffix area
-            let suffix_box = gtk4::Box::builder()
-                .orientation(gtk4::Orientation::Horizontal)
-                .spacing(8)
-                .valign(gtk4::Align::Center)
+        // Collapse the remaining zones into a single "+N" badge
+        if port.zones.len() > MAX_ZONE_BADGES {
+            let more_label = gtk4::Label::builder()
+                .label(format!("+{}", port.zones.len() - MAX_ZONE_BADGES))
+                .css_classes(vec!["caption".to_string(), "card".to_string()])
+                .tooltip_text(port.zones[MAX_ZONE_BADGES..].join(", "))
                 .build();
+            more_label.add_css_class("dim-label");
+            suffix_box.append(&more_label);
+        }
 
-            for zone in port.zones.iter().take(MAX_ZONE_BADGES) {
-                let label = gtk4::Label::builder()
-                    .label(zone)
-                    .css_classes(vec!["caption".to_string(), "card".to_string()])
-                    .build();
-                label.add_css_class("dim-label");
-                suffix_box.append(&label);
-            }
+        // Temporary rules show when they are removed
+        if let Some(expires) = port.expires_at {
+            let time = format_time(expires);
+            let date = format_datetime(expires);
+            let expiry_label = gtk4::Label::builder()
+                .label(gettext("Until %s").replacen("%s", &time, 1))
+                .css_classes(vec!["caption".to_string(), "card".to_string()])
+                .tooltip_text(gettext("Removed automatically on %s").replacen("%s", &date, 1))
+                .build();
+            expiry_label.add_css_class("warning");
+            suffix_box.append(&expiry_label);
+        }
 
-            // Collapse the remaining zones into a single "+N" badge
-            if port.zones.len() > MAX_ZONE_BADGES {
-                let more_label = gtk4::Label::builder()
-                    .label(format!("+{}", port.zones.len() - MAX_ZONE_BADGES))
-                    .css_classes(vec!["caption".to_string(), "card".to_string()])
-                    .tooltip_text(port.zones[MAX_ZONE_BADGES..].join(", "))
-                    .build();
-                more_label.add_css_class("dim-label");
-                suffix_box.append(&more_label);
-            }
+        // Rules missing from the permanent configuration vanish on reload
+        if !port.runtime_only.is_empty() {
+            let runtime_label = gtk4::Label::builder()
+                .label(gettext("Runtime only"))
+                .css_classes(vec!["caption".to_string(), "card".to_string()])
+                .tooltip_text(gettext(
+                    "Lost when firewalld reloads or the system restarts",
+                ))
+                .build();
+            runtime_label.add_css_class("warning");
+            suffix_box.append(&runtime_label);
+        }
 
-            row.add_suffix(&suffix_box);
+        row.add_suffix(&suffix_box);
 
-            // Protocol badge
-            let proto_label = gtk4::Label::builder()
-                .label(port.protocol_display())
+        // Hit count, once counters have been read
+        if let Some(counters) = imp.counters.borrow().as_ref() {
+            let hits = counters.hits(&port.zones, &port.port_spec(), &port.protocols, is_blocked);
+            let hits_label = gtk4::Label::builder()
                 .css_classes(vec!["caption".to_string()])
                 .valign(gtk4::Align::Center)
                 .build();
-            if port.protocols.contains(&"tcp".to_string()) {
-                proto_label.add_css_class("accent");
+            match hits {
+                Some(hits) => {
+                    hits_label.set_label(
+                        &gettext("%d hits • %s")
+                            .replacen("%d", &hits.packets.to_string(), 1)
+                            .replacen("%s", &format_bytes(hits.bytes), 1),
+                    );
+                    hits_label.set_tooltip_text(Some(&gettext(
+                        "Packets matched since firewalld last loaded its rules",
+                    )));
+                    if hits.packets == 0 {
+                        hits_label.add_css_class("warning");
+                    }
+                }
+                None => {
+                    hits_label.set_label(&gettext("Not counted"));
+                    hits_label
+                        .set_tooltip_text(Some(&gettext("This rule has no nftables counter")));
+                    hits_label.add_css_class("dim-label");
+                }
             }
-            proto_label.set_margin_end(8);
-            row.add_suffix(&proto_label);
+            row.add_suffix(&hits_label);
+        }
 
-            let port_clone = port.clone();
-            let page_clone = self.clone();
+        // Protocol badge
+        let proto_label = gtk4::Label::builder()
+            .label(port.protocol_display())
+            .css_classes(vec!["caption".to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+        if port.protocols.contains(&"tcp".to_string()) {
+            proto_label.add_css_class("accent");
+        }
+        proto_label.set_margin_end(8);
+        row.add_suffix(&proto_label);
+
+        let port_clone = port.clone();
+        let page_clone = self.clone();
 
-            // Edit button
-            let edit_button = gtk4::Button::builder()
-                .icon_name("document-edit-symbolic")
+        // One-click fix for runtime-only rules
+        if !port.runtime_only.is_empty() {
+            let permanent_button = gtk4::Button::builder()
+                .icon_name("document-save-symbolic")
                 .css_classes(vec!["flat".to_string()])
                 .valign(gtk4::Align::Center)
-                .tooltip_text(gettext("Edit rule"))
+                .tooltip_text(gettext("Make permanent"))
                 .build();
-
-            let p_clone = port.clone();
-            let page_c = self.clone();
-            edit_button.connect_clicked(move |_| {
-                page_c.show_edit_dialog(&p_clone);
+            let runtime_only = port.runtime_only.clone();
+            let page = self.clone();
+            permanent_button.connect_clicked(move |button| {
+                page.make_permanent(runtime_only.clone(), button);
             });
-            row.add_suffix(&edit_button);
+            row.add_suffix(&permanent_button);
+        }
 
-            // Delete button
-            let delete_button = gtk4::Button::builder()
-                .icon_name("user-trash-symbolic")
-                .css_classes(vec!["flat".to_string(), "error".to_string()])
-                .valign(gtk4::Align::Center)
-                .tooltip_text(gettext("Delete this port rule"))
-                .build();
+        // Edit button
+        let edit_button = gtk4::Button::builder()
+            .icon_name("document-edit-symbolic")
+            .css_classes(vec!["flat".to_string()])
+            .valign(gtk4::Align::Center)
+            .tooltip_text(gettext("Edit rule"))
+            .build();
 
-            let row_clone = row.clone();
-            delete_button.connect_clicked(move |button| {
-                let page = page_clone.clone();
-                let port = port_clone.clone();
-                let button = button.clone();
-                let row = row_clone.clone();
-
-                // Confirm before removing a firewall rule — this is destructive
-                // and, for an SSH/remote port, can lock the user out
-                let dialog = adw::AlertDialog::builder()
-                    .heading(format!("Delete rule for {}?", port.port_spec()))
-                    .body(format!(
-                        "This removes the {} rule from {} zone(s), for this session and permanently.",
-                        if port.is_blocked() { "block" } else { "open-port" },
-                        port.zones.len()
-                    ))
-                    .build();
-                dialog.add_response("cancel", "_Cancel");
-                dialog.add_response("delete", "_Delete");
-                dialog.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
-                dialog.set_default_response(Some("cancel"));
-
-                dialog.connect_response(None, move |_, response| {
-                    if response == "delete" {
-                        button.set_sensitive(false);
-                        row.set_sensitive(false);
-                        row.add_css_class("dim-label");
-                        page.delete_consolidated_port(&port);
-                    }
-                });
+        let p_clone = port.clone();
+        let page_c = self.clone();
+        edit_button.connect_clicked(move |_| {
+            page_c.show_edit_dialog(&p_clone);
+        });
+        row.add_suffix(&edit_button);
 
-                if let Some(root) = page_clone.root() {
-                    if let Some(window) = root.downcast_ref::<gtk4::Window>() {
-                        dialog.present(Some(window));
-                    }
-                }
-            });
+        // Delete button
+        let delete_button = gtk4::Button::builder()
+            .icon_name("user-trash-symbolic")
+            .css_classes(vec!["flat".to_string(), "error".to_string()])
+            .valign(gtk4::Align::Center)
+            .tooltip_text(gettext("Delete this port rule"))
+            .build();
 
-            row.add_suffix(&delete_button);
+        let row_clone = row.clone();
+        delete_button.connect_clicked(move |button| {
+            page_clone.request_delete(&port_clone, button, &row_clone);
+        });
 
-            group.add(&row);
+        row.add_suffix(&delete_button);
+        row
+    }
 
-            // Track the row in the appropriate list for cleanup
-            if is_blocked {
-                imp.blocked_rows.borrow_mut().push(row);
-            } else {
-                imp.ports_rows.borrow_mut().push(row);
+    /// Confirm and delete a rule. Closing an open TCP port that remote SSH
+    /// sessions come in on warns about the lockout instead, offering a trial.
+    fn request_delete(&self, port: &ConsolidatedPort, button: &gtk4::Button, row: &adw::ActionRow) {
+        let page = self.clone();
+        let port = port.clone();
+        let button = button.clone();
+        let row = row.clone();
+        let deleted = port.clone();
+        let delete = move || {
+            button.set_sensitive(false);
+            row.set_sensitive(false);
+            row.add_css_class("dim-label");
+            page.delete_consolidated_port(&deleted);
+        };
+
+        if port.is_blocked() {
+            self.confirm_delete(&port, delete);
+            return;
+        }
+
+        let page = self.clone();
+        let start = port.number;
+        let end = port.end_number.unwrap_or(port.number);
+        let ranges = port
+            .protocols
+            .iter()
+            .map(|protocol| (start, end, protocol.clone()))
+            .collect();
+        lockout_guard::with_sessions_on(ranges, move |sessions| {
+            let window = match page.main_window() {
+                Some(window) if !sessions.is_empty() => window,
+                _ => {
+                    page.confirm_delete(&port, delete);
+                    return;
+                }
+            };
+            let spec = port.port_spec();
+            let zones = port.zones.clone();
+            let protocols = port.protocols.clone();
+            let (revert_spec, revert_zones, revert_protocols) =
+                (spec.clone(), zones.clone(), protocols.clone());
+            let trial = TrialChange {
+                apply: Box::new(move |client| {
+                    for zone in &zones {
+                        for protocol in &protocols {
+                            client.remove_port(zone, &spec, protocol, false)?;
+                        }
+                    }
+                    Ok(())
+                }),
+                revert: Box::new(move |client| {
+                    for zone in &revert_zones {
+                        for protocol in &revert_protocols {
+                            client.add_port(zone, &revert_spec, protocol, false)?;
+                        }
+                    }
+                    Ok(())
+                }),
+            };
+            lockout_guard::present_lockout_guard(
+                &window,
+                &format!("Closing port {}", port.port_spec()),
+                &sessions,
+                trial,
+                delete,
+                || {},
+            );
+        });
+    }
+
+    /// Ask before removing a rule, then call `delete`.
+    fn confirm_delete(&self, port: &ConsolidatedPort, delete: impl Fn() + 'static) {
+        // Confirm before removing a firewall rule — this is destructive
+        let dialog = adw::AlertDialog::builder()
+            .heading(format!("Delete rule for {}?", port.port_spec()))
+            .body(format!(
+                "This removes the {} rule from {} zone(s), for this session and permanently.",
+                if port.is_blocked() {
+                    "block"
+                } else {
+                    "open-port"
+                },
+                port.zones.len()
+            ))
+            .build();
+        dialog.add_response("cancel", "_Cancel");
+        dialog.add_response("delete", "_Delete");
+        dialog.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
+        dialog.set_default_response(Some("cancel"));
+
+        dialog.connect_response(None, move |_, response| {
+            if response == "delete" {
+                delete();
             }
+        });
+
+        if let Some(window) = self.main_window() {
+            dialog.present(Some(&window));
         }
     }
 
@@ -567,93 +1137,552 @@ impl PortsPage {
         let page = self.clone();
 
         glib::spawn_future_local(async move {
-            let port_spec = port_data.port_spec();
-            let protocols = port_data.protocols.clone();
-            let zones = port_data.zones.clone();
-            let raw_rules = port_data.raw_rules.clone();
-
-            // Clone for use after the closure moves the originals
-            let port_spec_after = port_spec.clone();
-            let protocols_after = protocols.clone();
-            let zones_after = zones.clone();
-
-            let result = gtk4::gio::spawn_blocking(move || {
-                let mut client = crate::firewall::FirewallClient::new();
+            let port_for_removal = port_data.clone();
+            let result = crate::firewall::call(move |client| {
                 if let Err(e) = client.connect() {
                     return Err(anyhow::anyhow!("Not connected to firewalld: {}", e));
                 }
+                // Without the rules read back, the deletion cannot be undone
+                let calls = capture_port_rules(client, &port_for_removal).unwrap_or_default();
+                remove_port_rules(client, &port_for_removal);
+                let undo = journal::record(
+                    format!("Port {} deleted", port_for_removal.port_spec()),
+                    calls,
+                );
+                // Removing a block may leave the port open or closed,
+                // depending on the zone; only a removed opening has an
+                // expected outcome
+                let probe = if port_for_removal.is_blocked() {
+                    None
+                } else {
+                    probe_port(
+                        client,
+                        &port_for_removal.zones,
+                        &port_for_removal.port_spec(),
+                        &port_for_removal.protocols,
+                        Expectation::Closed,
+                    )
+                    .ok()
+                };
+                Ok((probe, undo))
+            })
+            .await;
 
-                let port_str = port_spec;
+            match result {
+                Ok(Ok((probe, undo))) => {
+                    page.show_undo_toast(
+                        &format!(
+                            "Port {} deleted from {} zone(s){}",
+                            port_data.port_spec(),
+                            port_data.zones.len(),
+                            probe_suffix(probe.as_ref())
+                        ),
+                        undo,
+                    );
+                    page.forget_metadata(&port_data);
+                    page.request_refresh();
+                }
+                Ok(Err(e)) => {
+                    page.show_toast(&format!("{}: {}", gettext("Failed to delete port"), e));
+                }
+                Err(_) => {
+                    page.show_toast(&gettext("Failed to delete port"));
+                }
+            }
+        });
+    }
 
-                for zone in &zones {
-                    // Remove the exact rich rules this entry was parsed from,
-                    // so externally-created rules (any family or verb) match.
-                    for rule in &raw_rules {
-                        let _ = client.remove_rich_rule(zone, rule, false);
-                        let _ = client.remove_rich_rule(zone, rule, true);
-                    }
+    /// Drop the stored metadata of a removed rule.
+    fn forget_metadata(&self, port: &ConsolidatedPort) {
+        let port_spec = port.port_spec();
+        let mut storage = self.imp().storage.borrow_mut();
+        for zone in &port.zones {
+            for protocol in &port.protocols {
+                storage.remove(&PortStorage::make_key(&port_spec, protocol, zone));
+            }
+        }
+    }
 
-                    for protocol in &protocols {
-                        // Remove from both runtime and permanent
-                        let _ = client.remove_port(zone, &port_str, protocol, false);
-                        let _ = client.remove_port(zone, &port_str, protocol, true);
+    /// Read rule hit counters through the privileged helper and show them on
+    /// the port rows.
+    fn load_counters(&self, button: &gtk4::Button) {
+        button.set_sensitive(false);
+        let page = self.clone();
+        let button = button.clone();
 
-                        // Fall back to reconstructed block rules for rules we did
-                        // not capture (dual-stack family-less + legacy ipv4, both verbs)
-                        if let Some(valid_proto) = validate_protocol(protocol) {
-                            for rule in block_rule_variants(&port_str, valid_proto) {
-                                let _ = client.remove_rich_rule(zone, &rule, false);
-                                let _ = client.remove_rich_rule(zone, &rule, true);
-                            }
-                        }
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(read_rule_counters).await;
+            button.set_sensitive(true);
+
+            match result {
+                Ok(Ok(counters)) => {
+                    let imp = page.imp();
+                    if let Some(row) = imp.counters_row.borrow().as_ref() {
+                        let read_at = format_time(chrono::Local::now().timestamp());
+                        row.set_subtitle(&if counters.is_empty() {
+                            gettext(
+                                "firewalld's rules carry no nftables counters, so hits cannot be measured",
+                            )
+                        } else {
+                            gettext("%d rules counted • read at %s • counters reset when firewalld reloads")
+                                .replacen("%d", &counters.len().to_string(), 1)
+                                .replacen("%s", &read_at, 1)
+                        });
+                    }
+                    if let Some(cleanup) = imp.cleanup_button.borrow().as_ref() {
+                        cleanup.set_sensitive(!counters.is_empty());
                     }
+                    imp.counters.replace(Some(counters));
+                    // Same rules, so only the rows need building again
+                    rebind_store(&page.row_store());
+                }
+                Ok(Err(e)) => {
+                    page.show_toast(&format!(
+                        "{}: {}",
+                        gettext("Failed to read hit counters"),
+                        e
+                    ));
+                }
+                Err(_) => {
+                    page.show_toast(&gettext("Failed to read hit counters"));
+                }
+            }
+        });
+    }
+
+    /// Open rules with a zero hit count whose recorded creation date is at
+    /// least `days` old. Rules without a counter or a known age are never
+    /// candidates.
+    fn unused_rules(&self, days: i64) -> Vec<ConsolidatedPort> {
+        let imp = self.imp();
+        let counters = imp.counters.borrow();
+        let Some(counters) = counters.as_ref() else {
+            return Vec::new();
+        };
+        let cutoff = chrono::Local::now().naive_local() - chrono::Duration::days(days);
+        let mut storage = imp.storage.borrow_mut();
+
+        imp.consolidated
+            .borrow()
+            .iter()
+            .filter(|port| !port.is_blocked())
+            .filter(|port| {
+                counters
+                    .hits(&port.zones, &port.port_spec(), &port.protocols, false)
+                    .is_some_and(|hits| hits.packets == 0)
+            })
+            .filter(|port| {
+                // Every zone/protocol pair must be known and old enough
+                port.zones.iter().all(|zone| {
+                    port.protocols.iter().all(|protocol| {
+                        storage
+                            .get(&PortStorage::make_key(&port.port_spec(), protocol, zone))
+                            .and_then(|m| m.created())
+                            .is_some_and(|created| created <= cutoff)
+                    })
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Show the assistant that removes open ports nothing has used.
+    fn show_cleanup_dialog(&self) {
+        let dialog = adw::Dialog::builder()
+            .title(gettext("Clean Up Unused Rules"))
+            .content_width(520)
+            .content_height(560)
+            .build();
+
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&adw::HeaderBar::new());
+
+        let prefs = adw::PreferencesPage::new();
+
+        let age_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "Open ports with no hits since firewalld last loaded its rules. \
+                 Only rules created in Security Center have a known age.",
+            ))
+            .build();
+        let age_row = adw::SpinRow::builder()
+            .title(gettext("Created at Least"))
+            .subtitle(gettext("Days ago"))
+            .adjustment(&gtk4::Adjustment::new(30.0, 1.0, 365.0, 1.0, 7.0, 0.0))
+            .build();
+        age_group.add(&age_row);
+        prefs.add(&age_group);
+
+        let candidates_group = adw::PreferencesGroup::builder()
+            .title(gettext("Unused Rules"))
+            .build();
+        prefs.add(&candidates_group);
+
+        let remove_button = gtk4::Button::builder()
+            .label(gettext("Remove Selected"))
+            .css_classes(vec!["destructive-action".to_string(), "pill".to_string()])
+            .halign(gtk4::Align::Center)
+            .margin_top(12)
+            .margin_bottom(12)
+            .build();
+
+        // Candidate rows and their check buttons, rebuilt when the age changes
+        let selection: Rc<RefCell<Vec<(ConsolidatedPort, gtk4::CheckButton)>>> =
+            Rc::new(RefCell::new(Vec::new()));
+        let rows: Rc<RefCell<Vec<adw::ActionRow>>> = Rc::new(RefCell::new(Vec::new()));
+
+        let rebuild = {
+            let page = self.clone();
+            let selection = selection.clone();
+            let rows = rows.clone();
+            let group = candidates_group.clone();
+            let remove_button = remove_button.clone();
+            move |days: i64| {
+                for row in rows.borrow_mut().drain(..) {
+                    group.remove(&row);
+                }
+                selection.borrow_mut().clear();
+
+                let candidates = page.unused_rules(days);
+                if candidates.is_empty() {
+                    let placeholder = adw::ActionRow::builder()
+                        .title(gettext("No unused rules"))
+                        .subtitle(gettext("Every counted rule is newer or has seen traffic"))
+                        .sensitive(false)
+                        .build();
+                    group.add(&placeholder);
+                    rows.borrow_mut().push(placeholder);
+                }
+                for port in candidates {
+                    let check = gtk4::CheckButton::builder()
+                        .active(true)
+                        .valign(gtk4::Align::Center)
+                        .build();
+                    let row = adw::ActionRow::builder()
+                        .title(glib::markup_escape_text(&port.display_title()).as_str())
+                        .subtitle(
+                            glib::markup_escape_text(&format!(
+                                "{} • {}",
+                                port.zones.join(", "),
+                                port.protocol_display()
+                            ))
+                            .as_str(),
+                        )
+                        .activatable_widget(&check)
+                        .build();
+                    row.add_prefix(&check);
+                    group.add(&row);
+                    rows.borrow_mut().push(row);
+                    selection.borrow_mut().push((port, check));
                 }
+                remove_button.set_sensitive(!selection.borrow().is_empty());
+            }
+        };
+        rebuild(age_row.value() as i64);
+        let rebuild = Rc::new(rebuild);
+        let rebuild_for_age = rebuild.clone();
+        age_row.connect_value_notify(move |row| rebuild_for_age(row.value() as i64));
 
-                Ok(())
+        let page = self.clone();
+        let dialog_for_remove = dialog.clone();
+        remove_button.connect_clicked(move |_| {
+            let selected: Vec<ConsolidatedPort> = selection
+                .borrow()
+                .iter()
+                .filter(|(_, check)| check.is_active())
+                .map(|(port, _)| port.clone())
+                .collect();
+            if selected.is_empty() {
+                return;
+            }
+            dialog_for_remove.close();
+            page.remove_unused_rules(selected);
+        });
+
+        toolbar.set_content(Some(&prefs));
+        toolbar.add_bottom_bar(&remove_button);
+        dialog.set_child(Some(&toolbar));
+
+        if let Some(root) = self.root() {
+            if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                dialog.present(Some(window));
+            }
+        }
+    }
+
+    /// Save runtime-only rules to the permanent configuration.
+    fn make_permanent(&self, ports: Vec<Port>, button: &gtk4::Button) {
+        let page = self.clone();
+        let button = button.clone();
+        button.set_sensitive(false);
+
+        glib::spawn_future_local(async move {
+            let result = crate::firewall::call(move |client| {
+                if let Err(e) = client.connect() {
+                    return Err(anyhow::anyhow!("Not connected to firewalld: {}", e));
+                }
+                make_rules_permanent(client, &ports)
             })
             .await;
 
             match result {
                 Ok(Ok(())) => {
+                    page.show_toast(&gettext("Rule saved permanently"));
+                    page.request_refresh();
+                }
+                Ok(Err(e)) => {
                     page.show_toast(&format!(
-                        "Port {} deleted from {} zone(s)",
-                        port_spec_after,
-                        zones_after.len()
+                        "{}: {}",
+                        gettext("Failed to save the rule permanently"),
+                        e
                     ));
+                    button.set_sensitive(true);
+                }
+                Err(_) => {
+                    page.show_toast(&gettext("Failed to save the rule permanently"));
+                    button.set_sensitive(true);
+                }
+            }
+        });
+    }
 
-                    // Update storage
-                    let mut storage = page.imp().storage.borrow_mut();
-                    for zone in &zones_after {
-                        for protocol in &protocols_after {
-                            let key = PortStorage::make_key(&port_spec_after, protocol, zone);
-                            storage.remove(&key);
-                        }
-                    }
-                    drop(storage);
+    /// Remove the rules picked in the cleanup assistant.
+    fn remove_unused_rules(&self, ports: Vec<ConsolidatedPort>) {
+        let page = self.clone();
+
+        glib::spawn_future_local(async move {
+            let to_remove = ports.clone();
+            let result = crate::firewall::call(move |client| {
+                if let Err(e) = client.connect() {
+                    return Err(anyhow::anyhow!("Not connected to firewalld: {}", e));
+                }
+                let mut calls = Vec::new();
+                for port in &to_remove {
+                    calls.extend(capture_port_rules(client, port).unwrap_or_default());
+                    remove_port_rules(client, port);
+                }
+                Ok(journal::record(
+                    format!("{} unused rules removed", to_remove.len()),
+                    calls,
+                ))
+            })
+            .await;
 
+            match result {
+                Ok(Ok(undo)) => {
+                    for port in &ports {
+                        page.forget_metadata(port);
+                    }
+                    page.show_undo_toast(
+                        &gettext("Removed %d unused rules").replace("%d", &ports.len().to_string()),
+                        undo,
+                    );
                     page.request_refresh();
                 }
                 Ok(Err(e)) => {
-                    page.show_toast(&format!("{}: {}", gettext("Failed to delete port"), e));
+                    page.show_toast(&format!("{}: {}", gettext("Failed to remove rules"), e));
                 }
                 Err(_) => {
-                    page.show_toast(&gettext("Failed to delete port"));
+                    page.show_toast(&gettext("Failed to remove rules"));
+                }
+            }
+        });
+    }
+
+    /// Save the names and descriptions of the rules to a file of the user's
+    /// choice.
+    fn export_names(&self) {
+        let profile = self.imp().storage.borrow_mut().export_profile();
+        if profile.ports.is_empty() {
+            self.show_toast(&gettext("No rule has a name to export"));
+            return;
+        }
+        let Some(window) = self.main_window() else {
+            return;
+        };
+        let dialog = gtk4::FileDialog::builder()
+            .title(gettext("Export Port Names"))
+            .initial_name("port-names.json")
+            .modal(true)
+            .build();
+        let page = self.clone();
+        dialog.save(Some(&window), gio::Cancellable::NONE, move |result| {
+            // Cancelling the dialog is not an error
+            let Ok(file) = result else {
+                return;
+            };
+            let Some(path) = file.path() else {
+                page.show_toast(&gettext("Choose a local file"));
+                return;
+            };
+            match profile.write(&path) {
+                Ok(()) => page.show_toast(
+                    &gettext("Exported %d names to %s")
+                        .replacen("%d", &profile.ports.len().to_string(), 1)
+                        .replacen("%s", &path.display().to_string(), 1),
+                ),
+                Err(e) => page.show_toast(&format!(
+                    "{}: {:#}",
+                    gettext("Failed to export the names"),
+                    e
+                )),
+            }
+        });
+    }
+
+    /// Read a names file and merge it, asking about the rules that already
+    /// have another name.
+    fn import_names(&self) {
+        let Some(window) = self.main_window() else {
+            return;
+        };
+        let dialog = gtk4::FileDialog::builder()
+            .title(gettext("Import Port Names"))
+            .modal(true)
+            .build();
+        let page = self.clone();
+        dialog.open(Some(&window), gio::Cancellable::NONE, move |result| {
+            let Some(path) = result.ok().and_then(|file| file.path()) else {
+                return;
+            };
+            let profile = match MetadataProfile::read(&path) {
+                Ok(profile) => profile,
+                Err(e) => {
+                    page.show_toast(&format!("{}: {:#}", gettext("Cannot import this file"), e));
+                    return;
                 }
+            };
+            let plan = page
+                .imp()
+                .storage
+                .borrow_mut()
+                .plan_import(&profile, &page.live_keys());
+            if plan.conflicts.is_empty() {
+                page.finish_import(&plan, &HashSet::new());
+            } else {
+                page.show_import_conflicts(plan);
             }
         });
     }
 
-    /// Request a refresh from the main window.
+    /// Storage keys of the rules shown.
+    fn live_keys(&self) -> HashSet<String> {
+        store_models::<PortObject>(&self.port_store())
+            .iter()
+            .map(|p| {
+                PortStorage::make_key(&p.port_spec(), &p.protocol, p.zone.as_deref().unwrap_or(""))
+            })
+            .collect()
+    }
+
+    /// Let the user pick which local names the imported ones replace.
+    fn show_import_conflicts(&self, plan: ImportPlan) {
+        let dialog = adw::Dialog::builder()
+            .title(gettext("Import Port Names"))
+            .content_width(520)
+            .content_height(560)
+            .build();
+
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&adw::HeaderBar::new());
+
+        let prefs = adw::PreferencesPage::new();
+        let group = adw::PreferencesGroup::builder()
+            .title(gettext("Conflicting Names"))
+            .description(gettext(
+                "These rules already have another name here. Check the ones to take from the file; \
+                 the others keep their name.",
+            ))
+            .build();
+        let mut checks: Vec<(String, gtk4::CheckButton)> = Vec::new();
+        for conflict in &plan.conflicts {
+            let check = gtk4::CheckButton::builder()
+                .valign(gtk4::Align::Center)
+                .build();
+            let row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&conflict.key).as_str())
+                .subtitle(
+                    glib::markup_escape_text(
+                        &gettext("Here: %s — File: %s")
+                            .replacen("%s", &conflict.local.name, 1)
+                            .replacen("%s", &conflict.imported.name, 1),
+                    )
+                    .as_str(),
+                )
+                .activatable_widget(&check)
+                .build();
+            row.add_prefix(&check);
+            group.add(&row);
+            checks.push((conflict.key.clone(), check));
+        }
+        prefs.add(&group);
+
+        let import_button = gtk4::Button::builder()
+            .label(gettext("Import"))
+            .css_classes(vec!["suggested-action".to_string(), "pill".to_string()])
+            .halign(gtk4::Align::Center)
+            .margin_top(12)
+            .margin_bottom(12)
+            .build();
+        let page = self.clone();
+        let dialog_for_import = dialog.clone();
+        import_button.connect_clicked(move |_| {
+            let replace: HashSet<String> = checks
+                .iter()
+                .filter(|(_, check)| check.is_active())
+                .map(|(key, _)| key.clone())
+                .collect();
+            dialog_for_import.close();
+            page.finish_import(&plan, &replace);
+        });
+
+        toolbar.set_content(Some(&prefs));
+        toolbar.add_bottom_bar(&import_button);
+        dialog.set_child(Some(&toolbar));
+
+        if let Some(window) = self.main_window() {
+            dialog.present(Some(&window));
+        }
+    }
+
+    /// Apply an import and report how it went.
+    fn finish_import(&self, plan: &ImportPlan, replace: &HashSet<String>) {
+        let written = self.imp().storage.borrow_mut().apply_import(plan, replace);
+        let mut message = gettext("Imported %d names").replace("%d", &written.to_string());
+        if plan.unchanged > 0 {
+            message.push_str(
+                &gettext(", %d already matching").replace("%d", &plan.unchanged.to_string()),
+            );
+        }
+        if plan.skipped > 0 {
+            message.push_str(
+                &gettext(", %d without a matching rule here")
+                    .replace("%d", &plan.skipped.to_string()),
+            );
+        }
+        self.show_toast(&message);
+        if written > 0 {
+            self.request_refresh();
+        }
+    }
+
+    /// Have the main window refresh what shows ports.
     fn request_refresh(&self) {
         if let Some(root) = self.root() {
             if let Some(window) = root.downcast_ref::<gtk4::Window>() {
                 if let Some(main_window) = window.downcast_ref::<super::MainWindow>() {
-                    main_window.refresh_data();
+                    main_window.invalidate(Invalidation::PortsChanged);
                 }
             }
         }
     }
 
+    fn main_window(&self) -> Option<super::MainWindow> {
+        self.root()
+            .and_then(|root| root.downcast::<gtk4::Window>().ok())
+            .and_then(|window| window.downcast::<super::MainWindow>().ok())
+    }
+
     /// Show a toast message.
     fn show_toast(&self, message: &str) {
         if let Some(root) = self.root() {
@@ -665,6 +1694,15 @@ impl PortsPage {
         }
     }
 
+    /// Show `message`, with an Undo button when the change left operation
+    /// journal entry `undo`.
+    fn show_undo_toast(&self, message: &str, undo: Option<u64>) {
+        match (undo, self.main_window()) {
+            (Some(id), Some(window)) => window.show_undo_toast(message, id),
+            _ => self.show_toast(message),
+        }
+    }
+
     /// Helper to clear all rows from a PreferencesGroup.
     /// Stores row references in the imp module for safe removal.
     fn clear_preferences_group_rows(
@@ -689,8 +1727,12 @@ impl PortsPage {
     fn show_add_dialog(&self) {
         let imp = self.imp();
 
+        // New rules go to the zone on view, else to the default zone
+        let selected_zone = imp.selected_zone.borrow().clone();
         let current_zone = imp.current_zone.borrow().clone();
-        let default_zone = if current_zone.is_empty() {
+        let default_zone = if !selected_zone.is_empty() {
+            selected_zone
+        } else if current_zone.is_empty() {
             "public".to_string()
         } else {
             current_zone
@@ -834,6 +1876,19 @@ impl PortsPage {
             .build();
         options_group.add(&permanent_row);
 
+        // Temporary rules are removed again when they expire
+        let expiry_row = adw::ComboRow::builder()
+            .title(gettext("Remove After"))
+            .subtitle(gettext("You are notified 10 minutes before"))
+            .model(&gtk4::StringList::new(&[
+                &gettext("Never"),
+                &gettext("1 hour"),
+                &gettext("8 hours"),
+                &gettext("1 day"),
+            ]))
+            .build();
+        options_group.add(&expiry_row);
+
         content.append(&options_group);
 
         dialog.set_extra_child(Some(&content));
@@ -882,6 +1937,15 @@ impl PortsPage {
                 let protocol_idx = protocol_row.selected();
                 let action = action_row.selected(); // 0=Allow, 1=Block
                 let permanent = permanent_row.is_active();
+                let lifetime = EXPIRY_CHOICES
+                    .get(expiry_row.selected() as usize)
+                    .copied()
+                    .unwrap_or(0);
+                let expires_at = if lifetime > 0 {
+                    chrono::Utc::now().timestamp() + lifetime
+                } else {
+                    0
+                };
 
                 // Collect selected zones
                 let selected_zones: Vec<String> = zone_switches_clone.borrow()
@@ -913,30 +1977,44 @@ impl PortsPage {
                         let name = sanitized_name.filter(|n| !n.is_empty());
 
                         // Determine protocols to add
-                        let protocols: Vec<&str> = match protocol_idx {
-                            0 => vec!["tcp"],
-                            1 => vec!["udp"],
-                            _ => vec!["tcp", "udp"],
+                        let protocols: Vec<String> = match protocol_idx {
+                            0 => vec!["tcp".to_string()],
+                            1 => vec!["udp".to_string()],
+                            _ => vec!["tcp".to_string(), "udp".to_string()],
                         };
 
                         // Add rules for each selected zone and protocol
-                        let zone_count = selected_zones.len();
-                        for zone in &selected_zones {
-                            for protocol in &protocols {
-                                page.add_port_rule(
-                                    zone,
-                                    &port_spec,
-                                    protocol,
-                                    action,
-                                    permanent,
-                                    name.clone()
-                                );
+                        let add_all = {
+                            let page = page.clone();
+                            let port_spec = port_spec.clone();
+                            let zones = selected_zones.clone();
+                            let protocols = protocols.clone();
+                            move || {
+                                for zone in &zones {
+                                    for protocol in &protocols {
+                                        page.add_port_rule(
+                                            zone,
+                                            &port_spec,
+                                            protocol,
+                                            action,
+                                            permanent,
+                                            name.clone(),
+                                            expires_at,
+                                        );
+                                    }
+                                }
+
+                                // Show summary toast
+                                if zones.len() > 1 {
+                                    page.show_toast(&format!("Adding port {} to {} zones...", port_spec, zones.len()));
+                                }
                             }
-                        }
+                        };
 
-                        // Show summary toast
-                        if zone_count > 1 {
-                            page.show_toast(&format!("Adding port {} to {} zones...", port_spec, zone_count));
+                        if action == 0 {
+                            add_all();
+                        } else {
+                            page.guard_block(start, end, selected_zones, protocols, add_all);
                         }
                     }
                     None => {
@@ -955,7 +2033,71 @@ impl PortsPage {
         }
     }
 
-    /// Add a port rule (Allow or Block incoming traffic).
+    /// Call `add` to block ports `start..=end`, warning first when remote
+    /// SSH sessions come in on them and offering a trial of the block.
+    fn guard_block(
+        &self,
+        start: u16,
+        end: u16,
+        zones: Vec<String>,
+        protocols: Vec<String>,
+        add: impl Fn() + 'static,
+    ) {
+        let page = self.clone();
+        let ranges = protocols
+            .iter()
+            .map(|protocol| (start, end, protocol.clone()))
+            .collect();
+        lockout_guard::with_sessions_on(ranges, move |sessions| {
+            let window = match page.main_window() {
+                Some(window) if !sessions.is_empty() => window,
+                _ => {
+                    add();
+                    return;
+                }
+            };
+            let rules: Vec<(String, String)> = zones
+                .iter()
+                .flat_map(|zone| {
+                    protocols.iter().filter_map(move |protocol| {
+                        let protocol = validate_protocol(protocol)?;
+                        let spec = format_port_spec(start, end);
+                        Some((
+                            zone.clone(),
+                            RichRule::port_block(&spec, protocol).to_string(),
+                        ))
+                    })
+                })
+                .collect();
+            let added = rules.clone();
+            let trial = TrialChange {
+                apply: Box::new(move |client| {
+                    for (zone, rule) in &added {
+                        client.add_rich_rule(zone, rule, false)?;
+                    }
+                    Ok(())
+                }),
+                revert: Box::new(move |client| {
+                    for (zone, rule) in &rules {
+                        client.remove_rich_rule(zone, rule, false)?;
+                    }
+                    Ok(())
+                }),
+            };
+            lockout_guard::present_lockout_guard(
+                &window,
+                &format!("Blocking port {}", format_port_spec(start, end)),
+                &sessions,
+                trial,
+                add,
+                || {},
+            );
+        });
+    }
+
+    /// Add a port rule (Allow or Block incoming traffic), removed again at
+    /// `expires_at` (Unix seconds) unless that is 0.
+    #[allow(clippy::too_many_arguments)]
     fn add_port_rule(
         &self,
         zone: &str,
@@ -964,6 +2106,7 @@ impl PortsPage {
         action: u32,
         permanent: bool,
         name: Option<String>,
+        expires_at: i64,
     ) {
         let zone = zone.to_string();
         let port = port.to_string();
@@ -975,8 +2118,7 @@ impl PortsPage {
             let port_clone = port.clone();
             let protocol_clone = protocol.clone();
 
-            let result = gtk4::gio::spawn_blocking(move || {
-                let mut client = crate::firewall::FirewallClient::new();
+            let result = crate::firewall::call(move |client| {
                 if let Err(e) = client.connect() {
                     return Err(anyhow::anyhow!("Not connected to firewalld: {}", e));
                 }
@@ -990,10 +2132,7 @@ impl PortsPage {
                     // No family attribute: the rule must cover IPv4 AND IPv6,
                     // otherwise the port stays reachable over IPv6.
                     if let Some(valid_proto) = validate_protocol(&protocol_clone) {
-                        let rule = format!(
-                            "rule port port=\"{}\" protocol=\"{}\" reject",
-                            port_clone, valid_proto
-                        );
+                        let rule = RichRule::port_block(&port_clone, valid_proto).to_string();
                         client.add_rich_rule(&zone_clone, &rule, permanent)?
                     } else {
                         return Err(anyhow::anyhow!("Invalid protocol: {}", protocol_clone));
@@ -1008,19 +2147,37 @@ impl PortsPage {
                 } else {
                     gettext("Port blocked (rejected)")
                 };
-                Ok((msg, outcome))
+                let probe = probe_port(
+                    client,
+                    &[zone_clone],
+                    &port_clone,
+                    &[protocol_clone],
+                    if action == 0 {
+                        Expectation::Open
+                    } else {
+                        Expectation::Closed
+                    },
+                )
+                .ok();
+                Ok((msg, outcome, probe))
             })
             .await;
 
             match result {
-                Ok(Ok((msg, outcome))) => {
+                Ok(Ok((msg, outcome, probe))) => {
                     if outcome.failed() {
                         page.show_toast(&format!(
                             "Port {}/{}: {} for this session only — saving permanently failed",
                             port, protocol, msg
                         ));
                     } else {
-                        page.show_toast(&format!("Port {}/{}: {}", port, protocol, msg));
+                        page.show_toast(&format!(
+                            "Port {}/{}: {}{}",
+                            port,
+                            protocol,
+                            msg,
+                            probe_suffix(probe.as_ref())
+                        ));
                     }
 
                     // Save rule metadata
@@ -1042,6 +2199,7 @@ impl PortsPage {
                         } else {
                             "Incoming: Blocked".to_string()
                         };
+                        metadata.expires_at = expires_at;
                         page.imp().storage.borrow_mut().set(key, metadata);
                     }
 
@@ -1070,7 +2228,7 @@ impl PortsPage {
     ) {
         // Convert to new method - only supports incoming now
         let action = 0; // Allow
-        self.add_port_rule(zone, port, protocol, action, permanent, name);
+        self.add_port_rule(zone, port, protocol, action, permanent, name, 0);
     }
 
     /// Show the edit port dialog.
@@ -1104,6 +2262,12 @@ impl PortsPage {
             .build();
         details_group.add(&name_entry);
 
+        let port_entry = adw::EntryRow::builder()
+            .title(gettext("Port or Range (e.g. 8080 or 10-20)"))
+            .text(port.port_spec())
+            .build();
+        details_group.add(&port_entry);
+
         // Protocol selection
         // Determine initial generic selection
         let has_tcp = port.protocols.contains(&"tcp".to_string());
@@ -1230,21 +2394,28 @@ impl PortsPage {
         dialog.add_response("save", "_Save");
         dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
 
-        // Gate Save on a valid name so an invalid edit can't close the dialog
+        // Gate Save on a valid name and port so an invalid edit can't close
+        // the dialog
         {
             let dialog = dialog.clone();
             let name_entry_v = name_entry.clone();
-            let revalidate = move || {
+            let port_entry_v = port_entry.clone();
+            let revalidate = Rc::new(move || {
                 let name_ok = validate_port_name(&name_entry_v.text()).is_some();
-                if name_ok {
-                    name_entry_v.remove_css_class("error");
-                } else {
-                    name_entry_v.add_css_class("error");
+                let port_ok = parse_port_spec(&port_entry_v.text()).is_some();
+                for (entry, ok) in [(&name_entry_v, name_ok), (&port_entry_v, port_ok)] {
+                    if ok {
+                        entry.remove_css_class("error");
+                    } else {
+                        entry.add_css_class("error");
+                    }
                 }
-                dialog.set_response_enabled("save", name_ok);
-            };
+                dialog.set_response_enabled("save", name_ok && port_ok);
+            });
             revalidate();
-            name_entry.connect_changed(move |_| revalidate());
+            let on_name = revalidate.clone();
+            name_entry.connect_changed(move |_| on_name());
+            port_entry.connect_changed(move |_| revalidate());
         }
 
         let zone_switches_clone = zone_switches.clone();
@@ -1277,6 +2448,11 @@ impl PortsPage {
                 }
                 let name = sanitized_name.filter(|n| !n.is_empty());
 
+                let Some(range) = parse_port_spec(&port_entry.text()) else {
+                    page.show_toast(&gettext("Invalid port. Enter a number from 1 to 65535 or a range such as 6000-6100."));
+                    return;
+                };
+
                 let protocols: Vec<&str> = match protocol_idx {
                     0 => vec!["tcp"],
                     1 => vec!["udp"],
@@ -1284,7 +2460,7 @@ impl PortsPage {
                 };
 
                 // apply_edit will remove old rules and add new ones
-                page.apply_port_edit(&port_original, &selected_zones, &protocols, action_idx, permanent, name);
+                page.apply_port_edit(&port_original, range, &selected_zones, &protocols, action_idx, permanent, name);
             }
         });
 
@@ -1296,10 +2472,13 @@ impl PortsPage {
         }
     }
 
-    /// Apply edits to a port: remove old rules, add new ones.
+    /// Apply edits to a port: remove old rules, add new ones for the port or
+    /// `(start, end)` range.
+    #[allow(clippy::too_many_arguments)]
     fn apply_port_edit(
         &self,
         original: &ConsolidatedPort,
+        (start, end): (u16, u16),
         new_zones: &[String],
         new_protocols: &[&str],
         action: u32,
@@ -1320,12 +2499,11 @@ impl PortsPage {
         let raw_rules = original.raw_rules.clone();
 
         glib::spawn_future_local(async move {
-            let port_num = original.number;
-            let port_end = original.end_number;
             let port_str = original.port_spec();
+            let new_spec = format_port_spec(start, end);
+            let new_spec_after = new_spec.clone();
 
-            let result = gtk4::gio::spawn_blocking(move || {
-                let mut client = crate::firewall::FirewallClient::new();
+            let result = crate::firewall::call(move |client| {
                 if let Err(e) = client.connect() {
                     return Err(anyhow::anyhow!("Not connected to firewalld: {}", e));
                 }
@@ -1358,13 +2536,10 @@ impl PortsPage {
                     for protocol in &new_protocols {
                         if let Some(valid_proto) = validate_protocol(protocol) {
                             let outcome = if action == 0 {
-                                client.add_port(zone, &port_str, valid_proto, permanent)?
+                                client.add_port(zone, &new_spec, valid_proto, permanent)?
                             } else {
                                 // Family-less rule: covers IPv4 and IPv6
-                                let rule = format!(
-                                    "rule port port=\"{}\" protocol=\"{}\" reject",
-                                    port_str, valid_proto
-                                );
+                                let rule = RichRule::port_block(&new_spec, valid_proto).to_string();
                                 client.add_rich_rule(zone, &rule, permanent)?
                             };
                             if outcome.failed() {
@@ -1376,29 +2551,44 @@ impl PortsPage {
                     }
                 }
 
-                Ok(permanent_failed)
+                let expected = if action == 0 {
+                    Expectation::Open
+                } else {
+                    Expectation::Closed
+                };
+                let probe =
+                    probe_port(client, &new_zones, &new_spec, &new_protocols, expected).ok();
+                Ok((permanent_failed, probe))
             })
             .await;
 
             match result {
-                Ok(Ok(permanent_failed)) => {
+                Ok(Ok((permanent_failed, probe))) => {
                     let port_spec = original_after.port_spec();
                     if permanent_failed {
                         page.show_toast(&format!(
                             "Updated port {} for this session only — saving permanently failed",
-                            port_spec
+                            new_spec_after
                         ));
                     } else {
-                        page.show_toast(&format!("Updated port {}", port_spec));
+                        page.show_toast(&format!(
+                            "Updated port {}{}",
+                            new_spec_after,
+                            probe_suffix(probe.as_ref())
+                        ));
                     }
 
                     // Update storage: remove old keys, add new keys
                     let mut storage = page.imp().storage.borrow_mut();
 
-                    // Remove old keys
+                    // Remove old keys, keeping a temporary rule's expiry
+                    let mut expires_at = 0;
                     for zone in &original_after.zones {
                         for protocol in &original_after.protocols {
                             let key = PortStorage::make_key(&port_spec, protocol, zone);
+                            if let Some(old) = storage.get(&key) {
+                                expires_at = expires_at.max(old.expires_at);
+                            }
                             storage.remove(&key);
                         }
                     }
@@ -1406,10 +2596,10 @@ impl PortsPage {
                     // Add new keys
                     for zone in &new_zones_after {
                         for protocol in &new_protocols_after {
-                            let key = PortStorage::make_key(&port_spec, protocol, zone);
+                            let key = PortStorage::make_key(&new_spec_after, protocol, zone);
                             let mut metadata = PortMetadata::new(name.as_deref().unwrap_or(""));
-                            metadata.port = port_num;
-                            metadata.end_port = port_end.filter(|&e| e > port_num).unwrap_or(0);
+                            metadata.port = start;
+                            metadata.end_port = if end > start { end } else { 0 };
                             metadata.protocol = protocol.clone();
                             metadata.zone = zone.clone();
                             metadata.incoming_action = if action == 0 {
@@ -1422,6 +2612,7 @@ impl PortsPage {
                             } else {
                                 "Incoming: Blocked".to_string()
                             };
+                            metadata.expires_at = expires_at;
                             storage.set(key, metadata);
                         }
                     }
@@ -1483,11 +2674,31 @@ mod imp {
         pub client: RefCell<Option<Rc<RefCell<FirewallClient>>>>,
         pub storage: RefCell<PortStorage>,
         // Track rows we've added for safe removal
-        pub ports_rows: RefCell<Vec<adw::ActionRow>>,
         pub summary_rows: RefCell<Vec<adw::ActionRow>>,
-        pub blocked_rows: RefCell<Vec<adw::ActionRow>>,
+        // The open and blocked lists over `row_store`, and what shows
+        // when they are empty
+        pub row_store: OnceCell<gio::ListStore>,
+        pub open_model: RefCell<Option<gtk4::FilterListModel>>,
+        pub blocked_model: RefCell<Option<gtk4::FilterListModel>>,
+        pub open_list: RefCell<Option<gtk4::ScrolledWindow>>,
+        pub blocked_list: RefCell<Option<gtk4::ScrolledWindow>>,
+        pub ports_placeholder: RefCell<Option<adw::ActionRow>>,
+        pub blocked_placeholder: RefCell<Option<adw::ActionRow>>,
         // Cached zone names for the dropdown
         pub cached_zones: RefCell<Vec<String>>,
+        // Zone whose rules are shown; empty for all zones
+        pub selected_zone: RefCell<String>,
+        pub zone_dropdown: RefCell<Option<adw::ComboRow>>,
+        // Search, protocol and verdict narrowing both lists
+        pub port_filter: RefCell<PortFilter>,
+        pub rule_filter: OnceCell<gtk4::CustomFilter>,
+        // Last port list, re-rendered when hit counters arrive
+        pub port_store: OnceCell<gio::ListStore>,
+        pub consolidated: RefCell<Vec<ConsolidatedPort>>,
+        // Hit counters, once read through the privileged helper
+        pub counters: RefCell<Option<RuleCounters>>,
+        pub counters_row: RefCell<Option<adw::ActionRow>>,
+        pub cleanup_button: RefCell<Option<gtk4::Button>>,
     }
 
     #[glib::object_subclass]
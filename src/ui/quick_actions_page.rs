@@ -10,13 +10,19 @@
 //! - Firewall management: enable, disable, reload, panic mode
 //! - Network management: restart NetworkManager
 //! - Service management: restart common services
+//! - Guided travel hardening wizard
+//! - Role checklists (web server, workstation, home NAS)
+//! - Actions on firewalld, NetworkManager and the SSH server pause, with the
+//!   reason shown inline, while the service is starting, stopping or not
+//!   running
 //!
 //! # Architecture
 //!
-//! Actions are executed via the QuickActionsManager which routes to
-//! appropriate D-Bus services (firewalld, systemd).
+//! Actions run as typed operations through the OperationRunner, which routes
+//! them to the appropriate D-Bus services (firewalld, systemd).
 
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 
 use gtk4::glib;
 use gtk4::prelude::*;
@@ -25,10 +31,12 @@ use libadwaita as adw;
 use libadwaita::prelude::*;
 use tracing::{error, info};
 
+use super::density::{self, Role};
 use crate::admin::{
-    ActionCategory, AdminAction, AdminActionResult, QuickActionsManager, QUICK_ACTIONS,
+    ActionCategory, AdminAction, Operation, OperationResult, OperationRunner, QUICK_ACTIONS,
 };
 use crate::i18n::gettext;
+use crate::systemd::{UnitActivity, UnitStatus, WatchedUnit};
 
 glib::wrapper! {
     /// Page with quick one-click admin actions.
@@ -60,11 +68,8 @@ impl QuickActionsPage {
         let header = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Horizontal)
             .spacing(12)
-            .margin_start(24)
-            .margin_end(24)
-            .margin_top(24)
-            .margin_bottom(12)
             .build();
+        density::track(&header, Role::PageHeader);
 
         let title_box = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Vertical)
@@ -103,13 +108,15 @@ impl QuickActionsPage {
 
         let content = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Vertical)
-            .spacing(24)
-            .margin_top(24)
-            .margin_bottom(24)
-            .margin_start(24)
-            .margin_end(24)
             .hexpand(true)
             .build();
+        density::track(
+            &content,
+            Role::Content {
+                margin: 24,
+                spacing: 24,
+            },
+        );
 
         // Warning banner (same style as Services page)
         let warning_banner = adw::Banner::builder()
@@ -133,6 +140,52 @@ impl QuickActionsPage {
             .description(gettext("Manage system services"))
             .build();
 
+        // Guided multi-step hardening
+        let guided_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "Chain several changes, each previewed and optional",
+            ))
+            .build();
+        let travel_row = adw::ActionRow::builder()
+            .title(gettext("Secure for Travel"))
+            .subtitle(gettext(
+                "Public zone, no file sharing, random MAC addresses, tighter SSH and screen lock",
+            ))
+            .activatable(true)
+            .build();
+        travel_row.add_prefix(&gtk4::Image::from_icon_name("airplane-mode-symbolic"));
+        let travel_btn = gtk4::Button::builder()
+            .label(gettext("Start…"))
+            .valign(gtk4::Align::Center)
+            .build();
+        travel_row.add_suffix(&travel_btn);
+        travel_row.set_activatable_widget(Some(&travel_btn));
+        let page = self.clone();
+        travel_btn.connect_clicked(move |_| page.open_travel_wizard());
+        guided_group.add(&travel_row);
+
+        let checklist_row = adw::ActionRow::builder()
+            .title(gettext("Security Checklists"))
+            .subtitle(gettext(
+                "Step-by-step lists for a web server, a workstation or a home NAS",
+            ))
+            .activatable(true)
+            .build();
+        checklist_row.add_prefix(&gtk4::Image::from_icon_name("checkbox-checked-symbolic"));
+        let checklist_btn = gtk4::Button::builder()
+            .label(gettext("Open…"))
+            .valign(gtk4::Align::Center)
+            .build();
+        checklist_row.add_suffix(&checklist_btn);
+        checklist_row.set_activatable_widget(Some(&checklist_btn));
+        let page = self.clone();
+        checklist_btn.connect_clicked(move |_| {
+            if let Some(window) = page.root().and_downcast::<gtk4::Window>() {
+                super::role_checklists::present_role_checklists(&window);
+            }
+        });
+        guided_group.add(&checklist_row);
+
         for action in QUICK_ACTIONS.iter() {
             let row = self.create_action_row(action);
 
@@ -158,6 +211,11 @@ impl QuickActionsPage {
             &gettext("Services"),
         ));
         content.append(&services_group);
+        content.append(&Self::create_section_header(
+            "starred-symbolic",
+            &gettext("Guided"),
+        ));
+        content.append(&guided_group);
 
         scrolled.set_child(Some(&content));
         toast_overlay.set_child(Some(&scrolled));
@@ -210,8 +268,25 @@ impl QuickActionsPage {
             execute_btn.add_css_class("suggested-action");
         }
 
+        // Why the action is paused, while the service it needs is busy
+        if action.operation.depends_on().is_some() {
+            let reason_label = gtk4::Label::builder()
+                .css_classes(vec!["caption".to_string(), "warning".to_string()])
+                .valign(gtk4::Align::Center)
+                .wrap(true)
+                .max_width_chars(28)
+                .visible(false)
+                .build();
+            row.add_suffix(&reason_label);
+            self.imp().unit_rows.borrow_mut().push((
+                action.operation,
+                execute_btn.clone(),
+                reason_label,
+            ));
+        }
+
         // Clone values for the closure
-        let action_id = action.id.to_string();
+        let operation = action.operation;
         let action_title = action.title.to_string();
         let is_destructive = action.destructive;
         let page = self.clone();
@@ -219,16 +294,16 @@ impl QuickActionsPage {
         execute_btn.connect_clicked(move |button| {
             if is_destructive {
                 // Show confirmation dialog for destructive actions
-                page.show_confirmation_dialog(&action_id, &action_title, button);
+                page.show_confirmation_dialog(operation, &action_title, button);
             } else {
-                page.execute_action(&action_id, button);
+                page.execute_action(operation, button);
             }
         });
 
         row.add_suffix(&execute_btn);
 
         // Make row activatable as well
-        let action_id = action.id.to_string();
+        let operation = action.operation;
         let is_destructive = action.destructive;
         let page = self.clone();
 
@@ -236,10 +311,13 @@ impl QuickActionsPage {
             // Find the button in the row suffix
             if let Some(suffix) = row.last_child() {
                 if let Ok(btn) = suffix.downcast::<gtk4::Button>() {
+                    if !btn.is_sensitive() {
+                        return;
+                    }
                     if is_destructive {
-                        page.show_confirmation_dialog(&action_id, row.title().as_str(), &btn);
+                        page.show_confirmation_dialog(operation, row.title().as_str(), &btn);
                     } else {
-                        page.execute_action(&action_id, &btn);
+                        page.execute_action(operation, &btn);
                     }
                 }
             }
@@ -249,7 +327,12 @@ impl QuickActionsPage {
     }
 
     /// Show confirmation dialog for destructive actions.
-    fn show_confirmation_dialog(&self, action_id: &str, action_title: &str, button: &gtk4::Button) {
+    fn show_confirmation_dialog(
+        &self,
+        operation: Operation,
+        action_title: &str,
+        button: &gtk4::Button,
+    ) {
         let dialog = adw::AlertDialog::builder()
             .heading(gettext("Confirm Action"))
             .body(format!(
@@ -267,12 +350,11 @@ impl QuickActionsPage {
         dialog.set_close_response("cancel");
 
         let page = self.clone();
-        let action_id = action_id.to_string();
         let button = button.clone();
 
         dialog.connect_response(None, move |dialog, response| {
             if response == "confirm" {
-                page.execute_action(&action_id, &button);
+                page.execute_action(operation, &button);
             }
             dialog.close();
         });
@@ -284,13 +366,13 @@ impl QuickActionsPage {
         }
     }
 
-    /// Execute an action by ID.
-    fn execute_action(&self, action_id: &str, button: &gtk4::Button) {
+    /// Run an operation.
+    fn execute_action(&self, operation: Operation, button: &gtk4::Button) {
         let page = self.clone();
-        let action_id_owned = action_id.to_string();
         let button_clone = button.clone();
 
         // Disable button during execution
+        self.imp().running.borrow_mut().insert(operation.id());
         button.set_sensitive(false);
         button.set_label(&gettext("Running..."));
 
@@ -302,21 +384,26 @@ impl QuickActionsPage {
             label.add_css_class("dim-label");
         }
 
-        info!("Executing action: {}", action_id);
+        info!("Executing action: {}", operation.id());
 
         glib::spawn_future_local(async move {
-            let result = gtk4::gio::spawn_blocking({
-                let action_id = action_id_owned.clone();
-                move || {
-                    let mut manager = QuickActionsManager::new();
-                    manager.execute(&action_id)
-                }
-            })
-            .await;
+            let result =
+                gtk4::gio::spawn_blocking(move || OperationRunner::new().run_revertible(operation))
+                    .await;
 
             match result {
-                Ok(action_result) => {
-                    page.handle_action_result(&action_id_owned, &action_result);
+                Ok((action_result, undo)) => {
+                    page.handle_action_result(&action_result);
+                    // Risky changes revert themselves unless kept
+                    if let Some(undo) = undo {
+                        if let Some(window) = page
+                            .root()
+                            .and_then(|root| root.downcast::<gtk4::Window>().ok())
+                            .and_then(|window| window.downcast::<super::MainWindow>().ok())
+                        {
+                            window.revert_unless_kept(undo);
+                        }
+                    }
                 }
                 Err(e) => {
                     error!("Task execution failed: {:?}", e);
@@ -324,24 +411,38 @@ impl QuickActionsPage {
                 }
             }
 
-            // Re-enable button
+            // Re-enable button, unless its service is busy meanwhile
+            page.imp().running.borrow_mut().remove(operation.id());
             button_clone.set_sensitive(true);
             button_clone.set_label(&gettext("Execute"));
+            page.update_availability();
         });
     }
 
     /// Handle the result of an action execution.
-    fn handle_action_result(&self, _action_id: &str, result: &AdminActionResult) {
+    fn handle_action_result(&self, result: &OperationResult) {
         // Create a user-friendly message
         let message = if result.success {
-            format!("✓ {}", result.message)
+            format!(
+                "✓ {} ({:.1} s)",
+                result.message,
+                result.duration.as_secs_f64()
+            )
         } else {
-            format!("✗ {}", result.message)
+            let title = result
+                .operation
+                .action()
+                .map(|a| gettext(a.title))
+                .unwrap_or_else(|| result.operation.id().to_string());
+            format!("✗ {}: {}", title, result.message)
         };
 
         // Update status label with appropriate styling
         if let Some(label) = self.imp().status_label.borrow().as_ref() {
             label.set_label(&message);
+            // The steps taken, as the equivalent commands
+            let details = result.details.join("\n");
+            label.set_tooltip_text((!details.is_empty()).then_some(details.as_str()));
             // Update styling based on success/failure
             label.remove_css_class("dim-label");
             label.remove_css_class("success");
@@ -362,6 +463,51 @@ impl QuickActionsPage {
         self.show_toast(&result.message, !result.success);
     }
 
+    /// Record the live state of a watched service and pause or resume the
+    /// actions that depend on it.
+    pub fn set_unit_activity(&self, status: UnitStatus) {
+        self.imp()
+            .unit_states
+            .borrow_mut()
+            .insert(status.unit, status.activity);
+        self.update_availability();
+    }
+
+    /// Enable each action whose service allows it, and explain the others.
+    fn update_availability(&self) {
+        let imp = self.imp();
+        let states = imp.unit_states.borrow();
+        for (operation, button, reason_label) in imp.unit_rows.borrow().iter() {
+            // Leave actions that are running alone
+            if imp.running.borrow().contains(operation.id()) {
+                continue;
+            }
+            let Some((unit, needs_running)) = operation.depends_on() else {
+                continue;
+            };
+            let reason = states
+                .get(&unit)
+                .and_then(|activity| activity.blocked_reason(needs_running));
+            button.set_sensitive(reason.is_none());
+            match reason {
+                Some(reason) => {
+                    reason_label.set_label(&gettext(reason).replace("%s", &gettext(unit.label())));
+                    reason_label.set_visible(true);
+                }
+                None => reason_label.set_visible(false),
+            }
+        }
+    }
+
+    /// Open the travel hardening wizard.
+    fn open_travel_wizard(&self) {
+        let Some(window) = self.root().and_downcast::<gtk4::Window>() else {
+            return;
+        };
+        let page = self.clone();
+        super::travel_wizard::present_travel_wizard(&window, move || page.request_refresh());
+    }
+
     /// Request a global refresh from the main window.
     fn request_refresh(&self) {
         if let Some(root) = self.root() {
@@ -417,6 +563,13 @@ mod imp {
     pub struct QuickActionsPage {
         pub toast_overlay: RefCell<Option<adw::ToastOverlay>>,
         pub status_label: RefCell<Option<gtk4::Label>>,
+        /// Actions that depend on a watched service, with their button and
+        /// the label explaining why they are paused.
+        pub unit_rows: RefCell<Vec<(Operation, gtk4::Button, gtk4::Label)>>,
+        /// Ids of the operations running now.
+        pub running: RefCell<HashSet<&'static str>>,
+        /// Last known state of each watched service.
+        pub unit_states: RefCell<HashMap<WatchedUnit, UnitActivity>>,
     }
 
     #[glib::object_subclass]
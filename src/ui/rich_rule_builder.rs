@@ -0,0 +1,567 @@
+// Security Center - Rich rule builder dialog
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Guided editor for firewalld rich rules. Each part of a rule — family,
+//! priority, source, destination, what it matches, logging, auditing and
+//! action — has its own field; the rule is assembled as a [`RichRule`],
+//! shown as firewalld will store it and checked before it can be saved.
+//! Editing an existing rule replaces it, and puts it back if the new one
+//! is refused.
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use super::app_state::Invalidation;
+use super::MainWindow;
+use crate::i18n::gettext;
+use crate::models::{
+    Family, RichRule, RuleAction, RuleAddress, RuleElement, RuleLog, RULE_LOG_LEVELS,
+    RULE_PORT_PROTOCOLS,
+};
+
+/// Kinds of element offered, in the order of the "Match" list.
+const ELEMENT_LABELS: [&str; 8] = [
+    "Any traffic",
+    "Service",
+    "Port",
+    "Protocol",
+    "Source port",
+    "ICMP type",
+    "ICMP block",
+    "Masquerade",
+];
+
+/// Actions offered, in the order of the "Action" list.
+const ACTION_LABELS: [&str; 4] = ["None (log or audit only)", "Accept", "Reject", "Drop"];
+
+/// Whether the builder can show every part of `rule`, so that editing it
+/// loses nothing. Forward ports, marks and audit limits are kept as they
+/// are.
+pub fn can_edit(rule: &RichRule) -> bool {
+    !matches!(rule.element, Some(RuleElement::ForwardPort { .. }))
+        && !matches!(rule.action, Some(RuleAction::Mark(_)))
+        && !matches!(rule.audit, Some(Some(_)))
+}
+
+/// The fields of the builder.
+#[derive(Clone)]
+struct Form {
+    zone: adw::ComboRow,
+    family: adw::ComboRow,
+    priority: adw::SpinRow,
+    source: adw::EntryRow,
+    source_invert: adw::SwitchRow,
+    destination: adw::EntryRow,
+    destination_invert: adw::SwitchRow,
+    element: adw::ComboRow,
+    value: adw::EntryRow,
+    protocol: adw::ComboRow,
+    log: adw::ExpanderRow,
+    log_prefix: adw::EntryRow,
+    log_level: adw::ComboRow,
+    log_limit: adw::EntryRow,
+    audit: adw::SwitchRow,
+    action: adw::ComboRow,
+    reject_type: adw::EntryRow,
+    action_limit: adw::EntryRow,
+    permanent: adw::SwitchRow,
+}
+
+impl Form {
+    /// The rule the fields describe.
+    fn rule(&self) -> anyhow::Result<RichRule> {
+        let optional = |row: &adw::EntryRow| {
+            let text = row.text().trim().to_string();
+            (!text.is_empty()).then_some(text)
+        };
+        let address = |row: &adw::EntryRow, invert: &adw::SwitchRow| {
+            optional(row)
+                .map(|text| {
+                    RuleAddress::from_input(&text).map(|mut address| {
+                        address.invert = invert.is_active();
+                        address
+                    })
+                })
+                .transpose()
+        };
+
+        let value = self.value.text().trim().to_string();
+        let protocol = RULE_PORT_PROTOCOLS
+            .get(self.protocol.selected() as usize)
+            .unwrap_or(&RULE_PORT_PROTOCOLS[0])
+            .to_string();
+        let element = match self.element.selected() {
+            1 => Some(RuleElement::Service(value)),
+            2 => Some(RuleElement::Port {
+                port: value,
+                protocol,
+            }),
+            3 => Some(RuleElement::Protocol(value)),
+            4 => Some(RuleElement::SourcePort {
+                port: value,
+                protocol,
+            }),
+            5 => Some(RuleElement::IcmpType(value)),
+            6 => Some(RuleElement::IcmpBlock(value)),
+            7 => Some(RuleElement::Masquerade),
+            _ => None,
+        };
+
+        let action = match self.action.selected() {
+            1 => Some(RuleAction::Accept),
+            2 => Some(RuleAction::Reject(optional(&self.reject_type))),
+            3 => Some(RuleAction::Drop),
+            _ => None,
+        };
+
+        let rule = RichRule {
+            priority: self.priority.value() as i32,
+            family: match self.family.selected() {
+                1 => Some(Family::Ipv4),
+                2 => Some(Family::Ipv6),
+                _ => None,
+            },
+            source: address(&self.source, &self.source_invert)?,
+            destination: address(&self.destination, &self.destination_invert)?,
+            element,
+            log: self.log.enables_expansion().then(|| RuleLog {
+                prefix: optional(&self.log_prefix),
+                level: match self.log_level.selected() {
+                    0 => None,
+                    n => RULE_LOG_LEVELS.get(n as usize - 1).map(|l| l.to_string()),
+                },
+                limit: optional(&self.log_limit),
+            }),
+            audit: self.audit.is_active().then_some(None),
+            action_limit: action.as_ref().and_then(|_| optional(&self.action_limit)),
+            action,
+        };
+        rule.validate()?;
+        Ok(rule)
+    }
+
+    /// Show `rule` in the fields.
+    fn fill(&self, rule: &RichRule) {
+        self.family.set_selected(match rule.family {
+            None => 0,
+            Some(Family::Ipv4) => 1,
+            Some(Family::Ipv6) => 2,
+        });
+        self.priority.set_value(rule.priority as f64);
+        if let Some(source) = &rule.source {
+            self.source.set_text(&source.input_text());
+            self.source_invert.set_active(source.invert);
+        }
+        if let Some(destination) = &rule.destination {
+            self.destination.set_text(&destination.input_text());
+            self.destination_invert.set_active(destination.invert);
+        }
+
+        let select_protocol = |protocol: &str| {
+            let index = RULE_PORT_PROTOCOLS.iter().position(|p| *p == protocol);
+            self.protocol.set_selected(index.unwrap_or(0) as u32);
+        };
+        let (element, value) = match &rule.element {
+            None => (0, ""),
+            Some(RuleElement::Service(name)) => (1, name.as_str()),
+            Some(RuleElement::Port { port, protocol }) => {
+                select_protocol(protocol);
+                (2, port.as_str())
+            }
+            Some(RuleElement::Protocol(protocol)) => (3, protocol.as_str()),
+            Some(RuleElement::SourcePort { port, protocol }) => {
+                select_protocol(protocol);
+                (4, port.as_str())
+            }
+            Some(RuleElement::IcmpType(name)) => (5, name.as_str()),
+            Some(RuleElement::IcmpBlock(name)) => (6, name.as_str()),
+            Some(RuleElement::Masquerade) | Some(RuleElement::ForwardPort { .. }) => (7, ""),
+        };
+        self.element.set_selected(element);
+        self.value.set_text(value);
+
+        if let Some(log) = &rule.log {
+            self.log.set_enable_expansion(true);
+            self.log_prefix
+                .set_text(log.prefix.as_deref().unwrap_or_default());
+            let level = log
+                .level
+                .as_deref()
+                .and_then(|level| RULE_LOG_LEVELS.iter().position(|l| *l == level))
+                .map_or(0, |index| index + 1);
+            self.log_level.set_selected(level as u32);
+            self.log_limit
+                .set_text(log.limit.as_deref().unwrap_or_default());
+        }
+        self.audit.set_active(rule.audit.is_some());
+
+        self.action.set_selected(match &rule.action {
+            None | Some(RuleAction::Mark(_)) => 0,
+            Some(RuleAction::Accept) => 1,
+            Some(RuleAction::Reject(_)) => 2,
+            Some(RuleAction::Drop) => 3,
+        });
+        if let Some(RuleAction::Reject(Some(kind))) = &rule.action {
+            self.reject_type.set_text(kind);
+        }
+        self.action_limit
+            .set_text(rule.action_limit.as_deref().unwrap_or_default());
+    }
+
+    /// Show only the fields that apply to the chosen element and action.
+    fn update_visibility(&self) {
+        let element = self.element.selected();
+        self.value.set_visible(!matches!(element, 0 | 7));
+        self.value.set_title(&gettext(match element {
+            1 => "Service Name (e.g. ssh)",
+            2 => "Port or Range (e.g. 8080 or 10-20)",
+            3 => "Protocol (e.g. icmp or gre)",
+            4 => "Source Port or Range",
+            _ => "ICMP Type (e.g. echo-request)",
+        }));
+        self.protocol.set_visible(matches!(element, 2 | 4));
+
+        let action = self.action.selected();
+        self.reject_type.set_visible(action == 2);
+        self.action_limit.set_visible(action != 0);
+    }
+}
+
+/// Build and present the rule builder anchored to `window`.
+///
+/// `zones` are the zones a rule can be added to, `zone` the one preselected.
+/// When `original` is given the dialog edits that rule of `zone`.
+pub fn present_rich_rule_builder(
+    window: &MainWindow,
+    zones: &[String],
+    zone: &str,
+    original: Option<&str>,
+) {
+    let existing = original.and_then(|text| RichRule::parse(text).ok());
+    let dialog = adw::Dialog::builder()
+        .title(if original.is_some() {
+            gettext("Edit Rich Rule")
+        } else {
+            gettext("New Rich Rule")
+        })
+        .content_width(560)
+        .content_height(720)
+        .build();
+
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&adw::HeaderBar::new());
+
+    let page = adw::PreferencesPage::new();
+
+    // --- Rule ---
+    let rule_group = adw::PreferencesGroup::builder()
+        .title(gettext("Rule"))
+        .build();
+    let zone_refs: Vec<&str> = zones.iter().map(String::as_str).collect();
+    let zone_row = adw::ComboRow::builder()
+        .title(gettext("Zone"))
+        .model(&gtk4::StringList::new(&zone_refs))
+        .selected(zones.iter().position(|z| z == zone).unwrap_or(0) as u32)
+        .build();
+    rule_group.add(&zone_row);
+    let family_row = adw::ComboRow::builder()
+        .title(gettext("Family"))
+        .model(&gtk4::StringList::new(&[
+            &gettext("IPv4 and IPv6"),
+            "IPv4",
+            "IPv6",
+        ]))
+        .build();
+    rule_group.add(&family_row);
+    let priority_row = adw::SpinRow::builder()
+        .title(gettext("Priority"))
+        .subtitle(gettext("Rules with a lower priority are checked first"))
+        .adjustment(&gtk4::Adjustment::new(
+            0.0, -32768.0, 32767.0, 1.0, 10.0, 0.0,
+        ))
+        .build();
+    rule_group.add(&priority_row);
+    page.add(&rule_group);
+
+    // --- Match ---
+    let match_group = adw::PreferencesGroup::builder()
+        .title(gettext("Match"))
+        .description(gettext(
+            "Addresses may be an address, a network such as 192.168.1.0/24, a MAC address or ipset:NAME",
+        ))
+        .build();
+    let source_row = adw::EntryRow::builder()
+        .title(gettext("Source (optional)"))
+        .build();
+    match_group.add(&source_row);
+    let source_invert_row = adw::SwitchRow::builder()
+        .title(gettext("Any Source Except This"))
+        .build();
+    match_group.add(&source_invert_row);
+    let destination_row = adw::EntryRow::builder()
+        .title(gettext("Destination (optional)"))
+        .build();
+    match_group.add(&destination_row);
+    let destination_invert_row = adw::SwitchRow::builder()
+        .title(gettext("Any Destination Except This"))
+        .build();
+    match_group.add(&destination_invert_row);
+    let element_labels: Vec<String> = ELEMENT_LABELS.iter().map(|l| gettext(l)).collect();
+    let element_refs: Vec<&str> = element_labels.iter().map(String::as_str).collect();
+    let element_row = adw::ComboRow::builder()
+        .title(gettext("Traffic"))
+        .model(&gtk4::StringList::new(&element_refs))
+        .build();
+    match_group.add(&element_row);
+    let value_row = adw::EntryRow::new();
+    match_group.add(&value_row);
+    let protocol_row = adw::ComboRow::builder()
+        .title(gettext("Protocol"))
+        .model(&gtk4::StringList::new(&RULE_PORT_PROTOCOLS))
+        .build();
+    match_group.add(&protocol_row);
+    page.add(&match_group);
+
+    // --- Logging ---
+    let logging_group = adw::PreferencesGroup::builder()
+        .title(gettext("Logging"))
+        .build();
+    let log_row = adw::ExpanderRow::builder()
+        .title(gettext("Log Matches"))
+        .subtitle(gettext("Write matched packets to the kernel log"))
+        .show_enable_switch(true)
+        .enable_expansion(false)
+        .build();
+    let log_prefix_row = adw::EntryRow::builder()
+        .title(gettext("Prefix (optional)"))
+        .build();
+    log_row.add_row(&log_prefix_row);
+    let mut level_labels = vec![gettext("Default")];
+    level_labels.extend(RULE_LOG_LEVELS.iter().map(|l| l.to_string()));
+    let level_refs: Vec<&str> = level_labels.iter().map(String::as_str).collect();
+    let log_level_row = adw::ComboRow::builder()
+        .title(gettext("Level"))
+        .model(&gtk4::StringList::new(&level_refs))
+        .build();
+    log_row.add_row(&log_level_row);
+    let log_limit_row = adw::EntryRow::builder()
+        .title(gettext("Rate Limit (optional, e.g. 3/m)"))
+        .build();
+    log_row.add_row(&log_limit_row);
+    logging_group.add(&log_row);
+    let audit_row = adw::SwitchRow::builder()
+        .title(gettext("Audit Matches"))
+        .subtitle(gettext("Record matched packets in the audit log"))
+        .build();
+    logging_group.add(&audit_row);
+    page.add(&logging_group);
+
+    // --- Action ---
+    let action_group = adw::PreferencesGroup::builder()
+        .title(gettext("Action"))
+        .build();
+    let action_labels: Vec<String> = ACTION_LABELS.iter().map(|l| gettext(l)).collect();
+    let action_refs: Vec<&str> = action_labels.iter().map(String::as_str).collect();
+    let action_row = adw::ComboRow::builder()
+        .title(gettext("Action"))
+        .model(&gtk4::StringList::new(&action_refs))
+        .selected(1)
+        .build();
+    action_group.add(&action_row);
+    let reject_type_row = adw::EntryRow::builder()
+        .title(gettext("Reject With (optional ICMP type)"))
+        .build();
+    action_group.add(&reject_type_row);
+    let action_limit_row = adw::EntryRow::builder()
+        .title(gettext("Rate Limit (optional, e.g. 10/s)"))
+        .build();
+    action_group.add(&action_limit_row);
+    let permanent_row = adw::SwitchRow::builder()
+        .title(gettext("Make Permanent"))
+        .subtitle(gettext("Rule persists after reboot"))
+        .active(true)
+        .build();
+    action_group.add(&permanent_row);
+    page.add(&action_group);
+
+    // --- Preview ---
+    let preview_group = adw::PreferencesGroup::builder()
+        .title(gettext("Preview"))
+        .build();
+    let preview_label = gtk4::Label::builder()
+        .wrap(true)
+        .wrap_mode(gtk4::pango::WrapMode::WordChar)
+        .xalign(0.0)
+        .selectable(true)
+        .css_classes(["monospace"])
+        .build();
+    preview_group.add(&preview_label);
+    page.add(&preview_group);
+
+    let form = Form {
+        zone: zone_row,
+        family: family_row,
+        priority: priority_row,
+        source: source_row,
+        source_invert: source_invert_row,
+        destination: destination_row,
+        destination_invert: destination_invert_row,
+        element: element_row,
+        value: value_row,
+        protocol: protocol_row,
+        log: log_row,
+        log_prefix: log_prefix_row,
+        log_level: log_level_row,
+        log_limit: log_limit_row,
+        audit: audit_row,
+        action: action_row,
+        reject_type: reject_type_row,
+        action_limit: action_limit_row,
+        permanent: permanent_row,
+    };
+    if let Some(rule) = &existing {
+        form.fill(rule);
+    }
+
+    let save_button = gtk4::Button::builder()
+        .label(gettext("Save"))
+        .css_classes(["suggested-action", "pill"])
+        .halign(gtk4::Align::Center)
+        .margin_top(12)
+        .margin_bottom(12)
+        .build();
+
+    // Rebuild the preview on every change
+    let refresh = {
+        let form = form.clone();
+        let preview_label = preview_label.clone();
+        let save_button = save_button.clone();
+        move || {
+            form.update_visibility();
+            match form.rule() {
+                Ok(rule) => {
+                    preview_label.set_label(&rule.to_string());
+                    preview_label.remove_css_class("error");
+                    save_button.set_sensitive(true);
+                }
+                Err(e) => {
+                    preview_label.set_label(&e.to_string());
+                    preview_label.add_css_class("error");
+                    save_button.set_sensitive(false);
+                }
+            }
+        }
+    };
+    refresh();
+    for row in [
+        &form.source,
+        &form.destination,
+        &form.value,
+        &form.log_prefix,
+        &form.log_limit,
+        &form.reject_type,
+        &form.action_limit,
+    ] {
+        let refresh = refresh.clone();
+        row.connect_changed(move |_| refresh());
+    }
+    for row in [
+        &form.family,
+        &form.element,
+        &form.protocol,
+        &form.log_level,
+        &form.action,
+    ] {
+        let refresh = refresh.clone();
+        row.connect_selected_notify(move |_| refresh());
+    }
+    for row in [&form.source_invert, &form.destination_invert, &form.audit] {
+        let refresh = refresh.clone();
+        row.connect_active_notify(move |_| refresh());
+    }
+    {
+        let refresh = refresh.clone();
+        form.priority.connect_value_notify(move |_| refresh());
+    }
+    form.log.connect_enable_expansion_notify(move |_| refresh());
+
+    let window_for_save = window.clone();
+    let dialog_for_save = dialog.clone();
+    let zones = zones.to_vec();
+    let original = original.map(|text| (zone.to_string(), text.to_string()));
+    save_button.connect_clicked(move |_| {
+        let Ok(rule) = form.rule() else {
+            return;
+        };
+        let Some(zone) = zones.get(form.zone.selected() as usize).cloned() else {
+            return;
+        };
+        dialog_for_save.close();
+        save_rule(
+            &window_for_save,
+            zone,
+            rule.to_string(),
+            form.permanent.is_active(),
+            original.clone(),
+        );
+    });
+
+    toolbar.set_content(Some(&page));
+    toolbar.add_bottom_bar(&save_button);
+    dialog.set_child(Some(&toolbar));
+    dialog.present(Some(window));
+}
+
+/// Add `rule` to `zone` in the background, replacing `original` (its zone
+/// and text) if given, and report the result.
+fn save_rule(
+    window: &MainWindow,
+    zone: String,
+    rule: String,
+    permanent: bool,
+    original: Option<(String, String)>,
+) {
+    let window = window.clone();
+
+    glib::spawn_future_local(async move {
+        let zone_for_save = zone.clone();
+        let result = crate::firewall::call(move |client| {
+            client.connect()?;
+            if let Some((old_zone, old_rule)) = &original {
+                client.remove_rich_rule(old_zone, old_rule, true)?;
+            }
+            match client.add_rich_rule(&zone_for_save, &rule, permanent) {
+                Ok(outcome) => Ok(outcome.failed()),
+                Err(e) => {
+                    // Put the replaced rule back rather than lose it
+                    if let Some((old_zone, old_rule)) = &original {
+                        let _ = client.add_rich_rule(old_zone, old_rule, true);
+                    }
+                    Err(e)
+                }
+            }
+        })
+        .await;
+
+        match result {
+            Ok(Ok(permanent_failed)) => {
+                let message = if permanent_failed {
+                    gettext("Rule saved in '%s' for this session only — saving permanently failed")
+                } else {
+                    gettext("Rule saved in '%s'")
+                };
+                window.show_toast(&message.replace("%s", &zone));
+                window.invalidate(Invalidation::ZonesChanged);
+            }
+            Ok(Err(e)) => {
+                window.show_toast(&format!("{}: {}", gettext("Failed to save rule"), e));
+            }
+            Err(_) => {
+                window.show_toast(&gettext("Failed to save rule"));
+            }
+        }
+    });
+}
@@ -0,0 +1,400 @@
+// Security Center - Rich Rules Page
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Rich rules of every zone, described in words, with a guided builder to
+//! add and edit them.
+
+use std::cell::RefCell;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use super::app_state::Invalidation;
+use super::density::{self, Role};
+use super::rich_rule_builder::{can_edit, present_rich_rule_builder};
+use crate::i18n::gettext;
+use crate::models::{RichRule, RuleAction, RuleElement, Zone};
+
+glib::wrapper! {
+    /// Rich rules page listing the rules of each zone.
+    pub struct RichRulesPage(ObjectSubclass<imp::RichRulesPage>)
+        @extends gtk4::Box, gtk4::Widget,
+        @implements gtk4::Orientable;
+}
+
+impl RichRulesPage {
+    /// Create a new rich rules page.
+    pub fn new() -> Self {
+        let page: Self = glib::Object::new();
+        page.setup_ui();
+        page
+    }
+
+    /// Setup the UI.
+    fn setup_ui(&self) {
+        let imp = self.imp();
+
+        self.set_orientation(gtk4::Orientation::Vertical);
+        self.set_spacing(0);
+
+        // Header
+        let header_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(12)
+            .build();
+        density::track(&header_box, Role::PageHeader);
+
+        let title_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(4)
+            .hexpand(true)
+            .build();
+
+        let title = gtk4::Label::builder()
+            .label(gettext("Rich Rules"))
+            .css_classes(vec!["title-1".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        let subtitle = gtk4::Label::builder()
+            .label(gettext(
+                "Fine-grained rules by address, service, port or protocol, with logging",
+            ))
+            .css_classes(vec!["dim-label".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        title_box.append(&title);
+        title_box.append(&subtitle);
+        header_box.append(&title_box);
+
+        let add_button = gtk4::Button::builder()
+            .label(gettext("Add Rule"))
+            .css_classes(vec!["suggested-action".to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+
+        let page = self.clone();
+        add_button.connect_clicked(move |_| {
+            page.open_builder(None, None);
+        });
+        header_box.append(&add_button);
+        self.append(&header_box);
+
+        // Scrolled container
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .vexpand(true)
+            .hexpand(true)
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .build();
+        self.append(&scrolled);
+
+        let content = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .hexpand(true)
+            .build();
+        density::track(
+            &content,
+            Role::Content {
+                margin: 24,
+                spacing: 24,
+            },
+        );
+        scrolled.set_child(Some(&content));
+
+        let empty = adw::StatusPage::builder()
+            .icon_name("format-justify-left-symbolic")
+            .title(gettext("No Rich Rules"))
+            .description(gettext(
+                "Rich rules allow or deny traffic from specific addresses, log it or limit its rate",
+            ))
+            .vexpand(true)
+            .build();
+        content.append(&empty);
+
+        imp.content.replace(Some(content));
+        imp.empty.replace(Some(empty));
+    }
+
+    /// Show a toast message.
+    fn show_toast(&self, message: &str) {
+        if let Some(window) = self.main_window() {
+            window.show_toast(message);
+        }
+    }
+
+    /// Have the main window refresh what shows zone rules.
+    fn request_refresh(&self) {
+        if let Some(window) = self.main_window() {
+            window.invalidate(Invalidation::ZonesChanged);
+        }
+    }
+
+    fn main_window(&self) -> Option<super::MainWindow> {
+        self.root()
+            .and_then(|root| root.downcast::<gtk4::Window>().ok())
+            .and_then(|window| window.downcast::<super::MainWindow>().ok())
+    }
+
+    /// Open the rule builder, editing `rule` of `zone` when given. New rules
+    /// go to the default zone unless another is chosen.
+    fn open_builder(&self, zone: Option<&str>, rule: Option<&str>) {
+        let imp = self.imp();
+        let zones = imp.zone_names.borrow().clone();
+        if zones.is_empty() {
+            self.show_toast(&gettext("Not connected to firewalld"));
+            return;
+        }
+        let default_zone = imp.default_zone.borrow().clone();
+        let zone = zone.unwrap_or(&default_zone);
+        if let Some(window) = self.main_window() {
+            present_rich_rule_builder(&window, &zones, zone, rule);
+        }
+    }
+
+    /// Update the page with the rules of `zones`.
+    pub fn set_zones(&self, zones: &[Zone]) {
+        let imp = self.imp();
+        imp.zone_names
+            .replace(zones.iter().map(|z| z.name.clone()).collect());
+        if let Some(zone) = zones.iter().find(|z| z.is_default) {
+            imp.default_zone.replace(zone.name.clone());
+        }
+
+        let Some(content) = imp.content.borrow().clone() else {
+            return;
+        };
+        for group in imp.groups.borrow_mut().drain(..) {
+            content.remove(&group);
+        }
+
+        let mut groups = Vec::new();
+        for zone in zones.iter().filter(|z| !z.rich_rules.is_empty()) {
+            let group = adw::PreferencesGroup::builder()
+                .title(glib::markup_escape_text(&zone.name).as_str())
+                .description(
+                    gettext("%d rules, checked in priority order")
+                        .replace("%d", &zone.rich_rules.len().to_string()),
+                )
+                .build();
+            for rule in &zone.rich_rules {
+                group.add(&self.create_rule_row(&zone.name, rule));
+            }
+            content.append(&group);
+            groups.push(group);
+        }
+
+        if let Some(empty) = imp.empty.borrow().as_ref() {
+            empty.set_visible(groups.is_empty());
+        }
+        imp.groups.replace(groups);
+    }
+
+    /// Row describing one rule, with its raw text below.
+    fn create_rule_row(&self, zone: &str, text: &str) -> adw::ActionRow {
+        let parsed = RichRule::parse(text).ok();
+        let title = parsed
+            .as_ref()
+            .map(describe_rule)
+            .unwrap_or_else(|| gettext("Rule not understood"));
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(&title).as_str())
+            .subtitle(glib::markup_escape_text(text).as_str())
+            .subtitle_selectable(true)
+            .build();
+
+        let icon = match parsed.as_ref().and_then(|rule| rule.action.as_ref()) {
+            Some(RuleAction::Accept) => "emblem-ok-symbolic",
+            Some(action) if action.is_deny() => "action-unavailable-symbolic",
+            _ => "document-properties-symbolic",
+        };
+        row.add_prefix(&gtk4::Image::from_icon_name(icon));
+
+        if parsed.as_ref().is_some_and(can_edit) {
+            let page = self.clone();
+            let zone = zone.to_string();
+            let rule = text.to_string();
+            let edit_button = gtk4::Button::builder()
+                .icon_name("document-edit-symbolic")
+                .valign(gtk4::Align::Center)
+                .css_classes(["flat"])
+                .tooltip_text(gettext("Edit rule"))
+                .build();
+            edit_button.connect_clicked(move |_| {
+                page.open_builder(Some(&zone), Some(&rule));
+            });
+            row.add_suffix(&edit_button);
+        }
+
+        let page = self.clone();
+        let zone = zone.to_string();
+        let rule = text.to_string();
+        let delete_button = gtk4::Button::builder()
+            .icon_name("user-trash-symbolic")
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat", "error"])
+            .tooltip_text(gettext("Delete rule"))
+            .build();
+        delete_button.connect_clicked(move |button| {
+            page.confirm_delete_rule(&zone, &rule, button);
+        });
+        row.add_suffix(&delete_button);
+
+        row
+    }
+
+    /// Ask before deleting a rule, then delete it at runtime and permanently.
+    fn confirm_delete_rule(&self, zone: &str, rule: &str, button: &gtk4::Button) {
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("Delete rule?"))
+            .body(rule)
+            .build();
+        dialog.add_response("cancel", "_Cancel");
+        dialog.add_response("delete", "_Delete");
+        dialog.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
+        dialog.set_default_response(Some("cancel"));
+
+        let page = self.clone();
+        let zone = zone.to_string();
+        let rule = rule.to_string();
+        let button = button.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response == "delete" {
+                button.set_sensitive(false);
+                page.delete_rule(&zone, &rule, &button);
+            }
+        });
+
+        if let Some(root) = self.root() {
+            if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                dialog.present(Some(window));
+            }
+        }
+    }
+
+    /// Remove a rule from `zone`, runtime and permanent.
+    fn delete_rule(&self, zone: &str, rule: &str, button: &gtk4::Button) {
+        let zone = zone.to_string();
+        let rule = rule.to_string();
+        let page = self.clone();
+        let button = button.clone();
+
+        glib::spawn_future_local(async move {
+            let zone_clone = zone.clone();
+            let result = crate::firewall::call(move |client| {
+                if client.connect().is_err() {
+                    return Err(anyhow::anyhow!("Not connected to firewalld"));
+                }
+                client.remove_rich_rule(&zone_clone, &rule, true)
+            })
+            .await;
+
+            match result {
+                Ok(Ok(outcome)) => {
+                    let message = if outcome.failed() {
+                        gettext("Rule removed from '%s' for this session only — saving permanently failed")
+                    } else {
+                        gettext("Rule removed from '%s'")
+                    };
+                    page.show_toast(&message.replace("%s", &zone));
+                    page.request_refresh();
+                }
+                Ok(Err(e)) => {
+                    button.set_sensitive(true);
+                    page.show_toast(&format!("{}: {}", gettext("Failed to remove rule"), e));
+                }
+                Err(_) => {
+                    button.set_sensitive(true);
+                    page.show_toast(&gettext("Failed to remove rule"));
+                }
+            }
+        });
+    }
+}
+
+/// Plain-language summary of a rule, e.g. "Accept service ssh from
+/// 10.0.0.0/8".
+fn describe_rule(rule: &RichRule) -> String {
+    let verb = match &rule.action {
+        Some(RuleAction::Accept) => gettext("Accept"),
+        Some(RuleAction::Reject(_)) => gettext("Reject"),
+        Some(RuleAction::Drop) => gettext("Drop"),
+        Some(RuleAction::Mark(_)) => gettext("Mark"),
+        None => match &rule.element {
+            Some(RuleElement::Masquerade) => gettext("Masquerade"),
+            Some(RuleElement::IcmpBlock(_)) => gettext("Block"),
+            Some(RuleElement::ForwardPort { .. }) => gettext("Forward"),
+            _ if rule.log.is_some() => gettext("Log"),
+            _ => gettext("Audit"),
+        },
+    };
+    let what = match &rule.element {
+        None | Some(RuleElement::Masquerade) => gettext("all traffic"),
+        Some(RuleElement::Service(name)) => gettext("service %s").replace("%s", name),
+        Some(RuleElement::Port { port, protocol })
+        | Some(RuleElement::ForwardPort { port, protocol, .. }) => {
+            gettext("port %s").replace("%s", &format!("{}/{}", port, protocol))
+        }
+        Some(RuleElement::SourcePort { port, protocol }) => {
+            gettext("source port %s").replace("%s", &format!("{}/{}", port, protocol))
+        }
+        Some(RuleElement::Protocol(protocol)) => gettext("protocol %s").replace("%s", protocol),
+        Some(RuleElement::IcmpType(name)) | Some(RuleElement::IcmpBlock(name)) => {
+            gettext("ICMP %s").replace("%s", name)
+        }
+    };
+
+    let mut summary = format!("{} {}", verb, what);
+    let address = |address: &crate::models::RuleAddress| {
+        if address.invert {
+            gettext("anything but %s").replace("%s", &address.input_text())
+        } else {
+            address.input_text()
+        }
+    };
+    if let Some(source) = &rule.source {
+        summary.push_str(&gettext(" from %s").replace("%s", &address(source)));
+    }
+    if let Some(destination) = &rule.destination {
+        summary.push_str(&gettext(" to %s").replace("%s", &address(destination)));
+    }
+    if rule.action.is_some() && rule.log.is_some() {
+        summary.push_str(&gettext(", logged"));
+    }
+    summary
+}
+
+impl Default for RichRulesPage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct RichRulesPage {
+        pub content: RefCell<Option<gtk4::Box>>,
+        pub empty: RefCell<Option<adw::StatusPage>>,
+        /// One group per zone that has rules, replaced on every refresh.
+        pub groups: RefCell<Vec<adw::PreferencesGroup>>,
+        pub zone_names: RefCell<Vec<String>>,
+        pub default_zone: RefCell<String>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for RichRulesPage {
+        const NAME: &'static str = "SecurityCenterRichRulesPage";
+        type Type = super::RichRulesPage;
+        type ParentType = gtk4::Box;
+    }
+
+    impl ObjectImpl for RichRulesPage {}
+    impl WidgetImpl for RichRulesPage {}
+    impl BoxImpl for RichRulesPage {}
+}
@@ -0,0 +1,217 @@
+// Security Center - Role checklists dialog
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Interactive security checklist for what the machine is used for (web
+//! server, workstation, home NAS). Items are ticked off by hand and the
+//! progress is kept in the settings; each item opens the page where it is
+//! checked or runs the operation that takes care of it.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use crate::admin::{ChecklistItem, ChecklistRole, OperationRunner};
+use crate::config::Settings;
+use crate::i18n::gettext;
+
+/// The dialog's widgets that change with the role and the progress.
+#[derive(Clone)]
+struct ChecklistView {
+    window: gtk4::Window,
+    dialog: adw::Dialog,
+    toasts: adw::ToastOverlay,
+    page: adw::PreferencesPage,
+    progress_row: adw::ActionRow,
+    progress_bar: gtk4::ProgressBar,
+    items: Rc<RefCell<Option<adw::PreferencesGroup>>>,
+}
+
+/// Build and present the checklist dialog over `parent`.
+pub fn present_role_checklists(parent: &gtk4::Window) {
+    let dialog = adw::Dialog::builder()
+        .title(gettext("Security Checklists"))
+        .content_width(600)
+        .content_height(680)
+        .build();
+
+    let header = adw::HeaderBar::new();
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&header);
+
+    let page = adw::PreferencesPage::new();
+    let role_group = adw::PreferencesGroup::builder()
+        .description(gettext(
+            "Pick what this machine is used for and work through the list. Tick items off as you \
+             go; your progress is saved.",
+        ))
+        .build();
+    let roles = gtk4::StringList::new(&[]);
+    for role in ChecklistRole::ALL {
+        roles.append(&gettext(role.title()));
+    }
+    let role_row = adw::ComboRow::builder()
+        .title(gettext("Role"))
+        .model(&roles)
+        .build();
+    role_group.add(&role_row);
+
+    let progress_bar = gtk4::ProgressBar::builder()
+        .valign(gtk4::Align::Center)
+        .width_request(160)
+        .build();
+    let progress_row = adw::ActionRow::builder().title(gettext("Progress")).build();
+    progress_row.add_suffix(&progress_bar);
+    role_group.add(&progress_row);
+    page.add(&role_group);
+
+    let toasts = adw::ToastOverlay::new();
+    toasts.set_child(Some(&page));
+    toolbar.set_content(Some(&toasts));
+    dialog.set_child(Some(&toolbar));
+
+    let view = ChecklistView {
+        window: parent.clone(),
+        dialog: dialog.clone(),
+        toasts,
+        page,
+        progress_row,
+        progress_bar,
+        items: Rc::new(RefCell::new(None)),
+    };
+
+    // Start with the first role that has progress
+    let settings = Settings::new();
+    let initial = ChecklistRole::ALL
+        .iter()
+        .position(|role| !settings.checklist_done(*role).is_empty())
+        .unwrap_or(0);
+    role_row.set_selected(initial as u32);
+    view.show_role(ChecklistRole::ALL[initial]);
+    role_row.connect_selected_notify(move |row| {
+        if let Some(role) = ChecklistRole::ALL.get(row.selected() as usize) {
+            view.show_role(*role);
+        }
+    });
+
+    dialog.present(Some(parent));
+}
+
+impl ChecklistView {
+    /// Replace the items with those of `role`.
+    fn show_role(&self, role: ChecklistRole) {
+        if let Some(old) = self.items.borrow_mut().take() {
+            self.page.remove(&old);
+        }
+        let settings = Settings::new();
+        let done = settings.checklist_done(role);
+        let group = adw::PreferencesGroup::builder()
+            .title(gettext(role.title()))
+            .build();
+        for item in role.items() {
+            let checked = done.iter().any(|id| id == item.id);
+            group.add(&self.create_item_row(role, item, checked));
+        }
+        self.page.add(&group);
+        self.items.replace(Some(group));
+        self.update_progress(role);
+    }
+
+    fn update_progress(&self, role: ChecklistRole) {
+        let (checked, total) = role.progress(Settings::new().checklist_done(role));
+        self.progress_row.set_subtitle(
+            &gettext("%d of %d done")
+                .replacen("%d", &checked.to_string(), 1)
+                .replacen("%d", &total.to_string(), 1),
+        );
+        self.progress_bar
+            .set_fraction(checked as f64 / total.max(1) as f64);
+    }
+
+    fn create_item_row(
+        &self,
+        role: ChecklistRole,
+        item: &'static ChecklistItem,
+        checked: bool,
+    ) -> adw::ActionRow {
+        let row = adw::ActionRow::builder()
+            .title(gettext(item.title))
+            .subtitle(gettext(item.description))
+            .build();
+        let check = gtk4::CheckButton::builder()
+            .active(checked)
+            .valign(gtk4::Align::Center)
+            .build();
+        row.add_prefix(&check);
+        row.set_activatable_widget(Some(&check));
+        let view = self.clone();
+        check.connect_toggled(move |check| {
+            Settings::new().set_checklist_item_done(role, item.id, check.is_active());
+            view.update_progress(role);
+        });
+
+        if let Some(operation) = item.operation {
+            let button = gtk4::Button::builder()
+                .label(gettext("Fix"))
+                .tooltip_text(gettext("Make this change now"))
+                .valign(gtk4::Align::Center)
+                .build();
+            let view = self.clone();
+            let check = check.clone();
+            button.connect_clicked(move |button| {
+                button.set_sensitive(false);
+                let button = button.clone();
+                let view = view.clone();
+                let check = check.clone();
+                glib::spawn_future_local(async move {
+                    let result = gtk4::gio::spawn_blocking(move || {
+                        OperationRunner::new().run_revertible(operation)
+                    })
+                    .await;
+                    button.set_sensitive(true);
+                    let Ok((result, undo)) = result else {
+                        view.toasts
+                            .add_toast(adw::Toast::new(&gettext("Failed to execute action")));
+                        return;
+                    };
+                    if let Some(window) = view.window.downcast_ref::<super::MainWindow>() {
+                        // Risky changes revert themselves unless kept
+                        if let Some(undo) = undo {
+                            window.revert_unless_kept(undo);
+                        }
+                        if result.success {
+                            window.refresh_data();
+                        }
+                    }
+                    if result.success {
+                        check.set_active(true);
+                    }
+                    view.toasts.add_toast(adw::Toast::new(&result.message));
+                });
+            });
+            row.add_suffix(&button);
+        }
+
+        if let Some(page) = item.page {
+            let button = gtk4::Button::builder()
+                .icon_name("go-next-symbolic")
+                .tooltip_text(gettext("Open the page where this is checked"))
+                .valign(gtk4::Align::Center)
+                .css_classes(vec!["flat".to_string()])
+                .build();
+            let view = self.clone();
+            button.connect_clicked(move |_| {
+                view.dialog.close();
+                if let Some(window) = view.window.downcast_ref::<super::MainWindow>() {
+                    window.show_page(page);
+                }
+            });
+            row.add_suffix(&button);
+        }
+        row
+    }
+}
@@ -0,0 +1,262 @@
+// Security Center - Rule search dialog
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Search dialog over every zone's ports, services, sources, interfaces and
+//! rich rules (Ctrl+Shift+F). Zones and the definitions of the services they
+//! allow are read once when the dialog opens; each match can jump to the
+//! page that manages it.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use super::MainWindow;
+use crate::firewall::{search_rules, RuleKind, RuleMatch, RuleQuery};
+use crate::i18n::gettext;
+use crate::models::{Service, Zone};
+
+/// Matches listed before the rest are summarised.
+const MAX_RESULTS: usize = 200;
+
+pub(super) fn kind_label(kind: RuleKind) -> String {
+    match kind {
+        RuleKind::Port => gettext("Port"),
+        RuleKind::Service => gettext("Service"),
+        RuleKind::Source => gettext("Source"),
+        RuleKind::Interface => gettext("Interface"),
+        RuleKind::RichRule => gettext("Rich rule"),
+    }
+}
+
+pub(super) fn kind_icon(kind: RuleKind) -> &'static str {
+    match kind {
+        RuleKind::Port => "network-transmit-receive-symbolic",
+        RuleKind::Service => "application-x-addon-symbolic",
+        RuleKind::Source => "network-workgroup-symbolic",
+        RuleKind::Interface => "network-wired-symbolic",
+        RuleKind::RichRule => "text-x-generic-symbolic",
+    }
+}
+
+/// Navigation id of the page that manages a kind of entry.
+fn kind_page(kind: RuleKind) -> &'static str {
+    match kind {
+        RuleKind::Port => "ports",
+        RuleKind::RichRule => "rich-rules",
+        RuleKind::Service => "services",
+        RuleKind::Source | RuleKind::Interface => "zones",
+    }
+}
+
+/// Zones plus the definitions of every service enabled in one of them.
+fn load_rules() -> anyhow::Result<(Vec<Zone>, HashMap<String, Service>)> {
+    crate::firewall::read_blocking(|client| -> anyhow::Result<_> {
+        client.connect()?;
+        let zones = client.get_zones()?;
+        let mut services = HashMap::new();
+        for name in zones.iter().flat_map(|zone| zone.services.iter()) {
+            if services.contains_key(name) {
+                continue;
+            }
+            if let Ok(service) = client.get_service_settings(name) {
+                services.insert(name.clone(), service);
+            }
+        }
+        Ok((zones, services))
+    })?
+}
+
+/// Build and present the search dialog over `window`.
+pub fn present_rule_search(window: &MainWindow) {
+    let dialog = adw::Dialog::builder()
+        .title(gettext("Search Rules"))
+        .content_width(600)
+        .content_height(560)
+        .build();
+
+    let search_entry = gtk4::SearchEntry::builder()
+        .placeholder_text(gettext("Port, service, address or rule text"))
+        .hexpand(true)
+        .sensitive(false)
+        .build();
+    let regex_toggle = gtk4::ToggleButton::builder()
+        .label(".*")
+        .tooltip_text(gettext("Regular expression"))
+        .css_classes(vec!["flat".to_string()])
+        .build();
+    let search_box = gtk4::Box::builder()
+        .orientation(gtk4::Orientation::Horizontal)
+        .spacing(6)
+        .build();
+    search_box.append(&search_entry);
+    search_box.append(&regex_toggle);
+
+    let header = adw::HeaderBar::builder().title_widget(&search_box).build();
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&header);
+
+    let status = adw::StatusPage::builder()
+        .icon_name("system-search-symbolic")
+        .title(gettext("Loading rules…"))
+        .build();
+    let results = gtk4::ListBox::builder()
+        .selection_mode(gtk4::SelectionMode::None)
+        .css_classes(vec!["boxed-list".to_string()])
+        .valign(gtk4::Align::Start)
+        .build();
+    let clamp = adw::Clamp::builder()
+        .child(&results)
+        .margin_top(12)
+        .margin_bottom(12)
+        .margin_start(12)
+        .margin_end(12)
+        .build();
+    let scrolled = gtk4::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .vexpand(true)
+        .child(&clamp)
+        .build();
+
+    let stack = gtk4::Stack::new();
+    stack.add_named(&status, Some("status"));
+    stack.add_named(&scrolled, Some("results"));
+    toolbar.set_content(Some(&stack));
+    dialog.set_child(Some(&toolbar));
+    dialog.present(Some(window));
+
+    let window = window.clone();
+    glib::spawn_future_local(async move {
+        let (zones, services) = match gtk4::gio::spawn_blocking(load_rules).await {
+            Ok(Ok(data)) => data,
+            Ok(Err(e)) => {
+                status.set_icon_name(Some("dialog-error-symbolic"));
+                status.set_title(&gettext("Cannot Read the Firewall Rules"));
+                status.set_description(Some(&glib::markup_escape_text(&format!("{:#}", e))));
+                return;
+            }
+            Err(_) => return,
+        };
+        let view = ResultsView {
+            window,
+            rules: (zones, services),
+            stack,
+            status,
+            results,
+        };
+        let run = {
+            let search_entry = search_entry.clone();
+            let regex_toggle = regex_toggle.clone();
+            move || view.show(&search_entry.text(), regex_toggle.is_active())
+        };
+        run();
+        let run = Rc::new(run);
+        let on_search = run.clone();
+        search_entry.connect_search_changed(move |_| on_search());
+        regex_toggle.connect_toggled(move |_| run());
+        search_entry.set_sensitive(true);
+        search_entry.grab_focus();
+    });
+}
+
+/// The dialog's result area and the rules it searches.
+struct ResultsView {
+    window: MainWindow,
+    rules: (Vec<Zone>, HashMap<String, Service>),
+    stack: gtk4::Stack,
+    status: adw::StatusPage,
+    results: gtk4::ListBox,
+}
+
+impl ResultsView {
+    fn show(&self, text: &str, regex: bool) {
+        let Self {
+            window,
+            rules,
+            stack,
+            status,
+            results,
+        } = self;
+        results.remove_all();
+        status.set_icon_name(Some("system-search-symbolic"));
+        status.set_description(None);
+
+        let query = match RuleQuery::new(text, regex) {
+            Ok(query) => query,
+            Err(e) => {
+                status.set_icon_name(Some("dialog-warning-symbolic"));
+                status.set_title(&gettext("Invalid Regular Expression"));
+                status.set_description(Some(&glib::markup_escape_text(&format!("{:#}", e))));
+                stack.set_visible_child_name("status");
+                return;
+            }
+        };
+        if query.is_empty() {
+            status.set_title(&gettext("Search All Zones"));
+            status.set_description(Some(&gettext(
+                "Find where a port, service, address or interface is allowed. A port number also \
+                 finds the ranges and services that open it.",
+            )));
+            stack.set_visible_child_name("status");
+            return;
+        }
+
+        let (zones, services) = rules;
+        let matches = search_rules(zones, services, &query);
+        if matches.is_empty() {
+            status.set_title(&gettext("No Matching Rules"));
+            stack.set_visible_child_name("status");
+            return;
+        }
+
+        for found in matches.iter().take(MAX_RESULTS) {
+            results.append(&create_match_row(window, found));
+        }
+        if matches.len() > MAX_RESULTS {
+            let more = adw::ActionRow::builder()
+                .title(
+                    gettext("%d more matches not shown")
+                        .replace("%d", &(matches.len() - MAX_RESULTS).to_string()),
+                )
+                .css_classes(vec!["dim-label".to_string()])
+                .build();
+            results.append(&more);
+        }
+        stack.set_visible_child_name("results");
+    }
+}
+
+fn create_match_row(window: &MainWindow, found: &RuleMatch) -> adw::ActionRow {
+    let mut subtitle = gettext("%s in zone %s")
+        .replacen("%s", &kind_label(found.kind), 1)
+        .replacen("%s", &found.zone, 1);
+    if let Some(via) = &found.via {
+        subtitle = format!("{} · {}", subtitle, gettext("opens %s").replace("%s", via));
+    }
+
+    let row = adw::ActionRow::builder()
+        .title(glib::markup_escape_text(&found.entry).as_str())
+        .subtitle(glib::markup_escape_text(&subtitle).as_str())
+        .activatable(true)
+        .tooltip_text(gettext("Open the page that manages this rule"))
+        .build();
+    row.add_prefix(&gtk4::Image::from_icon_name(kind_icon(found.kind)));
+    row.add_suffix(&gtk4::Image::from_icon_name("go-next-symbolic"));
+
+    let window = window.clone();
+    let page = kind_page(found.kind);
+    row.connect_activated(move |row| {
+        if let Some(dialog) = row
+            .ancestor(adw::Dialog::static_type())
+            .and_downcast::<adw::Dialog>()
+        {
+            dialog.close();
+        }
+        window.show_page(page);
+    });
+    row
+}
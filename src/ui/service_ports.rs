@@ -0,0 +1,117 @@
+// Security Center - Service Port Tooltips
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Tooltips listing the ports and protocols a firewalld service opens,
+//! wherever a service name is shown, so enabling "samba" is not a guess.
+//! Definitions come from the firewalld metadata cache; one that has not
+//! been read yet is fetched in the background the first time it is
+//! hovered, and the tooltip updates when it arrives.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+
+use crate::firewall::cache;
+use crate::i18n::gettext;
+
+thread_local! {
+    /// Services being fetched.
+    static PENDING: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+    /// Services firewalld does not know, so they are not asked for again.
+    static MISSING: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+}
+
+/// What service `name` opens, or `None` while its definition is unknown.
+fn ports_of(name: &str) -> Option<String> {
+    if MISSING.with(|missing| missing.borrow().contains(name)) {
+        return Some(gettext("Not a service firewalld knows"));
+    }
+    let service = cache::cache().service(name)?;
+    let mut opens: Vec<String> = service
+        .ports
+        .iter()
+        .map(|(port, protocol)| format!("{}/{}", port, protocol))
+        .collect();
+    opens.extend(
+        service
+            .helpers
+            .iter()
+            .map(|helper| gettext("%s helper").replace("%s", helper)),
+    );
+    Some(if opens.is_empty() {
+        gettext("Opens no ports of its own")
+    } else {
+        opens.join(", ")
+    })
+}
+
+/// Tooltip text for `names`: the ports of one service, or one line per
+/// service for several.
+fn tooltip_text(names: &[String], widget: &gtk4::Widget) -> String {
+    let mut lines = Vec::new();
+    for name in names {
+        let ports = ports_of(name).unwrap_or_else(|| {
+            fetch(name, widget);
+            gettext("Looking up its ports…")
+        });
+        lines.push(if names.len() == 1 {
+            format!("{}: {}", gettext("Opens"), ports)
+        } else {
+            format!("{}: {}", name, ports)
+        });
+    }
+    lines.join("\n")
+}
+
+/// Read the definition of `name` into the cache, then refresh the tooltip
+/// of `widget`.
+fn fetch(name: &str, widget: &gtk4::Widget) {
+    let fresh = PENDING.with(|pending| pending.borrow_mut().insert(name.to_string()));
+    if !fresh {
+        return;
+    }
+    let name = name.to_string();
+    let widget = widget.downgrade();
+    glib::spawn_future_local(async move {
+        let lookup = name.clone();
+        let result = crate::firewall::read(move |client| {
+            client.connect()?;
+            client.get_service_settings(&lookup)
+        })
+        .await;
+        PENDING.with(|pending| pending.borrow_mut().remove(&name));
+        if !matches!(result, Ok(Ok(_))) {
+            MISSING.with(|missing| missing.borrow_mut().insert(name));
+        }
+        if let Some(widget) = widget.upgrade() {
+            widget.trigger_tooltip_query();
+        }
+    });
+}
+
+/// Show the ports of service `name` when `widget` is hovered.
+pub fn attach_service_tooltip(widget: &impl IsA<gtk4::Widget>, name: &str) {
+    attach_services_tooltip(widget, &[name.to_string()]);
+}
+
+/// Show the ports of each of `names` when `widget` is hovered.
+pub fn attach_services_tooltip(widget: &impl IsA<gtk4::Widget>, names: &[String]) {
+    if names.is_empty() {
+        return;
+    }
+    let names = names.to_vec();
+    widget.set_has_tooltip(true);
+    widget.connect_query_tooltip(move |widget, _, _, _, tooltip| {
+        tooltip.set_text(Some(&tooltip_text(&names, widget.upcast_ref())));
+        true
+    });
+}
+
+/// Tooltip for service `name` drawn inside `widget`, for widgets that hit
+/// test their own content.
+pub fn service_tooltip(name: &str, widget: &gtk4::Widget) -> String {
+    tooltip_text(&[name.to_string()], widget)
+}
@@ -2,20 +2,29 @@
 // Copyright (C) 2026 Christos Daggas
 // SPDX-License-Identifier: MIT
 
-//! Services management page.
+//! Services management page, with the connection tracking helpers the
+//! enabled services attach.
 
-use std::cell::RefCell;
+use std::cell::{OnceCell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
-use gtk4::glib;
 use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
+use gtk4::{gio, glib};
 use libadwaita as adw;
 use libadwaita::prelude::*;
 
-use crate::firewall::FirewallClient;
+use super::app_state::Invalidation;
+use super::density::{self, Role};
+use super::lockout_guard::{self, TrialChange};
+use super::service_ports::attach_service_tooltip;
+use crate::admin::{helper_description, is_risky_service, query_helper_policy, HelperPolicy, Undo};
+use crate::firewall::journal::{self, RuleItem};
+use crate::firewall::{FirewallClient, AUTOMATIC_HELPERS_VALUES};
 use crate::i18n::gettext;
-use crate::models::Service;
+use crate::models::{rebind_store, store_models, sync_store, ModelObject, Service, ServiceObject};
+use crate::validation::{parse_port_list, parse_port_spec, validate_firewall_service};
 
 glib::wrapper! {
     /// Services page showing firewall services.
@@ -48,11 +57,8 @@ impl ServicesPage {
         let header_box = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Horizontal)
             .spacing(12)
-            .margin_start(24)
-            .margin_end(24)
-            .margin_top(24)
-            .margin_bottom(12)
             .build();
+        density::track(&header_box, Role::PageHeader);
 
         let title_box = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Vertical)
@@ -90,13 +96,15 @@ impl ServicesPage {
         // Main content - no clamp for full width
         let content = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Vertical)
-            .spacing(24)
-            .margin_top(24)
-            .margin_bottom(24)
-            .margin_start(24)
-            .margin_end(24)
             .hexpand(true)
             .build();
+        density::track(
+            &content,
+            Role::Content {
+                margin: 24,
+                spacing: 24,
+            },
+        );
         scrolled.set_child(Some(&content));
 
         // Info banner about authentication
@@ -123,9 +131,8 @@ impl ServicesPage {
                             .imp()
                             .selected_zone
                             .replace(s.string().to_string());
-                        // Re-render so enabled state reflects the selected zone
-                        let services = page_for_zone.imp().services.borrow().clone();
-                        page_for_zone.render_services(&services);
+                        // Re-filter so enabled state reflects the selected zone
+                        page_for_zone.refilter();
                     }
                 }
             }
@@ -137,7 +144,7 @@ impl ServicesPage {
         // Search filter over the full service list
         let search_entry = gtk4::SearchEntry::builder()
             .placeholder_text(gettext(
-                "Search services (e.g. postgresql, wireguard, mosh)",
+                "Search services by name, description or port (e.g. postgresql, 5432)",
             ))
             .hexpand(true)
             .build();
@@ -147,21 +154,62 @@ impl ServicesPage {
                 .imp()
                 .search_text
                 .replace(entry.text().to_string().to_lowercase());
-            let services = page_for_search.imp().services.borrow().clone();
-            page_for_search.render_services(&services);
+            page_for_search.refilter();
         });
         content.append(&search_entry);
 
         // Enabled services group
-        content.append(&Self::create_section_header(
+        let enabled_header = Self::create_section_header(
             "preferences-system-symbolic",
             &gettext("Enabled Services"),
-        ));
+        );
+        let add_button = gtk4::Button::builder()
+            .icon_name("list-add-symbolic")
+            .tooltip_text(gettext("Add Service"))
+            .css_classes(vec!["flat".to_string()])
+            .hexpand(true)
+            .halign(gtk4::Align::End)
+            .build();
+        let page_for_add = self.clone();
+        add_button.connect_clicked(move |_| page_for_add.present_service_picker());
+        enabled_header.append(&add_button);
+        let create_button = gtk4::Button::builder()
+            .icon_name("document-new-symbolic")
+            .tooltip_text(gettext("Create Custom Service"))
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        let page_for_create = self.clone();
+        create_button.connect_clicked(move |_| page_for_create.present_service_creator());
+        enabled_header.append(&create_button);
+        content.append(&enabled_header);
         let enabled_group = adw::PreferencesGroup::builder()
             .description(gettext("Services allowing traffic through the firewall"))
             .build();
+        let enabled_placeholder = adw::ActionRow::builder()
+            .subtitle(gettext("Enable services below to allow specific traffic"))
+            .sensitive(false)
+            .build();
+        enabled_group.add(&enabled_placeholder);
         content.append(&enabled_group);
+        content.append(&self.create_service_list(true));
         imp.enabled_group.replace(Some(enabled_group));
+        imp.enabled_placeholder.replace(Some(enabled_placeholder));
+
+        // Conntrack helpers of the enabled services and the automatic policy
+        content.append(&Self::create_section_header(
+            "security-medium-symbolic",
+            &gettext("Connection Tracking Helpers"),
+        ));
+        let helpers_group = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "Helpers read the traffic of protocols such as FTP and SIP to let in the \
+                 extra connections they negotiate. They parse untrusted data in the kernel \
+                 and have been abused to open ports, so only services that need them \
+                 should attach one.",
+            ))
+            .build();
+        content.append(&helpers_group);
+        imp.helpers_group.replace(Some(helpers_group));
 
         // All services group — every firewalld service definition
         content.append(&Self::create_section_header(
@@ -171,8 +219,141 @@ impl ServicesPage {
         let all_group = adw::PreferencesGroup::builder()
             .description(gettext("Every service firewalld knows about"))
             .build();
+        let all_placeholder = adw::ActionRow::builder().sensitive(false).build();
+        all_group.add(&all_placeholder);
         content.append(&all_group);
+        content.append(&self.create_service_list(false));
         imp.all_group.replace(Some(all_group));
+        imp.all_placeholder.replace(Some(all_placeholder));
+        self.update_placeholders();
+    }
+
+    /// The enabled or the other services of the selected zone, sorted by
+    /// name and filtered by the search. Rows are built for the services in
+    /// sight, and the labels follow their [`ServiceObject`].
+    fn create_service_list(&self, enabled: bool) -> gtk4::ScrolledWindow {
+        let sorter = gtk4::StringSorter::new(Some(ServiceObject::this_expression("name")));
+        let sorted = gtk4::SortListModel::new(Some(self.service_store()), Some(sorter));
+        let page = self.downgrade();
+        let filter = gtk4::CustomFilter::new(move |object| {
+            let (Some(page), Some(service)) =
+                (page.upgrade(), object.downcast_ref::<ServiceObject>())
+            else {
+                return false;
+            };
+            let imp = page.imp();
+            let name = service.name();
+            let search = imp.search_text.borrow();
+            // Descriptions and ports count once the definition was read
+            page.is_enabled_in_zone(&name) == enabled
+                && (search.is_empty()
+                    || match imp.service_details.borrow().get(&name) {
+                        Some(details) => details.matches_query(&search),
+                        None => service.model().matches_query(&search),
+                    })
+        });
+        let model = gtk4::FilterListModel::new(Some(sorted), Some(filter.clone()));
+        let page = self.clone();
+        model.connect_items_changed(move |_, _, _, _| page.update_placeholders());
+
+        let factory = gtk4::SignalListItemFactory::new();
+        let page = self.downgrade();
+        factory.connect_bind(move |_, item| {
+            let Some(item) = item.downcast_ref::<gtk4::ListItem>() else {
+                return;
+            };
+            let service = item.item().and_downcast::<ServiceObject>();
+            if let (Some(page), Some(service)) = (page.upgrade(), service) {
+                item.set_child(Some(&page.create_service_row(&service, enabled)));
+            }
+        });
+        factory.connect_unbind(|_, item| {
+            if let Some(item) = item.downcast_ref::<gtk4::ListItem>() {
+                item.set_child(gtk4::Widget::NONE);
+            }
+        });
+
+        let list_view = gtk4::ListView::builder()
+            .model(&gtk4::NoSelection::new(Some(model.clone())))
+            .factory(&factory)
+            .show_separators(true)
+            .css_classes(vec!["card".to_string()])
+            .build();
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .propagate_natural_height(true)
+            .max_content_height(480)
+            .child(&list_view)
+            .build();
+        let imp = self.imp();
+        if enabled {
+            imp.enabled_filter.replace(Some(filter));
+            imp.enabled_model.replace(Some(model));
+            imp.enabled_list.replace(Some(scrolled.clone()));
+        } else {
+            imp.all_filter.replace(Some(filter));
+            imp.all_model.replace(Some(model));
+            imp.all_list.replace(Some(scrolled.clone()));
+        }
+        scrolled
+    }
+
+    fn is_enabled_in_zone(&self, service: &str) -> bool {
+        let imp = self.imp();
+        let zone = imp.selected_zone.borrow();
+        imp.zone_enabled
+            .borrow()
+            .get(zone.as_str())
+            .is_some_and(|services| services.iter().any(|s| s == service))
+    }
+
+    /// Sort the services into the enabled and other list again, after the
+    /// zone, its services or the search changed.
+    fn refilter(&self) {
+        let imp = self.imp();
+        for filter in [&imp.enabled_filter, &imp.all_filter] {
+            if let Some(filter) = filter.borrow().as_ref() {
+                filter.changed(gtk4::FilterChange::Different);
+            }
+        }
+        self.update_placeholders();
+        self.render_helpers();
+    }
+
+    /// Show a placeholder instead of a list without services.
+    fn update_placeholders(&self) {
+        let imp = self.imp();
+        let count = |model: &RefCell<Option<gtk4::FilterListModel>>| {
+            model
+                .borrow()
+                .as_ref()
+                .map(|model| model.n_items())
+                .unwrap_or_default()
+        };
+        let (enabled, others) = (count(&imp.enabled_model), count(&imp.all_model));
+        if let Some(placeholder) = imp.enabled_placeholder.borrow().as_ref() {
+            placeholder.set_visible(enabled == 0);
+            let title = if imp.search_text.borrow().is_empty() {
+                gettext("No services enabled in '%s'")
+            } else {
+                gettext("No matching services enabled in '%s'")
+            };
+            placeholder.set_title(&title.replace("%s", &imp.selected_zone.borrow()));
+        }
+        if let Some(list) = imp.enabled_list.borrow().as_ref() {
+            list.set_visible(enabled > 0);
+        }
+        if let Some(placeholder) = imp.all_placeholder.borrow().as_ref() {
+            placeholder.set_visible(others == 0);
+            placeholder.set_title(&if imp.search_text.borrow().is_empty() {
+                gettext("No services available")
+            } else {
+                gettext("No matching services")
+            });
+        }
+        if let Some(list) = imp.all_list.borrow().as_ref() {
+            list.set_visible(others > 0);
+        }
     }
 
     /// Set the default zone for operations.
@@ -205,192 +386,369 @@ impl ServicesPage {
     /// Provide the per-zone enabled-service lists so the page can show the
     /// correct state for whichever zone is selected.
     pub fn set_zone_services(&self, zone_services: std::collections::HashMap<String, Vec<String>>) {
+        let changed = *self.imp().zone_enabled.borrow() != zone_services;
         self.imp().zone_enabled.replace(zone_services);
+        if changed {
+            self.refilter();
+        }
+    }
+
+    /// Provide the per-zone services enabled at runtime only, which are
+    /// flagged and offered to be made permanent.
+    pub fn set_runtime_only_services(&self, runtime_only: HashMap<String, HashSet<String>>) {
+        let changed = *self.imp().zone_runtime_only.borrow() != runtime_only;
+        self.imp().zone_runtime_only.replace(runtime_only);
+        // The badges are not part of the services, so rebuild their rows
+        if changed {
+            rebind_store(&self.service_store());
+        }
     }
 
-    /// Update the page with the full service list, then render.
+    /// Every known service, as a list of [`ServiceObject`]s that stays the
+    /// same across refreshes.
+    pub fn service_store(&self) -> gio::ListStore {
+        self.imp()
+            .service_store
+            .get_or_init(gio::ListStore::new::<ServiceObject>)
+            .clone()
+    }
+
+    /// The last service list, so the picker needs no D-Bus round-trip.
+    fn services(&self) -> Vec<Service> {
+        store_models::<ServiceObject>(&self.service_store())
+    }
+
+    /// Update the page with the full service list. Services that did not
+    /// change keep their rows.
     pub fn set_services(&self, services: &[Service]) {
-        self.imp().services.replace(services.to_vec());
-        self.render_services(services);
+        sync_store::<ServiceObject>(&self.service_store(), services);
+        self.refresh_helpers();
+    }
+
+    /// Read the helper policy and the helpers of every enabled service in
+    /// the background, then show them.
+    fn refresh_helpers(&self) {
+        let names: HashSet<String> = self
+            .imp()
+            .zone_enabled
+            .borrow()
+            .values()
+            .flatten()
+            .cloned()
+            .collect();
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result = gio::spawn_blocking(move || {
+                let policy = query_helper_policy();
+                let (automatic, helpers) = crate::firewall::read_blocking(move |client| {
+                    if client.connect().is_err() {
+                        return (None, HashMap::new());
+                    }
+                    let automatic = client.get_automatic_helpers().ok();
+                    let helpers: HashMap<String, Vec<String>> = names
+                        .iter()
+                        .filter_map(|name| client.get_service_settings(name).ok())
+                        .filter(|service| !service.helpers.is_empty())
+                        .map(|service| (service.name, service.helpers))
+                        .collect();
+                    (automatic, helpers)
+                })
+                .unwrap_or_default();
+                (policy, automatic, helpers)
+            })
+            .await;
+            if let Ok((policy, automatic, helpers)) = result {
+                let imp = page.imp();
+                imp.helper_policy.replace(Some(policy));
+                imp.automatic_helpers.replace(automatic);
+                imp.service_helpers.replace(helpers);
+                page.render_helpers();
+            }
+        });
     }
 
-    /// Render the enabled and all-services groups for the selected zone,
-    /// honoring the current search filter.
-    fn render_services(&self, services: &[Service]) {
+    /// Rebuild the helpers group for the selected zone.
+    fn render_helpers(&self) {
         let imp = self.imp();
+        let Some(group) = imp.helpers_group.borrow().clone() else {
+            return;
+        };
+        for row in imp.helper_rows.take() {
+            group.remove(&row);
+        }
+        let Some(policy) = imp.helper_policy.borrow().clone() else {
+            return;
+        };
 
-        Self::clear_preferences_group(imp.enabled_group.borrow().as_ref());
-        Self::clear_preferences_group(imp.all_group.borrow().as_ref());
+        let mut rows: Vec<gtk4::Widget> = Vec::new();
+        let automatic = imp.automatic_helpers.borrow().clone();
+        match automatic {
+            // firewalld before 1.0: the setting can be changed here
+            Some(Some(value)) => rows.push(self.create_automatic_helpers_row(&value).upcast()),
+            _ => rows.push(Self::create_automatic_state_row(&policy).upcast()),
+        }
 
-        let selected_zone = imp.selected_zone.borrow().clone();
-        let zone_enabled = imp.zone_enabled.borrow();
-        let enabled_in_zone: std::collections::HashSet<&str> = zone_enabled
-            .get(&selected_zone)
-            .map(|v| v.iter().map(|s| s.as_str()).collect())
+        let zone = imp.selected_zone.borrow().clone();
+        let service_helpers = imp.service_helpers.borrow();
+        let mut services: Vec<&String> = imp
+            .zone_enabled
+            .borrow()
+            .get(&zone)
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(|name| service_helpers.get_key_value(name).map(|(k, _)| k))
+                    .collect()
+            })
             .unwrap_or_default();
+        services.sort();
+        if services.is_empty() {
+            let row = adw::ActionRow::builder()
+                .title(gettext("No service in '%s' attaches a helper").replace("%s", &zone))
+                .sensitive(false)
+                .build();
+            rows.push(row.upcast());
+        }
+        for name in services {
+            let helpers = &service_helpers[name];
+            let subtitle = helpers
+                .iter()
+                .map(|helper| format!("{}: {}", helper, gettext(helper_description(helper))))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(name).as_str())
+                .subtitle(glib::markup_escape_text(&subtitle).as_str())
+                .subtitle_lines(4)
+                .build();
+            row.add_prefix(&gtk4::Image::from_icon_name(self.get_service_icon(name)));
+            let badge = gtk4::Label::builder()
+                .label(helpers.join(", "))
+                .css_classes(vec!["caption".to_string(), "dim-label".to_string()])
+                .valign(gtk4::Align::Center)
+                .tooltip_text(gettext(
+                    "Disable the service above if this zone does not need it",
+                ))
+                .build();
+            row.add_suffix(&badge);
+            rows.push(row.upcast());
+        }
 
-        let search = imp.search_text.borrow().clone();
-        let matches = |name: &str| search.is_empty() || name.to_lowercase().contains(&search);
-
-        // Enabled services (in the selected zone)
-        let mut enabled_services: Vec<&Service> = services
-            .iter()
-            .filter(|s| enabled_in_zone.contains(s.name.as_str()) && matches(&s.name))
-            .collect();
-        enabled_services.sort_by(|a, b| a.name.cmp(&b.name));
-
-        // Every other service, filtered by search
-        let mut all_services: Vec<&Service> = services
-            .iter()
-            .filter(|s| !enabled_in_zone.contains(s.name.as_str()) && matches(&s.name))
-            .collect();
-        all_services.sort_by(|a, b| a.name.cmp(&b.name));
-
-        if enabled_services.is_empty() {
-            if let Some(group) = imp.enabled_group.borrow().as_ref() {
-                let placeholder = adw::ActionRow::builder()
-                    .title(gettext("No services enabled in '%s'").replace("%s", &selected_zone))
-                    .subtitle(gettext("Enable services below to allow specific traffic"))
-                    .sensitive(false)
-                    .build();
-                group.add(&placeholder);
-            }
-        } else {
-            for service in enabled_services {
-                self.add_service_row(service, true);
-            }
+        if !policy.loaded.is_empty() {
+            let row = adw::ActionRow::builder()
+                .title(gettext("Loaded Helper Modules"))
+                .subtitle(policy.loaded.join(", "))
+                .build();
+            row.add_prefix(&gtk4::Image::from_icon_name(
+                "application-x-firmware-symbolic",
+            ));
+            rows.push(row.upcast());
         }
 
-        if all_services.is_empty() {
-            if let Some(group) = imp.all_group.borrow().as_ref() {
-                let placeholder = adw::ActionRow::builder()
-                    .title(if search.is_empty() {
-                        gettext("No services available")
-                    } else {
-                        gettext("No matching services")
-                    })
-                    .sensitive(false)
-                    .build();
-                group.add(&placeholder);
-            }
-        } else {
-            for service in all_services {
-                self.add_service_row(service, false);
-            }
+        for row in &rows {
+            group.add(row);
         }
+        imp.helper_rows.replace(rows);
     }
 
-    /// Helper to clear all rows from a PreferencesGroup.
-    fn clear_preferences_group(group: Option<&adw::PreferencesGroup>) {
-        if let Some(group) = group {
-            // Collect all rows first to avoid modification during iteration
-            let mut rows_to_remove: Vec<gtk4::Widget> = Vec::new();
+    /// Row changing firewalld's AutomaticHelpers, currently `value`.
+    fn create_automatic_helpers_row(&self, value: &str) -> adw::ComboRow {
+        let labels = [gettext("System Default"), gettext("On"), gettext("Off")];
+        let row = adw::ComboRow::builder()
+            .title(gettext("Automatic Helpers"))
+            .subtitle(gettext(
+                "Recommended: Off. Services that need a helper attach it themselves.",
+            ))
+            .model(&gtk4::StringList::new(
+                &labels.iter().map(String::as_str).collect::<Vec<_>>(),
+            ))
+            .build();
+        let selected = AUTOMATIC_HELPERS_VALUES
+            .iter()
+            .position(|v| *v == value)
+            .unwrap_or(0);
+        row.set_selected(selected as u32);
+        if value != "no" {
+            let icon = gtk4::Image::from_icon_name("dialog-warning-symbolic");
+            icon.add_css_class("warning");
+            row.add_prefix(&icon);
+        }
 
-            // Recursively find all ActionRow and ExpanderRow widgets
-            fn find_rows(widget: &gtk4::Widget, rows: &mut Vec<gtk4::Widget>) {
-                // Check if this widget is a row type
-                if widget.downcast_ref::<adw::ActionRow>().is_some()
-                    || widget.downcast_ref::<adw::ExpanderRow>().is_some()
-                {
-                    rows.push(widget.clone());
-                }
-                // Check children
-                if let Some(first) = widget.first_child() {
-                    let mut child = Some(first);
-                    while let Some(c) = child {
-                        find_rows(&c, rows);
-                        child = c.next_sibling();
-                    }
-                }
+        let page = self.clone();
+        row.connect_selected_notify(move |row| {
+            if let Some(value) = AUTOMATIC_HELPERS_VALUES.get(row.selected() as usize) {
+                row.set_sensitive(false);
+                page.set_automatic_helpers(value);
             }
+        });
+        row
+    }
 
-            // Find all rows in the group
-            if let Some(first) = group.first_child() {
-                let mut child = Some(first);
-                while let Some(c) = child {
-                    find_rows(&c, &mut rows_to_remove);
-                    child = c.next_sibling();
-                }
-            }
+    /// Read-only row for firewalld 1.0 and later, which never assigns
+    /// helpers itself; only an old kernel switch can.
+    fn create_automatic_state_row(policy: &HelperPolicy) -> adw::ActionRow {
+        let (subtitle, icon_name, class) = if policy.automatic() {
+            (
+                gettext(
+                    "On: the kernel attaches every loaded helper to matching traffic \
+                     (net.netfilter.nf_conntrack_helper = 1)",
+                ),
+                "dialog-warning-symbolic",
+                "warning",
+            )
+        } else {
+            (
+                gettext("Off: helpers only inspect traffic of the services that attach them"),
+                "emblem-ok-symbolic",
+                "success",
+            )
+        };
+        let row = adw::ActionRow::builder()
+            .title(gettext("Automatic Helpers"))
+            .subtitle(subtitle)
+            .build();
+        let icon = gtk4::Image::from_icon_name(icon_name);
+        icon.add_css_class(class);
+        row.add_prefix(&icon);
+        row
+    }
 
-            // Remove each row using the group's remove method
-            for row in rows_to_remove {
-                group.remove(&row);
+    /// Change AutomaticHelpers; firewalld reloads to apply it.
+    fn set_automatic_helpers(&self, value: &'static str) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result = crate::firewall::call(move |client| {
+                client.connect()?;
+                client.set_automatic_helpers(value)
+            })
+            .await;
+            match result {
+                Ok(Ok(())) => page.show_toast(&gettext("Automatic helpers updated")),
+                Ok(Err(e)) => page.show_toast(&format!(
+                    "{}: {}",
+                    gettext("Failed to change automatic helpers"),
+                    e
+                )),
+                Err(_) => page.show_toast(&gettext("Failed to change automatic helpers")),
             }
-        }
+            page.refresh_helpers();
+        });
     }
 
-    /// Add a service row to the appropriate group.
-    fn add_service_row(&self, service: &Service, enabled: bool) {
+    /// Build the row of a service in the enabled or the other list.
+    fn create_service_row(&self, object: &ServiceObject, enabled: bool) -> adw::ActionRow {
         let imp = self.imp();
 
-        let group = if enabled {
-            imp.enabled_group.borrow()
-        } else {
-            imp.all_group.borrow()
-        };
-
-        if let Some(group) = group.as_ref() {
-            // Escape defensively: AdwActionRow renders title/subtitle as markup
-            let row = adw::ActionRow::builder()
-                .title(glib::markup_escape_text(&service.name).as_str())
-                .subtitle(glib::markup_escape_text(service.human_description()).as_str())
+        // Prefer the full definition when the picker has already fetched it
+        let service = imp
+            .service_details
+            .borrow()
+            .get(&object.name())
+            .cloned()
+            .unwrap_or_else(|| object.model());
+
+        // Escape defensively: AdwActionRow renders title/subtitle as markup
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(&service.name).as_str())
+            .subtitle(glib::markup_escape_text(service.human_description()).as_str())
+            .activatable(true)
+            .build();
+        let page = self.clone();
+        let service_name = service.name.clone();
+        row.connect_activated(move |_| page.present_service_details(&service_name));
+
+        // Service icon
+        let icon_name = self.get_service_icon(&service.name);
+        row.add_prefix(&gtk4::Image::from_icon_name(icon_name));
+        attach_service_tooltip(&row, &service.name);
+
+        // Ports badge
+        let ports_summary = service.ports_summary();
+        if !ports_summary.is_empty() {
+            let ports_label = gtk4::Label::builder()
+                .label(&ports_summary)
+                .css_classes(vec!["caption".to_string(), "dim-label".to_string()])
+                .valign(gtk4::Align::Center)
                 .build();
+            row.add_suffix(&ports_label);
+        }
 
-            // Service icon
-            let icon_name = self.get_service_icon(&service.name);
-            row.add_prefix(&gtk4::Image::from_icon_name(icon_name));
-
-            // Ports badge
-            let ports_summary = service.ports_summary();
-            if !ports_summary.is_empty() {
-                let ports_label = gtk4::Label::builder()
-                    .label(&ports_summary)
-                    .css_classes(vec!["caption".to_string(), "dim-label".to_string()])
-                    .valign(gtk4::Align::Center)
-                    .build();
-                row.add_suffix(&ports_label);
-            }
-
-            // Toggle switch
-            let switch = gtk4::Switch::builder()
-                .active(enabled)
+        // Runtime-only badge, with a one-click fix
+        let zone = imp.selected_zone.borrow().clone();
+        let runtime_only = enabled
+            && imp
+                .zone_runtime_only
+                .borrow()
+                .get(&zone)
+                .is_some_and(|services| services.contains(&service.name));
+        if runtime_only {
+            let badge = gtk4::Label::builder()
+                .label(gettext("Runtime only"))
+                .css_classes(vec![
+                    "caption".to_string(),
+                    "card".to_string(),
+                    "warning".to_string(),
+                ])
+                .tooltip_text(gettext(
+                    "Lost when firewalld reloads or the system restarts",
+                ))
                 .valign(gtk4::Align::Center)
-                .tooltip_text(if enabled {
-                    gettext("Disable service")
-                } else {
-                    gettext("Enable service")
-                })
                 .build();
+            row.add_suffix(&badge);
 
-            let service_name = service.name.clone();
+            let permanent_button = gtk4::Button::builder()
+                .icon_name("document-save-symbolic")
+                .tooltip_text(gettext("Make permanent"))
+                .valign(gtk4::Align::Center)
+                .css_classes(vec!["flat".to_string()])
+                .build();
             let page = self.clone();
-            let is_enabled = enabled;
+            let service_name = service.name.clone();
+            permanent_button.connect_clicked(move |button| {
+                page.make_permanent(&service_name, button);
+            });
+            row.add_suffix(&permanent_button);
+        }
 
-            switch.connect_state_set(move |switch, state| {
-                // Prevent loops
-                if state == is_enabled {
-                    return glib::Propagation::Stop;
-                }
+        // Toggle switch
+        let switch = gtk4::Switch::builder()
+            .active(enabled)
+            .valign(gtk4::Align::Center)
+            .tooltip_text(if enabled {
+                gettext("Disable service")
+            } else {
+                gettext("Enable service")
+            })
+            .build();
 
-                let service_name = service_name.clone();
-                let page = page.clone();
-                let switch = switch.clone();
+        let service_name = service.name.clone();
+        let service_ports = service.ports.clone();
+        let page = self.clone();
+        let is_enabled = enabled;
 
-                switch.set_sensitive(false);
+        switch.connect_state_set(move |switch, state| {
+            // Prevent loops
+            if state == is_enabled {
+                return glib::Propagation::Stop;
+            }
 
-                if state {
-                    page.enable_service(&service_name, switch);
-                } else {
-                    page.disable_service(&service_name, switch);
-                }
+            let service_name = service_name.clone();
+            let page = page.clone();
+            let switch = switch.clone();
 
-                glib::Propagation::Proceed
-            });
+            switch.set_sensitive(false);
 
-            row.add_suffix(&switch);
-            group.add(&row);
-        }
+            if state {
+                page.enable_service(&service_name, Some(switch));
+            } else {
+                page.request_disable(&service_name, &service_ports, switch);
+            }
+
+            glib::Propagation::Proceed
+        });
+
+        row.add_suffix(&switch);
+        row
     }
 
     /// Get appropriate icon for service type.
@@ -411,8 +769,9 @@ impl ServicesPage {
         }
     }
 
-    /// Enable a service.
-    fn enable_service(&self, name: &str, switch: gtk4::Switch) {
+    /// Enable a service. `switch` is the row toggle to restore on failure,
+    /// if the request came from one.
+    fn enable_service(&self, name: &str, switch: Option<gtk4::Switch>) {
         let imp = self.imp();
         let zone = imp.selected_zone.borrow().clone();
         let service_name = name.to_string();
@@ -421,8 +780,7 @@ impl ServicesPage {
         glib::spawn_future_local(async move {
             let service_clone = service_name.clone();
             let zone_clone = zone.clone();
-            let result = gtk4::gio::spawn_blocking(move || {
-                let mut client = crate::firewall::FirewallClient::new();
+            let result = crate::firewall::call(move |client| {
                 if client.connect().is_err() {
                     return Err(anyhow::anyhow!("Not connected to firewalld"));
                 }
@@ -445,19 +803,128 @@ impl ServicesPage {
                     page.request_refresh();
                 }
                 Ok(Err(e)) => {
-                    switch.set_active(false);
+                    if let Some(switch) = &switch {
+                        switch.set_active(false);
+                    }
                     page.show_toast(&format!("{}: {}", gettext("Failed to enable service"), e));
                 }
                 Err(_) => {
-                    switch.set_active(false);
+                    if let Some(switch) = &switch {
+                        switch.set_active(false);
+                    }
                     page.show_toast(&gettext("Failed to enable service"));
                 }
             }
-            switch.set_sensitive(true);
+            if let Some(switch) = switch {
+                switch.set_sensitive(true);
+            }
         });
     }
 
-    /// Disable a service.
+    /// Save a service enabled at runtime only to the permanent
+    /// configuration of the selected zone.
+    fn make_permanent(&self, name: &str, button: &gtk4::Button) {
+        let zone = self.imp().selected_zone.borrow().clone();
+        let service_name = name.to_string();
+        let page = self.clone();
+        let button = button.clone();
+        button.set_sensitive(false);
+
+        glib::spawn_future_local(async move {
+            let service_clone = service_name.clone();
+            let result = crate::firewall::call(move |client| {
+                if client.connect().is_err() {
+                    return Err(anyhow::anyhow!("Not connected to firewalld"));
+                }
+                // Already enabled at runtime, so this only writes the
+                // permanent configuration
+                client.enable_service(&zone, &service_clone, true)
+            })
+            .await;
+
+            match result {
+                Ok(Ok(outcome)) if !outcome.failed() => {
+                    page.show_toast(
+                        &gettext("Service '%s' is now permanent").replace("%s", &service_name),
+                    );
+                    page.request_refresh();
+                }
+                Ok(Ok(_)) => {
+                    page.show_toast(&gettext("Failed to save the service permanently"));
+                    button.set_sensitive(true);
+                }
+                Ok(Err(e)) => {
+                    page.show_toast(&format!(
+                        "{}: {}",
+                        gettext("Failed to save the service permanently"),
+                        e
+                    ));
+                    button.set_sensitive(true);
+                }
+                Err(_) => {
+                    page.show_toast(&gettext("Failed to save the service permanently"));
+                    button.set_sensitive(true);
+                }
+            }
+        });
+    }
+
+    /// Disable a service, warning first when remote SSH sessions come in
+    /// on its TCP ports and offering a trial of the change. Disabling ssh
+    /// itself always reverts unless kept, so it needs no warning.
+    fn request_disable(&self, name: &str, ports: &[(String, String)], switch: gtk4::Switch) {
+        if is_risky_service(name) {
+            self.disable_service(name, switch);
+            return;
+        }
+
+        let ranges: Vec<(u16, u16, String)> = ports
+            .iter()
+            .filter_map(|(port, protocol)| {
+                let (start, end) = parse_port_spec(port)?;
+                Some((start, end, protocol.clone()))
+            })
+            .collect();
+        let page = self.clone();
+        let name = name.to_string();
+        lockout_guard::with_sessions_on(ranges, move |sessions| {
+            let window = match page.main_window() {
+                Some(window) if !sessions.is_empty() => window,
+                _ => {
+                    page.disable_service(&name, switch);
+                    return;
+                }
+            };
+            let zone = page.imp().selected_zone.borrow().clone();
+            let (revert_zone, service, revert_service) = (zone.clone(), name.clone(), name.clone());
+            let trial = TrialChange {
+                apply: Box::new(move |client| {
+                    client.disable_service(&zone, &service, false)?;
+                    Ok(())
+                }),
+                revert: Box::new(move |client| {
+                    client.enable_service(&revert_zone, &revert_service, false)?;
+                    Ok(())
+                }),
+            };
+            let (commit_page, commit_switch) = (page.clone(), switch.clone());
+            let commit_name = name.clone();
+            lockout_guard::present_lockout_guard(
+                &window,
+                &gettext("Disabling the '%s' service").replacen("%s", &name, 1),
+                &sessions,
+                trial,
+                move || commit_page.disable_service(&commit_name, commit_switch.clone()),
+                move || {
+                    switch.set_active(true);
+                    switch.set_sensitive(true);
+                },
+            );
+        });
+    }
+
+    /// Disable a service. Disabling a risky one such as ssh is reverted
+    /// unless the user keeps it in time; others can be undone from the toast.
     fn disable_service(&self, name: &str, switch: gtk4::Switch) {
         let imp = self.imp();
         let zone = imp.selected_zone.borrow().clone();
@@ -467,26 +934,59 @@ impl ServicesPage {
         glib::spawn_future_local(async move {
             let service_clone = service_name.clone();
             let zone_clone = zone.clone();
-            let result = gtk4::gio::spawn_blocking(move || {
-                let mut client = crate::firewall::FirewallClient::new();
+            let result = crate::firewall::call(move |client| {
                 if client.connect().is_err() {
                     return Err(anyhow::anyhow!("Not connected to firewalld"));
                 }
-                client.disable_service(&zone_clone, &service_clone, true)
+                // Where the service is enabled, read before it is removed
+                let calls = client
+                    .get_zones()
+                    .map(|zones| {
+                        zones
+                            .iter()
+                            .filter(|zone| zone.name == zone_clone)
+                            .flat_map(|zone| {
+                                journal::inverse_of_removal(
+                                    zone,
+                                    &[RuleItem::Service(service_clone.clone())],
+                                )
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let outcome = client.disable_service(&zone_clone, &service_clone, true)?;
+                let undo = journal::record(
+                    format!("Service {} removed from zone {}", service_clone, zone_clone),
+                    calls,
+                );
+                Ok((outcome, undo))
             })
             .await;
 
             match result {
-                Ok(Ok(outcome)) => {
-                    if outcome.failed() {
-                        page.show_toast(&format!(
+                Ok(Ok((outcome, undo))) => {
+                    let message = if outcome.failed() {
+                        format!(
                             "Service '{}' disabled for this session only — saving permanently failed",
                             service_name
-                        ));
+                        )
                     } else {
-                        page.show_toast(
-                            &gettext("Service '%s' disabled").replace("%s", &service_name),
-                        );
+                        gettext("Service '%s' disabled").replace("%s", &service_name)
+                    };
+                    match (undo, page.main_window()) {
+                        // The revert countdown undoes a risky one instead
+                        (Some(id), Some(window)) if !is_risky_service(&service_name) => {
+                            window.show_undo_toast(&message, id)
+                        }
+                        _ => page.show_toast(&message),
+                    }
+                    if is_risky_service(&service_name) {
+                        if let Some(window) = page.main_window() {
+                            window.revert_unless_kept(Undo::ZoneService {
+                                zone,
+                                service: service_name,
+                            });
+                        }
                     }
                     page.request_refresh();
                 }
@@ -503,17 +1003,418 @@ impl ServicesPage {
         });
     }
 
-    /// Request a refresh from the main window.
+    /// Present a searchable picker of the services not yet enabled in the
+    /// selected zone. Descriptions and ports are fetched lazily for the
+    /// rows that match the search, a batch at a time.
+    fn present_service_picker(&self) {
+        let imp = self.imp();
+        let zone = imp.selected_zone.borrow().clone();
+
+        let enabled_in_zone: HashSet<String> = imp
+            .zone_enabled
+            .borrow()
+            .get(&zone)
+            .map(|v| v.iter().cloned().collect())
+            .unwrap_or_default();
+        let mut names: Vec<String> = self
+            .services()
+            .iter()
+            .filter(|s| !enabled_in_zone.contains(&s.name))
+            .map(|s| s.name.clone())
+            .collect();
+        names.sort();
+
+        let dialog = adw::Dialog::builder()
+            .title(gettext("Add Service to '%s'").replace("%s", &zone))
+            .content_width(520)
+            .content_height(600)
+            .build();
+
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&adw::HeaderBar::new());
+
+        let search_entry = gtk4::SearchEntry::builder()
+            .placeholder_text(gettext("Search by name or description"))
+            .margin_start(12)
+            .margin_end(12)
+            .margin_bottom(6)
+            .build();
+        toolbar.add_top_bar(&search_entry);
+
+        let list = gtk4::ListBox::builder()
+            .selection_mode(gtk4::SelectionMode::None)
+            .css_classes(vec!["boxed-list".to_string()])
+            .valign(gtk4::Align::Start)
+            .margin_start(12)
+            .margin_end(12)
+            .margin_top(6)
+            .margin_bottom(12)
+            .build();
+        list.set_placeholder(Some(
+            &gtk4::Label::builder()
+                .label(gettext("No matching services"))
+                .css_classes(vec!["dim-label".to_string()])
+                .margin_top(12)
+                .margin_bottom(12)
+                .build(),
+        ));
+
+        let rows: Rc<Vec<PickerRow>> = Rc::new(
+            names
+                .iter()
+                .map(|name| {
+                    let row = adw::ActionRow::builder()
+                        .title(glib::markup_escape_text(name).as_str())
+                        .subtitle(gettext("Loading description…"))
+                        .activatable(true)
+                        .build();
+                    row.add_prefix(&gtk4::Image::from_icon_name(self.get_service_icon(name)));
+                    attach_service_tooltip(&row, name);
+                    let ports = gtk4::Label::builder()
+                        .css_classes(vec!["caption".to_string(), "dim-label".to_string()])
+                        .valign(gtk4::Align::Center)
+                        .build();
+                    row.add_suffix(&ports);
+                    row.add_suffix(&gtk4::Image::from_icon_name("list-add-symbolic"));
+
+                    let page = self.clone();
+                    let dialog = dialog.clone();
+                    let service_name = name.clone();
+                    row.connect_activated(move |_| {
+                        dialog.close();
+                        page.enable_service(&service_name, None);
+                    });
+
+                    list.append(&row);
+                    PickerRow {
+                        name: name.clone(),
+                        row,
+                        ports,
+                    }
+                })
+                .collect(),
+        );
+        self.update_picker_rows(&rows);
+
+        // Match the name, or the description once it is known
+        let page_for_filter = self.clone();
+        let rows_for_filter = rows.clone();
+        list.set_filter_func(move |widget| {
+            let query = page_for_filter.imp().picker_query.borrow().clone();
+            if query.is_empty() {
+                return true;
+            }
+            let Some(entry) = rows_for_filter
+                .iter()
+                .find(|r| r.row.upcast_ref::<gtk4::Widget>() == widget)
+            else {
+                return true;
+            };
+            entry.name.to_lowercase().contains(&query)
+                || page_for_filter
+                    .imp()
+                    .service_details
+                    .borrow()
+                    .get(&entry.name)
+                    .is_some_and(|s| s.description.to_lowercase().contains(&query))
+        });
+
+        let page_for_search = self.clone();
+        let rows_for_search = rows.clone();
+        let list_for_search = list.clone();
+        search_entry.connect_search_changed(move |entry| {
+            page_for_search
+                .imp()
+                .picker_query
+                .replace(entry.text().trim().to_lowercase());
+            list_for_search.invalidate_filter();
+            page_for_search.load_picker_details(&rows_for_search, &list_for_search);
+        });
+
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .vexpand(true)
+            .child(&list)
+            .build();
+        // Fetch the next batch as the user scrolls down
+        let page_for_scroll = self.clone();
+        let rows_for_scroll = rows.clone();
+        let list_for_scroll = list.clone();
+        scrolled.connect_edge_reached(move |_, edge| {
+            if edge == gtk4::PositionType::Bottom {
+                page_for_scroll.load_picker_details(&rows_for_scroll, &list_for_scroll);
+            }
+        });
+
+        toolbar.set_content(Some(&scrolled));
+        dialog.set_child(Some(&toolbar));
+
+        imp.picker_query.replace(String::new());
+        dialog.present(Some(self));
+        search_entry.grab_focus();
+        self.load_picker_details(&rows, &list);
+    }
+
+    /// Fetch definitions for the next batch of matching picker rows that
+    /// are neither cached nor already being fetched.
+    fn load_picker_details(&self, rows: &Rc<Vec<PickerRow>>, list: &gtk4::ListBox) {
+        let imp = self.imp();
+        let query = imp.picker_query.borrow().clone();
+        let names: Vec<String> = {
+            let cache = imp.service_details.borrow();
+            let mut pending = imp.details_pending.borrow_mut();
+            let names: Vec<String> = rows
+                .iter()
+                .filter(|r| query.is_empty() || r.name.to_lowercase().contains(&query))
+                .filter(|r| !cache.contains_key(&r.name) && !pending.contains(&r.name))
+                .take(PICKER_BATCH)
+                .map(|r| r.name.clone())
+                .collect();
+            pending.extend(names.iter().cloned());
+            names
+        };
+        if names.is_empty() {
+            return;
+        }
+
+        let page = self.clone();
+        let rows = rows.clone();
+        let list = list.clone();
+        glib::spawn_future_local(async move {
+            let requested = names.clone();
+            let result = crate::firewall::call(move |client| {
+                client.connect()?;
+                Ok::<_, anyhow::Error>(
+                    names
+                        .iter()
+                        .filter_map(|name| client.get_service_settings(name).ok())
+                        .collect::<Vec<Service>>(),
+                )
+            })
+            .await;
+
+            let imp = page.imp();
+            {
+                let mut pending = imp.details_pending.borrow_mut();
+                for name in &requested {
+                    pending.remove(name);
+                }
+            }
+            match result {
+                Ok(Ok(services)) => {
+                    let mut cache = imp.service_details.borrow_mut();
+                    for service in services {
+                        cache.insert(service.name.clone(), service);
+                    }
+                }
+                Ok(Err(e)) => tracing::warn!("Failed to load service definitions: {}", e),
+                Err(_) => tracing::warn!("Failed to load service definitions"),
+            }
+            page.update_picker_rows(&rows);
+            // Descriptions can now match the search
+            list.invalidate_filter();
+        });
+    }
+
+    /// Show cached descriptions and ports on the picker rows.
+    fn update_picker_rows(&self, rows: &[PickerRow]) {
+        let cache = self.imp().service_details.borrow();
+        for entry in rows {
+            if let Some(service) = cache.get(&entry.name) {
+                entry
+                    .row
+                    .set_subtitle(glib::markup_escape_text(service.human_description()).as_str());
+                entry.ports.set_label(&service.ports_summary());
+            }
+        }
+    }
+
+    /// Read the full definition of `name` and show it in a dialog.
+    fn present_service_details(&self, name: &str) {
+        let page = self.clone();
+        let name = name.to_string();
+        glib::spawn_future_local(async move {
+            let name_for_read = name.clone();
+            let result = crate::firewall::call(move |client| {
+                client.connect()?;
+                client.get_service_settings(&name_for_read)
+            })
+            .await;
+            match result {
+                Ok(Ok(service)) => {
+                    page.imp()
+                        .service_details
+                        .borrow_mut()
+                        .insert(service.name.clone(), service.clone());
+                    page.show_service_details(&service);
+                }
+                Ok(Err(e)) => page.show_toast(&format!(
+                    "{}: {}",
+                    gettext("Failed to read the service definition"),
+                    e
+                )),
+                Err(_) => page.show_toast(&gettext("Failed to read the service definition")),
+            }
+        });
+    }
+
+    /// Present the definition of `service`: what it opens and where.
+    fn show_service_details(&self, service: &Service) {
+        let dialog = adw::Dialog::builder()
+            .title(&service.name)
+            .content_width(480)
+            .build();
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&adw::HeaderBar::new());
+
+        let group = adw::PreferencesGroup::builder()
+            .description(glib::markup_escape_text(service.human_description()).as_str())
+            .build();
+        let pairs = |items: &[(String, String)], separator: &str| -> Vec<String> {
+            items
+                .iter()
+                .map(|(a, b)| format!("{}{}{}", a, separator, b))
+                .collect()
+        };
+        let entries = [
+            (gettext("Ports"), pairs(&service.ports, "/")),
+            (gettext("Protocols"), service.protocols.clone()),
+            (gettext("Source Ports"), pairs(&service.source_ports, "/")),
+            (gettext("Helpers"), service.helpers.clone()),
+            (gettext("Destinations"), pairs(&service.destinations, ": ")),
+        ];
+        for (title, items) in entries {
+            let row = adw::ActionRow::builder()
+                .title(title)
+                .subtitle(if items.is_empty() {
+                    gettext("None")
+                } else {
+                    glib::markup_escape_text(&items.join(", ")).to_string()
+                })
+                .subtitle_selectable(true)
+                .css_classes(vec!["property".to_string()])
+                .build();
+            group.add(&row);
+        }
+
+        let preferences = adw::PreferencesPage::new();
+        preferences.add(&group);
+        toolbar.set_content(Some(&preferences));
+        dialog.set_child(Some(&toolbar));
+        dialog.present(Some(self));
+    }
+
+    /// Present the dialog defining a new firewalld service with its ports.
+    fn present_service_creator(&self) {
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("Create Custom Service"))
+            .body(gettext(
+                "The service is saved permanently and can then be enabled in any zone. \
+                 Reloading firewalld to add it discards changes made for this session only.",
+            ))
+            .build();
+
+        let group = adw::PreferencesGroup::new();
+        let name_row = adw::EntryRow::builder()
+            .title(gettext("Name (letters, digits, -, _, . and +)"))
+            .build();
+        group.add(&name_row);
+        let description_row = adw::EntryRow::builder()
+            .title(gettext("Description (optional)"))
+            .build();
+        group.add(&description_row);
+        let ports_row = adw::EntryRow::builder()
+            .title(gettext("Ports, e.g. 8080/tcp, 6000-6100/udp"))
+            .build();
+        group.add(&ports_row);
+
+        dialog.set_extra_child(Some(&group));
+        dialog.add_response("cancel", "_Cancel");
+        dialog.add_response("create", "C_reate");
+        dialog.set_response_appearance("create", adw::ResponseAppearance::Suggested);
+
+        let existing: HashSet<String> = self.services().into_iter().map(|s| s.name).collect();
+        let revalidate = Rc::new({
+            let dialog = dialog.clone();
+            let name_row = name_row.clone();
+            let ports_row = ports_row.clone();
+            move || {
+                let name = name_row.text();
+                let name_ok =
+                    validate_firewall_service(&name).is_some() && !existing.contains(name.as_str());
+                let ports_ok = parse_port_list(&ports_row.text()).is_some();
+                for (row, ok) in [(&name_row, name_ok), (&ports_row, ports_ok)] {
+                    if ok || row.text().is_empty() {
+                        row.remove_css_class("error");
+                    } else {
+                        row.add_css_class("error");
+                    }
+                }
+                dialog.set_response_enabled("create", name_ok && ports_ok);
+            }
+        });
+        revalidate();
+        let revalidate_name = revalidate.clone();
+        name_row.connect_changed(move |_| revalidate_name());
+        ports_row.connect_changed(move |_| revalidate());
+
+        let page = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "create" {
+                return;
+            }
+            let mut service = Service::new(name_row.text().trim());
+            service.description = description_row.text().trim().to_string();
+            service.ports = parse_port_list(&ports_row.text()).unwrap_or_default();
+            page.create_service(service);
+        });
+
+        dialog.present(Some(self));
+    }
+
+    /// Create `service` in the background, reload firewalld so it can be
+    /// enabled, and report the result.
+    fn create_service(&self, service: Service) {
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let name = service.name.clone();
+            let result = crate::firewall::call(move |client| {
+                client.connect()?;
+                client.create_service(&service)?;
+                client.reload()?;
+                Ok::<_, anyhow::Error>(())
+            })
+            .await;
+            match result {
+                Ok(Ok(())) => {
+                    page.show_toast(&gettext("Service '%s' created").replace("%s", &name));
+                    page.request_refresh();
+                }
+                Ok(Err(e)) => {
+                    page.show_toast(&format!("{}: {}", gettext("Failed to create service"), e));
+                }
+                Err(_) => page.show_toast(&gettext("Failed to create service")),
+            }
+        });
+    }
+
+    /// Have the main window refresh what shows services.
     fn request_refresh(&self) {
         if let Some(root) = self.root() {
             if let Some(window) = root.downcast_ref::<gtk4::Window>() {
                 if let Some(main_window) = window.downcast_ref::<super::MainWindow>() {
-                    main_window.refresh_data();
+                    main_window.invalidate(Invalidation::ServicesChanged);
                 }
             }
         }
     }
 
+    fn main_window(&self) -> Option<super::MainWindow> {
+        self.root()
+            .and_then(|root| root.downcast::<gtk4::Window>().ok())
+            .and_then(|window| window.downcast::<super::MainWindow>().ok())
+    }
+
     /// Show a toast message.
     fn show_toast(&self, message: &str) {
         if let Some(root) = self.root() {
@@ -550,6 +1451,17 @@ impl ServicesPage {
     }
 }
 
+/// Number of service definitions the picker fetches per batch.
+const PICKER_BATCH: usize = 30;
+
+/// A row of the service picker and the widgets filled in once the
+/// service's definition is fetched.
+struct PickerRow {
+    name: String,
+    row: adw::ActionRow,
+    ports: gtk4::Label,
+}
+
 impl Default for ServicesPage {
     fn default() -> Self {
         Self::new()
@@ -563,6 +1475,16 @@ mod imp {
     pub struct ServicesPage {
         pub enabled_group: RefCell<Option<adw::PreferencesGroup>>,
         pub all_group: RefCell<Option<adw::PreferencesGroup>>,
+        // The enabled and other lists over `service_store`, and what shows
+        // when they are empty
+        pub enabled_filter: RefCell<Option<gtk4::CustomFilter>>,
+        pub all_filter: RefCell<Option<gtk4::CustomFilter>>,
+        pub enabled_model: RefCell<Option<gtk4::FilterListModel>>,
+        pub all_model: RefCell<Option<gtk4::FilterListModel>>,
+        pub enabled_list: RefCell<Option<gtk4::ScrolledWindow>>,
+        pub all_list: RefCell<Option<gtk4::ScrolledWindow>>,
+        pub enabled_placeholder: RefCell<Option<adw::ActionRow>>,
+        pub all_placeholder: RefCell<Option<adw::ActionRow>>,
         pub default_zone: RefCell<String>,
         pub client: RefCell<Option<Rc<RefCell<FirewallClient>>>>,
         // The zone currently targeted by enable/disable (defaults to the
@@ -571,10 +1493,25 @@ mod imp {
         pub available_zones: RefCell<Vec<String>>,
         // Per-zone enabled service names, so state reflects the selected zone.
         pub zone_enabled: RefCell<std::collections::HashMap<String, Vec<String>>>,
-        // Cache of the last service list so search re-filters without a D-Bus round-trip.
-        pub services: RefCell<Vec<Service>>,
+        // Per-zone services enabled at runtime only, lost on reload.
+        pub zone_runtime_only: RefCell<HashMap<String, HashSet<String>>>,
+        // The last service list, see `service_store()`
+        pub service_store: OnceCell<gio::ListStore>,
         pub search_text: RefCell<String>,
         pub zone_dropdown: RefCell<Option<adw::ComboRow>>,
+        // Service definitions fetched by the picker, keyed by name. Kept for
+        // the page's lifetime so reopening the picker costs no D-Bus calls.
+        pub service_details: RefCell<HashMap<String, Service>>,
+        pub details_pending: RefCell<HashSet<String>>,
+        pub picker_query: RefCell<String>,
+        pub helpers_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub helper_rows: RefCell<Vec<gtk4::Widget>>,
+        pub helper_policy: RefCell<Option<HelperPolicy>>,
+        // firewalld's AutomaticHelpers: `None` until read, `Some(None)` when
+        // firewalld no longer has the setting.
+        pub automatic_helpers: RefCell<Option<Option<String>>>,
+        // Helpers attached by enabled services, keyed by service name.
+        pub service_helpers: RefCell<HashMap<String, Vec<String>>>,
     }
 
     #[glib::object_subclass]
@@ -3,17 +3,42 @@
 // SPDX-License-Identifier: MIT
 
 //! System services management page.
+//!
+//! The Security view manages a curated set of security-relevant services.
+//! All Services lists every service of the system or user manager in a
+//! `GtkListView`, filtered by state, startup and text.
 
-use std::cell::RefCell;
+use std::cell::{Cell, OnceCell, RefCell};
+use std::rc::Rc;
 
-use gtk4::glib;
 use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
+use gtk4::{gio, glib};
 use libadwaita as adw;
 use libadwaita::prelude::*;
 
+use super::density::{self, Role};
+use super::palette;
+use crate::helper::{run_privileged, HelperOp};
 use crate::i18n::gettext;
-use crate::systemd::{ServiceInfo, ServiceState, SystemdClient};
+use crate::models::{sync_store, ModelObject, UnitObject};
+use crate::systemd::{
+    parse_hardening_dropin, read_unit_files, read_unit_log, EnablementFilter, HardeningDirective,
+    JournalLine, SandboxReport, ServiceInfo, ServiceState, StateFilter, SystemdClient, UnitFile,
+    UnitFilter, UnitLog, UnitScope,
+};
+
+/// Line counts offered in the Recent Logs dialog.
+const LOG_LINE_COUNTS: [usize; 3] = [50, 200, 1000];
+
+/// Actions of a service's More menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ServiceCommand {
+    Reload,
+    ReloadOrRestart,
+    Mask,
+    Unmask,
+}
 
 glib::wrapper! {
     /// System services management page.
@@ -41,11 +66,8 @@ impl SystemServicesPage {
         let header_box = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Horizontal)
             .spacing(12)
-            .margin_start(24)
-            .margin_end(24)
-            .margin_top(24)
-            .margin_bottom(12)
             .build();
+        density::track(&header_box, Role::PageHeader);
 
         let title_box = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Vertical)
@@ -77,10 +99,32 @@ impl SystemServicesPage {
 
         let page_clone = self.clone();
         refresh_button.connect_clicked(move |_| {
-            page_clone.refresh_services();
+            if page_clone.showing_all_services() {
+                page_clone.load_all_services();
+            } else {
+                page_clone.refresh_services();
+            }
         });
 
+        // The curated security services, or every service with filters
+        let security_toggle = gtk4::ToggleButton::builder()
+            .label(gettext("Security"))
+            .active(true)
+            .build();
+        let all_toggle = gtk4::ToggleButton::builder()
+            .label(gettext("All Services"))
+            .group(&security_toggle)
+            .build();
+        let view_switch = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .css_classes(vec!["linked".to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+        view_switch.append(&security_toggle);
+        view_switch.append(&all_toggle);
+
         header_box.append(&title_box);
+        header_box.append(&view_switch);
         header_box.append(&refresh_button);
         self.append(&header_box);
 
@@ -94,13 +138,15 @@ impl SystemServicesPage {
 
         let content = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Vertical)
-            .spacing(24)
-            .margin_top(24)
-            .margin_bottom(24)
-            .margin_start(24)
-            .margin_end(24)
             .hexpand(true)
             .build();
+        density::track(
+            &content,
+            Role::Content {
+                margin: 24,
+                spacing: 24,
+            },
+        );
 
         // Search entry
         let search_entry = gtk4::SearchEntry::builder()
@@ -151,12 +197,318 @@ impl SystemServicesPage {
         content.append(&failed_group);
 
         scrolled.set_child(Some(&content));
-        self.append(&scrolled);
+
+        let view_stack = gtk4::Stack::builder()
+            .transition_type(gtk4::StackTransitionType::Crossfade)
+            .vexpand(true)
+            .build();
+        view_stack.add_named(&scrolled, Some("security"));
+        view_stack.add_named(&self.create_all_services_view(), Some("all"));
+        imp.view_stack.replace(Some(view_stack.clone()));
+        self.append(&view_stack);
+
+        let page = self.clone();
+        all_toggle.connect_toggled(move |toggle| {
+            let Some(stack) = page.imp().view_stack.borrow().clone() else {
+                return;
+            };
+            if toggle.is_active() {
+                stack.set_visible_child_name("all");
+                // Listed on first view; the refresh button lists again
+                if page.unit_store().n_items() == 0 {
+                    page.load_all_services();
+                }
+            } else {
+                stack.set_visible_child_name("security");
+            }
+        });
 
         // Toast overlay for notifications
         imp.toast_overlay.replace(None);
     }
 
+    /// The All Services view: filters above a list that only builds the
+    /// rows in sight, so it stays fast with hundreds of units.
+    fn create_all_services_view(&self) -> gtk4::Box {
+        let imp = self.imp();
+        let view = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(12)
+            .build();
+        density::track(
+            &view,
+            Role::Content {
+                margin: 24,
+                spacing: 12,
+            },
+        );
+
+        let filter_bar = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(6)
+            .build();
+        let search_entry = gtk4::SearchEntry::builder()
+            .placeholder_text(gettext("Search all services..."))
+            .hexpand(true)
+            .build();
+        let state_dropdown = gtk4::DropDown::from_strings(&[
+            &gettext("Any State"),
+            &gettext("Running"),
+            &gettext("Stopped"),
+            &gettext("Failed"),
+        ]);
+        state_dropdown.set_tooltip_text(Some(&gettext("State")));
+        let enablement_dropdown = gtk4::DropDown::from_strings(&[
+            &gettext("Any Startup"),
+            &gettext("Enabled"),
+            &gettext("Disabled"),
+            &gettext("Masked"),
+        ]);
+        enablement_dropdown.set_tooltip_text(Some(&gettext("Started at boot")));
+        let scope_dropdown = gtk4::DropDown::from_strings(&[&gettext("System"), &gettext("User")]);
+        scope_dropdown
+            .set_tooltip_text(Some(&gettext("System services, or your own user services")));
+        filter_bar.append(&search_entry);
+        filter_bar.append(&state_dropdown);
+        filter_bar.append(&enablement_dropdown);
+        filter_bar.append(&scope_dropdown);
+        view.append(&filter_bar);
+
+        let count_label = gtk4::Label::builder()
+            .css_classes(vec!["dim-label".to_string(), "caption".to_string()])
+            .halign(gtk4::Align::Start)
+            .build();
+        imp.unit_count_label.replace(Some(count_label.clone()));
+        view.append(&count_label);
+
+        // Store, then the filter, then rows for what is in sight
+        let page = self.downgrade();
+        let filter = gtk4::CustomFilter::new(move |object| {
+            let Some(page) = page.upgrade() else {
+                return true;
+            };
+            object
+                .downcast_ref::<UnitObject>()
+                .is_some_and(|unit| page.imp().unit_filter.borrow().matches(&unit.model()))
+        });
+        let filtered = gtk4::FilterListModel::new(Some(self.unit_store()), Some(filter.clone()));
+        imp.unit_custom_filter.replace(Some(filter));
+        let page = self.clone();
+        filtered.connect_items_changed(move |_, _, _, _| page.update_unit_count());
+        imp.unit_filtered.replace(Some(filtered.clone()));
+
+        let factory = gtk4::SignalListItemFactory::new();
+        factory.connect_setup(|_, item| {
+            if let Some(item) = item.downcast_ref::<gtk4::ListItem>() {
+                Self::setup_unit_row(item);
+            }
+        });
+        let list_view = gtk4::ListView::builder()
+            .model(&gtk4::NoSelection::new(Some(filtered)))
+            .factory(&factory)
+            .css_classes(vec!["card".to_string()])
+            .build();
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .vexpand(true)
+            .child(&list_view)
+            .build();
+        view.append(&scrolled);
+
+        let page = self.clone();
+        search_entry.connect_search_changed(move |entry| {
+            page.imp().unit_filter.borrow_mut().query = entry.text().to_lowercase();
+            page.refilter_units();
+        });
+        let page = self.clone();
+        state_dropdown.connect_selected_notify(move |dropdown| {
+            page.imp().unit_filter.borrow_mut().state = match dropdown.selected() {
+                1 => StateFilter::Running,
+                2 => StateFilter::Stopped,
+                3 => StateFilter::Failed,
+                _ => StateFilter::Any,
+            };
+            page.refilter_units();
+        });
+        let page = self.clone();
+        enablement_dropdown.connect_selected_notify(move |dropdown| {
+            page.imp().unit_filter.borrow_mut().enablement = match dropdown.selected() {
+                1 => EnablementFilter::Enabled,
+                2 => EnablementFilter::Disabled,
+                3 => EnablementFilter::Masked,
+                _ => EnablementFilter::Any,
+            };
+            page.refilter_units();
+        });
+        let page = self.clone();
+        scope_dropdown.connect_selected_notify(move |dropdown| {
+            let scope = match dropdown.selected() {
+                1 => UnitScope::User,
+                _ => UnitScope::System,
+            };
+            page.imp().unit_scope.set(scope);
+            // Different units altogether, so nothing is reused
+            page.unit_store().remove_all();
+            page.load_all_services();
+        });
+
+        view
+    }
+
+    /// One row of the All Services list, bound to whichever unit it shows.
+    fn setup_unit_row(item: &gtk4::ListItem) {
+        let row = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(12)
+            .margin_top(8)
+            .margin_bottom(8)
+            .margin_start(12)
+            .margin_end(12)
+            .build();
+        let icon = gtk4::Image::new();
+        let text = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(2)
+            .hexpand(true)
+            .build();
+        let name = gtk4::Label::builder()
+            .halign(gtk4::Align::Start)
+            .ellipsize(gtk4::pango::EllipsizeMode::End)
+            .build();
+        let description = gtk4::Label::builder()
+            .halign(gtk4::Align::Start)
+            .ellipsize(gtk4::pango::EllipsizeMode::End)
+            .css_classes(vec!["dim-label".to_string(), "caption".to_string()])
+            .build();
+        text.append(&name);
+        text.append(&description);
+        let state = gtk4::Label::builder().valign(gtk4::Align::Center).build();
+        let startup = gtk4::Label::builder()
+            .valign(gtk4::Align::Center)
+            .width_chars(9)
+            .xalign(1.0)
+            .css_classes(vec!["dim-label".to_string(), "caption".to_string()])
+            .build();
+        row.append(&icon);
+        row.append(&text);
+        row.append(&state);
+        row.append(&startup);
+        item.set_child(Some(&row));
+
+        // Follow the unit's properties, so updated units need no new rows
+        let unit = item.property_expression("item");
+        unit.chain_property::<UnitObject>("name")
+            .bind(&name, "label", gtk4::Widget::NONE);
+        unit.chain_property::<UnitObject>("description").bind(
+            &description,
+            "label",
+            gtk4::Widget::NONE,
+        );
+        let sub_state = unit.chain_property::<UnitObject>("sub-state");
+        sub_state
+            .chain_closure::<String>(glib::closure!(
+                |_: Option<glib::Object>, sub_state: String| unit_state_icon(&sub_state)
+            ))
+            .bind(&icon, "icon-name", gtk4::Widget::NONE);
+        sub_state
+            .chain_closure::<String>(glib::closure!(
+                |_: Option<glib::Object>, sub_state: String| unit_state_label(&sub_state)
+            ))
+            .bind(&state, "label", gtk4::Widget::NONE);
+        sub_state
+            .chain_closure::<Vec<String>>(glib::closure!(
+                |_: Option<glib::Object>, sub_state: String| {
+                    vec![
+                        "caption".to_string(),
+                        ServiceState::from_str(&sub_state).css_class().to_string(),
+                    ]
+                }
+            ))
+            .bind(&state, "css-classes", gtk4::Widget::NONE);
+        unit.chain_property::<UnitObject>("file-state")
+            .chain_closure::<String>(glib::closure!(
+                |_: Option<glib::Object>, file_state: String| unit_file_state_label(&file_state)
+            ))
+            .bind(&startup, "label", gtk4::Widget::NONE);
+    }
+
+    /// Every service listed in the All Services view, as
+    /// [`UnitObject`]s that stay the same across refreshes.
+    fn unit_store(&self) -> gio::ListStore {
+        self.imp()
+            .unit_store
+            .get_or_init(gio::ListStore::new::<UnitObject>)
+            .clone()
+    }
+
+    fn showing_all_services(&self) -> bool {
+        self.imp()
+            .view_stack
+            .borrow()
+            .as_ref()
+            .and_then(|stack| stack.visible_child_name())
+            .is_some_and(|name| name == "all")
+    }
+
+    /// List every service of the selected scope. Units still listed keep
+    /// their objects and only the rows of changed ones update.
+    fn load_all_services(&self) {
+        let scope = self.imp().unit_scope.get();
+        if let Some(label) = self.imp().unit_count_label.borrow().as_ref() {
+            label.set_label(&gettext("Loading services…"));
+        }
+        let page = self.clone();
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(move || {
+                let mut client = SystemdClient::new();
+                client.connect_scope(scope)?;
+                client.list_all_services()
+            })
+            .await;
+            // The scope changed meanwhile; its own listing follows
+            if page.imp().unit_scope.get() != scope {
+                return;
+            }
+            match result {
+                Ok(Ok(units)) => {
+                    sync_store::<UnitObject>(&page.unit_store(), &units);
+                    page.update_unit_count();
+                }
+                Ok(Err(e)) => {
+                    page.unit_store().remove_all();
+                    if let Some(label) = page.imp().unit_count_label.borrow().as_ref() {
+                        label.set_label(&format!("{}: {:#}", gettext("Cannot list services"), e));
+                    }
+                }
+                Err(_) => {}
+            }
+        });
+    }
+
+    fn refilter_units(&self) {
+        if let Some(filter) = self.imp().unit_custom_filter.borrow().as_ref() {
+            filter.changed(gtk4::FilterChange::Different);
+        }
+    }
+
+    fn update_unit_count(&self) {
+        let imp = self.imp();
+        let shown = imp
+            .unit_filtered
+            .borrow()
+            .as_ref()
+            .map(|model| model.n_items())
+            .unwrap_or_default();
+        let total = self.unit_store().n_items();
+        if let Some(label) = imp.unit_count_label.borrow().as_ref() {
+            label.set_label(
+                &gettext("%d of %d services")
+                    .replacen("%d", &shown.to_string(), 1)
+                    .replacen("%d", &total.to_string(), 1),
+            );
+        }
+    }
+
     /// Show a toast message.
     fn show_toast(&self, message: &str) {
         // Find the toast overlay by walking up the widget tree
@@ -299,6 +651,10 @@ impl SystemServicesPage {
             subtitle_parts.push(format!("Memory: {}", mem));
         }
 
+        if service.is_masked {
+            subtitle_parts.push(gettext("Masked"));
+        }
+
         let subtitle = subtitle_parts.join(" • ");
 
         let row = adw::ActionRow::builder()
@@ -317,6 +673,34 @@ impl SystemServicesPage {
             .build();
         row.add_prefix(&state_icon);
 
+        // Sandboxing exposure, like `systemd-analyze security`
+        if let Some(report) = &service.sandbox {
+            let level = report.level();
+            let sandbox_button = gtk4::Button::builder()
+                .label(format!(
+                    "{:.1} {}",
+                    report.exposure(),
+                    gettext(level.label())
+                ))
+                .css_classes(vec![
+                    "flat".to_string(),
+                    "caption".to_string(),
+                    level.css_class().to_string(),
+                ])
+                .tooltip_text(gettext(
+                    "Sandboxing exposure from 0 (fully sandboxed) to 10 (unrestricted)",
+                ))
+                .valign(gtk4::Align::Center)
+                .build();
+            let page = self.clone();
+            let name = service.display_name.clone();
+            let report = report.clone();
+            sandbox_button.connect_clicked(move |_| {
+                page.present_sandbox_dialog(&name, &report);
+            });
+            row.add_suffix(&sandbox_button);
+        }
+
         // Action buttons box
         let actions_box = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Horizontal)
@@ -327,6 +711,36 @@ impl SystemServicesPage {
         let service_name = service.name.clone();
         let is_running = service.state == ServiceState::Running;
 
+        // Recent journal lines, e.g. why the service failed
+        let logs_button = gtk4::Button::builder()
+            .icon_name("text-x-generic-symbolic")
+            .css_classes(vec!["flat".to_string()])
+            .tooltip_text(gettext("Recent logs"))
+            .valign(gtk4::Align::Center)
+            .build();
+        let page = self.clone();
+        let name = service_name.clone();
+        logs_button.connect_clicked(move |_| {
+            page.present_log_dialog(&name);
+        });
+        actions_box.append(&logs_button);
+
+        // Unit file and the hardening editor
+        if service.is_installed {
+            let unit_button = gtk4::Button::builder()
+                .icon_name("document-edit-symbolic")
+                .css_classes(vec!["flat".to_string()])
+                .tooltip_text(gettext("Unit file and hardening"))
+                .valign(gtk4::Align::Center)
+                .build();
+            let page = self.clone();
+            let name = service_name.clone();
+            unit_button.connect_clicked(move |_| {
+                page.present_unit_file_dialog(&name);
+            });
+            actions_box.append(&unit_button);
+        }
+
         // Start/Stop button
         let toggle_button = gtk4::Button::builder()
             .icon_name(if is_running {
@@ -355,6 +769,10 @@ impl SystemServicesPage {
             }
         });
 
+        // A masked service cannot be started or enabled until unmasked
+        if service.is_masked && !is_running {
+            toggle_button.set_sensitive(false);
+        }
         actions_box.append(&toggle_button);
 
         // Restart button (only for running services)
@@ -442,13 +860,566 @@ impl SystemServicesPage {
             glib::Propagation::Stop
         });
 
+        enable_switch.set_sensitive(!service.is_masked);
         actions_box.append(&enable_switch);
 
+        actions_box.append(&self.service_menu(service));
+
         row.add_suffix(&actions_box);
 
         row
     }
 
+    /// Menu with the less common actions of a service: reloading its
+    /// configuration and masking it.
+    fn service_menu(&self, service: &ServiceInfo) -> gtk4::MenuButton {
+        let items = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(2)
+            .build();
+        let popover = gtk4::Popover::builder().child(&items).build();
+
+        let mut entries: Vec<(String, ServiceCommand)> = Vec::new();
+        if service.state == ServiceState::Running {
+            entries.push((gettext("Reload"), ServiceCommand::Reload));
+            entries.push((
+                gettext("Reload or Restart"),
+                ServiceCommand::ReloadOrRestart,
+            ));
+        }
+        if service.is_masked {
+            entries.push((gettext("Unmask"), ServiceCommand::Unmask));
+        } else {
+            entries.push((gettext("Mask…"), ServiceCommand::Mask));
+        }
+
+        for (label, command) in entries {
+            let button = gtk4::Button::builder()
+                .label(label)
+                .css_classes(vec!["flat".to_string()])
+                .build();
+            let page = self.downgrade();
+            let popover_ref = popover.downgrade();
+            let name = service.name.clone();
+            button.connect_clicked(move |_| {
+                if let Some(popover) = popover_ref.upgrade() {
+                    popover.popdown();
+                }
+                let Some(page) = page.upgrade() else { return };
+                if command == ServiceCommand::Mask {
+                    page.confirm_mask(&name);
+                } else {
+                    page.run_service_command(&name, command);
+                }
+            });
+            items.append(&button);
+        }
+
+        gtk4::MenuButton::builder()
+            .icon_name("view-more-symbolic")
+            .tooltip_text(gettext("More actions"))
+            .css_classes(vec!["flat".to_string()])
+            .valign(gtk4::Align::Center)
+            .popover(&popover)
+            .build()
+    }
+
+    /// Ask before masking a service.
+    fn confirm_mask(&self, name: &str) {
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("Mask %s?").replace("%s", name.trim_end_matches(".service")))
+            .body(gettext(
+                "The service is stopped and can no longer be started, neither by hand nor \
+                 by another service that needs it, until it is unmasked. Use this for \
+                 services that should never run.",
+            ))
+            .build();
+        dialog.add_response("cancel", "_Cancel");
+        dialog.add_response("mask", "_Mask");
+        dialog.set_response_appearance("mask", adw::ResponseAppearance::Destructive);
+        dialog.set_default_response(Some("cancel"));
+
+        let page = self.clone();
+        let name = name.to_string();
+        dialog.connect_response(None, move |_, response| {
+            if response == "mask" {
+                page.run_service_command(&name, ServiceCommand::Mask);
+            }
+        });
+        dialog.present(Some(self));
+    }
+
+    /// Run a menu action on a service in the background and report it.
+    fn run_service_command(&self, name: &str, command: ServiceCommand) {
+        let page = self.clone();
+        let service_name = name.to_string();
+
+        glib::spawn_future_local(async move {
+            let name_clone = service_name.clone();
+            let result = gtk4::gio::spawn_blocking(move || {
+                let mut client = SystemdClient::new();
+                client.connect()?;
+                match command {
+                    ServiceCommand::Reload => client.reload_service(&name_clone),
+                    ServiceCommand::ReloadOrRestart => {
+                        client.reload_or_restart_service(&name_clone)
+                    }
+                    ServiceCommand::Mask => {
+                        client.stop_service(&name_clone)?;
+                        client.mask_service(&name_clone)
+                    }
+                    ServiceCommand::Unmask => client.unmask_service(&name_clone),
+                }
+            })
+            .await;
+
+            let display_name = service_name.trim_end_matches(".service");
+            match result {
+                Ok(Ok(())) => {
+                    let message = match command {
+                        ServiceCommand::Reload => gettext("Reloaded %s"),
+                        ServiceCommand::ReloadOrRestart => gettext("Reloaded or restarted %s"),
+                        ServiceCommand::Mask => gettext("Stopped and masked %s"),
+                        ServiceCommand::Unmask => gettext("Unmasked %s"),
+                    };
+                    page.show_toast(&message.replace("%s", display_name));
+                }
+                Ok(Err(e)) => page.show_toast(&format!("{}: {}", gettext("Error"), e)),
+                Err(e) => page.show_toast(&format!("{}: {:?}", gettext("Error"), e)),
+            }
+            page.refresh_services();
+        });
+    }
+
+    /// Present a dialog listing the sandboxing settings of a service.
+    fn present_sandbox_dialog(&self, name: &str, report: &SandboxReport) {
+        let dialog = adw::Dialog::builder()
+            .title(gettext("Sandboxing of %s").replace("%s", name))
+            .content_width(640)
+            .content_height(560)
+            .build();
+
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&adw::HeaderBar::new());
+
+        let prefs = adw::PreferencesPage::new();
+        let level = report.level();
+        let summary = adw::PreferencesGroup::builder()
+            .title(format!(
+                "{}: {:.1} ({})",
+                gettext("Exposure"),
+                report.exposure(),
+                gettext(level.label())
+            ))
+            .description(gettext(
+                "Settings in the service's unit file that limit what it can reach if it is \
+                 compromised. Add missing ones from the unit file dialog or in a drop-in, \
+                 after checking the service still works with them.",
+            ))
+            .build();
+        prefs.add(&summary);
+
+        for (title, checks) in [
+            (gettext("Not Applied"), report.failed()),
+            (
+                gettext("Applied"),
+                report.checks.iter().filter(|c| c.passed()).collect(),
+            ),
+        ] {
+            if checks.is_empty() {
+                continue;
+            }
+            let group = adw::PreferencesGroup::builder().title(title).build();
+            for check in checks {
+                let row = adw::ActionRow::builder()
+                    .title(check.setting)
+                    .subtitle(gettext(check.description))
+                    .build();
+                let (icon_name, class) = if check.passed() {
+                    ("emblem-ok-symbolic", "success")
+                } else if check.badness < 1.0 {
+                    ("dialog-warning-symbolic", "warning")
+                } else {
+                    ("window-close-symbolic", "error")
+                };
+                let icon = gtk4::Image::from_icon_name(icon_name);
+                icon.add_css_class(class);
+                row.add_prefix(&icon);
+                let value_label = gtk4::Label::builder()
+                    .label(&check.value)
+                    .css_classes(vec!["monospace".to_string(), "caption".to_string()])
+                    .selectable(true)
+                    .wrap(true)
+                    .wrap_mode(gtk4::pango::WrapMode::WordChar)
+                    .max_width_chars(24)
+                    .xalign(1.0)
+                    .build();
+                row.add_suffix(&value_label);
+                group.add(&row);
+            }
+            prefs.add(&group);
+        }
+
+        toolbar.set_content(Some(&prefs));
+        dialog.set_child(Some(&toolbar));
+        dialog.present(Some(self));
+    }
+
+    /// Present a dialog with the recent journal lines of a service, which
+    /// can follow new lines as they are logged.
+    fn present_log_dialog(&self, unit: &str) {
+        let dialog = adw::Dialog::builder()
+            .title(gettext("Logs of %s").replace("%s", unit.trim_end_matches(".service")))
+            .content_width(800)
+            .content_height(560)
+            .build();
+
+        let header = adw::HeaderBar::new();
+        let count_labels: Vec<String> = LOG_LINE_COUNTS
+            .iter()
+            .map(|n| gettext("Last %d lines").replace("%d", &n.to_string()))
+            .collect();
+        let count_refs: Vec<&str> = count_labels.iter().map(|s| s.as_str()).collect();
+        let count_dropdown = gtk4::DropDown::from_strings(&count_refs);
+        count_dropdown.set_tooltip_text(Some(&gettext("Number of lines to read back")));
+        header.pack_start(&count_dropdown);
+
+        let follow_button = gtk4::ToggleButton::builder()
+            .icon_name("media-playback-start-symbolic")
+            .tooltip_text(gettext("Follow new lines"))
+            .build();
+        header.pack_end(&follow_button);
+
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&header);
+
+        let buffer = gtk4::TextBuffer::new(None);
+        let style = palette::current();
+        for (name, (r, g, b)) in [("error", style.error()), ("warning", style.warning())] {
+            let tag = gtk4::TextTag::builder()
+                .name(name)
+                .foreground_rgba(&gtk4::gdk::RGBA::new(r as f32, g as f32, b as f32, 1.0))
+                .build();
+            buffer.tag_table().add(&tag);
+        }
+        let text_view = gtk4::TextView::builder()
+            .buffer(&buffer)
+            .editable(false)
+            .cursor_visible(false)
+            .monospace(true)
+            .wrap_mode(gtk4::WrapMode::WordChar)
+            .left_margin(12)
+            .right_margin(12)
+            .top_margin(12)
+            .bottom_margin(12)
+            .build();
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .vexpand(true)
+            .child(&text_view)
+            .build();
+        toolbar.set_content(Some(&scrolled));
+
+        // Read back the chosen number of lines, replacing what is shown
+        let load = {
+            let unit = unit.to_string();
+            let buffer = buffer.clone();
+            let text_view = text_view.clone();
+            move |lines: usize| {
+                let unit = unit.clone();
+                let buffer = buffer.clone();
+                let text_view = text_view.clone();
+                glib::spawn_future_local(async move {
+                    let result =
+                        gtk4::gio::spawn_blocking(move || read_unit_log(&unit, lines)).await;
+                    buffer.set_text("");
+                    match result {
+                        Ok(Ok(entries)) if entries.is_empty() => buffer.set_text(&gettext(
+                            "No log lines. Reading the logs of system services needs \
+                             membership in the systemd-journal or adm group.",
+                        )),
+                        Ok(Ok(entries)) => Self::append_log_lines(&text_view, &entries),
+                        Ok(Err(e)) => {
+                            buffer.set_text(&format!("{}: {}", gettext("Cannot read the logs"), e))
+                        }
+                        Err(_) => {}
+                    }
+                });
+            }
+        };
+        load(LOG_LINE_COUNTS[0]);
+        count_dropdown.connect_selected_notify(move |dropdown| {
+            if let Some(lines) = LOG_LINE_COUNTS.get(dropdown.selected() as usize) {
+                load(*lines);
+            }
+        });
+
+        // Following runs journalctl -f until switched off or the dialog closes
+        let follower: Rc<RefCell<Option<UnitLog>>> = Rc::new(RefCell::new(None));
+        let page = self.clone();
+        let unit_name = unit.to_string();
+        let state = follower.clone();
+        let view = text_view.clone();
+        follow_button.connect_toggled(move |button| {
+            if !button.is_active() {
+                state.replace(None);
+                return;
+            }
+            match UnitLog::follow(&unit_name) {
+                Ok(log) => {
+                    state.replace(Some(log));
+                    let state = state.clone();
+                    let view = view.clone();
+                    glib::timeout_add_local(std::time::Duration::from_millis(500), move || {
+                        let Some(entries) = state.borrow().as_ref().map(|log| log.take_new())
+                        else {
+                            return glib::ControlFlow::Break;
+                        };
+                        if !entries.is_empty() {
+                            Self::append_log_lines(&view, &entries);
+                        }
+                        glib::ControlFlow::Continue
+                    });
+                }
+                Err(e) => {
+                    page.show_toast(&format!("{}: {}", gettext("Cannot follow the logs"), e));
+                    button.set_active(false);
+                }
+            }
+        });
+        dialog.connect_closed(move |_| {
+            follower.replace(None);
+        });
+
+        dialog.set_child(Some(&toolbar));
+        dialog.present(Some(self));
+    }
+
+    /// Present a dialog with the unit file and drop-ins of a service, and
+    /// an editor that adds hardening settings in a drop-in of its own.
+    fn present_unit_file_dialog(&self, unit: &str) {
+        let dialog = adw::Dialog::builder()
+            .title(gettext("Unit File of %s").replace("%s", unit.trim_end_matches(".service")))
+            .content_width(760)
+            .content_height(620)
+            .build();
+
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&adw::HeaderBar::new());
+        let prefs = adw::PreferencesPage::new();
+        toolbar.set_content(Some(&prefs));
+        dialog.set_child(Some(&toolbar));
+
+        let page = self.clone();
+        let unit = unit.to_string();
+        let dialog_ref = dialog.clone();
+        glib::spawn_future_local(async move {
+            let name = unit.clone();
+            let result = gtk4::gio::spawn_blocking(move || {
+                let mut client = SystemdClient::new();
+                client.connect()?;
+                let paths = client.get_unit_file_paths(&name)?;
+                Ok::<_, anyhow::Error>(read_unit_files(&paths))
+            })
+            .await;
+            match result {
+                Ok(Ok(files)) => page.fill_unit_file_dialog(&dialog_ref, &prefs, &unit, &files),
+                Ok(Err(e)) => {
+                    let group = adw::PreferencesGroup::builder()
+                        .title(gettext("Cannot read the unit file"))
+                        .description(e.to_string())
+                        .build();
+                    prefs.add(&group);
+                }
+                Err(_) => {}
+            }
+        });
+
+        dialog.present(Some(self));
+    }
+
+    /// Add the hardening editor and one group per unit file to the dialog.
+    fn fill_unit_file_dialog(
+        &self,
+        dialog: &adw::Dialog,
+        prefs: &adw::PreferencesPage,
+        unit: &str,
+        files: &[UnitFile],
+    ) {
+        let current: Vec<(HardeningDirective, String)> = files
+            .iter()
+            .filter(|file| file.is_hardening_dropin())
+            .filter_map(|file| file.contents.as_ref().ok())
+            .flat_map(|contents| parse_hardening_dropin(contents))
+            .collect();
+        let value_of = |directive: HardeningDirective| {
+            current
+                .iter()
+                .find(|(d, _)| *d == directive)
+                .map(|(_, value)| value.clone())
+        };
+
+        let hardening = adw::PreferencesGroup::builder()
+            .title(gettext("Hardening"))
+            .description(gettext(
+                "Applied in a drop-in override, after which systemd is reloaded and the \
+                 service restarted. Check the service still works; switch a setting off \
+                 to remove it again.",
+            ))
+            .build();
+        let apply_button = gtk4::Button::builder()
+            .label(gettext("Apply and Restart"))
+            .css_classes(vec!["suggested-action".to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+        hardening.set_header_suffix(Some(&apply_button));
+
+        // Settings with a single value are switches, the others pick one
+        let mut switches: Vec<(HardeningDirective, adw::SwitchRow)> = Vec::new();
+        let mut combos: Vec<(HardeningDirective, adw::ComboRow)> = Vec::new();
+        for directive in HardeningDirective::ALL {
+            let current = value_of(directive);
+            if directive.values().len() == 1 {
+                let row = adw::SwitchRow::builder()
+                    .title(directive.key())
+                    .subtitle(gettext(directive.description()))
+                    .active(current.is_some())
+                    .build();
+                hardening.add(&row);
+                switches.push((directive, row));
+            } else {
+                let mut labels = vec![gettext("Not set")];
+                labels.extend(directive.values().iter().map(|v| v.to_string()));
+                let label_refs: Vec<&str> = labels.iter().map(|s| s.as_str()).collect();
+                let selected = current
+                    .and_then(|value| directive.values().iter().position(|v| *v == value))
+                    .map_or(0, |index| index + 1);
+                let row = adw::ComboRow::builder()
+                    .title(directive.key())
+                    .subtitle(gettext(directive.description()))
+                    .model(&gtk4::StringList::new(&label_refs))
+                    .selected(selected as u32)
+                    .build();
+                hardening.add(&row);
+                combos.push((directive, row));
+            }
+        }
+        prefs.add(&hardening);
+
+        let page = self.clone();
+        let unit_name = unit.to_string();
+        let dialog = dialog.clone();
+        apply_button.connect_clicked(move |button| {
+            let mut settings = Vec::new();
+            for (directive, row) in &switches {
+                if row.is_active() {
+                    settings.push((*directive, directive.values()[0].to_string()));
+                }
+            }
+            for (directive, row) in &combos {
+                let selected = row.selected() as usize;
+                if let Some(value) = selected
+                    .checked_sub(1)
+                    .and_then(|i| directive.values().get(i))
+                {
+                    settings.push((*directive, value.to_string()));
+                }
+            }
+            button.set_sensitive(false);
+            page.apply_hardening(&unit_name, settings, &dialog);
+        });
+
+        for file in files {
+            let title = if file.is_hardening_dropin() {
+                gettext("Drop-in added by Security Center")
+            } else if Some(file) == files.first() {
+                gettext("Unit File")
+            } else {
+                gettext("Drop-in")
+            };
+            let group = adw::PreferencesGroup::builder()
+                .title(title)
+                .description(&file.path)
+                .build();
+            let text = match &file.contents {
+                Ok(contents) => contents.clone(),
+                Err(e) => format!("{}: {}", gettext("Cannot read the file"), e),
+            };
+            let view = gtk4::TextView::builder()
+                .editable(false)
+                .cursor_visible(false)
+                .monospace(true)
+                .wrap_mode(gtk4::WrapMode::WordChar)
+                .left_margin(12)
+                .right_margin(12)
+                .top_margin(12)
+                .bottom_margin(12)
+                .css_classes(vec!["card".to_string()])
+                .build();
+            view.buffer().set_text(text.trim_end());
+            group.add(&view);
+            prefs.add(&group);
+        }
+    }
+
+    /// Write the hardening drop-in of `unit` through the privileged helper,
+    /// which reloads systemd and restarts the service.
+    fn apply_hardening(
+        &self,
+        unit: &str,
+        settings: Vec<(HardeningDirective, String)>,
+        dialog: &adw::Dialog,
+    ) {
+        let op = HelperOp::HardenService {
+            unit: unit.to_string(),
+            settings,
+        };
+        let page = self.clone();
+        let dialog = dialog.clone();
+        glib::spawn_future_local(async move {
+            let result = gtk4::gio::spawn_blocking(move || run_privileged(&op)).await;
+            dialog.close();
+            match result {
+                Ok(Ok(message)) => page.show_toast(&message),
+                Ok(Err(e)) => {
+                    page.show_toast(&format!("{}: {}", gettext("Failed to apply hardening"), e))
+                }
+                Err(_) => page.show_toast(&gettext("Failed to apply hardening")),
+            }
+            page.refresh_services();
+        });
+    }
+
+    /// Append journal lines to a log view and scroll to the newest.
+    fn append_log_lines(view: &gtk4::TextView, entries: &[JournalLine]) {
+        let buffer = view.buffer();
+        for entry in entries {
+            let time = chrono::DateTime::from_timestamp(entry.time, 0)
+                .map(|t| {
+                    t.with_timezone(&chrono::Local)
+                        .format("%b %d %H:%M:%S")
+                        .to_string()
+                })
+                .unwrap_or_default();
+            let source = match entry.pid {
+                Some(pid) => format!("{}[{}]", entry.identifier, pid),
+                None => entry.identifier.clone(),
+            };
+            let line = format!("{} {}: {}\n", time, source, entry.message);
+            let mut end = buffer.end_iter();
+            if entry.is_error() {
+                buffer.insert_with_tags_by_name(&mut end, &line, &["error"]);
+            } else if entry.is_warning() {
+                buffer.insert_with_tags_by_name(&mut end, &line, &["warning"]);
+            } else {
+                buffer.insert(&mut end, &line);
+            }
+        }
+        let end = buffer.create_mark(None, &buffer.end_iter(), false);
+        view.scroll_mark_onscreen(&end);
+        buffer.delete_mark(&end);
+    }
+
     /// Start a service.
     fn start_service(&self, name: &str) {
         let page = self.clone();
@@ -600,6 +1571,37 @@ impl SystemServicesPage {
     }
 }
 
+fn unit_state_icon(sub_state: &str) -> String {
+    match ServiceState::from_str(sub_state) {
+        ServiceState::Running => "media-playback-start-symbolic",
+        ServiceState::Stopped => "media-playback-stop-symbolic",
+        ServiceState::Failed => "dialog-error-symbolic",
+        ServiceState::Unknown => "dialog-question-symbolic",
+    }
+    .to_string()
+}
+
+fn unit_state_label(sub_state: &str) -> String {
+    match ServiceState::from_str(sub_state) {
+        ServiceState::Running => gettext("Running"),
+        ServiceState::Stopped => gettext("Stopped"),
+        ServiceState::Failed => gettext("Failed"),
+        ServiceState::Unknown => sub_state.to_string(),
+    }
+}
+
+/// Whether the unit starts at boot, from its `UnitFileState`.
+fn unit_file_state_label(file_state: &str) -> String {
+    match file_state {
+        "enabled" | "enabled-runtime" => gettext("Enabled"),
+        "disabled" => gettext("Disabled"),
+        "static" => gettext("Static"),
+        "masked" | "masked-runtime" => gettext("Masked"),
+        "" => gettext("Transient"),
+        other => other.to_string(),
+    }
+}
+
 impl Default for SystemServicesPage {
     fn default() -> Self {
         Self::new()
@@ -618,6 +1620,13 @@ mod imp {
         pub search_entry: RefCell<Option<gtk4::SearchEntry>>,
         pub services: RefCell<Vec<ServiceInfo>>,
         pub current_rows: RefCell<Vec<adw::ActionRow>>,
+        pub view_stack: RefCell<Option<gtk4::Stack>>,
+        pub unit_store: OnceCell<gio::ListStore>,
+        pub unit_filtered: RefCell<Option<gtk4::FilterListModel>>,
+        pub unit_custom_filter: RefCell<Option<gtk4::CustomFilter>>,
+        pub unit_filter: RefCell<UnitFilter>,
+        pub unit_scope: Cell<UnitScope>,
+        pub unit_count_label: RefCell<Option<gtk4::Label>>,
     }
 
     #[glib::object_subclass]
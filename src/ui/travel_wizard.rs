@@ -0,0 +1,308 @@
+// Security Center - Travel hardening wizard
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Guided "Secure for Travel" dialog. It reads the current configuration,
+//! previews the changes of every step (public default zone, no file
+//! sharing, randomized MAC addresses, a tighter SSH server, screen lock)
+//! and runs the steps left switched on as admin operations, showing the
+//! outcome of each one in place. Steps that are already satisfied are shown
+//! but cannot be switched on.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use crate::admin::{
+    query_travel_plan, Operation, OperationResult, OperationRunner, TravelPlan, TravelStep, Undo,
+    MAC_DROPIN,
+};
+use crate::i18n::gettext;
+
+fn step_title(step: TravelStep) -> String {
+    match step {
+        TravelStep::PublicZone => gettext("Use the Public Zone"),
+        TravelStep::FileSharing => gettext("Stop File Sharing"),
+        TravelStep::MacRandomization => gettext("Randomize MAC Addresses"),
+        TravelStep::Ssh => gettext("Tighten SSH"),
+        TravelStep::ScreenLock => gettext("Lock the Screen"),
+    }
+}
+
+fn step_icon(step: TravelStep) -> &'static str {
+    match step {
+        TravelStep::PublicZone => "network-workgroup-symbolic",
+        TravelStep::FileSharing => "folder-remote-symbolic",
+        TravelStep::MacRandomization => "network-wireless-symbolic",
+        TravelStep::Ssh => "utilities-terminal-symbolic",
+        TravelStep::ScreenLock => "system-lock-screen-symbolic",
+    }
+}
+
+/// Subtitle of a step that has nothing left to do.
+fn step_done(step: TravelStep, plan: &TravelPlan) -> String {
+    match step {
+        TravelStep::PublicZone => gettext("The default zone is already public"),
+        TravelStep::FileSharing => gettext("No file-sharing service is running or allowed"),
+        TravelStep::MacRandomization => gettext("Wi-Fi already uses randomized addresses"),
+        TravelStep::Ssh if plan.ssh.installed && !plan.ssh.reads_drop_ins => gettext(
+            "sshd_config does not read sshd_config.d, so its settings cannot be changed safely",
+        ),
+        TravelStep::Ssh => gettext("SSH is already restricted"),
+        TravelStep::ScreenLock if plan.screen_lock.is_none() => {
+            gettext("GNOME screen lock settings are not available")
+        }
+        TravelStep::ScreenLock => gettext("The screen already locks promptly"),
+    }
+}
+
+/// Preview line for one operation of the wizard.
+fn describe(operation: &Operation, plan: &TravelPlan) -> String {
+    match operation {
+        Operation::SetDefaultZone { zone } => gettext("Change the default zone from %s to %s")
+            .replacen("%s", &plan.default_zone, 1)
+            .replacen("%s", zone, 1),
+        Operation::StopAndDisableUnit { unit } => {
+            gettext("Stop %s and keep it from starting at boot").replace("%s", unit)
+        }
+        Operation::RemoveZoneService {
+            zone,
+            service: "ssh",
+        } => gettext("Block incoming SSH connections in the %s zone").replace("%s", zone),
+        Operation::RemoveZoneService { zone, service } => gettext("Remove %s from the %s zone")
+            .replacen("%s", service, 1)
+            .replacen("%s", zone, 1),
+        Operation::EnableMacRandomization => gettext(
+            "Give each network its own stable random address by installing %s",
+        )
+        .replace("%s", MAC_DROPIN),
+        Operation::HardenSsh => gettext(
+            "Refuse root logins and empty passwords, allow 3 login attempts and disable X11 forwarding",
+        ),
+        Operation::EnableScreenLock => {
+            let minutes = plan
+                .screen_lock
+                .map(|current| current.target().idle_delay / 60)
+                .unwrap_or_default();
+            gettext("Lock as soon as the screen blanks, after %s minutes of inactivity")
+                .replace("%s", &minutes.to_string())
+        }
+        other => other.id().to_string(),
+    }
+}
+
+/// Build and present the wizard anchored to `parent`. `on_applied` runs
+/// after at least one step changed the system.
+pub fn present_travel_wizard(parent: &gtk4::Window, on_applied: impl Fn() + 'static) {
+    let dialog = adw::Dialog::builder()
+        .title(gettext("Secure for Travel"))
+        .content_width(560)
+        .content_height(640)
+        .build();
+
+    let apply_button = gtk4::Button::builder()
+        .label(gettext("Apply"))
+        .css_classes(vec!["suggested-action".to_string()])
+        .sensitive(false)
+        .build();
+    let header = adw::HeaderBar::new();
+    header.pack_end(&apply_button);
+
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&header);
+
+    let spinner = gtk4::Spinner::builder()
+        .spinning(true)
+        .width_request(32)
+        .height_request(32)
+        .build();
+    let loading = adw::StatusPage::builder()
+        .title(gettext("Checking the current configuration…"))
+        .child(&spinner)
+        .build();
+    toolbar.set_content(Some(&loading));
+    dialog.set_child(Some(&toolbar));
+    dialog.present(Some(parent));
+
+    let on_applied = Rc::new(on_applied);
+    let window = parent.clone();
+    glib::spawn_future_local(async move {
+        let plan = match gtk4::gio::spawn_blocking(query_travel_plan).await {
+            Ok(Ok(plan)) => plan,
+            Ok(Err(e)) => {
+                let status = adw::StatusPage::builder()
+                    .icon_name("dialog-error-symbolic")
+                    .title(gettext("Cannot Read the Current Configuration"))
+                    .description(glib::markup_escape_text(&format!("{:#}", e)).as_str())
+                    .build();
+                toolbar.set_content(Some(&status));
+                return;
+            }
+            Err(_) => return,
+        };
+
+        let page = adw::PreferencesPage::new();
+        let intro = adw::PreferencesGroup::builder()
+            .description(gettext(
+                "Review each step before applying it. Switch off the steps you want to skip.",
+            ))
+            .build();
+        page.add(&intro);
+
+        let steps_group = adw::PreferencesGroup::new();
+        let rows: Rc<RefCell<Vec<(TravelStep, adw::ExpanderRow)>>> =
+            Rc::new(RefCell::new(Vec::new()));
+        for step in TravelStep::ALL {
+            let operations = plan.operations(step);
+            let row = adw::ExpanderRow::builder()
+                .title(step_title(step))
+                .show_enable_switch(true)
+                .enable_expansion(!operations.is_empty())
+                .sensitive(!operations.is_empty())
+                .build();
+            row.add_prefix(&gtk4::Image::from_icon_name(step_icon(step)));
+            if operations.is_empty() {
+                row.set_subtitle(&glib::markup_escape_text(&step_done(step, &plan)));
+            } else {
+                for operation in &operations {
+                    let change = adw::ActionRow::builder()
+                        .title(glib::markup_escape_text(&describe(operation, &plan)).as_str())
+                        .build();
+                    row.add_row(&change);
+                }
+            }
+            if step == TravelStep::Ssh
+                && !operations.is_empty()
+                && plan.ssh.installed
+                && !plan.ssh.reads_drop_ins
+            {
+                row.add_row(
+                    &adw::ActionRow::builder()
+                        .title(gettext(
+                            "sshd_config does not read sshd_config.d; the server settings are left unchanged",
+                        ))
+                        .css_classes(vec!["dim-label".to_string()])
+                        .build(),
+                );
+            }
+            steps_group.add(&row);
+            rows.borrow_mut().push((step, row));
+        }
+        page.add(&steps_group);
+
+        let any_pending = TravelStep::ALL
+            .iter()
+            .any(|&step| !plan.operations(step).is_empty());
+        if !any_pending {
+            intro.set_description(Some(&gettext(
+                "This system is already prepared for travel.",
+            )));
+        }
+        apply_button.set_sensitive(any_pending);
+        toolbar.set_content(Some(&page));
+
+        let plan = Rc::new(plan);
+        apply_button.connect_clicked(move |button| {
+            let selected: Vec<(TravelStep, Vec<Operation>)> = rows
+                .borrow()
+                .iter()
+                .filter(|(_, row)| row.enables_expansion())
+                .map(|(step, _)| (*step, plan.operations(*step)))
+                .filter(|(_, operations)| !operations.is_empty())
+                .collect();
+            if selected.is_empty() {
+                return;
+            }
+            button.set_sensitive(false);
+            button.set_label(&gettext("Applying…"));
+            for (_, row) in rows.borrow().iter() {
+                row.set_sensitive(false);
+            }
+
+            let rows = rows.clone();
+            let button = button.clone();
+            let on_applied = on_applied.clone();
+            let window = window.clone();
+            glib::spawn_future_local(async move {
+                let (results, undos) = gtk4::gio::spawn_blocking(move || run_steps(selected))
+                    .await
+                    .unwrap_or_default();
+                if let Some(window) = window.downcast_ref::<super::MainWindow>() {
+                    for undo in undos {
+                        window.revert_unless_kept(undo);
+                    }
+                }
+                let mut changed = false;
+                for (step, step_results) in &results {
+                    let Some((_, row)) = rows.borrow().iter().find(|(s, _)| s == step).cloned()
+                    else {
+                        continue;
+                    };
+                    changed |= step_results.iter().any(|r| r.success);
+                    show_step_result(&row, step_results);
+                }
+                button.set_label(&gettext("Applied"));
+                if changed {
+                    on_applied();
+                }
+            });
+        });
+    });
+}
+
+/// Run each step's operations in order, stopping a step at its first
+/// failure; later steps still run. Also returns what reverts the risky
+/// operations among them.
+fn run_steps(
+    steps: Vec<(TravelStep, Vec<Operation>)>,
+) -> (Vec<(TravelStep, Vec<OperationResult>)>, Vec<Undo>) {
+    let mut runner = OperationRunner::new();
+    let mut undos = Vec::new();
+    let results = steps
+        .into_iter()
+        .map(|(step, operations)| {
+            let mut results = Vec::new();
+            for operation in operations {
+                let (result, undo) = runner.run_revertible(operation);
+                undos.extend(undo);
+                let failed = !result.success;
+                results.push(result);
+                if failed {
+                    break;
+                }
+            }
+            (step, results)
+        })
+        .collect();
+    (results, undos)
+}
+
+fn show_step_result(row: &adw::ExpanderRow, results: &[OperationResult]) {
+    let failure = results.iter().find(|r| !r.success);
+    let (icon, css, subtitle) = match failure {
+        None => ("emblem-ok-symbolic", "success", gettext("Applied")),
+        Some(result) => (
+            "dialog-error-symbolic",
+            "error",
+            gettext("Failed: %s").replace("%s", &result.message),
+        ),
+    };
+    row.set_sensitive(true);
+    row.set_show_enable_switch(false);
+    row.set_subtitle(&glib::markup_escape_text(&subtitle));
+    let image = gtk4::Image::from_icon_name(icon);
+    image.add_css_class(css);
+    row.add_suffix(&image);
+    // The steps taken, as the equivalent commands
+    let details: Vec<&str> = results
+        .iter()
+        .flat_map(|r| r.details.iter().map(String::as_str))
+        .collect();
+    if !details.is_empty() {
+        row.set_tooltip_text(Some(&details.join("\n")));
+    }
+}
@@ -0,0 +1,155 @@
+// Security Center - Firewall Activity Sparkline Widget
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! A header-sized chart of accepted and denied packets per second: accepted
+//! traffic as a filled line, denied packets as bars in the status palette's
+//! error colour on their own scale so a handful of drops stays visible next
+//! to heavy traffic.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::*;
+use gtk4::{glib, graphene};
+use libadwaita as adw;
+
+use crate::ui::palette;
+
+/// Samples kept, one per second.
+const HISTORY: usize = 60;
+
+glib::wrapper! {
+    /// Rolling accepted/denied packet rate chart.
+    pub struct ActivitySparkline(ObjectSubclass<imp::ActivitySparkline>)
+        @extends gtk4::Widget;
+}
+
+impl ActivitySparkline {
+    pub fn new() -> Self {
+        glib::Object::new()
+    }
+
+    /// Append one sample of packets per second.
+    pub fn push(&self, accepted: f64, denied: f64) {
+        let mut samples = self.imp().samples.borrow_mut();
+        if samples.len() == HISTORY {
+            samples.pop_front();
+        }
+        samples.push_back((accepted, denied));
+        drop(samples);
+        self.queue_draw();
+    }
+}
+
+impl Default for ActivitySparkline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct ActivitySparkline {
+        pub samples: RefCell<VecDeque<(f64, f64)>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ActivitySparkline {
+        const NAME: &'static str = "SecurityCenterActivitySparkline";
+        type Type = super::ActivitySparkline;
+        type ParentType = gtk4::Widget;
+    }
+
+    impl ObjectImpl for ActivitySparkline {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+            obj.set_size_request(72, 20);
+            obj.set_valign(gtk4::Align::Center);
+        }
+    }
+
+    impl WidgetImpl for ActivitySparkline {
+        fn snapshot(&self, snapshot: &gtk4::Snapshot) {
+            let widget = self.obj();
+            let width = widget.width() as f64;
+            let height = widget.height() as f64;
+            if width <= 0.0 || height <= 0.0 {
+                return;
+            }
+
+            let bounds = graphene::Rect::new(0.0, 0.0, width as f32, height as f32);
+            let cr = snapshot.append_cairo(&bounds);
+
+            // Baseline so the widget reads as a chart even while idle
+            let fg = widget.color();
+            cr.set_source_rgba(fg.red() as f64, fg.green() as f64, fg.blue() as f64, 0.2);
+            cr.set_line_width(1.0);
+            cr.move_to(0.0, height - 0.5);
+            cr.line_to(width, height - 0.5);
+            let _ = cr.stroke();
+
+            let samples = self.samples.borrow();
+            if samples.is_empty() {
+                return;
+            }
+
+            // Right-aligned: the newest sample is always at the right edge
+            let step = width / (HISTORY as f64 - 1.0);
+            let x_at = |i: usize| width - (samples.len() - 1 - i) as f64 * step;
+            let pad = 2.0;
+            let plot_h = (height - pad * 2.0).max(1.0);
+
+            let accepted_max = samples.iter().map(|s| s.0).fold(0.0_f64, f64::max);
+            if accepted_max > f64::EPSILON && samples.len() >= 2 {
+                let is_dark = adw::StyleManager::default().is_dark();
+                let (r, g, b) = if is_dark {
+                    (0.45, 0.62, 0.95)
+                } else {
+                    (0.21, 0.52, 0.89)
+                };
+                let y_at = |v: f64| height - pad - (v / accepted_max) * plot_h;
+
+                cr.set_source_rgba(r, g, b, 0.18);
+                cr.move_to(x_at(0), height);
+                for (i, s) in samples.iter().enumerate() {
+                    cr.line_to(x_at(i), y_at(s.0));
+                }
+                cr.line_to(width, height);
+                cr.close_path();
+                let _ = cr.fill();
+
+                cr.set_source_rgba(r, g, b, 0.9);
+                cr.set_line_width(1.25);
+                for (i, s) in samples.iter().enumerate() {
+                    if i == 0 {
+                        cr.move_to(x_at(i), y_at(s.0));
+                    } else {
+                        cr.line_to(x_at(i), y_at(s.0));
+                    }
+                }
+                let _ = cr.stroke();
+            }
+
+            let denied_max = samples.iter().map(|s| s.1).fold(0.0_f64, f64::max);
+            if denied_max > f64::EPSILON {
+                let (r, g, b) = palette::current().error();
+                cr.set_source_rgba(r, g, b, 0.9);
+                let bar_w = (step * 0.8).max(1.0);
+                for (i, s) in samples.iter().enumerate() {
+                    if s.1 <= 0.0 {
+                        continue;
+                    }
+                    // At least a third of the height so single drops stand out
+                    let h = (s.1 / denied_max * plot_h).max(plot_h / 3.0);
+                    cr.rectangle(x_at(i) - bar_w, height - h, bar_w, h);
+                }
+                let _ = cr.fill();
+            }
+        }
+    }
+}
@@ -10,6 +10,8 @@ use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
 use gtk4::{gdk, glib, graphene};
 
+use crate::ui::density::{self, Role};
+
 /// A bar entry for the chart.
 #[derive(Debug, Clone)]
 pub struct BarEntry {
@@ -159,8 +161,7 @@ mod imp {
             self.parent_constructed();
 
             let obj = self.obj();
-            obj.set_width_request(250);
-            obj.set_height_request(150);
+            density::track(&*obj, Role::Size(250, 150));
 
             self.placeholder.replace("No data available".to_string());
         }
@@ -3,7 +3,8 @@
 // SPDX-License-Identifier: MIT
 
 //! A segmented donut (ring) chart drawn with Cairo, theme-aware, used on the
-//! overview page for the connection-state breakdown.
+//! overview page for the connection-state breakdown. Each segment can carry a
+//! mark (check, cross, dash) so states stay distinguishable without colour.
 
 use std::cell::RefCell;
 use std::f64::consts::PI;
@@ -13,8 +14,19 @@ use gtk4::subclass::prelude::*;
 use gtk4::{glib, graphene};
 use libadwaita as adw;
 
-/// One ring segment: a value and its fill color as linear RGB in `0.0..=1.0`.
-pub type DonutSegment = (f64, (f64, f64, f64));
+use crate::ui::density::{self, Role};
+
+/// Shape drawn on a segment to identify its state independently of colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentMark {
+    Check,
+    Cross,
+    Dash,
+}
+
+/// One ring segment: a value, its fill color as linear RGB in `0.0..=1.0` and
+/// its mark.
+pub type DonutSegment = (f64, (f64, f64, f64), SegmentMark);
 
 glib::wrapper! {
     /// A donut chart plotting proportional segments around a ring.
@@ -27,11 +39,16 @@ impl DonutChart {
         glib::Object::new()
     }
 
-    /// Replace the plotted segments (value, rgb). Zero-value segments are skipped.
+    /// Replace the plotted segments (value, rgb, mark). Zero-value segments are skipped.
     pub fn set_segments(&self, segments: &[DonutSegment]) {
         *self.imp().segments.borrow_mut() = segments.to_vec();
         self.queue_draw();
     }
+
+    /// The plotted segments, in the order they were set.
+    pub fn segments(&self) -> Vec<DonutSegment> {
+        self.imp().segments.borrow().clone()
+    }
 }
 
 impl Default for DonutChart {
@@ -58,7 +75,7 @@ mod imp {
     impl ObjectImpl for DonutChart {
         fn constructed(&self) {
             self.parent_constructed();
-            self.obj().set_size_request(132, 132);
+            density::track(&*self.obj(), Role::Size(132, 132));
         }
     }
 
@@ -93,14 +110,15 @@ mod imp {
             let _ = cr.stroke();
 
             let segments = self.segments.borrow();
-            let total: f64 = segments.iter().map(|(v, _)| *v).sum();
+            let total: f64 = segments.iter().map(|(v, _, _)| *v).sum();
             if total <= 0.0 {
                 return;
             }
 
             // Draw each segment clockwise from the top (12 o'clock).
             let mut start = -PI / 2.0;
-            for (v, (r, g, b)) in segments.iter() {
+            let mut marks = Vec::new();
+            for (v, (r, g, b), mark) in segments.iter() {
                 if *v <= 0.0 {
                     continue;
                 }
@@ -108,8 +126,40 @@ mod imp {
                 cr.set_source_rgb(*r, *g, *b);
                 cr.arc(cx, cy, radius, start, start + sweep);
                 let _ = cr.stroke();
+                // Only where the segment is long enough to hold the mark
+                if sweep * radius >= thickness * 1.5 {
+                    marks.push((start + sweep / 2.0, *mark));
+                }
                 start += sweep;
             }
+
+            // Marks on top, centred on each segment.
+            let size = thickness * 0.28;
+            cr.set_source_rgba(1.0, 1.0, 1.0, 0.95);
+            cr.set_line_width(2.0);
+            cr.set_line_cap(gtk4::cairo::LineCap::Round);
+            for (angle, mark) in marks {
+                let mx = cx + radius * angle.cos();
+                let my = cy + radius * angle.sin();
+                match mark {
+                    SegmentMark::Check => {
+                        cr.move_to(mx - size, my);
+                        cr.line_to(mx - size * 0.3, my + size * 0.7);
+                        cr.line_to(mx + size, my - size * 0.7);
+                    }
+                    SegmentMark::Cross => {
+                        cr.move_to(mx - size * 0.8, my - size * 0.8);
+                        cr.line_to(mx + size * 0.8, my + size * 0.8);
+                        cr.move_to(mx + size * 0.8, my - size * 0.8);
+                        cr.line_to(mx - size * 0.8, my + size * 0.8);
+                    }
+                    SegmentMark::Dash => {
+                        cr.move_to(mx - size, my);
+                        cr.line_to(mx + size, my);
+                    }
+                }
+                let _ = cr.stroke();
+            }
         }
     }
 }
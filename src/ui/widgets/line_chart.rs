@@ -70,6 +70,11 @@ impl LineChart {
         self.queue_draw();
     }
 
+    /// The data series shown, for exporting what the chart plots.
+    pub fn series(&self) -> Vec<DataSeries> {
+        self.imp().series.borrow().clone()
+    }
+
     /// Add a single value to each series (for live updates).
     pub fn push_values(&self, values: &[f64]) {
         let imp = self.imp();
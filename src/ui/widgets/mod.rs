@@ -4,17 +4,20 @@
 
 //! Custom UI widgets.
 
+mod activity_sparkline;
 mod bar_chart;
 mod donut_chart;
 mod line_chart;
 mod meter_bar;
 mod network_activity_chart;
 mod sparkline;
+mod zone_topology;
 
+pub use activity_sparkline::ActivitySparkline;
 pub use bar_chart::BarChart;
-pub use donut_chart::DonutChart;
-#[allow(unused_imports)] // retained for reuse
-pub use line_chart::LineChart;
+pub use donut_chart::{DonutChart, SegmentMark};
+pub use line_chart::{DataSeries, LineChart};
 pub use meter_bar::MeterBar;
-pub use network_activity_chart::{list_interfaces, NetworkActivityChart};
+pub use network_activity_chart::{list_interfaces, ChartAnnotation, NetworkActivityChart};
 pub use sparkline::Sparkline;
+pub use zone_topology::ZoneTopology;
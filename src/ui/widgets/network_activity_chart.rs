@@ -4,14 +4,36 @@
 
 //! Live network activity chart with spike visualization.
 //! Styled to match Network Manager's network activity graph.
+//!
+//! Events such as firewall reloads can be marked on the time axis with
+//! [`NetworkActivityChart::set_annotations`], drawn as dashed vertical lines
+//! whose labels show as tooltips.
 
 use std::cell::RefCell;
 
+use chrono::{DateTime, Local};
 use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
 use gtk4::{glib, graphene};
 use libadwaita as adw;
 
+use crate::i18n::format_time;
+use crate::ui::density::{self, Role};
+
+/// Seconds between live samples.
+const SAMPLE_SECS: f64 = 1.0;
+/// How close, in pixels, the pointer must be to a marker to show its label.
+const MARKER_HIT_WIDTH: f64 = 6.0;
+
+/// An event marked on the chart's time axis.
+#[derive(Debug, Clone)]
+pub struct ChartAnnotation {
+    pub time: DateTime<Local>,
+    pub label: String,
+    /// Marker colour as RGB.
+    pub color: (f64, f64, f64),
+}
+
 glib::wrapper! {
     /// A network activity chart showing connection data with spike visualization.
     pub struct NetworkActivityChart(ObjectSubclass<imp::NetworkActivityChart>)
@@ -56,6 +78,35 @@ impl NetworkActivityChart {
         self.queue_draw();
     }
 
+    /// Mark events on the time axis. Markers scroll with the data and
+    /// disappear once older than the visible history.
+    pub fn set_annotations(&self, annotations: Vec<ChartAnnotation>) {
+        *self.imp().annotations.borrow_mut() = annotations;
+        self.queue_draw();
+    }
+
+    /// Horizontal position of `time` on a chart `width` pixels wide, or
+    /// `None` when it is older than the visible history.
+    fn time_x(&self, time: DateTime<Local>, now: DateTime<Local>, width: f64) -> Option<f64> {
+        let points = self.imp().inbound_data.borrow().len();
+        let span = points.saturating_sub(1) as f64 * SAMPLE_SECS;
+        if span <= 0.0 {
+            return None;
+        }
+        let age = (now - time).num_milliseconds().max(0) as f64 / 1000.0;
+        (age <= span).then(|| width - age / span * width)
+    }
+
+    /// The plotted history in KB/s, inbound and outbound, oldest first
+    /// and one sample per second.
+    pub fn data(&self) -> (Vec<f64>, Vec<f64>) {
+        let imp = self.imp();
+        (
+            imp.inbound_data.borrow().clone(),
+            imp.outbound_data.borrow().clone(),
+        )
+    }
+
     /// Get the current inbound value.
     pub fn current_inbound(&self) -> f64 {
         self.imp()
@@ -105,6 +156,11 @@ impl NetworkActivityChart {
 
         let chart = self.clone();
         glib::timeout_add_local(std::time::Duration::from_millis(1000), move || {
+            if crate::safe_mode::is_enabled() {
+                // Sample afresh when resumed rather than across the pause
+                chart.imp().prev_stats.replace(None);
+                return glib::ControlFlow::Continue;
+            }
             let iface = chart.imp().selected_iface.borrow().clone();
             let (rx_bytes, tx_bytes) = read_network_stats(iface.as_deref());
 
@@ -197,6 +253,7 @@ mod imp {
         pub collecting: Cell<bool>,
         #[allow(clippy::type_complexity)]
         pub rate_callback: RefCell<Option<Box<dyn Fn(f64, f64)>>>,
+        pub annotations: RefCell<Vec<super::ChartAnnotation>>,
     }
 
     #[glib::object_subclass]
@@ -211,14 +268,38 @@ mod imp {
             self.parent_constructed();
 
             let obj = self.obj();
-            obj.set_width_request(300);
-            obj.set_height_request(120);
+            density::track(&*obj, Role::Size(300, 120));
 
             self.max_points.set(60);
 
             // Initialize with zeros
             *self.inbound_data.borrow_mut() = vec![0.0; 60];
             *self.outbound_data.borrow_mut() = vec![0.0; 60];
+
+            // Show the labels of the markers under the pointer
+            obj.set_has_tooltip(true);
+            obj.connect_query_tooltip(|chart, x, _y, _keyboard, tooltip| {
+                let now = Local::now();
+                let width = chart.width() as f64;
+                let text = chart
+                    .imp()
+                    .annotations
+                    .borrow()
+                    .iter()
+                    .filter(|a| {
+                        chart
+                            .time_x(a.time, now, width)
+                            .is_some_and(|ax| (ax - x as f64).abs() <= MARKER_HIT_WIDTH)
+                    })
+                    .map(|a| format!("{}  {}", format_time(a.time.timestamp()), a.label))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                if text.is_empty() {
+                    return false;
+                }
+                tooltip.set_text(Some(&text));
+                true
+            });
         }
     }
 
@@ -319,6 +400,24 @@ mod imp {
                 }
             }
             let _ = cr.stroke();
+
+            // Event markers on top of the data
+            let now = Local::now();
+            cr.set_line_width(1.0);
+            for annotation in self.annotations.borrow().iter() {
+                let Some(x) = widget.time_x(annotation.time, now, width) else {
+                    continue;
+                };
+                let (r, g, b) = annotation.color;
+                cr.set_source_rgba(r, g, b, 0.9);
+                cr.set_dash(&[3.0, 3.0], 0.0);
+                cr.move_to(x, 0.0);
+                cr.line_to(x, height);
+                let _ = cr.stroke();
+                cr.set_dash(&[], 0.0);
+                cr.arc(x, 4.0, 3.0, 0.0, std::f64::consts::TAU);
+                let _ = cr.fill();
+            }
         }
     }
 }
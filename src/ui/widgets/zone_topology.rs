@@ -0,0 +1,369 @@
+// Security Center - Zone Topology Widget
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Node-edge map of the zones in use, drawn with Cairo. Each zone sits in
+//! the middle column, linked on the left to the interfaces and sources
+//! bound to it and on the right to the services and ports it lets through.
+//! Hovering a service lists the ports it opens.
+
+use std::cell::RefCell;
+
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::*;
+use gtk4::{glib, graphene};
+use libadwaita as adw;
+
+use crate::models::Zone;
+use crate::ui::palette;
+use crate::ui::service_ports::service_tooltip;
+
+/// Height of one node row.
+const ROW_HEIGHT: f64 = 30.0;
+/// Space between zone bands.
+const BAND_GAP: f64 = 14.0;
+/// Allowed items listed per zone before the rest collapse into "+N more".
+const MAX_ALLOWED: usize = 6;
+/// Center of the column of allowed items, as a fraction of the width.
+const ALLOWED_COLUMN: f64 = 0.84;
+/// Widest node, as a fraction of the width.
+const MAX_NODE_WIDTH: f64 = 0.28;
+
+/// One zone of the map, with the nodes on both sides of it.
+#[derive(Debug, Clone)]
+struct Band {
+    zone: String,
+    is_default: bool,
+    /// Interfaces and sources bound to the zone.
+    inputs: Vec<String>,
+    /// Services and ports let through, or a single summary of the target.
+    allowed: Vec<String>,
+    /// How many of `allowed`, from the start, are services.
+    service_count: usize,
+    /// Whether the zone accepts everything, not only `allowed`.
+    accepts_all: bool,
+}
+
+impl Band {
+    fn from_zone(zone: &Zone) -> Self {
+        let mut inputs = zone.interfaces.clone();
+        inputs.extend(zone.sources.iter().cloned());
+        if inputs.is_empty() {
+            // The default zone also takes traffic no other zone claims
+            inputs.push("other traffic".to_string());
+        }
+
+        let accepts_all = zone.target == "ACCEPT";
+        let mut allowed: Vec<String> = if accepts_all {
+            vec!["all traffic".to_string()]
+        } else {
+            zone.services
+                .iter()
+                .cloned()
+                .chain(zone.ports.iter().cloned())
+                .collect()
+        };
+        let service_count = if accepts_all {
+            0
+        } else {
+            zone.services.len().min(MAX_ALLOWED - 1)
+        };
+        if allowed.len() > MAX_ALLOWED {
+            let more = allowed.len() - (MAX_ALLOWED - 1);
+            allowed.truncate(MAX_ALLOWED - 1);
+            allowed.push(format!("+{} more", more));
+        }
+        if allowed.is_empty() {
+            allowed.push("nothing".to_string());
+        }
+
+        Self {
+            zone: zone.name.clone(),
+            is_default: zone.is_default,
+            inputs,
+            allowed,
+            service_count,
+            accepts_all,
+        }
+    }
+
+    fn rows(&self) -> usize {
+        self.inputs.len().max(self.allowed.len())
+    }
+
+    fn height(&self) -> f64 {
+        self.rows() as f64 * ROW_HEIGHT
+    }
+
+    /// The service drawn at `y`, measured from the top of the band.
+    fn service_at(&self, y: f64) -> Option<&str> {
+        let offset = (self.rows() - self.allowed.len()) as f64 * ROW_HEIGHT / 2.0;
+        let index = ((y - offset) / ROW_HEIGHT).floor();
+        if index < 0.0 {
+            return None;
+        }
+        let index = index as usize;
+        (index < self.service_count).then(|| self.allowed[index].as_str())
+    }
+}
+
+glib::wrapper! {
+    /// A map of interfaces, their zones and what each zone lets through.
+    pub struct ZoneTopology(ObjectSubclass<imp::ZoneTopology>)
+        @extends gtk4::Widget;
+}
+
+impl ZoneTopology {
+    pub fn new() -> Self {
+        glib::Object::new()
+    }
+
+    /// Show the zones in use among `zones`.
+    pub fn set_zones(&self, zones: &[Zone]) {
+        let bands: Vec<Band> = zones
+            .iter()
+            .filter(|z| z.is_in_use())
+            .map(Band::from_zone)
+            .collect();
+        let height: f64 = bands.iter().map(|b| b.height() + BAND_GAP).sum::<f64>() + BAND_GAP;
+        self.set_height_request(height.max(ROW_HEIGHT * 2.0) as i32);
+        self.imp().bands.replace(bands);
+        self.queue_draw();
+    }
+}
+
+impl Default for ZoneTopology {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod imp {
+    use super::*;
+
+    use gtk4::cairo::{Context, FontSlant, FontWeight};
+
+    #[derive(Default)]
+    pub struct ZoneTopology {
+        pub(super) bands: RefCell<Vec<Band>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ZoneTopology {
+        const NAME: &'static str = "SecurityCenterZoneTopology";
+        type Type = super::ZoneTopology;
+        type ParentType = gtk4::Widget;
+    }
+
+    impl ObjectImpl for ZoneTopology {
+        fn constructed(&self) {
+            self.parent_constructed();
+            self.obj().set_hexpand(true);
+            self.obj().set_height_request((ROW_HEIGHT * 2.0) as i32);
+            self.obj().set_has_tooltip(true);
+        }
+    }
+
+    impl WidgetImpl for ZoneTopology {
+        fn query_tooltip(&self, x: i32, y: i32, keyboard: bool, tooltip: &gtk4::Tooltip) -> bool {
+            let widget = self.obj();
+            let width = widget.width() as f64;
+            // Only service nodes, in the right column, have a tooltip
+            if keyboard || (x as f64) < width * (ALLOWED_COLUMN - MAX_NODE_WIDTH / 2.0) {
+                return false;
+            }
+            let mut top = BAND_GAP;
+            for band in self.bands.borrow().iter() {
+                let y = y as f64 - top;
+                if (0.0..band.height()).contains(&y) {
+                    let Some(service) = band.service_at(y) else {
+                        return false;
+                    };
+                    tooltip.set_text(Some(&service_tooltip(service, widget.upcast_ref())));
+                    return true;
+                }
+                top += band.height() + BAND_GAP;
+            }
+            false
+        }
+
+        fn snapshot(&self, snapshot: &gtk4::Snapshot) {
+            let widget = self.obj();
+            let width = widget.width() as f64;
+            let height = widget.height() as f64;
+            if width <= 0.0 || height <= 0.0 {
+                return;
+            }
+
+            let bounds = graphene::Rect::new(0.0, 0.0, width as f32, height as f32);
+            let cr = snapshot.append_cairo(&bounds);
+            cr.select_font_face("Sans", FontSlant::Normal, FontWeight::Normal);
+            cr.set_font_size(11.0);
+
+            let text = widget.color();
+            let text = (text.red() as f64, text.green() as f64, text.blue() as f64);
+            let is_dark = adw::StyleManager::default().is_dark();
+            let node_fill = if is_dark { 1.0 } else { 0.0 };
+            let status = palette::current();
+            let accent = (0.21, 0.52, 0.89);
+
+            let bands = self.bands.borrow();
+            if bands.is_empty() {
+                let message = "No zone has interfaces or sources";
+                cr.set_source_rgba(text.0, text.1, text.2, 0.5);
+                let extents = cr.text_extents(message).ok();
+                let text_width = extents.map(|e| e.width()).unwrap_or(0.0);
+                cr.move_to((width - text_width) / 2.0, height / 2.0);
+                let _ = cr.show_text(message);
+                return;
+            }
+
+            // Column centers: inputs, zones, allowed
+            let columns = [width * 0.16, width * 0.5, width * ALLOWED_COLUMN];
+            let max_node_width = width * MAX_NODE_WIDTH;
+
+            let mut top = BAND_GAP;
+            for band in bands.iter() {
+                let band_height = band.height();
+                let center = top + band_height / 2.0;
+                let row_y = |index: usize, count: usize| {
+                    let offset = (band.rows() - count) as f64 * ROW_HEIGHT / 2.0;
+                    top + offset + (index as f64 + 0.5) * ROW_HEIGHT
+                };
+
+                let zone_label = if band.is_default {
+                    format!("{} (default)", band.zone)
+                } else {
+                    band.zone.clone()
+                };
+                cr.select_font_face("Sans", FontSlant::Normal, FontWeight::Bold);
+                let zone_width = node_width(&cr, &zone_label, max_node_width);
+                cr.select_font_face("Sans", FontSlant::Normal, FontWeight::Normal);
+                let zone_left = columns[1] - zone_width / 2.0;
+                let zone_right = columns[1] + zone_width / 2.0;
+
+                // Edges first, so nodes cover their ends
+                cr.set_line_width(1.5);
+                for (i, input) in band.inputs.iter().enumerate() {
+                    let y = row_y(i, band.inputs.len());
+                    let x = columns[0] + node_width(&cr, input, max_node_width) / 2.0;
+                    cr.set_source_rgba(text.0, text.1, text.2, 0.3);
+                    curve(&cr, (x, y), (zone_left, center));
+                }
+                let allowed_color = if band.accepts_all {
+                    status.warning()
+                } else {
+                    status.success()
+                };
+                for (i, item) in band.allowed.iter().enumerate() {
+                    let y = row_y(i, band.allowed.len());
+                    let x = columns[2] - node_width(&cr, item, max_node_width) / 2.0;
+                    cr.set_source_rgba(allowed_color.0, allowed_color.1, allowed_color.2, 0.6);
+                    curve(&cr, (zone_right, center), (x, y));
+                }
+
+                // Input nodes
+                for (i, input) in band.inputs.iter().enumerate() {
+                    let y = row_y(i, band.inputs.len());
+                    draw_node(
+                        &cr,
+                        input,
+                        (columns[0], y),
+                        max_node_width,
+                        (node_fill, node_fill, node_fill, 0.08),
+                        (text.0, text.1, text.2, 0.9),
+                    );
+                }
+
+                // Zone node
+                cr.select_font_face("Sans", FontSlant::Normal, FontWeight::Bold);
+                draw_node(
+                    &cr,
+                    &zone_label,
+                    (columns[1], center),
+                    max_node_width,
+                    (accent.0, accent.1, accent.2, 1.0),
+                    (1.0, 1.0, 1.0, 1.0),
+                );
+                cr.select_font_face("Sans", FontSlant::Normal, FontWeight::Normal);
+
+                // Allowed nodes
+                for (i, item) in band.allowed.iter().enumerate() {
+                    let y = row_y(i, band.allowed.len());
+                    draw_node(
+                        &cr,
+                        item,
+                        (columns[2], y),
+                        max_node_width,
+                        (allowed_color.0, allowed_color.1, allowed_color.2, 0.18),
+                        (text.0, text.1, text.2, 0.9),
+                    );
+                }
+
+                top += band_height + BAND_GAP;
+            }
+        }
+    }
+
+    /// Width of the pill drawn for `label`.
+    fn node_width(cr: &Context, label: &str, max_width: f64) -> f64 {
+        let text_width = cr.text_extents(label).map(|e| e.x_advance()).unwrap_or(0.0);
+        (text_width + 20.0).min(max_width)
+    }
+
+    /// Draw a pill centered on `center` with `label` in it, shortened with
+    /// an ellipsis when it does not fit.
+    fn draw_node(
+        cr: &Context,
+        label: &str,
+        center: (f64, f64),
+        max_width: f64,
+        fill: (f64, f64, f64, f64),
+        ink: (f64, f64, f64, f64),
+    ) {
+        let width = node_width(cr, label, max_width);
+        let height = ROW_HEIGHT - 8.0;
+        let x = center.0 - width / 2.0;
+        let y = center.1 - height / 2.0;
+
+        rounded_rect(cr, x, y, width, height, height / 2.0);
+        cr.set_source_rgba(fill.0, fill.1, fill.2, fill.3);
+        let _ = cr.fill();
+
+        let mut shown = label.to_string();
+        while shown.chars().count() > 1
+            && cr
+                .text_extents(&shown)
+                .map(|e| e.x_advance() + 20.0 > width)
+                .unwrap_or(false)
+        {
+            shown.pop();
+            shown.pop();
+            shown.push('…');
+        }
+        let extents = cr.text_extents(&shown).ok();
+        let text_width = extents.map(|e| e.x_advance()).unwrap_or(0.0);
+        cr.set_source_rgba(ink.0, ink.1, ink.2, ink.3);
+        cr.move_to(center.0 - text_width / 2.0, center.1 + 4.0);
+        let _ = cr.show_text(&shown);
+    }
+
+    /// Horizontal S-curve from `from` to `to`.
+    fn curve(cr: &Context, from: (f64, f64), to: (f64, f64)) {
+        let mid = (from.0 + to.0) / 2.0;
+        cr.move_to(from.0, from.1);
+        cr.curve_to(mid, from.1, mid, to.1, to.0, to.1);
+        let _ = cr.stroke();
+    }
+
+    fn rounded_rect(cr: &Context, x: f64, y: f64, w: f64, h: f64, r: f64) {
+        use std::f64::consts::PI;
+        let r = r.min(w / 2.0).min(h / 2.0);
+        cr.new_sub_path();
+        cr.arc(x + w - r, y + r, r, -PI / 2.0, 0.0);
+        cr.arc(x + w - r, y + h - r, r, 0.0, PI / 2.0);
+        cr.arc(x + r, y + h - r, r, PI / 2.0, PI);
+        cr.arc(x + r, y + r, r, PI, 1.5 * PI);
+        cr.close_path();
+    }
+}
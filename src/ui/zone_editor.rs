@@ -0,0 +1,371 @@
+// Security Center - Zone editor dialog
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Editor for the settings of a zone that are not rules: its target,
+//! blocked ICMP types, masquerading, forwarding and description. Changes
+//! are written to the permanent configuration through firewalld's
+//! `config.zone` interface and take effect when firewalld reloads, which
+//! the dialog offers to do right away.
+//!
+//! New zones are created the same way, from a name, target and
+//! description, and firewalld is reloaded so they can be used at once.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use super::app_state::Invalidation;
+use super::MainWindow;
+use crate::i18n::gettext;
+use crate::models::{target_label, ZoneSettings, ZONE_TARGETS};
+use crate::validation::validate_zone_name;
+
+/// Build and present the editor for `zone` anchored to `window`, starting
+/// from its current `settings`. `icmp_types` are the ICMP types that can be
+/// blocked.
+pub fn present_zone_editor(
+    window: &MainWindow,
+    zone: &str,
+    settings: ZoneSettings,
+    icmp_types: &[String],
+) {
+    let dialog = adw::Dialog::builder()
+        .title(gettext("Edit Zone '%s'").replace("%s", zone))
+        .content_width(520)
+        .content_height(680)
+        .build();
+
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&adw::HeaderBar::new());
+
+    let page = adw::PreferencesPage::new();
+
+    // --- General ---
+    let general_group = adw::PreferencesGroup::builder()
+        .title(gettext("General"))
+        .build();
+
+    let short_row = adw::EntryRow::builder()
+        .title(gettext("Display Name"))
+        .text(&settings.short)
+        .build();
+    general_group.add(&short_row);
+
+    let description_row = adw::EntryRow::builder()
+        .title(gettext("Description"))
+        .text(&settings.description)
+        .build();
+    general_group.add(&description_row);
+
+    let target_labels: Vec<&str> = ZONE_TARGETS.iter().map(|t| target_label(t)).collect();
+    let target_row = adw::ComboRow::builder()
+        .title(gettext("Target"))
+        .subtitle(gettext(
+            "What happens to traffic no rule of the zone matches",
+        ))
+        .model(&gtk4::StringList::new(&target_labels))
+        .selected(
+            ZONE_TARGETS
+                .iter()
+                .position(|t| *t == settings.target)
+                .unwrap_or(0) as u32,
+        )
+        .build();
+    general_group.add(&target_row);
+    page.add(&general_group);
+
+    // --- Routing ---
+    let routing_group = adw::PreferencesGroup::builder()
+        .title(gettext("Routing"))
+        .build();
+
+    let masquerade_row = adw::SwitchRow::builder()
+        .title(gettext("Masquerade"))
+        .subtitle(gettext(
+            "Share this machine's connection by rewriting the source of forwarded traffic",
+        ))
+        .active(settings.masquerade)
+        .build();
+    routing_group.add(&masquerade_row);
+
+    let forward_row = adw::SwitchRow::builder()
+        .title(gettext("Forward"))
+        .subtitle(gettext(
+            "Let traffic pass between the interfaces and sources of this zone",
+        ))
+        .active(settings.forward)
+        .build();
+    routing_group.add(&forward_row);
+    page.add(&routing_group);
+
+    // --- ICMP ---
+    let icmp_group = adw::PreferencesGroup::builder()
+        .title(gettext("ICMP"))
+        .build();
+
+    let inversion_row = adw::SwitchRow::builder()
+        .title(gettext("Invert ICMP Blocks"))
+        .subtitle(gettext(
+            "Block every ICMP type except the ones selected below",
+        ))
+        .active(settings.icmp_block_inversion)
+        .build();
+    icmp_group.add(&inversion_row);
+
+    let types_row = adw::ExpanderRow::builder()
+        .title(gettext("Blocked ICMP Types"))
+        .build();
+    let icmp_switches: Rc<RefCell<Vec<(String, adw::SwitchRow)>>> =
+        Rc::new(RefCell::new(Vec::new()));
+    // Keep types the zone blocks even when firewalld no longer lists them
+    let mut all_types = icmp_types.to_vec();
+    for blocked in &settings.icmp_blocks {
+        if !all_types.contains(blocked) {
+            all_types.push(blocked.clone());
+        }
+    }
+    for icmp_type in &all_types {
+        let row = adw::SwitchRow::builder()
+            .title(glib::markup_escape_text(icmp_type).as_str())
+            .active(settings.icmp_blocks.contains(icmp_type))
+            .build();
+        types_row.add_row(&row);
+        icmp_switches.borrow_mut().push((icmp_type.clone(), row));
+    }
+    let update_types_subtitle = {
+        let types_row = types_row.clone();
+        let icmp_switches = icmp_switches.clone();
+        let inversion_row = inversion_row.clone();
+        move || {
+            let count = icmp_switches
+                .borrow()
+                .iter()
+                .filter(|(_, row)| row.is_active())
+                .count();
+            let subtitle = if inversion_row.is_active() {
+                gettext("%d allowed, all others blocked")
+            } else {
+                gettext("%d blocked")
+            };
+            types_row.set_subtitle(&subtitle.replace("%d", &count.to_string()));
+            types_row.set_title(&if inversion_row.is_active() {
+                gettext("Allowed ICMP Types")
+            } else {
+                gettext("Blocked ICMP Types")
+            });
+        }
+    };
+    update_types_subtitle();
+    for (_, row) in icmp_switches.borrow().iter() {
+        let update = update_types_subtitle.clone();
+        row.connect_active_notify(move |_| update());
+    }
+    {
+        let update = update_types_subtitle.clone();
+        inversion_row.connect_active_notify(move |_| update());
+    }
+    icmp_group.add(&types_row);
+    page.add(&icmp_group);
+
+    // --- Apply ---
+    let apply_group = adw::PreferencesGroup::new();
+    let reload_row = adw::SwitchRow::builder()
+        .title(gettext("Apply Now"))
+        .subtitle(gettext(
+            "Reload firewalld after saving; changes made for this session only are lost",
+        ))
+        .active(true)
+        .build();
+    apply_group.add(&reload_row);
+    page.add(&apply_group);
+
+    let save_button = gtk4::Button::builder()
+        .label(gettext("Save"))
+        .css_classes(["suggested-action", "pill"])
+        .halign(gtk4::Align::Center)
+        .margin_top(12)
+        .margin_bottom(12)
+        .build();
+
+    let window_for_save = window.clone();
+    let dialog_for_save = dialog.clone();
+    let zone = zone.to_string();
+    save_button.connect_clicked(move |_| {
+        let updated = ZoneSettings {
+            short: short_row.text().trim().to_string(),
+            description: description_row.text().trim().to_string(),
+            target: ZONE_TARGETS
+                .get(target_row.selected() as usize)
+                .unwrap_or(&ZONE_TARGETS[0])
+                .to_string(),
+            icmp_blocks: icmp_switches
+                .borrow()
+                .iter()
+                .filter(|(_, row)| row.is_active())
+                .map(|(name, _)| name.clone())
+                .collect(),
+            icmp_block_inversion: inversion_row.is_active(),
+            masquerade: masquerade_row.is_active(),
+            forward: forward_row.is_active(),
+        };
+        dialog_for_save.close();
+        save_zone_settings(
+            &window_for_save,
+            zone.clone(),
+            updated,
+            reload_row.is_active(),
+        );
+    });
+
+    toolbar.set_content(Some(&page));
+    toolbar.add_bottom_bar(&save_button);
+    dialog.set_child(Some(&toolbar));
+    dialog.present(Some(window));
+}
+
+/// Write `settings` to `zone` in the background, reload firewalld when
+/// `reload` is set, and report the result.
+fn save_zone_settings(window: &MainWindow, zone: String, settings: ZoneSettings, reload: bool) {
+    let window = window.clone();
+
+    glib::spawn_future_local(async move {
+        let zone_for_save = zone.clone();
+        let result = crate::firewall::call(move |client| {
+            client.connect()?;
+            client.update_zone_settings(&zone_for_save, &settings)?;
+            if reload {
+                client.reload()?;
+            }
+            Ok::<_, anyhow::Error>(())
+        })
+        .await;
+
+        match result {
+            Ok(Ok(())) => {
+                let message = if reload {
+                    gettext("Zone '%s' updated")
+                } else {
+                    gettext("Zone '%s' updated — reload firewalld to apply")
+                };
+                window.show_toast(&message.replace("%s", &zone));
+                window.invalidate(Invalidation::ZonesChanged);
+            }
+            Ok(Err(e)) => {
+                window.show_toast(&format!("{}: {}", gettext("Failed to update zone"), e));
+            }
+            Err(_) => {
+                window.show_toast(&gettext("Failed to update zone"));
+            }
+        }
+    });
+}
+
+/// Build and present the dialog creating a zone, anchored to `window`.
+/// `existing` are the names already taken.
+pub fn present_zone_creator(window: &MainWindow, existing: &[String]) {
+    let dialog = adw::AlertDialog::builder()
+        .heading(gettext("New Zone"))
+        .body(gettext(
+            "The zone starts without services or ports. Reloading firewalld to add it \
+             discards changes made for this session only.",
+        ))
+        .build();
+
+    let group = adw::PreferencesGroup::new();
+
+    let name_row = adw::EntryRow::builder()
+        .title(gettext("Name (letters, digits, - and _)"))
+        .build();
+    group.add(&name_row);
+
+    let target_labels: Vec<&str> = ZONE_TARGETS.iter().map(|t| target_label(t)).collect();
+    let target_row = adw::ComboRow::builder()
+        .title(gettext("Target"))
+        .subtitle(gettext(
+            "What happens to traffic no rule of the zone matches",
+        ))
+        .model(&gtk4::StringList::new(&target_labels))
+        .build();
+    group.add(&target_row);
+
+    let description_row = adw::EntryRow::builder()
+        .title(gettext("Description (optional)"))
+        .build();
+    group.add(&description_row);
+
+    dialog.set_extra_child(Some(&group));
+    dialog.add_response("cancel", "_Cancel");
+    dialog.add_response("create", "C_reate");
+    dialog.set_response_appearance("create", adw::ResponseAppearance::Suggested);
+
+    let revalidate = {
+        let dialog = dialog.clone();
+        let name_row = name_row.clone();
+        let existing = existing.to_vec();
+        move || {
+            let name = name_row.text();
+            let ok = validate_zone_name(&name).is_some() && !existing.iter().any(|z| *z == name);
+            if ok || name.is_empty() {
+                name_row.remove_css_class("error");
+            } else {
+                name_row.add_css_class("error");
+            }
+            dialog.set_response_enabled("create", ok);
+        }
+    };
+    revalidate();
+    name_row.connect_changed(move |_| revalidate());
+
+    let window_for_create = window.clone();
+    dialog.connect_response(None, move |_, response| {
+        if response != "create" {
+            return;
+        }
+        let target = ZONE_TARGETS
+            .get(target_row.selected() as usize)
+            .unwrap_or(&ZONE_TARGETS[0]);
+        create_zone(
+            &window_for_create,
+            name_row.text().to_string(),
+            target.to_string(),
+            description_row.text().trim().to_string(),
+        );
+    });
+
+    dialog.present(Some(window));
+}
+
+/// Create `name` in the background, reload firewalld so it can be used,
+/// and report the result.
+fn create_zone(window: &MainWindow, name: String, target: String, description: String) {
+    let window = window.clone();
+
+    glib::spawn_future_local(async move {
+        let name_for_create = name.clone();
+        let result = crate::firewall::call(move |client| {
+            client.connect()?;
+            client.create_zone(&name_for_create, &target, &description)?;
+            client.reload()?;
+            Ok::<_, anyhow::Error>(())
+        })
+        .await;
+
+        match result {
+            Ok(Ok(())) => {
+                window.show_toast(&gettext("Zone '%s' created").replace("%s", &name));
+                window.invalidate(Invalidation::ZonesChanged);
+            }
+            Ok(Err(e)) => {
+                window.show_toast(&format!("{}: {}", gettext("Failed to create zone"), e));
+            }
+            Err(_) => {
+                window.show_toast(&gettext("Failed to create zone"));
+            }
+        }
+    });
+}
@@ -0,0 +1,272 @@
+// Security Center - Zone import dialog
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Preview and import of a firewalld zone XML file dropped onto the main
+//! window. The preview lists the file's services and ports with a check box
+//! each; importing adds the checked ones to a zone picked by the user,
+//! for this session and permanently. Elements that cannot be imported are
+//! listed so nothing is dropped silently.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use super::app_state::Invalidation;
+use super::service_ports::attach_service_tooltip;
+use super::MainWindow;
+use crate::firewall::ZoneFile;
+use crate::i18n::gettext;
+
+/// A rule selected for import.
+#[derive(Clone)]
+enum ImportRule {
+    Service(String),
+    Port(String, String),
+}
+
+/// Build and present the import preview for `zone_file` anchored to `window`.
+///
+/// `zones` are the zones the rules can be imported into; the one named like
+/// the file is preselected, then `default_zone`.
+pub fn present_zone_import(
+    window: &MainWindow,
+    file_name: &str,
+    zone_file: ZoneFile,
+    zones: &[String],
+    default_zone: &str,
+) {
+    let dialog = adw::Dialog::builder()
+        .title(gettext("Import Zone File"))
+        .content_width(520)
+        .content_height(620)
+        .build();
+
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&adw::HeaderBar::new());
+
+    let page = adw::PreferencesPage::new();
+
+    // --- File ---
+    let file_group = adw::PreferencesGroup::builder()
+        .title(glib::markup_escape_text(file_name).as_str())
+        .description(if zone_file.description.is_empty() {
+            gettext("firewalld zone definition")
+        } else {
+            glib::markup_escape_text(&zone_file.description).to_string()
+        })
+        .build();
+    if !zone_file.short.is_empty() {
+        let name_row = adw::ActionRow::builder()
+            .title(gettext("Zone Name"))
+            .subtitle(glib::markup_escape_text(&zone_file.short).as_str())
+            .build();
+        file_group.add(&name_row);
+    }
+    if let Some(target) = &zone_file.target {
+        let target_row = adw::ActionRow::builder()
+            .title(gettext("Target"))
+            .subtitle(glib::markup_escape_text(target).as_str())
+            .build();
+        target_row.set_tooltip_text(Some(&gettext(
+            "The target is not imported; it stays as configured on the chosen zone",
+        )));
+        file_group.add(&target_row);
+    }
+    page.add(&file_group);
+
+    // --- Destination zone ---
+    let zone_group = adw::PreferencesGroup::new();
+    let zone_refs: Vec<&str> = zones.iter().map(|z| z.as_str()).collect();
+    let zone_row = adw::ComboRow::builder()
+        .title(gettext("Import Into"))
+        .subtitle(gettext("Services and ports are added to this zone"))
+        .model(&gtk4::StringList::new(&zone_refs))
+        .build();
+    let stem = file_name.trim_end_matches(".xml");
+    let preselect = zones
+        .iter()
+        .position(|z| z == stem)
+        .or_else(|| zones.iter().position(|z| z == default_zone))
+        .unwrap_or(0);
+    zone_row.set_selected(preselect as u32);
+    zone_group.add(&zone_row);
+    page.add(&zone_group);
+
+    // --- Rules ---
+    let selection: Rc<RefCell<Vec<(ImportRule, gtk4::CheckButton)>>> =
+        Rc::new(RefCell::new(Vec::new()));
+
+    let add_rule_row = |group: &adw::PreferencesGroup, title: &str, icon: &str, rule| {
+        let check = gtk4::CheckButton::builder()
+            .active(true)
+            .valign(gtk4::Align::Center)
+            .build();
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(title).as_str())
+            .activatable_widget(&check)
+            .build();
+        row.add_prefix(&check);
+        row.add_suffix(&gtk4::Image::from_icon_name(icon));
+        if let ImportRule::Service(name) = &rule {
+            attach_service_tooltip(&row, name);
+        }
+        group.add(&row);
+        selection.borrow_mut().push((rule, check));
+    };
+
+    if !zone_file.services.is_empty() {
+        let services_group = adw::PreferencesGroup::builder()
+            .title(gettext("Services"))
+            .build();
+        for service in &zone_file.services {
+            add_rule_row(
+                &services_group,
+                service,
+                "application-x-addon-symbolic",
+                ImportRule::Service(service.clone()),
+            );
+        }
+        page.add(&services_group);
+    }
+
+    if !zone_file.ports.is_empty() {
+        let ports_group = adw::PreferencesGroup::builder()
+            .title(gettext("Ports"))
+            .build();
+        for (port, protocol) in &zone_file.ports {
+            add_rule_row(
+                &ports_group,
+                &format!("{}/{}", port, protocol),
+                "network-transmit-receive-symbolic",
+                ImportRule::Port(port.clone(), protocol.clone()),
+            );
+        }
+        page.add(&ports_group);
+    }
+
+    if zone_file.rule_count() == 0 {
+        let empty_group = adw::PreferencesGroup::new();
+        let empty_row = adw::ActionRow::builder()
+            .title(gettext("Nothing to import"))
+            .subtitle(gettext("The file opens no services or ports"))
+            .sensitive(false)
+            .build();
+        empty_group.add(&empty_row);
+        page.add(&empty_group);
+    }
+
+    if !zone_file.skipped.is_empty() {
+        let skipped_group = adw::PreferencesGroup::builder()
+            .title(gettext("Not Imported"))
+            .description(gettext(
+                "These parts of the file have no equivalent in Security Center; apply them with firewall-cmd if needed",
+            ))
+            .build();
+        let skipped_row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(&zone_file.skipped.join(", ")).as_str())
+            .build();
+        skipped_row.add_prefix(&gtk4::Image::from_icon_name("dialog-information-symbolic"));
+        skipped_group.add(&skipped_row);
+        page.add(&skipped_group);
+    }
+
+    let import_button = gtk4::Button::builder()
+        .label(gettext("Import"))
+        .css_classes(vec!["suggested-action".to_string(), "pill".to_string()])
+        .halign(gtk4::Align::Center)
+        .margin_top(12)
+        .margin_bottom(12)
+        .sensitive(zone_file.rule_count() > 0 && !zones.is_empty())
+        .build();
+
+    let window_for_import = window.clone();
+    let dialog_for_import = dialog.clone();
+    let zones = zones.to_vec();
+    import_button.connect_clicked(move |_| {
+        let Some(zone) = zones.get(zone_row.selected() as usize).cloned() else {
+            return;
+        };
+        let rules: Vec<ImportRule> = selection
+            .borrow()
+            .iter()
+            .filter(|(_, check)| check.is_active())
+            .map(|(rule, _)| rule.clone())
+            .collect();
+        if rules.is_empty() {
+            return;
+        }
+        dialog_for_import.close();
+        import_rules(&window_for_import, zone, rules);
+    });
+
+    toolbar.set_content(Some(&page));
+    toolbar.add_bottom_bar(&import_button);
+    dialog.set_child(Some(&toolbar));
+    dialog.present(Some(window));
+}
+
+/// Add `rules` to `zone` in the background and report the result.
+fn import_rules(window: &MainWindow, zone: String, rules: Vec<ImportRule>) {
+    let window = window.clone();
+
+    glib::spawn_future_local(async move {
+        let total = rules.len();
+        let zone_for_import = zone.clone();
+        let result = crate::firewall::call(move |client| {
+            client.connect()?;
+            let mut imported = 0;
+            let mut runtime_only = 0;
+            for rule in &rules {
+                let outcome = match rule {
+                    ImportRule::Service(name) => {
+                        client.enable_service(&zone_for_import, name, true)
+                    }
+                    ImportRule::Port(port, protocol) => {
+                        client.add_port(&zone_for_import, port, protocol, true)
+                    }
+                };
+                match outcome {
+                    Ok(outcome) => {
+                        imported += 1;
+                        if outcome.failed() {
+                            runtime_only += 1;
+                        }
+                    }
+                    Err(e) => tracing::warn!("Zone import: {}", e),
+                }
+            }
+            Ok::<_, anyhow::Error>((imported, runtime_only))
+        })
+        .await;
+
+        match result {
+            Ok(Ok((imported, runtime_only))) => {
+                let mut message = gettext("Imported %d of %d rules into '%s'")
+                    .replacen("%d", &imported.to_string(), 1)
+                    .replacen("%d", &total.to_string(), 1)
+                    .replacen("%s", &zone, 1);
+                if runtime_only > 0 {
+                    message = format!(
+                        "{} — {}",
+                        message,
+                        gettext("%d only for this session")
+                            .replace("%d", &runtime_only.to_string())
+                    );
+                }
+                window.show_toast(&message);
+                window.invalidate(Invalidation::ZonesChanged);
+            }
+            Ok(Err(e)) => {
+                window.show_toast(&format!("{}: {}", gettext("Import failed"), e));
+            }
+            Err(_) => {
+                window.show_toast(&gettext("Import failed"));
+            }
+        }
+    });
+}
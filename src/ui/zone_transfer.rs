@@ -0,0 +1,353 @@
+// Security Center - Zone transfer dialog
+// Copyright (C) 2026 Christos Daggas
+// SPDX-License-Identifier: MIT
+
+//! Two zones side by side with their services and ports. The entries
+//! selected on one side are moved or copied to the other with the arrow
+//! buttons, at runtime and permanently; if any change fails, the others
+//! are reverted and both zones stay as they were.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gtk4::glib;
+use gtk4::prelude::*;
+use libadwaita as adw;
+use libadwaita::prelude::*;
+
+use super::app_state::Invalidation;
+use super::service_ports::attach_service_tooltip;
+use super::MainWindow;
+use crate::firewall::{
+    apply_transfer, plan_transfer, FirewallClient, TransferMode, ZoneChange, ZoneRule,
+};
+use crate::i18n::gettext;
+use crate::models::Zone;
+
+/// One side of the dialog.
+struct Pane {
+    zone_dropdown: gtk4::DropDown,
+    list: gtk4::ListBox,
+    /// The rules shown, in list order.
+    rules: RefCell<Vec<ZoneRule>>,
+}
+
+impl Pane {
+    fn new(zone_names: &gtk4::StringList, selected: u32) -> Rc<Self> {
+        let zone_dropdown = gtk4::DropDown::builder()
+            .model(zone_names)
+            .selected(selected)
+            .build();
+        let list = gtk4::ListBox::builder()
+            .selection_mode(gtk4::SelectionMode::Multiple)
+            .css_classes(vec!["boxed-list".to_string()])
+            .valign(gtk4::Align::Start)
+            .build();
+        list.set_placeholder(Some(
+            &gtk4::Label::builder()
+                .label(gettext("No services or ports"))
+                .css_classes(vec!["dim-label".to_string()])
+                .margin_top(12)
+                .margin_bottom(12)
+                .build(),
+        ));
+        Rc::new(Self {
+            zone_dropdown,
+            list,
+            rules: RefCell::new(Vec::new()),
+        })
+    }
+
+    fn widget(&self) -> gtk4::Box {
+        let column = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(12)
+            .hexpand(true)
+            .build();
+        column.append(&self.zone_dropdown);
+        let scrolled = gtk4::ScrolledWindow::builder()
+            .child(&self.list)
+            .hscrollbar_policy(gtk4::PolicyType::Never)
+            .vexpand(true)
+            .build();
+        column.append(&scrolled);
+        column
+    }
+
+    /// Show the rules of `zone`, clearing the selection.
+    fn show(&self, zone: &Zone) {
+        self.list.remove_all();
+        let rules = ZoneRule::of_zone(zone);
+        for rule in &rules {
+            let row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&rule.label()).as_str())
+                .build();
+            let icon = match rule {
+                ZoneRule::Service(name) => {
+                    attach_service_tooltip(&row, name);
+                    "application-x-addon-symbolic"
+                }
+                ZoneRule::Port(..) => "network-transmit-receive-symbolic",
+            };
+            row.add_prefix(&gtk4::Image::from_icon_name(icon));
+            self.list.append(&row);
+        }
+        self.rules.replace(rules);
+    }
+
+    /// The rules selected in the list.
+    fn selected_rules(&self) -> Vec<ZoneRule> {
+        let rules = self.rules.borrow();
+        self.list
+            .selected_rows()
+            .iter()
+            .filter_map(|row| usize::try_from(row.index()).ok())
+            .filter_map(|index| rules.get(index).cloned())
+            .collect()
+    }
+}
+
+/// Shared state of the dialog.
+struct Transfer {
+    window: MainWindow,
+    toasts: adw::ToastOverlay,
+    zones: RefCell<Vec<Zone>>,
+    left: Rc<Pane>,
+    right: Rc<Pane>,
+    move_button: gtk4::ToggleButton,
+    arrows: [gtk4::Button; 2],
+}
+
+impl Transfer {
+    fn zone(&self, pane: &Pane) -> Option<Zone> {
+        self.zones
+            .borrow()
+            .get(pane.zone_dropdown.selected() as usize)
+            .cloned()
+    }
+
+    fn refresh(&self) {
+        for pane in [&self.left, &self.right] {
+            if let Some(zone) = self.zone(pane) {
+                pane.show(&zone);
+            }
+        }
+    }
+
+    /// Move or copy the rules selected in `from` to the zone of `to`.
+    fn run(self: &Rc<Self>, from: &Pane, to: &Pane) {
+        let (Some(source), Some(destination)) = (self.zone(from), self.zone(to)) else {
+            return;
+        };
+        if source.name == destination.name {
+            self.toast(&gettext("Choose two different zones"));
+            return;
+        }
+        let rules = from.selected_rules();
+        if rules.is_empty() {
+            self.toast(&gettext("Select the services and ports to transfer"));
+            return;
+        }
+        let mode = if self.move_button.is_active() {
+            TransferMode::Move
+        } else {
+            TransferMode::Copy
+        };
+        let changes = plan_transfer(&source, &destination, &rules, mode);
+        if changes.is_empty() {
+            self.toast(&gettext("Zone '%s' already has them").replace("%s", &destination.name));
+            return;
+        }
+
+        self.set_busy(true);
+        let transfer = self.clone();
+        let count = rules.len();
+        glib::spawn_future_local(async move {
+            let changes_for_apply = changes.clone();
+            let result = crate::firewall::call(move |client| {
+                client.connect()?;
+                apply_transfer(client, &changes_for_apply)
+            })
+            .await;
+            transfer.set_busy(false);
+
+            match result {
+                Ok(Ok(())) => {
+                    transfer.applied(&changes);
+                    let message = match mode {
+                        TransferMode::Move => gettext("Moved %d rules to '%s'"),
+                        TransferMode::Copy => gettext("Copied %d rules to '%s'"),
+                    };
+                    let message = message.replacen("%d", &count.to_string(), 1).replacen(
+                        "%s",
+                        &destination.name,
+                        1,
+                    );
+                    transfer.toast(&message);
+                    transfer.window.invalidate(Invalidation::ZonesChanged);
+                }
+                Ok(Err(e)) => {
+                    transfer.toast(&format!(
+                        "{}: {} — {}",
+                        gettext("Transfer failed"),
+                        e,
+                        gettext("no changes were kept")
+                    ));
+                    transfer.window.invalidate(Invalidation::ZonesChanged);
+                }
+                Err(_) => transfer.toast(&gettext("Transfer failed")),
+            }
+        });
+    }
+
+    /// Show `changes` in both lists without reading the zones again.
+    fn applied(&self, changes: &[ZoneChange]) {
+        for zone in self.zones.borrow_mut().iter_mut() {
+            for change in changes {
+                change.apply_to(zone);
+            }
+        }
+        self.refresh();
+    }
+
+    fn set_busy(&self, busy: bool) {
+        for button in &self.arrows {
+            button.set_sensitive(!busy);
+        }
+    }
+
+    fn toast(&self, message: &str) {
+        self.toasts.add_toast(adw::Toast::new(message));
+    }
+}
+
+/// Build and present the transfer dialog for `zones`, anchored to `window`,
+/// with `first` on the left.
+pub fn present_zone_transfer(window: &MainWindow, zones: Vec<Zone>, first: &str) {
+    let dialog = adw::Dialog::builder()
+        .title(gettext("Move Rules Between Zones"))
+        .content_width(760)
+        .content_height(560)
+        .build();
+
+    let toolbar = adw::ToolbarView::new();
+    toolbar.add_top_bar(&adw::HeaderBar::new());
+
+    let names: Vec<&str> = zones.iter().map(|zone| zone.name.as_str()).collect();
+    let zone_names = gtk4::StringList::new(&names);
+    let left_index = zones
+        .iter()
+        .position(|zone| zone.name == first)
+        .unwrap_or(0);
+    let right_index = if zones.len() > 1 && left_index == 0 {
+        1
+    } else {
+        0
+    };
+    let left = Pane::new(&zone_names, left_index as u32);
+    let right = Pane::new(&zone_names, right_index as u32);
+
+    // Move or copy, then the arrows between the panes
+    let move_button = gtk4::ToggleButton::builder()
+        .label(gettext("Move"))
+        .active(true)
+        .tooltip_text(gettext("Remove the entries from the zone they come from"))
+        .build();
+    let copy_button = gtk4::ToggleButton::builder()
+        .label(gettext("Copy"))
+        .group(&move_button)
+        .tooltip_text(gettext("Keep the entries in the zone they come from too"))
+        .build();
+    let mode_box = gtk4::Box::builder()
+        .css_classes(vec!["linked".to_string()])
+        .build();
+    mode_box.append(&move_button);
+    mode_box.append(&copy_button);
+
+    let to_right = gtk4::Button::builder()
+        .icon_name("go-next-symbolic")
+        .tooltip_text(gettext(
+            "Transfer the selected entries to the zone on the right",
+        ))
+        .css_classes(vec!["circular".to_string()])
+        .halign(gtk4::Align::Center)
+        .build();
+    let to_left = gtk4::Button::builder()
+        .icon_name("go-previous-symbolic")
+        .tooltip_text(gettext(
+            "Transfer the selected entries to the zone on the left",
+        ))
+        .css_classes(vec!["circular".to_string()])
+        .halign(gtk4::Align::Center)
+        .build();
+
+    let controls = gtk4::Box::builder()
+        .orientation(gtk4::Orientation::Vertical)
+        .spacing(12)
+        .valign(gtk4::Align::Center)
+        .build();
+    controls.append(&mode_box);
+    controls.append(&to_right);
+    controls.append(&to_left);
+
+    let panes = gtk4::Box::builder()
+        .orientation(gtk4::Orientation::Horizontal)
+        .spacing(12)
+        .margin_top(12)
+        .margin_bottom(12)
+        .margin_start(12)
+        .margin_end(12)
+        .build();
+    panes.append(&left.widget());
+    panes.append(&controls);
+    panes.append(&right.widget());
+
+    let toasts = adw::ToastOverlay::new();
+    toasts.set_child(Some(&panes));
+
+    let transfer = Rc::new(Transfer {
+        window: window.clone(),
+        toasts: toasts.clone(),
+        zones: RefCell::new(zones),
+        left: left.clone(),
+        right: right.clone(),
+        move_button,
+        arrows: [to_right.clone(), to_left.clone()],
+    });
+    transfer.refresh();
+
+    for pane in [&left, &right] {
+        let transfer = Rc::downgrade(&transfer);
+        let pane_weak = Rc::downgrade(pane);
+        pane.zone_dropdown.connect_selected_notify(move |_| {
+            if let (Some(transfer), Some(pane)) = (transfer.upgrade(), pane_weak.upgrade()) {
+                if let Some(zone) = transfer.zone(&pane) {
+                    pane.show(&zone);
+                }
+            }
+        });
+    }
+
+    // The buttons are part of the state, so they only hold it weakly
+    let transfer_for_right = Rc::downgrade(&transfer);
+    to_right.connect_clicked(move |_| {
+        if let Some(t) = transfer_for_right.upgrade() {
+            t.run(&t.left, &t.right);
+        }
+    });
+    let transfer_for_left = Rc::downgrade(&transfer);
+    to_left.connect_clicked(move |_| {
+        if let Some(t) = transfer_for_left.upgrade() {
+            t.run(&t.right, &t.left);
+        }
+    });
+
+    // Keep the state for as long as the dialog is open
+    dialog.connect_closed(move |_| {
+        let _ = &transfer;
+    });
+
+    toolbar.set_content(Some(&toasts));
+    dialog.set_child(Some(&toolbar));
+    dialog.present(Some(window));
+}
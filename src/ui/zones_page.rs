@@ -4,18 +4,23 @@
 
 //! Zones management page.
 
-use std::cell::RefCell;
+use std::cell::{Cell, OnceCell, RefCell};
 use std::rc::Rc;
 
-use gtk4::glib;
 use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
+use gtk4::{gio, glib};
 use libadwaita as adw;
 use libadwaita::prelude::*;
 
-use crate::firewall::FirewallClient;
+use super::app_state::Invalidation;
+use super::density::{self, Role};
+use super::service_ports::attach_services_tooltip;
+use super::widgets::ZoneTopology;
+use crate::admin::{is_risky_default_zone, zone_suggestions, Undo, ZoneServiceRule};
+use crate::firewall::{is_builtin_zone, FirewallClient};
 use crate::i18n::gettext;
-use crate::models::Zone;
+use crate::models::{sync_store, ModelObject, Zone, ZoneObject};
 
 glib::wrapper! {
     /// Zones page showing firewall zones.
@@ -48,11 +53,8 @@ impl ZonesPage {
         let header_box = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Horizontal)
             .spacing(12)
-            .margin_start(24)
-            .margin_end(24)
-            .margin_top(24)
-            .margin_bottom(12)
             .build();
+        density::track(&header_box, Role::PageHeader);
 
         let title_box = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Vertical)
@@ -75,6 +77,30 @@ impl ZonesPage {
         title_box.append(&title);
         title_box.append(&subtitle);
         header_box.append(&title_box);
+
+        let transfer_button = gtk4::Button::builder()
+            .label(gettext("Move Rules"))
+            .tooltip_text(gettext("Move or copy services and ports between two zones"))
+            .valign(gtk4::Align::Center)
+            .build();
+
+        let page = self.clone();
+        transfer_button.connect_clicked(move |_| {
+            page.show_transfer_dialog();
+        });
+        header_box.append(&transfer_button);
+
+        let add_button = gtk4::Button::builder()
+            .label(gettext("New Zone"))
+            .css_classes(vec!["suggested-action".to_string()])
+            .valign(gtk4::Align::Center)
+            .build();
+
+        let page = self.clone();
+        add_button.connect_clicked(move |_| {
+            page.show_create_dialog();
+        });
+        header_box.append(&add_button);
         self.append(&header_box);
 
         // Scrolled container
@@ -88,15 +114,30 @@ impl ZonesPage {
         // Main content - no clamp for full width
         let content = gtk4::Box::builder()
             .orientation(gtk4::Orientation::Vertical)
-            .spacing(24)
-            .margin_top(24)
-            .margin_bottom(24)
-            .margin_start(24)
-            .margin_end(24)
             .hexpand(true)
             .build();
+        density::track(
+            &content,
+            Role::Content {
+                margin: 24,
+                spacing: 24,
+            },
+        );
         scrolled.set_child(Some(&content));
 
+        // Map of interfaces, zones and what each lets through
+        content.append(&Self::create_section_header(
+            "network-wired-symbolic",
+            &gettext("Topology"),
+        ));
+        let topology = ZoneTopology::new();
+        let topology_card = gtk4::Frame::builder()
+            .child(&topology)
+            .css_classes(vec!["card".to_string()])
+            .build();
+        content.append(&topology_card);
+        imp.topology.replace(Some(topology));
+
         // Active zones group
         content.append(&Self::create_section_header(
             "network-workgroup-symbolic",
@@ -131,27 +172,41 @@ impl ZonesPage {
         }
     }
 
-    /// Request a refresh from the main window.
-    fn request_refresh(&self) {
+    fn main_window(&self) -> Option<super::MainWindow> {
+        self.root()
+            .and_then(|root| root.downcast::<gtk4::Window>().ok())
+            .and_then(|window| window.downcast::<super::MainWindow>().ok())
+    }
+
+    /// Have the main window refresh what shows `change`.
+    fn request_refresh(&self, change: Invalidation) {
         if let Some(root) = self.root() {
             if let Some(window) = root.downcast_ref::<gtk4::Window>() {
                 if let Some(main_window) = window.downcast_ref::<super::MainWindow>() {
-                    main_window.refresh_data();
+                    main_window.invalidate(change);
                 }
             }
         }
     }
 
-    /// Set a zone as the default zone.
+    /// Set a zone as the default zone. A lockdown zone such as drop is
+    /// set back to the previous default unless the user keeps it in time.
     fn set_default_zone(&self, zone_name: &str) {
         let zone = zone_name.to_string();
         let page = self.clone();
+        let previous = self
+            .zone_store()
+            .iter::<ZoneObject>()
+            .filter_map(Result::ok)
+            .find(|zone| zone.is_default())
+            .map(|zone| zone.name());
+        let undo = previous
+            .filter(|_| is_risky_default_zone(&zone))
+            .map(Undo::DefaultZone);
 
         glib::spawn_future_local(async move {
             let zone_clone = zone.clone();
-            let result = gtk4::gio::spawn_blocking(move || {
-                // Create a new client for this operation
-                let mut client = crate::firewall::FirewallClient::new();
+            let result = crate::firewall::call(move |client| {
                 if client.connect().is_err() {
                     return Err(anyhow::anyhow!("Failed to connect"));
                 }
@@ -162,7 +217,10 @@ impl ZonesPage {
             match result {
                 Ok(Ok(())) => {
                     page.show_toast(&gettext("Default zone set to '%s'").replace("%s", &zone));
-                    page.request_refresh();
+                    if let (Some(undo), Some(window)) = (undo, page.main_window()) {
+                        window.revert_unless_kept(undo);
+                    }
+                    page.request_refresh(Invalidation::ZonesChanged);
                 }
                 Ok(Err(e)) => {
                     page.show_toast(&format!("{}: {}", gettext("Failed to set default zone"), e));
@@ -174,18 +232,185 @@ impl ZonesPage {
         });
     }
 
+    /// Read the zone's settings and the known ICMP types, then open the
+    /// zone editor.
+    fn edit_zone(&self, zone_name: &str, button: &gtk4::Button) {
+        let zone = zone_name.to_string();
+        let page = self.clone();
+        let button = button.clone();
+        button.set_sensitive(false);
+
+        glib::spawn_future_local(async move {
+            let zone_clone = zone.clone();
+            let result = crate::firewall::call(move |client| {
+                if client.connect().is_err() {
+                    return Err(anyhow::anyhow!("Not connected to firewalld"));
+                }
+                let settings = client.get_zone_settings(&zone_clone)?;
+                let icmp_types = client.get_icmp_types().unwrap_or_default();
+                Ok((settings, icmp_types))
+            })
+            .await;
+            button.set_sensitive(true);
+
+            match result {
+                Ok(Ok((settings, icmp_types))) => {
+                    let window = page
+                        .root()
+                        .and_then(|root| root.downcast::<gtk4::Window>().ok())
+                        .and_then(|window| window.downcast::<super::MainWindow>().ok());
+                    if let Some(window) = window {
+                        super::zone_editor::present_zone_editor(
+                            &window,
+                            &zone,
+                            settings,
+                            &icmp_types,
+                        );
+                    }
+                }
+                Ok(Err(e)) => {
+                    page.show_toast(&format!("{}: {}", gettext("Failed to read zone"), e));
+                }
+                Err(_) => {
+                    page.show_toast(&gettext("Failed to read zone"));
+                }
+            }
+        });
+    }
+
+    /// Open the dialog creating a zone.
+    fn show_create_dialog(&self) {
+        let existing: Vec<String> = self
+            .zone_store()
+            .iter::<ZoneObject>()
+            .filter_map(Result::ok)
+            .map(|zone| zone.model().name)
+            .collect();
+        let window = self
+            .root()
+            .and_then(|root| root.downcast::<gtk4::Window>().ok())
+            .and_then(|window| window.downcast::<super::MainWindow>().ok());
+        if let Some(window) = window {
+            super::zone_editor::present_zone_creator(&window, &existing);
+        }
+    }
+
+    /// Open the dialog moving rules between zones, with the default zone on
+    /// the left.
+    fn show_transfer_dialog(&self) {
+        let zones: Vec<Zone> = self
+            .zone_store()
+            .iter::<ZoneObject>()
+            .filter_map(Result::ok)
+            .map(|zone| zone.model())
+            .collect();
+        if zones.len() < 2 {
+            self.show_toast(&gettext("Moving rules needs at least two zones"));
+            return;
+        }
+        let first = zones
+            .iter()
+            .find(|zone| zone.is_default)
+            .map(|zone| zone.name.clone())
+            .unwrap_or_default();
+        if let Some(window) = self.main_window() {
+            super::zone_transfer::present_zone_transfer(&window, zones, &first);
+        }
+    }
+
+    /// Ask before deleting a zone, then delete it and reload firewalld.
+    fn confirm_delete_zone(&self, zone_name: &str, button: &gtk4::Button) {
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("Delete zone '%s'?").replace("%s", zone_name))
+            .body(gettext(
+                "The zone and its rules are removed from the permanent configuration. \
+                 Reloading firewalld to apply this discards changes made for this session only.",
+            ))
+            .build();
+        dialog.add_response("cancel", "_Cancel");
+        dialog.add_response("delete", "_Delete");
+        dialog.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
+        dialog.set_default_response(Some("cancel"));
+
+        let page = self.clone();
+        let zone = zone_name.to_string();
+        let button = button.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response == "delete" {
+                button.set_sensitive(false);
+                page.delete_zone(&zone, &button);
+            }
+        });
+
+        if let Some(root) = self.root() {
+            if let Some(window) = root.downcast_ref::<gtk4::Window>() {
+                dialog.present(Some(window));
+            }
+        }
+    }
+
+    /// Delete a zone the user created and reload firewalld.
+    fn delete_zone(&self, zone_name: &str, button: &gtk4::Button) {
+        let zone = zone_name.to_string();
+        let page = self.clone();
+        let button = button.clone();
+
+        glib::spawn_future_local(async move {
+            let zone_clone = zone.clone();
+            let result = crate::firewall::call(move |client| {
+                if client.connect().is_err() {
+                    return Err(anyhow::anyhow!("Not connected to firewalld"));
+                }
+                client.delete_zone(&zone_clone)?;
+                client.reload()
+            })
+            .await;
+
+            match result {
+                Ok(Ok(())) => {
+                    page.show_toast(&gettext("Zone '%s' deleted").replace("%s", &zone));
+                    page.request_refresh(Invalidation::ZonesChanged);
+                }
+                Ok(Err(e)) => {
+                    button.set_sensitive(true);
+                    page.show_toast(&format!("{}: {}", gettext("Failed to delete zone"), e));
+                }
+                Err(_) => {
+                    button.set_sensitive(true);
+                    page.show_toast(&gettext("Failed to delete zone"));
+                }
+            }
+        });
+    }
+
+    /// The zones shown, as a list of [`ZoneObject`]s that stays the same
+    /// across refreshes.
+    pub fn zone_store(&self) -> gio::ListStore {
+        self.imp()
+            .zone_store
+            .get_or_init(gio::ListStore::new::<ZoneObject>)
+            .clone()
+    }
+
     /// Update the page with zone data.
     pub fn set_zones(&self, zones: &[Zone]) {
         let imp = self.imp();
+        let store = self.zone_store();
+        sync_store::<ZoneObject>(&store, zones);
+
+        if let Some(topology) = imp.topology.borrow().as_ref() {
+            topology.set_zones(zones);
+        }
 
         // Clear ALL existing rows from both groups using helper
         Self::clear_preferences_group(imp.active_group.borrow().as_ref());
         Self::clear_preferences_group(imp.available_group.borrow().as_ref());
 
         // Separate active (with interfaces/sources) vs available zones
-        let (active, available): (Vec<_>, Vec<_>) = zones
-            .iter()
-            .partition(|z| !z.interfaces.is_empty() || !z.sources.is_empty());
+        let (active, available): (Vec<_>, Vec<_>) = store
+            .iter::<ZoneObject>()
+            .filter_map(Result::ok)
+            .partition(|z| !z.interfaces().is_empty() || !z.sources().is_empty());
 
         // Add active zones
         if let Some(group) = imp.active_group.borrow().as_ref() {
@@ -245,11 +470,29 @@ impl ZonesPage {
     }
 
     /// Create a row for a zone (new working version).
-    fn create_zone_row_new(&self, zone: &Zone) -> adw::ExpanderRow {
-        let row = adw::ExpanderRow::builder()
-            .title(&zone.name)
-            .subtitle(&zone.description)
+    fn create_zone_row_new(&self, object: &ZoneObject) -> adw::ExpanderRow {
+        let row = adw::ExpanderRow::new();
+        object
+            .bind_property("name", &row, "title")
+            .sync_create()
             .build();
+        let zone = object.model();
+        // What the zone applies to, below its description
+        let bindings: Vec<&str> = zone
+            .interfaces
+            .iter()
+            .chain(&zone.sources)
+            .map(String::as_str)
+            .collect();
+        if bindings.is_empty() {
+            row.set_subtitle(&zone.description);
+        } else {
+            row.set_subtitle(&format!(
+                "{}\n{}",
+                zone.description,
+                gettext("Bound to %s").replace("%s", &bindings.join(", "))
+            ));
+        }
 
         // Icon based on zone type
         let icon = match zone.name.as_str() {
@@ -261,6 +504,30 @@ impl ZonesPage {
         };
         row.add_prefix(&gtk4::Image::from_icon_name(icon));
 
+        // Live counts of what the zone lets through
+        let counts: Vec<String> = [
+            (zone.services.len(), "1 service", "%d services"),
+            (zone.ports.len(), "1 port", "%d ports"),
+            (zone.rich_rules.len(), "1 rich rule", "%d rich rules"),
+        ]
+        .into_iter()
+        .filter(|(count, _, _)| *count > 0)
+        .map(|(count, one, many)| match count {
+            1 => gettext(one),
+            _ => gettext(many).replace("%d", &count.to_string()),
+        })
+        .collect();
+        let counts_label = gtk4::Label::builder()
+            .label(if counts.is_empty() {
+                gettext("No rules")
+            } else {
+                counts.join(" • ")
+            })
+            .css_classes(["caption", "dim-label"])
+            .valign(gtk4::Align::Center)
+            .build();
+        row.add_suffix(&counts_label);
+
         // Default badge if this is the default zone
         if zone.is_default {
             let badge = gtk4::Label::builder()
@@ -284,12 +551,64 @@ impl ZonesPage {
             row.add_suffix(&button);
         }
 
+        // Edit button
+        let zone_name = zone.name.clone();
+        let page = self.clone();
+        let edit_button = gtk4::Button::builder()
+            .icon_name("document-edit-symbolic")
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat"])
+            .tooltip_text(gettext("Edit zone settings"))
+            .build();
+        edit_button.connect_clicked(move |button| {
+            page.edit_zone(&zone_name, button);
+        });
+        row.add_suffix(&edit_button);
+
+        // Zones the user created can be deleted while nothing uses them
+        if !is_builtin_zone(&zone.name) && !zone.is_in_use() {
+            let zone_name = zone.name.clone();
+            let page = self.clone();
+            let delete_button = gtk4::Button::builder()
+                .icon_name("user-trash-symbolic")
+                .valign(gtk4::Align::Center)
+                .css_classes(["flat", "error"])
+                .tooltip_text(gettext("Delete this zone"))
+                .build();
+            delete_button.connect_clicked(move |button| {
+                page.confirm_delete_zone(&zone_name, button);
+            });
+            row.add_suffix(&delete_button);
+        }
+
+        // Baseline suggestions for zones that carry traffic
+        let suggestions = if zone.is_in_use() {
+            zone_suggestions(&zone.name, &zone.services)
+        } else {
+            Vec::new()
+        };
+        if !suggestions.is_empty() {
+            let severity = suggestions[0].severity;
+            let badge = gtk4::Image::builder()
+                .icon_name(severity.icon())
+                .css_classes([severity.css_class()])
+                .valign(gtk4::Align::Center)
+                .tooltip_text(
+                    gettext("%d risky services allowed")
+                        .replace("%d", &suggestions.len().to_string()),
+                )
+                .build();
+            row.add_suffix(&badge);
+            row.add_row(&self.create_suggestions_row(&zone.name, &suggestions));
+        }
+
         // Sub-rows for zone details
         if !zone.services.is_empty() {
             let services_row = adw::ActionRow::builder()
                 .title(gettext("Services"))
                 .subtitle(zone.services.join(", "))
                 .build();
+            attach_services_tooltip(&services_row, &zone.services);
             row.add_row(&services_row);
         }
 
@@ -301,6 +620,15 @@ impl ZonesPage {
             row.add_row(&ports_row);
         }
 
+        if !zone.rich_rules.is_empty() {
+            let rules_row = adw::ActionRow::builder()
+                .title(gettext("Rich Rules"))
+                .subtitle(zone.rich_rules.join("\n"))
+                .subtitle_selectable(true)
+                .build();
+            row.add_row(&rules_row);
+        }
+
         if !zone.interfaces.is_empty() {
             let ifaces_row = adw::ActionRow::builder()
                 .title(gettext("Interfaces"))
@@ -317,9 +645,357 @@ impl ZonesPage {
             row.add_row(&sources_row);
         }
 
+        // ICMP filtering, built the first time the zone is expanded
+        let page = self.clone();
+        let built = Cell::new(false);
+        row.connect_expanded_notify(move |row| {
+            if row.is_expanded() && !built.replace(true) {
+                page.add_icmp_row(row, &zone);
+            }
+        });
+
         row
     }
 
+    /// Add the ICMP filtering row to a zone's row, reading the ICMP types
+    /// firewalld knows the first time.
+    fn add_icmp_row(&self, row: &adw::ExpanderRow, zone: &Zone) {
+        if !self.imp().icmp_types.borrow().is_empty() {
+            row.add_row(&self.create_icmp_row(zone));
+            return;
+        }
+
+        let page = self.clone();
+        let row = row.clone();
+        let zone = zone.clone();
+        glib::spawn_future_local(async move {
+            let result = crate::firewall::call(move |client| {
+                if client.connect().is_err() {
+                    return Err(anyhow::anyhow!("Not connected to firewalld"));
+                }
+                client.get_icmp_types()
+            })
+            .await;
+
+            match result {
+                Ok(Ok(types)) => {
+                    page.imp().icmp_types.replace(types);
+                    row.add_row(&page.create_icmp_row(&zone));
+                }
+                Ok(Err(e)) => {
+                    page.show_toast(&format!("{}: {}", gettext("Failed to read ICMP types"), e));
+                }
+                Err(_) => {
+                    page.show_toast(&gettext("Failed to read ICMP types"));
+                }
+            }
+        });
+    }
+
+    /// Row with a toggle per ICMP type and the zone's block inversion.
+    /// Changes apply at once, for this session and permanently.
+    fn create_icmp_row(&self, zone: &Zone) -> adw::PreferencesRow {
+        let content = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(8)
+            .margin_top(10)
+            .margin_bottom(10)
+            .margin_start(12)
+            .margin_end(12)
+            .build();
+
+        let header = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(12)
+            .build();
+        header.append(
+            &gtk4::Label::builder()
+                .label(gettext("ICMP Filtering"))
+                .css_classes(["heading"])
+                .halign(gtk4::Align::Start)
+                .hexpand(true)
+                .build(),
+        );
+        header.append(
+            &gtk4::Label::builder()
+                .label(gettext("Invert"))
+                .css_classes(["dim-label"])
+                .build(),
+        );
+        let inversion = gtk4::Switch::builder()
+            .active(zone.icmp_block_inversion)
+            .valign(gtk4::Align::Center)
+            .tooltip_text(gettext("Let only the selected types through"))
+            .build();
+        header.append(&inversion);
+        content.append(&header);
+
+        let explanation = |inverted: bool| {
+            if inverted {
+                gettext("Only the selected ICMP types are let through; all others are blocked.")
+            } else {
+                gettext("The selected ICMP types are blocked. Keep echo-request to answer ping.")
+            }
+        };
+        let description = gtk4::Label::builder()
+            .label(explanation(zone.icmp_block_inversion))
+            .css_classes(["dim-label", "caption"])
+            .halign(gtk4::Align::Start)
+            .wrap(true)
+            .xalign(0.0)
+            .build();
+        content.append(&description);
+
+        let page = self.clone();
+        let zone_name = zone.name.clone();
+        inversion.connect_state_set(move |switch, state| {
+            description.set_label(&explanation(state));
+            page.set_icmp_block_inversion(&zone_name, state, switch);
+            glib::Propagation::Proceed
+        });
+
+        // Types firewalld knows, plus any blocked type it no longer lists
+        let mut types = self.imp().icmp_types.borrow().clone();
+        for blocked in &zone.icmp_blocks {
+            if !types.contains(blocked) {
+                types.push(blocked.clone());
+            }
+        }
+        types.sort();
+
+        let toggles = gtk4::FlowBox::builder()
+            .selection_mode(gtk4::SelectionMode::None)
+            .column_spacing(6)
+            .row_spacing(6)
+            .max_children_per_line(8)
+            .build();
+        for icmp_type in types {
+            let toggle = gtk4::ToggleButton::builder()
+                .label(&icmp_type)
+                .active(zone.icmp_blocks.contains(&icmp_type))
+                .build();
+            let page = self.clone();
+            let zone_name = zone.name.clone();
+            toggle.connect_toggled(move |toggle| {
+                page.set_icmp_block(&zone_name, &icmp_type, toggle.is_active(), toggle);
+            });
+            toggles.insert(&toggle, -1);
+        }
+        content.append(&toggles);
+
+        let row = adw::PreferencesRow::builder().activatable(false).build();
+        row.set_child(Some(&content));
+        row
+    }
+
+    /// Block or unblock an ICMP type in a zone. On failure the page is
+    /// refreshed, which puts the toggle back.
+    fn set_icmp_block(
+        &self,
+        zone: &str,
+        icmp_type: &str,
+        blocked: bool,
+        toggle: &gtk4::ToggleButton,
+    ) {
+        let zone = zone.to_string();
+        let icmp_type = icmp_type.to_string();
+        let page = self.clone();
+        let toggle = toggle.clone();
+        toggle.set_sensitive(false);
+
+        glib::spawn_future_local(async move {
+            let (zone_clone, type_clone) = (zone.clone(), icmp_type.clone());
+            let result = crate::firewall::call(move |client| {
+                if client.connect().is_err() {
+                    return Err(anyhow::anyhow!("Not connected to firewalld"));
+                }
+                if blocked {
+                    client.add_icmp_block(&zone_clone, &type_clone, true)
+                } else {
+                    client.remove_icmp_block(&zone_clone, &type_clone, true)
+                }
+            })
+            .await;
+            toggle.set_sensitive(true);
+
+            match result {
+                Ok(Ok(outcome)) => {
+                    if outcome.failed() {
+                        page.show_toast(&format!(
+                            "ICMP type {} changed in zone '{}' for this session only — saving permanently failed",
+                            icmp_type, zone
+                        ));
+                    }
+                }
+                Ok(Err(e)) => {
+                    page.show_toast(&format!(
+                        "{}: {}",
+                        gettext("Failed to change ICMP block"),
+                        e
+                    ));
+                    page.request_refresh(Invalidation::ZonesChanged);
+                }
+                Err(_) => {
+                    page.show_toast(&gettext("Failed to change ICMP block"));
+                    page.request_refresh(Invalidation::ZonesChanged);
+                }
+            }
+        });
+    }
+
+    /// Turn a zone's ICMP block inversion on or off.
+    fn set_icmp_block_inversion(&self, zone: &str, enabled: bool, switch: &gtk4::Switch) {
+        let zone = zone.to_string();
+        let page = self.clone();
+        let switch = switch.clone();
+        switch.set_sensitive(false);
+
+        glib::spawn_future_local(async move {
+            let zone_clone = zone.clone();
+            let result = crate::firewall::call(move |client| {
+                if client.connect().is_err() {
+                    return Err(anyhow::anyhow!("Not connected to firewalld"));
+                }
+                client.set_icmp_block_inversion(&zone_clone, enabled, true)
+            })
+            .await;
+            switch.set_sensitive(true);
+
+            match result {
+                Ok(Ok(outcome)) => {
+                    if outcome.failed() {
+                        page.show_toast(&format!(
+                            "ICMP block inversion of zone '{}' changed for this session only — saving permanently failed",
+                            zone
+                        ));
+                    }
+                }
+                Ok(Err(e)) => {
+                    page.show_toast(&format!(
+                        "{}: {}",
+                        gettext("Failed to change ICMP block inversion"),
+                        e
+                    ));
+                    page.request_refresh(Invalidation::ZonesChanged);
+                }
+                Err(_) => {
+                    page.show_toast(&gettext("Failed to change ICMP block inversion"));
+                    page.request_refresh(Invalidation::ZonesChanged);
+                }
+            }
+        });
+    }
+
+    /// Row of chips, one per risky service, each removing its service from
+    /// the zone when clicked.
+    fn create_suggestions_row(
+        &self,
+        zone_name: &str,
+        suggestions: &[&'static ZoneServiceRule],
+    ) -> adw::PreferencesRow {
+        let content = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(8)
+            .margin_top(10)
+            .margin_bottom(10)
+            .margin_start(12)
+            .margin_end(12)
+            .build();
+        content.append(
+            &gtk4::Label::builder()
+                .label(gettext("Suggested removals"))
+                .css_classes(["heading"])
+                .halign(gtk4::Align::Start)
+                .build(),
+        );
+        content.append(
+            &gtk4::Label::builder()
+                .label(gettext(
+                    "These services should not be allowed in this zone. Click one to remove it.",
+                ))
+                .css_classes(["dim-label", "caption"])
+                .halign(gtk4::Align::Start)
+                .wrap(true)
+                .xalign(0.0)
+                .build(),
+        );
+
+        let chips = gtk4::FlowBox::builder()
+            .selection_mode(gtk4::SelectionMode::None)
+            .column_spacing(6)
+            .row_spacing(6)
+            .max_children_per_line(8)
+            .build();
+        for rule in suggestions {
+            let chip_content = gtk4::Box::builder()
+                .orientation(gtk4::Orientation::Horizontal)
+                .spacing(4)
+                .build();
+            chip_content.append(&gtk4::Label::new(Some(rule.service)));
+            chip_content.append(&gtk4::Image::from_icon_name("window-close-symbolic"));
+            let chip = gtk4::Button::builder()
+                .child(&chip_content)
+                .css_classes(["suggestion-chip", rule.severity.css_class()])
+                .tooltip_text(format!(
+                    "{} ({})",
+                    gettext(rule.reason),
+                    gettext(rule.severity.label())
+                ))
+                .build();
+            let page = self.clone();
+            let zone = zone_name.to_string();
+            let service = rule.service;
+            chip.connect_clicked(move |chip| {
+                chip.set_sensitive(false);
+                page.remove_risky_service(&zone, service, chip);
+            });
+            chips.insert(&chip, -1);
+        }
+        content.append(&chips);
+
+        let row = adw::PreferencesRow::builder().activatable(false).build();
+        row.set_child(Some(&content));
+        row
+    }
+
+    /// Remove a service flagged by the zone baseline.
+    fn remove_risky_service(&self, zone: &str, service: &'static str, chip: &gtk4::Button) {
+        let zone = zone.to_string();
+        let page = self.clone();
+        let chip = chip.clone();
+
+        glib::spawn_future_local(async move {
+            let zone_clone = zone.clone();
+            let result = crate::firewall::call(move |client| {
+                if client.connect().is_err() {
+                    return Err(anyhow::anyhow!("Not connected to firewalld"));
+                }
+                client.disable_service(&zone_clone, service, true)
+            })
+            .await;
+
+            match result {
+                Ok(Ok(outcome)) => {
+                    let message = if outcome.failed() {
+                        gettext("Removed %s from zone '%s' for this session only — saving permanently failed")
+                    } else {
+                        gettext("Removed %s from zone '%s'")
+                    };
+                    page.show_toast(&message.replacen("%s", service, 1).replacen("%s", &zone, 1));
+                    page.request_refresh(Invalidation::ServicesChanged);
+                }
+                Ok(Err(e)) => {
+                    chip.set_sensitive(true);
+                    page.show_toast(&format!("{}: {}", gettext("Failed to remove service"), e));
+                }
+                Err(_) => {
+                    chip.set_sensitive(true);
+                    page.show_toast(&gettext("Failed to remove service"));
+                }
+            }
+        });
+    }
+
     /// Create a section header with icon on the left.
     fn create_section_header(icon_name: &str, title: &str) -> gtk4::Box {
         let header = gtk4::Box::builder()
@@ -358,7 +1034,11 @@ mod imp {
     pub struct ZonesPage {
         pub active_group: RefCell<Option<adw::PreferencesGroup>>,
         pub available_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub topology: RefCell<Option<ZoneTopology>>,
         pub client: RefCell<Option<Rc<RefCell<FirewallClient>>>>,
+        pub zone_store: OnceCell<gio::ListStore>,
+        /// ICMP types firewalld knows, read when first needed.
+        pub icmp_types: RefCell<Vec<String>>,
     }
 
     #[glib::object_subclass]
@@ -51,6 +51,26 @@ pub fn format_port_spec(start: u16, end: u16) -> String {
     }
 }
 
+/// Parse a list of ports with their protocol, such as
+/// "8080/tcp, 6000-6100/udp", separated by commas or whitespace. Returns
+/// `None` when the list is empty or an entry is invalid.
+pub fn parse_port_list(s: &str) -> Option<Vec<(String, String)>> {
+    let mut ports = Vec::new();
+    for entry in s.split(|c: char| c == ',' || c.is_whitespace()) {
+        if entry.is_empty() {
+            continue;
+        }
+        let (spec, protocol) = entry.split_once('/')?;
+        let (start, end) = parse_port_spec(spec)?;
+        let protocol = validate_protocol(protocol)?;
+        let port = (format_port_spec(start, end), protocol.to_string());
+        if !ports.contains(&port) {
+            ports.push(port);
+        }
+    }
+    (!ports.is_empty()).then_some(ports)
+}
+
 /// Validate and sanitize a user-provided port name.
 /// Returns `Some(String)` if valid, `None` otherwise.
 pub fn validate_port_name(name: &str) -> Option<String> {
@@ -79,6 +99,10 @@ pub fn validate_systemctl_action(action: &str) -> Result<()> {
         "restart",
         "enable",
         "disable",
+        "reload",
+        "reload-or-restart",
+        "mask",
+        "unmask",
         "daemon-reload",
     ];
     if ALLOWED.contains(&action) {
@@ -137,6 +161,100 @@ pub fn validate_zone_name(name: &str) -> Option<&str> {
     }
 }
 
+/// Validate a firewalld service name (`ssh`, `freeipa-4`, `kube-apiserver`, …):
+/// non-empty, at most 64 characters of ASCII letters, digits, `-`, `_`, `.`
+/// and `+`.
+pub fn validate_firewall_service(name: &str) -> Option<&str> {
+    if name.is_empty() || name.len() > 64 {
+        return None;
+    }
+    if name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '+'))
+    {
+        Some(name)
+    } else {
+        None
+    }
+}
+
+/// Validate a firewalld ICMP type name (`echo-request`, `redirect`,
+/// `neighbour-solicitation`, …): non-empty, at most 64 characters of ASCII
+/// letters, digits and `-`.
+pub fn validate_icmp_type(name: &str) -> Option<&str> {
+    if name.is_empty() || name.len() > 64 {
+        return None;
+    }
+    name.chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        .then_some(name)
+}
+
+/// Validate a network interface name: at most 15 characters (`IFNAMSIZ`
+/// minus the terminator) of ASCII letters, digits, `-`, `_` and `.`, and
+/// not `.` or `..`, which the kernel rejects as well.
+pub fn validate_interface_name(name: &str) -> Option<&str> {
+    if name.is_empty() || name.len() > 15 || name == "." || name == ".." {
+        return None;
+    }
+    name.chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .then_some(name)
+}
+
+/// Validate a journald `Storage=` value.
+pub fn validate_journal_storage(storage: &str) -> Option<&str> {
+    match storage {
+        "auto" | "persistent" | "volatile" => Some(storage),
+        _ => None,
+    }
+}
+
+/// Validate a journald size such as `SystemMaxUse=` (`512M`, `2G`, ...).
+///
+/// Only a positive integer with an optional K/M/G/T suffix is accepted, so the
+/// value can be written into a configuration file verbatim.
+pub fn validate_journal_size(size: &str) -> Option<&str> {
+    let digits = size.trim_end_matches(['K', 'M', 'G', 'T']);
+    let suffix_len = size.len() - digits.len();
+    if digits.is_empty() || digits.len() > 12 || suffix_len > 1 {
+        return None;
+    }
+    if !digits.chars().all(|c| c.is_ascii_digit()) || digits.chars().all(|c| c == '0') {
+        return None;
+    }
+    Some(size)
+}
+
+/// Validate a sysctl key in dotted form (`net.ipv4.ip_forward`): segments of
+/// lowercase ASCII letters, digits, `-` and `_`, so the key maps to a path
+/// below `/proc/sys` without escaping it.
+pub fn validate_sysctl_key(key: &str) -> Option<&str> {
+    if key.is_empty() || key.len() > 128 {
+        return None;
+    }
+    let valid = key.split('.').all(|segment| {
+        !segment.is_empty()
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    });
+    valid.then_some(key)
+}
+
+/// Validate a sysctl value: at most 64 characters of ASCII letters, digits,
+/// spaces and `-`, `_`, `:`, `/`, `,`. Newlines and `=` are rejected so the
+/// value can be written into a sysctl.d file verbatim.
+pub fn validate_sysctl_value(value: &str) -> Option<&str> {
+    if value.trim().is_empty() || value.len() > 64 {
+        return None;
+    }
+    value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | '_' | ':' | '/' | ','))
+        .then_some(value)
+}
+
 /// Validate a theme string.
 pub fn validate_theme(theme: &str) -> Option<&str> {
     match theme {
@@ -145,6 +263,38 @@ pub fn validate_theme(theme: &str) -> Option<&str> {
     }
 }
 
+/// Validate a status palette identifier.
+pub fn validate_status_palette(palette: &str) -> Option<&str> {
+    match palette {
+        "default" | "red-green" | "blue-yellow" => Some(palette),
+        _ => None,
+    }
+}
+
+/// Validate a layout density identifier.
+pub fn validate_density(density: &str) -> Option<&str> {
+    match density {
+        "comfortable" | "compact" => Some(density),
+        _ => None,
+    }
+}
+
+/// Validate a startup page identifier (a sidebar page name).
+pub fn validate_startup_page(page: &str) -> Option<&str> {
+    match page {
+        "overview" | "network-exposure" | "ports" => Some(page),
+        _ => None,
+    }
+}
+
+/// Validate a resumable task: a page whose scan runs when it is opened.
+pub fn validate_last_task(task: &str) -> Option<&str> {
+    match task {
+        "network-exposure" | "platform" | "cleanup" => Some(task),
+        _ => None,
+    }
+}
+
 /// Clamp a window dimension to reasonable bounds.
 pub fn clamp_window_dimension(value: i32) -> i32 {
     value.clamp(100, 10000)
@@ -202,12 +352,50 @@ mod tests {
         assert_eq!(parse_port_spec("80; rm -rf /"), None);
     }
 
+    #[test]
+    fn test_validate_firewall_service() {
+        assert_eq!(validate_firewall_service("ssh"), Some("ssh"));
+        assert_eq!(validate_firewall_service("freeipa-4"), Some("freeipa-4"));
+        assert_eq!(validate_firewall_service(""), None);
+        assert_eq!(validate_firewall_service("ssh\" accept"), None);
+        assert_eq!(validate_firewall_service("a b"), None);
+    }
+
+    #[test]
+    fn test_validate_icmp_type() {
+        assert_eq!(validate_icmp_type("echo-request"), Some("echo-request"));
+        assert_eq!(validate_icmp_type(""), None);
+        assert_eq!(validate_icmp_type("echo-request\" accept"), None);
+    }
+
     #[test]
     fn test_format_port_spec() {
         assert_eq!(format_port_spec(80, 80), "80");
         assert_eq!(format_port_spec(10, 20), "10-20");
     }
 
+    #[test]
+    fn test_parse_port_list() {
+        assert_eq!(
+            parse_port_list("8080/tcp, 6000-6100/udp 8080/tcp"),
+            Some(vec![
+                ("8080".to_string(), "tcp".to_string()),
+                ("6000-6100".to_string(), "udp".to_string()),
+            ])
+        );
+        assert_eq!(
+            parse_port_list(" 53/udp,53/tcp "),
+            Some(vec![
+                ("53".to_string(), "udp".to_string()),
+                ("53".to_string(), "tcp".to_string()),
+            ])
+        );
+        assert_eq!(parse_port_list(""), None);
+        assert_eq!(parse_port_list("8080"), None);
+        assert_eq!(parse_port_list("8080/icmp"), None);
+        assert_eq!(parse_port_list("80/tcp, 0/udp"), None);
+    }
+
     #[test]
     fn test_validate_port_name_valid() {
         assert_eq!(validate_port_name("HTTP"), Some("HTTP".to_string()));
@@ -238,6 +426,8 @@ mod tests {
         assert!(validate_systemctl_action("restart").is_ok());
         assert!(validate_systemctl_action("enable").is_ok());
         assert!(validate_systemctl_action("disable").is_ok());
+        assert!(validate_systemctl_action("reload-or-restart").is_ok());
+        assert!(validate_systemctl_action("mask").is_ok());
         assert!(validate_systemctl_action("daemon-reload").is_ok());
     }
 
@@ -287,6 +477,56 @@ mod tests {
         assert_eq!(validate_zone_name("thisnameiswaytoolongforzone"), None);
     }
 
+    #[test]
+    fn test_validate_journal_storage() {
+        assert_eq!(validate_journal_storage("persistent"), Some("persistent"));
+        assert_eq!(validate_journal_storage("auto"), Some("auto"));
+        assert_eq!(validate_journal_storage("none"), None);
+        assert_eq!(
+            validate_journal_storage("persistent\nForwardToWall=yes"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_validate_journal_size() {
+        assert_eq!(validate_journal_size("512M"), Some("512M"));
+        assert_eq!(validate_journal_size("2G"), Some("2G"));
+        assert_eq!(validate_journal_size("1048576"), Some("1048576"));
+        assert_eq!(validate_journal_size("0"), None);
+        assert_eq!(validate_journal_size("G"), None);
+        assert_eq!(validate_journal_size("1GG"), None);
+        assert_eq!(validate_journal_size("1G\n[Journal]"), None);
+        assert_eq!(validate_journal_size("-1G"), None);
+        assert_eq!(validate_journal_size(""), None);
+    }
+
+    #[test]
+    fn test_validate_sysctl() {
+        assert!(validate_sysctl_key("net.ipv4.ip_forward").is_some());
+        assert!(validate_sysctl_key("kernel.kptr_restrict").is_some());
+        assert!(validate_sysctl_key("net.ipv4.conf.all.rp_filter").is_some());
+        assert!(validate_sysctl_key("../../etc/passwd").is_none());
+        assert!(validate_sysctl_key("net/ipv4/ip_forward").is_none());
+        assert!(validate_sysctl_key("net..ip_forward").is_none());
+        assert!(validate_sysctl_key("").is_none());
+        assert_eq!(validate_sysctl_value("0"), Some("0"));
+        assert!(validate_sysctl_value("4096 87380 6291456").is_some());
+        assert!(validate_sysctl_value("1\nkernel.panic = 1").is_none());
+        assert!(validate_sysctl_value("a=b").is_none());
+        assert!(validate_sysctl_value(" ").is_none());
+    }
+
+    #[test]
+    fn test_validate_interface_name() {
+        assert_eq!(validate_interface_name("enp3s0"), Some("enp3s0"));
+        assert_eq!(validate_interface_name("wlan0.1"), Some("wlan0.1"));
+        assert!(validate_interface_name("").is_none());
+        assert!(validate_interface_name("..").is_none());
+        assert!(validate_interface_name("eth0 wol g").is_none());
+        assert!(validate_interface_name("a-very-long-interface").is_none());
+    }
+
     #[test]
     fn test_validate_theme() {
         assert_eq!(validate_theme("system"), Some("system"));